@@ -25,8 +25,9 @@ echo "📋 Checking for duplicate migration prefixes..."
 
 cd "${MIGRATIONS_DIR}" || { echo "❌ Error: ${MIGRATIONS_DIR} directory not found"; exit 1; }
 
-# Extract all prefixes (everything before first underscore)
-DUPLICATES=$(ls -1 *.sql 2>/dev/null | sed 's/_.*$//' | sort | uniq -d)
+# Extract all prefixes (everything before first underscore); a reversible
+# migration's .down.sql shares its prefix with the .up.sql
+DUPLICATES=$(ls -1 *.sql 2>/dev/null | grep -v '\.down\.sql$' | sed 's/_.*$//' | sort | uniq -d)
 
 if [ -n "${DUPLICATES}" ]; then
     echo "❌ FAIL: Duplicate migration prefixes found:"
@@ -84,7 +85,7 @@ echo ""
 echo "🔗 Checking for gaps in sequential numbering (warnings only)..."
 
 # Get only 3-digit prefixes, sorted numerically
-SEQUENTIAL_PREFIXES=$(ls -1 *.sql 2>/dev/null | sed 's/_.*$//' | grep -E '^[0-9]{3}$' | sort -n | uniq)
+SEQUENTIAL_PREFIXES=$(ls -1 *.sql 2>/dev/null | grep -v '\.down\.sql$' | sed 's/_.*$//' | grep -E '^[0-9]{3}$' | sort -n | uniq)
 
 if [ -n "${SEQUENTIAL_PREFIXES}" ]; then
     PREV_NUM=""
@@ -129,9 +130,10 @@ echo "📝 Validating filename conventions..."
 INVALID_NAME=0
 
 for file in *.sql; do
-    # Check format: NNN_description.sql or YYYYMMDDHHMMSS_description.sql
+    # Check format: NNN_description.sql or YYYYMMDDHHMMSS_description.sql,
+    # with .up.sql/.down.sql for reversible migrations
     # Description must be lowercase, alphanumeric + underscores
-    if ! [[ "$file" =~ ^[0-9]{3}_[a-z0-9_]+\.sql$ ]] && ! [[ "$file" =~ ^[0-9]{14}_[a-z0-9_]+\.sql$ ]]; then
+    if ! [[ "$file" =~ ^[0-9]{3}_[a-z0-9_]+(\.up|\.down)?\.sql$ ]] && ! [[ "$file" =~ ^[0-9]{14}_[a-z0-9_]+(\.up|\.down)?\.sql$ ]]; then
         if [ $INVALID_NAME -eq 0 ]; then
             echo "❌ FAIL: Invalid filename convention:"
             echo ""
@@ -151,6 +153,57 @@ fi
 
 echo ""
 
+# ─────────────────────────────────────────────────────────────────────────────
+# 5. Reversible migrations come in pairs
+# ─────────────────────────────────────────────────────────────────────────────
+
+echo "↩️  Checking .up.sql/.down.sql pairs..."
+
+UNPAIRED=0
+
+for file in *.up.sql *.down.sql; do
+    [ -e "$file" ] || continue
+    case "$file" in
+        *.up.sql) pair="${file%.up.sql}.down.sql" ;;
+        *.down.sql) pair="${file%.down.sql}.up.sql" ;;
+    esac
+    if [ ! -e "$pair" ]; then
+        if [ $UNPAIRED -eq 0 ]; then
+            echo "❌ FAIL: Reversible migrations missing their counterpart:"
+            echo ""
+        fi
+        echo "  - ${file} (expected ${pair})"
+        UNPAIRED=1
+    fi
+done
+
+# Migrations from this version on must all be reversible
+FIRST_REVERSIBLE=20261016090000
+
+for file in [0-9]*.sql; do
+    case "$file" in
+        *.up.sql|*.down.sql) continue ;;
+    esac
+    prefix="${file%%_*}"
+    if [ ${#prefix} -eq 14 ] && [ "$prefix" -ge "$FIRST_REVERSIBLE" ]; then
+        if [ $UNPAIRED -eq 0 ]; then
+            echo "❌ FAIL: Reversible migrations missing their counterpart:"
+            echo ""
+        fi
+        echo "  - ${file} (expected ${file%.sql}.up.sql and ${file%.sql}.down.sql)"
+        UNPAIRED=1
+    fi
+done
+
+if [ $UNPAIRED -eq 1 ]; then
+    echo ""
+    EXIT_CODE=1
+else
+    echo "✅ PASS: All reversible migrations are paired"
+fi
+
+echo ""
+
 # ─────────────────────────────────────────────────────────────────────────────
 # Summary
 # ─────────────────────────────────────────────────────────────────────────────
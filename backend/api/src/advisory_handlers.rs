@@ -0,0 +1,433 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use shared::SecurityNotice;
+use uuid::Uuid;
+
+use crate::{
+    advisory_matcher::{self, VersionRange, SELECT_ADVISORIES},
+    advisory_models::{Advisory, AffectedContract, CreateAdvisoryRequest, UpdateAdvisoryRequest},
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Length of `advisories.title`
+const MAX_TITLE_LEN: usize = 255;
+/// Ranges an advisory may list; more than this is almost certainly a mistake
+const MAX_RANGES: usize = 20;
+
+fn parse_advisory_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidAdvisoryId",
+            format!("Invalid advisory ID format: {}", id),
+        )
+    })
+}
+
+fn require_admin(claims: &AuthClaims) -> ApiResult<()> {
+    if auth::is_admin(claims) {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "Administrative privileges are required to manage security advisories",
+        ))
+    }
+}
+
+fn advisory_not_found(id: &str) -> ApiError {
+    ApiError::not_found(
+        "AdvisoryNotFound",
+        format!("No advisory found with ID: {}", id),
+    )
+}
+
+fn normalize_ranges(ranges: &[String]) -> ApiResult<Vec<String>> {
+    let ranges: Vec<String> = ranges
+        .iter()
+        .map(|range| range.trim().to_string())
+        .collect();
+    if ranges.is_empty() || ranges.len() > MAX_RANGES {
+        return Err(ApiError::unprocessable(
+            "InvalidAffectedVersions",
+            format!(
+                "An advisory needs 1 to {} affected version ranges",
+                MAX_RANGES
+            ),
+        ));
+    }
+    if let Some(invalid) = ranges
+        .iter()
+        .find(|range| VersionRange::parse(range).is_none())
+    {
+        return Err(ApiError::unprocessable(
+            "InvalidAffectedVersions",
+            format!(
+                "'{}' is not a version range; use e.g. '>=20.0.0, <20.5.2', '^21.1.0' or '=22.0.0'",
+                invalid
+            ),
+        ));
+    }
+    Ok(ranges)
+}
+
+fn normalize_title(title: &str) -> ApiResult<String> {
+    let title = title.trim();
+    if title.is_empty() || title.len() > MAX_TITLE_LEN {
+        return Err(ApiError::unprocessable(
+            "InvalidAdvisoryTitle",
+            format!("title must be 1 to {} characters", MAX_TITLE_LEN),
+        ));
+    }
+    Ok(title.to_string())
+}
+
+fn normalize_url(url: &str) -> ApiResult<String> {
+    let url = url.trim();
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        return Err(ApiError::unprocessable(
+            "InvalidAdvisoryUrl",
+            "url must be an http(s) URL",
+        ));
+    }
+    Ok(url.to_string())
+}
+
+async fn fetch_advisory(state: &AppState, advisory_id: Uuid) -> ApiResult<Option<Advisory>> {
+    sqlx::query_as(&format!("{} WHERE a.id = $1", SELECT_ADVISORIES))
+        .bind(advisory_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get advisory", err))
+}
+
+/// Re-matches a saved advisory and returns it with its new affected count
+async fn rematch(state: &AppState, advisory: Advisory) -> ApiResult<Advisory> {
+    advisory_matcher::rematch_advisory(&state.db, &advisory)
+        .await
+        .map_err(|err| db_internal_error("match advisory against contracts", err))?;
+    fetch_advisory(state, advisory.id)
+        .await?
+        .ok_or_else(|| advisory_not_found(&advisory.id.to_string()))
+}
+
+/// Notices currently raised for a contract, most severe first
+pub(crate) async fn contract_security_notices(
+    pool: &sqlx::PgPool,
+    contract_id: Uuid,
+) -> Result<Vec<SecurityNotice>, sqlx::Error> {
+    sqlx::query_as(
+        r#"
+        SELECT n.advisory_id, a.title, a.severity::TEXT AS severity,
+               a.component::TEXT AS component, n.matched_version,
+               cv.version AS contract_version, a.url, n.created_at
+        FROM security_notices n
+        JOIN advisories a ON a.id = n.advisory_id
+        JOIN contract_versions cv ON cv.id = n.contract_version_id
+        WHERE n.contract_id = $1
+        ORDER BY a.severity DESC, n.created_at DESC
+        "#,
+    )
+    .bind(contract_id)
+    .fetch_all(pool)
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/advisories",
+    responses(
+        (status = 200, description = "Unresolved advisories first, newest first", body = [Advisory])
+    ),
+    tag = "Security Advisories"
+)]
+pub async fn list_advisories(State(state): State<AppState>) -> ApiResult<Json<Vec<Advisory>>> {
+    let advisories: Vec<Advisory> = sqlx::query_as(&format!(
+        "{} ORDER BY a.resolved_at IS NOT NULL, a.created_at DESC",
+        SELECT_ADVISORIES
+    ))
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list advisories", err))?;
+
+    Ok(Json(advisories))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/advisories",
+    request_body = CreateAdvisoryRequest,
+    responses(
+        (status = 201, description = "Advisory created and matched against every contract", body = Advisory),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 422, description = "Invalid version ranges, title or URL")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Security Advisories"
+)]
+pub async fn create_advisory(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<CreateAdvisoryRequest>,
+) -> ApiResult<(StatusCode, Json<Advisory>)> {
+    require_admin(&claims)?;
+
+    let affected_versions = normalize_ranges(&req.affected_versions)?;
+    let title = normalize_title(&req.title)?;
+    let url = req.url.as_deref().map(normalize_url).transpose()?;
+
+    let advisory: Advisory = sqlx::query_as(
+        r#"
+        INSERT INTO advisories
+            (component, affected_versions, severity, title, description, url, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *, 0::BIGINT AS affected_contracts
+        "#,
+    )
+    .bind(req.component)
+    .bind(&affected_versions)
+    .bind(req.severity)
+    .bind(&title)
+    .bind(req.description.trim())
+    .bind(&url)
+    .bind(&claims.sub)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("create advisory", err))?;
+
+    let advisory = rematch(&state, advisory).await?;
+    tracing::info!(
+        advisory_id = %advisory.id,
+        component = advisory.component.as_str(),
+        severity = advisory.severity.as_str(),
+        affected_contracts = advisory.affected_contracts,
+        "advisory created"
+    );
+
+    Ok((StatusCode::CREATED, Json(advisory)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/advisories/{id}",
+    params(
+        ("id" = String, Path, description = "Advisory UUID")
+    ),
+    responses(
+        (status = 200, description = "Advisory", body = Advisory),
+        (status = 400, description = "Malformed ID"),
+        (status = 404, description = "Advisory not found")
+    ),
+    tag = "Security Advisories"
+)]
+pub async fn get_advisory(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Advisory>> {
+    let advisory_id = parse_advisory_id(&id)?;
+
+    fetch_advisory(&state, advisory_id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| advisory_not_found(&id))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/advisories/{id}",
+    params(
+        ("id" = String, Path, description = "Advisory UUID")
+    ),
+    request_body = UpdateAdvisoryRequest,
+    responses(
+        (status = 200, description = "Advisory updated and re-matched", body = Advisory),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Advisory not found"),
+        (status = 422, description = "Invalid version ranges, title or URL")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Security Advisories"
+)]
+pub async fn update_advisory(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<UpdateAdvisoryRequest>,
+) -> ApiResult<Json<Advisory>> {
+    require_admin(&claims)?;
+    let advisory_id = parse_advisory_id(&id)?;
+
+    let affected_versions = req
+        .affected_versions
+        .as_deref()
+        .map(normalize_ranges)
+        .transpose()?;
+    let title = req.title.as_deref().map(normalize_title).transpose()?;
+    let url = req.url.as_deref().map(normalize_url).transpose()?;
+
+    let advisory: Advisory = sqlx::query_as(
+        r#"
+        UPDATE advisories
+        SET affected_versions = COALESCE($1, affected_versions),
+            severity = COALESCE($2, severity),
+            title = COALESCE($3, title),
+            description = COALESCE($4, description),
+            url = COALESCE($5, url),
+            resolved_at = CASE $6
+                WHEN TRUE THEN COALESCE(resolved_at, NOW())
+                WHEN FALSE THEN NULL
+                ELSE resolved_at
+            END,
+            created_by = $7,
+            updated_at = NOW()
+        WHERE id = $8
+        RETURNING *, 0::BIGINT AS affected_contracts
+        "#,
+    )
+    .bind(&affected_versions)
+    .bind(req.severity)
+    .bind(&title)
+    .bind(req.description.as_deref().map(str::trim))
+    .bind(&url)
+    .bind(req.resolved)
+    .bind(&claims.sub)
+    .bind(advisory_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update advisory", err))?
+    .ok_or_else(|| advisory_not_found(&id))?;
+
+    let advisory = rematch(&state, advisory).await?;
+    tracing::info!(
+        advisory_id = %advisory.id,
+        resolved = advisory.resolved_at.is_some(),
+        affected_contracts = advisory.affected_contracts,
+        "advisory updated"
+    );
+
+    Ok(Json(advisory))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/advisories/{id}",
+    params(
+        ("id" = String, Path, description = "Advisory UUID")
+    ),
+    responses(
+        (status = 204, description = "Advisory and its notices deleted"),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Advisory not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Security Advisories"
+)]
+pub async fn delete_advisory(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    require_admin(&claims)?;
+    let advisory_id = parse_advisory_id(&id)?;
+
+    let result = sqlx::query("DELETE FROM advisories WHERE id = $1")
+        .bind(advisory_id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete advisory", err))?;
+
+    if result.rows_affected() == 0 {
+        return Err(advisory_not_found(&id));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/advisories/{id}/contracts",
+    params(
+        ("id" = String, Path, description = "Advisory UUID")
+    ),
+    responses(
+        (status = 200, description = "Contracts the advisory currently affects", body = [AffectedContract]),
+        (status = 400, description = "Malformed ID"),
+        (status = 404, description = "Advisory not found")
+    ),
+    tag = "Security Advisories"
+)]
+pub async fn list_affected_contracts(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<AffectedContract>>> {
+    let advisory_id = parse_advisory_id(&id)?;
+    if fetch_advisory(&state, advisory_id).await?.is_none() {
+        return Err(advisory_not_found(&id));
+    }
+
+    let contracts: Vec<AffectedContract> = sqlx::query_as(
+        r#"
+        SELECT c.id, c.contract_id, c.name, c.network::TEXT AS network,
+               cv.version AS contract_version, n.matched_version, n.created_at AS noticed_at
+        FROM security_notices n
+        JOIN contracts c ON c.id = n.contract_id
+        JOIN contract_versions cv ON cv.id = n.contract_version_id
+        WHERE n.advisory_id = $1
+        ORDER BY c.name, c.network
+        "#,
+    )
+    .bind(advisory_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list affected contracts", err))?;
+
+    Ok(Json(contracts))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/security-notices",
+    params(
+        ("id" = String, Path, description = "Contract UUID")
+    ),
+    responses(
+        (status = 200, description = "Advisories affecting the contract's newest build, most severe first", body = [SecurityNotice]),
+        (status = 400, description = "Malformed contract ID"),
+        (status = 404, description = "Contract not found")
+    ),
+    tag = "Security Advisories"
+)]
+pub async fn list_contract_security_notices(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<SecurityNotice>>> {
+    let contract_id = Uuid::parse_str(&id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidContractId",
+            format!("Invalid contract ID format: {}", id),
+        )
+    })?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM contracts WHERE id = $1)")
+        .bind(contract_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check contract exists", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found with ID: {}", id),
+        ));
+    }
+
+    contract_security_notices(&state.db, contract_id)
+        .await
+        .map(Json)
+        .map_err(|err| db_internal_error("list security notices", err))
+}
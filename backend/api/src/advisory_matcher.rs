@@ -0,0 +1,331 @@
+//! Matching of advisories against the toolchain contracts were built with.
+//!
+//! A contract is affected by an unresolved advisory when the newest of its
+//! versions with WASM metadata (see [`crate::wasm_metadata`]) was built with
+//! a soroban-sdk or rustc version in one of the advisory's ranges, and
+//! `security_notices` holds a row per affected contract. Notices are
+//! recomputed for an advisory whenever it is created or changed, and for a
+//! contract whenever WASM is uploaded for it, so resolving the advisory or
+//! uploading a WASM rebuilt with a fixed version clears them. Contracts newly
+//! affected by an advisory are announced to their `security` subscribers.
+
+use std::collections::HashSet;
+
+use shared::{SemVer, VersionConstraint};
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::advisory_models::{Advisory, AdvisoryComponent};
+use crate::subscription_notifications;
+
+/// Advisories with the number of contracts each affects
+pub(crate) const SELECT_ADVISORIES: &str = "SELECT a.*,
+        (SELECT COUNT(*) FROM security_notices n WHERE n.advisory_id = a.id) AS affected_contracts
+ FROM advisories a";
+
+/// The newest version of each contract that has WASM metadata
+const LATEST_BUILDS: &str = "SELECT DISTINCT ON (contract_id)
+        contract_id, id AS contract_version_id, version,
+        wasm_meta ->> 'sdk_version' AS sdk_version,
+        wasm_meta ->> 'rustc_version' AS rustc_version
+ FROM contract_versions
+ WHERE wasm_meta IS NOT NULL";
+
+#[derive(Debug, Clone)]
+enum Comparator {
+    Greater(SemVer),
+    GreaterEq(SemVer),
+    Less(SemVer),
+    LessEq(SemVer),
+    /// An exact version, `^` or `~` requirement
+    Constraint(VersionConstraint),
+}
+
+impl Comparator {
+    fn matches(&self, version: &SemVer) -> bool {
+        match self {
+            Comparator::Greater(bound) => version > bound,
+            Comparator::GreaterEq(bound) => version >= bound,
+            Comparator::Less(bound) => version < bound,
+            Comparator::LessEq(bound) => version <= bound,
+            Comparator::Constraint(constraint) => constraint.matches(version),
+        }
+    }
+}
+
+/// A version requirement such as `>=20.0.0, <20.5.2`: comma-separated
+/// comparators that must all hold
+#[derive(Debug, Clone)]
+pub struct VersionRange(Vec<Comparator>);
+
+impl VersionRange {
+    pub fn parse(range: &str) -> Option<Self> {
+        range
+            .split(',')
+            .map(|part| {
+                let part = part.trim();
+                let bound = |rest: &str| SemVer::parse(rest.trim());
+                if let Some(rest) = part.strip_prefix(">=") {
+                    bound(rest).map(Comparator::GreaterEq)
+                } else if let Some(rest) = part.strip_prefix("<=") {
+                    bound(rest).map(Comparator::LessEq)
+                } else if let Some(rest) = part.strip_prefix('>') {
+                    bound(rest).map(Comparator::Greater)
+                } else if let Some(rest) = part.strip_prefix('<') {
+                    bound(rest).map(Comparator::Less)
+                } else {
+                    VersionConstraint::parse(part.strip_prefix('=').unwrap_or(part))
+                        .map(Comparator::Constraint)
+                }
+            })
+            .collect::<Option<Vec<_>>>()
+            .map(VersionRange)
+    }
+
+    pub fn matches(&self, version: &SemVer) -> bool {
+        self.0.iter().all(|comparator| comparator.matches(version))
+    }
+}
+
+/// The semantic version in a recorded toolchain version: `22.0.7#2115…` is
+/// 22.0.7 and `rustc 1.81.0 (eeb90cda1 2024-09-04)` is 1.81.0
+pub fn toolchain_version(recorded: &str) -> Option<SemVer> {
+    let recorded = recorded.trim();
+    let recorded = recorded.strip_prefix("rustc ").unwrap_or(recorded);
+    SemVer::parse(recorded.split(['#', ' ']).next()?)
+}
+
+/// Whether `recorded` falls in any of `ranges`. Ranges that don't parse
+/// match nothing; they are rejected when an advisory is saved.
+pub fn affects(ranges: &[String], recorded: &str) -> bool {
+    let Some(version) = toolchain_version(recorded) else {
+        return false;
+    };
+    ranges
+        .iter()
+        .filter_map(|range| VersionRange::parse(range))
+        .any(|range| range.matches(&version))
+}
+
+/// The newest version of a contract that has WASM metadata
+#[derive(Debug, FromRow)]
+struct Build {
+    contract_id: Uuid,
+    contract_version_id: Uuid,
+    version: String,
+    sdk_version: Option<String>,
+    rustc_version: Option<String>,
+}
+
+impl Build {
+    /// The version of `advisory`'s component this build used, if affected
+    fn affected_by(&self, advisory: &Advisory) -> Option<&str> {
+        let recorded = match advisory.component {
+            AdvisoryComponent::SorobanSdk => self.sdk_version.as_deref(),
+            AdvisoryComponent::Rustc => self.rustc_version.as_deref(),
+        }?;
+        affects(&advisory.affected_versions, recorded).then_some(recorded)
+    }
+}
+
+/// Recomputes the notices of `advisory`, returning how many contracts it
+/// affects.
+pub async fn rematch_advisory(pool: &PgPool, advisory: &Advisory) -> Result<usize, sqlx::Error> {
+    let builds: Vec<Build> = if advisory.resolved_at.is_some() {
+        Vec::new()
+    } else {
+        sqlx::query_as(&format!(
+            "{} ORDER BY contract_id, created_at DESC",
+            LATEST_BUILDS
+        ))
+        .fetch_all(pool)
+        .await?
+    };
+    let affected: Vec<(&Build, &str)> = builds
+        .iter()
+        .filter_map(|build| Some((build, build.affected_by(advisory)?)))
+        .collect();
+
+    let mut tx = pool.begin().await?;
+    let contract_ids: Vec<Uuid> = affected
+        .iter()
+        .map(|(build, _)| build.contract_id)
+        .collect();
+    let cleared: HashSet<Uuid> = sqlx::query_scalar(
+        "DELETE FROM security_notices
+         WHERE advisory_id = $1 AND contract_id <> ALL($2)
+         RETURNING contract_id",
+    )
+    .bind(advisory.id)
+    .bind(&contract_ids)
+    .fetch_all(&mut *tx)
+    .await?
+    .into_iter()
+    .collect();
+    let existing: HashSet<Uuid> =
+        sqlx::query_scalar("SELECT contract_id FROM security_notices WHERE advisory_id = $1")
+            .bind(advisory.id)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .collect();
+
+    for (build, matched_version) in &affected {
+        record_notice(&mut tx, advisory, build, matched_version).await?;
+        if !existing.contains(&build.contract_id) {
+            announce(&mut tx, advisory, build, matched_version).await?;
+        }
+    }
+    tx.commit().await?;
+
+    tracing::info!(
+        advisory_id = %advisory.id,
+        affected = affected.len(),
+        cleared = cleared.len(),
+        "advisory matched against contracts"
+    );
+    Ok(affected.len())
+}
+
+/// Recomputes the notices of one contract, after WASM was uploaded for it.
+/// Returns how many advisories affect it.
+pub async fn rematch_contract(pool: &PgPool, contract_id: Uuid) -> Result<usize, sqlx::Error> {
+    let build: Option<Build> = sqlx::query_as(&format!(
+        "{} AND contract_id = $1 ORDER BY contract_id, created_at DESC",
+        LATEST_BUILDS
+    ))
+    .bind(contract_id)
+    .fetch_optional(pool)
+    .await?;
+    let advisories: Vec<Advisory> = match build {
+        Some(_) => {
+            sqlx::query_as(&format!(
+                "{} WHERE a.resolved_at IS NULL",
+                SELECT_ADVISORIES
+            ))
+            .fetch_all(pool)
+            .await?
+        }
+        None => Vec::new(),
+    };
+    let affecting: Vec<(&Advisory, &Build, &str)> = match &build {
+        Some(build) => advisories
+            .iter()
+            .filter_map(|advisory| Some((advisory, build, build.affected_by(advisory)?)))
+            .collect(),
+        None => Vec::new(),
+    };
+
+    let mut tx = pool.begin().await?;
+    let advisory_ids: Vec<Uuid> = affecting.iter().map(|(advisory, ..)| advisory.id).collect();
+    sqlx::query("DELETE FROM security_notices WHERE contract_id = $1 AND advisory_id <> ALL($2)")
+        .bind(contract_id)
+        .bind(&advisory_ids)
+        .execute(&mut *tx)
+        .await?;
+    let existing: HashSet<Uuid> =
+        sqlx::query_scalar("SELECT advisory_id FROM security_notices WHERE contract_id = $1")
+            .bind(contract_id)
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .collect();
+
+    for (advisory, build, matched_version) in &affecting {
+        record_notice(&mut tx, advisory, build, matched_version).await?;
+        if !existing.contains(&advisory.id) {
+            announce(&mut tx, advisory, build, matched_version).await?;
+        }
+    }
+    tx.commit().await?;
+    Ok(affecting.len())
+}
+
+async fn record_notice(
+    tx: &mut Transaction<'_, Postgres>,
+    advisory: &Advisory,
+    build: &Build,
+    matched_version: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO security_notices (advisory_id, contract_id, contract_version_id, matched_version)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (advisory_id, contract_id) DO UPDATE
+        SET contract_version_id = EXCLUDED.contract_version_id,
+            matched_version = EXCLUDED.matched_version
+        "#,
+    )
+    .bind(advisory.id)
+    .bind(build.contract_id)
+    .bind(build.contract_version_id)
+    .bind(matched_version)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Queues a new notice for the contract's `security` subscribers
+async fn announce(
+    tx: &mut Transaction<'_, Postgres>,
+    advisory: &Advisory,
+    build: &Build,
+    matched_version: &str,
+) -> Result<(), sqlx::Error> {
+    let contract = subscription_notifications::contract_ref(&mut **tx, build.contract_id).await?;
+    let notice = subscription_notifications::security_notice(
+        &contract,
+        advisory,
+        &build.version,
+        matched_version,
+    );
+    subscription_notifications::enqueue(&mut **tx, build.contract_id, &notice).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(ranges: &[&str]) -> Vec<String> {
+        ranges.iter().map(|range| range.to_string()).collect()
+    }
+
+    #[test]
+    fn ranges_combine_comparators() {
+        let affected = ranges(&[">=20.0.0, <20.5.2", "=21.0.0"]);
+        assert!(affects(&affected, "20.0.0"));
+        assert!(affects(&affected, "20.5.1#4a6a9f1f2e1d"));
+        assert!(!affects(&affected, "20.5.2"));
+        assert!(affects(&affected, "21.0.0"));
+        assert!(!affects(&affected, "21.0.1"));
+        assert!(!affects(&affected, "19.9.9"));
+
+        assert!(affects(&ranges(&["^21.1.0"]), "21.7.7"));
+        assert!(!affects(&ranges(&["^21.1.0"]), "22.0.0"));
+        assert!(affects(
+            &ranges(&["~1.81.0"]),
+            "rustc 1.81.1 (eeb90cda1 2024-09-04)"
+        ));
+        assert!(affects(&ranges(&["<=1.79.0"]), "1.79.0"));
+        assert!(affects(&ranges(&[">1.79.0"]), "1.80.0"));
+    }
+
+    #[test]
+    fn unparseable_versions_and_ranges_match_nothing() {
+        assert!(VersionRange::parse(">=20.0").is_none());
+        assert!(VersionRange::parse("").is_none());
+        assert!(VersionRange::parse(">=20.0.0,").is_none());
+        assert!(!affects(&ranges(&["latest"]), "20.0.0"));
+        assert!(!affects(&ranges(&[">=20.0.0"]), "unknown"));
+    }
+
+    #[test]
+    fn toolchain_versions_drop_commit_and_date() {
+        let version = toolchain_version("22.0.7#211569aa49c8d896877dfca1f2eb4fe9071121c8").unwrap();
+        assert_eq!(version.to_string(), "22.0.7");
+        let version = toolchain_version("rustc 1.81.0 (eeb90cda1 2024-09-04)").unwrap();
+        assert_eq!(version.to_string(), "1.81.0");
+        assert!(toolchain_version("nightly").is_none());
+    }
+}
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Toolchain component an advisory is about, matching the
+/// `advisory_component` enum in Postgres
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "advisory_component", rename_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum AdvisoryComponent {
+    SorobanSdk,
+    Rustc,
+}
+
+impl AdvisoryComponent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdvisoryComponent::SorobanSdk => "soroban-sdk",
+            AdvisoryComponent::Rustc => "rustc",
+        }
+    }
+}
+
+/// Matching the `advisory_severity` enum in Postgres
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    utoipa::ToSchema,
+)]
+#[sqlx(type_name = "advisory_severity", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisorySeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl AdvisorySeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdvisorySeverity::Low => "low",
+            AdvisorySeverity::Medium => "medium",
+            AdvisorySeverity::High => "high",
+            AdvisorySeverity::Critical => "critical",
+        }
+    }
+}
+
+/// A vulnerability in a range of soroban-sdk or rustc versions
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct Advisory {
+    pub id: Uuid,
+    pub component: AdvisoryComponent,
+    /// Version requirements such as `>=20.0.0, <20.5.2`; a version matching
+    /// any of them is affected
+    pub affected_versions: Vec<String>,
+    pub severity: AdvisorySeverity,
+    pub title: String,
+    pub description: String,
+    /// Link to the full advisory
+    pub url: Option<String>,
+    /// Resolved advisories no longer raise notices
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// JWT subject of the admin who last changed the advisory
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Contracts with a notice for this advisory
+    pub affected_contracts: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateAdvisoryRequest {
+    pub component: AdvisoryComponent,
+    pub affected_versions: Vec<String>,
+    pub severity: AdvisorySeverity,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    pub url: Option<String>,
+}
+
+/// Partial update; omitted fields keep their current values
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpdateAdvisoryRequest {
+    pub affected_versions: Option<Vec<String>>,
+    pub severity: Option<AdvisorySeverity>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    /// `true` resolves the advisory and clears its notices, `false` reopens it
+    pub resolved: Option<bool>,
+}
+
+/// A contract an advisory affects
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct AffectedContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub network: String,
+    /// Contract version whose WASM matched
+    pub contract_version: String,
+    pub matched_version: String,
+    pub noticed_at: DateTime<Utc>,
+}
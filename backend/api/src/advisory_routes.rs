@@ -0,0 +1,25 @@
+use axum::{routing::get, Router};
+
+use crate::{advisory_handlers, state::AppState};
+
+pub fn advisory_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/advisories",
+            get(advisory_handlers::list_advisories).post(advisory_handlers::create_advisory),
+        )
+        .route(
+            "/api/advisories/:id",
+            get(advisory_handlers::get_advisory)
+                .patch(advisory_handlers::update_advisory)
+                .delete(advisory_handlers::delete_advisory),
+        )
+        .route(
+            "/api/advisories/:id/contracts",
+            get(advisory_handlers::list_affected_contracts),
+        )
+        .route(
+            "/api/contracts/:id/security-notices",
+            get(advisory_handlers::list_contract_security_notices),
+        )
+}
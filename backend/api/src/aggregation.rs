@@ -2,21 +2,34 @@ use chrono::Timelike;
 use sqlx::PgPool;
 use std::time::Duration;
 
+use crate::incident_watchdog::{self, Component};
+use crate::shutdown::Shutdown;
+
 /// Spawn the background aggregation task.
 ///
 /// Runs every hour:
 ///   1. Aggregate raw events into daily summaries (yesterday + today).
 ///   2. Delete raw events older than 90 days.
-pub fn spawn_aggregation_task(pool: PgPool) {
-    tokio::spawn(async move {
+///
+/// A run in progress completes before the task stops on shutdown.
+pub fn spawn_aggregation_task(pool: PgPool, shutdown: &Shutdown) {
+    let token = shutdown.token();
+    shutdown.spawn(async move {
         let mut interval = tokio::time::interval(Duration::from_secs(3600));
 
         loop {
-            interval.tick().await;
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
             tracing::info!("aggregation: starting hourly run");
 
-            if let Err(err) = run_aggregation(&pool).await {
-                tracing::error!(error = ?err, "aggregation: run failed");
+            match run_aggregation(&pool).await {
+                Ok(()) => incident_watchdog::report_success(Component::Aggregation),
+                Err(err) => {
+                    tracing::error!(error = ?err, "aggregation: run failed");
+                    incident_watchdog::report_failure(Component::Aggregation, &err.to_string());
+                }
             }
 
             if let Err(err) = cleanup_old_events(&pool).await {
@@ -34,6 +47,7 @@ pub fn spawn_aggregation_task(pool: PgPool) {
                 }
             }
         }
+        tracing::info!("aggregation: stopped");
     });
 }
 
@@ -8,7 +8,7 @@ use uuid::Uuid;
 /// is never blocked waiting for a write.  Any database error is logged at
 /// the WARN level but never propagated to the caller.
 pub fn increment_view_count_async(pool: PgPool, contract_id: Uuid) {
-    tokio::spawn(async move {
+    crate::request_tracing::spawn(async move {
         let result = sqlx::query("UPDATE contracts SET view_count = view_count + 1 WHERE id = $1")
             .bind(contract_id)
             .execute(&pool)
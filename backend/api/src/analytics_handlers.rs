@@ -1,6 +1,6 @@
 //! Contract interaction analytics handlers (issue #415)
 //!
-//! Provides two endpoints:
+//! Provides three endpoints:
 //!
 //!   GET /api/contracts/:id/analytics  – per-contract analytics with real
 //!                                       deployment counts, view count, and
@@ -9,13 +9,17 @@
 //!   GET /api/analytics/summary        – registry-wide aggregates broken down
 //!                                       by category and by network, enabling
 //!                                       trending-by-category identification.
+//!
+//!   GET /api/analytics/timeseries     – publishes, verifications and downloads
+//!                                       per hour/day/week/month, for growth
+//!                                       charts.
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     Json,
 };
-use chrono::NaiveDate;
-use serde::Serialize;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
 use shared::{DeploymentStats, InteractorStats, TopUser};
 use uuid::Uuid;
 
@@ -82,8 +86,7 @@ pub struct AnalyticsSummaryResponse {
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
 fn db_err(op: &str, err: sqlx::Error) -> ApiError {
-    tracing::error!(operation = op, error = ?err, "database operation failed");
-    ApiError::internal("An unexpected database error occurred")
+    ApiError::from_sqlx(op, err)
 }
 
 fn parse_contract_id(id: &str) -> ApiResult<Uuid> {
@@ -125,7 +128,7 @@ pub async fn get_contract_analytics(
     let view_count: Option<i64> =
         sqlx::query_scalar("SELECT view_count FROM contracts WHERE id = $1")
             .bind(contract_uuid)
-            .fetch_optional(&state.db)
+            .fetch_optional(state.read_pool())
             .await
             .map_err(|err| db_err("fetch view_count", err))?;
 
@@ -147,7 +150,7 @@ pub async fn get_contract_analytics(
          AND    interaction_type = 'deploy'",
     )
     .bind(contract_uuid)
-    .fetch_one(&state.db)
+    .fetch_one(state.read_pool())
     .await
     .map_err(|err| db_err("fetch deploy total", err))?;
 
@@ -161,7 +164,7 @@ pub async fn get_contract_analytics(
          AND    user_address IS NOT NULL",
     )
     .bind(contract_uuid)
-    .fetch_one(&state.db)
+    .fetch_one(state.read_pool())
     .await
     .map_err(|err| db_err("fetch unique deployers", err))?;
 
@@ -174,7 +177,7 @@ pub async fn get_contract_analytics(
          GROUP  BY network",
     )
     .bind(contract_uuid)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_err("fetch deploy by network", err))?;
 
@@ -195,7 +198,7 @@ pub async fn get_contract_analytics(
          AND    user_address IS NOT NULL",
     )
     .bind(contract_uuid)
-    .fetch_one(&state.db)
+    .fetch_one(state.read_pool())
     .await
     .map_err(|err| db_err("fetch unique interactors", err))?;
 
@@ -209,7 +212,7 @@ pub async fn get_contract_analytics(
          LIMIT  10",
     )
     .bind(contract_uuid)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_err("fetch top interactors", err))?;
 
@@ -240,7 +243,7 @@ pub async fn get_contract_analytics(
     )
     .bind(thirty_days_ago)
     .bind(contract_uuid)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_err("fetch 30-day timeline", err))?;
 
@@ -273,7 +276,7 @@ pub async fn get_contract_analytics(
     )
     .bind(seven_days_ago)
     .bind(contract_uuid)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_err("fetch 7-day trend", err))?;
 
@@ -404,6 +407,370 @@ pub async fn get_analytics_summary(
     }))
 }
 
+// ── Registry growth time series ──────────────────────────────────────────────
+
+/// How long a computed series is served from cache.
+const TIMESERIES_CACHE_SECS: u64 = 300;
+/// Most buckets one series may have, whatever the interval.
+const MAX_TIMESERIES_BUCKETS: i64 = 366;
+/// Longest window that can be requested.
+const MAX_TIMESERIES_WINDOW_DAYS: i64 = 730;
+
+/// A registry activity counted over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeseriesMetric {
+    /// Contracts published
+    Publishes,
+    /// Successful verifications
+    Verifications,
+    /// Source downloads
+    Downloads,
+}
+
+impl TimeseriesMetric {
+    const ALL: [TimeseriesMetric; 3] = [
+        TimeseriesMetric::Publishes,
+        TimeseriesMetric::Verifications,
+        TimeseriesMetric::Downloads,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            TimeseriesMetric::Publishes => "publishes",
+            TimeseriesMetric::Verifications => "verifications",
+            TimeseriesMetric::Downloads => "downloads",
+        }
+    }
+
+    /// Query yielding one `at` timestamp per counted event.
+    fn events_sql(self) -> &'static str {
+        match self {
+            TimeseriesMetric::Publishes => "SELECT created_at AS at FROM contracts",
+            TimeseriesMetric::Verifications => {
+                "SELECT COALESCE(verified_at, created_at) AS at FROM verifications \
+                 WHERE status = 'verified'"
+            }
+            TimeseriesMetric::Downloads => {
+                "SELECT created_at AS at FROM source_access_logs WHERE action = 'download'"
+            }
+        }
+    }
+}
+
+/// Width of one bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeseriesInterval {
+    Hour,
+    Day,
+    /// ISO weeks, starting on Monday
+    Week,
+    Month,
+}
+
+impl TimeseriesInterval {
+    fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "hour" => Some(TimeseriesInterval::Hour),
+            "day" => Some(TimeseriesInterval::Day),
+            "week" => Some(TimeseriesInterval::Week),
+            "month" => Some(TimeseriesInterval::Month),
+            _ => None,
+        }
+    }
+
+    /// Field name for Postgres `date_trunc`.
+    fn unit(self) -> &'static str {
+        match self {
+            TimeseriesInterval::Hour => "hour",
+            TimeseriesInterval::Day => "day",
+            TimeseriesInterval::Week => "week",
+            TimeseriesInterval::Month => "month",
+        }
+    }
+
+    /// Length of one bucket, taking a month as 30 days.
+    fn approx_duration(self) -> Duration {
+        match self {
+            TimeseriesInterval::Hour => Duration::hours(1),
+            TimeseriesInterval::Day => Duration::days(1),
+            TimeseriesInterval::Week => Duration::weeks(1),
+            TimeseriesInterval::Month => Duration::days(30),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TimeseriesQuery {
+    /// Metric to count: publishes, verifications or downloads (default publishes)
+    pub metric: Option<String>,
+    /// Comma-separated metrics, for several series in one response
+    pub metrics: Option<String>,
+    /// Bucket width: hour, day, week or month (default day)
+    pub interval: Option<String>,
+    /// How far back to go, e.g. `48h`, `90d` or `12w` (default 90d)
+    pub window: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TimeseriesPoint {
+    /// Start of the bucket (UTC)
+    pub bucket: DateTime<Utc>,
+    pub value: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MetricSeries {
+    pub metric: TimeseriesMetric,
+    /// Sum of all buckets
+    pub total: i64,
+    /// One point per bucket, oldest first; empty buckets are zero
+    pub points: Vec<TimeseriesPoint>,
+}
+
+/// Response for GET /api/analytics/timeseries.
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TimeseriesResponse {
+    pub interval: TimeseriesInterval,
+    pub window: String,
+    /// Start of the first bucket
+    pub from: DateTime<Utc>,
+    /// When the series was computed; results are cached for up to five minutes
+    pub to: DateTime<Utc>,
+    pub series: Vec<MetricSeries>,
+}
+
+/// Parses `metric` and `metrics` together, keeping the first occurrence of each.
+fn parse_metrics(metric: Option<&str>, metrics: Option<&str>) -> ApiResult<Vec<TimeseriesMetric>> {
+    let mut parsed = Vec::new();
+    for name in metric
+        .into_iter()
+        .chain(metrics)
+        .flat_map(|list| list.split(','))
+        .map(|name| name.trim().to_ascii_lowercase())
+        .filter(|name| !name.is_empty())
+    {
+        let metric = TimeseriesMetric::ALL
+            .into_iter()
+            .find(|m| m.name() == name)
+            .ok_or_else(|| {
+                ApiError::bad_request(
+                    "InvalidMetric",
+                    format!(
+                        "Unknown metric '{}'; expected one of publishes, verifications, downloads",
+                        name
+                    ),
+                )
+            })?;
+        if !parsed.contains(&metric) {
+            parsed.push(metric);
+        }
+    }
+    if parsed.is_empty() {
+        parsed.push(TimeseriesMetric::Publishes);
+    }
+    Ok(parsed)
+}
+
+/// Parses a window such as `48h`, `90d` or `12w`.
+fn parse_window(window: &str) -> ApiResult<Duration> {
+    let invalid = || {
+        ApiError::bad_request(
+            "InvalidWindow",
+            format!(
+                "Invalid window '{}'; use a number of hours, days or weeks such as 48h, 90d or 12w",
+                window
+            ),
+        )
+    };
+    let window = window.trim();
+    let unit = window.chars().last().ok_or_else(invalid)?;
+    let count: i64 = window[..window.len() - unit.len_utf8()]
+        .parse()
+        .map_err(|_| invalid())?;
+    if count <= 0 || count > MAX_TIMESERIES_WINDOW_DAYS * 24 {
+        return Err(invalid());
+    }
+    match unit.to_ascii_lowercase() {
+        'h' => Ok(Duration::hours(count)),
+        'd' => Ok(Duration::days(count)),
+        'w' => Ok(Duration::weeks(count)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Keeps every request to a bounded number of buckets over a bounded window.
+fn validate_window(interval: TimeseriesInterval, window: Duration) -> ApiResult<()> {
+    if window > Duration::days(MAX_TIMESERIES_WINDOW_DAYS) {
+        return Err(ApiError::bad_request(
+            "InvalidWindow",
+            format!(
+                "The window may be at most {} days",
+                MAX_TIMESERIES_WINDOW_DAYS
+            ),
+        ));
+    }
+    let bucket = interval.approx_duration();
+    if window < bucket {
+        return Err(ApiError::bad_request(
+            "InvalidWindow",
+            format!("The window must span at least one {}", interval.unit()),
+        ));
+    }
+    let buckets = window.num_seconds() / bucket.num_seconds();
+    if buckets > MAX_TIMESERIES_BUCKETS {
+        return Err(ApiError::bad_request(
+            "TooManyBuckets",
+            format!(
+                "A {}-long window has {} {} buckets, more than the limit of {}; use a shorter window or a wider interval",
+                format_window(window),
+                buckets,
+                interval.unit(),
+                MAX_TIMESERIES_BUCKETS
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// The shortest `h`/`d`/`w` spelling of a window, so equivalent windows
+/// share a cache entry.
+fn format_window(window: Duration) -> String {
+    let hours = window.num_hours();
+    if hours % (24 * 7) == 0 {
+        format!("{}w", hours / (24 * 7))
+    } else if hours % 24 == 0 {
+        format!("{}d", hours / 24)
+    } else {
+        format!("{}h", hours)
+    }
+}
+
+/// Counts of registry activity per time bucket, for growth charts.
+///
+/// Buckets are aligned to the interval in UTC, so the first one starts
+/// before `now - window`. Several metrics can be requested together with
+/// `metrics=publishes,verifications`. Results are cached for five minutes.
+#[utoipa::path(
+    get,
+    path = "/api/analytics/timeseries",
+    params(TimeseriesQuery),
+    responses(
+        (status = 200, description = "One series per requested metric", body = TimeseriesResponse),
+        (status = 400, description = "Unknown metric or interval, or a window that is invalid or has too many buckets")
+    ),
+    tag = "Analytics"
+)]
+pub async fn get_analytics_timeseries(
+    State(state): State<AppState>,
+    Query(query): Query<TimeseriesQuery>,
+) -> ApiResult<Json<TimeseriesResponse>> {
+    let metrics = parse_metrics(query.metric.as_deref(), query.metrics.as_deref())?;
+    let interval = match query.interval.as_deref() {
+        Some(value) => TimeseriesInterval::parse(value).ok_or_else(|| {
+            ApiError::bad_request(
+                "InvalidInterval",
+                format!(
+                    "Unknown interval '{}'; expected hour, day, week or month",
+                    value
+                ),
+            )
+        })?,
+        None => TimeseriesInterval::Day,
+    };
+    let window = parse_window(query.window.as_deref().unwrap_or("90d"))?;
+    validate_window(interval, window)?;
+    let window_label = format_window(window);
+
+    let now = Utc::now();
+    // The slot in the key expires entries after at most TIMESERIES_CACHE_SECS
+    let cache_key = format!(
+        "timeseries:{}:{}:{}:{}",
+        metrics
+            .iter()
+            .map(|m| m.name())
+            .collect::<Vec<_>>()
+            .join(","),
+        interval.unit(),
+        window_label,
+        now.timestamp() as u64 / TIMESERIES_CACHE_SECS
+    );
+    if let (Some(cached), true) = state.cache.get("analytics", &cache_key).await {
+        if let Ok(response) = serde_json::from_str::<TimeseriesResponse>(&cached) {
+            return Ok(Json(response));
+        }
+    }
+
+    let start = now - window;
+    let mut series = Vec::with_capacity(metrics.len());
+    for metric in &metrics {
+        let points: Vec<(DateTime<Utc>, i64)> = sqlx::query_as(&format!(
+            r#"
+            WITH counts AS (
+                SELECT date_trunc($1, e.at AT TIME ZONE 'UTC') AS bucket,
+                       COUNT(*)::BIGINT AS value
+                FROM ({events}) e
+                WHERE e.at >= date_trunc($1, $2 AT TIME ZONE 'UTC') AT TIME ZONE 'UTC'
+                  AND e.at <= $3
+                GROUP BY 1
+            )
+            SELECT b.bucket AT TIME ZONE 'UTC', COALESCE(c.value, 0)::BIGINT
+            FROM generate_series(
+                     date_trunc($1, $2 AT TIME ZONE 'UTC'),
+                     date_trunc($1, $3 AT TIME ZONE 'UTC'),
+                     ('1 ' || $1)::INTERVAL
+                 ) AS b(bucket)
+            LEFT JOIN counts c ON c.bucket = b.bucket
+            ORDER BY b.bucket
+            "#,
+            events = metric.events_sql()
+        ))
+        .bind(interval.unit())
+        .bind(start)
+        .bind(now)
+        .fetch_all(state.read_pool())
+        .await
+        .map_err(|err| db_err("fetch analytics timeseries", err))?;
+
+        series.push(MetricSeries {
+            metric: *metric,
+            total: points.iter().map(|(_, value)| value).sum(),
+            points: points
+                .into_iter()
+                .map(|(bucket, value)| TimeseriesPoint { bucket, value })
+                .collect(),
+        });
+    }
+
+    let from = series
+        .first()
+        .and_then(|s| s.points.first())
+        .map(|p| p.bucket)
+        .unwrap_or(start);
+    let response = TimeseriesResponse {
+        interval,
+        window: window_label,
+        from,
+        to: now,
+        series,
+    };
+
+    if let Ok(serialized) = serde_json::to_string(&response) {
+        state
+            .cache
+            .put(
+                "analytics",
+                &cache_key,
+                serialized,
+                Some(std::time::Duration::from_secs(TIMESERIES_CACHE_SECS)),
+            )
+            .await;
+    }
+
+    Ok(Json(response))
+}
+
 // ── Unit tests ────────────────────────────────────────────────────────────────
 
 #[cfg(test)]
@@ -448,4 +815,56 @@ mod tests {
         let avg = total_interactions as f64 / contract_count as f64;
         assert_eq!(avg, 25.0);
     }
+
+    #[test]
+    fn timeseries_metrics_default_and_deduplicate() {
+        assert_eq!(
+            parse_metrics(None, None).unwrap(),
+            vec![TimeseriesMetric::Publishes]
+        );
+        assert_eq!(
+            parse_metrics(
+                Some("downloads"),
+                Some("Publishes, downloads,verifications")
+            )
+            .unwrap(),
+            vec![
+                TimeseriesMetric::Downloads,
+                TimeseriesMetric::Publishes,
+                TimeseriesMetric::Verifications
+            ]
+        );
+        assert!(parse_metrics(None, Some("publishes,stars")).is_err());
+    }
+
+    #[test]
+    fn timeseries_windows_are_parsed_and_bounded() {
+        assert_eq!(parse_window("90d").unwrap(), Duration::days(90));
+        assert_eq!(parse_window("48h").unwrap(), Duration::hours(48));
+        assert_eq!(parse_window("12W").unwrap(), Duration::weeks(12));
+        for invalid in ["", "d", "90", "-3d", "0d", "3m", "1.5d", "999999999w"] {
+            assert!(
+                parse_window(invalid).is_err(),
+                "{} should be rejected",
+                invalid
+            );
+        }
+
+        let day = TimeseriesInterval::Day;
+        assert!(validate_window(day, Duration::days(90)).is_ok());
+        assert!(validate_window(day, Duration::days(366)).is_ok());
+        assert!(validate_window(day, Duration::days(367)).is_err());
+        assert!(validate_window(day, Duration::hours(12)).is_err());
+        assert!(validate_window(TimeseriesInterval::Hour, Duration::days(7)).is_ok());
+        assert!(validate_window(TimeseriesInterval::Hour, Duration::days(30)).is_err());
+        assert!(validate_window(TimeseriesInterval::Month, Duration::days(730)).is_ok());
+        assert!(validate_window(TimeseriesInterval::Month, Duration::days(731)).is_err());
+    }
+
+    #[test]
+    fn timeseries_windows_format_to_their_shortest_unit() {
+        assert_eq!(format_window(Duration::days(14)), "2w");
+        assert_eq!(format_window(Duration::days(90)), "90d");
+        assert_eq!(format_window(Duration::hours(36)), "36h");
+    }
 }
@@ -1,4 +1,4 @@
-use axum::{extract::Request, http::header, http::StatusCode, middleware::Next, response::Response};
+use axum::{extract::Request, http::header, middleware::Next, response::Response};
 use chrono::{Duration, Utc};
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
@@ -73,7 +73,7 @@ impl AuthManager {
         Ok(Self::new(secret))
     }
 
-    fn validate_jwt_secret(secret: &str) -> Result<(), AuthConfigError> {
+    pub fn validate_jwt_secret(secret: &str) -> Result<(), AuthConfigError> {
         let actual_len = secret.len();
         if actual_len < MIN_JWT_SECRET_LEN {
             return Err(AuthConfigError::JwtSecretTooShort {
@@ -149,7 +149,7 @@ impl<S> axum::extract::FromRequestParts<S> for AuthClaims
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = ApiError;
 
     async fn from_request_parts(
         parts: &mut axum::http::request::Parts,
@@ -160,13 +160,15 @@ where
             .get(header::AUTHORIZATION)
             .and_then(|v| v.to_str().ok())
             .and_then(|v| v.strip_prefix("Bearer "))
-            .ok_or(StatusCode::UNAUTHORIZED)?;
+            .ok_or_else(|| {
+                ApiError::unauthorized("Authorization header with Bearer token is required")
+            })?;
 
-        let auth_manager =
-            AuthManager::from_env().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let auth_manager = AuthManager::from_env()
+            .map_err(|_| ApiError::internal("Authentication configuration error"))?;
         auth_manager
             .validate_jwt(auth_header)
-            .map_err(|_| StatusCode::UNAUTHORIZED)
+            .map_err(|_| ApiError::unauthorized("Invalid or expired authentication token"))
     }
 }
 
@@ -179,7 +181,7 @@ fn extract_bearer_token(req: &Request) -> Option<&str> {
         .filter(|token| !token.is_empty())
 }
 
-fn is_admin(claims: &AuthClaims) -> bool {
+pub fn is_admin(claims: &AuthClaims) -> bool {
     claims.admin || matches!(claims.role.as_deref(), Some("admin" | "ADMIN" | "Admin"))
 }
 
@@ -104,11 +104,8 @@ pub async fn verify_challenge(
     let token = mgr
         .verify_and_issue_jwt(&payload.address, &payload.public_key, &payload.signature)
         .map_err(|_| {
-            ApiError::new(
-                StatusCode::UNAUTHORIZED,
-                "AuthFailed",
-                "invalid challenge response",
-            )
+            ApiError::unauthorized("invalid challenge response")
+                .with_details(serde_json::json!({ "reason": "AuthFailed" }))
         })?;
     Ok((
         StatusCode::OK,
@@ -147,6 +144,22 @@ mod tests {
         let (job_engine, _rx) = soroban_batch::engine::JobEngine::new();
         AppState {
             db,
+            config: Arc::new(crate::config::Config::for_tests()),
+            read_db: None,
+            outbound: crate::outbound_http::OutboundClient::new(Default::default()),
+            flags: Default::default(),
+            component_health: Arc::new(crate::component_health::ComponentHealth::new(
+                "http://localhost:3001/api/status",
+                Default::default(),
+            )),
+            source_storage: shared::source_storage::SourceStorage::local(std::env::temp_dir()),
+            replay_guard: Arc::new(crate::replay_guard::ReplayGuard::in_memory(
+                Default::default(),
+            )),
+            webhook_test_sends: Arc::new(crate::rate_limit::CallerLimiter::new(
+                5,
+                std::time::Duration::from_secs(60),
+            )),
             started_at: Instant::now(),
             cache: Arc::new(CacheLayer::new(CacheConfig::default())),
             registry,
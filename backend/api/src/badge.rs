@@ -0,0 +1,238 @@
+//! shields.io-style status badges for embedding in READMEs.
+//!
+//! The SVG follows the shields "flat" style, so a badge served from here sits
+//! next to shields badges without looking out of place. Text is measured with
+//! Verdana 11px advance widths, the font the template asks for.
+
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::incident_feed::escape_xml;
+
+/// Label shown on the left half when the request doesn't override it
+pub const DEFAULT_LABEL: &str = "registry";
+/// Longest `?label=` accepted; longer labels are cut
+pub const MAX_LABEL_CHARS: usize = 64;
+/// How long clients and CDNs may keep a badge. Short, so a contract that
+/// gets verified or deprecated shows it within minutes.
+pub const BADGE_MAX_AGE_SECS: u32 = 300;
+
+/// Horizontal padding on each side of both halves
+const PADDING: f64 = 5.0;
+/// Advance width of characters outside printable ASCII
+const FALLBACK_CHAR_WIDTH: f64 = 7.0;
+/// Verdana 11px advance widths for ' ' through '~'
+const VERDANA_11: [f64; 95] = [
+    3.87, 4.33, 5.05, 9.0, 6.99, 11.84, 7.98, 2.97, 4.99, 4.99, 6.99, 9.0, 4.0, 4.99, 4.0, 4.99,
+    6.99, 6.99, 6.99, 6.99, 6.99, 6.99, 6.99, 6.99, 6.99, 6.99, 4.99, 4.99, 9.0, 9.0, 9.0, 5.99,
+    11.0, 7.52, 7.54, 7.68, 8.48, 6.96, 6.32, 8.53, 8.27, 4.63, 5.0, 7.62, 6.12, 9.27, 8.23, 8.66,
+    6.63, 8.66, 7.6, 7.52, 6.78, 8.05, 7.52, 10.88, 7.54, 6.77, 7.54, 4.99, 4.99, 4.99, 9.0, 6.99,
+    6.99, 6.61, 6.83, 5.73, 6.83, 6.55, 3.78, 6.83, 6.96, 3.02, 3.78, 6.51, 3.02, 10.66, 6.96,
+    6.68, 6.83, 6.83, 4.69, 5.73, 4.33, 6.96, 6.51, 8.98, 6.51, 6.51, 5.78, 6.98, 4.99, 6.98, 9.0,
+];
+
+/// What a contract's badge says
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BadgeStatus {
+    Verified,
+    Unverified,
+    Deprecated,
+    NotFound,
+}
+
+impl BadgeStatus {
+    /// A deprecation outranks verification: users of a verified contract
+    /// that is being retired need to hear about it first
+    pub fn for_contract(is_verified: bool, deprecated: bool) -> Self {
+        match (is_verified, deprecated) {
+            (_, true) => BadgeStatus::Deprecated,
+            (true, false) => BadgeStatus::Verified,
+            (false, false) => BadgeStatus::Unverified,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            BadgeStatus::Verified => "verified",
+            BadgeStatus::Unverified => "unverified",
+            BadgeStatus::Deprecated => "deprecated",
+            BadgeStatus::NotFound => "not found",
+        }
+    }
+
+    /// shields.io named color, used by the JSON endpoint
+    pub fn color_name(&self) -> &'static str {
+        match self {
+            BadgeStatus::Verified => "brightgreen",
+            BadgeStatus::Unverified | BadgeStatus::NotFound => "lightgrey",
+            BadgeStatus::Deprecated => "orange",
+        }
+    }
+
+    /// Hex value of [`Self::color_name`] in the shields palette
+    fn color_hex(&self) -> &'static str {
+        match self {
+            BadgeStatus::Verified => "#4c1",
+            BadgeStatus::Unverified | BadgeStatus::NotFound => "#9f9f9f",
+            BadgeStatus::Deprecated => "#fe7d37",
+        }
+    }
+}
+
+/// Body of the JSON variant, in the shields.io endpoint schema
+/// (<https://shields.io/badges/endpoint-badge>)
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ShieldsEndpoint {
+    /// Always 1
+    pub schema_version: u8,
+    pub label: String,
+    pub message: String,
+    pub color: String,
+    /// Tells shields not to show the badge as valid when the contract
+    /// wasn't found
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_error: bool,
+    pub cache_seconds: u32,
+}
+
+impl ShieldsEndpoint {
+    pub fn new(label: &str, status: BadgeStatus) -> Self {
+        Self {
+            schema_version: 1,
+            label: label.to_string(),
+            message: status.message().to_string(),
+            color: status.color_name().to_string(),
+            is_error: status == BadgeStatus::NotFound,
+            cache_seconds: BADGE_MAX_AGE_SECS,
+        }
+    }
+}
+
+/// The label to show for a `?label=` value: the default when absent or
+/// blank, otherwise trimmed and cut to [`MAX_LABEL_CHARS`]
+pub fn label_or_default(label: Option<&str>) -> String {
+    match label.map(str::trim) {
+        Some(label) if !label.is_empty() => label.chars().take(MAX_LABEL_CHARS).collect(),
+        _ => DEFAULT_LABEL.to_string(),
+    }
+}
+
+/// Rendered width of `text` in Verdana 11px
+pub fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| match c {
+            ' '..='~' => VERDANA_11[c as usize - ' ' as usize],
+            _ => FALLBACK_CHAR_WIDTH,
+        })
+        .sum()
+}
+
+/// Renders a two-part flat badge
+pub fn render_svg(label: &str, status: BadgeStatus) -> String {
+    let message = status.message();
+    let label_text = text_width(label);
+    let message_text = text_width(message);
+    let label_width = (label_text + 2.0 * PADDING).round();
+    let message_width = (message_text + 2.0 * PADDING).round();
+    let total = label_width + message_width;
+    let label = escape_xml(label);
+    let title = format!("{}: {}", label, message);
+
+    let mut svg = String::new();
+    let _ = write!(
+        svg,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{total}" height="20" role="img" aria-label="{title}"><title>{title}</title><linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient><clipPath id="r"><rect width="{total}" height="20" rx="3" fill="#fff"/></clipPath><g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="#555"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{total}" height="20" fill="url(#s)"/></g><g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" text-rendering="geometricPrecision" font-size="110">"##,
+        color = status.color_hex(),
+    );
+    // Text is drawn at 10x and scaled down, as shields does, so positions
+    // keep one decimal of precision in integer attributes
+    for (text, width, start, section) in [
+        (label.as_str(), label_text, 0.0, label_width),
+        (message, message_text, label_width, message_width),
+    ] {
+        let x = ((start + section / 2.0) * 10.0).round();
+        let length = (width * 10.0).round();
+        let _ = write!(
+            svg,
+            r##"<text aria-hidden="true" x="{x}" y="150" fill="#010101" fill-opacity=".3" transform="scale(.1)" textLength="{length}">{text}</text><text x="{x}" y="140" transform="scale(.1)" fill="#fff" textLength="{length}">{text}</text>"##,
+        );
+    }
+    svg.push_str("</g></svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deprecation_outranks_verification() {
+        assert_eq!(
+            BadgeStatus::for_contract(true, true),
+            BadgeStatus::Deprecated
+        );
+        assert_eq!(
+            BadgeStatus::for_contract(true, false),
+            BadgeStatus::Verified
+        );
+        assert_eq!(
+            BadgeStatus::for_contract(false, false),
+            BadgeStatus::Unverified
+        );
+    }
+
+    #[test]
+    fn text_width_matches_verdana() {
+        assert_eq!(text_width(""), 0.0);
+        assert!((text_width("registry") - 42.35).abs() < 0.01);
+        // wide glyphs measure wider than narrow ones of the same count
+        assert!(text_width("WWW") > text_width("iii"));
+        assert_eq!(text_width("é"), FALLBACK_CHAR_WIDTH);
+    }
+
+    #[test]
+    fn svg_sizes_sections_to_their_text() {
+        let svg = render_svg("registry", BadgeStatus::Verified);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"103\""));
+        assert!(svg.contains(r##"<rect width="52" height="20" fill="#555"/>"##));
+        assert!(svg.contains(r##"<rect x="52" width="51" height="20" fill="#4c1"/>"##));
+        assert!(svg.contains("<title>registry: verified</title>"));
+        assert!(svg.ends_with("</g></svg>"));
+    }
+
+    #[test]
+    fn labels_are_escaped_and_bounded() {
+        let svg = render_svg("<a href=\"x\">&", BadgeStatus::Deprecated);
+        assert!(svg.contains("&lt;a href=&quot;x&quot;&gt;&amp;"));
+        assert!(!svg.contains("<a href"));
+        assert!(svg.contains("#fe7d37"));
+
+        assert_eq!(label_or_default(None), DEFAULT_LABEL);
+        assert_eq!(label_or_default(Some("  ")), DEFAULT_LABEL);
+        assert_eq!(label_or_default(Some(" audit ")), "audit");
+        assert_eq!(
+            label_or_default(Some(&"x".repeat(100))).len(),
+            MAX_LABEL_CHARS
+        );
+    }
+
+    #[test]
+    fn shields_json_follows_the_endpoint_schema() {
+        let body = serde_json::to_value(ShieldsEndpoint::new("registry", BadgeStatus::Unverified))
+            .unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "schemaVersion": 1,
+                "label": "registry",
+                "message": "unverified",
+                "color": "lightgrey",
+                "cacheSeconds": BADGE_MAX_AGE_SECS,
+            })
+        );
+        let missing = ShieldsEndpoint::new("registry", BadgeStatus::NotFound);
+        assert!(missing.is_error);
+    }
+}
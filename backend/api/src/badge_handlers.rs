@@ -0,0 +1,128 @@
+//! Verification status badges, see [`crate::badge`].
+//!
+//!   GET /api/contracts/:id/badge.svg   – the badge as an image
+//!   GET /api/contracts/:id/badge.json  – the same badge for shields.io's endpoint badge
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use shared::VisibilityType;
+use uuid::Uuid;
+
+use crate::{
+    badge::{self, BadgeStatus, ShieldsEndpoint},
+    error::ApiResult,
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct BadgeQuery {
+    /// Text for the left half of the badge (default `registry`)
+    pub label: Option<String>,
+}
+
+/// Status of a public contract by registry UUID or contract address.
+/// Private contracts read as not found so a badge can't reveal them.
+async fn contract_badge_status(state: &AppState, id: &str) -> ApiResult<BadgeStatus> {
+    let row: Option<(bool, VisibilityType, bool)> = sqlx::query_as(
+        "SELECT c.is_verified, c.visibility,
+                EXISTS (SELECT 1 FROM contract_deprecations d
+                        WHERE d.contract_id = c.id AND d.deprecated_at <= NOW())
+         FROM contracts c
+         WHERE c.id = $1 OR c.contract_id = $2
+         ORDER BY c.created_at
+         LIMIT 1",
+    )
+    .bind(Uuid::parse_str(id).ok())
+    .bind(id)
+    .fetch_optional(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("fetch contract badge status", err))?;
+
+    Ok(match row {
+        Some((is_verified, visibility, deprecated)) if visibility != VisibilityType::Private => {
+            BadgeStatus::for_contract(is_verified, deprecated)
+        }
+        _ => BadgeStatus::NotFound,
+    })
+}
+
+fn badge_status_code(status: BadgeStatus) -> StatusCode {
+    match status {
+        BadgeStatus::NotFound => StatusCode::NOT_FOUND,
+        _ => StatusCode::OK,
+    }
+}
+
+fn cache_control() -> String {
+    format!("public, max-age={}", badge::BADGE_MAX_AGE_SECS)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/badge.svg",
+    params(
+        ("id" = String, Path, description = "Contract UUID or address"),
+        BadgeQuery
+    ),
+    responses(
+        (status = 200, description = "Verification badge", body = String, content_type = "image/svg+xml"),
+        (status = 404, description = "\"not found\" badge for unknown or private contracts", body = String, content_type = "image/svg+xml")
+    ),
+    tag = "Contracts"
+)]
+pub async fn get_contract_badge_svg(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<BadgeQuery>,
+) -> ApiResult<Response> {
+    let status = contract_badge_status(&state, &id).await?;
+    let label = badge::label_or_default(query.label.as_deref());
+
+    Ok((
+        badge_status_code(status),
+        [
+            (
+                header::CONTENT_TYPE,
+                "image/svg+xml; charset=utf-8".to_string(),
+            ),
+            (header::CACHE_CONTROL, cache_control()),
+        ],
+        badge::render_svg(&label, status),
+    )
+        .into_response())
+}
+
+/// The badge in the shields.io endpoint schema, for
+/// `https://img.shields.io/endpoint?url=<this URL>`. Unknown contracts
+/// answer 200 with `isError` set, which shields shows as an error badge.
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/badge.json",
+    params(
+        ("id" = String, Path, description = "Contract UUID or address"),
+        BadgeQuery
+    ),
+    responses(
+        (status = 200, description = "shields.io endpoint badge", body = ShieldsEndpoint)
+    ),
+    tag = "Contracts"
+)]
+pub async fn get_contract_badge_json(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<BadgeQuery>,
+) -> ApiResult<Response> {
+    let status = contract_badge_status(&state, &id).await?;
+    let label = badge::label_or_default(query.label.as_deref());
+
+    Ok((
+        [(header::CACHE_CONTROL, cache_control())],
+        Json(ShieldsEndpoint::new(&label, status)),
+    )
+        .into_response())
+}
@@ -1,25 +1,27 @@
-use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+use axum::{extract::State, Json};
 use futures_util::stream::{self, StreamExt};
 use serde_json::{json, Value};
 use shared::{BatchVerifyItem, BatchVerifyRequest, Contract};
 
-use crate::{onchain_verification::OnChainVerifier, state::AppState};
+use crate::{
+    contract_freshness,
+    error::{ApiError, ApiResult},
+    onchain_verification::OnChainVerifier,
+    state::AppState,
+};
 
 pub async fn batch_verify_contracts(
     State(state): State<AppState>,
     Json(req): Json<BatchVerifyRequest>,
-) -> impl IntoResponse {
+) -> ApiResult<Json<Value>> {
     if req.contracts.is_empty() {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(json!({
-                "error": "invalid_request",
-                "message": "contracts must not be empty"
-            })),
-        );
+        return Err(ApiError::validation(
+            "contracts",
+            "contracts must not be empty",
+        ));
     }
 
-    let verifier = OnChainVerifier::new();
+    let verifier = OnChainVerifier::new(state.outbound.clone(), state.config.rpc.clone());
     let results = stream::iter(req.contracts.into_iter().map(|item| {
         let state = state.clone();
         let verifier = verifier.clone();
@@ -38,16 +40,13 @@ pub async fn batch_verify_contracts(
         .filter(|result| result.pointer("/on_chain/cached").and_then(Value::as_bool) == Some(true))
         .count();
 
-    (
-        StatusCode::OK,
-        Json(json!({
-            "total": results.len(),
-            "verified": verified,
-            "failed": results.len().saturating_sub(verified),
-            "cached": cached,
-            "results": results
-        })),
-    )
+    Ok(Json(json!({
+        "total": results.len(),
+        "verified": verified,
+        "failed": results.len().saturating_sub(verified),
+        "cached": cached,
+        "results": results
+    })))
 }
 
 async fn verify_batch_item(
@@ -103,6 +102,14 @@ async fn verify_batch_item(
         }
     };
 
+    if on_chain.contract_exists_on_chain && on_chain.wasm_hash_matches {
+        if let Err(err) =
+            contract_freshness::record_onchain_confirmation(&state.db, contract.id).await
+        {
+            tracing::warn!(error = ?err, contract_id = %contract.id, "failed to record on-chain confirmation");
+        }
+    }
+
     let source_verification = match (&item.source_code, &item.compiler_version) {
         (Some(source_code), Some(compiler_version)) if !source_code.trim().is_empty() => Some(
             verifier::verify_contract(
@@ -110,6 +117,7 @@ async fn verify_batch_item(
                 &contract.wasm_hash,
                 Some(compiler_version),
                 item.build_params.as_ref(),
+                None,
             )
             .await,
         ),
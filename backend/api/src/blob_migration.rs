@@ -0,0 +1,201 @@
+//! `api blobs migrate`: moves stored artifacts into the configured backend.
+//!
+//! After `SOURCE_STORAGE_BACKEND` changes, rows in `contract_sources` still
+//! point at the old backend. Each blob is read, checked against its recorded
+//! hash, written to the new backend and its row repointed; the old copy is
+//! deleted once the row is updated unless `--keep` is given.
+
+use anyhow::Context;
+use shared::source_storage::{SourceFormat, SourceStorage};
+use sqlx::{Connection, PgConnection};
+use uuid::Uuid;
+
+const USAGE: &str = "usage: api blobs migrate [--dry-run] [--keep]";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Options {
+    dry_run: bool,
+    keep: bool,
+}
+
+fn parse_command(args: &[String]) -> anyhow::Result<Options> {
+    let mut args = args.iter().map(String::as_str);
+    if args.next() != Some("migrate") {
+        anyhow::bail!(USAGE);
+    }
+    let mut options = Options {
+        dry_run: false,
+        keep: false,
+    };
+    for arg in args {
+        match arg {
+            "--dry-run" => options.dry_run = true,
+            "--keep" => options.keep = true,
+            _ => anyhow::bail!(USAGE),
+        }
+    }
+    Ok(options)
+}
+
+#[derive(sqlx::FromRow)]
+struct StoredBlob {
+    id: Uuid,
+    storage_backend: String,
+    storage_key: String,
+    source_hash: String,
+    source_format: SourceFormat,
+    version: String,
+    contract_id: String,
+}
+
+/// Entry point for `api blobs ...`. Reads `DATABASE_URL` and the
+/// `SOURCE_STORAGE_*` settings and prints to stdout.
+pub async fn run_cli(args: &[String]) -> anyhow::Result<()> {
+    let options = parse_command(args)?;
+    let storage = SourceStorage::new()
+        .await
+        .context("invalid source storage configuration")?;
+    let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+    let mut conn = PgConnection::connect(&database_url)
+        .await
+        .context("failed to connect to the database")?;
+
+    let blobs: Vec<StoredBlob> = sqlx::query_as(
+        "SELECT s.id, s.storage_backend, s.storage_key, s.source_hash, s.source_format,
+                v.version, c.contract_id
+           FROM contract_sources s
+           JOIN contract_versions v ON v.id = s.contract_version_id
+           JOIN contracts c ON c.id = v.contract_id
+          WHERE s.storage_backend <> $1
+          ORDER BY s.created_at",
+    )
+    .bind(storage.backend())
+    .fetch_all(&mut conn)
+    .await
+    .context("failed to list stored sources")?;
+
+    if blobs.is_empty() {
+        println!("Every blob is already stored on {}", storage.backend());
+        return Ok(());
+    }
+    println!(
+        "{} blob(s) to move to {}{}",
+        blobs.len(),
+        storage.backend(),
+        if options.dry_run { " (dry run)" } else { "" }
+    );
+
+    let mut failed = 0;
+    for blob in &blobs {
+        let label = format!(
+            "{} {} {} ({}:{})",
+            blob.contract_id,
+            blob.version,
+            blob.source_format,
+            blob.storage_backend,
+            blob.storage_key
+        );
+        if options.dry_run {
+            println!("  would move {}", label);
+            continue;
+        }
+        match move_blob(&storage, &mut conn, blob, options.keep).await {
+            Ok(key) => println!("  moved  {} -> {}", label, key),
+            Err(err) => {
+                failed += 1;
+                println!("  FAILED {}: {:#}", label, err);
+            }
+        }
+    }
+    conn.close().await?;
+
+    if failed > 0 {
+        anyhow::bail!("{} of {} blob(s) could not be moved", failed, blobs.len());
+    }
+    Ok(())
+}
+
+async fn move_blob(
+    storage: &SourceStorage,
+    conn: &mut PgConnection,
+    blob: &StoredBlob,
+    keep: bool,
+) -> anyhow::Result<String> {
+    let key = storage
+        .copy_to_configured(
+            &blob.storage_backend,
+            &blob.storage_key,
+            &blob.source_hash,
+            &blob.contract_id,
+            &blob.version,
+            blob.source_format.clone(),
+        )
+        .await?;
+
+    // Guarded on the old location so a concurrent re-upload is not overwritten
+    let updated = sqlx::query(
+        "UPDATE contract_sources SET storage_backend = $2, storage_key = $3
+          WHERE id = $1 AND storage_backend = $4 AND storage_key = $5",
+    )
+    .bind(blob.id)
+    .bind(storage.backend())
+    .bind(&key)
+    .bind(&blob.storage_backend)
+    .bind(&blob.storage_key)
+    .execute(&mut *conn)
+    .await
+    .context("failed to update contract_sources")?
+    .rows_affected();
+
+    if updated == 0 {
+        storage.delete_source(storage.backend(), &key).await?;
+        anyhow::bail!("the row changed while the blob was being copied");
+    }
+    if !keep {
+        // The row already points at the copy, so this is not a failure
+        if let Err(err) = storage
+            .delete_source(&blob.storage_backend, &blob.storage_key)
+            .await
+        {
+            println!(
+                "  warning: could not delete {}:{}: {}",
+                blob.storage_backend, blob.storage_key, err
+            );
+        }
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_migrate_flags() {
+        assert_eq!(
+            parse_command(&args(&["migrate"])).unwrap(),
+            Options {
+                dry_run: false,
+                keep: false
+            }
+        );
+        assert_eq!(
+            parse_command(&args(&["migrate", "--keep", "--dry-run"])).unwrap(),
+            Options {
+                dry_run: true,
+                keep: true
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_commands() {
+        assert!(parse_command(&args(&[])).is_err());
+        assert!(parse_command(&args(&["copy"])).is_err());
+        assert!(parse_command(&args(&["migrate", "--force"])).is_err());
+    }
+}
@@ -0,0 +1,367 @@
+//! Build metadata and SBOMs of verified contracts.
+//!
+//! A verification that submits its `Cargo.lock` records the lockfile hash
+//! with the toolchain details, and gets a CycloneDX SBOM generated from it.
+//! When the request also names the repository and commit, the lockfile must
+//! be the one committed there.
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+use shared::{BuildMetadata, Contract, ContractBuildInfo};
+use uuid::Uuid;
+use verifier::build_info::{self, SbomSubject};
+
+const SBOM_CONTENT_TYPE: &str = "application/vnd.cyclonedx+json";
+
+/// Whether `commit` is an abbreviated or full git commit hash.
+fn is_commit_hash(commit: &str) -> bool {
+    (7..=40).contains(&commit.len()) && commit.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Where the raw `Cargo.lock` at `commit` of a GitHub or GitLab repository
+/// can be downloaded from. `None` for other hosts, and for commits or
+/// repository paths that would point the download somewhere else.
+fn raw_lockfile_url(source_url: &str, commit: &str) -> Option<String> {
+    if !is_commit_hash(commit) {
+        return None;
+    }
+    let trimmed = source_url.trim().trim_end_matches('/');
+    let trimmed = trimmed.strip_suffix(".git").unwrap_or(trimmed);
+    let (host, path) = trimmed.strip_prefix("https://")?.split_once('/')?;
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    if segments
+        .iter()
+        .any(|segment| segment.contains("..") || segment.contains(['?', '#']))
+    {
+        return None;
+    }
+
+    match host.to_ascii_lowercase().as_str() {
+        "github.com" | "www.github.com" if segments.len() == 2 => Some(format!(
+            "https://raw.githubusercontent.com/{}/{}/{}/Cargo.lock",
+            segments[0], segments[1], commit
+        )),
+        "gitlab.com" if segments.len() >= 2 && !segments.contains(&"-") => Some(format!(
+            "https://gitlab.com/{}/-/raw/{}/Cargo.lock",
+            segments.join("/"),
+            commit
+        )),
+        _ => None,
+    }
+}
+
+/// Fails unless `cargo_lock` is the `Cargo.lock` committed at `commit` in
+/// the repository at `source_url`.
+pub async fn check_lockfile_at_commit(
+    state: &AppState,
+    source_url: &str,
+    commit: &str,
+    cargo_lock: &str,
+) -> ApiResult<()> {
+    if !is_commit_hash(commit) {
+        return Err(ApiError::bad_request(
+            "InvalidCommitHash",
+            format!(
+                "commit_hash must be 7 to 40 hexadecimal characters, not {:?}",
+                commit
+            ),
+        ));
+    }
+    let url = raw_lockfile_url(source_url, commit).ok_or_else(|| {
+        ApiError::bad_request(
+            "UnsupportedRepository",
+            format!(
+                "Lockfiles can only be checked against GitHub and GitLab repository URLs, not {}; \
+                 leave out commit_hash to skip the check",
+                source_url
+            ),
+        )
+    })?;
+
+    let response = state
+        .outbound
+        .send(state.outbound.get(&url))
+        .await
+        .map_err(|err| {
+            ApiError::service_unavailable(
+                "RepositoryUnavailable",
+                format!("Could not fetch Cargo.lock from {}: {}", source_url, err),
+            )
+        })?;
+
+    let submitted = build_info::lockfile_hash(cargo_lock);
+    let details = |committed: Option<&str>| {
+        json!({
+            "source_url": source_url,
+            "commit_hash": commit,
+            "submitted_sha256": submitted,
+            "committed_sha256": committed,
+        })
+    };
+
+    if response.status() == StatusCode::NOT_FOUND {
+        return Err(ApiError::unprocessable(
+            "LockfileMismatch",
+            format!(
+                "{} has no Cargo.lock at commit {}, or the commit does not exist",
+                source_url, commit
+            ),
+        )
+        .with_details(details(None)));
+    }
+    if !response.status().is_success() {
+        return Err(ApiError::service_unavailable(
+            "RepositoryUnavailable",
+            format!(
+                "Could not fetch Cargo.lock from {}: {}",
+                source_url,
+                response.status()
+            ),
+        ));
+    }
+    let committed_lockfile = response.text().await.map_err(|err| {
+        ApiError::service_unavailable(
+            "RepositoryUnavailable",
+            format!("Could not read Cargo.lock from {}: {}", source_url, err),
+        )
+    })?;
+
+    let committed = build_info::lockfile_hash(&committed_lockfile);
+    if committed != submitted {
+        return Err(ApiError::unprocessable(
+            "LockfileMismatch",
+            format!(
+                "The submitted Cargo.lock does not match the one at commit {} of {}",
+                commit, source_url
+            ),
+        )
+        .with_details(details(Some(&committed))));
+    }
+    Ok(())
+}
+
+/// The SBOM for `contract` built from `cargo_lock`, named after the version
+/// published with the contract's current WASM
+pub async fn generate_sbom(
+    state: &AppState,
+    contract: &Contract,
+    cargo_lock: &str,
+) -> ApiResult<Value> {
+    let packages = build_info::parse_lockfile(cargo_lock)
+        .map_err(|err| ApiError::bad_request("InvalidLockfile", err.to_string()))?;
+    let version: Option<String> = sqlx::query_scalar(
+        "SELECT version FROM contract_versions
+          WHERE contract_id = $1 AND wasm_hash = $2
+          ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(contract.id)
+    .bind(&contract.wasm_hash)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get version for sbom", err))?;
+
+    let name = match (&contract.namespace, &contract.slug) {
+        (Some(namespace), Some(slug)) => format!("{}/{}", namespace, slug),
+        _ => contract.name.clone(),
+    };
+    Ok(build_info::cyclonedx_sbom(
+        &SbomSubject {
+            name: &name,
+            version: version.as_deref(),
+            wasm_hash: &contract.wasm_hash,
+        },
+        &packages,
+    ))
+}
+
+/// Stores the build metadata of a successful verification
+pub async fn record_build_info(
+    pool: &sqlx::PgPool,
+    verification_id: Uuid,
+    contract_id: Uuid,
+    build: &BuildMetadata,
+    source_url: Option<&str>,
+    commit_hash: Option<&str>,
+    sbom: Option<&Value>,
+) -> ApiResult<()> {
+    sqlx::query(
+        "INSERT INTO contract_build_info (
+             verification_id, contract_id, rustc_version, soroban_sdk_version, cargo_features,
+             build_profile, target, build_flags, rustflags, lockfile_sha256, source_url,
+             commit_hash, sbom
+         ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+    )
+    .bind(verification_id)
+    .bind(contract_id)
+    .bind(&build.rustc_version)
+    .bind(&build.soroban_sdk_version)
+    .bind(&build.cargo_features)
+    .bind(&build.build_profile)
+    .bind(&build.target)
+    .bind(&build.build_flags)
+    .bind(&build.rustflags)
+    .bind(&build.lockfile_sha256)
+    .bind(source_url)
+    .bind(commit_hash)
+    .bind(sbom)
+    .execute(pool)
+    .await
+    .map_err(|err| db_internal_error("record build info", err))?;
+    Ok(())
+}
+
+fn parse_contract_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidContractId",
+            format!("Invalid contract ID format: {}", id),
+        )
+    })
+}
+
+/// Get the build metadata of a contract's latest verification
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/build-info",
+    params(
+        ("id" = String, Path, description = "Contract UUID")
+    ),
+    responses(
+        (status = 200, description = "Toolchain and build settings the contract was verified with", body = ContractBuildInfo),
+        (status = 400, description = "Invalid contract ID format"),
+        (status = 404, description = "The contract has not been verified with build metadata")
+    ),
+    tag = "Verification"
+)]
+pub async fn get_build_info(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ContractBuildInfo>> {
+    let contract_id = parse_contract_id(&id)?;
+    let info: Option<ContractBuildInfo> = sqlx::query_as(
+        "SELECT contract_id, verification_id, rustc_version, soroban_sdk_version, cargo_features,
+                build_profile, target, build_flags, rustflags, lockfile_sha256, source_url,
+                commit_hash, sbom IS NOT NULL AS has_sbom, created_at AS verified_at
+           FROM contract_build_info
+          WHERE contract_id = $1
+          ORDER BY created_at DESC
+          LIMIT 1",
+    )
+    .bind(contract_id)
+    .fetch_optional(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("get build info", err))?;
+
+    info.map(Json).ok_or_else(|| {
+        ApiError::not_found(
+            "BuildInfoNotFound",
+            format!("No build metadata recorded for contract {}", contract_id),
+        )
+    })
+}
+
+/// Get the CycloneDX SBOM of a contract's latest verification
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/sbom",
+    params(
+        ("id" = String, Path, description = "Contract UUID")
+    ),
+    responses(
+        (status = 200, description = "CycloneDX 1.5 JSON document", body = Object, content_type = "application/vnd.cyclonedx+json"),
+        (status = 400, description = "Invalid contract ID format"),
+        (status = 404, description = "The latest verification had no lockfile to build an SBOM from")
+    ),
+    tag = "Verification"
+)]
+pub async fn get_sbom(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Response> {
+    let contract_id = parse_contract_id(&id)?;
+    let sbom: Option<Option<Value>> = sqlx::query_scalar(
+        "SELECT sbom FROM contract_build_info
+          WHERE contract_id = $1
+          ORDER BY created_at DESC
+          LIMIT 1",
+    )
+    .bind(contract_id)
+    .fetch_optional(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("get sbom", err))?;
+
+    let sbom = sbom.flatten().ok_or_else(|| {
+        ApiError::not_found(
+            "SbomNotFound",
+            format!(
+                "No SBOM for contract {}; verify it with its Cargo.lock to generate one",
+                contract_id
+            ),
+        )
+    })?;
+    Ok(([(header::CONTENT_TYPE, SBOM_CONTENT_TYPE)], Json(sbom)).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lockfile_urls_for_supported_hosts() {
+        assert_eq!(
+            raw_lockfile_url("https://github.com/acme/escrow.git", "abc1234").as_deref(),
+            Some("https://raw.githubusercontent.com/acme/escrow/abc1234/Cargo.lock")
+        );
+        assert_eq!(
+            raw_lockfile_url("https://gitlab.com/acme/contracts/escrow/", "abc1234").as_deref(),
+            Some("https://gitlab.com/acme/contracts/escrow/-/raw/abc1234/Cargo.lock")
+        );
+    }
+
+    #[test]
+    fn no_lockfile_url_for_other_commits() {
+        let full = "0123456789abcdef0123456789abcdef01234567";
+        assert!(raw_lockfile_url("https://github.com/acme/escrow", full).is_some());
+        for commit in [
+            "abc123",
+            "main",
+            "../../x",
+            "abc1234?",
+            &format!("{}8", full),
+        ] {
+            assert_eq!(
+                raw_lockfile_url("https://github.com/acme/escrow", commit),
+                None,
+                "{}",
+                commit
+            );
+        }
+    }
+
+    #[test]
+    fn no_lockfile_url_for_other_sources() {
+        for url in [
+            "https://gitea.com/acme/escrow",
+            "https://github.com/acme",
+            "https://github.com/acme/escrow/tree/main",
+            "http://github.com/acme/escrow",
+            "https://gitlab.com/acme/escrow/-/tree/main",
+            "https://github.com/acme/..",
+            "https://github.com/acme/escrow?x=",
+            "https://github.com/acme/escrow#x",
+            "https://gitlab.com/acme/../escrow",
+        ] {
+            assert_eq!(raw_lockfile_url(url, "abc1234"), None, "{}", url);
+        }
+    }
+}
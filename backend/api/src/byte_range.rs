@@ -0,0 +1,206 @@
+//! `Range` requests on artifact downloads, so an interrupted transfer can be
+//! resumed instead of restarted.
+//!
+//! Downloads advertise `Accept-Ranges: bytes` and answer a single `bytes=`
+//! range with `206 Partial Content`. Malformed ranges and ranges starting
+//! past the end get `416` with `Content-Range: bytes */{size}`. Several
+//! ranges at once, other units, and an `If-Range` that no longer matches the
+//! artifact's `ETag` are answered with the whole artifact.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use shared::source_storage::SourceDownload;
+
+use crate::error::{ApiError, ApiResult};
+
+/// Bytes `start..=end` of an artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    pub fn content_range(&self, size: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, size)
+    }
+}
+
+/// The part of an artifact of `size` bytes with `etag` the request asks
+/// for; `None` for all of it.
+pub fn requested(headers: &HeaderMap, etag: &str, size: u64) -> ApiResult<Option<ByteRange>> {
+    let Some(range) = headers.get(header::RANGE) else {
+        return Ok(None);
+    };
+    // A resumed download that would be stitched onto a different artifact;
+    // weak validators and dates never match, there is no Last-Modified
+    if let Some(if_range) = headers.get(header::IF_RANGE) {
+        if if_range.as_bytes() != etag.as_bytes() {
+            return Ok(None);
+        }
+    }
+    let range = range
+        .to_str()
+        .map_err(|_| ApiError::range_not_satisfiable(size, "Range is not valid ASCII"))?;
+    parse(range, size)
+}
+
+/// Resolves a `Range` header value against an artifact of `size` bytes
+pub fn parse(range: &str, size: u64) -> ApiResult<Option<ByteRange>> {
+    let Some((unit, spec)) = range.trim().split_once('=') else {
+        return Err(unsatisfiable(range, size));
+    };
+    if !unit.trim().eq_ignore_ascii_case("bytes") || spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((first, last)) = spec.trim().split_once('-') else {
+        return Err(unsatisfiable(range, size));
+    };
+    let number = |text: &str| {
+        text.trim()
+            .parse::<u64>()
+            .map_err(|_| unsatisfiable(range, size))
+    };
+
+    let resolved = match (first.trim(), last.trim()) {
+        // The final `last` bytes
+        ("", last) => {
+            let suffix = number(last)?;
+            (suffix > 0 && size > 0).then(|| ByteRange {
+                start: size.saturating_sub(suffix),
+                end: size - 1,
+            })
+        }
+        (first, "") => {
+            let start = number(first)?;
+            (start < size).then(|| ByteRange {
+                start,
+                end: size - 1,
+            })
+        }
+        (first, last) => {
+            let (start, end) = (number(first)?, number(last)?);
+            (start <= end && start < size).then(|| ByteRange {
+                start,
+                end: end.min(size - 1),
+            })
+        }
+    };
+    resolved.map(Some).ok_or_else(|| unsatisfiable(range, size))
+}
+
+fn unsatisfiable(range: &str, size: u64) -> ApiError {
+    ApiError::range_not_satisfiable(
+        size,
+        format!("Range '{}' is not satisfiable for {} bytes", range, size),
+    )
+}
+
+/// A stored artifact of `size` bytes sent as an attachment: the requested
+/// `range` of it with `206`, or all of it
+pub fn respond(
+    download: SourceDownload,
+    range: Option<ByteRange>,
+    size: u64,
+    etag: &str,
+    content_type: &str,
+    filename: &str,
+) -> Response {
+    let stream = match download {
+        SourceDownload::Redirect(url) => {
+            return (StatusCode::TEMPORARY_REDIRECT, [(header::LOCATION, url)]).into_response()
+        }
+        SourceDownload::Stream(stream) => stream,
+    };
+    let (status, length) = match range {
+        Some(range) => (StatusCode::PARTIAL_CONTENT, range.length()),
+        None => (StatusCode::OK, size),
+    };
+    let mut response = (
+        status,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::CONTENT_LENGTH, length.to_string()),
+            (header::ACCEPT_RANGES, "bytes".to_string()),
+            (header::ETAG, etag.to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        Body::from_stream(stream),
+    )
+        .into_response();
+    if let Some(range) = range {
+        if let Ok(value) = range.content_range(size).parse() {
+            response.headers_mut().insert(header::CONTENT_RANGE, value);
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> Option<ByteRange> {
+        Some(ByteRange { start, end })
+    }
+
+    #[test]
+    fn single_ranges_are_clamped_to_the_artifact() {
+        assert_eq!(parse("bytes=0-99", 1000).unwrap(), range(0, 99));
+        assert_eq!(parse("bytes=500-", 1000).unwrap(), range(500, 999));
+        assert_eq!(parse("bytes=900-5000", 1000).unwrap(), range(900, 999));
+        assert_eq!(parse("bytes=-100", 1000).unwrap(), range(900, 999));
+        assert_eq!(parse("bytes=-5000", 1000).unwrap(), range(0, 999));
+        assert_eq!(
+            range(900, 999).unwrap().content_range(1000),
+            "bytes 900-999/1000"
+        );
+    }
+
+    #[test]
+    fn invalid_and_unsatisfiable_ranges_are_416() {
+        for header in [
+            "bytes=1000-",
+            "bytes=1000-1200",
+            "bytes=50-10",
+            "bytes=-0",
+            "bytes=abc-",
+            "bytes=10",
+            "bytes",
+        ] {
+            let err = parse(header, 1000).unwrap_err();
+            assert_eq!(err.status(), StatusCode::RANGE_NOT_SATISFIABLE, "{header}");
+        }
+        let response = parse("bytes=0-", 0).unwrap_err().into_response();
+        assert_eq!(response.headers()[header::CONTENT_RANGE], "bytes */0");
+    }
+
+    #[test]
+    fn other_units_and_multiple_ranges_get_everything() {
+        assert_eq!(parse("items=0-5", 1000).unwrap(), None);
+        assert_eq!(parse("bytes=0-5, 10-20", 1000).unwrap(), None);
+    }
+
+    #[test]
+    fn a_stale_if_range_gets_everything() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::RANGE, "bytes=100-".parse().unwrap());
+        headers.insert(header::IF_RANGE, "\"abc\"".parse().unwrap());
+        assert_eq!(
+            requested(&headers, "\"abc\"", 1000).unwrap(),
+            range(100, 999)
+        );
+        assert_eq!(requested(&headers, "\"def\"", 1000).unwrap(), None);
+        assert_eq!(requested(&HeaderMap::new(), "\"abc\"", 1000).unwrap(), None);
+    }
+}
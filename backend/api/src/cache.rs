@@ -1,7 +1,10 @@
 use moka::future::Cache as MokaCache;
+use shared::models::Contract;
 use sqlx::PgPool;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
+use uuid::Uuid;
 
 /// Cache configuration options
 #[derive(Clone, Debug)]
@@ -10,6 +13,9 @@ pub struct CacheConfig {
     pub max_capacity: u64,
     pub redis_enabled: bool,
     pub redis_url: Option<String>,
+    /// Maximum number of contract rows kept for `GET /api/contracts/{id}`
+    pub contract_max_entries: u64,
+    pub contract_ttl: Duration,
 }
 
 impl Default for CacheConfig {
@@ -19,6 +25,8 @@ impl Default for CacheConfig {
             max_capacity: 10_000,
             redis_enabled: false,
             redis_url: None,
+            contract_max_entries: 1_000,
+            contract_ttl: Duration::from_secs(30),
         }
     }
 }
@@ -43,11 +51,25 @@ impl CacheConfig {
 
         config.redis_url = std::env::var("REDIS_URL").ok();
 
+        if let Ok(entries_str) = std::env::var("CONTRACT_CACHE_MAX_ENTRIES") {
+            if let Ok(entries) = entries_str.parse::<u64>() {
+                config.contract_max_entries = entries;
+            }
+        }
+
+        if let Ok(ttl_str) = std::env::var("CONTRACT_CACHE_TTL_SECS") {
+            if let Ok(ttl) = ttl_str.parse::<u64>() {
+                config.contract_ttl = Duration::from_secs(ttl);
+            }
+        }
+
         tracing::info!(
-            "Cache config loaded: enabled={}, capacity={}, redis_enabled={}",
+            "Cache config loaded: enabled={}, capacity={}, redis_enabled={}, contract_entries={}, contract_ttl={}s",
             config.enabled,
             config.max_capacity,
-            config.redis_enabled
+            config.redis_enabled,
+            config.contract_max_entries,
+            config.contract_ttl.as_secs()
         );
 
         config
@@ -59,6 +81,11 @@ pub struct CacheLayer {
     pub verification_cache: MokaCache<String, String>,
     pub generic_cache: MokaCache<String, String>,
     pub contract_access_cache: MokaCache<String, bool>,
+    /// Contract rows by id, invalidated by every handler that writes them
+    pub contract_cache: MokaCache<Uuid, Contract>,
+    /// Bumped on every contract invalidation so a lookup that raced a write
+    /// does not put the row it read before the write back into the cache
+    contract_generation: AtomicU64,
     pub config: CacheConfig,
     pub redis_cm: Option<ConnectionManager>,
 }
@@ -92,6 +119,12 @@ impl CacheLayer {
             .time_to_live(Duration::from_secs(60))
             .build();
 
+        // Short TTL: writes outside this process are only picked up on expiry
+        let contract_cache = MokaCache::builder()
+            .max_capacity(config.contract_max_entries)
+            .time_to_live(config.contract_ttl)
+            .build();
+
         // Initialize Redis connection manager if Redis is enabled
         let redis_cm = if config.redis_enabled {
             if let Some(redis_url) = &config.redis_url {
@@ -102,12 +135,12 @@ impl CacheLayer {
                             Some(cm)
                         }
                         Err(e) => {
-                            tracing::warn!("Failed to create Redis connection manager: {}", e);
+                            tracing::warn!(error = %e, "Failed to create Redis connection manager");
                             None
                         }
                     },
                     Err(e) => {
-                        tracing::warn!("Failed to create Redis client: {}", e);
+                        tracing::warn!(error = %e, "Failed to create Redis client");
                         None
                     }
                 }
@@ -123,6 +156,8 @@ impl CacheLayer {
             verification_cache,
             generic_cache,
             contract_access_cache,
+            contract_cache,
+            contract_generation: AtomicU64::new(0),
             config,
             redis_cm,
         }
@@ -135,7 +170,7 @@ impl CacheLayer {
     pub async fn get_abi(&self, contract_id: &str, bypass_cache: bool) -> Option<String> {
         if !self.config.enabled || bypass_cache {
             if bypass_cache {
-                tracing::debug!("Bypassing cache for contract_id: {}", contract_id);
+                tracing::debug!(%contract_id, "Bypassing cache");
             }
             return None;
         }
@@ -199,6 +234,52 @@ impl CacheLayer {
         self.verification_cache.invalidate(bytecode_hash).await;
     }
 
+    /// Returns the cached contract, or a miss together with the generation to
+    /// hand back to [`CacheLayer::put_contract`] after loading the row.
+    pub async fn get_contract(&self, id: Uuid) -> Result<Contract, u64> {
+        let generation = self.contract_generation.load(Ordering::SeqCst);
+        if !self.config.enabled {
+            return Err(generation);
+        }
+
+        match self.contract_cache.get(&id).await {
+            Some(contract) => {
+                crate::metrics::CONTRACT_CACHE_HITS.inc();
+                Ok(contract)
+            }
+            None => {
+                crate::metrics::CONTRACT_CACHE_MISSES.inc();
+                Err(generation)
+            }
+        }
+    }
+
+    /// Caches a contract loaded after a miss, unless it was invalidated in
+    /// the meantime.
+    pub async fn put_contract(&self, contract: Contract, generation: u64) {
+        if !self.config.enabled || self.contract_generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+
+        let id = contract.id;
+        self.contract_cache.insert(id, contract).await;
+        // An invalidation between the check and the insert may have missed it
+        if self.contract_generation.load(Ordering::SeqCst) != generation {
+            self.contract_cache.invalidate(&id).await;
+        }
+    }
+
+    pub async fn invalidate_contract(&self, id: Uuid) {
+        self.contract_generation.fetch_add(1, Ordering::SeqCst);
+        self.contract_cache.invalidate(&id).await;
+    }
+
+    /// For writes that touch many contracts at once.
+    pub fn invalidate_all_contracts(&self) {
+        self.contract_generation.fetch_add(1, Ordering::SeqCst);
+        self.contract_cache.invalidate_all();
+    }
+
     // Generic cache methods with namespace support
     pub async fn get(&self, ns: &str, key: &str) -> (Option<String>, bool) {
         if !self.config.enabled {
@@ -456,10 +537,72 @@ mod tests {
             max_capacity: 100,
             redis_enabled: false,
             redis_url: None,
+            ..Default::default()
         }).await;
 
         assert!(cache.should_refresh_contract_access("contract-1").await);
         assert!(!cache.should_refresh_contract_access("contract-1").await);
         assert!(cache.should_refresh_contract_access("contract-2").await);
     }
+
+    fn contract(id: Uuid, name: &str) -> Contract {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "contract_id": "C123",
+            "wasm_hash": "abc",
+            "name": name,
+            "description": null,
+            "publisher_id": Uuid::new_v4(),
+            "network": "testnet",
+            "is_verified": false,
+            "category": null,
+            "tags": [],
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z",
+            "verified_at": null,
+            "last_accessed_at": null,
+            "organization_id": null,
+            "visibility": "public"
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_contract_cache_invalidation() {
+        let cache = CacheLayer::new(CacheConfig::default()).await;
+        let id = Uuid::new_v4();
+
+        let generation = cache.get_contract(id).await.unwrap_err();
+        cache.put_contract(contract(id, "before"), generation).await;
+        assert_eq!(cache.get_contract(id).await.unwrap().name, "before");
+
+        cache.invalidate_contract(id).await;
+        assert!(cache.get_contract(id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_contract_read_racing_a_write_is_not_cached() {
+        let cache = CacheLayer::new(CacheConfig::default()).await;
+        let id = Uuid::new_v4();
+
+        // A lookup misses and loads the old row while an update commits
+        let generation = cache.get_contract(id).await.unwrap_err();
+        cache.invalidate_contract(id).await;
+        cache.put_contract(contract(id, "stale"), generation).await;
+
+        assert!(cache.get_contract(id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_contract_cache_disabled() {
+        let cache = CacheLayer::new(CacheConfig {
+            enabled: false,
+            ..Default::default()
+        })
+        .await;
+        let id = Uuid::new_v4();
+
+        cache.put_contract(contract(id, "c"), 0).await;
+        assert!(cache.get_contract(id).await.is_err());
+    }
 }
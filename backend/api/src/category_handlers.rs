@@ -164,8 +164,7 @@ const LIST_QUERY: &str = r#"
 "#;
 
 fn db_err(op: &str, err: sqlx::Error) -> ApiError {
-    tracing::error!(operation = op, error = ?err, "database operation failed");
-    ApiError::internal("An unexpected database error occurred")
+    ApiError::from_sqlx(op, err)
 }
 
 fn parse_category_id(id: &str) -> ApiResult<Uuid> {
@@ -517,11 +516,11 @@ pub async fn delete_category(
     })?;
 
     if is_default {
-        return Err(ApiError::new(
-            axum::http::StatusCode::FORBIDDEN,
-            "DefaultCategory",
-            format!("'{}' is a default category and cannot be deleted", name),
-        ));
+        return Err(ApiError::forbidden(format!(
+            "'{}' is a default category and cannot be deleted",
+            name
+        ))
+        .with_details(serde_json::json!({ "reason": "DefaultCategory" })));
     }
 
     // Count contracts currently using this category.
@@ -549,6 +548,7 @@ pub async fn delete_category(
             .execute(&state.db)
             .await
             .map_err(|err| db_err("clear contracts category on force delete", err))?;
+        state.cache.invalidate_all_contracts();
     }
 
     sqlx::query("DELETE FROM contract_categories WHERE id = $1")
@@ -0,0 +1,514 @@
+//! Notification channels managed by their owners.
+//!
+//!   POST   /api/me/channels                 – register a webhook or email address and start verifying it
+//!   GET    /api/me/channels                 – the caller's channels
+//!   GET    /api/me/channels/:id             – one channel
+//!   PATCH  /api/me/channels/:id             – rename, disable or re-enable a channel
+//!   DELETE /api/me/channels/:id             – remove a channel no subscription uses
+//!   POST   /api/me/channels/:id/verify      – send a new challenge or confirmation email
+//!   GET    /api/me/channels/:id/deliveries  – recent verification and delivery attempts
+//!   GET    /api/channels/confirm?token=     – confirmation link sent to email channels
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    channel_models::{
+        ChannelAttempt, ChannelConfirmation, ChannelKind, CreateChannelRequest,
+        UpdateChannelRequest, UserChannel,
+    },
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    notification_channels,
+    state::AppState,
+    subscription_handlers::{ensure_public_webhook, validate_email, validate_webhook_url},
+};
+
+/// Channels one owner may register
+const MAX_CHANNELS_PER_OWNER: i64 = 25;
+const MAX_NAME_LEN: usize = 100;
+const DEFAULT_ATTEMPTS_LIMIT: i64 = 50;
+const MAX_ATTEMPTS_LIMIT: i64 = 200;
+
+fn validate_name(name: Option<&str>) -> ApiResult<Option<String>> {
+    let Some(name) = name.map(str::trim).filter(|name| !name.is_empty()) else {
+        return Ok(None);
+    };
+    if name.chars().count() > MAX_NAME_LEN || name.chars().any(char::is_control) {
+        return Err(ApiError::bad_request(
+            "InvalidChannelName",
+            format!(
+                "name must be at most {} characters, without control characters",
+                MAX_NAME_LEN
+            ),
+        ));
+    }
+    Ok(Some(name.to_string()))
+}
+
+fn parse_channel_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidChannelId",
+            format!("Invalid channel ID format: {}", id),
+        )
+    })
+}
+
+/// Loads a channel owned by the caller; other owners' channels are reported
+/// as missing
+pub(crate) async fn fetch_owned_channel(
+    state: &AppState,
+    channel_id: Uuid,
+    claims: &AuthClaims,
+) -> ApiResult<UserChannel> {
+    sqlx::query_as("SELECT * FROM user_notification_channels WHERE id = $1 AND owner = $2")
+        .bind(channel_id)
+        .bind(&claims.sub)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get notification channel", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "ChannelNotFound",
+                format!("No channel found with ID: {}", channel_id),
+            )
+        })
+}
+
+/// Returns the caller's channel for `target`, registering it and starting
+/// its verification when there is none yet. The flag says whether it is new.
+pub(crate) async fn register_channel(
+    state: &AppState,
+    claims: &AuthClaims,
+    kind: &str,
+    target: &str,
+    name: Option<String>,
+) -> ApiResult<(UserChannel, bool)> {
+    let existing: Option<UserChannel> = sqlx::query_as(
+        "SELECT * FROM user_notification_channels WHERE owner = $1 AND kind = $2 AND target = $3",
+    )
+    .bind(&claims.sub)
+    .bind(kind)
+    .bind(target)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("find notification channel", err))?;
+    if let Some(channel) = existing {
+        return Ok((channel, false));
+    }
+
+    let held: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM user_notification_channels WHERE owner = $1")
+            .bind(&claims.sub)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("count notification channels", err))?;
+    if held >= MAX_CHANNELS_PER_OWNER {
+        return Err(ApiError::conflict(
+            "ChannelLimitReached",
+            format!(
+                "An owner can register at most {} channels; remove one first",
+                MAX_CHANNELS_PER_OWNER
+            ),
+        ));
+    }
+
+    // A concurrent registration of the same target wins; use its row
+    let inserted: Option<UserChannel> = sqlx::query_as(
+        "INSERT INTO user_notification_channels (owner, name, kind, target)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (owner, kind, target) DO NOTHING
+         RETURNING *",
+    )
+    .bind(&claims.sub)
+    .bind(&name)
+    .bind(kind)
+    .bind(target)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("create notification channel", err))?;
+    let Some(channel) = inserted else {
+        let channel = sqlx::query_as(
+            "SELECT * FROM user_notification_channels
+             WHERE owner = $1 AND kind = $2 AND target = $3",
+        )
+        .bind(&claims.sub)
+        .bind(kind)
+        .bind(target)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("find notification channel", err))?;
+        return Ok((channel, false));
+    };
+
+    tracing::info!(channel_id = %channel.id, kind, "notification channel registered");
+
+    let channel = notification_channels::start_verification(state, &channel)
+        .await
+        .map_err(|err| db_internal_error("start channel verification", err))?;
+    Ok((channel, true))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/me/channels",
+    request_body = CreateChannelRequest,
+    responses(
+        (status = 201, description = "Channel registered and verification started; webhooks that echo the challenge are verified right away", body = UserChannel),
+        (status = 400, description = "Invalid webhook URL or one without a public address, invalid email address or name"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 409, description = "Target already registered, or channel limit reached")
+    ),
+    tag = "Channels"
+)]
+pub async fn create_channel(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<CreateChannelRequest>,
+) -> ApiResult<(StatusCode, Json<UserChannel>)> {
+    let target = match req.kind {
+        ChannelKind::Webhook => {
+            let target = validate_webhook_url(req.target.trim())?;
+            ensure_public_webhook(&state, &target).await?;
+            target
+        }
+        ChannelKind::Email => validate_email(req.target.trim())?,
+    };
+    let name = validate_name(req.name.as_deref())?;
+
+    let (channel, created) =
+        register_channel(&state, &claims, req.kind.as_str(), &target, name).await?;
+    if !created {
+        return Err(ApiError::conflict(
+            "ChannelExists",
+            format!("{} is already registered as channel {}", target, channel.id),
+        ));
+    }
+    Ok((StatusCode::CREATED, Json(channel)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/channels",
+    responses(
+        (status = 200, description = "The caller's channels, oldest first", body = [UserChannel]),
+        (status = 401, description = "Missing or invalid API token")
+    ),
+    tag = "Channels"
+)]
+pub async fn list_channels(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Vec<UserChannel>>> {
+    let channels: Vec<UserChannel> = sqlx::query_as(
+        "SELECT * FROM user_notification_channels WHERE owner = $1 ORDER BY created_at",
+    )
+    .bind(&claims.sub)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list notification channels", err))?;
+
+    Ok(Json(channels))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/channels/{id}",
+    params(("id" = String, Path, description = "Channel UUID")),
+    responses(
+        (status = 200, description = "The channel", body = UserChannel),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "No such channel owned by the caller")
+    ),
+    tag = "Channels"
+)]
+pub async fn get_channel(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<Json<UserChannel>> {
+    let channel_id = parse_channel_id(&id)?;
+    Ok(Json(
+        fetch_owned_channel(&state, channel_id, &claims).await?,
+    ))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/me/channels/{id}",
+    params(("id" = String, Path, description = "Channel UUID")),
+    request_body = UpdateChannelRequest,
+    responses(
+        (status = 200, description = "Updated channel; re-enabling starts verification again", body = UserChannel),
+        (status = 400, description = "Invalid name"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "No such channel owned by the caller")
+    ),
+    tag = "Channels"
+)]
+pub async fn update_channel(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<UpdateChannelRequest>,
+) -> ApiResult<Json<UserChannel>> {
+    let channel_id = parse_channel_id(&id)?;
+    let mut channel = fetch_owned_channel(&state, channel_id, &claims).await?;
+
+    if req.name.is_some() {
+        let name = validate_name(req.name.as_deref())?;
+        channel = sqlx::query_as(
+            "UPDATE user_notification_channels SET name = $2, updated_at = NOW()
+             WHERE id = $1 RETURNING *",
+        )
+        .bind(channel_id)
+        .bind(&name)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("rename notification channel", err))?;
+    }
+
+    match req.enabled {
+        Some(false) if channel.status != "disabled" => {
+            let mut tx = state
+                .db
+                .begin()
+                .await
+                .map_err(|err| db_internal_error("begin channel update", err))?;
+            channel = sqlx::query_as(
+                "UPDATE user_notification_channels
+                 SET status = 'disabled', disabled_at = NOW(), verification_token = NULL,
+                     updated_at = NOW()
+                 WHERE id = $1 RETURNING *",
+            )
+            .bind(channel_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("disable notification channel", err))?;
+            notification_channels::fail_pending_deliveries(
+                &mut *tx,
+                channel_id,
+                "channel disabled by its owner",
+            )
+            .await
+            .map_err(|err| db_internal_error("disable notification channel", err))?;
+            tx.commit()
+                .await
+                .map_err(|err| db_internal_error("commit channel update", err))?;
+            tracing::info!(%channel_id, "notification channel disabled by owner");
+        }
+        Some(true) if channel.status == "disabled" => {
+            channel = notification_channels::start_verification(&state, &channel)
+                .await
+                .map_err(|err| db_internal_error("start channel verification", err))?;
+        }
+        _ => {}
+    }
+
+    Ok(Json(channel))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/me/channels/{id}",
+    params(("id" = String, Path, description = "Channel UUID")),
+    responses(
+        (status = 204, description = "Channel removed with its attempt history"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "No such channel owned by the caller"),
+        (status = 409, description = "Subscriptions still deliver to the channel")
+    ),
+    tag = "Channels"
+)]
+pub async fn delete_channel(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    let channel_id = parse_channel_id(&id)?;
+    fetch_owned_channel(&state, channel_id, &claims).await?;
+
+    let in_use: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM contract_subscriptions WHERE channel_id = $1")
+            .bind(channel_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("count channel subscriptions", err))?;
+    if in_use > 0 {
+        return Err(ApiError::conflict(
+            "ChannelInUse",
+            format!(
+                "{} subscription(s) deliver to this channel; unsubscribe them first",
+                in_use
+            ),
+        ));
+    }
+
+    sqlx::query("DELETE FROM user_notification_channels WHERE id = $1 AND owner = $2")
+        .bind(channel_id)
+        .bind(&claims.sub)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete notification channel", err))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/me/channels/{id}/verify",
+    params(("id" = String, Path, description = "Channel UUID")),
+    responses(
+        (status = 200, description = "New challenge or confirmation email sent; see `status` and `last_error` for the outcome", body = UserChannel),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "No such channel owned by the caller"),
+        (status = 409, description = "Channel is already verified")
+    ),
+    tag = "Channels"
+)]
+pub async fn verify_channel(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<Json<UserChannel>> {
+    let channel_id = parse_channel_id(&id)?;
+    let channel = fetch_owned_channel(&state, channel_id, &claims).await?;
+
+    if !matches!(channel.status.as_str(), "pending" | "disabled") {
+        return Err(ApiError::conflict(
+            "ChannelAlreadyVerified",
+            format!("Channel {} is already verified", channel_id),
+        ));
+    }
+
+    let channel = notification_channels::start_verification(&state, &channel)
+        .await
+        .map_err(|err| db_internal_error("start channel verification", err))?;
+    Ok(Json(channel))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AttemptsQuery {
+    /// Attempts to return, newest first (default 50, at most 200)
+    pub limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/channels/{id}/deliveries",
+    params(("id" = String, Path, description = "Channel UUID"), AttemptsQuery),
+    responses(
+        (status = 200, description = "Recent verification and delivery attempts, newest first", body = [ChannelAttempt]),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "No such channel owned by the caller")
+    ),
+    tag = "Channels"
+)]
+pub async fn list_channel_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<AttemptsQuery>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Vec<ChannelAttempt>>> {
+    let channel_id = parse_channel_id(&id)?;
+    fetch_owned_channel(&state, channel_id, &claims).await?;
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ATTEMPTS_LIMIT)
+        .clamp(1, MAX_ATTEMPTS_LIMIT);
+    let attempts: Vec<ChannelAttempt> = sqlx::query_as(
+        "SELECT * FROM user_notification_channel_attempts
+         WHERE channel_id = $1
+         ORDER BY attempted_at DESC
+         LIMIT $2",
+    )
+    .bind(channel_id)
+    .bind(limit)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list channel attempts", err))?;
+
+    Ok(Json(attempts))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ConfirmQuery {
+    /// Token from the confirmation email
+    pub token: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/channels/confirm",
+    params(ConfirmQuery),
+    responses(
+        (status = 200, description = "Email channel verified", body = ChannelConfirmation),
+        (status = 400, description = "Link is invalid, expired or was replaced by a newer one")
+    ),
+    tag = "Channels"
+)]
+pub async fn confirm_channel(
+    State(state): State<AppState>,
+    Query(query): Query<ConfirmQuery>,
+) -> ApiResult<Json<ChannelConfirmation>> {
+    let invalid = || {
+        ApiError::bad_request(
+            "InvalidConfirmationLink",
+            "This confirmation link is invalid or has expired; request a new one",
+        )
+    };
+    let (channel_id, nonce) =
+        notification_channels::read_confirmation(state.config.jwt_secret.expose(), &query.token)
+            .ok_or_else(invalid)?;
+
+    let verified = notification_channels::mark_verified(&state.db, channel_id, &nonce)
+        .await
+        .map_err(|err| db_internal_error("confirm notification channel", err))?;
+
+    if !verified {
+        // Following the same link twice is fine
+        let status: Option<String> =
+            sqlx::query_scalar("SELECT status FROM user_notification_channels WHERE id = $1")
+                .bind(channel_id)
+                .fetch_optional(&state.db)
+                .await
+                .map_err(|err| db_internal_error("confirm notification channel", err))?;
+        return match status {
+            Some(status) if status == "verified" || status == "failing" => {
+                Ok(Json(ChannelConfirmation { channel_id, status }))
+            }
+            _ => Err(invalid()),
+        };
+    }
+
+    tracing::info!(%channel_id, "email notification channel confirmed");
+    Ok(Json(ChannelConfirmation {
+        channel_id,
+        status: "verified".to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_trimmed_and_bounded() {
+        assert_eq!(validate_name(None).unwrap(), None);
+        assert_eq!(validate_name(Some("  ")).unwrap(), None);
+        assert_eq!(
+            validate_name(Some(" ops pager ")).unwrap(),
+            Some("ops pager".to_string())
+        );
+        assert!(validate_name(Some(&"x".repeat(MAX_NAME_LEN + 1))).is_err());
+        assert!(validate_name(Some("a\nb")).is_err());
+    }
+}
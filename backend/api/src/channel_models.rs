@@ -0,0 +1,88 @@
+//! Self-service notification channels, see [`crate::notification_channels`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Where a channel delivers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChannelKind {
+    /// JSON POST to an http(s) URL
+    Webhook,
+    /// Plain-text email through the SMTP relay
+    Email,
+}
+
+impl ChannelKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChannelKind::Webhook => "webhook",
+            ChannelKind::Email => "email",
+        }
+    }
+}
+
+/// A webhook or email address registered by its owner
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct UserChannel {
+    pub id: Uuid,
+    /// Subject of the API token that registered the channel
+    pub owner: String,
+    pub name: Option<String>,
+    pub kind: String,   // 'webhook' | 'email'
+    pub target: String, // Webhook URL or recipient email address
+    /// `pending` until verified; `failing` after a failed delivery, and
+    /// `disabled` once too many failed in a row
+    pub status: String, // 'pending' | 'verified' | 'failing' | 'disabled'
+    pub verified_at: Option<DateTime<Utc>>,
+    pub consecutive_failures: i32,
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<DateTime<Utc>>,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of `POST /api/me/channels`
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct CreateChannelRequest {
+    pub kind: ChannelKind,
+    /// Webhook URL or email address
+    pub target: String,
+    pub name: Option<String>,
+}
+
+/// Body of `PATCH /api/me/channels/:id`
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct UpdateChannelRequest {
+    pub name: Option<String>,
+    /// `false` disables the channel; `true` re-enables a disabled channel,
+    /// which has to be verified again
+    pub enabled: Option<bool>,
+}
+
+/// One verification or delivery attempt on a channel
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct ChannelAttempt {
+    pub id: Uuid,
+    pub channel_id: Uuid,
+    pub purpose: String, // 'verification' | 'delivery'
+    /// Subscription delivery the attempt sent, if it was a delivery
+    pub delivery_id: Option<Uuid>,
+    pub subject: Option<String>,
+    pub succeeded: bool,
+    /// HTTP status the webhook answered with; absent for email and for
+    /// requests that got no response
+    pub status_code: Option<i32>,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// Result of following an emailed confirmation link
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ChannelConfirmation {
+    pub channel_id: Uuid,
+    pub status: String,
+}
@@ -0,0 +1,32 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{channel_handlers, state::AppState};
+
+pub fn channel_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/me/channels",
+            get(channel_handlers::list_channels).post(channel_handlers::create_channel),
+        )
+        .route(
+            "/api/me/channels/:id",
+            get(channel_handlers::get_channel)
+                .patch(channel_handlers::update_channel)
+                .delete(channel_handlers::delete_channel),
+        )
+        .route(
+            "/api/me/channels/:id/verify",
+            post(channel_handlers::verify_channel),
+        )
+        .route(
+            "/api/me/channels/:id/deliveries",
+            get(channel_handlers::list_channel_deliveries),
+        )
+        .route(
+            "/api/channels/confirm",
+            get(channel_handlers::confirm_channel),
+        )
+}
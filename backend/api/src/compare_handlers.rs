@@ -0,0 +1,493 @@
+//! Side-by-side comparison of two contracts, or of a contract and one of
+//! its earlier versions.
+//!
+//!   GET /api/contracts/compare?a=<id>[@<version>]&b=<id>[@<version>]
+//!
+//! Each side is a contract UUID or address, optionally pinned to a version.
+//! Without a version the contract's latest version is used. The diff covers
+//! metadata fields, tags, the WASM hash and, when both sides have a stored
+//! interface, functions added, removed or with a changed signature.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use shared::{Contract, ContractVersion, Network, OrganizationRole, VisibilityType};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    breaking_changes::{self, diff_abi, has_breaking_changes, BreakingChange},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    org_handlers,
+    state::AppState,
+    type_safety::{parser::parse_json_spec, types::ContractFunction},
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct CompareQuery {
+    /// Base side: contract UUID or address, optionally `@<version>`
+    pub a: String,
+    /// Side compared against `a`, in the same form
+    pub b: String,
+}
+
+/// One side of a comparison as it was resolved
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ComparedContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub network: Network,
+    /// The pinned version, or the latest one; absent when the contract has
+    /// no versions
+    pub version: Option<String>,
+    pub wasm_hash: String,
+    /// Whether an interface (ABI) is stored for this side
+    pub has_interface: bool,
+}
+
+/// A metadata field that differs between the two sides
+#[derive(Debug, Serialize, PartialEq, utoipa::ToSchema)]
+pub struct FieldChange {
+    pub field: String,
+    #[schema(value_type = Object)]
+    pub before: Value,
+    #[schema(value_type = Object)]
+    pub after: Value,
+}
+
+#[derive(Debug, Serialize, PartialEq, utoipa::ToSchema)]
+pub struct SignatureChange {
+    pub function: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct InterfaceDiff {
+    pub functions_added: Vec<String>,
+    pub functions_removed: Vec<String>,
+    pub signatures_changed: Vec<SignatureChange>,
+    /// Whether callers of `a` would break against `b`
+    pub breaking: bool,
+    /// Every function and type change, as reported by the breaking-change check
+    pub changes: Vec<BreakingChange>,
+}
+
+impl InterfaceDiff {
+    fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ContractComparison {
+    pub a: ComparedContract,
+    pub b: ComparedContract,
+    /// True when nothing below differs
+    pub identical: bool,
+    pub same_wasm: bool,
+    /// Metadata fields whose values differ, `before` from `a` and `after` from `b`
+    pub metadata: Vec<FieldChange>,
+    pub tags_added: Vec<String>,
+    pub tags_removed: Vec<String>,
+    /// Absent unless both sides have a stored interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface: Option<InterfaceDiff>,
+}
+
+/// A resolved side: the contract, the version it is pinned to and its ABI
+struct Side {
+    contract: Contract,
+    version: Option<ContractVersion>,
+    abi: Option<String>,
+}
+
+impl Side {
+    fn wasm_hash(&self) -> &str {
+        self.version
+            .as_ref()
+            .map_or(&self.contract.wasm_hash, |v| &v.wasm_hash)
+    }
+
+    /// Field values compared between sides, in response order
+    fn metadata(&self) -> Vec<(&'static str, Value)> {
+        let c = &self.contract;
+        let v = self.version.as_ref();
+        vec![
+            ("name", json!(c.name)),
+            ("description", json!(c.description)),
+            ("category", json!(c.category)),
+            ("network", json!(c.network)),
+            ("publisher_id", json!(c.publisher_id)),
+            ("is_verified", json!(c.is_verified)),
+            ("version", json!(v.map(|v| &v.version))),
+            ("source_url", json!(v.and_then(|v| v.source_url.as_ref()))),
+            ("commit_hash", json!(v.and_then(|v| v.commit_hash.as_ref()))),
+        ]
+    }
+
+    fn summary(&self) -> ComparedContract {
+        ComparedContract {
+            id: self.contract.id,
+            contract_id: self.contract.contract_id.clone(),
+            name: self.contract.name.clone(),
+            network: self.contract.network.clone(),
+            version: self.version.as_ref().map(|v| v.version.clone()),
+            wasm_hash: self.wasm_hash().to_string(),
+            has_interface: self.abi.is_some(),
+        }
+    }
+}
+
+/// Splits `<id>@<version>`; the version is optional
+fn parse_selector(selector: &str) -> ApiResult<(&str, Option<&str>)> {
+    let (id, version) = match selector.trim().split_once('@') {
+        Some((id, version)) => (id, Some(version)),
+        None => (selector.trim(), None),
+    };
+    if id.is_empty() || version.is_some_and(str::is_empty) {
+        return Err(ApiError::bad_request(
+            "InvalidSelector",
+            format!(
+                "Expected a contract UUID or address, optionally followed by @<version>; got '{}'",
+                selector
+            ),
+        ));
+    }
+    Ok((id, version))
+}
+
+async fn resolve_side(
+    state: &AppState,
+    claims: Option<&AuthClaims>,
+    selector: &str,
+) -> ApiResult<Side> {
+    let (id, version) = parse_selector(selector)?;
+
+    let contract: Contract = sqlx::query_as(
+        "SELECT * FROM contracts WHERE id = $1 OR contract_id = $2 ORDER BY created_at LIMIT 1",
+    )
+    .bind(Uuid::parse_str(id).ok())
+    .bind(id)
+    .fetch_optional(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("fetch contract to compare", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found for '{}'", id),
+        )
+    })?;
+
+    if contract.visibility == VisibilityType::Private {
+        let is_member = match (claims, contract.organization_id) {
+            (Some(claims), Some(org_id)) => org_handlers::check_org_role(
+                state.read_pool(),
+                org_id,
+                &claims.sub,
+                OrganizationRole::Viewer,
+            )
+            .await
+            .is_ok(),
+            _ => false,
+        };
+        if !is_member {
+            return Err(ApiError::forbidden(
+                "This contract is private and you do not have access to it",
+            ));
+        }
+    }
+
+    let pinned = version.is_some();
+    let version: Option<ContractVersion> = match version {
+        Some(version) => Some(
+            sqlx::query_as(
+                "SELECT * FROM contract_versions WHERE contract_id = $1 AND version = $2",
+            )
+            .bind(contract.id)
+            .bind(version)
+            .fetch_optional(state.read_pool())
+            .await
+            .map_err(|err| db_internal_error("fetch version to compare", err))?
+            .ok_or_else(|| {
+                ApiError::not_found(
+                    "VersionNotFound",
+                    format!("{} has no version {}", contract.name, version),
+                )
+            })?,
+        ),
+        None => sqlx::query_as(
+            "SELECT * FROM contract_versions WHERE contract_id = $1
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(contract.id)
+        .fetch_optional(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("fetch latest version to compare", err))?,
+    };
+
+    // Use the interface stored for the resolved version. An unpinned side
+    // whose latest version has none falls back to the contract's newest ABI.
+    let mut abi = match version {
+        Some(ref v) => fetch_abi(state, &format!("{}@{}", contract.id, v.version)).await?,
+        None => None,
+    };
+    if abi.is_none() && !pinned {
+        abi = fetch_abi(state, &contract.id.to_string()).await?;
+    }
+
+    Ok(Side {
+        contract,
+        version,
+        abi,
+    })
+}
+
+/// The ABI for a breaking-change selector, or `None` when none is stored
+async fn fetch_abi(state: &AppState, selector: &str) -> ApiResult<Option<String>> {
+    match breaking_changes::resolve_abi(state, selector, false).await {
+        Ok(abi) => Ok(Some(abi)),
+        Err(ApiError::NotFound { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// `name(param: Type, ...) -> Return`
+fn signature(function: &ContractFunction) -> String {
+    let params: Vec<String> = function
+        .params
+        .iter()
+        .map(|p| format!("{}: {}", p.name, p.param_type.display_name()))
+        .collect();
+    format!(
+        "{}({}) -> {}",
+        function.name,
+        params.join(", "),
+        function.return_type.display_name()
+    )
+}
+
+fn diff_interfaces(a: &str, b: &str) -> ApiResult<InterfaceDiff> {
+    let parse = |abi: &str, side: &str| {
+        parse_json_spec(abi, side).map_err(|e| {
+            ApiError::unprocessable(
+                "InvalidABI",
+                format!("Failed to parse the interface of {}: {}", side, e),
+            )
+        })
+    };
+    let old = parse(a, "a")?;
+    let new = parse(b, "b")?;
+
+    let old_sigs: BTreeMap<&str, String> = old
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), signature(f)))
+        .collect();
+    let new_sigs: BTreeMap<&str, String> = new
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), signature(f)))
+        .collect();
+
+    let changes = diff_abi(&old, &new);
+    Ok(InterfaceDiff {
+        functions_added: new_sigs
+            .keys()
+            .filter(|name| !old_sigs.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect(),
+        functions_removed: old_sigs
+            .keys()
+            .filter(|name| !new_sigs.contains_key(*name))
+            .map(|name| name.to_string())
+            .collect(),
+        signatures_changed: old_sigs
+            .iter()
+            .filter_map(|(name, before)| {
+                let after = new_sigs.get(name)?;
+                (before != after).then(|| SignatureChange {
+                    function: name.to_string(),
+                    before: before.clone(),
+                    after: after.clone(),
+                })
+            })
+            .collect(),
+        breaking: has_breaking_changes(&changes),
+        changes,
+    })
+}
+
+/// Tags only in `b` and tags only in `a`, compared case-insensitively
+fn tag_changes(a: &[String], b: &[String]) -> (Vec<String>, Vec<String>) {
+    let lower = |tags: &[String]| -> BTreeSet<String> {
+        tags.iter().map(|t| t.trim().to_lowercase()).collect()
+    };
+    let (a_set, b_set) = (lower(a), lower(b));
+    (
+        b_set.difference(&a_set).cloned().collect(),
+        a_set.difference(&b_set).cloned().collect(),
+    )
+}
+
+fn metadata_changes(a: &Side, b: &Side) -> Vec<FieldChange> {
+    a.metadata()
+        .into_iter()
+        .zip(b.metadata())
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((field, before), (_, after))| FieldChange {
+            field: field.to_string(),
+            before,
+            after,
+        })
+        .collect()
+}
+
+fn compare(a: &Side, b: &Side) -> ApiResult<ContractComparison> {
+    let metadata = metadata_changes(a, b);
+    let (tags_added, tags_removed) = tag_changes(&a.contract.tags, &b.contract.tags);
+    let same_wasm = a.wasm_hash() == b.wasm_hash();
+    let interface = match (&a.abi, &b.abi) {
+        (Some(old), Some(new)) => Some(diff_interfaces(old, new)?),
+        _ => None,
+    };
+    // One side having an interface and the other not is a difference too
+    let same_interface = match interface {
+        Some(ref diff) => diff.is_empty(),
+        None => a.abi.is_some() == b.abi.is_some(),
+    };
+
+    Ok(ContractComparison {
+        identical: metadata.is_empty()
+            && tags_added.is_empty()
+            && tags_removed.is_empty()
+            && same_wasm
+            && same_interface,
+        a: a.summary(),
+        b: b.summary(),
+        same_wasm,
+        metadata,
+        tags_added,
+        tags_removed,
+        interface,
+    })
+}
+
+/// Compare two contracts, or a contract with one of its versions.
+#[utoipa::path(
+    get,
+    path = "/api/contracts/compare",
+    params(CompareQuery),
+    responses(
+        (status = 200, description = "Structured diff of the two sides", body = ContractComparison),
+        (status = 400, description = "Malformed selector"),
+        (status = 403, description = "A side is a private contract the caller can't see"),
+        (status = 404, description = "Unknown contract or version"),
+        (status = 422, description = "A stored interface could not be parsed")
+    ),
+    tag = "Analysis"
+)]
+pub async fn compare_contracts(
+    State(state): State<AppState>,
+    claims: Option<AuthClaims>,
+    Query(query): Query<CompareQuery>,
+) -> ApiResult<Json<ContractComparison>> {
+    let a = resolve_side(&state, claims.as_ref(), &query.a).await?;
+    let b = resolve_side(&state, claims.as_ref(), &query.b).await?;
+    Ok(Json(compare(&a, &b)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn side(name: &str, tags: &[&str], wasm_hash: &str, version: Option<&str>) -> Side {
+        let contract: Contract = serde_json::from_value(json!({
+            "id": Uuid::nil(),
+            "contract_id": "CABC",
+            "wasm_hash": wasm_hash,
+            "name": name,
+            "description": null,
+            "publisher_id": Uuid::nil(),
+            "network": "testnet",
+            "is_verified": false,
+            "category": "DeFi",
+            "tags": tags,
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z",
+            "verified_at": null,
+            "last_accessed_at": null,
+            "organization_id": null,
+            "visibility": "public"
+        }))
+        .unwrap();
+        let version = version.map(|version| ContractVersion {
+            id: Uuid::nil(),
+            contract_id: Uuid::nil(),
+            version: version.to_string(),
+            wasm_hash: wasm_hash.to_string(),
+            source_url: None,
+            commit_hash: None,
+            release_notes: None,
+            created_at: Utc::now(),
+            state_schema: None,
+            signature: None,
+            publisher_key: None,
+            signature_algorithm: None,
+            signed_at: None,
+            signature_nonce: None,
+            wasm_meta: None,
+        });
+        Side {
+            contract,
+            version,
+            abi: None,
+        }
+    }
+
+    #[test]
+    fn selectors_take_an_optional_version() {
+        assert_eq!(parse_selector("CABC").unwrap(), ("CABC", None));
+        assert_eq!(
+            parse_selector(" CABC@1.2.0 ").unwrap(),
+            ("CABC", Some("1.2.0"))
+        );
+        assert!(parse_selector("@1.0.0").is_err());
+        assert!(parse_selector("CABC@").is_err());
+    }
+
+    #[test]
+    fn the_same_contract_is_identical() {
+        let a = side("pool", &["dex"], "h1", Some("1.0.0"));
+        let b = side("pool", &["DEX"], "h1", Some("1.0.0"));
+        let diff = compare(&a, &b).unwrap();
+        assert!(diff.identical);
+        assert!(diff.same_wasm);
+        assert!(diff.metadata.is_empty());
+    }
+
+    #[test]
+    fn changed_fields_tags_and_wasm_are_reported() {
+        let a = side("pool", &["dex", "amm"], "h1", Some("1.0.0"));
+        let b = side("pool-v2", &["dex", "lending"], "h2", Some("2.0.0"));
+        let diff = compare(&a, &b).unwrap();
+
+        assert!(!diff.identical);
+        assert!(!diff.same_wasm);
+        assert_eq!(diff.tags_added, vec!["lending"]);
+        assert_eq!(diff.tags_removed, vec!["amm"]);
+        let fields: Vec<&str> = diff.metadata.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["name", "version"]);
+        assert_eq!(diff.metadata[1].before, json!("1.0.0"));
+        assert_eq!(diff.metadata[1].after, json!("2.0.0"));
+        assert!(diff.interface.is_none());
+    }
+}
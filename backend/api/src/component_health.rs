@@ -0,0 +1,201 @@
+//! Degraded mode for the contracts API.
+//!
+//! The incident watchdog records here which components have an open
+//! incident, e.g. `database` or `blob_store`. While one is impaired, routes
+//! that depend on it answer 503 straight away instead of waiting for pool or
+//! storage timeouts, and the body names the incident and the status page:
+//!
+//! ```json
+//! { "error": { "code": "SERVICE_UNAVAILABLE", "message": "...",
+//!   "details": { "reason": "COMPONENT_IMPAIRED", "component": "database",
+//!     "incident_id": "...", "impaired_since": "...",
+//!     "status_page_url": "https://registry.example/api/status" } } }
+//! ```
+//!
+//! `incident_id` is `null` until the watchdog has managed to write the
+//! incident, which for the database only happens once it answers again.
+//! Routes that do not touch the component, and reads answered from the
+//! cache, keep serving. The watchdog clears a component once its checks pass
+//! again, and turning the `degraded_mode` feature flag off lets every
+//! request through regardless. Runtime overrides are stored in the database,
+//! so while the database itself is down that takes
+//! `FEATURE_DEGRADED_MODE=false` and a restart.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use axum::{
+    body::Body,
+    extract::State,
+    http::Request,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::error::ApiError;
+use crate::feature_flags::FeatureFlags;
+
+pub const DATABASE: &str = "database";
+pub const BLOB_STORE: &str = "blob_store";
+
+/// Roughly one watchdog probe interval
+const RETRY_AFTER_SECS: u64 = 30;
+
+/// Why a component is considered impaired
+#[derive(Debug, Clone, PartialEq)]
+pub struct Impairment {
+    /// The watchdog's incident, once it has been written
+    pub incident_id: Option<Uuid>,
+    pub since: DateTime<Utc>,
+}
+
+/// Components with an open incident, shared across handlers
+#[derive(Debug)]
+pub struct ComponentHealth {
+    impaired: RwLock<HashMap<&'static str, Impairment>>,
+    status_page_url: String,
+    flags: Arc<FeatureFlags>,
+}
+
+impl ComponentHealth {
+    pub fn new(status_page_url: impl Into<String>, flags: Arc<FeatureFlags>) -> Self {
+        Self {
+            impaired: RwLock::new(HashMap::new()),
+            status_page_url: status_page_url.into(),
+            flags,
+        }
+    }
+
+    /// Replaces the set of impaired components; anything left out has
+    /// recovered.
+    pub fn replace(&self, impaired: HashMap<&'static str, Impairment>) {
+        let mut current = self.impaired.write().unwrap_or_else(|e| e.into_inner());
+        for (component, impairment) in &impaired {
+            if !current.contains_key(component) {
+                tracing::warn!(
+                    component,
+                    incident_id = ?impairment.incident_id,
+                    "component impaired; dependent routes answer 503"
+                );
+            }
+        }
+        for component in current.keys() {
+            if !impaired.contains_key(component) {
+                tracing::info!(component, "component recovered; degraded mode cleared");
+            }
+        }
+        *current = impaired;
+    }
+
+    pub fn impairment(&self, component: &str) -> Option<Impairment> {
+        self.impaired
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(component)
+            .cloned()
+    }
+
+    /// Errors with a 503 when `component` is impaired and degraded mode is on
+    pub fn check(&self, component: &'static str) -> Result<(), ApiError> {
+        if !self.flags.degraded_mode.enabled() {
+            return Ok(());
+        }
+        let Some(impairment) = self.impairment(component) else {
+            return Ok(());
+        };
+
+        Err(ApiError::service_unavailable(
+            "COMPONENT_IMPAIRED",
+            format!(
+                "The registry's {} is impaired; see the status page for updates",
+                component.replace('_', " ")
+            ),
+        )
+        .with_retry_after(RETRY_AFTER_SECS)
+        .with_details(json!({
+            "component": component,
+            "incident_id": impairment.incident_id,
+            "impaired_since": impairment.since,
+            "status_page_url": self.status_page_url,
+        })))
+    }
+
+    /// Middleware state for routes that need every one of `components`
+    pub fn requires(self: &Arc<Self>, components: &'static [&'static str]) -> Dependencies {
+        Dependencies {
+            health: self.clone(),
+            components,
+        }
+    }
+}
+
+/// Components a group of routes cannot serve without
+#[derive(Clone)]
+pub struct Dependencies {
+    health: Arc<ComponentHealth>,
+    components: &'static [&'static str],
+}
+
+/// Answers 503 without running the handler while a dependency is impaired.
+/// Apply with `route_layer` so that unknown paths still 404.
+pub async fn short_circuit(
+    State(dependencies): State<Dependencies>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    for component in dependencies.components {
+        if let Err(err) = dependencies.health.check(component) {
+            return err.into_response();
+        }
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health() -> ComponentHealth {
+        ComponentHealth::new(
+            "https://status.example/api/status",
+            Arc::new(FeatureFlags::default()),
+        )
+    }
+
+    fn impaired(component: &'static str) -> HashMap<&'static str, Impairment> {
+        HashMap::from([(
+            component,
+            Impairment {
+                incident_id: Some(Uuid::nil()),
+                since: DateTime::from_timestamp(1_800_000_000, 0).unwrap(),
+            },
+        )])
+    }
+
+    #[test]
+    fn only_impaired_components_fail_until_they_recover() {
+        let health = health();
+        assert!(health.check(DATABASE).is_ok());
+
+        health.replace(impaired(DATABASE));
+        let err = health.check(DATABASE).unwrap_err();
+        assert_eq!(err.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
+        assert!(health.check(BLOB_STORE).is_ok());
+
+        health.replace(HashMap::new());
+        assert!(health.check(DATABASE).is_ok());
+    }
+
+    #[test]
+    fn flag_lets_requests_through() {
+        let health = health();
+        health.replace(impaired(BLOB_STORE));
+        health
+            .flags
+            .apply_overrides(&HashMap::from([("degraded_mode".to_string(), false)]));
+        assert!(health.check(BLOB_STORE).is_ok());
+    }
+}
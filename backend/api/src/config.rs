@@ -0,0 +1,800 @@
+//! Typed API configuration, read and validated once at startup.
+//!
+//! [`Config::from_env`] parses every setting below and reports all invalid
+//! ones together, so a typo fails the boot with a complete list instead of
+//! surfacing deep inside a request. The result is stored in
+//! [`crate::state::AppState::config`].
+//!
+//! | Variable                        | Default                                   |
+//! |---------------------------------|-------------------------------------------|
+//! | `DATABASE_URL`                  | required, `postgres://` or `postgresql://` |
+//! | `JWT_SECRET`                    | required, at least 32 characters          |
+//! | `HOST`                          | `0.0.0.0`                                 |
+//! | `PORT`                          | 3001                                      |
+//! | `RUN_MIGRATIONS`                | `false`                                   |
+//! | `SOROBAN_RPC_MAINNET`           | `https://mainnet.sorobanrpc.com`          |
+//! | `SOROBAN_RPC_TESTNET`           | `https://soroban-testnet.stellar.org`     |
+//! | `SOROBAN_RPC_FUTURENET`         | `https://rpc-futurenet.stellar.org`       |
+//! | `SOROBAN_RPC_TIMEOUT_SECS`      | 15                                        |
+//! | `SOROBAN_RPC_MAX_RETRIES`       | 3                                         |
+//! | `JOB_WORKERS`                   | 2                                         |
+//! | `JOB_MAX_ATTEMPTS`              | 5                                         |
+//! | `JOB_BASE_BACKOFF_SECS`         | 30                                        |
+//! | `JOB_LEASE_SECS`                | 900                                       |
+//! | `WEBHOOK_TEST_SENDS_PER_MINUTE` | 5                                         |
+//! | `STATUS_PAGE_URL`               | `$PUBLIC_BASE_URL/api/status`             |
+//!
+//! Pool sizes (`DB_*`, see [`crate::db_pool`]), rate limits (`RATE_LIMIT_*`,
+//! see [`crate::rate_limit`]), outbound/webhook settings (`OUTBOUND_*`, see
+//! [`crate::outbound_http`]), feature defaults (`FEATURE_*`, see
+//! [`crate::feature_flags`]), log output (`LOG_*`, see
+//! [`crate::request_tracing`]), publish screening (`PUBLISH_SCREENING_*`,
+//! see [`crate::publish_screening`]), signed publish replay protection
+//! (`SIGNATURE_*`, see [`crate::replay_guard`]), runtime settings such as
+//! `SLOW_QUERY_THRESHOLD_MS` and `REQUEST_TIMEOUT_*_MS` (see
+//! [`crate::feature_flags`]), data retention
+//! (`RETENTION_*`, see [`crate::retention`]) and the stale-contract sweep
+//! (`FRESHNESS_*`, see [`crate::contract_freshness`]) use the variables and
+//! defaults documented in their modules, but are validated here with the
+//! rest.
+//!
+//! Secrets are wrapped in [`Secret`], which never prints its value, so the
+//! effective configuration can be logged at startup.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::auth::AuthManager;
+use crate::db_pool::PoolConfig;
+use crate::feature_flags::FeatureFlags;
+use crate::outbound_http::OutboundConfig;
+use crate::publish_screening::{self, ScreeningConfig};
+use crate::rate_limit::RateLimitConfig;
+use crate::replay_guard::ReplayConfig;
+use crate::request_tracing::{LogFormat, LoggingConfig};
+use crate::retention::{Retention, RetentionConfig, RetentionTable};
+
+const DEFAULT_HOST: &str = "0.0.0.0";
+const DEFAULT_PORT: u16 = 3001;
+const DEFAULT_RPC_MAINNET: &str = "https://mainnet.sorobanrpc.com";
+const DEFAULT_RPC_TESTNET: &str = "https://soroban-testnet.stellar.org";
+const DEFAULT_RPC_FUTURENET: &str = "https://rpc-futurenet.stellar.org";
+const DEFAULT_RPC_TIMEOUT_SECS: u64 = 15;
+const DEFAULT_RPC_MAX_RETRIES: u32 = 3;
+const DEFAULT_JOB_WORKERS: usize = 2;
+const DEFAULT_JOB_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_JOB_BASE_BACKOFF_SECS: u64 = 30;
+const DEFAULT_JOB_LEASE_SECS: u64 = 900;
+const DEFAULT_WEBHOOK_TEST_SENDS_PER_MINUTE: usize = 5;
+const DEFAULT_FRESHNESS_VERSION_DAYS: u32 = 365;
+const DEFAULT_FRESHNESS_ONCHAIN_DAYS: u32 = 90;
+const DEFAULT_FRESHNESS_PUBLISHER_DAYS: u32 = 365;
+const DEFAULT_FRESHNESS_WARNING_DAYS: u32 = 14;
+const DEFAULT_FRESHNESS_SWEEP_INTERVAL_SECS: u64 = 3600;
+const DEFAULT_PUBLIC_BASE_URL: &str = "http://localhost:3001";
+
+/// A value that must not appear in logs
+#[derive(Clone, PartialEq, Eq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[redacted]")
+    }
+}
+
+/// One invalid or missing setting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    pub var: &'static str,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.var, self.message)
+    }
+}
+
+/// Every problem found while loading the configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigErrors(pub Vec<ConfigError>);
+
+impl ConfigErrors {
+    pub fn vars(&self) -> Vec<&'static str> {
+        self.0.iter().map(|err| err.var).collect()
+    }
+}
+
+impl fmt::Display for ConfigErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} invalid setting(s)", self.0.len())?;
+        for err in &self.0 {
+            write!(f, "\n  - {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ConfigErrors {}
+
+/// Soroban RPC endpoints used for on-chain verification
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcConfig {
+    pub mainnet: String,
+    pub testnet: String,
+    pub futurenet: String,
+    pub timeout: Duration,
+    pub max_retries: u32,
+}
+
+impl Default for RpcConfig {
+    fn default() -> Self {
+        Self {
+            mainnet: DEFAULT_RPC_MAINNET.to_string(),
+            testnet: DEFAULT_RPC_TESTNET.to_string(),
+            futurenet: DEFAULT_RPC_FUTURENET.to_string(),
+            timeout: Duration::from_secs(DEFAULT_RPC_TIMEOUT_SECS),
+            max_retries: DEFAULT_RPC_MAX_RETRIES,
+        }
+    }
+}
+
+/// Background job workers, see [`crate::jobs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobsConfig {
+    /// Jobs run concurrently by this instance
+    pub workers: usize,
+    /// Attempts before a job that keeps failing is moved to `dead`
+    pub max_attempts: u32,
+    /// Wait before the first retry; doubles with every further attempt
+    pub base_backoff: Duration,
+    /// How long a running job may take before it is assumed abandoned and
+    /// queued again
+    pub lease: Duration,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            workers: DEFAULT_JOB_WORKERS,
+            max_attempts: DEFAULT_JOB_MAX_ATTEMPTS,
+            base_backoff: Duration::from_secs(DEFAULT_JOB_BASE_BACKOFF_SECS),
+            lease: Duration::from_secs(DEFAULT_JOB_LEASE_SECS),
+        }
+    }
+}
+
+/// The stale-contract sweep, see [`crate::contract_freshness`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FreshnessConfig {
+    pub sweep_enabled: bool,
+    /// Days without a new version before a contract counts as quiet
+    pub version_days: u32,
+    /// Days without an on-chain confirmation
+    pub onchain_days: u32,
+    /// Days without publisher activity
+    pub publisher_days: u32,
+    /// Days between the warning to subscribers and the stale mark
+    pub warning_days: u32,
+    pub sweep_interval: Duration,
+}
+
+impl Default for FreshnessConfig {
+    fn default() -> Self {
+        Self {
+            sweep_enabled: true,
+            version_days: DEFAULT_FRESHNESS_VERSION_DAYS,
+            onchain_days: DEFAULT_FRESHNESS_ONCHAIN_DAYS,
+            publisher_days: DEFAULT_FRESHNESS_PUBLISHER_DAYS,
+            warning_days: DEFAULT_FRESHNESS_WARNING_DAYS,
+            sweep_interval: Duration::from_secs(DEFAULT_FRESHNESS_SWEEP_INTERVAL_SECS),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub database_url: Secret,
+    pub jwt_secret: Secret,
+    pub bind_addr: SocketAddr,
+    /// Apply pending migrations before serving
+    pub run_migrations: bool,
+    pub pool: PoolConfig,
+    pub rate_limit: RateLimitConfig,
+    pub rpc: RpcConfig,
+    pub jobs: JobsConfig,
+    /// Timeouts, per-host limits and circuit breaker for webhooks and RPC
+    pub outbound: OutboundConfig,
+    /// Test webhooks (`POST /api/webhooks/test`) each caller may send a minute
+    pub webhook_test_sends_per_minute: usize,
+    /// Startup value of each feature flag, before runtime overrides
+    pub feature_defaults: BTreeMap<&'static str, bool>,
+    /// Startup value of each runtime setting, before runtime overrides
+    pub setting_defaults: BTreeMap<&'static str, u64>,
+    pub feature_refresh_interval: Duration,
+    pub logging: LoggingConfig,
+    /// Duplicate and spam checks on publish
+    pub screening: ScreeningConfig,
+    /// Accepted age of publish signatures and where their nonces are kept
+    pub replay: ReplayConfig,
+    /// How long each table's rows are kept, and when they are purged
+    pub retention: RetentionConfig,
+    /// When quiet contracts are warned about and marked stale
+    pub freshness: FreshnessConfig,
+    /// Linked from degraded-mode responses, see [`crate::component_health`]
+    pub status_page_url: String,
+}
+
+impl Config {
+    pub fn from_env() -> Result<Self, ConfigErrors> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Builds the configuration from `lookup`, which stands in for the
+    /// environment.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigErrors> {
+        let mut env = Env {
+            lookup: &lookup,
+            errors: Vec::new(),
+        };
+
+        let database_url = env.required("DATABASE_URL").and_then(|url| {
+            if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+                Some(Secret(url))
+            } else {
+                env.invalid(
+                    "DATABASE_URL",
+                    "must start with postgres:// or postgresql://",
+                );
+                None
+            }
+        });
+        let jwt_secret = env.required("JWT_SECRET").and_then(|secret| {
+            match AuthManager::validate_jwt_secret(&secret) {
+                Ok(()) => Some(Secret(secret)),
+                Err(err) => {
+                    env.invalid("JWT_SECRET", err.to_string());
+                    None
+                }
+            }
+        });
+
+        let host = env.parse::<IpAddr>("HOST", DEFAULT_HOST.parse().unwrap());
+        let port = env.parse::<u16>("PORT", DEFAULT_PORT);
+        let run_migrations = env.flag("RUN_MIGRATIONS", false);
+
+        let pool_defaults = PoolConfig::default();
+        let statement_timeout = env.parse::<u64>(
+            "DB_STATEMENT_TIMEOUT_SECS",
+            pool_defaults.statement_timeout.map_or(0, |t| t.as_secs()),
+        );
+        let pool = PoolConfig {
+            max_connections: env.positive("DB_MAX_POOL_SIZE", pool_defaults.max_connections),
+            acquire_timeout: env
+                .positive_secs("DB_ACQUIRE_TIMEOUT_SECS", pool_defaults.acquire_timeout),
+            statement_timeout: (statement_timeout > 0)
+                .then(|| Duration::from_secs(statement_timeout)),
+            connect_retry_period: Duration::from_secs(env.parse::<u64>(
+                "DB_CONNECT_RETRY_SECS",
+                pool_defaults.connect_retry_period.as_secs(),
+            )),
+            ..pool_defaults
+        };
+
+        let rate_defaults = RateLimitConfig::default();
+        let anon_var = if lookup("RATE_LIMIT_ANON_PER_MINUTE").is_some() {
+            "RATE_LIMIT_ANON_PER_MINUTE"
+        } else {
+            "RATE_LIMIT_READ_PER_MINUTE"
+        };
+        let rate_limit = RateLimitConfig {
+            anonymous_limit: env.positive(anon_var, rate_defaults.anonymous_limit),
+            auth_limit: env.positive("RATE_LIMIT_AUTH_PER_MINUTE", rate_defaults.auth_limit),
+            window: env.positive_secs("RATE_LIMIT_WINDOW_SECONDS", rate_defaults.window),
+        };
+
+        let rpc_defaults = RpcConfig::default();
+        let rpc = RpcConfig {
+            mainnet: env.http_url("SOROBAN_RPC_MAINNET", rpc_defaults.mainnet),
+            testnet: env.http_url("SOROBAN_RPC_TESTNET", rpc_defaults.testnet),
+            futurenet: env.http_url("SOROBAN_RPC_FUTURENET", rpc_defaults.futurenet),
+            timeout: env.positive_secs("SOROBAN_RPC_TIMEOUT_SECS", rpc_defaults.timeout),
+            max_retries: env.positive("SOROBAN_RPC_MAX_RETRIES", rpc_defaults.max_retries),
+        };
+
+        let job_defaults = JobsConfig::default();
+        let jobs = JobsConfig {
+            workers: env.positive("JOB_WORKERS", job_defaults.workers),
+            max_attempts: env.positive("JOB_MAX_ATTEMPTS", job_defaults.max_attempts),
+            base_backoff: env.positive_secs("JOB_BASE_BACKOFF_SECS", job_defaults.base_backoff),
+            lease: env.positive_secs("JOB_LEASE_SECS", job_defaults.lease),
+        };
+
+        let outbound_defaults = OutboundConfig::default();
+        let outbound = OutboundConfig {
+            connect_timeout: env.positive_secs(
+                "OUTBOUND_CONNECT_TIMEOUT_SECS",
+                outbound_defaults.connect_timeout,
+            ),
+            request_timeout: env.positive_secs(
+                "OUTBOUND_REQUEST_TIMEOUT_SECS",
+                outbound_defaults.request_timeout,
+            ),
+            max_concurrent_per_host: env.positive(
+                "OUTBOUND_MAX_CONCURRENT_PER_HOST",
+                outbound_defaults.max_concurrent_per_host,
+            ),
+            breaker_failures: env.positive(
+                "OUTBOUND_BREAKER_FAILURES",
+                outbound_defaults.breaker_failures,
+            ),
+            breaker_open: env
+                .positive_secs("OUTBOUND_BREAKER_OPEN_SECS", outbound_defaults.breaker_open),
+            allow_private_webhooks: env.flag(
+                "OUTBOUND_ALLOW_PRIVATE_WEBHOOKS",
+                outbound_defaults.allow_private_webhooks,
+            ),
+        };
+
+        let webhook_test_sends_per_minute = env.positive(
+            "WEBHOOK_TEST_SENDS_PER_MINUTE",
+            DEFAULT_WEBHOOK_TEST_SENDS_PER_MINUTE,
+        );
+
+        let flag_defaults = FeatureFlags::default();
+        let feature_defaults = flag_defaults
+            .all()
+            .into_iter()
+            .map(|flag| (flag.name(), env.flag(flag.env_var(), flag.view().default)))
+            .collect();
+        let setting_defaults = flag_defaults
+            .settings()
+            .into_iter()
+            .map(|setting| {
+                (
+                    setting.name(),
+                    env.positive(setting.env_var(), setting.view().default),
+                )
+            })
+            .collect();
+        let feature_refresh_interval =
+            env.positive_secs("FEATURE_FLAGS_REFRESH_SECS", flag_defaults.refresh_interval);
+
+        let logging_defaults = LoggingConfig::default();
+        let logging = LoggingConfig {
+            format: env.parse::<LogFormat>("LOG_FORMAT", logging_defaults.format),
+            sampled_targets: env
+                .get("LOG_SAMPLED_TARGETS")
+                .map(|raw| {
+                    raw.split(',')
+                        .map(str::trim)
+                        .filter(|target| !target.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or(logging_defaults.sampled_targets),
+            debug_sample_every: env.positive(
+                "LOG_DEBUG_SAMPLE_EVERY",
+                logging_defaults.debug_sample_every,
+            ),
+        };
+
+        let screening_defaults = ScreeningConfig::default();
+        let screening = ScreeningConfig {
+            enabled: env.flag("PUBLISH_SCREENING_ENABLED", screening_defaults.enabled),
+            name_similarity: env.fraction(
+                "PUBLISH_SCREENING_NAME_SIMILARITY",
+                screening_defaults.name_similarity,
+            ),
+            description_similarity: env.fraction(
+                "PUBLISH_SCREENING_DESCRIPTION_SIMILARITY",
+                screening_defaults.description_similarity,
+            ),
+            lookback_days: env.positive(
+                "PUBLISH_SCREENING_LOOKBACK_DAYS",
+                screening_defaults.lookback_days,
+            ),
+            new_publisher_days: env.parse(
+                "PUBLISH_SCREENING_NEW_PUBLISHER_DAYS",
+                screening_defaults.new_publisher_days,
+            ),
+            blocked_terms: env
+                .get("PUBLISH_SCREENING_BLOCKED_TERMS")
+                .map(|raw| publish_screening::parse_blocked_terms(&raw))
+                .unwrap_or(screening_defaults.blocked_terms),
+        };
+
+        let replay_defaults = ReplayConfig::default();
+        let replay = ReplayConfig {
+            max_age: env.positive_secs("SIGNATURE_MAX_AGE_SECS", replay_defaults.max_age),
+            max_clock_skew: env.positive_secs(
+                "SIGNATURE_MAX_CLOCK_SKEW_SECS",
+                replay_defaults.max_clock_skew,
+            ),
+            nonce_capacity: env
+                .positive("SIGNATURE_NONCE_CAPACITY", replay_defaults.nonce_capacity),
+            redis_url: env.get("SIGNATURE_NONCE_REDIS_URL").map(Secret),
+        };
+
+        let retention_defaults = RetentionConfig::default();
+        let run_hour = env.parse("RETENTION_RUN_HOUR", retention_defaults.run_hour);
+        let retention = RetentionConfig {
+            policies: RetentionTable::ALL
+                .into_iter()
+                .map(|table| (table, env.parse(table.env_var(), Retention::Forever)))
+                .collect(),
+            dry_run: env.flag("RETENTION_DRY_RUN", retention_defaults.dry_run),
+            run_hour: if run_hour < 24 {
+                run_hour
+            } else {
+                env.invalid("RETENTION_RUN_HOUR", "must be an hour from 0 to 23");
+                retention_defaults.run_hour
+            },
+            batch_size: env.positive("RETENTION_BATCH_SIZE", retention_defaults.batch_size),
+        };
+
+        let freshness_defaults = FreshnessConfig::default();
+        let freshness = FreshnessConfig {
+            sweep_enabled: env.flag("FRESHNESS_SWEEP_ENABLED", freshness_defaults.sweep_enabled),
+            version_days: env.positive("FRESHNESS_VERSION_DAYS", freshness_defaults.version_days),
+            onchain_days: env.positive("FRESHNESS_ONCHAIN_DAYS", freshness_defaults.onchain_days),
+            publisher_days: env.positive(
+                "FRESHNESS_PUBLISHER_DAYS",
+                freshness_defaults.publisher_days,
+            ),
+            warning_days: env.positive("FRESHNESS_WARNING_DAYS", freshness_defaults.warning_days),
+            sweep_interval: env.positive_secs(
+                "FRESHNESS_SWEEP_INTERVAL_SECS",
+                freshness_defaults.sweep_interval,
+            ),
+        };
+
+        let public_base_url = env
+            .get("PUBLIC_BASE_URL")
+            .unwrap_or_else(|| DEFAULT_PUBLIC_BASE_URL.to_string());
+        let status_page_url = env.http_url(
+            "STATUS_PAGE_URL",
+            format!("{}/api/status", public_base_url.trim_end_matches('/')),
+        );
+
+        match (database_url, jwt_secret) {
+            (Some(database_url), Some(jwt_secret)) if env.errors.is_empty() => Ok(Self {
+                database_url,
+                jwt_secret,
+                bind_addr: SocketAddr::new(host, port),
+                run_migrations,
+                pool,
+                rate_limit,
+                rpc,
+                jobs,
+                outbound,
+                webhook_test_sends_per_minute,
+                feature_defaults,
+                setting_defaults,
+                feature_refresh_interval,
+                logging,
+                screening,
+                replay,
+                retention,
+                freshness,
+                status_page_url,
+            }),
+            _ => Err(ConfigErrors(env.errors)),
+        }
+    }
+
+    /// Minimal valid configuration for unit tests
+    #[cfg(test)]
+    pub fn for_tests() -> Self {
+        Self::from_lookup(|key| match key {
+            "DATABASE_URL" => Some("postgres://localhost/test".to_string()),
+            "JWT_SECRET" => Some("a".repeat(32)),
+            _ => None,
+        })
+        .expect("test configuration is valid")
+    }
+
+    /// Logs the effective configuration once at startup; secrets are redacted.
+    pub fn log_summary(&self) {
+        tracing::info!(config = ?self, "Effective configuration");
+    }
+}
+
+/// Reads variables and collects every error instead of stopping at the first.
+struct Env<'a, F: Fn(&str) -> Option<String>> {
+    lookup: &'a F,
+    errors: Vec<ConfigError>,
+}
+
+impl<F: Fn(&str) -> Option<String>> Env<'_, F> {
+    fn invalid(&mut self, var: &'static str, message: impl Into<String>) {
+        self.errors.push(ConfigError {
+            var,
+            message: message.into(),
+        });
+    }
+
+    /// Unset and blank values are treated alike.
+    fn get(&self, var: &str) -> Option<String> {
+        (self.lookup)(var)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn required(&mut self, var: &'static str) -> Option<String> {
+        let value = self.get(var);
+        if value.is_none() {
+            self.invalid(var, "must be set");
+        }
+        value
+    }
+
+    fn parse<T: FromStr>(&mut self, var: &'static str, default: T) -> T {
+        let Some(raw) = self.get(var) else {
+            return default;
+        };
+        match raw.parse() {
+            Ok(value) => value,
+            Err(_) => {
+                self.invalid(var, format!("invalid value `{}`", raw));
+                default
+            }
+        }
+    }
+
+    fn positive<T>(&mut self, var: &'static str, default: T) -> T
+    where
+        T: FromStr + PartialOrd + Default + Copy,
+    {
+        let value = self.parse(var, default);
+        if value > T::default() {
+            value
+        } else {
+            self.invalid(var, "must be greater than zero");
+            default
+        }
+    }
+
+    fn positive_secs(&mut self, var: &'static str, default: Duration) -> Duration {
+        Duration::from_secs(self.positive(var, default.as_secs()))
+    }
+
+    /// A ratio in (0, 1]
+    fn fraction(&mut self, var: &'static str, default: f64) -> f64 {
+        let value = self.parse(var, default);
+        if value > 0.0 && value <= 1.0 {
+            value
+        } else {
+            self.invalid(var, "must be greater than 0 and at most 1");
+            default
+        }
+    }
+
+    fn flag(&mut self, var: &'static str, default: bool) -> bool {
+        let Some(raw) = self.get(var) else {
+            return default;
+        };
+        match raw.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => {
+                self.invalid(var, format!("expected true or false, got `{}`", raw));
+                default
+            }
+        }
+    }
+
+    fn http_url(&mut self, var: &'static str, default: String) -> String {
+        let Some(raw) = self.get(var) else {
+            return default;
+        };
+        match reqwest::Url::parse(&raw) {
+            Ok(url) if matches!(url.scheme(), "http" | "https") && url.has_host() => raw,
+            _ => {
+                self.invalid(var, format!("expected an http(s) URL, got `{}`", raw));
+                default
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const SECRET: &str = "0123456789abcdef0123456789abcdef";
+
+    fn load(vars: &[(&str, &str)]) -> Result<Config, ConfigErrors> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        Config::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn a_complete_environment_is_loaded() {
+        let config = load(&[
+            ("DATABASE_URL", "postgres://registry:hunter2@db/registry"),
+            ("JWT_SECRET", SECRET),
+            ("HOST", "127.0.0.1"),
+            ("PORT", "8080"),
+            ("RUN_MIGRATIONS", "true"),
+            ("DB_MAX_POOL_SIZE", "25"),
+            ("DB_STATEMENT_TIMEOUT_SECS", "30"),
+            ("RATE_LIMIT_READ_PER_MINUTE", "250"),
+            ("RATE_LIMIT_WINDOW_SECONDS", "120"),
+            ("SOROBAN_RPC_TESTNET", "http://localhost:8000/soroban/rpc"),
+            ("SOROBAN_RPC_MAX_RETRIES", "5"),
+            ("JOB_WORKERS", "4"),
+            ("OUTBOUND_MAX_CONCURRENT_PER_HOST", "2"),
+            ("WEBHOOK_TEST_SENDS_PER_MINUTE", "3"),
+            ("FEATURE_ONCHAIN_SYNC", "off"),
+            ("SLOW_QUERY_THRESHOLD_MS", "250"),
+            ("LOG_FORMAT", "pretty"),
+            ("LOG_SAMPLED_TARGETS", "sqlx, hyper"),
+            ("PUBLISH_SCREENING_NAME_SIMILARITY", "0.65"),
+            ("PUBLISH_SCREENING_BLOCKED_TERMS", "Airdrop, casino"),
+            ("SIGNATURE_MAX_AGE_SECS", "120"),
+            ("RETENTION_INCIDENTS", "365"),
+            ("RETENTION_SOURCE_ACCESS_LOGS", "forever"),
+            ("RETENTION_DRY_RUN", "true"),
+            ("FRESHNESS_ONCHAIN_DAYS", "30"),
+            ("STATUS_PAGE_URL", "https://status.example.com"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.bind_addr, "127.0.0.1:8080".parse().unwrap());
+        assert!(config.run_migrations);
+        assert_eq!(config.pool.max_connections, 25);
+        assert_eq!(config.pool.statement_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(config.rate_limit.anonymous_limit, 250);
+        assert_eq!(config.rate_limit.window, Duration::from_secs(120));
+        assert_eq!(config.rpc.testnet, "http://localhost:8000/soroban/rpc");
+        assert_eq!(config.rpc.mainnet, DEFAULT_RPC_MAINNET);
+        assert_eq!(config.rpc.max_retries, 5);
+        assert_eq!(config.jobs.workers, 4);
+        assert_eq!(config.jobs.max_attempts, DEFAULT_JOB_MAX_ATTEMPTS);
+        assert_eq!(config.outbound.max_concurrent_per_host, 2);
+        assert_eq!(config.webhook_test_sends_per_minute, 3);
+        assert!(!config.feature_defaults["onchain_sync"]);
+        assert!(config.feature_defaults["full_text_search"]);
+        assert_eq!(config.setting_defaults["slow_query_ms"], 250);
+        assert_eq!(config.setting_defaults["request_timeout_long_ms"], 300_000);
+        assert_eq!(config.logging.format, LogFormat::Pretty);
+        assert_eq!(config.logging.sampled_targets, vec!["sqlx", "hyper"]);
+        assert_eq!(config.screening.name_similarity, 0.65);
+        assert_eq!(config.screening.blocked_terms, vec!["airdrop", "casino"]);
+        assert_eq!(config.replay.max_age, Duration::from_secs(120));
+        assert_eq!(
+            config.retention.retention(RetentionTable::Incidents),
+            Retention::Days(365)
+        );
+        assert_eq!(
+            config.retention.retention(RetentionTable::SourceAccessLogs),
+            Retention::Forever
+        );
+        assert!(config.retention.dry_run);
+        assert_eq!(config.freshness.onchain_days, 30);
+        assert_eq!(
+            config.freshness.version_days,
+            DEFAULT_FRESHNESS_VERSION_DAYS
+        );
+        assert_eq!(config.status_page_url, "https://status.example.com");
+    }
+
+    #[test]
+    fn defaults_fill_everything_but_the_required_settings() {
+        let config = load(&[
+            ("DATABASE_URL", "postgresql://db/x"),
+            ("JWT_SECRET", SECRET),
+        ])
+        .unwrap();
+        assert_eq!(
+            config.bind_addr,
+            SocketAddr::from(([0, 0, 0, 0], DEFAULT_PORT))
+        );
+        assert!(!config.run_migrations);
+        assert_eq!(config.rpc, RpcConfig::default());
+        assert_eq!(config.jobs, JobsConfig::default());
+        assert_eq!(config.outbound, OutboundConfig::default());
+        assert_eq!(config.screening, ScreeningConfig::default());
+        assert_eq!(config.replay, ReplayConfig::default());
+        assert!(!config.retention.purges_anything());
+        assert_eq!(config.retention.run_hour, 3);
+        assert_eq!(config.freshness, FreshnessConfig::default());
+        assert_eq!(config.status_page_url, "http://localhost:3001/api/status");
+    }
+
+    #[test]
+    fn every_error_is_reported_together() {
+        let err = load(&[
+            ("JWT_SECRET", "short"),
+            ("PORT", "http"),
+            ("DB_MAX_POOL_SIZE", "0"),
+            ("SOROBAN_RPC_MAINNET", "mainnet.sorobanrpc.com"),
+            ("FEATURE_FULL_TEXT_SEARCH", "sometimes"),
+            ("RETENTION_INCIDENT_AUDIT_LOG", "90 days"),
+            ("RETENTION_RUN_HOUR", "24"),
+        ])
+        .unwrap_err();
+
+        assert_eq!(
+            err.vars(),
+            vec![
+                "DATABASE_URL",
+                "JWT_SECRET",
+                "PORT",
+                "DB_MAX_POOL_SIZE",
+                "SOROBAN_RPC_MAINNET",
+                "FEATURE_FULL_TEXT_SEARCH",
+                "RETENTION_INCIDENT_AUDIT_LOG",
+                "RETENTION_RUN_HOUR",
+            ]
+        );
+        assert!(err.to_string().starts_with("8 invalid setting(s)"));
+    }
+
+    #[test]
+    fn a_database_url_must_be_postgres() {
+        let err = load(&[("DATABASE_URL", "mysql://db/x"), ("JWT_SECRET", SECRET)]).unwrap_err();
+        assert_eq!(err.vars(), vec!["DATABASE_URL"]);
+    }
+
+    #[test]
+    fn one_bad_value_fails_an_otherwise_valid_environment() {
+        for (var, value) in [
+            ("HOST", "localhost"),
+            ("RATE_LIMIT_AUTH_PER_MINUTE", "-1"),
+            ("OUTBOUND_REQUEST_TIMEOUT_SECS", "0"),
+            ("WEBHOOK_TEST_SENDS_PER_MINUTE", "0"),
+            ("RUN_MIGRATIONS", "maybe"),
+            ("LOG_FORMAT", "text"),
+            ("LOG_DEBUG_SAMPLE_EVERY", "0"),
+            ("PUBLISH_SCREENING_NAME_SIMILARITY", "1.5"),
+            ("PUBLISH_SCREENING_LOOKBACK_DAYS", "0"),
+            ("FRESHNESS_WARNING_DAYS", "0"),
+        ] {
+            let err = load(&[
+                ("DATABASE_URL", "postgres://db/x"),
+                ("JWT_SECRET", SECRET),
+                (var, value),
+            ])
+            .unwrap_err();
+            assert_eq!(err.vars(), vec![var]);
+        }
+    }
+
+    #[test]
+    fn secrets_are_redacted_in_debug_output() {
+        let config = load(&[
+            ("DATABASE_URL", "postgres://registry:hunter2@db/registry"),
+            ("JWT_SECRET", SECRET),
+        ])
+        .unwrap();
+        let printed = format!("{:?}", config);
+
+        assert!(!printed.contains("hunter2"));
+        assert!(!printed.contains(SECRET));
+        assert!(printed.contains("[redacted]"));
+        assert_eq!(
+            config.database_url.expose(),
+            "postgres://registry:hunter2@db/registry"
+        );
+    }
+}
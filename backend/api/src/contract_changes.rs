@@ -0,0 +1,299 @@
+//! What changed in the registry between two points in time.
+//!
+//!   GET /api/contracts/changes?since=<ts>[&until=<ts>][&network=][&group=]
+//!
+//! Changes are grouped the way analysts read them:
+//!
+//! - `added`: listed contracts created in the window
+//! - `verified`: listed contracts whose verification landed in the window
+//! - `deprecated`: contracts deprecated in the window
+//! - `removed`: contracts retired in the window, or delisted by a rejected
+//!   publish review
+//! - `updated`: listed contracts, not added in the window, whose metadata,
+//!   versions or publisher changed, read from the audit log
+//!
+//! Without `group` the first page of every group is returned; with it only
+//! that group, at the requested page. Windows are capped at
+//! `CONTRACT_CHANGES_MAX_WINDOW_DAYS` (default 366) so every query stays on
+//! a bounded range of the timestamp indexes.
+
+use axum::{
+    extract::{rejection::QueryRejection, Query, State},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use shared::{pagination::Paginated, Network};
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::{db_internal_error, map_query_rejection},
+    pagination::{PageParams, Pagination},
+    state::AppState,
+};
+
+const DEFAULT_MAX_WINDOW_DAYS: i64 = 366;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeGroup {
+    Added,
+    Verified,
+    Deprecated,
+    Removed,
+    Updated,
+}
+
+impl ChangeGroup {
+    const ALL: [ChangeGroup; 5] = [
+        ChangeGroup::Added,
+        ChangeGroup::Verified,
+        ChangeGroup::Deprecated,
+        ChangeGroup::Removed,
+        ChangeGroup::Updated,
+    ];
+
+    /// Rows of `(id, changed_at, detail)` for changes in [$1, $2)
+    fn sql(self) -> &'static str {
+        match self {
+            ChangeGroup::Added => {
+                "SELECT c.id, c.created_at AS changed_at, NULL::TEXT AS detail
+                 FROM contracts c
+                 WHERE c.publish_status = 'published'
+                   AND c.created_at >= $1 AND c.created_at < $2"
+            }
+            ChangeGroup::Verified => {
+                "SELECT c.id, c.verified_at AS changed_at, NULL::TEXT AS detail
+                 FROM contracts c
+                 WHERE c.publish_status = 'published' AND c.is_verified
+                   AND c.verified_at >= $1 AND c.verified_at < $2"
+            }
+            ChangeGroup::Deprecated => {
+                "SELECT d.contract_id AS id, d.deprecated_at AS changed_at, NULL::TEXT AS detail
+                 FROM contract_deprecations d
+                 WHERE d.deprecated_at >= $1 AND d.deprecated_at < $2"
+            }
+            ChangeGroup::Removed => {
+                "SELECT d.contract_id AS id, d.retirement_at AS changed_at, 'retired' AS detail
+                 FROM contract_deprecations d
+                 WHERE d.retirement_at >= $1 AND d.retirement_at < LEAST($2, NOW())
+                 UNION ALL
+                 SELECT l.contract_id, MAX(l.timestamp), 'rejected'
+                 FROM contract_audit_log l
+                 JOIN contracts r ON r.id = l.contract_id
+                 WHERE l.action_type = 'publish_rejected' AND r.publish_status = 'rejected'
+                   AND l.timestamp >= $1 AND l.timestamp < $2
+                 GROUP BY l.contract_id"
+            }
+            ChangeGroup::Updated => {
+                "SELECT l.contract_id AS id, MAX(l.timestamp) AS changed_at,
+                        string_agg(DISTINCT l.action_type::TEXT, ', ' ORDER BY l.action_type::TEXT)
+                            AS detail
+                 FROM contract_audit_log l
+                 JOIN contracts u ON u.id = l.contract_id
+                 WHERE l.action_type IN ('metadata_updated', 'version_created', 'publisher_changed')
+                   AND l.timestamp >= $1 AND l.timestamp < $2
+                   AND u.publish_status = 'published'
+                   AND NOT (u.created_at >= $1 AND u.created_at < $2)
+                 GROUP BY l.contract_id"
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ContractChangesQuery {
+    /// Start of the window, inclusive (RFC 3339)
+    pub since: DateTime<Utc>,
+    /// End of the window, exclusive (RFC 3339, default now)
+    pub until: Option<DateTime<Utc>>,
+    pub network: Option<Network>,
+    /// Page through one group; the others are left out
+    pub group: Option<ChangeGroup>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct ContractChange {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub network: Network,
+    pub changed_at: DateTime<Utc>,
+    /// `retired` or `rejected` for removals; the audit actions for updates
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ContractChanges {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<Network>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added: Option<Paginated<ContractChange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<Paginated<ContractChange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<Paginated<ContractChange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub removed: Option<Paginated<ContractChange>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated: Option<Paginated<ContractChange>>,
+}
+
+fn max_window_days() -> i64 {
+    std::env::var("CONTRACT_CHANGES_MAX_WINDOW_DAYS")
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .filter(|days| *days > 0)
+        .unwrap_or(DEFAULT_MAX_WINDOW_DAYS)
+}
+
+fn check_window(since: DateTime<Utc>, until: DateTime<Utc>, max_days: i64) -> ApiResult<()> {
+    if since >= until {
+        return Err(ApiError::validation(
+            "since",
+            "`since` must be before `until`",
+        ));
+    }
+    if until - since > Duration::days(max_days) {
+        return Err(ApiError::validation(
+            "until",
+            format!("The window can span at most {} days", max_days),
+        )
+        .with_details(json!({ "max_window_days": max_days })));
+    }
+    Ok(())
+}
+
+async fn fetch_group(
+    state: &AppState,
+    group: ChangeGroup,
+    query: &ContractChangesQuery,
+    until: DateTime<Utc>,
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Paginated<ContractChange>> {
+    // Private contracts never show up, whatever group they changed in
+    const FILTER: &str = "c.visibility = 'public' AND ($3::network_type IS NULL OR c.network = $3)";
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM ({}) g JOIN contracts c ON c.id = g.id WHERE {}",
+        group.sql(),
+        FILTER
+    ))
+    .bind(query.since)
+    .bind(until)
+    .bind(&query.network)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count contract changes", err))?;
+
+    let items: Vec<ContractChange> = sqlx::query_as(&format!(
+        "SELECT c.id, c.contract_id, c.name, c.network, g.changed_at, g.detail
+         FROM ({}) g
+         JOIN contracts c ON c.id = g.id
+         WHERE {}
+         ORDER BY g.changed_at DESC, c.id
+         LIMIT $4 OFFSET $5",
+        group.sql(),
+        FILTER
+    ))
+    .bind(query.since)
+    .bind(until)
+    .bind(&query.network)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list contract changes", err))?;
+
+    Ok(Paginated::new(items, total, limit, offset))
+}
+
+/// Contracts added, verified, deprecated, removed and updated in a window,
+/// newest change first within each group.
+#[utoipa::path(
+    get,
+    path = "/api/contracts/changes",
+    params(ContractChangesQuery, PageParams),
+    responses(
+        (status = 200, description = "Changes in the window, by group", body = ContractChanges),
+        (status = 400, description = "Invalid window, or one longer than the maximum")
+    ),
+    tag = "Contracts"
+)]
+pub async fn get_contract_changes(
+    State(state): State<AppState>,
+    params: Result<Query<ContractChangesQuery>, QueryRejection>,
+    pagination: Pagination,
+) -> ApiResult<Json<ContractChanges>> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+    let until = query.until.unwrap_or_else(Utc::now);
+    check_window(query.since, until, max_window_days())?;
+
+    let mut changes = ContractChanges {
+        since: query.since,
+        until,
+        network: query.network.clone(),
+        added: None,
+        verified: None,
+        deprecated: None,
+        removed: None,
+        updated: None,
+    };
+    for group in ChangeGroup::ALL {
+        let page = match query.group {
+            Some(wanted) if wanted != group => continue,
+            Some(_) => pagination,
+            // Each group starts at its first page; `group` pages further
+            None => Pagination {
+                offset: 0,
+                ..pagination
+            },
+        };
+        let found = Some(fetch_group(&state, group, &query, until, page).await?);
+        match group {
+            ChangeGroup::Added => changes.added = found,
+            ChangeGroup::Verified => changes.verified = found,
+            ChangeGroup::Deprecated => changes.deprecated = found,
+            ChangeGroup::Removed => changes.removed = found,
+            ChangeGroup::Updated => changes.updated = found,
+        }
+    }
+
+    Ok(Json(changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use chrono::TimeZone;
+
+    #[test]
+    fn windows_are_bounded() {
+        let since = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        assert!(check_window(since, since + Duration::days(30), 366).is_ok());
+        assert!(check_window(since, since + Duration::days(366), 366).is_ok());
+
+        let err = check_window(since, since + Duration::days(367), 366).unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(err.body().error.details["max_window_days"], 366);
+
+        let err = check_window(since, since, 366).unwrap_err();
+        assert_eq!(err.body().error.details["field"], "since");
+    }
+
+    #[test]
+    fn groups_parse_from_the_query() {
+        let uri = "/api/contracts/changes?since=2024-06-01T00:00:00Z&network=testnet&group=removed"
+            .parse()
+            .unwrap();
+        let Query(query) = Query::<ContractChangesQuery>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.group, Some(ChangeGroup::Removed));
+        assert!(query.until.is_none());
+    }
+}
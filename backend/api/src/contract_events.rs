@@ -601,6 +601,16 @@ mod tests {
             is_maintenance: false,
             logical_id: None,
             network_configs: None,
+            verified_at: None,
+            last_accessed_at: None,
+            relevance_score: None,
+            organization_id: None,
+            visibility: shared::VisibilityType::Public,
+            namespace: None,
+            slug: None,
+            publish_status: shared::PublishStatus::Published,
+            revision: 0,
+            freshness: shared::Freshness::default(),
         }
     }
 
@@ -0,0 +1,195 @@
+//! Contract freshness and the sweep that marks quiet contracts stale.
+//!
+//! Every contract response carries `freshness` (see [`shared::Freshness`]):
+//! the days since its newest version, since on-chain verification last found
+//! it with its registered hash, and since its publisher last published,
+//! edited its metadata or re-confirmed it. A contract is quiet once all three
+//! are past their threshold: `FRESHNESS_VERSION_DAYS` (default 365),
+//! `FRESHNESS_ONCHAIN_DAYS` (default 90) and `FRESHNESS_PUBLISHER_DAYS`
+//! (default 365). Registration counts as the first version, and a contract
+//! never confirmed on chain is past the second threshold.
+//!
+//! Every `FRESHNESS_SWEEP_INTERVAL_SECS` (default 3600) the sweep queues a
+//! `freshness` event for the subscribers of contracts that will be quiet
+//! within `FRESHNESS_WARNING_DAYS` (default 14), and marks `stale_at` on
+//! those warned at least that long ago that are quiet now. Stale contracts
+//! are labeled in responses and ranked lower in search. A new version, a
+//! metadata edit or `POST /api/contracts/:id/freshness/confirm` clears the
+//! warning and the mark at once; contracts that freshen up otherwise, e.g.
+//! by an on-chain confirmation, are cleared by the next sweep.
+//! `FRESHNESS_SWEEP_ENABLED=false` turns the sweep off.
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::config::FreshnessConfig;
+use crate::shutdown::Shutdown;
+use crate::subscription_notifications::{self, ContractRef};
+
+/// Quiet on all three signals; `$1` to `$3` are the cutoffs of
+/// [`FreshnessConfig::cutoffs`]
+const QUIET: &str = "COALESCE(last_version_at, created_at) < $1 \
+     AND (last_seen_on_chain_at IS NULL OR last_seen_on_chain_at < $2) \
+     AND COALESCE(publisher_active_at, created_at) < $3";
+
+impl FreshnessConfig {
+    /// Version, on-chain and publisher timestamps before which a contract
+    /// is quiet at `at`
+    pub fn cutoffs(&self, at: DateTime<Utc>) -> [DateTime<Utc>; 3] {
+        let days = |days: u32| at - chrono::Duration::days(i64::from(days));
+        [
+            days(self.version_days),
+            days(self.onchain_days),
+            days(self.publisher_days),
+        ]
+    }
+
+    fn warning(&self) -> chrono::Duration {
+        chrono::Duration::days(i64::from(self.warning_days))
+    }
+}
+
+/// What one sweep changed
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SweepOutcome {
+    pub cleared: u64,
+    pub warned: u64,
+    pub marked: u64,
+}
+
+/// Records that on-chain verification found the contract with its hash
+pub async fn record_onchain_confirmation<'e>(
+    executor: impl PgExecutor<'e>,
+    contract_id: Uuid,
+) -> Result<(), sqlx::Error> {
+    sqlx::query("UPDATE contracts SET last_seen_on_chain_at = NOW() WHERE id = $1")
+        .bind(contract_id)
+        .execute(executor)
+        .await?;
+    Ok(())
+}
+
+/// Spawn the background task that warns about and marks stale contracts.
+pub fn spawn_freshness_sweep(pool: PgPool, config: FreshnessConfig, shutdown: &Shutdown) {
+    if !config.sweep_enabled {
+        tracing::info!("freshness: sweep disabled");
+        return;
+    }
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(config.sweep_interval);
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            match sweep(&pool, &config, Utc::now()).await {
+                Ok(outcome) if outcome != SweepOutcome::default() => {
+                    tracing::info!(?outcome, "freshness: sweep finished");
+                }
+                Ok(_) => {}
+                Err(err) => tracing::error!(error = ?err, "freshness: sweep failed"),
+            }
+        }
+    });
+}
+
+/// Clears contracts that are no longer going quiet, warns the subscribers
+/// of those that are about to and marks the ones whose warning has run out.
+pub async fn sweep(
+    pool: &PgPool,
+    config: &FreshnessConfig,
+    now: DateTime<Utc>,
+) -> Result<SweepOutcome, sqlx::Error> {
+    let [version, onchain, publisher] = config.cutoffs(now + config.warning());
+
+    let cleared = sqlx::query(&format!(
+        "UPDATE contracts SET stale_warned_at = NULL, stale_at = NULL \
+         WHERE (stale_warned_at IS NOT NULL OR stale_at IS NOT NULL) AND NOT ({})",
+        QUIET
+    ))
+    .bind(version)
+    .bind(onchain)
+    .bind(publisher)
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    let mut tx = pool.begin().await?;
+    let warned: Vec<ContractRef> = sqlx::query_as(&format!(
+        "UPDATE contracts SET stale_warned_at = $4 \
+         WHERE stale_warned_at IS NULL AND stale_at IS NULL AND {} \
+         RETURNING id, contract_id, name, network::TEXT AS network",
+        QUIET
+    ))
+    .bind(version)
+    .bind(onchain)
+    .bind(publisher)
+    .bind(now)
+    .fetch_all(&mut *tx)
+    .await?;
+    for contract in &warned {
+        let notice =
+            subscription_notifications::freshness_notice(contract, now, now + config.warning());
+        subscription_notifications::enqueue(&mut *tx, contract.id, &notice).await?;
+    }
+    tx.commit().await?;
+
+    let [version, onchain, publisher] = config.cutoffs(now);
+    let marked = sqlx::query(&format!(
+        "UPDATE contracts SET stale_at = NOW() \
+         WHERE stale_at IS NULL AND stale_warned_at <= $4 AND {}",
+        QUIET
+    ))
+    .bind(version)
+    .bind(onchain)
+    .bind(publisher)
+    .bind(now - config.warning())
+    .execute(pool)
+    .await?
+    .rows_affected();
+
+    Ok(SweepOutcome {
+        cleared,
+        warned: warned.len() as u64,
+        marked,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn cutoffs_follow_each_threshold() {
+        let config = FreshnessConfig {
+            version_days: 30,
+            onchain_days: 7,
+            publisher_days: 60,
+            ..FreshnessConfig::default()
+        };
+        let now = Utc.with_ymd_and_hms(2026, 10, 16, 12, 0, 0).unwrap();
+        assert_eq!(
+            config.cutoffs(now),
+            [
+                Utc.with_ymd_and_hms(2026, 9, 16, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 10, 9, 12, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2026, 8, 17, 12, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn warnings_go_out_before_the_contract_is_quiet() {
+        let config = FreshnessConfig::default();
+        let now = Utc::now();
+        let warn = config.cutoffs(now + config.warning());
+        let mark = config.cutoffs(now);
+        for (warn, mark) in warn.iter().zip(mark) {
+            assert_eq!(*warn - mark, chrono::Duration::days(14));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Manual re-confirmation of a contract, see [`crate::contract_freshness`].
+//!
+//!   POST /api/contracts/:id/freshness/confirm    – the publisher vouches for the contract
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use shared::Freshness;
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Counts as publisher activity: clears the contract's stale warning and
+/// mark. Only its publisher, members of its organization and admins may
+/// confirm it.
+#[utoipa::path(
+    post,
+    path = "/api/contracts/{id}/freshness/confirm",
+    params(("id" = Uuid, Path, description = "Contract UUID")),
+    responses(
+        (status = 200, description = "Contract confirmed; its freshness now", body = shared::FreshnessView),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Caller does not own the contract"),
+        (status = 404, description = "Contract not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Contracts"
+)]
+pub async fn confirm_contract(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Path(id): Path<Uuid>,
+) -> ApiResult<Json<Freshness>> {
+    let freshness: Option<Freshness> = sqlx::query_as(
+        "UPDATE contracts c
+            SET publisher_active_at = NOW(), stale_warned_at = NULL, stale_at = NULL
+          WHERE c.id = $1
+            AND ($3
+                 OR c.publisher_id IN (SELECT id FROM publishers WHERE stellar_address = $2)
+                 OR c.organization_id IN (
+                     SELECT om.organization_id FROM organization_members om
+                     JOIN publishers mp ON mp.id = om.publisher_id
+                     WHERE mp.stellar_address = $2))
+          RETURNING last_version_at, last_seen_on_chain_at, publisher_active_at, stale_at",
+    )
+    .bind(id)
+    .bind(&claims.sub)
+    .bind(auth::is_admin(&claims))
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("confirm contract", err))?;
+
+    let Some(freshness) = freshness else {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM contracts WHERE id = $1)")
+                .bind(id)
+                .fetch_one(&state.db)
+                .await
+                .map_err(|err| db_internal_error("check contract exists", err))?;
+        return Err(if exists {
+            ApiError::forbidden(
+                "Only the contract's publisher or its organization's members can confirm it",
+            )
+        } else {
+            ApiError::not_found(
+                "ContractNotFound",
+                format!("No contract found with ID: {}", id),
+            )
+        });
+    };
+
+    state.cache.invalidate_contract(id).await;
+    Ok(Json(freshness))
+}
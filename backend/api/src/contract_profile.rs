@@ -0,0 +1,198 @@
+//! Official performance profiles of verified contract versions.
+//!
+//! Once a verification build matches the deployed contract, every exported
+//! method of the built WASM is costed by the profiler's budget engine and
+//! the resulting profile is stored against the version with that hash.
+//! Verifying the version again replaces it in one statement, so readers
+//! never see a half-written profile.
+//!
+//!   GET /api/contracts/:id/versions/:version/profile    – the stored profile
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use shared::{ContractProfileSummary, MethodCost};
+use soroban_profiler::{estimate_wasm_methods, BudgetModel, MethodBudget, ProfileData};
+use sqlx::{types::Json as SqlJson, PgPool};
+use uuid::Uuid;
+
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::state::AppState;
+
+/// Engine recorded with profiles estimated by [`estimate_wasm_methods`]
+const BUDGET_ENGINE: &str = "budgeted";
+
+/// `GET /api/contracts/:id/versions/:version/profile`
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct VersionProfile {
+    pub version: String,
+    /// The verification whose build was profiled
+    pub verification_id: Uuid,
+    pub engine: String,
+    #[schema(value_type = Vec<MethodCost>)]
+    pub methods: SqlJson<Vec<MethodCost>>,
+    /// The full profile, loadable by `soroban-registry profile --compare`
+    #[schema(value_type = Object)]
+    pub profile: serde_json::Value,
+    pub profiled_at: DateTime<Utc>,
+}
+
+/// Profiles the WASM a verification built and stores it against the
+/// contract's version with that hash, replacing any earlier profile of the
+/// version. `None` when no version has the hash or the module cannot be
+/// profiled; neither fails the verification.
+pub(crate) async fn record_verified_profile(
+    db: &PgPool,
+    contract_id: Uuid,
+    contract_address: &str,
+    verification_id: Uuid,
+    wasm_hash: &str,
+    wasm: Vec<u8>,
+) -> Result<Option<ContractProfileSummary>, sqlx::Error> {
+    let version: Option<String> = sqlx::query_scalar(
+        "SELECT version FROM contract_versions
+         WHERE contract_id = $1 AND wasm_hash = $2
+         ORDER BY created_at DESC
+         LIMIT 1",
+    )
+    .bind(contract_id)
+    .bind(wasm_hash)
+    .fetch_optional(db)
+    .await?;
+    let Some(version) = version else {
+        return Ok(None);
+    };
+
+    let estimated =
+        tokio::task::spawn_blocking(move || estimate_wasm_methods(&wasm, &BudgetModel::default()))
+            .await;
+    let methods = match estimated {
+        Ok(Ok(methods)) => methods,
+        Ok(Err(err)) => {
+            tracing::warn!(%contract_id, %version, error = %err, "could not profile verified WASM");
+            return Ok(None);
+        }
+        Err(err) => {
+            tracing::warn!(%contract_id, %version, error = %err, "profiling task failed");
+            return Ok(None);
+        }
+    };
+    let profile = ProfileData::from_budgets(contract_address, &methods);
+    let profile = match serde_json::to_value(&profile) {
+        Ok(profile) => profile,
+        Err(err) => {
+            tracing::warn!(%contract_id, %version, error = %err, "could not serialize profile");
+            return Ok(None);
+        }
+    };
+    let costs: Vec<MethodCost> = methods.iter().map(method_cost).collect();
+
+    let profiled_at: DateTime<Utc> = sqlx::query_scalar(
+        "INSERT INTO contract_version_profiles
+             (contract_id, version, verification_id, engine, profile, methods)
+         VALUES ($1, $2, $3, $4, $5, $6)
+         ON CONFLICT (contract_id, version) DO UPDATE
+            SET verification_id = EXCLUDED.verification_id,
+                engine = EXCLUDED.engine,
+                profile = EXCLUDED.profile,
+                methods = EXCLUDED.methods,
+                profiled_at = NOW()
+         RETURNING profiled_at",
+    )
+    .bind(contract_id)
+    .bind(&version)
+    .bind(verification_id)
+    .bind(BUDGET_ENGINE)
+    .bind(&profile)
+    .bind(SqlJson(&costs))
+    .fetch_one(db)
+    .await?;
+
+    Ok(Some(ContractProfileSummary {
+        version,
+        engine: BUDGET_ENGINE.to_string(),
+        methods: costs,
+        profiled_at,
+    }))
+}
+
+fn method_cost(budget: &MethodBudget) -> MethodCost {
+    MethodCost {
+        method: budget.method.clone(),
+        cpu_instructions: budget.resources.cpu_instructions.unwrap_or(0),
+        memory_bytes: budget.resources.memory_bytes.unwrap_or(0),
+    }
+}
+
+/// Summary of the profile of the contract's newest profiled version, for
+/// the contract detail
+pub(crate) async fn contract_profile_summary(
+    pool: &PgPool,
+    contract_id: Uuid,
+) -> Result<Option<ContractProfileSummary>, sqlx::Error> {
+    let row: Option<(String, String, SqlJson<Vec<MethodCost>>, DateTime<Utc>)> = sqlx::query_as(
+        "SELECT p.version, p.engine, p.methods, p.profiled_at
+         FROM contract_version_profiles p
+         JOIN contract_versions cv
+           ON cv.contract_id = p.contract_id AND cv.version = p.version
+         WHERE p.contract_id = $1
+         ORDER BY cv.created_at DESC
+         LIMIT 1",
+    )
+    .bind(contract_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(
+        |(version, engine, methods, profiled_at)| ContractProfileSummary {
+            version,
+            engine,
+            methods: methods.0,
+            profiled_at,
+        },
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/versions/{version}/profile",
+    params(
+        ("id" = String, Path, description = "Contract UUID or address"),
+        ("version" = String, Path, description = "Contract version")
+    ),
+    responses(
+        (status = 200, description = "The version's official performance profile", body = VersionProfile),
+        (status = 404, description = "Contract not found, or the version has not been profiled")
+    ),
+    tag = "Versions"
+)]
+pub async fn get_version_profile(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(String, String)>,
+) -> ApiResult<Json<VersionProfile>> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, &id).await?;
+    let profile: Option<VersionProfile> = sqlx::query_as(
+        "SELECT version, verification_id, engine, methods, profile, profiled_at
+         FROM contract_version_profiles
+         WHERE contract_id = $1 AND version = $2",
+    )
+    .bind(contract_uuid)
+    .bind(&version)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch version profile", err))?;
+
+    profile.map(Json).ok_or_else(|| {
+        ApiError::not_found(
+            "ProfileNotFound",
+            format!(
+                "Version {} has no profile; verify it to record one",
+                version
+            ),
+        )
+    })
+}
@@ -0,0 +1,159 @@
+//! Optimistic concurrency for contract edits.
+//!
+//! `contracts.revision` counts the edits made through the API: metadata
+//! changes and WASM uploads. `GET /api/contracts/:id` and every edit return
+//! it as a strong `ETag`. An edit sent with `If-Match` applies only while the
+//! contract is still at that revision and otherwise fails with 412, carrying
+//! the current record so the client can show what changed and try again.
+//! Edits without `If-Match` apply unconditionally, as they always have.
+
+use axum::{
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    Json,
+};
+use serde_json::json;
+use shared::Contract;
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+};
+
+/// A response body with the contract revision as its `ETag`
+pub type Tagged<T> = ([(HeaderName, HeaderValue); 1], Json<T>);
+
+pub fn etag(revision: i64) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", revision)).expect("digits are a valid header value")
+}
+
+pub fn tagged<T>(revision: i64, body: T) -> Tagged<T> {
+    ([(header::ETAG, etag(revision))], Json(body))
+}
+
+/// Revisions the client's `If-Match` accepts; `None` when it sent none or
+/// `*`, which any revision satisfies. Weak tags are compared like strong
+/// ones since revisions only ever identify one exact state.
+pub fn expected_revisions(headers: &HeaderMap) -> ApiResult<Option<Vec<i64>>> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        ApiError::bad_request(
+            "InvalidIfMatch",
+            "If-Match must list the contract's ETags, e.g. \"3\", or be *",
+        )
+    };
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    let revisions = value
+        .split(',')
+        .map(|tag| {
+            let tag = tag.trim();
+            let tag = tag.strip_prefix("W/").unwrap_or(tag);
+            tag.strip_prefix('"')?.strip_suffix('"')?.parse().ok()
+        })
+        .collect::<Option<Vec<i64>>>()
+        .ok_or_else(invalid)?;
+    Ok(Some(revisions))
+}
+
+/// Fails with 412 unless the contract's revision satisfies `expected`
+pub fn check(expected: Option<&[i64]>, current: &Contract) -> ApiResult<()> {
+    match expected {
+        Some(expected) if !expected.contains(&current.revision) => Err(stale(current)),
+        _ => Ok(()),
+    }
+}
+
+/// Claims the next revision of a contract for an edit that doesn't rewrite
+/// the `contracts` row itself, such as a WASM upload. Returns the new
+/// revision, or `None` when the contract moved past `expected`.
+pub async fn bump<'e>(
+    executor: impl PgExecutor<'e>,
+    contract_id: Uuid,
+    expected: Option<&[i64]>,
+) -> Result<Option<i64>, sqlx::Error> {
+    sqlx::query_scalar(
+        "UPDATE contracts SET revision = revision + 1, updated_at = NOW()
+         WHERE id = $1 AND ($2::BIGINT[] IS NULL OR revision = ANY($2))
+         RETURNING revision",
+    )
+    .bind(contract_id)
+    .bind(expected)
+    .fetch_optional(executor)
+    .await
+}
+
+/// The 412 for an edit that lost the race, with the contract as it is now
+pub async fn precondition_failed(pool: &sqlx::PgPool, contract_id: Uuid) -> ApiError {
+    let current: Option<Contract> = match sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
+        .bind(contract_id)
+        .fetch_optional(pool)
+        .await
+    {
+        Ok(current) => current,
+        Err(err) => return db_internal_error("fetch contract after failed precondition", err),
+    };
+    match current {
+        Some(current) => stale(&current),
+        None => ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found with ID: {}", contract_id),
+        ),
+    }
+}
+
+fn stale(current: &Contract) -> ApiError {
+    ApiError::precondition_failed(
+        "RevisionMismatch",
+        format!(
+            "The contract was changed since you read it; it is now at revision {}",
+            current.revision
+        ),
+    )
+    .with_details(json!({ "revision": current.revision, "current": current }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn if_match(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn if_match_lists_revisions() {
+        assert_eq!(expected_revisions(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(expected_revisions(&if_match("*")).unwrap(), None);
+        assert_eq!(
+            expected_revisions(&if_match("\"3\"")).unwrap(),
+            Some(vec![3])
+        );
+        assert_eq!(
+            expected_revisions(&if_match("W/\"3\", \"4\"")).unwrap(),
+            Some(vec![3, 4])
+        );
+        assert_eq!(etag(7), "\"7\"");
+    }
+
+    #[test]
+    fn malformed_if_match_is_rejected() {
+        for value in ["3", "\"three\"", "\"3\",", "\"3"] {
+            let err = expected_revisions(&if_match(value)).unwrap_err();
+            assert_eq!(
+                err.status(),
+                axum::http::StatusCode::BAD_REQUEST,
+                "{}",
+                value
+            );
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! The interface Soroban contracts embed in their WASM.
+//!
+//! The SDK writes a `contractspecv0` custom section holding a stream of XDR
+//! `ScSpecEntry` values, one per exported function and user-defined type.
+//! Only the functions are read: user-defined types they take or return are
+//! referred to by name, so a change inside a struct or enum is not seen.
+
+use std::io::{Cursor, Read, Seek};
+
+use stellar_xdr::curr::{Limited, Limits, ReadXdr, ScSpecEntry, ScSpecFunctionV0, ScSpecTypeDef};
+
+use crate::type_safety::types::{
+    ContractABI, ContractFunction, FunctionParam, FunctionVisibility, SorobanType,
+};
+use crate::wasm_metadata::read_custom_sections;
+
+const SPEC_SECTION: &str = "contractspecv0";
+/// Specs grow with the interface, but even large contracts stay well below
+/// this; anything larger is skipped
+const MAX_SPEC_SECTION_BYTES: u64 = 1024 * 1024;
+
+/// Interface of an in-memory WASM module.
+pub fn extract(wasm: &[u8], contract_name: &str) -> Option<ContractABI> {
+    read(Cursor::new(wasm), contract_name)
+}
+
+/// Interface of the WASM module in `reader`, or `None` when it exports no
+/// functions. A malformed spec keeps the functions read before the damage.
+pub fn read<R: Read + Seek>(mut reader: R, contract_name: &str) -> Option<ContractABI> {
+    let mut data = Vec::new();
+    let _ = read_custom_sections(&mut reader, SPEC_SECTION, MAX_SPEC_SECTION_BYTES, &mut data);
+
+    let mut abi = ContractABI::new(contract_name.to_string());
+    let mut limited = Limited::new(Cursor::new(&data[..]), Limits::len(data.len()));
+    for entry in ScSpecEntry::read_xdr_iter(&mut limited) {
+        match entry {
+            Ok(ScSpecEntry::FunctionV0(function)) => abi.functions.push(function_of(&function)),
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+    (!abi.functions.is_empty()).then_some(abi)
+}
+
+fn function_of(function: &ScSpecFunctionV0) -> ContractFunction {
+    let doc = function.doc.to_utf8_string_lossy();
+    ContractFunction {
+        name: function.name.0.to_utf8_string_lossy(),
+        visibility: FunctionVisibility::Public,
+        params: function
+            .inputs
+            .iter()
+            .map(|input| FunctionParam {
+                name: input.name.to_utf8_string_lossy(),
+                param_type: type_of(&input.type_),
+                doc: None,
+            })
+            .collect(),
+        return_type: function
+            .outputs
+            .first()
+            .map(type_of)
+            .unwrap_or(SorobanType::Void),
+        doc: (!doc.trim().is_empty()).then_some(doc),
+        // The spec doesn't say, and interface diffs don't look at it
+        is_mutable: true,
+    }
+}
+
+fn type_of(def: &ScSpecTypeDef) -> SorobanType {
+    match def {
+        ScSpecTypeDef::Bool => SorobanType::Bool,
+        ScSpecTypeDef::Void => SorobanType::Void,
+        ScSpecTypeDef::U32 => SorobanType::U32,
+        ScSpecTypeDef::I32 => SorobanType::I32,
+        ScSpecTypeDef::U64 => SorobanType::U64,
+        ScSpecTypeDef::I64 => SorobanType::I64,
+        ScSpecTypeDef::Timepoint => SorobanType::Timepoint,
+        ScSpecTypeDef::Duration => SorobanType::Duration,
+        ScSpecTypeDef::U128 => SorobanType::U128,
+        ScSpecTypeDef::I128 => SorobanType::I128,
+        ScSpecTypeDef::U256 => SorobanType::U256,
+        ScSpecTypeDef::I256 => SorobanType::I256,
+        ScSpecTypeDef::Bytes => SorobanType::Bytes,
+        ScSpecTypeDef::String => SorobanType::String,
+        ScSpecTypeDef::Symbol => SorobanType::Symbol,
+        ScSpecTypeDef::Address => SorobanType::Address,
+        ScSpecTypeDef::Option(option) => SorobanType::Option {
+            value_type: Box::new(type_of(&option.value_type)),
+        },
+        ScSpecTypeDef::Result(result) => SorobanType::Result {
+            ok_type: Box::new(type_of(&result.ok_type)),
+            err_type: Box::new(type_of(&result.error_type)),
+        },
+        ScSpecTypeDef::Vec(vec) => SorobanType::Vec {
+            element_type: Box::new(type_of(&vec.element_type)),
+        },
+        ScSpecTypeDef::Map(map) => SorobanType::Map {
+            key_type: Box::new(type_of(&map.key_type)),
+            value_type: Box::new(type_of(&map.value_type)),
+        },
+        ScSpecTypeDef::Tuple(tuple) => SorobanType::Tuple {
+            elements: tuple.value_types.iter().map(type_of).collect(),
+        },
+        ScSpecTypeDef::BytesN(bytes) => SorobanType::BytesN { n: bytes.n },
+        ScSpecTypeDef::Udt(udt) => SorobanType::Custom {
+            name: udt.name.to_utf8_string_lossy(),
+        },
+        // `Val`, `Error` and `MuxedAddress` have no counterpart of their own
+        other => SorobanType::Custom {
+            name: other.name().to_string(),
+        },
+    }
+}
+
+/// Hand-built `contractspecv0` sections for tests
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use stellar_xdr::curr::{
+        Limits, ScSpecEntry, ScSpecFunctionInputV0, ScSpecFunctionV0, ScSpecTypeDef, ScSymbol,
+        WriteXdr,
+    };
+
+    use crate::wasm_metadata::fixtures::{custom_section, module};
+
+    /// An exported function: its name, named inputs and optional output
+    pub(crate) type Function<'a> = (
+        &'a str,
+        &'a [(&'a str, ScSpecTypeDef)],
+        Option<ScSpecTypeDef>,
+    );
+
+    /// A WASM module whose spec exports `functions`
+    pub(crate) fn contract(functions: &[Function]) -> Vec<u8> {
+        let spec: Vec<u8> = functions
+            .iter()
+            .flat_map(|(name, inputs, output)| {
+                ScSpecEntry::FunctionV0(ScSpecFunctionV0 {
+                    doc: String::new().try_into().unwrap(),
+                    name: ScSymbol(name.to_string().try_into().unwrap()),
+                    inputs: inputs
+                        .iter()
+                        .map(|(name, type_)| ScSpecFunctionInputV0 {
+                            doc: String::new().try_into().unwrap(),
+                            name: name.to_string().try_into().unwrap(),
+                            type_: type_.clone(),
+                        })
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap(),
+                    outputs: output
+                        .iter()
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap(),
+                })
+                .to_xdr(Limits::none())
+                .unwrap()
+            })
+            .collect();
+        module(&[custom_section(super::SPEC_SECTION, &spec)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stellar_xdr::curr::{ScSpecTypeOption, ScSpecTypeUdt};
+
+    use super::fixtures::contract;
+    use super::*;
+
+    #[test]
+    fn reads_exported_functions() {
+        let wasm = contract(&[
+            (
+                "transfer",
+                &[
+                    ("from", ScSpecTypeDef::Address),
+                    ("to", ScSpecTypeDef::Address),
+                    ("amount", ScSpecTypeDef::I128),
+                ],
+                None,
+            ),
+            (
+                "config",
+                &[],
+                Some(ScSpecTypeDef::Option(Box::new(ScSpecTypeOption {
+                    value_type: Box::new(ScSpecTypeDef::Udt(ScSpecTypeUdt {
+                        name: "Config".to_string().try_into().unwrap(),
+                    })),
+                }))),
+            ),
+        ]);
+
+        let abi = extract(&wasm, "token").expect("interface");
+        assert_eq!(abi.functions.len(), 2);
+        let transfer = abi.find_function("transfer").unwrap();
+        assert_eq!(transfer.params.len(), 3);
+        assert_eq!(transfer.params[2].name, "amount");
+        assert_eq!(transfer.params[2].param_type, SorobanType::I128);
+        assert_eq!(transfer.return_type, SorobanType::Void);
+        assert_eq!(
+            abi.find_function("config").unwrap().return_type,
+            SorobanType::Option {
+                value_type: Box::new(SorobanType::Custom {
+                    name: "Config".to_string()
+                })
+            }
+        );
+    }
+
+    #[test]
+    fn modules_without_a_spec_have_no_interface() {
+        assert!(extract(&contract(&[]), "token").is_none());
+        assert!(extract(b"not a wasm module", "token").is_none());
+    }
+}
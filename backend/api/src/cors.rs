@@ -0,0 +1,280 @@
+//! CORS policy for browser clients.
+//!
+//! Configured from the environment:
+//!
+//! | Variable                 | Default                                                     |
+//! |--------------------------|-------------------------------------------------------------|
+//! | `ALLOWED_ORIGINS`        | `http://localhost:3000,https://soroban-registry.vercel.app` |
+//! | `CORS_ALLOWED_METHODS`   | `GET,HEAD,POST,PUT,PATCH,DELETE`                            |
+//! | `CORS_MAX_AGE_SECS`      | 3600                                                        |
+//! | `CORS_ALLOW_CREDENTIALS` | `false`                                                     |
+//!
+//! `ALLOWED_ORIGINS=*` allows any origin and is meant for local development.
+//! Browsers refuse credentialed responses to a wildcard origin, so combining
+//! it with `CORS_ALLOW_CREDENTIALS=true` is rejected at startup instead of
+//! silently producing a policy that either fails or is too permissive.
+
+use std::fmt;
+use std::time::Duration;
+
+use axum::http::{header, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+const DEFAULT_ALLOWED_ORIGINS: &str = "http://localhost:3000,https://soroban-registry.vercel.app";
+const DEFAULT_ALLOWED_METHODS: &str = "GET,HEAD,POST,PUT,PATCH,DELETE";
+const DEFAULT_MAX_AGE_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorsConfigError {
+    CredentialsWithWildcard,
+    WildcardWithOrigins,
+    InvalidOrigin(String),
+    InvalidMethod(String),
+    InvalidValue { var: &'static str, value: String },
+}
+
+impl fmt::Display for CorsConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CorsConfigError::CredentialsWithWildcard => write!(
+                f,
+                "CORS_ALLOW_CREDENTIALS=true requires explicit ALLOWED_ORIGINS, not `*`"
+            ),
+            CorsConfigError::WildcardWithOrigins => {
+                write!(f, "ALLOWED_ORIGINS cannot mix `*` with explicit origins")
+            }
+            CorsConfigError::InvalidOrigin(origin) => write!(
+                f,
+                "invalid origin `{}` in ALLOWED_ORIGINS; expected scheme://host[:port] with no path",
+                origin
+            ),
+            CorsConfigError::InvalidMethod(method) => {
+                write!(f, "invalid HTTP method `{}` in CORS_ALLOWED_METHODS", method)
+            }
+            CorsConfigError::InvalidValue { var, value } => {
+                write!(f, "invalid value `{}` for {}", value, var)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CorsConfigError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AllowedOrigins {
+    Any,
+    List(Vec<HeaderValue>),
+}
+
+#[derive(Debug, Clone)]
+pub struct CorsConfig {
+    origins: AllowedOrigins,
+    methods: Vec<Method>,
+    max_age: Duration,
+    allow_credentials: bool,
+}
+
+impl CorsConfig {
+    pub fn from_env() -> Result<Self, CorsConfigError> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Builds the policy from `lookup`, which stands in for the environment.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, CorsConfigError> {
+        let origins = parse_origins(
+            &lookup("ALLOWED_ORIGINS").unwrap_or_else(|| DEFAULT_ALLOWED_ORIGINS.to_string()),
+        )?;
+        let methods = parse_methods(
+            &lookup("CORS_ALLOWED_METHODS").unwrap_or_else(|| DEFAULT_ALLOWED_METHODS.to_string()),
+        )?;
+        let max_age = match lookup("CORS_MAX_AGE_SECS") {
+            Some(raw) => raw
+                .trim()
+                .parse()
+                .map_err(|_| CorsConfigError::InvalidValue {
+                    var: "CORS_MAX_AGE_SECS",
+                    value: raw,
+                })?,
+            None => DEFAULT_MAX_AGE_SECS,
+        };
+        let allow_credentials = match lookup("CORS_ALLOW_CREDENTIALS") {
+            Some(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+                "true" | "1" => true,
+                "false" | "0" | "" => false,
+                _ => {
+                    return Err(CorsConfigError::InvalidValue {
+                        var: "CORS_ALLOW_CREDENTIALS",
+                        value: raw,
+                    })
+                }
+            },
+            None => false,
+        };
+
+        if allow_credentials && origins == AllowedOrigins::Any {
+            return Err(CorsConfigError::CredentialsWithWildcard);
+        }
+
+        Ok(Self {
+            origins,
+            methods,
+            max_age: Duration::from_secs(max_age),
+            allow_credentials,
+        })
+    }
+
+    pub fn layer(&self) -> CorsLayer {
+        let allow_origin = match &self.origins {
+            AllowedOrigins::Any => AllowOrigin::any(),
+            AllowedOrigins::List(origins) => AllowOrigin::list(origins.iter().cloned()),
+        };
+
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(self.methods.clone())
+            .allow_headers([
+                header::CONTENT_TYPE,
+                header::AUTHORIZATION,
+                crate::request_tracing::X_REQUEST_ID.clone(),
+                crate::request_tracing::X_CORRELATION_ID.clone(),
+            ])
+            .expose_headers([
+                header::RETRY_AFTER,
+                crate::request_tracing::X_REQUEST_ID.clone(),
+                crate::request_tracing::X_CORRELATION_ID.clone(),
+            ])
+            .allow_credentials(self.allow_credentials)
+            .max_age(self.max_age)
+    }
+
+    /// Logs the effective policy once at startup.
+    pub fn log_summary(&self) {
+        let origins = match &self.origins {
+            AllowedOrigins::Any => "*".to_string(),
+            AllowedOrigins::List(origins) => origins
+                .iter()
+                .filter_map(|o| o.to_str().ok())
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+        if self.origins == AllowedOrigins::Any {
+            tracing::warn!("CORS allows any origin; restrict ALLOWED_ORIGINS in production");
+        }
+        tracing::info!(
+            origins = %origins,
+            methods = ?self.methods,
+            max_age_secs = self.max_age.as_secs(),
+            allow_credentials = self.allow_credentials,
+            "CORS configuration"
+        );
+    }
+}
+
+fn parse_origins(raw: &str) -> Result<AllowedOrigins, CorsConfigError> {
+    let entries: Vec<&str> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if entries.contains(&"*") {
+        return if entries.len() == 1 {
+            Ok(AllowedOrigins::Any)
+        } else {
+            Err(CorsConfigError::WildcardWithOrigins)
+        };
+    }
+
+    entries
+        .into_iter()
+        .map(|origin| {
+            let (scheme, rest) = origin
+                .split_once("://")
+                .ok_or_else(|| CorsConfigError::InvalidOrigin(origin.to_string()))?;
+            // Browsers send `Origin` without a path or trailing slash, so an
+            // entry with one would never match.
+            if !matches!(scheme, "http" | "https") || rest.is_empty() || rest.contains('/') {
+                return Err(CorsConfigError::InvalidOrigin(origin.to_string()));
+            }
+            HeaderValue::from_str(origin)
+                .map_err(|_| CorsConfigError::InvalidOrigin(origin.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(AllowedOrigins::List)
+}
+
+fn parse_methods(raw: &str) -> Result<Vec<Method>, CorsConfigError> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|method| {
+            Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+                .map_err(|_| CorsConfigError::InvalidMethod(method.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config_from(vars: &[(&str, &str)]) -> Result<CorsConfig, CorsConfigError> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        CorsConfig::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn defaults_allow_the_registry_frontends() {
+        let config = config_from(&[]).unwrap();
+        assert_eq!(
+            config.origins,
+            AllowedOrigins::List(vec![
+                HeaderValue::from_static("http://localhost:3000"),
+                HeaderValue::from_static("https://soroban-registry.vercel.app"),
+            ])
+        );
+        assert!(config.methods.contains(&Method::PUT));
+        assert!(!config.allow_credentials);
+    }
+
+    #[test]
+    fn credentials_with_wildcard_are_rejected() {
+        let err = config_from(&[("ALLOWED_ORIGINS", "*"), ("CORS_ALLOW_CREDENTIALS", "true")])
+            .unwrap_err();
+        assert_eq!(err, CorsConfigError::CredentialsWithWildcard);
+    }
+
+    #[test]
+    fn wildcard_cannot_be_mixed_with_origins() {
+        let err = config_from(&[("ALLOWED_ORIGINS", "*,https://app.example.com")]).unwrap_err();
+        assert_eq!(err, CorsConfigError::WildcardWithOrigins);
+    }
+
+    #[test]
+    fn origins_with_paths_are_rejected() {
+        for origin in ["https://app.example.com/", "app.example.com", "ftp://x.io"] {
+            let err = config_from(&[("ALLOWED_ORIGINS", origin)]).unwrap_err();
+            assert_eq!(err, CorsConfigError::InvalidOrigin(origin.to_string()));
+        }
+    }
+
+    #[test]
+    fn invalid_settings_fail_instead_of_defaulting() {
+        assert!(matches!(
+            config_from(&[("CORS_MAX_AGE_SECS", "soon")]),
+            Err(CorsConfigError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            config_from(&[("CORS_ALLOW_CREDENTIALS", "yes please")]),
+            Err(CorsConfigError::InvalidValue { .. })
+        ));
+        assert!(matches!(
+            config_from(&[("CORS_ALLOWED_METHODS", "GET,NOT A METHOD")]),
+            Err(CorsConfigError::InvalidMethod(_))
+        ));
+    }
+}
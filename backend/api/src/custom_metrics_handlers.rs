@@ -15,8 +15,7 @@ use crate::{
 };
 
 fn db_error(operation: &str, err: sqlx::Error) -> ApiError {
-    tracing::error!(operation, error = ?err, "database operation failed");
-    ApiError::internal("Database operation failed")
+    ApiError::from_sqlx(operation, err)
 }
 
 #[derive(Debug, Deserialize, utoipa::IntoParams)]
@@ -280,8 +280,7 @@ async fn fetch_contract_uuid(state: &AppState, contract_id: &str) -> ApiResult<U
 }
 
 fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
-    tracing::error!(operation = operation, error = ?err, "database operation failed");
-    ApiError::internal("Database operation failed")
+    ApiError::from_sqlx(operation, err)
 }
 
 async fn column_exists(state: &AppState, table: &str, column: &str) -> ApiResult<bool> {
@@ -1,118 +1,486 @@
 use axum::{
-    http::StatusCode,
+    http::{
+        header::{CONTENT_RANGE, RETRY_AFTER},
+        HeaderValue, StatusCode,
+    },
     response::{IntoResponse, Response},
     Json,
 };
-use chrono::{SecondsFormat, Utc};
 use serde::Serialize;
-use serde_json::{json, Value};
-
-#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
-#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
-pub enum ErrorCode {
-    BadRequest,
-    Unauthorized,
-    Forbidden,
-    NotFound,
-    Conflict,
-    UnprocessableEntity,
-    PayloadTooLarge,
-    RateLimited,
-    InternalError,
-}
-
-impl ErrorCode {
-    fn from_status(status: StatusCode) -> Self {
-        match status {
-            StatusCode::BAD_REQUEST => Self::BadRequest,
-            StatusCode::UNAUTHORIZED => Self::Unauthorized,
-            StatusCode::FORBIDDEN => Self::Forbidden,
-            StatusCode::NOT_FOUND => Self::NotFound,
-            StatusCode::CONFLICT => Self::Conflict,
-            StatusCode::UNPROCESSABLE_ENTITY => Self::UnprocessableEntity,
-            StatusCode::PAYLOAD_TOO_LARGE => Self::PayloadTooLarge,
-            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited,
-            _ => Self::InternalError,
-        }
-    }
-}
+use serde_json::{json, Map, Value};
+use sqlx::error::ErrorKind;
 
+/// Every error the API returns. Serializes to
+/// `{ "error": { "code", "message", "details" } }` where `code` identifies the
+/// class of failure and `details.reason`, when present, the specific cause.
 #[derive(Debug)]
-pub struct ApiError {
-    status: StatusCode,
-    error_code: ErrorCode,
-    message: String,
-    details: Option<Value>,
+pub enum ApiError {
+    BadRequest {
+        message: String,
+        details: Option<Value>,
+    },
+    /// A single field failed validation
+    Validation {
+        field: String,
+        message: String,
+        details: Option<Value>,
+    },
+    Unauthorized {
+        message: String,
+        details: Option<Value>,
+    },
+    Forbidden {
+        message: String,
+        details: Option<Value>,
+    },
+    NotFound {
+        message: String,
+        details: Option<Value>,
+    },
+    Conflict {
+        message: String,
+        details: Option<Value>,
+    },
+    Unprocessable {
+        message: String,
+        details: Option<Value>,
+    },
+    /// An `If-Match` precondition no longer holds
+    PreconditionFailed {
+        message: String,
+        details: Option<Value>,
+    },
+    /// The record is under a legal hold
+    Locked {
+        message: String,
+        details: Option<Value>,
+    },
+    PayloadTooLarge {
+        message: String,
+        details: Option<Value>,
+    },
+    UnsupportedMediaType {
+        message: String,
+        details: Option<Value>,
+    },
+    /// A `Range` outside a representation of `size` bytes; answered with
+    /// `Content-Range: bytes */{size}`
+    RangeNotSatisfiable {
+        message: String,
+        size: u64,
+        details: Option<Value>,
+    },
+    RateLimited {
+        message: String,
+        retry_after: Option<u64>,
+        details: Option<Value>,
+    },
+    ServiceUnavailable {
+        message: String,
+        retry_after: Option<u64>,
+        details: Option<Value>,
+    },
+    Internal {
+        message: String,
+        details: Option<Value>,
+    },
+    /// The request ran past its route's time budget
+    GatewayTimeout {
+        message: String,
+        details: Option<Value>,
+    },
+    NotImplemented {
+        message: String,
+        details: Option<Value>,
+    },
 }
 
 impl std::fmt::Display for ApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}: {}", self.error_code, self.message)
+        write!(f, "{}: {}", self.code(), self.message())
     }
 }
 
-#[derive(Debug, Serialize)]
-struct ErrorResponse {
-    error_code: ErrorCode,
-    message: String,
-    details: Value,
-    timestamp: String,
+impl std::error::Error for ApiError {}
+
+/// Wire format of an error response
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ErrorResponse {
+    pub error: ErrorBody,
 }
 
-impl ApiError {
-    pub fn new(status: StatusCode, error: impl Into<String>, message: impl Into<String>) -> Self {
-        let reason = error.into();
-        Self {
-            status,
-            error_code: ErrorCode::from_status(status),
-            message: message.into(),
-            details: if reason.is_empty() {
-                None
-            } else {
-                Some(json!({ "reason": reason }))
-            },
-        }
-    }
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ErrorBody {
+    /// Error class, e.g. `NOT_FOUND` or `VALIDATION_ERROR`
+    pub code: &'static str,
+    pub message: String,
+    /// Always an object; `reason` carries the handler-specific error code
+    #[schema(value_type = Object)]
+    pub details: Value,
+    /// Same value as the `X-Request-ID` response header; quote it when
+    /// reporting a problem
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
 
-    pub fn with_details(mut self, details: impl Into<Value>) -> Self {
-        self.details = Some(details.into());
-        self
+fn reason(reason: impl Into<String>) -> Option<Value> {
+    let reason = reason.into();
+    if reason.is_empty() {
+        None
+    } else {
+        Some(json!({ "reason": reason }))
     }
+}
 
+impl ApiError {
     pub fn bad_request(error: impl Into<String>, message: impl Into<String>) -> Self {
-        Self::new(StatusCode::BAD_REQUEST, error, message)
+        Self::BadRequest {
+            message: message.into(),
+            details: reason(error),
+        }
+    }
+
+    pub fn validation(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Validation {
+            field: field.into(),
+            message: message.into(),
+            details: None,
+        }
     }
 
     pub fn not_found(error: impl Into<String>, message: impl Into<String>) -> Self {
-        Self::new(StatusCode::NOT_FOUND, error, message)
+        Self::NotFound {
+            message: message.into(),
+            details: reason(error),
+        }
     }
 
     pub fn internal(message: impl Into<String>) -> Self {
-        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "INTERNAL_ERROR", message)
+        Self::Internal {
+            message: message.into(),
+            details: None,
+        }
     }
 
     pub fn unauthorized(message: impl Into<String>) -> Self {
-        Self::new(StatusCode::UNAUTHORIZED, "UNAUTHORIZED", message)
+        Self::Unauthorized {
+            message: message.into(),
+            details: None,
+        }
     }
 
     pub fn forbidden(message: impl Into<String>) -> Self {
-        Self::new(StatusCode::FORBIDDEN, "FORBIDDEN", message)
+        Self::Forbidden {
+            message: message.into(),
+            details: None,
+        }
     }
 
     pub fn rate_limited(message: impl Into<String>) -> Self {
-        Self::new(StatusCode::TOO_MANY_REQUESTS, "RATE_LIMITED", message)
+        Self::RateLimited {
+            message: message.into(),
+            retry_after: None,
+            details: None,
+        }
+    }
+
+    pub fn service_unavailable(error: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::ServiceUnavailable {
+            message: message.into(),
+            retry_after: None,
+            details: reason(error),
+        }
+    }
+
+    pub fn payload_too_large(message: impl Into<String>) -> Self {
+        Self::PayloadTooLarge {
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn unsupported_media_type(message: impl Into<String>) -> Self {
+        Self::UnsupportedMediaType {
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn not_implemented(message: impl Into<String>) -> Self {
+        Self::NotImplemented {
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    pub fn range_not_satisfiable(size: u64, message: impl Into<String>) -> Self {
+        Self::RangeNotSatisfiable {
+            message: message.into(),
+            size,
+            details: None,
+        }
     }
 
     pub fn unprocessable(error: impl Into<String>, message: impl Into<String>) -> Self {
-        Self::new(StatusCode::UNPROCESSABLE_ENTITY, error, message)
+        Self::Unprocessable {
+            message: message.into(),
+            details: reason(error),
+        }
     }
 
     pub fn conflict(error: impl Into<String>, message: impl Into<String>) -> Self {
-        Self::new(StatusCode::CONFLICT, error, message)
+        Self::Conflict {
+            message: message.into(),
+            details: reason(error),
+        }
+    }
+
+    pub fn precondition_failed(error: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::PreconditionFailed {
+            message: message.into(),
+            details: reason(error),
+        }
+    }
+
+    pub fn locked(error: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::Locked {
+            message: message.into(),
+            details: reason(error),
+        }
+    }
+
+    pub fn gateway_timeout(error: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::GatewayTimeout {
+            message: message.into(),
+            details: reason(error),
+        }
+    }
+
+    /// Maps a sqlx error onto the matching error class and logs it. Missing
+    /// rows become 404s, constraint violations 409/400s, and an exhausted or
+    /// unreachable pool or a statement timeout a retryable 503; anything else
+    /// is a 500.
+    pub fn from_sqlx(operation: &str, err: sqlx::Error) -> Self {
+        let mapped = match &err {
+            sqlx::Error::RowNotFound => {
+                Self::not_found("RowNotFound", "The requested resource was not found")
+            }
+            // statement_timeout fired: the database is overloaded, not broken
+            sqlx::Error::Database(db_err) if db_err.code().as_deref() == Some("57014") => {
+                Self::service_unavailable(
+                    "DATABASE_TIMEOUT",
+                    "The database took too long to respond",
+                )
+                .with_retry_after(5)
+            }
+            sqlx::Error::Database(db_err) => {
+                let constraint = db_err.constraint().map(str::to_owned);
+                match db_err.kind() {
+                    ErrorKind::UniqueViolation => Self::conflict(
+                        "UniqueViolation",
+                        "A resource with the same unique key already exists",
+                    )
+                    .with_details(json!({ "constraint": constraint })),
+                    ErrorKind::ForeignKeyViolation => Self::conflict(
+                        "ForeignKeyViolation",
+                        "The request references a resource that does not exist or is still in use",
+                    )
+                    .with_details(json!({ "constraint": constraint })),
+                    ErrorKind::NotNullViolation | ErrorKind::CheckViolation => {
+                        let field = db_err
+                            .try_downcast_ref::<sqlx::postgres::PgDatabaseError>()
+                            .and_then(|pg| pg.column())
+                            .map(str::to_owned)
+                            .or_else(|| constraint.clone())
+                            .unwrap_or_default();
+                        Self::validation(field, "Value violates a database constraint")
+                            .with_details(json!({ "constraint": constraint }))
+                    }
+                    _ => Self::internal("An unexpected database error occurred"),
+                }
+            }
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed | sqlx::Error::Io(_) => {
+                Self::service_unavailable(
+                    "DATABASE_UNAVAILABLE",
+                    "The database is temporarily unavailable",
+                )
+                .with_retry_after(5)
+            }
+            _ => Self::internal("An unexpected database error occurred"),
+        };
+
+        if mapped.status().is_server_error() {
+            tracing::error!(operation, error = ?err, code = mapped.code(), "database operation failed");
+        } else {
+            tracing::warn!(operation, error = %err, code = mapped.code(), "database operation rejected");
+        }
+        mapped
+    }
+
+    /// Merges `details` into the error's details object. Non-object values
+    /// replace it outright.
+    pub fn with_details(mut self, details: impl Into<Value>) -> Self {
+        match (self.details_mut(), details.into()) {
+            (Some(Value::Object(existing)), Value::Object(extra)) => existing.extend(extra),
+            (slot, value) => *slot = Some(value),
+        }
+        self
+    }
+
+    /// Sets `Retry-After` on rate-limited and unavailable responses; ignored
+    /// for other classes.
+    pub fn with_retry_after(mut self, seconds: u64) -> Self {
+        if let Self::RateLimited { retry_after, .. }
+        | Self::ServiceUnavailable { retry_after, .. } = &mut self
+        {
+            *retry_after = Some(seconds);
+        }
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::BadRequest { .. } | Self::Validation { .. } => StatusCode::BAD_REQUEST,
+            Self::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            Self::Forbidden { .. } => StatusCode::FORBIDDEN,
+            Self::NotFound { .. } => StatusCode::NOT_FOUND,
+            Self::Conflict { .. } => StatusCode::CONFLICT,
+            Self::Unprocessable { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::PreconditionFailed { .. } => StatusCode::PRECONDITION_FAILED,
+            Self::Locked { .. } => StatusCode::LOCKED,
+            Self::PayloadTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::UnsupportedMediaType { .. } => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::RangeNotSatisfiable { .. } => StatusCode::RANGE_NOT_SATISFIABLE,
+            Self::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+            Self::ServiceUnavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+            Self::Internal { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::GatewayTimeout { .. } => StatusCode::GATEWAY_TIMEOUT,
+            Self::NotImplemented { .. } => StatusCode::NOT_IMPLEMENTED,
+        }
+    }
+
+    /// Machine-readable error class returned as `error.code`
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::BadRequest { .. } => "BAD_REQUEST",
+            Self::Validation { .. } => "VALIDATION_ERROR",
+            Self::Unauthorized { .. } => "UNAUTHORIZED",
+            Self::Forbidden { .. } => "FORBIDDEN",
+            Self::NotFound { .. } => "NOT_FOUND",
+            Self::Conflict { .. } => "CONFLICT",
+            Self::Unprocessable { .. } => "UNPROCESSABLE_ENTITY",
+            Self::PreconditionFailed { .. } => "PRECONDITION_FAILED",
+            Self::Locked { .. } => "LOCKED",
+            Self::PayloadTooLarge { .. } => "PAYLOAD_TOO_LARGE",
+            Self::UnsupportedMediaType { .. } => "UNSUPPORTED_MEDIA_TYPE",
+            Self::RangeNotSatisfiable { .. } => "RANGE_NOT_SATISFIABLE",
+            Self::RateLimited { .. } => "RATE_LIMITED",
+            Self::ServiceUnavailable { .. } => "SERVICE_UNAVAILABLE",
+            Self::Internal { .. } => "INTERNAL_ERROR",
+            Self::GatewayTimeout { .. } => "GATEWAY_TIMEOUT",
+            Self::NotImplemented { .. } => "NOT_IMPLEMENTED",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::BadRequest { message, .. }
+            | Self::Validation { message, .. }
+            | Self::Unauthorized { message, .. }
+            | Self::Forbidden { message, .. }
+            | Self::NotFound { message, .. }
+            | Self::Conflict { message, .. }
+            | Self::Unprocessable { message, .. }
+            | Self::PreconditionFailed { message, .. }
+            | Self::Locked { message, .. }
+            | Self::PayloadTooLarge { message, .. }
+            | Self::UnsupportedMediaType { message, .. }
+            | Self::RangeNotSatisfiable { message, .. }
+            | Self::RateLimited { message, .. }
+            | Self::ServiceUnavailable { message, .. }
+            | Self::Internal { message, .. }
+            | Self::GatewayTimeout { message, .. }
+            | Self::NotImplemented { message, .. } => message,
+        }
+    }
+
+    pub fn details(&self) -> Option<&Value> {
+        match self {
+            Self::BadRequest { details, .. }
+            | Self::Validation { details, .. }
+            | Self::Unauthorized { details, .. }
+            | Self::Forbidden { details, .. }
+            | Self::NotFound { details, .. }
+            | Self::Conflict { details, .. }
+            | Self::Unprocessable { details, .. }
+            | Self::PreconditionFailed { details, .. }
+            | Self::Locked { details, .. }
+            | Self::PayloadTooLarge { details, .. }
+            | Self::UnsupportedMediaType { details, .. }
+            | Self::RangeNotSatisfiable { details, .. }
+            | Self::RateLimited { details, .. }
+            | Self::ServiceUnavailable { details, .. }
+            | Self::Internal { details, .. }
+            | Self::GatewayTimeout { details, .. }
+            | Self::NotImplemented { details, .. } => details.as_ref(),
+        }
+    }
+
+    fn details_mut(&mut self) -> &mut Option<Value> {
+        match self {
+            Self::BadRequest { details, .. }
+            | Self::Validation { details, .. }
+            | Self::Unauthorized { details, .. }
+            | Self::Forbidden { details, .. }
+            | Self::NotFound { details, .. }
+            | Self::Conflict { details, .. }
+            | Self::Unprocessable { details, .. }
+            | Self::PreconditionFailed { details, .. }
+            | Self::Locked { details, .. }
+            | Self::PayloadTooLarge { details, .. }
+            | Self::UnsupportedMediaType { details, .. }
+            | Self::RangeNotSatisfiable { details, .. }
+            | Self::RateLimited { details, .. }
+            | Self::ServiceUnavailable { details, .. }
+            | Self::Internal { details, .. }
+            | Self::GatewayTimeout { details, .. }
+            | Self::NotImplemented { details, .. } => details,
+        }
+    }
+
+    fn retry_after(&self) -> Option<u64> {
+        match self {
+            Self::RateLimited { retry_after, .. }
+            | Self::ServiceUnavailable { retry_after, .. } => *retry_after,
+            _ => None,
+        }
     }
 
-    pub fn db_error(message: impl Into<String>) -> Self {
-        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "DATABASE_ERROR", message)
+    /// Response body; validation errors always name the offending field and
+    /// retryable errors repeat `Retry-After` as `retry_after_seconds`.
+    pub fn body(&self) -> ErrorResponse {
+        let mut details = match self.details() {
+            Some(Value::Object(map)) => map.clone(),
+            Some(other) => Map::from_iter([("info".to_string(), other.clone())]),
+            None => Map::new(),
+        };
+        if let Self::Validation { field, .. } = self {
+            details
+                .entry("field")
+                .or_insert_with(|| Value::String(field.clone()));
+        }
+        if let Some(seconds) = self.retry_after() {
+            details.insert("retry_after_seconds".to_string(), json!(seconds));
+        }
+
+        ErrorResponse {
+            error: ErrorBody {
+                code: self.code(),
+                message: self.message().to_string(),
+                details: Value::Object(details),
+                request_id: None,
+            },
+        }
     }
 }
 
@@ -120,14 +488,21 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let correlation_id = crate::request_tracing::current_request_id()
             .unwrap_or_else(crate::request_tracing::generate_request_id);
-        let payload = ErrorResponse {
-            error_code: self.error_code,
-            message: self.message,
-            details: self.details.unwrap_or_else(|| json!({})),
-            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
-        };
 
-        let mut response = (self.status, Json(payload)).into_response();
+        let mut body = self.body();
+        body.error.request_id = Some(correlation_id.clone());
+
+        let mut response = (self.status(), Json(body)).into_response();
+        if let Some(seconds) = self.retry_after() {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER, HeaderValue::from(seconds));
+        }
+        if let Self::RangeNotSatisfiable { size, .. } = self {
+            if let Ok(value) = HeaderValue::from_str(&format!("bytes */{}", size)) {
+                response.headers_mut().insert(CONTENT_RANGE, value);
+            }
+        }
         crate::request_tracing::attach_request_id_headers(response.headers_mut(), &correlation_id);
         response
     }
@@ -138,8 +513,7 @@ pub type AppError = ApiError;
 
 impl From<sqlx::Error> for ApiError {
     fn from(e: sqlx::Error) -> Self {
-        tracing::error!(err = %e, "database error");
-        ApiError::internal("Database error")
+        ApiError::from_sqlx("database query", e)
     }
 }
 
@@ -147,6 +521,13 @@ impl From<sqlx::Error> for ApiError {
 mod tests {
     use super::*;
 
+    async fn body_json(response: Response) -> Value {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("response body should be readable");
+        serde_json::from_slice(&body).expect("response body should be valid json")
+    }
+
     #[tokio::test]
     async fn api_error_uses_standard_response_shape() {
         let response = ApiError::bad_request("INVALID_INPUT", "Invalid request payload")
@@ -155,29 +536,55 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
-        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
-            .await
-            .expect("response body should be readable");
-        let value: serde_json::Value =
-            serde_json::from_slice(&body).expect("response body should be valid json");
-
-        assert_eq!(value["error_code"], "BAD_REQUEST");
-        assert_eq!(value["message"], "Invalid request payload");
-        assert_eq!(value["details"]["field"], "name");
-        assert!(value["timestamp"].is_string());
+        let value = body_json(response).await;
+        assert_eq!(value["error"]["code"], "BAD_REQUEST");
+        assert_eq!(value["error"]["message"], "Invalid request payload");
+        assert_eq!(value["error"]["details"]["reason"], "INVALID_INPUT");
+        assert_eq!(value["error"]["details"]["field"], "name");
     }
 
     #[tokio::test]
     async fn rate_limited_errors_use_rate_limited_code() {
-        let response = ApiError::rate_limited("Too many requests").into_response();
+        let response = ApiError::rate_limited("Too many requests")
+            .with_retry_after(30)
+            .into_response();
         assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RETRY_AFTER], "30");
 
-        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
-            .await
-            .expect("response body should be readable");
-        let value: serde_json::Value =
-            serde_json::from_slice(&body).expect("response body should be valid json");
+        let value = body_json(response).await;
+        assert_eq!(value["error"]["code"], "RATE_LIMITED");
+        assert_eq!(value["error"]["details"]["retry_after_seconds"], 30);
+    }
+
+    #[tokio::test]
+    async fn validation_errors_name_the_field() {
+        let response = ApiError::validation("name", "must not be empty").into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let value = body_json(response).await;
+        assert_eq!(value["error"]["code"], "VALIDATION_ERROR");
+        assert_eq!(value["error"]["details"]["field"], "name");
+    }
+
+    #[test]
+    fn missing_rows_map_to_not_found() {
+        let err = ApiError::from_sqlx("test", sqlx::Error::RowNotFound);
+        assert_eq!(err.status(), StatusCode::NOT_FOUND);
+        assert_eq!(err.code(), "NOT_FOUND");
+    }
+
+    #[test]
+    fn pool_exhaustion_is_retryable() {
+        let err = ApiError::from_sqlx("test", sqlx::Error::PoolTimedOut);
+        assert_eq!(err.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(err.details().unwrap()["reason"], "DATABASE_UNAVAILABLE");
+        assert_eq!(err.retry_after(), Some(5));
+    }
 
-        assert_eq!(value["error_code"], "RATE_LIMITED");
+    #[test]
+    fn unclassified_database_errors_stay_internal() {
+        let err = ApiError::from_sqlx("test", sqlx::Error::Protocol("boom".into()));
+        assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(err.message(), "An unexpected database error occurred");
     }
 }
@@ -0,0 +1,335 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::types::Json as SqlJson;
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    escalation_policy_models::{
+        CreateEscalationPolicyRequest, EscalationPolicy, EscalationStep, EscalationStepInput,
+        UpdateEscalationPolicyRequest,
+    },
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Steps a policy may have; more than this is almost certainly a mistake
+const MAX_STEPS: usize = 10;
+/// Length of `incidents.incident_type`
+const MAX_INCIDENT_TYPE_LEN: usize = 50;
+
+fn parse_policy_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidEscalationPolicyId",
+            format!("Invalid escalation policy ID format: {}", id),
+        )
+    })
+}
+
+fn require_admin(claims: &AuthClaims) -> ApiResult<()> {
+    if auth::is_admin(claims) {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "Administrative privileges are required to manage escalation policies",
+        ))
+    }
+}
+
+fn policy_not_found(id: &str) -> ApiError {
+    ApiError::not_found(
+        "EscalationPolicyNotFound",
+        format!("No escalation policy found with ID: {}", id),
+    )
+}
+
+fn normalize_incident_type(incident_type: Option<&str>) -> ApiResult<Option<String>> {
+    let Some(incident_type) = incident_type.map(str::trim) else {
+        return Ok(None);
+    };
+    if incident_type.is_empty() || incident_type.len() > MAX_INCIDENT_TYPE_LEN {
+        return Err(ApiError::unprocessable(
+            "InvalidIncidentType",
+            format!(
+                "incident_type must be 1 to {} characters; omit it for the global policy",
+                MAX_INCIDENT_TYPE_LEN
+            ),
+        ));
+    }
+    Ok(Some(incident_type.to_string()))
+}
+
+/// Validates the steps and checks that every channel they name exists.
+async fn parse_steps(
+    state: &AppState,
+    inputs: &[EscalationStepInput],
+) -> ApiResult<Vec<EscalationStep>> {
+    if inputs.is_empty() || inputs.len() > MAX_STEPS {
+        return Err(ApiError::unprocessable(
+            "InvalidEscalationSteps",
+            format!("A policy needs 1 to {} steps", MAX_STEPS),
+        ));
+    }
+
+    let mut steps = Vec::with_capacity(inputs.len());
+    for (index, input) in inputs.iter().enumerate() {
+        let delay_seconds = input.delay.to_seconds().map_err(|reason| {
+            ApiError::unprocessable(
+                "InvalidEscalationSteps",
+                format!("steps[{}].delay: {}", index, reason),
+            )
+        })?;
+
+        let mut channel_ids = input.channel_ids.clone();
+        channel_ids.sort();
+        channel_ids.dedup();
+        if channel_ids.is_empty() {
+            return Err(ApiError::unprocessable(
+                "InvalidEscalationSteps",
+                format!(
+                    "steps[{}] must name at least one notification channel",
+                    index
+                ),
+            ));
+        }
+        steps.push(EscalationStep {
+            delay_seconds,
+            channel_ids,
+        });
+    }
+
+    let named: Vec<Uuid> = steps
+        .iter()
+        .flat_map(|step| step.channel_ids.iter().copied())
+        .collect();
+    let unknown: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT id FROM UNNEST($1::UUID[]) AS named(id)
+         WHERE NOT EXISTS (SELECT 1 FROM notification_channels c WHERE c.id = named.id)",
+    )
+    .bind(&named)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check notification channels", err))?;
+    if !unknown.is_empty() {
+        let ids: Vec<String> = unknown.iter().map(Uuid::to_string).collect();
+        return Err(ApiError::unprocessable(
+            "UnknownNotificationChannel",
+            format!("Unknown notification channel IDs: {}", ids.join(", ")),
+        ));
+    }
+
+    Ok(steps)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/escalation-policies",
+    responses(
+        (status = 200, description = "Global policy first, then per-incident-type policies", body = [EscalationPolicy]),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Escalation Policies"
+)]
+pub async fn list_escalation_policies(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Vec<EscalationPolicy>>> {
+    require_admin(&claims)?;
+
+    let policies: Vec<EscalationPolicy> = sqlx::query_as(
+        "SELECT * FROM escalation_policies
+         ORDER BY incident_type IS NOT NULL, incident_type",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list escalation policies", err))?;
+
+    Ok(Json(policies))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/escalation-policies",
+    request_body = CreateEscalationPolicyRequest,
+    responses(
+        (status = 201, description = "Escalation policy created", body = EscalationPolicy),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 409, description = "A policy already exists for this incident type"),
+        (status = 422, description = "Invalid steps or unknown notification channel")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Escalation Policies"
+)]
+pub async fn create_escalation_policy(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<CreateEscalationPolicyRequest>,
+) -> ApiResult<(StatusCode, Json<EscalationPolicy>)> {
+    require_admin(&claims)?;
+
+    let incident_type = normalize_incident_type(req.incident_type.as_deref())?;
+    let steps = parse_steps(&state, &req.steps).await?;
+
+    let policy: EscalationPolicy = sqlx::query_as(
+        r#"
+        INSERT INTO escalation_policies (incident_type, min_severity, steps, created_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(&incident_type)
+    .bind(req.min_severity)
+    .bind(SqlJson(&steps))
+    .bind(&claims.sub)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => ApiError::conflict(
+            "EscalationPolicyExists",
+            match &incident_type {
+                Some(incident_type) => format!(
+                    "Incidents of type '{}' already have an escalation policy",
+                    incident_type
+                ),
+                None => "A global escalation policy already exists".to_string(),
+            },
+        ),
+        _ => db_internal_error("create escalation policy", err),
+    })?;
+
+    tracing::info!(
+        policy_id = %policy.id,
+        incident_type = ?policy.incident_type,
+        steps = steps.len(),
+        "escalation policy created"
+    );
+
+    Ok((StatusCode::CREATED, Json(policy)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/escalation-policies/{id}",
+    params(
+        ("id" = String, Path, description = "Escalation policy UUID")
+    ),
+    responses(
+        (status = 200, description = "Escalation policy", body = EscalationPolicy),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Escalation policy not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Escalation Policies"
+)]
+pub async fn get_escalation_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<Json<EscalationPolicy>> {
+    require_admin(&claims)?;
+    let policy_id = parse_policy_id(&id)?;
+
+    sqlx::query_as("SELECT * FROM escalation_policies WHERE id = $1")
+        .bind(policy_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get escalation policy", err))?
+        .map(Json)
+        .ok_or_else(|| policy_not_found(&id))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/escalation-policies/{id}",
+    params(
+        ("id" = String, Path, description = "Escalation policy UUID")
+    ),
+    request_body = UpdateEscalationPolicyRequest,
+    responses(
+        (status = 200, description = "Escalation policy updated", body = EscalationPolicy),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Escalation policy not found"),
+        (status = 422, description = "Invalid steps or unknown notification channel")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Escalation Policies"
+)]
+pub async fn update_escalation_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<UpdateEscalationPolicyRequest>,
+) -> ApiResult<Json<EscalationPolicy>> {
+    require_admin(&claims)?;
+    let policy_id = parse_policy_id(&id)?;
+
+    let steps = match &req.steps {
+        Some(inputs) => Some(parse_steps(&state, inputs).await?),
+        None => None,
+    };
+
+    sqlx::query_as(
+        r#"
+        UPDATE escalation_policies
+        SET min_severity = COALESCE($1, min_severity),
+            steps = COALESCE($2, steps),
+            created_by = $3,
+            updated_at = NOW()
+        WHERE id = $4
+        RETURNING *
+        "#,
+    )
+    .bind(req.min_severity)
+    .bind(steps.map(SqlJson))
+    .bind(&claims.sub)
+    .bind(policy_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update escalation policy", err))?
+    .map(Json)
+    .ok_or_else(|| policy_not_found(&id))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/escalation-policies/{id}",
+    params(
+        ("id" = String, Path, description = "Escalation policy UUID")
+    ),
+    responses(
+        (status = 204, description = "Escalation policy deleted"),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Escalation policy not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Escalation Policies"
+)]
+pub async fn delete_escalation_policy(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    require_admin(&claims)?;
+    let policy_id = parse_policy_id(&id)?;
+
+    let result = sqlx::query("DELETE FROM escalation_policies WHERE id = $1")
+        .bind(policy_id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete escalation policy", err))?;
+
+    if result.rows_affected() == 0 {
+        return Err(policy_not_found(&id));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,62 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{types::Json, FromRow};
+use uuid::Uuid;
+
+use crate::incident_models::{DurationInput, IncidentSeverity};
+
+/// One step of an escalation policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EscalationStep {
+    /// Seconds to wait after the previous step fired, or after the incident
+    /// was opened for the first step
+    pub delay_seconds: i64,
+    /// `notification_channels` notified when the step fires
+    pub channel_ids: Vec<Uuid>,
+}
+
+/// Ordered escalation steps for unacknowledged incidents, either the global
+/// policy (`incident_type` null) or the policy for one incident type
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct EscalationPolicy {
+    pub id: Uuid,
+    /// `null` for the global policy
+    pub incident_type: Option<String>,
+    /// Incidents at least this severe escalate
+    pub min_severity: IncidentSeverity,
+    #[schema(value_type = Vec<EscalationStep>)]
+    pub steps: Json<Vec<EscalationStep>>,
+    /// JWT subject of the admin who last changed the policy
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EscalationStepInput {
+    /// Integer seconds, an ISO-8601 duration or a shorthand such as "15m"
+    pub delay: DurationInput,
+    pub channel_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateEscalationPolicyRequest {
+    /// Omit to create the global policy
+    pub incident_type: Option<String>,
+    /// Defaults to `critical`
+    #[serde(default = "critical")]
+    pub min_severity: IncidentSeverity,
+    pub steps: Vec<EscalationStepInput>,
+}
+
+fn critical() -> IncidentSeverity {
+    IncidentSeverity::Critical
+}
+
+/// Partial update; omitted fields keep their current values
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpdateEscalationPolicyRequest {
+    pub min_severity: Option<IncidentSeverity>,
+    /// Replaces every step. Incidents keep the number of steps already fired.
+    pub steps: Option<Vec<EscalationStepInput>>,
+}
@@ -0,0 +1,18 @@
+use axum::{routing::get, Router};
+
+use crate::{escalation_policy_handlers, state::AppState};
+
+pub fn escalation_policy_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/escalation-policies",
+            get(escalation_policy_handlers::list_escalation_policies)
+                .post(escalation_policy_handlers::create_escalation_policy),
+        )
+        .route(
+            "/api/escalation-policies/:id",
+            get(escalation_policy_handlers::get_escalation_policy)
+                .patch(escalation_policy_handlers::update_escalation_policy)
+                .delete(escalation_policy_handlers::delete_escalation_policy),
+        )
+}
@@ -0,0 +1,114 @@
+//! Admin endpoints for runtime feature flags, see [`crate::feature_flags`].
+//!
+//!   GET /api/admin/flags          – every flag with its current value
+//!   PUT /api/admin/flags/:name    – override a flag on every instance
+//!   GET /api/admin/settings       – every runtime setting with its value
+//!   PUT /api/admin/settings/:name – override a setting on every instance
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    auth::AuthClaims,
+    error::{ApiError, ApiResult},
+    feature_flags::{FlagView, SettingView},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateFlagRequest {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateSettingRequest {
+    pub value: u64,
+}
+
+pub async fn list_flags(State(state): State<AppState>) -> Json<Vec<FlagView>> {
+    Json(
+        state
+            .flags
+            .all()
+            .into_iter()
+            .map(|flag| flag.view())
+            .collect(),
+    )
+}
+
+pub async fn update_flag(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Path(name): Path<String>,
+    Json(req): Json<UpdateFlagRequest>,
+) -> ApiResult<Json<FlagView>> {
+    let flag = state.flags.get(&name).ok_or_else(|| {
+        ApiError::not_found(
+            "UnknownFeatureFlag",
+            format!("No feature flag named '{}'", name),
+        )
+    })?;
+
+    state
+        .flags
+        .set(&state.db, flag, req.enabled, &claims.sub)
+        .await
+        .map_err(|err| db_internal_error("update feature flag", err))?;
+    tracing::warn!(
+        flag = flag.name(),
+        enabled = req.enabled,
+        admin = %claims.sub,
+        "Feature flag overridden"
+    );
+
+    Ok(Json(flag.view()))
+}
+
+pub async fn list_settings(State(state): State<AppState>) -> Json<Vec<SettingView>> {
+    Json(
+        state
+            .flags
+            .settings()
+            .into_iter()
+            .map(|setting| setting.view())
+            .collect(),
+    )
+}
+
+pub async fn update_setting(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Path(name): Path<String>,
+    Json(req): Json<UpdateSettingRequest>,
+) -> ApiResult<Json<SettingView>> {
+    let setting = state.flags.setting(&name).ok_or_else(|| {
+        ApiError::not_found(
+            "UnknownSetting",
+            format!("No runtime setting named '{}'", name),
+        )
+    })?;
+    if req.value == 0 || req.value > i64::MAX as u64 {
+        return Err(ApiError::validation(
+            "value",
+            "Settings are positive numbers",
+        ));
+    }
+
+    state
+        .flags
+        .set_setting(&state.db, setting, req.value, &claims.sub)
+        .await
+        .map_err(|err| db_internal_error("update runtime setting", err))?;
+    tracing::warn!(
+        setting = setting.name(),
+        value = req.value,
+        admin = %claims.sub,
+        "Runtime setting overridden"
+    );
+
+    Ok(Json(setting.view()))
+}
@@ -0,0 +1,491 @@
+//! Runtime feature flags.
+//!
+//! Risky features are gated by a flag on [`crate::state::AppState::flags`]
+//! that handlers check per request, e.g.
+//! `state.flags.full_text_search.enabled()`. Every flag has a compiled-in
+//! default, which `FEATURE_<NAME>` (`true`/`false`) replaces at startup;
+//! [`crate::config`] rejects any other value. An
+//! admin can override it at runtime through `PUT /api/admin/flags/{name}`;
+//! overrides live in the `feature_flags` table and every instance re-reads
+//! them in the background, so a flip reaches the whole fleet without a
+//! restart.
+//!
+//! | Variable                      | Default |
+//! |-------------------------------|---------|
+//! | `FEATURE_FULL_TEXT_SEARCH`    | true    |
+//! | `FEATURE_ONCHAIN_SYNC`        | true    |
+//! | `FEATURE_DEGRADED_MODE`       | true    |
+//! | `FEATURE_FLAGS_REFRESH_SECS`  | 15      |
+//!
+//! Numeric settings work the same way: a compiled-in default, an
+//! environment variable replacing it at startup and a runtime override set
+//! through `PUT /api/admin/settings/{name}`, stored in `runtime_settings`
+//! and refreshed with the flags.
+//!
+//! | Setting                      | Variable                     | Default |
+//! |------------------------------|------------------------------|---------|
+//! | `slow_query_ms`              | `SLOW_QUERY_THRESHOLD_MS`    | 500     |
+//! | `request_timeout_short_ms`   | `REQUEST_TIMEOUT_SHORT_MS`   | 10000   |
+//! | `request_timeout_default_ms` | `REQUEST_TIMEOUT_DEFAULT_MS` | 30000   |
+//! | `request_timeout_long_ms`    | `REQUEST_TIMEOUT_LONG_MS`    | 300000  |
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::config::Config;
+use crate::shutdown::Shutdown;
+
+const DEFAULT_REFRESH_SECS: u64 = 15;
+
+/// A single on/off switch
+#[derive(Debug)]
+pub struct Flag {
+    name: &'static str,
+    /// `FEATURE_<NAME>`, read by [`crate::config`]
+    env_var: &'static str,
+    description: &'static str,
+    /// Compiled-in default, or the `FEATURE_<NAME>` value when set
+    base: bool,
+    enabled: AtomicBool,
+}
+
+impl Flag {
+    fn new(
+        name: &'static str,
+        env_var: &'static str,
+        description: &'static str,
+        default: bool,
+    ) -> Self {
+        Self {
+            name,
+            env_var,
+            description,
+            base: default,
+            enabled: AtomicBool::new(default),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn env_var(&self) -> &'static str {
+        self.env_var
+    }
+
+    fn set(&self, enabled: bool) {
+        let was = self.enabled.swap(enabled, Ordering::Relaxed);
+        if was != enabled {
+            tracing::info!(flag = self.name, enabled, "Feature flag changed");
+        }
+    }
+
+    pub fn view(&self) -> FlagView {
+        let enabled = self.enabled();
+        FlagView {
+            name: self.name,
+            description: self.description,
+            enabled,
+            default: self.base,
+            overridden: enabled != self.base,
+        }
+    }
+}
+
+/// A flag as reported by the admin endpoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct FlagView {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub enabled: bool,
+    /// Value used when no runtime override is stored
+    pub default: bool,
+    pub overridden: bool,
+}
+
+/// A number tunable at runtime, e.g. a threshold in milliseconds
+#[derive(Debug)]
+pub struct Setting {
+    name: &'static str,
+    env_var: &'static str,
+    description: &'static str,
+    /// Compiled-in default, or the environment value when set
+    base: u64,
+    value: AtomicU64,
+}
+
+impl Setting {
+    fn new(
+        name: &'static str,
+        env_var: &'static str,
+        description: &'static str,
+        default: u64,
+    ) -> Self {
+        Self {
+            name,
+            env_var,
+            description,
+            base: default,
+            value: AtomicU64::new(default),
+        }
+    }
+
+    pub fn get(&self) -> u64 {
+        self.value.load(Ordering::Relaxed)
+    }
+
+    /// The value read as milliseconds
+    pub fn millis(&self) -> Duration {
+        Duration::from_millis(self.get())
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn env_var(&self) -> &'static str {
+        self.env_var
+    }
+
+    fn set(&self, value: u64) {
+        let was = self.value.swap(value, Ordering::Relaxed);
+        if was != value {
+            tracing::info!(setting = self.name, value, "Runtime setting changed");
+        }
+    }
+
+    pub fn view(&self) -> SettingView {
+        let value = self.get();
+        SettingView {
+            name: self.name,
+            description: self.description,
+            value,
+            default: self.base,
+            overridden: value != self.base,
+        }
+    }
+}
+
+/// A setting as reported by the admin endpoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct SettingView {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub value: u64,
+    /// Value used when no runtime override is stored
+    pub default: u64,
+    pub overridden: bool,
+}
+
+#[derive(Debug)]
+pub struct FeatureFlags {
+    /// Rank `sort_by=relevance` searches with Postgres full-text scoring
+    /// rather than by name match alone
+    pub full_text_search: Flag,
+    /// Refresh the network catalog from Soroban RPC in the background
+    pub onchain_sync: Flag,
+    /// Answer 503 from routes whose database or blob store has an open
+    /// watchdog incident, see [`crate::component_health`]
+    pub degraded_mode: Flag,
+    /// Statements slower than this are logged and counted, see
+    /// [`crate::slow_queries`]
+    pub slow_query_ms: Setting,
+    /// Time budgets of searches and listings, of most routes, and of
+    /// uploads, downloads, imports and exports, see
+    /// [`crate::request_timeout`]
+    pub request_timeout_short_ms: Setting,
+    pub request_timeout_default_ms: Setting,
+    pub request_timeout_long_ms: Setting,
+    pub refresh_interval: Duration,
+}
+
+impl Default for FeatureFlags {
+    /// Compiled-in defaults only, ignoring the environment
+    fn default() -> Self {
+        Self {
+            full_text_search: Flag::new(
+                "full_text_search",
+                "FEATURE_FULL_TEXT_SEARCH",
+                "Rank relevance searches with full-text scoring",
+                true,
+            ),
+            onchain_sync: Flag::new(
+                "onchain_sync",
+                "FEATURE_ONCHAIN_SYNC",
+                "Refresh the network catalog from Soroban RPC",
+                true,
+            ),
+            degraded_mode: Flag::new(
+                "degraded_mode",
+                "FEATURE_DEGRADED_MODE",
+                "Fail fast with 503 while a component the route needs is impaired",
+                true,
+            ),
+            slow_query_ms: Setting::new(
+                "slow_query_ms",
+                "SLOW_QUERY_THRESHOLD_MS",
+                "Log and count SQL statements slower than this many milliseconds",
+                500,
+            ),
+            request_timeout_short_ms: Setting::new(
+                "request_timeout_short_ms",
+                "REQUEST_TIMEOUT_SHORT_MS",
+                "Milliseconds a search or listing may take before it is answered 504",
+                10_000,
+            ),
+            request_timeout_default_ms: Setting::new(
+                "request_timeout_default_ms",
+                "REQUEST_TIMEOUT_DEFAULT_MS",
+                "Milliseconds most requests may take before they are answered 504",
+                30_000,
+            ),
+            request_timeout_long_ms: Setting::new(
+                "request_timeout_long_ms",
+                "REQUEST_TIMEOUT_LONG_MS",
+                "Milliseconds an upload, download, import or export may take before it is answered 504",
+                300_000,
+            ),
+            refresh_interval: Duration::from_secs(DEFAULT_REFRESH_SECS),
+        }
+    }
+}
+
+impl FeatureFlags {
+    /// Flags starting from the validated `FEATURE_*` values in `config`
+    pub fn from_config(config: &Config) -> Self {
+        let mut flags = Self::default();
+        for flag in [
+            &mut flags.full_text_search,
+            &mut flags.onchain_sync,
+            &mut flags.degraded_mode,
+        ] {
+            if let Some(&value) = config.feature_defaults.get(flag.name) {
+                flag.base = value;
+                flag.enabled = AtomicBool::new(value);
+            }
+        }
+        for setting in [
+            &mut flags.slow_query_ms,
+            &mut flags.request_timeout_short_ms,
+            &mut flags.request_timeout_default_ms,
+            &mut flags.request_timeout_long_ms,
+        ] {
+            if let Some(&value) = config.setting_defaults.get(setting.name) {
+                setting.base = value;
+                setting.value = AtomicU64::new(value);
+            }
+        }
+        flags.refresh_interval = config.feature_refresh_interval;
+        flags
+    }
+
+    pub fn all(&self) -> [&Flag; 3] {
+        [
+            &self.full_text_search,
+            &self.onchain_sync,
+            &self.degraded_mode,
+        ]
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Flag> {
+        self.all().into_iter().find(|flag| flag.name == name)
+    }
+
+    pub fn settings(&self) -> [&Setting; 4] {
+        [
+            &self.slow_query_ms,
+            &self.request_timeout_short_ms,
+            &self.request_timeout_default_ms,
+            &self.request_timeout_long_ms,
+        ]
+    }
+
+    pub fn setting(&self, name: &str) -> Option<&Setting> {
+        self.settings()
+            .into_iter()
+            .find(|setting| setting.name == name)
+    }
+
+    /// Current value of every flag, for health output
+    pub fn snapshot(&self) -> BTreeMap<&'static str, bool> {
+        self.all()
+            .into_iter()
+            .map(|flag| (flag.name, flag.enabled()))
+            .collect()
+    }
+
+    /// Applies the stored overrides; flags without one go back to their
+    /// default. Unknown names (e.g. from a newer release) are ignored.
+    pub fn apply_overrides(&self, overrides: &HashMap<String, bool>) {
+        for flag in self.all() {
+            flag.set(overrides.get(flag.name).copied().unwrap_or(flag.base));
+        }
+    }
+
+    /// Same as [`Self::apply_overrides`], for the settings
+    pub fn apply_setting_overrides(&self, overrides: &HashMap<String, u64>) {
+        for setting in self.settings() {
+            setting.set(overrides.get(setting.name).copied().unwrap_or(setting.base));
+        }
+    }
+
+    /// Re-reads the overrides from `feature_flags` and `runtime_settings`.
+    pub async fn refresh(&self, pool: &PgPool) -> Result<(), sqlx::Error> {
+        let rows: Vec<(String, bool)> = sqlx::query_as("SELECT name, enabled FROM feature_flags")
+            .fetch_all(pool)
+            .await?;
+        self.apply_overrides(&rows.into_iter().collect());
+
+        let rows: Vec<(String, i64)> = sqlx::query_as("SELECT name, value FROM runtime_settings")
+            .fetch_all(pool)
+            .await?;
+        self.apply_setting_overrides(
+            &rows
+                .into_iter()
+                .map(|(name, value)| (name, value.max(1) as u64))
+                .collect(),
+        );
+        Ok(())
+    }
+
+    /// Stores an override and applies it to this instance immediately; other
+    /// instances pick it up on their next refresh.
+    pub async fn set(
+        &self,
+        pool: &PgPool,
+        flag: &Flag,
+        enabled: bool,
+        updated_by: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO feature_flags (name, enabled, updated_by, updated_at)
+             VALUES ($1, $2, $3, NOW())
+             ON CONFLICT (name) DO UPDATE
+                SET enabled = EXCLUDED.enabled,
+                    updated_by = EXCLUDED.updated_by,
+                    updated_at = NOW()",
+        )
+        .bind(flag.name)
+        .bind(enabled)
+        .bind(updated_by)
+        .execute(pool)
+        .await?;
+        flag.set(enabled);
+        Ok(())
+    }
+
+    /// Same as [`Self::set`], for a setting
+    pub async fn set_setting(
+        &self,
+        pool: &PgPool,
+        setting: &Setting,
+        value: u64,
+        updated_by: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO runtime_settings (name, value, updated_by, updated_at)
+             VALUES ($1, $2, $3, NOW())
+             ON CONFLICT (name) DO UPDATE
+                SET value = EXCLUDED.value,
+                    updated_by = EXCLUDED.updated_by,
+                    updated_at = NOW()",
+        )
+        .bind(setting.name)
+        .bind(value as i64)
+        .bind(updated_by)
+        .execute(pool)
+        .await?;
+        setting.set(value);
+        Ok(())
+    }
+
+    /// Loads the overrides immediately and then every `refresh_interval`
+    /// until shutdown. While the table is unreachable the last values stay.
+    pub fn spawn_refresh(self: &Arc<Self>, pool: PgPool, shutdown: &Shutdown) {
+        let flags = self.clone();
+        let token = shutdown.token();
+        shutdown.spawn(async move {
+            let mut ticker = tokio::time::interval(flags.refresh_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = ticker.tick() => {
+                        if let Err(err) = flags.refresh(&pool).await {
+                            tracing::warn!(error = %err, "Failed to refresh feature flags, keeping current values");
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_replaces_the_defaults() {
+        let mut config = Config::for_tests();
+        config.feature_defaults.insert("full_text_search", false);
+        config.feature_refresh_interval = Duration::from_secs(5);
+        let flags = FeatureFlags::from_config(&config);
+
+        assert!(!flags.full_text_search.enabled());
+        assert!(!flags.full_text_search.view().overridden);
+        assert!(flags.onchain_sync.enabled());
+        assert_eq!(flags.refresh_interval, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn setting_overrides_apply_and_clear() {
+        let mut config = Config::for_tests();
+        config.setting_defaults.insert("slow_query_ms", 250);
+        let flags = FeatureFlags::from_config(&config);
+        assert_eq!(flags.slow_query_ms.millis(), Duration::from_millis(250));
+        assert!(!flags.slow_query_ms.view().overridden);
+
+        flags.apply_setting_overrides(&HashMap::from([("slow_query_ms".to_string(), 40)]));
+        assert_eq!(flags.slow_query_ms.get(), 40);
+        assert!(flags.slow_query_ms.view().overridden);
+        assert_eq!(flags.request_timeout_long_ms.get(), 300_000);
+
+        flags.apply_setting_overrides(&HashMap::new());
+        assert_eq!(flags.slow_query_ms.get(), 250);
+    }
+
+    #[test]
+    fn overrides_apply_and_clear() {
+        let flags = FeatureFlags::default();
+        let overrides = HashMap::from([
+            ("full_text_search".to_string(), false),
+            ("graphql".to_string(), true),
+        ]);
+
+        flags.apply_overrides(&overrides);
+        assert!(!flags.full_text_search.enabled());
+        assert!(flags.full_text_search.view().overridden);
+        assert!(flags.get("graphql").is_none());
+
+        flags.apply_overrides(&HashMap::new());
+        assert!(flags.full_text_search.enabled());
+        assert_eq!(
+            flags.snapshot(),
+            BTreeMap::from([
+                ("degraded_mode", true),
+                ("full_text_search", true),
+                ("onchain_sync", true),
+            ])
+        );
+    }
+}
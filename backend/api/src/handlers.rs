@@ -2,9 +2,10 @@ pub mod reviews;
 
 use crate::validation::extractors::ValidatedJson;
 use axum::{
+    body::Body,
     extract::{
         rejection::{JsonRejection, QueryRejection},
-        Path, Query, State,
+        Extension, Path, Query, State,
     },
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
@@ -14,22 +15,24 @@ use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use serde_json::{json, Value};
 use shared::{
-    pagination::Cursor, AdvancedSearchRequest, AnalyticsEventType, AuditActionType,
-    ChangePublisherRequest, Contract, ContractAnalyticsResponse, ContractAuditLog,
-    ContractChangelogEntry, ContractChangelogResponse, ContractGetResponse,
-    ContractInteractionResponse, ContractSearchParams, ContractSource, ContractVersion,
-    CreateContractVersionRequest, CreateInteractionBatchRequest, CreateInteractionRequest,
-    DeploymentStats, FavoriteSearch, FieldOperator, InteractionTimeSeriesPoint,
-    InteractionTimeSeriesResponse, InteractionsListResponse, InteractionsQueryParams,
-    InteractorStats, Network, NetworkConfig, NetworkEndpoints, NetworkInfo, NetworkListResponse,
-    NetworkStatus, PaginatedResponse, PublishRequest, Publisher, QueryCondition, QueryNode,
-    QueryOperator, SaveFavoriteSearchRequest, SearchSuggestion, SearchSuggestionsResponse, SemVer,
-    TimelineEntry, TopUser, TrendingParams, UpdateContractMetadataRequest,
-    UpdateContractStatusRequest, VerifyRequest,
+    pagination::{Cursor, Paginated},
+    search::{self, ContractSearchHit, SearchHighlight},
+    AdvancedSearchRequest, AnalyticsEventType, AuditActionType, ChangePublisherRequest, Contract,
+    ContractAnalyticsResponse, ContractAuditLog, ContractChangelogEntry, ContractChangelogResponse,
+    ContractGetResponse, ContractInteractionResponse, ContractSearchParams, ContractSource,
+    ContractVersion, CreateContractVersionRequest, CreateInteractionBatchRequest,
+    CreateInteractionRequest, DeploymentStats, FavoriteSearch, FieldOperator,
+    InteractionTimeSeriesPoint, InteractionTimeSeriesResponse, InteractionsListResponse,
+    InteractionsQueryParams, InteractorStats, Network, NetworkConfig, NetworkEndpoints,
+    NetworkInfo, NetworkListResponse, NetworkStatus, OrganizationRole, PublishRequest,
+    PublishStatus, Publisher, QueryCondition, QueryNode, QueryOperator, SaveFavoriteSearchRequest,
+    SearchSuggestion, SearchSuggestionsResponse, SemVer, TimelineEntry, TopUser, TrendingParams,
+    UpdateContractMetadataRequest, UpdateContractStatusRequest, VerifyRequest, VisibilityType,
 };
 use sqlx::QueryBuilder;
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
+use tokio::io::AsyncReadExt;
 use uuid::Uuid;
 
 /// Query params for GET /contracts/:id (Issue #43)
@@ -45,23 +48,58 @@ pub struct BatchContractsQuery {
     /// Comma-separated list of fields to include in each contract result.
     /// Example: fields=name,address,network
     pub fields: Option<String>,
+    /// Set to `false` for the legacy array in request order, with `null` for
+    /// unknown ids (deprecated, removed next release)
+    #[serde(default = "default_true")]
+    pub envelope: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Most contract ids accepted by one batch lookup
+const MAX_BATCH_CONTRACTS: usize = 100;
+
+/// Response of POST /api/contracts/batch
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct BatchContractsResponse {
+    /// Every requested id, as sent, mapped to its contract or to
+    /// `{"error": "not_found"}`
+    #[schema(value_type = Object)]
+    pub contracts: std::collections::BTreeMap<String, Value>,
+    /// Requested ids that matched no contract, in request order
+    pub missing: Vec<String>,
 }
 
 use crate::{
-    analytics,
+    advisory_handlers, advisory_matcher, analytics,
+    auth::AuthClaims,
     breaking_changes::{diff_abi, has_breaking_changes, resolve_abi},
+    build_info_handlers, byte_range, component_health,
     contract_events::{ContractEventEnvelope, ContractEventVisibility},
-    dependency,
+    contract_freshness, contract_profile,
+    contract_revision::{self, Tagged},
+    contract_spec, dependency,
     error::{ApiError, ApiResult},
-    onchain_verification::OnChainVerifier,
+    incident_watchdog::{self, Component},
+    interface_compat::{self, VersionCompat},
+    jobs::{self, Job, JobKind},
+    onchain_verification::{OnChainVerificationResult, OnChainVerifier},
+    org_handlers,
+    outbound_http::OutboundClient,
+    pagination::{PageParams, Pagination},
+    profile_uploads, publish_screening, search_weights, slug_handlers,
     state::AppState,
+    subscription_notifications, tag_handlers,
     type_safety::parser::parse_json_spec,
     type_safety::{generate_openapi, to_json, to_yaml},
+    validation::payload_size::{self, BodyLimits},
+    wasm_metadata::{self, WasmMetadata},
 };
 
 pub(crate) fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
-    tracing::error!(operation = operation, error = ?err, "database operation failed");
-    ApiError::internal("An unexpected database error occurred")
+    ApiError::from_sqlx(operation, err)
 }
 
 #[allow(dead_code)]
@@ -72,7 +110,7 @@ fn map_json_rejection(err: JsonRejection) -> ApiError {
     )
 }
 
-fn map_query_rejection(err: QueryRejection) -> ApiError {
+pub(crate) fn map_query_rejection(err: QueryRejection) -> ApiError {
     ApiError::bad_request(
         "InvalidQuery",
         format!("Invalid query parameters: {}", err.body_text()),
@@ -109,7 +147,7 @@ async fn track_contract_access(state: &AppState, contract_id: Uuid) {
     }
 
     let db = state.db.clone();
-    tokio::spawn(async move {
+    crate::request_tracing::spawn(async move {
         if let Err(err) = sqlx::query("UPDATE contracts SET last_accessed_at = NOW() WHERE id = $1")
             .bind(contract_id)
             .execute(&db)
@@ -292,18 +330,18 @@ fn derive_network_status(
     (NetworkStatus::Online, None)
 }
 
-async fn probe_network_health(client: &reqwest::Client, health_url: &str) -> bool {
-    match client.get(health_url).send().await {
+async fn probe_network_health(client: &OutboundClient, health_url: &str) -> bool {
+    let request = client.get(health_url).timeout(Duration::from_secs(5));
+    match client.send(request).await {
         Ok(response) => response.status().is_success(),
         Err(_) => false,
     }
 }
 
-async fn fetch_network_catalog(db: &sqlx::PgPool) -> Result<NetworkListResponse, sqlx::Error> {
-    let client = reqwest::Client::builder()
-        .timeout(Duration::from_secs(5))
-        .build()
-        .unwrap_or_else(|_| reqwest::Client::new());
+async fn fetch_network_catalog(
+    db: &sqlx::PgPool,
+    client: &OutboundClient,
+) -> Result<NetworkListResponse, sqlx::Error> {
     let now = chrono::Utc::now();
 
     let mut networks = Vec::new();
@@ -318,7 +356,7 @@ async fn fetch_network_catalog(db: &sqlx::PgPool) -> Result<NetworkListResponse,
         .fetch_optional(db)
         .await?;
 
-        let rpc_healthy = probe_network_health(&client, &health_url).await;
+        let rpc_healthy = probe_network_health(client, &health_url).await;
         let (status, status_message) = derive_network_status(rpc_healthy, snapshot.as_ref(), now);
 
         networks.push(NetworkInfo {
@@ -350,7 +388,7 @@ async fn fetch_network_catalog(db: &sqlx::PgPool) -> Result<NetworkListResponse,
 }
 
 async fn refresh_network_catalog_cache(state: &AppState) -> Result<NetworkListResponse, ApiError> {
-    let response = fetch_network_catalog(&state.db)
+    let response = fetch_network_catalog(&state.db, &state.outbound)
         .await
         .map_err(|err| db_internal_error("fetch network catalog", err))?;
 
@@ -369,15 +407,29 @@ async fn refresh_network_catalog_cache(state: &AppState) -> Result<NetworkListRe
     Ok(response)
 }
 
-pub async fn run_network_catalog_refresh(state: AppState) {
+pub async fn run_network_catalog_refresh(
+    state: AppState,
+    shutdown: tokio_util::sync::CancellationToken,
+) {
     let mut interval = tokio::time::interval(Duration::from_secs(NETWORKS_REFRESH_INTERVAL_SECS));
 
     loop {
-        interval.tick().await;
-        if let Err(err) = refresh_network_catalog_cache(&state).await {
-            tracing::warn!(error = ?err, "failed to refresh network catalog cache");
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
+        if !state.flags.onchain_sync.enabled() {
+            continue;
+        }
+        match refresh_network_catalog_cache(&state).await {
+            Ok(_) => incident_watchdog::report_success(Component::NetworkSync),
+            Err(err) => {
+                tracing::warn!(error = ?err, "failed to refresh network catalog cache");
+                incident_watchdog::report_failure(Component::NetworkSync, &err.to_string());
+            }
         }
     }
+    tracing::info!("network catalog refresh stopped");
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, sqlx::Type)]
@@ -415,51 +467,6 @@ fn default_audit_limit() -> i64 {
     100
 }
 
-#[derive(Debug, serde::Deserialize)]
-pub struct PublisherContractsQuery {
-    #[serde(default = "default_contracts_limit")]
-    pub limit: i64,
-    #[serde(default)]
-    pub offset: i64,
-}
-
-fn default_contracts_limit() -> i64 {
-    20
-}
-
-const DEFAULT_CONTRACT_LIST_LIMIT: i64 = 50;
-const MAX_CONTRACT_LIST_LIMIT: i64 = 1000;
-
-fn validate_contract_list_pagination(
-    params: &ContractSearchParams,
-) -> Result<(i64, i64, i64), ApiError> {
-    let limit = params.limit.unwrap_or(DEFAULT_CONTRACT_LIST_LIMIT);
-    if !(1..=MAX_CONTRACT_LIST_LIMIT).contains(&limit) {
-        return Err(ApiError::bad_request(
-            "InvalidPaginationLimit",
-            format!(
-                "Invalid `limit` value {limit}. Expected an integer between 1 and {MAX_CONTRACT_LIST_LIMIT}."
-            ),
-        ));
-    }
-
-    if let Some(offset) = params.offset {
-        if offset < 0 {
-            return Err(ApiError::bad_request(
-                "InvalidPaginationOffset",
-                format!("Invalid `offset` value {offset}. Expected a non-negative integer."),
-            ));
-        }
-
-        let page = (offset / limit) + 1;
-        return Ok((limit, offset, page));
-    }
-
-    let page = params.page.unwrap_or(1).max(1);
-    let offset = (page - 1).max(0) * limit;
-    Ok((limit, offset, page))
-}
-
 fn extract_ip_address(headers: &HeaderMap) -> String {
     if let Some(forwarded_for) = headers
         .get("x-forwarded-for")
@@ -842,7 +849,8 @@ pub async fn health_check(State(state): State<AppState>) -> (StatusCode, Json<Va
         Json(json!({
             "status": "healthy",
             "version": VERSION,
-            "timestamp": now
+            "timestamp": now,
+            "feature_flags": state.flags.snapshot()
         })),
     )
 }
@@ -962,7 +970,8 @@ pub async fn health_check_detailed(State(state): State<AppState>) -> (StatusCode
             "dependencies": {
                 "database": db_health,
                 "cache": cache_health
-            }
+            },
+            "feature_flags": state.flags.snapshot()
         })),
     )
 }
@@ -977,18 +986,18 @@ pub async fn health_check_detailed(State(state): State<AppState>) -> (StatusCode
 )]
 pub async fn get_stats(State(state): State<AppState>) -> ApiResult<Json<Value>> {
     let total_contracts: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM contracts")
-        .fetch_one(&state.db)
+        .fetch_one(state.read_pool())
         .await
         .map_err(|err| db_internal_error("count contracts", err))?;
 
     let verified_contracts: i64 =
         sqlx::query_scalar("SELECT COUNT(*) FROM contracts WHERE is_verified = true")
-            .fetch_one(&state.db)
+            .fetch_one(state.read_pool())
             .await
             .map_err(|err| db_internal_error("count verified contracts", err))?;
 
     let total_publishers: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM publishers")
-        .fetch_one(&state.db)
+        .fetch_one(state.read_pool())
         .await
         .map_err(|err| db_internal_error("count publishers", err))?;
 
@@ -1074,7 +1083,8 @@ pub async fn get_contract_search_suggestions(
                     CASE WHEN lower(name) LIKE $2 THEN 1.0 ELSE 0.0 END
                 ) AS score
             FROM contracts
-            WHERE lower(name) LIKE $2 OR lower(name) % $1
+            WHERE publish_status = 'published'
+              AND (lower(name) LIKE $2 OR lower(name) % $1)
 
             UNION ALL
 
@@ -1087,6 +1097,7 @@ pub async fn get_contract_search_suggestions(
                 ) AS score
             FROM contracts
             WHERE category IS NOT NULL
+              AND publish_status = 'published'
               AND (lower(category) LIKE $2 OR lower(category) % $1)
         )
         SELECT text, kind, score
@@ -1099,7 +1110,7 @@ pub async fn get_contract_search_suggestions(
     .bind(&normalized)
     .bind(&prefix)
     .bind(limit)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_internal_error("fetch search suggestions", err))?;
 
@@ -1132,7 +1143,7 @@ pub async fn get_contract_search_suggestions(
     path = "/api/contracts",
     params(ContractSearchParams),
     responses(
-        (status = 200, description = "List of contracts", body = PaginatedResponse<Contract>),
+        (status = 200, description = "List of contracts, with description fragments matching `query`", body = Paginated<ContractSearchHit>),
         (status = 400, description = "Invalid query parameters")
     ),
     tag = "Contracts"
@@ -1140,6 +1151,7 @@ pub async fn get_contract_search_suggestions(
 pub async fn list_contracts(
     State(state): State<AppState>,
     claims: Option<shared::AuthClaims>,
+    pagination: Result<Pagination, ApiError>,
     params: Result<Query<ContractSearchParams>, QueryRejection>,
 ) -> axum::response::Response {
     let search_started_at = std::time::Instant::now();
@@ -1147,18 +1159,13 @@ pub async fn list_contracts(
         Ok(q) => q,
         Err(err) => return map_query_rejection(err).into_response(),
     };
-
-    let (limit, offset, page) = match validate_contract_list_pagination(&params) {
-        Ok(values) => values,
+    let Pagination { limit, offset } = match pagination {
+        Ok(pagination) => pagination,
         Err(err) => return err.into_response(),
     };
 
     let cursor = params.cursor.as_ref().and_then(|c| Cursor::decode(c).ok());
-    let (page, offset) = if cursor.is_some() {
-        (1, 0)
-    } else {
-        (page, offset)
-    };
+    let offset = if cursor.is_some() { 0 } else { offset };
 
     let sort_by = params.sort_by.clone().unwrap_or_else(|| {
         if params.query.is_some() {
@@ -1192,81 +1199,27 @@ pub async fn list_contracts(
         "DESC"
     };
 
-    // Weights for ranking (configurable via parameters or default)
-    let w_text = params.w_text.unwrap_or(1.0);
-    let w_pop = params.w_pop.unwrap_or(0.5);
-    let w_rec = params.w_rec.unwrap_or(0.3);
-    let w_rat = params.w_rat.unwrap_or(0.4);
-    let w_pers = 0.5; // Weight for personalized boost
-
-    // Build dynamic query with advanced ranking
-    let mut query = String::from("WITH contract_stats AS (\n");
-    query.push_str(
-        "    SELECT 
-                c.id,
-                COUNT(DISTINCT ci.id) as interaction_count,
-                COUNT(DISTINCT cv.id) as deployment_count,
-                COALESCE(AVG(r.rating), 0) as avg_rating,
-                COUNT(DISTINCT r.id) as review_count",
+    let mut query: QueryBuilder<'_, sqlx::Postgres> = QueryBuilder::new(
+        "SELECT c.* FROM contracts c \
+         LEFT JOIN contract_interactions ci ON ci.contract_id = c.id \
+         LEFT JOIN contract_versions cv ON cv.contract_id = c.id \
+         WHERE (c.visibility = 'public'",
     );
+    let mut count_query: QueryBuilder<'_, sqlx::Postgres> =
+        QueryBuilder::new("SELECT COUNT(*) FROM contracts c WHERE (c.visibility = 'public'");
 
-    if let Some(ref uid) = params.user_id {
-        let cleaned_uid = uid.replace('\'', "''");
-        query.push_str(&format!(
-            ",\n                COUNT(DISTINCT CASE WHEN ci.user_address = '{}' THEN ci.id END) as user_interaction_count",
-            cleaned_uid
-        ));
-    } else {
-        query.push_str(",\n                0 as user_interaction_count");
-    }
-
-    query.push_str(
-        "\n            FROM contracts c
-            LEFT JOIN contract_interactions ci ON c.id = ci.contract_id
-            LEFT JOIN contract_versions cv ON c.id = cv.contract_id
-            LEFT JOIN reviews r ON c.id = r.contract_id AND r.is_flagged = FALSE
-            GROUP BY c.id
-        ),\n",
-    );
-
-    query.push_str("ranked_contracts AS (\n");
-    query.push_str("    SELECT \n");
-    query.push_str("        c.*, \n");
-
-    if let Some(ref q) = params.query {
-        // Clean query for tsquery
-        let cleaned_q = q.replace('\'', "''");
-        query.push_str(&format!(
-            "        ts_rank_cd(c.search_vector, plainto_tsquery('english', '{}')) as text_relevance,\n",
-            cleaned_q
-        ));
-    } else {
-        query.push_str("        0.0 as text_relevance,\n");
-    }
-
-    query.push_str(&format!(
-        "        LOG(1 + cs.interaction_count + 2 * cs.deployment_count) as popularity_score,
-        1.0 / (1.0 + EXTRACT(DAYS FROM (NOW() - c.updated_at)) / 30.0) as recency_score,
-        (cs.avg_rating / 5.0) * LOG(1.0 + cs.review_count) as rating_score,
-        LOG(1 + cs.user_interaction_count) as personal_boost
-    FROM contracts c
-    JOIN contract_stats cs ON c.id = cs.id
-    WHERE (c.visibility = 'public'"
-    ));
-
-    let mut count_query =
-        String::from("SELECT COUNT(*) FROM contracts c WHERE (c.visibility = 'public'");
-
-    if let Some(claims) = claims {
-        let visibility_clause = format!(
-            " OR (c.visibility = 'private' AND c.organization_id IN (SELECT organization_id FROM organization_members om JOIN publishers p ON om.publisher_id = p.id WHERE p.stellar_address = '{}'))",
-            claims.sub.replace('\'', "''")
-        );
-        query.push_str(&visibility_clause);
-        count_query.push_str(&visibility_clause);
+    if let Some(ref claims) = claims {
+        for builder in [&mut query, &mut count_query] {
+            builder.push(
+                " OR (c.visibility = 'private' AND c.organization_id IN (SELECT organization_id FROM organization_members om JOIN publishers p ON om.publisher_id = p.id WHERE p.stellar_address = ",
+            );
+            builder.push_bind(claims.sub.clone());
+            builder.push("))");
+        }
     }
-    query.push_str(")");
-    count_query.push_str(")");
+    // Publishes held by screening stay out of listings until approved
+    query.push(") AND c.publish_status = 'published'");
+    count_query.push(") AND c.publish_status = 'published'");
 
     if params.verified_only.unwrap_or(false) {
         query.push(" AND c.is_verified = true");
@@ -1281,12 +1234,6 @@ pub async fn list_contracts(
     }
 
     // Filter by network(s)
-    let network_list = params
-        .networks
-        .as_ref()
-        .filter(|n| !n.is_empty())
-        .cloned()
-        .or_else(|| params.network.map(|n| vec![n]));
     if let Some(ref nets) = network_list {
         query.push(" AND c.network IN (");
         let mut separated = query.separated(", ");
@@ -1303,6 +1250,109 @@ pub async fn list_contracts(
         separated.push_unseparated(")");
     }
 
+    // Filter by tag(s), matching every alias of each requested tag
+    if let Some(tags) = params.tags.as_ref().filter(|t| !t.is_empty()) {
+        let spellings = match tag_handlers::expand_tags(state.read_pool(), tags).await {
+            Ok(spellings) => spellings,
+            Err(err) => return db_internal_error("expand tag aliases", err).into_response(),
+        };
+        query.push(" AND EXISTS (SELECT 1 FROM unnest(c.tags) t WHERE lower(t) = ANY(");
+        query.push_bind(spellings.clone());
+        query.push("))");
+        count_query.push(" AND EXISTS (SELECT 1 FROM unnest(c.tags) t WHERE lower(t) = ANY(");
+        count_query.push_bind(spellings);
+        count_query.push("))");
+    }
+
+    // With matches_only, a text query also filters: it must match the name,
+    // tags, or the search document (the description when full-text search
+    // is off). Otherwise it only ranks.
+    if let Some(q) = query_filter(&params) {
+        let words: Vec<String> = q.split_whitespace().map(str::to_string).collect();
+        let spellings = match tag_handlers::expand_tags(state.read_pool(), &words).await {
+            Ok(spellings) => spellings,
+            Err(err) => return db_internal_error("expand tag aliases", err).into_response(),
+        };
+        let contains = format!("%{}%", q.to_lowercase());
+        let full_text = state.flags.full_text_search.enabled();
+        for builder in [&mut query, &mut count_query] {
+            builder.push(" AND (lower(c.name) LIKE ");
+            builder.push_bind(contains.clone());
+            if full_text {
+                builder.push(" OR c.search_document @@ contracts_build_tsquery(");
+                builder.push_bind(q.to_string());
+                builder.push(")");
+            } else {
+                builder.push(" OR lower(c.description) LIKE ");
+                builder.push_bind(contains.clone());
+            }
+            builder.push(" OR EXISTS (SELECT 1 FROM unnest(c.tags) t WHERE lower(t) = ANY(");
+            builder.push_bind(spellings.clone());
+            builder.push(")))");
+        }
+    }
+
+    // Scope to one publisher or organization
+    if let Some(ref address) = params.publisher_address {
+        query.push(" AND c.publisher_id IN (SELECT id FROM publishers WHERE stellar_address = ");
+        query.push_bind(address);
+        query.push(")");
+        count_query
+            .push(" AND c.publisher_id IN (SELECT id FROM publishers WHERE stellar_address = ");
+        count_query.push_bind(address);
+        count_query.push(")");
+    }
+
+    if let Some(ref slug_or_id) = params.organization {
+        let org = match org_handlers::resolve_organization(state.read_pool(), slug_or_id).await {
+            Ok(org) => org,
+            Err(err) => return err.into_response(),
+        };
+        query.push(" AND c.organization_id = ");
+        query.push_bind(org.id);
+        count_query.push(" AND c.organization_id = ");
+        count_query.push_bind(org.id);
+    }
+
+    // Contracts any of whose versions embed the metadata key, and a value
+    // starting with meta_value when given
+    let meta_key = params
+        .meta_key
+        .as_deref()
+        .map(str::trim)
+        .filter(|k| !k.is_empty());
+    match (meta_key, params.meta_value.as_deref()) {
+        (Some(key), value) => {
+            let prefix = value.map(|value| {
+                format!(
+                    "{}%",
+                    value
+                        .replace('\\', "\\\\")
+                        .replace('%', "\\%")
+                        .replace('_', "\\_")
+                )
+            });
+            for builder in [&mut query, &mut count_query] {
+                builder.push(
+                    " AND EXISTS (SELECT 1 FROM contract_versions cv WHERE cv.contract_id = c.id AND cv.wasm_meta -> 'entries' ? ",
+                );
+                builder.push_bind(key.to_string());
+                if let Some(ref prefix) = prefix {
+                    builder.push(" AND cv.wasm_meta -> 'entries' ->> ");
+                    builder.push_bind(key.to_string());
+                    builder.push(" LIKE ");
+                    builder.push_bind(prefix.clone());
+                }
+                builder.push(")");
+            }
+        }
+        (None, Some(_)) => {
+            return ApiError::bad_request("InvalidMetaFilter", "meta_value requires meta_key")
+                .into_response();
+        }
+        (None, None) => {}
+    }
+
     if let Some(created_from) = params.created_from {
         query.push(" AND c.created_at >= ");
         query.push_bind(created_from);
@@ -1405,7 +1455,6 @@ pub async fn list_contracts(
             query.push(", c.id ");
             query.push(id_direction);
         }
-        shared::SortBy::Deployments => "c.deployment_count".to_string(),
         shared::SortBy::Relevance => {
             if let Some(ref q) = params.query {
                 let prefix = format!("{}%", q.to_ascii_lowercase());
@@ -1413,9 +1462,34 @@ pub async fn list_contracts(
                 query.push_bind(q);
                 query.push(") THEN 3.0 WHEN lower(c.name) LIKE ");
                 query.push_bind(&prefix);
-                query.push(" THEN 1.5 ELSE 0.0 END + ts_rank_cd(c.search_document, contracts_build_tsquery(");
-                query.push_bind(q);
-                query.push("), 32)) ");
+                query.push(" THEN 1.5 ELSE 0.0 END");
+                if state.flags.full_text_search.enabled() {
+                    query.push(" + ts_rank_cd(");
+                    query.push(search_weights::RANK_WEIGHTS_SQL);
+                    query.push(", c.search_document, contracts_build_tsquery(");
+                    query.push_bind(q);
+                    query.push("), 32)");
+                }
+                // Tag aliases aren't in the search document; rank contracts
+                // tagged with a query word, or any alias of one, like a
+                // prefix match
+                let words: Vec<String> = q.split_whitespace().map(str::to_string).collect();
+                match tag_handlers::expand_tags(state.read_pool(), &words).await {
+                    Ok(spellings) if !spellings.is_empty() => {
+                        query.push(
+                            " + CASE WHEN EXISTS (SELECT 1 FROM unnest(c.tags) t WHERE lower(t) = ANY(",
+                        );
+                        query.push_bind(spellings);
+                        query.push(")) THEN 1.5 ELSE 0.0 END");
+                    }
+                    Ok(_) => {}
+                    Err(err) => {
+                        return db_internal_error("expand tag aliases", err).into_response()
+                    }
+                }
+                // Stale contracts rank below fresh ones that match as well
+                query.push(" - CASE WHEN c.stale_at IS NOT NULL THEN 1.5 ELSE 0.0 END");
+                query.push(") ");
                 query.push(direction);
                 query.push(", c.id ");
                 query.push(id_direction);
@@ -1433,15 +1507,19 @@ pub async fn list_contracts(
     query.push(" OFFSET ");
     query.push_bind(offset);
 
-    let contracts: Vec<Contract> = match query.build_query_as().fetch_all(&state.db).await {
+    let contracts: Vec<Contract> = match query.build_query_as().fetch_all(state.read_pool()).await {
         Ok(rows) => rows,
         Err(err) => {
-            tracing::error!(query = %query, error = ?err, "Search query failed");
+            tracing::error!(query = %query.sql(), error = ?err, "Search query failed");
             return db_internal_error("list contracts with ranking", err).into_response();
         }
     };
 
-    let total: i64 = match count_query.build_query_scalar().fetch_one(&state.db).await {
+    let total: i64 = match count_query
+        .build_query_scalar()
+        .fetch_one(state.read_pool())
+        .await
+    {
         Ok(v) => v,
         Err(err) => return db_internal_error("count filtered contracts", err).into_response(),
     };
@@ -1455,31 +1533,90 @@ pub async fn list_contracts(
         );
     }
 
-    let mut response = PaginatedResponse::new(contracts, total, page, limit);
+    let mut response = Paginated::new(contracts, total, limit, offset);
 
-    // Generate next cursor if we have full page
-    if response.items.len() >= limit as usize {
+    // `total` also counts rows before a cursor, so in cursor mode a full page
+    // is the only sign that more results follow
+    if cursor.is_some() {
+        response.has_more = response.items.len() >= limit as usize;
+    }
+
+    if response.has_more {
         if let Some(last) = response.items.last() {
             if let Some(timestamp) = contract_timestamp_for_sort(last, &sort_by) {
-                let next_cursor = Cursor::new(timestamp, last.id).encode();
-                response.next_cursor = Some(next_cursor);
+                response.next_cursor = Some(Cursor::new(timestamp, last.id).encode());
             }
         }
     }
 
-    // Generate prev cursor if we have items and are not on the first page
-    if params.cursor.is_some() || page > 1 {
-        if let Some(first) = response.items.first() {
-            if let Some(timestamp) = contract_timestamp_for_sort(first, &sort_by) {
-                let prev_cursor = Cursor::new(timestamp, first.id).encode();
-                response.prev_cursor = Some(prev_cursor);
-            }
-        }
-    }
+    let mut highlights = match params.query.as_deref() {
+        Some(q) => search_highlights(&state, &response.items, q).await,
+        None => HashMap::new(),
+    };
+    let response = response.map(|contract| ContractSearchHit {
+        highlights: highlights.remove(&contract.id).into_iter().collect(),
+        contract,
+    });
 
     (StatusCode::OK, Json(response)).into_response()
 }
 
+/// The text query `list_contracts` filters on, only given `matches_only`
+fn query_filter(params: &ContractSearchParams) -> Option<&str> {
+    if !params.matches_only.unwrap_or(false) {
+        return None;
+    }
+    params
+        .query
+        .as_deref()
+        .map(str::trim)
+        .filter(|q| !q.is_empty())
+}
+
+/// Description fragments showing why each contract matched `q`, from
+/// `ts_headline` when full-text search is on and by matching the query's
+/// words otherwise. Contracts that matched on name or tags alone get none.
+async fn search_highlights(
+    state: &AppState,
+    contracts: &[Contract],
+    q: &str,
+) -> HashMap<Uuid, SearchHighlight> {
+    if !state.flags.full_text_search.enabled() {
+        return contracts
+            .iter()
+            .filter_map(|contract| {
+                let description = contract.description.as_deref()?;
+                let highlight = search::highlight_terms("description", description, q)?;
+                Some((contract.id, highlight))
+            })
+            .collect();
+    }
+
+    let ids: Vec<Uuid> = contracts.iter().map(|contract| contract.id).collect();
+    let rows: Vec<(Uuid, String)> = match sqlx::query_as(
+        "SELECT id, ts_headline('english', description, contracts_build_tsquery($1), $2) \
+         FROM contracts \
+         WHERE id = ANY($3) AND description_search @@ contracts_build_tsquery($1)",
+    )
+    .bind(q)
+    .bind(search::headline_options())
+    .bind(&ids)
+    .fetch_all(state.read_pool())
+    .await
+    {
+        Ok(rows) => rows,
+        Err(err) => {
+            // The results are still right without highlights
+            tracing::warn!(error = ?err, "search highlight query failed");
+            return HashMap::new();
+        }
+    };
+
+    rows.into_iter()
+        .filter_map(|(id, marked)| Some((id, search::parse_headline("description", &marked)?)))
+        .collect()
+}
+
 /// Get a specific contract by ID. Optional ?network= returns network-specific config (Issue #43).
 #[utoipa::path(
     get,
@@ -1489,9 +1626,10 @@ pub async fn list_contracts(
         GetContractQuery
     ),
     responses(
-        (status = 200, description = "Contract details", body = ContractGetResponse),
+        (status = 200, description = "Contract details; the ETag is the revision edits take in If-Match", body = ContractGetResponse),
         (status = 404, description = "Contract not found"),
-        (status = 400, description = "Invalid contract ID format")
+        (status = 400, description = "Invalid contract ID format"),
+        (status = 503, description = "Not cached and the database is impaired")
     ),
     tag = "Contracts"
 )]
@@ -1500,7 +1638,7 @@ pub async fn get_contract(
     claims: Option<shared::AuthClaims>,
     Path(id): Path<String>,
     Query(query): Query<GetContractQuery>,
-) -> ApiResult<Json<ContractGetResponse>> {
+) -> ApiResult<Tagged<ContractGetResponse>> {
     let contract_uuid = Uuid::parse_str(&id).map_err(|_| {
         ApiError::bad_request(
             "InvalidContractId",
@@ -1508,22 +1646,38 @@ pub async fn get_contract(
         )
     })?;
 
-    let mut contract: Contract = sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
-        .bind(contract_uuid)
-        .fetch_one(&state.db)
-        .await
-        .map_err(|err| match err {
-            sqlx::Error::RowNotFound => ApiError::not_found(
-                "ContractNotFound",
-                format!("No contract found with ID: {}", id),
-            ),
-            _ => db_internal_error("get contract by id", err),
-        })?;
+    // Cached contracts are served through a database incident, without the
+    // parts of the response that need the database
+    let database_impaired = state
+        .component_health
+        .check(component_health::DATABASE)
+        .is_err();
+
+    let mut contract: Contract = match state.cache.get_contract(contract_uuid).await {
+        Ok(contract) => contract,
+        Err(generation) => {
+            state.component_health.check(component_health::DATABASE)?;
+            let contract: Contract = sqlx::query_as("SELECT * FROM contracts WHERE id = $1")
+                .bind(contract_uuid)
+                .fetch_one(&state.db)
+                .await
+                .map_err(|err| match err {
+                    sqlx::Error::RowNotFound => ApiError::not_found(
+                        "ContractNotFound",
+                        format!("No contract found with ID: {}", id),
+                    ),
+                    _ => db_internal_error("get contract by id", err),
+                })?;
+            state.cache.put_contract(contract.clone(), generation).await;
+            contract
+        }
+    };
 
     // Visibility check
     if contract.visibility == shared::VisibilityType::Private {
         let is_member = if let Some(ref claims) = claims {
             if let Some(org_id) = contract.organization_id {
+                state.component_health.check(component_health::DATABASE)?;
                 crate::org_handlers::check_org_role(
                     &state.pool,
                     org_id,
@@ -1566,7 +1720,7 @@ pub async fn get_contract(
     };
 
     // Record search click if applicable
-    if query.from_search.unwrap_or(false) {
+    if query.from_search.unwrap_or(false) && !database_impaired {
         let _ = analytics::record_event(
             &state.db,
             shared::AnalyticsEventType::SearchClick,
@@ -1581,24 +1735,76 @@ pub async fn get_contract(
         )
         .await;
     }
-    track_contract_access(&state, contract.id).await;
+    let (incident_summary, ratings, security_notices, performance, latest_profile, readme) =
+        if database_impaired {
+            (None, None, Vec::new(), None, None, None)
+        } else {
+            track_contract_access(&state, contract.id).await;
+            let incident_summary =
+                crate::incident_handlers::contract_incident_summary(state.read_pool(), contract.id)
+                    .await
+                    .map_err(|err| db_internal_error("summarize contract incidents", err))?;
+            let ratings = reviews::rating_stats(state.read_pool(), contract.id)
+                .await
+                .map_err(|err| db_internal_error("fetch rating stats", err))?;
+            let security_notices =
+                advisory_handlers::contract_security_notices(state.read_pool(), contract.id)
+                    .await
+                    .map_err(|err| db_internal_error("fetch security notices", err))?;
+            let performance =
+                contract_profile::contract_profile_summary(state.read_pool(), contract.id)
+                    .await
+                    .map_err(|err| db_internal_error("fetch profile summary", err))?;
+            let latest_profile =
+                profile_uploads::latest_profile_summary(state.read_pool(), contract.id)
+                    .await
+                    .map_err(|err| db_internal_error("fetch uploaded profile summary", err))?;
+            let readme: (Option<String>, bool) =
+                sqlx::query_as("SELECT readme, index_readme FROM contracts WHERE id = $1")
+                    .bind(contract.id)
+                    .fetch_one(state.read_pool())
+                    .await
+                    .map_err(|err| db_internal_error("fetch contract readme", err))?;
+            (
+                incident_summary,
+                Some(ratings),
+                security_notices,
+                performance,
+                latest_profile,
+                Some(readme),
+            )
+        };
+    let (readme, index_readme) = match readme {
+        Some((readme, index_readme)) => (readme, Some(index_readme)),
+        None => (None, None),
+    };
 
-    Ok(Json(ContractGetResponse {
-        contract,
-        current_network,
-        network_config,
-    }))
+    Ok(contract_revision::tagged(
+        contract.revision,
+        ContractGetResponse {
+            contract,
+            current_network,
+            network_config,
+            incident_summary,
+            ratings,
+            security_notices,
+            performance,
+            latest_profile,
+            readme,
+            index_readme,
+        },
+    ))
 }
 
-/// Fetch multiple contracts in a single request, preserving request order.
+/// Fetch multiple contracts, by UUID or contract address, in a single query.
 #[utoipa::path(
     post,
     path = "/api/contracts/batch",
     params(BatchContractsQuery),
     request_body = Vec<String>,
     responses(
-        (status = 200, description = "Batch contract results in request order", body = [Object]),
-        (status = 400, description = "Invalid request")
+        (status = 200, description = "Requested ids mapped to their contracts, with unknown ids listed in `missing`", body = BatchContractsResponse),
+        (status = 400, description = "More than 100 ids")
     ),
     tag = "Contracts"
 )]
@@ -1606,12 +1812,13 @@ pub async fn get_contracts_batch(
     State(state): State<AppState>,
     Query(query): Query<BatchContractsQuery>,
     Json(contract_ids): Json<Vec<String>>,
-) -> ApiResult<Json<Vec<Option<Value>>>> {
-    if contract_ids.len() > 100 {
+) -> ApiResult<Response> {
+    if contract_ids.len() > MAX_BATCH_CONTRACTS {
         return Err(ApiError::bad_request(
             "BatchTooLarge",
             format!(
-                "Maximum of 100 contract IDs allowed, received {}",
+                "Maximum of {} contract IDs allowed, received {}",
+                MAX_BATCH_CONTRACTS,
                 contract_ids.len()
             ),
         ));
@@ -1650,19 +1857,46 @@ pub async fn get_contracts_batch(
         by_uuid.insert(contract.id, contract);
     }
 
-    let mut ordered_results: Vec<Option<Value>> = Vec::with_capacity(contract_ids.len());
-    for requested in contract_ids {
+    let lookup = |requested: &str| {
         let trimmed = requested.trim();
-
-        let contract = Uuid::parse_str(trimmed)
+        Uuid::parse_str(trimmed)
             .ok()
             .and_then(|id| by_uuid.get(&id))
-            .or_else(|| by_contract_id.get(trimmed));
+            .or_else(|| by_contract_id.get(trimmed))
+            .map(|c| contract_to_filtered_value(c, fields.as_ref()))
+    };
+
+    if !query.envelope {
+        let ordered_results: Vec<Option<Value>> = contract_ids
+            .iter()
+            .map(|requested| lookup(requested))
+            .collect();
+        let mut response = Json(ordered_results).into_response();
+        response
+            .headers_mut()
+            .insert("Deprecation", header::HeaderValue::from_static("true"));
+        return Ok(response);
+    }
 
-        ordered_results.push(contract.map(|c| contract_to_filtered_value(c, fields.as_ref())));
+    let mut response = BatchContractsResponse {
+        contracts: std::collections::BTreeMap::new(),
+        missing: Vec::new(),
+    };
+    for requested in contract_ids {
+        if response.contracts.contains_key(&requested) {
+            continue;
+        }
+        let result = match lookup(&requested) {
+            Some(contract) => contract,
+            None => {
+                response.missing.push(requested.clone());
+                json!({ "error": "not_found" })
+            }
+        };
+        response.contracts.insert(requested, result);
     }
 
-    Ok(Json(ordered_results))
+    Ok(Json(response).into_response())
 }
 
 #[utoipa::path(
@@ -1717,6 +1951,13 @@ pub struct ContractSourceResponse {
     pub source_size: i64,
     pub source_base64: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// `contractmetav0` metadata found in an uploaded WASM module
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_meta: Option<WasmMetadata>,
+    /// How the interface in an uploaded WASM module's `contractspecv0`
+    /// compares to the previous version's stored interface
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compat: Option<VersionCompat>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, utoipa::IntoParams)]
@@ -1742,28 +1983,222 @@ pub struct ContractSourceDiffResponse {
     ),
     request_body = UploadContractSourceRequest,
     responses(
-        (status = 201, description = "Source uploaded", body = ContractSourceResponse),
+        (status = 201, description = "Source uploaded; the contract's new revision is the ETag", body = ContractSourceResponse),
         (status = 400, description = "Invalid input"),
-        (status = 404, description = "Contract version not found")
+        (status = 404, description = "Contract version not found"),
+        (status = 412, description = "The contract changed since the If-Match revision")
     ),
     tag = "Source"
 )]
 pub async fn upload_contract_source(
     State(state): State<AppState>,
     Path((id, version)): Path<(String, String)>,
+    headers: HeaderMap,
     Json(req): Json<UploadContractSourceRequest>,
-) -> ApiResult<Json<ContractSourceResponse>> {
+) -> ApiResult<Tagged<ContractSourceResponse>> {
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let version_row = fetch_contract_version(&state, contract_uuid, &contract_id, &version).await?;
+    let expected = contract_revision::expected_revisions(&headers)?;
+
+    let source_bytes = BASE64
+        .decode(&req.source_base64)
+        .map_err(|_| ApiError::bad_request("InvalidBase64", "source_base64 must be base64"))?;
+
+    let source_format = parse_source_format(&req.source_format)?;
+    let revision = claim_revision(&state, contract_uuid, expected.as_deref()).await?;
+
+    let (backend, storage_key, source_hash) = state
+        .source_storage
+        .store_source(&contract_id, &version, source_format.clone(), &source_bytes)
+        .await
+        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
+
+    let (wasm_meta, compat) = match source_format {
+        shared::models::SourceFormat::Wasm => {
+            let meta = wasm_metadata::extract(&source_bytes);
+            record_wasm_meta(&state, &version_row, meta.as_ref()).await?;
+            state.cache.invalidate_contract(contract_uuid).await;
+            let interface = contract_spec::extract(&source_bytes, &contract_id);
+            let compat =
+                uploaded_compat(&state, contract_uuid, &contract_id, &version, interface).await;
+            (meta, compat)
+        }
+        shared::models::SourceFormat::Rust => (None, None),
+    };
+
+    let source_row = record_contract_source(
+        &state,
+        version_row.id,
+        source_format,
+        &backend,
+        &storage_key,
+        &source_hash,
+        source_bytes.len() as i64,
+    )
+    .await?;
+
+    Ok(contract_revision::tagged(
+        revision,
+        ContractSourceResponse {
+            id: source_row.id,
+            contract_version_id: source_row.contract_version_id,
+            source_format: source_row.source_format.to_string(),
+            storage_backend: source_row.storage_backend,
+            storage_key: source_row.storage_key,
+            source_hash: source_row.source_hash,
+            source_size: source_row.source_size,
+            source_base64: Some(req.source_base64),
+            created_at: source_row.created_at,
+            wasm_meta,
+            compat,
+        },
+    ))
+}
+
+/// How an uploaded WASM module's interface compares to the previous
+/// version's. A missing or unreadable ABI for the previous version only
+/// leaves the comparison out; it doesn't fail the upload.
+async fn uploaded_compat(
+    state: &AppState,
+    contract_uuid: Uuid,
+    contract_id: &str,
+    version: &str,
+    interface: Option<crate::type_safety::types::ContractABI>,
+) -> Option<VersionCompat> {
+    let (interface, semver) = (interface?, SemVer::parse(version)?);
+    interface_compat::against_previous(state, contract_uuid, contract_id, &semver, &interface)
+        .await
+        .unwrap_or_else(|err| {
+            tracing::warn!(
+                contract_id = %contract_id,
+                version = %version,
+                error = ?err,
+                "could not compare uploaded WASM interface with the previous version"
+            );
+            None
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/contracts/{id}/versions/{version}/wasm",
+    params(
+        ("id" = String, Path, description = "Contract UUID"),
+        ("version" = String, Path, description = "Contract version")
+    ),
+    request_body(content = Vec<u8>, content_type = "application/wasm"),
+    responses(
+        (status = 200, description = "WASM module stored; the contract's new revision is the ETag", body = ContractSourceResponse),
+        (status = 400, description = "Body is not a WASM module"),
+        (status = 404, description = "Contract version not found"),
+        (status = 412, description = "The contract changed since the If-Match revision"),
+        (status = 413, description = "Module exceeds MAX_WASM_UPLOAD_MB"),
+        (status = 415, description = "Content-Type is not application/wasm")
+    ),
+    tag = "Source"
+)]
+pub async fn upload_contract_wasm(
+    State(state): State<AppState>,
+    Extension(limits): Extension<BodyLimits>,
+    Path((id, version)): Path<(String, String)>,
+    headers: HeaderMap,
+    claims: Option<AuthClaims>,
+    body: Body,
+) -> ApiResult<Tagged<ContractSourceResponse>> {
     let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    org_handlers::authorize_contract_write(&state, claims.as_ref(), contract_uuid).await?;
+    let version_row = fetch_contract_version(&state, contract_uuid, &contract_id, &version).await?;
+    let expected = contract_revision::expected_revisions(&headers)?;
+
+    // Streamed to disk so the module is never buffered in memory; the temp
+    // file is removed when `spooled` is dropped.
+    let spooled = payload_size::spool_to_temp_file(body, limits.wasm_bytes).await?;
 
+    let mut magic = [0u8; 4];
+    let mut file = tokio::fs::File::open(spooled.file.path())
+        .await
+        .map_err(|e| ApiError::internal(format!("failed to read upload: {}", e)))?;
+    if file.read_exact(&mut magic).await.is_err() || magic != *b"\0asm" {
+        return Err(ApiError::bad_request(
+            "InvalidWasm",
+            "Request body is not a WASM module",
+        ));
+    }
+    let revision = claim_revision(&state, contract_uuid, expected.as_deref()).await?;
+
+    let (backend, storage_key, source_hash) = state
+        .source_storage
+        .store_source_file(
+            &contract_id,
+            &version,
+            shared::models::SourceFormat::Wasm,
+            spooled.file.path(),
+            spooled.sha256.clone(),
+        )
+        .await
+        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
+
+    // Only the metadata and spec sections are read back; the rest is seeked over
+    let path = spooled.file.path().to_path_buf();
+    let name = contract_id.clone();
+    let (wasm_meta, interface) = tokio::task::spawn_blocking(move || {
+        let open = || std::fs::File::open(&path).ok().map(std::io::BufReader::new);
+        (
+            open().and_then(wasm_metadata::read),
+            open().and_then(|file| contract_spec::read(file, &name)),
+        )
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("failed to read WASM metadata: {}", e)))?;
+    record_wasm_meta(&state, &version_row, wasm_meta.as_ref()).await?;
+
+    let compat = uploaded_compat(&state, contract_uuid, &contract_id, &version, interface).await;
+
+    let source_row = record_contract_source(
+        &state,
+        version_row.id,
+        shared::models::SourceFormat::Wasm,
+        &backend,
+        &storage_key,
+        &source_hash,
+        spooled.size as i64,
+    )
+    .await?;
+    state.cache.invalidate_contract(contract_uuid).await;
+
+    Ok(contract_revision::tagged(
+        revision,
+        ContractSourceResponse {
+            id: source_row.id,
+            contract_version_id: source_row.contract_version_id,
+            source_format: source_row.source_format.to_string(),
+            storage_backend: source_row.storage_backend,
+            storage_key: source_row.storage_key,
+            source_hash: source_row.source_hash,
+            source_size: source_row.source_size,
+            source_base64: None,
+            created_at: source_row.created_at,
+            wasm_meta,
+            compat,
+        },
+    ))
+}
+
+pub(crate) async fn fetch_contract_version(
+    state: &AppState,
+    contract_uuid: Uuid,
+    contract_id: &str,
+    version: &str,
+) -> ApiResult<ContractVersion> {
     let version_row: Option<ContractVersion> =
         sqlx::query_as("SELECT * FROM contract_versions WHERE contract_id = $1 AND version = $2")
             .bind(contract_uuid)
-            .bind(&version)
+            .bind(version)
             .fetch_optional(&state.db)
             .await
             .map_err(|err| db_internal_error("fetch contract version", err))?;
 
-    let version_row = version_row.ok_or_else(|| {
+    version_row.ok_or_else(|| {
         ApiError::not_found(
             "ContractVersionNotFound",
             format!(
@@ -1771,42 +2206,84 @@ pub async fn upload_contract_source(
                 version, contract_id
             ),
         )
-    })?;
+    })
+}
 
-    let source_bytes = BASE64
-        .decode(&req.source_base64)
-        .map_err(|_| ApiError::bad_request("InvalidBase64", "source_base64 must be base64"))?;
+fn parse_source_format(format: &str) -> ApiResult<shared::models::SourceFormat> {
+    match format.to_lowercase().as_str() {
+        "rust" => Ok(shared::models::SourceFormat::Rust),
+        "wasm" => Ok(shared::models::SourceFormat::Wasm),
+        other => Err(ApiError::bad_request(
+            "InvalidSourceFormat",
+            format!(
+                "Unsupported source format '{}', expected 'rust' or 'wasm'",
+                other
+            ),
+        )),
+    }
+}
 
-    let source_format = match req.source_format.to_lowercase().as_str() {
-        "rust" => shared::models::SourceFormat::Rust,
-        "wasm" => shared::models::SourceFormat::Wasm,
-        other => {
-            return Err(ApiError::bad_request(
-                "InvalidSourceFormat",
-                format!(
-                    "Unsupported source format '{}', expected 'rust' or 'wasm'",
-                    other
-                ),
-            ))
+/// Claims the contract's next revision for an upload, before anything is
+/// stored, so a stale upload fails with 412 without side effects.
+async fn claim_revision(
+    state: &AppState,
+    contract_uuid: Uuid,
+    expected: Option<&[i64]>,
+) -> ApiResult<i64> {
+    match contract_revision::bump(&state.db, contract_uuid, expected).await {
+        Ok(Some(revision)) => {
+            state.cache.invalidate_contract(contract_uuid).await;
+            Ok(revision)
         }
-    };
+        Ok(None) => Err(contract_revision::precondition_failed(&state.db, contract_uuid).await),
+        Err(err) => Err(db_internal_error("claim contract revision", err)),
+    }
+}
 
-    let (backend, storage_key, source_hash) = state
-        .source_storage
-        .store_source(&contract_id, &version, source_format.clone(), &source_bytes)
+/// Stores the metadata embedded in a version's uploaded WASM, clearing it
+/// when the module embeds none, and re-checks the contract against security
+/// advisories.
+async fn record_wasm_meta(
+    state: &AppState,
+    version: &ContractVersion,
+    meta: Option<&WasmMetadata>,
+) -> ApiResult<()> {
+    sqlx::query("UPDATE contract_versions SET wasm_meta = $2 WHERE id = $1")
+        .bind(version.id)
+        .bind(meta.map(sqlx::types::Json))
+        .execute(&state.db)
         .await
-        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
-
-    let source_size = source_bytes.len() as i64;
+        .map_err(|err| db_internal_error("record WASM metadata", err))?;
+
+    // The upload itself succeeded; stale notices are corrected on the next one
+    if let Err(err) = advisory_matcher::rematch_contract(&state.db, version.contract_id).await {
+        tracing::error!(
+            error = ?err,
+            contract_id = %version.contract_id,
+            "failed to match contract against security advisories"
+        );
+    }
+    Ok(())
+}
 
+/// Inserts the `contract_sources` row for a stored artifact and logs the upload.
+async fn record_contract_source(
+    state: &AppState,
+    contract_version_id: Uuid,
+    source_format: shared::models::SourceFormat,
+    backend: &str,
+    storage_key: &str,
+    source_hash: &str,
+    source_size: i64,
+) -> ApiResult<ContractSource> {
     let source_row: ContractSource = sqlx::query_as(
         "INSERT INTO contract_sources (contract_version_id, source_format, storage_backend, storage_key, source_hash, source_size) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
     )
-    .bind(version_row.id)
+    .bind(contract_version_id)
     .bind(source_format)
-    .bind(&backend)
-    .bind(&storage_key)
-    .bind(&source_hash)
+    .bind(backend)
+    .bind(storage_key)
+    .bind(source_hash)
     .bind(source_size)
     .fetch_one(&state.db)
     .await
@@ -1825,17 +2302,7 @@ pub async fn upload_contract_source(
     .await
     .map_err(|err| db_internal_error("insert source access log", err))?;
 
-    Ok(Json(ContractSourceResponse {
-        id: source_row.id,
-        contract_version_id: source_row.contract_version_id,
-        source_format: source_row.source_format.to_string(),
-        storage_backend: source_row.storage_backend,
-        storage_key: source_row.storage_key,
-        source_hash: source_row.source_hash,
-        source_size: source_row.source_size,
-        source_base64: Some(req.source_base64),
-        created_at: source_row.created_at,
-    }))
+    Ok(source_row)
 }
 
 #[utoipa::path(
@@ -1867,25 +2334,7 @@ pub async fn get_contract_source(
             .await
             .map_err(|err| db_internal_error("fetch contract version", err))?;
 
-    let format = query
-        .source_format
-        .as_deref()
-        .unwrap_or("rust")
-        .to_lowercase();
-
-    let source_format = match format.as_str() {
-        "rust" => shared::models::SourceFormat::Rust,
-        "wasm" => shared::models::SourceFormat::Wasm,
-        other => {
-            return Err(ApiError::bad_request(
-                "InvalidSourceFormat",
-                format!(
-                    "Unsupported source format '{}', expected 'rust' or 'wasm'",
-                    other
-                ),
-            ))
-        }
-    };
+    let source_format = parse_source_format(query.source_format.as_deref().unwrap_or("rust"))?;
 
     let source_row: ContractSource = sqlx::query_as(
         "SELECT * FROM contract_sources WHERE contract_version_id = $1 AND source_format = $2",
@@ -1930,9 +2379,98 @@ pub async fn get_contract_source(
         source_size: source_row.source_size,
         source_base64: Some(BASE64.encode(source_bytes)),
         created_at: source_row.created_at,
+        wasm_meta: None,
+        compat: None,
     }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/versions/{version}/source/download",
+    params(
+        ("id" = String, Path, description = "Contract UUID"),
+        ("version" = String, Path, description = "Contract version"),
+        ContractSourceQuery
+    ),
+    responses(
+        (status = 200, description = "Raw artifact; its SHA-256 is in the ETag header", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 206, description = "The part of the artifact asked for with Range", body = Vec<u8>, content_type = "application/octet-stream"),
+        (status = 307, description = "Redirect to a presigned URL when SOURCE_STORAGE_DOWNLOAD=presign"),
+        (status = 404, description = "Version or source not found"),
+        (status = 416, description = "Malformed Range, or one starting past the end of the artifact")
+    ),
+    tag = "Source"
+)]
+pub async fn download_contract_source(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(String, String)>,
+    Query(query): Query<ContractSourceQuery>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let version_row = fetch_contract_version(&state, contract_uuid, &contract_id, &version).await?;
+    let source_format = parse_source_format(query.source_format.as_deref().unwrap_or("wasm"))?;
+
+    let source_row: ContractSource = sqlx::query_as(
+        "SELECT * FROM contract_sources WHERE contract_version_id = $1 AND source_format = $2",
+    )
+    .bind(version_row.id)
+    .bind(&source_format)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch contract source", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "SourceNotFound",
+            format!(
+                "No {} source uploaded for version '{}' of contract {}",
+                source_format, version, contract_id
+            ),
+        )
+    })?;
+
+    let etag = format!("\"{}\"", source_row.source_hash);
+    let size = source_row.source_size.max(0) as u64;
+    let range = byte_range::requested(&headers, &etag, size)?;
+
+    let download = state
+        .source_storage
+        .download_source(
+            &source_row.storage_backend,
+            &source_row.storage_key,
+            range.map(|range| (range.start, range.end)),
+        )
+        .await
+        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
+
+    // A resumed transfer is logged with the range it asked for
+    sqlx::query(
+        "INSERT INTO source_access_logs (contract_source_id, action, actor, request_ip, user_agent, details) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(source_row.id)
+    .bind("download")
+    .bind(None::<String>)
+    .bind(None::<String>)
+    .bind(None::<String>)
+    .bind(range.map(|range| json!({ "range": range.content_range(size) })))
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("insert source access log", err))?;
+
+    let (content_type, extension) = match source_format {
+        shared::models::SourceFormat::Wasm => ("application/wasm", "wasm"),
+        shared::models::SourceFormat::Rust => ("text/plain; charset=utf-8", "rs"),
+    };
+    Ok(byte_range::respond(
+        download,
+        range,
+        size,
+        &etag,
+        content_type,
+        &format!("{}-{}.{}", contract_id, version, extension),
+    ))
+}
+
 #[utoipa::path(
     get,
     path = "/api/contracts/{id}/versions/{version}/source/diff",
@@ -2064,13 +2602,14 @@ pub async fn get_contract_source_diff(
         ("id" = String, Path, description = "Contract UUID")
     ),
     responses(
-        (status = 200, description = "Contract changelog with breaking-change markers", body = ContractChangelogResponse),
+        (status = 200, description = "Contract changelog, newest first, with WASM hash changes, size deltas and breaking-change markers", body = ContractChangelogResponse),
         (status = 400, description = "Invalid contract ID format"),
         (status = 404, description = "Contract not found")
     ),
     tag = "Versions"
 )]
-/// GET /api/contracts/:id/changelog (and /contracts/:id/changelog) — release history with breaking-change markers.
+/// GET /api/contracts/:id/changelog (and /contracts/:id/changelog) — release history with
+/// WASM hash changes, size deltas between consecutive versions and breaking-change markers.
 pub async fn get_contract_changelog(
     State(state): State<AppState>,
     Path(id): Path<String>,
@@ -2086,14 +2625,26 @@ pub async fn get_contract_changelog(
     .await
     .map_err(|err| db_internal_error("get contract versions for changelog", err))?;
 
+    let version_ids: Vec<Uuid> = versions.iter().map(|v| v.id).collect();
+    let wasm_sizes: HashMap<Uuid, i64> = sqlx::query_as::<_, (Uuid, i64)>(
+        "SELECT contract_version_id, source_size FROM contract_sources \
+         WHERE source_format = 'wasm' AND contract_version_id = ANY($1)",
+    )
+    .bind(&version_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get wasm sizes for changelog", err))?
+    .into_iter()
+    .collect();
+
     let mut entries: Vec<ContractChangelogEntry> = Vec::with_capacity(versions.len());
 
-    let mut prev_version: Option<String> = None;
+    let mut prev_version: Option<&ContractVersion> = None;
     for v in &versions {
         let mut breaking = false;
         let mut breaking_changes: Vec<String> = Vec::new();
 
-        if let Some(prev) = prev_version.as_deref() {
+        if let Some(prev) = prev_version.map(|p| p.version.as_str()) {
             let old_selector = format!("{}@{}", contract_id, prev);
             let new_selector = format!("{}@{}", contract_id, v.version);
 
@@ -2120,17 +2671,23 @@ pub async fn get_contract_changelog(
                 .collect();
         }
 
+        let wasm_size = wasm_sizes.get(&v.id).copied();
+        let prev_size = prev_version.and_then(|p| wasm_sizes.get(&p.id).copied());
         entries.push(ContractChangelogEntry {
             version: v.version.clone(),
             created_at: v.created_at,
             commit_hash: v.commit_hash.clone(),
             source_url: v.source_url.clone(),
             release_notes: v.release_notes.clone(),
+            wasm_hash: v.wasm_hash.clone(),
+            wasm_changed: prev_version.is_some_and(|p| p.wasm_hash != v.wasm_hash),
+            wasm_size,
+            size_delta: wasm_size.zip(prev_size).map(|(size, prev)| size - prev),
             breaking,
             breaking_changes,
         });
 
-        prev_version = Some(v.version.clone());
+        prev_version = Some(v);
     }
 
     // Most APIs return newest-first for timelines.
@@ -2150,18 +2707,21 @@ pub async fn get_contract_changelog(
     ),
     request_body = CreateContractVersionRequest,
     responses(
-        (status = 201, description = "Version created successfully", body = ContractVersion),
+        (status = 201, description = "Version created successfully, with how its interface compares to the previous version", body = CreatedContractVersion),
         (status = 400, description = "Invalid input or version conflict"),
-        (status = 404, description = "Contract not found")
+        (status = 404, description = "Contract not found"),
+        (status = 422, description = "Breaking changes without a major version bump, and allow_breaking not set")
     ),
     tag = "Versions"
 )]
 pub async fn create_contract_version(
     State(state): State<AppState>,
     Path(id): Path<String>,
+    claims: Option<AuthClaims>,
     ValidatedJson(req): ValidatedJson<CreateContractVersionRequest>,
-) -> ApiResult<Json<ContractVersion>> {
+) -> ApiResult<Json<CreatedContractVersion>> {
     let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    org_handlers::authorize_contract_write(&state, claims.as_ref(), contract_uuid).await?;
     if !req.contract_id.trim().is_empty() && req.contract_id != contract_id {
         return Err(ApiError::bad_request(
             "ContractMismatch",
@@ -2177,9 +2737,11 @@ pub async fn create_contract_version(
     })?;
 
     // Optional Ed25519 signature verification for this contract version.
-    // When a signature is provided, we require a matching publisher_key and
-    // verify the detached signature over "{contract_id}:{version}:{wasm_hash}".
-    let (version_signature, version_publisher_key, version_algorithm) =
+    // When a signature is provided, we require a matching publisher_key, a
+    // timestamp and a nonce, and verify the detached signature over
+    // "{contract_id}:{version}:{wasm_hash}:{signed_at}:{nonce}". The replay
+    // guard then accepts it only while recent and only once.
+    let (version_signature, version_publisher_key, version_algorithm, signed_at, nonce) =
         match (&req.signature, &req.publisher_key) {
             (Some(sig), Some(pk)) if !sig.trim().is_empty() && !pk.trim().is_empty() => {
                 // Decode public key (base64, 32 bytes)
@@ -2214,11 +2776,20 @@ pub async fn create_contract_version(
                 })?;
                 let signature = Signature::from_bytes(&sig_array);
 
+                let (Some(signed_at), Some(nonce)) = (req.signed_at, req.nonce.as_deref()) else {
+                    return Err(ApiError::bad_request(
+                        "InvalidSignatureMetadata",
+                        "signed_at and nonce are required with a signature",
+                    ));
+                };
+
                 // Construct signing message and verify
-                let message = crate::signing_handlers::create_signing_message(
-                    &req.wasm_hash,
+                let message = crate::replay_guard::signing_message(
                     &contract_id,
                     &req.version,
+                    &req.wasm_hash,
+                    signed_at,
+                    nonce,
                 );
 
                 let crypto_valid = verifying_key.verify(&message, &signature).is_ok();
@@ -2229,6 +2800,15 @@ pub async fn create_contract_version(
                     ));
                 }
 
+                // Only checked once the signature is known to be genuine, so
+                // nobody else can use up a publisher's nonces
+                let now = chrono::Utc::now().timestamp();
+                state
+                    .replay_guard
+                    .check(pk.trim(), signed_at, nonce, now)
+                    .await
+                    .map_err(|err| err.into_api_error(now, state.replay_guard.max_age()))?;
+
                 let algo = req
                     .signature_algorithm
                     .clone()
@@ -2245,14 +2825,16 @@ pub async fn create_contract_version(
                     Some(sig.trim().to_string()),
                     Some(pk.trim().to_string()),
                     Some(algo),
+                    chrono::DateTime::from_timestamp(signed_at, 0),
+                    Some(nonce.to_string()),
                 )
             }
             (None, None) => {
                 // No signature metadata provided – proceed without cryptographic binding.
-                (None, None, None)
+                (None, None, None, None, None)
             }
-            (Some(s), None) if s.trim().is_empty() => (None, None, None),
-            (None, Some(pk)) if pk.trim().is_empty() => (None, None, None),
+            (Some(s), None) if s.trim().is_empty() => (None, None, None, None, None),
+            (None, Some(pk)) if pk.trim().is_empty() => (None, None, None, None, None),
             _ => {
                 return Err(ApiError::bad_request(
                     "InvalidSignatureMetadata",
@@ -2261,55 +2843,34 @@ pub async fn create_contract_version(
             }
         };
 
-    let existing_versions: Vec<String> =
-        sqlx::query_scalar("SELECT version FROM contract_versions WHERE contract_id = $1")
-            .bind(contract_uuid)
-            .fetch_all(&state.db)
-            .await
-            .map_err(|err| db_internal_error("fetch contract versions", err))?;
-
-    if !existing_versions.is_empty() {
-        let mut parsed: Vec<SemVer> = Vec::with_capacity(existing_versions.len());
-        for version in &existing_versions {
-            let parsed_version = SemVer::parse(version).ok_or_else(|| {
-                ApiError::unprocessable(
-                    "InvalidExistingVersion",
-                    format!("Existing version '{}' is not valid semver", version),
-                )
-            })?;
-            parsed.push(parsed_version);
-        }
-        parsed.sort();
-        let latest_version = parsed.last().cloned();
-
-        if let Some(old_version) = latest_version {
-            let old_selector = format!("{}@{}", contract_id, old_version);
-            let old_abi = resolve_abi(&state, &old_selector, false).await?;
-            let old_spec = crate::type_safety::parser::parse_json_spec(&old_abi, &contract_id)
-                .map_err(|e| {
-                    ApiError::bad_request("InvalidABI", format!("Failed to parse old ABI: {}", e))
-                })?;
-
-            let new_spec =
-                crate::type_safety::parser::parse_json_spec(&req.abi.to_string(), &contract_id)
-                    .map_err(|e| {
-                        ApiError::bad_request(
-                            "InvalidABI",
-                            format!("Failed to parse new ABI: {}", e),
-                        )
-                    })?;
-
-            let changes = diff_abi(&old_spec, &new_spec);
-            if has_breaking_changes(&changes) && new_version.major == old_version.major {
-                return Err(ApiError::unprocessable(
-                    "BreakingChangeWithoutMajorBump",
-                    format!(
-                        "Breaking changes detected; bump major version from {} to {}",
-                        old_version, new_version
-                    ),
-                ));
-            }
+    let new_spec = parse_json_spec(&req.abi.to_string(), &contract_id).map_err(|e| {
+        ApiError::bad_request("InvalidABI", format!("Failed to parse new ABI: {}", e))
+    })?;
+    let compat = interface_compat::against_previous(
+        &state,
+        contract_uuid,
+        &contract_id,
+        &new_version,
+        &new_spec,
+    )
+    .await?;
+    if let Some(compat) = compat.as_ref().filter(|c| c.breaks_semver()) {
+        if !req.allow_breaking {
+            return Err(ApiError::unprocessable(
+                "BreakingChangeWithoutMajorBump",
+                format!(
+                    "Breaking changes detected; bump major version from {} to {} \
+                     or set allow_breaking",
+                    compat.previous_version, new_version
+                ),
+            ));
         }
+        tracing::warn!(
+            contract_id = %contract_id,
+            version = %req.version,
+            breaking_changes = compat.breaking_count,
+            "contract version published with breaking changes and no major bump"
+        );
     }
 
     let mut tx = state
@@ -2320,8 +2881,8 @@ pub async fn create_contract_version(
 
     let version_row: ContractVersion = sqlx::query_as(
         "INSERT INTO contract_versions \
-            (contract_id, version, wasm_hash, source_url, commit_hash, release_notes, signature, publisher_key, signature_algorithm) \
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) \
+            (contract_id, version, wasm_hash, source_url, commit_hash, release_notes, signature, publisher_key, signature_algorithm, signed_at, signature_nonce) \
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) \
          RETURNING *",
     )
     .bind(contract_uuid)
@@ -2333,6 +2894,8 @@ pub async fn create_contract_version(
     .bind(&version_signature)
     .bind(&version_publisher_key)
     .bind(&version_algorithm)
+    .bind(signed_at)
+    .bind(&nonce)
     .fetch_one(&mut *tx)
     .await
     .map_err(|err| match err {
@@ -2364,10 +2927,22 @@ pub async fn create_contract_version(
         .await
         .map_err(|err| db_internal_error("increment deployment count", err))?;
 
+    let subscribed = subscription_notifications::contract_ref(&mut *tx, contract_uuid)
+        .await
+        .map_err(|err| db_internal_error("fetch contract for subscriptions", err))?;
+    subscription_notifications::enqueue(
+        &mut *tx,
+        contract_uuid,
+        &subscription_notifications::version_notice(&subscribed, &version_row),
+    )
+    .await
+    .map_err(|err| db_internal_error("queue version notifications", err))?;
+
     tx.commit()
         .await
         .map_err(|err| db_internal_error("commit contract version", err))?;
 
+    state.cache.invalidate_contract(contract_uuid).await;
     state.cache.invalidate_abi(&contract_id).await;
     state.cache.invalidate_abi(&contract_uuid.to_string()).await;
     state
@@ -2418,10 +2993,26 @@ pub async fn create_contract_version(
             ));
     }
 
-    Ok(Json(version_row))
+    Ok(Json(CreatedContractVersion {
+        version: version_row,
+        compat,
+    }))
+}
+
+/// A created contract version; `compat` compares its interface with the
+/// previous version's and is absent for a contract's first version
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct CreatedContractVersion {
+    #[serde(flatten)]
+    pub version: ContractVersion,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compat: Option<VersionCompat>,
 }
 
-async fn fetch_contract_identity(state: &AppState, id: &str) -> ApiResult<(Uuid, String)> {
+pub(crate) async fn fetch_contract_identity(
+    state: &AppState,
+    id: &str,
+) -> ApiResult<(Uuid, String)> {
     if let Ok(uuid) = Uuid::parse_str(id) {
         let row = sqlx::query_as::<_, (Uuid, String)>(
             "SELECT id, contract_id FROM contracts WHERE id = $1",
@@ -2502,17 +3093,38 @@ async fn fetch_contract_network(
     path = "/api/contracts",
     request_body = PublishRequest,
     responses(
-        (status = 201, description = "Contract published successfully", body = Contract),
+        (status = 201, description = "Contract published, or held for review when `publish_status` is `pending_review`", body = Contract),
         (status = 400, description = "Invalid input or contract ID"),
-        (status = 409, description = "Contract already registered")
+        (status = 409, description = "Contract already registered, or its slug is taken")
     ),
     tag = "Contracts"
 )]
 pub async fn publish_contract(
     State(state): State<AppState>,
     headers: HeaderMap,
+    claims: Option<AuthClaims>,
     ValidatedJson(req): ValidatedJson<PublishRequest>,
 ) -> ApiResult<Json<Contract>> {
+    // Publishing under an organization requires maintainer rights in it
+    let organization = match req.organization.as_deref() {
+        Some(slug_or_id) => {
+            let claims = claims.as_ref().ok_or_else(|| {
+                ApiError::unauthorized(
+                    "Publishing under an organization requires an authentication token",
+                )
+            })?;
+            let org = org_handlers::resolve_organization(&state.db, slug_or_id).await?;
+            org_handlers::check_org_role(&state.db, org.id, &claims.sub, OrganizationRole::Member)
+                .await?;
+            Some(org)
+        }
+        None => None,
+    };
+    let visibility = match &organization {
+        Some(org) if org.is_private => VisibilityType::Private,
+        _ => VisibilityType::Public,
+    };
+
     let publisher: Publisher = sqlx::query_as(
         "INSERT INTO publishers (stellar_address) VALUES ($1)
          ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = EXCLUDED.stellar_address
@@ -2523,6 +3135,18 @@ pub async fn publish_contract(
     .await
     .map_err(|err| db_internal_error("upsert publisher", err))?;
 
+    let slug = req
+        .slug
+        .clone()
+        .unwrap_or_else(|| shared::slug::slugify(&req.name));
+    let namespace = slug_handlers::contract_namespace(
+        &state.db,
+        publisher.id,
+        organization.as_ref().map(|org| org.id),
+    )
+    .await?;
+    slug_handlers::ensure_slug_available(&state.db, &namespace, &slug, &req.network, None).await?;
+
     let wasm_hash = req.wasm_hash.clone();
     let network_key = req.network.to_string();
     let mut config_map = serde_json::Map::new();
@@ -2537,9 +3161,35 @@ pub async fn publish_contract(
     );
     let network_configs = serde_json::Value::Object(config_map);
 
+    // Private contracts are only seen by their organization, so they aren't
+    // screened for duplicates or spam
+    let screening = if visibility == VisibilityType::Public {
+        publish_screening::screen(
+            &state.db,
+            &state.config.screening,
+            &publish_screening::Submission {
+                name: &req.name,
+                description: req.description.as_deref(),
+                tags: &req.tags,
+                wasm_hash: &wasm_hash,
+                publisher_id: publisher.id,
+                publisher_created_at: publisher.created_at,
+            },
+        )
+        .await
+        .map_err(|err| db_internal_error("screen publish", err))?
+    } else {
+        publish_screening::Screening::default()
+    };
+    let publish_status = if screening.hold {
+        PublishStatus::PendingReview
+    } else {
+        PublishStatus::Published
+    };
+
     let contract: Contract = sqlx::query_as(
-        "INSERT INTO contracts (contract_id, wasm_hash, name, description, publisher_id, network, category, tags, logical_id, network_configs)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        "INSERT INTO contracts (contract_id, wasm_hash, name, description, publisher_id, network, category, tags, logical_id, network_configs, organization_id, visibility, namespace, slug, publish_status)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, normalize_contract_tags($8), $9, $10, $11, $12, $13, $14, $15)
          RETURNING *"
     )
     .bind(&req.contract_id)
@@ -2552,6 +3202,11 @@ pub async fn publish_contract(
     .bind(&req.tags)
     .bind(Option::<Uuid>::None as Option<Uuid>)
     .bind(&network_configs)
+    .bind(organization.as_ref().map(|org| org.id))
+    .bind(&visibility)
+    .bind(&namespace)
+    .bind(&slug)
+    .bind(publish_status)
     .fetch_one(&state.db)
     .await
     .map_err(|err| {
@@ -2566,6 +3221,16 @@ pub async fn publish_contract(
                     ),
                 );
             }
+            // Lost a race with another publish of the same name
+            if e.constraint() == Some("contracts_namespace_slug_network_key") {
+                return ApiError::conflict(
+                    "SlugTaken",
+                    format!(
+                        "{}/{} is already taken on {}",
+                        namespace, slug, req.network
+                    ),
+                );
+            }
         }
         db_internal_error("create contract", err)
     })?;
@@ -2582,6 +3247,18 @@ pub async fn publish_contract(
         .await
         .map_err(|err| db_internal_error("fetch contract after insert", err))?;
 
+    if !screening.flags.is_empty() {
+        publish_screening::record_flags(&state.db, contract.id, &screening.flags)
+            .await
+            .map_err(|err| db_internal_error("record publish flags", err))?;
+        tracing::info!(
+            contract_id = %contract.contract_id,
+            flags = screening.flags.len(),
+            held = screening.hold,
+            "publish flagged by screening"
+        );
+    }
+
     // Save dependencies if provided
     if !req.dependencies.is_empty() {
         if let Err(e) =
@@ -2608,7 +3285,8 @@ pub async fn publish_contract(
         "network": { "before": Value::Null, "after": contract.network.to_string() },
         "is_verified": { "before": Value::Null, "after": contract.is_verified },
         "category": { "before": Value::Null, "after": contract.category },
-        "tags": { "before": Value::Null, "after": contract.tags }
+        "tags": { "before": Value::Null, "after": contract.tags },
+        "slug": { "before": Value::Null, "after": contract.slug }
     });
 
     write_contract_audit_log(
@@ -2651,12 +3329,15 @@ pub async fn publish_contract(
     )
     .await;
 
-    state
-        .contract_events
-        .publish(ContractEventEnvelope::deployed(
-            &contract,
-            Some(publisher.stellar_address.clone()),
-        ));
+    // Held publishes are announced when an admin approves them
+    if publish_status == PublishStatus::Published {
+        state
+            .contract_events
+            .publish(ContractEventEnvelope::deployed(
+                &contract,
+                Some(publisher.stellar_address.clone()),
+            ));
+    }
 
     if req.is_cicd {
         crate::events::emit_cicd_pipeline(
@@ -2754,10 +3435,11 @@ pub async fn get_publisher(
     get,
     path = "/api/publishers/{id}/contracts",
     params(
-        ("id" = String, Path, description = "Publisher UUID")
+        ("id" = String, Path, description = "Publisher UUID"),
+        PageParams
     ),
     responses(
-        (status = 200, description = "List of contracts by publisher", body = [Contract]),
+        (status = 200, description = "List of contracts by publisher", body = Paginated<Contract>),
         (status = 404, description = "Publisher not found")
     ),
     tag = "Publishers"
@@ -2765,8 +3447,8 @@ pub async fn get_publisher(
 pub async fn get_publisher_contracts(
     State(state): State<AppState>,
     Path(id): Path<String>,
-    Query(query): Query<PublisherContractsQuery>,
-) -> ApiResult<Json<PaginatedResponse<Contract>>> {
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Json<Paginated<Contract>>> {
     let publisher_uuid = Uuid::parse_str(&id).map_err(|_| {
         ApiError::bad_request(
             "InvalidPublisherId",
@@ -2774,14 +3456,10 @@ pub async fn get_publisher_contracts(
         )
     })?;
 
-    // Validate and cap limit (max 100)
-    let limit = query.limit.clamp(1, 100);
-    let offset = query.offset.max(0);
-
     // Get total count
     let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM contracts WHERE publisher_id = $1")
         .bind(publisher_uuid)
-        .fetch_one(&state.db)
+        .fetch_one(state.read_pool())
         .await
         .map_err(|err| db_internal_error("get publisher contracts count", err))?;
 
@@ -2792,14 +3470,116 @@ pub async fn get_publisher_contracts(
     .bind(publisher_uuid)
     .bind(limit)
     .bind(offset)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_internal_error("get publisher contracts", err))?;
 
-    let page = (offset / limit) + 1;
-    let response = PaginatedResponse::new(contracts, total, page, limit);
+    Ok(Json(Paginated::new(contracts, total, limit, offset)))
+}
 
-    Ok(Json(response))
+/// Query params for GET /api/me/contracts
+#[derive(Debug, serde::Deserialize, utoipa::IntoParams)]
+pub struct MyContractsQuery {
+    /// Only list contracts on this network
+    pub network: Option<Network>,
+}
+
+/// A contract the caller owns, with the state public listings leave out
+#[derive(Debug, serde::Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct OwnedContract {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub contract: Contract,
+    /// `publisher` when the caller published it, `organization` when it
+    /// belongs to one of the caller's organizations
+    pub owned_via: String,
+    /// Status of the latest verification request: `pending`, `verified`
+    /// or `failed`, or `unverified` when none was made
+    pub verification_status: String,
+    /// `deployed`, `hash_mismatch` or `missing` from the last on-chain
+    /// check, `unknown` when no recent check is cached
+    #[sqlx(skip)]
+    pub on_chain_status: String,
+    pub last_seen_on_chain_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub deprecated_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub retirement_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Contracts published by the caller or owned by one of their
+/// organizations, including unverified, deprecated and private ones
+#[utoipa::path(
+    get,
+    path = "/api/me/contracts",
+    params(MyContractsQuery, PageParams),
+    responses(
+        (status = 200, description = "Contracts owned by the caller", body = Paginated<OwnedContract>),
+        (status = 401, description = "Missing or invalid token")
+    ),
+    security(("bearer_auth" = [])),
+    tag = "Publishers"
+)]
+pub async fn list_my_contracts(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Query(query): Query<MyContractsQuery>,
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Json<Paginated<OwnedContract>>> {
+    const OWNED: &str = "
+        FROM contracts c
+        JOIN publishers p ON p.id = c.publisher_id
+        LEFT JOIN contract_deprecations d ON d.contract_id = c.id
+        WHERE (p.stellar_address = $1
+               OR c.organization_id IN (
+                   SELECT om.organization_id FROM organization_members om
+                   JOIN publishers mp ON mp.id = om.publisher_id
+                   WHERE mp.stellar_address = $1))
+          AND ($2::network_type IS NULL OR c.network = $2)";
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) {}", OWNED))
+        .bind(&claims.sub)
+        .bind(&query.network)
+        .fetch_one(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("count my contracts", err))?;
+
+    let mut contracts: Vec<OwnedContract> = sqlx::query_as(&format!(
+        "SELECT c.*,
+                CASE WHEN p.stellar_address = $1 THEN 'publisher' ELSE 'organization' END AS owned_via,
+                COALESCE(
+                    (SELECT v.status::text FROM verifications v
+                     WHERE v.contract_id = c.id ORDER BY v.created_at DESC LIMIT 1),
+                    CASE WHEN c.is_verified THEN 'verified' ELSE 'unverified' END
+                ) AS verification_status,
+                d.deprecated_at, d.retirement_at
+         {}
+         ORDER BY c.created_at DESC LIMIT $3 OFFSET $4",
+        OWNED
+    ))
+    .bind(&claims.sub)
+    .bind(&query.network)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list my contracts", err))?;
+
+    for owned in &mut contracts {
+        let key = OnChainVerificationResult::cache_key(&owned.contract);
+        let check = state
+            .cache
+            .get_verification(&key)
+            .await
+            .and_then(|cached| serde_json::from_str::<OnChainVerificationResult>(&cached).ok());
+        owned.on_chain_status = match check {
+            Some(check) if !check.contract_exists_on_chain => "missing",
+            Some(check) if !check.wasm_hash_matches => "hash_mismatch",
+            Some(_) => "deployed",
+            None => "unknown",
+        }
+        .to_string();
+    }
+
+    Ok(Json(Paginated::new(contracts, total, limit, offset)))
 }
 
 /// Query for contract ABI and OpenAPI (optional version)
@@ -2913,14 +3693,8 @@ pub async fn get_contract_openapi_json(
 }
 
 // Stubs for upstream added endpoints
-fn planned_not_implemented_response() -> (StatusCode, Json<Value>) {
-    (
-        StatusCode::NOT_IMPLEMENTED,
-        Json(json!({
-            "error": "not_implemented",
-            "message": "This endpoint is planned but not yet functional"
-        })),
-    )
+fn planned_not_implemented_response() -> ApiError {
+    ApiError::not_implemented("This endpoint is planned but not yet functional")
 }
 
 pub async fn get_contract_state() -> impl IntoResponse {
@@ -3031,7 +3805,7 @@ pub async fn get_contract_graph(
         }
     }
 
-    let graph = dependency::build_dependency_graph(&state.db, query.network)
+    let graph = dependency::build_dependency_graph(state.read_pool(), query.network)
         .await
         .map_err(|e| ApiError::internal(format!("Failed to build graph: {}", e)))?;
 
@@ -3174,7 +3948,7 @@ pub async fn get_trending_contracts(
     )
     .bind(trailing_days)
     .bind(limit)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_internal_error("fetch trending contracts", err))?;
 
@@ -3215,12 +3989,26 @@ pub async fn get_trending_contracts(
     })))
 }
 
+/// Payload of a `verify_contract` job
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct VerificationJob {
+    #[serde(flatten)]
+    pub request: VerifyRequest,
+    /// Client address of the request that queued the job, for the audit log
+    pub ip_address: String,
+}
+
+/// Queue a source verification. The build runs in the background; follow
+/// the returned job at `/api/jobs/{id}`. Once it succeeds the job's result
+/// holds the verification, and a failed verification is reported as a
+/// `failed` job with the reason in `last_error`. Without a `source_url`, the
+/// repository embedded in the contract's WASM metadata is used.
 #[utoipa::path(
     post,
     path = "/api/contracts/verify",
     request_body = VerifyRequest,
     responses(
-        (status = 200, description = "Verification successful", body = Object),
+        (status = 202, description = "Verification queued", body = Job),
         (status = 400, description = "Invalid request"),
         (status = 404, description = "Contract not found")
     ),
@@ -3229,8 +4017,75 @@ pub async fn get_trending_contracts(
 pub async fn verify_contract(
     State(state): State<AppState>,
     headers: HeaderMap,
-    ValidatedJson(req): ValidatedJson<VerifyRequest>,
-) -> ApiResult<Json<Value>> {
+    claims: Option<AuthClaims>,
+    ValidatedJson(mut req): ValidatedJson<VerifyRequest>,
+) -> ApiResult<(StatusCode, HeaderMap, Json<Job>)> {
+    let exists: bool =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM contracts WHERE contract_id = $1)")
+            .bind(&req.contract_id)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("check contract for verification", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found with contract_id: {}", req.contract_id),
+        ));
+    }
+    if req.source_url.is_none() {
+        req.source_url = embedded_repository(&state, &req.contract_id).await?;
+    }
+
+    let payload = json!(VerificationJob {
+        request: req,
+        ip_address: extract_ip_address(&headers),
+    });
+    let job = jobs::enqueue(
+        &state.db,
+        &state.config.jobs,
+        JobKind::VerifyContract,
+        &payload,
+        claims.as_ref().map(|claims| claims.sub.as_str()),
+    )
+    .await
+    .map_err(|err| db_internal_error("queue verification job", err))?;
+
+    Ok(job_accepted(job))
+}
+
+/// Repository named by the WASM metadata of the contract's latest version
+/// that names one
+async fn embedded_repository(state: &AppState, contract_id: &str) -> ApiResult<Option<String>> {
+    sqlx::query_scalar(
+        "SELECT cv.wasm_meta ->> 'repository'
+         FROM contract_versions cv
+         JOIN contracts c ON c.id = cv.contract_id
+         WHERE c.contract_id = $1 AND cv.wasm_meta ->> 'repository' IS NOT NULL
+         ORDER BY cv.created_at DESC
+         LIMIT 1",
+    )
+    .bind(contract_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch embedded repository", err))
+}
+
+/// `202 Accepted` with the job and a `Location` header pointing at its status
+pub(crate) fn job_accepted(job: Job) -> (StatusCode, HeaderMap, Json<Job>) {
+    let mut headers = HeaderMap::new();
+    if let Ok(location) = job.status_url().parse() {
+        headers.insert(header::LOCATION, location);
+    }
+    (StatusCode::ACCEPTED, headers, Json(job))
+}
+
+/// Builds the submitted source and checks it against the contract on chain.
+/// Runs as a `verify_contract` job; errors other than 5xx fail the job.
+pub(crate) async fn run_verification(state: &AppState, job: VerificationJob) -> ApiResult<Value> {
+    let VerificationJob {
+        request: req,
+        ip_address,
+    } = job;
     let contract: Contract = sqlx::query_as(
         "SELECT * FROM contracts WHERE contract_id = $1 ORDER BY created_at DESC LIMIT 1",
     )
@@ -3266,20 +4121,52 @@ pub async fn verify_contract(
     .await
     .map_err(|err| db_internal_error("insert verification record", err))?;
 
+    if let (Some(cargo_lock), Some(source_url), Some(commit_hash)) =
+        (&req.cargo_lock, &req.source_url, &req.commit_hash)
+    {
+        if let Err(err) = build_info_handlers::check_lockfile_at_commit(
+            state,
+            source_url,
+            commit_hash,
+            cargo_lock,
+        )
+        .await
+        {
+            sqlx::query(
+                "UPDATE verifications
+                 SET status = 'failed', verified_at = NULL, error_message = $2
+                 WHERE id = $1",
+            )
+            .bind(verification_id)
+            .bind(err.message())
+            .execute(&state.db)
+            .await
+            .map_err(|db_err| db_internal_error("mark verification as failed", db_err))?;
+            return Err(err);
+        }
+    }
+
     let verification_result = verifier::verify_contract(
         &req.source_code,
         &contract.wasm_hash,
         Some(&req.compiler_version),
         Some(&req.build_params),
+        req.cargo_lock.as_deref(),
     )
     .await;
-    let onchain_verifier = OnChainVerifier::new();
-    let abi_json = resolve_abi(&state, &contract.contract_id).await.ok();
+    let onchain_verifier = OnChainVerifier::new(state.outbound.clone(), state.config.rpc.clone());
+    let abi_json = resolve_abi(state, &contract.contract_id, false).await.ok();
     let onchain_result = onchain_verifier
         .verify_contract(&state.cache, &contract, abi_json.as_deref())
         .await;
+    if let Ok(onchain) = &onchain_result {
+        if onchain.contract_exists_on_chain && onchain.wasm_hash_matches {
+            contract_freshness::record_onchain_confirmation(&state.db, contract.id)
+                .await
+                .map_err(|err| db_internal_error("record on-chain confirmation", err))?;
+        }
+    }
 
-    let ip_address = extract_ip_address(&headers);
     let before_status = previous_status.unwrap_or_else(|| "pending".to_string());
 
     match (verification_result, onchain_result) {
@@ -3307,6 +4194,34 @@ pub async fn verify_contract(
             .execute(&state.db)
             .await
             .map_err(|err| db_internal_error("mark contract verified", err))?;
+            state.cache.invalidate_contract(contract.id).await;
+
+            let sbom = match &req.cargo_lock {
+                Some(cargo_lock) => {
+                    Some(build_info_handlers::generate_sbom(state, &contract, cargo_lock).await?)
+                }
+                None => None,
+            };
+            build_info_handlers::record_build_info(
+                &state.db,
+                verification_id,
+                contract.id,
+                &result.build,
+                req.source_url.as_deref(),
+                req.commit_hash.as_deref(),
+                sbom.as_ref(),
+            )
+            .await?;
+            let profile = contract_profile::record_verified_profile(
+                &state.db,
+                contract.id,
+                &contract.contract_id,
+                verification_id,
+                &result.compiled_wasm_hash,
+                result.compiled_wasm,
+            )
+            .await
+            .map_err(|err| db_internal_error("record verified profile", err))?;
 
             let verification_changes = json!({
                 "verification_id": { "before": Value::Null, "after": verification_id },
@@ -3384,15 +4299,29 @@ pub async fn verify_contract(
             )
             .await;
 
-            Ok(Json(json!({
+            subscription_notifications::enqueue(
+                &state.db,
+                contract.id,
+                &subscription_notifications::verification_notice(
+                    &(&contract).into(),
+                    verification_id,
+                ),
+            )
+            .await
+            .map_err(|err| db_internal_error("queue verification notifications", err))?;
+
+            Ok(json!({
                 "verified": true,
                 "status": "verified",
                 "verification_id": verification_id,
                 "contract_id": contract.id,
                 "compiled_wasm_hash": result.compiled_wasm_hash,
                 "deployed_wasm_hash": result.deployed_wasm_hash,
-                "on_chain": onchain
-            })))
+                "on_chain": onchain,
+                "build": result.build,
+                "sbom_available": sbom.is_some(),
+                "profile": profile
+            }))
         }
         (Ok(result), Ok(onchain)) => {
             let mut reasons = Vec::new();
@@ -3529,13 +4458,16 @@ pub async fn verify_contract(
     patch,
     path = "/api/contracts/{id}/metadata",
     params(
-        ("id" = String, Path, description = "Contract UUID")
+        ("id" = String, Path, description = "Contract UUID"),
+        ("If-Match" = Option<String>, Header, description = "ETag of the revision the change is based on")
     ),
     request_body = UpdateContractMetadataRequest,
     responses(
-        (status = 200, description = "Metadata updated successfully", body = Contract),
+        (status = 200, description = "Metadata updated successfully; the new revision is the ETag", body = Contract),
         (status = 404, description = "Contract not found"),
-        (status = 400, description = "Invalid input")
+        (status = 400, description = "Invalid input or If-Match header"),
+        (status = 409, description = "The new slug is taken"),
+        (status = 412, description = "The contract changed since the If-Match revision; details carry the current record")
     ),
     tag = "Contracts"
 )]
@@ -3543,12 +4475,16 @@ pub async fn update_contract_metadata(
     State(state): State<AppState>,
     Path(id): Path<String>,
     headers: HeaderMap,
+    claims: Option<AuthClaims>,
     ValidatedJson(req): ValidatedJson<UpdateContractMetadataRequest>,
-) -> ApiResult<Json<Contract>> {
+) -> ApiResult<Tagged<Contract>> {
     if req.name.is_none()
         && req.description.is_none()
         && req.category.is_none()
         && req.tags.is_none()
+        && req.slug.is_none()
+        && req.readme.is_none()
+        && req.index_readme.is_none()
     {
         return Err(ApiError::bad_request(
             "InvalidRequest",
@@ -3574,15 +4510,47 @@ pub async fn update_contract_metadata(
             ),
             _ => db_internal_error("fetch contract for metadata update", err),
         })?;
+    org_handlers::authorize_contract_write(&state, claims.as_ref(), contract_uuid).await?;
+    let expected = contract_revision::expected_revisions(&headers)?;
+    contract_revision::check(expected.as_deref(), &before)?;
 
-    let after: Contract = sqlx::query_as(
+    let renamed = req.slug.is_some() && req.slug != before.slug;
+    if let (true, Some(namespace), Some(slug)) = (renamed, &before.namespace, &req.slug) {
+        slug_handlers::ensure_slug_available(
+            &state.db,
+            namespace,
+            slug,
+            &before.network,
+            Some(contract_uuid),
+        )
+        .await?;
+    }
+
+    let (readme_before, index_readme_before): (Option<String>, bool) =
+        sqlx::query_as("SELECT readme, index_readme FROM contracts WHERE id = $1")
+            .bind(contract_uuid)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch contract readme", err))?;
+
+    // Checked again here: another edit may have landed since `before` was read.
+    // Changing the README or `index_readme` reindexes the contract through
+    // the search document trigger.
+    let after: Option<Contract> = sqlx::query_as(
         "UPDATE contracts
             SET name = COALESCE($2, name),
                 description = COALESCE($3, description),
                 category = COALESCE($4, category),
-                tags = COALESCE($5, tags),
-                updated_at = NOW()
-          WHERE id = $1
+                tags = COALESCE(normalize_contract_tags($5), tags),
+                slug = COALESCE($6, slug),
+                readme = CASE WHEN $8::TEXT IS NULL THEN readme ELSE NULLIF($8, '') END,
+                index_readme = COALESCE($9, index_readme),
+                revision = revision + 1,
+                updated_at = NOW(),
+                publisher_active_at = NOW(),
+                stale_warned_at = NULL,
+                stale_at = NULL
+          WHERE id = $1 AND ($7::BIGINT[] IS NULL OR revision = ANY($7))
           RETURNING *",
     )
     .bind(contract_uuid)
@@ -3590,9 +4558,35 @@ pub async fn update_contract_metadata(
     .bind(req.description.as_deref())
     .bind(req.category.as_deref())
     .bind(req.tags.as_ref())
-    .fetch_one(&state.db)
+    .bind(req.slug.as_deref())
+    .bind(expected.as_deref())
+    .bind(req.readme.as_deref())
+    .bind(req.index_readme)
+    .fetch_optional(&state.db)
     .await
-    .map_err(|err| db_internal_error("update contract metadata", err))?;
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref e)
+            if e.constraint() == Some("contracts_namespace_slug_network_key") =>
+        {
+            ApiError::conflict(
+                "SlugTaken",
+                format!(
+                    "{}/{} is already taken on {}",
+                    before.namespace.as_deref().unwrap_or_default(),
+                    req.slug.as_deref().unwrap_or_default(),
+                    before.network
+                ),
+            )
+        }
+        _ => db_internal_error("update contract metadata", err),
+    })?;
+    let Some(after) = after else {
+        return Err(contract_revision::precondition_failed(&state.db, contract_uuid).await);
+    };
+    if renamed {
+        slug_handlers::keep_redirect(&state.db, &before, &after).await?;
+    }
+    state.cache.invalidate_contract(contract_uuid).await;
 
     let mut changes = serde_json::Map::new();
     if before.name != after.name {
@@ -3619,6 +4613,26 @@ pub async fn update_contract_metadata(
             json!({ "before": before.tags, "after": after.tags }),
         );
     }
+    if before.slug != after.slug {
+        changes.insert(
+            "slug".to_string(),
+            json!({ "before": before.slug, "after": after.slug }),
+        );
+    }
+    // READMEs are long; the log only says one was replaced
+    let new_readme = req.readme.as_deref().filter(|readme| !readme.is_empty());
+    if req.readme.is_some() && new_readme != readme_before.as_deref() {
+        changes.insert("readme".to_string(), json!({ "changed": true }));
+    }
+    if req
+        .index_readme
+        .is_some_and(|index| index != index_readme_before)
+    {
+        changes.insert(
+            "index_readme".to_string(),
+            json!({ "before": index_readme_before, "after": req.index_readme }),
+        );
+    }
 
     if !changes.is_empty() {
         let changes_value = Value::Object(changes.clone());
@@ -3653,7 +4667,7 @@ pub async fn update_contract_metadata(
             ));
     }
 
-    Ok(Json(after))
+    Ok(contract_revision::tagged(after.revision, after))
 }
 
 #[utoipa::path(
@@ -3724,6 +4738,7 @@ pub async fn change_contract_publisher(
     .fetch_one(&state.db)
     .await
     .map_err(|err| db_internal_error("update contract publisher", err))?;
+    state.cache.invalidate_contract(contract_uuid).await;
 
     if before.publisher_id != after.publisher_id {
         let changes = json!({
@@ -3836,6 +4851,7 @@ pub async fn update_contract_status(
         .execute(&state.db)
         .await
         .map_err(|err| db_internal_error("update contract verification flag from status", err))?;
+    state.cache.invalidate_contract(contract_uuid).await;
 
     let before_status = previous_status.unwrap_or_else(|| "pending".to_string());
     if before_status != normalized_status || contract.is_verified != is_verified_after {
@@ -3888,6 +4904,19 @@ pub async fn update_contract_status(
         .await
         .map_err(|err| db_internal_error("fetch contract after status update", err))?;
 
+    if normalized_status == "verified" {
+        subscription_notifications::enqueue(
+            &state.db,
+            contract_uuid,
+            &subscription_notifications::verification_notice(
+                &(&contract_after).into(),
+                verification_id,
+            ),
+        )
+        .await
+        .map_err(|err| db_internal_error("queue verification notifications", err))?;
+    }
+
     state
         .contract_events
         .publish(ContractEventEnvelope::status_updated(
@@ -4015,7 +5044,7 @@ pub async fn get_all_audit_logs(
     )
     .bind(limit)
     .bind(offset)
-    .fetch_all(&state.db)
+    .fetch_all(state.read_pool())
     .await
     .map_err(|err| db_internal_error("fetch all audit logs", err))?;
 
@@ -4467,10 +5496,6 @@ mod tests {
 
     #[tokio::test]
     async fn test_health_check_shutdown_returns_503() {
-        unsafe {
-            std::env::set_var("JWT_SECRET", "abcdefghijklmnopqrstuvwxyz012345");
-        }
-
         let is_shutting_down = Arc::new(AtomicBool::new(true));
 
         // Connect lazy so it doesn't fail immediately without a DB
@@ -4479,7 +5504,10 @@ mod tests {
             .unwrap();
         let registry = Registry::new();
         let (job_engine, _rx) = soroban_batch::engine::JobEngine::new();
-        let state = AppState::new(db, registry, Arc::new(job_engine), is_shutting_down);
+        let config = Arc::new(crate::config::Config::for_tests());
+        let state = AppState::new(config, db, registry, Arc::new(job_engine), is_shutting_down)
+            .await
+            .unwrap();
 
         let (status, json) = health_check(State(state)).await;
 
@@ -4488,6 +5516,29 @@ mod tests {
         assert_eq!(value["status"], "shutting_down");
     }
 
+    #[test]
+    fn text_query_only_filters_with_matches_only() {
+        let params = |value: serde_json::Value| -> ContractSearchParams {
+            serde_json::from_value(value).unwrap()
+        };
+        assert_eq!(
+            query_filter(&params(serde_json::json!({ "query": "swap" }))),
+            None
+        );
+        assert_eq!(
+            query_filter(&params(
+                serde_json::json!({ "query": " swap ", "matches_only": true })
+            )),
+            Some("swap")
+        );
+        assert_eq!(
+            query_filter(&params(
+                serde_json::json!({ "query": " ", "matches_only": true })
+            )),
+            None
+        );
+    }
+
     #[test]
     fn split_audit_changes_extracts_before_after() {
         let changes = json!({
@@ -4547,6 +5598,14 @@ mod tests {
             is_maintenance: false,
             logical_id: None,
             network_configs: None,
+            relevance_score: None,
+            organization_id: None,
+            visibility: shared::VisibilityType::Public,
+            namespace: None,
+            slug: None,
+            publish_status: shared::PublishStatus::Published,
+            revision: 0,
+            freshness: shared::Freshness::default(),
         };
 
         assert_eq!(
@@ -4626,7 +5685,7 @@ mod tests {
     path = "/api/contracts/search",
     request_body = AdvancedSearchRequest,
     responses(
-        (status = 200, description = "Search results", body = PaginatedResponse<Contract>),
+        (status = 200, description = "Search results", body = Paginated<Contract>),
         (status = 400, description = "Invalid query DSL")
     ),
     tag = "Contracts"
@@ -4634,10 +5693,12 @@ mod tests {
 pub async fn advanced_search_contracts(
     State(state): State<AppState>,
     ValidatedJson(req): ValidatedJson<AdvancedSearchRequest>,
-) -> ApiResult<Json<PaginatedResponse<Contract>>> {
-    let limit = req.limit.unwrap_or(20).clamp(1, 100);
-    let offset = req.offset.unwrap_or(0).max(0);
-    let page = (offset / limit) + 1;
+) -> ApiResult<Json<Paginated<Contract>>> {
+    let Pagination { limit, offset } = Pagination::try_from(PageParams {
+        limit: req.limit,
+        offset: req.offset,
+        page: None,
+    })?;
 
     let mut query_builder: sqlx::QueryBuilder<'_, sqlx::Postgres> =
         sqlx::QueryBuilder::new("SELECT c.* FROM contracts c ");
@@ -4676,7 +5737,8 @@ pub async fn advanced_search_contracts(
             query_builder.push("COUNT(DISTINCT cv.id) ");
         }
         shared::SortBy::Relevance => {
-            query_builder.push("c.created_at "); // Default relevance if no query term
+            // Default relevance if no query term; stale contracts last
+            query_builder.push("(c.stale_at IS NOT NULL) ASC, c.created_at ");
         }
         _ => {
             query_builder.push("c.created_at ");
@@ -4693,7 +5755,7 @@ pub async fn advanced_search_contracts(
 
     let query = query_builder.build_query_as::<Contract>();
     let contracts = query
-        .fetch_all(&state.db)
+        .fetch_all(state.read_pool())
         .await
         .map_err(|err| db_internal_error("advanced search contracts", err))?;
 
@@ -4705,11 +5767,11 @@ pub async fn advanced_search_contracts(
 
     let total: i64 = count_builder
         .build_query_scalar()
-        .fetch_one(&state.db)
+        .fetch_one(state.read_pool())
         .await
         .map_err(|err| db_internal_error("count advanced search", err))?;
 
-    Ok(Json(PaginatedResponse::new(contracts, total, page, limit)))
+    Ok(Json(Paginated::new(contracts, total, limit, offset)))
 }
 
 fn build_where_clause<'a>(
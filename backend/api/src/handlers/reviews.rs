@@ -4,8 +4,9 @@
 // 
 // This module implements the complete review system for contracts:
 // - Submit reviews (POST /contracts/:id/reviews)
-// - Fetch reviews with sorting (GET /contracts/:id/reviews)
-// - Vote on review helpfulness (POST /contracts/:id/reviews/:review_id/vote)
+// - Fetch reviews a page at a time, sorted and filtered (GET /contracts/:id/reviews)
+// - Toggle a helpful vote (POST /contracts/:id/reviews/:review_id/helpful)
+// - Set a helpful vote explicitly (POST /contracts/:id/reviews/:review_id/vote)
 // - Flag reviews for moderation (POST /contracts/:id/reviews/:review_id/flag)
 // - Moderate reviews (admin only) (POST /contracts/:id/reviews/:review_id/moderate)
 // - Get rating aggregation (GET /contracts/:id/rating-stats)
@@ -16,9 +17,13 @@
 // - Admins can approve or reject pending reviews
 // - Users can flag reviews for moderation
 //
+// Helpfulness:
+// - Each caller (token subject) has at most one helpful vote per approved review
+// - helpful_count is the number of those votes and drives sort=most_helpful
+//
 // Rating Aggregation:
 // - Average rating is computed from all approved reviews
-// - Rating distribution (1-5 stars) is tracked
+// - Rating distribution (1-5 stars) is tracked, and included in contract details
 // - Aggregation is computed dynamically on each request (can be cached for performance)
 // ═══════════════════════════════════════════════════════════════════════════
 
@@ -29,19 +34,27 @@ use axum::{
     Json,
 };
 use serde::Deserialize;
-use sqlx::{PgPool, Postgres, Transaction};
+use shared::pagination::Paginated;
+use sqlx::PgPool;
 use uuid::Uuid;
 
 use crate::{
     auth::AuthClaims,
     error::{ApiError, ApiResult},
+    handlers::db_internal_error,
     models::{
         ContractRatingStats, CreateReviewRequest, FlagReviewRequest, GetReviewsQuery,
-        ModerateReviewRequest, RatingDistribution, ReviewResponse, ReviewSortBy, ReviewStatus,
+        ModerateReviewRequest, ReviewHelpfulResponse, ReviewResponse, ReviewSortBy, ReviewStatus,
         ReviewVoteRequest, ReviewVoteResponse,
     },
+    pagination::{PageParams, Pagination},
+    state::AppState,
 };
 
+/// Columns selected into a [`ReviewResponse`]
+const REVIEW_COLUMNS: &str = "id, contract_id, user_id, version, rating::float8 AS rating, \
+     review_text, helpful_count, is_flagged, status, created_at, updated_at";
+
 // ═══════════════════════════════════════════════════════════════════════════
 // SUBMIT REVIEW
 // ═══════════════════════════════════════════════════════════════════════════
@@ -67,7 +80,7 @@ pub struct CreateReviewQuery {
 }
 
 pub async fn create_review(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
     Path(contract_id): Path<Uuid>,
     Query(query_params): Query<CreateReviewQuery>,
     claims: AuthClaims,
@@ -86,7 +99,7 @@ pub async fn create_review(
         "SELECT EXISTS(SELECT 1 FROM contracts WHERE id = $1)",
     )
     .bind(contract_id)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error checking contract existence");
@@ -112,7 +125,7 @@ pub async fn create_review(
             "#,
         )
         .bind(&claims.sub)
-        .fetch_one(&pool)
+        .fetch_one(&state.db)
         .await
         .map_err(|e| {
             tracing::error!(error = ?e, "database error checking verified status");
@@ -121,8 +134,7 @@ pub async fn create_review(
 
         if !has_verified {
             return Err(ApiError::forbidden(
-                "VerifiedUserRequired",
-                "Only users with verified contracts can submit reviews".to_string(),
+                "Only users with verified contracts can submit reviews",
             ));
         }
     }
@@ -132,7 +144,7 @@ pub async fn create_review(
         "SELECT id FROM publishers WHERE stellar_address = $1",
     )
     .bind(&claims.sub)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error fetching publisher");
@@ -155,7 +167,7 @@ pub async fn create_review(
     )
     .bind(contract_id)
     .bind(user_id)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error checking duplicate review");
@@ -171,30 +183,20 @@ pub async fn create_review(
 
     // Insert the review with 'pending' status
     // New reviews require admin approval before becoming visible
-    let review = sqlx::query_as::<_, ReviewResponse>(
+    let review = sqlx::query_as::<_, ReviewResponse>(&format!(
         r#"
         INSERT INTO reviews (contract_id, user_id, version, rating, review_text, status, helpful_count, is_flagged)
         VALUES ($1, $2, $3, $4, $5, 'pending', 0, false)
-        RETURNING 
-            id, 
-            contract_id, 
-            user_id, 
-            version, 
-            rating::float8 as "rating!", 
-            review_text, 
-            helpful_count, 
-            is_flagged, 
-            status, 
-            created_at,
-            updated_at
+        RETURNING {}
         "#,
-    )
+        REVIEW_COLUMNS
+    ))
     .bind(contract_id)
     .bind(user_id)
     .bind(&payload.version)
     .bind(payload.rating)
     .bind(&payload.review_text)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error creating review");
@@ -216,229 +218,239 @@ pub async fn create_review(
 // ═══════════════════════════════════════════════════════════════════════════
 // GET /api/contracts/:id/reviews
 //
-// Fetches approved reviews for a contract with sorting support.
-// Only reviews with status = 'approved' are returned.
+// Fetches approved reviews for a contract, a page at a time (`limit`/`offset`
+// or `page`). Only reviews with status = 'approved' are returned.
 //
-// Sorting options:
+// Sorting options (`sort`, or `sort_by` with the older names):
+// - newest: Order by created_at DESC (default)
+// - highest: Order by rating DESC
+// - lowest: Order by rating ASC
 // - most_helpful: Order by helpful_count DESC
-// - most_recent: Order by created_at DESC (default)
-// - highest_rated: Order by rating DESC
-// - lowest_rated: Order by rating ASC
+//
+// `rating=<1-5>` keeps only reviews in that bar of the star histogram.
 // ═══════════════════════════════════════════════════════════════════════════
 
+/// Approved reviews of a contract.
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/reviews",
+    params(
+        ("id" = Uuid, Path, description = "Contract ID"),
+        GetReviewsQuery,
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "A page of approved reviews", body = Paginated<ReviewResponse>),
+        (status = 400, description = "Invalid rating filter or pagination")
+    ),
+    tag = "Reviews"
+)]
 pub async fn get_reviews(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
     Path(contract_id): Path<Uuid>,
     Query(query): Query<GetReviewsQuery>,
-) -> ApiResult<Json<Vec<ReviewResponse>>> {
-    // Validate limit (max 100 to prevent abuse)
-    let limit = query.limit.min(100).max(1);
-
-    // Build ORDER BY clause based on sort_by parameter
-    let order_clause = match query.sort_by {
-        ReviewSortBy::MostHelpful => "ORDER BY r.helpful_count DESC, r.created_at DESC",
-        ReviewSortBy::MostRecent => "ORDER BY r.created_at DESC",
-        ReviewSortBy::HighestRated => "ORDER BY r.rating DESC, r.created_at DESC",
-        ReviewSortBy::LowestRated => "ORDER BY r.rating ASC, r.created_at DESC",
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Json<Paginated<ReviewResponse>>> {
+    if let Some(rating) = query.rating {
+        if !(1..=5).contains(&rating) {
+            return Err(ApiError::bad_request(
+                "InvalidRating",
+                format!(
+                    "`rating` must be a number of stars from 1 to 5, got {}",
+                    rating
+                ),
+            ));
+        }
+    }
+
+    // Build ORDER BY clause based on the sort parameter; the id keeps pages
+    // stable between equal keys
+    let order_clause = match query.sort {
+        ReviewSortBy::Newest => "ORDER BY created_at DESC, id DESC",
+        ReviewSortBy::Highest => "ORDER BY rating DESC, created_at DESC, id DESC",
+        ReviewSortBy::Lowest => "ORDER BY rating ASC, created_at DESC, id DESC",
+        ReviewSortBy::MostHelpful => "ORDER BY helpful_count DESC, created_at DESC, id DESC",
     };
 
     // Fetch only approved reviews (moderation workflow)
-    let query_str = format!(
-        r#"
-        SELECT 
-            r.id, 
-            r.contract_id, 
-            r.user_id, 
-            r.version, 
-            r.rating::float8 as "rating!", 
-            r.review_text, 
-            r.helpful_count, 
-            r.is_flagged, 
-            r.status, 
-            r.created_at,
-            r.updated_at
-        FROM reviews r
-        WHERE r.contract_id = $1 
-          AND r.status = 'approved'
-        {}
-        LIMIT $2 OFFSET $3
-        "#,
-        order_clause
-    );
+    const FILTER: &str = "contract_id = $1 AND status = 'approved' \
+         AND ($2::INT IS NULL OR FLOOR(rating)::INT = $2)";
 
-    let reviews = sqlx::query_as::<_, ReviewResponse>(&query_str)
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM reviews WHERE {}", FILTER))
         .bind(contract_id)
-        .bind(limit)
-        .bind(query.offset)
-        .fetch_all(&pool)
+        .bind(query.rating)
+        .fetch_one(&state.db)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, contract_id = %contract_id, "database error fetching reviews");
-            ApiError::internal("Failed to fetch reviews")
-        })?;
+        .map_err(|err| db_internal_error("count reviews", err))?;
+
+    let reviews = sqlx::query_as::<_, ReviewResponse>(&format!(
+        "SELECT {} FROM reviews WHERE {} {} LIMIT $3 OFFSET $4",
+        REVIEW_COLUMNS, FILTER, order_clause
+    ))
+    .bind(contract_id)
+    .bind(query.rating)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch reviews", err))?;
 
-    Ok(Json(reviews))
+    Ok(Json(Paginated::new(reviews, total, limit, offset)))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
 // VOTE ON REVIEW HELPFULNESS
 // ═══════════════════════════════════════════════════════════════════════════
+// POST /api/contracts/:id/reviews/:review_id/helpful
 // POST /api/contracts/:id/reviews/:review_id/vote
 //
-// Allows users to vote on whether a review was helpful.
-// Prevents duplicate votes from the same user.
-// Updates the helpful_count on the review.
+// A caller has at most one helpful vote per review, keyed by the token's
+// subject. /helpful toggles it; /vote sets it, `helpful: false` withdrawing
+// it. helpful_count is kept in step under a lock on the review row.
+// Only approved reviews can be voted on.
 // ═══════════════════════════════════════════════════════════════════════════
 
-pub async fn vote_review(
-    State(pool): State<PgPool>,
-    Path((contract_id, review_id)): Path<(Uuid, i32)>,
-    claims: AuthClaims,
-    Json(payload): Json<ReviewVoteRequest>,
-) -> ApiResult<Json<ReviewVoteResponse>> {
-    // Verify the review exists and belongs to the contract
-    let review_exists = sqlx::query_scalar::<_, bool>(
-        "SELECT EXISTS(SELECT 1 FROM reviews WHERE id = $1 AND contract_id = $2)",
+/// Casts (`Some(true)`), withdraws (`Some(false)`) or toggles (`None`)
+/// `voter`'s helpful vote. Also returns whether the vote changed.
+async fn record_helpful_vote(
+    db: &PgPool,
+    contract_id: Uuid,
+    review_id: i32,
+    voter: &str,
+    helpful: Option<bool>,
+) -> ApiResult<(ReviewHelpfulResponse, bool)> {
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("start helpful vote", err))?;
+
+    // Locking the review serializes votes on it, so helpful_count stays exact
+    let count: Option<i32> = sqlx::query_scalar(
+        "SELECT helpful_count FROM reviews
+         WHERE id = $1 AND contract_id = $2 AND status = 'approved'
+         FOR UPDATE",
     )
     .bind(review_id)
     .bind(contract_id)
-    .fetch_one(&pool)
+    .fetch_optional(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "database error checking review existence");
-        ApiError::internal("Failed to verify review existence")
-    })?;
-
-    if !review_exists {
+    .map_err(|err| db_internal_error("lock review", err))?;
+    let Some(mut helpful_count) = count else {
         return Err(ApiError::not_found(
             "ReviewNotFound",
-            "Review not found or does not belong to this contract".to_string(),
-        ));
-    }
-
-    // Get user's publisher ID
-    let user_id = sqlx::query_scalar::<_, Option<Uuid>>(
-        "SELECT id FROM publishers WHERE stellar_address = $1",
-    )
-    .bind(&claims.sub)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "database error fetching publisher");
-        ApiError::internal("Failed to fetch publisher information")
-    })?;
-
-    // Use user_id or create a placeholder for anonymous voting
-    // For now, we require authentication, so user_id should exist
-    if user_id.is_none() {
-        return Err(ApiError::bad_request(
-            "UserNotFound",
-            "User must have a publisher account to vote".to_string(),
+            "Review not found or does not belong to this contract",
         ));
-    }
-
-    let user_id = user_id.unwrap();
-
-    // Start a transaction to ensure and update vote
-    let mut tx = pool.begin().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to start transaction");
-        ApiError::internal("Failed to start database transaction")
-    })?;
+    };
 
-    // Check if user already voted on this review
-    let existing_vote = sqlx::query_scalar::<_, Option<bool>>(
-        "SELECT vote FROM review_votes WHERE review_id = $1 AND user_id = $2",
+    let had_voted: bool = sqlx::query_scalar(
+        "SELECT EXISTS(SELECT 1 FROM review_helpful_votes WHERE review_id = $1 AND voter = $2)",
     )
     .bind(review_id)
-    .bind(user_id)
+    .bind(voter)
     .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "database error checking existing vote");
-        ApiError::internal("Failed to check existing vote")
-    })?;
+    .map_err(|err| db_internal_error("check helpful vote", err))?;
 
-    let vote_recorded = if let Some(prev_vote) = existing_vote {
-        // Update existing vote if it's different
-        if prev_vote != payload.helpful {
-            sqlx::query("UPDATE review_votes SET vote = $1 WHERE review_id = $2 AND user_id = $3")
-                .bind(payload.helpful)
-                .bind(review_id)
-                .bind(user_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    tracing::error!(error = ?e, "database error updating vote");
-                    ApiError::internal("Failed to update vote")
-                })?;
-
-            // Adjust helpful_count: +1 if changing to helpful, -1 if changing to unhelpful
-            let delta = if payload.helpful { 1 } else { -1 };
-            sqlx::query("UPDATE reviews SET helpful_count = helpful_count + $1 WHERE id = $2")
-                .bind(delta)
-                .bind(review_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    tracing::error!(error = ?e, "database error updating helpful count");
-                    ApiError::internal("Failed to update helpful count")
-                })?;
-
-            true
+    let voted = helpful.unwrap_or(!had_voted);
+    if voted != had_voted {
+        let change = if voted {
+            "INSERT INTO review_helpful_votes (review_id, voter) VALUES ($1, $2)"
         } else {
-            // Vote is the same, no change needed
-            false
-        }
-    } else {
-        // Insert new vote
-        sqlx::query(
-            "INSERT INTO review_votes (review_id, user_id, vote) VALUES ($1, $2, $3)",
+            "DELETE FROM review_helpful_votes WHERE review_id = $1 AND voter = $2"
+        };
+        sqlx::query(change)
+            .bind(review_id)
+            .bind(voter)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("record helpful vote", err))?;
+
+        helpful_count = sqlx::query_scalar(
+            "UPDATE reviews SET helpful_count = helpful_count + $1 WHERE id = $2
+             RETURNING helpful_count",
         )
+        .bind(if voted { 1 } else { -1 })
         .bind(review_id)
-        .bind(user_id)
-        .bind(payload.helpful)
-        .execute(&mut *tx)
+        .fetch_one(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "database error inserting vote");
-            ApiError::internal("Failed to insert vote")
-        })?;
-
-        // Update helpful_count: +1 for helpful, 0 for unhelpful (unhelpful doesn't decrease)
-        if payload.helpful {
-            sqlx::query("UPDATE reviews SET helpful_count = helpful_count + 1 WHERE id = $1")
-                .bind(review_id)
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    tracing::error!(error = ?e, "database error updating helpful count");
-                    ApiError::internal("Failed to update helpful count")
-                })?;
-        }
+        .map_err(|err| db_internal_error("update helpful count", err))?;
+    }
 
-        true
-    };
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit helpful vote", err))?;
+
+    Ok((
+        ReviewHelpfulResponse {
+            review_id,
+            helpful_count,
+            voted,
+        },
+        voted != had_voted,
+    ))
+}
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to commit transaction");
-        ApiError::internal("Failed to commit vote transaction")
-    })?;
+/// Toggle the caller's helpful vote on a review.
+#[utoipa::path(
+    post,
+    path = "/api/contracts/{id}/reviews/{review_id}/helpful",
+    params(
+        ("id" = Uuid, Path, description = "Contract ID"),
+        ("review_id" = i32, Path, description = "Review ID")
+    ),
+    responses(
+        (status = 200, description = "The vote was cast or withdrawn", body = ReviewHelpfulResponse),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "No such approved review on this contract")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Reviews"
+)]
+pub async fn toggle_helpful_vote(
+    State(state): State<AppState>,
+    Path((contract_id, review_id)): Path<(Uuid, i32)>,
+    claims: AuthClaims,
+) -> ApiResult<Json<ReviewHelpfulResponse>> {
+    let (vote, _) =
+        record_helpful_vote(&state.db, contract_id, review_id, &claims.sub, None).await?;
+    Ok(Json(vote))
+}
 
-    // Fetch updated helpful_count
-    let helpful_count = sqlx::query_scalar::<_, i32>(
-        "SELECT helpful_count FROM reviews WHERE id = $1",
+/// Cast or withdraw the caller's helpful vote on a review.
+#[utoipa::path(
+    post,
+    path = "/api/contracts/{id}/reviews/{review_id}/vote",
+    params(
+        ("id" = Uuid, Path, description = "Contract ID"),
+        ("review_id" = i32, Path, description = "Review ID")
+    ),
+    request_body = ReviewVoteRequest,
+    responses(
+        (status = 200, description = "`vote_recorded` is false when the vote was already in that state", body = ReviewVoteResponse),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "No such approved review on this contract")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Reviews"
+)]
+pub async fn vote_review(
+    State(state): State<AppState>,
+    Path((contract_id, review_id)): Path<(Uuid, i32)>,
+    claims: AuthClaims,
+    Json(payload): Json<ReviewVoteRequest>,
+) -> ApiResult<Json<ReviewVoteResponse>> {
+    let (vote, vote_recorded) = record_helpful_vote(
+        &state.db,
+        contract_id,
+        review_id,
+        &claims.sub,
+        Some(payload.helpful),
     )
-    .bind(review_id)
-    .fetch_one(&pool)
-    .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "database error fetching helpful count");
-        ApiError::internal("Failed to fetch updated helpful count")
-    })?;
+    .await?;
 
     Ok(Json(ReviewVoteResponse {
         review_id,
-        helpful_count,
+        helpful_count: vote.helpful_count,
         vote_recorded,
     }))
 }
@@ -454,7 +466,7 @@ pub async fn vote_review(
 // ═══════════════════════════════════════════════════════════════════════════
 
 pub async fn flag_review(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
     Path((contract_id, review_id)): Path<(Uuid, i32)>,
     claims: AuthClaims,
     Json(payload): Json<FlagReviewRequest>,
@@ -465,7 +477,7 @@ pub async fn flag_review(
     )
     .bind(review_id)
     .bind(contract_id)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error checking review existence");
@@ -484,7 +496,7 @@ pub async fn flag_review(
         "SELECT id FROM publishers WHERE stellar_address = $1",
     )
     .bind(&claims.sub)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error fetching publisher");
@@ -506,7 +518,7 @@ pub async fn flag_review(
     )
     .bind(review_id)
     .bind(user_id)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error checking existing flag");
@@ -527,7 +539,7 @@ pub async fn flag_review(
     .bind(review_id)
     .bind(user_id)
     .bind(&payload.reason)
-    .execute(&pool)
+    .execute(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error inserting flag");
@@ -537,7 +549,7 @@ pub async fn flag_review(
     // Mark review as flagged
     sqlx::query("UPDATE reviews SET is_flagged = true WHERE id = $1")
         .bind(review_id)
-        .execute(&pool)
+        .execute(&state.db)
         .await
         .map_err(|e| {
             tracing::error!(error = ?e, "database error updating review flag status");
@@ -565,7 +577,7 @@ pub async fn flag_review(
 // ═══════════════════════════════════════════════════════════════════════════
 
 pub async fn moderate_review(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
     Path((contract_id, review_id)): Path<(Uuid, i32)>,
     claims: AuthClaims,
     Json(payload): Json<ModerateReviewRequest>,
@@ -573,8 +585,7 @@ pub async fn moderate_review(
     // Verify admin status
     if !claims.admin && claims.role.as_deref() != Some("admin") {
         return Err(ApiError::forbidden(
-            "AdminRequired",
-            "Only administrators can moderate reviews".to_string(),
+            "Only administrators can moderate reviews",
         ));
     }
 
@@ -596,7 +607,7 @@ pub async fn moderate_review(
     )
     .bind(review_id)
     .bind(contract_id)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error checking review existence");
@@ -611,28 +622,18 @@ pub async fn moderate_review(
     }
 
     // Update review status
-    let review = sqlx::query_as::<_, ReviewResponse>(
+    let review = sqlx::query_as::<_, ReviewResponse>(&format!(
         r#"
         UPDATE reviews 
         SET status = $1
         WHERE id = $2
-        RETURNING 
-            id, 
-            contract_id, 
-            user_id, 
-            version, 
-            rating::float8 as "rating!", 
-            review_text, 
-            helpful_count, 
-            is_flagged, 
-            status, 
-            created_at,
-            updated_at
+        RETURNING {}
         "#,
-    )
+        REVIEW_COLUMNS
+    ))
     .bind(&new_status)
     .bind(review_id)
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error updating review status");
@@ -643,7 +644,7 @@ pub async fn moderate_review(
     if new_status == ReviewStatus::Approved {
         sqlx::query("UPDATE review_flags SET resolved = true WHERE review_id = $1")
             .bind(review_id)
-            .execute(&pool)
+            .execute(&state.db)
             .await
             .ok(); // Don't fail if this fails
     }
@@ -668,64 +669,53 @@ pub async fn moderate_review(
 // - Total number of reviews
 // - Distribution of ratings (1-5 stars)
 //
+// The same aggregate is part of GET /api/contracts/:id as `ratings`.
+//
 // Performance:
 // - Computed dynamically on each request
 // - Can be cached at application layer for high-traffic contracts
 // - Uses database indexes on (contract_id, status) for efficient filtering
 // ═══════════════════════════════════════════════════════════════════════════
 
-pub async fn get_rating_stats(
-    State(pool): State<PgPool>,
-    Path(contract_id): Path<Uuid>,
-) -> ApiResult<Json<ContractRatingStats>> {
-    // Fetch aggregated stats in a single query
-    let stats = sqlx::query(
+/// Average rating and star histogram of a contract's approved reviews
+pub async fn rating_stats(db: &PgPool, contract_id: Uuid) -> sqlx::Result<ContractRatingStats> {
+    sqlx::query_as(
         r#"
-        SELECT 
-            AVG(rating)::float8 as avg_rating,
-            COUNT(*) as total_reviews,
-            COUNT(*) FILTER (WHERE rating >= 1.0 AND rating < 2.0) as stars_1,
-            COUNT(*) FILTER (WHERE rating >= 2.0 AND rating < 3.0) as stars_2,
-            COUNT(*) FILTER (WHERE rating >= 3.0 AND rating < 4.0) as stars_3,
-            COUNT(*) FILTER (WHERE rating >= 4.0 AND rating < 5.0) as stars_4,
-            COUNT(*) FILTER (WHERE rating >= 5.0) as stars_5
+        SELECT
+            COALESCE(AVG(rating), 0)::float8 AS average_rating,
+            COUNT(*) AS total_reviews,
+            COUNT(*) FILTER (WHERE FLOOR(rating) = 1) AS stars_1,
+            COUNT(*) FILTER (WHERE FLOOR(rating) = 2) AS stars_2,
+            COUNT(*) FILTER (WHERE FLOOR(rating) = 3) AS stars_3,
+            COUNT(*) FILTER (WHERE FLOOR(rating) = 4) AS stars_4,
+            COUNT(*) FILTER (WHERE FLOOR(rating) = 5) AS stars_5
         FROM reviews
         WHERE contract_id = $1 AND status = 'approved'
         "#,
     )
     .bind(contract_id)
-    .fetch_one(&pool)
+    .fetch_one(db)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, contract_id = %contract_id, "database error fetching rating stats");
-        ApiError::internal("Failed to fetch rating statistics")
-    })?;
-
-    let avg_rating: Option<f64> = stats.try_get("avg_rating").ok().flatten();
-    let total_reviews: Option<i64> = stats.try_get("total_reviews").ok().flatten();
-    let stars_1: Option<i64> = stats.try_get("stars_1").ok().flatten();
-    let stars_2: Option<i64> = stats.try_get("stars_2").ok().flatten();
-    let stars_3: Option<i64> = stats.try_get("stars_3").ok().flatten();
-    let stars_4: Option<i64> = stats.try_get("stars_4").ok().flatten();
-    let stars_5: Option<i64> = stats.try_get("stars_5").ok().flatten();
-
-    let rating_distribution = if total_reviews.unwrap_or(0) > 0 {
-        Some(RatingDistribution {
-            stars_1: stars_1.unwrap_or(0),
-            stars_2: stars_2.unwrap_or(0),
-            stars_3: stars_3.unwrap_or(0),
-            stars_4: stars_4.unwrap_or(0),
-            stars_5: stars_5.unwrap_or(0),
-        })
-    } else {
-        None
-    };
+}
 
-    Ok(Json(ContractRatingStats {
-        average_rating: avg_rating.unwrap_or(0.0),
-        total_reviews: total_reviews.unwrap_or(0),
-        rating_distribution,
-    }))
+/// Rating aggregate of a contract's approved reviews.
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/rating-stats",
+    params(("id" = Uuid, Path, description = "Contract ID")),
+    responses(
+        (status = 200, description = "Average rating and star histogram", body = ContractRatingStats)
+    ),
+    tag = "Reviews"
+)]
+pub async fn get_rating_stats(
+    State(state): State<AppState>,
+    Path(contract_id): Path<Uuid>,
+) -> ApiResult<Json<ContractRatingStats>> {
+    rating_stats(&state.db, contract_id)
+        .await
+        .map(Json)
+        .map_err(|err| db_internal_error("fetch rating stats", err))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -733,12 +723,12 @@ pub async fn get_rating_stats(
 // ═══════════════════════════════════════════════════════════════════════════
 
 pub async fn get_pending_reviews_count(
-    State(pool): State<PgPool>,
+    State(state): State<AppState>,
 ) -> ApiResult<Json<serde_json::Value>> {
     let count = sqlx::query_scalar::<_, i64>(
         "SELECT COUNT(*) FROM reviews WHERE status = 'pending'",
     )
-    .fetch_one(&pool)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| {
         tracing::error!(error = ?e, "database error fetching pending reviews count");
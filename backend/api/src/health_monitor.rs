@@ -6,6 +6,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 
 use crate::state::AppState;
@@ -84,14 +85,21 @@ impl HealthMonitorStatus {
 }
 
 /// Main loop for the health monitor background task
-pub async fn run_health_monitor(state: AppState, status: HealthMonitorStatus) {
+pub async fn run_health_monitor(
+    state: AppState,
+    status: HealthMonitorStatus,
+    shutdown: CancellationToken,
+) {
     info!("Starting health monitor background task");
 
     // Run every hour
     let mut interval = time::interval(time::Duration::from_secs(3600));
 
     loop {
-        interval.tick().await;
+        tokio::select! {
+            _ = shutdown.cancelled() => break,
+            _ = interval.tick() => {}
+        }
         info!("Running health checks...");
 
         status.total_runs.fetch_add(1, Ordering::Relaxed);
@@ -111,6 +119,7 @@ pub async fn run_health_monitor(state: AppState, status: HealthMonitorStatus) {
             }
         }
     }
+    info!("Health monitor stopped");
 }
 
 async fn perform_health_checks(pool: &PgPool, status: &HealthMonitorStatus) -> Result<()> {
@@ -378,9 +387,16 @@ mod tests {
             is_maintenance: false,
             logical_id: None,
             network_configs: None,
-            last_verified_at: None,
-            deployment_count: 0,
-            audit_status: shared::AuditStatus::None,
+            verified_at: None,
+            last_accessed_at: None,
+            relevance_score: None,
+            organization_id: None,
+            visibility: shared::VisibilityType::Public,
+            namespace: None,
+            slug: None,
+            publish_status: shared::PublishStatus::Published,
+            revision: 0,
+            freshness: shared::Freshness::default(),
         }
     }
 
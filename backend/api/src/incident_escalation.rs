@@ -0,0 +1,317 @@
+//! Escalation of incidents nobody acknowledges.
+//!
+//! An open incident at or above its policy's `min_severity` escalates until
+//! someone calls `POST /api/incidents/:id/ack`. The policy for the incident's
+//! type applies, or else the global one; without either nothing escalates.
+//! Each step waits `delay_seconds` after the previous one fired (the first
+//! after the incident was opened), then re-notifies its channels through the
+//! incident notification queue and records itself in the incident timeline.
+//!
+//! `incidents.escalation_step` counts the steps fired and is advanced with a
+//! compare-and-set, so a restarted scheduler carries on where it stopped and
+//! concurrent replicas never fire a step twice. A scheduler that was down
+//! fires one overdue step per run rather than the whole backlog at once.
+//! Editing a policy keeps the number of steps each incident has fired.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use sqlx::{types::Json, FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::escalation_policy_models::EscalationStep;
+use crate::incident_notifications;
+use crate::shutdown::Shutdown;
+
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_secs(30);
+const AUTHOR: &str = "escalation";
+const BATCH_SIZE: i64 = 200;
+
+/// Source of the current time, replaced in tests
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Index of the step to fire now, if one is due. `fired` steps have fired
+/// already and the wait for the next one began at `clock_at`.
+pub fn due_step(
+    steps: &[EscalationStep],
+    fired: i32,
+    clock_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<usize> {
+    let next = usize::try_from(fired).ok()?;
+    let step = steps.get(next)?;
+    (now >= clock_at + chrono::Duration::seconds(step.delay_seconds)).then_some(next)
+}
+
+/// An open, unacknowledged incident with steps left in its policy
+#[derive(Debug, FromRow)]
+struct Candidate {
+    id: Uuid,
+    incident_type: String,
+    escalation_step: i32,
+    escalation_clock_at: DateTime<Utc>,
+    policy_type: Option<String>,
+    steps: Json<Vec<EscalationStep>>,
+}
+
+/// Spawn the background task that escalates unacknowledged incidents.
+pub fn spawn_escalation_scheduler(pool: PgPool, shutdown: &Shutdown) {
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            if let Err(err) = escalate_due(&pool, &SystemClock).await {
+                tracing::error!(error = ?err, "incident escalation: run failed");
+            }
+        }
+        tracing::info!("incident escalation: scheduler stopped");
+    });
+}
+
+/// Fires every step that is due at `clock.now()`, returning how many fired.
+pub async fn escalate_due(pool: &PgPool, clock: &dyn Clock) -> Result<usize, sqlx::Error> {
+    let candidates: Vec<Candidate> = sqlx::query_as(
+        r#"
+        SELECT i.id, i.incident_type, i.escalation_step, i.escalation_clock_at,
+               p.incident_type AS policy_type, p.steps
+        FROM incidents i
+        JOIN LATERAL (
+            SELECT incident_type, min_severity, steps FROM escalation_policies
+            WHERE incident_type = i.incident_type OR incident_type IS NULL
+            ORDER BY incident_type IS NULL
+            LIMIT 1
+        ) p ON TRUE
+        WHERE i.end_time IS NULL AND i.deleted_at IS NULL AND i.acknowledged_at IS NULL
+          AND i.severity <= p.min_severity
+          AND i.escalation_step < jsonb_array_length(p.steps)
+        ORDER BY i.escalation_clock_at
+        LIMIT $1
+        "#,
+    )
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    let now = clock.now();
+    let mut fired = 0;
+    for candidate in &candidates {
+        let Some(index) = due_step(
+            &candidate.steps,
+            candidate.escalation_step,
+            candidate.escalation_clock_at,
+            now,
+        ) else {
+            continue;
+        };
+        if fire(pool, candidate, index, now).await? {
+            fired += 1;
+        }
+    }
+    Ok(fired)
+}
+
+/// Fires step `index` unless the incident moved on since it was read.
+async fn fire(
+    pool: &PgPool,
+    candidate: &Candidate,
+    index: usize,
+    now: DateTime<Utc>,
+) -> Result<bool, sqlx::Error> {
+    let step = &candidate.steps[index];
+    let mut tx = pool.begin().await?;
+
+    let advanced = sqlx::query(
+        r#"
+        UPDATE incidents SET escalation_step = $2 + 1, escalation_clock_at = $3
+        WHERE id = $1 AND escalation_step = $2
+          AND end_time IS NULL AND deleted_at IS NULL AND acknowledged_at IS NULL
+        "#,
+    )
+    .bind(candidate.id)
+    .bind(candidate.escalation_step)
+    .bind(now)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    if advanced == 0 {
+        // Acknowledged, resolved, or fired by another replica in the meantime
+        return Ok(false);
+    }
+
+    let notified =
+        incident_notifications::enqueue_channels(&mut tx, candidate.id, &step.channel_ids).await?;
+
+    let policy = match &candidate.policy_type {
+        Some(incident_type) => format!("{} policy", incident_type),
+        None => "global policy".to_string(),
+    };
+    let recipients = if notified.is_empty() {
+        "none of its channels are enabled".to_string()
+    } else {
+        format!("notifying {}", notified.join(", "))
+    };
+    let message = format!(
+        "Not acknowledged; escalation step {} of {} ({}): {}.",
+        index + 1,
+        candidate.steps.len(),
+        policy,
+        recipients
+    );
+    sqlx::query("INSERT INTO incident_updates (incident_id, author, message) VALUES ($1, $2, $3)")
+        .bind(candidate.id)
+        .bind(AUTHOR)
+        .bind(&message)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    if notified.is_empty() {
+        tracing::warn!(
+            incident_id = %candidate.id,
+            step = index + 1,
+            "incident escalated, but none of the step's channels are enabled"
+        );
+    } else {
+        tracing::info!(
+            incident_id = %candidate.id,
+            incident_type = %candidate.incident_type,
+            step = index + 1,
+            channels = notified.len(),
+            "incident escalated"
+        );
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// A clock that only moves when told to
+    struct ManualClock(Mutex<DateTime<Utc>>);
+
+    impl ManualClock {
+        fn new() -> Self {
+            Self(Mutex::new(
+                DateTime::from_timestamp(1_800_000_000, 0).unwrap(),
+            ))
+        }
+
+        fn advance(&self, seconds: i64) {
+            *self.0.lock().unwrap() += chrono::Duration::seconds(seconds);
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    fn steps(delays: &[i64]) -> Vec<EscalationStep> {
+        delays
+            .iter()
+            .map(|&delay_seconds| EscalationStep {
+                delay_seconds,
+                channel_ids: vec![Uuid::nil()],
+            })
+            .collect()
+    }
+
+    /// The scheduler's view of one incident, advanced the way `fire` does
+    struct Escalation {
+        fired: i32,
+        clock_at: DateTime<Utc>,
+    }
+
+    impl Escalation {
+        fn opened(clock: &ManualClock) -> Self {
+            Self {
+                fired: 0,
+                clock_at: clock.now(),
+            }
+        }
+
+        fn run(&mut self, steps: &[EscalationStep], clock: &ManualClock) -> Option<usize> {
+            let index = due_step(steps, self.fired, self.clock_at, clock.now())?;
+            self.fired += 1;
+            self.clock_at = clock.now();
+            Some(index)
+        }
+    }
+
+    #[test]
+    fn steps_fire_after_their_delay_from_the_previous_step() {
+        let clock = ManualClock::new();
+        let steps = steps(&[300, 900]);
+        let mut incident = Escalation::opened(&clock);
+
+        assert_eq!(incident.run(&steps, &clock), None);
+        clock.advance(299);
+        assert_eq!(incident.run(&steps, &clock), None);
+        clock.advance(1);
+        assert_eq!(incident.run(&steps, &clock), Some(0));
+        assert_eq!(incident.run(&steps, &clock), None);
+
+        clock.advance(899);
+        assert_eq!(incident.run(&steps, &clock), None);
+        clock.advance(1);
+        assert_eq!(incident.run(&steps, &clock), Some(1));
+
+        clock.advance(86_400);
+        assert_eq!(incident.run(&steps, &clock), None, "no steps left");
+    }
+
+    #[test]
+    fn overdue_steps_fire_one_per_run_and_restart_the_wait() {
+        let clock = ManualClock::new();
+        let steps = steps(&[0, 60, 60]);
+        let mut incident = Escalation::opened(&clock);
+
+        // The scheduler was down for an hour
+        clock.advance(3600);
+        assert_eq!(incident.run(&steps, &clock), Some(0));
+        assert_eq!(incident.run(&steps, &clock), None);
+        clock.advance(60);
+        assert_eq!(incident.run(&steps, &clock), Some(1));
+    }
+
+    #[test]
+    fn fired_count_survives_a_restart_and_policy_edits() {
+        let clock = ManualClock::new();
+        let opened = clock.now();
+        clock.advance(120);
+
+        // Persisted state after step 1 fired at +60s
+        let fired = 1;
+        let clock_at = opened + chrono::Duration::seconds(60);
+        assert_eq!(
+            due_step(&steps(&[60, 60]), fired, clock_at, clock.now()),
+            Some(1)
+        );
+        assert_eq!(
+            due_step(&steps(&[60, 600]), fired, clock_at, clock.now()),
+            None
+        );
+        assert_eq!(due_step(&steps(&[60]), fired, clock_at, clock.now()), None);
+        assert_eq!(due_step(&steps(&[60]), -1, clock_at, clock.now()), None);
+    }
+}
@@ -0,0 +1,401 @@
+//! Atom (RFC 4287) feed of incident activity for feed readers.
+//!
+//! Entry ids are `tag:` URIs derived from the underlying rows, so an entry
+//! keeps the same id across requests and readers don't show duplicates.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use uuid::Uuid;
+
+use crate::{
+    incident_models::{Incident, IncidentUpdate},
+    maintenance_models::MaintenanceWindow,
+};
+
+/// Maximum number of entries in the feed
+pub const FEED_ENTRY_LIMIT: usize = 50;
+
+const TAG_PREFIX: &str = "tag:soroban-registry,2026:";
+
+/// One `<entry>` in the feed. `summary` is plain text; it is HTML-escaped
+/// when rendered.
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    pub id: String,
+    pub title: String,
+    pub updated: DateTime<Utc>,
+    pub link: String,
+    pub category: &'static str,
+    pub summary: String,
+}
+
+impl FeedEntry {
+    pub fn opened(base_url: &str, incident: &Incident) -> Self {
+        Self {
+            id: format!("{}incidents/{}/opened", TAG_PREFIX, incident.id),
+            title: format!(
+                "[{}] {} incident opened",
+                incident.severity.as_str().to_uppercase(),
+                incident.incident_type
+            ),
+            updated: incident.created_at,
+            link: incident_link(base_url, incident.id),
+            category: "incident",
+            summary: incident.description.clone(),
+        }
+    }
+
+    /// Returns `None` for incidents that are still open.
+    pub fn resolved(base_url: &str, incident: &Incident) -> Option<Self> {
+        let end_time = incident.end_time?;
+        Some(Self {
+            id: format!("{}incidents/{}/resolved", TAG_PREFIX, incident.id),
+            title: format!("{} incident resolved", incident.incident_type),
+            updated: end_time,
+            link: incident_link(base_url, incident.id),
+            category: "resolution",
+            summary: incident.description.clone(),
+        })
+    }
+
+    pub fn status_update(base_url: &str, incident_type: &str, update: &IncidentUpdate) -> Self {
+        let status = update.status.map_or("update", |s| s.as_str());
+        Self {
+            id: format!(
+                "{}incidents/{}/updates/{}",
+                TAG_PREFIX, update.incident_id, update.id
+            ),
+            title: format!("{} incident: {}", incident_type, status),
+            updated: update.created_at,
+            link: incident_link(base_url, update.incident_id),
+            category: "status",
+            summary: update.message.clone(),
+        }
+    }
+
+    pub fn severity_change(
+        base_url: &str,
+        audit_id: Uuid,
+        incident_id: Uuid,
+        incident_type: &str,
+        from: &str,
+        to: &str,
+        at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: format!("{}incidents/{}/audit/{}", TAG_PREFIX, incident_id, audit_id),
+            title: format!("{} incident severity changed to {}", incident_type, to),
+            updated: at,
+            link: incident_link(base_url, incident_id),
+            category: "status",
+            summary: format!("Severity changed from {} to {}.", from, to),
+        }
+    }
+
+    /// One entry per window; status changes update it in place.
+    pub fn maintenance(base_url: &str, window: &MaintenanceWindow) -> Self {
+        let summary = format!(
+            "{} from {} to {}.{}",
+            window.status.as_str().replace('_', " "),
+            timestamp(window.scheduled_start),
+            timestamp(window.scheduled_end),
+            window
+                .description
+                .as_deref()
+                .map(|d| format!(" {}", d))
+                .unwrap_or_default()
+        );
+        Self {
+            id: format!("{}maintenance/{}", TAG_PREFIX, window.id),
+            title: format!("Maintenance: {}", window.title),
+            updated: window.updated_at,
+            link: format!(
+                "{}/api/maintenance-windows/{}",
+                base_url.trim_end_matches('/'),
+                window.id
+            ),
+            category: "maintenance",
+            summary: capitalize_first(&summary),
+        }
+    }
+
+    pub fn publication(
+        base_url: &str,
+        verification_id: Uuid,
+        contract_id: Uuid,
+        contract_name: &str,
+        verified_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: format!(
+                "{}contracts/{}/verifications/{}",
+                TAG_PREFIX, contract_id, verification_id
+            ),
+            title: format!("Contract verified: {}", contract_name),
+            updated: verified_at,
+            link: format!(
+                "{}/api/contracts/{}",
+                base_url.trim_end_matches('/'),
+                contract_id
+            ),
+            category: "publication",
+            summary: format!("{} is now verified in the registry.", contract_name),
+        }
+    }
+}
+
+fn incident_link(base_url: &str, incident_id: Uuid) -> String {
+    format!(
+        "{}/api/incidents/{}",
+        base_url.trim_end_matches('/'),
+        incident_id
+    )
+}
+
+/// Sorts newest first, keeps the most recent [`FEED_ENTRY_LIMIT`] entries and
+/// renders the feed document.
+pub fn render_atom(base_url: &str, mut entries: Vec<FeedEntry>, now: DateTime<Utc>) -> String {
+    entries.sort_by(|a, b| b.updated.cmp(&a.updated).then_with(|| a.id.cmp(&b.id)));
+    entries.truncate(FEED_ENTRY_LIMIT);
+
+    let base = base_url.trim_end_matches('/');
+    let updated = entries.first().map(|e| e.updated).unwrap_or(now);
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    out.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    let _ = writeln!(out, "  <id>{}incidents</id>", TAG_PREFIX);
+    out.push_str("  <title>Soroban Registry incidents</title>\n");
+    let _ = writeln!(out, "  <updated>{}</updated>", timestamp(updated));
+    let _ = writeln!(
+        out,
+        "  <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>",
+        escape_xml(&format!("{}/api/incidents/feed.atom", base))
+    );
+    let _ = writeln!(
+        out,
+        "  <link rel=\"alternate\" href=\"{}\"/>",
+        escape_xml(&format!("{}/api/status", base))
+    );
+    out.push_str("  <author><name>Soroban Registry</name></author>\n");
+
+    for entry in &entries {
+        out.push_str("  <entry>\n");
+        let _ = writeln!(out, "    <id>{}</id>", escape_xml(&entry.id));
+        let _ = writeln!(out, "    <title>{}</title>", escape_xml(&entry.title));
+        let _ = writeln!(out, "    <updated>{}</updated>", timestamp(entry.updated));
+        let _ = writeln!(out, "    <link href=\"{}\"/>", escape_xml(&entry.link));
+        let _ = writeln!(out, "    <category term=\"{}\"/>", entry.category);
+        let _ = writeln!(
+            out,
+            "    <content type=\"html\">{}</content>",
+            escape_xml(&format!("<p>{}</p>", escape_xml(&entry.summary)))
+        );
+        out.push_str("  </entry>\n");
+    }
+
+    out.push_str("</feed>\n");
+    out
+}
+
+fn capitalize_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn timestamp(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Escapes text for use in XML character data and attribute values.
+pub(crate) fn escape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab/newline are not allowed in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::incident_models::{IncidentSeverity, IncidentUpdateStatus};
+    use chrono::TimeZone;
+
+    fn incident(end_time: Option<DateTime<Utc>>) -> Incident {
+        let start = Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap();
+        Incident {
+            id: Uuid::nil(),
+            contract_id: None,
+            incident_type: "outage".to_string(),
+            severity: IncidentSeverity::Critical,
+            description: "RPC <down> & \"unreachable\"".to_string(),
+            start_time: start,
+            end_time,
+            rto_achieved: None,
+            rpo_achieved: None,
+            lessons_learned: None,
+            notified_users: false,
+            created_at: start,
+            updated_at: start,
+            deleted_at: None,
+            maintenance_window_id: None,
+            source: "api".to_string(),
+            external_id: None,
+            objectives: Default::default(),
+            escalation: Default::default(),
+            affected_contracts: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Checks the RFC 4287 requirements we rely on: a single root `feed` in the
+    /// Atom namespace with id/title/updated, every entry carrying
+    /// id/title/updated/link, RFC 3339 timestamps, and balanced tags.
+    fn assert_valid_atom(xml: &str) {
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n"));
+        assert!(xml.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+
+        let mut stack: Vec<String> = Vec::new();
+        let mut rest = &xml[xml.find("?>").unwrap() + 2..];
+        while let Some(start) = rest.find('<') {
+            let end = rest[start..].find('>').expect("unterminated tag") + start;
+            let tag = &rest[start + 1..end];
+            assert!(!rest[..start].contains('>'), "stray '>' in text");
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(stack.pop().as_deref(), Some(name), "mismatched </{}>", name);
+            } else if !tag.ends_with('/') {
+                stack.push(tag.split_whitespace().next().unwrap().to_string());
+            }
+            rest = &rest[end + 1..];
+        }
+        assert!(stack.is_empty(), "unclosed tags: {:?}", stack);
+
+        let head = &xml[..xml.find("<entry>").unwrap_or(xml.len())];
+        for required in ["<id>", "<title>", "<updated>"] {
+            assert_eq!(head.matches(required).count(), 1, "feed {}", required);
+        }
+        for entry in xml.split("<entry>").skip(1) {
+            let entry = &entry[..entry.find("</entry>").unwrap()];
+            for required in ["<id>", "<title>", "<updated>", "<link "] {
+                assert_eq!(entry.matches(required).count(), 1, "entry {}", required);
+            }
+        }
+        for updated in xml.split("<updated>").skip(1) {
+            let value = &updated[..updated.find("</updated>").unwrap()];
+            assert!(DateTime::parse_from_rfc3339(value).is_ok(), "{}", value);
+        }
+    }
+
+    #[test]
+    fn feed_is_valid_atom_and_escapes_content() {
+        let end = Utc.with_ymd_and_hms(2026, 10, 1, 14, 0, 0).unwrap();
+        let incident = incident(Some(end));
+        let update = IncidentUpdate {
+            id: Uuid::from_u128(1),
+            incident_id: incident.id,
+            author: "ops".to_string(),
+            message: "<script>alert(1)</script>".to_string(),
+            status: Some(IncidentUpdateStatus::Identified),
+            post_resolution: false,
+            created_at: Utc.with_ymd_and_hms(2026, 10, 1, 13, 0, 0).unwrap(),
+        };
+        let entries = vec![
+            FeedEntry::opened("https://registry.example/", &incident),
+            FeedEntry::status_update("https://registry.example/", "outage", &update),
+            FeedEntry::resolved("https://registry.example/", &incident).unwrap(),
+        ];
+
+        let xml = render_atom("https://registry.example/", entries, Utc::now());
+        assert_valid_atom(&xml);
+
+        assert!(xml.contains("<updated>2026-10-01T14:00:00Z</updated>\n  <link rel=\"self\""));
+        assert!(xml
+            .contains("&lt;p&gt;&amp;lt;script&amp;gt;alert(1)&amp;lt;/script&amp;gt;&lt;/p&gt;"));
+        assert!(xml.contains("RPC &amp;lt;down&amp;gt; &amp;amp; &amp;quot;unreachable&amp;quot;"));
+        assert!(xml.contains("<title>outage incident: identified</title>"));
+        assert!(xml.contains("href=\"https://registry.example/api/incidents/feed.atom\""));
+
+        let resolved = xml.find("/resolved</id>").unwrap();
+        let opened = xml.find("/opened</id>").unwrap();
+        assert!(resolved < opened, "entries must be newest first");
+    }
+
+    #[test]
+    fn entry_ids_are_stable_and_open_incidents_have_no_resolution() {
+        let incident = incident(None);
+        assert!(FeedEntry::resolved("", &incident).is_none());
+        assert_eq!(
+            FeedEntry::opened("", &incident).id,
+            FeedEntry::opened("http://other", &incident).id
+        );
+    }
+
+    #[test]
+    fn feed_keeps_most_recent_entries() {
+        let incident = incident(None);
+        let entries: Vec<FeedEntry> = (0..60)
+            .map(|i| FeedEntry {
+                id: format!("{}test/{}", TAG_PREFIX, i),
+                updated: incident.created_at + chrono::Duration::minutes(i),
+                ..FeedEntry::opened("", &incident)
+            })
+            .collect();
+
+        let xml = render_atom("", entries, Utc::now());
+        assert_valid_atom(&xml);
+        assert_eq!(xml.matches("<entry>").count(), FEED_ENTRY_LIMIT);
+        assert!(xml.contains("test/59</id>"));
+        assert!(!xml.contains("test/9</id>"));
+    }
+
+    #[test]
+    fn empty_feed_is_still_valid() {
+        let xml = render_atom("", Vec::new(), Utc::now());
+        assert_valid_atom(&xml);
+    }
+
+    #[test]
+    fn maintenance_entry_is_keyed_by_window() {
+        use crate::maintenance_models::MaintenanceWindowStatus;
+
+        let start = Utc.with_ymd_and_hms(2026, 11, 1, 2, 0, 0).unwrap();
+        let window = MaintenanceWindow {
+            id: Uuid::from_u128(7),
+            title: "Database upgrade".to_string(),
+            description: Some("Postgres 16".to_string()),
+            scheduled_start: start,
+            scheduled_end: start + chrono::Duration::hours(2),
+            status: MaintenanceWindowStatus::InProgress,
+            components: vec!["database".to_string()],
+            created_by: "admin".to_string(),
+            created_at: start,
+            updated_at: start,
+            affected_contracts: Vec::new(),
+        };
+
+        let entry = FeedEntry::maintenance("", &window);
+        assert!(entry
+            .id
+            .ends_with("maintenance/00000000-0000-0000-0000-000000000007"));
+        assert_eq!(entry.title, "Maintenance: Database upgrade");
+        assert_eq!(
+            entry.summary,
+            "In progress from 2026-11-01T02:00:00Z to 2026-11-01T04:00:00Z. Postgres 16"
+        );
+        assert_valid_atom(&render_atom("", vec![entry], Utc::now()));
+    }
+}
@@ -0,0 +1,2175 @@
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use shared::{pagination::Paginated, ContractIncidentSummary};
+use sqlx::{FromRow, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::{db_internal_error, map_query_rejection},
+    incident_feed::{self, FeedEntry},
+    incident_models::{
+        requires_recovery_validation, uptime_percentage, AffectedContract, CreateIncidentRequest,
+        CreateIncidentUpdateRequest, CreateRecoveryValidationRequest, DurationInput, Incident,
+        IncidentActionItem, IncidentAuditEntry, IncidentCount, IncidentDetail, IncidentFeedQuery,
+        IncidentMetrics, IncidentMetricsQuery, IncidentReportQuery, IncidentSeverity,
+        IncidentUpdate, ListActionItemsQuery, ListIncidentsQuery, LongestOpenIncident,
+        MonthlyIncidentMetrics, NotificationChannel, NotificationDelivery, NotificationPreview,
+        ObjectiveCompliance, OverallStatus, Postmortem, PublicIncident, RecoveryValidation,
+        RecoveryValidationCoverage, RegistryStatus, UpdateActionItemRequest, UpdateIncidentRequest,
+        UpdatePostmortemRequest, UptimeSummary, RECOVERY_VALIDATED_INCIDENT_TYPES,
+    },
+    incident_notifications, incident_report,
+    legal_holds::{self, HoldTarget},
+    maintenance_handlers,
+    pagination::{PageParams, Pagination},
+    recovery_objective_handlers::resolve_targets,
+    recovery_objective_models::ObjectiveOutcome,
+    state::AppState,
+    subscription_notifications,
+};
+
+fn parse_incident_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidIncidentId",
+            format!("Invalid incident ID format: {}", id),
+        )
+    })
+}
+
+/// Combines the legacy singular `contract_id` with `affected_contract_ids`,
+/// dropping duplicates while keeping the caller's order.
+pub(crate) fn merge_affected_contract_ids(legacy: Option<Uuid>, ids: &[Uuid]) -> Vec<Uuid> {
+    let mut merged: Vec<Uuid> = Vec::with_capacity(ids.len() + 1);
+    for id in legacy.into_iter().chain(ids.iter().copied()) {
+        if !merged.contains(&id) {
+            merged.push(id);
+        }
+    }
+    merged
+}
+
+/// Rejects the request with 422 when any of the referenced contracts is unknown.
+pub(crate) async fn ensure_contracts_exist(state: &AppState, ids: &[Uuid]) -> ApiResult<()> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let found: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM contracts WHERE id = ANY($1)")
+        .bind(ids)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("validate affected contracts", err))?;
+
+    let unknown: Vec<Uuid> = ids
+        .iter()
+        .filter(|id| !found.contains(id))
+        .copied()
+        .collect();
+    if unknown.is_empty() {
+        return Ok(());
+    }
+
+    Err(ApiError::unprocessable(
+        "UnknownContracts",
+        format!(
+            "Unknown contract IDs: {}",
+            unknown
+                .iter()
+                .map(Uuid::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    )
+    .with_details(serde_json::json!({ "unknown_contract_ids": unknown })))
+}
+
+/// Replaces the incident's affected contracts and keeps the legacy
+/// `incidents.contract_id` column pointing at the first one.
+async fn replace_affected_contracts(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    incident: &mut Incident,
+    ids: &[Uuid],
+) -> ApiResult<()> {
+    sqlx::query("DELETE FROM incident_contracts WHERE incident_id = $1")
+        .bind(incident.id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("clear affected contracts", err))?;
+
+    sqlx::query(
+        "INSERT INTO incident_contracts (incident_id, contract_id)
+         SELECT $1, UNNEST($2::UUID[])",
+    )
+    .bind(incident.id)
+    .bind(ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(|err| db_internal_error("insert affected contracts", err))?;
+
+    let primary = ids.first().copied();
+    if incident.contract_id != primary {
+        sqlx::query("UPDATE incidents SET contract_id = $2 WHERE id = $1")
+            .bind(incident.id)
+            .bind(primary)
+            .execute(&mut **tx)
+            .await
+            .map_err(|err| db_internal_error("update primary affected contract", err))?;
+        incident.contract_id = primary;
+    }
+
+    Ok(())
+}
+
+#[derive(FromRow)]
+struct AffectedContractRow {
+    incident_id: Uuid,
+    id: Uuid,
+    name: String,
+}
+
+/// Looks up the affected contracts for a batch of incidents with a single query.
+async fn fetch_affected_contracts(
+    state: &AppState,
+    incident_ids: &[Uuid],
+) -> ApiResult<Vec<AffectedContractRow>> {
+    if incident_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    sqlx::query_as(
+        "SELECT ic.incident_id, c.id, c.name
+         FROM incident_contracts ic
+         JOIN contracts c ON c.id = ic.contract_id
+         WHERE ic.incident_id = ANY($1)
+         ORDER BY c.name",
+    )
+    .bind(incident_ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("load affected contracts", err))
+}
+
+fn affected_contracts_for(
+    rows: &[AffectedContractRow],
+    incident_id: Uuid,
+) -> Vec<AffectedContract> {
+    rows.iter()
+        .filter(|row| row.incident_id == incident_id)
+        .map(|row| AffectedContract {
+            id: row.id,
+            name: row.name.clone(),
+        })
+        .collect()
+}
+
+/// Fills `affected_contracts` for a batch of incidents.
+async fn load_affected_contracts(state: &AppState, incidents: &mut [Incident]) -> ApiResult<()> {
+    let ids: Vec<Uuid> = incidents.iter().map(|i| i.id).collect();
+    let rows = fetch_affected_contracts(state, &ids).await?;
+
+    for incident in incidents.iter_mut() {
+        incident.affected_contracts = affected_contracts_for(&rows, incident.id);
+    }
+
+    Ok(())
+}
+
+/// Validates an optional RTO/RPO value, naming the offending field on failure.
+pub(crate) fn parse_recovery_duration(
+    field: &str,
+    input: Option<&DurationInput>,
+) -> ApiResult<Option<i64>> {
+    input
+        .map(|value| {
+            value.to_seconds().map_err(|reason| {
+                ApiError::unprocessable(
+                    "InvalidRecoveryDuration",
+                    format!(
+                        "{}: {}. Use integer seconds or an ISO-8601 duration such as \"PT2H30M\"",
+                        field, reason
+                    ),
+                )
+            })
+        })
+        .transpose()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/incidents",
+    request_body = CreateIncidentRequest,
+    responses(
+        (status = 201, description = "Incident created", body = Incident),
+        (status = 400, description = "Invalid incident payload"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 422, description = "Unknown affected contract IDs")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn create_incident(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<CreateIncidentRequest>,
+) -> ApiResult<(StatusCode, Json<Incident>)> {
+    if !auth::is_admin(&claims) {
+        return Err(ApiError::forbidden(
+            "Administrative privileges are required to open incidents",
+        ));
+    }
+    let incident = open_incident(&state, &req, None).await?;
+    Ok((StatusCode::CREATED, Json(incident)))
+}
+
+/// Creates an incident with its affected contracts, maintenance tagging and
+/// notifications. Shared by the API handler and the incident watchdog, which
+/// passes the component it is reporting on for de-duplication.
+pub(crate) async fn open_incident(
+    state: &AppState,
+    req: &CreateIncidentRequest,
+    watchdog_component: Option<&str>,
+) -> ApiResult<Incident> {
+    if req.incident_type.trim().is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidIncidentType",
+            "incident_type must not be empty",
+        ));
+    }
+
+    let affected_ids = merge_affected_contract_ids(req.contract_id, &req.affected_contract_ids);
+    ensure_contracts_exist(state, &affected_ids).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin create incident", err))?;
+
+    let mut incident: Incident = sqlx::query_as(
+        r#"
+        INSERT INTO incidents
+            (contract_id, incident_type, severity, description, start_time, watchdog_component)
+        VALUES ($1, $2, $3, $4, COALESCE($5, NOW()), $6)
+        RETURNING *
+        "#,
+    )
+    .bind(affected_ids.first().copied())
+    .bind(req.incident_type.trim())
+    .bind(req.severity)
+    .bind(&req.description)
+    .bind(req.start_time)
+    .bind(watchdog_component)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create incident", err))?;
+
+    if !affected_ids.is_empty() {
+        replace_affected_contracts(&mut tx, &mut incident, &affected_ids).await?;
+    }
+
+    incident.maintenance_window_id = maintenance_handlers::tag_incident(&mut tx, incident.id)
+        .await
+        .map_err(|err| db_internal_error("tag incident with maintenance window", err))?;
+    if let Some(window_id) = incident.maintenance_window_id {
+        tracing::info!(incident_id = %incident.id, %window_id, "incident started during maintenance");
+    }
+
+    if req.notify_users || incident_notifications::should_auto_notify(&incident) {
+        let queued = incident_notifications::enqueue(&mut tx, incident.id)
+            .await
+            .map_err(|err| db_internal_error("queue incident notifications", err))?;
+        tracing::info!(incident_id = %incident.id, queued, "incident notifications queued");
+    }
+
+    subscription_notifications::enqueue_incident(&mut tx, &incident, &affected_ids)
+        .await
+        .map_err(|err| db_internal_error("queue incident subscription notifications", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit create incident", err))?;
+
+    tracing::info!(
+        incident_id = %incident.id,
+        incident_type = %incident.incident_type,
+        severity = incident.severity.as_str(),
+        affected_contracts = affected_ids.len(),
+        source = if watchdog_component.is_some() { "watchdog" } else { "api" },
+        "incident opened"
+    );
+
+    load_affected_contracts(state, std::slice::from_mut(&mut incident)).await?;
+
+    Ok(incident)
+}
+
+/// Incident type of restore drills recorded by the snapshot endpoints
+pub(crate) const DR_DRILL_INCIDENT_TYPE: &str = "dr_drill";
+
+/// Records a finished disaster recovery drill as a resolved informational
+/// incident, so its measured RTO/RPO count towards objective compliance.
+pub(crate) async fn record_recovery_drill(
+    state: &AppState,
+    description: &str,
+    start_time: DateTime<Utc>,
+    rto_seconds: i64,
+    rpo_seconds: i64,
+) -> ApiResult<Incident> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin record recovery drill", err))?;
+
+    let mut incident: Incident = sqlx::query_as(
+        r#"
+        INSERT INTO incidents
+            (incident_type, severity, description, start_time, end_time,
+             rto_achieved_seconds, rpo_achieved_seconds)
+        VALUES ($1, $2, $3, $4, NOW(), $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(DR_DRILL_INCIDENT_TYPE)
+    .bind(IncidentSeverity::Informational)
+    .bind(description)
+    .bind(start_time)
+    .bind(rto_seconds)
+    .bind(rpo_seconds)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("record recovery drill", err))?;
+
+    record_objective_outcome(&mut tx, &mut incident, true).await?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit record recovery drill", err))?;
+
+    tracing::info!(
+        incident_id = %incident.id,
+        rto_seconds,
+        rpo_seconds,
+        rto_met = ?incident.objectives.rto_met,
+        "recovery drill recorded"
+    );
+    Ok(incident)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents",
+    params(ListIncidentsQuery, PageParams),
+    responses(
+        (status = 200, description = "Open critical incidents first, then by start time, newest first. A bare array is returned when envelope=false.", body = Paginated<Incident>),
+        (status = 400, description = "Invalid filter parameters")
+    ),
+    tag = "Incidents"
+)]
+pub async fn list_incidents(
+    State(state): State<AppState>,
+    claims: Option<AuthClaims>,
+    pagination: Pagination,
+    params: Result<Query<ListIncidentsQuery>, QueryRejection>,
+) -> ApiResult<Response> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+    let page = query_incidents(&state, claims.as_ref(), &query, pagination).await?;
+    Ok(incident_page_response(page, query.envelope))
+}
+
+/// Serializes a page as the standard `Paginated` envelope, or as the legacy
+/// bare array (flagged with a `Deprecation` header) when `envelope=false`.
+fn incident_page_response(page: Paginated<Incident>, envelope: bool) -> Response {
+    if envelope {
+        return Json(page).into_response();
+    }
+
+    let mut response = Json(page.items).into_response();
+    response
+        .headers_mut()
+        .insert("Deprecation", HeaderValue::from_static("true"));
+    response
+}
+
+/// Runs a filtered, paginated incident query shared by the incident and
+/// per-contract listing endpoints.
+async fn query_incidents(
+    state: &AppState,
+    claims: Option<&AuthClaims>,
+    query: &ListIncidentsQuery,
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Paginated<Incident>> {
+    if query.include_deleted && !claims.is_some_and(auth::is_admin) {
+        return Err(ApiError::forbidden(
+            "include_deleted=true is restricted to administrators",
+        ));
+    }
+
+    if let (Some(after), Some(before)) = (query.start_after, query.start_before) {
+        if after > before {
+            return Err(ApiError::bad_request(
+                "InvalidDateRange",
+                "start_after must not be later than start_before",
+            ));
+        }
+    }
+
+    let mut count_builder: QueryBuilder<'_, Postgres> =
+        QueryBuilder::new("SELECT COUNT(*) FROM incidents");
+    push_incident_filters(&mut count_builder, query);
+    let total: i64 = count_builder
+        .build_query_scalar()
+        .fetch_one(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("count incidents", err))?;
+
+    let mut builder: QueryBuilder<'_, Postgres> = QueryBuilder::new("SELECT * FROM incidents");
+    push_incident_filters(&mut builder, query);
+    builder
+        .push(" ORDER BY (end_time IS NULL AND severity = 'critical') DESC, start_time DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+
+    let mut incidents: Vec<Incident> = builder
+        .build_query_as()
+        .fetch_all(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("list incidents", err))?;
+    load_affected_contracts(state, &mut incidents).await?;
+
+    Ok(Paginated::new(incidents, total, limit, offset))
+}
+
+/// Appends the WHERE clause for the list filters, binding every user value.
+fn push_incident_filters<'a>(
+    builder: &mut QueryBuilder<'a, Postgres>,
+    query: &'a ListIncidentsQuery,
+) {
+    builder.push(" WHERE 1=1");
+    if !query.include_deleted {
+        builder.push(" AND deleted_at IS NULL");
+    }
+    if let Some(contract_id) = query.contract_id {
+        builder
+            .push(
+                " AND EXISTS (SELECT 1 FROM incident_contracts ic \
+                 WHERE ic.incident_id = incidents.id AND ic.contract_id = ",
+            )
+            .push_bind(contract_id)
+            .push(")");
+    }
+    if let Some(incident_type) = query.incident_type.as_deref() {
+        builder
+            .push(" AND incident_type = ")
+            .push_bind(incident_type);
+    }
+    if let Some(severity) = query.severity {
+        builder.push(" AND severity = ").push_bind(severity);
+    }
+    if let Some(min_severity) = query.min_severity {
+        // The enum is declared most-severe first, so "at least" means "<="
+        builder.push(" AND severity <= ").push_bind(min_severity);
+    }
+    if query.open_only {
+        builder.push(" AND end_time IS NULL");
+    }
+    if let Some(start_after) = query.start_after {
+        builder.push(" AND start_time >= ").push_bind(start_after);
+    }
+    if let Some(start_before) = query.start_before {
+        builder.push(" AND start_time < ").push_bind(start_before);
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/{id}",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    responses(
+        (status = 200, description = "Incident details with the most recent update", body = IncidentDetail),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_incident(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<IncidentDetail>> {
+    let incident_id = parse_incident_id(&id)?;
+    let mut incident = fetch_live_incident(&state, incident_id, &id).await?;
+    load_affected_contracts(&state, std::slice::from_mut(&mut incident)).await?;
+
+    let latest_update: Option<IncidentUpdate> = sqlx::query_as(
+        "SELECT * FROM incident_updates WHERE incident_id = $1
+         ORDER BY created_at DESC LIMIT 1",
+    )
+    .bind(incident_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get latest incident update", err))?;
+
+    let notifications: Vec<NotificationDelivery> = sqlx::query_as(
+        r#"
+        SELECT d.channel_id, c.name AS channel_name, c.kind, d.status, d.attempts,
+               d.last_error, d.delivered_at
+        FROM incident_notification_deliveries d
+        JOIN notification_channels c ON c.id = d.channel_id
+        WHERE d.incident_id = $1
+        ORDER BY c.name
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("get incident notification deliveries", err))?;
+
+    let postmortem = load_postmortem(&state, incident_id).await?;
+    let recovery_validations = load_recovery_validations(&state, incident_id).await?;
+    incident.objectives = objective_outcome(&state, &incident).await?;
+
+    Ok(Json(IncidentDetail {
+        incident,
+        latest_update,
+        notifications,
+        postmortem: (!postmortem.is_empty()).then_some(postmortem),
+        recovery_validations,
+    }))
+}
+
+/// Checks that a resolution is chronologically sound and that recovery
+/// metrics are only recorded once the incident has an end time.
+fn validate_resolution(
+    start_time: DateTime<Utc>,
+    end_time: Option<DateTime<Utc>>,
+    sets_recovery_metrics: bool,
+) -> ApiResult<()> {
+    match end_time {
+        Some(end) if end < start_time => Err(ApiError::unprocessable(
+            "InvalidEndTime",
+            "end_time must not be earlier than start_time",
+        )
+        .with_details(serde_json::json!({
+            "start_time": start_time,
+            "end_time": end,
+        }))),
+        None if sets_recovery_metrics => Err(ApiError::unprocessable(
+            "IncidentUnresolved",
+            "rto_achieved and rpo_achieved can only be set once end_time is recorded",
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Loads a non-deleted incident or returns 404 with the caller-supplied ID.
+async fn fetch_live_incident(
+    state: &AppState,
+    incident_id: Uuid,
+    raw_id: &str,
+) -> ApiResult<Incident> {
+    sqlx::query_as("SELECT * FROM incidents WHERE id = $1 AND deleted_at IS NULL")
+        .bind(incident_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get incident by id", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "IncidentNotFound",
+                format!("No incident found with ID: {}", raw_id),
+            )
+        })
+}
+
+/// The stored objective outcome, or for incidents resolved before targets
+/// were recorded (and open incidents) the comparison with current targets.
+async fn objective_outcome(state: &AppState, incident: &Incident) -> ApiResult<ObjectiveOutcome> {
+    if incident.objectives.rto_target_seconds.is_some() {
+        return Ok(incident.objectives);
+    }
+
+    let targets = resolve_targets(&state.db, Some(incident.id))
+        .await
+        .map_err(|err| db_internal_error("resolve recovery objectives", err))?;
+    Ok(ObjectiveOutcome::evaluate(
+        incident.rto_achieved.map(|d| d.seconds),
+        incident.rpo_achieved.map(|d| d.seconds),
+        targets.rto_target_seconds,
+        targets.rpo_target_seconds,
+    ))
+}
+
+/// Compares the achieved RTO/RPO with the recovery targets and stores the
+/// outcome. Targets are fixed when the incident is resolved, so later edits
+/// to the achieved values are judged against the same targets.
+async fn record_objective_outcome(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    incident: &mut Incident,
+    resolving: bool,
+) -> ApiResult<()> {
+    let stored = incident
+        .objectives
+        .rto_target_seconds
+        .zip(incident.objectives.rpo_target_seconds);
+    let (rto_target, rpo_target, configured) = match stored {
+        Some((rto, rpo)) if !resolving => (rto, rpo, false),
+        _ => {
+            let targets = resolve_targets(&mut **tx, Some(incident.id))
+                .await
+                .map_err(|err| db_internal_error("resolve recovery objectives", err))?;
+            (
+                targets.rto_target_seconds,
+                targets.rpo_target_seconds,
+                targets.is_configured(),
+            )
+        }
+    };
+
+    let outcome = ObjectiveOutcome::evaluate(
+        incident.rto_achieved.map(|d| d.seconds),
+        incident.rpo_achieved.map(|d| d.seconds),
+        rto_target,
+        rpo_target,
+    );
+
+    sqlx::query(
+        r#"
+        UPDATE incidents
+        SET rto_target_seconds = $1, rto_met = $2, rto_delta_seconds = $3,
+            rpo_target_seconds = $4, rpo_met = $5, rpo_delta_seconds = $6
+        WHERE id = $7
+        "#,
+    )
+    .bind(outcome.rto_target_seconds)
+    .bind(outcome.rto_met)
+    .bind(outcome.rto_delta_seconds)
+    .bind(outcome.rpo_target_seconds)
+    .bind(outcome.rpo_met)
+    .bind(outcome.rpo_delta_seconds)
+    .bind(incident.id)
+    .execute(&mut **tx)
+    .await
+    .map_err(|err| db_internal_error("record recovery objective outcome", err))?;
+
+    if resolving && configured {
+        incident.warnings = outcome.missing_value_warnings();
+    }
+    incident.objectives = outcome;
+    Ok(())
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/incidents/{id}",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    request_body = UpdateIncidentRequest,
+    responses(
+        (status = 200, description = "Incident updated; `warnings` lists recovery objectives left unrecorded on resolution and backup/data-loss incidents resolved without a passing recovery validation", body = Incident),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Incident not found"),
+        (status = 422, description = "Invalid RTO/RPO, end_time before start_time, recovery metrics on an unresolved incident, or unknown contract IDs")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn update_incident(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<UpdateIncidentRequest>,
+) -> ApiResult<Json<Incident>> {
+    if !auth::is_admin(&claims) {
+        return Err(ApiError::forbidden(
+            "Administrative privileges are required to update incidents",
+        ));
+    }
+    let incident_id = parse_incident_id(&id)?;
+    let rto_seconds = parse_recovery_duration("rto_achieved", req.rto_achieved.as_ref())?;
+    let rpo_seconds = parse_recovery_duration("rpo_achieved", req.rpo_achieved.as_ref())?;
+
+    let affected_ids =
+        (req.affected_contract_ids.is_some() || req.contract_id.is_some()).then(|| {
+            merge_affected_contract_ids(
+                req.contract_id,
+                req.affected_contract_ids.as_deref().unwrap_or_default(),
+            )
+        });
+    if let Some(ids) = &affected_ids {
+        ensure_contracts_exist(&state, ids).await?;
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin update incident", err))?;
+
+    let (start_time, current_end_time, previous_severity): (
+        DateTime<Utc>,
+        Option<DateTime<Utc>>,
+        IncidentSeverity,
+    ) = sqlx::query_as(
+        "SELECT start_time, end_time, severity FROM incidents
+         WHERE id = $1 AND deleted_at IS NULL
+         FOR UPDATE",
+    )
+    .bind(incident_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("lock incident for update", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "IncidentNotFound",
+            format!("No incident found with ID: {}", id),
+        )
+    })?;
+
+    validate_resolution(
+        start_time,
+        req.end_time.or(current_end_time),
+        rto_seconds.is_some() || rpo_seconds.is_some(),
+    )?;
+
+    let mut incident: Incident = sqlx::query_as(
+        r#"
+        UPDATE incidents
+        SET severity = COALESCE($1, severity),
+            end_time = COALESCE($2, end_time),
+            rto_achieved_seconds = COALESCE($3, rto_achieved_seconds),
+            rpo_achieved_seconds = COALESCE($4, rpo_achieved_seconds),
+            lessons_learned = COALESCE($5, lessons_learned),
+            updated_at = NOW()
+        WHERE id = $6 AND deleted_at IS NULL
+        RETURNING *
+        "#,
+    )
+    .bind(req.severity)
+    .bind(req.end_time)
+    .bind(rto_seconds)
+    .bind(rpo_seconds)
+    .bind(&req.lessons_learned)
+    .bind(incident_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("update incident", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "IncidentNotFound",
+            format!("No incident found with ID: {}", id),
+        )
+    })?;
+
+    if let Some(ids) = &affected_ids {
+        replace_affected_contracts(&mut tx, &mut incident, ids).await?;
+    }
+
+    let resolving = current_end_time.is_none() && incident.end_time.is_some();
+    if resolving
+        || (incident.end_time.is_some() && (rto_seconds.is_some() || rpo_seconds.is_some()))
+    {
+        record_objective_outcome(&mut tx, &mut incident, resolving).await?;
+    }
+
+    if resolving && requires_recovery_validation(&incident.incident_type) {
+        let validated: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                SELECT 1 FROM incident_recovery_validations WHERE incident_id = $1 AND passed
+             )",
+        )
+        .bind(incident.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("check recovery validations", err))?;
+        if !validated {
+            incident.warnings.push(format!(
+                "Incident resolved without a passing recovery validation; record one with POST /api/incidents/{}/validations",
+                incident.id
+            ));
+        }
+    }
+
+    if previous_severity != incident.severity {
+        sqlx::query(
+            "INSERT INTO incident_audit_log (incident_id, action, changed_by, details)
+             VALUES ($1, 'severity_changed', $2, $3)",
+        )
+        .bind(incident.id)
+        .bind(&claims.sub)
+        .bind(serde_json::json!({
+            "from": previous_severity.as_str(),
+            "to": incident.severity.as_str(),
+        }))
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("write incident audit log", err))?;
+
+        sqlx::query(
+            "INSERT INTO incident_updates (incident_id, author, message) VALUES ($1, $2, $3)",
+        )
+        .bind(incident.id)
+        .bind(&claims.sub)
+        .bind(format!(
+            "Severity changed from {} to {}.",
+            previous_severity.as_str(),
+            incident.severity.as_str()
+        ))
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("record severity change", err))?;
+
+        tracing::info!(
+            incident_id = %incident.id,
+            from = previous_severity.as_str(),
+            to = incident.severity.as_str(),
+            "incident severity changed"
+        );
+    }
+
+    if req.notify_users == Some(true) {
+        let queued = incident_notifications::enqueue(&mut tx, incident.id)
+            .await
+            .map_err(|err| db_internal_error("queue incident notifications", err))?;
+        tracing::info!(incident_id = %incident.id, queued, "incident notifications queued");
+    }
+
+    // Announces a resolution, and the incident to contracts newly named in it
+    let named: Vec<Uuid> =
+        sqlx::query_scalar("SELECT contract_id FROM incident_contracts WHERE incident_id = $1")
+            .bind(incident.id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("fetch affected contracts", err))?;
+    subscription_notifications::enqueue_incident(&mut tx, &incident, &named)
+        .await
+        .map_err(|err| db_internal_error("queue incident subscription notifications", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit update incident", err))?;
+
+    load_affected_contracts(&state, std::slice::from_mut(&mut incident)).await?;
+
+    Ok(Json(incident))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/incidents/{id}",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    responses(
+        (status = 204, description = "Incident soft-deleted"),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Incident not found or already deleted"),
+        (status = 423, description = "The incident is under a legal hold")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn delete_incident(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    if !auth::is_admin(&claims) {
+        return Err(ApiError::forbidden(
+            "Administrative privileges are required to delete incidents",
+        ));
+    }
+
+    let incident_id = parse_incident_id(&id)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin delete incident", err))?;
+
+    let deleted: Option<Incident> = sqlx::query_as(
+        "UPDATE incidents SET deleted_at = NOW(), updated_at = NOW()
+         WHERE id = $1 AND deleted_at IS NULL
+         RETURNING *",
+    )
+    .bind(incident_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("soft delete incident", err))?;
+
+    let Some(deleted) = deleted else {
+        return Err(ApiError::not_found(
+            "IncidentNotFound",
+            format!("No incident found with ID: {}", id),
+        ));
+    };
+    // Checked once the row is locked; refusing rolls the delete back
+    legal_holds::ensure_not_held(&mut *tx, HoldTarget::Incident, deleted.id).await?;
+
+    sqlx::query(
+        "INSERT INTO incident_audit_log (incident_id, action, changed_by, details)
+         VALUES ($1, 'deleted', $2, $3)",
+    )
+    .bind(deleted.id)
+    .bind(&claims.sub)
+    .bind(serde_json::json!({
+        "incident_type": deleted.incident_type,
+        "deleted_at": deleted.deleted_at,
+    }))
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("write incident audit log", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit delete incident", err))?;
+
+    tracing::info!(incident_id = %deleted.id, actor = %claims.sub, "incident soft-deleted");
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/incidents/{id}/updates",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    request_body = CreateIncidentUpdateRequest,
+    responses(
+        (status = 201, description = "Update appended to the incident timeline", body = IncidentUpdate),
+        (status = 400, description = "Malformed incident ID or empty message"),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "Incident not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn create_incident_update(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<CreateIncidentUpdateRequest>,
+) -> ApiResult<(StatusCode, Json<IncidentUpdate>)> {
+    let incident_id = parse_incident_id(&id)?;
+
+    let message = req.message.trim();
+    if message.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidIncidentUpdate",
+            "message must not be empty",
+        ));
+    }
+
+    let incident = fetch_live_incident(&state, incident_id, &id).await?;
+    let post_resolution = incident.end_time.is_some();
+
+    let update: IncidentUpdate = sqlx::query_as(
+        r#"
+        INSERT INTO incident_updates (incident_id, author, message, status, post_resolution)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(&claims.sub)
+    .bind(message)
+    .bind(req.status)
+    .bind(post_resolution)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("create incident update", err))?;
+
+    if post_resolution {
+        tracing::info!(incident_id = %incident_id, update_id = %update.id, "post-resolution incident update");
+    }
+
+    Ok((StatusCode::CREATED, Json(update)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/{id}/updates",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    responses(
+        (status = 200, description = "Incident timeline, oldest first", body = [IncidentUpdate]),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Incidents"
+)]
+pub async fn list_incident_updates(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<IncidentUpdate>>> {
+    let incident_id = parse_incident_id(&id)?;
+    fetch_live_incident(&state, incident_id, &id).await?;
+
+    let updates: Vec<IncidentUpdate> = sqlx::query_as(
+        "SELECT * FROM incident_updates WHERE incident_id = $1 ORDER BY created_at ASC",
+    )
+    .bind(incident_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list incident updates", err))?;
+
+    Ok(Json(updates))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/incidents/{id}/ack",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    responses(
+        (status = 200, description = "Incident acknowledged; acknowledging again changes nothing", body = Incident),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "Incident not found"),
+        (status = 409, description = "Incident is already resolved")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn acknowledge_incident(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Incident>> {
+    let incident_id = parse_incident_id(&id)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin acknowledge incident", err))?;
+
+    let acknowledged: Option<Incident> = sqlx::query_as(
+        "UPDATE incidents SET acknowledged_at = NOW(), acknowledged_by = $2
+         WHERE id = $1 AND deleted_at IS NULL AND end_time IS NULL AND acknowledged_at IS NULL
+         RETURNING *",
+    )
+    .bind(incident_id)
+    .bind(&claims.sub)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("acknowledge incident", err))?;
+
+    let mut incident = match acknowledged {
+        Some(incident) => {
+            let message = match incident.escalation.escalation_step {
+                0 => format!("Acknowledged by {}.", claims.sub),
+                step => format!(
+                    "Acknowledged by {} after {} escalation step{}; escalation stopped.",
+                    claims.sub,
+                    step,
+                    if step == 1 { "" } else { "s" }
+                ),
+            };
+            sqlx::query(
+                "INSERT INTO incident_updates (incident_id, author, message) VALUES ($1, $2, $3)",
+            )
+            .bind(incident.id)
+            .bind(&claims.sub)
+            .bind(&message)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("record incident acknowledgement", err))?;
+
+            tracing::info!(
+                incident_id = %incident.id,
+                actor = %claims.sub,
+                escalation_step = incident.escalation.escalation_step,
+                "incident acknowledged"
+            );
+            incident
+        }
+        None => {
+            let incident: Incident =
+                sqlx::query_as("SELECT * FROM incidents WHERE id = $1 AND deleted_at IS NULL")
+                    .bind(incident_id)
+                    .fetch_optional(&mut *tx)
+                    .await
+                    .map_err(|err| db_internal_error("get incident by id", err))?
+                    .ok_or_else(|| {
+                        ApiError::not_found(
+                            "IncidentNotFound",
+                            format!("No incident found with ID: {}", id),
+                        )
+                    })?;
+            if incident.escalation.acknowledged_at.is_none() {
+                return Err(ApiError::conflict(
+                    "IncidentResolved",
+                    "The incident is already resolved; there is nothing to acknowledge",
+                ));
+            }
+            incident
+        }
+    };
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit acknowledge incident", err))?;
+
+    load_affected_contracts(&state, std::slice::from_mut(&mut incident)).await?;
+
+    Ok(Json(incident))
+}
+
+async fn load_recovery_validations(
+    state: &AppState,
+    incident_id: Uuid,
+) -> ApiResult<Vec<RecoveryValidation>> {
+    sqlx::query_as(
+        "SELECT * FROM incident_recovery_validations WHERE incident_id = $1
+         ORDER BY created_at ASC, id",
+    )
+    .bind(incident_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list recovery validations", err))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/incidents/{id}/validations",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    request_body = CreateRecoveryValidationRequest,
+    responses(
+        (status = 201, description = "Recovery validation recorded", body = RecoveryValidation),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "Incident not found"),
+        (status = 422, description = "Empty `restored`, a blank check subject, `passed` contradicting the checks, or an unknown snapshot")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn create_recovery_validation(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<CreateRecoveryValidationRequest>,
+) -> ApiResult<(StatusCode, Json<RecoveryValidation>)> {
+    let incident_id = parse_incident_id(&id)?;
+
+    let restored = req.restored.trim();
+    if restored.is_empty() {
+        return Err(ApiError::unprocessable(
+            "InvalidRecoveryValidation",
+            "restored must describe what was restored",
+        ));
+    }
+    let passed = req
+        .outcome()
+        .map_err(|msg| ApiError::unprocessable("InvalidRecoveryValidation", msg))?;
+
+    fetch_live_incident(&state, incident_id, &id).await?;
+
+    if let Some(snapshot_id) = req.snapshot_id {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM registry_snapshots WHERE id = $1)")
+                .bind(snapshot_id)
+                .fetch_one(&state.db)
+                .await
+                .map_err(|err| db_internal_error("look up snapshot", err))?;
+        if !exists {
+            return Err(ApiError::unprocessable(
+                "UnknownSnapshot",
+                format!("No snapshot found with ID: {}", snapshot_id),
+            ));
+        }
+    }
+
+    let validation: RecoveryValidation = sqlx::query_as(
+        r#"
+        INSERT INTO incident_recovery_validations
+            (incident_id, restored, snapshot_id, checks, passed, notes, performed_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING *
+        "#,
+    )
+    .bind(incident_id)
+    .bind(restored)
+    .bind(req.snapshot_id)
+    .bind(sqlx::types::Json(&req.checks))
+    .bind(passed)
+    .bind(non_blank(req.notes.as_deref()))
+    .bind(&claims.sub)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("create recovery validation", err))?;
+
+    tracing::info!(
+        incident_id = %incident_id,
+        validation_id = %validation.id,
+        passed,
+        performed_by = %claims.sub,
+        "recovery validation recorded"
+    );
+
+    Ok((StatusCode::CREATED, Json(validation)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/{id}/validations",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    responses(
+        (status = 200, description = "Recovery validations, oldest first", body = [RecoveryValidation]),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Incidents"
+)]
+pub async fn list_recovery_validations(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<Vec<RecoveryValidation>>> {
+    let incident_id = parse_incident_id(&id)?;
+    fetch_live_incident(&state, incident_id, &id).await?;
+    Ok(Json(load_recovery_validations(&state, incident_id).await?))
+}
+
+#[derive(FromRow)]
+struct PostmortemRow {
+    root_cause: Option<String>,
+    detection_method: Option<String>,
+    contributing_factors: Vec<String>,
+}
+
+async fn load_postmortem(state: &AppState, incident_id: Uuid) -> ApiResult<Postmortem> {
+    let row: PostmortemRow = sqlx::query_as(
+        "SELECT root_cause, detection_method, contributing_factors FROM incidents WHERE id = $1",
+    )
+    .bind(incident_id)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("load incident postmortem", err))?;
+
+    let action_items: Vec<IncidentActionItem> = sqlx::query_as(
+        "SELECT * FROM incident_action_items WHERE incident_id = $1 ORDER BY created_at, id",
+    )
+    .bind(incident_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("load incident action items", err))?;
+
+    Ok(Postmortem {
+        root_cause: row.root_cause,
+        detection_method: row.detection_method,
+        contributing_factors: row.contributing_factors,
+        action_items,
+    })
+}
+
+/// Trims optional free text, treating blank strings as absent.
+fn non_blank(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/incidents/{id}/postmortem",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    request_body = UpdatePostmortemRequest,
+    responses(
+        (status = 200, description = "Postmortem saved", body = Postmortem),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "Incident not found"),
+        (status = 422, description = "Empty action item description or action item from another incident")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn put_incident_postmortem(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<UpdatePostmortemRequest>,
+) -> ApiResult<Json<Postmortem>> {
+    let incident_id = parse_incident_id(&id)?;
+
+    if let Some(index) = req
+        .action_items
+        .iter()
+        .position(|item| item.description.trim().is_empty())
+    {
+        return Err(ApiError::unprocessable(
+            "InvalidActionItem",
+            format!("action_items[{}].description must not be empty", index),
+        ));
+    }
+
+    let contributing_factors: Vec<String> = req
+        .contributing_factors
+        .iter()
+        .filter_map(|factor| non_blank(Some(factor)))
+        .collect();
+    let kept_ids: Vec<Uuid> = req.action_items.iter().filter_map(|item| item.id).collect();
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin update postmortem", err))?;
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE incidents
+        SET root_cause = $1,
+            detection_method = $2,
+            contributing_factors = $3,
+            updated_at = NOW()
+        WHERE id = $4 AND deleted_at IS NULL
+        "#,
+    )
+    .bind(non_blank(req.root_cause.as_deref()))
+    .bind(non_blank(req.detection_method.as_deref()))
+    .bind(&contributing_factors)
+    .bind(incident_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("update incident postmortem", err))?;
+
+    if updated.rows_affected() == 0 {
+        return Err(ApiError::not_found(
+            "IncidentNotFound",
+            format!("No incident found with ID: {}", id),
+        ));
+    }
+
+    sqlx::query("DELETE FROM incident_action_items WHERE incident_id = $1 AND id <> ALL($2)")
+        .bind(incident_id)
+        .bind(&kept_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("remove dropped action items", err))?;
+
+    for item in &req.action_items {
+        let query = match item.id {
+            Some(item_id) => sqlx::query(
+                r#"
+                UPDATE incident_action_items
+                SET description = $3, owner = $4, due_date = $5, done = $6, updated_at = NOW()
+                WHERE id = $1 AND incident_id = $2
+                "#,
+            )
+            .bind(item_id),
+            None => sqlx::query(
+                r#"
+                INSERT INTO incident_action_items (incident_id, description, owner, due_date, done)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            ),
+        };
+
+        let result = query
+            .bind(incident_id)
+            .bind(item.description.trim())
+            .bind(non_blank(item.owner.as_deref()))
+            .bind(item.due_date)
+            .bind(item.done)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("save action item", err))?;
+
+        if let (Some(item_id), 0) = (item.id, result.rows_affected()) {
+            return Err(ApiError::unprocessable(
+                "UnknownActionItem",
+                format!("Action item {} does not belong to incident {}", item_id, id),
+            ));
+        }
+    }
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit update postmortem", err))?;
+
+    tracing::info!(
+        incident_id = %incident_id,
+        author = %claims.sub,
+        action_items = req.action_items.len(),
+        "incident postmortem updated"
+    );
+
+    Ok(Json(load_postmortem(&state, incident_id).await?))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/incidents/{id}/action-items/{item_id}",
+    params(
+        ("id" = String, Path, description = "Incident UUID"),
+        ("item_id" = String, Path, description = "Action item UUID")
+    ),
+    request_body = UpdateActionItemRequest,
+    responses(
+        (status = 200, description = "Action item updated", body = IncidentActionItem),
+        (status = 400, description = "Malformed ID"),
+        (status = 401, description = "Authentication required"),
+        (status = 404, description = "Action item not found on this incident"),
+        (status = 422, description = "Empty description")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn update_action_item(
+    State(state): State<AppState>,
+    Path((id, item_id)): Path<(String, String)>,
+    _claims: AuthClaims,
+    Json(req): Json<UpdateActionItemRequest>,
+) -> ApiResult<Json<IncidentActionItem>> {
+    let incident_id = parse_incident_id(&id)?;
+    let action_item_id = Uuid::parse_str(&item_id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidActionItemId",
+            format!("Invalid action item ID format: {}", item_id),
+        )
+    })?;
+
+    if matches!(req.description.as_deref(), Some(d) if d.trim().is_empty()) {
+        return Err(ApiError::unprocessable(
+            "InvalidActionItem",
+            "description must not be empty",
+        ));
+    }
+
+    let item: IncidentActionItem = sqlx::query_as(
+        r#"
+        UPDATE incident_action_items a
+        SET description = COALESCE($3, a.description),
+            owner = COALESCE($4, a.owner),
+            due_date = COALESCE($5, a.due_date),
+            done = COALESCE($6, a.done),
+            updated_at = NOW()
+        FROM incidents i
+        WHERE a.id = $1 AND a.incident_id = $2
+          AND i.id = a.incident_id AND i.deleted_at IS NULL
+        RETURNING a.*
+        "#,
+    )
+    .bind(action_item_id)
+    .bind(incident_id)
+    .bind(req.description.as_deref().map(str::trim))
+    .bind(non_blank(req.owner.as_deref()))
+    .bind(req.due_date)
+    .bind(req.done)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update action item", err))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "ActionItemNotFound",
+            format!("No action item {} on incident {}", item_id, id),
+        )
+    })?;
+
+    Ok(Json(item))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/action-items",
+    params(ListActionItemsQuery),
+    responses(
+        (status = 200, description = "Action items across all incidents, soonest due first", body = [IncidentActionItem])
+    ),
+    tag = "Incidents"
+)]
+pub async fn list_action_items(
+    State(state): State<AppState>,
+    params: Result<Query<ListActionItemsQuery>, QueryRejection>,
+) -> ApiResult<Json<Vec<IncidentActionItem>>> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+
+    let items: Vec<IncidentActionItem> = sqlx::query_as(
+        r#"
+        SELECT a.*
+        FROM incident_action_items a
+        JOIN incidents i ON i.id = a.incident_id
+        WHERE i.deleted_at IS NULL
+          AND ($1::BOOLEAN IS NULL OR a.done <> $1)
+        ORDER BY a.done, a.due_date NULLS LAST, a.created_at
+        "#,
+    )
+    .bind(query.open)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list action items", err))?;
+
+    Ok(Json(items))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/{id}/notifications/preview",
+    params(
+        ("id" = String, Path, description = "Incident UUID")
+    ),
+    responses(
+        (status = 200, description = "Rendered notification and target channels; nothing is sent", body = NotificationPreview),
+        (status = 400, description = "Malformed incident ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Incident not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn preview_incident_notification(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<Json<NotificationPreview>> {
+    if !auth::is_admin(&claims) {
+        return Err(ApiError::forbidden(
+            "Administrative privileges are required to preview notifications",
+        ));
+    }
+
+    let incident_id = parse_incident_id(&id)?;
+    let incident = fetch_live_incident(&state, incident_id, &id).await?;
+
+    let channels: Vec<NotificationChannel> =
+        sqlx::query_as("SELECT * FROM notification_channels WHERE enabled ORDER BY name")
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("list notification channels", err))?;
+
+    Ok(Json(NotificationPreview {
+        auto_notify: incident_notifications::should_auto_notify(&incident),
+        channels,
+        notification: incident_notifications::render(&incident),
+    }))
+}
+
+#[derive(FromRow)]
+struct IncidentMetricsSummary {
+    total: i64,
+    open: i64,
+    mttr_mean: Option<f64>,
+    mttr_median: Option<f64>,
+    rto_measured: i64,
+    rto_met: i64,
+    rto_mean_delta: Option<f64>,
+    rpo_measured: i64,
+    rpo_met: i64,
+    rpo_mean_delta: Option<f64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/metrics",
+    params(IncidentMetricsQuery),
+    responses(
+        (status = 200, description = "Aggregate incident metrics for the window", body = IncidentMetrics),
+        (status = 400, description = "Invalid window")
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_incident_metrics(
+    State(state): State<AppState>,
+    params: Result<Query<IncidentMetricsQuery>, QueryRejection>,
+) -> ApiResult<Json<IncidentMetrics>> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+    let window_days = query
+        .window_days()
+        .map_err(|msg| ApiError::bad_request("InvalidWindow", msg))?;
+    let window_start = Utc::now() - Duration::days(window_days);
+
+    let targets = resolve_targets(state.read_pool(), None)
+        .await
+        .map_err(|err| db_internal_error("resolve recovery objectives", err))?;
+
+    let summary: IncidentMetricsSummary = sqlx::query_as(
+        r#"
+        SELECT
+            COUNT(*) AS total,
+            COUNT(*) FILTER (WHERE end_time IS NULL) AS open,
+            AVG(EXTRACT(EPOCH FROM end_time - start_time)::FLOAT8) AS mttr_mean,
+            PERCENTILE_CONT(0.5) WITHIN GROUP (
+                ORDER BY EXTRACT(EPOCH FROM end_time - start_time)::FLOAT8
+            ) FILTER (WHERE end_time IS NOT NULL) AS mttr_median,
+            COUNT(rto_achieved_seconds) AS rto_measured,
+            COUNT(*) FILTER (
+                WHERE COALESCE(rto_met, rto_achieved_seconds <= $2)
+            ) AS rto_met,
+            AVG(COALESCE(rto_delta_seconds, rto_achieved_seconds - $2)::FLOAT8) AS rto_mean_delta,
+            COUNT(rpo_achieved_seconds) AS rpo_measured,
+            COUNT(*) FILTER (
+                WHERE COALESCE(rpo_met, rpo_achieved_seconds <= $3)
+            ) AS rpo_met,
+            AVG(COALESCE(rpo_delta_seconds, rpo_achieved_seconds - $3)::FLOAT8) AS rpo_mean_delta
+        FROM incidents
+        WHERE deleted_at IS NULL AND start_time >= $1
+          AND ($4 OR maintenance_window_id IS NULL)
+          AND NOT ($5 AND source = 'import')
+        "#,
+    )
+    .bind(window_start)
+    .bind(targets.rto_target_seconds)
+    .bind(targets.rpo_target_seconds)
+    .bind(query.include_maintenance)
+    .bind(query.exclude_imported)
+    .fetch_one(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("compute incident metrics", err))?;
+
+    let by_type: Vec<IncidentCount> = sqlx::query_as(
+        "SELECT incident_type AS key, COUNT(*) AS count
+         FROM incidents
+         WHERE deleted_at IS NULL AND start_time >= $1
+           AND ($2 OR maintenance_window_id IS NULL)
+           AND NOT ($3 AND source = 'import')
+         GROUP BY incident_type
+         ORDER BY count DESC, key",
+    )
+    .bind(window_start)
+    .bind(query.include_maintenance)
+    .bind(query.exclude_imported)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("count incidents by type", err))?;
+
+    let by_severity: Vec<IncidentCount> = sqlx::query_as(
+        "SELECT severity::TEXT AS key, COUNT(*) AS count
+         FROM incidents
+         WHERE deleted_at IS NULL AND start_time >= $1
+           AND ($2 OR maintenance_window_id IS NULL)
+           AND NOT ($3 AND source = 'import')
+         GROUP BY severity
+         ORDER BY severity",
+    )
+    .bind(window_start)
+    .bind(query.include_maintenance)
+    .bind(query.exclude_imported)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("count incidents by severity", err))?;
+
+    let (validation_resolved, validation_passed): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT
+            COUNT(*),
+            COUNT(*) FILTER (WHERE EXISTS (
+                SELECT 1 FROM incident_recovery_validations v
+                WHERE v.incident_id = i.id AND v.passed
+            ))
+        FROM incidents i
+        WHERE deleted_at IS NULL AND start_time >= $1 AND end_time IS NOT NULL
+          AND ($2 OR maintenance_window_id IS NULL)
+          AND NOT ($4 AND source = 'import')
+          AND REPLACE(LOWER(TRIM(incident_type)), '-', '_') = ANY($3)
+        "#,
+    )
+    .bind(window_start)
+    .bind(query.include_maintenance)
+    .bind(RECOVERY_VALIDATED_INCIDENT_TYPES)
+    .bind(query.exclude_imported)
+    .fetch_one(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("compute recovery validation coverage", err))?;
+
+    let longest_open: Option<LongestOpenIncident> = sqlx::query_as(
+        "SELECT id, incident_type, severity, start_time,
+                EXTRACT(EPOCH FROM NOW() - start_time)::BIGINT AS open_seconds
+         FROM incidents
+         WHERE deleted_at IS NULL AND end_time IS NULL
+           AND ($1 OR maintenance_window_id IS NULL)
+           AND NOT ($2 AND source = 'import')
+         ORDER BY start_time ASC
+         LIMIT 1",
+    )
+    .bind(query.include_maintenance)
+    .bind(query.exclude_imported)
+    .fetch_optional(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("find longest open incident", err))?;
+
+    let monthly: Vec<MonthlyIncidentMetrics> = sqlx::query_as(
+        r#"
+        SELECT
+            TO_CHAR(DATE_TRUNC('month', start_time), 'YYYY-MM') AS month,
+            COUNT(*) AS total,
+            COUNT(*) FILTER (WHERE end_time IS NOT NULL) AS resolved,
+            COUNT(*) FILTER (WHERE end_time IS NULL) AS open,
+            AVG(EXTRACT(EPOCH FROM end_time - start_time)::FLOAT8) AS mttr_mean_seconds,
+            PERCENTILE_CONT(0.5) WITHIN GROUP (
+                ORDER BY EXTRACT(EPOCH FROM end_time - start_time)::FLOAT8
+            ) FILTER (WHERE end_time IS NOT NULL) AS mttr_median_seconds
+        FROM incidents
+        WHERE deleted_at IS NULL AND start_time >= $1
+          AND ($2 OR maintenance_window_id IS NULL)
+          AND NOT ($3 AND source = 'import')
+        GROUP BY DATE_TRUNC('month', start_time)
+        ORDER BY DATE_TRUNC('month', start_time)
+        "#,
+    )
+    .bind(window_start)
+    .bind(query.include_maintenance)
+    .bind(query.exclude_imported)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("compute monthly incident metrics", err))?;
+
+    Ok(Json(IncidentMetrics {
+        window_days,
+        window_start,
+        total_incidents: summary.total,
+        resolved_incidents: summary.total - summary.open,
+        open_incidents: summary.open,
+        mttr_mean_seconds: summary.mttr_mean,
+        mttr_median_seconds: summary.mttr_median,
+        by_type,
+        by_severity,
+        rto_compliance: ObjectiveCompliance::new(
+            targets.rto_target_seconds,
+            targets.source,
+            summary.rto_measured,
+            summary.rto_met,
+            summary.rto_mean_delta,
+        ),
+        rpo_compliance: ObjectiveCompliance::new(
+            targets.rpo_target_seconds,
+            targets.source,
+            summary.rpo_measured,
+            summary.rpo_met,
+            summary.rpo_mean_delta,
+        ),
+        recovery_validation: RecoveryValidationCoverage::new(
+            validation_resolved,
+            validation_passed,
+        ),
+        longest_open,
+        monthly,
+    }))
+}
+
+/// Incidents at or above this severity count as downtime for uptime figures
+const DOWNTIME_SEVERITY: IncidentSeverity = IncidentSeverity::Major;
+/// Cache policy for the unauthenticated status and feed endpoints
+const PUBLIC_CACHE_CONTROL: &str = "public, max-age=30";
+
+#[utoipa::path(
+    get,
+    path = "/api/status",
+    responses(
+        (status = 200, description = "Public registry status", body = RegistryStatus)
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_status(State(state): State<AppState>) -> ApiResult<Response> {
+    let now = Utc::now();
+
+    let mut open_incidents: Vec<PublicIncident> = sqlx::query_as(
+        r#"
+        SELECT i.id, i.incident_type, i.severity, i.description, i.start_time,
+               latest.status, i.updated_at
+        FROM incidents i
+        LEFT JOIN LATERAL (
+            SELECT status FROM incident_updates u
+            WHERE u.incident_id = i.id
+            ORDER BY u.created_at DESC
+            LIMIT 1
+        ) latest ON TRUE
+        WHERE i.deleted_at IS NULL AND i.end_time IS NULL
+        ORDER BY i.severity, i.start_time
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list open incidents for status", err))?;
+
+    let ids: Vec<Uuid> = open_incidents.iter().map(|i| i.id).collect();
+    let rows = fetch_affected_contracts(&state, &ids).await?;
+    for incident in open_incidents.iter_mut() {
+        incident.affected_contracts = affected_contracts_for(&rows, incident.id);
+    }
+
+    let maintenance = maintenance_handlers::upcoming_windows(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list maintenance windows for status", err))?;
+
+    let window_90 = now - Duration::days(90);
+    let outages: Vec<(DateTime<Utc>, Option<DateTime<Utc>>)> = sqlx::query_as(
+        "SELECT start_time, end_time
+         FROM incidents
+         WHERE deleted_at IS NULL
+           AND severity <= $1
+           AND (end_time IS NULL OR end_time > $2)",
+    )
+    .bind(DOWNTIME_SEVERITY)
+    .bind(window_90)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("load outages for uptime", err))?;
+
+    let status = RegistryStatus {
+        status: OverallStatus::from_open_severities(open_incidents.iter().map(|i| i.severity)),
+        open_incidents,
+        maintenance,
+        uptime: UptimeSummary {
+            last_30_days: uptime_percentage(&outages, now - Duration::days(30), now),
+            last_90_days: uptime_percentage(&outages, window_90, now),
+        },
+        generated_at: now,
+    };
+
+    Ok((
+        [(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static(PUBLIC_CACHE_CONTROL),
+        )],
+        Json(status),
+    )
+        .into_response())
+}
+
+/// Base URL used for absolute links in the Atom feed
+pub(crate) fn public_base_url() -> String {
+    std::env::var("PUBLIC_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+#[derive(FromRow)]
+struct FeedUpdateRow {
+    #[sqlx(flatten)]
+    update: IncidentUpdate,
+    incident_type: String,
+}
+
+#[derive(FromRow)]
+struct FeedSeverityChangeRow {
+    id: Uuid,
+    incident_id: Uuid,
+    incident_type: String,
+    details: Option<serde_json::Value>,
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(FromRow)]
+struct FeedPublicationRow {
+    id: Uuid,
+    contract_id: Uuid,
+    name: String,
+    verified_at: DateTime<Utc>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/feed.atom",
+    params(IncidentFeedQuery),
+    responses(
+        (status = 200, description = "Atom feed of recent incident activity", body = String, content_type = "application/atom+xml"),
+        (status = 400, description = "Unknown include value")
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_incident_feed(
+    State(state): State<AppState>,
+    params: Result<Query<IncidentFeedQuery>, QueryRejection>,
+) -> ApiResult<Response> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+    let include_publications = query
+        .includes_publications()
+        .map_err(|msg| ApiError::bad_request("InvalidFeedInclude", msg))?;
+
+    let base_url = public_base_url();
+    let limit = incident_feed::FEED_ENTRY_LIMIT as i64;
+    let mut entries = Vec::new();
+
+    let incidents: Vec<Incident> = sqlx::query_as(
+        "SELECT * FROM incidents
+         WHERE deleted_at IS NULL
+         ORDER BY GREATEST(created_at, COALESCE(end_time, created_at)) DESC
+         LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list incidents for feed", err))?;
+    for incident in &incidents {
+        entries.push(FeedEntry::opened(&base_url, incident));
+        entries.extend(FeedEntry::resolved(&base_url, incident));
+    }
+
+    let updates: Vec<FeedUpdateRow> = sqlx::query_as(
+        "SELECT u.*, i.incident_type
+         FROM incident_updates u
+         JOIN incidents i ON i.id = u.incident_id
+         WHERE i.deleted_at IS NULL AND u.status IS NOT NULL
+         ORDER BY u.created_at DESC
+         LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list incident updates for feed", err))?;
+    entries.extend(
+        updates
+            .iter()
+            .map(|row| FeedEntry::status_update(&base_url, &row.incident_type, &row.update)),
+    );
+
+    let severity_changes: Vec<FeedSeverityChangeRow> = sqlx::query_as(
+        "SELECT a.id, a.incident_id, i.incident_type, a.details, a.timestamp
+         FROM incident_audit_log a
+         JOIN incidents i ON i.id = a.incident_id
+         WHERE i.deleted_at IS NULL AND a.action = 'severity_changed'
+         ORDER BY a.timestamp DESC
+         LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list severity changes for feed", err))?;
+    for row in &severity_changes {
+        let field = |name: &str| {
+            row.details
+                .as_ref()
+                .and_then(|d| d[name].as_str())
+                .unwrap_or("unknown")
+                .to_string()
+        };
+        entries.push(FeedEntry::severity_change(
+            &base_url,
+            row.id,
+            row.incident_id,
+            &row.incident_type,
+            &field("from"),
+            &field("to"),
+            row.timestamp,
+        ));
+    }
+
+    let windows = maintenance_handlers::upcoming_windows(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list maintenance windows for feed", err))?;
+    entries.extend(
+        windows
+            .iter()
+            .map(|window| FeedEntry::maintenance(&base_url, window)),
+    );
+
+    if include_publications {
+        let publications: Vec<FeedPublicationRow> = sqlx::query_as(
+            "SELECT v.id, v.contract_id, c.name, v.verified_at
+             FROM verifications v
+             JOIN contracts c ON c.id = v.contract_id
+             WHERE v.status = 'verified' AND v.verified_at IS NOT NULL
+             ORDER BY v.verified_at DESC
+             LIMIT $1",
+        )
+        .bind(limit)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("list verified contracts for feed", err))?;
+        entries.extend(publications.iter().map(|row| {
+            FeedEntry::publication(
+                &base_url,
+                row.id,
+                row.contract_id,
+                &row.name,
+                row.verified_at,
+            )
+        }));
+    }
+
+    let xml = incident_feed::render_atom(&base_url, entries, Utc::now());
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "application/atom+xml; charset=utf-8")
+        .header(header::CACHE_CONTROL, PUBLIC_CACHE_CONTROL)
+        .body(axum::body::Body::from(xml))
+        .map_err(|_| ApiError::internal("Failed to build response"))
+}
+
+/// Postgres codes for a missing table or column, i.e. a database that has not
+/// run the incident migrations yet
+const UNDEFINED_TABLE: &str = "42P01";
+const UNDEFINED_COLUMN: &str = "42703";
+
+/// Summarizes the incidents that affected a contract for its detail view.
+/// Returns `None` when the incident tables don't exist.
+pub(crate) async fn contract_incident_summary(
+    pool: &sqlx::PgPool,
+    contract_id: Uuid,
+) -> Result<Option<ContractIncidentSummary>, sqlx::Error> {
+    let result = sqlx::query_as(
+        "SELECT COUNT(*) AS incident_count,
+                MAX(i.start_time) AS last_incident_at,
+                (ARRAY_AGG(i.severity::text ORDER BY i.start_time DESC))[1]
+                    AS last_incident_severity,
+                COALESCE(BOOL_OR(i.end_time IS NULL), FALSE) AS has_open_incident,
+                COALESCE(BOOL_OR(i.end_time IS NULL AND i.severity = 'critical'), FALSE)
+                    AS has_open_critical_incident
+         FROM incidents i
+         JOIN incident_contracts ic ON ic.incident_id = i.id
+         WHERE ic.contract_id = $1 AND i.deleted_at IS NULL",
+    )
+    .bind(contract_id)
+    .fetch_one(pool)
+    .await;
+
+    match result {
+        Ok(summary) => Ok(Some(summary)),
+        Err(sqlx::Error::Database(err))
+            if matches!(
+                err.code().as_deref(),
+                Some(UNDEFINED_TABLE | UNDEFINED_COLUMN)
+            ) =>
+        {
+            Ok(None)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/incidents",
+    params(
+        ("id" = String, Path, description = "Contract UUID"),
+        ListIncidentsQuery,
+        PageParams
+    ),
+    responses(
+        (status = 200, description = "Incidents that affected the contract", body = Paginated<Incident>),
+        (status = 400, description = "Malformed contract ID or invalid filters"),
+        (status = 404, description = "Contract not found")
+    ),
+    tag = "Incidents"
+)]
+pub async fn list_contract_incidents(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: Option<AuthClaims>,
+    pagination: Pagination,
+    params: Result<Query<ListIncidentsQuery>, QueryRejection>,
+) -> ApiResult<Response> {
+    let Query(mut query) = params.map_err(map_query_rejection)?;
+    let contract_id = Uuid::parse_str(&id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidContractId",
+            format!("Invalid contract ID format: {}", id),
+        )
+    })?;
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM contracts WHERE id = $1)")
+        .bind(contract_id)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("check contract exists", err))?;
+    if !exists {
+        return Err(ApiError::not_found(
+            "ContractNotFound",
+            format!("No contract found with ID: {}", id),
+        ));
+    }
+
+    query.contract_id = Some(contract_id);
+    let page = query_incidents(&state, claims.as_ref(), &query, pagination).await?;
+    Ok(incident_page_response(page, query.envelope))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/{id}/report",
+    params(
+        ("id" = String, Path, description = "Incident UUID"),
+        IncidentReportQuery
+    ),
+    responses(
+        (status = 200, description = "Markdown incident report", body = String, content_type = "text/markdown"),
+        (status = 400, description = "Malformed incident ID or unsupported format"),
+        (status = 404, description = "Incident not found")
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_incident_report(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    params: Result<Query<IncidentReportQuery>, QueryRejection>,
+) -> ApiResult<Response> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+    let format = query.format.as_deref().unwrap_or("markdown");
+    if !format.eq_ignore_ascii_case("markdown") && !format.eq_ignore_ascii_case("md") {
+        return Err(ApiError::bad_request(
+            "UnsupportedReportFormat",
+            format!(
+                "Unsupported report format '{}'; use format=markdown",
+                format
+            ),
+        ));
+    }
+
+    let incident_id = parse_incident_id(&id)?;
+    let mut incident = fetch_live_incident(&state, incident_id, &id).await?;
+    load_affected_contracts(&state, std::slice::from_mut(&mut incident)).await?;
+
+    let updates: Vec<IncidentUpdate> = sqlx::query_as(
+        "SELECT * FROM incident_updates WHERE incident_id = $1 ORDER BY created_at ASC",
+    )
+    .bind(incident_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list incident updates for report", err))?;
+
+    let status_changes: Vec<IncidentAuditEntry> = sqlx::query_as(
+        "SELECT action, changed_by, details, timestamp
+         FROM incident_audit_log
+         WHERE incident_id = $1
+         ORDER BY timestamp ASC",
+    )
+    .bind(incident_id)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list incident audit log for report", err))?;
+
+    let postmortem = load_postmortem(&state, incident_id).await?;
+    let recovery_validations = load_recovery_validations(&state, incident_id).await?;
+    incident.objectives = objective_outcome(&state, &incident).await?;
+
+    let markdown = incident_report::render_markdown(&incident_report::IncidentReport {
+        incident: &incident,
+        updates: &updates,
+        status_changes: &status_changes,
+        postmortem: &postmortem,
+        recovery_validations: &recovery_validations,
+        generated_at: Utc::now(),
+    });
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, "text/markdown; charset=utf-8")
+        .body(axum::body::Body::from(markdown))
+        .map_err(|_| ApiError::internal("Failed to build response"))
+}
@@ -0,0 +1,492 @@
+//! Incident counts per affected contract and time bucket, for spotting
+//! incident-prone contracts and when incidents cluster.
+//!
+//!   GET /api/incidents/heatmap?window=180d&bucket=week&top=10&weighted=true
+//!
+//! One grouped query over `incident_contracts` counts every incident once
+//! per contract it affected, ranks the contracts by incident count and
+//! folds all but the `top` ones into a single `other` row. With `weighted`
+//! each incident counts by its severity (critical 4, major 3, minor 2,
+//! informational 1) in `score`. Downtime is the summed duration of the
+//! cell's resolved incidents; open ones have none yet. Registry-wide
+//! incidents that name no contract aren't part of the matrix.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{rejection::QueryRejection, Query, State},
+    Json,
+};
+use chrono::{DateTime, Datelike, Duration, Months, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::{db_internal_error, map_query_rejection},
+    incident_models::parse_window_days,
+    state::AppState,
+};
+
+const DEFAULT_WINDOW_DAYS: i64 = 180;
+const DEFAULT_TOP: i64 = 10;
+const MAX_TOP: i64 = 50;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HeatmapBucket {
+    Day,
+    #[default]
+    Week,
+    Month,
+}
+
+impl HeatmapBucket {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+
+    /// Windows this bucket size can split, in days: at least one bucket,
+    /// at most a screen's width of them
+    fn window_range(self) -> (i64, i64) {
+        match self {
+            Self::Day => (1, 90),
+            Self::Week => (7, 730),
+            Self::Month => (30, 730),
+        }
+    }
+
+    /// Start of the bucket `at` falls in; weeks start on Monday, as
+    /// Postgres' `date_trunc` has them
+    fn start_of(self, at: DateTime<Utc>) -> DateTime<Utc> {
+        let day = at.date_naive();
+        let start = match self {
+            Self::Day => day,
+            Self::Week => day - Duration::days(i64::from(day.weekday().num_days_from_monday())),
+            Self::Month => day.with_day(1).expect("every month has a first day"),
+        };
+        start.and_time(NaiveTime::MIN).and_utc()
+    }
+
+    fn next(self, start: DateTime<Utc>) -> DateTime<Utc> {
+        match self {
+            Self::Day => start + Duration::days(1),
+            Self::Week => start + Duration::days(7),
+            Self::Month => start + Months::new(1),
+        }
+    }
+
+    /// Starts of every bucket from the one holding `from` to the one
+    /// holding `until`
+    fn starts(self, from: DateTime<Utc>, until: DateTime<Utc>) -> Vec<DateTime<Utc>> {
+        let mut starts = Vec::new();
+        let mut start = self.start_of(from);
+        while start <= until {
+            starts.push(start);
+            start = self.next(start);
+        }
+        starts
+    }
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct IncidentHeatmapQuery {
+    /// Look-back window such as `180d` or `26w` (default `180d`)
+    pub window: Option<String>,
+    /// `day` (windows up to 90d), `week` (7d to 730d, the default) or
+    /// `month` (30d to 730d)
+    #[serde(default)]
+    pub bucket: HeatmapBucket,
+    /// Contracts shown on their own row; the rest are rolled into `other`
+    /// (default 10, max 50)
+    pub top: Option<i64>,
+    /// Score incidents by severity instead of counting them
+    #[serde(default)]
+    pub weighted: bool,
+    /// Count incidents that started during planned maintenance
+    #[serde(default)]
+    pub include_maintenance: bool,
+    /// Leave out incidents brought in through `POST /api/incidents/import`
+    #[serde(default)]
+    pub exclude_imported: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct HeatmapContract {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub network: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct HeatmapCell {
+    pub incidents: i64,
+    /// `incidents`, or their severity weights with `weighted`
+    pub score: i64,
+    /// Summed duration of the resolved incidents; absent when none are
+    pub downtime_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct HeatmapRow {
+    /// Absent on the `other` row
+    pub contract: Option<HeatmapContract>,
+    /// Rolls up every contract outside the top ones
+    pub other: bool,
+    /// Contracts the row covers: 1, or how many `other` rolls up
+    pub contracts: i64,
+    pub incidents: i64,
+    pub score: i64,
+    pub downtime_minutes: Option<i64>,
+    /// One per entry of `buckets`
+    pub cells: Vec<HeatmapCell>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IncidentHeatmap {
+    pub window_days: i64,
+    pub window_start: DateTime<Utc>,
+    pub bucket: HeatmapBucket,
+    pub weighted: bool,
+    pub top: i64,
+    /// Start of each column, oldest first
+    pub buckets: Vec<DateTime<Utc>>,
+    /// Top contracts by incident count, then `other` when there are more
+    pub rows: Vec<HeatmapRow>,
+    /// Highest cell score, for scaling colors
+    pub max_score: i64,
+    /// Contracts with at least one incident in the window
+    pub contracts_with_incidents: i64,
+}
+
+/// One cell of the grouped query; the contract columns are null on the
+/// `other` row
+#[derive(Debug, Clone, FromRow)]
+struct CellRow {
+    contract_id: Option<Uuid>,
+    address: Option<String>,
+    name: Option<String>,
+    network: Option<String>,
+    bucket: DateTime<Utc>,
+    incidents: i64,
+    weighted: i64,
+    downtime_minutes: Option<f64>,
+    contracts: i64,
+}
+
+fn validate(query: &IncidentHeatmapQuery) -> ApiResult<(i64, i64)> {
+    let window_days = parse_window_days(query.window.as_deref(), DEFAULT_WINDOW_DAYS)
+        .map_err(|msg| ApiError::bad_request("InvalidWindow", msg))?;
+    let (min, max) = query.bucket.window_range();
+    if !(min..=max).contains(&window_days) {
+        return Err(ApiError::bad_request(
+            "InvalidBucket",
+            format!(
+                "bucket={} needs a window between {}d and {}d, got {}d",
+                query.bucket.as_str(),
+                min,
+                max,
+                window_days
+            ),
+        ));
+    }
+
+    let top = query.top.unwrap_or(DEFAULT_TOP);
+    if !(1..=MAX_TOP).contains(&top) {
+        return Err(ApiError::bad_request(
+            "InvalidTop",
+            format!("top must be between 1 and {}", MAX_TOP),
+        ));
+    }
+    Ok((window_days, top))
+}
+
+fn add_downtime(total: Option<i64>, minutes: Option<i64>) -> Option<i64> {
+    match (total, minutes) {
+        (Some(total), Some(minutes)) => Some(total + minutes),
+        (total, minutes) => total.or(minutes),
+    }
+}
+
+/// Lays the grouped cells out as rows over `buckets`: contracts by
+/// incident count, then `other`.
+fn assemble(cells: Vec<CellRow>, buckets: &[DateTime<Utc>], weighted: bool) -> Vec<HeatmapRow> {
+    let columns: HashMap<DateTime<Utc>, usize> = buckets
+        .iter()
+        .enumerate()
+        .map(|(index, start)| (*start, index))
+        .collect();
+    let contracts_total = cells.iter().map(|cell| cell.contracts).max().unwrap_or(0);
+
+    let mut rows: Vec<HeatmapRow> = Vec::new();
+    for cell in cells {
+        let row = match rows
+            .iter()
+            .position(|row| row.contract.as_ref().map(|c| c.id) == cell.contract_id)
+        {
+            Some(index) => &mut rows[index],
+            None => {
+                rows.push(HeatmapRow {
+                    contract: cell.contract_id.map(|id| HeatmapContract {
+                        id,
+                        contract_id: cell.address.clone().unwrap_or_default(),
+                        name: cell.name.clone().unwrap_or_default(),
+                        network: cell.network.clone().unwrap_or_default(),
+                    }),
+                    other: cell.contract_id.is_none(),
+                    contracts: 1,
+                    incidents: 0,
+                    score: 0,
+                    downtime_minutes: None,
+                    cells: vec![HeatmapCell::default(); buckets.len()],
+                });
+                rows.last_mut().expect("just pushed")
+            }
+        };
+
+        let score = if weighted {
+            cell.weighted
+        } else {
+            cell.incidents
+        };
+        let downtime = cell.downtime_minutes.map(|minutes| minutes.round() as i64);
+        row.incidents += cell.incidents;
+        row.score += score;
+        row.downtime_minutes = add_downtime(row.downtime_minutes, downtime);
+        if let Some(&column) = columns.get(&cell.bucket) {
+            let target = &mut row.cells[column];
+            target.incidents += cell.incidents;
+            target.score += score;
+            target.downtime_minutes = add_downtime(target.downtime_minutes, downtime);
+        }
+    }
+
+    let shown = rows.iter().filter(|row| !row.other).count() as i64;
+    for row in rows.iter_mut().filter(|row| row.other) {
+        row.contracts = contracts_total - shown;
+    }
+    rows.sort_by(|a, b| {
+        a.other
+            .cmp(&b.other)
+            .then(b.incidents.cmp(&a.incidents))
+            .then_with(|| {
+                let name = |row: &HeatmapRow| row.contract.as_ref().map(|c| c.name.clone());
+                name(a).cmp(&name(b))
+            })
+    });
+    rows
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/incidents/heatmap",
+    params(IncidentHeatmapQuery),
+    responses(
+        (status = 200, description = "Incident counts per contract and time bucket", body = IncidentHeatmap),
+        (status = 400, description = "Invalid window, bucket for the window, or top")
+    ),
+    tag = "Incidents"
+)]
+pub async fn get_incident_heatmap(
+    State(state): State<AppState>,
+    params: Result<Query<IncidentHeatmapQuery>, QueryRejection>,
+) -> ApiResult<Json<IncidentHeatmap>> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+    let (window_days, top) = validate(&query)?;
+    let now = Utc::now();
+    let window_start = now - Duration::days(window_days);
+
+    let cells: Vec<CellRow> = sqlx::query_as(
+        r#"
+        WITH pairs AS (
+            SELECT ic.contract_id,
+                   date_trunc($2, i.start_time AT TIME ZONE 'UTC') AT TIME ZONE 'UTC' AS bucket,
+                   CASE i.severity::TEXT
+                       WHEN 'critical' THEN 4
+                       WHEN 'major' THEN 3
+                       WHEN 'minor' THEN 2
+                       ELSE 1
+                   END AS weight,
+                   EXTRACT(EPOCH FROM i.end_time - i.start_time)::FLOAT8 / 60 AS downtime_minutes
+            FROM incidents i
+            JOIN incident_contracts ic ON ic.incident_id = i.id
+            WHERE i.deleted_at IS NULL AND i.start_time >= $1
+              AND ($4 OR i.maintenance_window_id IS NULL)
+              AND NOT ($5 AND i.source = 'import')
+        ),
+        ranked AS (
+            SELECT contract_id,
+                   ROW_NUMBER() OVER (ORDER BY COUNT(*) DESC, contract_id) AS rank,
+                   COUNT(*) OVER () AS contracts
+            FROM pairs
+            GROUP BY contract_id
+        )
+        SELECT CASE WHEN r.rank <= $3 THEN p.contract_id END AS contract_id,
+               MIN(c.contract_id) AS address,
+               MIN(c.name) AS name,
+               MIN(c.network::TEXT) AS network,
+               p.bucket,
+               COUNT(*) AS incidents,
+               SUM(p.weight)::BIGINT AS weighted,
+               SUM(p.downtime_minutes) AS downtime_minutes,
+               MAX(r.contracts) AS contracts
+        FROM pairs p
+        JOIN ranked r ON r.contract_id = p.contract_id
+        LEFT JOIN contracts c ON c.id = p.contract_id AND r.rank <= $3
+        GROUP BY 1, p.bucket
+        "#,
+    )
+    .bind(window_start)
+    .bind(query.bucket.as_str())
+    .bind(top)
+    .bind(query.include_maintenance)
+    .bind(query.exclude_imported)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("compute incident heatmap", err))?;
+
+    let contracts_with_incidents = cells.iter().map(|cell| cell.contracts).max().unwrap_or(0);
+    let buckets = query.bucket.starts(window_start, now);
+    let rows = assemble(cells, &buckets, query.weighted);
+    let max_score = rows
+        .iter()
+        .flat_map(|row| row.cells.iter().map(|cell| cell.score))
+        .max()
+        .unwrap_or(0);
+
+    Ok(Json(IncidentHeatmap {
+        window_days,
+        window_start,
+        bucket: query.bucket,
+        weighted: query.weighted,
+        top,
+        buckets,
+        rows,
+        max_score,
+        contracts_with_incidents,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 13, 30, 0).unwrap()
+    }
+
+    fn midnight(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+
+    fn query(window: &str, bucket: HeatmapBucket) -> IncidentHeatmapQuery {
+        IncidentHeatmapQuery {
+            window: Some(window.to_string()),
+            bucket,
+            top: None,
+            weighted: false,
+            include_maintenance: false,
+            exclude_imported: false,
+        }
+    }
+
+    #[test]
+    fn buckets_start_like_date_trunc() {
+        // 2026-10-15 is a Thursday
+        assert_eq!(
+            HeatmapBucket::Week.start_of(at(2026, 10, 15)),
+            midnight(2026, 10, 12)
+        );
+        assert_eq!(
+            HeatmapBucket::Month.start_of(at(2026, 10, 15)),
+            midnight(2026, 10, 1)
+        );
+        assert_eq!(
+            HeatmapBucket::Day.start_of(at(2026, 10, 15)),
+            midnight(2026, 10, 15)
+        );
+
+        let months = HeatmapBucket::Month.starts(at(2026, 1, 31), at(2026, 4, 2));
+        assert_eq!(
+            months,
+            vec![
+                midnight(2026, 1, 1),
+                midnight(2026, 2, 1),
+                midnight(2026, 3, 1),
+                midnight(2026, 4, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn windows_must_suit_the_bucket() {
+        assert_eq!(
+            validate(&query("180d", HeatmapBucket::Week)).unwrap(),
+            (180, DEFAULT_TOP)
+        );
+        assert!(validate(&query("180d", HeatmapBucket::Day)).is_err());
+        assert!(validate(&query("3d", HeatmapBucket::Week)).is_err());
+        assert!(validate(&query("2w", HeatmapBucket::Month)).is_err());
+        assert!(validate(&query("12w", HeatmapBucket::Month)).is_ok());
+        assert!(validate(&query("soon", HeatmapBucket::Week)).is_err());
+
+        let mut too_many = query("90d", HeatmapBucket::Week);
+        too_many.top = Some(MAX_TOP + 1);
+        assert!(validate(&too_many).is_err());
+    }
+
+    fn cell(contract: Option<Uuid>, bucket: DateTime<Utc>, incidents: i64) -> CellRow {
+        CellRow {
+            contract_id: contract,
+            address: contract.map(|_| "CABC".to_string()),
+            name: contract.map(|id| format!("contract-{}", &id.to_string()[..4])),
+            network: contract.map(|_| "testnet".to_string()),
+            bucket,
+            incidents,
+            weighted: incidents * 3,
+            downtime_minutes: Some(incidents as f64 * 10.4),
+            contracts: 5,
+        }
+    }
+
+    #[test]
+    fn cells_are_laid_out_per_contract_with_other_last() {
+        let buckets = [midnight(2026, 10, 5), midnight(2026, 10, 12)];
+        let (quiet, busy) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut open = cell(Some(busy), buckets[1], 1);
+        open.downtime_minutes = None;
+        let cells = vec![
+            cell(None, buckets[0], 4),
+            cell(Some(quiet), buckets[1], 1),
+            cell(Some(busy), buckets[0], 2),
+            open,
+        ];
+
+        let rows = assemble(cells, &buckets, false);
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].contract.as_ref().unwrap().id, busy);
+        assert_eq!(rows[0].incidents, 3);
+        assert_eq!(rows[0].cells[0].downtime_minutes, Some(21));
+        assert_eq!(rows[0].cells[1].downtime_minutes, None);
+        assert_eq!(rows[0].downtime_minutes, Some(21));
+        assert_eq!(rows[1].contract.as_ref().unwrap().id, quiet);
+
+        let other = &rows[2];
+        assert!(other.other && other.contract.is_none());
+        assert_eq!(other.contracts, 3);
+        assert_eq!(other.cells[0].incidents, 4);
+        assert_eq!(other.cells[1], HeatmapCell::default());
+
+        let weighted = assemble(vec![cell(Some(busy), buckets[0], 2)], &buckets, true);
+        assert_eq!(weighted[0].score, 6);
+        assert_eq!(weighted[0].incidents, 2);
+    }
+}
@@ -0,0 +1,645 @@
+//! Bulk import of incident history from spreadsheets and other trackers.
+//!
+//!   POST /api/incidents/import[?dry_run=true]   – NDJSON, one incident per line
+//!
+//! The body is read line by line and every line is validated before anything
+//! is written; each problem is reported with its line number. Lines with
+//! problems are skipped, the rest are inserted in transactions of
+//! [`BATCH_SIZE`]. A line whose `external_id` was imported before is skipped
+//! as a duplicate, so an import can be re-run after fixing the bad lines.
+//!
+//! Imported incidents are stored with `source = 'import'` and are already
+//! resolved: they send no notifications, aren't escalated and aren't matched
+//! to maintenance windows. Metrics count them unless `exclude_imported` is
+//! set. At most `INCIDENT_IMPORT_MAX_RECORDS` (default 5000) records are
+//! accepted per request.
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    body::Body,
+    extract::{rejection::QueryRejection, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::{db_internal_error, map_query_rejection},
+    incident_handlers::merge_affected_contract_ids,
+    incident_models::{DurationInput, IncidentSeverity},
+    state::AppState,
+};
+
+const DEFAULT_MAX_RECORDS: usize = 5_000;
+/// Incidents inserted per transaction
+const BATCH_SIZE: usize = 500;
+/// Longest accepted line; a record is a few hundred bytes
+const MAX_LINE_BYTES: usize = 64 * 1024;
+const MAX_INCIDENT_TYPE_LEN: usize = 50;
+const MAX_EXTERNAL_ID_LEN: usize = 255;
+
+/// One line of the import
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ImportIncidentRecord {
+    /// ID in the tracker the incident comes from; re-imports skip it
+    pub external_id: Option<String>,
+    pub incident_type: String,
+    /// Defaults to `major` when omitted
+    #[serde(default)]
+    pub severity: IncidentSeverity,
+    pub description: String,
+    pub start_time: DateTime<Utc>,
+    /// Required: only resolved incidents can be imported
+    pub end_time: Option<DateTime<Utc>>,
+    pub rto_achieved: Option<DurationInput>,
+    pub rpo_achieved: Option<DurationInput>,
+    pub lessons_learned: Option<String>,
+    pub contract_id: Option<Uuid>,
+    #[serde(default)]
+    pub affected_contract_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ImportQuery {
+    /// Validate and report without inserting anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportLineStatus {
+    Imported,
+    /// Would be imported; only in dry runs
+    Valid,
+    /// Its `external_id` was already imported, or appears earlier in the file
+    Duplicate,
+    Invalid,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ImportLineOutcome {
+    /// 1-based line number in the body
+    pub line: usize,
+    pub status: ImportLineStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub incident_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct IncidentImportReport {
+    pub dry_run: bool,
+    /// Non-blank lines read
+    pub records: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+    /// One entry per non-blank line, in order
+    pub lines: Vec<ImportLineOutcome>,
+}
+
+/// A line that passed validation, ready to insert
+#[derive(Debug)]
+struct PreparedIncident {
+    id: Uuid,
+    record: ImportIncidentRecord,
+    affected_ids: Vec<Uuid>,
+    rto_seconds: Option<i64>,
+    rpo_seconds: Option<i64>,
+}
+
+fn max_records() -> usize {
+    std::env::var("INCIDENT_IMPORT_MAX_RECORDS")
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .filter(|max| *max > 0)
+        .unwrap_or(DEFAULT_MAX_RECORDS)
+}
+
+/// Parses one line; `None` for a blank one
+fn parse_line(line: &[u8]) -> Option<Result<ImportIncidentRecord, String>> {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return None;
+    }
+    Some(serde_json::from_slice(line).map_err(|err| format!("invalid JSON: {}", err)))
+}
+
+/// Checks everything about a record that doesn't need the database
+fn validate(
+    record: ImportIncidentRecord,
+    now: DateTime<Utc>,
+) -> Result<PreparedIncident, Vec<String>> {
+    let mut errors = Vec::new();
+
+    let incident_type = record.incident_type.trim();
+    if incident_type.is_empty() {
+        errors.push("incident_type must not be empty".to_string());
+    } else if incident_type.chars().count() > MAX_INCIDENT_TYPE_LEN {
+        errors.push(format!(
+            "incident_type must be at most {} characters",
+            MAX_INCIDENT_TYPE_LEN
+        ));
+    }
+    if record.description.trim().is_empty() {
+        errors.push("description must not be empty".to_string());
+    }
+    if let Some(external_id) = &record.external_id {
+        let external_id = external_id.trim();
+        if external_id.is_empty() {
+            errors.push("external_id must not be blank".to_string());
+        } else if external_id.chars().count() > MAX_EXTERNAL_ID_LEN {
+            errors.push(format!(
+                "external_id must be at most {} characters",
+                MAX_EXTERNAL_ID_LEN
+            ));
+        }
+    }
+
+    if record.start_time > now {
+        errors.push("start_time must not be in the future".to_string());
+    }
+    match record.end_time {
+        None => errors.push("end_time is required; only resolved incidents can be imported".into()),
+        Some(end) if end < record.start_time => {
+            errors.push("end_time must not be earlier than start_time".to_string())
+        }
+        Some(end) if end > now => errors.push("end_time must not be in the future".to_string()),
+        Some(_) => {}
+    }
+
+    let mut duration = |field: &str, input: Option<&DurationInput>| {
+        input.and_then(|input| match input.to_seconds() {
+            Ok(seconds) => Some(seconds),
+            Err(err) => {
+                errors.push(format!("{}: {}", field, err));
+                None
+            }
+        })
+    };
+    let rto_seconds = duration("rto_achieved", record.rto_achieved.as_ref());
+    let rpo_seconds = duration("rpo_achieved", record.rpo_achieved.as_ref());
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let affected_ids =
+        merge_affected_contract_ids(record.contract_id, &record.affected_contract_ids);
+    Ok(PreparedIncident {
+        id: Uuid::new_v4(),
+        record: ImportIncidentRecord {
+            external_id: record.external_id.map(|id| id.trim().to_string()),
+            incident_type: incident_type.to_string(),
+            ..record
+        },
+        affected_ids,
+        rto_seconds,
+        rpo_seconds,
+    })
+}
+
+/// Reads the body's lines, failing once it holds more than `max_records`
+/// records or a line longer than [`MAX_LINE_BYTES`]
+async fn read_lines(
+    body: Body,
+    max_records: usize,
+) -> ApiResult<Vec<(usize, Result<ImportIncidentRecord, String>)>> {
+    let mut records = Vec::new();
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut line_no = 0;
+    let mut stream = body.into_data_stream();
+
+    let mut take = |line: &[u8], line_no: usize| -> ApiResult<()> {
+        if let Some(parsed) = parse_line(line) {
+            if records.len() == max_records {
+                return Err(ApiError::payload_too_large(format!(
+                    "At most {} incidents can be imported per request",
+                    max_records
+                )));
+            }
+            records.push((line_no, parsed));
+        }
+        Ok(())
+    };
+
+    loop {
+        let chunk = stream.next().await;
+        let done = chunk.is_none();
+        if let Some(chunk) = chunk {
+            let chunk = chunk.map_err(|err| {
+                ApiError::bad_request("InvalidBody", format!("Failed to read body: {}", err))
+            })?;
+            buffer.extend_from_slice(&chunk);
+        }
+
+        let mut start = 0;
+        while let Some(pos) = buffer[start..].iter().position(|b| *b == b'\n') {
+            line_no += 1;
+            take(&buffer[start..start + pos], line_no)?;
+            start += pos + 1;
+        }
+        buffer.drain(..start);
+
+        if buffer.len() > MAX_LINE_BYTES {
+            return Err(ApiError::bad_request(
+                "LineTooLong",
+                format!(
+                    "Line {} is longer than {} bytes",
+                    line_no + 1,
+                    MAX_LINE_BYTES
+                ),
+            ));
+        }
+        if done {
+            if !buffer.is_empty() {
+                take(&buffer, line_no + 1)?;
+            }
+            break;
+        }
+    }
+    Ok(records)
+}
+
+/// Import resolved incidents from another tracker, one JSON record per line.
+/// Requires administrative privileges.
+#[utoipa::path(
+    post,
+    path = "/api/incidents/import",
+    params(ImportQuery),
+    request_body(
+        content = ImportIncidentRecord,
+        content_type = "application/x-ndjson",
+        description = "One ImportIncidentRecord per line"
+    ),
+    responses(
+        (status = 200, description = "Outcome of every line", body = IncidentImportReport),
+        (status = 400, description = "A line is too long or the body couldn't be read"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 413, description = "More records than INCIDENT_IMPORT_MAX_RECORDS"),
+        (status = 415, description = "Content-Type is not application/x-ndjson")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Incidents"
+)]
+pub async fn import_incidents(
+    State(state): State<AppState>,
+    params: Result<Query<ImportQuery>, QueryRejection>,
+    claims: AuthClaims,
+    body: Body,
+) -> ApiResult<Json<IncidentImportReport>> {
+    if !auth::is_admin(&claims) {
+        return Err(ApiError::forbidden(
+            "Administrative privileges are required to import incidents",
+        ));
+    }
+    let Query(query) = params.map_err(map_query_rejection)?;
+
+    let lines = read_lines(body, max_records()).await?;
+    let now = Utc::now();
+
+    let mut outcomes: Vec<ImportLineOutcome> = Vec::with_capacity(lines.len());
+    let mut prepared: Vec<(usize, PreparedIncident)> = Vec::new();
+    let mut first_line_of: HashMap<String, usize> = HashMap::new();
+    for (line, parsed) in lines {
+        let result = parsed
+            .map_err(|err| vec![err])
+            .and_then(|r| validate(r, now));
+        match result {
+            Ok(incident) => {
+                let earlier = incident
+                    .record
+                    .external_id
+                    .as_ref()
+                    .and_then(|id| first_line_of.get(id).copied());
+                if let Some(earlier) = earlier {
+                    outcomes.push(ImportLineOutcome {
+                        line,
+                        status: ImportLineStatus::Duplicate,
+                        external_id: incident.record.external_id,
+                        incident_id: None,
+                        errors: vec![format!("external_id already appears on line {}", earlier)],
+                    });
+                    continue;
+                }
+                if let Some(id) = &incident.record.external_id {
+                    first_line_of.insert(id.clone(), line);
+                }
+                outcomes.push(ImportLineOutcome {
+                    line,
+                    status: ImportLineStatus::Valid,
+                    external_id: incident.record.external_id.clone(),
+                    incident_id: None,
+                    errors: Vec::new(),
+                });
+                prepared.push((outcomes.len() - 1, incident));
+            }
+            Err(errors) => outcomes.push(ImportLineOutcome {
+                line,
+                status: ImportLineStatus::Invalid,
+                external_id: None,
+                incident_id: None,
+                errors,
+            }),
+        }
+    }
+
+    // Contracts and earlier imports are looked up once for the whole body
+    let contract_ids: Vec<Uuid> = prepared
+        .iter()
+        .flat_map(|(_, incident)| incident.affected_ids.iter().copied())
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let known_contracts: HashSet<Uuid> = if contract_ids.is_empty() {
+        HashSet::new()
+    } else {
+        sqlx::query_scalar::<_, Uuid>("SELECT id FROM contracts WHERE id = ANY($1)")
+            .bind(&contract_ids)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("validate imported contracts", err))?
+            .into_iter()
+            .collect()
+    };
+    let external_ids: Vec<String> = first_line_of.into_keys().collect();
+    let imported_before: HashSet<String> = if external_ids.is_empty() {
+        HashSet::new()
+    } else {
+        sqlx::query_scalar::<_, String>(
+            "SELECT external_id FROM incidents WHERE external_id = ANY($1)",
+        )
+        .bind(&external_ids)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|err| db_internal_error("find imported incidents", err))?
+        .into_iter()
+        .collect()
+    };
+
+    prepared.retain(|(index, incident)| {
+        let outcome = &mut outcomes[*index];
+        let unknown: Vec<String> = incident
+            .affected_ids
+            .iter()
+            .filter(|id| !known_contracts.contains(id))
+            .map(Uuid::to_string)
+            .collect();
+        if !unknown.is_empty() {
+            outcome.status = ImportLineStatus::Invalid;
+            outcome
+                .errors
+                .push(format!("Unknown contract IDs: {}", unknown.join(", ")));
+            return false;
+        }
+        if incident
+            .record
+            .external_id
+            .as_ref()
+            .is_some_and(|id| imported_before.contains(id))
+        {
+            outcome.status = ImportLineStatus::Duplicate;
+            outcome
+                .errors
+                .push("external_id was already imported".to_string());
+            return false;
+        }
+        true
+    });
+
+    if !query.dry_run {
+        for batch in prepared.chunks(BATCH_SIZE) {
+            let inserted = insert_batch(&state, batch, &claims.sub).await?;
+            for (index, incident) in batch {
+                let outcome = &mut outcomes[*index];
+                if inserted.contains(&incident.id) {
+                    outcome.status = ImportLineStatus::Imported;
+                    outcome.incident_id = Some(incident.id);
+                } else {
+                    // Imported by a concurrent request since the lookup above
+                    outcome.status = ImportLineStatus::Duplicate;
+                    outcome
+                        .errors
+                        .push("external_id was already imported".to_string());
+                }
+            }
+        }
+    }
+
+    let count = |status: ImportLineStatus| outcomes.iter().filter(|o| o.status == status).count();
+    let report = IncidentImportReport {
+        dry_run: query.dry_run,
+        records: outcomes.len(),
+        imported: count(ImportLineStatus::Imported),
+        duplicates: count(ImportLineStatus::Duplicate),
+        invalid: count(ImportLineStatus::Invalid),
+        lines: outcomes,
+    };
+    tracing::info!(
+        admin = %claims.sub,
+        dry_run = report.dry_run,
+        records = report.records,
+        imported = report.imported,
+        duplicates = report.duplicates,
+        invalid = report.invalid,
+        "incident import finished"
+    );
+    Ok(Json(report))
+}
+
+/// Inserts one batch with its affected contracts and audit entries in a
+/// single transaction, and returns the ids that were inserted. Incidents
+/// whose `external_id` exists by now are left out.
+async fn insert_batch(
+    state: &AppState,
+    batch: &[(usize, PreparedIncident)],
+    actor: &str,
+) -> ApiResult<HashSet<Uuid>> {
+    let mut ids = Vec::with_capacity(batch.len());
+    let mut contract_ids = Vec::with_capacity(batch.len());
+    let mut types = Vec::with_capacity(batch.len());
+    let mut severities = Vec::with_capacity(batch.len());
+    let mut descriptions = Vec::with_capacity(batch.len());
+    let mut start_times = Vec::with_capacity(batch.len());
+    let mut end_times = Vec::with_capacity(batch.len());
+    let mut rtos = Vec::with_capacity(batch.len());
+    let mut rpos = Vec::with_capacity(batch.len());
+    let mut lessons = Vec::with_capacity(batch.len());
+    let mut external_ids = Vec::with_capacity(batch.len());
+    for (_, incident) in batch {
+        let record = &incident.record;
+        ids.push(incident.id);
+        contract_ids.push(incident.affected_ids.first().copied());
+        types.push(record.incident_type.as_str());
+        severities.push(record.severity.as_str());
+        descriptions.push(record.description.as_str());
+        start_times.push(record.start_time);
+        end_times.push(record.end_time);
+        rtos.push(incident.rto_seconds);
+        rpos.push(incident.rpo_seconds);
+        lessons.push(record.lessons_learned.as_deref());
+        external_ids.push(record.external_id.as_deref());
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin incident import batch", err))?;
+
+    let inserted: HashSet<Uuid> = sqlx::query_scalar::<_, Uuid>(
+        r#"
+        INSERT INTO incidents
+            (id, contract_id, incident_type, severity, description, start_time, end_time,
+             rto_achieved_seconds, rpo_achieved_seconds, lessons_learned, source, external_id)
+        SELECT id, contract_id, incident_type, severity::incident_severity, description,
+               start_time, end_time, rto, rpo, lessons_learned, 'import', external_id
+        FROM UNNEST($1::UUID[], $2::UUID[], $3::TEXT[], $4::TEXT[], $5::TEXT[],
+                    $6::TIMESTAMPTZ[], $7::TIMESTAMPTZ[], $8::BIGINT[], $9::BIGINT[],
+                    $10::TEXT[], $11::TEXT[])
+            AS t(id, contract_id, incident_type, severity, description, start_time, end_time,
+                 rto, rpo, lessons_learned, external_id)
+        ON CONFLICT (external_id) WHERE external_id IS NOT NULL DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(&ids)
+    .bind(&contract_ids)
+    .bind(&types)
+    .bind(&severities)
+    .bind(&descriptions)
+    .bind(&start_times)
+    .bind(&end_times)
+    .bind(&rtos)
+    .bind(&rpos)
+    .bind(&lessons)
+    .bind(&external_ids)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("insert imported incidents", err))?
+    .into_iter()
+    .collect();
+
+    let mut affected_incidents = Vec::new();
+    let mut affected_contracts = Vec::new();
+    let mut audited = Vec::new();
+    let mut audited_lines = Vec::new();
+    for (line, incident) in batch {
+        if !inserted.contains(&incident.id) {
+            continue;
+        }
+        for contract in &incident.affected_ids {
+            affected_incidents.push(incident.id);
+            affected_contracts.push(*contract);
+        }
+        audited.push(incident.id);
+        audited_lines.push(*line as i64);
+    }
+
+    sqlx::query(
+        "INSERT INTO incident_contracts (incident_id, contract_id)
+         SELECT * FROM UNNEST($1::UUID[], $2::UUID[])",
+    )
+    .bind(&affected_incidents)
+    .bind(&affected_contracts)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("insert imported affected contracts", err))?;
+
+    sqlx::query(
+        "INSERT INTO incident_audit_log (incident_id, action, changed_by, details)
+         SELECT id, 'imported', $3, jsonb_build_object('line', line)
+         FROM UNNEST($1::UUID[], $2::BIGINT[]) AS t(id, line)",
+    )
+    .bind(&audited)
+    .bind(&audited_lines)
+    .bind(actor)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("write incident import audit log", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit incident import batch", err))?;
+    Ok(inserted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn record(json: &str) -> ImportIncidentRecord {
+        parse_line(json.as_bytes()).unwrap().unwrap()
+    }
+
+    fn errors(json: &str) -> Vec<String> {
+        validate(record(json), Utc::now()).unwrap_err()
+    }
+
+    #[test]
+    fn blank_lines_are_skipped_and_bad_json_is_reported() {
+        assert!(parse_line(b"").is_none());
+        assert!(parse_line(b"  \r").is_none());
+        let err = parse_line(b"{\"incident_type\": ").unwrap().unwrap_err();
+        assert!(err.starts_with("invalid JSON"), "{err}");
+        let unknown = parse_line(br#"{"incident_type":"outage","description":"x","start_time":"2024-01-01T00:00:00Z","sevrity":"minor"}"#)
+            .unwrap()
+            .unwrap_err();
+        assert!(unknown.contains("sevrity"), "{unknown}");
+    }
+
+    #[test]
+    fn valid_record_is_prepared() {
+        let prepared = validate(
+            record(
+                r#"{"external_id":" PD-42 ","incident_type":"outage","severity":"critical",
+                    "description":"RPC down","start_time":"2024-03-01T10:00:00Z",
+                    "end_time":"2024-03-01T12:30:00Z","rto_achieved":"2h30m","rpo_achieved":60}"#,
+            ),
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(prepared.record.external_id.as_deref(), Some("PD-42"));
+        assert_eq!(prepared.rto_seconds, Some(9_000));
+        assert_eq!(prepared.rpo_seconds, Some(60));
+        assert_eq!(prepared.record.severity, IncidentSeverity::Critical);
+    }
+
+    #[test]
+    fn every_problem_on_a_line_is_collected() {
+        let future = (Utc::now() + Duration::days(1)).to_rfc3339();
+        let found = errors(&format!(
+            r#"{{"incident_type":" ","description":"","start_time":"{future}","rto_achieved":"soon"}}"#
+        ));
+        assert_eq!(found.len(), 5, "{found:?}");
+        assert!(found.iter().any(|e| e.starts_with("end_time is required")));
+        assert!(found.iter().any(|e| e.starts_with("rto_achieved:")));
+
+        let backwards = errors(
+            r#"{"incident_type":"outage","description":"x","start_time":"2024-03-02T00:00:00Z",
+                "end_time":"2024-03-01T00:00:00Z"}"#,
+        );
+        assert_eq!(backwards, ["end_time must not be earlier than start_time"]);
+    }
+
+    #[tokio::test]
+    async fn lines_are_numbered_and_capped() {
+        let body = "\n{\"a\":1}\r\n\n{\"b\":2}";
+        let lines = read_lines(Body::from(body), 10).await.unwrap();
+        let numbers: Vec<usize> = lines.iter().map(|(line, _)| *line).collect();
+        assert_eq!(numbers, [2, 4]);
+
+        let err = read_lines(Body::from(body), 1).await.unwrap_err();
+        assert_eq!(err.status(), axum::http::StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
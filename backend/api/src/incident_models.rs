@@ -0,0 +1,1122 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    postgres::{PgTypeInfo, PgValueRef},
+    FromRow, Postgres,
+};
+use uuid::Uuid;
+
+use crate::maintenance_models::MaintenanceWindow;
+use crate::recovery_objective_models::{ObjectiveOutcome, ObjectiveSource};
+
+/// Upper bound for an achieved RTO/RPO; anything longer is almost certainly a typo
+pub const MAX_RECOVERY_DURATION_SECS: i64 = 30 * 24 * 60 * 60;
+
+/// How badly an incident affects users. Variants are ordered from most to
+/// least severe, matching the `incident_severity` enum in Postgres.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    sqlx::Type,
+    utoipa::ToSchema,
+)]
+#[sqlx(type_name = "incident_severity", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentSeverity {
+    Critical,
+    #[default]
+    Major,
+    Minor,
+    Informational,
+}
+
+impl IncidentSeverity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncidentSeverity::Critical => "critical",
+            IncidentSeverity::Major => "major",
+            IncidentSeverity::Minor => "minor",
+            IncidentSeverity::Informational => "informational",
+        }
+    }
+}
+
+/// An operational incident affecting the registry or one of its contracts
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct Incident {
+    pub id: Uuid,
+    pub contract_id: Option<Uuid>,
+    pub incident_type: String, // 'outage', 'data_loss', 'security', 'degradation', ...
+    pub severity: IncidentSeverity,
+    pub description: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    #[sqlx(rename = "rto_achieved_seconds")]
+    pub rto_achieved: Option<RecoveryDuration>,
+    #[sqlx(rename = "rpo_achieved_seconds")]
+    pub rpo_achieved: Option<RecoveryDuration>,
+    pub lessons_learned: Option<String>,
+    /// True once at least one notification channel accepted a delivery
+    pub notified_users: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Set when an admin soft-deletes the incident; only visible with `include_deleted`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Set when the incident started inside a maintenance window; such
+    /// incidents are left out of metrics by default
+    #[serde(default)]
+    pub maintenance_window_id: Option<Uuid>,
+    /// `import` for incidents backfilled through `POST /api/incidents/import`,
+    /// which metrics can leave out; `api` otherwise
+    #[serde(default)]
+    pub source: String,
+    /// ID in the tracker an imported incident came from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Recovery targets applied when the incident was resolved and whether
+    /// the achieved RTO/RPO met them. The detail view fills in the current
+    /// targets for incidents that have not been resolved yet.
+    #[sqlx(flatten)]
+    #[serde(default)]
+    pub objectives: ObjectiveOutcome,
+    /// Acknowledgement and how far the escalation policy has got
+    #[sqlx(flatten)]
+    #[serde(default)]
+    pub escalation: EscalationState,
+    /// Every contract affected by the incident, loaded from `incident_contracts`
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub affected_contracts: Vec<AffectedContract>,
+    /// Non-fatal problems with the request, e.g. resolving without an RTO/RPO
+    /// while targets are configured
+    #[sqlx(skip)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// Whether anyone has acknowledged an incident, and the escalation steps
+/// fired while nobody did. See [`crate::incident_escalation`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct EscalationState {
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    /// JWT subject of whoever acknowledged it
+    pub acknowledged_by: Option<String>,
+    /// Number of escalation steps fired so far
+    pub escalation_step: i32,
+    /// When the wait for the next escalation step began
+    #[serde(skip)]
+    pub escalation_clock_at: DateTime<Utc>,
+}
+
+/// A contract affected by an incident
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct AffectedContract {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// Incident as returned by `GET /api/incidents/:id`, with its latest timeline entry
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IncidentDetail {
+    #[serde(flatten)]
+    pub incident: Incident,
+    pub latest_update: Option<IncidentUpdate>,
+    /// Per-channel delivery status of the incident notification
+    pub notifications: Vec<NotificationDelivery>,
+    /// Present once any postmortem field or action item has been recorded
+    pub postmortem: Option<Postmortem>,
+    /// Checks of restores performed during the incident, oldest first
+    pub recovery_validations: Vec<RecoveryValidation>,
+}
+
+/// Structured postmortem for an incident
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Postmortem {
+    pub root_cause: Option<String>,
+    pub detection_method: Option<String>,
+    pub contributing_factors: Vec<String>,
+    pub action_items: Vec<IncidentActionItem>,
+}
+
+impl Postmortem {
+    pub fn is_empty(&self) -> bool {
+        self.root_cause.is_none()
+            && self.detection_method.is_none()
+            && self.contributing_factors.is_empty()
+            && self.action_items.is_empty()
+    }
+}
+
+/// A follow-up task arising from an incident postmortem
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct IncidentActionItem {
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub description: String,
+    pub owner: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub done: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Action item as submitted in a postmortem; items with an `id` update the
+/// existing row, items without one are created
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ActionItemInput {
+    pub id: Option<Uuid>,
+    pub description: String,
+    pub owner: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub done: bool,
+}
+
+/// Replaces the postmortem; action items missing from the list are removed
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpdatePostmortemRequest {
+    pub root_cause: Option<String>,
+    pub detection_method: Option<String>,
+    #[serde(default)]
+    pub contributing_factors: Vec<String>,
+    #[serde(default)]
+    pub action_items: Vec<ActionItemInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpdateActionItemRequest {
+    pub description: Option<String>,
+    pub owner: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub done: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct ListActionItemsQuery {
+    /// Only items not yet done (`true`) or only completed items (`false`)
+    pub open: Option<bool>,
+}
+
+/// Incident types whose resolution should be backed by a passing recovery
+/// validation, compared after lowercasing and mapping `-` to `_`
+pub const RECOVERY_VALIDATED_INCIDENT_TYPES: &[&str] = &["backup", "data_loss"];
+
+pub fn requires_recovery_validation(incident_type: &str) -> bool {
+    let normalized = incident_type.trim().to_ascii_lowercase().replace('-', "_");
+    RECOVERY_VALIDATED_INCIDENT_TYPES.contains(&normalized.as_str())
+}
+
+/// One comparison between restored data and the source it was restored from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecoveryCheck {
+    /// Rows in a restored table, e.g. against a snapshot's `row_counts`
+    RowCount {
+        subject: String,
+        expected: i64,
+        actual: i64,
+    },
+    /// Digest of the restored data; hex digests compare case-insensitively
+    Checksum {
+        subject: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl RecoveryCheck {
+    pub fn subject(&self) -> &str {
+        match self {
+            RecoveryCheck::RowCount { subject, .. } | RecoveryCheck::Checksum { subject, .. } => {
+                subject
+            }
+        }
+    }
+
+    pub fn matches(&self) -> bool {
+        match self {
+            RecoveryCheck::RowCount {
+                expected, actual, ..
+            } => expected == actual,
+            RecoveryCheck::Checksum {
+                expected, actual, ..
+            } => expected.trim().eq_ignore_ascii_case(actual.trim()),
+        }
+    }
+}
+
+/// Record that a restore performed during an incident was verified
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct RecoveryValidation {
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    /// What was restored, e.g. "contracts from the 02:00 snapshot"
+    pub restored: String,
+    /// Registry snapshot the data came from, if any
+    pub snapshot_id: Option<Uuid>,
+    #[schema(value_type = Vec<RecoveryCheck>)]
+    pub checks: sqlx::types::Json<Vec<RecoveryCheck>>,
+    pub passed: bool,
+    pub notes: Option<String>,
+    pub performed_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateRecoveryValidationRequest {
+    pub restored: String,
+    pub snapshot_id: Option<Uuid>,
+    #[serde(default)]
+    pub checks: Vec<RecoveryCheck>,
+    /// Defaults to whether every check matched; required when there are no checks
+    pub passed: Option<bool>,
+    pub notes: Option<String>,
+}
+
+impl CreateRecoveryValidationRequest {
+    /// Whether the validation passed. A validation cannot pass while one of
+    /// its checks does not match, but may fail with all checks matching.
+    pub fn outcome(&self) -> Result<bool, String> {
+        if let Some(index) = self
+            .checks
+            .iter()
+            .position(|check| check.subject().trim().is_empty())
+        {
+            return Err(format!("checks[{}].subject must not be empty", index));
+        }
+
+        let mismatch = self.checks.iter().find(|check| !check.matches());
+        match (self.passed, mismatch) {
+            (None, _) if self.checks.is_empty() => {
+                Err("passed is required when no checks are given".to_string())
+            }
+            (None, mismatch) => Ok(mismatch.is_none()),
+            (Some(true), Some(check)) => Err(format!(
+                "passed cannot be true while the {} check does not match",
+                check.subject()
+            )),
+            (Some(passed), _) => Ok(passed),
+        }
+    }
+}
+
+/// Operator-configured destination for incident notifications
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct NotificationChannel {
+    pub id: Uuid,
+    pub name: String,
+    pub kind: String,   // 'webhook' | 'smtp'
+    pub target: String, // Webhook URL or recipient email address
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Delivery state of an incident notification on one channel
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct NotificationDelivery {
+    pub channel_id: Uuid,
+    pub channel_name: String,
+    pub kind: String,
+    pub status: String, // 'pending' | 'delivered' | 'failed'
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+/// A notification rendered for an incident, identical to what channels receive
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RenderedNotification {
+    pub subject: String,
+    pub body: String,
+    /// JSON body POSTed to webhook channels
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+}
+
+/// Dry-run output of `GET /api/incidents/:id/notifications/preview`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct NotificationPreview {
+    /// Whether the incident's severity triggers notification without an explicit request
+    pub auto_notify: bool,
+    /// Enabled channels a delivery would be queued for
+    pub channels: Vec<NotificationChannel>,
+    pub notification: RenderedNotification,
+}
+
+/// Status of the response effort at the time an update was posted
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "incident_update_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum IncidentUpdateStatus {
+    Investigating,
+    Identified,
+    Monitoring,
+    Resolved,
+}
+
+impl IncidentUpdateStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IncidentUpdateStatus::Investigating => "investigating",
+            IncidentUpdateStatus::Identified => "identified",
+            IncidentUpdateStatus::Monitoring => "monitoring",
+            IncidentUpdateStatus::Resolved => "resolved",
+        }
+    }
+}
+
+/// An immutable entry in an incident's timeline
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct IncidentUpdate {
+    pub id: Uuid,
+    pub incident_id: Uuid,
+    pub author: String,
+    pub message: String,
+    pub status: Option<IncidentUpdateStatus>,
+    /// True when posted after the incident was resolved
+    pub post_resolution: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Row of `incident_audit_log`, e.g. a severity change
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct IncidentAuditEntry {
+    pub action: String,
+    pub changed_by: String,
+    pub details: Option<serde_json::Value>,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct IncidentReportQuery {
+    /// Report format; only `markdown` is supported
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct IncidentFeedQuery {
+    /// Comma-separated extra entry kinds; only `publications` is supported
+    pub include: Option<String>,
+}
+
+impl IncidentFeedQuery {
+    /// Whether verified-contract announcements were requested.
+    pub fn includes_publications(&self) -> Result<bool, String> {
+        let mut publications = false;
+        for kind in self
+            .include
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+        {
+            match kind {
+                "publications" => publications = true,
+                other => {
+                    return Err(format!(
+                        "Unknown include value '{}'; supported: publications",
+                        other
+                    ))
+                }
+            }
+        }
+        Ok(publications)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateIncidentUpdateRequest {
+    pub message: String,
+    pub status: Option<IncidentUpdateStatus>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateIncidentRequest {
+    /// Single affected contract; kept for older clients, merged into `affected_contract_ids`
+    pub contract_id: Option<Uuid>,
+    #[serde(default)]
+    pub affected_contract_ids: Vec<Uuid>,
+    pub incident_type: String,
+    /// Defaults to `major` when omitted
+    #[serde(default)]
+    pub severity: IncidentSeverity,
+    pub description: String,
+    /// Defaults to the time of the request when omitted
+    pub start_time: Option<DateTime<Utc>>,
+    /// Notify users even when severity is below `major`
+    #[serde(default, alias = "notified_users")]
+    pub notify_users: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpdateIncidentRequest {
+    /// Severity changes are recorded in the incident's audit timeline
+    pub severity: Option<IncidentSeverity>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub rto_achieved: Option<DurationInput>,
+    pub rpo_achieved: Option<DurationInput>,
+    pub lessons_learned: Option<String>,
+    /// `true` queues notifications to every channel that hasn't delivered yet
+    #[serde(alias = "notified_users")]
+    pub notify_users: Option<bool>,
+    /// Replaces the affected contracts; the singular `contract_id` is merged in
+    pub affected_contract_ids: Option<Vec<Uuid>>,
+    pub contract_id: Option<Uuid>,
+}
+
+/// An achieved recovery objective, returned both as seconds and human-readable text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+pub struct RecoveryDuration {
+    pub seconds: i64,
+    /// e.g. "2h 30m"
+    #[schema(value_type = String)]
+    pub display: DisplayDuration,
+}
+
+impl RecoveryDuration {
+    pub fn from_seconds(seconds: i64) -> Self {
+        Self {
+            seconds,
+            display: DisplayDuration(seconds),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RecoveryDuration {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            seconds: i64,
+        }
+
+        Raw::deserialize(deserializer).map(|raw| Self::from_seconds(raw.seconds))
+    }
+}
+
+impl sqlx::Type<Postgres> for RecoveryDuration {
+    fn type_info() -> PgTypeInfo {
+        <i64 as sqlx::Type<Postgres>>::type_info()
+    }
+}
+
+impl<'r> sqlx::Decode<'r, Postgres> for RecoveryDuration {
+    fn decode(value: PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let seconds = <i64 as sqlx::Decode<'r, Postgres>>::decode(value)?;
+        Ok(Self::from_seconds(seconds))
+    }
+}
+
+/// Serializes a second count as a compact string such as "1d 2h" or "45s"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayDuration(i64);
+
+impl DisplayDuration {
+    pub fn new(seconds: i64) -> Self {
+        Self(seconds)
+    }
+}
+
+impl std::fmt::Display for DisplayDuration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0 == 0 {
+            return f.write_str("0s");
+        }
+
+        let units = [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+        let mut remaining = self.0;
+        let mut parts = Vec::new();
+        for (suffix, size) in units {
+            if remaining >= size {
+                parts.push(format!("{}{}", remaining / size, suffix));
+                remaining %= size;
+            }
+        }
+        f.write_str(&parts.join(" "))
+    }
+}
+
+impl Serialize for DisplayDuration {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Client-supplied RTO/RPO: integer seconds, an ISO-8601 duration ("PT2H30M"),
+/// or a shorthand such as "2h" / "90m"
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(untagged)]
+pub enum DurationInput {
+    Seconds(i64),
+    Text(String),
+}
+
+impl DurationInput {
+    /// Resolves the input to a validated number of seconds.
+    pub fn to_seconds(&self) -> Result<i64, String> {
+        let seconds = match self {
+            DurationInput::Seconds(seconds) => *seconds,
+            DurationInput::Text(text) => parse_duration_text(text)?,
+        };
+
+        if seconds < 0 {
+            return Err("duration must not be negative".to_string());
+        }
+        if seconds > MAX_RECOVERY_DURATION_SECS {
+            return Err(format!(
+                "duration of {} exceeds the maximum of {}",
+                DisplayDuration(seconds),
+                DisplayDuration(MAX_RECOVERY_DURATION_SECS)
+            ));
+        }
+        Ok(seconds)
+    }
+}
+
+fn parse_duration_text(text: &str) -> Result<i64, String> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return Err("duration must not be empty".to_string());
+    }
+    if let Ok(seconds) = trimmed.parse::<i64>() {
+        return Ok(seconds);
+    }
+
+    let upper = trimmed.to_ascii_uppercase();
+    match upper.strip_prefix('P') {
+        Some(rest) => parse_iso8601_duration(rest),
+        None => parse_unit_sequence(&upper, false),
+    }
+    .ok_or_else(|| format!("could not parse duration '{}'", trimmed))
+}
+
+/// Parses the part of an ISO-8601 duration after the leading `P`. Years and
+/// months are rejected because their length in seconds is ambiguous.
+fn parse_iso8601_duration(rest: &str) -> Option<i64> {
+    let (date_part, time_part) = match rest.split_once('T') {
+        Some((date, time)) if !time.is_empty() => (date, Some(time)),
+        Some(_) => return None,
+        None => (rest, None),
+    };
+    if date_part.is_empty() && time_part.is_none() {
+        return None;
+    }
+
+    let mut total = 0i64;
+    if !date_part.is_empty() {
+        total = total.checked_add(parse_unit_sequence(date_part, true)?)?;
+    }
+    if let Some(time) = time_part {
+        total = total.checked_add(parse_unit_sequence(time, false)?)?;
+    }
+    Some(total)
+}
+
+/// Parses runs of `<number><unit>`, e.g. "1H30M". Date designators are W/D;
+/// time designators are D/H/M/S (D is accepted for shorthand like "1d").
+fn parse_unit_sequence(input: &str, date: bool) -> Option<i64> {
+    let mut total = 0i64;
+    let mut digits = String::new();
+    let mut seen_unit = false;
+
+    for ch in input.chars().filter(|c| !c.is_whitespace()) {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let unit = match (ch, date) {
+            ('W', true) => 604_800,
+            ('D', _) => 86_400,
+            ('H', false) => 3_600,
+            ('M', false) => 60,
+            ('S', false) => 1,
+            _ => return None,
+        };
+        let value: i64 = digits.parse().ok()?;
+        total = total.checked_add(value.checked_mul(unit)?)?;
+        digits.clear();
+        seen_unit = true;
+    }
+
+    (seen_unit && digits.is_empty()).then_some(total)
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct ListIncidentsQuery {
+    /// Admin-only: include soft-deleted incidents
+    #[serde(default)]
+    pub include_deleted: bool,
+    /// Only incidents affecting this contract
+    pub contract_id: Option<Uuid>,
+    pub incident_type: Option<String>,
+    /// Only incidents with exactly this severity
+    pub severity: Option<IncidentSeverity>,
+    /// Only incidents at least this severe (e.g. `major` includes `critical`)
+    pub min_severity: Option<IncidentSeverity>,
+    /// Only incidents without an end_time
+    #[serde(default)]
+    pub open_only: bool,
+    /// Only incidents that started at or after this RFC 3339 timestamp
+    pub start_after: Option<DateTime<Utc>>,
+    /// Only incidents that started before this RFC 3339 timestamp
+    pub start_before: Option<DateTime<Utc>>,
+    /// Set to `false` for the legacy bare-array response (deprecated, removed next release)
+    #[serde(default = "default_envelope")]
+    pub envelope: bool,
+}
+
+fn default_envelope() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct IncidentMetricsQuery {
+    /// Look-back window such as `90d` or `12w` (default `90d`, max `730d`)
+    pub window: Option<String>,
+    /// Count incidents that started during planned maintenance
+    #[serde(default)]
+    pub include_maintenance: bool,
+    /// Leave out incidents brought in through `POST /api/incidents/import`
+    #[serde(default)]
+    pub exclude_imported: bool,
+}
+
+impl IncidentMetricsQuery {
+    pub const DEFAULT_WINDOW_DAYS: i64 = 90;
+    pub const MAX_WINDOW_DAYS: i64 = 730;
+
+    /// Parses `window` into a number of days.
+    pub fn window_days(&self) -> Result<i64, String> {
+        parse_window_days(self.window.as_deref(), Self::DEFAULT_WINDOW_DAYS)
+    }
+}
+
+/// Parses a look-back window such as `90d` or `12w` into a number of days,
+/// `default` when absent, at most [`IncidentMetricsQuery::MAX_WINDOW_DAYS`].
+pub fn parse_window_days(window: Option<&str>, default: i64) -> Result<i64, String> {
+    let Some(raw) = window.map(str::trim) else {
+        return Ok(default);
+    };
+
+    let (number, multiplier) = match raw.char_indices().last() {
+        Some((idx, 'd')) => (&raw[..idx], 1),
+        Some((idx, 'w')) => (&raw[..idx], 7),
+        _ => (raw, 1),
+    };
+    let days = number
+        .parse::<i64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| format!("invalid window '{}' (expected e.g. 90d or 12w)", raw))?;
+
+    if !(1..=IncidentMetricsQuery::MAX_WINDOW_DAYS).contains(&days) {
+        return Err(format!(
+            "window must be between 1d and {}d",
+            IncidentMetricsQuery::MAX_WINDOW_DAYS
+        ));
+    }
+    Ok(days)
+}
+
+/// Aggregate incident statistics for post-incident reviews
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IncidentMetrics {
+    pub window_days: i64,
+    pub window_start: DateTime<Utc>,
+    /// Incidents that started within the window
+    pub total_incidents: i64,
+    pub resolved_incidents: i64,
+    /// Still-open incidents; excluded from MTTR
+    pub open_incidents: i64,
+    pub mttr_mean_seconds: Option<f64>,
+    pub mttr_median_seconds: Option<f64>,
+    pub by_type: Vec<IncidentCount>,
+    pub by_severity: Vec<IncidentCount>,
+    pub rto_compliance: ObjectiveCompliance,
+    pub rpo_compliance: ObjectiveCompliance,
+    pub recovery_validation: RecoveryValidationCoverage,
+    /// Oldest unresolved incident, regardless of the window
+    pub longest_open: Option<LongestOpenIncident>,
+    /// Per-month breakdown, oldest month first
+    pub monthly: Vec<MonthlyIncidentMetrics>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct IncidentCount {
+    pub key: String,
+    pub count: i64,
+}
+
+/// Share of incidents with a recorded RTO/RPO that met the target in force
+/// when each was resolved
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ObjectiveCompliance {
+    /// Current registry-wide target; per-contract overrides may differ
+    pub target_seconds: i64,
+    pub target_source: ObjectiveSource,
+    /// Incidents with an achieved value recorded
+    pub measured: i64,
+    pub met: i64,
+    pub percentage: Option<f64>,
+    /// Mean of achieved minus target; negative when recovery beat the targets
+    pub mean_delta_seconds: Option<f64>,
+}
+
+impl ObjectiveCompliance {
+    pub fn new(
+        target_seconds: i64,
+        target_source: ObjectiveSource,
+        measured: i64,
+        met: i64,
+        mean_delta_seconds: Option<f64>,
+    ) -> Self {
+        let percentage = (measured > 0).then(|| met as f64 * 100.0 / measured as f64);
+        Self {
+            target_seconds,
+            target_source,
+            measured,
+            met,
+            percentage,
+            mean_delta_seconds,
+        }
+    }
+}
+
+/// Resolved backup and data-loss incidents (see
+/// [`RECOVERY_VALIDATED_INCIDENT_TYPES`]) with a passing recovery validation
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RecoveryValidationCoverage {
+    pub resolved: i64,
+    pub validated: i64,
+    /// `validated / resolved`, between 0 and 1
+    pub fraction: Option<f64>,
+}
+
+impl RecoveryValidationCoverage {
+    pub fn new(resolved: i64, validated: i64) -> Self {
+        Self {
+            resolved,
+            validated,
+            fraction: (resolved > 0).then(|| validated as f64 / resolved as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct LongestOpenIncident {
+    pub id: Uuid,
+    pub incident_type: String,
+    pub severity: IncidentSeverity,
+    pub start_time: DateTime<Utc>,
+    pub open_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct MonthlyIncidentMetrics {
+    /// `YYYY-MM`
+    pub month: String,
+    pub total: i64,
+    pub resolved: i64,
+    pub open: i64,
+    pub mttr_mean_seconds: Option<f64>,
+    pub mttr_median_seconds: Option<f64>,
+}
+
+/// Overall registry health shown on the public status page, ordered from
+/// best to worst
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OverallStatus {
+    Operational,
+    Degraded,
+    MajorOutage,
+}
+
+impl OverallStatus {
+    /// Derives the overall state from the severities of currently open
+    /// incidents. Informational incidents never degrade the status.
+    pub fn from_open_severities(severities: impl IntoIterator<Item = IncidentSeverity>) -> Self {
+        severities
+            .into_iter()
+            .map(|severity| match severity {
+                IncidentSeverity::Critical => OverallStatus::MajorOutage,
+                IncidentSeverity::Major | IncidentSeverity::Minor => OverallStatus::Degraded,
+                IncidentSeverity::Informational => OverallStatus::Operational,
+            })
+            .max()
+            .unwrap_or(OverallStatus::Operational)
+    }
+}
+
+/// An open incident with only the fields that are safe to publish
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct PublicIncident {
+    pub id: Uuid,
+    pub incident_type: String,
+    pub severity: IncidentSeverity,
+    pub description: String,
+    pub start_time: DateTime<Utc>,
+    /// Status from the most recent timeline update, if any
+    pub status: Option<IncidentUpdateStatus>,
+    pub updated_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub affected_contracts: Vec<AffectedContract>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UptimeSummary {
+    pub last_30_days: f64,
+    pub last_90_days: f64,
+}
+
+/// Response body for the public `GET /api/status` endpoint
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RegistryStatus {
+    pub status: OverallStatus,
+    pub open_incidents: Vec<PublicIncident>,
+    /// Upcoming and in-progress maintenance windows
+    pub maintenance: Vec<MaintenanceWindow>,
+    pub uptime: UptimeSummary,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Percentage of `[window_start, now]` not covered by any outage interval.
+/// Open outages (no end) run until `now`; overlapping outages are only
+/// counted once.
+pub fn uptime_percentage(
+    outages: &[(DateTime<Utc>, Option<DateTime<Utc>>)],
+    window_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> f64 {
+    let window_secs = (now - window_start).num_seconds();
+    if window_secs <= 0 {
+        return 100.0;
+    }
+
+    let mut clipped: Vec<(DateTime<Utc>, DateTime<Utc>)> = outages
+        .iter()
+        .map(|(start, end)| ((*start).max(window_start), end.unwrap_or(now).min(now)))
+        .filter(|(start, end)| start < end)
+        .collect();
+    clipped.sort();
+
+    let mut down_secs = 0;
+    let mut current: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+    for (start, end) in clipped {
+        current = match current {
+            Some((cur_start, cur_end)) if start <= cur_end => Some((cur_start, cur_end.max(end))),
+            Some((cur_start, cur_end)) => {
+                down_secs += (cur_end - cur_start).num_seconds();
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    if let Some((cur_start, cur_end)) = current {
+        down_secs += (cur_end - cur_start).num_seconds();
+    }
+
+    (window_secs - down_secs) as f64 * 100.0 / window_secs as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seconds(text: &str) -> Result<i64, String> {
+        DurationInput::Text(text.to_string()).to_seconds()
+    }
+
+    #[test]
+    fn parses_all_supported_duration_formats() {
+        assert_eq!(DurationInput::Seconds(7200).to_seconds(), Ok(7200));
+        assert_eq!(seconds("7200"), Ok(7200));
+        assert_eq!(seconds("2h"), Ok(7200));
+        assert_eq!(seconds("120m"), Ok(7200));
+        assert_eq!(seconds("PT2H"), Ok(7200));
+        assert_eq!(seconds("1h 30m"), Ok(5400));
+        assert_eq!(seconds("pt1h30m15s"), Ok(5415));
+        assert_eq!(seconds("P1DT2H"), Ok(93_600));
+        assert_eq!(seconds("P1W"), Ok(604_800));
+    }
+
+    #[test]
+    fn rejects_unparseable_or_out_of_range_durations() {
+        assert!(seconds("").is_err());
+        assert!(seconds("soon").is_err());
+        assert!(seconds("P").is_err());
+        assert!(seconds("PT").is_err());
+        assert!(seconds("P1M").is_err());
+        assert!(seconds("2x").is_err());
+        assert!(seconds("30").is_ok());
+        assert!(seconds("-5").is_err());
+        assert!(DurationInput::Seconds(-1).to_seconds().is_err());
+        assert!(seconds("P365D").is_err());
+    }
+
+    #[test]
+    fn metrics_window_parses_days_and_weeks() {
+        let window = |w: Option<&str>| {
+            IncidentMetricsQuery {
+                window: w.map(str::to_string),
+                include_maintenance: false,
+                exclude_imported: false,
+            }
+            .window_days()
+        };
+        assert_eq!(window(None), Ok(90));
+        assert_eq!(window(Some("30d")), Ok(30));
+        assert_eq!(window(Some("12w")), Ok(84));
+        assert_eq!(window(Some("14")), Ok(14));
+        assert!(window(Some("0d")).is_err());
+        assert!(window(Some("1000d")).is_err());
+        assert!(window(Some("3m")).is_err());
+    }
+
+    #[test]
+    fn formats_durations_for_display() {
+        assert_eq!(DisplayDuration(0).to_string(), "0s");
+        assert_eq!(DisplayDuration(45).to_string(), "45s");
+        assert_eq!(DisplayDuration(5400).to_string(), "1h 30m");
+        assert_eq!(DisplayDuration(93_600).to_string(), "1d 2h");
+
+        let json = serde_json::to_value(RecoveryDuration::from_seconds(7200)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({ "seconds": 7200, "display": "2h" })
+        );
+    }
+
+    #[test]
+    fn overall_status_follows_worst_open_severity() {
+        use IncidentSeverity::*;
+        assert_eq!(
+            OverallStatus::from_open_severities([]),
+            OverallStatus::Operational
+        );
+        assert_eq!(
+            OverallStatus::from_open_severities([Informational]),
+            OverallStatus::Operational
+        );
+        assert_eq!(
+            OverallStatus::from_open_severities([Informational, Minor]),
+            OverallStatus::Degraded
+        );
+        assert_eq!(
+            OverallStatus::from_open_severities([Major, Critical, Minor]),
+            OverallStatus::MajorOutage
+        );
+    }
+
+    #[test]
+    fn uptime_merges_overlapping_and_clips_to_window() {
+        let now = Utc::now();
+        let start = now - chrono::Duration::days(10);
+        let hours_ago = |h: i64| now - chrono::Duration::hours(h);
+
+        assert_eq!(uptime_percentage(&[], start, now), 100.0);
+
+        // Two overlapping outages covering 24h in total, plus one that began
+        // before the window and ended 12h into it.
+        let outages = [
+            (hours_ago(48), Some(hours_ago(36))),
+            (hours_ago(40), Some(hours_ago(24))),
+            (
+                start - chrono::Duration::days(1),
+                Some(start + chrono::Duration::hours(12)),
+            ),
+        ];
+        let expected = (240.0 - 36.0) * 100.0 / 240.0;
+        assert!((uptime_percentage(&outages, start, now) - expected).abs() < 1e-9);
+
+        // An open outage runs until now.
+        let open = [(hours_ago(24), None)];
+        let expected = (240.0 - 24.0) * 100.0 / 240.0;
+        assert!((uptime_percentage(&open, start, now) - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn feed_include_accepts_only_publications() {
+        let query = |include: Option<&str>| IncidentFeedQuery {
+            include: include.map(str::to_string),
+        };
+        assert_eq!(query(None).includes_publications(), Ok(false));
+        assert_eq!(query(Some("")).includes_publications(), Ok(false));
+        assert_eq!(
+            query(Some(" publications ")).includes_publications(),
+            Ok(true)
+        );
+        assert!(query(Some("publications,drafts"))
+            .includes_publications()
+            .is_err());
+    }
+
+    #[test]
+    fn recovery_validation_types_are_normalized() {
+        assert!(requires_recovery_validation("data_loss"));
+        assert!(requires_recovery_validation(" Data-Loss "));
+        assert!(requires_recovery_validation("backup"));
+        assert!(!requires_recovery_validation("outage"));
+        assert!(!requires_recovery_validation("dr_drill"));
+    }
+
+    #[test]
+    fn recovery_validation_outcome_follows_checks() {
+        let request =
+            |checks: Vec<RecoveryCheck>, passed: Option<bool>| CreateRecoveryValidationRequest {
+                restored: "contracts".to_string(),
+                snapshot_id: None,
+                checks,
+                passed,
+                notes: None,
+            };
+        let rows = |actual: i64| RecoveryCheck::RowCount {
+            subject: "contracts".to_string(),
+            expected: 120,
+            actual,
+        };
+        let checksum = RecoveryCheck::Checksum {
+            subject: "archive".to_string(),
+            expected: "ABC123".to_string(),
+            actual: "abc123".to_string(),
+        };
+
+        assert_eq!(
+            request(vec![rows(120), checksum.clone()], None).outcome(),
+            Ok(true)
+        );
+        assert_eq!(request(vec![rows(119)], None).outcome(), Ok(false));
+        assert_eq!(request(vec![rows(120)], Some(false)).outcome(), Ok(false));
+        assert_eq!(request(Vec::new(), Some(true)).outcome(), Ok(true));
+
+        let err = request(vec![checksum, rows(119)], Some(true))
+            .outcome()
+            .unwrap_err();
+        assert!(err.contains("contracts check does not match"), "{}", err);
+        assert!(request(Vec::new(), None).outcome().is_err());
+    }
+
+    #[test]
+    fn recovery_checks_are_tagged_by_kind() {
+        let check: RecoveryCheck = serde_json::from_value(serde_json::json!({
+            "kind": "row_count", "subject": "reviews", "expected": 3, "actual": 3
+        }))
+        .unwrap();
+        assert!(check.matches());
+        assert_eq!(check.subject(), "reviews");
+    }
+}
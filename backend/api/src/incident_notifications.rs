@@ -0,0 +1,651 @@
+//! Incident notification dispatcher.
+//!
+//! Deliveries are queued in `incident_notification_deliveries` (one row per
+//! enabled channel) and sent by a background task with exponential backoff.
+//! `incidents.notified_users` is set once any channel accepts a delivery.
+//! Webhooks go through the shared [`OutboundClient`] and a batch is sent
+//! concurrently, so one slow or dead endpoint cannot hold up the others.
+//! Contract subscriptions are delivered with the same senders and limits, see
+//! [`crate::subscription_notifications`].
+
+use std::time::Duration;
+
+use futures_util::stream::{self, StreamExt};
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::incident_models::{Incident, IncidentSeverity, RenderedNotification};
+use crate::outbound_http::OutboundClient;
+use crate::shutdown::Shutdown;
+
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_secs(15);
+pub(crate) const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const BATCH_SIZE: i64 = 20;
+pub(crate) const DELIVERY_CONCURRENCY: usize = 8;
+pub(crate) const MAX_ATTEMPTS: i32 = 5;
+pub(crate) const BASE_BACKOFF_SECS: i64 = 30;
+
+/// Incidents at or above this severity notify users without being asked to.
+pub fn should_auto_notify(incident: &Incident) -> bool {
+    incident.severity <= IncidentSeverity::Major
+}
+
+/// Queues a delivery to every enabled channel. Channels that previously gave up
+/// are re-queued; pending or delivered rows are left alone.
+pub async fn enqueue(
+    tx: &mut Transaction<'_, Postgres>,
+    incident_id: Uuid,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO incident_notification_deliveries (incident_id, channel_id)
+        SELECT $1, id FROM notification_channels WHERE enabled
+        ON CONFLICT (incident_id, channel_id) DO UPDATE
+            SET status = 'pending', attempts = 0, last_error = NULL, next_attempt_at = NOW()
+            WHERE incident_notification_deliveries.status = 'failed'
+        "#,
+    )
+    .bind(incident_id)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Queues a delivery to each of `channel_ids` that is enabled, sending it
+/// again even where an earlier one was delivered. Returns the channels'
+/// names.
+pub async fn enqueue_channels(
+    tx: &mut Transaction<'_, Postgres>,
+    incident_id: Uuid,
+    channel_ids: &[Uuid],
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        WITH queued AS (
+            INSERT INTO incident_notification_deliveries (incident_id, channel_id)
+            SELECT $1, id FROM notification_channels WHERE enabled AND id = ANY($2)
+            ON CONFLICT (incident_id, channel_id) DO UPDATE
+                SET status = 'pending', attempts = 0, last_error = NULL,
+                    next_attempt_at = NOW(), delivered_at = NULL
+            RETURNING channel_id
+        )
+        SELECT c.name FROM queued q JOIN notification_channels c ON c.id = q.channel_id
+        ORDER BY c.name
+        "#,
+    )
+    .bind(incident_id)
+    .bind(channel_ids)
+    .fetch_all(&mut **tx)
+    .await
+}
+
+/// Renders the subject, plain-text body and webhook payload for an incident.
+pub fn render(incident: &Incident) -> RenderedNotification {
+    let severity = incident.severity.as_str();
+    let state = if incident.end_time.is_some() {
+        "resolved"
+    } else {
+        "ongoing"
+    };
+    let scope = incident
+        .contract_id
+        .map(|id| format!("contract {}", id))
+        .unwrap_or_else(|| "the registry".to_string());
+    let escalation = &incident.escalation;
+    let escalated = incident.end_time.is_none()
+        && escalation.acknowledged_at.is_none()
+        && escalation.escalation_step > 0;
+
+    let subject = format!(
+        "[{}] {} incident affecting {} ({}{})",
+        severity.to_uppercase(),
+        incident.incident_type,
+        scope,
+        state,
+        if escalated {
+            format!(
+                ", unacknowledged, escalation step {}",
+                escalation.escalation_step
+            )
+        } else {
+            String::new()
+        }
+    )
+    .replace(['\r', '\n'], " ");
+
+    let mut body = format!(
+        "{}\n\nSeverity: {}\nStarted: {}\n",
+        incident.description,
+        severity,
+        incident.start_time.to_rfc3339()
+    );
+    if let Some(end) = incident.end_time {
+        body.push_str(&format!("Resolved: {}\n", end.to_rfc3339()));
+    }
+    body.push_str(&format!("Incident ID: {}\n", incident.id));
+    if escalated {
+        body.push_str(&format!(
+            "\nNobody has acknowledged this incident yet. Acknowledge it with POST /api/incidents/{}/ack to stop escalating.\n",
+            incident.id
+        ));
+    }
+
+    let payload = serde_json::json!({
+        "event": "incident.notification",
+        "event_version": crate::webhook_events::CURRENT_PAYLOAD_VERSION,
+        "incident_id": incident.id,
+        "contract_id": incident.contract_id,
+        "incident_type": incident.incident_type,
+        "severity": severity,
+        "state": state,
+        "description": incident.description,
+        "start_time": incident.start_time,
+        "end_time": incident.end_time,
+        "acknowledged": escalation.acknowledged_at.is_some(),
+        "escalation_step": escalation.escalation_step,
+        "subject": subject,
+    });
+
+    RenderedNotification {
+        subject,
+        body,
+        payload,
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DueDelivery {
+    id: Uuid,
+    incident_id: Uuid,
+    attempts: i32,
+    kind: String,
+    target: String,
+}
+
+/// Spawn the background task that sends queued incident notifications.
+/// On shutdown the delivery in progress is recorded and the rest of the
+/// batch is handed back to the queue.
+pub fn spawn_notification_dispatcher(pool: PgPool, client: OutboundClient, shutdown: &Shutdown) {
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            if let Err(err) = dispatch_due(&pool, &client, &token).await {
+                tracing::error!(error = ?err, "incident notifications: dispatch run failed");
+            }
+        }
+        tracing::info!("incident notifications: dispatcher stopped");
+    });
+}
+
+async fn dispatch_due(
+    pool: &PgPool,
+    client: &OutboundClient,
+    shutdown: &CancellationToken,
+) -> Result<(), sqlx::Error> {
+    // Lease due rows for a few minutes so concurrent API replicas don't double-send.
+    let due: Vec<DueDelivery> = sqlx::query_as(
+        r#"
+        UPDATE incident_notification_deliveries d
+        SET next_attempt_at = NOW() + INTERVAL '5 minutes'
+        FROM notification_channels c
+        WHERE d.channel_id = c.id
+          AND d.id IN (
+              SELECT id FROM incident_notification_deliveries
+              WHERE status = 'pending' AND next_attempt_at <= NOW()
+              ORDER BY next_attempt_at
+              LIMIT $1
+              FOR UPDATE SKIP LOCKED
+          )
+        RETURNING d.id, d.incident_id, d.attempts, c.kind, c.target
+        "#,
+    )
+    .bind(BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    // Built up front: a closure inside the stream would make the spawned
+    // dispatcher fail the `Send` check on higher-ranked lifetimes
+    let sends: Vec<_> = due
+        .iter()
+        .map(|delivery| deliver(pool, client, shutdown, delivery))
+        .collect();
+    let outcomes: Vec<Result<Option<Uuid>, sqlx::Error>> = stream::iter(sends)
+        .buffer_unordered(DELIVERY_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut unsent = Vec::new();
+    let mut first_error = None;
+    for outcome in outcomes {
+        match outcome {
+            Ok(Some(id)) => unsent.push(id),
+            Ok(None) => {}
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    if !unsent.is_empty() {
+        release_leases(pool, &unsent).await?;
+        tracing::info!(
+            released = unsent.len(),
+            "incident notifications: returned unsent deliveries to the queue"
+        );
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Sends one delivery and records the attempt. Returns the delivery's id
+/// instead when shutdown began before it was sent.
+async fn deliver(
+    pool: &PgPool,
+    client: &OutboundClient,
+    shutdown: &CancellationToken,
+    delivery: &DueDelivery,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    if shutdown.is_cancelled() {
+        return Ok(Some(delivery.id));
+    }
+
+    // A destination that keeps failing is skipped without using up attempts
+    if delivery.kind == "webhook" {
+        if let Some(retry_after) = client.blocked_for(&delivery.target) {
+            defer(pool, delivery, retry_after).await?;
+            return Ok(None);
+        }
+    }
+
+    let incident: Option<Incident> =
+        sqlx::query_as("SELECT * FROM incidents WHERE id = $1 AND deleted_at IS NULL")
+            .bind(delivery.incident_id)
+            .fetch_optional(pool)
+            .await?;
+
+    let result = match incident {
+        Some(incident) => {
+            let rendered = render(&incident);
+            match delivery.kind.as_str() {
+                "webhook" => send_webhook(client, &delivery.target, &rendered).await,
+                "smtp" => send_email(&delivery.target, &rendered).await,
+                other => Err(format!("unsupported channel kind '{}'", other)),
+            }
+        }
+        None => Err("incident was deleted before delivery".to_string()),
+    };
+
+    record_attempt(pool, delivery, result).await?;
+    Ok(None)
+}
+
+/// Pushes a delivery back until its destination's circuit closes again.
+async fn defer(
+    pool: &PgPool,
+    delivery: &DueDelivery,
+    retry_after: Duration,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE incident_notification_deliveries
+         SET next_attempt_at = NOW() + make_interval(secs => $2)
+         WHERE id = $1 AND status = 'pending'",
+    )
+    .bind(delivery.id)
+    .bind(retry_after.as_secs_f64())
+    .execute(pool)
+    .await?;
+
+    tracing::info!(
+        incident_id = %delivery.incident_id,
+        delivery_id = %delivery.id,
+        retry_in_secs = retry_after.as_secs(),
+        "incident notification deferred, webhook destination is failing"
+    );
+    Ok(())
+}
+
+/// Makes leased deliveries due again so another replica picks them up now
+/// instead of after the lease expires.
+async fn release_leases(pool: &PgPool, ids: &[Uuid]) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE incident_notification_deliveries SET next_attempt_at = NOW()
+         WHERE id = ANY($1) AND status = 'pending'",
+    )
+    .bind(ids)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn record_attempt(
+    pool: &PgPool,
+    delivery: &DueDelivery,
+    result: Result<(), String>,
+) -> Result<(), sqlx::Error> {
+    match result {
+        Ok(()) => {
+            let mut tx = pool.begin().await?;
+            sqlx::query(
+                "UPDATE incident_notification_deliveries
+                 SET status = 'delivered', attempts = attempts + 1, last_error = NULL,
+                     delivered_at = NOW()
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query("UPDATE incidents SET notified_users = TRUE WHERE id = $1")
+                .bind(delivery.incident_id)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+
+            tracing::info!(
+                incident_id = %delivery.incident_id,
+                delivery_id = %delivery.id,
+                kind = %delivery.kind,
+                "incident notification delivered"
+            );
+        }
+        Err(reason) => {
+            let attempts = delivery.attempts + 1;
+            let exhausted = attempts >= MAX_ATTEMPTS;
+            let backoff_secs = BASE_BACKOFF_SECS << delivery.attempts.clamp(0, 10);
+
+            sqlx::query(
+                "UPDATE incident_notification_deliveries
+                 SET attempts = $2, last_error = $3,
+                     status = CASE WHEN $4 THEN 'failed' ELSE 'pending' END,
+                     next_attempt_at = NOW() + make_interval(secs => $5)
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .bind(attempts)
+            .bind(&reason)
+            .bind(exhausted)
+            .bind(backoff_secs as f64)
+            .execute(pool)
+            .await?;
+
+            tracing::warn!(
+                incident_id = %delivery.incident_id,
+                delivery_id = %delivery.id,
+                kind = %delivery.kind,
+                attempts,
+                exhausted,
+                error = %reason,
+                "incident notification delivery failed"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn send_webhook(
+    client: &OutboundClient,
+    url: &str,
+    notification: &RenderedNotification,
+) -> Result<(), String> {
+    send_webhook_with_status(client, url, notification).await.1
+}
+
+/// Like [`send_webhook`], also returning the HTTP status the destination
+/// answered with, if it answered at all. Hosts that now resolve to a
+/// non-public address are refused, see [`OutboundClient::send_webhook`].
+pub(crate) async fn send_webhook_with_status(
+    client: &OutboundClient,
+    url: &str,
+    notification: &RenderedNotification,
+) -> (Option<u16>, Result<(), String>) {
+    let response = match client
+        .send_webhook(
+            client
+                .post(url)
+                .timeout(SEND_TIMEOUT)
+                .json(&notification.payload),
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => return (None, Err(format!("webhook request failed: {}", e))),
+    };
+
+    let status = response.status();
+    let result = if status.is_success() {
+        Ok(())
+    } else {
+        Err(format!("webhook returned {}", status))
+    };
+    (Some(status.as_u16()), result)
+}
+
+/// Sends a plain-text email through the relay at `SMTP_HOST`/`SMTP_PORT`.
+///
+/// Only unauthenticated relaying is supported, which matches the usual setup
+/// of a local MTA or sidecar that handles TLS and credentials itself.
+pub(crate) async fn send_email(
+    to: &str,
+    notification: &RenderedNotification,
+) -> Result<(), String> {
+    let host = std::env::var("SMTP_HOST").map_err(|_| "SMTP_HOST is not configured".to_string())?;
+    let port = std::env::var("SMTP_PORT")
+        .ok()
+        .and_then(|p| p.parse::<u16>().ok())
+        .unwrap_or(25);
+    let from = std::env::var("SMTP_FROM")
+        .unwrap_or_else(|_| "incidents@soroban-registry.local".to_string());
+
+    tokio::time::timeout(
+        SEND_TIMEOUT,
+        smtp_send(&host, port, &from, to, notification),
+    )
+    .await
+    .map_err(|_| "SMTP delivery timed out".to_string())?
+}
+
+async fn smtp_send(
+    host: &str,
+    port: u16,
+    from: &str,
+    to: &str,
+    notification: &RenderedNotification,
+) -> Result<(), String> {
+    let stream = TcpStream::connect((host, port))
+        .await
+        .map_err(|e| format!("SMTP connect to {}:{} failed: {}", host, port, e))?;
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+
+    expect_reply(&mut reader, 220).await?;
+
+    let message = format!(
+        "From: <{}>\r\nTo: <{}>\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n.\r\n",
+        from,
+        to,
+        notification.subject,
+        dot_stuff(&notification.body)
+    );
+    let exchange = [
+        ("EHLO soroban-registry\r\n".to_string(), 250),
+        (format!("MAIL FROM:<{}>\r\n", from), 250),
+        (format!("RCPT TO:<{}>\r\n", to), 250),
+        ("DATA\r\n".to_string(), 354),
+        (message, 250),
+    ];
+
+    for (command, expected) in exchange {
+        writer
+            .write_all(command.as_bytes())
+            .await
+            .map_err(|e| format!("SMTP write failed: {}", e))?;
+        expect_reply(&mut reader, expected).await?;
+    }
+
+    // The message is already accepted; a failed QUIT doesn't matter.
+    let _ = writer.write_all(b"QUIT\r\n").await;
+    Ok(())
+}
+
+/// Reads one (possibly multi-line) SMTP reply and checks its status code.
+async fn expect_reply<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    expected: u16,
+) -> Result<(), String> {
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("SMTP read failed: {}", e))?;
+        if read == 0 {
+            return Err("SMTP server closed the connection".to_string());
+        }
+
+        let code: u16 = line
+            .get(..3)
+            .and_then(|c| c.parse().ok())
+            .ok_or_else(|| format!("malformed SMTP reply: {}", line.trim_end()))?;
+
+        // Continuation lines look like "250-SIZE"; the final line uses a space.
+        if line.as_bytes().get(3) == Some(&b'-') {
+            continue;
+        }
+
+        return if code == expected {
+            Ok(())
+        } else {
+            Err(format!("SMTP server replied: {}", line.trim_end()))
+        };
+    }
+}
+
+/// Normalizes line endings to CRLF and escapes lines starting with '.' (RFC 5321 §4.5.2).
+fn dot_stuff(body: &str) -> String {
+    body.lines()
+        .map(|line| {
+            if line.starts_with('.') {
+                format!(".{}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\r\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn incident(severity: IncidentSeverity) -> Incident {
+        Incident {
+            id: Uuid::new_v4(),
+            contract_id: None,
+            incident_type: "outage".to_string(),
+            severity,
+            description: "API unreachable".to_string(),
+            start_time: Utc::now(),
+            end_time: None,
+            rto_achieved: None,
+            rpo_achieved: None,
+            lessons_learned: None,
+            notified_users: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            maintenance_window_id: None,
+            source: "api".to_string(),
+            external_id: None,
+            objectives: Default::default(),
+            escalation: Default::default(),
+            affected_contracts: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn only_major_and_above_auto_notify() {
+        assert!(should_auto_notify(&incident(IncidentSeverity::Critical)));
+        assert!(should_auto_notify(&incident(IncidentSeverity::Major)));
+        assert!(!should_auto_notify(&incident(IncidentSeverity::Minor)));
+        assert!(!should_auto_notify(&incident(
+            IncidentSeverity::Informational
+        )));
+    }
+
+    #[test]
+    fn render_includes_severity_and_scope() {
+        let rendered = render(&incident(IncidentSeverity::Critical));
+        assert_eq!(
+            rendered.subject,
+            "[CRITICAL] outage incident affecting the registry (ongoing)"
+        );
+        assert!(rendered.body.starts_with("API unreachable"));
+        assert_eq!(rendered.payload["severity"], "critical");
+        assert_eq!(rendered.payload["state"], "ongoing");
+    }
+
+    #[test]
+    fn payload_matches_the_event_catalog() {
+        use crate::webhook_events::{event_type, schema_fields, CURRENT_PAYLOAD_VERSION};
+
+        let rendered = render(&incident(IncidentSeverity::Major));
+        let schema = event_type("incident.notification", CURRENT_PAYLOAD_VERSION)
+            .unwrap()
+            .schema(CURRENT_PAYLOAD_VERSION);
+        let mut fields: Vec<String> = rendered
+            .payload
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        fields.sort();
+        assert_eq!(fields, schema_fields(&schema));
+        assert_eq!(rendered.payload["event_version"], CURRENT_PAYLOAD_VERSION);
+    }
+
+    #[test]
+    fn render_marks_unacknowledged_escalations() {
+        let mut incident = incident(IncidentSeverity::Critical);
+        incident.escalation.escalation_step = 2;
+        let rendered = render(&incident);
+        assert!(rendered
+            .subject
+            .ends_with("(ongoing, unacknowledged, escalation step 2)"));
+        assert!(rendered.body.contains("/ack"));
+        assert_eq!(rendered.payload["escalation_step"], 2);
+
+        incident.escalation.acknowledged_at = Some(Utc::now());
+        let rendered = render(&incident);
+        assert!(rendered.subject.ends_with("(ongoing)"));
+        assert_eq!(rendered.payload["acknowledged"], true);
+    }
+
+    #[test]
+    fn dot_stuffing_escapes_leading_dots() {
+        assert_eq!(dot_stuff("a\n.b\nc"), "a\r\n..b\r\nc");
+    }
+
+    #[tokio::test]
+    async fn expect_reply_handles_multiline_responses() {
+        let mut ok = BufReader::new(&b"250-smtp.local\r\n250-SIZE 1000\r\n250 OK\r\n"[..]);
+        assert!(expect_reply(&mut ok, 250).await.is_ok());
+
+        let mut rejected = BufReader::new(&b"550 mailbox unavailable\r\n"[..]);
+        let err = expect_reply(&mut rejected, 250).await.unwrap_err();
+        assert!(err.contains("550"));
+    }
+}
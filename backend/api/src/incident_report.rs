@@ -0,0 +1,621 @@
+//! Markdown rendering of incident reports for pasting into wikis and postmortems.
+//!
+//! All user-provided text goes through [`escape_markdown`] / [`escape_cell`] so
+//! that descriptions and update messages can't inject headings, links or break
+//! the tables.
+
+use std::fmt::Write;
+
+use chrono::{DateTime, Utc};
+
+use crate::incident_models::{
+    DisplayDuration, Incident, IncidentAuditEntry, IncidentUpdate, IncidentUpdateStatus,
+    Postmortem, RecoveryCheck, RecoveryDuration, RecoveryValidation,
+};
+
+/// Everything needed to render a report for one incident.
+pub struct IncidentReport<'a> {
+    pub incident: &'a Incident,
+    pub updates: &'a [IncidentUpdate],
+    pub status_changes: &'a [IncidentAuditEntry],
+    pub postmortem: &'a Postmortem,
+    pub recovery_validations: &'a [RecoveryValidation],
+    pub generated_at: DateTime<Utc>,
+}
+
+enum TimelineEvent<'a> {
+    Opened,
+    Update(&'a IncidentUpdate),
+    StatusChange(&'a IncidentAuditEntry),
+    Resolved,
+}
+
+pub fn render_markdown(report: &IncidentReport<'_>) -> String {
+    let incident = report.incident;
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        "# Incident Report: {}\n",
+        escape_markdown(&incident.incident_type)
+    );
+
+    if incident.end_time.is_none() {
+        let _ = writeln!(
+            out,
+            "> **INCIDENT STILL OPEN** - this report reflects the state at {} and will change as the incident progresses.\n",
+            format_timestamp(report.generated_at)
+        );
+    }
+
+    let _ = writeln!(out, "## Summary\n");
+    let _ = writeln!(out, "{}\n", escape_markdown(&incident.description));
+    let _ = writeln!(out, "| Field | Value |");
+    let _ = writeln!(out, "| --- | --- |");
+    let _ = writeln!(out, "| Incident ID | `{}` |", incident.id);
+    let _ = writeln!(
+        out,
+        "| Severity | {} |",
+        capitalize(incident.severity.as_str())
+    );
+    let _ = writeln!(out, "| Type | {} |", escape_cell(&incident.incident_type));
+    let _ = writeln!(
+        out,
+        "| Started | {} |",
+        format_timestamp(incident.start_time)
+    );
+    match incident.end_time {
+        Some(end) => {
+            let _ = writeln!(out, "| Resolved | {} |", format_timestamp(end));
+            let duration = (end - incident.start_time).num_seconds().max(0);
+            let _ = writeln!(out, "| Duration | {} |", DisplayDuration::new(duration));
+        }
+        None => {
+            let open_for = (report.generated_at - incident.start_time)
+                .num_seconds()
+                .max(0);
+            let _ = writeln!(out, "| Resolved | _Unresolved_ |");
+            let _ = writeln!(
+                out,
+                "| Duration | {} so far |",
+                DisplayDuration::new(open_for)
+            );
+        }
+    }
+    let _ = writeln!(
+        out,
+        "| Users notified | {} |\n",
+        if incident.notified_users { "Yes" } else { "No" }
+    );
+
+    let _ = writeln!(out, "## Affected Contracts\n");
+    if incident.affected_contracts.is_empty() {
+        let _ = writeln!(out, "_Registry-wide (no specific contracts)_\n");
+    } else {
+        for contract in &incident.affected_contracts {
+            let _ = writeln!(
+                out,
+                "- {} (`{}`)",
+                escape_markdown(&contract.name),
+                contract.id
+            );
+        }
+        out.push('\n');
+    }
+
+    let _ = writeln!(out, "## Timeline\n");
+    for (at, event) in timeline(report) {
+        let _ = writeln!(out, "- **{}** - {}", format_timestamp(at), describe(&event));
+    }
+    out.push('\n');
+
+    let _ = writeln!(out, "## Recovery Objectives\n");
+    let _ = writeln!(out, "| Objective | Achieved | Target | Met | Delta |");
+    let _ = writeln!(out, "| --- | --- | --- | --- | --- |");
+    let objectives = &incident.objectives;
+    for (label, achieved, target, met, delta) in [
+        (
+            "RTO",
+            incident.rto_achieved,
+            objectives.rto_target_seconds,
+            objectives.rto_met,
+            objectives.rto_delta_seconds,
+        ),
+        (
+            "RPO",
+            incident.rpo_achieved,
+            objectives.rpo_target_seconds,
+            objectives.rpo_met,
+            objectives.rpo_delta_seconds,
+        ),
+    ] {
+        let _ = writeln!(
+            out,
+            "{}",
+            objective_row(label, achieved, target, met, delta)
+        );
+    }
+    out.push('\n');
+
+    if !report.recovery_validations.is_empty() {
+        write_recovery_validations(&mut out, report.recovery_validations);
+    }
+
+    if !report.postmortem.is_empty() {
+        write_postmortem(&mut out, report.postmortem);
+    }
+
+    let _ = writeln!(out, "## Lessons Learned\n");
+    match incident
+        .lessons_learned
+        .as_deref()
+        .filter(|l| !l.trim().is_empty())
+    {
+        Some(lessons) => {
+            let _ = writeln!(out, "{}\n", escape_markdown(lessons));
+        }
+        None => {
+            let _ = writeln!(out, "_None recorded._\n");
+        }
+    }
+
+    let _ = writeln!(out, "---\n");
+    let _ = writeln!(
+        out,
+        "_Generated {} by Soroban Registry_",
+        format_timestamp(report.generated_at)
+    );
+
+    out
+}
+
+/// Merges updates and audit-log status changes into one chronological list,
+/// bracketed by the open and resolve events.
+fn timeline<'a>(report: &IncidentReport<'a>) -> Vec<(DateTime<Utc>, TimelineEvent<'a>)> {
+    let mut events: Vec<(DateTime<Utc>, TimelineEvent<'a>)> =
+        vec![(report.incident.start_time, TimelineEvent::Opened)];
+    events.extend(
+        report
+            .updates
+            .iter()
+            .map(|u| (u.created_at, TimelineEvent::Update(u))),
+    );
+    events.extend(
+        report
+            .status_changes
+            .iter()
+            .map(|c| (c.timestamp, TimelineEvent::StatusChange(c))),
+    );
+    if let Some(end) = report.incident.end_time {
+        events.push((end, TimelineEvent::Resolved));
+    }
+
+    // Stable sort keeps "opened" ahead of anything posted in the same second.
+    events.sort_by_key(|(at, _)| *at);
+    events
+}
+
+fn describe(event: &TimelineEvent<'_>) -> String {
+    match event {
+        TimelineEvent::Opened => "Incident opened".to_string(),
+        TimelineEvent::Resolved => "Incident resolved".to_string(),
+        TimelineEvent::Update(update) => {
+            let status = update
+                .status
+                .map(|s| format!("[{}] ", update_status_label(s)))
+                .unwrap_or_default();
+            let post_resolution = if update.post_resolution {
+                " _(post-resolution)_"
+            } else {
+                ""
+            };
+            // Indent continuation lines so multi-line messages stay inside the list item.
+            let message = escape_markdown(&update.message).replace('\n', "\n  ");
+            format!(
+                "{}{}: {}{}",
+                status,
+                escape_markdown(&update.author),
+                message,
+                post_resolution
+            )
+        }
+        TimelineEvent::StatusChange(change) => match change.action.as_str() {
+            "severity_changed" => format!(
+                "Severity changed from {} to {} by {}",
+                escape_markdown(detail(change, "from")),
+                escape_markdown(detail(change, "to")),
+                escape_markdown(&change.changed_by)
+            ),
+            other => format!(
+                "{} by {}",
+                escape_markdown(&capitalize(&other.replace('_', " "))),
+                escape_markdown(&change.changed_by)
+            ),
+        },
+    }
+}
+
+fn detail<'a>(change: &'a IncidentAuditEntry, key: &str) -> &'a str {
+    change
+        .details
+        .as_ref()
+        .and_then(|d| d[key].as_str())
+        .unwrap_or("unknown")
+}
+
+fn objective_row(
+    label: &str,
+    achieved: Option<RecoveryDuration>,
+    target_seconds: Option<i64>,
+    met: Option<bool>,
+    delta_seconds: Option<i64>,
+) -> String {
+    let achieved = achieved
+        .map(|achieved| achieved.display.to_string())
+        .unwrap_or_else(|| "_Not recorded_".to_string());
+    let target = target_seconds
+        .map(|target| DisplayDuration::new(target).to_string())
+        .unwrap_or_else(|| "_Not set_".to_string());
+    let met = match met {
+        Some(true) => "Yes",
+        Some(false) => "No",
+        None => "-",
+    };
+    let delta = match delta_seconds {
+        Some(delta) if delta > 0 => format!("+{}", DisplayDuration::new(delta)),
+        Some(delta) if delta < 0 => format!("-{}", DisplayDuration::new(-delta)),
+        Some(_) => "0s".to_string(),
+        None => "-".to_string(),
+    };
+    format!(
+        "| {} | {} | {} | {} | {} |",
+        label, achieved, target, met, delta
+    )
+}
+
+fn update_status_label(status: IncidentUpdateStatus) -> &'static str {
+    match status {
+        IncidentUpdateStatus::Investigating => "INVESTIGATING",
+        IncidentUpdateStatus::Identified => "IDENTIFIED",
+        IncidentUpdateStatus::Monitoring => "MONITORING",
+        IncidentUpdateStatus::Resolved => "RESOLVED",
+    }
+}
+
+fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+fn capitalize(value: &str) -> String {
+    let mut chars = value.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn write_postmortem(out: &mut String, postmortem: &Postmortem) {
+    let _ = writeln!(out, "## Postmortem\n");
+    for (heading, text) in [
+        ("Root Cause", &postmortem.root_cause),
+        ("Detection", &postmortem.detection_method),
+    ] {
+        if let Some(text) = text {
+            let _ = writeln!(out, "### {}\n\n{}\n", heading, escape_markdown(text));
+        }
+    }
+
+    if !postmortem.contributing_factors.is_empty() {
+        let _ = writeln!(out, "### Contributing Factors\n");
+        for factor in &postmortem.contributing_factors {
+            let _ = writeln!(out, "- {}", escape_inline(factor));
+        }
+        out.push('\n');
+    }
+
+    if !postmortem.action_items.is_empty() {
+        let _ = writeln!(out, "### Action Items\n");
+        for item in &postmortem.action_items {
+            let mut details = Vec::new();
+            if let Some(owner) = &item.owner {
+                details.push(escape_inline(owner));
+            }
+            if let Some(due) = item.due_date {
+                details.push(format!("due {}", due));
+            }
+            let suffix = if details.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", details.join(", "))
+            };
+            let _ = writeln!(
+                out,
+                "- [{}] {}{}",
+                if item.done { "x" } else { " " },
+                escape_inline(&item.description),
+                suffix
+            );
+        }
+        out.push('\n');
+    }
+}
+
+fn write_recovery_validations(out: &mut String, validations: &[RecoveryValidation]) {
+    let _ = writeln!(out, "## Recovery Validation\n");
+    for validation in validations {
+        let outcome = if validation.passed {
+            "Passed"
+        } else {
+            "Failed"
+        };
+        let _ = writeln!(
+            out,
+            "### {} - {}\n",
+            outcome,
+            escape_inline(&validation.restored)
+        );
+        let mut source = format!(
+            "Checked by {} at {}",
+            escape_inline(&validation.performed_by),
+            format_timestamp(validation.created_at)
+        );
+        if let Some(snapshot_id) = validation.snapshot_id {
+            let _ = write!(source, ", restored from snapshot `{}`", snapshot_id);
+        }
+        let _ = writeln!(out, "{}.\n", source);
+
+        if !validation.checks.is_empty() {
+            let _ = writeln!(out, "| Check | Subject | Expected | Actual | Match |");
+            let _ = writeln!(out, "| --- | --- | --- | --- | --- |");
+            for check in validation.checks.iter() {
+                let (kind, expected, actual) = match check {
+                    RecoveryCheck::RowCount {
+                        expected, actual, ..
+                    } => ("Row count", expected.to_string(), actual.to_string()),
+                    RecoveryCheck::Checksum {
+                        expected, actual, ..
+                    } => ("Checksum", escape_cell(expected), escape_cell(actual)),
+                };
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {} | {} | {} |",
+                    kind,
+                    escape_cell(check.subject()),
+                    expected,
+                    actual,
+                    if check.matches() { "Yes" } else { "No" }
+                );
+            }
+            out.push('\n');
+        }
+
+        if let Some(notes) = &validation.notes {
+            let _ = writeln!(out, "{}\n", escape_markdown(notes));
+        }
+    }
+}
+
+/// Backslash-escapes Markdown syntax in user text and neutralizes raw HTML.
+/// Block markers (headings, quotes, list bullets) are only escaped at the start
+/// of a line, where they would otherwise change the document structure.
+pub fn escape_markdown(text: &str) -> String {
+    let normalized = text.replace("\r\n", "\n");
+    let mut out = String::with_capacity(normalized.len());
+
+    for (i, line) in normalized.split('\n').enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+
+        let trimmed = line.trim_start();
+        out.push_str(&line[..line.len() - trimmed.len()]);
+        if trimmed.starts_with(['#', '>', '-', '+', '=']) {
+            out.push('\\');
+        } else if let Some(digits) = trimmed.find(|c: char| !c.is_ascii_digit()) {
+            // "1. item" would become an ordered list
+            if digits > 0 && trimmed[digits..].starts_with(['.', ')']) {
+                out.push_str(&trimmed[..digits]);
+                out.push('\\');
+                out.push_str(&escape_inline(&trimmed[digits..]));
+                continue;
+            }
+        }
+        out.push_str(&escape_inline(trimmed));
+    }
+    out
+}
+
+fn escape_inline(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' | '`' | '*' | '_' | '[' | ']' | '|' | '~' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '<' => out.push_str("&lt;"),
+            '&' => out.push_str("&amp;"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+/// Escapes text for a single table cell, which can't contain line breaks.
+fn escape_cell(text: &str) -> String {
+    escape_markdown(text).replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::incident_models::{AffectedContract, IncidentActionItem, IncidentSeverity};
+    use crate::recovery_objective_models::ObjectiveOutcome;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn incident(end_time: Option<DateTime<Utc>>) -> Incident {
+        let start = Utc.with_ymd_and_hms(2026, 10, 1, 12, 0, 0).unwrap();
+        Incident {
+            id: Uuid::nil(),
+            contract_id: None,
+            incident_type: "outage".to_string(),
+            severity: IncidentSeverity::Critical,
+            description: "RPC nodes *down*".to_string(),
+            start_time: start,
+            end_time,
+            rto_achieved: Some(RecoveryDuration::from_seconds(7200)),
+            rpo_achieved: None,
+            lessons_learned: Some("Add a [second] provider".to_string()),
+            notified_users: true,
+            created_at: start,
+            updated_at: start,
+            deleted_at: None,
+            maintenance_window_id: None,
+            source: "api".to_string(),
+            external_id: None,
+            objectives: ObjectiveOutcome::evaluate(Some(7200), None, 4 * 3600, 3600),
+            escalation: Default::default(),
+            affected_contracts: vec![AffectedContract {
+                id: Uuid::nil(),
+                name: "token_v2".to_string(),
+            }],
+            warnings: Vec::new(),
+        }
+    }
+
+    fn render(incident: &Incident, updates: &[IncidentUpdate]) -> String {
+        render_markdown(&IncidentReport {
+            incident,
+            updates,
+            status_changes: &[],
+            postmortem: &Postmortem::default(),
+            recovery_validations: &[],
+            generated_at: Utc.with_ymd_and_hms(2026, 10, 2, 0, 0, 0).unwrap(),
+        })
+    }
+
+    #[test]
+    fn escapes_user_text() {
+        assert_eq!(escape_markdown("# title"), "\\# title");
+        assert_eq!(escape_markdown("ok\n- item"), "ok\n\\- item");
+        assert_eq!(escape_markdown("1. first"), "1\\. first");
+        assert_eq!(escape_markdown("v1.2 - done"), "v1.2 - done");
+        assert_eq!(escape_markdown("[x](http://evil)"), "\\[x\\](http://evil)");
+        assert_eq!(escape_markdown("<script>"), "&lt;script>");
+        assert_eq!(escape_cell("a|b\nc"), "a\\|b c");
+    }
+
+    #[test]
+    fn open_incident_has_banner() {
+        let report = render(&incident(None), &[]);
+        assert!(report.contains("**INCIDENT STILL OPEN**"));
+        assert!(report.contains("| Resolved | _Unresolved_ |"));
+    }
+
+    #[test]
+    fn resolved_report_includes_all_sections() {
+        let end = Utc.with_ymd_and_hms(2026, 10, 1, 14, 30, 0).unwrap();
+        let update = IncidentUpdate {
+            id: Uuid::new_v4(),
+            incident_id: Uuid::nil(),
+            author: "ops".to_string(),
+            message: "Failing over".to_string(),
+            status: Some(IncidentUpdateStatus::Identified),
+            post_resolution: false,
+            created_at: Utc.with_ymd_and_hms(2026, 10, 1, 13, 0, 0).unwrap(),
+        };
+        let report = render(&incident(Some(end)), &[update]);
+
+        assert!(!report.contains("INCIDENT STILL OPEN"));
+        assert!(report.contains("RPC nodes \\*down\\*"));
+        assert!(report.contains("| Severity | Critical |"));
+        assert!(report.contains("| Duration | 2h 30m |"));
+        assert!(report.contains("- token\\_v2"));
+        assert!(report.contains("| RTO | 2h | 4h | Yes | -2h |"));
+        assert!(report.contains("| RPO | _Not recorded_ | 1h | - | - |"));
+        assert!(report.contains("Add a \\[second\\] provider"));
+
+        let opened = report.find("Incident opened").unwrap();
+        let update = report.find("[IDENTIFIED] ops: Failing over").unwrap();
+        let resolved = report.find("Incident resolved").unwrap();
+        assert!(opened < update && update < resolved);
+    }
+
+    #[test]
+    fn postmortem_section_renders_action_items_as_tasks() {
+        let incident = incident(None);
+        let start = incident.start_time;
+        let postmortem = Postmortem {
+            root_cause: Some("Expired TLS cert".to_string()),
+            detection_method: None,
+            contributing_factors: vec!["No expiry alert".to_string()],
+            action_items: vec![IncidentActionItem {
+                id: Uuid::nil(),
+                incident_id: Uuid::nil(),
+                description: "Automate renewal".to_string(),
+                owner: Some("ops".to_string()),
+                due_date: chrono::NaiveDate::from_ymd_opt(2026, 11, 1),
+                done: true,
+                created_at: start,
+                updated_at: start,
+            }],
+        };
+        let report = render_markdown(&IncidentReport {
+            incident: &incident,
+            updates: &[],
+            status_changes: &[],
+            postmortem: &postmortem,
+            recovery_validations: &[],
+            generated_at: start,
+        });
+
+        assert!(report.contains("### Root Cause\n\nExpired TLS cert"));
+        assert!(!report.contains("### Detection"));
+        assert!(report.contains("- No expiry alert"));
+        assert!(report.contains("- [x] Automate renewal (ops, due 2026-11-01)"));
+        assert!(!render(&incident, &[]).contains("## Postmortem"));
+    }
+
+    #[test]
+    fn recovery_validations_list_their_checks() {
+        let incident = incident(None);
+        let validation = RecoveryValidation {
+            id: Uuid::nil(),
+            incident_id: Uuid::nil(),
+            restored: "contracts table".to_string(),
+            snapshot_id: Some(Uuid::nil()),
+            checks: sqlx::types::Json(vec![
+                RecoveryCheck::RowCount {
+                    subject: "contracts".to_string(),
+                    expected: 120,
+                    actual: 118,
+                },
+                RecoveryCheck::Checksum {
+                    subject: "archive".to_string(),
+                    expected: "ab12".to_string(),
+                    actual: "AB12".to_string(),
+                },
+            ]),
+            passed: false,
+            notes: Some("Two rows were written after the snapshot".to_string()),
+            performed_by: "ops".to_string(),
+            created_at: incident.start_time,
+        };
+        let report = render_markdown(&IncidentReport {
+            incident: &incident,
+            updates: &[],
+            status_changes: &[],
+            postmortem: &Postmortem::default(),
+            recovery_validations: std::slice::from_ref(&validation),
+            generated_at: incident.start_time,
+        });
+
+        assert!(report.contains("### Failed - contracts table\n"));
+        assert!(report.contains("Checked by ops at 2026-10-01 12:00 UTC, restored from snapshot `00000000-0000-0000-0000-000000000000`."));
+        assert!(report.contains("| Row count | contracts | 120 | 118 | No |"));
+        assert!(report.contains("| Checksum | archive | ab12 | AB12 | Yes |"));
+        assert!(report.contains("Two rows were written after the snapshot"));
+        assert!(!render(&incident, &[]).contains("## Recovery Validation"));
+    }
+}
@@ -0,0 +1,85 @@
+use axum::{
+    middleware,
+    routing::{get, patch, post, put},
+    Router,
+};
+
+use crate::{
+    incident_handlers, incident_heatmap, incident_import, state::AppState, validation::payload_size,
+};
+
+pub fn incident_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/incidents",
+            get(incident_handlers::list_incidents).post(incident_handlers::create_incident),
+        )
+        .route(
+            "/api/incidents/metrics",
+            get(incident_handlers::get_incident_metrics),
+        )
+        .route(
+            "/api/incidents/heatmap",
+            get(incident_heatmap::get_incident_heatmap),
+        )
+        .route(
+            "/api/incidents/feed.atom",
+            get(incident_handlers::get_incident_feed),
+        )
+        .route(
+            "/api/incidents/action-items",
+            get(incident_handlers::list_action_items),
+        )
+        .route(
+            "/api/incidents/:id/postmortem",
+            put(incident_handlers::put_incident_postmortem),
+        )
+        .route(
+            "/api/incidents/:id/action-items/:item_id",
+            patch(incident_handlers::update_action_item),
+        )
+        .route("/api/status", get(incident_handlers::get_status))
+        .route(
+            "/api/incidents/:id",
+            get(incident_handlers::get_incident)
+                .patch(incident_handlers::update_incident)
+                .delete(incident_handlers::delete_incident),
+        )
+        .route(
+            "/api/incidents/:id/updates",
+            get(incident_handlers::list_incident_updates)
+                .post(incident_handlers::create_incident_update),
+        )
+        .route(
+            "/api/incidents/:id/ack",
+            post(incident_handlers::acknowledge_incident),
+        )
+        .route(
+            "/api/incidents/:id/validations",
+            get(incident_handlers::list_recovery_validations)
+                .post(incident_handlers::create_recovery_validation),
+        )
+        .route(
+            "/api/contracts/:id/incidents",
+            get(incident_handlers::list_contract_incidents),
+        )
+        .route(
+            "/api/incidents/:id/report",
+            get(incident_handlers::get_incident_report),
+        )
+        .route(
+            "/api/incidents/:id/notifications/preview",
+            get(incident_handlers::preview_incident_notification),
+        )
+}
+
+/// The NDJSON import is kept out of the JSON body checks that `main` applies
+/// to [`incident_routes`].
+pub fn incident_import_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/incidents/import",
+            post(incident_import::import_incidents),
+        )
+        .layer(middleware::from_fn(payload_size::ndjson_middleware))
+}
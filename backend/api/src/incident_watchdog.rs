@@ -0,0 +1,865 @@
+//! Incident watchdog.
+//!
+//! Readiness probes and background jobs report their outcome here. After
+//! `INCIDENT_WATCHDOG_FAILURE_THRESHOLD` consecutive failures within
+//! `INCIDENT_WATCHDOG_WINDOW_SECS` the watchdog opens a `major` incident for
+//! the failing component through the same path as `POST /api/incidents`, and
+//! once `INCIDENT_WATCHDOG_RECOVERY_THRESHOLD` checks pass again it resolves
+//! the incident (or only posts a `monitoring` update when
+//! `INCIDENT_WATCHDOG_AUTO_RESOLVE=false`).
+//!
+//! Each component maps onto at most one open incident. A component that
+//! fails again within `INCIDENT_WATCHDOG_REOPEN_SECS` of recovering reopens
+//! its previous incident instead of creating a new one, and a partial unique
+//! index on `incidents.watchdog_component` keeps replicas from duplicating
+//! each other. A reopened incident needs acknowledging again and escalates
+//! from the first step.
+//!
+//! Components with an open incident are also published to
+//! [`crate::component_health`] after every probe, which is what puts the
+//! contracts API into degraded mode and takes it out again.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, Utc};
+use once_cell::sync::Lazy;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::component_health::{self, Impairment};
+use crate::incident_handlers;
+use crate::incident_models::{CreateIncidentRequest, IncidentSeverity, IncidentUpdateStatus};
+use crate::shutdown::Shutdown;
+use crate::state::AppState;
+
+const AUTHOR: &str = "watchdog";
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+const DEFAULT_WINDOW_SECS: u64 = 900;
+const DEFAULT_RECOVERY_THRESHOLD: u32 = 2;
+const DEFAULT_REOPEN_SECS: u64 = 1800;
+const DEFAULT_INTERVAL_SECS: u64 = 30;
+
+static TRACKER: Lazy<Mutex<Tracker>> =
+    Lazy::new(|| Mutex::new(Tracker::new(WatchdogConfig::from_env())));
+
+/// Registry component whose health the watchdog tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Component {
+    /// Database readiness probe, the same check as `/health/ready`
+    Database,
+    /// Write and read back of a marker blob in source storage
+    BlobStore,
+    /// Network catalog refresh against the synced RPC data
+    NetworkSync,
+    /// Hourly analytics aggregation
+    Aggregation,
+}
+
+impl Component {
+    const ALL: [Component; 4] = [
+        Component::Database,
+        Component::BlobStore,
+        Component::NetworkSync,
+        Component::Aggregation,
+    ];
+
+    /// Component name, matching the names used by maintenance windows
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Component::Database => component_health::DATABASE,
+            Component::BlobStore => component_health::BLOB_STORE,
+            Component::NetworkSync => "rpc_sync",
+            Component::Aggregation => "background_job",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.as_str() == name)
+    }
+
+    fn incident_type(&self) -> String {
+        format!("{}_failure", self.as_str())
+    }
+
+    /// How often the component reports. Slow jobs get a wider failure window
+    /// so that the threshold can still be reached.
+    fn report_interval(&self, config: &WatchdogConfig) -> Duration {
+        match self {
+            Component::Database | Component::BlobStore => {
+                Duration::from_std(config.probe_interval).unwrap_or_else(|_| Duration::zero())
+            }
+            Component::NetworkSync => Duration::seconds(60),
+            Component::Aggregation => Duration::hours(1),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchdogConfig {
+    pub enabled: bool,
+    pub failure_threshold: u32,
+    pub window: Duration,
+    pub recovery_threshold: u32,
+    pub reopen_window: Duration,
+    pub auto_resolve: bool,
+    pub probe_interval: std::time::Duration,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            window: Duration::seconds(DEFAULT_WINDOW_SECS as i64),
+            recovery_threshold: DEFAULT_RECOVERY_THRESHOLD,
+            reopen_window: Duration::seconds(DEFAULT_REOPEN_SECS as i64),
+            auto_resolve: true,
+            probe_interval: std::time::Duration::from_secs(DEFAULT_INTERVAL_SECS),
+        }
+    }
+}
+
+impl WatchdogConfig {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: env_bool("INCIDENT_WATCHDOG_ENABLED", true),
+            failure_threshold: env_u64(
+                "INCIDENT_WATCHDOG_FAILURE_THRESHOLD",
+                DEFAULT_FAILURE_THRESHOLD as u64,
+            ) as u32,
+            window: Duration::seconds(
+                env_u64("INCIDENT_WATCHDOG_WINDOW_SECS", DEFAULT_WINDOW_SECS) as i64,
+            ),
+            recovery_threshold: env_u64(
+                "INCIDENT_WATCHDOG_RECOVERY_THRESHOLD",
+                DEFAULT_RECOVERY_THRESHOLD as u64,
+            ) as u32,
+            reopen_window: Duration::seconds(env_u64(
+                "INCIDENT_WATCHDOG_REOPEN_SECS",
+                DEFAULT_REOPEN_SECS,
+            ) as i64),
+            auto_resolve: env_bool("INCIDENT_WATCHDOG_AUTO_RESOLVE", true),
+            probe_interval: std::time::Duration::from_secs(env_u64(
+                "INCIDENT_WATCHDOG_INTERVAL_SECS",
+                DEFAULT_INTERVAL_SECS,
+            )),
+        }
+    }
+
+    fn window_for(&self, component: Component) -> Duration {
+        self.window
+            .max(component.report_interval(self) * self.failure_threshold as i32)
+    }
+}
+
+/// Incident the watchdog has opened (or is about to open) for a component
+#[derive(Debug, Clone, PartialEq)]
+struct TrackedIncident {
+    /// `None` until the incident row has been written
+    id: Option<Uuid>,
+    started_at: DateTime<Utc>,
+    detail: String,
+    failures: usize,
+    recovered_at: Option<DateTime<Utc>>,
+    /// Whether the database reflects `recovered_at`
+    synced: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ComponentState {
+    /// Consecutive failures still inside the window
+    failures: Vec<DateTime<Utc>>,
+    /// Consecutive successes while an incident is open
+    successes: u32,
+    incident: Option<TrackedIncident>,
+}
+
+#[derive(Debug)]
+struct Tracker {
+    config: WatchdogConfig,
+    components: HashMap<Component, ComponentState>,
+}
+
+/// Database change needed to bring a component's incident in line with
+/// the tracker
+#[derive(Debug, Clone, PartialEq)]
+enum Change {
+    Open {
+        started_at: DateTime<Utc>,
+        detail: String,
+        failures: usize,
+    },
+    Reopen {
+        incident_id: Uuid,
+        detail: String,
+    },
+    Recover {
+        incident_id: Uuid,
+        recovered_at: DateTime<Utc>,
+    },
+}
+
+impl Tracker {
+    fn new(config: WatchdogConfig) -> Self {
+        Self {
+            config,
+            components: HashMap::new(),
+        }
+    }
+
+    fn record_failure(&mut self, component: Component, detail: &str, now: DateTime<Utc>) {
+        let window = self.config.window_for(component);
+        let threshold = self.config.failure_threshold.max(1) as usize;
+        let reopen_window = self.config.reopen_window;
+        let state = self.components.entry(component).or_default();
+
+        state.successes = 0;
+        state.failures.retain(|at| now - *at <= window);
+        state.failures.push(now);
+
+        if let Some(incident) = &state.incident {
+            if incident
+                .recovered_at
+                .is_some_and(|at| now - at > reopen_window && incident.synced)
+            {
+                state.incident = None;
+            }
+        }
+
+        if state.failures.len() < threshold {
+            return;
+        }
+
+        match &mut state.incident {
+            Some(incident) => {
+                if incident.recovered_at.is_some() {
+                    incident.recovered_at = None;
+                    incident.detail = detail.to_string();
+                    // Unsynced recovery never reached the database, so
+                    // only a written one needs undoing.
+                    incident.synced = incident.id.is_some() && !incident.synced;
+                }
+            }
+            None => {
+                state.incident = Some(TrackedIncident {
+                    id: None,
+                    started_at: state.failures[0],
+                    detail: detail.to_string(),
+                    failures: state.failures.len(),
+                    recovered_at: None,
+                    synced: false,
+                });
+            }
+        }
+    }
+
+    fn record_success(&mut self, component: Component, now: DateTime<Utc>) {
+        let threshold = self.config.recovery_threshold.max(1);
+        let state = self.components.entry(component).or_default();
+
+        state.failures.clear();
+        let Some(incident) = &mut state.incident else {
+            return;
+        };
+        if incident.recovered_at.is_some() {
+            return;
+        }
+
+        state.successes += 1;
+        if state.successes >= threshold {
+            state.successes = 0;
+            incident.recovered_at = Some(now);
+            incident.synced = false;
+        }
+    }
+
+    /// Adopts an incident already open in the database, e.g. one left by a
+    /// previous process or opened by another replica.
+    fn adopt(&mut self, component: Component, incident_id: Uuid, started_at: DateTime<Utc>) {
+        let state = self.components.entry(component).or_default();
+        match &mut state.incident {
+            Some(incident) if incident.id.is_none() => {
+                incident.id = Some(incident_id);
+                incident.synced = incident.recovered_at.is_none();
+            }
+            Some(_) => {}
+            None => {
+                state.incident = Some(TrackedIncident {
+                    id: Some(incident_id),
+                    started_at,
+                    detail: String::new(),
+                    failures: 0,
+                    recovered_at: None,
+                    synced: true,
+                });
+            }
+        }
+    }
+
+    fn pending(&self) -> Vec<(Component, Change)> {
+        let mut pending = Vec::new();
+        for component in Component::ALL {
+            let Some(incident) = self
+                .components
+                .get(&component)
+                .and_then(|s| s.incident.as_ref())
+            else {
+                continue;
+            };
+            if incident.synced {
+                continue;
+            }
+
+            let change = match (incident.id, incident.recovered_at) {
+                (None, _) => Change::Open {
+                    started_at: incident.started_at,
+                    detail: incident.detail.clone(),
+                    failures: incident.failures,
+                },
+                (Some(incident_id), Some(recovered_at)) => Change::Recover {
+                    incident_id,
+                    recovered_at,
+                },
+                (Some(incident_id), None) => Change::Reopen {
+                    incident_id,
+                    detail: incident.detail.clone(),
+                },
+            };
+            pending.push((component, change));
+        }
+        pending
+    }
+
+    /// Components whose incident has not recovered, including ones whose
+    /// row has not been written yet
+    fn impaired(&self) -> HashMap<&'static str, Impairment> {
+        self.components
+            .iter()
+            .filter_map(|(component, state)| {
+                let incident = state.incident.as_ref()?;
+                incident.recovered_at.is_none().then(|| {
+                    (
+                        component.as_str(),
+                        Impairment {
+                            incident_id: incident.id,
+                            since: incident.started_at,
+                        },
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Records that `change` was applied. Opening a row for an incident that
+    /// has since recovered leaves the recovery pending.
+    fn applied(&mut self, component: Component, change: &Change, incident_id: Uuid) {
+        let Some(incident) = self
+            .components
+            .get_mut(&component)
+            .and_then(|s| s.incident.as_mut())
+        else {
+            return;
+        };
+
+        match change {
+            Change::Open { .. } => {
+                incident.id = Some(incident_id);
+                incident.synced = incident.recovered_at.is_none();
+            }
+            Change::Reopen { .. } => incident.synced = incident.recovered_at.is_none(),
+            Change::Recover { .. } => incident.synced = incident.recovered_at.is_some(),
+        }
+    }
+}
+
+/// Records a failed check. Cheap enough to call from request handlers.
+pub fn report_failure(component: Component, detail: &str) {
+    let mut tracker = TRACKER.lock().unwrap_or_else(|e| e.into_inner());
+    tracker.record_failure(component, detail, Utc::now());
+}
+
+/// Records a passing check.
+pub fn report_success(component: Component) {
+    let mut tracker = TRACKER.lock().unwrap_or_else(|e| e.into_inner());
+    tracker.record_success(component, Utc::now());
+}
+
+fn publish_health(state: &AppState) {
+    let impaired = TRACKER.lock().unwrap_or_else(|e| e.into_inner()).impaired();
+    state.component_health.replace(impaired);
+}
+
+/// Spawns the task that probes the database and blob store and writes
+/// tracked incidents to the `incidents` table.
+pub fn spawn_incident_watchdog(state: AppState, shutdown: &Shutdown) {
+    let config = TRACKER
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .config
+        .clone();
+    if !config.enabled {
+        tracing::info!("incident watchdog: disabled");
+        return;
+    }
+
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        if let Err(err) = adopt_open_incidents(&state.db).await {
+            tracing::warn!(error = ?err, "incident watchdog: failed to load open incidents");
+        }
+
+        let mut interval = tokio::time::interval(config.probe_interval);
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+
+            match sqlx::query_scalar::<_, i32>("SELECT 1")
+                .fetch_one(&state.db)
+                .await
+            {
+                Ok(_) => report_success(Component::Database),
+                Err(err) => report_failure(Component::Database, &err.to_string()),
+            }
+            match state.source_storage.probe().await {
+                Ok(()) => report_success(Component::BlobStore),
+                Err(err) => report_failure(Component::BlobStore, &err.to_string()),
+            }
+
+            // Publish before syncing, which stalls while the database is down
+            publish_health(&state);
+            sync_incidents(&state, &config).await;
+            publish_health(&state);
+        }
+        tracing::info!("incident watchdog: stopped");
+    });
+}
+
+async fn adopt_open_incidents(pool: &PgPool) -> Result<(), sqlx::Error> {
+    let open: Vec<(Uuid, String, DateTime<Utc>)> = sqlx::query_as(
+        r#"
+        SELECT id, watchdog_component, start_time FROM incidents
+        WHERE watchdog_component IS NOT NULL AND end_time IS NULL AND deleted_at IS NULL
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    let mut tracker = TRACKER.lock().unwrap_or_else(|e| e.into_inner());
+    for (id, name, started_at) in open {
+        if let Some(component) = Component::from_name(&name) {
+            tracker.adopt(component, id, started_at);
+        }
+    }
+    Ok(())
+}
+
+async fn sync_incidents(state: &AppState, config: &WatchdogConfig) {
+    let pending = TRACKER.lock().unwrap_or_else(|e| e.into_inner()).pending();
+
+    for (component, change) in pending {
+        let result = match &change {
+            Change::Open {
+                started_at,
+                detail,
+                failures,
+            } => open(state, component, *started_at, detail, *failures).await,
+            Change::Reopen {
+                incident_id,
+                detail,
+            } => reopen(&state.db, *incident_id, detail)
+                .await
+                .map(|_| *incident_id)
+                .map_err(|err| err.to_string()),
+            Change::Recover {
+                incident_id,
+                recovered_at,
+            } => recover(&state.db, *incident_id, *recovered_at, config.auto_resolve)
+                .await
+                .map(|_| *incident_id)
+                .map_err(|err| err.to_string()),
+        };
+
+        // Failures stay pending and are retried on the next tick, which also
+        // covers the database itself being down.
+        match result {
+            Ok(incident_id) => {
+                tracing::info!(
+                    component = component.as_str(),
+                    incident_id = %incident_id,
+                    change = ?change,
+                    "incident watchdog: incident updated"
+                );
+                TRACKER.lock().unwrap_or_else(|e| e.into_inner()).applied(
+                    component,
+                    &change,
+                    incident_id,
+                );
+            }
+            Err(err) => tracing::warn!(
+                component = component.as_str(),
+                error = %err,
+                "incident watchdog: failed to sync incident"
+            ),
+        }
+    }
+}
+
+async fn open(
+    state: &AppState,
+    component: Component,
+    started_at: DateTime<Utc>,
+    detail: &str,
+    failures: usize,
+) -> Result<Uuid, String> {
+    // Another replica may already have opened one for this component.
+    let existing: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        SELECT id FROM incidents
+        WHERE watchdog_component = $1 AND end_time IS NULL AND deleted_at IS NULL
+        "#,
+    )
+    .bind(component.as_str())
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| err.to_string())?;
+    if let Some(id) = existing {
+        return Ok(id);
+    }
+
+    let req = CreateIncidentRequest {
+        contract_id: None,
+        affected_contract_ids: Vec::new(),
+        incident_type: component.incident_type(),
+        severity: IncidentSeverity::Major,
+        description: format!(
+            "Opened automatically after {} consecutive {} health check failures. Last error: {}",
+            failures,
+            component.as_str(),
+            detail
+        ),
+        start_time: Some(started_at),
+        notify_users: false,
+    };
+
+    incident_handlers::open_incident(state, &req, Some(component.as_str()))
+        .await
+        .map(|incident| incident.id)
+        .map_err(|err| err.to_string())
+}
+
+async fn reopen(pool: &PgPool, incident_id: Uuid, detail: &str) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let reopened = sqlx::query(
+        r#"
+        UPDATE incidents
+        SET end_time = NULL, updated_at = NOW(),
+            acknowledged_at = NULL, acknowledged_by = NULL,
+            escalation_step = 0, escalation_clock_at = NOW()
+        WHERE id = $1 AND deleted_at IS NULL AND end_time IS NOT NULL
+        "#,
+    )
+    .bind(incident_id)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+
+    let message = if reopened > 0 {
+        format!(
+            "Checks are failing again; incident reopened. Last error: {}",
+            detail
+        )
+    } else {
+        format!("Checks are failing again. Last error: {}", detail)
+    };
+    append_update(
+        &mut tx,
+        incident_id,
+        &message,
+        IncidentUpdateStatus::Investigating,
+    )
+    .await?;
+
+    tx.commit().await
+}
+
+async fn recover(
+    pool: &PgPool,
+    incident_id: Uuid,
+    recovered_at: DateTime<Utc>,
+    auto_resolve: bool,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    let status = if auto_resolve {
+        // Someone may have resolved it by hand already; leave that alone.
+        let resolved = sqlx::query(
+            r#"
+            UPDATE incidents SET end_time = $2, updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL AND end_time IS NULL
+            "#,
+        )
+        .bind(incident_id)
+        .bind(recovered_at)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if resolved == 0 {
+            return tx.commit().await;
+        }
+        IncidentUpdateStatus::Resolved
+    } else {
+        IncidentUpdateStatus::Monitoring
+    };
+
+    append_update(
+        &mut tx,
+        incident_id,
+        "Health checks are passing again.",
+        status,
+    )
+    .await?;
+
+    tx.commit().await
+}
+
+async fn append_update(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    incident_id: Uuid,
+    message: &str,
+    status: IncidentUpdateStatus,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO incident_updates (incident_id, author, message, status)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(incident_id)
+    .bind(AUTHOR)
+    .bind(message)
+    .bind(status)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+fn env_u64(key: &str, default: u64) -> u64 {
+    match env::var(key) {
+        Ok(raw) => match raw.parse::<u64>() {
+            Ok(value) if value > 0 => value,
+            _ => {
+                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
+
+fn env_bool(key: &str, default: bool) -> bool {
+    match env::var(key) {
+        Ok(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => {
+                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracker() -> Tracker {
+        Tracker::new(WatchdogConfig {
+            probe_interval: std::time::Duration::from_secs(30),
+            ..WatchdogConfig::default()
+        })
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_800_000_000 + secs, 0).unwrap()
+    }
+
+    fn fail(tracker: &mut Tracker, times: &[i64]) {
+        for t in times {
+            tracker.record_failure(Component::Database, "connection refused", at(*t));
+        }
+    }
+
+    fn open_synced(tracker: &mut Tracker, id: Uuid) {
+        let pending = tracker.pending();
+        assert!(matches!(pending[0].1, Change::Open { .. }));
+        tracker.applied(Component::Database, &pending[0].1, id);
+    }
+
+    #[test]
+    fn opens_after_threshold_within_window() {
+        let mut tracker = tracker();
+        fail(&mut tracker, &[0, 30]);
+        assert!(tracker.pending().is_empty());
+
+        fail(&mut tracker, &[60]);
+        assert_eq!(
+            tracker.pending(),
+            vec![(
+                Component::Database,
+                Change::Open {
+                    started_at: at(0),
+                    detail: "connection refused".to_string(),
+                    failures: 3,
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn failures_outside_window_or_interrupted_do_not_open() {
+        let mut tracker = tracker();
+        fail(&mut tracker, &[0, 1000, 2000]);
+        assert!(tracker.pending().is_empty());
+
+        fail(&mut tracker, &[3000, 3030]);
+        tracker.record_success(Component::Database, at(3060));
+        fail(&mut tracker, &[3090]);
+        assert!(tracker.pending().is_empty());
+    }
+
+    #[test]
+    fn hourly_jobs_get_a_wider_window() {
+        let mut tracker = tracker();
+        for hour in 0..3 {
+            tracker.record_failure(Component::Aggregation, "timeout", at(hour * 3600));
+        }
+        assert_eq!(tracker.pending().len(), 1);
+    }
+
+    #[test]
+    fn repeated_failures_do_not_duplicate() {
+        let mut tracker = tracker();
+        let id = Uuid::new_v4();
+        fail(&mut tracker, &[0, 30, 60]);
+        open_synced(&mut tracker, id);
+
+        fail(&mut tracker, &[90, 120, 150, 180]);
+        assert!(tracker.pending().is_empty());
+    }
+
+    #[test]
+    fn recovers_after_consecutive_successes() {
+        let mut tracker = tracker();
+        let id = Uuid::new_v4();
+        fail(&mut tracker, &[0, 30, 60]);
+        open_synced(&mut tracker, id);
+
+        tracker.record_success(Component::Database, at(90));
+        assert!(tracker.pending().is_empty());
+        tracker.record_success(Component::Database, at(120));
+        assert_eq!(
+            tracker.pending(),
+            vec![(
+                Component::Database,
+                Change::Recover {
+                    incident_id: id,
+                    recovered_at: at(120),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn flapping_reopens_the_same_incident() {
+        let mut tracker = tracker();
+        let id = Uuid::new_v4();
+        fail(&mut tracker, &[0, 30, 60]);
+        open_synced(&mut tracker, id);
+        tracker.record_success(Component::Database, at(90));
+        tracker.record_success(Component::Database, at(120));
+        let pending = tracker.pending();
+        tracker.applied(Component::Database, &pending[0].1, id);
+
+        fail(&mut tracker, &[150, 180, 210]);
+        assert!(matches!(
+            tracker.pending()[..],
+            [(Component::Database, Change::Reopen { incident_id, .. })] if incident_id == id
+        ));
+    }
+
+    #[test]
+    fn failing_long_after_recovery_opens_a_new_incident() {
+        let mut tracker = tracker();
+        let id = Uuid::new_v4();
+        fail(&mut tracker, &[0, 30, 60]);
+        open_synced(&mut tracker, id);
+        tracker.record_success(Component::Database, at(90));
+        tracker.record_success(Component::Database, at(120));
+        let pending = tracker.pending();
+        tracker.applied(Component::Database, &pending[0].1, id);
+
+        fail(&mut tracker, &[5000, 5030, 5060]);
+        assert!(matches!(
+            tracker.pending()[..],
+            [(Component::Database, Change::Open { .. })]
+        ));
+    }
+
+    #[test]
+    fn recovery_before_open_is_written_after_open() {
+        let mut tracker = tracker();
+        let id = Uuid::new_v4();
+        fail(&mut tracker, &[0, 30, 60]);
+        tracker.record_success(Component::Database, at(90));
+        tracker.record_success(Component::Database, at(120));
+
+        open_synced(&mut tracker, id);
+        assert!(matches!(
+            tracker.pending()[..],
+            [(Component::Database, Change::Recover { incident_id, .. })] if incident_id == id
+        ));
+    }
+
+    #[test]
+    fn impaired_until_recovered() {
+        let mut tracker = tracker();
+        let id = Uuid::new_v4();
+        fail(&mut tracker, &[0, 30]);
+        assert!(tracker.impaired().is_empty());
+
+        fail(&mut tracker, &[60]);
+        assert_eq!(
+            tracker.impaired()["database"],
+            Impairment {
+                incident_id: None,
+                since: at(0),
+            }
+        );
+        open_synced(&mut tracker, id);
+        assert_eq!(tracker.impaired()["database"].incident_id, Some(id));
+
+        tracker.record_success(Component::Database, at(90));
+        tracker.record_success(Component::Database, at(120));
+        assert!(tracker.impaired().is_empty());
+    }
+
+    #[test]
+    fn adopted_incidents_recover() {
+        let mut tracker = tracker();
+        let id = Uuid::new_v4();
+        tracker.adopt(Component::NetworkSync, id, at(0));
+        assert!(tracker.pending().is_empty());
+
+        tracker.record_success(Component::NetworkSync, at(60));
+        tracker.record_success(Component::NetworkSync, at(120));
+        assert!(matches!(
+            tracker.pending()[..],
+            [(Component::NetworkSync, Change::Recover { incident_id, .. })] if incident_id == id
+        ));
+    }
+}
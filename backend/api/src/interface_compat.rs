@@ -0,0 +1,379 @@
+//! How a contract version's interface relates to the one before it.
+//!
+//! The interface diff from [`crate::breaking_changes`] is summed up as
+//! breaking (a function removed, or its parameters or return type changed),
+//! additive (only functions or types added) or compatible (nothing callers
+//! can notice). A breaking change that keeps the previous major version is
+//! flagged: version creation refuses it unless `allow_breaking` is set, and
+//! then warns about it.
+
+use axum::{
+    extract::{Path, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use shared::SemVer;
+use uuid::Uuid;
+
+use crate::breaking_changes::{diff_abi, resolve_abi, BreakingChange, ChangeSeverity};
+use crate::error::{ApiError, ApiResult};
+use crate::handlers::{db_internal_error, fetch_contract_identity};
+use crate::state::AppState;
+use crate::type_safety::parser::parse_json_spec;
+use crate::type_safety::types::ContractABI;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Compatibility {
+    /// Existing callers can break
+    Breaking,
+    /// Functions or types were added; nothing existing changed
+    Additive,
+    /// No change callers can notice
+    Compatible,
+}
+
+/// `GET /api/contracts/:id/versions/:version/compat`
+#[derive(Debug, Serialize, Clone, utoipa::ToSchema)]
+pub struct VersionCompat {
+    pub version: String,
+    /// The highest version below `version`, which it is compared with
+    pub previous_version: String,
+    pub compatibility: Compatibility,
+    pub breaking_count: usize,
+    pub changes: Vec<BreakingChange>,
+    /// Set when the change is breaking but the major version was not bumped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+impl VersionCompat {
+    /// Breaking changes that keep the previous major version
+    pub fn breaks_semver(&self) -> bool {
+        self.warning.is_some()
+    }
+}
+
+pub fn classify(changes: &[BreakingChange]) -> Compatibility {
+    if changes
+        .iter()
+        .any(|c| c.severity == ChangeSeverity::Breaking)
+    {
+        Compatibility::Breaking
+    } else if changes.iter().any(|c| c.category.ends_with("_added")) {
+        Compatibility::Additive
+    } else {
+        Compatibility::Compatible
+    }
+}
+
+/// Compares the interface of `version` with that of `previous`
+pub fn assess(
+    previous: &SemVer,
+    previous_abi: &ContractABI,
+    version: &SemVer,
+    abi: &ContractABI,
+) -> VersionCompat {
+    let changes = diff_abi(previous_abi, abi);
+    let compatibility = classify(&changes);
+    let breaking_count = changes
+        .iter()
+        .filter(|c| c.severity == ChangeSeverity::Breaking)
+        .count();
+    let warning = (compatibility == Compatibility::Breaking && version.major == previous.major)
+        .then(|| {
+            format!(
+                "{} breaking change{} without a major version bump from {} (expected {}.0.0 or later)",
+                breaking_count,
+                if breaking_count == 1 { "" } else { "s" },
+                previous,
+                previous.major + 1
+            )
+        });
+    VersionCompat {
+        version: version.to_string(),
+        previous_version: previous.to_string(),
+        compatibility,
+        breaking_count,
+        changes,
+        warning,
+    }
+}
+
+/// The highest existing version of the contract below `version`
+pub async fn previous_version(
+    state: &AppState,
+    contract_uuid: Uuid,
+    version: &SemVer,
+) -> ApiResult<Option<SemVer>> {
+    let existing: Vec<String> =
+        sqlx::query_scalar("SELECT version FROM contract_versions WHERE contract_id = $1")
+            .bind(contract_uuid)
+            .fetch_all(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch contract versions", err))?;
+
+    let mut previous: Option<SemVer> = None;
+    for existing in &existing {
+        let parsed = SemVer::parse(existing).ok_or_else(|| {
+            ApiError::unprocessable(
+                "InvalidExistingVersion",
+                format!("Existing version '{}' is not valid semver", existing),
+            )
+        })?;
+        if parsed < *version && previous.as_ref().map_or(true, |p| parsed > *p) {
+            previous = Some(parsed);
+        }
+    }
+    Ok(previous)
+}
+
+/// The stored interface of one version of the contract
+pub async fn stored_interface(
+    state: &AppState,
+    contract_id: &str,
+    version: &SemVer,
+) -> ApiResult<ContractABI> {
+    let selector = format!("{}@{}", contract_id, version);
+    let abi = resolve_abi(state, &selector, false).await?;
+    parse_json_spec(&abi, contract_id).map_err(|e| {
+        ApiError::bad_request(
+            "InvalidABI",
+            format!("Failed to parse the ABI of {}: {}", version, e),
+        )
+    })
+}
+
+/// How `version`, whose interface is `abi`, relates to the version before
+/// it; `None` for a contract's first version
+pub async fn against_previous(
+    state: &AppState,
+    contract_uuid: Uuid,
+    contract_id: &str,
+    version: &SemVer,
+    abi: &ContractABI,
+) -> ApiResult<Option<VersionCompat>> {
+    let Some(previous) = previous_version(state, contract_uuid, version).await? else {
+        return Ok(None);
+    };
+    let previous_abi = stored_interface(state, contract_id, &previous).await?;
+    Ok(Some(assess(&previous, &previous_abi, version, abi)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/versions/{version}/compat",
+    params(
+        ("id" = String, Path, description = "Contract UUID or address"),
+        ("version" = String, Path, description = "Semver version")
+    ),
+    responses(
+        (status = 200, description = "Interface changes since the previous version and their classification", body = VersionCompat),
+        (status = 400, description = "Invalid version"),
+        (status = 404, description = "Contract, version or ABI not found, or no earlier version")
+    ),
+    tag = "Versions"
+)]
+pub async fn get_version_compat(
+    State(state): State<AppState>,
+    Path((id, version)): Path<(String, String)>,
+) -> ApiResult<Json<VersionCompat>> {
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let version = SemVer::parse(&version).ok_or_else(|| {
+        ApiError::bad_request(
+            "InvalidVersion",
+            "Version must be valid semver (e.g. 1.2.3)",
+        )
+    })?;
+    let abi = stored_interface(&state, &contract_id, &version).await?;
+    against_previous(&state, contract_uuid, &contract_id, &version, &abi)
+        .await?
+        .map(Json)
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "NoPreviousVersion",
+                format!("{} is the contract's first version", version),
+            )
+        })
+}
+
+/// `POST /api/contracts/:id/versions/compat`
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CompatPreviewRequest {
+    pub version: String,
+    /// The interface of the version about to be published, in the same form
+    /// as `CreateContractVersionRequest::abi`
+    #[schema(value_type = Object)]
+    pub abi: serde_json::Value,
+}
+
+/// How a version about to be published would compare to the one before it,
+/// so publishing tools can show breaking changes before creating anything.
+/// `null` when it would be the contract's first version.
+#[utoipa::path(
+    post,
+    path = "/api/contracts/{id}/versions/compat",
+    params(("id" = String, Path, description = "Contract UUID or address")),
+    request_body = CompatPreviewRequest,
+    responses(
+        (status = 200, description = "Interface changes since the previous version, or null for a first version", body = Option<VersionCompat>),
+        (status = 400, description = "Invalid version or ABI"),
+        (status = 404, description = "Contract not found, or the previous version has no ABI")
+    ),
+    tag = "Versions"
+)]
+pub async fn preview_version_compat(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(req): Json<CompatPreviewRequest>,
+) -> ApiResult<Json<Option<VersionCompat>>> {
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let version = SemVer::parse(&req.version).ok_or_else(|| {
+        ApiError::bad_request(
+            "InvalidVersion",
+            "Version must be valid semver (e.g. 1.2.3)",
+        )
+    })?;
+    let abi = parse_json_spec(&req.abi.to_string(), &contract_id)
+        .map_err(|e| ApiError::bad_request("InvalidABI", format!("Failed to parse ABI: {}", e)))?;
+    against_previous(&state, contract_uuid, &contract_id, &version, &abi)
+        .await
+        .map(Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use stellar_xdr::curr::ScSpecTypeDef;
+
+    use super::*;
+    use crate::contract_spec::{self, fixtures::contract};
+
+    fn version(v: &str) -> SemVer {
+        SemVer::parse(v).unwrap()
+    }
+
+    /// `v1` and `v2` of a token, as WASM fixtures, compared as 1.0.0 and
+    /// `next`
+    fn compare(v1: &[u8], v2: &[u8], next: &str) -> VersionCompat {
+        let old = contract_spec::extract(v1, "token").unwrap();
+        let new = contract_spec::extract(v2, "token").unwrap();
+        assess(&version("1.0.0"), &old, &version(next), &new)
+    }
+
+    fn token_v1() -> Vec<u8> {
+        contract(&[
+            (
+                "transfer",
+                &[
+                    ("from", ScSpecTypeDef::Address),
+                    ("to", ScSpecTypeDef::Address),
+                    ("amount", ScSpecTypeDef::I128),
+                ],
+                None,
+            ),
+            (
+                "balance",
+                &[("id", ScSpecTypeDef::Address)],
+                Some(ScSpecTypeDef::I128),
+            ),
+        ])
+    }
+
+    #[test]
+    fn removed_functions_and_changed_signatures_are_breaking() {
+        let balance_only = contract(&[(
+            "balance",
+            &[("id", ScSpecTypeDef::Address)],
+            Some(ScSpecTypeDef::I128),
+        )]);
+        let compat = compare(&token_v1(), &balance_only, "2.0.0");
+        assert_eq!(compat.compatibility, Compatibility::Breaking);
+        assert_eq!(compat.breaking_count, 1);
+        assert!(!compat.breaks_semver());
+
+        let narrower = contract(&[
+            (
+                "transfer",
+                &[
+                    ("from", ScSpecTypeDef::Address),
+                    ("to", ScSpecTypeDef::Address),
+                    ("amount", ScSpecTypeDef::U64),
+                ],
+                None,
+            ),
+            (
+                "balance",
+                &[("id", ScSpecTypeDef::Address)],
+                Some(ScSpecTypeDef::U64),
+            ),
+        ]);
+        let compat = compare(&token_v1(), &narrower, "1.1.0");
+        assert_eq!(compat.compatibility, Compatibility::Breaking);
+        let categories: Vec<&str> = compat.changes.iter().map(|c| c.category.as_str()).collect();
+        assert!(categories.contains(&"param_type_changed"));
+        assert!(categories.contains(&"return_type_changed"));
+        assert_eq!(
+            compat.warning.as_deref(),
+            Some("2 breaking changes without a major version bump from 1.0.0 (expected 2.0.0 or later)")
+        );
+    }
+
+    #[test]
+    fn added_functions_are_additive() {
+        let with_burn = contract(&[
+            (
+                "transfer",
+                &[
+                    ("from", ScSpecTypeDef::Address),
+                    ("to", ScSpecTypeDef::Address),
+                    ("amount", ScSpecTypeDef::I128),
+                ],
+                None,
+            ),
+            (
+                "balance",
+                &[("id", ScSpecTypeDef::Address)],
+                Some(ScSpecTypeDef::I128),
+            ),
+            (
+                "burn",
+                &[
+                    ("from", ScSpecTypeDef::Address),
+                    ("amount", ScSpecTypeDef::I128),
+                ],
+                None,
+            ),
+        ]);
+        let compat = compare(&token_v1(), &with_burn, "1.1.0");
+        assert_eq!(compat.compatibility, Compatibility::Additive);
+        assert_eq!(compat.breaking_count, 0);
+        assert!(!compat.breaks_semver());
+    }
+
+    #[test]
+    fn unchanged_and_renamed_parameters_are_compatible() {
+        let compat = compare(&token_v1(), &token_v1(), "1.0.1");
+        assert_eq!(compat.compatibility, Compatibility::Compatible);
+        assert!(compat.changes.is_empty());
+
+        let renamed = contract(&[
+            (
+                "transfer",
+                &[
+                    ("from", ScSpecTypeDef::Address),
+                    ("to", ScSpecTypeDef::Address),
+                    ("value", ScSpecTypeDef::I128),
+                ],
+                None,
+            ),
+            (
+                "balance",
+                &[("account", ScSpecTypeDef::Address)],
+                Some(ScSpecTypeDef::I128),
+            ),
+        ]);
+        let compat = compare(&token_v1(), &renamed, "1.0.1");
+        assert_eq!(compat.compatibility, Compatibility::Compatible);
+        assert_eq!(compat.changes.len(), 2);
+    }
+}
@@ -0,0 +1,174 @@
+//! Status of background jobs, see [`crate::jobs`].
+//!
+//!   GET  /api/jobs/:id                – a job's status and, once done, its result
+//!   GET  /api/admin/jobs              – jobs that failed or ran out of attempts
+//!   POST /api/admin/jobs/:id/retry    – queue a failed or dead job again
+//!
+//! A job queued with an API token is only visible to that token's subject
+//! and to admins; anonymous jobs are visible to anyone holding the id.
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use shared::pagination::Paginated;
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    jobs::{self, Job, JOB_COLUMNS},
+    pagination::{PageParams, Pagination},
+    state::AppState,
+};
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct FailedJobsQuery {
+    /// `failed` or `dead`; both when omitted. Any other job status is
+    /// accepted too.
+    pub status: Option<String>,
+    /// Only jobs of this type, e.g. `verify_contract`
+    pub job_type: Option<String>,
+}
+
+fn job_not_found(id: Uuid) -> ApiError {
+    ApiError::not_found("JobNotFound", format!("No job found with ID: {}", id))
+}
+
+/// Status of a background job.
+#[utoipa::path(
+    get,
+    path = "/api/jobs/{id}",
+    params(("id" = Uuid, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "The job", body = Job),
+        (status = 404, description = "No such job, or it belongs to someone else")
+    ),
+    tag = "Jobs"
+)]
+pub async fn get_job(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Option<AuthClaims>,
+) -> ApiResult<Json<Job>> {
+    let job = jobs::get_job(&state.db, id)
+        .await
+        .map_err(|err| db_internal_error("fetch job", err))?
+        .ok_or_else(|| job_not_found(id))?;
+
+    // Someone else's job is reported like a missing one
+    if let Some(owner) = &job.created_by {
+        let allowed = claims
+            .as_ref()
+            .is_some_and(|claims| &claims.sub == owner || auth::is_admin(claims));
+        if !allowed {
+            return Err(job_not_found(id));
+        }
+    }
+    Ok(Json(job))
+}
+
+/// Jobs that did not succeed, most recently finished first.
+#[utoipa::path(
+    get,
+    path = "/api/admin/jobs",
+    params(FailedJobsQuery, PageParams),
+    responses(
+        (status = 200, description = "Failed and dead jobs", body = Paginated<Job>),
+        (status = 400, description = "Unknown status"),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Jobs"
+)]
+pub async fn list_failed_jobs(
+    State(state): State<AppState>,
+    Query(query): Query<FailedJobsQuery>,
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Json<Paginated<Job>>> {
+    let statuses: Vec<String> = match query.status {
+        Some(status) if jobs::STATUSES.contains(&status.as_str()) => vec![status],
+        Some(status) => {
+            return Err(ApiError::bad_request(
+                "InvalidStatus",
+                format!(
+                    "Unknown job status '{}', expected one of: {}",
+                    status,
+                    jobs::STATUSES.join(", ")
+                ),
+            ))
+        }
+        None => vec!["failed".to_string(), "dead".to_string()],
+    };
+    const FILTER: &str = "status = ANY($1) AND ($2::TEXT IS NULL OR job_type = $2)";
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM jobs WHERE {}", FILTER))
+        .bind(&statuses)
+        .bind(&query.job_type)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|err| db_internal_error("count failed jobs", err))?;
+
+    let items: Vec<Job> = sqlx::query_as(&format!(
+        "SELECT {} FROM jobs WHERE {}
+         ORDER BY finished_at DESC NULLS LAST, created_at DESC
+         LIMIT $3 OFFSET $4",
+        JOB_COLUMNS, FILTER
+    ))
+    .bind(&statuses)
+    .bind(&query.job_type)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list failed jobs", err))?;
+
+    Ok(Json(Paginated::new(items, total, limit, offset)))
+}
+
+/// Queue a failed or dead job again with a fresh set of attempts.
+#[utoipa::path(
+    post,
+    path = "/api/admin/jobs/{id}/retry",
+    params(("id" = Uuid, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Job queued again", body = Job),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "No such job"),
+        (status = 409, description = "The job is queued, running or succeeded")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Jobs"
+)]
+pub async fn retry_job(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Job>> {
+    let Some(job) = jobs::requeue(&state.db, id)
+        .await
+        .map_err(|err| db_internal_error("requeue job", err))?
+    else {
+        let job = jobs::get_job(&state.db, id)
+            .await
+            .map_err(|err| db_internal_error("fetch job", err))?
+            .ok_or_else(|| job_not_found(id))?;
+        return Err(ApiError::conflict(
+            "JobNotRetryable",
+            format!(
+                "Only failed or dead jobs can be retried; this one is {}",
+                job.status
+            ),
+        ));
+    };
+
+    tracing::info!(
+        job_id = %job.id,
+        job_type = %job.job_type,
+        admin = %claims.sub,
+        "job queued again"
+    );
+    Ok(Json(job))
+}
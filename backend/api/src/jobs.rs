@@ -0,0 +1,414 @@
+//! Persistent background jobs.
+//!
+//! Work that takes longer than a request should (contract verification,
+//! registry snapshots) is queued as a row in `jobs` and the endpoint answers
+//! `202 Accepted` with the job. Callers follow it through
+//! `GET /api/jobs/:id`, see [`crate::job_handlers`].
+//!
+//! Each instance runs `JOB_WORKERS` workers (see [`crate::config`]). A worker
+//! leases one due job at a time with `FOR UPDATE SKIP LOCKED`, so instances
+//! never run the same job twice. A job ends as
+//!
+//! - `succeeded`, with the handler's result,
+//! - `failed`, when the handler rejected it (a 4xx-class error: bad payload,
+//!   missing contract, failed verification); these are not retried,
+//! - `dead`, when transient errors used up `JOB_MAX_ATTEMPTS`. Retries wait
+//!   `JOB_BASE_BACKOFF_SECS`, doubling with each attempt.
+//!
+//! A job still `running` when its lease (`JOB_LEASE_SECS`) runs out belonged
+//! to a worker that stopped; it is queued again, or moved to `dead` when it
+//! has no attempts left. Admins can queue `failed` and `dead` jobs again.
+
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures_util::FutureExt;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{FromRow, PgExecutor, PgPool};
+use uuid::Uuid;
+
+use crate::config::JobsConfig;
+use crate::error::ApiError;
+use crate::shutdown::Shutdown;
+use crate::state::AppState;
+use crate::{handlers, registry_snapshot_handlers};
+
+/// How often an idle worker looks for due jobs
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How often expired leases are reclaimed
+const RECLAIM_INTERVAL: Duration = Duration::from_secs(60);
+
+pub const STATUSES: [&str; 5] = ["queued", "running", "succeeded", "failed", "dead"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    /// Build and check a submitted source, see [`handlers::run_verification`]
+    VerifyContract,
+    /// Export the registry to the blob store, see
+    /// [`registry_snapshot_handlers::run_snapshot_export`]
+    RegistrySnapshot,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::VerifyContract => "verify_contract",
+            JobKind::RegistrySnapshot => "registry_snapshot",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "verify_contract" => Some(JobKind::VerifyContract),
+            "registry_snapshot" => Some(JobKind::RegistrySnapshot),
+            _ => None,
+        }
+    }
+}
+
+/// A job as callers see it; the payload stays private to the worker
+#[derive(Debug, Clone, Serialize, FromRow, utoipa::ToSchema)]
+pub struct Job {
+    pub id: Uuid,
+    /// `verify_contract` or `registry_snapshot`
+    pub job_type: String,
+    /// `queued`, `running`, `succeeded`, `failed` or `dead`
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    /// Error of the last unsuccessful attempt
+    pub last_error: Option<String>,
+    /// What the job produced, once it succeeded
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<Value>,
+    /// Subject of the token that queued the job; `None` for anonymous requests
+    pub created_by: Option<String>,
+    /// When a queued job becomes due
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+pub const JOB_COLUMNS: &str = "id, job_type, status, attempts, max_attempts, last_error, result,
+     created_by, next_attempt_at, created_at, started_at, finished_at";
+
+impl Job {
+    /// Where the job's status can be followed
+    pub fn status_url(&self) -> String {
+        format!("/api/jobs/{}", self.id)
+    }
+}
+
+/// Why an attempt did not succeed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobError {
+    /// Might work later; retried with backoff
+    Transient(String),
+    /// Will not work on retry; the job fails at once
+    Rejected(String),
+}
+
+impl From<ApiError> for JobError {
+    fn from(err: ApiError) -> Self {
+        let message = err.message().to_string();
+        if err.status().is_server_error() || err.status().as_u16() == 429 {
+            JobError::Transient(message)
+        } else {
+            JobError::Rejected(message)
+        }
+    }
+}
+
+/// Queues a job and returns it. Pass a transaction as `executor` to queue
+/// it together with other writes.
+pub async fn enqueue<'e>(
+    executor: impl PgExecutor<'e>,
+    config: &JobsConfig,
+    kind: JobKind,
+    payload: &Value,
+    created_by: Option<&str>,
+) -> Result<Job, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "INSERT INTO jobs (job_type, payload, max_attempts, created_by)
+         VALUES ($1, $2, $3, $4)
+         RETURNING {}",
+        JOB_COLUMNS
+    ))
+    .bind(kind.as_str())
+    .bind(payload)
+    .bind(config.max_attempts as i32)
+    .bind(created_by)
+    .fetch_one(executor)
+    .await
+}
+
+pub async fn get_job(pool: &PgPool, id: Uuid) -> Result<Option<Job>, sqlx::Error> {
+    sqlx::query_as(&format!("SELECT {} FROM jobs WHERE id = $1", JOB_COLUMNS))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+}
+
+/// Queues a `failed` or `dead` job again with a fresh set of attempts.
+/// Returns `None` when there is no such job in either state.
+pub async fn requeue(pool: &PgPool, id: Uuid) -> Result<Option<Job>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "UPDATE jobs
+         SET status = 'queued', attempts = 0, next_attempt_at = NOW(), locked_until = NULL,
+             started_at = NULL, finished_at = NULL, updated_at = NOW()
+         WHERE id = $1 AND status IN ('failed', 'dead')
+         RETURNING {}",
+        JOB_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await
+}
+
+#[derive(Debug, FromRow)]
+struct LeasedJob {
+    id: Uuid,
+    job_type: String,
+    payload: Value,
+    attempts: i32,
+    max_attempts: i32,
+}
+
+/// Spawn the job workers and the task that reclaims abandoned jobs. Like
+/// the notification dispatchers, a worker finishes the job it is running
+/// before it stops.
+pub fn spawn_job_workers(state: AppState, shutdown: &Shutdown) {
+    let config = state.config.jobs.clone();
+
+    for worker in 0..config.workers {
+        let state = state.clone();
+        let token = shutdown.token();
+        shutdown.spawn(async move {
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = interval.tick() => {}
+                }
+                // Keep going while there is work, then wait for the next tick
+                while !token.is_cancelled() {
+                    match run_next(&state).await {
+                        Ok(true) => {}
+                        Ok(false) => break,
+                        Err(err) => {
+                            tracing::error!(worker, error = ?err, "jobs: worker run failed");
+                            break;
+                        }
+                    }
+                }
+            }
+            tracing::info!(worker, "jobs: worker stopped");
+        });
+    }
+
+    let pool = state.db.clone();
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(RECLAIM_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            match reclaim_expired(&pool).await {
+                Ok(0) => {}
+                Ok(reclaimed) => tracing::warn!(reclaimed, "jobs: reclaimed abandoned jobs"),
+                Err(err) => tracing::error!(error = ?err, "jobs: reclaiming leases failed"),
+            }
+        }
+    });
+}
+
+/// Leases and runs the next due job. Returns whether there was one.
+async fn run_next(state: &AppState) -> Result<bool, sqlx::Error> {
+    let config = &state.config.jobs;
+    let job: Option<LeasedJob> = sqlx::query_as(
+        "UPDATE jobs
+         SET status = 'running', attempts = attempts + 1, started_at = NOW(),
+             locked_until = NOW() + make_interval(secs => $1), updated_at = NOW()
+         WHERE id = (
+             SELECT id FROM jobs
+             WHERE status = 'queued' AND next_attempt_at <= NOW()
+             ORDER BY next_attempt_at
+             LIMIT 1
+             FOR UPDATE SKIP LOCKED
+         )
+         RETURNING id, job_type, payload, attempts, max_attempts",
+    )
+    .bind(config.lease.as_secs_f64())
+    .fetch_optional(&state.db)
+    .await?;
+    let Some(job) = job else {
+        return Ok(false);
+    };
+
+    let outcome = AssertUnwindSafe(execute(state, &job))
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|_| Err(JobError::Transient("job handler panicked".to_string())));
+    record_outcome(&state.db, config, &job, outcome).await?;
+    Ok(true)
+}
+
+async fn execute(state: &AppState, job: &LeasedJob) -> Result<Value, JobError> {
+    match JobKind::parse(&job.job_type) {
+        Some(JobKind::VerifyContract) => {
+            let payload = serde_json::from_value(job.payload.clone())
+                .map_err(|err| JobError::Rejected(format!("invalid payload: {}", err)))?;
+            Ok(handlers::run_verification(state, payload).await?)
+        }
+        Some(JobKind::RegistrySnapshot) => {
+            let payload = serde_json::from_value(job.payload.clone())
+                .map_err(|err| JobError::Rejected(format!("invalid payload: {}", err)))?;
+            Ok(registry_snapshot_handlers::run_snapshot_export(state, payload).await?)
+        }
+        None => Err(JobError::Rejected(format!(
+            "unknown job type '{}'",
+            job.job_type
+        ))),
+    }
+}
+
+/// Seconds to wait before retrying after `attempts` failed attempts
+fn backoff_secs(config: &JobsConfig, attempts: i32) -> u64 {
+    config.base_backoff.as_secs() << (attempts - 1).clamp(0, 10)
+}
+
+/// The state a job moves to after an attempt
+fn next_status(
+    outcome: &Result<Value, JobError>,
+    attempts: i32,
+    max_attempts: i32,
+) -> &'static str {
+    match outcome {
+        Ok(_) => "succeeded",
+        Err(JobError::Rejected(_)) => "failed",
+        Err(JobError::Transient(_)) if attempts >= max_attempts => "dead",
+        Err(JobError::Transient(_)) => "queued",
+    }
+}
+
+async fn record_outcome(
+    pool: &PgPool,
+    config: &JobsConfig,
+    job: &LeasedJob,
+    outcome: Result<Value, JobError>,
+) -> Result<(), sqlx::Error> {
+    let status = next_status(&outcome, job.attempts, job.max_attempts);
+    let (result, error) = match outcome {
+        Ok(result) => (Some(result), None),
+        Err(JobError::Transient(err) | JobError::Rejected(err)) => (None, Some(err)),
+    };
+
+    // The attempt number guards against a lease that was reclaimed meanwhile
+    let updated = sqlx::query(
+        "UPDATE jobs
+         SET status = $3, result = $4, last_error = $5, locked_until = NULL, updated_at = NOW(),
+             next_attempt_at = CASE WHEN $3 = 'queued'
+                                    THEN NOW() + make_interval(secs => $6)
+                                    ELSE next_attempt_at END,
+             finished_at = CASE WHEN $3 = 'queued' THEN NULL ELSE NOW() END
+         WHERE id = $1 AND attempts = $2 AND status = 'running'",
+    )
+    .bind(job.id)
+    .bind(job.attempts)
+    .bind(status)
+    .bind(result)
+    .bind(&error)
+    .bind(backoff_secs(config, job.attempts) as f64)
+    .execute(pool)
+    .await?;
+    if updated.rows_affected() == 0 {
+        tracing::warn!(
+            job_id = %job.id,
+            job_type = %job.job_type,
+            "job outlived its lease and was queued again; outcome dropped"
+        );
+        return Ok(());
+    }
+
+    match status {
+        "succeeded" => tracing::info!(job_id = %job.id, job_type = %job.job_type, "job succeeded"),
+        "queued" => tracing::warn!(
+            job_id = %job.id,
+            job_type = %job.job_type,
+            attempts = job.attempts,
+            error = error.as_deref().unwrap_or_default(),
+            "job attempt failed, will retry"
+        ),
+        _ => tracing::error!(
+            job_id = %job.id,
+            job_type = %job.job_type,
+            status,
+            error = error.as_deref().unwrap_or_default(),
+            "job failed"
+        ),
+    }
+    Ok(())
+}
+
+/// Queues jobs whose worker stopped mid-run again, or moves them to `dead`
+/// when they have no attempts left
+async fn reclaim_expired(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let reclaimed = sqlx::query(
+        "UPDATE jobs
+         SET status = CASE WHEN attempts >= max_attempts THEN 'dead' ELSE 'queued' END,
+             finished_at = CASE WHEN attempts >= max_attempts THEN NOW() END,
+             last_error = 'worker stopped before the job finished',
+             locked_until = NULL, next_attempt_at = NOW(), updated_at = NOW()
+         WHERE id IN (
+             SELECT id FROM jobs
+             WHERE status = 'running' AND locked_until < NOW()
+             FOR UPDATE SKIP LOCKED
+         )",
+    )
+    .execute(pool)
+    .await?;
+    Ok(reclaimed.rows_affected())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transient_errors_are_retried_until_attempts_run_out() {
+        let transient = Err(JobError::Transient("rpc timeout".into()));
+        assert_eq!(next_status(&transient, 1, 5), "queued");
+        assert_eq!(next_status(&transient, 5, 5), "dead");
+        let rejected = Err(JobError::Rejected("bytecode mismatch".into()));
+        assert_eq!(next_status(&rejected, 1, 5), "failed");
+        assert_eq!(next_status(&Ok(Value::Null), 3, 5), "succeeded");
+    }
+
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        let config = JobsConfig::default();
+        assert_eq!(backoff_secs(&config, 1), 30);
+        assert_eq!(backoff_secs(&config, 2), 60);
+        assert_eq!(backoff_secs(&config, 4), 240);
+        assert_eq!(backoff_secs(&config, 40), 30 << 10);
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        assert_eq!(
+            JobError::from(ApiError::unprocessable("VerificationFailed", "mismatch")),
+            JobError::Rejected("mismatch".into())
+        );
+        assert!(matches!(
+            JobError::from(ApiError::internal("database unavailable")),
+            JobError::Transient(_)
+        ));
+    }
+}
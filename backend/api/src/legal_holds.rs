@@ -0,0 +1,456 @@
+//! Legal holds on contracts and incidents under dispute or investigation.
+//!
+//! While a record is held it can't be deleted, unpublished or purged by the
+//! retention job; those paths answer 423 Locked with the hold that blocked
+//! them. Setting and releasing a hold is written to the record's audit log.
+//! The `legal_hold` flag on the record mirrors whether an active hold exists.
+//!
+//!   GET  /api/admin/holds              – active holds, newest first
+//!   POST /api/admin/holds              – hold a contract or incident
+//!   POST /api/admin/holds/:id/release  – release a hold; a reason is required
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use shared::AuditActionType;
+use sqlx::{FromRow, PgExecutor, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+const MAX_REASON_CHARS: usize = 2_000;
+
+/// What a hold is placed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HoldTarget {
+    Contract,
+    Incident,
+}
+
+impl HoldTarget {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HoldTarget::Contract => "contract",
+            HoldTarget::Incident => "incident",
+        }
+    }
+
+    fn table(&self) -> &'static str {
+        match self {
+            HoldTarget::Contract => "contracts",
+            HoldTarget::Incident => "incidents",
+        }
+    }
+}
+
+/// One row of `legal_holds`
+#[derive(Debug, Clone, Serialize, FromRow, utoipa::ToSchema)]
+pub struct LegalHold {
+    pub id: Uuid,
+    pub target_type: String, // 'contract' | 'incident'
+    pub target_id: Uuid,
+    pub reason: String,
+    /// Admin who set the hold
+    pub set_by: String,
+    pub set_at: DateTime<Utc>,
+    pub released_by: Option<String>,
+    pub released_at: Option<DateTime<Utc>>,
+    pub release_reason: Option<String>,
+}
+
+/// Body of `POST /api/admin/holds`
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateHoldRequest {
+    pub target_type: HoldTarget,
+    pub target_id: Uuid,
+    /// Why the record is held, e.g. a case reference
+    pub reason: String,
+}
+
+/// Body of `POST /api/admin/holds/:id/release`
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ReleaseHoldRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct LegalHoldList {
+    pub holds: Vec<LegalHold>,
+}
+
+/// Trims the reason and refuses a blank or oversized one
+fn clean_reason(reason: &str) -> ApiResult<String> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(ApiError::validation("reason", "A reason is required"));
+    }
+    if reason.chars().count() > MAX_REASON_CHARS {
+        return Err(ApiError::validation(
+            "reason",
+            format!("reason must be at most {} characters", MAX_REASON_CHARS),
+        ));
+    }
+    Ok(reason.to_string())
+}
+
+/// The 423 answered when a held record would be deleted or purged
+fn held_error(hold: &LegalHold) -> ApiError {
+    ApiError::locked(
+        "LegalHold",
+        format!(
+            "This {} is under a legal hold and can't be removed",
+            hold.target_type
+        ),
+    )
+    .with_details(json!({
+        "hold": {
+            "id": hold.id,
+            "reason": hold.reason,
+            "set_by": hold.set_by,
+            "set_at": hold.set_at,
+        }
+    }))
+}
+
+async fn active_hold<'e>(
+    executor: impl PgExecutor<'e>,
+    target: HoldTarget,
+    target_id: Uuid,
+) -> ApiResult<Option<LegalHold>> {
+    sqlx::query_as(
+        "SELECT * FROM legal_holds
+         WHERE target_type = $1 AND target_id = $2 AND released_at IS NULL",
+    )
+    .bind(target.as_str())
+    .bind(target_id)
+    .fetch_optional(executor)
+    .await
+    .map_err(|err| db_internal_error("look up legal hold", err))
+}
+
+/// Refuses with 423 Locked if the record is under an active hold. Call it
+/// inside the transaction that removes the record, after the record's row
+/// is locked, so a hold set concurrently can't slip past it.
+pub(crate) async fn ensure_not_held<'e>(
+    executor: impl PgExecutor<'e>,
+    target: HoldTarget,
+    target_id: Uuid,
+) -> ApiResult<()> {
+    match active_hold(executor, target, target_id).await? {
+        Some(hold) => Err(held_error(&hold)),
+        None => Ok(()),
+    }
+}
+
+/// Writes a hold change to the audit log of the held record
+async fn audit(
+    tx: &mut Transaction<'_, Postgres>,
+    hold: &LegalHold,
+    target: HoldTarget,
+    actor: &str,
+) -> ApiResult<()> {
+    let released = hold.released_at.is_some();
+    let details = json!({
+        "hold_id": hold.id,
+        "reason": hold.reason,
+        "release_reason": hold.release_reason,
+    });
+    match target {
+        HoldTarget::Contract => {
+            let action = if released {
+                AuditActionType::LegalHoldReleased
+            } else {
+                AuditActionType::LegalHoldSet
+            };
+            sqlx::query(
+                "INSERT INTO contract_audit_log (action_type, contract_id, old_value, new_value, changed_by)
+                 VALUES ($1, $2, $3, $4, $5)",
+            )
+            .bind(action)
+            .bind(hold.target_id)
+            .bind(json!({ "legal_hold": released }))
+            .bind(json!({ "legal_hold": !released, "hold": details }))
+            .bind(actor)
+            .execute(&mut **tx)
+            .await
+            .map_err(|err| db_internal_error("write contract audit log", err))?;
+        }
+        HoldTarget::Incident => {
+            let action = if released {
+                "legal_hold_released"
+            } else {
+                "legal_hold_set"
+            };
+            sqlx::query(
+                "INSERT INTO incident_audit_log (incident_id, action, changed_by, details)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(hold.target_id)
+            .bind(action)
+            .bind(actor)
+            .bind(details)
+            .execute(&mut **tx)
+            .await
+            .map_err(|err| db_internal_error("write incident audit log", err))?;
+        }
+    }
+    Ok(())
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/holds",
+    responses(
+        (status = 200, description = "Active legal holds, newest first", body = LegalHoldList),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Legal Holds"
+)]
+pub async fn list_holds(State(state): State<AppState>) -> ApiResult<Json<LegalHoldList>> {
+    let holds = sqlx::query_as(
+        "SELECT * FROM legal_holds
+         WHERE released_at IS NULL
+         ORDER BY set_at DESC",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list legal holds", err))?;
+    Ok(Json(LegalHoldList { holds }))
+}
+
+/// Hold a contract or incident. It can't be deleted, unpublished or purged
+/// until the hold is released.
+#[utoipa::path(
+    post,
+    path = "/api/admin/holds",
+    request_body = CreateHoldRequest,
+    responses(
+        (status = 201, description = "Hold set", body = LegalHold),
+        (status = 400, description = "Missing reason"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "No such contract or incident"),
+        (status = 409, description = "The record is already held")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Legal Holds"
+)]
+pub async fn create_hold(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<CreateHoldRequest>,
+) -> ApiResult<(StatusCode, Json<LegalHold>)> {
+    let reason = clean_reason(&req.reason)?;
+    let target = req.target_type;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin legal hold", err))?;
+
+    let exists: Option<Uuid> = sqlx::query_scalar(&format!(
+        "SELECT id FROM {} WHERE id = $1 FOR UPDATE",
+        target.table()
+    ))
+    .bind(req.target_id)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("lock held record", err))?;
+    if exists.is_none() {
+        return Err(ApiError::not_found(
+            "TargetNotFound",
+            format!("No {} found with ID: {}", target.as_str(), req.target_id),
+        ));
+    }
+    if let Some(existing) = active_hold(&mut *tx, target, req.target_id).await? {
+        return Err(ApiError::conflict(
+            "AlreadyHeld",
+            format!("This {} is already under a legal hold", target.as_str()),
+        )
+        .with_details(json!({ "hold_id": existing.id })));
+    }
+
+    let hold: LegalHold = sqlx::query_as(
+        "INSERT INTO legal_holds (target_type, target_id, reason, set_by)
+         VALUES ($1, $2, $3, $4)
+         RETURNING *",
+    )
+    .bind(target.as_str())
+    .bind(req.target_id)
+    .bind(&reason)
+    .bind(&claims.sub)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create legal hold", err))?;
+
+    sqlx::query(&format!(
+        "UPDATE {} SET legal_hold = TRUE WHERE id = $1",
+        target.table()
+    ))
+    .bind(req.target_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("flag held record", err))?;
+
+    audit(&mut tx, &hold, target, &claims.sub).await?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit legal hold", err))?;
+
+    if target == HoldTarget::Contract {
+        state.cache.invalidate_all_contracts();
+    }
+    tracing::warn!(
+        hold_id = %hold.id,
+        target_type = target.as_str(),
+        target_id = %hold.target_id,
+        admin = %claims.sub,
+        "legal hold set"
+    );
+    Ok((StatusCode::CREATED, Json(hold)))
+}
+
+/// Release an active hold. The record can be removed again.
+#[utoipa::path(
+    post,
+    path = "/api/admin/holds/{id}/release",
+    params(("id" = Uuid, Path, description = "Hold UUID")),
+    request_body = ReleaseHoldRequest,
+    responses(
+        (status = 200, description = "Hold released", body = LegalHold),
+        (status = 400, description = "Missing reason"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "No such hold"),
+        (status = 409, description = "The hold was already released")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Legal Holds"
+)]
+pub async fn release_hold(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: AuthClaims,
+    Json(req): Json<ReleaseHoldRequest>,
+) -> ApiResult<Json<LegalHold>> {
+    let reason = clean_reason(&req.reason)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin legal hold release", err))?;
+
+    let current: Option<LegalHold> =
+        sqlx::query_as("SELECT * FROM legal_holds WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("lock legal hold", err))?;
+    let Some(current) = current else {
+        return Err(ApiError::not_found(
+            "HoldNotFound",
+            format!("No legal hold found with ID: {}", id),
+        ));
+    };
+    if current.released_at.is_some() {
+        return Err(ApiError::conflict(
+            "HoldReleased",
+            "This legal hold was already released",
+        ));
+    }
+    let target = match current.target_type.as_str() {
+        "contract" => HoldTarget::Contract,
+        _ => HoldTarget::Incident,
+    };
+
+    let hold: LegalHold = sqlx::query_as(
+        "UPDATE legal_holds
+            SET released_by = $2, released_at = NOW(), release_reason = $3
+          WHERE id = $1
+          RETURNING *",
+    )
+    .bind(id)
+    .bind(&claims.sub)
+    .bind(&reason)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("release legal hold", err))?;
+
+    // The record may be gone already if it was held after its removal
+    sqlx::query(&format!(
+        "UPDATE {} SET legal_hold = FALSE WHERE id = $1",
+        target.table()
+    ))
+    .bind(hold.target_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("unflag held record", err))?;
+
+    audit(&mut tx, &hold, target, &claims.sub).await?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit legal hold release", err))?;
+
+    if target == HoldTarget::Contract {
+        state.cache.invalidate_all_contracts();
+    }
+    tracing::warn!(
+        hold_id = %hold.id,
+        target_type = target.as_str(),
+        target_id = %hold.target_id,
+        admin = %claims.sub,
+        "legal hold released"
+    );
+    Ok(Json(hold))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hold() -> LegalHold {
+        LegalHold {
+            id: Uuid::nil(),
+            target_type: "incident".to_string(),
+            target_id: Uuid::nil(),
+            reason: "Case 2026-114".to_string(),
+            set_by: "admin-1".to_string(),
+            set_at: Utc::now(),
+            released_by: None,
+            released_at: None,
+            release_reason: None,
+        }
+    }
+
+    #[test]
+    fn reasons_are_required_and_bounded() {
+        assert!(clean_reason("   ").is_err());
+        assert!(clean_reason(&"x".repeat(MAX_REASON_CHARS + 1)).is_err());
+        assert_eq!(clean_reason(" Case 2026-114 ").unwrap(), "Case 2026-114");
+    }
+
+    #[test]
+    fn held_error_is_423_and_names_the_hold() {
+        let err = held_error(&hold());
+        assert_eq!(err.status(), StatusCode::LOCKED);
+        let details = err.details().cloned().unwrap();
+        assert_eq!(details["reason"], "LegalHold");
+        assert_eq!(details["hold"]["reason"], "Case 2026-114");
+        assert_eq!(details["hold"]["set_by"], "admin-1");
+        assert!(details["hold"]["set_at"].is_string());
+    }
+}
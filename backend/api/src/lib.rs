@@ -2,15 +2,30 @@ pub mod auth;
 pub mod backup_handlers;
 pub mod backup_routes;
 pub mod cache;
+pub mod component_health;
+pub mod config;
 pub mod contract_events;
+pub mod cors;
+pub mod db_pool;
 pub mod disaster_recovery_models;
 pub mod error;
+pub mod feature_flags;
 pub mod health_monitor;
 pub mod metrics;
 pub mod notification_handlers;
 pub mod notification_routes;
+pub mod outbound_http;
 pub mod post_incident_handlers;
 pub mod post_incident_routes;
+pub mod publish_screening;
+pub mod rate_limit;
+pub mod read_replica;
+pub mod replay_guard;
+pub mod request_timeout;
 pub mod request_tracing;
 pub mod resource_tracking;
+pub mod retention;
+pub mod schema_migrations;
+pub mod shutdown;
+pub mod slow_queries;
 pub mod state;
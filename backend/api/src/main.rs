@@ -1,167 +1,252 @@
 #![warn(unused_imports)]
 
 mod ab_test_handlers;
+mod advisory_handlers;
+mod advisory_matcher;
+mod advisory_models;
+mod advisory_routes;
 mod aggregation;
 mod analytics;
 mod auth;
 mod auth_handlers;
+mod badge;
+mod badge_handlers;
 mod batch_verify_handlers;
+mod blob_migration;
 mod breaking_changes;
+mod build_info_handlers;
+mod byte_range;
 mod cache;
 mod canary_handlers;
+mod compare_handlers;
 mod compatibility_testing_handlers;
+mod component_health;
+mod config;
+mod contract_changes;
 mod contract_events;
+mod contract_freshness;
+mod contract_freshness_handlers;
+mod contract_profile;
+mod contract_revision;
+mod contract_spec;
+mod cors;
 mod db_monitoring;
+mod db_pool;
 
 mod activity_feed_handlers;
 mod activity_feed_routes;
 mod analytics_handlers;
 mod category_handlers;
+mod channel_handlers;
+mod channel_models;
+mod channel_routes;
 mod custom_metrics_handlers;
 mod dependency;
 mod dependency_handlers;
 mod deprecation_handlers;
 mod error;
+mod escalation_policy_handlers;
+mod escalation_policy_models;
+mod escalation_policy_routes;
 mod events;
+mod feature_flag_handlers;
+mod feature_flags;
 mod handlers;
 mod health;
 pub mod health_monitor;
 #[cfg(test)]
 mod health_tests;
+mod incident_feed;
+mod incident_escalation;
+mod incident_handlers;
+mod incident_heatmap;
+mod incident_import;
+mod incident_models;
+mod incident_notifications;
+mod incident_report;
+mod incident_routes;
+mod incident_watchdog;
+mod interface_compat;
+mod job_handlers;
+mod jobs;
+mod legal_holds;
+mod maintenance_handlers;
+mod maintenance_models;
+mod maintenance_routes;
 mod metrics;
 mod metrics_handler;
 mod migration_handlers;
 mod models;
 mod multisig_handlers;
 mod multisig_routes;
+mod notification_channels;
 mod onchain_verification;
 #[cfg(feature = "openapi")]
 mod openapi;
+mod outbound_http;
 mod org_handlers;
+mod pagination;
 mod performance_handlers;
+mod profile_uploads;
+mod publish_review_handlers;
+mod publish_screening;
 mod rate_limit;
+mod read_replica;
+mod recovery_objective_handlers;
+mod recovery_objective_models;
+mod recovery_objective_routes;
+mod registry_snapshot_handlers;
+mod registry_snapshot_models;
 mod release_notes_handlers;
 mod release_notes_routes;
+mod replay_guard;
+mod request_timeout;
 pub mod request_tracing;
 mod resource_handlers;
 mod resource_tracking;
+mod retention;
+mod retention_handlers;
 mod routes;
+mod saved_search_handlers;
+mod schema_migrations;
+mod search_weights;
 pub mod security_log;
+mod shutdown;
 pub mod signing_handlers;
+mod slow_queries;
 mod similarity_handlers;
+mod slug_handlers;
 mod simulation;
 mod simulation_handlers;
 mod state;
+mod subscription_handlers;
+mod subscription_models;
+mod subscription_notifications;
+mod subscription_routes;
+mod tag_handlers;
+mod telemetry_handlers;
 mod type_safety;
 mod validation;
+mod wasm_compare;
+mod wasm_metadata;
+mod webhook_events;
+mod webhook_handlers;
+mod webhook_routes;
 mod websocket;
 
 use anyhow::Result;
-use axum::extract::{Request, State};
-use axum::http::{header, HeaderValue, Method, StatusCode};
-use axum::response::Response;
-use axum::{middleware, Router};
+use axum::{extract::DefaultBodyLimit, middleware, Router};
 use dotenv::dotenv;
 use prometheus::Registry;
-use sqlx::postgres::PgPoolOptions;
-use std::net::SocketAddr;
-use std::sync::atomic::{AtomicBool, Ordering};
+use sqlx::{Connection, PgConnection};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use tower_http::cors::CorsLayer;
-
-async fn track_in_flight_middleware(
-    State(state): State<AppState>,
-    req: Request,
-    next: middleware::Next,
-) -> Result<Response, ApiError> {
-    if state.is_shutting_down.load(Ordering::Relaxed) {
-        return Err(ApiError::new(
-            StatusCode::SERVICE_UNAVAILABLE,
-            "SERVICE_UNAVAILABLE",
-            "Service is shutting down and temporarily unavailable",
-        ));
-    }
-    crate::metrics::HTTP_IN_FLIGHT.inc();
-    let res = next.run(req).await;
-    crate::metrics::HTTP_IN_FLIGHT.dec();
-    Ok(res)
-}
 
-use crate::error::ApiError;
 use crate::rate_limit::RateLimitState;
 use crate::state::AppState;
+use crate::validation::payload_size::BodyLimits;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     // Load environment variables
     dotenv().ok();
 
-    // Initialize structured JSON tracing (ELK/Splunk compatible)
-    request_tracing::init_json_tracing();
-
-    // Fail fast on startup when JWT configuration is invalid.
-    if let Err(err) = auth::AuthManager::from_env() {
-        tracing::error!(
-            error = %err,
-            "JWT authentication configuration is invalid. Set JWT_SECRET to a strong value with at least {} characters.",
-            auth::MIN_JWT_SECRET_LEN
-        );
-        return Err(anyhow::anyhow!(
-            "Invalid JWT authentication configuration: {}",
-            err
-        ));
+    // `api migrate ...` manages the schema and exits without starting the server
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("migrate") {
+        return schema_migrations::run_cli(&args[1..]).await;
+    }
+    // `api blobs migrate` moves stored artifacts to the configured backend
+    if args.first().map(String::as_str) == Some("blobs") {
+        return blob_migration::run_cli(&args[1..]).await;
     }
 
-    // Database connection with dynamic pool size
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    // Configuration is read before tracing starts so LOG_FORMAT applies to
+    // every line, including the errors below
+    let config = config::Config::from_env();
+    let logging = config
+        .as_ref()
+        .map(|config| config.logging.clone())
+        .unwrap_or_default();
+    request_tracing::init_tracing(&logging);
+
+    // Fail fast with every invalid setting at once, including a weak JWT_SECRET
+    let config = match config {
+        Ok(config) => {
+            config.log_summary();
+            Arc::new(config)
+        }
+        Err(errors) => {
+            for err in &errors.0 {
+                tracing::error!(var = err.var, "Invalid configuration: {}", err.message);
+            }
+            return Err(anyhow::anyhow!("Invalid configuration: {}", errors));
+        }
+    };
 
-    let logical_cores = std::thread::available_parallelism()
-        .map(|n| n.get())
-        .unwrap_or(4);
+    // Fail fast on an unsafe or malformed CORS policy
+    let cors = match cors::CorsConfig::from_env() {
+        Ok(config) => {
+            config.log_summary();
+            config.layer()
+        }
+        Err(err) => {
+            tracing::error!(error = %err, "CORS configuration is invalid");
+            return Err(anyhow::anyhow!("Invalid CORS configuration: {}", err));
+        }
+    };
+
+    // Database connection; retried with backoff so the API can start before Postgres
+    let database_url = config.database_url.expose();
+    config.pool.log_summary();
+    let pool = db_pool::connect_with_retry(database_url, &config.pool).await?;
+
+    // Dedicated connection so DB_STATEMENT_TIMEOUT_SECS doesn't cut off long migrations
+    let mut conn = PgConnection::connect(database_url).await?;
+    if config.run_migrations {
+        match schema_migrations::up(&mut conn).await {
+            Ok(applied) => {
+                for m in &applied {
+                    tracing::info!(
+                        version = m.version,
+                        description = %m.description,
+                        "Applied migration"
+                    );
+                }
+            }
+            Err(err) => {
+                tracing::error!(error = %err, "Migrations failed");
+                return Err(anyhow::anyhow!("Migrations failed: {}", err));
+            }
+        }
+    }
 
-    let default_max_pool = (logical_cores * 2).max(10);
-    let max_pool_size = std::env::var("DB_MAX_POOL_SIZE")
-        .ok()
-        .and_then(|s| s.parse::<u32>().ok())
-        .unwrap_or(default_max_pool as u32);
+    // Refuse to serve against a schema newer than this binary, e.g. after a rollback
+    let schema = schema_migrations::status(&mut conn).await?;
+    conn.close().await?;
+    if let Err(err) = schema.ensure_compatible() {
+        tracing::error!(error = %err, "Database schema is incompatible with this binary");
+        return Err(anyhow::anyhow!("Incompatible database schema: {}", err));
+    }
+    if !schema.pending.is_empty() {
+        tracing::warn!(
+            pending = schema.pending.len(),
+            "Database has pending migrations; set RUN_MIGRATIONS=true or run `api migrate up`"
+        );
+    }
 
     tracing::info!(
-        max_pool_size = max_pool_size,
-        logical_cores = logical_cores,
-        "Initializing database connection pool"
+        schema_version = ?schema.current_version(),
+        "Database connected"
     );
 
-    let pool = PgPoolOptions::new()
-        .max_connections(max_pool_size)
-        .acquire_timeout(std::time::Duration::from_secs(30))
-        .connect(&database_url)
-        .await?;
-
-    // Run migrations (skip if SKIP_MIGRATIONS=true, useful when migrations were applied manually)
-    let skip_migrations = std::env::var("SKIP_MIGRATIONS")
-        .map(|v| v == "true" || v == "1")
-        .unwrap_or(false);
-
-    if skip_migrations {
-        tracing::info!("Skipping automatic migrations (SKIP_MIGRATIONS=true)");
-    } else {
-        sqlx::migrate!("../../database/migrations")
-            .run(&pool)
-            .await?;
-    }
-
-    tracing::info!("Database connected and migrations applied");
-
     // Check migration versioning state on startup (Issue #252)
     migration_handlers::check_migrations_on_startup(&pool).await;
 
-    // Spawn the hourly analytics aggregation background task
-    aggregation::spawn_aggregation_task(pool.clone());
-
     // Create prometheus registry for metrics
     let registry = Registry::new();
     if let Err(e) = crate::metrics::register_all(&registry) {
-        tracing::error!("Failed to register metrics: {}", e);
+        tracing::error!(error = %e, "Failed to register metrics");
     }
 
     // Job engine omitted: optional dependency; add soroban_batch and uncomment to enable.
@@ -171,13 +256,45 @@ async fn main() -> Result<()> {
 
     // Create app state
     let is_shutting_down = Arc::new(AtomicBool::new(false));
+    let shutdown = shutdown::Shutdown::from_env(is_shutting_down.clone());
     // Job engine: initialize for background batch processing
     let (job_engine, job_rx) = soroban_batch::engine::JobEngine::new();
     let job_engine = Arc::new(job_engine);
     let je = job_engine.clone();
     tokio::spawn(async move { je.run_worker(job_rx).await });
 
-    let state = AppState::new(pool.clone(), registry, job_engine, is_shutting_down.clone()).await;
+    let mut state = AppState::new(
+        config.clone(),
+        pool.clone(),
+        registry,
+        job_engine,
+        is_shutting_down.clone(),
+    )
+    .await?;
+
+    // Optional read replica for heavy GET endpoints; falls back to the primary while down
+    if let Some(replica_config) = read_replica::ReplicaConfig::from_env() {
+        let replica = match read_replica::ReadReplica::connect_lazy(
+            &replica_config.database_url,
+            &config.pool,
+        ) {
+            Ok(replica) => replica,
+            Err(err) => {
+                tracing::error!(error = %err, "Invalid DATABASE_READ_URL");
+                return Err(anyhow::anyhow!("Invalid DATABASE_READ_URL: {}", err));
+            }
+        };
+        replica.spawn_health_check(replica_config.health_check_interval, &shutdown);
+        tracing::info!("Read replica configured for GET requests");
+        state = state.with_read_replica(replica);
+    }
+
+    // Pick up feature flag overrides made on any instance
+    state.flags.spawn_refresh(pool.clone(), &shutdown);
+    slow_queries::watch(state.flags.clone());
+
+    // Spawn the hourly analytics aggregation background task
+    aggregation::spawn_aggregation_task(pool.clone(), &shutdown);
 
     // Spawn the background DB and cache monitoring task
     db_monitoring::spawn_db_monitoring_task(pool.clone(), state.cache.clone());
@@ -185,63 +302,84 @@ async fn main() -> Result<()> {
     // Spawn the health monitor background task (Issue #333)
     let hm_state = state.clone();
     let hm_status = state.health_monitor_status.clone();
-    tokio::spawn(async move {
-        health_monitor::run_health_monitor(hm_state, hm_status).await;
-    });
+    shutdown.spawn(health_monitor::run_health_monitor(
+        hm_state,
+        hm_status,
+        shutdown.token(),
+    ));
+
+    // Deliver queued incident notifications to webhook/SMTP channels
+    incident_notifications::spawn_notification_dispatcher(
+        pool.clone(),
+        state.outbound.clone(),
+        &shutdown,
+    );
+
+    // Deliver contract events to user subscriptions
+    subscription_notifications::spawn_subscription_dispatcher(
+        pool.clone(),
+        state.outbound.clone(),
+        &shutdown,
+    );
+
+    // Escalate critical incidents nobody has acknowledged
+    incident_escalation::spawn_escalation_scheduler(pool.clone(), &shutdown);
+
+    // Open and resolve incidents from repeated health check failures
+    incident_watchdog::spawn_incident_watchdog(state.clone(), &shutdown);
+
+    // Run queued verification and snapshot jobs
+    jobs::spawn_job_workers(state.clone(), &shutdown);
+
+    // Purge incident, audit and notification data past its retention window
+    retention::spawn_retention_task(pool.clone(), state.config.retention.clone(), &shutdown);
+
+    // Warn about and mark contracts that have gone quiet
+    contract_freshness::spawn_freshness_sweep(
+        pool.clone(),
+        state.config.freshness.clone(),
+        &shutdown,
+    );
 
     let network_state = state.clone();
-    tokio::spawn(async move {
-        handlers::run_network_catalog_refresh(network_state).await;
-    });
+    shutdown.spawn(handlers::run_network_catalog_refresh(
+        network_state,
+        shutdown.token(),
+    ));
 
     // Warm up the cache
     state.cache.clone().warm_up(pool.clone());
 
-    let rate_limit_state = RateLimitState::from_env();
+    let rate_limit_state = RateLimitState::new(config.rate_limit.clone());
     rate_limit_state.spawn_eviction_task();
 
-    let allowed_origins = std::env::var("ALLOWED_ORIGINS").unwrap_or_else(|_| {
-        "http://localhost:3000,https://soroban-registry.vercel.app".to_string()
-    });
-
-    let origins: Vec<HeaderValue> = allowed_origins
-        .split(',')
-        .filter_map(|s| {
-            let s = s.trim();
-            if s.is_empty() {
-                None
-            } else {
-                Some(HeaderValue::from_str(s).expect("Invalid allowed origin"))
-            }
-        })
-        .collect();
-
-    let cors = CorsLayer::new()
-        .allow_origin(origins)
-        .allow_methods([
-            Method::GET,
-            Method::POST,
-            Method::PATCH,
-            Method::DELETE,
-            Method::OPTIONS,
-        ])
-        .allow_headers([
-            header::CONTENT_TYPE,
-            header::AUTHORIZATION,
-            crate::request_tracing::X_REQUEST_ID.clone(),
-            crate::request_tracing::X_CORRELATION_ID.clone(),
-        ])
-        .expose_headers([
-            crate::request_tracing::X_REQUEST_ID.clone(),
-            crate::request_tracing::X_CORRELATION_ID.clone(),
-        ]);
+    let body_limits = BodyLimits::from_env();
+
+    // Contract routes answer 503 at once while a component they need has an
+    // open watchdog incident
+    let needs_database = middleware::from_fn_with_state(
+        state
+            .component_health
+            .requires(&[component_health::DATABASE]),
+        component_health::short_circuit,
+    );
+    let needs_blob_store = middleware::from_fn_with_state(
+        state
+            .component_health
+            .requires(&[component_health::DATABASE, component_health::BLOB_STORE]),
+        component_health::short_circuit,
+    );
 
     // Build router
     let app = Router::new()
         .merge(routes::auth_routes())
         .merge(routes::organization_routes())
-        .merge(routes::contract_routes())
+        .merge(routes::contract_routes().route_layer(needs_database))
+        .merge(routes::contract_source_routes().route_layer(needs_blob_store.clone()))
+        .merge(routes::cached_contract_routes())
         .merge(routes::publisher_routes())
+        .merge(routes::job_routes())
+        .merge(routes::telemetry_routes())
         .merge(routes::health_routes())
         .merge(routes::network_routes())
         .merge(routes::openapi_routes())
@@ -256,17 +394,37 @@ async fn main() -> Result<()> {
         .merge(routes::observability_routes())
         .merge(routes::websocket_routes())
         .merge(release_notes_routes::release_notes_routes())
+        .merge(incident_routes::incident_routes())
+        .merge(maintenance_routes::maintenance_routes())
+        .merge(recovery_objective_routes::recovery_objective_routes())
+        .merge(escalation_policy_routes::escalation_policy_routes())
+        .merge(advisory_routes::advisory_routes())
+        .merge(subscription_routes::subscription_routes())
+        .merge(channel_routes::channel_routes())
+        .merge(webhook_routes::webhook_routes())
         .nest("/api", activity_feed_routes::routes())
         .fallback(handlers::route_not_found)
-        .layer(middleware::from_fn(
-            validation::payload_size::payload_size_validation_middleware,
+        .layer(middleware::from_fn_with_state(
+            body_limits,
+            validation::payload_size::json_body_middleware,
         ))
+        .layer(DefaultBodyLimit::max(body_limits.json_bytes))
+        .merge(routes::wasm_upload_routes(body_limits).route_layer(needs_blob_store))
+        .merge(incident_routes::incident_import_routes())
         .layer(middleware::from_fn(
             validation::enhanced_extractors::validation_failure_tracking_middleware,
         ))
         .layer(middleware::from_fn_with_state(
-            state.clone(),
-            track_in_flight_middleware,
+            state.flags.clone(),
+            request_timeout::timeout_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.read_db.clone(),
+            read_replica::read_routing_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            is_shutting_down.clone(),
+            shutdown::track_in_flight,
         ))
         .layer(middleware::from_fn_with_state(
             rate_limit_state,
@@ -276,113 +434,24 @@ async fn main() -> Result<()> {
         .layer(middleware::from_fn(request_tracing::tracing_middleware))
         .with_state(state.clone());
 
-    // Start server (port configurable via PORT env var, default 3001)
-    let port: u16 = std::env::var("PORT")
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(3001);
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
-    tracing::info!("API server listening on {}", addr);
+    // Start server (HOST and PORT, default 0.0.0.0:3001)
+    let addr = config.bind_addr;
+    tracing::info!(%addr, "API server listening");
 
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    let (tx, mut rx) = tokio::sync::mpsc::channel::<()>(1);
-
-    let server = axum::serve(
-        listener,
-        app.into_make_service_with_connect_info::<SocketAddr>(),
-    )
-    .with_graceful_shutdown(async move {
-        let ctrl_c = async {
-            tokio::signal::ctrl_c()
-                .await
-                .expect("failed to install Ctrl+C handler");
-        };
 
-        #[cfg(unix)]
-        let terminate = async {
-            tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
-                .expect("failed to install signal handler")
-                .recv()
-                .await;
-        };
-
-        #[cfg(not(unix))]
-        let terminate = std::future::pending::<()>();
-
-        tokio::select! {
-            _ = ctrl_c => {},
-            _ = terminate => {},
-        }
-
-        tracing::info!(
-            "SIGTERM/SIGINT received. Failing health checks and stopping new requests..."
-        );
-        let _ = tx.send(()).await;
-    });
-
-    let server_task = tokio::spawn(async move {
-        if let Err(e) = server.await {
-            tracing::error!("Server error: {}", e);
-        }
-    });
-
-    if let Some(()) = rx.recv().await {
-        is_shutting_down.store(true, Ordering::SeqCst);
-        let initial_in_flight = crate::metrics::HTTP_IN_FLIGHT.get();
-        tracing::info!(
-            "Graceful shutdown initiated. In-flight requests: {}",
-            initial_in_flight
-        );
-
-        let timeout_secs = std::env::var("SHUTDOWN_TIMEOUT")
-            .unwrap_or_else(|_| "30".to_string())
-            .parse::<u64>()
-            .unwrap_or(30);
-
-        let start_time = std::time::Instant::now();
-        let timeout_duration = std::time::Duration::from_secs(timeout_secs);
-
-        let mut success = false;
-        loop {
-            let in_flight = crate::metrics::HTTP_IN_FLIGHT.get();
-            if in_flight == 0 {
-                tracing::info!(
-                    "All in-flight requests completed in {}ms. In-flight: 0",
-                    start_time.elapsed().as_millis()
-                );
-                success = true;
-                break;
-            }
-            if start_time.elapsed() > timeout_duration {
-                tracing::error!(
-                    "Graceful shutdown timeout ({}s) reached. {} requests still in-flight.",
-                    timeout_secs,
-                    in_flight
-                );
-                break;
-            }
-            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        }
-
-        tracing::info!("Closing database connections cleanly...");
-        pool.close().await;
+    // Drain requests, then background tasks, and close the pool last
+    let requests_drained = shutdown
+        .serve(listener, app, shutdown::termination_signal())
+        .await?;
+    let tasks_stopped = shutdown.stop_background_tasks().await;
 
-        let shutdown_duration = start_time.elapsed();
-        tracing::info!(
-            "Shutdown complete. Duration: {}ms",
-            shutdown_duration.as_millis()
-        );
+    tracing::info!("Closing database connections cleanly...");
+    pool.close().await;
+    tracing::info!(duration_ms = shutdown.elapsed_ms(), "Shutdown complete");
 
-        if success {
-            std::process::exit(0);
-        } else {
-            std::process::exit(1);
-        }
-    } else {
-        let _ = server_task.await;
-        tracing::info!("Closing database connections cleanly...");
-        pool.close().await;
-        tracing::info!("Shutdown complete");
+    if !(requests_drained && tasks_stopped) {
+        std::process::exit(1);
     }
 
     Ok(())
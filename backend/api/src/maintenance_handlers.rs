@@ -0,0 +1,567 @@
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::{db_internal_error, map_query_rejection},
+    incident_handlers::ensure_contracts_exist,
+    incident_models::AffectedContract,
+    maintenance_models::{
+        normalize_components, validate_window, CreateMaintenanceWindowRequest,
+        ListMaintenanceWindowsQuery, MaintenanceWindow, UpdateMaintenanceWindowRequest,
+    },
+    state::AppState,
+};
+
+fn parse_window_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidMaintenanceWindowId",
+            format!("Invalid maintenance window ID format: {}", id),
+        )
+    })
+}
+
+fn require_admin(claims: &AuthClaims) -> ApiResult<()> {
+    if auth::is_admin(claims) {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "Administrative privileges are required to manage maintenance windows",
+        ))
+    }
+}
+
+fn window_not_found(id: &str) -> ApiError {
+    ApiError::not_found(
+        "MaintenanceWindowNotFound",
+        format!("No maintenance window found with ID: {}", id),
+    )
+}
+
+#[derive(FromRow)]
+struct WindowContractRow {
+    window_id: Uuid,
+    id: Uuid,
+    name: String,
+}
+
+/// Fills `affected_contracts` for a batch of windows with a single query.
+pub(crate) async fn load_window_contracts(
+    db: &PgPool,
+    windows: &mut [MaintenanceWindow],
+) -> Result<(), sqlx::Error> {
+    if windows.is_empty() {
+        return Ok(());
+    }
+
+    let ids: Vec<Uuid> = windows.iter().map(|w| w.id).collect();
+    let rows: Vec<WindowContractRow> = sqlx::query_as(
+        "SELECT wc.window_id, c.id, c.name
+         FROM maintenance_window_contracts wc
+         JOIN contracts c ON c.id = wc.contract_id
+         WHERE wc.window_id = ANY($1)
+         ORDER BY c.name",
+    )
+    .bind(&ids)
+    .fetch_all(db)
+    .await?;
+
+    for window in windows.iter_mut() {
+        window.affected_contracts = rows
+            .iter()
+            .filter(|row| row.window_id == window.id)
+            .map(|row| AffectedContract {
+                id: row.id,
+                name: row.name.clone(),
+            })
+            .collect();
+    }
+    Ok(())
+}
+
+/// Windows that are in progress or scheduled and not yet over, soonest first.
+/// Used by the public status endpoint and the Atom feed.
+pub(crate) async fn upcoming_windows(db: &PgPool) -> Result<Vec<MaintenanceWindow>, sqlx::Error> {
+    let mut windows: Vec<MaintenanceWindow> = sqlx::query_as(
+        "SELECT * FROM maintenance_windows
+         WHERE status IN ('scheduled', 'in_progress') AND scheduled_end > NOW()
+         ORDER BY scheduled_start",
+    )
+    .fetch_all(db)
+    .await?;
+    load_window_contracts(db, &mut windows).await?;
+    Ok(windows)
+}
+
+/// Links a newly created incident to the maintenance window it started in, if
+/// any. Windows scoped to specific contracts only match incidents affecting
+/// one of those contracts.
+pub(crate) async fn tag_incident(
+    tx: &mut Transaction<'_, Postgres>,
+    incident_id: Uuid,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        UPDATE incidents i
+        SET maintenance_window_id = (
+            SELECT mw.id
+            FROM maintenance_windows mw
+            WHERE mw.status IN ('scheduled', 'in_progress')
+              AND i.start_time >= mw.scheduled_start
+              AND i.start_time < mw.scheduled_end
+              AND (
+                  NOT EXISTS (
+                      SELECT 1 FROM maintenance_window_contracts wc WHERE wc.window_id = mw.id
+                  )
+                  OR EXISTS (
+                      SELECT 1
+                      FROM maintenance_window_contracts wc
+                      JOIN incident_contracts ic ON ic.contract_id = wc.contract_id
+                      WHERE wc.window_id = mw.id AND ic.incident_id = i.id
+                  )
+              )
+            ORDER BY mw.scheduled_start DESC
+            LIMIT 1
+        )
+        WHERE i.id = $1
+        RETURNING i.maintenance_window_id
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_one(&mut **tx)
+    .await
+}
+
+#[derive(FromRow)]
+struct OverlappingWindow {
+    id: Uuid,
+    title: String,
+}
+
+/// Rejects with 409 when another open window covering any of the same
+/// components or contracts overlaps `[start, end)`. Callers must hold the
+/// maintenance advisory lock so two concurrent requests can't both pass.
+async fn ensure_no_overlap(
+    tx: &mut Transaction<'_, Postgres>,
+    exclude_id: Option<Uuid>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    components: &[String],
+    contract_ids: &[Uuid],
+) -> ApiResult<()> {
+    let overlapping: Option<OverlappingWindow> = sqlx::query_as(
+        r#"
+        SELECT mw.id, mw.title
+        FROM maintenance_windows mw
+        WHERE mw.status IN ('scheduled', 'in_progress')
+          AND ($1::UUID IS NULL OR mw.id <> $1)
+          AND mw.scheduled_start < $3
+          AND mw.scheduled_end > $2
+          AND (
+              mw.components && $4
+              OR EXISTS (
+                  SELECT 1 FROM maintenance_window_contracts wc
+                  WHERE wc.window_id = mw.id AND wc.contract_id = ANY($5)
+              )
+          )
+        ORDER BY mw.scheduled_start
+        LIMIT 1
+        "#,
+    )
+    .bind(exclude_id)
+    .bind(start)
+    .bind(end)
+    .bind(components)
+    .bind(contract_ids)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|err| db_internal_error("check maintenance window overlap", err))?;
+
+    match overlapping {
+        Some(window) => Err(ApiError::conflict(
+            "MaintenanceWindowOverlap",
+            format!(
+                "Overlaps maintenance window '{}' for the same component or contract",
+                window.title
+            ),
+        )
+        .with_details(serde_json::json!({ "conflicting_window_id": window.id }))),
+        None => Ok(()),
+    }
+}
+
+async fn lock_schedule(tx: &mut Transaction<'_, Postgres>) -> ApiResult<()> {
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('maintenance_windows'))")
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("lock maintenance schedule", err))?;
+    Ok(())
+}
+
+async fn replace_window_contracts(
+    tx: &mut Transaction<'_, Postgres>,
+    window_id: Uuid,
+    contract_ids: &[Uuid],
+) -> ApiResult<()> {
+    sqlx::query("DELETE FROM maintenance_window_contracts WHERE window_id = $1")
+        .bind(window_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("clear maintenance window contracts", err))?;
+
+    sqlx::query(
+        "INSERT INTO maintenance_window_contracts (window_id, contract_id)
+         SELECT $1, UNNEST($2::UUID[])
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(window_id)
+    .bind(contract_ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(|err| db_internal_error("insert maintenance window contracts", err))?;
+
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/maintenance-windows",
+    request_body = CreateMaintenanceWindowRequest,
+    responses(
+        (status = 201, description = "Maintenance window scheduled", body = MaintenanceWindow),
+        (status = 400, description = "Invalid schedule or missing title"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 409, description = "Overlaps another window for the same component or contract"),
+        (status = 422, description = "Unknown contract IDs")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Maintenance Windows"
+)]
+pub async fn create_maintenance_window(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<CreateMaintenanceWindowRequest>,
+) -> ApiResult<(StatusCode, Json<MaintenanceWindow>)> {
+    require_admin(&claims)?;
+
+    let title = req.title.trim();
+    if title.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidMaintenanceWindow",
+            "title must not be empty",
+        ));
+    }
+    let components = normalize_components(&req.components);
+    validate_window(
+        req.scheduled_start,
+        req.scheduled_end,
+        &components,
+        &req.contract_ids,
+    )
+    .map_err(|msg| ApiError::bad_request("InvalidMaintenanceWindow", msg))?;
+    ensure_contracts_exist(&state, &req.contract_ids).await?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin create maintenance window", err))?;
+
+    lock_schedule(&mut tx).await?;
+    ensure_no_overlap(
+        &mut tx,
+        None,
+        req.scheduled_start,
+        req.scheduled_end,
+        &components,
+        &req.contract_ids,
+    )
+    .await?;
+
+    let mut window: MaintenanceWindow = sqlx::query_as(
+        r#"
+        INSERT INTO maintenance_windows
+            (title, description, scheduled_start, scheduled_end, components, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING *
+        "#,
+    )
+    .bind(title)
+    .bind(&req.description)
+    .bind(req.scheduled_start)
+    .bind(req.scheduled_end)
+    .bind(&components)
+    .bind(&claims.sub)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("create maintenance window", err))?;
+
+    replace_window_contracts(&mut tx, window.id, &req.contract_ids).await?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit create maintenance window", err))?;
+
+    load_window_contracts(&state.db, std::slice::from_mut(&mut window))
+        .await
+        .map_err(|err| db_internal_error("load maintenance window contracts", err))?;
+
+    tracing::info!(
+        window_id = %window.id,
+        start = %window.scheduled_start,
+        end = %window.scheduled_end,
+        "maintenance window scheduled"
+    );
+
+    Ok((StatusCode::CREATED, Json(window)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/maintenance-windows",
+    params(ListMaintenanceWindowsQuery),
+    responses(
+        (status = 200, description = "Maintenance windows, soonest first", body = [MaintenanceWindow]),
+        (status = 400, description = "Invalid filter parameters")
+    ),
+    tag = "Maintenance Windows"
+)]
+pub async fn list_maintenance_windows(
+    State(state): State<AppState>,
+    params: Result<Query<ListMaintenanceWindowsQuery>, QueryRejection>,
+) -> ApiResult<Json<Vec<MaintenanceWindow>>> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+
+    let mut windows: Vec<MaintenanceWindow> = sqlx::query_as(
+        r#"
+        SELECT * FROM maintenance_windows
+        WHERE ($1::maintenance_window_status IS NULL OR status = $1)
+          AND (NOT $2 OR (status IN ('scheduled', 'in_progress') AND scheduled_end > NOW()))
+        ORDER BY scheduled_start
+        "#,
+    )
+    .bind(query.status)
+    .bind(query.upcoming)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list maintenance windows", err))?;
+
+    load_window_contracts(&state.db, &mut windows)
+        .await
+        .map_err(|err| db_internal_error("load maintenance window contracts", err))?;
+
+    Ok(Json(windows))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/maintenance-windows/{id}",
+    params(
+        ("id" = String, Path, description = "Maintenance window UUID")
+    ),
+    responses(
+        (status = 200, description = "Maintenance window", body = MaintenanceWindow),
+        (status = 400, description = "Malformed ID"),
+        (status = 404, description = "Maintenance window not found")
+    ),
+    tag = "Maintenance Windows"
+)]
+pub async fn get_maintenance_window(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<MaintenanceWindow>> {
+    let window_id = parse_window_id(&id)?;
+
+    let mut window: MaintenanceWindow =
+        sqlx::query_as("SELECT * FROM maintenance_windows WHERE id = $1")
+            .bind(window_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("get maintenance window", err))?
+            .ok_or_else(|| window_not_found(&id))?;
+
+    load_window_contracts(&state.db, std::slice::from_mut(&mut window))
+        .await
+        .map_err(|err| db_internal_error("load maintenance window contracts", err))?;
+
+    Ok(Json(window))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/maintenance-windows/{id}",
+    params(
+        ("id" = String, Path, description = "Maintenance window UUID")
+    ),
+    request_body = UpdateMaintenanceWindowRequest,
+    responses(
+        (status = 200, description = "Maintenance window updated", body = MaintenanceWindow),
+        (status = 400, description = "Malformed ID or invalid schedule"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Maintenance window not found"),
+        (status = 409, description = "Window is closed, or the new schedule overlaps another window"),
+        (status = 422, description = "Unknown contract IDs")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Maintenance Windows"
+)]
+pub async fn update_maintenance_window(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<UpdateMaintenanceWindowRequest>,
+) -> ApiResult<Json<MaintenanceWindow>> {
+    require_admin(&claims)?;
+    let window_id = parse_window_id(&id)?;
+
+    if matches!(req.title.as_deref(), Some(t) if t.trim().is_empty()) {
+        return Err(ApiError::bad_request(
+            "InvalidMaintenanceWindow",
+            "title must not be empty",
+        ));
+    }
+    if let Some(ids) = &req.contract_ids {
+        ensure_contracts_exist(&state, ids).await?;
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin update maintenance window", err))?;
+
+    lock_schedule(&mut tx).await?;
+
+    let current: MaintenanceWindow =
+        sqlx::query_as("SELECT * FROM maintenance_windows WHERE id = $1 FOR UPDATE")
+            .bind(window_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("lock maintenance window", err))?
+            .ok_or_else(|| window_not_found(&id))?;
+
+    if current.status.is_closed() {
+        return Err(ApiError::conflict(
+            "MaintenanceWindowClosed",
+            format!(
+                "Maintenance window is {} and can no longer be changed",
+                current.status.as_str()
+            ),
+        ));
+    }
+
+    let start = req.scheduled_start.unwrap_or(current.scheduled_start);
+    let end = req.scheduled_end.unwrap_or(current.scheduled_end);
+    let status = req.status.unwrap_or(current.status);
+    let components = match &req.components {
+        Some(components) => normalize_components(components),
+        None => current.components.clone(),
+    };
+    let contract_ids: Vec<Uuid> = match &req.contract_ids {
+        Some(ids) => ids.clone(),
+        None => sqlx::query_scalar(
+            "SELECT contract_id FROM maintenance_window_contracts WHERE window_id = $1",
+        )
+        .bind(window_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("load maintenance window contracts", err))?,
+    };
+
+    validate_window(start, end, &components, &contract_ids)
+        .map_err(|msg| ApiError::bad_request("InvalidMaintenanceWindow", msg))?;
+    if !status.is_closed() {
+        ensure_no_overlap(
+            &mut tx,
+            Some(window_id),
+            start,
+            end,
+            &components,
+            &contract_ids,
+        )
+        .await?;
+    }
+
+    let mut window: MaintenanceWindow = sqlx::query_as(
+        r#"
+        UPDATE maintenance_windows
+        SET title = COALESCE($1, title),
+            description = COALESCE($2, description),
+            scheduled_start = $3,
+            scheduled_end = $4,
+            status = $5,
+            components = $6,
+            updated_at = NOW()
+        WHERE id = $7
+        RETURNING *
+        "#,
+    )
+    .bind(req.title.as_deref().map(str::trim))
+    .bind(&req.description)
+    .bind(start)
+    .bind(end)
+    .bind(status)
+    .bind(&components)
+    .bind(window_id)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("update maintenance window", err))?;
+
+    if req.contract_ids.is_some() {
+        replace_window_contracts(&mut tx, window_id, &contract_ids).await?;
+    }
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit update maintenance window", err))?;
+
+    load_window_contracts(&state.db, std::slice::from_mut(&mut window))
+        .await
+        .map_err(|err| db_internal_error("load maintenance window contracts", err))?;
+
+    Ok(Json(window))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/maintenance-windows/{id}",
+    params(
+        ("id" = String, Path, description = "Maintenance window UUID")
+    ),
+    responses(
+        (status = 204, description = "Maintenance window deleted"),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Maintenance window not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Maintenance Windows"
+)]
+pub async fn delete_maintenance_window(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    require_admin(&claims)?;
+    let window_id = parse_window_id(&id)?;
+
+    let result = sqlx::query("DELETE FROM maintenance_windows WHERE id = $1")
+        .bind(window_id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete maintenance window", err))?;
+
+    if result.rows_affected() == 0 {
+        return Err(window_not_found(&id));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
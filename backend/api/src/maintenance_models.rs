@@ -0,0 +1,148 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::incident_models::AffectedContract;
+
+/// Lifecycle of a planned maintenance window
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema,
+)]
+#[sqlx(type_name = "maintenance_window_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceWindowStatus {
+    Scheduled,
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+impl MaintenanceWindowStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MaintenanceWindowStatus::Scheduled => "scheduled",
+            MaintenanceWindowStatus::InProgress => "in_progress",
+            MaintenanceWindowStatus::Completed => "completed",
+            MaintenanceWindowStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Completed and cancelled windows can no longer be edited and never
+    /// conflict with new windows.
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self,
+            MaintenanceWindowStatus::Completed | MaintenanceWindowStatus::Cancelled
+        )
+    }
+}
+
+/// Planned downtime for registry components and/or specific contracts
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct MaintenanceWindow {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub scheduled_start: DateTime<Utc>,
+    pub scheduled_end: DateTime<Utc>,
+    pub status: MaintenanceWindowStatus,
+    /// Registry components under maintenance, e.g. `api`, `rpc_sync`, `database`
+    pub components: Vec<String>,
+    /// JWT subject of the admin who scheduled the window
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Loaded from `maintenance_window_contracts`
+    #[sqlx(skip)]
+    #[serde(default)]
+    pub affected_contracts: Vec<AffectedContract>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateMaintenanceWindowRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub scheduled_start: DateTime<Utc>,
+    pub scheduled_end: DateTime<Utc>,
+    #[serde(default)]
+    pub components: Vec<String>,
+    #[serde(default)]
+    pub contract_ids: Vec<Uuid>,
+}
+
+/// Partial update; omitted fields keep their current values
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpdateMaintenanceWindowRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub scheduled_start: Option<DateTime<Utc>>,
+    pub scheduled_end: Option<DateTime<Utc>>,
+    pub status: Option<MaintenanceWindowStatus>,
+    pub components: Option<Vec<String>>,
+    pub contract_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+pub struct ListMaintenanceWindowsQuery {
+    /// Only windows that have not yet ended and are not cancelled or completed
+    #[serde(default)]
+    pub upcoming: bool,
+    pub status: Option<MaintenanceWindowStatus>,
+}
+
+/// Trims, lowercases and de-duplicates component names, dropping blanks.
+pub fn normalize_components(components: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(components.len());
+    for component in components {
+        let component = component.trim().to_ascii_lowercase();
+        if !component.is_empty() && !normalized.contains(&component) {
+            normalized.push(component);
+        }
+    }
+    normalized
+}
+
+/// Checks the window covers a positive span and names something to maintain.
+pub fn validate_window(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    components: &[String],
+    contract_ids: &[Uuid],
+) -> Result<(), String> {
+    if end <= start {
+        return Err("scheduled_end must be after scheduled_start".to_string());
+    }
+    if components.is_empty() && contract_ids.is_empty() {
+        return Err("at least one component or contract must be affected".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn components_are_normalized() {
+        let input = vec![
+            " API ".to_string(),
+            "api".to_string(),
+            "".to_string(),
+            "rpc_sync".to_string(),
+        ];
+        assert_eq!(normalize_components(&input), vec!["api", "rpc_sync"]);
+    }
+
+    #[test]
+    fn window_needs_positive_span_and_scope() {
+        let start = Utc::now();
+        let api = vec!["api".to_string()];
+        assert!(validate_window(start, start + Duration::hours(1), &api, &[]).is_ok());
+        assert!(validate_window(start, start + Duration::hours(1), &[], &[Uuid::nil()]).is_ok());
+        assert!(validate_window(start, start, &api, &[]).is_err());
+        assert!(validate_window(start, start - Duration::hours(1), &api, &[]).is_err());
+        assert!(validate_window(start, start + Duration::hours(1), &[], &[]).is_err());
+    }
+}
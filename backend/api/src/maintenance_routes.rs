@@ -0,0 +1,18 @@
+use axum::{routing::get, Router};
+
+use crate::{maintenance_handlers, state::AppState};
+
+pub fn maintenance_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/maintenance-windows",
+            get(maintenance_handlers::list_maintenance_windows)
+                .post(maintenance_handlers::create_maintenance_window),
+        )
+        .route(
+            "/api/maintenance-windows/:id",
+            get(maintenance_handlers::get_maintenance_window)
+                .patch(maintenance_handlers::update_maintenance_window)
+                .delete(maintenance_handlers::delete_maintenance_window),
+        )
+}
@@ -71,6 +71,11 @@ pub static HTTP_RESPONSE_SIZE: Lazy<HistogramVec> = histogram_vec!(
     "HTTP response body size",
     &["method"]
 );
+pub static HTTP_REQUEST_TIMEOUTS: Lazy<IntCounterVec> = counter_vec!(
+    "http_request_timeouts_total",
+    "Requests answered 504 after running past their time budget",
+    &["route", "budget"]
+);
 
 // ── Contracts ───────────────────────────────────────────────────────────────
 pub static CONTRACTS_TOTAL: Lazy<IntGauge> =
@@ -165,6 +170,11 @@ pub static SEARCH_SLOW_QUERIES: Lazy<IntCounterVec> = counter_vec!(
     "Search queries slower than the threshold",
     &["type"]
 );
+pub static DB_SLOW_QUERIES: Lazy<IntCounterVec> = counter_vec!(
+    "db_slow_queries_total",
+    "SQL statements slower than the slow-query threshold",
+    &["route"]
+);
 
 // ── Cache ───────────────────────────────────────────────────────────────────
 pub static CACHE_HITS: Lazy<IntCounter> = counter!("cache_hits_total", "Cache hits");
@@ -183,6 +193,11 @@ pub static VERIFICATION_CACHE_MISSES: Lazy<IntCounter> = counter!(
     "Verification cache misses"
 );
 
+pub static CONTRACT_CACHE_HITS: Lazy<IntCounter> =
+    counter!("contract_cache_hits_total", "Contract lookup cache hits");
+pub static CONTRACT_CACHE_MISSES: Lazy<IntCounter> =
+    counter!("contract_cache_misses_total", "Contract lookup cache misses");
+
 pub static REDIS_CACHE_HITS: Lazy<IntCounter> =
     counter!("redis_cache_hits_total", "Redis cache hits");
 pub static REDIS_CACHE_MISSES: Lazy<IntCounter> =
@@ -290,12 +305,30 @@ pub static PUBLISHERS_TOTAL: Lazy<IntGauge> =
 pub static PUBLISHER_REGISTRATIONS: Lazy<IntCounter> =
     counter!("publisher_registrations_total", "Publisher registrations");
 
+// ── Outbound HTTP ───────────────────────────────────────────────────────────
+pub static OUTBOUND_REQUESTS: Lazy<IntCounterVec> = counter_vec!(
+    "outbound_http_requests_total",
+    "Outbound HTTP requests by destination and outcome",
+    &["destination", "outcome"]
+);
+pub static OUTBOUND_CIRCUIT_OPEN: Lazy<IntGaugeVec> = gauge_vec!(
+    "outbound_http_circuit_open",
+    "1 while a destination is skipped after repeated failures",
+    &["destination"]
+);
+pub static OUTBOUND_CIRCUIT_TRIPS: Lazy<IntCounterVec> = counter_vec!(
+    "outbound_http_circuit_trips_total",
+    "Times a destination's circuit breaker opened",
+    &["destination"]
+);
+
 pub fn register_all(r: &Registry) -> prometheus::Result<()> {
     r.register(Box::new(HTTP_REQUESTS_TOTAL.clone()))?;
     r.register(Box::new(HTTP_REQUEST_DURATION.clone()))?;
     r.register(Box::new(HTTP_IN_FLIGHT.clone()))?;
     r.register(Box::new(HTTP_REQUEST_SIZE.clone()))?;
     r.register(Box::new(HTTP_RESPONSE_SIZE.clone()))?;
+    r.register(Box::new(HTTP_REQUEST_TIMEOUTS.clone()))?;
     r.register(Box::new(CONTRACTS_TOTAL.clone()))?;
     r.register(Box::new(CONTRACTS_PUBLISHED.clone()))?;
     r.register(Box::new(CONTRACTS_VERIFIED.clone()))?;
@@ -321,6 +354,7 @@ pub fn register_all(r: &Registry) -> prometheus::Result<()> {
     r.register(Box::new(DB_POOL_UTILIZATION.clone()))?;
     r.register(Box::new(SEARCH_QUERY_DURATION.clone()))?;
     r.register(Box::new(SEARCH_SLOW_QUERIES.clone()))?;
+    r.register(Box::new(DB_SLOW_QUERIES.clone()))?;
 
     r.register(Box::new(CACHE_HITS.clone()))?;
     r.register(Box::new(CACHE_MISSES.clone()))?;
@@ -331,6 +365,8 @@ pub fn register_all(r: &Registry) -> prometheus::Result<()> {
     r.register(Box::new(ABI_CACHE_MISSES.clone()))?;
     r.register(Box::new(VERIFICATION_CACHE_HITS.clone()))?;
     r.register(Box::new(VERIFICATION_CACHE_MISSES.clone()))?;
+    r.register(Box::new(CONTRACT_CACHE_HITS.clone()))?;
+    r.register(Box::new(CONTRACT_CACHE_MISSES.clone()))?;
     r.register(Box::new(REDIS_CACHE_HITS.clone()))?;
     r.register(Box::new(REDIS_CACHE_MISSES.clone()))?;
     r.register(Box::new(RESOURCE_RECORDINGS.clone()))?;
@@ -359,6 +395,9 @@ pub fn register_all(r: &Registry) -> prometheus::Result<()> {
     r.register(Box::new(PATCHES_FAILED.clone()))?;
     r.register(Box::new(PUBLISHERS_TOTAL.clone()))?;
     r.register(Box::new(PUBLISHER_REGISTRATIONS.clone()))?;
+    r.register(Box::new(OUTBOUND_REQUESTS.clone()))?;
+    r.register(Box::new(OUTBOUND_CIRCUIT_OPEN.clone()))?;
+    r.register(Box::new(OUTBOUND_CIRCUIT_TRIPS.clone()))?;
     r.register(Box::new(JOB_QUEUE_DEPTH.clone()))?;
     r.register(Box::new(JOB_PROCESSING_DURATION.clone()))?;
     r.register(Box::new(JOB_FAILURES_TOTAL.clone()))?;
@@ -3,6 +3,6 @@
 
 pub use shared::{
     ContractRatingStats, CreateReviewRequest, FlagReviewRequest, GetReviewsQuery,
-    ModerateReviewRequest, RatingDistribution, ReviewResponse, ReviewSortBy, ReviewStatus,
-    ReviewVoteRequest, ReviewVoteResponse,
+    ModerateReviewRequest, RatingDistribution, ReviewHelpfulResponse, ReviewResponse, ReviewSortBy,
+    ReviewStatus, ReviewVoteRequest, ReviewVoteResponse,
 };
@@ -237,10 +237,7 @@ pub async fn create_policy(
     .bind(ordered_approvals)
     .fetch_one(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to create multisig policy");
-        ApiError::db_error("Failed to create multisig policy")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("create multisig policy", e))?;
 
     Ok(Json(policy))
 }
@@ -282,18 +279,16 @@ pub async fn create_deploy_proposal(
     .bind(payload.policy_id)
     .fetch_optional(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to fetch multisig policy");
-        ApiError::db_error("Failed to load multisig policy")
-    })?
+    .map_err(|e| ApiError::from_sqlx("fetch multisig policy", e))?
     .ok_or_else(|| ApiError::not_found("PolicyNotFound", "multisig policy not found"))?;
 
     let expires_at = Utc::now() + chrono::Duration::seconds(i64::from(policy.expiry_seconds));
 
-    let mut tx = state.db.begin().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to start transaction");
-        ApiError::db_error("Failed to create deploy proposal")
-    })?;
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| ApiError::from_sqlx("start transaction", e))?;
 
     let proposal: DeployProposal = sqlx::query_as(
         "INSERT INTO deploy_proposals (
@@ -317,10 +312,7 @@ pub async fn create_deploy_proposal(
     .bind(policy.threshold)
     .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to create deploy proposal");
-        ApiError::db_error("Failed to create deploy proposal")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("create deploy proposal", e))?;
 
     for signer in &policy.signer_addresses {
         sqlx::query(
@@ -338,10 +330,7 @@ pub async fn create_deploy_proposal(
         }))
         .execute(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to queue multisig notification");
-            ApiError::db_error("Failed to queue multisig notifications")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("queue multisig notification", e))?;
     }
 
     sqlx::query(
@@ -358,15 +347,11 @@ pub async fn create_deploy_proposal(
     }))
     .execute(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to insert audit event");
-        ApiError::db_error("Failed to record audit trail")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("insert audit event", e))?;
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to commit deploy proposal transaction");
-        ApiError::db_error("Failed to finalize deploy proposal")
-    })?;
+    tx.commit()
+        .await
+        .map_err(|e| ApiError::from_sqlx("commit deploy proposal transaction", e))?;
 
     metrics::MULTISIG_PROPOSALS.inc();
     Ok(Json(proposal))
@@ -391,10 +376,11 @@ pub async fn sign_proposal(
     let decision = payload.decision.unwrap_or(ApprovalDecision::Approved);
     let signer = payload.signer_address.trim().to_string();
 
-    let mut tx = state.db.begin().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to start signing transaction");
-        ApiError::db_error("Failed to sign proposal")
-    })?;
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| ApiError::from_sqlx("start signing transaction", e))?;
 
     let signing_state = sqlx::query_as::<_, ProposalSigningState>(
         "SELECT
@@ -411,10 +397,7 @@ pub async fn sign_proposal(
     .bind(proposal_id)
     .fetch_optional(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to load proposal");
-        ApiError::db_error("Failed to load proposal")
-    })?
+    .map_err(|e| ApiError::from_sqlx("load proposal", e))?
     .ok_or_else(|| ApiError::not_found("ProposalNotFound", "deployment proposal not found"))?;
 
     if signing_state.status != ProposalStatus::Pending {
@@ -434,14 +417,10 @@ pub async fn sign_proposal(
         .bind(proposal_id)
         .execute(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to mark proposal expired");
-            ApiError::db_error("Failed to update proposal status")
-        })?;
-        tx.commit().await.map_err(|e| {
-            tracing::error!(error = ?e, "failed to finalize expiry update");
-            ApiError::db_error("Failed to update proposal status")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("mark proposal expired", e))?;
+        tx.commit()
+            .await
+            .map_err(|e| ApiError::from_sqlx("finalize expiry update", e))?;
         return Err(ApiError::conflict(
             "ProposalExpired",
             "proposal has already expired",
@@ -471,10 +450,7 @@ pub async fn sign_proposal(
         .bind(proposal_id)
         .fetch_one(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to count signatures");
-            ApiError::db_error("Failed to evaluate signature ordering")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("count signatures", e))?;
 
         if signer_position != approved_count as i32 {
             return Err(ApiError::conflict(
@@ -501,10 +477,7 @@ pub async fn sign_proposal(
     .bind(step_index)
     .fetch_optional(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to insert proposal signature");
-        ApiError::db_error("Failed to record signature")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("insert proposal signature", e))?;
 
     if inserted.is_none() {
         return Err(ApiError::conflict(
@@ -532,10 +505,7 @@ pub async fn sign_proposal(
     .bind(json!({ "step_index": step_index }))
     .execute(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to insert audit event");
-        ApiError::db_error("Failed to record audit trail")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("insert audit event", e))?;
 
     let signatures_collected: i64 = sqlx::query_scalar(
         "SELECT COUNT(*) FROM proposal_signatures
@@ -544,10 +514,7 @@ pub async fn sign_proposal(
     .bind(proposal_id)
     .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to count approved signatures");
-        ApiError::db_error("Failed to evaluate proposal threshold")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("count approved signatures", e))?;
 
     let mut proposal_status = ProposalStatus::Pending;
     if decision == ApprovalDecision::Rejected {
@@ -564,10 +531,7 @@ pub async fn sign_proposal(
         .bind(payload.comment.as_deref())
         .fetch_one(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to reject proposal");
-            ApiError::db_error("Failed to update proposal status")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("reject proposal", e))?;
 
         proposal_status = updated_status;
         metrics::MULTISIG_REJECTIONS.inc();
@@ -583,10 +547,7 @@ pub async fn sign_proposal(
         .bind(proposal_id)
         .fetch_optional(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to approve proposal");
-            ApiError::db_error("Failed to update proposal status")
-        })?
+        .map_err(|e| ApiError::from_sqlx("approve proposal", e))?
         .unwrap_or(ProposalStatus::Approved);
 
         proposal_status = updated_status;
@@ -605,16 +566,12 @@ pub async fn sign_proposal(
         }))
         .execute(&mut *tx)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to insert proposal_approved audit event");
-            ApiError::db_error("Failed to record audit trail")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("insert proposal_approved audit event", e))?;
     }
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to commit signing transaction");
-        ApiError::db_error("Failed to finalize signature")
-    })?;
+    tx.commit()
+        .await
+        .map_err(|e| ApiError::from_sqlx("commit signing transaction", e))?;
 
     let threshold_met = proposal_status == ProposalStatus::Approved;
     let signatures_needed =
@@ -636,10 +593,11 @@ pub async fn execute_proposal(
         ApiError::bad_request("InvalidProposalId", "proposal id must be a valid UUID")
     })?;
 
-    let mut tx = state.db.begin().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to start execute transaction");
-        ApiError::db_error("Failed to execute proposal")
-    })?;
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| ApiError::from_sqlx("start execute transaction", e))?;
 
     let proposal = sqlx::query_as::<_, (String, String, ProposalStatus)>(
         "SELECT contract_id, wasm_hash, status
@@ -650,10 +608,7 @@ pub async fn execute_proposal(
     .bind(proposal_id)
     .fetch_optional(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to fetch proposal for execution");
-        ApiError::db_error("Failed to load proposal")
-    })?
+    .map_err(|e| ApiError::from_sqlx("fetch proposal for execution", e))?
     .ok_or_else(|| ApiError::not_found("ProposalNotFound", "deployment proposal not found"))?;
 
     let (contract_id, wasm_hash, status) = proposal;
@@ -676,10 +631,7 @@ pub async fn execute_proposal(
     .bind(proposal_id)
     .fetch_one(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to mark proposal executed");
-        ApiError::db_error("Failed to execute proposal")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("mark proposal executed", e))?;
 
     sqlx::query(
         "INSERT INTO multisig_approval_audit_events (
@@ -694,15 +646,11 @@ pub async fn execute_proposal(
     }))
     .execute(&mut *tx)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to insert execution audit event");
-        ApiError::db_error("Failed to record audit trail")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("insert execution audit event", e))?;
 
-    tx.commit().await.map_err(|e| {
-        tracing::error!(error = ?e, "failed to commit execute transaction");
-        ApiError::db_error("Failed to finalize execution")
-    })?;
+    tx.commit()
+        .await
+        .map_err(|e| ApiError::from_sqlx("commit execute transaction", e))?;
 
     metrics::MULTISIG_EXECUTIONS.inc();
 
@@ -732,10 +680,7 @@ pub async fn proposal_info(
     .bind(proposal_id)
     .fetch_optional(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to load proposal info");
-        ApiError::db_error("Failed to load proposal")
-    })?
+    .map_err(|e| ApiError::from_sqlx("load proposal info", e))?
     .ok_or_else(|| ApiError::not_found("ProposalNotFound", "deployment proposal not found"))?;
 
     let policy = sqlx::query_as::<_, MultisigPolicy>(
@@ -746,10 +691,7 @@ pub async fn proposal_info(
     .bind(proposal.policy_id)
     .fetch_one(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to load policy for proposal info");
-        ApiError::db_error("Failed to load proposal policy")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("load policy for proposal info", e))?;
 
     let signatures = sqlx::query_as::<_, ProposalSignature>(
         "SELECT
@@ -767,10 +709,7 @@ pub async fn proposal_info(
     .bind(proposal_id)
     .fetch_all(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to load proposal signatures");
-        ApiError::db_error("Failed to load proposal signatures")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("load proposal signatures", e))?;
 
     let signatures_collected: i64 = sqlx::query_scalar(
         "SELECT COUNT(*) FROM proposal_signatures
@@ -779,10 +718,7 @@ pub async fn proposal_info(
     .bind(proposal_id)
     .fetch_one(&state.db)
     .await
-    .map_err(|e| {
-        tracing::error!(error = ?e, "failed to count approved signatures");
-        ApiError::db_error("Failed to evaluate proposal threshold")
-    })?;
+    .map_err(|e| ApiError::from_sqlx("count approved signatures", e))?;
 
     let signatures_needed = (i64::from(proposal.required_approvals) - signatures_collected).max(0);
 
@@ -817,10 +753,7 @@ pub async fn list_proposals(
         .bind(status)
         .fetch_one(&state.db)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to count proposals");
-            ApiError::db_error("Failed to load proposals")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("count proposals", e))?;
 
         let items = sqlx::query_as::<_, DeployProposal>(
             "SELECT
@@ -836,20 +769,14 @@ pub async fn list_proposals(
         .bind(limit)
         .fetch_all(&state.db)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to list proposals");
-            ApiError::db_error("Failed to load proposals")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("list proposals", e))?;
 
         (items, total)
     } else {
         let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM deploy_proposals")
             .fetch_one(&state.db)
             .await
-            .map_err(|e| {
-                tracing::error!(error = ?e, "failed to count proposals");
-                ApiError::db_error("Failed to load proposals")
-            })?;
+            .map_err(|e| ApiError::from_sqlx("count proposals", e))?;
 
         let items = sqlx::query_as::<_, DeployProposal>(
             "SELECT
@@ -863,10 +790,7 @@ pub async fn list_proposals(
         .bind(limit)
         .fetch_all(&state.db)
         .await
-        .map_err(|e| {
-            tracing::error!(error = ?e, "failed to list proposals");
-            ApiError::db_error("Failed to load proposals")
-        })?;
+        .map_err(|e| ApiError::from_sqlx("list proposals", e))?;
 
         (items, total)
     };
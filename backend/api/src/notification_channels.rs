@@ -0,0 +1,453 @@
+//! Verification and health of user-registered notification channels.
+//!
+//! A channel starts out `pending` and receives nothing until its owner proves
+//! control of it. A webhook is sent a `channel.verification` POST carrying a
+//! challenge and must answer 2xx with the challenge as its body (plain, or as
+//! `{"challenge": "..."}`). An email address is sent a confirmation link
+//! signed with the API's JWT secret that expires after
+//! [`CONFIRMATION_TTL_HOURS`]. Starting verification again replaces the
+//! challenge, so older links stop working.
+//!
+//! Every attempt is logged in `user_notification_channel_attempts`. A failed
+//! delivery marks the channel `failing`; after [`FAILURE_LIMIT`] failures in a
+//! row it is `disabled`, its pending deliveries are failed, and it has to be
+//! verified again before it is used.
+
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgExecutor, Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::channel_models::UserChannel;
+use crate::incident_handlers::public_base_url;
+use crate::incident_models::RenderedNotification;
+use crate::incident_notifications::{send_email, SEND_TIMEOUT};
+use crate::outbound_http::OutboundClient;
+use crate::state::AppState;
+
+/// Consecutive failed deliveries that disable a channel
+pub const FAILURE_LIMIT: i32 = 10;
+/// How long an emailed confirmation link stays valid
+pub const CONFIRMATION_TTL_HOURS: i64 = 48;
+/// Attempts kept per channel; older ones are pruned as new ones are logged
+const ATTEMPT_HISTORY: i64 = 200;
+/// Channel states that receive deliveries
+pub const ACTIVE_STATUSES: [&str; 2] = ["verified", "failing"];
+const CONFIRMATION_PURPOSE: &str = "channel-confirm";
+
+/// Claims of a confirmation link. Lacking `sub`, the token can never pass as
+/// an API token.
+#[derive(Debug, Serialize, Deserialize)]
+struct ConfirmationClaims {
+    channel: Uuid,
+    nonce: String,
+    purpose: String,
+    exp: i64,
+}
+
+fn new_token() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect()
+}
+
+fn sign_confirmation(secret: &str, channel_id: Uuid, nonce: &str) -> Result<String, String> {
+    let claims = ConfirmationClaims {
+        channel: channel_id,
+        nonce: nonce.to_string(),
+        purpose: CONFIRMATION_PURPOSE.to_string(),
+        exp: (Utc::now() + Duration::hours(CONFIRMATION_TTL_HOURS)).timestamp(),
+    };
+    encode(
+        &Header::default(),
+        &claims,
+        &EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .map_err(|e| format!("could not sign confirmation link: {}", e))
+}
+
+/// The channel and nonce of a confirmation token, if it is genuine and has
+/// not expired
+pub fn read_confirmation(secret: &str, token: &str) -> Option<(Uuid, String)> {
+    let claims = decode::<ConfirmationClaims>(
+        token,
+        &DecodingKey::from_secret(secret.as_bytes()),
+        &Validation::new(Algorithm::HS256),
+    )
+    .ok()?
+    .claims;
+    (claims.purpose == CONFIRMATION_PURPOSE).then_some((claims.channel, claims.nonce))
+}
+
+/// Whether a webhook's answer to a challenge echoes it back
+fn echoes_challenge(body: &str, challenge: &str) -> bool {
+    let body = body.trim();
+    if body == challenge {
+        return true;
+    }
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|value| value["challenge"].as_str().map(|c| c == challenge))
+        .unwrap_or(false)
+}
+
+/// One attempt to log against a channel
+pub struct Attempt<'a> {
+    pub purpose: &'a str, // 'verification' | 'delivery'
+    pub delivery_id: Option<Uuid>,
+    pub subject: Option<&'a str>,
+    pub status_code: Option<u16>,
+    pub error: Option<&'a str>,
+}
+
+pub async fn log_attempt(
+    tx: &mut Transaction<'_, Postgres>,
+    channel_id: Uuid,
+    attempt: &Attempt<'_>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO user_notification_channel_attempts
+             (channel_id, purpose, delivery_id, subject, succeeded, status_code, error)
+         VALUES ($1, $2, $3, $4, $5, $6, $7)",
+    )
+    .bind(channel_id)
+    .bind(attempt.purpose)
+    .bind(attempt.delivery_id)
+    .bind(attempt.subject)
+    .bind(attempt.error.is_none())
+    .bind(attempt.status_code.map(i32::from))
+    .bind(attempt.error)
+    .execute(&mut **tx)
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM user_notification_channel_attempts
+         WHERE channel_id = $1 AND id NOT IN (
+             SELECT id FROM user_notification_channel_attempts
+             WHERE channel_id = $1
+             ORDER BY attempted_at DESC
+             LIMIT $2
+         )",
+    )
+    .bind(channel_id)
+    .bind(ATTEMPT_HISTORY)
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(())
+}
+
+/// Records the outcome of a delivery on its channel. Returns whether this
+/// failure disabled the channel.
+pub async fn record_delivery(
+    tx: &mut Transaction<'_, Postgres>,
+    channel_id: Uuid,
+    attempt: &Attempt<'_>,
+) -> Result<bool, sqlx::Error> {
+    log_attempt(tx, channel_id, attempt).await?;
+
+    let Some(error) = attempt.error else {
+        sqlx::query(
+            "UPDATE user_notification_channels
+             SET status = 'verified', consecutive_failures = 0, last_error = NULL,
+                 last_delivered_at = NOW(), updated_at = NOW()
+             WHERE id = $1 AND status = ANY($2)",
+        )
+        .bind(channel_id)
+        .bind(&ACTIVE_STATUSES[..])
+        .execute(&mut **tx)
+        .await?;
+        return Ok(false);
+    };
+
+    let disabled: Option<bool> = sqlx::query_scalar(
+        "UPDATE user_notification_channels
+         SET consecutive_failures = consecutive_failures + 1, last_error = $2,
+             status = CASE WHEN consecutive_failures + 1 >= $3 THEN 'disabled' ELSE 'failing' END,
+             disabled_at = CASE WHEN consecutive_failures + 1 >= $3 THEN NOW() END,
+             updated_at = NOW()
+         WHERE id = $1 AND status = ANY($4)
+         RETURNING status = 'disabled'",
+    )
+    .bind(channel_id)
+    .bind(error)
+    .bind(FAILURE_LIMIT)
+    .bind(&ACTIVE_STATUSES[..])
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    let disabled = disabled.unwrap_or(false);
+    if disabled {
+        fail_pending_deliveries(
+            &mut **tx,
+            channel_id,
+            "channel disabled after repeated failures",
+        )
+        .await?;
+    }
+    Ok(disabled)
+}
+
+/// Gives up on everything still queued for a channel that stopped receiving
+pub async fn fail_pending_deliveries<'e>(
+    executor: impl PgExecutor<'e>,
+    channel_id: Uuid,
+    reason: &str,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE contract_subscription_deliveries d
+         SET status = 'failed', last_error = $2
+         FROM contract_subscriptions s
+         WHERE d.subscription_id = s.id AND s.channel_id = $1 AND d.status = 'pending'",
+    )
+    .bind(channel_id)
+    .bind(reason)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+/// Puts a channel back to `pending` with a fresh challenge and sends it:
+/// a webhook is verified on the spot if it echoes the challenge, an email
+/// address is sent a confirmation link. Returns the channel as it is now.
+pub async fn start_verification(
+    state: &AppState,
+    channel: &UserChannel,
+) -> Result<UserChannel, sqlx::Error> {
+    let challenge = new_token();
+    sqlx::query(
+        "UPDATE user_notification_channels
+         SET status = 'pending', verification_token = $2, consecutive_failures = 0,
+             disabled_at = NULL, updated_at = NOW()
+         WHERE id = $1",
+    )
+    .bind(channel.id)
+    .bind(&challenge)
+    .execute(&state.db)
+    .await?;
+
+    let (status_code, result) = match channel.kind.as_str() {
+        "webhook" => send_challenge(&state.outbound, channel, &challenge).await,
+        "email" => (None, send_confirmation(state, channel, &challenge).await),
+        other => (None, Err(format!("unsupported channel kind '{}'", other))),
+    };
+
+    let mut tx = state.db.begin().await?;
+    log_attempt(
+        &mut tx,
+        channel.id,
+        &Attempt {
+            purpose: "verification",
+            delivery_id: None,
+            subject: None,
+            status_code,
+            error: result.as_ref().err().map(String::as_str),
+        },
+    )
+    .await?;
+
+    match &result {
+        Ok(()) if channel.kind == "webhook" => {
+            mark_verified(&mut *tx, channel.id, &challenge).await?;
+        }
+        // The confirmation email is out; the link finishes verification
+        Ok(()) => {
+            sqlx::query("UPDATE user_notification_channels SET last_error = NULL WHERE id = $1")
+                .bind(channel.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        Err(reason) => {
+            sqlx::query("UPDATE user_notification_channels SET last_error = $2 WHERE id = $1")
+                .bind(channel.id)
+                .bind(reason)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+    tx.commit().await?;
+
+    tracing::info!(
+        channel_id = %channel.id,
+        kind = %channel.kind,
+        ok = result.is_ok(),
+        "notification channel verification started"
+    );
+
+    sqlx::query_as("SELECT * FROM user_notification_channels WHERE id = $1")
+        .bind(channel.id)
+        .fetch_one(&state.db)
+        .await
+}
+
+/// Verifies a pending channel if `challenge` is still its current one.
+/// Returns whether it was.
+pub async fn mark_verified<'e>(
+    executor: impl PgExecutor<'e>,
+    channel_id: Uuid,
+    challenge: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE user_notification_channels
+         SET status = 'verified', verified_at = NOW(), verification_token = NULL,
+             last_error = NULL, updated_at = NOW()
+         WHERE id = $1 AND status = 'pending' AND verification_token = $2",
+    )
+    .bind(channel_id)
+    .bind(challenge)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() == 1)
+}
+
+/// POSTs the challenge to a webhook channel. The connection only goes to a
+/// public address of its host, see [`OutboundClient::send_webhook`].
+async fn send_challenge(
+    client: &OutboundClient,
+    channel: &UserChannel,
+    challenge: &str,
+) -> (Option<u16>, Result<(), String>) {
+    let payload = serde_json::json!({
+        "event": "channel.verification",
+        "event_version": crate::webhook_events::CURRENT_PAYLOAD_VERSION,
+        "channel_id": channel.id,
+        "challenge": challenge,
+    });
+    let response = match client
+        .send_webhook(
+            client
+                .post(&channel.target)
+                .timeout(SEND_TIMEOUT)
+                .json(&payload),
+        )
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => return (None, Err(format!("webhook request failed: {}", e))),
+    };
+
+    let status = response.status();
+    if !status.is_success() {
+        return (
+            Some(status.as_u16()),
+            Err(format!("webhook returned {}", status)),
+        );
+    }
+    let body = response.text().await.unwrap_or_default();
+    let result = if echoes_challenge(&body, challenge) {
+        Ok(())
+    } else {
+        Err("webhook did not echo the challenge in its response body".to_string())
+    };
+    (Some(status.as_u16()), result)
+}
+
+async fn send_confirmation(
+    state: &AppState,
+    channel: &UserChannel,
+    nonce: &str,
+) -> Result<(), String> {
+    let token = sign_confirmation(state.config.jwt_secret.expose(), channel.id, nonce)?;
+    let link = format!(
+        "{}/api/channels/confirm?token={}",
+        public_base_url().trim_end_matches('/'),
+        token
+    );
+    let notification = RenderedNotification {
+        subject: "Confirm your Soroban Registry notification address".to_string(),
+        body: format!(
+            "Someone, hopefully you, asked for Soroban Registry notifications to be sent to {}.\n\n\
+             Open this link within {} hours to confirm:\n{}\n\n\
+             If this wasn't you, ignore this email and nothing will be sent.\n",
+            channel.target, CONFIRMATION_TTL_HOURS, link
+        ),
+        payload: serde_json::Value::Null,
+    };
+    send_email(&channel.target, &notification).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outbound_http::OutboundConfig;
+    use axum::{routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn challenge_may_be_echoed_plain_or_as_json() {
+        assert!(echoes_challenge("abc123\n", "abc123"));
+        assert!(echoes_challenge(r#"{"challenge": "abc123"}"#, "abc123"));
+        assert!(!echoes_challenge(r#"{"challenge": "other"}"#, "abc123"));
+        assert!(!echoes_challenge("", "abc123"));
+    }
+
+    #[test]
+    fn confirmation_tokens_round_trip_and_cannot_authenticate() {
+        let secret = "s".repeat(32);
+        let channel_id = Uuid::new_v4();
+        let token = sign_confirmation(&secret, channel_id, "nonce").unwrap();
+
+        assert_eq!(
+            read_confirmation(&secret, &token),
+            Some((channel_id, "nonce".to_string()))
+        );
+        assert_eq!(read_confirmation(&"t".repeat(32), &token), None);
+        assert!(crate::auth::AuthManager::new(secret)
+            .validate_jwt(&token)
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn challenges_only_reach_public_addresses() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = Router::new().route(
+            "/",
+            post(move |body: String| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let challenge: serde_json::Value = serde_json::from_str(&body).unwrap();
+                async move { challenge["challenge"].as_str().unwrap().to_string() }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let now = Utc::now();
+        let channel = UserChannel {
+            id: Uuid::new_v4(),
+            owner: "owner".to_string(),
+            name: None,
+            kind: "webhook".to_string(),
+            target: format!("http://localhost:{port}/"),
+            status: "pending".to_string(),
+            verified_at: None,
+            consecutive_failures: 0,
+            last_error: None,
+            last_delivered_at: None,
+            disabled_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let client = OutboundClient::new(OutboundConfig::default());
+        let (status, result) = send_challenge(&client, &channel, "abc123").await;
+        assert_eq!(status, None);
+        assert!(result.unwrap_err().contains("not a public address"));
+        assert_eq!(hits.load(Ordering::SeqCst), 0);
+
+        let permissive = OutboundClient::new(OutboundConfig {
+            allow_private_webhooks: true,
+            ..OutboundConfig::default()
+        });
+        let (status, result) = send_challenge(&permissive, &channel, "abc123").await;
+        assert_eq!(status, Some(200));
+        assert_eq!(result, Ok(()));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,6 +1,5 @@
 use std::time::Duration;
 
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use shared::{Contract, Network, RegistryError};
 use stellar_strkey::{Contract as ContractStrkey, Strkey};
@@ -11,13 +10,10 @@ use stellar_xdr::curr::{
 };
 
 use crate::cache::CacheLayer;
+use crate::config::RpcConfig;
+use crate::outbound_http::{OutboundClient, OutboundError};
 use crate::type_safety::parser::parse_json_spec;
 
-const DEFAULT_RPC_MAINNET: &str = "https://mainnet.sorobanrpc.com";
-const DEFAULT_RPC_TESTNET: &str = "https://soroban-testnet.stellar.org";
-const DEFAULT_RPC_FUTURENET: &str = "https://rpc-futurenet.stellar.org";
-const DEFAULT_RPC_TIMEOUT_SECS: u64 = 15;
-const DEFAULT_RPC_MAX_RETRIES: u32 = 3;
 const DEFAULT_ACTIVITY_LOOKBACK_LEDGERS: u32 = 2_000;
 const DEFAULT_ACTIVITY_LIMIT: u32 = 25;
 
@@ -57,47 +53,30 @@ struct NetworkConfig {
 }
 
 impl NetworkConfig {
-    fn from_env(network: &Network) -> Self {
+    fn for_network(rpc: &RpcConfig, network: &Network) -> Self {
         let rpc_endpoint = match network {
-            Network::Mainnet => std::env::var("SOROBAN_RPC_MAINNET")
-                .unwrap_or_else(|_| DEFAULT_RPC_MAINNET.to_string()),
-            Network::Testnet => std::env::var("SOROBAN_RPC_TESTNET")
-                .unwrap_or_else(|_| DEFAULT_RPC_TESTNET.to_string()),
-            Network::Futurenet => std::env::var("SOROBAN_RPC_FUTURENET")
-                .unwrap_or_else(|_| DEFAULT_RPC_FUTURENET.to_string()),
+            Network::Mainnet => &rpc.mainnet,
+            Network::Testnet => &rpc.testnet,
+            Network::Futurenet => &rpc.futurenet,
         };
-        let timeout_secs = std::env::var("SOROBAN_RPC_TIMEOUT_SECS")
-            .ok()
-            .and_then(|value| value.parse::<u64>().ok())
-            .filter(|value| *value > 0)
-            .unwrap_or(DEFAULT_RPC_TIMEOUT_SECS);
-        let max_retries = std::env::var("SOROBAN_RPC_MAX_RETRIES")
-            .ok()
-            .and_then(|value| value.parse::<u32>().ok())
-            .filter(|value| *value > 0)
-            .unwrap_or(DEFAULT_RPC_MAX_RETRIES);
 
         Self {
-            rpc_endpoint,
-            timeout: Duration::from_secs(timeout_secs),
-            max_retries,
+            rpc_endpoint: rpc_endpoint.clone(),
+            timeout: rpc.timeout,
+            max_retries: rpc.max_retries,
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct OnChainVerifier {
-    client: Client,
+    client: OutboundClient,
+    rpc: RpcConfig,
 }
 
 impl OnChainVerifier {
-    pub fn new() -> Self {
-        Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(DEFAULT_RPC_TIMEOUT_SECS))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
-        }
+    pub fn new(client: OutboundClient, rpc: RpcConfig) -> Self {
+        Self { client, rpc }
     }
 
     pub async fn verify_contract(
@@ -119,7 +98,7 @@ impl OnChainVerifier {
             return Ok(parsed);
         }
 
-        let config = NetworkConfig::from_env(&contract.network);
+        let config = NetworkConfig::for_network(&self.rpc, &contract.network);
         let latest_ledger = self.get_latest_ledger(&config).await.ok();
 
         let mut warnings = Vec::new();
@@ -412,10 +391,12 @@ impl OnChainVerifier {
         for attempt in 0..config.max_retries {
             let response = self
                 .client
-                .post(&config.rpc_endpoint)
-                .timeout(config.timeout)
-                .json(&payload)
-                .send()
+                .send(
+                    self.client
+                        .post(&config.rpc_endpoint)
+                        .timeout(config.timeout)
+                        .json(&payload),
+                )
                 .await;
 
             match response {
@@ -445,6 +426,13 @@ impl OnChainVerifier {
                         )));
                     }
                 }
+                // Retrying can't help while the endpoint is being skipped
+                Err(err @ OutboundError::CircuitOpen { .. }) => {
+                    return Err(RegistryError::StellarRpc(format!(
+                        "{} skipped: {}",
+                        method, err
+                    )));
+                }
                 Err(err) => {
                     if attempt + 1 >= config.max_retries {
                         return Err(RegistryError::StellarRpc(format!(
@@ -584,6 +572,16 @@ mod tests {
             is_maintenance: false,
             logical_id: None,
             network_configs: None,
+            verified_at: None,
+            last_accessed_at: None,
+            relevance_score: None,
+            organization_id: None,
+            visibility: shared::VisibilityType::Public,
+            namespace: None,
+            slug: None,
+            publish_status: shared::PublishStatus::Published,
+            revision: 0,
+            freshness: shared::Freshness::default(),
         };
 
         assert_eq!(
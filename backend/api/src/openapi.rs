@@ -17,30 +17,44 @@ use utoipa::OpenApi;
         handlers::get_contracts_batch,
         handlers::get_contract,
         handlers::get_contract_versions,
+        crate::slug_handlers::resolve_contract,
         handlers::get_contract_changelog,
         handlers::get_trust_score,
         // `get_contract_state` / `update_contract_state` are currently stubs without
         // `#[utoipa::path]`, and break OpenAPI generation. Omit until implemented.
         handlers::create_contract_version,
+        crate::interface_compat::get_version_compat,
+        crate::interface_compat::preview_version_compat,
+        crate::wasm_compare::compare_version_wasm,
+        crate::contract_profile::get_version_profile,
+        crate::profile_uploads::upload_profile,
+        crate::profile_uploads::list_profiles,
         handlers::publish_contract,
         handlers::create_publisher,
         handlers::get_publisher,
         handlers::get_publisher_contracts,
+        handlers::list_my_contracts,
         handlers::get_contract_abi,
         handlers::get_contract_openapi_yaml,
         handlers::get_contract_openapi_json,
+        crate::badge_handlers::get_contract_badge_svg,
+        crate::badge_handlers::get_contract_badge_json,
         handlers::get_contract_analytics,
         handlers::get_contract_dependencies,
         handlers::get_contract_dependents,
         handlers::get_contract_graph,
+        crate::contract_changes::get_contract_changes,
         handlers::get_impact_analysis,
         handlers::get_trending_contracts,
         similarity_handlers::get_similar_contracts,
         similarity_handlers::analyze_contract_similarity_batch,
         handlers::verify_contract,
+        crate::build_info_handlers::get_build_info,
+        crate::build_info_handlers::get_sbom,
         handlers::update_contract_metadata,
         handlers::change_contract_publisher,
         handlers::update_contract_status,
+        crate::contract_freshness_handlers::confirm_contract,
         handlers::get_contract_audit_log,
         handlers::get_all_audit_logs,
         handlers::get_deployment_status,
@@ -52,6 +66,7 @@ use utoipa::OpenApi;
         crate::auth_handlers::get_challenge,
         crate::auth_handlers::verify_challenge,
         breaking_changes::get_breaking_changes,
+        crate::compare_handlers::compare_contracts,
         custom_metrics_handlers::get_metric_catalog,
         custom_metrics_handlers::get_contract_metrics,
         custom_metrics_handlers::record_contract_metric,
@@ -63,20 +78,156 @@ use utoipa::OpenApi;
         handlers::reviews::get_reviews,
         handlers::reviews::create_review,
         handlers::reviews::vote_review,
+        handlers::reviews::toggle_helpful_vote,
         handlers::reviews::flag_review,
         handlers::reviews::moderate_review,
         handlers::reviews::get_rating_stats,
+        // Incidents
+        crate::incident_handlers::create_incident,
+        crate::incident_handlers::list_incidents,
+        crate::incident_handlers::get_incident,
+        crate::incident_handlers::update_incident,
+        crate::incident_handlers::delete_incident,
+        crate::incident_handlers::create_incident_update,
+        crate::incident_handlers::list_incident_updates,
+        crate::incident_handlers::acknowledge_incident,
+        crate::incident_handlers::preview_incident_notification,
+        crate::incident_handlers::get_incident_metrics,
+        crate::incident_heatmap::get_incident_heatmap,
+        crate::incident_import::import_incidents,
+        crate::incident_handlers::list_contract_incidents,
+        crate::incident_handlers::get_incident_report,
+        crate::incident_handlers::get_status,
+        crate::incident_handlers::get_incident_feed,
+        crate::incident_handlers::put_incident_postmortem,
+        crate::incident_handlers::update_action_item,
+        crate::incident_handlers::list_action_items,
+        crate::incident_handlers::create_recovery_validation,
+        crate::incident_handlers::list_recovery_validations,
+        // Maintenance windows
+        crate::maintenance_handlers::create_maintenance_window,
+        crate::maintenance_handlers::list_maintenance_windows,
+        crate::maintenance_handlers::get_maintenance_window,
+        crate::maintenance_handlers::update_maintenance_window,
+        crate::maintenance_handlers::delete_maintenance_window,
+        // Recovery objectives
+        crate::recovery_objective_handlers::list_recovery_objectives,
+        crate::recovery_objective_handlers::create_recovery_objective,
+        crate::recovery_objective_handlers::get_recovery_objective,
+        crate::recovery_objective_handlers::update_recovery_objective,
+        crate::recovery_objective_handlers::delete_recovery_objective,
+        // Escalation policies
+        crate::escalation_policy_handlers::list_escalation_policies,
+        crate::escalation_policy_handlers::create_escalation_policy,
+        crate::escalation_policy_handlers::get_escalation_policy,
+        crate::escalation_policy_handlers::update_escalation_policy,
+        crate::escalation_policy_handlers::delete_escalation_policy,
+        // Security advisories
+        crate::advisory_handlers::list_advisories,
+        crate::advisory_handlers::create_advisory,
+        crate::advisory_handlers::get_advisory,
+        crate::advisory_handlers::update_advisory,
+        crate::advisory_handlers::delete_advisory,
+        crate::advisory_handlers::list_affected_contracts,
+        crate::advisory_handlers::list_contract_security_notices,
+        // Registry snapshots
+        crate::publish_review_handlers::list_publish_reviews,
+        crate::publish_review_handlers::approve_publish,
+        crate::publish_review_handlers::reject_publish,
+        crate::registry_snapshot_handlers::create_snapshot,
+        crate::registry_snapshot_handlers::list_snapshots,
+        crate::registry_snapshot_handlers::download_snapshot,
+        crate::registry_snapshot_handlers::restore_snapshot,
+        crate::job_handlers::get_job,
+        crate::job_handlers::list_failed_jobs,
+        crate::job_handlers::retry_job,
+        crate::retention_handlers::get_retention,
+        crate::search_weights::get_search_weights,
+        crate::search_weights::update_search_weights,
+        crate::legal_holds::list_holds,
+        crate::legal_holds::create_hold,
+        crate::legal_holds::release_hold,
+        // CLI telemetry
+        crate::telemetry_handlers::submit_telemetry,
+        crate::telemetry_handlers::get_telemetry,
+        // Contract subscriptions
+        crate::subscription_handlers::create_subscription,
+        crate::subscription_handlers::list_contract_subscriptions,
+        crate::subscription_handlers::delete_contract_subscriptions,
+        crate::subscription_handlers::list_my_subscriptions,
+        crate::subscription_handlers::delete_my_subscription,
+        // Webhook events
+        crate::webhook_handlers::list_webhook_events,
+        crate::webhook_handlers::test_webhook,
+        // Saved searches
+        crate::saved_search_handlers::save_search,
+        crate::saved_search_handlers::list_my_searches,
+        crate::saved_search_handlers::get_saved_search,
+        crate::saved_search_handlers::get_saved_search_results,
+        crate::saved_search_handlers::delete_saved_search,
+        // Notification channels
+        crate::channel_handlers::create_channel,
+        crate::channel_handlers::list_channels,
+        crate::channel_handlers::get_channel,
+        crate::channel_handlers::update_channel,
+        crate::channel_handlers::delete_channel,
+        crate::channel_handlers::verify_channel,
+        crate::channel_handlers::list_channel_deliveries,
+        crate::channel_handlers::confirm_channel,
+        // Categories
+        crate::category_handlers::list_categories,
+        // Tags
+        crate::tag_handlers::list_tags,
+        crate::tag_handlers::create_tag_alias,
+        crate::tag_handlers::delete_tag_alias,
+        crate::tag_handlers::bulk_update_my_tags,
+        crate::analytics_handlers::get_analytics_timeseries,
     ),
     components(
         schemas(
+            crate::error::ErrorResponse,
+            crate::error::ErrorBody,
             Contract,
+            shared::FreshnessView,
+            shared::search::ContractSearchHit,
+            shared::search::SearchHighlight,
+            shared::search::HighlightRange,
+            shared::slug::ResolvedContract,
             ContractGetResponse,
+            handlers::BatchContractsResponse,
+            ContractIncidentSummary,
+            SecurityNotice,
             NetworkConfig,
             Network,
             UpgradeStrategy,
             ContractVersion,
+            handlers::CreatedContractVersion,
+            crate::interface_compat::VersionCompat,
+            crate::interface_compat::Compatibility,
+            crate::interface_compat::CompatPreviewRequest,
+            crate::wasm_compare::WasmComparison,
+            crate::wasm_compare::ComparedBinary,
+            crate::wasm_compare::BinaryDiff,
+            crate::wasm_compare::SectionDelta,
+            crate::wasm_compare::FunctionMatching,
+            crate::wasm_compare::FunctionRef,
+            crate::wasm_compare::FunctionChange,
+            crate::wasm_compare::FunctionDiff,
+            crate::wasm_compare::SegmentChangeKind,
+            crate::wasm_compare::DataSegmentChange,
+            crate::wasm_compare::DataSegmentDiff,
+            crate::contract_profile::VersionProfile,
+            shared::ContractProfileSummary,
+            shared::MethodCost,
+            shared::UploadedProfileSummary,
+            crate::profile_uploads::UploadProfileRequest,
+            crate::profile_uploads::UploadedProfile,
+            crate::profile_uploads::UploadedProfileEntry,
+            crate::profile_uploads::UploadedProfileList,
             Verification,
             VerificationStatus,
+            BuildMetadata,
+            ContractBuildInfo,
             MaturityLevel,
             Publisher,
             ContractStats,
@@ -84,6 +235,7 @@ use utoipa::OpenApi;
             GraphEdge,
             GraphResponse,
             PublishRequest,
+            PublishStatus,
             MigrationScript,
             DeploymentEnvironment,
             CanaryRelease,
@@ -101,6 +253,8 @@ use utoipa::OpenApi;
             CustomMetric,
             PerformanceAnomaly,
             crate::handlers::ContractAuditLogEntry,
+            crate::handlers::OwnedContract,
+            crate::badge::ShieldsEndpoint,
             ContractInteraction,
             ContractDependency,
             ImpactAnalysisResponse,
@@ -127,6 +281,11 @@ use utoipa::OpenApi;
             breaking_changes::ChangeSeverity,
             breaking_changes::BreakingChange,
             breaking_changes::BreakingChangeReport,
+            crate::compare_handlers::ComparedContract,
+            crate::compare_handlers::FieldChange,
+            crate::compare_handlers::SignatureChange,
+            crate::compare_handlers::InterfaceDiff,
+            crate::compare_handlers::ContractComparison,
             ContractChangelogEntry,
             ContractChangelogResponse,
             custom_metrics_handlers::MetricSeriesResponse,
@@ -141,10 +300,152 @@ use utoipa::OpenApi;
             CreateReviewRequest,
             ReviewVoteRequest,
             ReviewVoteResponse,
+            ReviewHelpfulResponse,
             FlagReviewRequest,
             ModerateReviewRequest,
             ContractRatingStats,
             RatingDistribution,
+            // Incidents
+            crate::incident_models::Incident,
+            crate::incident_models::CreateIncidentRequest,
+            crate::incident_models::UpdateIncidentRequest,
+            crate::incident_models::IncidentSeverity,
+            crate::incident_models::AffectedContract,
+            crate::incident_models::IncidentDetail,
+            crate::incident_models::IncidentUpdate,
+            crate::incident_models::IncidentUpdateStatus,
+            crate::incident_models::CreateIncidentUpdateRequest,
+            crate::incident_models::NotificationChannel,
+            crate::incident_models::NotificationDelivery,
+            crate::incident_models::RenderedNotification,
+            crate::incident_models::NotificationPreview,
+            crate::incident_models::IncidentMetrics,
+            crate::incident_models::IncidentCount,
+            crate::incident_models::ObjectiveCompliance,
+            crate::incident_models::LongestOpenIncident,
+            crate::incident_models::MonthlyIncidentMetrics,
+            crate::incident_heatmap::IncidentHeatmap,
+            crate::incident_heatmap::HeatmapBucket,
+            crate::incident_heatmap::HeatmapRow,
+            crate::incident_heatmap::HeatmapCell,
+            crate::incident_heatmap::HeatmapContract,
+            crate::incident_import::ImportIncidentRecord,
+            crate::incident_import::ImportLineStatus,
+            crate::incident_import::ImportLineOutcome,
+            crate::incident_import::IncidentImportReport,
+            crate::incident_models::RecoveryDuration,
+            crate::incident_models::DurationInput,
+            crate::incident_models::RegistryStatus,
+            crate::incident_models::OverallStatus,
+            crate::incident_models::PublicIncident,
+            crate::incident_models::UptimeSummary,
+            crate::incident_models::Postmortem,
+            crate::incident_models::IncidentActionItem,
+            crate::incident_models::ActionItemInput,
+            crate::incident_models::UpdatePostmortemRequest,
+            crate::incident_models::UpdateActionItemRequest,
+            crate::incident_models::RecoveryCheck,
+            crate::incident_models::RecoveryValidation,
+            crate::incident_models::CreateRecoveryValidationRequest,
+            crate::incident_models::RecoveryValidationCoverage,
+            // Maintenance windows
+            crate::maintenance_models::MaintenanceWindow,
+            crate::maintenance_models::MaintenanceWindowStatus,
+            crate::maintenance_models::CreateMaintenanceWindowRequest,
+            crate::maintenance_models::UpdateMaintenanceWindowRequest,
+            // Recovery objectives
+            crate::recovery_objective_models::RecoveryObjective,
+            crate::recovery_objective_models::CreateRecoveryObjectiveRequest,
+            crate::recovery_objective_models::UpdateRecoveryObjectiveRequest,
+            crate::recovery_objective_models::ObjectiveSource,
+            crate::recovery_objective_models::ObjectiveOutcome,
+            // Escalation policies
+            crate::escalation_policy_models::EscalationPolicy,
+            crate::escalation_policy_models::EscalationStep,
+            crate::escalation_policy_models::EscalationStepInput,
+            crate::escalation_policy_models::CreateEscalationPolicyRequest,
+            crate::escalation_policy_models::UpdateEscalationPolicyRequest,
+            crate::incident_models::EscalationState,
+            // Security advisories
+            crate::advisory_models::Advisory,
+            crate::advisory_models::AdvisoryComponent,
+            crate::advisory_models::AdvisorySeverity,
+            crate::advisory_models::CreateAdvisoryRequest,
+            crate::advisory_models::UpdateAdvisoryRequest,
+            crate::advisory_models::AffectedContract,
+            crate::contract_changes::ChangeGroup,
+            crate::contract_changes::ContractChange,
+            crate::contract_changes::ContractChanges,
+            // Registry snapshots
+            crate::publish_review_handlers::PublishDecisionRequest,
+            crate::publish_review_handlers::PublishFlagView,
+            crate::publish_review_handlers::PublishReviewItem,
+            crate::registry_snapshot_models::RegistrySnapshot,
+            crate::registry_snapshot_models::RestoreSnapshotRequest,
+            crate::registry_snapshot_models::TableRestore,
+            crate::registry_snapshot_models::RestoreReport,
+            // Background jobs
+            crate::jobs::Job,
+            crate::retention::RetentionTable,
+            crate::retention::PurgeAction,
+            crate::retention::RetentionRun,
+            crate::retention_handlers::RetentionPolicy,
+            crate::retention_handlers::RetentionStatus,
+            crate::search_weights::SearchWeights,
+            crate::search_weights::UpdateSearchWeightsRequest,
+            crate::legal_holds::HoldTarget,
+            crate::legal_holds::LegalHold,
+            crate::legal_holds::LegalHoldList,
+            crate::legal_holds::CreateHoldRequest,
+            crate::legal_holds::ReleaseHoldRequest,
+            // CLI telemetry
+            crate::telemetry_handlers::TelemetryEvent,
+            crate::telemetry_handlers::TelemetryReport,
+            crate::telemetry_handlers::TelemetryAccepted,
+            crate::telemetry_handlers::CommandUsage,
+            crate::telemetry_handlers::FlagUsage,
+            crate::telemetry_handlers::TelemetrySummary,
+            // Contract subscriptions
+            crate::subscription_models::SubscriptionEvent,
+            crate::subscription_models::ContractSubscription,
+            crate::subscription_models::CreateSubscriptionRequest,
+            crate::subscription_models::SubscriptionDelivery,
+            crate::subscription_models::SubscriptionDetail,
+            // Webhook events
+            crate::webhook_handlers::PayloadVersionStatus,
+            crate::webhook_handlers::PayloadVersionInfo,
+            crate::webhook_handlers::WebhookEventType,
+            crate::webhook_handlers::WebhookEventCatalog,
+            crate::webhook_handlers::WebhookTestRequest,
+            crate::webhook_handlers::WebhookTestDelivery,
+            crate::webhook_handlers::WebhookTestResult,
+            crate::saved_search_handlers::SavedSearch,
+            crate::saved_search_handlers::SaveSearchRequest,
+            // Notification channels
+            crate::channel_models::ChannelKind,
+            crate::channel_models::UserChannel,
+            crate::channel_models::CreateChannelRequest,
+            crate::channel_models::UpdateChannelRequest,
+            crate::channel_models::ChannelAttempt,
+            crate::channel_models::ChannelConfirmation,
+            // Categories
+            crate::category_handlers::CategoryResponse,
+            // Tags
+            crate::tag_handlers::TagSummary,
+            crate::tag_handlers::CreateTagAliasRequest,
+            crate::tag_handlers::TagAlias,
+            crate::tag_handlers::TagAliasCreated,
+            crate::tag_handlers::TagRename,
+            crate::tag_handlers::TagScope,
+            crate::tag_handlers::TagOperation,
+            crate::tag_handlers::BulkTagRequest,
+            crate::tag_handlers::BulkTagResult,
+            crate::tag_handlers::BulkTagResponse,
+            crate::analytics_handlers::TimeseriesMetric,
+            crate::analytics_handlers::TimeseriesInterval,
+            crate::analytics_handlers::TimeseriesPoint,
+            crate::analytics_handlers::MetricSeries,
+            crate::analytics_handlers::TimeseriesResponse,
         )
     ),
     tags(
@@ -154,7 +455,7 @@ use utoipa::OpenApi;
         (name = "Publishers", description = "Publisher management"),
         (name = "Artifacts", description = "Contract ABIs and OpenAPI specs"),
         (name = "Analytics", description = "Usage and performance metrics"),
-        (name = "Analysis", description = "Contract ABI analysis and breaking changes"),
+        (name = "Analysis", description = "Contract ABI analysis, breaking changes and comparisons"),
         (name = "Graphs", description = "Dependency graphs and impact analysis"),
         (name = "Verification", description = "Source code verification"),
         (name = "Metrics", description = "Custom application metrics"),
@@ -164,6 +465,22 @@ use utoipa::OpenApi;
         (name = "Versions", description = "Contract version history and management"),
         (name = "Security", description = "Security and trust score assessments"),
         (name = "Reviews", description = "Contract reviews and ratings"),
+        (name = "Incidents", description = "Operational incidents and recovery tracking"),
+        (name = "Maintenance Windows", description = "Planned downtime for registry components and contracts"),
+        (name = "Recovery Objectives", description = "Target RTO/RPO for incidents, globally and per contract"),
+        (name = "Escalation Policies", description = "Re-notification of unacknowledged incidents, globally and per incident type"),
+        (name = "Security Advisories", description = "Vulnerable soroban-sdk and rustc versions, and the contracts built with them"),
+        (name = "Snapshots", description = "Registry snapshots for backup and disaster recovery drills"),
+        (name = "Subscriptions", description = "Per-contract event notifications for API token holders"),
+        (name = "Saved Searches", description = "Named contract searches that can be shared by id and run again"),
+        (name = "Channels", description = "Webhooks and email addresses that receive subscription deliveries once verified"),
+        (name = "Categories", description = "The contract category taxonomy and how many contracts use each category"),
+        (name = "Tags", description = "Contract tags and the aliases rewritten to them"),
+        (name = "Jobs", description = "Background jobs queued by verification and snapshot requests"),
+        (name = "Retention", description = "How long incident, audit and notification data is kept, and the nightly purge"),
+        (name = "Search", description = "How contract fields are weighted when ranking search results"),
+        (name = "Telemetry", description = "Anonymous, opt-in CLI usage counts"),
+        (name = "Legal Holds", description = "Keep disputed contracts and incidents from being deleted or purged"),
     ),
     modifiers(&SecurityAddon)
 )]
@@ -1,13 +1,22 @@
-use crate::{error::ApiResult, handlers::db_internal_error, state::AppState};
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    pagination::Pagination,
+    state::AppState,
+};
 use axum::{
     extract::{Path, State},
     http::StatusCode,
     Json,
 };
+use chrono::Utc;
 use shared::{
-    AuthClaims, CreateOrganizationRequest, InviteMemberRequest, Organization, OrganizationMember,
-    OrganizationRole, UpdateOrganizationRequest,
+    pagination::Paginated, AddMemberRequest, Contract, CreateOrganizationRequest,
+    InviteMemberRequest, Organization, OrganizationMember, OrganizationRole,
+    UpdateOrganizationRequest,
 };
+use sqlx::PgExecutor;
 use uuid::Uuid;
 
 /// Create a new organization
@@ -17,53 +26,49 @@ pub async fn create_organization(
     Json(payload): Json<CreateOrganizationRequest>,
 ) -> ApiResult<(StatusCode, Json<Organization>)> {
     let mut tx = state
-        .pool
+        .db
         .begin()
         .await
         .map_err(|e| db_internal_error("begin_transaction", e))?;
 
     // 1. Get publisher ID from Stellar address (claims.sub)
-    let publisher_id: Uuid = sqlx::query_scalar!(
-        "SELECT id FROM publishers WHERE stellar_address = $1",
-        claims.sub
+    let publisher_id = upsert_publisher(&mut *tx, &claims.sub).await?;
+
+    // 2. Create organization
+    let org: Organization = sqlx::query_as(
+        "INSERT INTO organizations (name, slug, description, is_private)
+         VALUES ($1, $2, $3, $4)
+         RETURNING *",
     )
+    .bind(&payload.name)
+    .bind(&payload.slug)
+    .bind(&payload.description)
+    .bind(payload.is_private.unwrap_or(true))
     .fetch_one(&mut *tx)
     .await
     .map_err(|e| {
-        if matches!(e, sqlx::Error::RowNotFound) {
-            StatusCode::UNAUTHORIZED.into()
-        } else {
-            db_internal_error("get_publisher_id", e)
+        if let sqlx::Error::Database(ref db_err) = e {
+            if db_err.is_unique_violation() {
+                return ApiError::conflict(
+                    "OrganizationSlugTaken",
+                    format!(
+                        "An organization with slug '{}' already exists",
+                        payload.slug
+                    ),
+                );
+            }
         }
+        db_internal_error("create_organization", e)
     })?;
 
-    // 2. Create organization
-    let org = sqlx::query_as!(
-        Organization,
-        r#"
-        INSERT INTO organizations (name, slug, description, is_private)
-        VALUES ($1, $2, $3, $4)
-        RETURNING *
-        "#,
-        payload.name,
-        payload.slug,
-        payload.description,
-        payload.is_private.unwrap_or(true)
-    )
-    .fetch_one(&mut *tx)
-    .await
-    .map_err(|e| db_internal_error("create_organization", e))?;
-
     // 3. Add creator as Admin
-    sqlx::query!(
-        r#"
-        INSERT INTO organization_members (organization_id, publisher_id, role)
-        VALUES ($1, $2, $3)
-        "#,
-        org.id,
-        publisher_id,
-        OrganizationRole::Admin as OrganizationRole
+    sqlx::query(
+        "INSERT INTO organization_members (organization_id, publisher_id, role)
+         VALUES ($1, $2, $3)",
     )
+    .bind(org.id)
+    .bind(publisher_id)
+    .bind(OrganizationRole::Admin)
     .execute(&mut *tx)
     .await
     .map_err(|e| db_internal_error("add_org_admin", e))?;
@@ -81,43 +86,8 @@ pub async fn get_organization(
     claims: Option<AuthClaims>,
     Path(slug_or_id): Path<String>,
 ) -> ApiResult<Json<Organization>> {
-    let org_id = Uuid::parse_str(&slug_or_id).ok();
-
-    let org = if let Some(id) = org_id {
-        sqlx::query_as!(
-            Organization,
-            "SELECT * FROM organizations WHERE id = $1",
-            id
-        )
-        .fetch_optional(&state.pool)
-        .await
-    } else {
-        sqlx::query_as!(
-            Organization,
-            "SELECT * FROM organizations WHERE slug = $1",
-            slug_or_id
-        )
-        .fetch_optional(&state.pool)
-        .await
-    }
-    .map_err(|e| db_internal_error("get_organization", e))?
-    .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Visibility check for private organizations
-    if org.is_private {
-        let is_member = if let Some(ref claims) = claims {
-            check_org_role(&state.pool, org.id, &claims.sub, OrganizationRole::Viewer)
-                .await
-                .is_ok()
-        } else {
-            false
-        };
-
-        if !is_member {
-            return Err(StatusCode::FORBIDDEN.into());
-        }
-    }
-
+    let org = resolve_organization(&state.db, &slug_or_id).await?;
+    ensure_visible(&state.db, &org, claims.as_ref()).await?;
     Ok(Json(org))
 }
 
@@ -129,32 +99,91 @@ pub async fn update_organization(
     Json(payload): Json<UpdateOrganizationRequest>,
 ) -> ApiResult<Json<Organization>> {
     // Check if user is Admin of the org
-    check_org_role(&state.pool, id, &claims.sub, OrganizationRole::Admin).await?;
-
-    let org = sqlx::query_as!(
-        Organization,
-        r#"
-        UPDATE organizations
-        SET 
-            name = COALESCE($1, name),
-            description = COALESCE($2, description),
-            is_private = COALESCE($3, is_private),
-            updated_at = NOW()
-        WHERE id = $4
-        RETURNING *
-        "#,
-        payload.name,
-        payload.description,
-        payload.is_private,
-        id
+    check_org_role(&state.db, id, &claims.sub, OrganizationRole::Admin).await?;
+
+    let org: Organization = sqlx::query_as(
+        "UPDATE organizations
+            SET name = COALESCE($1, name),
+                description = COALESCE($2, description),
+                is_private = COALESCE($3, is_private),
+                updated_at = NOW()
+          WHERE id = $4
+          RETURNING *",
     )
-    .fetch_one(&state.pool)
+    .bind(&payload.name)
+    .bind(&payload.description)
+    .bind(payload.is_private)
+    .bind(id)
+    .fetch_one(&state.db)
     .await
     .map_err(|e| db_internal_error("update_organization", e))?;
 
     Ok(Json(org))
 }
 
+/// Look up an organization by UUID or, failing that, by slug
+pub async fn resolve_organization<'e>(
+    executor: impl PgExecutor<'e>,
+    slug_or_id: &str,
+) -> ApiResult<Organization> {
+    let org: Option<Organization> = match Uuid::parse_str(slug_or_id) {
+        Ok(id) => {
+            sqlx::query_as("SELECT * FROM organizations WHERE id = $1")
+                .bind(id)
+                .fetch_optional(executor)
+                .await
+        }
+        Err(_) => {
+            sqlx::query_as("SELECT * FROM organizations WHERE slug = $1")
+                .bind(slug_or_id)
+                .fetch_optional(executor)
+                .await
+        }
+    }
+    .map_err(|e| db_internal_error("get_organization", e))?;
+
+    org.ok_or_else(|| {
+        ApiError::not_found(
+            "OrganizationNotFound",
+            format!("No organization found with slug or ID: {}", slug_or_id),
+        )
+    })
+}
+
+/// Private organizations are only visible to their members
+async fn ensure_visible(
+    pool: &sqlx::PgPool,
+    org: &Organization,
+    claims: Option<&AuthClaims>,
+) -> ApiResult<()> {
+    if !org.is_private {
+        return Ok(());
+    }
+    let is_member = match claims {
+        Some(claims) => check_org_role(pool, org.id, &claims.sub, OrganizationRole::Viewer)
+            .await
+            .is_ok(),
+        None => false,
+    };
+    if is_member {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "This organization is private to its members",
+        ))
+    }
+}
+
+/// Whether `role` grants at least the permissions of `min_role`
+pub fn role_satisfies(role: &OrganizationRole, min_role: &OrganizationRole) -> bool {
+    match (min_role, role) {
+        (OrganizationRole::Admin, OrganizationRole::Admin) => true,
+        (OrganizationRole::Member, OrganizationRole::Admin | OrganizationRole::Member) => true,
+        (OrganizationRole::Viewer, _) => true,
+        _ => false,
+    }
+}
+
 /// Helper to check if a user has a specific role (or higher) in an organization
 pub async fn check_org_role(
     pool: &sqlx::PgPool,
@@ -162,33 +191,65 @@ pub async fn check_org_role(
     user_address: &str,
     min_role: OrganizationRole,
 ) -> ApiResult<OrganizationRole> {
-    let member = sqlx::query!(
-        r#"
-        SELECT role as "role: OrganizationRole"
-        FROM organization_members om
-        JOIN publishers p ON om.publisher_id = p.id
-        WHERE om.organization_id = $1 AND p.stellar_address = $2
-        "#,
-        org_id,
-        user_address
+    let role: OrganizationRole = sqlx::query_scalar(
+        "SELECT om.role
+           FROM organization_members om
+           JOIN publishers p ON om.publisher_id = p.id
+          WHERE om.organization_id = $1 AND p.stellar_address = $2",
     )
+    .bind(org_id)
+    .bind(user_address)
     .fetch_optional(pool)
     .await
     .map_err(|e| db_internal_error("check_org_role", e))?
-    .ok_or(StatusCode::FORBIDDEN)?;
+    .ok_or_else(|| ApiError::forbidden("You are not a member of this organization"))?;
 
-    let has_access = match (min_role, member.role) {
-        (OrganizationRole::Admin, OrganizationRole::Admin) => true,
-        (OrganizationRole::Member, OrganizationRole::Admin | OrganizationRole::Member) => true,
-        (OrganizationRole::Viewer, _) => true,
-        _ => false,
-    };
-
-    if has_access {
-        Ok(member.role)
+    if role_satisfies(&role, &min_role) {
+        Ok(role)
     } else {
-        Err(StatusCode::FORBIDDEN.into())
+        Err(ApiError::forbidden(format!(
+            "This action requires the {} role in the organization",
+            role_name(&min_role)
+        )))
+    }
+}
+
+/// Contracts owned by an organization may only be changed by its members
+/// with the maintainer role or higher. Contracts published by an individual
+/// are left as they are.
+pub async fn authorize_contract_write(
+    state: &AppState,
+    claims: Option<&AuthClaims>,
+    contract_id: Uuid,
+) -> ApiResult<()> {
+    let organization_id = sqlx::query_scalar::<_, Option<Uuid>>(
+        "SELECT organization_id FROM contracts WHERE id = $1",
+    )
+    .bind(contract_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|e| db_internal_error("get_contract_organization", e))?
+    .flatten();
+
+    let Some(organization_id) = organization_id else {
+        return Ok(());
+    };
+    let claims = claims.ok_or_else(|| {
+        ApiError::unauthorized(
+            "This contract belongs to an organization; authenticate as one of its maintainers",
+        )
+    })?;
+    if auth::is_admin(claims) {
+        return Ok(());
     }
+    check_org_role(
+        &state.db,
+        organization_id,
+        &claims.sub,
+        OrganizationRole::Member,
+    )
+    .await?;
+    Ok(())
 }
 
 /// List all members of an organization
@@ -198,24 +259,149 @@ pub async fn list_org_members(
     Path(id): Path<Uuid>,
 ) -> ApiResult<Json<Vec<OrganizationMember>>> {
     // Check if user is a member of the org
-    check_org_role(&state.pool, id, &claims.sub, OrganizationRole::Viewer).await?;
-
-    let members = sqlx::query_as!(
-        OrganizationMember,
-        r#"
-        SELECT organization_id, publisher_id, role as "role: OrganizationRole", joined_at
-        FROM organization_members
-        WHERE organization_id = $1
-        "#,
-        id
+    check_org_role(&state.db, id, &claims.sub, OrganizationRole::Viewer).await?;
+
+    let members: Vec<OrganizationMember> = sqlx::query_as(
+        "SELECT organization_id, publisher_id, role, joined_at
+           FROM organization_members
+          WHERE organization_id = $1
+          ORDER BY joined_at",
     )
-    .fetch_all(&state.pool)
+    .bind(id)
+    .fetch_all(&state.db)
     .await
     .map_err(|e| db_internal_error("list_org_members", e))?;
 
     Ok(Json(members))
 }
 
+/// Add a publisher address to an organization, or change its role
+pub async fn add_member(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<AddMemberRequest>,
+) -> ApiResult<(StatusCode, Json<OrganizationMember>)> {
+    check_org_role(&state.db, id, &claims.sub, OrganizationRole::Admin).await?;
+
+    let address = payload.address.trim();
+    if address.is_empty() {
+        return Err(ApiError::validation("address", "address must not be empty"));
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| db_internal_error("begin_transaction", e))?;
+
+    let publisher_id = upsert_publisher(&mut *tx, address).await?;
+    ensure_owner_remains(&mut tx, id, publisher_id, Some(&payload.role)).await?;
+
+    let member: OrganizationMember = sqlx::query_as(
+        "INSERT INTO organization_members (organization_id, publisher_id, role)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (organization_id, publisher_id) DO UPDATE SET role = EXCLUDED.role
+         RETURNING organization_id, publisher_id, role, joined_at",
+    )
+    .bind(id)
+    .bind(publisher_id)
+    .bind(&payload.role)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| db_internal_error("add_member", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| db_internal_error("commit_transaction", e))?;
+
+    Ok((StatusCode::CREATED, Json(member)))
+}
+
+/// Remove a member from an organization. Admins can remove anyone; members
+/// can remove themselves.
+pub async fn remove_member(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Path((id, address)): Path<(Uuid, String)>,
+) -> ApiResult<StatusCode> {
+    if claims.sub != address {
+        check_org_role(&state.db, id, &claims.sub, OrganizationRole::Admin).await?;
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|e| db_internal_error("begin_transaction", e))?;
+
+    let publisher_id: Uuid = sqlx::query_scalar(
+        "SELECT om.publisher_id
+           FROM organization_members om
+           JOIN publishers p ON om.publisher_id = p.id
+          WHERE om.organization_id = $1 AND p.stellar_address = $2",
+    )
+    .bind(id)
+    .bind(&address)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(|e| db_internal_error("get_member", e))?
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "MemberNotFound",
+            format!("{} is not a member of this organization", address),
+        )
+    })?;
+
+    ensure_owner_remains(&mut tx, id, publisher_id, None).await?;
+
+    sqlx::query(
+        "DELETE FROM organization_members WHERE organization_id = $1 AND publisher_id = $2",
+    )
+    .bind(id)
+    .bind(publisher_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|e| db_internal_error("remove_member", e))?;
+
+    tx.commit()
+        .await
+        .map_err(|e| db_internal_error("commit_transaction", e))?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List the contracts owned by an organization
+pub async fn list_org_contracts(
+    State(state): State<AppState>,
+    claims: Option<AuthClaims>,
+    Path(slug_or_id): Path<String>,
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Json<Paginated<Contract>>> {
+    let org = resolve_organization(state.read_pool(), &slug_or_id).await?;
+    ensure_visible(&state.db, &org, claims.as_ref()).await?;
+
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM contracts WHERE organization_id = $1")
+            .bind(org.id)
+            .fetch_one(state.read_pool())
+            .await
+            .map_err(|e| db_internal_error("count_org_contracts", e))?;
+
+    let contracts: Vec<Contract> = sqlx::query_as(
+        "SELECT * FROM contracts WHERE organization_id = $1
+         ORDER BY created_at DESC LIMIT $2 OFFSET $3",
+    )
+    .bind(org.id)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|e| db_internal_error("list_org_contracts", e))?;
+
+    Ok(Json(Paginated::new(contracts, total, limit, offset)))
+}
+
 /// Invite a member to an organization
 pub async fn invite_member(
     State(state): State<AppState>,
@@ -224,33 +410,25 @@ pub async fn invite_member(
     Json(payload): Json<InviteMemberRequest>,
 ) -> ApiResult<StatusCode> {
     // Check if user is an Admin of the org
-    check_org_role(&state.pool, id, &claims.sub, OrganizationRole::Admin).await?;
+    check_org_role(&state.db, id, &claims.sub, OrganizationRole::Admin).await?;
 
     // Get inviter publisher ID
-    let inviter_id: Uuid = sqlx::query_scalar!(
-        "SELECT id FROM publishers WHERE stellar_address = $1",
-        claims.sub
-    )
-    .fetch_one(&state.pool)
-    .await
-    .map_err(|e| db_internal_error("get_inviter_id", e))?;
+    let inviter_id = upsert_publisher(&state.db, &claims.sub).await?;
 
     let token = Uuid::new_v4().to_string();
     let expires_at = Utc::now() + chrono::Duration::days(7);
 
-    sqlx::query!(
-        r#"
-        INSERT INTO organization_invitations (organization_id, email, role, token, inviter_id, expires_at)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        "#,
-        id,
-        payload.email,
-        payload.role as OrganizationRole,
-        token,
-        inviter_id,
-        expires_at
+    sqlx::query(
+        "INSERT INTO organization_invitations (organization_id, email, role, token, inviter_id, expires_at)
+         VALUES ($1, $2, $3, $4, $5, $6)",
     )
-    .execute(&state.pool)
+    .bind(id)
+    .bind(&payload.email)
+    .bind(&payload.role)
+    .bind(&token)
+    .bind(inviter_id)
+    .bind(expires_at)
+    .execute(&state.db)
     .await
     .map_err(|e| db_internal_error("create_invitation", e))?;
 
@@ -267,56 +445,50 @@ pub async fn accept_invitation(
     Path(token): Path<String>,
 ) -> ApiResult<StatusCode> {
     let mut tx = state
-        .pool
+        .db
         .begin()
         .await
         .map_err(|e| db_internal_error("begin_transaction", e))?;
 
     // 1. Validate invitation
-    let invite = sqlx::query!(
-        r#"
-        SELECT * FROM organization_invitations
-        WHERE token = $1 AND accepted_at IS NULL AND expires_at > NOW()
-        "#,
-        token
+    let (invite_id, organization_id, role): (Uuid, Uuid, OrganizationRole) = sqlx::query_as(
+        "SELECT id, organization_id, role FROM organization_invitations
+          WHERE token = $1 AND accepted_at IS NULL AND expires_at > NOW()",
     )
+    .bind(&token)
     .fetch_optional(&mut *tx)
     .await
     .map_err(|e| db_internal_error("get_invitation", e))?
-    .ok_or(StatusCode::NOT_FOUND)?;
+    .ok_or_else(|| {
+        ApiError::not_found(
+            "InvitationNotFound",
+            "Invitation does not exist, has expired or was already accepted",
+        )
+    })?;
 
     // 2. Get publisher ID for accepting user
-    let publisher_id: Uuid = sqlx::query_scalar!(
-        "SELECT id FROM publishers WHERE stellar_address = $1",
-        claims.sub
-    )
-    .fetch_one(&mut *tx)
-    .await
-    .map_err(|e| db_internal_error("get_publisher_id", e))?;
+    let publisher_id = upsert_publisher(&mut *tx, &claims.sub).await?;
+    ensure_owner_remains(&mut tx, organization_id, publisher_id, Some(&role)).await?;
 
     // 3. Add member
-    sqlx::query!(
-        r#"
-        INSERT INTO organization_members (organization_id, publisher_id, role)
-        VALUES ($1, $2, $3)
-        ON CONFLICT (organization_id, publisher_id) DO UPDATE SET role = EXCLUDED.role
-        "#,
-        invite.organization_id,
-        publisher_id,
-        invite.role
+    sqlx::query(
+        "INSERT INTO organization_members (organization_id, publisher_id, role)
+         VALUES ($1, $2, $3)
+         ON CONFLICT (organization_id, publisher_id) DO UPDATE SET role = EXCLUDED.role",
     )
+    .bind(organization_id)
+    .bind(publisher_id)
+    .bind(&role)
     .execute(&mut *tx)
     .await
     .map_err(|e| db_internal_error("add_member", e))?;
 
     // 4. Mark invitation as accepted
-    sqlx::query!(
-        "UPDATE organization_invitations SET accepted_at = NOW() WHERE id = $1",
-        invite.id
-    )
-    .execute(&mut *tx)
-    .await
-    .map_err(|e| db_internal_error("mark_invite_accepted", e))?;
+    sqlx::query("UPDATE organization_invitations SET accepted_at = NOW() WHERE id = $1")
+        .bind(invite_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_internal_error("mark_invite_accepted", e))?;
 
     tx.commit()
         .await
@@ -324,3 +496,106 @@ pub async fn accept_invitation(
 
     Ok(StatusCode::OK)
 }
+
+async fn upsert_publisher<'e>(executor: impl PgExecutor<'e>, address: &str) -> ApiResult<Uuid> {
+    sqlx::query_scalar(
+        "INSERT INTO publishers (stellar_address) VALUES ($1)
+         ON CONFLICT (stellar_address) DO UPDATE SET stellar_address = EXCLUDED.stellar_address
+         RETURNING id",
+    )
+    .bind(address)
+    .fetch_one(executor)
+    .await
+    .map_err(|e| db_internal_error("upsert_publisher", e))
+}
+
+/// Rejects a membership change that would leave the organization without an
+/// admin. The admin rows are locked so two concurrent demotions cannot both
+/// pass the check.
+async fn ensure_owner_remains(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    org_id: Uuid,
+    publisher_id: Uuid,
+    new_role: Option<&OrganizationRole>,
+) -> ApiResult<()> {
+    let admins: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT publisher_id FROM organization_members
+          WHERE organization_id = $1 AND role = 'admin'
+          FOR UPDATE",
+    )
+    .bind(org_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| db_internal_error("lock_org_admins", e))?;
+
+    if keeps_an_owner(&admins, publisher_id, new_role) {
+        Ok(())
+    } else {
+        Err(ApiError::conflict(
+            "LastOwner",
+            "An organization must keep at least one admin; promote another member first",
+        ))
+    }
+}
+
+/// Whether the organization still has an admin after `publisher_id` takes
+/// `new_role` (`None` when it is removed).
+fn keeps_an_owner(
+    admins: &[Uuid],
+    publisher_id: Uuid,
+    new_role: Option<&OrganizationRole>,
+) -> bool {
+    new_role == Some(&OrganizationRole::Admin) || admins.iter().any(|id| *id != publisher_id)
+}
+
+fn role_name(role: &OrganizationRole) -> &'static str {
+    match role {
+        OrganizationRole::Admin => "admin",
+        OrganizationRole::Member => "member",
+        OrganizationRole::Viewer => "viewer",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_grant_everything_below_them() {
+        use OrganizationRole::*;
+        assert!(role_satisfies(&Admin, &Member));
+        assert!(role_satisfies(&Member, &Member));
+        assert!(role_satisfies(&Viewer, &Viewer));
+        assert!(!role_satisfies(&Viewer, &Member));
+        assert!(!role_satisfies(&Member, &Admin));
+    }
+
+    #[test]
+    fn owner_and_maintainer_are_accepted_as_role_names() {
+        let role: OrganizationRole = serde_json::from_str("\"owner\"").unwrap();
+        assert_eq!(role, OrganizationRole::Admin);
+        let role: OrganizationRole = serde_json::from_str("\"maintainer\"").unwrap();
+        assert_eq!(role, OrganizationRole::Member);
+    }
+
+    #[test]
+    fn the_last_owner_cannot_leave_or_be_demoted() {
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        assert!(!keeps_an_owner(&[owner], owner, None));
+        assert!(!keeps_an_owner(
+            &[owner],
+            owner,
+            Some(&OrganizationRole::Member)
+        ));
+        assert!(keeps_an_owner(
+            &[owner],
+            owner,
+            Some(&OrganizationRole::Admin)
+        ));
+        assert!(keeps_an_owner(&[owner, other], owner, None));
+        // Changing someone who is not an admin never matters
+        assert!(keeps_an_owner(&[owner], other, None));
+    }
+}
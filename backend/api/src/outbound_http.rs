@@ -0,0 +1,764 @@
+//! Shared client for outbound HTTP from background work (webhooks, Soroban RPC).
+//!
+//! Every request gets connect and overall timeouts, at most
+//! `OUTBOUND_MAX_CONCURRENT_PER_HOST` requests run against one destination at
+//! a time, and a destination that keeps failing is skipped for a while. The
+//! settings are validated at startup by [`crate::config`]:
+//!
+//! | Variable                           | Default |
+//! |------------------------------------|---------|
+//! | `OUTBOUND_CONNECT_TIMEOUT_SECS`    | 5       |
+//! | `OUTBOUND_REQUEST_TIMEOUT_SECS`    | 15      |
+//! | `OUTBOUND_MAX_CONCURRENT_PER_HOST` | 4       |
+//! | `OUTBOUND_BREAKER_FAILURES`        | 5       |
+//! | `OUTBOUND_BREAKER_OPEN_SECS`       | 60      |
+//! | `OUTBOUND_ALLOW_PRIVATE_WEBHOOKS`  | `false` |
+//!
+//! A destination is `scheme://host:port`. Transport errors, timeouts and 5xx
+//! responses count as failures; after `OUTBOUND_BREAKER_FAILURES` in a row the
+//! circuit opens and requests fail immediately with
+//! [`OutboundError::CircuitOpen`] until `OUTBOUND_BREAKER_OPEN_SECS` pass. The
+//! next request is then let through as a probe. Outcomes and breaker state are
+//! exported as `outbound_http_*` metrics.
+//!
+//! Webhook URLs come from API callers, so [`OutboundClient::send_webhook`]
+//! only sends to hosts whose every address is public: a loopback, private,
+//! link-local, unspecified or multicast address is refused. The check runs
+//! when a webhook is registered, and webhook connections resolve their host
+//! through the same check, so the addresses checked are the ones connected
+//! to and a host can't pass with one DNS answer and be reached at another.
+//! Webhook redirects aren't followed. `OUTBOUND_ALLOW_PRIVATE_WEBHOOKS`
+//! lifts the check for local development.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{redirect, Client, Request, RequestBuilder, Response, Url};
+use tokio::sync::Semaphore;
+
+use crate::metrics::{OUTBOUND_CIRCUIT_OPEN, OUTBOUND_CIRCUIT_TRIPS, OUTBOUND_REQUESTS};
+
+const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 15;
+const DEFAULT_MAX_CONCURRENT_PER_HOST: usize = 4;
+const DEFAULT_BREAKER_FAILURES: u32 = 5;
+const DEFAULT_BREAKER_OPEN_SECS: u64 = 60;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboundConfig {
+    pub connect_timeout: Duration,
+    /// Upper bound for the whole request, including waiting for a free slot
+    pub request_timeout: Duration,
+    pub max_concurrent_per_host: usize,
+    /// Consecutive failures that open a destination's circuit
+    pub breaker_failures: u32,
+    /// How long an open circuit skips its destination
+    pub breaker_open: Duration,
+    /// Let webhooks reach loopback and private addresses
+    pub allow_private_webhooks: bool,
+}
+
+impl Default for OutboundConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            request_timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            max_concurrent_per_host: DEFAULT_MAX_CONCURRENT_PER_HOST,
+            breaker_failures: DEFAULT_BREAKER_FAILURES,
+            breaker_open: Duration::from_secs(DEFAULT_BREAKER_OPEN_SECS),
+            allow_private_webhooks: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum OutboundError {
+    /// The destination failed repeatedly and is being skipped
+    CircuitOpen {
+        destination: String,
+        retry_after: Duration,
+    },
+    /// Every slot for the destination stayed busy for the whole timeout
+    Saturated {
+        destination: String,
+    },
+    /// A webhook host with a loopback, private or otherwise non-public address
+    NonPublicAddress {
+        host: String,
+        address: IpAddr,
+    },
+    /// A webhook host that didn't resolve to any address
+    Unresolvable {
+        host: String,
+    },
+    Request(reqwest::Error),
+}
+
+impl fmt::Display for OutboundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CircuitOpen {
+                destination,
+                retry_after,
+            } => write!(
+                f,
+                "{} is temporarily skipped after repeated failures (retry in {}s)",
+                destination,
+                retry_after.as_secs().max(1)
+            ),
+            Self::Saturated { destination } => {
+                write!(f, "too many concurrent requests to {}", destination)
+            }
+            Self::NonPublicAddress { host, address } => {
+                write!(
+                    f,
+                    "{} resolves to {}, which is not a public address",
+                    host, address
+                )
+            }
+            Self::Unresolvable { host } => write!(f, "{} does not resolve", host),
+            Self::Request(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for OutboundError {}
+
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+}
+
+struct Destination {
+    permits: Semaphore,
+    breaker: Mutex<Breaker>,
+}
+
+/// Cheap to clone; clones share connection pools, slots and breaker state.
+#[derive(Clone)]
+pub struct OutboundClient {
+    client: Client,
+    /// Like `client`, but resolves hosts through `webhook_resolver` and
+    /// doesn't follow redirects, whose targets would escape it
+    webhook_client: Client,
+    webhook_resolver: Arc<PublicResolver>,
+    config: Arc<OutboundConfig>,
+    destinations: Arc<Mutex<HashMap<String, Arc<Destination>>>>,
+}
+
+impl fmt::Debug for OutboundClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OutboundClient")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl OutboundClient {
+    pub fn new(config: OutboundConfig) -> Self {
+        Self::with_resolver(config, Arc::new(SystemResolver))
+    }
+
+    /// A client whose webhooks look hosts up with `lookup`
+    fn with_resolver(config: OutboundConfig, lookup: Arc<dyn Resolve>) -> Self {
+        let builder = || {
+            Client::builder()
+                .connect_timeout(config.connect_timeout)
+                .timeout(config.request_timeout)
+        };
+        let client = builder().build().unwrap_or_default();
+        let webhook_resolver = Arc::new(PublicResolver {
+            lookup,
+            allow_private: config.allow_private_webhooks,
+        });
+        let webhook_client = builder()
+            .redirect(redirect::Policy::none())
+            .dns_resolver(webhook_resolver.clone())
+            .build()
+            .unwrap_or_default();
+        Self {
+            client,
+            webhook_client,
+            webhook_resolver,
+            config: Arc::new(config),
+            destinations: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url)
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url)
+    }
+
+    /// How much longer `url`'s destination is skipped, if its circuit is open.
+    pub fn blocked_for(&self, url: &str) -> Option<Duration> {
+        let destination = destination_of(&Url::parse(url).ok()?);
+        let dest = self
+            .destinations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&destination)
+            .cloned()?;
+        open_for(&dest)
+    }
+
+    /// Sends a request built from [`OutboundClient::get`] or
+    /// [`OutboundClient::post`], subject to the destination's slot limit and
+    /// circuit breaker.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, OutboundError> {
+        let request = request.build().map_err(OutboundError::Request)?;
+        self.execute(&self.client, request).await
+    }
+
+    /// Like [`OutboundClient::send`] for a webhook: refused unless the host
+    /// only resolves to public addresses, and redirects are not followed.
+    /// Host names are checked as they are resolved for the connection.
+    pub async fn send_webhook(&self, request: RequestBuilder) -> Result<Response, OutboundError> {
+        let request = request.build().map_err(OutboundError::Request)?;
+        // IP literals are connected to without a lookup
+        if let Some(address) = literal_address(request.url()) {
+            self.webhook_resolver
+                .check(request.url().host_str().unwrap_or_default(), [address])?;
+        }
+        self.execute(&self.webhook_client, request)
+            .await
+            .map_err(|err| match &err {
+                OutboundError::Request(cause) => refused_address(cause).unwrap_or(err),
+                _ => err,
+            })
+    }
+
+    /// Refuses a webhook URL whose host has a loopback, private, link-local,
+    /// unspecified or multicast address, unless private webhooks are allowed.
+    pub async fn check_webhook_target(&self, url: &Url) -> Result<(), OutboundError> {
+        let host = url.host_str().unwrap_or_default();
+        if let Some(address) = literal_address(url) {
+            return self.webhook_resolver.check(host, [address]);
+        }
+        let name = host
+            .parse::<Name>()
+            .map_err(|_| OutboundError::Unresolvable {
+                host: host.to_string(),
+            })?;
+        self.webhook_resolver
+            .resolve(name)
+            .await
+            .map(drop)
+            .map_err(|err| match err.downcast::<OutboundError>() {
+                Ok(refused) => *refused,
+                Err(_) => OutboundError::Unresolvable {
+                    host: host.to_string(),
+                },
+            })
+    }
+
+    async fn execute(&self, client: &Client, request: Request) -> Result<Response, OutboundError> {
+        let destination = destination_of(request.url());
+        let dest = self.destination(&destination);
+
+        if let Some(retry_after) = open_for(&dest) {
+            OUTBOUND_REQUESTS
+                .with_label_values(&[&destination, "skipped"])
+                .inc();
+            return Err(OutboundError::CircuitOpen {
+                destination,
+                retry_after,
+            });
+        }
+
+        let started = Instant::now();
+        let _permit =
+            match tokio::time::timeout(self.config.request_timeout, dest.permits.acquire()).await {
+                Ok(Ok(permit)) => permit,
+                _ => {
+                    OUTBOUND_REQUESTS
+                        .with_label_values(&[&destination, "saturated"])
+                        .inc();
+                    return Err(OutboundError::Saturated { destination });
+                }
+            };
+
+        // Time spent waiting for a slot counts against the request timeout
+        let remaining = self
+            .config
+            .request_timeout
+            .saturating_sub(started.elapsed())
+            .max(Duration::from_millis(1));
+        let mut request = request;
+        let timeout = request.timeout().map_or(remaining, |t| (*t).min(remaining));
+        *request.timeout_mut() = Some(timeout);
+
+        let result = client.execute(request).await;
+        let failed = match &result {
+            Ok(response) => response.status().is_server_error(),
+            Err(_) => true,
+        };
+        self.record(&destination, &dest, failed);
+
+        let outcome = match &result {
+            Ok(_) if failed => "server_error",
+            Ok(_) => "ok",
+            Err(err) if err.is_timeout() => "timeout",
+            Err(_) => "error",
+        };
+        OUTBOUND_REQUESTS
+            .with_label_values(&[&destination, outcome])
+            .inc();
+
+        result.map_err(OutboundError::Request)
+    }
+
+    fn destination(&self, destination: &str) -> Arc<Destination> {
+        self.destinations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(destination.to_string())
+            .or_insert_with(|| {
+                Arc::new(Destination {
+                    permits: Semaphore::new(self.config.max_concurrent_per_host),
+                    breaker: Mutex::new(Breaker::default()),
+                })
+            })
+            .clone()
+    }
+
+    fn record(&self, destination: &str, dest: &Destination, failed: bool) {
+        let mut breaker = dest.breaker.lock().unwrap_or_else(PoisonError::into_inner);
+        if !failed {
+            if breaker.open_until.take().is_some() || breaker.consecutive_failures > 0 {
+                OUTBOUND_CIRCUIT_OPEN
+                    .with_label_values(&[destination])
+                    .set(0);
+            }
+            breaker.consecutive_failures = 0;
+            return;
+        }
+
+        breaker.consecutive_failures += 1;
+        if breaker.consecutive_failures >= self.config.breaker_failures {
+            breaker.open_until = Some(Instant::now() + self.config.breaker_open);
+            OUTBOUND_CIRCUIT_OPEN
+                .with_label_values(&[destination])
+                .set(1);
+            OUTBOUND_CIRCUIT_TRIPS
+                .with_label_values(&[destination])
+                .inc();
+            tracing::warn!(
+                destination,
+                failures = breaker.consecutive_failures,
+                open_secs = self.config.breaker_open.as_secs(),
+                "outbound http: circuit opened"
+            );
+        }
+    }
+}
+
+fn open_for(dest: &Destination) -> Option<Duration> {
+    let breaker = dest.breaker.lock().unwrap_or_else(PoisonError::into_inner);
+    breaker
+        .open_until
+        .and_then(|until| until.checked_duration_since(Instant::now()))
+}
+
+/// Looks webhook hosts up with `lookup` and fails for any with a non-public
+/// address. The webhook client connects through it, which is what keeps a
+/// host from passing the check with one answer and being reached at another
+/// (DNS rebinding).
+struct PublicResolver {
+    lookup: Arc<dyn Resolve>,
+    allow_private: bool,
+}
+
+impl PublicResolver {
+    fn check(
+        &self,
+        host: &str,
+        addresses: impl IntoIterator<Item = IpAddr>,
+    ) -> Result<(), OutboundError> {
+        if self.allow_private {
+            return Ok(());
+        }
+        only_public(host, addresses)
+    }
+}
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        let resolving = self.lookup.resolve(name);
+        let allow_private = self.allow_private;
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = resolving.await?.collect();
+            if !allow_private {
+                only_public(&host, addrs.iter().map(SocketAddr::ip))?;
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
+
+/// Fails unless `host` has addresses and all of them are public
+fn only_public(
+    host: &str,
+    addresses: impl IntoIterator<Item = IpAddr>,
+) -> Result<(), OutboundError> {
+    let mut addresses = addresses.into_iter().peekable();
+    if addresses.peek().is_none() {
+        return Err(OutboundError::Unresolvable {
+            host: host.to_string(),
+        });
+    }
+    match addresses.find(|address| !is_public(*address)) {
+        Some(address) => Err(OutboundError::NonPublicAddress {
+            host: host.to_string(),
+            address,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// The system's resolver, as reqwest uses by default
+struct SystemResolver;
+
+impl Resolve for SystemResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs = tokio::net::lookup_host((name.as_str(), 0)).await?;
+            Ok(Box::new(addrs.collect::<Vec<_>>().into_iter()) as Addrs)
+        })
+    }
+}
+
+/// The address of a URL whose host is an IP literal
+fn literal_address(url: &Url) -> Option<IpAddr> {
+    // IPv6 literals keep their brackets in `host_str`
+    url.host_str()?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+/// The refusal of [`PublicResolver`] behind a failed webhook connection
+fn refused_address(err: &reqwest::Error) -> Option<OutboundError> {
+    let mut cause = std::error::Error::source(err);
+    while let Some(err) = cause {
+        match err.downcast_ref::<OutboundError>() {
+            Some(OutboundError::NonPublicAddress { host, address }) => {
+                return Some(OutboundError::NonPublicAddress {
+                    host: host.clone(),
+                    address: *address,
+                })
+            }
+            Some(OutboundError::Unresolvable { host }) => {
+                return Some(OutboundError::Unresolvable { host: host.clone() })
+            }
+            _ => cause = err.source(),
+        }
+    }
+    None
+}
+
+/// Whether a webhook may be sent to `address`
+fn is_public(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(v4) => is_public_v4(v4),
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_public_v4(v4),
+            None => is_public_v6(v6),
+        },
+    }
+}
+
+fn is_public_v4(address: Ipv4Addr) -> bool {
+    let [first, second, ..] = address.octets();
+    !(address.is_loopback()
+        || address.is_private()
+        || address.is_link_local()
+        || address.is_unspecified()
+        || address.is_multicast()
+        || address.is_broadcast()
+        // 0.0.0.0/8 and the 100.64.0.0/10 carrier-grade NAT range
+        || first == 0
+        || (first == 100 && second & 0xc0 == 64))
+}
+
+fn is_public_v6(address: Ipv6Addr) -> bool {
+    let first = address.segments()[0];
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || address.is_multicast()
+        // fc00::/7 unique local and fe80::/10 link-local
+        || first & 0xfe00 == 0xfc00
+        || first & 0xffc0 == 0xfe80)
+}
+
+fn destination_of(url: &Url) -> String {
+    format!(
+        "{}://{}:{}",
+        url.scheme(),
+        url.host_str().unwrap_or_default(),
+        url.port_or_known_default().unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Serves `/` with `delay` before answering `status`; returns the base URL
+    /// and the highest number of requests it saw in flight at once.
+    async fn mock_server(delay: Duration, status: u16) -> (String, Arc<AtomicUsize>) {
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let (in_flight_h, peak_h) = (in_flight.clone(), peak.clone());
+        let app = Router::new().route(
+            "/",
+            get(move || {
+                let (in_flight, peak) = (in_flight_h.clone(), peak_h.clone());
+                async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(delay).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    axum::http::StatusCode::from_u16(status).unwrap()
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}/", addr), peak)
+    }
+
+    fn client(request_timeout_ms: u64, per_host: usize, failures: u32) -> OutboundClient {
+        OutboundClient::new(OutboundConfig {
+            connect_timeout: Duration::from_secs(1),
+            request_timeout: Duration::from_millis(request_timeout_ms),
+            max_concurrent_per_host: per_host,
+            breaker_failures: failures,
+            breaker_open: Duration::from_secs(60),
+            allow_private_webhooks: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn stuck_destination_does_not_block_others() {
+        let (stuck, _) = mock_server(Duration::from_secs(30), 200).await;
+        let (healthy, _) = mock_server(Duration::ZERO, 200).await;
+        let client = client(500, 1, 100);
+
+        // Occupy the stuck destination's only slot and queue more behind it
+        let blocked: Vec<_> = (0..3)
+            .map(|_| {
+                let client = client.clone();
+                let stuck = stuck.clone();
+                tokio::spawn(async move { client.send(client.get(&stuck)).await })
+            })
+            .collect();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let started = Instant::now();
+        let response = client.send(client.get(&healthy)).await.unwrap();
+        assert!(response.status().is_success());
+        assert!(started.elapsed() < Duration::from_millis(250));
+
+        for handle in blocked {
+            let err = handle.await.unwrap().unwrap_err();
+            assert!(
+                matches!(
+                    err,
+                    OutboundError::Request(_) | OutboundError::Saturated { .. }
+                ),
+                "{err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrency_is_capped_per_destination() {
+        let (slow, peak) = mock_server(Duration::from_millis(100), 200).await;
+        let client = client(5_000, 2, 100);
+
+        let requests: Vec<_> = (0..6)
+            .map(|_| {
+                let client = client.clone();
+                let slow = slow.clone();
+                tokio::spawn(async move { client.send(client.get(&slow)).await })
+            })
+            .collect();
+        for handle in requests {
+            assert!(handle.await.unwrap().is_ok());
+        }
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn repeated_failures_open_the_circuit() {
+        let (failing, _) = mock_server(Duration::ZERO, 503).await;
+        let client = client(1_000, 4, 3);
+
+        for _ in 0..3 {
+            let response = client.send(client.get(&failing)).await.unwrap();
+            assert_eq!(response.status(), 503);
+        }
+        assert!(client.blocked_for(&failing).is_some());
+
+        let started = Instant::now();
+        let err = client.send(client.get(&failing)).await.unwrap_err();
+        assert!(matches!(err, OutboundError::CircuitOpen { .. }), "{err}");
+        assert!(started.elapsed() < Duration::from_millis(50));
+
+        let destination = destination_of(&Url::parse(&failing).unwrap());
+        assert_eq!(
+            OUTBOUND_CIRCUIT_OPEN
+                .with_label_values(&[&destination])
+                .get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn failures_are_counted_per_destination() {
+        let (failing, _) = mock_server(Duration::ZERO, 500).await;
+        let (healthy, _) = mock_server(Duration::ZERO, 200).await;
+        let client = client(1_000, 4, 2);
+
+        client.send(client.get(&failing)).await.unwrap();
+        client.send(client.get(&healthy)).await.unwrap();
+        assert!(client.blocked_for(&healthy).is_none());
+        assert!(client.blocked_for(&failing).is_none());
+
+        client.send(client.get(&failing)).await.unwrap();
+        assert!(client.blocked_for(&failing).is_some());
+    }
+
+    #[test]
+    fn only_public_addresses_take_webhooks() {
+        for public in ["93.184.216.34", "100.128.0.1", "2606:2800:220:1::1"] {
+            assert!(is_public(public.parse().unwrap()), "{public}");
+        }
+        for private in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "224.0.0.1",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "ff02::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public(private.parse().unwrap()), "{private}");
+        }
+    }
+
+    #[tokio::test]
+    async fn webhooks_to_private_addresses_are_refused() {
+        let (local, _) = mock_server(Duration::ZERO, 200).await;
+        let client = client(1_000, 4, 5);
+
+        let err = client.send_webhook(client.get(&local)).await.unwrap_err();
+        assert!(
+            matches!(err, OutboundError::NonPublicAddress { .. }),
+            "{err}"
+        );
+        let err = client
+            .send_webhook(client.get("http://localhost/hook"))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, OutboundError::NonPublicAddress { .. }),
+            "{err}"
+        );
+
+        let permissive = OutboundClient::new(OutboundConfig {
+            allow_private_webhooks: true,
+            ..OutboundConfig::default()
+        });
+        let response = permissive
+            .send_webhook(permissive.get(&local))
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+    }
+
+    /// Answers `first` to the first lookup and `then` to every later one
+    struct Rebinding {
+        lookups: AtomicUsize,
+        first: IpAddr,
+        then: IpAddr,
+    }
+
+    impl Resolve for Rebinding {
+        fn resolve(&self, _: Name) -> Resolving {
+            let address = match self.lookups.fetch_add(1, Ordering::SeqCst) {
+                0 => self.first,
+                _ => self.then,
+            };
+            Box::pin(
+                async move { Ok(Box::new(std::iter::once(SocketAddr::new(address, 0))) as Addrs) },
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn webhooks_connect_to_the_addresses_checked() {
+        let (local, peak) = mock_server(Duration::ZERO, 200).await;
+        let port = local.trim_end_matches('/').rsplit(':').next().unwrap();
+        let url = format!("http://rebind.test:{port}/");
+        let rebinding = |allow_private_webhooks| {
+            OutboundClient::with_resolver(
+                OutboundConfig {
+                    allow_private_webhooks,
+                    ..OutboundConfig::default()
+                },
+                Arc::new(Rebinding {
+                    lookups: AtomicUsize::new(0),
+                    first: "93.184.216.34".parse().unwrap(),
+                    then: Ipv4Addr::LOCALHOST.into(),
+                }),
+            )
+        };
+
+        // Public when registered, loopback by the time it's sent
+        let client = rebinding(false);
+        client
+            .check_webhook_target(&url.parse().unwrap())
+            .await
+            .unwrap();
+        let err = client.send_webhook(client.get(&url)).await.unwrap_err();
+        assert!(
+            matches!(err, OutboundError::NonPublicAddress { address, .. } if address.is_loopback()),
+            "{err}"
+        );
+        assert_eq!(peak.load(Ordering::SeqCst), 0);
+
+        // The connection goes wherever the checked lookup pointed
+        let client = rebinding(true);
+        client
+            .check_webhook_target(&url.parse().unwrap())
+            .await
+            .unwrap();
+        let response = client.send_webhook(client.get(&url)).await.unwrap();
+        assert!(response.status().is_success());
+        assert_eq!(peak.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,149 @@
+//! `limit`/`offset` query parameters shared by every list endpoint.
+//!
+//! Handlers take a [`Pagination`] argument and answer with
+//! [`shared::pagination::Paginated`], so bounds are checked the same way
+//! everywhere and out-of-range values are a 400 naming the parameter.
+
+use axum::extract::{FromRequestParts, Query};
+use axum::http::request::Parts;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::error::ApiError;
+
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+pub const MAX_PAGE_LIMIT: i64 = 100;
+
+/// Pagination query parameters
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageParams {
+    /// Items per page (default 20, max 100); `page_size` is accepted as an alias
+    #[serde(alias = "page_size")]
+    pub limit: Option<i64>,
+    /// Number of items to skip (default 0)
+    pub offset: Option<i64>,
+    /// 1-based page number, used when `offset` is not given
+    pub page: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pagination {
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl Default for Pagination {
+    fn default() -> Self {
+        Self {
+            limit: DEFAULT_PAGE_LIMIT,
+            offset: 0,
+        }
+    }
+}
+
+impl TryFrom<PageParams> for Pagination {
+    type Error = ApiError;
+
+    fn try_from(params: PageParams) -> Result<Self, Self::Error> {
+        let limit = params.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+        if !(1..=MAX_PAGE_LIMIT).contains(&limit) {
+            return Err(invalid(
+                "limit",
+                format!("`limit` must be between 1 and {MAX_PAGE_LIMIT}, got {limit}"),
+            ));
+        }
+
+        let offset = match (params.offset, params.page) {
+            (Some(offset), _) if offset < 0 => {
+                return Err(invalid(
+                    "offset",
+                    format!("`offset` must be non-negative, got {offset}"),
+                ))
+            }
+            (Some(offset), _) => offset,
+            (None, Some(page)) if page < 1 => {
+                return Err(invalid(
+                    "page",
+                    format!("`page` must be 1 or greater, got {page}"),
+                ))
+            }
+            (None, Some(page)) => (page - 1).saturating_mul(limit),
+            (None, None) => 0,
+        };
+
+        Ok(Self { limit, offset })
+    }
+}
+
+fn invalid(field: &str, message: String) -> ApiError {
+    ApiError::validation(field, message).with_details(json!({ "reason": "InvalidPagination" }))
+}
+
+#[axum::async_trait]
+impl<S> FromRequestParts<S> for Pagination
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<PageParams>::try_from_uri(&parts.uri)
+            .map_err(|err| ApiError::bad_request("InvalidPagination", err.body_text()))?;
+        Self::try_from(params)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::{Request, StatusCode};
+
+    async fn extract(uri: &str) -> Result<Pagination, ApiError> {
+        let (mut parts, _) = Request::builder().uri(uri).body(()).unwrap().into_parts();
+        Pagination::from_request_parts(&mut parts, &()).await
+    }
+
+    #[tokio::test]
+    async fn defaults_to_the_first_page() {
+        assert_eq!(extract("/items").await.unwrap(), Pagination::default());
+    }
+
+    #[tokio::test]
+    async fn reads_limit_offset_and_page() {
+        assert_eq!(
+            extract("/items?limit=50&offset=100").await.unwrap(),
+            Pagination {
+                limit: 50,
+                offset: 100
+            }
+        );
+        assert_eq!(
+            extract("/items?page_size=10&page=3&query=x").await.unwrap(),
+            Pagination {
+                limit: 10,
+                offset: 20
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn out_of_range_values_are_rejected_with_the_field() {
+        for (uri, field) in [
+            ("/items?limit=0", "limit"),
+            ("/items?limit=101", "limit"),
+            ("/items?offset=-1", "offset"),
+            ("/items?page=0", "page"),
+        ] {
+            let err = extract(uri).await.unwrap_err();
+            assert_eq!(err.status(), StatusCode::BAD_REQUEST, "{uri}");
+            assert_eq!(err.body().error.details["field"], field, "{uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn non_numeric_values_are_rejected() {
+        let err = extract("/items?limit=many").await.unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    }
+}
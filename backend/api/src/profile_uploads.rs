@@ -0,0 +1,506 @@
+//! Profiles publishers run locally and attach to their contract, next to
+//! the official ones of [`crate::contract_profile`].
+//!
+//!   POST /api/contracts/:id/profiles    – upload a ProfileData document (publisher only)
+//!   GET  /api/contracts/:id/profiles    – the kept uploads, newest first
+//!
+//! Uploads are tagged with the method, the engine that recorded them and
+//! the CLI version. Only the newest `CONTRACT_PROFILES_PER_METHOD`
+//! (default 5) of each method are kept, and profiles over
+//! `CONTRACT_PROFILE_MAX_KB` (default 256) are refused.
+
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use shared::UploadedProfileSummary;
+use soroban_profiler::{parse_profile, ProfileData, ProfileMode};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::{db_internal_error, fetch_contract_identity, map_query_rejection},
+    state::AppState,
+};
+
+const DEFAULT_MAX_PROFILE_KB: usize = 256;
+const DEFAULT_PROFILES_PER_METHOD: i64 = 5;
+const MAX_CLI_VERSION_LEN: usize = 32;
+
+fn max_profile_bytes() -> usize {
+    std::env::var("CONTRACT_PROFILE_MAX_KB")
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .filter(|max| *max > 0)
+        .unwrap_or(DEFAULT_MAX_PROFILE_KB)
+        * 1024
+}
+
+fn profiles_per_method() -> i64 {
+    std::env::var("CONTRACT_PROFILES_PER_METHOD")
+        .ok()
+        .and_then(|raw| raw.trim().parse().ok())
+        .filter(|kept| *kept > 0)
+        .unwrap_or(DEFAULT_PROFILES_PER_METHOD)
+}
+
+/// Body of `POST /api/contracts/:id/profiles`
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UploadProfileRequest {
+    /// Version of the CLI that recorded the profile
+    pub cli_version: String,
+    /// The ProfileData document `soroban-registry profile --output` writes
+    #[schema(value_type = Object)]
+    pub profile: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct UploadedProfile {
+    pub id: Uuid,
+    pub method: String,
+    /// `instrumented`, `sampled`, `simulated` or `budgeted`
+    pub engine: String,
+    pub cli_version: String,
+    /// Subject of the uploader's API token
+    pub uploaded_by: String,
+    /// Run time in nanoseconds; CPU instructions for `simulated` and
+    /// `budgeted` profiles
+    pub total_duration_ns: i64,
+    pub cpu_instructions: Option<i64>,
+    pub memory_bytes: Option<i64>,
+    pub function_count: i32,
+    pub size_bytes: i32,
+    /// The full profile, loadable by `soroban-registry profile --compare`
+    #[schema(value_type = Object)]
+    pub profile: serde_json::Value,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ProfileListQuery {
+    /// Only uploads of this method
+    pub method: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct UploadedProfileList {
+    /// Uploads kept per method
+    pub kept_per_method: i64,
+    pub profiles: Vec<UploadedProfileEntry>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct UploadedProfileEntry {
+    #[serde(flatten)]
+    pub profile: UploadedProfile,
+    /// Whether the numbers come from a simulation rather than a run
+    pub simulated: bool,
+    pub summary: String,
+}
+
+fn compact(value: i64) -> String {
+    let value = value as f64;
+    if value >= 1e9 {
+        format!("{:.2}B", value / 1e9)
+    } else if value >= 1e6 {
+        format!("{:.2}M", value / 1e6)
+    } else if value >= 1e3 {
+        format!("{:.1}K", value / 1e3)
+    } else {
+        format!("{}", value)
+    }
+}
+
+fn engine_label(engine: &str) -> &str {
+    match engine {
+        "simulated" => "simulated via RPC",
+        "budgeted" => "budgeted estimate",
+        other => other,
+    }
+}
+
+/// One line describing an upload, for the contract detail and `info`.
+/// Simulated and budgeted profiles count instructions instead of time and
+/// say so.
+pub(crate) fn summary_line(
+    method: &str,
+    engine: &str,
+    cli_version: &str,
+    total_duration_ns: i64,
+    cpu_instructions: Option<i64>,
+    memory_bytes: Option<i64>,
+    function_count: i32,
+) -> String {
+    let measured = if matches!(engine, "simulated" | "budgeted") {
+        let cpu = cpu_instructions.unwrap_or(total_duration_ns);
+        match memory_bytes {
+            Some(memory) => format!(
+                "{} CPU instructions, {:.1} KB memory",
+                compact(cpu),
+                memory as f64 / 1024.0
+            ),
+            None => format!("{} CPU instructions", compact(cpu)),
+        }
+    } else {
+        format!(
+            "{:.3} ms over {} function{}",
+            total_duration_ns as f64 / 1e6,
+            function_count,
+            if function_count == 1 { "" } else { "s" }
+        )
+    };
+    format!(
+        "{}: {} ({}, CLI {})",
+        method,
+        measured,
+        engine_label(engine),
+        cli_version
+    )
+}
+
+fn is_simulated(engine: &str) -> bool {
+    engine == ProfileMode::Simulated.to_string()
+}
+
+/// The method a profile covers: the one it was run with, otherwise the
+/// outermost call it recorded
+fn profiled_method(profile: &ProfileData) -> Option<String> {
+    profile
+        .method
+        .clone()
+        .or_else(|| {
+            profile
+                .call_stack
+                .first()
+                .map(|frame| frame.function.clone())
+        })
+        .filter(|method| !method.trim().is_empty())
+}
+
+/// Checks an upload and reads the profile out of it, upgraded to the
+/// current schema
+fn validate_upload(req: &UploadProfileRequest) -> ApiResult<(ProfileData, usize)> {
+    let cli_version = req.cli_version.trim();
+    if cli_version.is_empty() || cli_version.len() > MAX_CLI_VERSION_LEN {
+        return Err(ApiError::validation(
+            "cli_version",
+            format!(
+                "cli_version must be 1 to {} characters",
+                MAX_CLI_VERSION_LEN
+            ),
+        ));
+    }
+
+    let document = req.profile.to_string();
+    let max = max_profile_bytes();
+    if document.len() > max {
+        return Err(ApiError::payload_too_large(format!(
+            "Profile is {} KB; uploaded profiles are limited to {} KB",
+            document.len().div_ceil(1024),
+            max / 1024
+        )));
+    }
+
+    let profile = parse_profile(&document)
+        .map_err(|err| ApiError::bad_request("InvalidProfile", err.to_string()))?
+        .profile;
+    Ok((profile, document.len()))
+}
+
+/// Summary of the contract's newest upload, for the contract detail
+pub(crate) async fn latest_profile_summary(
+    pool: &PgPool,
+    contract_id: Uuid,
+) -> Result<Option<UploadedProfileSummary>, sqlx::Error> {
+    type Row = (
+        Uuid,
+        String,
+        String,
+        String,
+        i64,
+        Option<i64>,
+        Option<i64>,
+        i32,
+        DateTime<Utc>,
+    );
+    let row: Option<Row> = sqlx::query_as(
+        "SELECT id, method, engine, cli_version, total_duration_ns, cpu_instructions,
+                memory_bytes, function_count, uploaded_at
+         FROM contract_uploaded_profiles
+         WHERE contract_id = $1
+         ORDER BY uploaded_at DESC
+         LIMIT 1",
+    )
+    .bind(contract_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.map(
+        |(id, method, engine, cli_version, duration, cpu, memory, functions, uploaded_at)| {
+            UploadedProfileSummary {
+                summary: summary_line(
+                    &method,
+                    &engine,
+                    &cli_version,
+                    duration,
+                    cpu,
+                    memory,
+                    functions,
+                ),
+                simulated: is_simulated(&engine),
+                id,
+                method,
+                engine,
+                cli_version,
+                uploaded_at,
+            }
+        },
+    ))
+}
+
+fn entry(profile: UploadedProfile) -> UploadedProfileEntry {
+    UploadedProfileEntry {
+        simulated: is_simulated(&profile.engine),
+        summary: summary_line(
+            &profile.method,
+            &profile.engine,
+            &profile.cli_version,
+            profile.total_duration_ns,
+            profile.cpu_instructions,
+            profile.memory_bytes,
+            profile.function_count,
+        ),
+        profile,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/contracts/{id}/profiles",
+    params(("id" = String, Path, description = "Contract UUID or address")),
+    request_body = UploadProfileRequest,
+    responses(
+        (status = 201, description = "Profile stored; older uploads of the method past the kept number were dropped", body = UploadedProfileEntry),
+        (status = 400, description = "Not a profile this registry can read, or it names no method"),
+        (status = 401, description = "Authentication required"),
+        (status = 403, description = "Caller does not own the contract"),
+        (status = 404, description = "Contract not found"),
+        (status = 413, description = "Profile larger than CONTRACT_PROFILE_MAX_KB")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Contracts"
+)]
+pub async fn upload_profile(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Path(id): Path<String>,
+    Json(req): Json<UploadProfileRequest>,
+) -> ApiResult<(StatusCode, Json<UploadedProfileEntry>)> {
+    let (contract_uuid, _) = fetch_contract_identity(&state, &id).await?;
+    let owns: bool = sqlx::query_scalar(
+        "SELECT $3
+                OR c.publisher_id IN (SELECT id FROM publishers WHERE stellar_address = $2)
+                OR c.organization_id IN (
+                    SELECT om.organization_id FROM organization_members om
+                    JOIN publishers mp ON mp.id = om.publisher_id
+                    WHERE mp.stellar_address = $2)
+         FROM contracts c
+         WHERE c.id = $1",
+    )
+    .bind(contract_uuid)
+    .bind(&claims.sub)
+    .bind(auth::is_admin(&claims))
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("check contract owner", err))?;
+    if !owns {
+        return Err(ApiError::forbidden(
+            "Only the contract's publisher or its organization's members can upload profiles",
+        ));
+    }
+
+    let (profile, size_bytes) = validate_upload(&req)?;
+    let method = profiled_method(&profile).ok_or_else(|| {
+        ApiError::bad_request(
+            "ProfileMethodMissing",
+            "The profile names no method; run `profile --method <name>` before uploading",
+        )
+    })?;
+    let engine = profile.mode.to_string();
+    let function_count = profile.functions.len() as i32;
+    let document = serde_json::to_value(&profile)
+        .map_err(|err| ApiError::internal(format!("Failed to store profile: {}", err)))?;
+    let kept = profiles_per_method();
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin upload profile", err))?;
+    let stored: UploadedProfile = sqlx::query_as(
+        "INSERT INTO contract_uploaded_profiles
+             (contract_id, method, engine, cli_version, uploaded_by, total_duration_ns,
+              cpu_instructions, memory_bytes, function_count, size_bytes, profile)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+         RETURNING id, method, engine, cli_version, uploaded_by, total_duration_ns,
+                   cpu_instructions, memory_bytes, function_count, size_bytes, profile,
+                   uploaded_at",
+    )
+    .bind(contract_uuid)
+    .bind(&method)
+    .bind(&engine)
+    .bind(req.cli_version.trim())
+    .bind(&claims.sub)
+    .bind(i64::try_from(profile.total_duration.as_nanos()).unwrap_or(i64::MAX))
+    .bind(profile.resources.cpu_instructions.map(|cpu| cpu as i64))
+    .bind(profile.resources.memory_bytes.map(|memory| memory as i64))
+    .bind(function_count)
+    .bind(size_bytes as i32)
+    .bind(&document)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("store uploaded profile", err))?;
+
+    let dropped = sqlx::query(
+        "DELETE FROM contract_uploaded_profiles
+         WHERE contract_id = $1 AND method = $2
+           AND id NOT IN (
+               SELECT id FROM contract_uploaded_profiles
+               WHERE contract_id = $1 AND method = $2
+               ORDER BY uploaded_at DESC, id
+               LIMIT $3)",
+    )
+    .bind(contract_uuid)
+    .bind(&method)
+    .bind(kept)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("drop old uploaded profiles", err))?
+    .rows_affected();
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit upload profile", err))?;
+
+    tracing::info!(
+        contract_id = %contract_uuid,
+        %method,
+        %engine,
+        size_bytes,
+        dropped,
+        "profile uploaded"
+    );
+    state.cache.invalidate_contract(contract_uuid).await;
+    Ok((StatusCode::CREATED, Json(entry(stored))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/profiles",
+    params(
+        ("id" = String, Path, description = "Contract UUID or address"),
+        ProfileListQuery
+    ),
+    responses(
+        (status = 200, description = "Uploaded profiles, newest first", body = UploadedProfileList),
+        (status = 404, description = "Contract not found")
+    ),
+    tag = "Contracts"
+)]
+pub async fn list_profiles(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    query: Result<Query<ProfileListQuery>, QueryRejection>,
+) -> ApiResult<Json<UploadedProfileList>> {
+    let Query(query) = query.map_err(map_query_rejection)?;
+    let (contract_uuid, _) = fetch_contract_identity(&state, &id).await?;
+    let profiles: Vec<UploadedProfile> = sqlx::query_as(
+        "SELECT id, method, engine, cli_version, uploaded_by, total_duration_ns,
+                cpu_instructions, memory_bytes, function_count, size_bytes, profile,
+                uploaded_at
+         FROM contract_uploaded_profiles
+         WHERE contract_id = $1 AND ($2::TEXT IS NULL OR method = $2)
+         ORDER BY uploaded_at DESC, id",
+    )
+    .bind(contract_uuid)
+    .bind(query.method.as_deref())
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list uploaded profiles", err))?;
+
+    Ok(Json(UploadedProfileList {
+        kept_per_method: profiles_per_method(),
+        profiles: profiles.into_iter().map(entry).collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_profiler::ResourceUsage;
+
+    #[test]
+    fn summaries_label_how_the_profile_was_recorded() {
+        assert_eq!(
+            summary_line(
+                "transfer",
+                "instrumented",
+                "0.4.2",
+                1_250_000,
+                None,
+                None,
+                4
+            ),
+            "transfer: 1.250 ms over 4 functions (instrumented, CLI 0.4.2)"
+        );
+        assert_eq!(
+            summary_line(
+                "transfer",
+                "simulated",
+                "0.4.2",
+                1_250_000,
+                Some(1_250_000),
+                Some(2048),
+                1
+            ),
+            "transfer: 1.25M CPU instructions, 2.0 KB memory (simulated via RPC, CLI 0.4.2)"
+        );
+        assert!(is_simulated("simulated"));
+        assert!(!is_simulated("instrumented"));
+    }
+
+    #[test]
+    fn uploads_are_checked_before_storing() {
+        let resources = ResourceUsage {
+            cpu_instructions: Some(900),
+            ..ResourceUsage::default()
+        };
+        let profile = ProfileData::from_simulation("CABC", "transfer", resources);
+        let upload = |cli_version: &str, profile: serde_json::Value| UploadProfileRequest {
+            cli_version: cli_version.to_string(),
+            profile,
+        };
+        let document = serde_json::to_value(&profile).unwrap();
+
+        let (parsed, size) = validate_upload(&upload("0.4.2", document.clone())).unwrap();
+        assert_eq!(profiled_method(&parsed).as_deref(), Some("transfer"));
+        assert_eq!(parsed.mode, ProfileMode::Simulated);
+        assert_eq!(size, document.to_string().len());
+
+        assert!(validate_upload(&upload(" ", document.clone())).is_err());
+        assert!(
+            validate_upload(&upload("0.4.2", serde_json::json!({"schema_version": 999}))).is_err()
+        );
+
+        let mut huge = document;
+        huge["contract_path"] = serde_json::Value::String("x".repeat(max_profile_bytes()));
+        let err = validate_upload(&upload("0.4.2", huge)).unwrap_err();
+        assert_eq!(err.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(err.message().contains("limited to 256 KB"));
+    }
+}
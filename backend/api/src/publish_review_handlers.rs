@@ -0,0 +1,341 @@
+//! Admin review of publishes held by [`crate::publish_screening`].
+//!
+//!   GET  /api/admin/publish-reviews              – held publishes with their flags
+//!   POST /api/admin/publish-reviews/:id/approve  – list the contract
+//!   POST /api/admin/publish-reviews/:id/reject   – keep it hidden; a reason is required
+//!
+//! Decisions are written to the contract's audit log with the reason and the
+//! admin who made them. Approval announces the contract to live subscribers,
+//! which publishing skipped while it was held. A contract under a legal
+//! hold can't be rejected.
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use shared::{pagination::Paginated, AuditActionType, Contract, Network, PublishStatus};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    contract_events::ContractEventEnvelope,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    legal_holds::{self, HoldTarget},
+    pagination::{PageParams, Pagination},
+    state::AppState,
+};
+
+/// Longest reason kept in the audit log
+const MAX_REASON_CHARS: usize = 1000;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct PublishReviewQuery {
+    /// `pending_review` (default), `rejected`, or `published` for listed
+    /// contracts that were flagged without being held
+    pub status: Option<PublishStatus>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct PublishDecisionRequest {
+    /// Why the publish was approved or rejected; required to reject
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, FromRow, utoipa::ToSchema)]
+pub struct PublishFlagView {
+    #[serde(skip)]
+    pub contract_id: Uuid,
+    /// `duplicate_wasm`, `similar_name`, `similar_description` or `blocked_term`
+    pub kind: String,
+    /// Existing contract the publish resembles
+    pub related_contract_id: Option<Uuid>,
+    pub related_contract_name: Option<String>,
+    /// Trigram similarity to the related contract, 0..1
+    pub similarity: Option<f64>,
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, FromRow, utoipa::ToSchema)]
+pub struct PublishReviewItem {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub network: Network,
+    pub wasm_hash: String,
+    pub publish_status: PublishStatus,
+    pub publisher_address: String,
+    /// When the publisher first appeared in the registry
+    pub publisher_created_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    #[sqlx(skip)]
+    pub flags: Vec<PublishFlagView>,
+}
+
+/// Publishes in a review state with what screening found, oldest first.
+#[utoipa::path(
+    get,
+    path = "/api/admin/publish-reviews",
+    params(PublishReviewQuery, PageParams),
+    responses(
+        (status = 200, description = "Screened publishes with their flags", body = Paginated<PublishReviewItem>),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Contracts"
+)]
+pub async fn list_publish_reviews(
+    State(state): State<AppState>,
+    Query(query): Query<PublishReviewQuery>,
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Json<Paginated<PublishReviewItem>>> {
+    let status = query.status.unwrap_or(PublishStatus::PendingReview);
+    // Listed contracts only show up here when screening flagged them
+    const FILTER: &str = "c.publish_status = $1
+        AND ($1 <> 'published'
+             OR EXISTS (SELECT 1 FROM contract_publish_flags f WHERE f.contract_id = c.id))";
+
+    let total: i64 = sqlx::query_scalar(&format!(
+        "SELECT COUNT(*) FROM contracts c WHERE {}",
+        FILTER
+    ))
+    .bind(status)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count publish reviews", err))?;
+
+    let mut items: Vec<PublishReviewItem> = sqlx::query_as(&format!(
+        "SELECT c.id, c.contract_id, c.name, c.description, c.network, c.wasm_hash,
+                c.publish_status, c.created_at,
+                p.stellar_address AS publisher_address,
+                p.created_at AS publisher_created_at
+         FROM contracts c
+         JOIN publishers p ON p.id = c.publisher_id
+         WHERE {}
+         ORDER BY c.created_at, c.id
+         LIMIT $2 OFFSET $3",
+        FILTER
+    ))
+    .bind(status)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list publish reviews", err))?;
+
+    let ids: Vec<Uuid> = items.iter().map(|item| item.id).collect();
+    let flags: Vec<PublishFlagView> = sqlx::query_as(
+        "SELECT f.contract_id, f.kind, f.related_contract_id,
+                r.name AS related_contract_name, f.similarity, f.detail
+         FROM contract_publish_flags f
+         LEFT JOIN contracts r ON r.id = f.related_contract_id
+         WHERE f.contract_id = ANY($1)
+         ORDER BY f.created_at, f.id",
+    )
+    .bind(&ids)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list publish flags", err))?;
+
+    let mut by_contract: HashMap<Uuid, Vec<PublishFlagView>> = HashMap::new();
+    for flag in flags {
+        by_contract.entry(flag.contract_id).or_default().push(flag);
+    }
+    for item in &mut items {
+        item.flags = by_contract.remove(&item.id).unwrap_or_default();
+    }
+
+    Ok(Json(Paginated::new(items, total, limit, offset)))
+}
+
+/// List a held publish.
+#[utoipa::path(
+    post,
+    path = "/api/admin/publish-reviews/{id}/approve",
+    params(("id" = Uuid, Path, description = "Contract UUID")),
+    request_body = PublishDecisionRequest,
+    responses(
+        (status = 200, description = "Contract published", body = Contract),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "No such contract"),
+        (status = 409, description = "The contract is not pending review")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Contracts"
+)]
+pub async fn approve_publish(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: AuthClaims,
+    Json(req): Json<PublishDecisionRequest>,
+) -> ApiResult<Json<Contract>> {
+    let reason = clean_reason(req.reason)?;
+    let contract = decide(&state, id, &claims, PublishStatus::Published, reason).await?;
+
+    let publisher_address: Option<String> =
+        sqlx::query_scalar("SELECT stellar_address FROM publishers WHERE id = $1")
+            .bind(contract.publisher_id)
+            .fetch_optional(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch publisher", err))?;
+    state
+        .contract_events
+        .publish(ContractEventEnvelope::deployed(
+            &contract,
+            publisher_address,
+        ));
+
+    Ok(Json(contract))
+}
+
+/// Reject a held publish. The contract stays out of listings and search.
+#[utoipa::path(
+    post,
+    path = "/api/admin/publish-reviews/{id}/reject",
+    params(("id" = Uuid, Path, description = "Contract UUID")),
+    request_body = PublishDecisionRequest,
+    responses(
+        (status = 200, description = "Contract rejected", body = Contract),
+        (status = 400, description = "Missing reason"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "No such contract"),
+        (status = 409, description = "The contract is not pending review"),
+        (status = 423, description = "The contract is under a legal hold")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Contracts"
+)]
+pub async fn reject_publish(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: AuthClaims,
+    Json(req): Json<PublishDecisionRequest>,
+) -> ApiResult<Json<Contract>> {
+    let Some(reason) = clean_reason(req.reason)? else {
+        return Err(ApiError::bad_request(
+            "ReasonRequired",
+            "A reason is required to reject a publish",
+        ));
+    };
+    let contract = decide(&state, id, &claims, PublishStatus::Rejected, Some(reason)).await?;
+    Ok(Json(contract))
+}
+
+/// Trims the reason; blank counts as none
+fn clean_reason(reason: Option<String>) -> ApiResult<Option<String>> {
+    let Some(reason) = reason
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+    else {
+        return Ok(None);
+    };
+    if reason.chars().count() > MAX_REASON_CHARS {
+        return Err(ApiError::bad_request(
+            "InvalidReason",
+            format!("reason must be at most {} characters", MAX_REASON_CHARS),
+        ));
+    }
+    Ok(Some(reason))
+}
+
+/// Moves a pending publish to `target` and audits the decision
+async fn decide(
+    state: &AppState,
+    id: Uuid,
+    claims: &AuthClaims,
+    target: PublishStatus,
+    reason: Option<String>,
+) -> ApiResult<Contract> {
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin publish review", err))?;
+
+    let current: Option<PublishStatus> =
+        sqlx::query_scalar("SELECT publish_status FROM contracts WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("lock contract for review", err))?;
+    match current {
+        None => {
+            return Err(ApiError::not_found(
+                "ContractNotFound",
+                format!("No contract found with ID: {}", id),
+            ))
+        }
+        Some(PublishStatus::PendingReview) => {}
+        Some(_) => {
+            return Err(ApiError::conflict(
+                "NotPendingReview",
+                "Only publishes pending review can be approved or rejected",
+            ))
+        }
+    }
+    // Rejecting unpublishes the contract for good
+    if target == PublishStatus::Rejected {
+        legal_holds::ensure_not_held(&mut *tx, HoldTarget::Contract, id).await?;
+    }
+
+    let contract: Contract =
+        sqlx::query_as("UPDATE contracts SET publish_status = $2 WHERE id = $1 RETURNING *")
+            .bind(id)
+            .bind(target)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("update publish status", err))?;
+
+    let action = match target {
+        PublishStatus::Published => AuditActionType::PublishApproved,
+        _ => AuditActionType::PublishRejected,
+    };
+    sqlx::query(
+        "INSERT INTO contract_audit_log (action_type, contract_id, old_value, new_value, changed_by)
+         VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(&action)
+    .bind(id)
+    .bind(json!({ "publish_status": PublishStatus::PendingReview }))
+    .bind(json!({ "publish_status": target, "reason": reason }))
+    .bind(&claims.sub)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("write publish review audit log", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit publish review", err))?;
+
+    state.cache.invalidate_all_contracts();
+    tracing::info!(
+        contract_id = %id,
+        decision = %action,
+        admin = %claims.sub,
+        "publish reviewed"
+    );
+    Ok(contract)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reasons_are_trimmed_and_bounded() {
+        assert_eq!(clean_reason(None).unwrap(), None);
+        assert_eq!(clean_reason(Some("   ".into())).unwrap(), None);
+        assert_eq!(
+            clean_reason(Some(" copy of an audited DEX ".into())).unwrap(),
+            Some("copy of an audited DEX".to_string())
+        );
+        assert!(clean_reason(Some("x".repeat(MAX_REASON_CHARS + 1))).is_err());
+    }
+}
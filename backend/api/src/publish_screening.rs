@@ -0,0 +1,346 @@
+//! Duplicate and spam screening for new publishes.
+//!
+//! Every public publish is compared against what other publishers already
+//! have in the registry:
+//!
+//! - an identical WASM hash under a different name is flagged and linked to
+//!   the earliest contract with that hash,
+//! - names and descriptions are compared by trigram similarity against
+//!   publishes from the last `lookback_days`,
+//! - name, description and tags are checked for blocklisted terms.
+//!
+//! Findings are stored in `contract_publish_flags`. A publish is held as
+//! `pending_review` when it contains a blocked term, or when a publisher
+//! younger than `new_publisher_days` trips any similarity check; everything
+//! else is published with its flags kept for reference, so forks by
+//! established publishers aren't blocked. Admins work the queue through
+//! [`crate::publish_review_handlers`].
+//!
+//! | Variable                                    | Default |
+//! |---------------------------------------------|---------|
+//! | `PUBLISH_SCREENING_ENABLED`                 | `true`  |
+//! | `PUBLISH_SCREENING_NAME_SIMILARITY`         | 0.8     |
+//! | `PUBLISH_SCREENING_DESCRIPTION_SIMILARITY`  | 0.9     |
+//! | `PUBLISH_SCREENING_LOOKBACK_DAYS`           | 30      |
+//! | `PUBLISH_SCREENING_NEW_PUBLISHER_DAYS`      | 7       |
+//! | `PUBLISH_SCREENING_BLOCKED_TERMS`           | none, comma-separated |
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+const DEFAULT_NAME_SIMILARITY: f64 = 0.8;
+const DEFAULT_DESCRIPTION_SIMILARITY: f64 = 0.9;
+const DEFAULT_LOOKBACK_DAYS: u32 = 30;
+const DEFAULT_NEW_PUBLISHER_DAYS: u32 = 7;
+/// Shorter descriptions ("A token contract") match too much to mean anything
+const MIN_DESCRIPTION_CHARS: usize = 40;
+/// Similar contracts recorded per check
+const MAX_MATCHES: i64 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreeningConfig {
+    pub enabled: bool,
+    /// Trigram similarity (0..1) at which names count as near-duplicates
+    pub name_similarity: f64,
+    /// Trigram similarity (0..1) at which descriptions count as near-duplicates
+    pub description_similarity: f64,
+    /// How far back name and description matches look
+    pub lookback_days: u32,
+    /// Publishers younger than this are held on any similarity match
+    pub new_publisher_days: u32,
+    /// Lowercased terms that hold a publish for review wherever they appear
+    pub blocked_terms: Vec<String>,
+}
+
+impl Default for ScreeningConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            name_similarity: DEFAULT_NAME_SIMILARITY,
+            description_similarity: DEFAULT_DESCRIPTION_SIMILARITY,
+            lookback_days: DEFAULT_LOOKBACK_DAYS,
+            new_publisher_days: DEFAULT_NEW_PUBLISHER_DAYS,
+            blocked_terms: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagKind {
+    DuplicateWasm,
+    SimilarName,
+    SimilarDescription,
+    BlockedTerm,
+}
+
+impl FlagKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlagKind::DuplicateWasm => "duplicate_wasm",
+            FlagKind::SimilarName => "similar_name",
+            FlagKind::SimilarDescription => "similar_description",
+            FlagKind::BlockedTerm => "blocked_term",
+        }
+    }
+}
+
+/// One finding about a publish
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishFlag {
+    pub kind: FlagKind,
+    /// Existing contract the publish resembles
+    pub related_contract_id: Option<Uuid>,
+    pub similarity: Option<f64>,
+    pub detail: String,
+}
+
+/// What is being published
+pub struct Submission<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub tags: &'a [String],
+    pub wasm_hash: &'a str,
+    pub publisher_id: Uuid,
+    pub publisher_created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+pub struct Screening {
+    pub flags: Vec<PublishFlag>,
+    /// Create the contract as `pending_review`
+    pub hold: bool,
+}
+
+#[derive(FromRow)]
+struct Match {
+    id: Uuid,
+    name: String,
+    score: f64,
+}
+
+/// Screens a submission. Returns no flags when screening is disabled.
+pub async fn screen(
+    db: &PgPool,
+    config: &ScreeningConfig,
+    submission: &Submission<'_>,
+) -> Result<Screening, sqlx::Error> {
+    if !config.enabled {
+        return Ok(Screening::default());
+    }
+
+    let mut flags = Vec::new();
+
+    // The earliest contract with this WASM is taken as the original
+    let original: Option<(Uuid, String)> = sqlx::query_as(
+        "SELECT id, name FROM contracts
+         WHERE wasm_hash = $1
+           AND publisher_id <> $2
+           AND lower(name) <> lower($3)
+           AND publish_status <> 'rejected'
+         ORDER BY created_at
+         LIMIT 1",
+    )
+    .bind(submission.wasm_hash)
+    .bind(submission.publisher_id)
+    .bind(submission.name)
+    .fetch_optional(db)
+    .await?;
+    if let Some((id, name)) = original {
+        flags.push(PublishFlag {
+            kind: FlagKind::DuplicateWasm,
+            related_contract_id: Some(id),
+            similarity: Some(1.0),
+            detail: format!("Same WASM as '{}', published under another name", name),
+        });
+    }
+
+    let names: Vec<Match> = sqlx::query_as(
+        "SELECT id, name, similarity(lower(name), lower($1))::float8 AS score
+         FROM contracts
+         WHERE publisher_id <> $2
+           AND publish_status <> 'rejected'
+           AND created_at >= NOW() - make_interval(days => $3)
+           AND similarity(lower(name), lower($1)) >= $4
+         ORDER BY score DESC, created_at
+         LIMIT $5",
+    )
+    .bind(submission.name)
+    .bind(submission.publisher_id)
+    .bind(config.lookback_days as i32)
+    .bind(config.name_similarity)
+    .bind(MAX_MATCHES)
+    .fetch_all(db)
+    .await?;
+    flags.extend(names.into_iter().map(|m| PublishFlag {
+        kind: FlagKind::SimilarName,
+        related_contract_id: Some(m.id),
+        similarity: Some(m.score),
+        detail: format!("Name resembles '{}'", m.name),
+    }));
+
+    if let Some(description) = submission
+        .description
+        .filter(|d| d.trim().chars().count() >= MIN_DESCRIPTION_CHARS)
+    {
+        let descriptions: Vec<Match> = sqlx::query_as(
+            "SELECT id, name, similarity(description, $1)::float8 AS score
+             FROM contracts
+             WHERE publisher_id <> $2
+               AND publish_status <> 'rejected'
+               AND description IS NOT NULL
+               AND created_at >= NOW() - make_interval(days => $3)
+               AND similarity(description, $1) >= $4
+             ORDER BY score DESC, created_at
+             LIMIT $5",
+        )
+        .bind(description)
+        .bind(submission.publisher_id)
+        .bind(config.lookback_days as i32)
+        .bind(config.description_similarity)
+        .bind(MAX_MATCHES)
+        .fetch_all(db)
+        .await?;
+        flags.extend(descriptions.into_iter().map(|m| PublishFlag {
+            kind: FlagKind::SimilarDescription,
+            related_contract_id: Some(m.id),
+            similarity: Some(m.score),
+            detail: format!("Description resembles that of '{}'", m.name),
+        }));
+    }
+
+    flags.extend(
+        blocked_terms_in(config, submission)
+            .into_iter()
+            .map(|term| PublishFlag {
+                kind: FlagKind::BlockedTerm,
+                related_contract_id: None,
+                similarity: None,
+                detail: format!("Contains blocked term '{}'", term),
+            }),
+    );
+
+    let new_publisher = Utc::now() - submission.publisher_created_at
+        < Duration::days(config.new_publisher_days as i64);
+    let hold = should_hold(&flags, new_publisher);
+    Ok(Screening { flags, hold })
+}
+
+/// Blocked terms found in the name, description or tags, in config order
+fn blocked_terms_in<'c>(config: &'c ScreeningConfig, submission: &Submission<'_>) -> Vec<&'c str> {
+    let mut text = submission.name.to_lowercase();
+    if let Some(description) = submission.description {
+        text.push('\n');
+        text.push_str(&description.to_lowercase());
+    }
+    for tag in submission.tags {
+        text.push('\n');
+        text.push_str(&tag.to_lowercase());
+    }
+    config
+        .blocked_terms
+        .iter()
+        .filter(|term| text.contains(term.as_str()))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Blocked terms always hold a publish. Resemblance to other contracts only
+/// holds it for new publishers; established ones are trusted with forks.
+fn should_hold(flags: &[PublishFlag], new_publisher: bool) -> bool {
+    flags.iter().any(|flag| match flag.kind {
+        FlagKind::BlockedTerm => true,
+        FlagKind::DuplicateWasm | FlagKind::SimilarName | FlagKind::SimilarDescription => {
+            new_publisher
+        }
+    })
+}
+
+/// Stores the findings for a newly created contract
+pub async fn record_flags(
+    db: &PgPool,
+    contract_id: Uuid,
+    flags: &[PublishFlag],
+) -> Result<(), sqlx::Error> {
+    for flag in flags {
+        sqlx::query(
+            "INSERT INTO contract_publish_flags
+                 (contract_id, kind, related_contract_id, similarity, detail)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(contract_id)
+        .bind(flag.kind.as_str())
+        .bind(flag.related_contract_id)
+        .bind(flag.similarity)
+        .bind(&flag.detail)
+        .execute(db)
+        .await?;
+    }
+    Ok(())
+}
+
+/// Splits `PUBLISH_SCREENING_BLOCKED_TERMS` into lowercased terms
+pub fn parse_blocked_terms(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(|term| term.trim().to_lowercase())
+        .filter(|term| !term.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flag(kind: FlagKind) -> PublishFlag {
+        PublishFlag {
+            kind,
+            related_contract_id: None,
+            similarity: None,
+            detail: String::new(),
+        }
+    }
+
+    #[test]
+    fn only_new_publishers_are_held_for_resemblance() {
+        let similar = [flag(FlagKind::SimilarName), flag(FlagKind::DuplicateWasm)];
+        assert!(should_hold(&similar, true));
+        assert!(!should_hold(&similar, false));
+        assert!(should_hold(&[flag(FlagKind::BlockedTerm)], false));
+        assert!(!should_hold(&[], true));
+    }
+
+    #[test]
+    fn blocked_terms_match_anywhere_case_insensitively() {
+        let config = ScreeningConfig {
+            blocked_terms: parse_blocked_terms(" Airdrop, ,free money,casino "),
+            ..ScreeningConfig::default()
+        };
+        assert_eq!(
+            config.blocked_terms,
+            vec!["airdrop", "free money", "casino"]
+        );
+
+        let tags = vec!["CASINO".to_string()];
+        let submission = Submission {
+            name: "Mega AIRDROP token",
+            description: Some("Claim now"),
+            tags: &tags,
+            wasm_hash: "abc",
+            publisher_id: Uuid::nil(),
+            publisher_created_at: Utc::now(),
+        };
+        assert_eq!(
+            blocked_terms_in(&config, &submission),
+            vec!["airdrop", "casino"]
+        );
+
+        let clean = Submission {
+            name: "Liquidity pool",
+            tags: &[],
+            ..submission
+        };
+        assert!(blocked_terms_in(&config, &clean).is_empty());
+    }
+}
@@ -28,7 +28,6 @@
 
 use std::{
     collections::{HashMap, VecDeque},
-    env,
     net::{IpAddr, SocketAddr},
     time::{Duration, Instant},
 };
@@ -36,10 +35,7 @@ use std::{
 use axum::{
     body::Body,
     extract::{connect_info::ConnectInfo, State},
-    http::{
-        header::{AUTHORIZATION, RETRY_AFTER},
-        HeaderName, HeaderValue, Request,
-    },
+    http::{header::AUTHORIZATION, HeaderName, HeaderValue, Request},
     middleware::Next,
     response::{IntoResponse, Response},
 };
@@ -69,11 +65,7 @@ pub struct RateLimitState {
 }
 
 impl RateLimitState {
-    pub fn from_env() -> Self {
-        Self::new(RateLimitConfig::from_env())
-    }
-
-    fn new(config: RateLimitConfig) -> Self {
+    pub fn new(config: RateLimitConfig) -> Self {
         Self {
             config: std::sync::Arc::new(config),
             buckets: std::sync::Arc::new(Mutex::new(HashMap::new())),
@@ -191,36 +183,25 @@ impl RateLimitState {
     }
 }
 
-struct RateLimitConfig {
-    anonymous_limit: u32,
-    auth_limit: u32,
-    window: Duration,
+/// Per-client request budgets, validated at startup by [`crate::config`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    pub anonymous_limit: u32,
+    pub auth_limit: u32,
+    pub window: Duration,
 }
 
-impl RateLimitConfig {
-    fn from_env() -> Self {
-        let anonymous_limit = env_u32_with_fallback(
-            "RATE_LIMIT_ANON_PER_MINUTE",
-            "RATE_LIMIT_READ_PER_MINUTE",
-            DEFAULT_ANON_LIMIT_PER_MINUTE,
-        );
-        let auth_limit = env_u32("RATE_LIMIT_AUTH_PER_MINUTE", DEFAULT_AUTH_LIMIT_PER_MINUTE);
-        let window_seconds = env_u64("RATE_LIMIT_WINDOW_SECONDS", DEFAULT_WINDOW_SECONDS).max(1);
-
-        tracing::info!(
-            anonymous_limit,
-            auth_limit,
-            window_seconds,
-            "Rate limiter configured"
-        );
-
+impl Default for RateLimitConfig {
+    fn default() -> Self {
         Self {
-            anonymous_limit,
-            auth_limit,
-            window: Duration::from_secs(window_seconds),
+            anonymous_limit: DEFAULT_ANON_LIMIT_PER_MINUTE,
+            auth_limit: DEFAULT_AUTH_LIMIT_PER_MINUTE,
+            window: Duration::from_secs(DEFAULT_WINDOW_SECONDS),
         }
     }
+}
 
+impl RateLimitConfig {
     #[cfg(test)]
     fn for_tests(anonymous_limit: u32, auth_limit: u32, window: Duration) -> Self {
         Self {
@@ -231,6 +212,52 @@ impl RateLimitConfig {
     }
 }
 
+/// A sliding window of at most `limit` calls per caller, for limits tighter
+/// than the per-client budgets above, such as test webhook sends
+#[derive(Debug)]
+pub struct CallerLimiter {
+    limit: usize,
+    window: Duration,
+    /// When each caller's calls of the window were made
+    calls: std::sync::Mutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+impl CallerLimiter {
+    pub fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            calls: Default::default(),
+        }
+    }
+
+    /// Counts a call by `caller`, or returns the seconds until the oldest
+    /// call of the window expires when the limit is already reached
+    pub fn check(&self, caller: &str, now: Instant) -> Result<(), u64> {
+        let mut calls = self
+            .calls
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        calls.retain(|_, times| {
+            while times
+                .front()
+                .is_some_and(|made| now.duration_since(*made) >= self.window)
+            {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+        let times = calls.entry(caller.to_string()).or_default();
+        if times.len() >= self.limit {
+            let oldest = times.front().copied().unwrap_or(now);
+            let wait = self.window.saturating_sub(now.duration_since(oldest));
+            return Err(ceil_duration_to_seconds(wait).max(1));
+        }
+        times.push_back(now);
+        Ok(())
+    }
+}
+
 #[derive(Hash, Eq, PartialEq)]
 struct BucketKey {
     client_key: String,
@@ -259,16 +286,9 @@ pub async fn rate_limit_middleware(
     if !decision.allowed {
         let mut response =
             ApiError::rate_limited("Too many requests. Please retry after the indicated time.")
-                .with_details(serde_json::json!({
-                    "retry_after_seconds": decision.reset_seconds
-                }))
+                .with_retry_after(decision.reset_seconds)
                 .into_response();
         attach_rate_limit_headers(&mut response, &decision);
-        response.headers_mut().insert(
-            RETRY_AFTER,
-            HeaderValue::from_str(&decision.reset_seconds.to_string())
-                .unwrap_or_else(|_| HeaderValue::from_static("1")),
-        );
         return response;
     }
 
@@ -409,47 +429,6 @@ fn endpoint_key(method: &Method, path: &str) -> String {
     }
 }
 
-fn env_u32(key: &str, default: u32) -> u32 {
-    match env::var(key) {
-        Ok(raw) => match raw.parse::<u32>() {
-            Ok(value) if value > 0 => value,
-            _ => {
-                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
-                default
-            }
-        },
-        Err(_) => default,
-    }
-}
-
-fn env_u32_with_fallback(primary_key: &str, fallback_key: &str, default: u32) -> u32 {
-    match env::var(primary_key) {
-        Ok(raw) => match raw.parse::<u32>() {
-            Ok(value) if value > 0 => value,
-            _ => {
-                tracing::warn!(
-                    "Invalid value for {primary_key} (`{raw}`), using default {default}"
-                );
-                default
-            }
-        },
-        Err(_) => env_u32(fallback_key, default),
-    }
-}
-
-fn env_u64(key: &str, default: u64) -> u64 {
-    match env::var(key) {
-        Ok(raw) => match raw.parse::<u64>() {
-            Ok(value) if value > 0 => value,
-            _ => {
-                tracing::warn!("Invalid value for {key} (`{raw}`), using default {default}");
-                default
-            }
-        },
-        Err(_) => default,
-    }
-}
-
 fn ceil_duration_to_seconds(duration: Duration) -> u64 {
     let secs = duration.as_secs();
     if duration.subsec_nanos() > 0 {
@@ -463,7 +442,7 @@ fn ceil_duration_to_seconds(duration: Duration) -> u64 {
 mod tests {
     use super::*;
     use axum::{
-        http::{Request, StatusCode},
+        http::{header::RETRY_AFTER, Request, StatusCode},
         middleware,
         routing::get,
         Router,
@@ -609,7 +588,8 @@ mod tests {
             .await
             .unwrap();
         let value: serde_json::Value = serde_json::from_slice(&body).unwrap();
-        assert_eq!(value["error_code"], "RATE_LIMITED");
+        assert_eq!(value["error"]["code"], "RATE_LIMITED");
+        assert!(value["error"]["details"]["retry_after_seconds"].is_u64());
     }
 
     #[tokio::test]
@@ -797,4 +777,20 @@ mod tests {
 
         assert_eq!(state.buckets.lock().await.len(), 0);
     }
+
+    #[test]
+    fn caller_limits_are_per_caller_and_slide() {
+        let limiter = CallerLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+        for _ in 0..2 {
+            assert!(limiter.check("alice", start).is_ok());
+        }
+        let retry_after = limiter.check("alice", start + Duration::from_secs(15));
+        assert_eq!(retry_after, Err(45));
+        assert!(limiter.check("bob", start).is_ok());
+
+        let later = start + Duration::from_secs(60);
+        assert!(limiter.check("alice", later).is_ok());
+        assert!(!limiter.calls.lock().unwrap().contains_key("bob"));
+    }
 }
@@ -0,0 +1,342 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::{FromRow, PgExecutor};
+use uuid::Uuid;
+
+use crate::{
+    auth::{self, AuthClaims},
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    incident_handlers::{ensure_contracts_exist, parse_recovery_duration},
+    incident_models::DurationInput,
+    recovery_objective_models::{
+        CreateRecoveryObjectiveRequest, ObjectiveSource, RecoveryObjective, RecoveryTargets,
+        UpdateRecoveryObjectiveRequest,
+    },
+    state::AppState,
+};
+
+const DEFAULT_RTO_TARGET_SECS: i64 = 4 * 60 * 60;
+const DEFAULT_RPO_TARGET_SECS: i64 = 60 * 60;
+
+fn parse_objective_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidRecoveryObjectiveId",
+            format!("Invalid recovery objective ID format: {}", id),
+        )
+    })
+}
+
+fn require_admin(claims: &AuthClaims) -> ApiResult<()> {
+    if auth::is_admin(claims) {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden(
+            "Administrative privileges are required to manage recovery objectives",
+        ))
+    }
+}
+
+fn objective_not_found(id: &str) -> ApiError {
+    ApiError::not_found(
+        "RecoveryObjectiveNotFound",
+        format!("No recovery objective found with ID: {}", id),
+    )
+}
+
+/// Validates a target. A zero RTO target could never be met, so it is rejected.
+fn parse_target(field: &str, input: &DurationInput, allow_zero: bool) -> ApiResult<i64> {
+    let seconds = parse_recovery_duration(field, Some(input))?.unwrap_or_default();
+    if seconds == 0 && !allow_zero {
+        return Err(ApiError::unprocessable(
+            "InvalidRecoveryDuration",
+            format!("{} must be greater than zero", field),
+        ));
+    }
+    Ok(seconds)
+}
+
+/// Reads a recovery target such as `INCIDENT_RTO_TARGET=2h`.
+fn env_target_seconds(var: &str) -> Option<i64> {
+    let raw = std::env::var(var).ok()?;
+    DurationInput::Text(raw.clone())
+        .to_seconds()
+        .map_err(|err| {
+            tracing::warn!(var, value = %raw, error = %err, "ignoring invalid recovery target");
+        })
+        .ok()
+}
+
+/// Targets used when no `recovery_objectives` row applies: the
+/// `INCIDENT_RTO_TARGET` / `INCIDENT_RPO_TARGET` variables, else built-in defaults.
+pub(crate) fn fallback_targets() -> RecoveryTargets {
+    let rto = env_target_seconds("INCIDENT_RTO_TARGET");
+    let rpo = env_target_seconds("INCIDENT_RPO_TARGET");
+    RecoveryTargets {
+        rto_target_seconds: rto.unwrap_or(DEFAULT_RTO_TARGET_SECS),
+        rpo_target_seconds: rpo.unwrap_or(DEFAULT_RPO_TARGET_SECS),
+        source: if rto.is_some() || rpo.is_some() {
+            ObjectiveSource::Environment
+        } else {
+            ObjectiveSource::Default
+        },
+    }
+}
+
+#[derive(FromRow)]
+struct TargetRow {
+    contract_rto: Option<i64>,
+    contract_rpo: Option<i64>,
+    global_rto: Option<i64>,
+    global_rpo: Option<i64>,
+}
+
+/// Resolves the targets for an incident, or the registry-wide targets when
+/// `incident_id` is `None`. The strictest override among the incident's
+/// affected contracts wins over the global objective.
+pub(crate) async fn resolve_targets<'e, E: PgExecutor<'e>>(
+    executor: E,
+    incident_id: Option<Uuid>,
+) -> Result<RecoveryTargets, sqlx::Error> {
+    let row: TargetRow = sqlx::query_as(
+        r#"
+        SELECT
+            MIN(rto_target_seconds) FILTER (WHERE contract_id IS NOT NULL) AS contract_rto,
+            MIN(rpo_target_seconds) FILTER (WHERE contract_id IS NOT NULL) AS contract_rpo,
+            MIN(rto_target_seconds) FILTER (WHERE contract_id IS NULL) AS global_rto,
+            MIN(rpo_target_seconds) FILTER (WHERE contract_id IS NULL) AS global_rpo
+        FROM recovery_objectives
+        WHERE contract_id IS NULL
+           OR contract_id IN (SELECT contract_id FROM incident_contracts WHERE incident_id = $1)
+        "#,
+    )
+    .bind(incident_id)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(RecoveryTargets::resolve(
+        row.contract_rto.zip(row.contract_rpo),
+        row.global_rto.zip(row.global_rpo),
+        fallback_targets(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/recovery-objectives",
+    responses(
+        (status = 200, description = "Global objective first, then per-contract overrides", body = [RecoveryObjective]),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Recovery Objectives"
+)]
+pub async fn list_recovery_objectives(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Vec<RecoveryObjective>>> {
+    require_admin(&claims)?;
+
+    let objectives: Vec<RecoveryObjective> = sqlx::query_as(
+        "SELECT * FROM recovery_objectives
+         ORDER BY contract_id IS NOT NULL, created_at",
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("list recovery objectives", err))?;
+
+    Ok(Json(objectives))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/recovery-objectives",
+    request_body = CreateRecoveryObjectiveRequest,
+    responses(
+        (status = 201, description = "Recovery objective created", body = RecoveryObjective),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 409, description = "An objective already exists for this scope"),
+        (status = 422, description = "Invalid target or unknown contract ID")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Recovery Objectives"
+)]
+pub async fn create_recovery_objective(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<CreateRecoveryObjectiveRequest>,
+) -> ApiResult<(StatusCode, Json<RecoveryObjective>)> {
+    require_admin(&claims)?;
+
+    let rto_target = parse_target("rto_target", &req.rto_target, false)?;
+    let rpo_target = parse_target("rpo_target", &req.rpo_target, true)?;
+    if let Some(contract_id) = req.contract_id {
+        ensure_contracts_exist(&state, &[contract_id]).await?;
+    }
+
+    let objective: RecoveryObjective = sqlx::query_as(
+        r#"
+        INSERT INTO recovery_objectives
+            (contract_id, rto_target_seconds, rpo_target_seconds, created_by)
+        VALUES ($1, $2, $3, $4)
+        RETURNING *
+        "#,
+    )
+    .bind(req.contract_id)
+    .bind(rto_target)
+    .bind(rpo_target)
+    .bind(&claims.sub)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => ApiError::conflict(
+            "RecoveryObjectiveExists",
+            match req.contract_id {
+                Some(id) => format!("Contract {} already has a recovery objective", id),
+                None => "A global recovery objective already exists".to_string(),
+            },
+        ),
+        _ => db_internal_error("create recovery objective", err),
+    })?;
+
+    tracing::info!(
+        objective_id = %objective.id,
+        contract_id = ?objective.contract_id,
+        rto_target,
+        rpo_target,
+        "recovery objective created"
+    );
+
+    Ok((StatusCode::CREATED, Json(objective)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/recovery-objectives/{id}",
+    params(
+        ("id" = String, Path, description = "Recovery objective UUID")
+    ),
+    responses(
+        (status = 200, description = "Recovery objective", body = RecoveryObjective),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Recovery objective not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Recovery Objectives"
+)]
+pub async fn get_recovery_objective(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<Json<RecoveryObjective>> {
+    require_admin(&claims)?;
+    let objective_id = parse_objective_id(&id)?;
+
+    sqlx::query_as("SELECT * FROM recovery_objectives WHERE id = $1")
+        .bind(objective_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("get recovery objective", err))?
+        .map(Json)
+        .ok_or_else(|| objective_not_found(&id))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/recovery-objectives/{id}",
+    params(
+        ("id" = String, Path, description = "Recovery objective UUID")
+    ),
+    request_body = UpdateRecoveryObjectiveRequest,
+    responses(
+        (status = 200, description = "Recovery objective updated", body = RecoveryObjective),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Recovery objective not found"),
+        (status = 422, description = "Invalid target")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Recovery Objectives"
+)]
+pub async fn update_recovery_objective(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<UpdateRecoveryObjectiveRequest>,
+) -> ApiResult<Json<RecoveryObjective>> {
+    require_admin(&claims)?;
+    let objective_id = parse_objective_id(&id)?;
+
+    let rto_target = req
+        .rto_target
+        .as_ref()
+        .map(|input| parse_target("rto_target", input, false))
+        .transpose()?;
+    let rpo_target = req
+        .rpo_target
+        .as_ref()
+        .map(|input| parse_target("rpo_target", input, true))
+        .transpose()?;
+
+    sqlx::query_as(
+        r#"
+        UPDATE recovery_objectives
+        SET rto_target_seconds = COALESCE($1, rto_target_seconds),
+            rpo_target_seconds = COALESCE($2, rpo_target_seconds),
+            created_by = $3,
+            updated_at = NOW()
+        WHERE id = $4
+        RETURNING *
+        "#,
+    )
+    .bind(rto_target)
+    .bind(rpo_target)
+    .bind(&claims.sub)
+    .bind(objective_id)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update recovery objective", err))?
+    .map(Json)
+    .ok_or_else(|| objective_not_found(&id))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/recovery-objectives/{id}",
+    params(
+        ("id" = String, Path, description = "Recovery objective UUID")
+    ),
+    responses(
+        (status = 204, description = "Recovery objective deleted"),
+        (status = 400, description = "Malformed ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Recovery objective not found")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Recovery Objectives"
+)]
+pub async fn delete_recovery_objective(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    require_admin(&claims)?;
+    let objective_id = parse_objective_id(&id)?;
+
+    let result = sqlx::query("DELETE FROM recovery_objectives WHERE id = $1")
+        .bind(objective_id)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete recovery objective", err))?;
+
+    if result.rows_affected() == 0 {
+        return Err(objective_not_found(&id));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
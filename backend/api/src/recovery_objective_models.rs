@@ -0,0 +1,195 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::incident_models::{DurationInput, RecoveryDuration};
+
+/// Target RTO/RPO, either registry-wide (`contract_id` null) or overriding
+/// the global targets for one contract
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct RecoveryObjective {
+    pub id: Uuid,
+    /// `null` for the global objective
+    pub contract_id: Option<Uuid>,
+    #[sqlx(rename = "rto_target_seconds")]
+    pub rto_target: RecoveryDuration,
+    #[sqlx(rename = "rpo_target_seconds")]
+    pub rpo_target: RecoveryDuration,
+    /// JWT subject of the admin who last changed the objective
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CreateRecoveryObjectiveRequest {
+    /// Omit to create the global objective
+    pub contract_id: Option<Uuid>,
+    pub rto_target: DurationInput,
+    pub rpo_target: DurationInput,
+}
+
+/// Partial update; omitted targets keep their current values
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UpdateRecoveryObjectiveRequest {
+    pub rto_target: Option<DurationInput>,
+    pub rpo_target: Option<DurationInput>,
+}
+
+/// Where the targets applied to an incident came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectiveSource {
+    /// Override for one of the affected contracts
+    Contract,
+    /// Global `recovery_objectives` row
+    Global,
+    /// `INCIDENT_RTO_TARGET` / `INCIDENT_RPO_TARGET`
+    Environment,
+    /// Built-in defaults; no targets have been configured
+    Default,
+}
+
+/// Targets in force for one incident
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryTargets {
+    pub rto_target_seconds: i64,
+    pub rpo_target_seconds: i64,
+    pub source: ObjectiveSource,
+}
+
+impl RecoveryTargets {
+    /// Picks the strictest override among the affected contracts, then the
+    /// global objective, then `fallback`.
+    pub fn resolve(
+        contract: Option<(i64, i64)>,
+        global: Option<(i64, i64)>,
+        fallback: RecoveryTargets,
+    ) -> Self {
+        let (targets, source) = match (contract, global) {
+            (Some(targets), _) => (targets, ObjectiveSource::Contract),
+            (None, Some(targets)) => (targets, ObjectiveSource::Global),
+            (None, None) => return fallback,
+        };
+        Self {
+            rto_target_seconds: targets.0,
+            rpo_target_seconds: targets.1,
+            source,
+        }
+    }
+
+    /// Whether an operator set these targets, as opposed to built-in defaults
+    pub fn is_configured(&self) -> bool {
+        self.source != ObjectiveSource::Default
+    }
+}
+
+/// Recovery targets applied to an incident and whether the achieved values
+/// met them. Stored when the incident is resolved.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, FromRow, utoipa::ToSchema,
+)]
+pub struct ObjectiveOutcome {
+    pub rto_target_seconds: Option<i64>,
+    pub rto_met: Option<bool>,
+    /// Achieved minus target; negative when recovery beat the target
+    pub rto_delta_seconds: Option<i64>,
+    pub rpo_target_seconds: Option<i64>,
+    pub rpo_met: Option<bool>,
+    /// Achieved minus target; negative when data loss stayed under the target
+    pub rpo_delta_seconds: Option<i64>,
+}
+
+impl ObjectiveOutcome {
+    /// Compares achieved values against the targets. Objectives without an
+    /// achieved value keep their target but leave `met` and the delta unset.
+    pub fn evaluate(
+        rto_achieved: Option<i64>,
+        rpo_achieved: Option<i64>,
+        rto_target_seconds: i64,
+        rpo_target_seconds: i64,
+    ) -> Self {
+        let compare = |achieved: Option<i64>, target: i64| {
+            achieved.map(|achieved| (achieved <= target, achieved - target))
+        };
+        let rto = compare(rto_achieved, rto_target_seconds);
+        let rpo = compare(rpo_achieved, rpo_target_seconds);
+
+        Self {
+            rto_target_seconds: Some(rto_target_seconds),
+            rto_met: rto.map(|(met, _)| met),
+            rto_delta_seconds: rto.map(|(_, delta)| delta),
+            rpo_target_seconds: Some(rpo_target_seconds),
+            rpo_met: rpo.map(|(met, _)| met),
+            rpo_delta_seconds: rpo.map(|(_, delta)| delta),
+        }
+    }
+
+    /// Warnings for objectives that have a target but no achieved value.
+    pub fn missing_value_warnings(&self) -> Vec<String> {
+        [
+            ("RTO", "rto_achieved", self.rto_target_seconds, self.rto_met),
+            ("RPO", "rpo_achieved", self.rpo_target_seconds, self.rpo_met),
+        ]
+        .into_iter()
+        .filter(|(_, _, target, met)| target.is_some() && met.is_none())
+        .map(|(label, field, _, _)| {
+            format!(
+                "Incident resolved without {}; set `{}` to track it against the {} target",
+                field, field, label
+            )
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULTS: RecoveryTargets = RecoveryTargets {
+        rto_target_seconds: 4 * 3600,
+        rpo_target_seconds: 3600,
+        source: ObjectiveSource::Default,
+    };
+
+    #[test]
+    fn contract_override_beats_global_and_fallback() {
+        let targets = RecoveryTargets::resolve(Some((600, 60)), Some((3600, 600)), DEFAULTS);
+        assert_eq!(targets.source, ObjectiveSource::Contract);
+        assert_eq!(targets.rto_target_seconds, 600);
+
+        let targets = RecoveryTargets::resolve(None, Some((3600, 600)), DEFAULTS);
+        assert_eq!(targets.source, ObjectiveSource::Global);
+        assert_eq!(targets.rpo_target_seconds, 600);
+
+        assert_eq!(RecoveryTargets::resolve(None, None, DEFAULTS), DEFAULTS);
+    }
+
+    #[test]
+    fn outcome_records_met_and_delta() {
+        let outcome = ObjectiveOutcome::evaluate(Some(3 * 3600), Some(7200), 4 * 3600, 3600);
+        assert_eq!(outcome.rto_target_seconds, Some(4 * 3600));
+        assert_eq!(outcome.rto_met, Some(true));
+        assert_eq!(outcome.rto_delta_seconds, Some(-3600));
+        assert_eq!(outcome.rpo_met, Some(false));
+        assert_eq!(outcome.rpo_delta_seconds, Some(3600));
+        assert!(outcome.missing_value_warnings().is_empty());
+    }
+
+    #[test]
+    fn missing_values_are_warned_about() {
+        let outcome = ObjectiveOutcome::evaluate(Some(60), None, 4 * 3600, 3600);
+        assert_eq!(outcome.rpo_target_seconds, Some(3600));
+        assert_eq!(outcome.rpo_met, None);
+        assert_eq!(outcome.rpo_delta_seconds, None);
+
+        let warnings = outcome.missing_value_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("rpo_achieved"));
+        assert!(ObjectiveOutcome::default()
+            .missing_value_warnings()
+            .is_empty());
+    }
+}
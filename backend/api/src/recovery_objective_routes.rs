@@ -0,0 +1,18 @@
+use axum::{routing::get, Router};
+
+use crate::{recovery_objective_handlers, state::AppState};
+
+pub fn recovery_objective_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/recovery-objectives",
+            get(recovery_objective_handlers::list_recovery_objectives)
+                .post(recovery_objective_handlers::create_recovery_objective),
+        )
+        .route(
+            "/api/recovery-objectives/:id",
+            get(recovery_objective_handlers::get_recovery_objective)
+                .patch(recovery_objective_handlers::update_recovery_objective)
+                .delete(recovery_objective_handlers::delete_recovery_objective),
+        )
+}
@@ -0,0 +1,525 @@
+//! Admin endpoints for registry snapshots, see [`crate::registry_snapshot_models`].
+//!
+//!   POST /api/admin/snapshots              – queue an export of the registry into the blob store
+//!   GET  /api/admin/snapshots              – stored snapshots, newest first
+//!   GET  /api/admin/snapshots/:id/download – the archive itself, resumable with `Range`
+//!   POST /api/admin/snapshots/:id/restore  – restore one, or rehearse it with `dry_run`
+//!
+//! A restore upserts every row of the snapshot by primary key inside one
+//! transaction. Rows created after the snapshot are left in place, so
+//! restoring into an empty database rebuilds it and restoring into a live
+//! one reverts the snapshotted rows. Rows that already match are skipped;
+//! overwritten rows get a fresh `updated_at` from the tables' triggers.
+
+use std::time::Instant;
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    byte_range,
+    error::{ApiError, ApiResult},
+    handlers::{self, db_internal_error},
+    incident_handlers,
+    jobs::{self, Job, JobKind},
+    registry_snapshot_models::{
+        RegistrySnapshot, RestoreReport, RestoreSnapshotRequest, SnapshotArchive, TableDump,
+        TableRestore, SNAPSHOT_FORMAT_VERSION, SNAPSHOT_TABLES,
+    },
+    state::AppState,
+};
+
+fn parse_snapshot_id(id: &str) -> ApiResult<Uuid> {
+    Uuid::parse_str(id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidSnapshotId",
+            format!("Invalid snapshot ID format: {}", id),
+        )
+    })
+}
+
+/// Quotes a column name read from the catalog
+fn quote_ident(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Columns that can be written, in table order; generated columns are left out
+async fn writable_columns(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT column_name::TEXT FROM information_schema.columns
+          WHERE table_schema = current_schema() AND table_name = $1 AND is_generated = 'NEVER'
+          ORDER BY ordinal_position",
+    )
+    .bind(table)
+    .fetch_all(&mut **tx)
+    .await
+}
+
+async fn primary_key_columns(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT a.attname::TEXT
+           FROM pg_index i
+           JOIN pg_attribute a ON a.attrelid = i.indrelid AND a.attnum = ANY(i.indkey)
+          WHERE i.indrelid = $1::regclass AND i.indisprimary
+          ORDER BY array_position(i.indkey, a.attnum)",
+    )
+    .bind(table)
+    .fetch_all(&mut **tx)
+    .await
+}
+
+async fn export_table(
+    tx: &mut Transaction<'_, Postgres>,
+    table: &str,
+) -> Result<TableDump, sqlx::Error> {
+    let columns = writable_columns(tx, table).await?;
+    let select = columns
+        .iter()
+        .map(|column| quote_ident(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    // `table` comes from SNAPSHOT_TABLES, never from the request
+    let rows: sqlx::types::Json<Vec<serde_json::Value>> = sqlx::query_scalar(&format!(
+        "SELECT COALESCE(json_agg(t), '[]') FROM (SELECT {} FROM {}) t",
+        select, table
+    ))
+    .fetch_one(&mut **tx)
+    .await?;
+
+    Ok(TableDump {
+        name: table.to_string(),
+        columns,
+        rows: rows.0,
+    })
+}
+
+/// Payload of a `registry_snapshot` job
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct SnapshotJob {
+    /// Admin who asked for the snapshot
+    pub created_by: String,
+}
+
+/// Queue a registry export. The job's result is the stored
+/// [`RegistrySnapshot`].
+#[utoipa::path(
+    post,
+    path = "/api/admin/snapshots",
+    responses(
+        (status = 202, description = "Export queued", body = Job),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Snapshots"
+)]
+pub async fn create_snapshot(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+) -> ApiResult<(StatusCode, HeaderMap, Json<Job>)> {
+    let payload = json!(SnapshotJob {
+        created_by: claims.sub.clone(),
+    });
+    let job = jobs::enqueue(
+        &state.db,
+        &state.config.jobs,
+        JobKind::RegistrySnapshot,
+        &payload,
+        Some(&claims.sub),
+    )
+    .await
+    .map_err(|err| db_internal_error("queue snapshot job", err))?;
+    Ok(handlers::job_accepted(job))
+}
+
+/// Exports the registry into the blob store and records the snapshot. Runs
+/// as a `registry_snapshot` job.
+pub(crate) async fn run_snapshot_export(
+    state: &AppState,
+    job: SnapshotJob,
+) -> ApiResult<serde_json::Value> {
+    let started = Instant::now();
+
+    // One read-only snapshot of the database, so tables agree with each other
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin snapshot export", err))?;
+    sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("start snapshot export", err))?;
+
+    let mut tables = Vec::with_capacity(SNAPSHOT_TABLES.len());
+    for table in SNAPSHOT_TABLES {
+        tables.push(
+            export_table(&mut tx, table)
+                .await
+                .map_err(|err| db_internal_error("export table", err))?,
+        );
+    }
+    tx.rollback()
+        .await
+        .map_err(|err| db_internal_error("end snapshot export", err))?;
+
+    let archive = SnapshotArchive {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        created_at: Utc::now(),
+        tables,
+    };
+    let bytes = archive
+        .encode()
+        .map_err(|err| ApiError::internal(format!("Failed to encode snapshot: {}", err)))?;
+    let sha256 = shared::source_storage::compute_sha256(&bytes);
+
+    let id = Uuid::new_v4();
+    let (storage_backend, storage_key) = state
+        .source_storage
+        .store_snapshot(id, &bytes)
+        .await
+        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
+
+    let snapshot: RegistrySnapshot = sqlx::query_as(
+        r#"
+        INSERT INTO registry_snapshots
+            (id, format_version, storage_backend, storage_key, size_bytes, sha256,
+             row_counts, created_by, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(SNAPSHOT_FORMAT_VERSION)
+    .bind(&storage_backend)
+    .bind(&storage_key)
+    .bind(bytes.len() as i64)
+    .bind(&sha256)
+    .bind(sqlx::types::Json(archive.row_counts()))
+    .bind(&job.created_by)
+    .bind(archive.created_at)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("record snapshot", err))?;
+
+    tracing::info!(
+        snapshot_id = %snapshot.id,
+        size_bytes = snapshot.size_bytes,
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        admin = %job.created_by,
+        "registry snapshot created"
+    );
+    Ok(json!(snapshot))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/snapshots",
+    responses(
+        (status = 200, description = "Snapshots, newest first", body = [RegistrySnapshot]),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Snapshots"
+)]
+pub async fn list_snapshots(
+    State(state): State<AppState>,
+) -> ApiResult<Json<Vec<RegistrySnapshot>>> {
+    let snapshots = sqlx::query_as("SELECT * FROM registry_snapshots ORDER BY created_at DESC")
+        .fetch_all(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("list snapshots", err))?;
+    Ok(Json(snapshots))
+}
+
+async fn load_snapshot(state: &AppState, id: &str) -> ApiResult<RegistrySnapshot> {
+    let snapshot_id = parse_snapshot_id(id)?;
+    sqlx::query_as("SELECT * FROM registry_snapshots WHERE id = $1")
+        .bind(snapshot_id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|err| db_internal_error("load snapshot", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SnapshotNotFound",
+                format!("No snapshot found with ID: {}", id),
+            )
+        })
+}
+
+/// The snapshot's gzipped archive. Its SHA-256 is the `ETag`, and `Range`
+/// resumes an interrupted download, see [`crate::byte_range`].
+#[utoipa::path(
+    get,
+    path = "/api/admin/snapshots/{id}/download",
+    params(("id" = String, Path, description = "Snapshot ID")),
+    responses(
+        (status = 200, description = "The archive", body = Vec<u8>, content_type = "application/gzip"),
+        (status = 206, description = "The part of the archive asked for with Range", body = Vec<u8>, content_type = "application/gzip"),
+        (status = 307, description = "Redirect to a presigned URL when SOURCE_STORAGE_DOWNLOAD=presign"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Snapshot not found"),
+        (status = 416, description = "Malformed Range, or one starting past the end of the archive")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Snapshots"
+)]
+pub async fn download_snapshot(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> ApiResult<Response> {
+    let snapshot = load_snapshot(&state, &id).await?;
+    let etag = format!("\"{}\"", snapshot.sha256);
+    let size = snapshot.size_bytes.max(0) as u64;
+    let range = byte_range::requested(&headers, &etag, size)?;
+
+    let download = state
+        .source_storage
+        .download_source(
+            &snapshot.storage_backend,
+            &snapshot.storage_key,
+            range.map(|range| (range.start, range.end)),
+        )
+        .await
+        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
+    Ok(byte_range::respond(
+        download,
+        range,
+        size,
+        &etag,
+        "application/gzip",
+        &format!("registry-snapshot-{}.json.gz", snapshot.id),
+    ))
+}
+
+/// Upserts one table's rows, returning (inserted, updated)
+async fn restore_table(
+    tx: &mut Transaction<'_, Postgres>,
+    dump: &TableDump,
+) -> ApiResult<(i64, i64)> {
+    let table = dump.name.as_str();
+    let existing = writable_columns(tx, table)
+        .await
+        .map_err(|err| db_internal_error("read table columns", err))?;
+    let key = primary_key_columns(tx, table)
+        .await
+        .map_err(|err| db_internal_error("read primary key", err))?;
+    if key.is_empty() || key.iter().any(|column| !dump.columns.contains(column)) {
+        return Err(ApiError::unprocessable(
+            "SnapshotIncompatible",
+            format!("Snapshot of {} is missing its primary key", table),
+        ));
+    }
+
+    let columns: Vec<&String> = existing
+        .iter()
+        .filter(|column| dump.columns.contains(column))
+        .collect();
+    let list = columns
+        .iter()
+        .map(|column| quote_ident(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let conflict = key
+        .iter()
+        .map(|column| quote_ident(column))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let updates: Vec<String> = columns
+        .iter()
+        .filter(|column| !key.contains(column))
+        .map(|column| format!("{0} = EXCLUDED.{0}", quote_ident(column)))
+        .collect();
+    // Rows that already match are left alone, which also keeps append-only
+    // tables such as incident_updates from rejecting the restore
+    let on_conflict = if updates.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        let row = |prefix: &str| {
+            columns
+                .iter()
+                .map(|column| format!("{}.{}", prefix, quote_ident(column)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        format!(
+            "DO UPDATE SET {} WHERE ROW({})::TEXT IS DISTINCT FROM ROW({})::TEXT",
+            updates.join(", "),
+            row("current"),
+            row("EXCLUDED")
+        )
+    };
+
+    // xmax is 0 for rows this statement inserted
+    let (inserted, updated): (i64, i64) = sqlx::query_as(&format!(
+        r#"
+        WITH upserted AS (
+            INSERT INTO {table} AS current ({list})
+            SELECT {list} FROM jsonb_populate_recordset(NULL::{table}, $1)
+            ON CONFLICT ({conflict}) {on_conflict}
+            RETURNING (xmax = 0) AS inserted
+        )
+        SELECT COUNT(*) FILTER (WHERE inserted), COUNT(*) FILTER (WHERE NOT inserted)
+          FROM upserted
+        "#
+    ))
+    .bind(sqlx::types::Json(&dump.rows))
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|err| {
+        ApiError::unprocessable(
+            "SnapshotRestoreFailed",
+            format!("Restoring {} failed: {}", table, err),
+        )
+    })?;
+
+    // Restored ids must not be handed out again by a serial column
+    if !existing.iter().any(|column| column == "id") {
+        return Ok((inserted, updated));
+    }
+    let sequence: Option<String> = sqlx::query_scalar("SELECT pg_get_serial_sequence($1, 'id')")
+        .bind(table)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("find id sequence", err))?;
+    if let Some(sequence) = sequence {
+        sqlx::query(&format!(
+            "SELECT setval($1::regclass, COALESCE((SELECT MAX(id) FROM {}), 0) + 1, false)",
+            table
+        ))
+        .bind(sequence)
+        .execute(&mut **tx)
+        .await
+        .map_err(|err| db_internal_error("advance id sequence", err))?;
+    }
+
+    Ok((inserted, updated))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/snapshots/{id}/restore",
+    params(
+        ("id" = String, Path, description = "Snapshot UUID")
+    ),
+    request_body = RestoreSnapshotRequest,
+    responses(
+        (status = 200, description = "Restored, or rolled back after counting rows when `dry_run` is set", body = RestoreReport),
+        (status = 400, description = "Malformed snapshot ID"),
+        (status = 403, description = "Administrative privileges required"),
+        (status = 404, description = "Snapshot not found"),
+        (status = 422, description = "Archive failed its checksum, has an unsupported format, or did not fit the current schema")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Snapshots"
+)]
+pub async fn restore_snapshot(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Path(id): Path<String>,
+    Json(req): Json<RestoreSnapshotRequest>,
+) -> ApiResult<Json<RestoreReport>> {
+    let snapshot = load_snapshot(&state, &id).await?;
+
+    let started_at = Utc::now();
+    let started = Instant::now();
+
+    let bytes = state
+        .source_storage
+        .retrieve_source(&snapshot.storage_backend, &snapshot.storage_key)
+        .await
+        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
+    if shared::source_storage::compute_sha256(&bytes) != snapshot.sha256 {
+        return Err(ApiError::unprocessable(
+            "SnapshotChecksumMismatch",
+            "The stored archive does not match the snapshot's checksum",
+        ));
+    }
+    let archive = SnapshotArchive::decode(&bytes)
+        .map_err(|err| ApiError::unprocessable("SnapshotUnreadable", err))?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin snapshot restore", err))?;
+    let mut tables = Vec::new();
+    for dump in archive.tables_in_restore_order() {
+        let (inserted, updated) = restore_table(&mut tx, dump).await?;
+        tables.push(TableRestore {
+            table: dump.name.clone(),
+            rows: dump.rows.len() as i64,
+            inserted,
+            updated,
+        });
+    }
+    if req.dry_run {
+        tx.rollback()
+            .await
+            .map_err(|err| db_internal_error("roll back snapshot restore", err))?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|err| db_internal_error("commit snapshot restore", err))?;
+    }
+    let elapsed = started.elapsed();
+
+    tracing::warn!(
+        snapshot_id = %snapshot.id,
+        dry_run = req.dry_run,
+        elapsed_ms = elapsed.as_millis() as u64,
+        admin = %claims.sub,
+        "registry snapshot restored"
+    );
+
+    let incident_id = if req.record_drill {
+        // Anything written after the snapshot was taken would be lost
+        let rpo_seconds = (started_at - snapshot.created_at).num_seconds().max(0);
+        let description = format!(
+            "Disaster recovery drill: {} snapshot {} taken {}",
+            if req.dry_run {
+                "dry-run restore of"
+            } else {
+                "restored"
+            },
+            snapshot.id,
+            snapshot.created_at.to_rfc3339()
+        );
+        let incident = incident_handlers::record_recovery_drill(
+            &state,
+            &description,
+            started_at,
+            elapsed.as_secs_f64().ceil() as i64,
+            rpo_seconds,
+        )
+        .await?;
+        Some(incident.id)
+    } else {
+        None
+    };
+
+    Ok(Json(RestoreReport {
+        snapshot_id: snapshot.id,
+        dry_run: req.dry_run,
+        started_at,
+        duration_ms: elapsed.as_millis() as i64,
+        tables,
+        incident_id,
+    }))
+}
@@ -790,8 +790,7 @@ async fn fetch_contract_identity(state: &AppState, id: &str) -> ApiResult<(Uuid,
 }
 
 fn db_internal_error(operation: &str, err: sqlx::Error) -> ApiError {
-    tracing::error!(operation = operation, error = ?err, "database operation failed");
-    ApiError::internal("Database operation failed")
+    ApiError::from_sqlx(operation, err)
 }
 
 #[cfg(test)]
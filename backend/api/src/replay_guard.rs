@@ -0,0 +1,306 @@
+//! Replay protection for signed version publishes.
+//!
+//! A signed publish covers a timestamp and a random nonce as well as the
+//! version itself, see [`signing_message`]. The registry accepts it only
+//! while the timestamp is recent and only once per nonce, so a captured
+//! request can't be sent again later to re-create a deleted version or spam
+//! new ones.
+//!
+//! Seen nonces are kept until their signature would have expired anyway: in
+//! Redis when `SIGNATURE_NONCE_REDIS_URL` is set, so every instance sees
+//! them, and otherwise in a bounded in-memory LRU per instance.
+
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use lru::LruCache;
+use redis::aio::ConnectionManager;
+use serde_json::json;
+
+use crate::config::Secret;
+use crate::error::ApiError;
+
+const DEFAULT_MAX_AGE_SECS: u64 = 300;
+const DEFAULT_MAX_CLOCK_SKEW_SECS: u64 = 60;
+const DEFAULT_NONCE_CAPACITY: usize = 100_000;
+/// Nonces shorter than this are too easy to guess or collide
+const MIN_NONCE_LEN: usize = 16;
+const MAX_NONCE_LEN: usize = 128;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayConfig {
+    /// How long after its timestamp a signature is accepted
+    pub max_age: Duration,
+    /// How far in the future a timestamp may be before the signer's clock is
+    /// taken to be wrong
+    pub max_clock_skew: Duration,
+    /// Nonces remembered in memory; the least recently seen are dropped first
+    pub nonce_capacity: usize,
+    /// Shared nonce store for deployments with several instances
+    pub redis_url: Option<Secret>,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            max_age: Duration::from_secs(DEFAULT_MAX_AGE_SECS),
+            max_clock_skew: Duration::from_secs(DEFAULT_MAX_CLOCK_SKEW_SECS),
+            nonce_capacity: DEFAULT_NONCE_CAPACITY,
+            redis_url: None,
+        }
+    }
+}
+
+/// The bytes a version publish signature covers
+pub fn signing_message(
+    contract_id: &str,
+    version: &str,
+    wasm_hash: &str,
+    signed_at: i64,
+    nonce: &str,
+) -> Vec<u8> {
+    format!(
+        "{}:{}:{}:{}:{}",
+        contract_id, version, wasm_hash, signed_at, nonce
+    )
+    .into_bytes()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The nonce is malformed
+    InvalidNonce,
+    /// The signature is older than the accepted window
+    Expired { age_secs: i64 },
+    /// The timestamp is ahead of the registry's clock by more than allowed
+    ClockSkew { offset_secs: i64 },
+    /// The nonce was already used with this key
+    Replayed,
+}
+
+impl ReplayError {
+    pub fn into_api_error(self, server_time: i64, max_age: Duration) -> ApiError {
+        match self {
+            ReplayError::InvalidNonce => ApiError::bad_request(
+                "InvalidNonce",
+                format!(
+                    "nonce must be {} to {} characters of [A-Za-z0-9_-]",
+                    MIN_NONCE_LEN, MAX_NONCE_LEN
+                ),
+            ),
+            ReplayError::Expired { age_secs } => ApiError::unprocessable(
+                "SignatureExpired",
+                format!(
+                    "Signature is {}s old; signatures are accepted for {}s",
+                    age_secs,
+                    max_age.as_secs()
+                ),
+            )
+            .with_details(json!({ "server_time": server_time, "age_secs": age_secs })),
+            ReplayError::ClockSkew { offset_secs } => ApiError::unprocessable(
+                "SignatureClockSkew",
+                format!(
+                    "Signature timestamp is {}s ahead of the registry's clock",
+                    offset_secs
+                ),
+            )
+            .with_details(json!({ "server_time": server_time, "offset_secs": offset_secs })),
+            ReplayError::Replayed => ApiError::conflict(
+                "SignatureReplayed",
+                "This signed request was already received; sign it again with a new nonce",
+            ),
+        }
+    }
+}
+
+pub struct ReplayGuard {
+    config: ReplayConfig,
+    /// Nonce key -> unix time after which its signature has expired anyway
+    seen: Mutex<LruCache<String, i64>>,
+    redis: Option<ConnectionManager>,
+}
+
+impl ReplayGuard {
+    pub fn in_memory(config: ReplayConfig) -> Self {
+        let capacity = NonZeroUsize::new(config.nonce_capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            config,
+            seen: Mutex::new(LruCache::new(capacity)),
+            redis: None,
+        }
+    }
+
+    /// Uses Redis for nonces when configured and reachable, memory otherwise
+    pub async fn connect(config: ReplayConfig) -> Self {
+        let redis = match &config.redis_url {
+            Some(url) => match redis::Client::open(url.expose()) {
+                Ok(client) => match ConnectionManager::new(client).await {
+                    Ok(cm) => Some(cm),
+                    Err(err) => {
+                        tracing::warn!(error = %err, "signature nonce store: Redis unavailable, using memory");
+                        None
+                    }
+                },
+                Err(err) => {
+                    tracing::warn!(error = %err, "signature nonce store: invalid Redis URL, using memory");
+                    None
+                }
+            },
+            None => None,
+        };
+        Self {
+            redis,
+            ..Self::in_memory(config)
+        }
+    }
+
+    pub fn max_age(&self) -> Duration {
+        self.config.max_age
+    }
+
+    /// Accepts a signature made at `signed_at` with `nonce` by `key` once,
+    /// as long as it is recent by the registry's clock (`now`).
+    pub async fn check(
+        &self,
+        key: &str,
+        signed_at: i64,
+        nonce: &str,
+        now: i64,
+    ) -> Result<(), ReplayError> {
+        let expires_at = self.check_time(signed_at, nonce, now)?;
+        let nonce_key = format!("{}:{}", key, nonce);
+
+        if let Some(redis) = &self.redis {
+            let mut conn = redis.clone();
+            let ttl = (expires_at - now).max(1);
+            // SET NX answers nil when the key already exists
+            let stored: redis::RedisResult<Option<String>> = redis::cmd("SET")
+                .arg(format!("signature-nonce:{}", nonce_key))
+                .arg(1)
+                .arg("NX")
+                .arg("EX")
+                .arg(ttl)
+                .query_async(&mut conn)
+                .await;
+            match stored {
+                Ok(Some(_)) => return Ok(()),
+                Ok(None) => return Err(ReplayError::Replayed),
+                Err(err) => {
+                    tracing::warn!(error = %err, "signature nonce store: Redis failed, using memory")
+                }
+            }
+        }
+        self.remember(nonce_key, expires_at, now)
+    }
+
+    /// When the signature stops being accepted, if it is acceptable now
+    fn check_time(&self, signed_at: i64, nonce: &str, now: i64) -> Result<i64, ReplayError> {
+        let valid_nonce = (MIN_NONCE_LEN..=MAX_NONCE_LEN).contains(&nonce.len())
+            && nonce
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_nonce {
+            return Err(ReplayError::InvalidNonce);
+        }
+
+        let age = now - signed_at;
+        if age > self.config.max_age.as_secs() as i64 {
+            return Err(ReplayError::Expired { age_secs: age });
+        }
+        if -age > self.config.max_clock_skew.as_secs() as i64 {
+            return Err(ReplayError::ClockSkew { offset_secs: -age });
+        }
+        Ok(signed_at + self.config.max_age.as_secs() as i64)
+    }
+
+    fn remember(&self, nonce_key: String, expires_at: i64, now: i64) -> Result<(), ReplayError> {
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        match seen.get(&nonce_key) {
+            Some(&until) if until >= now => Err(ReplayError::Replayed),
+            _ => {
+                seen.put(nonce_key, expires_at);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_790_000_000;
+    const NONCE: &str = "4f1c2a9e7b3d5c6e";
+
+    fn guard() -> ReplayGuard {
+        ReplayGuard::in_memory(ReplayConfig::default())
+    }
+
+    #[tokio::test]
+    async fn a_nonce_is_accepted_once_per_key() {
+        let guard = guard();
+        assert_eq!(guard.check("key-a", NOW - 10, NONCE, NOW).await, Ok(()));
+        assert_eq!(
+            guard.check("key-a", NOW - 10, NONCE, NOW + 5).await,
+            Err(ReplayError::Replayed)
+        );
+        // Another key may happen to pick the same nonce
+        assert_eq!(guard.check("key-b", NOW - 10, NONCE, NOW).await, Ok(()));
+    }
+
+    #[tokio::test]
+    async fn stale_signatures_are_rejected() {
+        let guard = guard();
+        assert_eq!(guard.check("key", NOW - 300, NONCE, NOW).await, Ok(()));
+        assert_eq!(
+            guard.check("key", NOW - 301, "0123456789abcdef", NOW).await,
+            Err(ReplayError::Expired { age_secs: 301 })
+        );
+    }
+
+    #[tokio::test]
+    async fn timestamps_ahead_of_the_server_are_clock_skew() {
+        let guard = guard();
+        assert_eq!(guard.check("key", NOW + 60, NONCE, NOW).await, Ok(()));
+        assert_eq!(
+            guard.check("key", NOW + 125, "0123456789abcdef", NOW).await,
+            Err(ReplayError::ClockSkew { offset_secs: 125 })
+        );
+        let err = ReplayError::ClockSkew { offset_secs: 125 }
+            .into_api_error(NOW, guard.max_age())
+            .body();
+        assert_eq!(err.error.details["reason"], "SignatureClockSkew");
+        assert_eq!(err.error.details["offset_secs"], 125);
+        assert_eq!(err.error.details["server_time"], NOW);
+    }
+
+    #[tokio::test]
+    async fn short_or_unusual_nonces_are_rejected() {
+        let guard = guard();
+        for nonce in ["abc", "0123456789abcdef!", &"a".repeat(129)] {
+            assert_eq!(
+                guard.check("key", NOW, nonce, NOW).await,
+                Err(ReplayError::InvalidNonce),
+                "{nonce}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn nonce_memory_is_bounded() {
+        let guard = ReplayGuard::in_memory(ReplayConfig {
+            nonce_capacity: 2,
+            ..ReplayConfig::default()
+        });
+        for nonce in ["aaaaaaaaaaaaaaaa", "bbbbbbbbbbbbbbbb", "cccccccccccccccc"] {
+            guard.check("key", NOW, nonce, NOW).await.unwrap();
+        }
+        assert_eq!(guard.seen.lock().unwrap().len(), 2);
+        // Expired entries don't block a nonce either
+        assert_eq!(
+            guard.remember("key:cccccccccccccccc".to_string(), NOW + 300, NOW + 301),
+            Ok(())
+        );
+    }
+}
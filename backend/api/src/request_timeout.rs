@@ -0,0 +1,207 @@
+//! Time budgets for requests.
+//!
+//! Every route gets a budget by what it does: searches and listings the
+//! short one, uploads, downloads, imports, exports and restores the long
+//! one, and everything else the default. A request still running when its
+//! budget runs out is dropped, which returns any database connection it held
+//! to the pool, and answered 504 `GATEWAY_TIMEOUT` with the budget in
+//! `details`. Timeouts are counted in
+//! `http_request_timeouts_total{route,budget}`.
+//!
+//! The budgets are runtime settings, see [`crate::feature_flags`]:
+//!
+//! | Budget    | Setting                      | Default |
+//! |-----------|------------------------------|---------|
+//! | `short`   | `request_timeout_short_ms`   | 10 s    |
+//! | `default` | `request_timeout_default_ms` | 30 s    |
+//! | `long`    | `request_timeout_long_ms`    | 5 min   |
+//!
+//! The budget covers producing the response, not streaming its body, so a
+//! slow client downloading a large artifact isn't cut off. WebSocket routes
+//! answer the upgrade at once and aren't affected either.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    body::Body,
+    extract::{MatchedPath, State},
+    http::{Method, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+
+use crate::{error::ApiError, feature_flags::FeatureFlags};
+
+/// GET routes that search or list
+const SHORT_ROUTES: &[&str] = &[
+    "/api/contracts",
+    "/api/contracts/suggestions",
+    "/api/contracts/trending",
+    "/api/contracts/changes",
+    "/api/searches/:id/results",
+    "/api/favorites/search",
+    "/api/categories",
+    "/api/tags",
+];
+
+/// Routes moving whole artifacts or datasets
+const LONG_ROUTE_SUFFIXES: &[&str] = &[
+    "/wasm",
+    "/source",
+    "/download",
+    "/import",
+    "/export",
+    "/restore",
+    "/batch-verify",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    Short,
+    Default,
+    Long,
+}
+
+impl Budget {
+    /// The budget of `route`, a matched route template
+    pub fn for_route(method: &Method, route: &str) -> Self {
+        if LONG_ROUTE_SUFFIXES
+            .iter()
+            .any(|suffix| route.ends_with(suffix))
+        {
+            Self::Long
+        } else if method == Method::GET && SHORT_ROUTES.contains(&route) {
+            Self::Short
+        } else {
+            Self::Default
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Short => "short",
+            Self::Default => "default",
+            Self::Long => "long",
+        }
+    }
+
+    fn limit(self, flags: &FeatureFlags) -> Duration {
+        match self {
+            Self::Short => flags.request_timeout_short_ms.millis(),
+            Self::Default => flags.request_timeout_default_ms.millis(),
+            Self::Long => flags.request_timeout_long_ms.millis(),
+        }
+    }
+}
+
+/// Answers 504 once a request runs past its route's budget
+pub async fn timeout_middleware(
+    State(flags): State<Arc<FeatureFlags>>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_owned())
+        .unwrap_or_default();
+    let budget = Budget::for_route(req.method(), &route);
+    let limit = budget.limit(&flags);
+
+    match tokio::time::timeout(limit, next.run(req)).await {
+        Ok(response) => response,
+        Err(_) => {
+            let limit_ms = limit.as_millis() as u64;
+            crate::metrics::HTTP_REQUEST_TIMEOUTS
+                .with_label_values(&[&route, budget.name()])
+                .inc();
+            tracing::warn!(
+                route = %route,
+                budget = budget.name(),
+                limit_ms,
+                "Request ran past its time budget"
+            );
+            ApiError::gateway_timeout(
+                "RequestTimeout",
+                format!("The request did not complete within {} ms", limit_ms),
+            )
+            .with_details(json!({ "budget": budget.name(), "limit_ms": limit_ms }))
+            .into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{http::StatusCode, middleware, routing::get, Router};
+    use std::collections::HashMap;
+    use tokio::sync::Semaphore;
+    use tower::ServiceExt;
+
+    #[test]
+    fn budgets_follow_what_the_route_does() {
+        assert_eq!(
+            Budget::for_route(&Method::GET, "/api/contracts"),
+            Budget::Short
+        );
+        assert_eq!(
+            Budget::for_route(&Method::POST, "/api/contracts"),
+            Budget::Default
+        );
+        assert_eq!(
+            Budget::for_route(
+                &Method::GET,
+                "/api/contracts/:id/versions/:version/source/download"
+            ),
+            Budget::Long
+        );
+        assert_eq!(
+            Budget::for_route(&Method::POST, "/api/incidents/import"),
+            Budget::Long
+        );
+        assert_eq!(
+            Budget::for_route(&Method::GET, "/api/contracts/:id"),
+            Budget::Default
+        );
+    }
+
+    #[tokio::test]
+    async fn a_stuck_request_is_answered_504_and_lets_go_of_its_connection() {
+        let flags = Arc::new(FeatureFlags::default());
+        flags.apply_setting_overrides(&HashMap::from([(
+            "request_timeout_default_ms".to_string(),
+            50,
+        )]));
+        // Stands in for the pool: the handler holds its only connection
+        let pool = Arc::new(Semaphore::new(1));
+        let held = pool.clone();
+        let app = Router::new()
+            .route(
+                "/slow",
+                get(move || async move {
+                    let _connection = held.acquire().await.unwrap();
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    "done"
+                }),
+            )
+            .layer(middleware::from_fn_with_state(flags, timeout_middleware));
+
+        let response = app
+            .oneshot(Request::get("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "GATEWAY_TIMEOUT");
+        assert_eq!(body["error"]["details"]["budget"], "default");
+        assert_eq!(body["error"]["details"]["limit_ms"], 50);
+        assert_eq!(pool.available_permits(), 1, "the connection was released");
+    }
+}
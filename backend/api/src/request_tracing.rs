@@ -1,35 +1,70 @@
 //! Structured request tracing middleware.
 //!
-//! Every incoming HTTP request gets a unique UUID (`X-Request-ID`), and a
-//! JSON-structured log line is emitted after the response is sent.
+//! Every incoming HTTP request gets a request ID: the caller's `X-Request-ID`
+//! when it is well-formed, otherwise a fresh UUID. The ID is recorded on a span
+//! covering the whole request, so every log line emitted while handling it
+//! carries `request_id`; it is returned in the `X-Request-ID` header and in
+//! error bodies. Use [`spawn`] for background work started by a request so it
+//! keeps the same ID. A JSON-structured log line is emitted after the response
+//! is sent.
 //!
 //! Health-check endpoints are intentionally skipped so they don't pollute
 //! the log stream.
 //!
 //! Log fields:
-//!   timestamp, request_id, method, path, status, duration_ms, user_ip
+//!   timestamp, level, target, request_id, method, route, path, status,
+//!   latency_ms, user_ip
+//!
+//! `route` is the matched route template (e.g. `/api/contracts/:id`), so
+//! requests can be grouped without parsing paths.
+//!
+//! ## Output
+//!
+//! | Variable                 | Default                                |
+//! |--------------------------|----------------------------------------|
+//! | `LOG_FORMAT`             | `json` (one object per event), `pretty` |
+//! | `LOG_SAMPLED_TARGETS`    | `sqlx,hyper,h2,reqwest,tower`          |
+//! | `LOG_DEBUG_SAMPLE_EVERY` | 20                                     |
+//!
+//! DEBUG and TRACE events from the sampled targets are very chatty once
+//! `RUST_LOG` enables them, so only one in `LOG_DEBUG_SAMPLE_EVERY` is kept
+//! (1 keeps all). INFO and above are never sampled.
 
 use axum::{
     body::Body,
-    extract::ConnectInfo,
+    extract::{ConnectInfo, MatchedPath},
     http::{HeaderMap, HeaderName, HeaderValue, Request},
     middleware::Next,
     response::Response,
 };
+use std::fmt;
+use std::future::Future;
 use std::net::SocketAddr;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
-use tracing::Instrument;
+use tracing::{subscriber::Interest, Instrument, Level, Metadata, Subscriber};
+use tracing_subscriber::{
+    fmt::MakeWriter,
+    layer::{Context, Filter},
+    registry::LookupSpan,
+    Layer,
+};
 use uuid::Uuid;
 
 /// Paths that should never be logged (health checks, readiness probes, etc.)
 const SKIP_LOG_PATHS: &[&str] = &["/health", "/healthz", "/ready", "/ping", "/metrics"];
 
+/// Incoming IDs longer than this are replaced with a generated one.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
 /// The response header name carrying the request ID back to the caller.
 pub static X_REQUEST_ID: HeaderName = HeaderName::from_static("x-request-id");
 pub static X_CORRELATION_ID: HeaderName = HeaderName::from_static("x-correlation-id");
 
 tokio::task_local! {
     static CURRENT_REQUEST_ID: String;
+    static CURRENT_ROUTE: String;
 }
 
 /// Axum middleware: attach a request ID, log the completed request as JSON,
@@ -42,6 +77,12 @@ pub async fn tracing_middleware(
     let request_id = request_id_from_headers(req.headers()).unwrap_or_else(generate_request_id);
     let method = req.method().to_string();
     let path = req.uri().path().to_owned();
+    // Unmatched requests (404s) have no route template
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_owned())
+        .unwrap_or_default();
     let user_ip = addr.ip().to_string();
 
     // Inject the request ID into extensions so handlers / DB layers can read it
@@ -52,13 +93,17 @@ pub async fn tracing_middleware(
         "http_request",
         request_id = %request_id,
         method = %method,
+        route = %route,
         path = %path,
         user_ip = %user_ip
     );
     let mut response = CURRENT_REQUEST_ID
-        .scope(request_id.clone(), next.run(req).instrument(span.clone()))
+        .scope(
+            request_id.clone(),
+            CURRENT_ROUTE.scope(route.clone(), next.run(req).instrument(span.clone())),
+        )
         .await;
-    let duration_ms = start.elapsed().as_millis() as u64;
+    let latency_ms = start.elapsed().as_millis() as u64;
 
     attach_request_id_headers(response.headers_mut(), &request_id);
 
@@ -69,13 +114,14 @@ pub async fn tracing_middleware(
 
     let status = response.status().as_u16();
 
-    // Emit a single structured JSON log line per request
+    // Emit a single structured log line per request
     tracing::info!(
         request_id = %request_id,
         method     = %method,
+        route      = %route,
         path       = %path,
         status     = status,
-        duration_ms = duration_ms,
+        latency_ms = latency_ms,
         user_ip    = %user_ip,
         "request"
     );
@@ -93,6 +139,11 @@ pub fn current_request_id() -> Option<String> {
         .ok()
 }
 
+/// Route template of the request being handled; empty for unmatched ones
+pub fn current_route() -> Option<String> {
+    CURRENT_ROUTE.try_with(|route| route.clone()).ok()
+}
+
 pub fn request_id_from_headers(headers: &HeaderMap) -> Option<String> {
     [X_REQUEST_ID.as_str(), X_CORRELATION_ID.as_str()]
         .iter()
@@ -101,11 +152,35 @@ pub fn request_id_from_headers(headers: &HeaderMap) -> Option<String> {
                 .get(*header_name)
                 .and_then(|value| value.to_str().ok())
                 .map(str::trim)
-                .filter(|value| !value.is_empty())
+                .filter(|value| is_valid_request_id(value))
                 .map(ToOwned::to_owned)
         })
 }
 
+/// Client-supplied IDs end up in log lines, so only short IDs made of
+/// URL-safe characters are accepted.
+fn is_valid_request_id(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+/// Spawns `future` inside the current span and request-ID scope, so log lines
+/// from background work started by a request carry that request's ID.
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let future = future.instrument(tracing::Span::current());
+    match current_request_id() {
+        Some(request_id) => tokio::spawn(CURRENT_REQUEST_ID.scope(request_id, future)),
+        None => tokio::spawn(future),
+    }
+}
+
 pub fn request_id_from_request<B>(request: &Request<B>) -> Option<String> {
     request
         .extensions()
@@ -141,31 +216,145 @@ impl RequestId {
     }
 }
 
-// ── JSON tracing subscriber initialiser ──────────────────────────────────────
+// ── Tracing subscriber initialiser ───────────────────────────────────────────
+
+const DEFAULT_SAMPLED_TARGETS: &[&str] = &["sqlx", "hyper", "h2", "reqwest", "tower"];
+const DEFAULT_DEBUG_SAMPLE_EVERY: u64 = 20;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One JSON object per event, for ELK / Splunk / Datadog ingestion
+    #[default]
+    Json,
+    /// Multi-line human-readable output for local development
+    Pretty,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
 
-/// Initialise `tracing-subscriber` with a JSON formatter suitable for
-/// ELK / Splunk / Datadog ingestion.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "pretty" => Ok(Self::Pretty),
+            other => Err(format!("unknown log format `{}`", other)),
+        }
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Json => "json",
+            Self::Pretty => "pretty",
+        })
+    }
+}
+
+/// Log output settings, validated at startup by [`crate::config`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggingConfig {
+    pub format: LogFormat,
+    /// Targets (and their submodules) whose DEBUG/TRACE events are sampled
+    pub sampled_targets: Vec<String>,
+    /// Keep one in this many sampled events; 1 keeps all
+    pub debug_sample_every: u64,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            format: LogFormat::default(),
+            sampled_targets: DEFAULT_SAMPLED_TARGETS
+                .iter()
+                .map(|target| target.to_string())
+                .collect(),
+            debug_sample_every: DEFAULT_DEBUG_SAMPLE_EVERY,
+        }
+    }
+}
+
+/// Per-layer filter that keeps one in `every` DEBUG/TRACE events from the
+/// sampled targets and lets everything else through.
+pub struct DebugSampler {
+    targets: Vec<String>,
+    every: u64,
+    seen: AtomicU64,
+}
+
+impl DebugSampler {
+    pub fn new(config: &LoggingConfig) -> Self {
+        Self {
+            targets: config.sampled_targets.clone(),
+            every: config.debug_sample_every.max(1),
+            seen: AtomicU64::new(0),
+        }
+    }
+
+    fn is_sampled(&self, meta: &Metadata<'_>) -> bool {
+        meta.is_event()
+            && *meta.level() >= Level::DEBUG
+            && self.targets.iter().any(|target| {
+                meta.target()
+                    .strip_prefix(target.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+    }
+}
+
+impl<S> Filter<S> for DebugSampler {
+    fn enabled(&self, meta: &Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        if self.every == 1 || !self.is_sampled(meta) {
+            return true;
+        }
+        self.seen.fetch_add(1, Ordering::Relaxed) % self.every == 0
+    }
+
+    fn callsite_enabled(&self, _meta: &'static Metadata<'static>) -> Interest {
+        // Decided per event, so the result must not be cached
+        Interest::sometimes()
+    }
+}
+
+/// JSON output with event fields at the top level next to `level` and
+/// `target`. The span list carries `request_id` and `route` from the
+/// enclosing `http_request` span even when a handler opens its own span.
+fn json_layer<S, W>(writer: W) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt::layer()
+        .json()
+        .flatten_event(true)
+        .with_current_span(true)
+        .with_span_list(true)
+        .with_writer(writer)
+}
+
+/// Initialise `tracing-subscriber` in the configured [`LogFormat`].
 ///
-/// Call this **once** at application startup, replacing the plain-text
-/// subscriber currently set up in `main.rs`.
+/// Call this **once** at application startup.
 ///
 /// Log rotation (daily, 7-day retention) is handled by the deployment
 /// environment (e.g. logrotate, Docker log driver, or a dedicated log
 /// shipper). The subscriber itself writes to stdout so the runtime can
 /// redirect / rotate as needed.
-pub fn init_json_tracing() {
-    use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+pub fn init_tracing(config: &LoggingConfig) {
+    use tracing_subscriber::{filter::FilterExt, layer::SubscriberExt, util::SubscriberInitExt};
+
+    let output = match config.format {
+        LogFormat::Json => json_layer(std::io::stdout).boxed(),
+        LogFormat::Pretty => tracing_subscriber::fmt::layer().pretty().boxed(),
+    };
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| "api=info,tower_http=info".into());
 
+    // `RUST_LOG` only filters the output, so the slow-query layer still sees
+    // every statement sqlx reports
     tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| "api=info,tower_http=info".into()),
-        )
-        .with(
-            tracing_subscriber::fmt::layer()
-                .json()
-                .with_current_span(true),
-        )
+        .with(output.with_filter(filter.and(DebugSampler::new(config))))
+        .with(crate::slow_queries::layer())
         .init();
 }
 
@@ -182,13 +371,60 @@ mod tests {
         Json, Router,
     };
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::sync::{Arc, Mutex};
     use tower::ServiceExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// Collects formatted log output so tests can parse it.
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Captured {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    impl Captured {
+        fn lines(&self) -> Vec<serde_json::Value> {
+            let output = String::from_utf8(self.0.lock().unwrap().clone()).unwrap();
+            output
+                .lines()
+                .map(|line| serde_json::from_str(line).expect("every log line is JSON"))
+                .collect()
+        }
+    }
+
+    fn capture(config: &LoggingConfig) -> (Captured, tracing::subscriber::DefaultGuard) {
+        let captured = Captured::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(json_layer(captured.clone()).with_filter(DebugSampler::new(config)));
+        (captured, tracing::subscriber::set_default(subscriber))
+    }
 
     async fn ok_handler(req: Request<Body>) -> impl IntoResponse {
         let request_id = request_id_from_request(&req).unwrap_or_default();
         Json(serde_json::json!({ "request_id": request_id }))
     }
 
+    async fn item_handler(axum::extract::Path(id): axum::extract::Path<String>) -> StatusCode {
+        tracing::info!(item = %id, "item loaded");
+        StatusCode::OK
+    }
+
     async fn error_handler() -> crate::error::ApiResult<Json<serde_json::Value>> {
         Err(crate::error::ApiError::bad_request(
             "BadRequest",
@@ -199,6 +435,7 @@ mod tests {
     fn app() -> Router {
         Router::new()
             .route("/ok", get(ok_handler))
+            .route("/items/:id", get(item_handler))
             .route("/error", get(error_handler))
             .layer(middleware::from_fn(tracing_middleware))
     }
@@ -288,6 +525,114 @@ mod tests {
             .await
             .expect("body should read");
         let json: serde_json::Value = serde_json::from_slice(&body).expect("json body");
-        assert_eq!(json["correlation_id"], "req-error-1");
+        assert_eq!(json["error"]["request_id"], "req-error-1");
+    }
+
+    #[tokio::test]
+    async fn rejects_unsafe_incoming_request_ids() {
+        let response = call(
+            app(),
+            Request::builder()
+                .uri("/ok")
+                .method("GET")
+                .header(X_REQUEST_ID.as_str(), "bad id\twith spaces")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        let request_id = response
+            .headers()
+            .get(X_REQUEST_ID.as_str())
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        assert_ne!(request_id, "bad id\twith spaces");
+        assert_eq!(request_id.len(), 36);
+        assert!(!is_valid_request_id(&"a".repeat(MAX_REQUEST_ID_LEN + 1)));
+    }
+
+    #[tokio::test]
+    async fn spawned_tasks_inherit_request_id() {
+        let inherited = CURRENT_REQUEST_ID
+            .scope("req-bg-1".to_string(), async {
+                spawn(async { current_request_id() }).await.unwrap()
+            })
+            .await;
+        assert_eq!(inherited.as_deref(), Some("req-bg-1"));
+
+        assert_eq!(spawn(async { current_request_id() }).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn json_log_lines_carry_request_fields() {
+        let (captured, _guard) = capture(&LoggingConfig::default());
+        call(
+            app(),
+            Request::builder()
+                .uri("/items/42")
+                .header(X_REQUEST_ID.as_str(), "req-log-1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+
+        let lines = captured.lines();
+        let request = lines
+            .iter()
+            .find(|line| line["message"] == "request")
+            .expect("request log line");
+        assert_eq!(request["level"], "INFO");
+        assert!(request["target"]
+            .as_str()
+            .unwrap()
+            .ends_with("request_tracing"));
+        assert_eq!(request["request_id"], "req-log-1");
+        assert_eq!(request["method"], "GET");
+        assert_eq!(request["route"], "/items/:id");
+        assert_eq!(request["path"], "/items/42");
+        assert_eq!(request["status"], 200);
+        assert!(request["latency_ms"].is_u64());
+
+        // Handler events get the request span's fields
+        let handler = lines
+            .iter()
+            .find(|line| line["message"] == "item loaded")
+            .expect("handler log line");
+        assert_eq!(handler["item"], "42");
+        assert_eq!(handler["span"]["request_id"], "req-log-1");
+        assert_eq!(handler["span"]["route"], "/items/:id");
+    }
+
+    #[test]
+    fn chatty_debug_targets_are_sampled() {
+        let (captured, _guard) = capture(&LoggingConfig {
+            sampled_targets: vec!["sqlx".to_string()],
+            debug_sample_every: 5,
+            ..LoggingConfig::default()
+        });
+
+        for _ in 0..10 {
+            tracing::debug!(target: "sqlx::query", "statement");
+            tracing::debug!(target: "sqlx_extra", "unrelated");
+        }
+        tracing::info!(target: "sqlx::pool", "pool ready");
+
+        let count = |message: &str| {
+            captured
+                .lines()
+                .iter()
+                .filter(|line| line["message"] == message)
+                .count()
+        };
+        assert_eq!(count("statement"), 2);
+        assert_eq!(count("unrelated"), 10);
+        assert_eq!(count("pool ready"), 1);
+    }
+
+    #[test]
+    fn log_format_parses_known_values_only() {
+        assert_eq!("JSON".parse::<LogFormat>(), Ok(LogFormat::Json));
+        assert_eq!("pretty".parse::<LogFormat>(), Ok(LogFormat::Pretty));
+        assert!("text".parse::<LogFormat>().is_err());
     }
 }
@@ -47,6 +47,22 @@ mod tests {
         let (job_engine, _rx) = soroban_batch::engine::JobEngine::new();
         AppState {
             db: create_test_pool(),
+            config: Arc::new(crate::config::Config::for_tests()),
+            read_db: None,
+            outbound: crate::outbound_http::OutboundClient::new(Default::default()),
+            flags: Default::default(),
+            component_health: Arc::new(crate::component_health::ComponentHealth::new(
+                "http://localhost:3001/api/status",
+                Default::default(),
+            )),
+            source_storage: shared::source_storage::SourceStorage::local(std::env::temp_dir()),
+            replay_guard: Arc::new(crate::replay_guard::ReplayGuard::in_memory(
+                Default::default(),
+            )),
+            webhook_test_sends: Arc::new(crate::rate_limit::CallerLimiter::new(
+                5,
+                std::time::Duration::from_secs(60),
+            )),
             started_at: Instant::now(),
             cache: Arc::new(CacheLayer::new(CacheConfig::default())),
             registry,
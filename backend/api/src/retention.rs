@@ -0,0 +1,513 @@
+//! Retention windows for incident, audit and notification data, enforced by
+//! a nightly purge.
+//!
+//! Every table in [`RetentionTable`] is kept forever unless
+//! `RETENTION_<TABLE>` (e.g. `RETENTION_INCIDENT_AUDIT_LOG=90`) gives a
+//! number of days. Once a day, from `RETENTION_RUN_HOUR` UTC (default 3), one
+//! instance claims the day's row in `retention_runs` and purges what is past
+//! its window: log rows and resolved incidents are deleted, notification
+//! channels and source access logs are anonymized in place. Rows go in
+//! batches of `RETENTION_BATCH_SIZE` (default 1000), each in its own
+//! transaction, so a long purge never holds locks for long.
+//!
+//! Open incidents are never purged, and a contract's audit trail is kept for
+//! as long as the contract has versions. Nor are incidents under a legal
+//! hold, or the audit trail of a held contract or incident (see
+//! [`crate::legal_holds`]). With `RETENTION_DRY_RUN=true` the
+//! run only counts the rows it would purge. Either way the counts are stored
+//! on the run's row, which `GET /api/admin/retention` shows with the policy.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, NaiveDate, Timelike, Utc};
+use serde::Serialize;
+use sqlx::{types::Json, FromRow, PgPool};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::shutdown::Shutdown;
+
+const DEFAULT_RUN_HOUR: u32 = 3;
+const DEFAULT_BATCH_SIZE: i64 = 1000;
+const POLL_INTERVAL: Duration = Duration::from_secs(600);
+
+/// A table the purge can shrink
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RetentionTable {
+    Incidents,
+    IncidentAuditLog,
+    ContractAuditLog,
+    UserNotificationChannelAttempts,
+    UserNotificationChannels,
+    SourceAccessLogs,
+}
+
+/// What happens to a row past its window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PurgeAction {
+    Delete,
+    /// Personal data is cleared and the row kept
+    Anonymize,
+}
+
+impl RetentionTable {
+    pub const ALL: [RetentionTable; 6] = [
+        RetentionTable::Incidents,
+        RetentionTable::IncidentAuditLog,
+        RetentionTable::ContractAuditLog,
+        RetentionTable::UserNotificationChannelAttempts,
+        RetentionTable::UserNotificationChannels,
+        RetentionTable::SourceAccessLogs,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RetentionTable::Incidents => "incidents",
+            RetentionTable::IncidentAuditLog => "incident_audit_log",
+            RetentionTable::ContractAuditLog => "contract_audit_log",
+            RetentionTable::UserNotificationChannelAttempts => "user_notification_channel_attempts",
+            RetentionTable::UserNotificationChannels => "user_notification_channels",
+            RetentionTable::SourceAccessLogs => "source_access_logs",
+        }
+    }
+
+    pub fn env_var(self) -> &'static str {
+        match self {
+            RetentionTable::Incidents => "RETENTION_INCIDENTS",
+            RetentionTable::IncidentAuditLog => "RETENTION_INCIDENT_AUDIT_LOG",
+            RetentionTable::ContractAuditLog => "RETENTION_CONTRACT_AUDIT_LOG",
+            RetentionTable::UserNotificationChannelAttempts => {
+                "RETENTION_USER_NOTIFICATION_CHANNEL_ATTEMPTS"
+            }
+            RetentionTable::UserNotificationChannels => "RETENTION_USER_NOTIFICATION_CHANNELS",
+            RetentionTable::SourceAccessLogs => "RETENTION_SOURCE_ACCESS_LOGS",
+        }
+    }
+
+    pub fn action(self) -> PurgeAction {
+        match self {
+            RetentionTable::UserNotificationChannels | RetentionTable::SourceAccessLogs => {
+                PurgeAction::Anonymize
+            }
+            _ => PurgeAction::Delete,
+        }
+    }
+
+    /// Which rows the window applies to, in words
+    pub fn rule(self) -> &'static str {
+        match self {
+            RetentionTable::Incidents => {
+                "resolved incidents, by end time, with their updates and timelines; open incidents are kept"
+            }
+            RetentionTable::IncidentAuditLog => "incident audit entries, by timestamp",
+            RetentionTable::ContractAuditLog => {
+                "contract audit entries, by timestamp, once the contract has no versions left"
+            }
+            RetentionTable::UserNotificationChannelAttempts => {
+                "channel verification and delivery attempts, by attempt time"
+            }
+            RetentionTable::UserNotificationChannels => {
+                "target, name and errors of channels disabled before the window"
+            }
+            RetentionTable::SourceAccessLogs => {
+                "actor, IP address and user agent of source access log entries"
+            }
+        }
+    }
+
+    /// Rows past the window; `$1` is the cutoff. Rows already anonymized
+    /// don't match, so a batch loop ends.
+    fn predicate(self) -> &'static str {
+        match self {
+            RetentionTable::Incidents => {
+                "end_time IS NOT NULL AND end_time < $1 AND NOT legal_hold"
+            }
+            RetentionTable::IncidentAuditLog => {
+                "timestamp < $1 AND NOT EXISTS \
+                 (SELECT 1 FROM legal_holds h WHERE h.target_type = 'incident' \
+                 AND h.target_id = incident_audit_log.incident_id AND h.released_at IS NULL)"
+            }
+            RetentionTable::ContractAuditLog => {
+                "timestamp < $1 AND NOT EXISTS \
+                 (SELECT 1 FROM contract_versions v WHERE v.contract_id = contract_audit_log.contract_id) \
+                 AND NOT EXISTS \
+                 (SELECT 1 FROM legal_holds h WHERE h.target_type = 'contract' \
+                 AND h.target_id = contract_audit_log.contract_id AND h.released_at IS NULL)"
+            }
+            RetentionTable::UserNotificationChannelAttempts => "attempted_at < $1",
+            RetentionTable::UserNotificationChannels => {
+                "status = 'disabled' AND disabled_at < $1 AND target NOT LIKE 'redacted:%'"
+            }
+            RetentionTable::SourceAccessLogs => {
+                "created_at < $1 AND \
+                 (actor IS NOT NULL OR request_ip IS NOT NULL OR user_agent IS NOT NULL)"
+            }
+        }
+    }
+
+    /// Purges up to `$2` rows older than `$1`
+    pub fn purge_statement(self) -> String {
+        let table = self.name();
+        let batch = format!(
+            "SELECT id FROM {} WHERE {} LIMIT $2 FOR UPDATE SKIP LOCKED",
+            table,
+            self.predicate()
+        );
+        match self {
+            RetentionTable::UserNotificationChannels => format!(
+                "UPDATE {} SET target = 'redacted:' || id::text, name = NULL, last_error = NULL, \
+                 verification_token = NULL, updated_at = NOW() WHERE id IN ({})",
+                table, batch
+            ),
+            RetentionTable::SourceAccessLogs => format!(
+                "UPDATE {} SET actor = NULL, request_ip = NULL, user_agent = NULL WHERE id IN ({})",
+                table, batch
+            ),
+            _ => format!("DELETE FROM {} WHERE id IN ({})", table, batch),
+        }
+    }
+
+    /// Counts the rows older than `$1` a purge would touch
+    pub fn count_statement(self) -> String {
+        format!(
+            "SELECT COUNT(*) FROM {} WHERE {}",
+            self.name(),
+            self.predicate()
+        )
+    }
+}
+
+/// How long a table's rows are kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Retention {
+    #[default]
+    Forever,
+    Days(u32),
+}
+
+impl Retention {
+    pub fn days(self) -> Option<u32> {
+        match self {
+            Retention::Forever => None,
+            Retention::Days(days) => Some(days),
+        }
+    }
+}
+
+impl FromStr for Retention {
+    type Err = String;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        if raw.eq_ignore_ascii_case("forever") {
+            return Ok(Retention::Forever);
+        }
+        match raw.parse::<u32>() {
+            Ok(days) if days > 0 => Ok(Retention::Days(days)),
+            _ => Err(format!(
+                "expected `forever` or a number of days, got `{}`",
+                raw
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionConfig {
+    /// Tables without an entry are kept forever
+    pub policies: BTreeMap<RetentionTable, Retention>,
+    /// Count what would be purged without changing anything
+    pub dry_run: bool,
+    /// UTC hour from which the day's run starts
+    pub run_hour: u32,
+    /// Rows per transaction
+    pub batch_size: i64,
+}
+
+impl Default for RetentionConfig {
+    fn default() -> Self {
+        Self {
+            policies: BTreeMap::new(),
+            dry_run: false,
+            run_hour: DEFAULT_RUN_HOUR,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+impl RetentionConfig {
+    pub fn retention(&self, table: RetentionTable) -> Retention {
+        self.policies.get(&table).copied().unwrap_or_default()
+    }
+
+    /// Whether any table has a window at all
+    pub fn purges_anything(&self) -> bool {
+        RetentionTable::ALL
+            .iter()
+            .any(|table| self.retention(*table) != Retention::Forever)
+    }
+
+    /// Table name to days kept, `null` for forever, as stored with each run
+    fn policy_summary(&self) -> BTreeMap<String, Option<u32>> {
+        RetentionTable::ALL
+            .iter()
+            .map(|table| (table.name().to_string(), self.retention(*table).days()))
+            .collect()
+    }
+}
+
+/// One nightly run, the summary audit record of what it purged
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct RetentionRun {
+    pub id: Uuid,
+    pub run_date: NaiveDate,
+    pub dry_run: bool,
+    /// Table name to days kept, `null` for forever
+    #[schema(value_type = Object)]
+    pub policy: Json<BTreeMap<String, Option<u32>>>,
+    /// Table name to rows deleted or anonymized, or that would have been in
+    /// a dry run
+    #[schema(value_type = Object)]
+    pub counts: Json<BTreeMap<String, i64>>,
+    /// Why the run stopped early
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// Unset while the run is in progress
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// The most recent run, if there has been one
+pub async fn last_run(pool: &PgPool) -> Result<Option<RetentionRun>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT id, run_date, dry_run, policy, counts, error, started_at, finished_at \
+         FROM retention_runs ORDER BY run_date DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await
+}
+
+/// Whether today's run may start: the run hour has come. Runs that already
+/// happened today are ruled out by claiming the date.
+pub fn is_due(now: DateTime<Utc>, run_hour: u32) -> bool {
+    now.hour() >= run_hour
+}
+
+/// Spawn the background task that purges data past its retention window.
+pub fn spawn_retention_task(pool: PgPool, config: RetentionConfig, shutdown: &Shutdown) {
+    if !config.purges_anything() {
+        tracing::info!("retention: every table is kept forever, purging is off");
+        return;
+    }
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            let now = Utc::now();
+            if !is_due(now, config.run_hour) {
+                continue;
+            }
+            if let Err(err) = run_once(&pool, &config, now.date_naive()).await {
+                tracing::error!(error = ?err, "retention: run failed");
+            }
+        }
+    });
+}
+
+/// Runs the purge for `date` unless another instance already has.
+async fn run_once(
+    pool: &PgPool,
+    config: &RetentionConfig,
+    date: NaiveDate,
+) -> Result<(), sqlx::Error> {
+    let run_id: Option<Uuid> = sqlx::query_scalar(
+        "INSERT INTO retention_runs (run_date, dry_run, policy) VALUES ($1, $2, $3) \
+         ON CONFLICT (run_date) DO NOTHING RETURNING id",
+    )
+    .bind(date)
+    .bind(config.dry_run)
+    .bind(Json(config.policy_summary()))
+    .fetch_optional(pool)
+    .await?;
+    let Some(run_id) = run_id else {
+        return Ok(());
+    };
+
+    let mut counts = BTreeMap::new();
+    let mut error = None;
+    for table in RetentionTable::ALL {
+        let Retention::Days(days) = config.retention(table) else {
+            continue;
+        };
+        let cutoff = Utc::now() - chrono::Duration::days(i64::from(days));
+        let purged = if config.dry_run {
+            count(pool, table, cutoff).await
+        } else {
+            purge(pool, table, cutoff, config.batch_size).await
+        };
+        match purged {
+            Ok(rows) => {
+                counts.insert(table.name().to_string(), rows);
+            }
+            Err((rows, err)) => {
+                counts.insert(table.name().to_string(), rows);
+                error = Some(format!("{}: {}", table.name(), err));
+                break;
+            }
+        }
+    }
+
+    tracing::info!(
+        dry_run = config.dry_run,
+        ?counts,
+        ?error,
+        "retention: run finished"
+    );
+    sqlx::query(
+        "UPDATE retention_runs SET counts = $2, error = $3, finished_at = NOW() WHERE id = $1",
+    )
+    .bind(run_id)
+    .bind(Json(&counts))
+    .bind(error)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn count(
+    pool: &PgPool,
+    table: RetentionTable,
+    cutoff: DateTime<Utc>,
+) -> Result<i64, (i64, sqlx::Error)> {
+    sqlx::query_scalar(&table.count_statement())
+        .bind(cutoff)
+        .fetch_one(pool)
+        .await
+        .map_err(|err| (0, err))
+}
+
+/// Purges in batches until one comes back short. On error, returns the rows
+/// purged by the batches committed before it.
+async fn purge(
+    pool: &PgPool,
+    table: RetentionTable,
+    cutoff: DateTime<Utc>,
+    batch_size: i64,
+) -> Result<i64, (i64, sqlx::Error)> {
+    let statement = table.purge_statement();
+    let mut total = 0;
+    loop {
+        let batch = async {
+            let mut tx = pool.begin().await?;
+            let rows = sqlx::query(&statement)
+                .bind(cutoff)
+                .bind(batch_size)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+            tx.commit().await?;
+            Ok::<_, sqlx::Error>(rows as i64)
+        };
+        let rows = batch.await.map_err(|err| (total, err))?;
+        total += rows;
+        if rows < batch_size {
+            return Ok(total);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn retention_is_forever_or_days() {
+        assert_eq!("forever".parse(), Ok(Retention::Forever));
+        assert_eq!("Forever".parse(), Ok(Retention::Forever));
+        assert_eq!("90".parse(), Ok(Retention::Days(90)));
+        assert!("0".parse::<Retention>().is_err());
+        assert!("-1".parse::<Retention>().is_err());
+        assert!("90d".parse::<Retention>().is_err());
+    }
+
+    #[test]
+    fn open_incidents_and_versioned_contracts_are_never_purged() {
+        let incidents = RetentionTable::Incidents.purge_statement();
+        assert!(incidents.starts_with("DELETE FROM incidents"));
+        assert!(incidents.contains("end_time IS NOT NULL"));
+
+        let audit = RetentionTable::ContractAuditLog.purge_statement();
+        assert!(audit.contains("NOT EXISTS (SELECT 1 FROM contract_versions"));
+        assert!(RetentionTable::ContractAuditLog
+            .count_statement()
+            .contains("NOT EXISTS (SELECT 1 FROM contract_versions"));
+    }
+
+    #[test]
+    fn held_records_and_their_audit_logs_are_never_purged() {
+        assert!(RetentionTable::Incidents
+            .purge_statement()
+            .contains("AND NOT legal_hold"));
+        for table in [
+            RetentionTable::IncidentAuditLog,
+            RetentionTable::ContractAuditLog,
+        ] {
+            for statement in [table.purge_statement(), table.count_statement()] {
+                assert!(statement.contains("FROM legal_holds h"), "{statement}");
+                assert!(statement.contains("released_at IS NULL"), "{statement}");
+            }
+        }
+    }
+
+    #[test]
+    fn anonymized_rows_are_not_matched_again() {
+        for table in RetentionTable::ALL {
+            let statement = table.purge_statement();
+            match table.action() {
+                PurgeAction::Anonymize => assert!(statement.starts_with("UPDATE"), "{statement}"),
+                PurgeAction::Delete => assert!(statement.starts_with("DELETE"), "{statement}"),
+            }
+            assert!(statement.contains("LIMIT $2"), "{statement}");
+        }
+        assert!(RetentionTable::UserNotificationChannels
+            .purge_statement()
+            .contains("target NOT LIKE 'redacted:%'"));
+        assert!(RetentionTable::SourceAccessLogs
+            .purge_statement()
+            .contains("actor IS NOT NULL OR request_ip IS NOT NULL"));
+    }
+
+    #[test]
+    fn runs_start_from_the_run_hour() {
+        let at = |hour| Utc.with_ymd_and_hms(2026, 10, 16, hour, 30, 0).unwrap();
+        assert!(!is_due(at(2), 3));
+        assert!(is_due(at(3), 3));
+        assert!(is_due(at(23), 3));
+        assert!(is_due(at(0), 0));
+    }
+
+    #[test]
+    fn only_tables_with_a_window_are_purged() {
+        let mut config = RetentionConfig::default();
+        assert!(!config.purges_anything());
+        assert_eq!(config.policy_summary()["incidents"], None);
+
+        config
+            .policies
+            .insert(RetentionTable::IncidentAuditLog, Retention::Days(30));
+        assert!(config.purges_anything());
+        assert_eq!(config.policy_summary()["incident_audit_log"], Some(30));
+        assert_eq!(
+            config.retention(RetentionTable::Incidents),
+            Retention::Forever
+        );
+    }
+}
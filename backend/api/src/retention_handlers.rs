@@ -0,0 +1,71 @@
+//! Data retention status, see [`crate::retention`].
+//!
+//!   GET /api/admin/retention    – the active policy per table and the last run
+
+use axum::{extract::State, Json};
+use serde::Serialize;
+
+use crate::{
+    error::ApiResult,
+    handlers::db_internal_error,
+    retention::{self, PurgeAction, RetentionRun, RetentionTable},
+    state::AppState,
+};
+
+/// The retention window of one table
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RetentionPolicy {
+    pub table: RetentionTable,
+    /// Days rows are kept; `null` keeps them forever
+    pub keep_days: Option<u32>,
+    pub action: PurgeAction,
+    /// Which rows the window applies to
+    pub applies_to: &'static str,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RetentionStatus {
+    /// Runs only count what they would purge
+    pub dry_run: bool,
+    /// UTC hour from which the nightly run starts
+    pub run_hour_utc: u32,
+    /// Rows purged per transaction
+    pub batch_size: i64,
+    pub policies: Vec<RetentionPolicy>,
+    /// `null` until the first run
+    pub last_run: Option<RetentionRun>,
+}
+
+/// The retention policy in effect and what the last nightly run purged.
+#[utoipa::path(
+    get,
+    path = "/api/admin/retention",
+    responses(
+        (status = 200, description = "Active policy and last run", body = RetentionStatus),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Retention"
+)]
+pub async fn get_retention(State(state): State<AppState>) -> ApiResult<Json<RetentionStatus>> {
+    let config = &state.config.retention;
+    let last_run = retention::last_run(&state.db)
+        .await
+        .map_err(|err| db_internal_error("fetch last retention run", err))?;
+
+    Ok(Json(RetentionStatus {
+        dry_run: config.dry_run,
+        run_hour_utc: config.run_hour,
+        batch_size: config.batch_size,
+        policies: RetentionTable::ALL
+            .into_iter()
+            .map(|table| RetentionPolicy {
+                table,
+                keep_days: config.retention(table).days(),
+                action: table.action(),
+                applies_to: table.rule(),
+            })
+            .collect(),
+        last_run,
+    }))
+}
@@ -1,16 +1,24 @@
 #[cfg(feature = "openapi")]
 use crate::openapi;
 use crate::{
-    ab_test_handlers, auth, auth_handlers, batch_verify_handlers, breaking_changes,
-    canary_handlers, category_handlers, compatibility_testing_handlers, contract_events,
-    custom_metrics_handlers, deprecation_handlers, handlers, metrics_handler, migration_handlers,
-    performance_handlers, resource_handlers, similarity_handlers, state::AppState, websocket,
+    ab_test_handlers, analytics_handlers, auth, auth_handlers, badge_handlers,
+    batch_verify_handlers, breaking_changes, build_info_handlers, canary_handlers,
+    category_handlers, compare_handlers, compatibility_testing_handlers, contract_changes,
+    contract_events, contract_freshness_handlers, contract_profile, custom_metrics_handlers,
+    deprecation_handlers, feature_flag_handlers, handlers, interface_compat, job_handlers,
+    legal_holds, metrics_handler, migration_handlers, org_handlers, performance_handlers,
+    profile_uploads, publish_review_handlers, registry_snapshot_handlers, resource_handlers,
+    retention_handlers, saved_search_handlers, search_weights, similarity_handlers, slug_handlers,
+    state::AppState,
+    tag_handlers, telemetry_handlers,
+    validation::payload_size::{self, BodyLimits},
+    wasm_compare, websocket,
 };
 
 use axum::{
     middleware,
     routing::{delete, get, patch, post, put},
-    Router,
+    Extension, Router,
 };
 #[cfg(feature = "openapi")]
 use utoipa::OpenApi;
@@ -45,7 +53,13 @@ pub fn contract_routes() -> Router<AppState> {
         .route("/api/contracts/batch", post(handlers::get_contracts_batch))
         .route("/contracts/batch", post(handlers::get_contracts_batch))
         .route("/api/contracts/graph", get(handlers::get_contract_graph))
-        .route("/api/contracts/:id", get(handlers::get_contract))
+        .route(
+            "/api/contracts/changes",
+            get(contract_changes::get_contract_changes),
+        )
+        .route("/api/resolve", get(slug_handlers::resolve_contract))
+        .route("/api/categories", get(category_handlers::list_categories))
+        .route("/api/tags", get(tag_handlers::list_tags))
         .route(
             "/api/contracts/:id/metadata",
             patch(handlers::update_contract_metadata),
@@ -58,6 +72,10 @@ pub fn contract_routes() -> Router<AppState> {
             "/api/contracts/:id/status",
             patch(handlers::update_contract_status),
         )
+        .route(
+            "/api/contracts/:id/freshness/confirm",
+            post(contract_freshness_handlers::confirm_contract),
+        )
         .route(
             "/api/contracts/:id/audit-log",
             get(handlers::get_contract_audit_log),
@@ -71,21 +89,41 @@ pub fn contract_routes() -> Router<AppState> {
             "/api/contracts/:id/openapi.json",
             get(handlers::get_contract_openapi_json),
         )
+        .route(
+            "/api/contracts/:id/badge.svg",
+            get(badge_handlers::get_contract_badge_svg),
+        )
+        .route(
+            "/api/contracts/:id/badge.json",
+            get(badge_handlers::get_contract_badge_json),
+        )
         .route(
             "/api/contracts/:id/versions",
             get(handlers::get_contract_versions).post(handlers::create_contract_version),
         )
         .route(
-            "/api/contracts/:id/changelog",
-            get(handlers::get_contract_changelog),
+            "/api/contracts/:id/versions/compat",
+            post(interface_compat::preview_version_compat),
         )
         .route(
-            "/api/contracts/:id/versions/:version/source",
-            get(handlers::get_contract_source).post(handlers::upload_contract_source),
+            "/api/contracts/:id/versions/compare",
+            get(wasm_compare::compare_version_wasm),
         )
         .route(
-            "/api/contracts/:id/versions/:version/source/diff",
-            get(handlers::get_contract_source_diff),
+            "/api/contracts/:id/versions/:version/compat",
+            get(interface_compat::get_version_compat),
+        )
+        .route(
+            "/api/contracts/:id/versions/:version/profile",
+            get(contract_profile::get_version_profile),
+        )
+        .route(
+            "/api/contracts/:id/profiles",
+            get(profile_uploads::list_profiles).post(profile_uploads::upload_profile),
+        )
+        .route(
+            "/api/contracts/:id/changelog",
+            get(handlers::get_contract_changelog),
         )
         .route(
             "/contracts/:id/changelog",
@@ -95,6 +133,10 @@ pub fn contract_routes() -> Router<AppState> {
             "/api/contracts/breaking-changes",
             get(breaking_changes::get_breaking_changes),
         )
+        .route(
+            "/api/contracts/compare",
+            get(compare_handlers::compare_contracts),
+        )
         .route(
             "/api/contracts/:id/interactions",
             get(handlers::get_contract_interactions).post(handlers::post_contract_interaction),
@@ -150,6 +192,14 @@ pub fn contract_routes() -> Router<AppState> {
             get(similarity_handlers::get_similar_contracts),
         )
         .route("/api/contracts/verify", post(handlers::verify_contract))
+        .route(
+            "/api/contracts/:id/build-info",
+            get(build_info_handlers::get_build_info),
+        )
+        .route(
+            "/api/contracts/:id/sbom",
+            get(build_info_handlers::get_sbom),
+        )
         .route(
             "/api/contracts/batch-verify",
             post(batch_verify_handlers::batch_verify_contracts),
@@ -229,6 +279,10 @@ pub fn contract_routes() -> Router<AppState> {
             "/api/contracts/:id/reviews/:review_id/vote",
             post(handlers::reviews::vote_review),
         )
+        .route(
+            "/api/contracts/:id/reviews/:review_id/helpful",
+            post(handlers::reviews::toggle_helpful_vote),
+        )
         .route(
             "/api/contracts/:id/reviews/:review_id/flag",
             post(handlers::reviews::flag_review),
@@ -242,6 +296,46 @@ pub fn contract_routes() -> Router<AppState> {
             get(handlers::reviews::get_rating_stats),
         )
         .merge(favorite_routes())
+        .merge(saved_search_routes())
+}
+
+/// Contract reads that are answered from the cache when possible. They
+/// check for an impaired database themselves on a cache miss, so `main` does
+/// not put them behind [`crate::component_health::short_circuit`].
+pub fn cached_contract_routes() -> Router<AppState> {
+    Router::new().route("/api/contracts/:id", get(handlers::get_contract))
+}
+
+/// Routes that read or write source blobs as well as the database
+pub fn contract_source_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/contracts/:id/versions/:version/source",
+            get(handlers::get_contract_source).post(handlers::upload_contract_source),
+        )
+        .route(
+            "/api/contracts/:id/versions/:version/source/diff",
+            get(handlers::get_contract_source_diff),
+        )
+        .route(
+            "/api/contracts/:id/versions/:version/source/download",
+            get(handlers::download_contract_source),
+        )
+}
+
+/// WASM uploads stream `application/wasm` bodies to disk, so they are kept
+/// out of the JSON body limit that `main` applies to every other route.
+pub fn wasm_upload_routes(limits: BodyLimits) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/contracts/:id/versions/:version/wasm",
+            post(handlers::upload_contract_wasm),
+        )
+        .layer(middleware::from_fn_with_state(
+            limits,
+            payload_size::wasm_upload_middleware,
+        ))
+        .layer(Extension(limits))
 }
 
 pub fn organization_routes() -> Router<AppState> {
@@ -256,7 +350,15 @@ pub fn organization_routes() -> Router<AppState> {
         )
         .route(
             "/api/organizations/:id/members",
-            get(org_handlers::list_org_members),
+            get(org_handlers::list_org_members).post(org_handlers::add_member),
+        )
+        .route(
+            "/api/organizations/:id/members/:address",
+            delete(org_handlers::remove_member),
+        )
+        .route(
+            "/api/organizations/:id/contracts",
+            get(org_handlers::list_org_contracts),
         )
         .route(
             "/api/organizations/:id/invitations",
@@ -286,6 +388,15 @@ pub fn publisher_routes() -> Router<AppState> {
             "/api/publishers/:id/contracts",
             get(handlers::get_publisher_contracts),
         )
+        .route("/api/me/contracts", get(handlers::list_my_contracts))
+        .route(
+            "/api/me/contracts/tags",
+            post(tag_handlers::bulk_update_my_tags),
+        )
+}
+
+pub fn job_routes() -> Router<AppState> {
+    Router::new().route("/api/jobs/:id", get(job_handlers::get_job))
 }
 
 pub fn favorite_routes() -> Router<AppState> {
@@ -300,6 +411,28 @@ pub fn favorite_routes() -> Router<AppState> {
         )
 }
 
+pub fn saved_search_routes() -> Router<AppState> {
+    Router::new()
+        .route("/api/searches", post(saved_search_handlers::save_search))
+        .route(
+            "/api/me/searches",
+            get(saved_search_handlers::list_my_searches),
+        )
+        .route(
+            "/api/searches/:id",
+            get(saved_search_handlers::get_saved_search)
+                .delete(saved_search_handlers::delete_saved_search),
+        )
+        .route(
+            "/api/searches/:id/results",
+            get(saved_search_handlers::get_saved_search_results),
+        )
+}
+
+pub fn telemetry_routes() -> Router<AppState> {
+    Router::new().route("/api/telemetry", post(telemetry_handlers::submit_telemetry))
+}
+
 pub fn health_routes() -> Router<AppState> {
     Router::new()
         .route("/health", get(handlers::health_check))
@@ -312,6 +445,10 @@ pub fn health_routes() -> Router<AppState> {
             "/api/analytics/summary",
             get(analytics_handlers::get_analytics_summary),
         )
+        .route(
+            "/api/analytics/timeseries",
+            get(analytics_handlers::get_analytics_timeseries),
+        )
 }
 
 pub fn network_routes() -> Router<AppState> {
@@ -470,6 +607,19 @@ pub fn performance_routes() -> Router<AppState> {
 pub fn admin_routes() -> Router<AppState> {
     Router::new()
         .route("/api/admin/audit-logs", get(handlers::get_all_audit_logs))
+        .route("/api/admin/flags", get(feature_flag_handlers::list_flags))
+        .route(
+            "/api/admin/flags/:name",
+            put(feature_flag_handlers::update_flag),
+        )
+        .route(
+            "/api/admin/settings",
+            get(feature_flag_handlers::list_settings),
+        )
+        .route(
+            "/api/admin/settings/:name",
+            put(feature_flag_handlers::update_setting),
+        )
         .merge(migration_routes())
         // Category management (issue #414) – admin-only write endpoints
         .route(
@@ -480,6 +630,61 @@ pub fn admin_routes() -> Router<AppState> {
             "/api/admin/categories/:id",
             put(category_handlers::update_category).delete(category_handlers::delete_category),
         )
+        .route(
+            "/api/admin/tag-aliases",
+            post(tag_handlers::create_tag_alias),
+        )
+        .route(
+            "/api/admin/tag-aliases/:alias",
+            delete(tag_handlers::delete_tag_alias),
+        )
+        .route(
+            "/api/admin/publish-reviews",
+            get(publish_review_handlers::list_publish_reviews),
+        )
+        .route(
+            "/api/admin/publish-reviews/:id/approve",
+            post(publish_review_handlers::approve_publish),
+        )
+        .route(
+            "/api/admin/publish-reviews/:id/reject",
+            post(publish_review_handlers::reject_publish),
+        )
+        .route(
+            "/api/admin/snapshots",
+            post(registry_snapshot_handlers::create_snapshot)
+                .get(registry_snapshot_handlers::list_snapshots),
+        )
+        .route(
+            "/api/admin/snapshots/:id/download",
+            get(registry_snapshot_handlers::download_snapshot),
+        )
+        .route(
+            "/api/admin/snapshots/:id/restore",
+            post(registry_snapshot_handlers::restore_snapshot),
+        )
+        .route("/api/admin/jobs", get(job_handlers::list_failed_jobs))
+        .route("/api/admin/jobs/:id/retry", post(job_handlers::retry_job))
+        .route(
+            "/api/admin/retention",
+            get(retention_handlers::get_retention),
+        )
+        .route(
+            "/api/admin/telemetry",
+            get(telemetry_handlers::get_telemetry),
+        )
+        .route(
+            "/api/admin/search/weights",
+            get(search_weights::get_search_weights).put(search_weights::update_search_weights),
+        )
+        .route(
+            "/api/admin/holds",
+            get(legal_holds::list_holds).post(legal_holds::create_hold),
+        )
+        .route(
+            "/api/admin/holds/:id/release",
+            post(legal_holds::release_hold),
+        )
         .route_layer(middleware::from_fn(auth::require_admin))
 }
 
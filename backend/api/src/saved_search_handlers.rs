@@ -0,0 +1,371 @@
+//! Saved contract searches, owned by the caller's API token subject.
+//!
+//!   POST   /api/searches              – save the query parameters of `GET /api/contracts` under a name
+//!   GET    /api/me/searches           – the caller's saved searches
+//!   GET    /api/searches/:id          – one saved search, for anyone holding its id
+//!   GET    /api/searches/:id/results  – run a saved search
+//!   DELETE /api/searches/:id          – remove one of the caller's saved searches
+//!
+//! A saved search runs with the access of whoever runs it, checked at that
+//! moment: private contracts only show up for members of their organization,
+//! so a search shared with a teammate, or run by an owner who has since left
+//! the organization, only returns the public contracts it matches.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{StatusCode, Uri},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use shared::ContractSearchParams;
+use sqlx::{FromRow, Row};
+
+use crate::{
+    auth::AuthClaims,
+    error::{ApiError, ApiResult},
+    handlers::{self, db_internal_error},
+    pagination::Pagination,
+    state::AppState,
+};
+
+/// Saved searches one owner may hold
+const MAX_SAVED_SEARCHES_PER_OWNER: i64 = 100;
+const MAX_NAME_LEN: usize = 100;
+/// Length of the random id; 62^10 ids make a collision between saved
+/// searches practically impossible
+const ID_LEN: usize = 10;
+/// Paging is chosen each time a saved search is run, not saved with it
+const PAGING_PARAMS: [&str; 5] = ["limit", "page_size", "offset", "page", "cursor"];
+
+#[derive(Debug, Clone, Serialize, FromRow, utoipa::ToSchema)]
+pub struct SavedSearch {
+    pub id: String,
+    /// Subject of the API token that saved the search
+    pub owner: String,
+    pub name: String,
+    /// Query parameters of `GET /api/contracts`, by name
+    #[schema(value_type = Object)]
+    pub params: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Body of `POST /api/searches`. Saving under a name the caller already
+/// uses replaces that search's parameters and keeps its id.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct SaveSearchRequest {
+    pub name: String,
+    /// Query parameters of `GET /api/contracts`, e.g.
+    /// `{"category": "oracle", "network": "mainnet", "verified_only": "true"}`
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+fn new_id() -> String {
+    rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(ID_LEN)
+        .map(char::from)
+        .collect()
+}
+
+/// `/api/contracts` with `params` as its query string
+fn search_uri(params: &BTreeMap<String, String>) -> Uri {
+    let mut url = reqwest::Url::parse("http://registry/api/contracts").expect("static URL");
+    url.query_pairs_mut().extend_pairs(params);
+    format!("{}?{}", url.path(), url.query().unwrap_or_default())
+        .parse()
+        .expect("URL-encoded path and query")
+}
+
+fn parse_params(
+    params: &BTreeMap<String, String>,
+) -> Result<Query<ContractSearchParams>, axum::extract::rejection::QueryRejection> {
+    Query::try_from_uri(&search_uri(params))
+}
+
+/// Checks the parameters of a search being saved, leaving out empty values
+/// and paging. Unknown parameter names are refused rather than silently
+/// ignored when the search runs.
+fn normalize_params(params: BTreeMap<String, String>) -> ApiResult<BTreeMap<String, String>> {
+    let params: BTreeMap<String, String> = params
+        .into_iter()
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .filter(|(name, value)| !value.is_empty() && !PAGING_PARAMS.contains(&name.as_str()))
+        .collect();
+
+    let Query(parsed) = parse_params(&params).map_err(|err| {
+        ApiError::bad_request(
+            "InvalidSearchParams",
+            format!("invalid search parameters: {}", err.body_text()),
+        )
+    })?;
+    let known = serde_json::to_value(parsed).unwrap_or_default();
+    if let Some(unknown) = params
+        .keys()
+        .find(|name| known.get(name.as_str()).is_none())
+    {
+        return Err(ApiError::bad_request(
+            "InvalidSearchParams",
+            format!("unknown search parameter '{}'", unknown),
+        ));
+    }
+    Ok(params)
+}
+
+async fn fetch_saved_search(state: &AppState, id: &str) -> ApiResult<SavedSearch> {
+    sqlx::query_as("SELECT * FROM saved_searches WHERE id = $1")
+        .bind(id)
+        .fetch_optional(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("fetch saved search", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "SavedSearchNotFound",
+                format!("No saved search with id {}", id),
+            )
+        })
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/searches",
+    request_body = SaveSearchRequest,
+    responses(
+        (status = 201, description = "Search saved", body = SavedSearch),
+        (status = 200, description = "The caller's search with the same name updated", body = SavedSearch),
+        (status = 400, description = "Missing name, or unknown or invalid search parameters"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 409, description = "Saved search limit reached")
+    ),
+    tag = "Saved Searches"
+)]
+pub async fn save_search(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<SaveSearchRequest>,
+) -> ApiResult<(StatusCode, Json<SavedSearch>)> {
+    let name = req.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(ApiError::bad_request(
+            "InvalidSearchName",
+            format!("name must be 1 to {} characters", MAX_NAME_LEN),
+        ));
+    }
+    let params = normalize_params(req.params)?;
+
+    let held: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM saved_searches WHERE owner = $1 AND name <> $2")
+            .bind(&claims.sub)
+            .bind(name)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("count saved searches", err))?;
+    if held >= MAX_SAVED_SEARCHES_PER_OWNER {
+        return Err(ApiError::conflict(
+            "SavedSearchLimitReached",
+            format!(
+                "A user can keep at most {} saved searches; delete one first",
+                MAX_SAVED_SEARCHES_PER_OWNER
+            ),
+        ));
+    }
+
+    // `xmax = 0` only for a freshly inserted row, not for one the upsert updated
+    let row = sqlx::query(
+        r#"
+        INSERT INTO saved_searches (id, owner, name, params)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (owner, name)
+            DO UPDATE SET params = EXCLUDED.params, updated_at = NOW()
+        RETURNING *, (xmax = 0) AS inserted
+        "#,
+    )
+    .bind(new_id())
+    .bind(&claims.sub)
+    .bind(name)
+    .bind(serde_json::to_value(&params).unwrap_or_default())
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("save search", err))?;
+
+    let search =
+        SavedSearch::from_row(&row).map_err(|err| db_internal_error("read saved search", err))?;
+    let inserted: bool = row
+        .try_get("inserted")
+        .map_err(|err| db_internal_error("read saved search", err))?;
+
+    tracing::info!(
+        saved_search_id = %search.id,
+        owner = %claims.sub,
+        inserted,
+        "search saved"
+    );
+
+    let status = if inserted {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(search)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/searches",
+    responses(
+        (status = 200, description = "The caller's saved searches, by name", body = [SavedSearch]),
+        (status = 401, description = "Missing or invalid API token")
+    ),
+    tag = "Saved Searches"
+)]
+pub async fn list_my_searches(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Vec<SavedSearch>>> {
+    let searches: Vec<SavedSearch> =
+        sqlx::query_as("SELECT * FROM saved_searches WHERE owner = $1 ORDER BY name")
+            .bind(&claims.sub)
+            .fetch_all(state.read_pool())
+            .await
+            .map_err(|err| db_internal_error("list saved searches", err))?;
+    Ok(Json(searches))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/searches/{id}",
+    params(("id" = String, Path, description = "Saved search id")),
+    responses(
+        (status = 200, description = "The saved search", body = SavedSearch),
+        (status = 404, description = "No saved search with this id")
+    ),
+    tag = "Saved Searches"
+)]
+pub async fn get_saved_search(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> ApiResult<Json<SavedSearch>> {
+    Ok(Json(fetch_saved_search(&state, &id).await?))
+}
+
+/// Runs a saved search as `GET /api/contracts` would with its parameters,
+/// paged by `limit`/`offset`/`page` on this request
+#[utoipa::path(
+    get,
+    path = "/api/searches/{id}/results",
+    params(
+        ("id" = String, Path, description = "Saved search id"),
+        crate::pagination::PageParams
+    ),
+    responses(
+        (status = 200, description = "Contracts matching the saved search that the caller can see", body = shared::pagination::Paginated<shared::search::ContractSearchHit>),
+        (status = 400, description = "Invalid pagination"),
+        (status = 404, description = "No saved search with this id")
+    ),
+    tag = "Saved Searches"
+)]
+pub async fn get_saved_search_results(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: Option<AuthClaims>,
+    pagination: Result<Pagination, ApiError>,
+) -> Response {
+    let search = match fetch_saved_search(&state, &id).await {
+        Ok(search) => search,
+        Err(err) => return err.into_response(),
+    };
+    let params: BTreeMap<String, String> =
+        serde_json::from_value(search.params).unwrap_or_default();
+    handlers::list_contracts(State(state), claims, pagination, parse_params(&params)).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/searches/{id}",
+    params(("id" = String, Path, description = "Saved search id")),
+    responses(
+        (status = 204, description = "Saved search removed"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "None of the caller's saved searches has this id")
+    ),
+    tag = "Saved Searches"
+)]
+pub async fn delete_saved_search(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    let removed = sqlx::query("DELETE FROM saved_searches WHERE id = $1 AND owner = $2")
+        .bind(&id)
+        .bind(&claims.sub)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete saved search", err))?
+        .rows_affected();
+
+    if removed == 0 {
+        return Err(ApiError::not_found(
+            "SavedSearchNotFound",
+            format!("You have no saved search with id {}", id),
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn paging_and_empty_values_are_not_saved() {
+        let saved = normalize_params(params(&[
+            ("query", "price feed"),
+            ("category", "oracle"),
+            ("verified_only", "true"),
+            ("tags", " "),
+            ("limit", "50"),
+            ("page", "2"),
+        ]))
+        .unwrap();
+        assert_eq!(
+            saved,
+            params(&[
+                ("category", "oracle"),
+                ("query", "price feed"),
+                ("verified_only", "true"),
+            ])
+        );
+    }
+
+    #[test]
+    fn unknown_and_invalid_parameters_are_refused() {
+        let err = normalize_params(params(&[("categroy", "oracle")])).unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+        assert!(err.body().error.message.contains("'categroy'"));
+
+        let err = normalize_params(params(&[("verified_only", "maybe")])).unwrap_err();
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn saved_parameters_run_as_a_query_string() {
+        let uri = search_uri(&params(&[("query", "price feed & oracle")]));
+        assert_eq!(uri.path(), "/api/contracts");
+        let Query(parsed) = Query::<ContractSearchParams>::try_from_uri(&uri).unwrap();
+        assert_eq!(parsed.query.as_deref(), Some("price feed & oracle"));
+        assert_eq!(new_id().len(), ID_LEN);
+    }
+}
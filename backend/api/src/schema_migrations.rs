@@ -0,0 +1,416 @@
+//! Schema migrations embedded in the binary.
+//!
+//! `database/migrations` is compiled in with `sqlx::migrate!`. Pending
+//! migrations run at startup when `RUN_MIGRATIONS=true`; otherwise apply them
+//! with the `migrate` subcommand:
+//!
+//! ```text
+//! api migrate status
+//! api migrate up
+//! api migrate down --steps 2
+//! ```
+//!
+//! Only migrations shipped as `.up.sql`/`.down.sql` pairs can be reverted.
+//! The API refuses to start against a database that has migrations this
+//! binary does not know about, which is what rolling back to an older release
+//! after `up` looks like.
+
+use std::fmt;
+
+use anyhow::Context;
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::{Connection, PgConnection};
+
+pub static MIGRATOR: Migrator = sqlx::migrate!("../../database/migrations");
+
+const USAGE: &str = "usage: api migrate <status | up | down --steps N>";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KnownMigration {
+    pub version: i64,
+    pub description: String,
+    /// Ships with a `.down.sql` script
+    pub reversible: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaStatus {
+    /// Applied migrations this binary knows about, oldest first
+    pub applied: Vec<KnownMigration>,
+    /// Migrations this binary would apply, oldest first
+    pub pending: Vec<KnownMigration>,
+    /// Applied versions missing from this binary, oldest first
+    pub unknown: Vec<i64>,
+    /// Version whose last run failed part-way
+    pub dirty: Option<i64>,
+}
+
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The database has migrations this binary does not know about
+    Ahead {
+        unknown: Vec<i64>,
+        binary_version: Option<i64>,
+    },
+    Dirty(i64),
+    Irreversible {
+        version: i64,
+        description: String,
+    },
+    NotEnoughApplied {
+        requested: usize,
+        applied: usize,
+    },
+    Migrate(MigrateError),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Ahead {
+                unknown,
+                binary_version,
+            } => write!(
+                f,
+                "database has migrations this binary does not know about ({}); this binary knows up to {}. Deploy a release that includes them or revert them with the newer binary",
+                unknown
+                    .iter()
+                    .map(i64::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                binary_version
+                    .map(|v| v.to_string())
+                    .unwrap_or_else(|| "none".to_string())
+            ),
+            SchemaError::Dirty(version) => write!(
+                f,
+                "migration {} failed part-way; fix the schema by hand and delete its row from _sqlx_migrations",
+                version
+            ),
+            SchemaError::Irreversible {
+                version,
+                description,
+            } => write!(
+                f,
+                "migration {} ({}) has no down script and cannot be reverted",
+                version, description
+            ),
+            SchemaError::NotEnoughApplied { requested, applied } => write!(
+                f,
+                "cannot revert {} migrations, only {} applied",
+                requested, applied
+            ),
+            SchemaError::Migrate(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl From<MigrateError> for SchemaError {
+    fn from(err: MigrateError) -> Self {
+        SchemaError::Migrate(err)
+    }
+}
+
+impl SchemaStatus {
+    pub fn compare(known: &[KnownMigration], applied: &[i64], dirty: Option<i64>) -> Self {
+        let mut status = SchemaStatus {
+            dirty,
+            ..SchemaStatus::default()
+        };
+        for migration in known {
+            if applied.contains(&migration.version) {
+                status.applied.push(migration.clone());
+            } else {
+                status.pending.push(migration.clone());
+            }
+        }
+        status.unknown = applied
+            .iter()
+            .copied()
+            .filter(|version| !known.iter().any(|m| m.version == *version))
+            .collect();
+        status.unknown.sort_unstable();
+        status
+    }
+
+    /// Newest applied version, including ones unknown to this binary
+    pub fn current_version(&self) -> Option<i64> {
+        self.applied
+            .iter()
+            .map(|m| m.version)
+            .chain(self.unknown.iter().copied())
+            .max()
+    }
+
+    /// Fails when the database is ahead of this binary or a migration was
+    /// left half-applied.
+    pub fn ensure_compatible(&self) -> Result<(), SchemaError> {
+        if !self.unknown.is_empty() {
+            return Err(SchemaError::Ahead {
+                unknown: self.unknown.clone(),
+                binary_version: binary_version(),
+            });
+        }
+        if let Some(version) = self.dirty {
+            return Err(SchemaError::Dirty(version));
+        }
+        Ok(())
+    }
+
+    /// The migrations `down --steps n` reverts, newest first, and the version
+    /// left in place afterwards (0 when everything is reverted).
+    pub fn plan_down(&self, steps: usize) -> Result<(Vec<KnownMigration>, i64), SchemaError> {
+        if steps > self.applied.len() {
+            return Err(SchemaError::NotEnoughApplied {
+                requested: steps,
+                applied: self.applied.len(),
+            });
+        }
+
+        let reverting: Vec<KnownMigration> =
+            self.applied.iter().rev().take(steps).cloned().collect();
+        if let Some(irreversible) = reverting.iter().find(|m| !m.reversible) {
+            return Err(SchemaError::Irreversible {
+                version: irreversible.version,
+                description: irreversible.description.clone(),
+            });
+        }
+
+        let target = self
+            .applied
+            .iter()
+            .rev()
+            .nth(steps)
+            .map(|m| m.version)
+            .unwrap_or(0);
+        Ok((reverting, target))
+    }
+}
+
+/// Migrations compiled into this binary, oldest first
+pub fn known_migrations() -> Vec<KnownMigration> {
+    // Reversible migrations appear twice in the migrator, once per direction.
+    MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| KnownMigration {
+            version: m.version,
+            description: m.description.to_string(),
+            reversible: MIGRATOR
+                .iter()
+                .any(|d| d.version == m.version && d.migration_type.is_down_migration()),
+        })
+        .collect()
+}
+
+/// Newest migration compiled into this binary
+pub fn binary_version() -> Option<i64> {
+    MIGRATOR.iter().map(|m| m.version).max()
+}
+
+pub async fn status(conn: &mut PgConnection) -> Result<SchemaStatus, SchemaError> {
+    conn.ensure_migrations_table().await?;
+    let dirty = conn.dirty_version().await?;
+    let applied: Vec<i64> = conn
+        .list_applied_migrations()
+        .await?
+        .into_iter()
+        .map(|m| m.version)
+        .collect();
+    Ok(SchemaStatus::compare(&known_migrations(), &applied, dirty))
+}
+
+/// Applies every pending migration and returns them.
+pub async fn up(conn: &mut PgConnection) -> Result<Vec<KnownMigration>, SchemaError> {
+    let before = status(conn).await?;
+    before.ensure_compatible()?;
+    MIGRATOR.run(&mut *conn).await?;
+    Ok(before.pending)
+}
+
+/// Reverts the newest `steps` migrations and returns them, newest first.
+pub async fn down(
+    conn: &mut PgConnection,
+    steps: usize,
+) -> Result<Vec<KnownMigration>, SchemaError> {
+    let before = status(conn).await?;
+    before.ensure_compatible()?;
+    let (reverting, target) = before.plan_down(steps)?;
+    MIGRATOR.undo(&mut *conn, target).await?;
+    Ok(reverting)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Status,
+    Up,
+    Down { steps: usize },
+}
+
+fn parse_command(args: &[String]) -> anyhow::Result<Command> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        ["status"] => Ok(Command::Status),
+        ["up"] => Ok(Command::Up),
+        ["down"] => Ok(Command::Down { steps: 1 }),
+        ["down", "--steps", n] | ["down", "-n", n] => match n.parse() {
+            Ok(steps) if steps > 0 => Ok(Command::Down { steps }),
+            _ => anyhow::bail!("--steps must be a positive integer\n{}", USAGE),
+        },
+        _ => anyhow::bail!(USAGE),
+    }
+}
+
+/// Entry point for `api migrate ...`. Reads `DATABASE_URL` and prints to
+/// stdout.
+pub async fn run_cli(args: &[String]) -> anyhow::Result<()> {
+    let command = parse_command(args)?;
+    let database_url = std::env::var("DATABASE_URL").context("DATABASE_URL must be set")?;
+    let mut conn = PgConnection::connect(&database_url)
+        .await
+        .context("failed to connect to the database")?;
+
+    match command {
+        Command::Status => print_status(&status(&mut conn).await?),
+        Command::Up => {
+            let applied = up(&mut conn).await?;
+            if applied.is_empty() {
+                println!("Database is up to date");
+            }
+            for m in &applied {
+                println!("Applied  {}  {}", m.version, m.description);
+            }
+        }
+        Command::Down { steps } => {
+            for m in down(&mut conn, steps).await? {
+                println!("Reverted {}  {}", m.version, m.description);
+            }
+        }
+    }
+
+    let version = status(&mut conn).await?.current_version();
+    println!(
+        "Schema version: {}",
+        version
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    );
+    conn.close().await?;
+    Ok(())
+}
+
+fn print_status(status: &SchemaStatus) {
+    println!(
+        "Binary knows {} migrations, up to {}",
+        status.applied.len() + status.pending.len(),
+        binary_version()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "none".to_string())
+    );
+    for m in &status.applied {
+        let dirty = if status.dirty == Some(m.version) {
+            "  (FAILED)"
+        } else {
+            ""
+        };
+        println!("  applied  {}  {}{}", m.version, m.description, dirty);
+    }
+    for m in &status.pending {
+        println!("  pending  {}  {}", m.version, m.description);
+    }
+    for version in &status.unknown {
+        println!("  unknown  {}  (not in this binary)", version);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn migration(version: i64, reversible: bool) -> KnownMigration {
+        KnownMigration {
+            version,
+            description: format!("m{}", version),
+            reversible,
+        }
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn status_splits_applied_pending_and_unknown() {
+        let known = [migration(1, false), migration(2, true), migration(3, true)];
+        let status = SchemaStatus::compare(&known, &[2, 1, 9], None);
+
+        assert_eq!(status.applied, known[..2].to_vec());
+        assert_eq!(status.pending, known[2..].to_vec());
+        assert_eq!(status.unknown, vec![9]);
+        assert_eq!(status.current_version(), Some(9));
+        assert!(matches!(
+            status.ensure_compatible(),
+            Err(SchemaError::Ahead { unknown, .. }) if unknown == vec![9]
+        ));
+    }
+
+    #[test]
+    fn behind_is_compatible_but_dirty_is_not() {
+        let known = [migration(1, false), migration(2, true)];
+        assert!(SchemaStatus::compare(&known, &[1], None)
+            .ensure_compatible()
+            .is_ok());
+        assert!(matches!(
+            SchemaStatus::compare(&known, &[1, 2], Some(2)).ensure_compatible(),
+            Err(SchemaError::Dirty(2))
+        ));
+    }
+
+    #[test]
+    fn down_reverts_newest_first_and_stops_at_irreversible() {
+        let known = [migration(1, false), migration(2, true), migration(3, true)];
+        let status = SchemaStatus::compare(&known, &[1, 2, 3], None);
+
+        let (reverting, target) = status.plan_down(2).unwrap();
+        assert_eq!(reverting, vec![migration(3, true), migration(2, true)]);
+        assert_eq!(target, 1);
+
+        assert!(matches!(
+            status.plan_down(3),
+            Err(SchemaError::Irreversible { version: 1, .. })
+        ));
+        assert!(matches!(
+            status.plan_down(4),
+            Err(SchemaError::NotEnoughApplied {
+                requested: 4,
+                applied: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert_eq!(parse_command(&args(&["status"])).unwrap(), Command::Status);
+        assert_eq!(parse_command(&args(&["up"])).unwrap(), Command::Up);
+        assert_eq!(
+            parse_command(&args(&["down"])).unwrap(),
+            Command::Down { steps: 1 }
+        );
+        assert_eq!(
+            parse_command(&args(&["down", "--steps", "3"])).unwrap(),
+            Command::Down { steps: 3 }
+        );
+        assert!(parse_command(&args(&["down", "--steps", "0"])).is_err());
+        assert!(parse_command(&args(&["sideways"])).is_err());
+    }
+
+    #[test]
+    fn embedded_migrations_are_ordered_and_unique() {
+        let known = known_migrations();
+        assert!(!known.is_empty());
+        assert!(known.windows(2).all(|w| w[0].version < w[1].version));
+        assert_eq!(binary_version(), known.last().map(|m| m.version));
+    }
+}
@@ -0,0 +1,142 @@
+//! How much each contract field counts when search results are ranked.
+//!
+//! A contract's `search_document` holds each field under its own weight
+//! label: name A, tags and category B, description C and the README D,
+//! unless the publisher turned `index_readme` off. Ranking reads the weight
+//! of every label from `search_field_weights` in the query itself, so a
+//! change applies to the next search on every instance.
+//!
+//!   GET /api/admin/search/weights    – the weights in effect
+//!   PUT /api/admin/search/weights    – change some of them
+
+use axum::{extract::State, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::AuthClaims,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// The weights as the array `ts_rank_cd` takes, lowest label (D) first
+pub(crate) const RANK_WEIGHTS_SQL: &str =
+    "(SELECT ARRAY[readme, description, tags, name]::FLOAT4[] FROM search_field_weights)";
+
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct SearchWeights {
+    /// Label A
+    pub name: f32,
+    /// Label B, shared with the category
+    pub tags: f32,
+    /// Label C
+    pub description: f32,
+    /// Label D; READMEs of contracts with `index_readme` off aren't indexed
+    pub readme: f32,
+    pub updated_at: DateTime<Utc>,
+    /// Admin who last changed them; `null` for the defaults
+    pub updated_by: Option<String>,
+}
+
+/// Weights to change, each between 0 and 1; the others are kept
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateSearchWeightsRequest {
+    pub name: Option<f32>,
+    pub tags: Option<f32>,
+    pub description: Option<f32>,
+    pub readme: Option<f32>,
+}
+
+impl UpdateSearchWeightsRequest {
+    fn validate(&self) -> ApiResult<()> {
+        let fields = [
+            ("name", self.name),
+            ("tags", self.tags),
+            ("description", self.description),
+            ("readme", self.readme),
+        ];
+        if fields.iter().all(|(_, weight)| weight.is_none()) {
+            return Err(ApiError::bad_request(
+                "InvalidRequest",
+                "At least one weight must be provided",
+            ));
+        }
+        for (field, weight) in fields {
+            // Postgres refuses ranking weights above 1
+            if weight.is_some_and(|weight| !(0.0..=1.0).contains(&weight)) {
+                return Err(ApiError::validation(field, "Weights are between 0 and 1"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/search/weights",
+    responses(
+        (status = 200, description = "Weights in effect", body = SearchWeights),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Search"
+)]
+pub async fn get_search_weights(State(state): State<AppState>) -> ApiResult<Json<SearchWeights>> {
+    let weights = sqlx::query_as(
+        "SELECT name, tags, description, readme, updated_at, updated_by
+         FROM search_field_weights",
+    )
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch search weights", err))?;
+    Ok(Json(weights))
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/admin/search/weights",
+    request_body = UpdateSearchWeightsRequest,
+    responses(
+        (status = 200, description = "Weights now in effect", body = SearchWeights),
+        (status = 400, description = "No weight given, or one outside 0 to 1"),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Search"
+)]
+pub async fn update_search_weights(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<UpdateSearchWeightsRequest>,
+) -> ApiResult<Json<SearchWeights>> {
+    req.validate()?;
+    let weights: SearchWeights = sqlx::query_as(
+        "UPDATE search_field_weights
+            SET name = COALESCE($1, name),
+                tags = COALESCE($2, tags),
+                description = COALESCE($3, description),
+                readme = COALESCE($4, readme),
+                updated_at = NOW(),
+                updated_by = $5
+          RETURNING name, tags, description, readme, updated_at, updated_by",
+    )
+    .bind(req.name)
+    .bind(req.tags)
+    .bind(req.description)
+    .bind(req.readme)
+    .bind(&claims.sub)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("update search weights", err))?;
+    tracing::warn!(
+        name = weights.name,
+        tags = weights.tags,
+        description = weights.description,
+        readme = weights.readme,
+        admin = %claims.sub,
+        "Search weights changed"
+    );
+
+    Ok(Json(weights))
+}
@@ -0,0 +1,215 @@
+//! Graceful shutdown.
+//!
+//! On SIGTERM or SIGINT the server:
+//!
+//! 1. sets the shared draining flag, so `/health/ready` and any new request
+//!    get a 503 and load balancers stop routing traffic here;
+//! 2. stops accepting connections and waits for in-flight requests;
+//! 3. cancels background tasks, which finish their current unit of work and
+//!    exit.
+//!
+//! Both waits share one budget of `SHUTDOWN_TIMEOUT` seconds (default 30).
+//! `main` closes the database pool only after both have finished.
+
+use std::future::{Future, IntoFuture};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use axum::Router;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+use crate::error::ApiError;
+
+const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 30;
+
+pub struct Shutdown {
+    draining: Arc<AtomicBool>,
+    drain_timeout: Duration,
+    token: CancellationToken,
+    tasks: TaskTracker,
+    started_at: OnceLock<Instant>,
+}
+
+impl Shutdown {
+    pub fn new(draining: Arc<AtomicBool>, drain_timeout: Duration) -> Self {
+        Self {
+            draining,
+            drain_timeout,
+            token: CancellationToken::new(),
+            tasks: TaskTracker::new(),
+            started_at: OnceLock::new(),
+        }
+    }
+
+    /// Reads the drain timeout from `SHUTDOWN_TIMEOUT`.
+    pub fn from_env(draining: Arc<AtomicBool>) -> Self {
+        let drain_timeout = match std::env::var("SHUTDOWN_TIMEOUT") {
+            Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!(
+                    "Invalid value for SHUTDOWN_TIMEOUT (`{raw}`), using default {DEFAULT_DRAIN_TIMEOUT_SECS}"
+                );
+                DEFAULT_DRAIN_TIMEOUT_SECS
+            }),
+            Err(_) => DEFAULT_DRAIN_TIMEOUT_SECS,
+        };
+        Self::new(draining, Duration::from_secs(drain_timeout))
+    }
+
+    /// Cancelled once in-flight requests have drained. Background loops
+    /// should check it between units of work, never in the middle of one.
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Spawns a background task that shutdown waits for before the pool is
+    /// closed.
+    pub fn spawn<F>(&self, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.spawn(task);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    fn begin(&self) {
+        self.started_at.get_or_init(Instant::now);
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    fn deadline(&self) -> Instant {
+        *self.started_at.get_or_init(Instant::now) + self.drain_timeout
+    }
+
+    /// Serves `app` until `signal` resolves, then drains in-flight requests.
+    /// Returns `false` if requests were still running at the deadline.
+    pub async fn serve(
+        &self,
+        listener: TcpListener,
+        app: Router,
+        signal: impl Future<Output = ()> + Send + 'static,
+    ) -> std::io::Result<bool> {
+        let signalled = CancellationToken::new();
+        let stop_accepting = signalled.clone();
+        let server = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(async move { stop_accepting.cancelled().await });
+        let mut server = std::pin::pin!(server.into_future());
+
+        tokio::select! {
+            result = &mut server => return result.map(|_| true),
+            _ = signal => {}
+        }
+
+        self.begin();
+        tracing::info!(
+            in_flight = crate::metrics::HTTP_IN_FLIGHT.get(),
+            "Shutdown signal received; failing readiness and draining in-flight requests"
+        );
+        signalled.cancel();
+
+        match tokio::time::timeout_at(self.deadline().into(), server).await {
+            Ok(result) => {
+                result?;
+                tracing::info!(
+                    elapsed_ms = self.elapsed_ms(),
+                    "All in-flight requests completed"
+                );
+                Ok(true)
+            }
+            Err(_) => {
+                tracing::error!(
+                    in_flight = crate::metrics::HTTP_IN_FLIGHT.get(),
+                    timeout_secs = self.drain_timeout.as_secs(),
+                    "Drain timeout reached with requests still in flight"
+                );
+                Ok(false)
+            }
+        }
+    }
+
+    /// Cancels background tasks and waits for them within what is left of
+    /// the drain timeout. Returns `false` if any were still running.
+    pub async fn stop_background_tasks(&self) -> bool {
+        self.begin();
+        self.token.cancel();
+        self.tasks.close();
+
+        let running = self.tasks.len();
+        if running > 0 {
+            tracing::info!(running, "Waiting for background tasks to finish");
+        }
+        match tokio::time::timeout_at(self.deadline().into(), self.tasks.wait()).await {
+            Ok(()) => true,
+            Err(_) => {
+                tracing::error!(
+                    running = self.tasks.len(),
+                    "Drain timeout reached with background tasks still running"
+                );
+                false
+            }
+        }
+    }
+
+    pub fn elapsed_ms(&self) -> u128 {
+        self.started_at
+            .get()
+            .map(|started| started.elapsed().as_millis())
+            .unwrap_or(0)
+    }
+}
+
+/// Resolves on SIGINT, or SIGTERM on unix.
+pub async fn termination_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install signal handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Rejects requests with 503 once draining has begun and counts the rest in
+/// `HTTP_IN_FLIGHT`.
+pub async fn track_in_flight(
+    State(draining): State<Arc<AtomicBool>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if draining.load(Ordering::SeqCst) {
+        return Err(ApiError::service_unavailable(
+            "SHUTTING_DOWN",
+            "Service is shutting down and temporarily unavailable",
+        ));
+    }
+    crate::metrics::HTTP_IN_FLIGHT.inc();
+    let res = next.run(req).await;
+    crate::metrics::HTTP_IN_FLIGHT.dec();
+    Ok(res)
+}
@@ -615,8 +615,7 @@ fn parse_uuid(id: &str, label: &str) -> Result<Uuid, ApiError> {
 }
 
 fn db_err(operation: &str, err: sqlx::Error) -> ApiError {
-    tracing::error!(operation = operation, error = ?err, "database operation failed");
-    ApiError::internal("An unexpected database error occurred")
+    ApiError::from_sqlx(operation, err)
 }
 
 #[cfg(test)]
@@ -0,0 +1,149 @@
+//! Logging and counting of slow SQL statements.
+//!
+//! sqlx reports every statement it runs as a `sqlx::query` tracing event
+//! carrying its elapsed time. [`layer`] wraps those reports: a statement that
+//! took longer than the `slow_query_ms` setting (`SLOW_QUERY_THRESHOLD_MS`,
+//! default 500, tunable at runtime, see [`crate::feature_flags`]) is logged
+//! at WARN with the route and request id of the request that ran it, and
+//! counted in `db_slow_queries_total{route}` on `/metrics`. Statements run
+//! outside a request, by background tasks, are labelled `background`.
+//!
+//! The threshold is read from the flags handed to [`watch`] once the app
+//! state exists; statements run before that, by migrations, aren't checked.
+
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use tracing_subscriber::{filter::filter_fn, layer::Context, registry::LookupSpan, Layer};
+
+use crate::feature_flags::FeatureFlags;
+
+/// Target sqlx reports statements under
+const SQLX_TARGET: &str = "sqlx::query";
+/// Route label for statements run outside a request
+const BACKGROUND: &str = "background";
+
+static FLAGS: OnceLock<Arc<FeatureFlags>> = OnceLock::new();
+
+/// Starts checking statements against `flags.slow_query_ms`
+pub fn watch(flags: Arc<FeatureFlags>) {
+    let _ = FLAGS.set(flags);
+}
+
+/// Layer for [`crate::request_tracing::init_tracing`], seeing only sqlx's
+/// statement reports
+pub fn layer<S>() -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    SlowQueryLayer.with_filter(filter_fn(|meta| meta.target() == SQLX_TARGET))
+}
+
+pub struct SlowQueryLayer;
+
+impl<S: Subscriber> Layer<S> for SlowQueryLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let Some(flags) = FLAGS.get() else {
+            return;
+        };
+        let mut statement = StatementReport::default();
+        event.record(&mut statement);
+        let threshold_ms = flags.slow_query_ms.get();
+        let Some(elapsed_ms) = statement.elapsed_ms() else {
+            return;
+        };
+        if elapsed_ms < threshold_ms as f64 {
+            return;
+        }
+
+        let route = crate::request_tracing::current_route()
+            .filter(|route| !route.is_empty())
+            .unwrap_or_else(|| BACKGROUND.to_string());
+        crate::metrics::DB_SLOW_QUERIES
+            .with_label_values(&[&route])
+            .inc();
+        tracing::warn!(
+            request_id = crate::request_tracing::current_request_id().as_deref(),
+            route = %route,
+            elapsed_ms = elapsed_ms as u64,
+            threshold_ms,
+            statement = %statement.summary,
+            "Slow query"
+        );
+    }
+}
+
+/// The fields of a sqlx statement report this module uses
+#[derive(Debug, Default)]
+struct StatementReport {
+    /// First words of the statement
+    summary: String,
+    elapsed_secs: Option<f64>,
+}
+
+impl StatementReport {
+    fn elapsed_ms(&self) -> Option<f64> {
+        self.elapsed_secs.map(|secs| secs * 1000.0)
+    }
+}
+
+impl Visit for StatementReport {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        if field.name() == "elapsed_secs" {
+            self.elapsed_secs = Some(value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "summary" {
+            self.summary = value.to_string();
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "summary" {
+            self.summary = format!("{:?}", value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn reads_the_statement_report() {
+        let last = Arc::new(Mutex::new(None));
+        let subscriber = tracing_subscriber::registry().with(RecordLast(last.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            let summary = String::from("select * from contracts");
+            tracing::event!(
+                target: "sqlx::query",
+                tracing::Level::DEBUG,
+                summary,
+                elapsed_secs = 0.75,
+            );
+        });
+
+        let report = last.lock().unwrap().take().unwrap();
+        assert_eq!(report.summary, "select * from contracts");
+        assert_eq!(report.elapsed_ms(), Some(750.0));
+    }
+
+    /// Keeps the last statement report, as the slow-query layer reads it
+    struct RecordLast(Arc<Mutex<Option<StatementReport>>>);
+
+    impl<S: Subscriber> Layer<S> for RecordLast {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            let mut report = StatementReport::default();
+            event.record(&mut report);
+            *self.0.lock().unwrap() = Some(report);
+        }
+    }
+}
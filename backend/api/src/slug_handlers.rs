@@ -0,0 +1,383 @@
+//! Contract names (`namespace/slug`) and resolving them to contracts.
+//!
+//! Slugs are set at publish time and can be changed through the metadata
+//! endpoint. A changed slug leaves a redirect behind, so names that were
+//! handed out keep resolving to the same contract.
+
+use crate::{
+    auth::AuthClaims,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    org_handlers,
+    state::AppState,
+};
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::json;
+use shared::{
+    slug::{ContractName, ResolvedContract},
+    Contract, ContractVersion, Network, OrganizationRole, SemVer, VisibilityType,
+};
+use sqlx::PgExecutor;
+use uuid::Uuid;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ResolveQuery {
+    /// `namespace/slug`, optionally followed by `@version`
+    pub name: String,
+    /// Version to resolve; overrides one given in `name`
+    pub version: Option<String>,
+    /// Required when the name exists on more than one network
+    pub network: Option<Network>,
+}
+
+/// The namespace a contract published by `publisher_id`, optionally under
+/// `organization_id`, is named in
+pub async fn contract_namespace<'e>(
+    executor: impl PgExecutor<'e>,
+    publisher_id: Uuid,
+    organization_id: Option<Uuid>,
+) -> ApiResult<String> {
+    sqlx::query_scalar::<_, Option<String>>("SELECT contract_namespace($1, $2)")
+        .bind(publisher_id)
+        .bind(organization_id)
+        .fetch_one(executor)
+        .await
+        .map_err(|err| db_internal_error("get contract namespace", err))?
+        .ok_or_else(|| {
+            ApiError::not_found(
+                "PublisherNotFound",
+                format!("No publisher found with ID: {}", publisher_id),
+            )
+        })
+}
+
+#[derive(sqlx::FromRow)]
+struct SlugOwner {
+    id: Uuid,
+    contract_id: String,
+    publisher: String,
+    organization: Option<String>,
+    redirect: bool,
+}
+
+/// Fails with 409 when `namespace/slug` already names a contract other than
+/// `except` on `network`, currently or as an old name. The error details
+/// say which contract and who owns it.
+pub async fn ensure_slug_available(
+    pool: &sqlx::PgPool,
+    namespace: &str,
+    slug: &str,
+    network: &Network,
+    except: Option<Uuid>,
+) -> ApiResult<()> {
+    let owner: Option<SlugOwner> = sqlx::query_as(
+        "SELECT c.id, c.contract_id, p.stellar_address AS publisher, o.slug AS organization,
+                owned.redirect
+           FROM (
+                SELECT id AS contract, FALSE AS redirect FROM contracts
+                 WHERE namespace = $1 AND slug = $2 AND network = $3
+                UNION ALL
+                SELECT contract_id, TRUE FROM contract_slug_redirects
+                 WHERE namespace = $1 AND slug = $2 AND network = $3
+           ) owned
+           JOIN contracts c ON c.id = owned.contract
+           JOIN publishers p ON p.id = c.publisher_id
+           LEFT JOIN organizations o ON o.id = c.organization_id
+          WHERE c.id IS DISTINCT FROM $4
+          LIMIT 1",
+    )
+    .bind(namespace)
+    .bind(slug)
+    .bind(network)
+    .bind(except)
+    .fetch_optional(pool)
+    .await
+    .map_err(|err| db_internal_error("check slug availability", err))?;
+
+    let Some(owner) = owner else {
+        return Ok(());
+    };
+    let name = format!("{}/{}", namespace, slug);
+    let message = if owner.redirect {
+        format!(
+            "{} is a former name of contract {} on {} and still resolves to it",
+            name, owner.contract_id, network
+        )
+    } else {
+        format!(
+            "{} is already taken by contract {} on {}",
+            name, owner.contract_id, network
+        )
+    };
+    Err(
+        ApiError::conflict("SlugTaken", message).with_details(json!({
+            "name": name,
+            "network": network.to_string(),
+            "owner": {
+                "id": owner.id,
+                "contract_id": owner.contract_id,
+                "publisher": owner.publisher,
+                "organization": owner.organization,
+            },
+        })),
+    )
+}
+
+/// Keeps `before`'s name resolving to it after its slug changed to that of
+/// `after`, and drops any redirect the new name had.
+pub async fn keep_redirect(
+    pool: &sqlx::PgPool,
+    before: &Contract,
+    after: &Contract,
+) -> ApiResult<()> {
+    let (Some(namespace), Some(old_slug)) = (&before.namespace, &before.slug) else {
+        return Ok(());
+    };
+    let mut tx = pool
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin_transaction", err))?;
+
+    sqlx::query(
+        "DELETE FROM contract_slug_redirects
+          WHERE namespace = $1 AND slug = $2 AND network = $3",
+    )
+    .bind(namespace)
+    .bind(&after.slug)
+    .bind(&after.network)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("drop slug redirect", err))?;
+
+    sqlx::query(
+        "INSERT INTO contract_slug_redirects (namespace, slug, network, contract_id)
+         VALUES ($1, $2, $3, $4)
+         ON CONFLICT (namespace, slug, network) DO UPDATE SET contract_id = EXCLUDED.contract_id",
+    )
+    .bind(namespace)
+    .bind(old_slug)
+    .bind(&before.network)
+    .bind(before.id)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("record slug redirect", err))?;
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit_transaction", err))
+}
+
+/// The version to resolve to when none is asked for: the highest stable
+/// semantic version, else the highest pre-release, else the newest.
+fn latest_version(versions: Vec<ContractVersion>) -> Option<ContractVersion> {
+    versions.into_iter().max_by_key(|version| {
+        let parsed = SemVer::parse(&version.version);
+        let stable = parsed.as_ref().map(|semver| semver.pre_release.is_none());
+        (stable, parsed, version.created_at)
+    })
+}
+
+/// Resolve a contract name to a contract and version
+#[utoipa::path(
+    get,
+    path = "/api/resolve",
+    params(ResolveQuery),
+    responses(
+        (status = 200, description = "Contract and version the name refers to", body = ResolvedContract),
+        (status = 400, description = "Invalid name, or the name exists on several networks"),
+        (status = 403, description = "The contract is private"),
+        (status = 404, description = "No contract or version with that name")
+    ),
+    tag = "Contracts"
+)]
+pub async fn resolve_contract(
+    State(state): State<AppState>,
+    claims: Option<AuthClaims>,
+    Query(query): Query<ResolveQuery>,
+) -> ApiResult<Json<ResolvedContract>> {
+    let name = ContractName::parse(&query.name).ok_or_else(|| {
+        ApiError::bad_request(
+            "InvalidContractName",
+            format!(
+                "'{}' is not a contract name; expected namespace/slug or namespace/slug@version",
+                query.name
+            ),
+        )
+    })?;
+    let requested_version = query.version.clone().or(name.version.clone());
+
+    let mut redirected = false;
+    let mut contracts: Vec<Contract> = sqlx::query_as(
+        "SELECT * FROM contracts
+          WHERE namespace = $1 AND slug = $2 AND ($3::network_type IS NULL OR network = $3)
+          ORDER BY network",
+    )
+    .bind(&name.namespace)
+    .bind(&name.slug)
+    .bind(&query.network)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("resolve contract name", err))?;
+    if contracts.is_empty() {
+        redirected = true;
+        contracts = sqlx::query_as(
+            "SELECT c.* FROM contract_slug_redirects r
+               JOIN contracts c ON c.id = r.contract_id
+              WHERE r.namespace = $1 AND r.slug = $2 AND ($3::network_type IS NULL OR r.network = $3)
+              ORDER BY c.network",
+        )
+        .bind(&name.namespace)
+        .bind(&name.slug)
+        .bind(&query.network)
+        .fetch_all(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("resolve contract redirect", err))?;
+    }
+
+    let contract = match contracts.len() {
+        0 => {
+            return Err(ApiError::not_found(
+                "ContractNotFound",
+                format!("No contract is named {}", name.qualified()),
+            ))
+        }
+        1 => contracts.remove(0),
+        _ => {
+            let networks: Vec<String> = contracts.iter().map(|c| c.network.to_string()).collect();
+            return Err(ApiError::bad_request(
+                "AmbiguousContractName",
+                format!(
+                    "{} exists on {}; pass network to pick one",
+                    name.qualified(),
+                    networks.join(", ")
+                ),
+            )
+            .with_details(json!({ "networks": networks })));
+        }
+    };
+
+    if contract.visibility == VisibilityType::Private {
+        let is_member = match (&claims, contract.organization_id) {
+            (Some(claims), Some(org_id)) => org_handlers::check_org_role(
+                &state.db,
+                org_id,
+                &claims.sub,
+                OrganizationRole::Viewer,
+            )
+            .await
+            .is_ok(),
+            _ => false,
+        };
+        if !is_member {
+            return Err(ApiError::forbidden(
+                "This contract is private and you do not have access to it",
+            ));
+        }
+    }
+
+    let version = match &requested_version {
+        Some(requested) => {
+            let version: Option<ContractVersion> = sqlx::query_as(
+                "SELECT * FROM contract_versions WHERE contract_id = $1 AND version = $2",
+            )
+            .bind(contract.id)
+            .bind(requested)
+            .fetch_optional(state.read_pool())
+            .await
+            .map_err(|err| db_internal_error("resolve contract version", err))?;
+            Some(version.ok_or_else(|| {
+                ApiError::not_found(
+                    "VersionNotFound",
+                    format!("{} has no version {}", name.qualified(), requested),
+                )
+            })?)
+        }
+        None => {
+            let versions: Vec<ContractVersion> =
+                sqlx::query_as("SELECT * FROM contract_versions WHERE contract_id = $1")
+                    .bind(contract.id)
+                    .fetch_all(state.read_pool())
+                    .await
+                    .map_err(|err| db_internal_error("get contract versions", err))?;
+            latest_version(versions)
+        }
+    };
+
+    Ok(Json(ResolvedContract {
+        id: contract.id,
+        contract_id: contract.contract_id,
+        network: contract.network,
+        name: format!(
+            "{}/{}",
+            contract.namespace.unwrap_or_default(),
+            contract.slug.unwrap_or_default()
+        ),
+        redirected_from: redirected.then(|| name.qualified()),
+        version,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn version(version: &str, day: u32) -> ContractVersion {
+        ContractVersion {
+            id: Uuid::new_v4(),
+            contract_id: Uuid::nil(),
+            version: version.to_string(),
+            wasm_hash: "00".to_string(),
+            source_url: None,
+            commit_hash: None,
+            release_notes: None,
+            created_at: Utc.with_ymd_and_hms(2026, 1, day, 0, 0, 0).unwrap(),
+            state_schema: None,
+            signature: None,
+            publisher_key: None,
+            signature_algorithm: None,
+            signed_at: None,
+            signature_nonce: None,
+            wasm_meta: None,
+        }
+    }
+
+    fn latest(versions: &[(&str, u32)]) -> Option<String> {
+        latest_version(
+            versions
+                .iter()
+                .map(|&(number, day)| version(number, day))
+                .collect(),
+        )
+        .map(|version| version.version)
+    }
+
+    #[test]
+    fn latest_is_the_highest_stable_version_not_the_newest() {
+        assert_eq!(
+            latest(&[("1.10.0", 1), ("1.2.0", 3), ("1.9.3", 2)]).as_deref(),
+            Some("1.10.0")
+        );
+        assert_eq!(
+            latest(&[("1.2.0", 1), ("2.0.0-beta.1", 2)]).as_deref(),
+            Some("1.2.0")
+        );
+    }
+
+    #[test]
+    fn pre_releases_and_free_form_versions_are_fallbacks() {
+        assert_eq!(
+            latest(&[("2.0.0-beta.1", 1), ("nightly", 2)]).as_deref(),
+            Some("2.0.0-beta.1")
+        );
+        assert_eq!(
+            latest(&[("alpha", 1), ("beta", 2)]).as_deref(),
+            Some("beta")
+        );
+        assert_eq!(latest(&[]), None);
+    }
+}
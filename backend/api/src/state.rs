@@ -1,13 +1,21 @@
 use crate::auth::AuthManager;
 use crate::cache::{CacheConfig, CacheLayer};
+use crate::component_health::ComponentHealth;
+use crate::config::Config;
+use crate::feature_flags::FeatureFlags;
 use crate::health_monitor::HealthMonitorStatus;
+use crate::outbound_http::OutboundClient;
+use crate::rate_limit::CallerLimiter;
+use crate::read_replica::{self, ReadReplica};
+use crate::replay_guard::ReplayGuard;
 use crate::resource_tracking::ResourceManager;
 use prometheus::Registry;
 use shared::error::RegistryError;
+use shared::source_storage::SourceStorage;
 use sqlx::PgPool;
 use std::sync::atomic::AtomicBool;
 use std::sync::{Arc, RwLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
 
 #[derive(Clone, Debug, serde::Serialize)]
@@ -37,7 +45,12 @@ pub enum RealtimeEvent {
 /// Application state shared across handlers
 #[derive(Clone)]
 pub struct AppState {
+    /// Primary pool; all writes and read-after-write queries go here
     pub db: PgPool,
+    /// Validated startup configuration, see [`crate::config`]
+    pub config: Arc<Config>,
+    /// Optional replica for heavy reads, see [`AppState::read_pool`]
+    pub read_db: Option<ReadReplica>,
     pub started_at: Instant,
     pub cache: Arc<CacheLayer>,
     pub registry: Registry,
@@ -48,25 +61,51 @@ pub struct AppState {
     pub resource_mgr: Arc<RwLock<ResourceManager>>,
     pub event_broadcaster: broadcast::Sender<RealtimeEvent>,
     pub contract_events: Arc<ContractEventHub>,
+    /// Shared client for webhooks and RPC calls, see [`crate::outbound_http`]
+    pub outbound: OutboundClient,
+    /// Runtime feature flags, see [`crate::feature_flags`]
+    pub flags: Arc<FeatureFlags>,
+    /// Components with an open incident, see [`crate::component_health`]
+    pub component_health: Arc<ComponentHealth>,
+    /// Blob storage for uploaded WASM modules and sources, see
+    /// [`shared::blob_store`]
+    pub source_storage: SourceStorage,
+    /// Seen nonces of signed publishes, see [`crate::replay_guard`]
+    pub replay_guard: Arc<ReplayGuard>,
+    /// Recent `POST /api/webhooks/test` sends per caller
+    pub webhook_test_sends: Arc<CallerLimiter>,
 }
 
 impl AppState {
     pub async fn new(
+        config: Arc<Config>,
         db: PgPool,
         registry: Registry,
         job_engine: Arc<soroban_batch::engine::JobEngine>,
         is_shutting_down: Arc<AtomicBool>,
     ) -> Result<Self, shared::error::RegistryError> {
-        let config = CacheConfig::from_env();
-        let auth_mgr = Arc::new(RwLock::new(
-            AuthManager::from_env().expect("JWT config validated at startup"),
-        ));
+        let cache_config = CacheConfig::from_env();
+        let auth_mgr = Arc::new(RwLock::new(AuthManager::new(
+            config.jwt_secret.expose().to_string(),
+        )));
         let resource_mgr = Arc::new(RwLock::new(ResourceManager::new()));
         let (event_broadcaster, _) = broadcast::channel(100);
+        let source_storage = SourceStorage::new().await?;
+        let replay_guard = Arc::new(ReplayGuard::connect(config.replay.clone()).await);
+        let flags = Arc::new(FeatureFlags::from_config(&config));
+        let component_health = Arc::new(ComponentHealth::new(
+            config.status_page_url.clone(),
+            flags.clone(),
+        ));
+        let webhook_test_sends = Arc::new(CallerLimiter::new(
+            config.webhook_test_sends_per_minute,
+            Duration::from_secs(60),
+        ));
         Ok(Self {
             db,
+            read_db: None,
             started_at: Instant::now(),
-            cache: Arc::new(CacheLayer::new(config).await),
+            cache: Arc::new(CacheLayer::new(cache_config).await),
             registry,
             job_engine,
             is_shutting_down,
@@ -74,6 +113,24 @@ impl AppState {
             auth_mgr,
             resource_mgr,
             event_broadcaster,
+            outbound: OutboundClient::new(config.outbound.clone()),
+            flags,
+            component_health,
+            source_storage,
+            replay_guard,
+            webhook_test_sends,
+            config,
         })
     }
+
+    pub fn with_read_replica(mut self, replica: ReadReplica) -> Self {
+        self.read_db = Some(replica);
+        self
+    }
+
+    /// Pool for read-only listing, search and analytics queries: the replica
+    /// when the current request was routed to it, otherwise the primary.
+    pub fn read_pool(&self) -> &PgPool {
+        read_replica::select(&self.db, self.read_db.as_ref())
+    }
 }
@@ -0,0 +1,593 @@
+//! Contract event subscriptions, owned by the caller's API token subject.
+//!
+//!   POST   /api/contracts/:id/subscriptions  – subscribe, or change the events of an existing subscription
+//!   GET    /api/contracts/:id/subscriptions  – the caller's subscriptions to the contract
+//!   DELETE /api/contracts/:id/subscriptions  – unsubscribe from the contract
+//!   GET    /api/me/subscriptions             – all of the caller's subscriptions with recent deliveries
+//!   DELETE /api/me/subscriptions/:id         – remove one subscription
+
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use sqlx::{FromRow, Row};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    channel_handlers::{fetch_owned_channel, register_channel},
+    error::{ApiError, ApiResult},
+    handlers::{db_internal_error, fetch_contract_identity},
+    org_handlers,
+    state::AppState,
+    subscription_models::{
+        ContractSubscription, CreateSubscriptionRequest, SubscriptionDelivery, SubscriptionDetail,
+    },
+    webhook_events,
+};
+
+/// Subscriptions one subscriber may hold across all contracts
+const MAX_SUBSCRIPTIONS_PER_SUBSCRIBER: i64 = 100;
+/// Deliveries shown per subscription in `GET /api/me/subscriptions`
+const RECENT_DELIVERIES: i64 = 5;
+
+/// Where a subscription request asks for deliveries to go
+#[derive(Debug, PartialEq, Eq)]
+enum Destination {
+    /// One of the caller's registered channels
+    Channel(Uuid),
+    /// A webhook URL or email address, registered as a channel if needed
+    Address { kind: &'static str, target: String },
+}
+
+/// Checks a subscription request, returning its event types without
+/// duplicates and where to deliver them.
+fn validate_request(req: &CreateSubscriptionRequest) -> ApiResult<(Vec<String>, Destination)> {
+    if req.events.is_empty() {
+        return Err(ApiError::bad_request(
+            "InvalidSubscription",
+            "events must name at least one of: version, verification, incident",
+        ));
+    }
+    let mut events: Vec<String> = Vec::with_capacity(req.events.len());
+    for event in &req.events {
+        let event = event.as_str().to_string();
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+
+    let destination = match (
+        req.channel_id,
+        req.webhook_url.as_deref(),
+        req.email.as_deref(),
+    ) {
+        (Some(channel_id), None, None) => Destination::Channel(channel_id),
+        (None, Some(url), None) => Destination::Address {
+            kind: "webhook",
+            target: validate_webhook_url(url.trim())?,
+        },
+        (None, None, Some(email)) => Destination::Address {
+            kind: "email",
+            target: validate_email(email.trim())?,
+        },
+        _ => {
+            return Err(ApiError::bad_request(
+                "InvalidSubscription",
+                "exactly one of channel_id, webhook_url and email is required",
+            ))
+        }
+    };
+
+    if let Some(version) = req.payload_version {
+        validate_payload_version(version, chrono::Utc::now())?;
+    }
+
+    Ok((events, destination))
+}
+
+/// Subscriptions can pin any payload version that is still delivered
+fn validate_payload_version(version: i32, now: chrono::DateTime<chrono::Utc>) -> ApiResult<()> {
+    match webhook_events::payload_version(version) {
+        Some(known) if !known.is_retired(now) => Ok(()),
+        _ => Err(ApiError::bad_request(
+            "UnsupportedPayloadVersion",
+            format!(
+                "payload_version {} is not delivered; see GET /api/webhooks/events",
+                version
+            ),
+        )),
+    }
+}
+
+pub(crate) fn validate_webhook_url(url: &str) -> ApiResult<String> {
+    let parsed = reqwest::Url::parse(url).map_err(|err| {
+        ApiError::bad_request("InvalidWebhookUrl", format!("invalid webhook_url: {}", err))
+    })?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none() {
+        return Err(ApiError::bad_request(
+            "InvalidWebhookUrl",
+            "webhook_url must be an http or https URL",
+        ));
+    }
+    Ok(parsed.to_string())
+}
+
+/// Refuses a webhook URL whose host resolves to a loopback, private or
+/// otherwise non-public address, see [`crate::outbound_http`]
+pub(crate) async fn ensure_public_webhook(state: &AppState, url: &str) -> ApiResult<()> {
+    let parsed = reqwest::Url::parse(url).map_err(|err| {
+        ApiError::bad_request("InvalidWebhookUrl", format!("invalid webhook_url: {}", err))
+    })?;
+    state
+        .outbound
+        .check_webhook_target(&parsed)
+        .await
+        .map_err(|err| {
+            ApiError::bad_request(
+                "InvalidWebhookUrl",
+                format!("webhook_url must point at a public address: {}", err),
+            )
+        })
+}
+
+/// Accepts a bare `local@domain` address. Anything that could break out of
+/// the SMTP envelope (whitespace, angle brackets, commas) is rejected.
+pub(crate) fn validate_email(email: &str) -> ApiResult<String> {
+    let valid = email.len() <= 254
+        && !email
+            .chars()
+            .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '<' | '>' | ',' | ';'))
+        && match email.split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain.contains('.')
+                    && !domain.starts_with('.')
+                    && !domain.ends_with('.')
+            }
+            None => false,
+        };
+
+    if valid {
+        Ok(email.to_string())
+    } else {
+        Err(ApiError::bad_request(
+            "InvalidEmail",
+            format!("'{}' is not a valid email address", email),
+        ))
+    }
+}
+
+/// Resolves the contract in the path, refusing private contracts outside
+/// the caller's organizations
+async fn subscribable_contract(state: &AppState, id: &str, claims: &AuthClaims) -> ApiResult<Uuid> {
+    let (contract_uuid, _) = fetch_contract_identity(state, id).await?;
+
+    let (visibility, organization_id): (shared::VisibilityType, Option<Uuid>) =
+        sqlx::query_as("SELECT visibility, organization_id FROM contracts WHERE id = $1")
+            .bind(contract_uuid)
+            .fetch_one(&state.db)
+            .await
+            .map_err(|err| db_internal_error("fetch contract visibility", err))?;
+
+    if visibility == shared::VisibilityType::Private {
+        let is_member = match organization_id {
+            Some(org_id) => org_handlers::check_org_role(
+                &state.db,
+                org_id,
+                &claims.sub,
+                shared::OrganizationRole::Viewer,
+            )
+            .await
+            .is_ok(),
+            None => false,
+        };
+        if !is_member {
+            return Err(ApiError::forbidden(
+                "This contract is private and you do not have access to it",
+            ));
+        }
+    }
+
+    Ok(contract_uuid)
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/contracts/{id}/subscriptions",
+    params(("id" = String, Path, description = "Contract UUID or on-chain contract ID")),
+    request_body = CreateSubscriptionRequest,
+    responses(
+        (status = 201, description = "Subscription created", body = ContractSubscription),
+        (status = 200, description = "Existing subscription to the same target updated with the new events", body = ContractSubscription),
+        (status = 400, description = "No events, not exactly one valid channel_id/webhook_url/email, a webhook_url without a public address, or a payload_version that is not delivered"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 403, description = "Private contract outside the caller's organizations"),
+        (status = 404, description = "Contract or channel not found"),
+        (status = 409, description = "Subscription or channel limit reached, or the channel is disabled")
+    ),
+    tag = "Subscriptions"
+)]
+pub async fn create_subscription(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+    Json(req): Json<CreateSubscriptionRequest>,
+) -> ApiResult<(StatusCode, Json<ContractSubscription>)> {
+    let (events, destination) = validate_request(&req)?;
+    let contract_id = subscribable_contract(&state, &id, &claims).await?;
+
+    let channel = match destination {
+        Destination::Channel(channel_id) => {
+            let channel = fetch_owned_channel(&state, channel_id, &claims).await?;
+            if channel.status == "disabled" {
+                return Err(ApiError::conflict(
+                    "ChannelDisabled",
+                    format!(
+                        "Channel {} is disabled; re-enable and verify it first",
+                        channel_id
+                    ),
+                ));
+            }
+            channel
+        }
+        Destination::Address { kind, target } => {
+            if kind == "webhook" {
+                ensure_public_webhook(&state, &target).await?;
+            }
+            register_channel(&state, &claims, kind, &target, None)
+                .await?
+                .0
+        }
+    };
+
+    let held: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM contract_subscriptions
+         WHERE subscriber = $1
+           AND NOT (contract_id = $2 AND channel = $3 AND target = $4)",
+    )
+    .bind(&claims.sub)
+    .bind(contract_id)
+    .bind(&channel.kind)
+    .bind(&channel.target)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count subscriptions", err))?;
+    if held >= MAX_SUBSCRIPTIONS_PER_SUBSCRIBER {
+        return Err(ApiError::conflict(
+            "SubscriptionLimitReached",
+            format!(
+                "A subscriber can hold at most {} subscriptions; remove one first",
+                MAX_SUBSCRIPTIONS_PER_SUBSCRIBER
+            ),
+        ));
+    }
+
+    // `xmax = 0` only for a freshly inserted row, not for one the upsert updated
+    let row = sqlx::query(
+        r#"
+        INSERT INTO contract_subscriptions
+            (subscriber, contract_id, event_types, channel, target, channel_id, payload_version)
+        VALUES ($1, $2, $3, $4, $5, $6, COALESCE($7, $8))
+        ON CONFLICT (subscriber, contract_id, channel, target)
+            DO UPDATE SET event_types = EXCLUDED.event_types,
+                          payload_version = COALESCE($7, contract_subscriptions.payload_version)
+        RETURNING *, (xmax = 0) AS inserted
+        "#,
+    )
+    .bind(&claims.sub)
+    .bind(contract_id)
+    .bind(&events)
+    .bind(&channel.kind)
+    .bind(&channel.target)
+    .bind(channel.id)
+    .bind(req.payload_version)
+    .bind(webhook_events::CURRENT_PAYLOAD_VERSION)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("create subscription", err))?;
+
+    let subscription = ContractSubscription::from_row(&row)
+        .map_err(|err| db_internal_error("read subscription", err))?;
+    let inserted: bool = row
+        .try_get("inserted")
+        .map_err(|err| db_internal_error("read subscription", err))?;
+
+    tracing::info!(
+        subscription_id = %subscription.id,
+        %contract_id,
+        channel_id = %channel.id,
+        events = %events.join(","),
+        payload_version = subscription.payload_version,
+        inserted,
+        "contract subscription saved"
+    );
+
+    let status = if inserted {
+        StatusCode::CREATED
+    } else {
+        StatusCode::OK
+    };
+    Ok((status, Json(subscription)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/subscriptions",
+    params(("id" = String, Path, description = "Contract UUID or on-chain contract ID")),
+    responses(
+        (status = 200, description = "The caller's subscriptions to the contract", body = [ContractSubscription]),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "Contract not found")
+    ),
+    tag = "Subscriptions"
+)]
+pub async fn list_contract_subscriptions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Vec<ContractSubscription>>> {
+    let (contract_id, _) = fetch_contract_identity(&state, &id).await?;
+
+    let subscriptions: Vec<ContractSubscription> = sqlx::query_as(
+        "SELECT * FROM contract_subscriptions
+         WHERE subscriber = $1 AND contract_id = $2
+         ORDER BY created_at",
+    )
+    .bind(&claims.sub)
+    .bind(contract_id)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list contract subscriptions", err))?;
+
+    Ok(Json(subscriptions))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/contracts/{id}/subscriptions",
+    params(("id" = String, Path, description = "Contract UUID or on-chain contract ID")),
+    responses(
+        (status = 204, description = "All of the caller's subscriptions to the contract removed"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "Contract not found, or not subscribed to it")
+    ),
+    tag = "Subscriptions"
+)]
+pub async fn delete_contract_subscriptions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    let (contract_id, _) = fetch_contract_identity(&state, &id).await?;
+
+    let removed = sqlx::query(
+        "DELETE FROM contract_subscriptions WHERE subscriber = $1 AND contract_id = $2",
+    )
+    .bind(&claims.sub)
+    .bind(contract_id)
+    .execute(&state.db)
+    .await
+    .map_err(|err| db_internal_error("delete contract subscriptions", err))?
+    .rows_affected();
+
+    if removed == 0 {
+        return Err(ApiError::not_found(
+            "SubscriptionNotFound",
+            format!("You are not subscribed to contract {}", id),
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/me/subscriptions",
+    responses(
+        (status = 200, description = "The caller's subscriptions, oldest first, each with its latest deliveries", body = [SubscriptionDetail]),
+        (status = 401, description = "Missing or invalid API token")
+    ),
+    tag = "Subscriptions"
+)]
+pub async fn list_my_subscriptions(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+) -> ApiResult<Json<Vec<SubscriptionDetail>>> {
+    let subscriptions: Vec<ContractSubscription> = sqlx::query_as(
+        "SELECT * FROM contract_subscriptions WHERE subscriber = $1 ORDER BY created_at",
+    )
+    .bind(&claims.sub)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list subscriptions", err))?;
+
+    let ids: Vec<Uuid> = subscriptions.iter().map(|s| s.id).collect();
+    let deliveries: Vec<SubscriptionDelivery> = sqlx::query_as(
+        r#"
+        SELECT id, subscription_id, event_type, subject, status, attempts, last_error,
+               next_attempt_at, delivered_at, created_at
+        FROM (
+            SELECT *, ROW_NUMBER() OVER (PARTITION BY subscription_id ORDER BY created_at DESC) AS n
+            FROM contract_subscription_deliveries
+            WHERE subscription_id = ANY($1)
+        ) recent
+        WHERE n <= $2
+        ORDER BY created_at DESC
+        "#,
+    )
+    .bind(&ids)
+    .bind(RECENT_DELIVERIES)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list subscription deliveries", err))?;
+
+    let mut by_subscription: HashMap<Uuid, Vec<SubscriptionDelivery>> = HashMap::new();
+    for delivery in deliveries {
+        by_subscription
+            .entry(delivery.subscription_id)
+            .or_default()
+            .push(delivery);
+    }
+
+    Ok(Json(
+        subscriptions
+            .into_iter()
+            .map(|subscription| SubscriptionDetail {
+                recent_deliveries: by_subscription.remove(&subscription.id).unwrap_or_default(),
+                subscription,
+            })
+            .collect(),
+    ))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/me/subscriptions/{id}",
+    params(("id" = String, Path, description = "Subscription UUID")),
+    responses(
+        (status = 204, description = "Subscription removed"),
+        (status = 400, description = "Malformed subscription ID"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "No such subscription owned by the caller")
+    ),
+    tag = "Subscriptions"
+)]
+pub async fn delete_my_subscription(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    claims: AuthClaims,
+) -> ApiResult<StatusCode> {
+    let subscription_id = Uuid::parse_str(&id).map_err(|_| {
+        ApiError::bad_request(
+            "InvalidSubscriptionId",
+            format!("Invalid subscription ID format: {}", id),
+        )
+    })?;
+
+    let removed =
+        sqlx::query("DELETE FROM contract_subscriptions WHERE id = $1 AND subscriber = $2")
+            .bind(subscription_id)
+            .bind(&claims.sub)
+            .execute(&state.db)
+            .await
+            .map_err(|err| db_internal_error("delete subscription", err))?
+            .rows_affected();
+
+    if removed == 0 {
+        return Err(ApiError::not_found(
+            "SubscriptionNotFound",
+            format!("No subscription found with ID: {}", id),
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::subscription_models::SubscriptionEvent;
+
+    fn request(
+        events: Vec<SubscriptionEvent>,
+        webhook_url: Option<&str>,
+        email: Option<&str>,
+    ) -> CreateSubscriptionRequest {
+        CreateSubscriptionRequest {
+            events,
+            channel_id: None,
+            webhook_url: webhook_url.map(str::to_string),
+            email: email.map(str::to_string),
+            payload_version: None,
+        }
+    }
+
+    #[test]
+    fn events_are_deduplicated_in_order() {
+        let (events, destination) = validate_request(&request(
+            vec![
+                SubscriptionEvent::Incident,
+                SubscriptionEvent::Version,
+                SubscriptionEvent::Incident,
+            ],
+            Some(" https://hooks.example.com/registry "),
+            None,
+        ))
+        .unwrap();
+        assert_eq!(events, vec!["incident", "version"]);
+        assert_eq!(
+            destination,
+            Destination::Address {
+                kind: "webhook",
+                target: "https://hooks.example.com/registry".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn exactly_one_channel_is_required() {
+        let events = vec![SubscriptionEvent::Version];
+        assert!(validate_request(&request(events.clone(), None, None)).is_err());
+        assert!(validate_request(&request(
+            events.clone(),
+            Some("https://a.example.com"),
+            Some("a@example.com")
+        ))
+        .is_err());
+        assert!(validate_request(&request(Vec::new(), None, Some("a@example.com"))).is_err());
+        let (_, destination) =
+            validate_request(&request(events.clone(), None, Some("a@example.com"))).unwrap();
+        assert!(matches!(
+            destination,
+            Destination::Address { kind: "email", .. }
+        ));
+
+        let channel_id = Uuid::new_v4();
+        let mut by_channel = request(events, None, None);
+        by_channel.channel_id = Some(channel_id);
+        assert_eq!(
+            validate_request(&by_channel).unwrap().1,
+            Destination::Channel(channel_id)
+        );
+        by_channel.email = Some("a@example.com".to_string());
+        assert!(validate_request(&by_channel).is_err());
+    }
+
+    #[test]
+    fn only_delivered_payload_versions_can_be_pinned() {
+        let mut req = request(
+            vec![SubscriptionEvent::Version],
+            Some("https://hooks.example.com/registry"),
+            None,
+        );
+        req.payload_version = Some(webhook_events::CURRENT_PAYLOAD_VERSION);
+        assert!(validate_request(&req).is_ok());
+        req.payload_version = Some(99);
+        assert!(validate_request(&req).is_err());
+
+        let sunset = webhook_events::payload_version(1)
+            .and_then(|version| version.sunset_at())
+            .unwrap();
+        assert!(validate_payload_version(1, sunset - chrono::Duration::days(1)).is_ok());
+        assert!(validate_payload_version(1, sunset).is_err());
+    }
+
+    #[test]
+    fn webhook_urls_must_be_http() {
+        assert!(validate_webhook_url("http://localhost:8080/hook").is_ok());
+        assert!(validate_webhook_url("ftp://example.com/hook").is_err());
+        assert!(validate_webhook_url("not a url").is_err());
+    }
+
+    #[test]
+    fn emails_cannot_inject_smtp_commands() {
+        assert!(validate_email("ops@example.com").is_ok());
+        assert!(validate_email("ops@example.com>\r\nRCPT TO:<x@y.z").is_err());
+        assert!(validate_email("a@b@example.com").is_err());
+        assert!(validate_email("ops@localhost").is_err());
+        assert!(validate_email("@example.com").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Per-contract event subscriptions, see [`crate::subscription_notifications`].
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Contract events a subscription can ask for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriptionEvent {
+    /// A new version was published
+    Version,
+    /// The contract passed verification
+    Verification,
+    /// The contract was named in an incident, or that incident was resolved
+    Incident,
+    /// The contract's WASM was built with a toolchain a security advisory
+    /// covers
+    Security,
+    /// The contract has gone quiet and is about to be marked stale
+    Freshness,
+}
+
+impl SubscriptionEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubscriptionEvent::Version => "version",
+            SubscriptionEvent::Verification => "verification",
+            SubscriptionEvent::Incident => "incident",
+            SubscriptionEvent::Security => "security",
+            SubscriptionEvent::Freshness => "freshness",
+        }
+    }
+}
+
+/// A user's subscription to one contract's events
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct ContractSubscription {
+    pub id: Uuid,
+    /// Subject of the API token that created the subscription
+    pub subscriber: String,
+    pub contract_id: Uuid,
+    /// Subscribed events: `version`, `verification`, `incident`,
+    /// `security` and/or `freshness`
+    pub event_types: Vec<String>,
+    /// Channel the subscription delivers through, see `/api/me/channels`
+    pub channel_id: Uuid,
+    pub channel: String, // 'webhook' | 'email'
+    pub target: String,  // Webhook URL or recipient email address
+    /// Webhook payload version deliveries are sent in, see
+    /// `GET /api/webhooks/events`
+    pub payload_version: i32,
+    /// Deliveries that failed in a row; reset by the next successful one
+    pub consecutive_failures: i32,
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Body of `POST /api/contracts/:id/subscriptions`; exactly one of
+/// `channel_id`, `webhook_url` and `email` is required. A webhook URL or
+/// email address not yet registered becomes a new channel, which receives
+/// nothing until it is verified.
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct CreateSubscriptionRequest {
+    pub events: Vec<SubscriptionEvent>,
+    /// One of the caller's channels
+    pub channel_id: Option<Uuid>,
+    /// http(s) URL that receives each event as a JSON POST
+    pub webhook_url: Option<String>,
+    /// Address that receives each event as a plain-text email
+    pub email: Option<String>,
+    /// Webhook payload version to pin; the current one for new
+    /// subscriptions, unchanged for existing ones when omitted
+    pub payload_version: Option<i32>,
+}
+
+/// Delivery state of one event for a subscription
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct SubscriptionDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_type: String,
+    pub subject: String,
+    pub status: String, // 'pending' | 'delivered' | 'failed'
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A subscription with its most recent deliveries, newest first
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SubscriptionDetail {
+    #[serde(flatten)]
+    pub subscription: ContractSubscription,
+    pub recent_deliveries: Vec<SubscriptionDelivery>,
+}
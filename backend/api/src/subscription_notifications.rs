@@ -0,0 +1,774 @@
+//! Delivery of contract events to user subscriptions.
+//!
+//! Publishing a version, passing verification, opening or resolving an
+//! incident, being affected by a security advisory and going quiet for long
+//! enough to be marked stale queue one row per matching subscription in
+//! `contract_subscription_deliveries`, rendered at that moment. A background
+//! task sends them the same way incident notifications are sent: webhooks
+//! through the shared [`OutboundClient`], email through the SMTP relay, with
+//! exponential backoff. Each subscription keeps its own retry state; the
+//! outcome of the last attempt is copied onto the subscription so its owner
+//! can see why deliveries stopped arriving. Deliveries go out only through
+//! verified channels, and each attempt also counts towards its channel's
+//! health, see [`crate::notification_channels`].
+
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, StreamExt};
+use shared::{Contract, ContractVersion};
+use sqlx::{FromRow, PgExecutor, PgPool};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::advisory_models::Advisory;
+use crate::incident_models::{Incident, RenderedNotification};
+use crate::incident_notifications::{
+    send_email, send_webhook_with_status, BASE_BACKOFF_SECS, BATCH_SIZE, DELIVERY_CONCURRENCY,
+    MAX_ATTEMPTS, POLL_INTERVAL,
+};
+use crate::notification_channels::{self, Attempt, ACTIVE_STATUSES, FAILURE_LIMIT};
+use crate::outbound_http::OutboundClient;
+use crate::shutdown::Shutdown;
+use crate::subscription_models::SubscriptionEvent;
+use crate::webhook_events::{self, CURRENT_PAYLOAD_VERSION};
+
+/// The parts of a contract an event notification mentions
+#[derive(Debug, Clone, FromRow)]
+pub struct ContractRef {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub name: String,
+    pub network: String,
+}
+
+impl From<&Contract> for ContractRef {
+    fn from(contract: &Contract) -> Self {
+        Self {
+            id: contract.id,
+            contract_id: contract.contract_id.clone(),
+            name: contract.name.clone(),
+            network: contract.network.to_string(),
+        }
+    }
+}
+
+const CONTRACT_REF_COLUMNS: &str = "id, contract_id, name, network::TEXT AS network";
+
+/// Loads what a notification says about a contract
+pub async fn contract_ref<'e>(
+    executor: impl PgExecutor<'e>,
+    contract_id: Uuid,
+) -> Result<ContractRef, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "SELECT {} FROM contracts WHERE id = $1",
+        CONTRACT_REF_COLUMNS
+    ))
+    .bind(contract_id)
+    .fetch_one(executor)
+    .await
+}
+
+/// One rendered event, ready to be queued for a contract's subscribers
+#[derive(Debug, Clone)]
+pub struct EventNotice {
+    pub event: SubscriptionEvent,
+    /// Identifies the event, so queuing it again for a subscription is a no-op
+    pub key: String,
+    pub notification: RenderedNotification,
+}
+
+/// Queues `notice` for every subscription to `contract_id` that asked for
+/// its event type and delivers through a verified channel. Returns how many
+/// deliveries were queued.
+pub async fn enqueue<'e>(
+    executor: impl PgExecutor<'e>,
+    contract_id: Uuid,
+    notice: &EventNotice,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO contract_subscription_deliveries
+            (subscription_id, event_type, event_key, subject, body, payload)
+        SELECT s.id, $2, $3, $4, $5, $6
+        FROM contract_subscriptions s
+        JOIN user_notification_channels c ON c.id = s.channel_id
+        WHERE s.contract_id = $1 AND $2 = ANY(s.event_types) AND c.status = ANY($7)
+        ON CONFLICT (subscription_id, event_key) DO NOTHING
+        "#,
+    )
+    .bind(contract_id)
+    .bind(notice.event.as_str())
+    .bind(&notice.key)
+    .bind(&notice.notification.subject)
+    .bind(&notice.notification.body)
+    .bind(&notice.notification.payload)
+    .bind(&ACTIVE_STATUSES[..])
+    .execute(executor)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Queues an incident for the subscribers of every contract it names. An
+/// incident is announced once when opened and once when resolved; contracts
+/// added to it later are announced on the next call.
+pub async fn enqueue_incident(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    incident: &Incident,
+    contract_ids: &[Uuid],
+) -> Result<u64, sqlx::Error> {
+    if contract_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let contracts: Vec<ContractRef> = sqlx::query_as(&format!(
+        "SELECT {} FROM contracts WHERE id = ANY($1)",
+        CONTRACT_REF_COLUMNS
+    ))
+    .bind(contract_ids)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    let mut queued = 0;
+    for contract in &contracts {
+        queued += enqueue(&mut **tx, contract.id, &incident_notice(contract, incident)).await?;
+    }
+    Ok(queued)
+}
+
+pub fn version_notice(contract: &ContractRef, version: &ContractVersion) -> EventNotice {
+    let subject = format!("{} {} published", contract.name, version.version);
+    let mut body = format!(
+        "Version {} of {} was published.\n\nContract: {} ({})\nWASM hash: {}\n",
+        version.version, contract.name, contract.contract_id, contract.network, version.wasm_hash
+    );
+    if let Some(notes) = &version.release_notes {
+        body.push_str(&format!("\nRelease notes:\n{}\n", notes));
+    }
+
+    EventNotice {
+        event: SubscriptionEvent::Version,
+        key: format!("version:{}", version.id),
+        notification: notification(
+            subject,
+            body,
+            contract,
+            serde_json::json!({
+                "version": version.version,
+                "wasm_hash": version.wasm_hash,
+                "source_url": version.source_url,
+                "release_notes": version.release_notes,
+                "published_at": version.created_at,
+            }),
+            SubscriptionEvent::Version,
+        ),
+    }
+}
+
+pub fn verification_notice(contract: &ContractRef, verification_id: Uuid) -> EventNotice {
+    let subject = format!("{} passed verification", contract.name);
+    let body = format!(
+        "{} was verified: its deployed WASM matches the published source.\n\nContract: {} ({})\nVerification ID: {}\n",
+        contract.name, contract.contract_id, contract.network, verification_id
+    );
+
+    EventNotice {
+        event: SubscriptionEvent::Verification,
+        key: format!("verification:{}", verification_id),
+        notification: notification(
+            subject,
+            body,
+            contract,
+            serde_json::json!({ "verification_id": verification_id }),
+            SubscriptionEvent::Verification,
+        ),
+    }
+}
+
+pub fn incident_notice(contract: &ContractRef, incident: &Incident) -> EventNotice {
+    let state = if incident.end_time.is_some() {
+        "resolved"
+    } else {
+        "opened"
+    };
+    let subject = format!(
+        "[{}] {} incident {} for {}",
+        incident.severity.as_str().to_uppercase(),
+        incident.incident_type,
+        state,
+        contract.name
+    );
+    let mut body = format!(
+        "{}\n\nContract: {} ({})\nSeverity: {}\nStarted: {}\n",
+        incident.description,
+        contract.contract_id,
+        contract.network,
+        incident.severity.as_str(),
+        incident.start_time.to_rfc3339()
+    );
+    if let Some(end) = incident.end_time {
+        body.push_str(&format!("Resolved: {}\n", end.to_rfc3339()));
+    }
+    body.push_str(&format!("Incident ID: {}\n", incident.id));
+
+    EventNotice {
+        event: SubscriptionEvent::Incident,
+        key: format!("incident:{}:{}", incident.id, state),
+        notification: notification(
+            subject,
+            body,
+            contract,
+            serde_json::json!({
+                "incident_id": incident.id,
+                "incident_type": incident.incident_type,
+                "severity": incident.severity.as_str(),
+                "state": state,
+                "description": incident.description,
+                "start_time": incident.start_time,
+                "end_time": incident.end_time,
+            }),
+            SubscriptionEvent::Incident,
+        ),
+    }
+}
+
+/// A contract's newest build uses a toolchain version `advisory` covers.
+/// Announced once per affected build.
+pub fn security_notice(
+    contract: &ContractRef,
+    advisory: &Advisory,
+    contract_version: &str,
+    matched_version: &str,
+) -> EventNotice {
+    let subject = format!(
+        "[{}] Security advisory affects {}: {}",
+        advisory.severity.as_str().to_uppercase(),
+        contract.name,
+        advisory.title
+    );
+    let mut body = format!(
+        "Version {} of {} was built with {} {}, which is affected by this advisory.\n\nContract: {} ({})\nSeverity: {}\nAffected versions: {}\n",
+        contract_version,
+        contract.name,
+        advisory.component.as_str(),
+        matched_version,
+        contract.contract_id,
+        contract.network,
+        advisory.severity.as_str(),
+        advisory.affected_versions.join(" || ")
+    );
+    if !advisory.description.is_empty() {
+        body.push_str(&format!("\n{}\n", advisory.description));
+    }
+    if let Some(url) = &advisory.url {
+        body.push_str(&format!("\nDetails: {}\n", url));
+    }
+    body.push_str(&format!("Advisory ID: {}\n", advisory.id));
+
+    EventNotice {
+        event: SubscriptionEvent::Security,
+        key: format!("security:{}:{}", advisory.id, contract_version),
+        notification: notification(
+            subject,
+            body,
+            contract,
+            serde_json::json!({
+                "advisory_id": advisory.id,
+                "title": advisory.title,
+                "severity": advisory.severity.as_str(),
+                "component": advisory.component.as_str(),
+                "affected_versions": advisory.affected_versions,
+                "matched_version": matched_version,
+                "contract_version": contract_version,
+                "url": advisory.url,
+            }),
+            SubscriptionEvent::Security,
+        ),
+    }
+}
+
+/// A contract has gone quiet and will be marked stale on `stale_on` unless
+/// its publisher acts first. Announced once per warning.
+pub fn freshness_notice(
+    contract: &ContractRef,
+    warned_at: DateTime<Utc>,
+    stale_on: DateTime<Utc>,
+) -> EventNotice {
+    let stale_on_date = stale_on.format("%Y-%m-%d");
+    let subject = format!(
+        "{} will be marked stale on {}",
+        contract.name, stale_on_date
+    );
+    let body = format!(
+        "{} has had no new version, on-chain confirmation or publisher activity for a long time and will be marked stale on {}. Stale contracts are labeled as such and ranked lower in search.\n\nContract: {} ({})\n\nPublishing a version, updating its metadata or re-confirming it (POST /api/contracts/{}/freshness/confirm) keeps it fresh.\n",
+        contract.name, stale_on_date, contract.contract_id, contract.network, contract.id
+    );
+
+    EventNotice {
+        event: SubscriptionEvent::Freshness,
+        key: format!("freshness:{}", warned_at.timestamp()),
+        notification: notification(
+            subject,
+            body,
+            contract,
+            serde_json::json!({ "warned_at": warned_at, "stale_on": stale_on }),
+            SubscriptionEvent::Freshness,
+        ),
+    }
+}
+
+/// Wraps event details in the envelope every subscription webhook receives,
+/// in the current payload version
+fn notification(
+    subject: String,
+    body: String,
+    contract: &ContractRef,
+    details: serde_json::Value,
+    event: SubscriptionEvent,
+) -> RenderedNotification {
+    let subject = subject.replace(['\r', '\n'], " ");
+    let payload = serde_json::json!({
+        "event": webhook_events::subscription_event_name(event, &details, CURRENT_PAYLOAD_VERSION),
+        "event_version": CURRENT_PAYLOAD_VERSION,
+        "contract": {
+            "id": contract.id,
+            "contract_id": contract.contract_id,
+            "name": contract.name,
+            "network": contract.network,
+        },
+        "subject": subject,
+        "details": details,
+    });
+
+    RenderedNotification {
+        subject,
+        body,
+        payload,
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct DueDelivery {
+    id: Uuid,
+    subscription_id: Uuid,
+    channel_id: Uuid,
+    attempts: i32,
+    subject: String,
+    body: String,
+    payload: serde_json::Value,
+    channel: String,
+    target: String,
+    payload_version: i32,
+}
+
+/// Spawn the background task that sends queued subscription deliveries.
+/// Shutdown is handled as in the incident notification dispatcher.
+pub fn spawn_subscription_dispatcher(pool: PgPool, client: OutboundClient, shutdown: &Shutdown) {
+    let token = shutdown.token();
+    shutdown.spawn(async move {
+        let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = token.cancelled() => break,
+                _ = interval.tick() => {}
+            }
+            if let Err(err) = dispatch_due(&pool, &client, &token).await {
+                tracing::error!(error = ?err, "subscriptions: dispatch run failed");
+            }
+        }
+        tracing::info!("subscriptions: dispatcher stopped");
+    });
+}
+
+async fn dispatch_due(
+    pool: &PgPool,
+    client: &OutboundClient,
+    shutdown: &CancellationToken,
+) -> Result<(), sqlx::Error> {
+    // Lease due rows for a few minutes so concurrent API replicas don't double-send.
+    let due: Vec<DueDelivery> = sqlx::query_as(
+        r#"
+        UPDATE contract_subscription_deliveries d
+        SET next_attempt_at = NOW() + INTERVAL '5 minutes'
+        FROM contract_subscriptions s
+        WHERE d.subscription_id = s.id
+          AND d.id IN (
+              SELECT pending.id FROM contract_subscription_deliveries pending
+              JOIN contract_subscriptions ps ON ps.id = pending.subscription_id
+              JOIN user_notification_channels c ON c.id = ps.channel_id
+              WHERE pending.status = 'pending' AND pending.next_attempt_at <= NOW()
+                AND c.status = ANY($2)
+              ORDER BY pending.next_attempt_at
+              LIMIT $1
+              FOR UPDATE OF pending SKIP LOCKED
+          )
+        RETURNING d.id, d.subscription_id, s.channel_id, d.attempts, d.subject, d.body,
+                  d.payload, s.channel, s.target, s.payload_version
+        "#,
+    )
+    .bind(BATCH_SIZE)
+    .bind(&ACTIVE_STATUSES[..])
+    .fetch_all(pool)
+    .await?;
+
+    // Built up front: a closure inside the stream would make the spawned
+    // dispatcher fail the `Send` check on higher-ranked lifetimes
+    let sends: Vec<_> = due
+        .iter()
+        .map(|delivery| deliver(pool, client, shutdown, delivery))
+        .collect();
+    let outcomes: Vec<Result<Option<Uuid>, sqlx::Error>> = stream::iter(sends)
+        .buffer_unordered(DELIVERY_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut unsent = Vec::new();
+    let mut first_error = None;
+    for outcome in outcomes {
+        match outcome {
+            Ok(Some(id)) => unsent.push(id),
+            Ok(None) => {}
+            Err(err) => {
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    if !unsent.is_empty() {
+        sqlx::query(
+            "UPDATE contract_subscription_deliveries SET next_attempt_at = NOW()
+             WHERE id = ANY($1) AND status = 'pending'",
+        )
+        .bind(&unsent)
+        .execute(pool)
+        .await?;
+        tracing::info!(
+            released = unsent.len(),
+            "subscriptions: returned unsent deliveries to the queue"
+        );
+    }
+
+    first_error.map_or(Ok(()), Err)
+}
+
+/// Sends one delivery and records the attempt. Returns the delivery's id
+/// instead when shutdown began before it was sent.
+async fn deliver(
+    pool: &PgPool,
+    client: &OutboundClient,
+    shutdown: &CancellationToken,
+    delivery: &DueDelivery,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    if shutdown.is_cancelled() {
+        return Ok(Some(delivery.id));
+    }
+
+    // A destination that keeps failing is skipped without using up attempts
+    if delivery.channel == "webhook" {
+        if let Some(retry_after) = client.blocked_for(&delivery.target) {
+            sqlx::query(
+                "UPDATE contract_subscription_deliveries
+                 SET next_attempt_at = NOW() + make_interval(secs => $2)
+                 WHERE id = $1 AND status = 'pending'",
+            )
+            .bind(delivery.id)
+            .bind(retry_after.as_secs_f64())
+            .execute(pool)
+            .await?;
+            return Ok(None);
+        }
+    }
+
+    let notification = RenderedNotification {
+        subject: delivery.subject.clone(),
+        body: delivery.body.clone(),
+        payload: webhook_events::for_version(
+            &delivery.payload,
+            webhook_events::delivered_version(delivery.payload_version, Utc::now()),
+        ),
+    };
+    let (status_code, result) = match delivery.channel.as_str() {
+        "webhook" => send_webhook_with_status(client, &delivery.target, &notification).await,
+        "email" => (None, send_email(&delivery.target, &notification).await),
+        other => (None, Err(format!("unsupported channel '{}'", other))),
+    };
+
+    record_attempt(pool, delivery, status_code, result).await?;
+    Ok(None)
+}
+
+/// Seconds to wait before retrying after `attempts` failed attempts
+fn backoff_secs(attempts: i32) -> i64 {
+    BASE_BACKOFF_SECS << attempts.clamp(0, 10)
+}
+
+async fn record_attempt(
+    pool: &PgPool,
+    delivery: &DueDelivery,
+    status_code: Option<u16>,
+    result: Result<(), String>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    match &result {
+        Ok(()) => {
+            sqlx::query(
+                "UPDATE contract_subscription_deliveries
+                 SET status = 'delivered', attempts = attempts + 1, last_error = NULL,
+                     delivered_at = NOW()
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                "UPDATE contract_subscriptions
+                 SET consecutive_failures = 0, last_error = NULL, last_delivered_at = NOW()
+                 WHERE id = $1",
+            )
+            .bind(delivery.subscription_id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        Err(reason) => {
+            let attempts = delivery.attempts + 1;
+            sqlx::query(
+                "UPDATE contract_subscription_deliveries
+                 SET attempts = $2, last_error = $3,
+                     status = CASE WHEN $4 THEN 'failed' ELSE 'pending' END,
+                     next_attempt_at = NOW() + make_interval(secs => $5)
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .bind(attempts)
+            .bind(reason)
+            .bind(attempts >= MAX_ATTEMPTS)
+            .bind(backoff_secs(delivery.attempts) as f64)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                "UPDATE contract_subscriptions
+                 SET consecutive_failures = consecutive_failures + 1, last_error = $2
+                 WHERE id = $1",
+            )
+            .bind(delivery.subscription_id)
+            .bind(reason)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    let channel_disabled = notification_channels::record_delivery(
+        &mut tx,
+        delivery.channel_id,
+        &Attempt {
+            purpose: "delivery",
+            delivery_id: Some(delivery.id),
+            subject: Some(&delivery.subject),
+            status_code,
+            error: result.as_ref().err().map(String::as_str),
+        },
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    if channel_disabled {
+        tracing::warn!(
+            channel_id = %delivery.channel_id,
+            failures = FAILURE_LIMIT,
+            "notification channel disabled after repeated delivery failures"
+        );
+    }
+
+    match result {
+        Ok(()) => tracing::info!(
+            subscription_id = %delivery.subscription_id,
+            delivery_id = %delivery.id,
+            channel = %delivery.channel,
+            "subscription delivery sent"
+        ),
+        Err(reason) => tracing::warn!(
+            subscription_id = %delivery.subscription_id,
+            delivery_id = %delivery.id,
+            channel = %delivery.channel,
+            attempts = delivery.attempts + 1,
+            exhausted = delivery.attempts + 1 >= MAX_ATTEMPTS,
+            error = %reason,
+            "subscription delivery failed"
+        ),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advisory_models::{AdvisoryComponent, AdvisorySeverity};
+    use crate::incident_models::IncidentSeverity;
+    use chrono::Utc;
+
+    fn contract() -> ContractRef {
+        ContractRef {
+            id: Uuid::new_v4(),
+            contract_id: "CABC".to_string(),
+            name: "token".to_string(),
+            network: "testnet".to_string(),
+        }
+    }
+
+    fn incident() -> Incident {
+        Incident {
+            id: Uuid::new_v4(),
+            contract_id: None,
+            incident_type: "exploit".to_string(),
+            severity: IncidentSeverity::Critical,
+            description: "Funds drained".to_string(),
+            start_time: Utc::now(),
+            end_time: None,
+            rto_achieved: None,
+            rpo_achieved: None,
+            lessons_learned: None,
+            notified_users: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deleted_at: None,
+            maintenance_window_id: None,
+            source: "api".to_string(),
+            external_id: None,
+            objectives: Default::default(),
+            escalation: Default::default(),
+            affected_contracts: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn incidents_are_announced_once_when_opened_and_once_when_resolved() {
+        let contract = contract();
+        let mut incident = incident();
+        let opened = incident_notice(&contract, &incident);
+        assert_eq!(opened.key, format!("incident:{}:opened", incident.id));
+        assert_eq!(
+            opened.notification.subject,
+            "[CRITICAL] exploit incident opened for token"
+        );
+
+        incident.end_time = Some(Utc::now());
+        let resolved = incident_notice(&contract, &incident);
+        assert_eq!(resolved.key, format!("incident:{}:resolved", incident.id));
+        assert_eq!(
+            resolved.notification.payload["details"]["state"],
+            "resolved"
+        );
+        assert!(resolved.notification.body.contains("Resolved: "));
+    }
+
+    #[test]
+    fn payload_names_the_event_and_contract() {
+        let contract = contract();
+        let verification_id = Uuid::new_v4();
+        let notice = verification_notice(&contract, verification_id);
+        let payload = &notice.notification.payload;
+        assert_eq!(payload["event"], "contract.verified");
+        assert_eq!(payload["event_version"], CURRENT_PAYLOAD_VERSION);
+        assert_eq!(payload["contract"]["contract_id"], "CABC");
+        assert_eq!(
+            payload["details"]["verification_id"],
+            verification_id.to_string()
+        );
+        assert_eq!(notice.key, format!("verification:{}", verification_id));
+    }
+
+    #[test]
+    fn notices_match_the_event_catalog() {
+        let contract = contract();
+        let mut resolved = incident();
+        resolved.end_time = Some(Utc::now());
+        let version = ContractVersion {
+            id: Uuid::new_v4(),
+            contract_id: contract.id,
+            version: "1.1.0".to_string(),
+            wasm_hash: "00".to_string(),
+            source_url: None,
+            commit_hash: None,
+            release_notes: None,
+            created_at: Utc::now(),
+            state_schema: None,
+            signature: None,
+            publisher_key: None,
+            signature_algorithm: None,
+            signed_at: None,
+            signature_nonce: None,
+            wasm_meta: None,
+        };
+        let advisory = Advisory {
+            id: Uuid::new_v4(),
+            component: AdvisoryComponent::SorobanSdk,
+            affected_versions: vec!["<20.5.2".to_string()],
+            severity: AdvisorySeverity::High,
+            title: "Storage TTL bug".to_string(),
+            description: String::new(),
+            url: None,
+            resolved_at: None,
+            created_by: "admin".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            affected_contracts: 0,
+        };
+
+        let notices = [
+            ("contract.published", version_notice(&contract, &version)),
+            (
+                "contract.verified",
+                verification_notice(&contract, Uuid::new_v4()),
+            ),
+            ("incident.created", incident_notice(&contract, &incident())),
+            ("incident.resolved", incident_notice(&contract, &resolved)),
+            (
+                "security.advisory",
+                security_notice(&contract, &advisory, "1.1.0", "20.3.1"),
+            ),
+            (
+                "contract.stale_warning",
+                freshness_notice(&contract, Utc::now(), Utc::now()),
+            ),
+        ];
+        for (name, notice) in notices {
+            let payload = &notice.notification.payload;
+            assert_eq!(payload["event"], name);
+            let schema = webhook_events::event_type(name, CURRENT_PAYLOAD_VERSION)
+                .unwrap()
+                .schema(CURRENT_PAYLOAD_VERSION);
+            let mut fields: Vec<String> = payload["details"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect();
+            fields.sort();
+            assert_eq!(
+                fields,
+                webhook_events::schema_fields(&schema["properties"]["details"]),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn subjects_stay_on_one_line() {
+        let mut contract = contract();
+        contract.name = "token\r\nBcc: someone".to_string();
+        let notice = verification_notice(&contract, Uuid::new_v4());
+        assert!(!notice.notification.subject.contains(['\r', '\n']));
+    }
+
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        assert_eq!(backoff_secs(0), BASE_BACKOFF_SECS);
+        assert_eq!(backoff_secs(3), BASE_BACKOFF_SECS * 8);
+        assert_eq!(backoff_secs(50), BASE_BACKOFF_SECS << 10);
+    }
+}
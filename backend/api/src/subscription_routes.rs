@@ -0,0 +1,24 @@
+use axum::{
+    routing::{delete, get},
+    Router,
+};
+
+use crate::{state::AppState, subscription_handlers};
+
+pub fn subscription_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/contracts/:id/subscriptions",
+            get(subscription_handlers::list_contract_subscriptions)
+                .post(subscription_handlers::create_subscription)
+                .delete(subscription_handlers::delete_contract_subscriptions),
+        )
+        .route(
+            "/api/me/subscriptions",
+            get(subscription_handlers::list_my_subscriptions),
+        )
+        .route(
+            "/api/me/subscriptions/:id",
+            delete(subscription_handlers::delete_my_subscription),
+        )
+}
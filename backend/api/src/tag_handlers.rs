@@ -0,0 +1,675 @@
+//! Contract tags and their aliases
+//!
+//!   GET    /api/tags                      – canonical tags with usage counts and aliases (public)
+//!   POST   /api/admin/tag-aliases         – make a tag an alias of another
+//!   DELETE /api/admin/tag-aliases/:alias  – remove an alias
+//!   POST   /api/me/contracts/tags         – rename, add or remove tags across the caller's contracts
+//!
+//! Aliased tags are rewritten to their canonical tag when a contract is
+//! published or its tags are edited (`normalize_contract_tags` in the
+//! database), and adding an alias rewrites the contracts that already carry
+//! it. Tag filters and search match every spelling of a tag, see
+//! [`expand_tags`].
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use shared::models::AuditActionType;
+use shared::pagination::Paginated;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    pagination::{PageParams, Pagination},
+    state::AppState,
+};
+
+/// Longest tag a contract may carry, as enforced on publish
+const MAX_TAG_LEN: usize = 50;
+/// Operations accepted in one bulk tag request
+const MAX_TAG_OPERATIONS: usize = 20;
+
+#[derive(Debug, Serialize, FromRow, utoipa::ToSchema)]
+pub struct TagSummary {
+    /// Canonical spelling of the tag
+    pub name: String,
+    /// Public contracts carrying the tag
+    pub usage_count: i64,
+    /// Lowercased spellings that are rewritten to this tag
+    pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateTagAliasRequest {
+    /// Tag to rewrite, matched case-insensitively
+    pub alias: String,
+    /// Tag it is rewritten to
+    pub canonical: String,
+}
+
+#[derive(Debug, Serialize, FromRow, utoipa::ToSchema)]
+pub struct TagAlias {
+    pub alias: String,
+    pub canonical: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TagAliasCreated {
+    #[serde(flatten)]
+    pub alias: TagAlias,
+    /// Contracts whose tags were rewritten to the canonical tag
+    pub contracts_updated: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct TagRename {
+    /// Tag to replace, matched case-insensitively
+    pub from: String,
+    pub to: String,
+}
+
+/// Which contracts an operation applies to; all of them when omitted
+#[derive(Debug, Clone, Default, Deserialize, utoipa::ToSchema)]
+pub struct TagScope {
+    /// Only contracts in this category, matched case-insensitively
+    pub category: Option<String>,
+}
+
+/// One of `rename`, `add` or `remove`, optionally limited by `where`
+#[derive(Debug, Clone, Deserialize, utoipa::ToSchema)]
+pub struct TagOperation {
+    pub rename: Option<TagRename>,
+    pub add: Option<String>,
+    /// Tag to remove, matched case-insensitively
+    pub remove: Option<String>,
+    #[serde(default, rename = "where")]
+    pub scope: TagScope,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BulkTagRequest {
+    /// Applied in order to every contract the caller owns
+    pub operations: Vec<TagOperation>,
+    /// Report what would change without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A contract whose tags changed, or would have
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkTagResult {
+    pub contract_id: Uuid,
+    pub name: String,
+    pub old_tags: Vec<String>,
+    /// After aliases were rewritten to their canonical tags
+    pub new_tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkTagResponse {
+    pub dry_run: bool,
+    /// Contracts whose tags changed
+    pub affected: usize,
+    pub contracts: Vec<BulkTagResult>,
+}
+
+#[derive(Debug, FromRow)]
+struct OwnedTags {
+    id: Uuid,
+    name: String,
+    category: Option<String>,
+    tags: Vec<String>,
+}
+
+/// Every spelling of the given tags, lowercased: each tag itself, its
+/// canonical tag and all aliases of that canonical tag. Filtering on the
+/// result makes "dex" and "amm" find the same contracts once one is an
+/// alias of the other.
+pub(crate) async fn expand_tags(db: &PgPool, tags: &[String]) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        r#"
+        WITH requested AS (
+            SELECT DISTINCT lower(btrim(t)) AS tag FROM unnest($1::TEXT[]) t
+        ),
+        canonical AS (
+            SELECT lower(COALESCE(a.canonical, r.tag)) AS tag
+            FROM requested r
+            LEFT JOIN tag_aliases a ON a.alias = r.tag
+        )
+        SELECT tag FROM requested
+        UNION
+        SELECT tag FROM canonical
+        UNION
+        SELECT a.alias FROM tag_aliases a JOIN canonical c ON lower(a.canonical) = c.tag
+        "#,
+    )
+    .bind(tags)
+    .fetch_all(db)
+    .await
+}
+
+fn validate_tag(field: &str, tag: &str) -> ApiResult<()> {
+    if tag.is_empty() || tag.chars().count() > MAX_TAG_LEN {
+        return Err(ApiError::bad_request(
+            "InvalidTag",
+            format!("{} must be between 1 and {} characters", field, MAX_TAG_LEN),
+        ));
+    }
+    Ok(())
+}
+
+/// The operation's tags trimmed and checked, or why it can't be applied
+fn validate_operation(op: &TagOperation) -> ApiResult<TagOperation> {
+    let given = [op.rename.is_some(), op.add.is_some(), op.remove.is_some()];
+    if given.iter().filter(|given| **given).count() != 1 {
+        return Err(ApiError::bad_request(
+            "InvalidTagOperation",
+            "Each operation needs exactly one of rename, add or remove",
+        ));
+    }
+    let mut op = op.clone();
+    if let Some(rename) = &mut op.rename {
+        rename.from = rename.from.trim().to_string();
+        rename.to = rename.to.trim().to_string();
+        validate_tag("rename.from", &rename.from)?;
+        validate_tag("rename.to", &rename.to)?;
+    }
+    if let Some(tag) = &mut op.add {
+        *tag = tag.trim().to_string();
+        validate_tag("add", tag)?;
+    }
+    if let Some(tag) = &mut op.remove {
+        *tag = tag.trim().to_string();
+        validate_tag("remove", tag)?;
+    }
+    Ok(op)
+}
+
+/// `tags` after applying `ops` in order to a contract in `category`.
+/// Matching ignores case, and a tag never ends up on a contract twice.
+fn apply_tag_operations(
+    tags: &[String],
+    category: Option<&str>,
+    ops: &[TagOperation],
+) -> Vec<String> {
+    let mut tags = tags.to_vec();
+
+    for op in ops {
+        let in_scope = match (&op.scope.category, category) {
+            (None, _) => true,
+            (Some(wanted), Some(category)) => wanted.trim().eq_ignore_ascii_case(category),
+            (Some(_), None) => false,
+        };
+        if !in_scope {
+            continue;
+        }
+        if let Some(rename) = &op.rename {
+            // The renamed tag takes the place of the first spelling of the
+            // old one; other spellings of either go
+            if let Some(at) = tags
+                .iter()
+                .position(|t| t.eq_ignore_ascii_case(&rename.from))
+            {
+                tags[at] = rename.to.clone();
+                let mut i = 0;
+                tags.retain(|t| {
+                    let keep = i == at
+                        || !(t.eq_ignore_ascii_case(&rename.from)
+                            || t.eq_ignore_ascii_case(&rename.to));
+                    i += 1;
+                    keep
+                });
+            }
+        }
+        if let Some(tag) = &op.add {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.clone());
+            }
+        }
+        if let Some(tag) = &op.remove {
+            tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+        }
+    }
+    tags
+}
+
+/// List canonical tags, most used first.
+///
+/// Aliases don't appear as tags of their own; they are listed under the tag
+/// they are rewritten to.
+#[utoipa::path(
+    get,
+    path = "/api/tags",
+    params(PageParams),
+    responses(
+        (status = 200, description = "Canonical tags with usage counts and aliases", body = Paginated<TagSummary>)
+    ),
+    tag = "Tags"
+)]
+pub async fn list_tags(
+    State(state): State<AppState>,
+    Pagination { limit, offset }: Pagination,
+) -> ApiResult<Json<Paginated<TagSummary>>> {
+    const TAGS: &str = r#"
+        WITH used AS (
+            SELECT t AS name, COUNT(*) AS usage_count
+            FROM contracts c, unnest(c.tags) t
+            WHERE c.visibility = 'public'
+            GROUP BY t
+        ),
+        names AS (
+            SELECT name FROM used
+            UNION
+            SELECT canonical FROM tag_aliases
+        ),
+        canonical AS (
+            SELECT n.name
+            FROM names n
+            WHERE NOT EXISTS (SELECT 1 FROM tag_aliases a WHERE a.alias = lower(n.name))
+        )
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!("{} SELECT COUNT(*) FROM canonical", TAGS))
+        .fetch_one(state.read_pool())
+        .await
+        .map_err(|err| db_internal_error("count tags", err))?;
+
+    let tags: Vec<TagSummary> = sqlx::query_as(&format!(
+        "{}
+        SELECT n.name,
+               COALESCE(u.usage_count, 0) AS usage_count,
+               ARRAY(SELECT a.alias FROM tag_aliases a
+                     WHERE lower(a.canonical) = lower(n.name)
+                     ORDER BY a.alias) AS aliases
+        FROM canonical n
+        LEFT JOIN used u ON u.name = n.name
+        ORDER BY usage_count DESC, n.name
+        LIMIT $1 OFFSET $2",
+        TAGS
+    ))
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(state.read_pool())
+    .await
+    .map_err(|err| db_internal_error("list tags", err))?;
+
+    Ok(Json(Paginated::new(tags, total, limit, offset)))
+}
+
+/// Make `alias` an alias of `canonical`.
+///
+/// Contracts already tagged with the alias are rewritten to the canonical
+/// tag in the same transaction. Aliases don't chain: the canonical tag may
+/// not itself be an alias, and a tag that other aliases point at can't
+/// become one.
+#[utoipa::path(
+    post,
+    path = "/api/admin/tag-aliases",
+    request_body = CreateTagAliasRequest,
+    responses(
+        (status = 201, description = "Alias created and existing contracts re-tagged", body = TagAliasCreated),
+        (status = 400, description = "Invalid tag"),
+        (status = 409, description = "The alias exists, or would chain onto another alias")
+    ),
+    tag = "Tags",
+    security(("bearer_auth" = []))
+)]
+pub async fn create_tag_alias(
+    State(state): State<AppState>,
+    Json(req): Json<CreateTagAliasRequest>,
+) -> ApiResult<(StatusCode, Json<TagAliasCreated>)> {
+    let alias = req.alias.trim().to_lowercase();
+    let canonical = req.canonical.trim().to_string();
+    validate_tag("alias", &alias)?;
+    validate_tag("canonical", &canonical)?;
+    if alias == canonical.to_lowercase() {
+        return Err(ApiError::bad_request(
+            "InvalidTag",
+            "A tag can't be an alias of itself",
+        ));
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin tag alias transaction", err))?;
+
+    // Concurrent inserts could otherwise build a chain the checks below
+    // each miss
+    sqlx::query("LOCK TABLE tag_aliases IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("lock tag aliases", err))?;
+
+    let canonical_of_canonical: Option<String> =
+        sqlx::query_scalar("SELECT canonical FROM tag_aliases WHERE alias = lower($1)")
+            .bind(&canonical)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("look up canonical tag", err))?;
+    if let Some(target) = canonical_of_canonical {
+        return Err(ApiError::conflict(
+            "CanonicalIsAlias",
+            format!(
+                "'{}' is itself an alias of '{}'; alias '{}' to '{}' instead",
+                canonical, target, alias, target
+            ),
+        ));
+    }
+
+    let aliased_to_alias: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM tag_aliases WHERE lower(canonical) = $1")
+            .bind(&alias)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("count aliases of tag", err))?;
+    if aliased_to_alias > 0 {
+        return Err(ApiError::conflict(
+            "AliasIsCanonical",
+            format!(
+                "'{}' is the canonical tag of {} alias(es); remove those first",
+                alias, aliased_to_alias
+            ),
+        ));
+    }
+
+    let created: TagAlias = sqlx::query_as(
+        "INSERT INTO tag_aliases (alias, canonical) VALUES ($1, $2)
+         RETURNING alias, canonical, created_at",
+    )
+    .bind(&alias)
+    .bind(&canonical)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|err| match err {
+        sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+            ApiError::conflict("AliasExists", format!("'{}' is already an alias", alias))
+        }
+        _ => db_internal_error("create tag alias", err),
+    })?;
+
+    let contracts_updated = sqlx::query(
+        "UPDATE contracts
+            SET tags = normalize_contract_tags(tags)
+          WHERE EXISTS (SELECT 1 FROM unnest(tags) t WHERE lower(btrim(t)) = $1)",
+    )
+    .bind(&alias)
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("re-tag contracts with alias", err))?
+    .rows_affected();
+
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit tag alias", err))?;
+
+    if contracts_updated > 0 {
+        state.cache.invalidate_all_contracts();
+    }
+
+    Ok((
+        StatusCode::CREATED,
+        Json(TagAliasCreated {
+            alias: created,
+            contracts_updated,
+        }),
+    ))
+}
+
+/// Remove an alias.
+///
+/// Contracts that were re-tagged when the alias was added keep the
+/// canonical tag; only future publishes stop being rewritten.
+#[utoipa::path(
+    delete,
+    path = "/api/admin/tag-aliases/{alias}",
+    params(
+        ("alias" = String, Path, description = "Alias to remove")
+    ),
+    responses(
+        (status = 204, description = "Alias removed"),
+        (status = 404, description = "No such alias")
+    ),
+    tag = "Tags",
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_tag_alias(
+    State(state): State<AppState>,
+    Path(alias): Path<String>,
+) -> ApiResult<StatusCode> {
+    let removed = sqlx::query("DELETE FROM tag_aliases WHERE alias = lower(btrim($1))")
+        .bind(&alias)
+        .execute(&state.db)
+        .await
+        .map_err(|err| db_internal_error("delete tag alias", err))?
+        .rows_affected();
+
+    if removed == 0 {
+        return Err(ApiError::not_found(
+            "AliasNotFound",
+            format!("'{}' is not a tag alias", alias),
+        ));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Rename, add or remove tags across every contract the caller owns.
+///
+/// Owned contracts are those published under the caller's address or by an
+/// organization they belong to. All operations run in one transaction, and
+/// each changed contract gets its own `metadata_updated` audit entry. With
+/// `dry_run` the transaction is rolled back, so the response shows what
+/// would change, aliases already rewritten.
+#[utoipa::path(
+    post,
+    path = "/api/me/contracts/tags",
+    request_body = BulkTagRequest,
+    responses(
+        (status = 200, description = "Contracts whose tags changed, or would have", body = BulkTagResponse),
+        (status = 400, description = "Invalid operation or tag"),
+        (status = 401, description = "No API token")
+    ),
+    tag = "Tags",
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_update_my_tags(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<BulkTagRequest>,
+) -> ApiResult<Json<BulkTagResponse>> {
+    if req.operations.is_empty() || req.operations.len() > MAX_TAG_OPERATIONS {
+        return Err(ApiError::bad_request(
+            "InvalidTagOperation",
+            format!(
+                "operations must hold between 1 and {} operations",
+                MAX_TAG_OPERATIONS
+            ),
+        ));
+    }
+    let operations = req
+        .operations
+        .iter()
+        .map(validate_operation)
+        .collect::<ApiResult<Vec<_>>>()?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin bulk tag transaction", err))?;
+
+    let owned: Vec<OwnedTags> = sqlx::query_as(
+        "SELECT c.id, c.name, c.category, c.tags
+         FROM contracts c
+         JOIN publishers p ON p.id = c.publisher_id
+         WHERE p.stellar_address = $1
+            OR c.organization_id IN (
+                SELECT om.organization_id FROM organization_members om
+                JOIN publishers mp ON mp.id = om.publisher_id
+                WHERE mp.stellar_address = $1)
+         ORDER BY c.name
+         FOR UPDATE OF c",
+    )
+    .bind(&claims.sub)
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("lock my contracts", err))?;
+
+    let mut contracts = Vec::new();
+    for contract in owned {
+        let tags = apply_tag_operations(&contract.tags, contract.category.as_deref(), &operations);
+        if tags == contract.tags {
+            continue;
+        }
+        let new_tags: Vec<String> = sqlx::query_scalar(
+            "UPDATE contracts SET tags = normalize_contract_tags($2),
+                    publisher_active_at = NOW(), stale_warned_at = NULL, stale_at = NULL
+             WHERE id = $1 RETURNING tags",
+        )
+        .bind(contract.id)
+        .bind(&tags)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("update contract tags", err))?;
+        if new_tags == contract.tags {
+            continue;
+        }
+
+        sqlx::query(
+            "INSERT INTO contract_audit_log (action_type, contract_id, old_value, new_value, changed_by)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(AuditActionType::MetadataUpdated)
+        .bind(contract.id)
+        .bind(json!({ "tags": contract.tags }))
+        .bind(json!({ "tags": new_tags, "source": "bulk_tags" }))
+        .bind(&claims.sub)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("write bulk tag audit log", err))?;
+
+        contracts.push(BulkTagResult {
+            contract_id: contract.id,
+            name: contract.name,
+            old_tags: contract.tags,
+            new_tags,
+        });
+    }
+
+    if req.dry_run {
+        tx.rollback()
+            .await
+            .map_err(|err| db_internal_error("roll back bulk tag dry run", err))?;
+    } else {
+        tx.commit()
+            .await
+            .map_err(|err| db_internal_error("commit bulk tags", err))?;
+        if !contracts.is_empty() {
+            state.cache.invalidate_all_contracts();
+        }
+    }
+
+    Ok(Json(BulkTagResponse {
+        dry_run: req.dry_run,
+        affected: contracts.len(),
+        contracts,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_bounded_like_published_tags() {
+        assert!(validate_tag("alias", "dex").is_ok());
+        assert!(validate_tag("alias", "").is_err());
+        assert!(validate_tag("alias", &"a".repeat(MAX_TAG_LEN)).is_ok());
+        assert!(validate_tag("alias", &"a".repeat(MAX_TAG_LEN + 1)).is_err());
+    }
+
+    fn ops(value: serde_json::Value) -> Vec<TagOperation> {
+        serde_json::from_value::<Vec<TagOperation>>(value)
+            .unwrap()
+            .iter()
+            .map(|op| validate_operation(op).unwrap())
+            .collect()
+    }
+
+    fn tags(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn renames_keep_position_and_never_duplicate() {
+        let rename = ops(json!([{ "rename": { "from": "AMM", "to": "dex" } }]));
+        assert_eq!(
+            apply_tag_operations(&tags(&["defi", "amm", "swap"]), None, &rename),
+            tags(&["defi", "dex", "swap"])
+        );
+        assert_eq!(
+            apply_tag_operations(&tags(&["dex", "amm", "Dex"]), None, &rename),
+            tags(&["dex"])
+        );
+        assert_eq!(
+            apply_tag_operations(&tags(&["swap"]), None, &rename),
+            tags(&["swap"])
+        );
+    }
+
+    #[test]
+    fn adds_respect_the_category_scope() {
+        let add = ops(json!([{ "add": "oracle", "where": { "category": "oracle" } }]));
+        assert_eq!(
+            apply_tag_operations(&tags(&["price"]), Some("Oracle"), &add),
+            tags(&["price", "oracle"])
+        );
+        assert_eq!(
+            apply_tag_operations(&tags(&["price"]), Some("DeFi"), &add),
+            tags(&["price"])
+        );
+        assert_eq!(
+            apply_tag_operations(&tags(&["price"]), None, &add),
+            tags(&["price"])
+        );
+        assert_eq!(
+            apply_tag_operations(&tags(&["Oracle"]), Some("oracle"), &add),
+            tags(&["Oracle"])
+        );
+    }
+
+    #[test]
+    fn operations_apply_in_order() {
+        let both = ops(json!([
+            { "remove": "legacy" },
+            { "rename": { "from": "v1", "to": "stable" } }
+        ]));
+        assert_eq!(
+            apply_tag_operations(&tags(&["LEGACY", "v1", "token"]), None, &both),
+            tags(&["stable", "token"])
+        );
+    }
+
+    #[test]
+    fn an_operation_does_exactly_one_thing() {
+        let invalid: Vec<TagOperation> = serde_json::from_value(json!([
+            {},
+            { "add": "a", "remove": "b" },
+            { "add": "   " }
+        ]))
+        .unwrap();
+        for op in &invalid {
+            assert!(validate_operation(op).is_err(), "{:?}", op);
+        }
+    }
+}
@@ -0,0 +1,361 @@
+//! Anonymous CLI usage counts, sent by CLIs whose users opted in.
+//!
+//! A report is folded into per-day counts of commands and flag names and
+//! then dropped. Its install id is only kept as a salted hash, so that each
+//! install reports at most once a day; nothing links counts to it.
+//!
+//!   POST /api/telemetry          – one CLI's events since its last report
+//!   GET  /api/admin/telemetry    – usage counts over the last days
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::db_internal_error,
+    state::AppState,
+};
+
+/// Events accepted in one report; the CLI keeps at most this many
+const MAX_EVENTS: usize = 1_000;
+const MAX_FLAGS_PER_EVENT: usize = 32;
+const MAX_COMMAND_LEN: usize = 100;
+const MAX_FLAG_LEN: usize = 64;
+const DEFAULT_DAYS: i32 = 30;
+const MAX_DAYS: i32 = 365;
+
+/// One CLI run
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct TelemetryEvent {
+    /// Subcommand path, e.g. `contracts list`
+    pub command: String,
+    /// Long names of the flags given, e.g. `--limit`
+    #[serde(default)]
+    pub flags: Vec<String>,
+    pub duration_ms: u64,
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct TelemetryReport {
+    /// Random id the CLI generated on its first report
+    pub install_id: Uuid,
+    pub cli_version: String,
+    pub events: Vec<TelemetryEvent>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TelemetryAccepted {
+    pub events: usize,
+}
+
+/// What one report adds to a command's counts for the day
+#[derive(Debug, Default)]
+struct CommandCounts {
+    invocations: i64,
+    failures: i64,
+    total_duration_ms: i64,
+    flags: BTreeMap<String, i64>,
+}
+
+/// `contracts list`: lowercase words of letters, digits and dashes
+fn valid_command(command: &str) -> bool {
+    !command.is_empty()
+        && command.len() <= MAX_COMMAND_LEN
+        && command.split(' ').all(|word| {
+            !word.is_empty()
+                && word
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        })
+}
+
+/// `--verified-only`
+fn valid_flag(flag: &str) -> bool {
+    flag.len() <= MAX_FLAG_LEN
+        && flag.strip_prefix("--").is_some_and(|name| {
+            !name.is_empty()
+                && name
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+        })
+}
+
+/// Rejects reports that could carry anything other than command and flag
+/// names, and totals what is left per command.
+fn aggregate(events: &[TelemetryEvent]) -> ApiResult<BTreeMap<&str, CommandCounts>> {
+    if events.len() > MAX_EVENTS {
+        return Err(ApiError::validation(
+            "events",
+            format!("At most {} events per report", MAX_EVENTS),
+        ));
+    }
+    let mut commands: BTreeMap<&str, CommandCounts> = BTreeMap::new();
+    for event in events {
+        if !valid_command(&event.command) {
+            return Err(ApiError::validation(
+                "command",
+                "Commands are subcommand names: lowercase letters, digits and dashes",
+            ));
+        }
+        if event.flags.len() > MAX_FLAGS_PER_EVENT
+            || !event.flags.iter().all(|flag| valid_flag(flag))
+        {
+            return Err(ApiError::validation(
+                "flags",
+                "Flags are long flag names like --limit, without values",
+            ));
+        }
+        let counts = commands.entry(&event.command).or_default();
+        counts.invocations += 1;
+        counts.failures += i64::from(!event.success);
+        counts.total_duration_ms = counts
+            .total_duration_ms
+            .saturating_add(i64::try_from(event.duration_ms).unwrap_or(i64::MAX));
+        for flag in &event.flags {
+            *counts.flags.entry(flag.clone()).or_default() += 1;
+        }
+    }
+    Ok(commands)
+}
+
+/// Records a CLI's usage counts. Each install is counted once a day; a
+/// second report the same day is answered 429 and the CLI keeps its events.
+#[utoipa::path(
+    post,
+    path = "/api/telemetry",
+    request_body = TelemetryReport,
+    responses(
+        (status = 202, description = "Report counted", body = TelemetryAccepted),
+        (status = 400, description = "Events carry more than command and flag names"),
+        (status = 429, description = "This install already reported today")
+    ),
+    tag = "Telemetry"
+)]
+pub async fn submit_telemetry(
+    State(state): State<AppState>,
+    Json(report): Json<TelemetryReport>,
+) -> ApiResult<(StatusCode, Json<TelemetryAccepted>)> {
+    let commands = aggregate(&report.events)?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|err| db_internal_error("begin telemetry report", err))?;
+    let first_today = sqlx::query(
+        "INSERT INTO cli_telemetry_installs (day, install_hash)
+         SELECT CURRENT_DATE, encode(sha256((salt || $1)::bytea), 'hex')
+         FROM cli_telemetry_salt
+         ON CONFLICT DO NOTHING",
+    )
+    .bind(report.install_id.to_string())
+    .execute(&mut *tx)
+    .await
+    .map_err(|err| db_internal_error("record telemetry install", err))?
+    .rows_affected()
+        > 0;
+    if !first_today {
+        return Err(ApiError::rate_limited(
+            "This install already reported today; try again tomorrow",
+        ));
+    }
+
+    for (command, counts) in &commands {
+        sqlx::query(
+            "INSERT INTO cli_telemetry_commands
+                 (day, command, invocations, failures, total_duration_ms)
+             VALUES (CURRENT_DATE, $1, $2, $3, $4)
+             ON CONFLICT (day, command) DO UPDATE
+                SET invocations = cli_telemetry_commands.invocations + EXCLUDED.invocations,
+                    failures = cli_telemetry_commands.failures + EXCLUDED.failures,
+                    total_duration_ms =
+                        cli_telemetry_commands.total_duration_ms + EXCLUDED.total_duration_ms",
+        )
+        .bind(command)
+        .bind(counts.invocations)
+        .bind(counts.failures)
+        .bind(counts.total_duration_ms)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| db_internal_error("record telemetry command", err))?;
+
+        for (flag, uses) in &counts.flags {
+            sqlx::query(
+                "INSERT INTO cli_telemetry_flags (day, command, flag, uses)
+                 VALUES (CURRENT_DATE, $1, $2, $3)
+                 ON CONFLICT (day, command, flag) DO UPDATE
+                    SET uses = cli_telemetry_flags.uses + EXCLUDED.uses",
+            )
+            .bind(command)
+            .bind(flag)
+            .bind(uses)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| db_internal_error("record telemetry flag", err))?;
+        }
+    }
+    tx.commit()
+        .await
+        .map_err(|err| db_internal_error("commit telemetry report", err))?;
+
+    tracing::debug!(
+        cli_version = %report.cli_version,
+        events = report.events.len(),
+        "telemetry report counted"
+    );
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(TelemetryAccepted {
+            events: report.events.len(),
+        }),
+    ))
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct TelemetryQuery {
+    /// Days to cover, today included (default 30, at most 365)
+    pub days: Option<i32>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct CommandUsage {
+    pub command: String,
+    pub invocations: i64,
+    pub failures: i64,
+    pub avg_duration_ms: f64,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow, utoipa::ToSchema)]
+pub struct FlagUsage {
+    pub command: String,
+    pub flag: String,
+    pub uses: i64,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TelemetrySummary {
+    pub days: i32,
+    /// Reports received; an install reporting on several days counts once
+    /// per day
+    pub reports: i64,
+    /// Most used first
+    pub commands: Vec<CommandUsage>,
+    pub flags: Vec<FlagUsage>,
+}
+
+/// CLI usage counts over the last `days` days.
+#[utoipa::path(
+    get,
+    path = "/api/admin/telemetry",
+    params(TelemetryQuery),
+    responses(
+        (status = 200, description = "Usage counts", body = TelemetrySummary),
+        (status = 403, description = "Administrative privileges required")
+    ),
+    security(("bearerAuth" = [])),
+    tag = "Telemetry"
+)]
+pub async fn get_telemetry(
+    State(state): State<AppState>,
+    Query(query): Query<TelemetryQuery>,
+) -> ApiResult<Json<TelemetrySummary>> {
+    let days = query.days.unwrap_or(DEFAULT_DAYS).clamp(1, MAX_DAYS);
+
+    let reports: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM cli_telemetry_installs
+         WHERE day > CURRENT_DATE - $1",
+    )
+    .bind(days)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|err| db_internal_error("count telemetry reports", err))?;
+
+    let commands: Vec<CommandUsage> = sqlx::query_as(
+        "SELECT command,
+                SUM(invocations)::BIGINT AS invocations,
+                SUM(failures)::BIGINT AS failures,
+                (SUM(total_duration_ms)::FLOAT8 / GREATEST(SUM(invocations), 1)) AS avg_duration_ms
+         FROM cli_telemetry_commands
+         WHERE day > CURRENT_DATE - $1
+         GROUP BY command
+         ORDER BY invocations DESC, command",
+    )
+    .bind(days)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("summarize telemetry commands", err))?;
+
+    let flags: Vec<FlagUsage> = sqlx::query_as(
+        "SELECT command, flag, SUM(uses)::BIGINT AS uses
+         FROM cli_telemetry_flags
+         WHERE day > CURRENT_DATE - $1
+         GROUP BY command, flag
+         ORDER BY uses DESC, command, flag",
+    )
+    .bind(days)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|err| db_internal_error("summarize telemetry flags", err))?;
+
+    Ok(Json(TelemetrySummary {
+        days,
+        reports,
+        commands,
+        flags,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(command: &str, flags: &[&str], success: bool) -> TelemetryEvent {
+        TelemetryEvent {
+            command: command.to_string(),
+            flags: flags.iter().map(|flag| flag.to_string()).collect(),
+            duration_ms: 40,
+            success,
+        }
+    }
+
+    #[test]
+    fn totals_events_per_command() {
+        let events = [
+            event("contracts list", &["--limit"], true),
+            event("contracts list", &["--limit", "--network"], false),
+            event("search", &[], true),
+        ];
+        let commands = aggregate(&events).unwrap();
+        let list = &commands["contracts list"];
+        assert_eq!(list.invocations, 2);
+        assert_eq!(list.failures, 1);
+        assert_eq!(list.total_duration_ms, 80);
+        assert_eq!(list.flags["--limit"], 2);
+        assert_eq!(list.flags["--network"], 1);
+        assert_eq!(commands["search"].invocations, 1);
+    }
+
+    #[test]
+    fn rejects_anything_but_names() {
+        for bad in [
+            event(
+                "search CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
+                &[],
+                true,
+            ),
+            event("search", &["--query=secret"], true),
+            event("search", &["-v"], true),
+            event("", &[], true),
+        ] {
+            assert!(aggregate(&[bad]).is_err());
+        }
+    }
+}
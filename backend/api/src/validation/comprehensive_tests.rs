@@ -442,12 +442,10 @@ mod tests {
 
     #[test]
     fn test_max_payload_size_calculation() {
-        // Default is 5MB
-        let max_bytes = 5 * 1024 * 1024;
-        assert_eq!(
-            crate::validation::payload_size::get_max_payload_bytes(),
-            max_bytes
-        );
+        // JSON bodies default to 1 MB, WASM uploads to 5 MB
+        let limits = crate::validation::payload_size::BodyLimits::from_lookup(|_| None);
+        assert_eq!(limits.json_bytes, 1024 * 1024);
+        assert_eq!(limits.wasm_bytes, 5 * 1024 * 1024);
     }
 
     // ============================================================================
@@ -483,14 +481,21 @@ mod tests {
             FieldError::new("name", "is required"),
         ];
 
-        let response = crate::validation::extractors::ValidationErrorResponse::new(errors);
-        assert_eq!(response.error_code, "BAD_REQUEST");
-        assert_eq!(response.details["reason"], "VALIDATION_ERROR");
+        let response = crate::error::ApiError::from(
+            crate::validation::extractors::ValidationError::new(errors),
+        );
+        assert_eq!(response.status(), axum::http::StatusCode::BAD_REQUEST);
+        assert_eq!(response.code(), "VALIDATION_ERROR");
+
+        let body = serde_json::to_value(response.body()).unwrap();
+        assert_eq!(body["error"]["details"]["field"], "contract_id");
         assert_eq!(
-            response.details["field_errors"].as_array().unwrap().len(),
+            body["error"]["details"]["field_errors"]
+                .as_array()
+                .unwrap()
+                .len(),
             2
         );
-        assert!(response.details["correlation_id"].as_str().is_some());
     }
 
     #[test]
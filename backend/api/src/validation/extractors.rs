@@ -6,13 +6,13 @@
 use axum::{
     async_trait,
     extract::{FromRequest, Request},
-    http::StatusCode,
+    response::IntoResponse,
     Json,
 };
-use chrono::{SecondsFormat, Utc};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::json;
-use uuid::Uuid;
+
+use crate::error::ApiError;
 
 /// A field-level validation error
 #[derive(Debug, Clone, Serialize)]
@@ -30,37 +30,6 @@ impl FieldError {
     }
 }
 
-/// Validation error response body
-#[derive(Debug, Serialize)]
-pub struct ValidationErrorResponse {
-    pub error_code: String,
-    pub message: String,
-    pub details: serde_json::Value,
-    pub timestamp: String,
-}
-
-impl ValidationErrorResponse {
-    pub fn new(errors: Vec<FieldError>, correlation_id: String) -> Self {
-        let error_summary = if errors.len() == 1 {
-            format!("Validation failed for field '{}'", errors[0].field)
-        } else {
-            format!("Validation failed for {} fields", errors.len())
-        };
-
-        Self {
-            error_code: "BAD_REQUEST".to_string(),
-            message: error_summary,
-            details: json!({
-                "reason": "VALIDATION_ERROR",
-                "field_errors": errors,
-                "correlation_id": Uuid::new_v4().to_string()
-            }),
-            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
-            correlation_id,
-        }
-    }
-}
-
 /// Validation error that converts to an HTTP response
 #[derive(Debug)]
 pub struct ValidationError {
@@ -79,17 +48,28 @@ impl ValidationError {
     }
 }
 
-impl axum::response::IntoResponse for ValidationError {
+/// Reported as `VALIDATION_ERROR`, naming the first failing field and listing
+/// every failure under `details.field_errors`.
+impl From<ValidationError> for ApiError {
+    fn from(err: ValidationError) -> Self {
+        let message = if err.errors.len() == 1 {
+            format!("Validation failed for field '{}'", err.errors[0].field)
+        } else {
+            format!("Validation failed for {} fields", err.errors.len())
+        };
+        let field = err
+            .errors
+            .first()
+            .map(|e| e.field.clone())
+            .unwrap_or_default();
+
+        ApiError::validation(field, message).with_details(json!({ "field_errors": err.errors }))
+    }
+}
+
+impl IntoResponse for ValidationError {
     fn into_response(self) -> axum::response::Response {
-        let correlation_id = crate::request_tracing::current_request_id()
-            .unwrap_or_else(crate::request_tracing::generate_request_id);
-        let response = ValidationErrorResponse::new(self.errors, correlation_id.clone());
-        let mut http_response = (StatusCode::BAD_REQUEST, Json(response)).into_response();
-        crate::request_tracing::attach_request_id_headers(
-            http_response.headers_mut(),
-            &correlation_id,
-        );
-        http_response
+        ApiError::from(self).into_response()
     }
 }
 
@@ -325,22 +305,24 @@ mod tests {
             FieldError::new("name", "must be at least 1 character"),
         ];
 
-        let response = ValidationErrorResponse::new(errors, "test-correlation-id".to_string());
+        let response = ApiError::from(ValidationError::new(errors));
 
-        assert_eq!(response.error_code, "BAD_REQUEST");
-        assert_eq!(response.details["reason"], "VALIDATION_ERROR");
+        assert_eq!(response.code(), "VALIDATION_ERROR");
         assert_eq!(
-            response.details["field_errors"].as_array().unwrap().len(),
+            response.details().unwrap()["field_errors"]
+                .as_array()
+                .unwrap()
+                .len(),
             2
         );
-        assert!(response.message.contains("2 fields"));
+        assert!(response.message().contains("2 fields"));
     }
 
     #[test]
     fn test_single_error_response() {
         let errors = vec![FieldError::new("name", "is required")];
-        let response = ValidationErrorResponse::new(errors, "test-correlation-id".to_string());
+        let response = ApiError::from(ValidationError::new(errors));
 
-        assert!(response.message.contains("field 'name'"));
+        assert!(response.message().contains("field 'name'"));
     }
 }
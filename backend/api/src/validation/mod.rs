@@ -41,21 +41,22 @@
 //!
 //! ## Validation Error Response
 //!
-//! When validation fails, a 400 Bad Request is returned:
+//! When validation fails, a 400 Bad Request is returned in the standard
+//! error shape (see [`crate::error::ApiError`]):
 //!
 //! ```json
 //! {
-//!   "error_code": "BAD_REQUEST",
-//!   "message": "Validation failed for 2 fields",
-//!   "details": {
-//!     "reason": "VALIDATION_ERROR",
-//!     "field_errors": [
-//!       {"field": "contract_id", "message": "must be a valid Stellar contract ID"},
-//!       {"field": "name", "message": "must be at least 1 character"}
-//!     ],
-//!     "correlation_id": "uuid-here"
-//!   },
-//!   "timestamp": "2026-02-20T10:30:00Z"
+//!   "error": {
+//!     "code": "VALIDATION_ERROR",
+//!     "message": "Validation failed for 2 fields",
+//!     "details": {
+//!       "field": "contract_id",
+//!       "field_errors": [
+//!         {"field": "contract_id", "message": "must be a valid Stellar contract ID"},
+//!         {"field": "name", "message": "must be at least 1 character"}
+//!       ]
+//!     }
+//!   }
 //! }
 //! ```
 
@@ -1,101 +1,496 @@
-//! Payload size validation middleware
+//! Request body limits and content-type enforcement
 //!
-//! This middleware enforces maximum request body size limits to prevent
-//! denial-of-service attacks and consume excessive resources.
+//! JSON endpoints only accept `application/json` bodies (415 otherwise) of
+//! at most `MAX_JSON_BODY_KB`. The WASM upload route is the one exception: it
+//! takes `application/wasm` up to `MAX_WASM_UPLOAD_MB` and streams the body to
+//! a temporary file instead of buffering it.
+//!
+//! Requests that declare an oversized `Content-Length` get a 413 before any of
+//! the body is read. Chunked bodies are cut off by `DefaultBodyLimit` once they
+//! pass the limit, and the bare 413 that produces is replaced with the
+//! standard error body.
+//!
+//! The incident import route takes NDJSON instead and limits the number of
+//! records it reads rather than the body size.
 //!
 //! Configuration via environment variables:
-//! - MAX_PAYLOAD_SIZE_MB: Maximum payload size in MB (default: 5)
+//! - MAX_JSON_BODY_KB: Maximum JSON body size in KB (default: 1024)
+//! - MAX_WASM_UPLOAD_MB: Maximum WASM upload size in MB (default: 5)
 
 use axum::{
     body::Body,
-    extract::{connect_info::ConnectInfo, MatchedPath},
-    http::Request,
+    extract::{connect_info::ConnectInfo, MatchedPath, State},
+    http::{header, HeaderMap, Method, Request, StatusCode},
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use std::net::SocketAddr;
+use futures_util::StreamExt;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use tokio::io::AsyncWriteExt;
 
 use crate::error::ApiError;
 
-const DEFAULT_MAX_PAYLOAD_MB: u64 = 5;
-const HEADER_CONTENT_LENGTH: &str = "content-length";
+const DEFAULT_MAX_JSON_BODY_KB: usize = 1024;
+const DEFAULT_MAX_WASM_UPLOAD_MB: usize = 5;
 
-/// Get configured max payload size in bytes
-pub fn get_max_payload_bytes() -> u64 {
-    let env_mb = std::env::var("MAX_PAYLOAD_SIZE_MB")
-        .ok()
-        .and_then(|v| v.parse::<u64>().ok())
-        .unwrap_or(DEFAULT_MAX_PAYLOAD_MB);
+/// Content types accepted by the WASM upload route
+pub const WASM_CONTENT_TYPES: [&str; 2] = ["application/wasm", "application/octet-stream"];
 
-    env_mb * 1024 * 1024
+/// Content types accepted by the incident import route
+pub const NDJSON_CONTENT_TYPES: [&str; 2] = ["application/x-ndjson", "application/jsonl"];
+
+/// Per-route body size limits, in bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BodyLimits {
+    pub json_bytes: usize,
+    pub wasm_bytes: usize,
+}
+
+impl Default for BodyLimits {
+    fn default() -> Self {
+        Self {
+            json_bytes: DEFAULT_MAX_JSON_BODY_KB * 1024,
+            wasm_bytes: DEFAULT_MAX_WASM_UPLOAD_MB * 1024 * 1024,
+        }
+    }
 }
 
-/// Middleware that validates request payload size
+impl BodyLimits {
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Reads the limits from `lookup`, which stands in for the environment.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let read = |var: &str, default: usize| match lookup(var) {
+            Some(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!("Invalid value for {var} (`{raw}`), using default {default}");
+                default
+            }),
+            None => default,
+        };
+
+        Self {
+            json_bytes: read("MAX_JSON_BODY_KB", DEFAULT_MAX_JSON_BODY_KB) * 1024,
+            wasm_bytes: read("MAX_WASM_UPLOAD_MB", DEFAULT_MAX_WASM_UPLOAD_MB) * 1024 * 1024,
+        }
+    }
+}
+
+/// Middleware for JSON routes
+///
+/// Requests with a body must be `application/json` and no larger than
+/// [`BodyLimits::json_bytes`]. Pair with `DefaultBodyLimit::max` set to the
+/// same value so chunked bodies are capped too.
+pub async fn json_body_middleware(
+    State(limits): State<BodyLimits>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if has_body(&req) && !is_json(req.headers()) {
+        return unsupported_media_type(req.headers(), "application/json").into_response();
+    }
+    if let Err(err) = check_content_length(&req, limits.json_bytes) {
+        return err.into_response();
+    }
+
+    let response = next.run(req).await;
+    if response.status() == StatusCode::PAYLOAD_TOO_LARGE && !is_json(response.headers()) {
+        return payload_too_large(limits.json_bytes, None).into_response();
+    }
+    response
+}
+
+/// Middleware for the WASM upload route
 ///
-/// Returns 413 Payload Too Large if the request body exceeds the configured limit.
-/// The limit is checked via the Content-Length header when available.
-pub async fn payload_size_validation_middleware(
-    ConnectInfo(addr): ConnectInfo<SocketAddr>,
-    matched_path: Option<MatchedPath>,
+/// Only checks the headers; the handler enforces [`BodyLimits::wasm_bytes`]
+/// on the stream itself via [`spool_to_temp_file`].
+pub async fn wasm_upload_middleware(
+    State(limits): State<BodyLimits>,
     req: Request<Body>,
     next: Next,
-) -> Result<Response, Response> {
-    let max_bytes = get_max_payload_bytes();
-    let request_id = crate::request_tracing::get_or_create_request_id(&req);
-
-    // Check Content-Length header
-    if let Some(content_length_str) = req.headers().get(HEADER_CONTENT_LENGTH) {
-        if let Ok(content_length_str) = content_length_str.to_str() {
-            if let Ok(size) = content_length_str.parse::<u64>() {
-                if size > max_bytes {
-                    let max_mb = max_bytes / (1024 * 1024);
-
-                    // Log the violation
-                    let client_ip = addr.ip();
-                    let path = matched_path
-                        .as_ref()
-                        .map(|p| p.as_str())
-                        .unwrap_or("unknown");
-
-                    crate::security_log::log_payload_too_large(
-                        client_ip,
-                        size as usize,
-                        max_bytes as usize,
-                        path,
-                        &request_id,
-                    );
-
-                    return Err(ApiError::new(
-                        axum::http::StatusCode::PAYLOAD_TOO_LARGE,
-                        "PAYLOAD_TOO_LARGE",
-                        format!(
-                            "Request payload exceeds maximum size of {} MB ({} bytes)",
-                            max_mb, max_bytes
-                        ),
-                    )
-                    .with_details(serde_json::json!({
-                        "max_size_mb": max_mb,
-                        "max_size_bytes": max_bytes,
-                        "provided_size_bytes": size,
-                        "correlation_id": correlation_id
-                    }))
-                    .into_response());
-                }
-            }
+) -> Response {
+    if !has_content_type(req.headers(), &WASM_CONTENT_TYPES) {
+        return unsupported_media_type(req.headers(), WASM_CONTENT_TYPES[0]).into_response();
+    }
+    if let Err(err) = check_content_length(&req, limits.wasm_bytes) {
+        return err.into_response();
+    }
+
+    next.run(req).await
+}
+
+/// Middleware for the NDJSON incident import route
+///
+/// Only checks the content type; the handler caps the records it reads.
+pub async fn ndjson_middleware(req: Request<Body>, next: Next) -> Response {
+    if !has_content_type(req.headers(), &NDJSON_CONTENT_TYPES) {
+        return unsupported_media_type(req.headers(), NDJSON_CONTENT_TYPES[0]).into_response();
+    }
+
+    next.run(req).await
+}
+
+/// A request body written to a temporary file, which is deleted on drop
+pub struct SpooledBody {
+    pub file: tempfile::NamedTempFile,
+    pub size: usize,
+    /// Hex-encoded SHA-256 of the contents
+    pub sha256: String,
+}
+
+/// Streams `body` into a temporary file, failing with 413 as soon as it
+/// passes `max_bytes` so oversized uploads are never held in full.
+pub async fn spool_to_temp_file(body: Body, max_bytes: usize) -> Result<SpooledBody, ApiError> {
+    let temp = tempfile::NamedTempFile::new()
+        .map_err(|e| ApiError::internal(format!("failed to create temp file: {}", e)))?;
+    let std_file = temp
+        .reopen()
+        .map_err(|e| ApiError::internal(format!("failed to open temp file: {}", e)))?;
+    let mut file = tokio::fs::File::from_std(std_file);
+
+    let mut hasher = Sha256::new();
+    let mut size = 0usize;
+    let mut stream = body.into_data_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| {
+            ApiError::bad_request(
+                "BodyReadFailed",
+                format!("Failed to read request body: {}", e),
+            )
+        })?;
+        size += chunk.len();
+        if size > max_bytes {
+            return Err(payload_too_large(max_bytes, None));
         }
+        hasher.update(&chunk);
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| ApiError::internal(format!("failed to write temp file: {}", e)))?;
     }
+    file.flush()
+        .await
+        .map_err(|e| ApiError::internal(format!("failed to write temp file: {}", e)))?;
 
-    Ok(next.run(req).await)
+    Ok(SpooledBody {
+        file: temp,
+        size,
+        sha256: hex::encode(hasher.finalize()),
+    })
+}
+
+fn has_body(req: &Request<Body>) -> bool {
+    if !matches!(
+        *req.method(),
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    ) {
+        return false;
+    }
+    req.headers().contains_key(header::TRANSFER_ENCODING)
+        || content_length(req.headers()).is_some_and(|len| len > 0)
+}
+
+fn content_length(headers: &HeaderMap) -> Option<usize> {
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+}
+
+fn mime_type(headers: &HeaderMap) -> Option<String> {
+    let value = headers.get(header::CONTENT_TYPE)?.to_str().ok()?;
+    let essence = value.split(';').next().unwrap_or_default();
+    Some(essence.trim().to_ascii_lowercase())
+}
+
+/// `application/json` or any `application/*+json` type
+fn is_json(headers: &HeaderMap) -> bool {
+    mime_type(headers).is_some_and(|mime| {
+        mime == "application/json" || (mime.starts_with("application/") && mime.ends_with("+json"))
+    })
+}
+
+fn has_content_type(headers: &HeaderMap, accepted: &[&str]) -> bool {
+    mime_type(headers).is_some_and(|mime| accepted.contains(&mime.as_str()))
+}
+
+fn check_content_length(req: &Request<Body>, max_bytes: usize) -> Result<(), ApiError> {
+    let Some(size) = content_length(req.headers()) else {
+        return Ok(());
+    };
+    if size <= max_bytes {
+        return Ok(());
+    }
+
+    let client_ip = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    let path = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str())
+        .unwrap_or("unknown");
+    let request_id = crate::request_tracing::get_or_create_request_id(req);
+    crate::security_log::log_payload_too_large(client_ip, size, max_bytes, path, &request_id);
+
+    Err(payload_too_large(max_bytes, Some(size)))
+}
+
+fn payload_too_large(max_bytes: usize, provided: Option<usize>) -> ApiError {
+    let mut details = json!({
+        "reason": "PayloadTooLarge",
+        "max_size_bytes": max_bytes,
+    });
+    if let Some(provided) = provided {
+        details["provided_size_bytes"] = json!(provided);
+    }
+    ApiError::payload_too_large(format!(
+        "Request body exceeds the maximum size of {} bytes",
+        max_bytes
+    ))
+    .with_details(details)
+}
+
+fn unsupported_media_type(headers: &HeaderMap, expected: &str) -> ApiError {
+    let received = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("none");
+    ApiError::unsupported_media_type(format!(
+        "Content-Type must be {}, got {}",
+        expected, received
+    ))
+    .with_details(json!({
+        "reason": "UnsupportedMediaType",
+        "expected": expected,
+        "received": received,
+    }))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use axum::{extract::DefaultBodyLimit, middleware, routing::post, Json, Router};
+    use tower::ServiceExt;
+
+    const LIMIT: usize = 64;
+
+    fn limits() -> BodyLimits {
+        BodyLimits {
+            json_bytes: LIMIT,
+            wasm_bytes: LIMIT,
+        }
+    }
+
+    async fn echo(Json(value): Json<serde_json::Value>) -> Json<serde_json::Value> {
+        Json(value)
+    }
+
+    async fn spool(body: Body) -> Result<String, ApiError> {
+        let spooled = spool_to_temp_file(body, LIMIT).await?;
+        Ok(spooled.size.to_string())
+    }
+
+    fn json_app() -> Router {
+        Router::new()
+            .route("/echo", post(echo))
+            .route("/ping", post(|| async { "pong" }).get(|| async { "pong" }))
+            .layer(middleware::from_fn_with_state(
+                limits(),
+                json_body_middleware,
+            ))
+            .layer(DefaultBodyLimit::max(LIMIT))
+    }
+
+    fn wasm_app() -> Router {
+        Router::new()
+            .route("/upload", post(spool))
+            .layer(middleware::from_fn_with_state(
+                limits(),
+                wasm_upload_middleware,
+            ))
+    }
+
+    /// A JSON string literal that serializes to exactly `len` bytes
+    fn json_of_len(len: usize) -> String {
+        format!("\"{}\"", "a".repeat(len - 2))
+    }
+
+    fn request(uri: &str, content_type: &str, body: String) -> Request<Body> {
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::CONTENT_TYPE, content_type)
+            .header(header::CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    /// Same as [`request`] but without `Content-Length`, as a chunked upload
+    fn chunked(uri: &str, content_type: &str, body: String) -> Request<Body> {
+        let chunks: Vec<Result<String, std::io::Error>> = body
+            .as_bytes()
+            .chunks(16)
+            .map(|c| Ok(String::from_utf8(c.to_vec()).unwrap()))
+            .collect();
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::CONTENT_TYPE, content_type)
+            .header(header::TRANSFER_ENCODING, "chunked")
+            .body(Body::from_stream(futures_util::stream::iter(chunks)))
+            .unwrap()
+    }
+
+    async fn error_code(response: Response) -> String {
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        body["error"]["code"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string()
+    }
 
     #[test]
-    fn test_default_max_payload() {
-        // Should return 5MB in bytes by default
-        let max = get_max_payload_bytes();
-        assert_eq!(max, 5 * 1024 * 1024);
+    fn limits_read_from_env_with_defaults() {
+        assert_eq!(BodyLimits::from_lookup(|_| None), BodyLimits::default());
+        let limits = BodyLimits::from_lookup(|key| match key {
+            "MAX_JSON_BODY_KB" => Some("2".to_string()),
+            "MAX_WASM_UPLOAD_MB" => Some("not a number".to_string()),
+            _ => None,
+        });
+        assert_eq!(limits.json_bytes, 2048);
+        assert_eq!(limits.wasm_bytes, DEFAULT_MAX_WASM_UPLOAD_MB * 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn json_body_at_the_limit_is_accepted() {
+        let res = json_app()
+            .oneshot(request("/echo", "application/json", json_of_len(LIMIT)))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn json_body_one_byte_over_is_rejected_before_reading() {
+        let res = json_app()
+            .oneshot(request("/echo", "application/json", json_of_len(LIMIT + 1)))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(res).await, "PAYLOAD_TOO_LARGE");
+    }
+
+    #[tokio::test]
+    async fn chunked_json_body_over_the_limit_gets_a_structured_413() {
+        let ok = json_app()
+            .oneshot(chunked("/echo", "application/json", json_of_len(LIMIT)))
+            .await
+            .unwrap();
+        assert_eq!(ok.status(), StatusCode::OK);
+
+        let res = json_app()
+            .oneshot(chunked("/echo", "application/json", json_of_len(LIMIT + 1)))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(res).await, "PAYLOAD_TOO_LARGE");
+    }
+
+    #[tokio::test]
+    async fn non_json_body_is_rejected_with_415() {
+        for content_type in ["text/plain", "application/x-www-form-urlencoded"] {
+            let res = json_app()
+                .oneshot(request("/echo", content_type, "\"hello\"".to_string()))
+                .await
+                .unwrap();
+            assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+            assert_eq!(error_code(res).await, "UNSUPPORTED_MEDIA_TYPE");
+        }
+
+        let res = json_app()
+            .oneshot(request(
+                "/echo",
+                "application/merge-patch+json; charset=utf-8",
+                "{}".to_string(),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn requests_without_a_body_skip_the_content_type_check() {
+        let post = Request::builder()
+            .method("POST")
+            .uri("/ping")
+            .body(Body::empty())
+            .unwrap();
+        assert_eq!(
+            json_app().oneshot(post).await.unwrap().status(),
+            StatusCode::OK
+        );
+
+        let get = Request::builder().uri("/ping").body(Body::empty()).unwrap();
+        assert_eq!(
+            json_app().oneshot(get).await.unwrap().status(),
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn wasm_upload_streams_up_to_the_limit() {
+        let at_limit = wasm_app()
+            .oneshot(chunked("/upload", "application/wasm", "a".repeat(LIMIT)))
+            .await
+            .unwrap();
+        assert_eq!(at_limit.status(), StatusCode::OK);
+
+        let over = wasm_app()
+            .oneshot(chunked(
+                "/upload",
+                "application/wasm",
+                "a".repeat(LIMIT + 1),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(over.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(error_code(over).await, "PAYLOAD_TOO_LARGE");
+
+        let declared = wasm_app()
+            .oneshot(request(
+                "/upload",
+                "application/wasm",
+                "a".repeat(LIMIT + 1),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(declared.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn wasm_upload_requires_a_wasm_content_type() {
+        let res = wasm_app()
+            .oneshot(request("/upload", "application/json", "{}".to_string()))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    }
+
+    #[tokio::test]
+    async fn spooled_body_is_hashed_and_kept_on_disk() {
+        let spooled = spool_to_temp_file(Body::from("\0asm"), LIMIT)
+            .await
+            .unwrap();
+        assert_eq!(spooled.size, 4);
+        assert_eq!(std::fs::read(spooled.file.path()).unwrap(), b"\0asm");
+        assert_eq!(spooled.sha256, hex::encode(Sha256::digest(b"\0asm")));
     }
 }
@@ -9,11 +9,12 @@ use shared::models::{
     Publisher, UpdateContractMetadataRequest, UpdateContractStatusRequest,
     UpdateMigrationStatusRequest, VerifyRequest,
 };
+use shared::slug::{slugify, validate_slug};
 
 use super::extractors::{FieldError, Validatable, ValidationBuilder};
 use super::sanitizers::{
     normalize_contract_id, normalize_stellar_address, sanitize_description_optional, sanitize_name,
-    sanitize_tags, sanitize_url_optional, trim,
+    sanitize_tags, sanitize_url_optional, trim, trim_optional,
 };
 use super::validators::{
     validate_category_whitelist, validate_contract_id, validate_json_depth, validate_length,
@@ -31,6 +32,8 @@ const MAX_NAME_LENGTH: usize = 255;
 const MIN_NAME_LENGTH: usize = 1;
 /// Maximum length for description
 const MAX_DESCRIPTION_LENGTH: usize = 5000;
+/// Maximum length for a README
+const MAX_README_LENGTH: usize = 100_000;
 /// Maximum number of tags allowed
 const MAX_TAGS_COUNT: usize = 10;
 /// Maximum length for each tag
@@ -79,6 +82,20 @@ impl Validatable for PublishRequest {
         for dep in &mut self.dependencies {
             dep.sanitize();
         }
+
+        if let Some(ref mut org) = self.organization {
+            *org = trim(org);
+            if org.is_empty() {
+                self.organization = None;
+            }
+        }
+
+        if let Some(ref mut slug) = self.slug {
+            *slug = trim(slug);
+            if slug.is_empty() {
+                self.slug = None;
+            }
+        }
     }
 
     fn validate(&self) -> Result<(), Vec<FieldError>> {
@@ -97,6 +114,15 @@ impl Validatable for PublishRequest {
         builder.check("name", || validate_no_xss(&self.name));
         builder.check("name", || validate_name_format(&self.name));
 
+        builder.check("slug", || match self.slug {
+            Some(ref slug) => validate_slug(slug),
+            None if slugify(&self.name).is_empty() => Err(
+                "name has no letters or digits to derive a slug from; set slug explicitly"
+                    .to_string(),
+            ),
+            None => Ok(()),
+        });
+
         if let Some(ref desc) = self.description {
             builder.check("description", || {
                 validate_length(desc, 0, MAX_DESCRIPTION_LENGTH)
@@ -153,6 +179,9 @@ impl Validatable for VerifyRequest {
         self.compiler_version = trim(&self.compiler_version);
         self.source_code = super::sanitizers::sanitize_source_code(&self.source_code);
         super::sanitizers::sanitize_json_value(&mut self.build_params);
+        // The lockfile is hashed as submitted, so it is not touched
+        sanitize_url_optional(&mut self.source_url);
+        trim_optional(&mut self.commit_hash);
     }
 
     fn validate(&self) -> Result<(), Vec<FieldError>> {
@@ -178,6 +207,30 @@ impl Validatable for VerifyRequest {
             validate_json_depth(&self.build_params, MAX_JSON_DEPTH)
         });
 
+        builder.check("cargo_lock", || match &self.cargo_lock {
+            Some(cargo_lock) => verifier::build_info::parse_lockfile(cargo_lock)
+                .map(|_| ())
+                .map_err(|err| err.to_string()),
+            None => Ok(()),
+        });
+
+        builder.check("source_url", || validate_url_optional(&self.source_url));
+
+        builder.check("commit_hash", || match &self.commit_hash {
+            Some(commit) => {
+                if self.source_url.is_none() {
+                    return Err("commit_hash requires source_url".to_string());
+                }
+                if !(7..=64).contains(&commit.len())
+                    || !commit.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    return Err("commit_hash must be 7 to 64 hexadecimal characters".to_string());
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        });
+
         builder.build()
     }
 }
@@ -364,6 +417,9 @@ impl Validatable for UpdateContractMetadataRequest {
         if let Some(ref mut tags) = self.tags {
             *tags = sanitize_tags(tags);
         }
+        if let Some(ref mut slug) = self.slug {
+            *slug = trim(slug);
+        }
     }
 
     fn validate(&self) -> Result<(), Vec<FieldError>> {
@@ -400,6 +456,14 @@ impl Validatable for UpdateContractMetadataRequest {
             });
         }
 
+        if let Some(ref slug) = self.slug {
+            builder.check("slug", || validate_slug(slug));
+        }
+
+        if let Some(ref readme) = self.readme {
+            builder.check("readme", || validate_length(readme, 0, MAX_README_LENGTH));
+        }
+
         builder.build()
     }
 }
@@ -542,6 +606,9 @@ mod tests {
             source_url: Some("https://github.com/user/repo".to_string()),
             publisher_address: valid_stellar_address(),
             dependencies: vec![],
+            is_cicd: false,
+            organization: None,
+            slug: None,
         };
 
         assert!(req.validate().is_ok());
@@ -560,6 +627,9 @@ mod tests {
             source_url: None,
             publisher_address: valid_stellar_address(),
             dependencies: vec![],
+            is_cicd: false,
+            organization: None,
+            slug: None,
         };
 
         let result = req.validate();
@@ -581,6 +651,9 @@ mod tests {
             source_url: None,
             publisher_address: valid_stellar_address(),
             dependencies: vec![],
+            is_cicd: false,
+            organization: None,
+            slug: None,
         };
 
         let result = req.validate();
@@ -589,6 +662,64 @@ mod tests {
         assert!(errors.iter().any(|e| e.field == "wasm_hash"));
     }
 
+    #[test]
+    fn test_verify_request_build_inputs() {
+        let mut req = VerifyRequest {
+            contract_id: valid_contract_id(),
+            source_code: "#![no_std]".to_string(),
+            build_params: serde_json::json!({}),
+            compiler_version: "21.7.7".to_string(),
+            cargo_lock: Some("[[package]]\nname = \"escrow\"\nversion = \"0.1.0\"\n".to_string()),
+            source_url: None,
+            commit_hash: Some("a1b2c3d".to_string()),
+        };
+        let errors = req.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "commit_hash"));
+
+        req.source_url = Some("https://github.com/acme/escrow".to_string());
+        assert!(req.validate().is_ok());
+
+        req.commit_hash = Some("main".to_string());
+        let errors = req.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "commit_hash"));
+
+        req.commit_hash = None;
+        req.cargo_lock = Some("not a lockfile".to_string());
+        let errors = req.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "cargo_lock"));
+    }
+
+    #[test]
+    fn test_publish_request_slug() {
+        let mut req = PublishRequest {
+            contract_id: valid_contract_id(),
+            wasm_hash: "a".repeat(64),
+            name: "Liquidity Pool".to_string(),
+            description: None,
+            network: Network::Testnet,
+            category: None,
+            tags: vec![],
+            source_url: None,
+            publisher_address: valid_stellar_address(),
+            dependencies: vec![],
+            is_cicd: false,
+            organization: None,
+            slug: Some("Liquidity_Pool".to_string()),
+        };
+        let errors = req.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "slug"));
+
+        req.slug = Some("liquidity-pool".to_string());
+        assert!(req.validate().is_ok());
+
+        // Without a slug, one is derived from the name
+        req.slug = None;
+        assert!(req.validate().is_ok());
+        req.name = "___".to_string();
+        let errors = req.validate().unwrap_err();
+        assert!(errors.iter().any(|e| e.field == "slug"));
+    }
+
     #[test]
     fn test_publish_request_sanitization() {
         let mut req = PublishRequest {
@@ -603,6 +734,9 @@ mod tests {
             publisher_address: "  gdlzfc3syjydzt7k67vz75hpjvieuvnixf47zg2fb2rmqqvu2hhgcysc  "
                 .to_string(),
             dependencies: vec![],
+            is_cicd: false,
+            organization: None,
+            slug: None,
         };
 
         req.sanitize();
@@ -0,0 +1,763 @@
+//! Comparison of two versions' stored WASM modules, below the interface.
+//!
+//!   GET /api/contracts/:id/versions/compare?from=<version>&to=<version>
+//!
+//! Reports how much each section grew or shrank, which functions were added,
+//! removed or changed, and which data segments changed, so a release that
+//! claims to only touch one function can be checked against its binary.
+//!
+//! Functions are matched by name when both modules keep a `name` section and
+//! by index otherwise; a function changed when the SHA-256 of its body did.
+//! Indexes shift when functions are added or removed earlier in the module,
+//! so without names a change in the middle shows up as every later function
+//! changing. Data segments are matched by their constant offset, or by index
+//! for passive ones.
+//!
+//! A version without a stored module (uploaded with
+//! `POST /api/contracts/:id/versions/:version/wasm`) doesn't fail the
+//! request: the result is marked `complete: false`, lists the version under
+//! `missing`, and only has the section sizes of the module that is stored.
+//! Complete results are cached by the two modules' hashes.
+
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{rejection::QueryRejection, Path, Query, State},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use shared::{models::SourceFormat, ContractSource, ContractVersion};
+use wasmparser::{DataKind, KnownCustom, Name, Operator, Parser, Payload, TypeRef};
+
+use crate::{
+    error::{ApiError, ApiResult},
+    handlers::{
+        db_internal_error, fetch_contract_identity, fetch_contract_version, map_query_rejection,
+    },
+    state::AppState,
+};
+
+const CACHE_NAMESPACE: &str = "wasm_compare";
+/// Hex digits of a body's SHA-256 reported per function
+const CODE_HASH_LEN: usize = 16;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WasmCompareQuery {
+    /// Base version
+    pub from: String,
+    /// Version compared against `from`
+    pub to: String,
+}
+
+/// One side of the comparison
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ComparedBinary {
+    pub version: String,
+    pub wasm_hash: String,
+    /// Whether the module is stored; nothing below is compared without it
+    pub stored: bool,
+    /// Size of the stored module in bytes
+    pub size: Option<i64>,
+}
+
+/// Bytes of one section's contents in each module, absent where the module
+/// has no such section. Custom sections are named `custom:<name>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SectionDelta {
+    pub section: String,
+    pub from_bytes: Option<u64>,
+    pub to_bytes: Option<u64>,
+    pub delta: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionMatching {
+    /// Both modules have a `name` section
+    Name,
+    /// At least one module has no `name` section
+    Index,
+}
+
+/// A function only one module has
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FunctionRef {
+    pub index: u32,
+    pub name: Option<String>,
+    /// Bytes of its body
+    pub size: u64,
+}
+
+/// A function both modules have, with a different body
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FunctionChange {
+    pub name: Option<String>,
+    pub from_index: u32,
+    pub to_index: u32,
+    pub from_size: u64,
+    pub to_size: u64,
+    /// First hex digits of the SHA-256 of each body
+    pub from_hash: String,
+    pub to_hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FunctionDiff {
+    pub matched_by: FunctionMatching,
+    pub added: Vec<FunctionRef>,
+    pub removed: Vec<FunctionRef>,
+    pub changed: Vec<FunctionChange>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DataSegmentChange {
+    pub change: SegmentChangeKind,
+    /// Constant memory offset of an active segment; absent for passive ones
+    pub offset: Option<i64>,
+    pub from_bytes: Option<u64>,
+    pub to_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DataSegmentDiff {
+    pub changes: Vec<DataSegmentChange>,
+    pub unchanged: usize,
+}
+
+/// What two modules' contents differ in; cached by their hashes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BinaryDiff {
+    /// The modules' sections, functions and data are the same
+    pub identical: bool,
+    pub sections: Vec<SectionDelta>,
+    pub functions: FunctionDiff,
+    pub data_segments: DataSegmentDiff,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct WasmComparison {
+    pub from: ComparedBinary,
+    pub to: ComparedBinary,
+    /// False when a version has no stored module; `missing` names it and
+    /// `diff` is absent
+    pub complete: bool,
+    pub missing: Vec<String>,
+    /// Section sizes of whichever modules are stored, for partial results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sections: Option<Vec<SectionDelta>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<BinaryDiff>,
+}
+
+/// The parts of a module that are compared
+#[derive(Debug, Default)]
+struct ModuleSummary {
+    /// Section name and content bytes, in module order; repeated custom
+    /// sections are added up
+    sections: Vec<(String, u64)>,
+    functions: Vec<FunctionBody>,
+    has_names: bool,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug)]
+struct FunctionBody {
+    index: u32,
+    name: Option<String>,
+    size: u64,
+    hash: String,
+}
+
+#[derive(Debug)]
+struct Segment {
+    index: u32,
+    offset: Option<i64>,
+    size: u64,
+    hash: String,
+}
+
+impl ModuleSummary {
+    fn add_section(&mut self, name: String, bytes: u64) {
+        match self
+            .sections
+            .iter_mut()
+            .find(|(section, _)| *section == name)
+        {
+            Some((_, total)) => *total += bytes,
+            None => self.sections.push((name, bytes)),
+        }
+    }
+}
+
+fn section_name(id: u8) -> &'static str {
+    match id {
+        1 => "type",
+        2 => "import",
+        3 => "function",
+        4 => "table",
+        5 => "memory",
+        6 => "global",
+        7 => "export",
+        8 => "start",
+        9 => "element",
+        10 => "code",
+        11 => "data",
+        12 => "datacount",
+        13 => "tag",
+        _ => "unknown",
+    }
+}
+
+fn code_hash(bytes: &[u8]) -> String {
+    let mut hash = shared::source_storage::compute_sha256(bytes);
+    hash.truncate(CODE_HASH_LEN);
+    hash
+}
+
+fn summarize(wasm: &[u8]) -> Result<ModuleSummary, wasmparser::BinaryReaderError> {
+    let mut summary = ModuleSummary::default();
+    let mut imported_functions = 0u32;
+    let mut defined = 0u32;
+    let mut names = BTreeMap::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        let payload = payload?;
+        if let Some((id, range)) = payload.as_section() {
+            let name = match &payload {
+                Payload::CustomSection(custom) => format!("custom:{}", custom.name()),
+                _ => section_name(id).to_string(),
+            };
+            summary.add_section(name, range.len() as u64);
+        }
+        match payload {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if let TypeRef::Func(_) = import?.ty {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let bytes = &wasm[body.range()];
+                summary.functions.push(FunctionBody {
+                    index: imported_functions + defined,
+                    name: None,
+                    size: bytes.len() as u64,
+                    hash: code_hash(bytes),
+                });
+                defined += 1;
+            }
+            Payload::DataSection(reader) => {
+                for (index, segment) in reader.into_iter().enumerate() {
+                    let segment = segment?;
+                    let offset = match segment.kind {
+                        DataKind::Active { offset_expr, .. } => {
+                            match offset_expr.get_operators_reader().read()? {
+                                Operator::I32Const { value } => Some(i64::from(value)),
+                                Operator::I64Const { value } => Some(value),
+                                _ => None,
+                            }
+                        }
+                        DataKind::Passive => None,
+                    };
+                    summary.segments.push(Segment {
+                        index: index as u32,
+                        offset,
+                        size: segment.data.len() as u64,
+                        hash: code_hash(segment.data),
+                    });
+                }
+            }
+            Payload::CustomSection(custom) => {
+                if let KnownCustom::Name(reader) = custom.as_known() {
+                    summary.has_names = true;
+                    for name in reader {
+                        if let Name::Function(functions) = name? {
+                            for naming in functions {
+                                let naming = naming?;
+                                names.insert(naming.index, naming.name.to_string());
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for function in &mut summary.functions {
+        function.name = names.remove(&function.index);
+    }
+    Ok(summary)
+}
+
+/// Sections of both modules, in the order they first appear
+fn section_deltas(from: Option<&ModuleSummary>, to: Option<&ModuleSummary>) -> Vec<SectionDelta> {
+    let sizes = |summary: Option<&ModuleSummary>| -> BTreeMap<String, u64> {
+        summary
+            .map(|summary| summary.sections.iter().cloned().collect())
+            .unwrap_or_default()
+    };
+    let (from_sizes, to_sizes) = (sizes(from), sizes(to));
+
+    let mut order: Vec<&String> = Vec::new();
+    for summary in [from, to].into_iter().flatten() {
+        for (name, _) in &summary.sections {
+            if !order.contains(&name) {
+                order.push(name);
+            }
+        }
+    }
+    order
+        .into_iter()
+        .map(|name| {
+            let from_bytes = from_sizes.get(name).copied();
+            let to_bytes = to_sizes.get(name).copied();
+            SectionDelta {
+                section: name.clone(),
+                from_bytes,
+                to_bytes,
+                delta: to_bytes.unwrap_or(0) as i64 - from_bytes.unwrap_or(0) as i64,
+            }
+        })
+        .collect()
+}
+
+/// How functions are told apart when matching them up
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum FunctionKey {
+    Name(String),
+    Index(u32),
+}
+
+fn diff_functions(from: &ModuleSummary, to: &ModuleSummary) -> FunctionDiff {
+    let matched_by = if from.has_names && to.has_names {
+        FunctionMatching::Name
+    } else {
+        FunctionMatching::Index
+    };
+    let keyed = |summary: &'_ ModuleSummary| -> BTreeMap<FunctionKey, usize> {
+        let mut keyed = BTreeMap::new();
+        for (position, function) in summary.functions.iter().enumerate() {
+            let key = match (&function.name, matched_by) {
+                (Some(name), FunctionMatching::Name) => FunctionKey::Name(name.clone()),
+                _ => FunctionKey::Index(function.index),
+            };
+            // Two functions can share a name; the later ones go by index
+            if keyed.contains_key(&key) {
+                keyed.insert(FunctionKey::Index(function.index), position);
+            } else {
+                keyed.insert(key, position);
+            }
+        }
+        keyed
+    };
+    let (from_keys, to_keys) = (keyed(from), keyed(to));
+    let reference = |function: &FunctionBody| FunctionRef {
+        index: function.index,
+        name: function.name.clone(),
+        size: function.size,
+    };
+
+    let mut diff = FunctionDiff {
+        matched_by,
+        added: Vec::new(),
+        removed: Vec::new(),
+        changed: Vec::new(),
+        unchanged: 0,
+    };
+    for (key, &position) in &from_keys {
+        let before = &from.functions[position];
+        match to_keys.get(key).map(|&position| &to.functions[position]) {
+            None => diff.removed.push(reference(before)),
+            Some(after) if after.hash == before.hash => diff.unchanged += 1,
+            Some(after) => diff.changed.push(FunctionChange {
+                name: after.name.clone().or_else(|| before.name.clone()),
+                from_index: before.index,
+                to_index: after.index,
+                from_size: before.size,
+                to_size: after.size,
+                from_hash: before.hash.clone(),
+                to_hash: after.hash.clone(),
+            }),
+        }
+    }
+    for (key, &position) in &to_keys {
+        if !from_keys.contains_key(key) {
+            diff.added.push(reference(&to.functions[position]));
+        }
+    }
+    diff.added.sort_by_key(|function| function.index);
+    diff.removed.sort_by_key(|function| function.index);
+    diff.changed.sort_by_key(|function| function.to_index);
+    diff
+}
+
+fn diff_segments(from: &ModuleSummary, to: &ModuleSummary) -> DataSegmentDiff {
+    // Passive segments have no offset; they are matched by index after the
+    // active ones
+    let keyed = |summary: &'_ ModuleSummary| -> BTreeMap<(bool, i64), usize> {
+        summary
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(position, segment)| match segment.offset {
+                Some(offset) => ((false, offset), position),
+                None => ((true, i64::from(segment.index)), position),
+            })
+            .collect()
+    };
+    let (from_keys, to_keys) = (keyed(from), keyed(to));
+
+    let mut diff = DataSegmentDiff {
+        changes: Vec::new(),
+        unchanged: 0,
+    };
+    let mut keys: Vec<&(bool, i64)> = from_keys.keys().chain(to_keys.keys()).collect();
+    keys.sort();
+    keys.dedup();
+    for key in keys {
+        let before = from_keys.get(key).map(|&position| &from.segments[position]);
+        let after = to_keys.get(key).map(|&position| &to.segments[position]);
+        let change = match (before, after) {
+            (Some(before), Some(after)) if before.hash == after.hash => {
+                diff.unchanged += 1;
+                continue;
+            }
+            (Some(_), Some(_)) => SegmentChangeKind::Changed,
+            (None, Some(_)) => SegmentChangeKind::Added,
+            (Some(_), None) => SegmentChangeKind::Removed,
+            (None, None) => continue,
+        };
+        diff.changes.push(DataSegmentChange {
+            change,
+            offset: before.or(after).and_then(|segment| segment.offset),
+            from_bytes: before.map(|segment| segment.size),
+            to_bytes: after.map(|segment| segment.size),
+        });
+    }
+    diff
+}
+
+fn diff_modules(from: &ModuleSummary, to: &ModuleSummary) -> BinaryDiff {
+    let sections = section_deltas(Some(from), Some(to));
+    let functions = diff_functions(from, to);
+    let data_segments = diff_segments(from, to);
+    BinaryDiff {
+        identical: sections
+            .iter()
+            .all(|section| section.from_bytes == section.to_bytes)
+            && functions.added.is_empty()
+            && functions.removed.is_empty()
+            && functions.changed.is_empty()
+            && data_segments.changes.is_empty(),
+        sections,
+        functions,
+        data_segments,
+    }
+}
+
+/// The version's stored module and its metadata, if one was uploaded
+async fn load_module(
+    state: &AppState,
+    version: &ContractVersion,
+) -> ApiResult<Option<(ContractSource, Vec<u8>)>> {
+    let source: Option<ContractSource> = sqlx::query_as(
+        "SELECT * FROM contract_sources WHERE contract_version_id = $1 AND source_format = $2",
+    )
+    .bind(version.id)
+    .bind(SourceFormat::Wasm)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|err| db_internal_error("fetch stored WASM", err))?;
+    let Some(source) = source else {
+        return Ok(None);
+    };
+
+    let bytes = state
+        .source_storage
+        .retrieve_source(&source.storage_backend, &source.storage_key)
+        .await
+        .map_err(|e| ApiError::internal(format!("source storage error: {}", e)))?;
+    if shared::source_storage::compute_sha256(&bytes) != source.source_hash {
+        return Err(ApiError::internal("Stored WASM integrity check failed"));
+    }
+    Ok(Some((source, bytes)))
+}
+
+fn parse_module(version: &str, wasm: &[u8]) -> ApiResult<ModuleSummary> {
+    summarize(wasm).map_err(|err| {
+        ApiError::unprocessable(
+            "InvalidWasm",
+            format!(
+                "The stored WASM of version {} could not be parsed: {}",
+                version, err
+            ),
+        )
+    })
+}
+
+fn compared(
+    version: &ContractVersion,
+    module: Option<&(ContractSource, Vec<u8>)>,
+) -> ComparedBinary {
+    ComparedBinary {
+        version: version.version.clone(),
+        wasm_hash: version.wasm_hash.clone(),
+        stored: module.is_some(),
+        size: module.map(|(source, _)| source.source_size),
+    }
+}
+
+/// Section, function and data segment differences between two versions'
+/// stored WASM.
+#[utoipa::path(
+    get,
+    path = "/api/contracts/{id}/versions/compare",
+    params(
+        ("id" = String, Path, description = "Contract UUID or address"),
+        WasmCompareQuery
+    ),
+    responses(
+        (status = 200, description = "Binary differences, partial when a version has no stored WASM", body = WasmComparison),
+        (status = 400, description = "Missing `from` or `to`"),
+        (status = 404, description = "Unknown contract or version"),
+        (status = 422, description = "A stored WASM could not be parsed")
+    ),
+    tag = "Versions"
+)]
+pub async fn compare_version_wasm(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    params: Result<Query<WasmCompareQuery>, QueryRejection>,
+) -> ApiResult<Json<WasmComparison>> {
+    let Query(query) = params.map_err(map_query_rejection)?;
+    let (contract_uuid, contract_id) = fetch_contract_identity(&state, &id).await?;
+    let from_version =
+        fetch_contract_version(&state, contract_uuid, &contract_id, &query.from).await?;
+    let to_version = fetch_contract_version(&state, contract_uuid, &contract_id, &query.to).await?;
+    let from_module = load_module(&state, &from_version).await?;
+    let to_module = load_module(&state, &to_version).await?;
+
+    let mut comparison = WasmComparison {
+        from: compared(&from_version, from_module.as_ref()),
+        to: compared(&to_version, to_module.as_ref()),
+        complete: from_module.is_some() && to_module.is_some(),
+        missing: Vec::new(),
+        sections: None,
+        diff: None,
+    };
+
+    let (from_module, to_module) = match (from_module, to_module) {
+        (Some(from), Some(to)) => (from, to),
+        (from_module, to_module) => {
+            comparison.missing = [&comparison.from, &comparison.to]
+                .into_iter()
+                .filter(|side| !side.stored)
+                .map(|side| side.version.clone())
+                .collect();
+            let summary = |version: &ContractVersion, module: Option<(ContractSource, Vec<u8>)>| {
+                module
+                    .map(|(_, wasm)| parse_module(&version.version, &wasm))
+                    .transpose()
+            };
+            let from = summary(&from_version, from_module)?;
+            let to = summary(&to_version, to_module)?;
+            comparison.sections = Some(section_deltas(from.as_ref(), to.as_ref()));
+            return Ok(Json(comparison));
+        }
+    };
+
+    let cache_key = format!("{}:{}", from_module.0.source_hash, to_module.0.source_hash);
+    if let (Some(cached), true) = state.cache.get(CACHE_NAMESPACE, &cache_key).await {
+        if let Ok(diff) = serde_json::from_str(&cached) {
+            comparison.diff = Some(diff);
+            return Ok(Json(comparison));
+        }
+    }
+
+    let (from_name, to_name) = (from_version.version, to_version.version);
+    let diff = tokio::task::spawn_blocking(move || -> ApiResult<BinaryDiff> {
+        let from = parse_module(&from_name, &from_module.1)?;
+        let to = parse_module(&to_name, &to_module.1)?;
+        Ok(diff_modules(&from, &to))
+    })
+    .await
+    .map_err(|e| ApiError::internal(format!("failed to compare WASM: {}", e)))??;
+
+    if let Ok(serialized) = serde_json::to_string(&diff) {
+        state
+            .cache
+            .put(CACHE_NAMESPACE, &cache_key, serialized, None)
+            .await;
+    }
+    comparison.diff = Some(diff);
+    Ok(Json(comparison))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wasm_metadata::fixtures::{custom_section, leb, section};
+
+    /// A `() -> ()` function body computing `i32.const value; drop`
+    fn body(value: u8) -> Vec<u8> {
+        let code = [0x00, 0x41, value, 0x1a, 0x0b];
+        let mut body = leb(code.len());
+        body.extend_from_slice(&code);
+        body
+    }
+
+    fn vec_of(items: &[Vec<u8>]) -> Vec<u8> {
+        let mut out = leb(items.len());
+        for item in items {
+            out.extend_from_slice(item);
+        }
+        out
+    }
+
+    /// A module defining one function per body value, with active data
+    /// segments at the given offsets and, optionally, function names
+    fn module(bodies: &[u8], segments: &[(u8, &[u8])], names: Option<&[&str]>) -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        wasm.extend(section(1, &vec_of(&[vec![0x60, 0, 0]])));
+        wasm.extend(section(3, &vec_of(&vec![vec![0]; bodies.len()])));
+        wasm.extend(section(5, &vec_of(&[vec![0, 1]])));
+        let bodies: Vec<Vec<u8>> = bodies.iter().map(|&value| body(value)).collect();
+        wasm.extend(section(10, &vec_of(&bodies)));
+        let segments: Vec<Vec<u8>> = segments
+            .iter()
+            .map(|(offset, data)| {
+                let mut segment = vec![0, 0x41, *offset, 0x0b];
+                segment.extend(leb(data.len()));
+                segment.extend_from_slice(data);
+                segment
+            })
+            .collect();
+        wasm.extend(section(11, &vec_of(&segments)));
+        if let Some(names) = names {
+            let namings: Vec<Vec<u8>> = names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| {
+                    let mut naming = leb(index);
+                    naming.extend(leb(name.len()));
+                    naming.extend_from_slice(name.as_bytes());
+                    naming
+                })
+                .collect();
+            let functions = vec_of(&namings);
+            let mut subsection = vec![1];
+            subsection.extend(leb(functions.len()));
+            subsection.extend(functions);
+            wasm.extend(custom_section("name", &subsection));
+        }
+        wasm
+    }
+
+    fn compare(from: &[u8], to: &[u8]) -> BinaryDiff {
+        diff_modules(&summarize(from).unwrap(), &summarize(to).unwrap())
+    }
+
+    #[test]
+    fn the_same_module_is_identical() {
+        let wasm = module(&[1, 2], &[(0, b"hello")], Some(&["init", "transfer"]));
+        let diff = compare(&wasm, &wasm);
+        assert!(diff.identical);
+        assert_eq!(diff.functions.unchanged, 2);
+        assert_eq!(diff.data_segments.unchanged, 1);
+        assert!(diff.sections.iter().all(|section| section.delta == 0));
+    }
+
+    #[test]
+    fn named_functions_are_matched_by_name() {
+        let from = module(&[1, 2, 3], &[], Some(&["init", "transfer", "burn"]));
+        // `transfer` changed, `burn` was removed and `mint` added before it
+        let to = module(&[1, 4, 5], &[], Some(&["init", "mint", "transfer"]));
+        let diff = compare(&from, &to);
+
+        assert!(!diff.identical);
+        assert_eq!(diff.functions.matched_by, FunctionMatching::Name);
+        assert_eq!(diff.functions.unchanged, 1);
+        let names = |functions: &[FunctionRef]| -> Vec<Option<String>> {
+            functions.iter().map(|f| f.name.clone()).collect()
+        };
+        assert_eq!(names(&diff.functions.added), [Some("mint".to_string())]);
+        assert_eq!(names(&diff.functions.removed), [Some("burn".to_string())]);
+        let changed = &diff.functions.changed[0];
+        assert_eq!(changed.name.as_deref(), Some("transfer"));
+        assert_eq!((changed.from_index, changed.to_index), (1, 2));
+        assert_ne!(changed.from_hash, changed.to_hash);
+    }
+
+    #[test]
+    fn unnamed_functions_are_matched_by_index() {
+        let from = module(&[1, 2], &[], None);
+        let to = module(&[1, 3, 4], &[], Some(&["init", "transfer", "mint"]));
+        let diff = compare(&from, &to);
+
+        assert_eq!(diff.functions.matched_by, FunctionMatching::Index);
+        assert_eq!(diff.functions.changed.len(), 1);
+        assert_eq!(diff.functions.changed[0].from_index, 1);
+        assert_eq!(diff.functions.added[0].index, 2);
+        let names = diff
+            .sections
+            .iter()
+            .find(|s| s.section == "custom:name")
+            .unwrap();
+        assert_eq!(names.from_bytes, None);
+        assert!(names.delta > 0);
+    }
+
+    #[test]
+    fn data_segments_are_matched_by_offset() {
+        let from = module(&[1], &[(0, b"hello"), (16, b"world")], None);
+        let to = module(&[1], &[(0, b"hello"), (16, b"earth!"), (32, b"new")], None);
+        let diff = compare(&from, &to);
+
+        assert_eq!(diff.data_segments.unchanged, 1);
+        assert_eq!(
+            diff.data_segments.changes,
+            [
+                DataSegmentChange {
+                    change: SegmentChangeKind::Changed,
+                    offset: Some(16),
+                    from_bytes: Some(5),
+                    to_bytes: Some(6),
+                },
+                DataSegmentChange {
+                    change: SegmentChangeKind::Added,
+                    offset: Some(32),
+                    from_bytes: None,
+                    to_bytes: Some(3),
+                },
+            ]
+        );
+        let data = diff.sections.iter().find(|s| s.section == "data").unwrap();
+        assert!(data.delta > 0);
+    }
+
+    #[test]
+    fn one_stored_module_reports_its_sections_only() {
+        let wasm = summarize(&module(&[1], &[], None)).unwrap();
+        let sections = section_deltas(None, Some(&wasm));
+        assert!(sections.iter().all(|section| section.from_bytes.is_none()));
+        assert_eq!(sections[0].section, "type");
+    }
+
+    #[test]
+    fn truncated_modules_are_rejected() {
+        let wasm = module(&[1, 2], &[(0, b"hello")], None);
+        assert!(summarize(&wasm[..wasm.len() - 3]).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! Metadata that Soroban contracts embed in their WASM.
+//!
+//! The SDK writes `contractmetav0` custom sections holding a stream of XDR
+//! `ScMetaEntry` key/value pairs: the rustc (`rsver`) and SDK (`rssdkver`)
+//! versions, plus whatever the contract adds with `contractmeta!`. None of it
+//! is required to publish, so missing, oversized or malformed sections yield
+//! whatever could be read rather than an error.
+
+use std::collections::BTreeMap;
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+use serde::{Deserialize, Serialize};
+use stellar_xdr::curr::{Limited, Limits, ReadXdr, ScMetaEntry, ScMetaV0};
+
+const META_SECTION: &str = "contractmetav0";
+/// Metadata sections are a few hundred bytes; anything this large is skipped
+const MAX_META_SECTION_BYTES: u64 = 64 * 1024;
+
+const SDK_VERSION_KEY: &str = "rssdkver";
+const RUSTC_VERSION_KEY: &str = "rsver";
+/// Keys contracts use for their authors, matched case-insensitively
+const AUTHORS_KEYS: &[&str] = &["authors", "author"];
+/// Keys contracts use for their repository; `source_repo` is SEP-55's
+const REPOSITORY_KEYS: &[&str] = &["source_repo", "repository", "repo_url", "repo"];
+
+/// `contractmetav0` metadata of a contract version's WASM
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WasmMetadata {
+    /// Soroban SDK version, e.g. `22.0.7#211569aa…`
+    pub sdk_version: Option<String>,
+    pub rustc_version: Option<String>,
+    pub authors: Option<String>,
+    /// Repository URL; SEP-55 `github:owner/repo` is expanded
+    pub repository: Option<String>,
+    /// Every entry as embedded, including the ones above
+    pub entries: BTreeMap<String, String>,
+}
+
+impl WasmMetadata {
+    fn from_entries(entries: BTreeMap<String, String>) -> Option<Self> {
+        if entries.is_empty() {
+            return None;
+        }
+        let lookup = |keys: &[&str]| {
+            keys.iter().find_map(|key| {
+                entries
+                    .iter()
+                    .find(|(k, v)| k.eq_ignore_ascii_case(key) && !v.trim().is_empty())
+                    .map(|(_, v)| v.trim().to_string())
+            })
+        };
+        Some(Self {
+            sdk_version: lookup(&[SDK_VERSION_KEY]),
+            rustc_version: lookup(&[RUSTC_VERSION_KEY]),
+            authors: lookup(AUTHORS_KEYS),
+            repository: lookup(REPOSITORY_KEYS).and_then(|repo| repository_url(&repo)),
+            entries,
+        })
+    }
+}
+
+/// `https://` URL of a repository reference, or `None` when it isn't one
+fn repository_url(reference: &str) -> Option<String> {
+    if let Some(path) = reference.strip_prefix("github:") {
+        let path = path.trim_matches('/');
+        return (path.split('/').count() == 2 && !path.contains("//"))
+            .then(|| format!("https://github.com/{}", path));
+    }
+    (reference.starts_with("https://") || reference.starts_with("http://"))
+        .then(|| reference.to_string())
+}
+
+/// Metadata of an in-memory WASM module.
+pub fn extract(wasm: &[u8]) -> Option<WasmMetadata> {
+    read(Cursor::new(wasm))
+}
+
+/// Metadata of the WASM module in `reader`. Only metadata sections are
+/// read; every other section is skipped over.
+pub fn read<R: Read + Seek>(mut reader: R) -> Option<WasmMetadata> {
+    let mut data = Vec::new();
+    // A malformed module keeps the sections read before the damage
+    let _ = read_custom_sections(&mut reader, META_SECTION, MAX_META_SECTION_BYTES, &mut data);
+    WasmMetadata::from_entries(decode_entries(&data))
+}
+
+/// Appends the contents of every custom section called `section` that is
+/// no larger than `max_bytes` to `out`; every other section is skipped.
+pub(crate) fn read_custom_sections<R: Read + Seek>(
+    reader: &mut R,
+    section: &str,
+    max_bytes: u64,
+    out: &mut Vec<u8>,
+) -> io::Result<()> {
+    let mut header = [0u8; 8];
+    reader.read_exact(&mut header)?;
+    if header[..4] != *b"\0asm" {
+        return Ok(());
+    }
+
+    loop {
+        let mut id = [0u8; 1];
+        if reader.read(&mut id)? == 0 {
+            return Ok(());
+        }
+        let (size, _) = read_u32_leb(reader)?;
+        let size = u64::from(size);
+        if id[0] != 0 {
+            reader.seek(SeekFrom::Current(size as i64))?;
+            continue;
+        }
+
+        // Custom section: a name, then its contents
+        let (name_len, leb_len) = read_u32_leb(reader)?;
+        let name_len = u64::from(name_len);
+        let Some(contents_len) = size.checked_sub(leb_len + name_len) else {
+            return Err(io::ErrorKind::InvalidData.into());
+        };
+        let mut name = Vec::new();
+        reader.take(name_len).read_to_end(&mut name)?;
+        if name == section.as_bytes() && contents_len <= max_bytes {
+            let read = reader.take(contents_len).read_to_end(out)?;
+            if read as u64 != contents_len {
+                return Err(io::ErrorKind::UnexpectedEof.into());
+            }
+        } else {
+            reader.seek(SeekFrom::Current(contents_len as i64))?;
+        }
+    }
+}
+
+/// Reads an unsigned LEB128 `u32`, returning it and its encoded length.
+fn read_u32_leb<R: Read>(reader: &mut R) -> io::Result<(u32, u64)> {
+    let mut value = 0u32;
+    for index in 0..5 {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        value |= u32::from(byte[0] & 0x7f) << (7 * index);
+        if byte[0] & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    Err(io::ErrorKind::InvalidData.into())
+}
+
+/// Decodes the `ScMetaEntry` stream up to its end or its first malformed
+/// entry. A key embedded twice keeps its last value.
+fn decode_entries(data: &[u8]) -> BTreeMap<String, String> {
+    let mut entries = BTreeMap::new();
+    let mut limited = Limited::new(Cursor::new(data), Limits::len(data.len()));
+    for entry in ScMetaEntry::read_xdr_iter(&mut limited) {
+        let Ok(ScMetaEntry::ScMetaV0(ScMetaV0 { key, val })) = entry else {
+            break;
+        };
+        entries.insert(key.to_utf8_string_lossy(), val.to_utf8_string_lossy());
+    }
+    entries
+}
+
+/// Hand-built WASM modules for tests
+#[cfg(test)]
+pub(crate) mod fixtures {
+    pub(crate) fn leb(mut value: usize) -> Vec<u8> {
+        let mut out = Vec::new();
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                out.push(byte);
+                return out;
+            }
+            out.push(byte | 0x80);
+        }
+    }
+
+    pub(crate) fn section(id: u8, contents: &[u8]) -> Vec<u8> {
+        let mut section = vec![id];
+        section.extend(leb(contents.len()));
+        section.extend_from_slice(contents);
+        section
+    }
+
+    pub(crate) fn custom_section(name: &str, contents: &[u8]) -> Vec<u8> {
+        let mut body = leb(name.len());
+        body.extend_from_slice(name.as_bytes());
+        body.extend_from_slice(contents);
+        section(0, &body)
+    }
+
+    pub(crate) fn module(sections: &[Vec<u8>]) -> Vec<u8> {
+        let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+        // An empty type section, which the reader has to skip
+        wasm.extend_from_slice(&[1, 1, 0]);
+        for section in sections {
+            wasm.extend_from_slice(section);
+        }
+        wasm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use stellar_xdr::curr::WriteXdr;
+
+    use super::fixtures::{custom_section, module};
+    use super::*;
+
+    fn meta(entries: &[(&str, &str)]) -> Vec<u8> {
+        entries
+            .iter()
+            .flat_map(|(key, val)| {
+                ScMetaEntry::ScMetaV0(ScMetaV0 {
+                    key: key.to_string().try_into().unwrap(),
+                    val: val.to_string().try_into().unwrap(),
+                })
+                .to_xdr(Limits::none())
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn extracts_sdk_toolchain_authors_and_repository() {
+        let wasm = module(&[
+            custom_section("contractspecv0", &[0xde, 0xad]),
+            custom_section(
+                META_SECTION,
+                &meta(&[
+                    ("rsver", "1.81.0"),
+                    (
+                        "rssdkver",
+                        "22.0.7#211569aa49c8d896877dfca1f2eb4fe9071121c8",
+                    ),
+                ]),
+            ),
+            custom_section(
+                META_SECTION,
+                &meta(&[
+                    ("Author", "Acme Labs"),
+                    ("source_repo", "github:acme/escrow"),
+                ]),
+            ),
+        ]);
+
+        let found = extract(&wasm).expect("metadata");
+        assert_eq!(
+            found.sdk_version.as_deref(),
+            Some("22.0.7#211569aa49c8d896877dfca1f2eb4fe9071121c8")
+        );
+        assert_eq!(found.rustc_version.as_deref(), Some("1.81.0"));
+        assert_eq!(found.authors.as_deref(), Some("Acme Labs"));
+        assert_eq!(
+            found.repository.as_deref(),
+            Some("https://github.com/acme/escrow")
+        );
+        assert_eq!(found.entries.len(), 4);
+        assert_eq!(found.entries["source_repo"], "github:acme/escrow");
+    }
+
+    #[test]
+    fn repository_must_be_a_url() {
+        assert_eq!(
+            repository_url("https://gitlab.com/acme/escrow").as_deref(),
+            Some("https://gitlab.com/acme/escrow")
+        );
+        assert_eq!(repository_url("github:acme"), None);
+        assert_eq!(repository_url("acme/escrow"), None);
+
+        let wasm = module(&[custom_section(
+            META_SECTION,
+            &meta(&[("repository", "somewhere")]),
+        )]);
+        let found = extract(&wasm).expect("metadata");
+        assert_eq!(found.repository, None);
+        assert_eq!(found.entries["repository"], "somewhere");
+    }
+
+    #[test]
+    fn modules_without_metadata_have_none() {
+        assert_eq!(extract(&module(&[])), None);
+        assert_eq!(extract(&module(&[custom_section(META_SECTION, &[])])), None);
+        assert_eq!(extract(b"not a wasm module"), None);
+        assert_eq!(extract(b""), None);
+    }
+
+    #[test]
+    fn malformed_metadata_keeps_what_was_readable() {
+        let mut stream = meta(&[("rsver", "1.81.0")]);
+        let second = meta(&[("rssdkver", "22.0.7")]);
+        stream.extend_from_slice(&second[..second.len() - 3]);
+        let found = extract(&module(&[custom_section(META_SECTION, &stream)])).expect("metadata");
+        assert_eq!(found.rustc_version.as_deref(), Some("1.81.0"));
+        assert_eq!(found.sdk_version, None);
+
+        // A module cut off inside a later section
+        let mut wasm = module(&[custom_section(META_SECTION, &meta(&[("rsver", "1.81.0")]))]);
+        wasm.extend_from_slice(&[0, 0xff, 0xff]);
+        assert_eq!(
+            extract(&wasm)
+                .and_then(|found| found.rustc_version)
+                .as_deref(),
+            Some("1.81.0")
+        );
+    }
+
+    #[test]
+    fn oversized_sections_are_skipped() {
+        let mut entries = meta(&[("rsver", "1.81.0")]);
+        entries.resize(MAX_META_SECTION_BYTES as usize + 1, 0);
+        let wasm = module(&[custom_section(META_SECTION, &entries)]);
+        assert_eq!(extract(&wasm), None);
+    }
+}
@@ -0,0 +1,646 @@
+//! Catalog of the webhook events the registry sends, and their payload
+//! versions.
+//!
+//! Every webhook body carries `event` and `event_version`. A payload version
+//! covers every event at once: version 2 renamed the subscription events
+//! (`contract.version` became `contract.published`, `contract.incident`
+//! became `incident.created` or `incident.resolved`, and so on), nothing
+//! else changed. Subscriptions are pinned to a version through their
+//! `payload_version`; a pinned version that is past its sunset is delivered
+//! as the current one. Payloads are stored in the current version when
+//! queued and rewritten for the subscription's version when sent, see
+//! [`for_version`].
+//!
+//! The JSON Schemas served by `GET /api/webhooks/events` are built here from
+//! the same field lists the samples of `POST /api/webhooks/test` are checked
+//! against; the subscription notices are checked against them in
+//! [`crate::subscription_notifications`].
+
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Value};
+
+use crate::subscription_models::SubscriptionEvent;
+
+/// Version new subscriptions and every non-subscription webhook get
+pub const CURRENT_PAYLOAD_VERSION: i32 = 2;
+
+/// A payload version and, once it is deprecated, the moment pinned
+/// subscriptions stop receiving it
+#[derive(Debug, Clone, Copy)]
+pub struct PayloadVersion {
+    pub version: i32,
+    pub sunset: Option<&'static str>,
+}
+
+pub const PAYLOAD_VERSIONS: &[PayloadVersion] = &[
+    PayloadVersion {
+        version: 1,
+        sunset: Some("2027-04-30T00:00:00Z"),
+    },
+    PayloadVersion {
+        version: 2,
+        sunset: None,
+    },
+];
+
+impl PayloadVersion {
+    pub fn sunset_at(&self) -> Option<DateTime<Utc>> {
+        self.sunset.map(|at| {
+            DateTime::parse_from_rfc3339(at)
+                .expect("payload version sunsets are RFC 3339")
+                .with_timezone(&Utc)
+        })
+    }
+
+    /// Past its sunset: no longer delivered or accepted for subscriptions
+    pub fn is_retired(&self, now: DateTime<Utc>) -> bool {
+        self.sunset_at().is_some_and(|sunset| now >= sunset)
+    }
+}
+
+pub fn payload_version(version: i32) -> Option<PayloadVersion> {
+    PAYLOAD_VERSIONS
+        .iter()
+        .find(|known| known.version == version)
+        .copied()
+}
+
+/// The version a subscription pinned to `pinned` is sent at `now`
+pub fn delivered_version(pinned: i32, now: DateTime<Utc>) -> i32 {
+    match payload_version(pinned) {
+        Some(version) if !version.is_retired(now) => pinned,
+        _ => CURRENT_PAYLOAD_VERSION,
+    }
+}
+
+/// How a payload is laid out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    /// `contract`, `subject` and the event's fields under `details`
+    Contract,
+    /// The event's fields at the top level
+    Flat,
+}
+
+/// One event type, in the versions between `since` and `until`
+#[derive(Debug)]
+pub struct EventType {
+    pub name: &'static str,
+    pub description: &'static str,
+    since: i32,
+    until: Option<i32>,
+    /// What subscriptions ask for to receive it; `None` for events sent
+    /// outside subscriptions
+    pub subscription_event: Option<SubscriptionEvent>,
+    /// For incident events split by state, the `details.state` it carries
+    incident_state: Option<&'static str>,
+    shape: Shape,
+    fields: fn() -> Vec<(&'static str, Value)>,
+    sample: fn() -> Value,
+    sample_subject: &'static str,
+}
+
+impl EventType {
+    fn exists_in(&self, version: i32) -> bool {
+        version >= self.since && self.until.is_none_or(|until| version <= until)
+    }
+
+    /// JSON Schema of the event's payload in `version`
+    pub fn schema(&self, version: i32) -> Value {
+        let fields = (self.fields)();
+        let mut properties = vec![
+            ("event", json!({ "const": self.name })),
+            ("event_version", json!({ "const": version })),
+        ];
+        match self.shape {
+            Shape::Contract => properties.extend([
+                (
+                    "contract",
+                    object(vec![
+                        ("id", uuid()),
+                        ("contract_id", string()),
+                        ("name", string()),
+                        ("network", string()),
+                    ]),
+                ),
+                ("subject", string()),
+                ("details", object(fields)),
+            ]),
+            Shape::Flat => properties.extend(fields),
+        }
+
+        let mut schema = object(properties);
+        schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+        schema["title"] = json!(self.name);
+        schema["description"] = json!(self.description);
+        schema
+    }
+
+    /// A payload of this event in `version`, with made-up values
+    pub fn sample(&self, version: i32) -> Value {
+        let mut payload = match self.shape {
+            Shape::Contract => json!({
+                "contract": {
+                    "id": "00000000-0000-0000-0000-000000000000",
+                    "contract_id": "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
+                    "name": "liquidity-pool",
+                    "network": "testnet",
+                },
+                "subject": self.sample_subject,
+                "details": (self.sample)(),
+            }),
+            Shape::Flat => (self.sample)(),
+        };
+        payload["event"] = json!(self.name);
+        payload["event_version"] = json!(version);
+        payload
+    }
+}
+
+pub static EVENT_TYPES: &[EventType] = &[
+    // Version 2
+    EventType {
+        name: "contract.published",
+        description: "A new version of the contract was published",
+        since: 2,
+        until: None,
+        subscription_event: Some(SubscriptionEvent::Version),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: version_fields,
+        sample: version_sample,
+        sample_subject: "liquidity-pool 1.1.0 published",
+    },
+    EventType {
+        name: "contract.verified",
+        description: "The contract passed source verification",
+        since: 2,
+        until: None,
+        subscription_event: Some(SubscriptionEvent::Verification),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: verification_fields,
+        sample: verification_sample,
+        sample_subject: "liquidity-pool passed verification",
+    },
+    EventType {
+        name: "incident.created",
+        description: "An incident naming the contract was opened",
+        since: 2,
+        until: None,
+        subscription_event: Some(SubscriptionEvent::Incident),
+        incident_state: Some("opened"),
+        shape: Shape::Contract,
+        fields: incident_fields,
+        sample: incident_opened_sample,
+        sample_subject: "[MAJOR] exploit incident opened for liquidity-pool",
+    },
+    EventType {
+        name: "incident.resolved",
+        description: "An incident naming the contract was resolved",
+        since: 2,
+        until: None,
+        subscription_event: Some(SubscriptionEvent::Incident),
+        incident_state: Some("resolved"),
+        shape: Shape::Contract,
+        fields: incident_fields,
+        sample: incident_resolved_sample,
+        sample_subject: "[MAJOR] exploit incident resolved for liquidity-pool",
+    },
+    EventType {
+        name: "security.advisory",
+        description: "The contract's newest build uses a toolchain a security advisory covers",
+        since: 2,
+        until: None,
+        subscription_event: Some(SubscriptionEvent::Security),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: security_fields,
+        sample: security_sample,
+        sample_subject: "[HIGH] Security advisory affects liquidity-pool: Storage TTL bug",
+    },
+    EventType {
+        name: "contract.stale_warning",
+        description: "The contract has gone quiet and is about to be marked stale",
+        since: 2,
+        until: None,
+        subscription_event: Some(SubscriptionEvent::Freshness),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: freshness_fields,
+        sample: freshness_sample,
+        sample_subject: "liquidity-pool will be marked stale on 2026-11-15",
+    },
+    // Version 1
+    EventType {
+        name: "contract.version",
+        description: "A new version of the contract was published",
+        since: 1,
+        until: Some(1),
+        subscription_event: Some(SubscriptionEvent::Version),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: version_fields,
+        sample: version_sample,
+        sample_subject: "liquidity-pool 1.1.0 published",
+    },
+    EventType {
+        name: "contract.verification",
+        description: "The contract passed source verification",
+        since: 1,
+        until: Some(1),
+        subscription_event: Some(SubscriptionEvent::Verification),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: verification_fields,
+        sample: verification_sample,
+        sample_subject: "liquidity-pool passed verification",
+    },
+    EventType {
+        name: "contract.incident",
+        description: "An incident naming the contract was opened or resolved",
+        since: 1,
+        until: Some(1),
+        subscription_event: Some(SubscriptionEvent::Incident),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: incident_fields,
+        sample: incident_opened_sample,
+        sample_subject: "[MAJOR] exploit incident opened for liquidity-pool",
+    },
+    EventType {
+        name: "contract.security",
+        description: "The contract's newest build uses a toolchain a security advisory covers",
+        since: 1,
+        until: Some(1),
+        subscription_event: Some(SubscriptionEvent::Security),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: security_fields,
+        sample: security_sample,
+        sample_subject: "[HIGH] Security advisory affects liquidity-pool: Storage TTL bug",
+    },
+    EventType {
+        name: "contract.freshness",
+        description: "The contract has gone quiet and is about to be marked stale",
+        since: 1,
+        until: Some(1),
+        subscription_event: Some(SubscriptionEvent::Freshness),
+        incident_state: None,
+        shape: Shape::Contract,
+        fields: freshness_fields,
+        sample: freshness_sample,
+        sample_subject: "liquidity-pool will be marked stale on 2026-11-15",
+    },
+    // Every version
+    EventType {
+        name: "incident.notification",
+        description: "An incident was opened, escalated or resolved; sent to incident notification recipients",
+        since: 1,
+        until: None,
+        subscription_event: None,
+        incident_state: None,
+        shape: Shape::Flat,
+        fields: incident_notification_fields,
+        sample: incident_notification_sample,
+        sample_subject: "",
+    },
+    EventType {
+        name: "channel.verification",
+        description: "Challenge a new webhook channel must echo back in its response body",
+        since: 1,
+        until: None,
+        subscription_event: None,
+        incident_state: None,
+        shape: Shape::Flat,
+        fields: channel_verification_fields,
+        sample: channel_verification_sample,
+        sample_subject: "",
+    },
+];
+
+/// The event types of `version`
+pub fn event_types(version: i32) -> impl Iterator<Item = &'static EventType> {
+    EVENT_TYPES
+        .iter()
+        .filter(move |event| event.exists_in(version))
+}
+
+/// The event called `name` in `version`
+pub fn event_type(name: &str, version: i32) -> Option<&'static EventType> {
+    event_types(version).find(|event| event.name == name)
+}
+
+/// The subscription event carrying `details` in `version`
+fn subscription_event_type(
+    event: SubscriptionEvent,
+    details: &Value,
+    version: i32,
+) -> Option<&'static EventType> {
+    let state = details.get("state").and_then(Value::as_str);
+    event_types(version).find(|candidate| {
+        candidate.subscription_event == Some(event)
+            && candidate
+                .incident_state
+                .is_none_or(|expected| Some(expected) == state)
+    })
+}
+
+/// Name of the subscription event carrying `details` in `version`
+pub fn subscription_event_name(event: SubscriptionEvent, details: &Value, version: i32) -> String {
+    subscription_event_type(event, details, version)
+        .map(|found| found.name.to_string())
+        .unwrap_or_else(|| format!("contract.{}", event.as_str()))
+}
+
+/// `payload`, written in any version, as `version` lays it out. Payloads of
+/// unknown events only have their `event_version` set.
+pub fn for_version(payload: &Value, version: i32) -> Value {
+    let mut payload = payload.clone();
+    let name = payload["event"].as_str().unwrap_or_default();
+    let renamed = EVENT_TYPES
+        .iter()
+        .find(|event| event.name == name)
+        .and_then(|event| event.subscription_event)
+        .and_then(|event| subscription_event_type(event, &payload["details"], version))
+        .map(|event| event.name);
+    if let Some(renamed) = renamed {
+        payload["event"] = json!(renamed);
+    }
+    if let Value::Object(fields) = &mut payload {
+        fields.insert("event_version".to_string(), json!(version));
+    }
+    payload
+}
+
+// Schema building blocks
+
+fn object(fields: Vec<(&'static str, Value)>) -> Value {
+    let required: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+    let properties: Map<String, Value> = fields
+        .into_iter()
+        .map(|(name, schema)| (name.to_string(), schema))
+        .collect();
+    json!({ "type": "object", "properties": properties, "required": required })
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn uuid() -> Value {
+    json!({ "type": "string", "format": "uuid" })
+}
+
+fn date_time() -> Value {
+    json!({ "type": "string", "format": "date-time" })
+}
+
+fn one_of(values: &[&str]) -> Value {
+    json!({ "type": "string", "enum": values })
+}
+
+fn nullable(mut schema: Value) -> Value {
+    let kind = schema["type"].clone();
+    schema["type"] = json!([kind, "null"]);
+    schema
+}
+
+// Event fields and samples
+
+fn version_fields() -> Vec<(&'static str, Value)> {
+    vec![
+        ("version", string()),
+        ("wasm_hash", string()),
+        ("source_url", nullable(string())),
+        ("release_notes", nullable(string())),
+        ("published_at", date_time()),
+    ]
+}
+
+fn version_sample() -> Value {
+    json!({
+        "version": "1.1.0",
+        "wasm_hash": "7d3c5e0f4b6a2a1e9c8d7f6e5d4c3b2a1f0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c",
+        "source_url": "https://github.com/acme/liquidity-pool",
+        "release_notes": "Fixes rounding in withdraw",
+        "published_at": "2026-10-01T12:00:00Z",
+    })
+}
+
+fn verification_fields() -> Vec<(&'static str, Value)> {
+    vec![("verification_id", uuid())]
+}
+
+fn verification_sample() -> Value {
+    json!({ "verification_id": "00000000-0000-0000-0000-000000000001" })
+}
+
+fn incident_fields() -> Vec<(&'static str, Value)> {
+    vec![
+        ("incident_id", uuid()),
+        ("incident_type", string()),
+        (
+            "severity",
+            one_of(&["critical", "major", "minor", "informational"]),
+        ),
+        ("state", one_of(&["opened", "resolved"])),
+        ("description", string()),
+        ("start_time", date_time()),
+        ("end_time", nullable(date_time())),
+    ]
+}
+
+fn incident_opened_sample() -> Value {
+    json!({
+        "incident_id": "00000000-0000-0000-0000-000000000002",
+        "incident_type": "exploit",
+        "severity": "major",
+        "state": "opened",
+        "description": "Withdrawals return more than deposited",
+        "start_time": "2026-10-01T12:00:00Z",
+        "end_time": null,
+    })
+}
+
+fn incident_resolved_sample() -> Value {
+    let mut sample = incident_opened_sample();
+    sample["state"] = json!("resolved");
+    sample["end_time"] = json!("2026-10-01T15:30:00Z");
+    sample
+}
+
+fn security_fields() -> Vec<(&'static str, Value)> {
+    vec![
+        ("advisory_id", uuid()),
+        ("title", string()),
+        ("severity", one_of(&["low", "medium", "high", "critical"])),
+        ("component", one_of(&["soroban-sdk", "rustc"])),
+        (
+            "affected_versions",
+            json!({ "type": "array", "items": { "type": "string" } }),
+        ),
+        ("matched_version", string()),
+        ("contract_version", string()),
+        ("url", nullable(string())),
+    ]
+}
+
+fn security_sample() -> Value {
+    json!({
+        "advisory_id": "00000000-0000-0000-0000-000000000003",
+        "title": "Storage TTL bug",
+        "severity": "high",
+        "component": "soroban-sdk",
+        "affected_versions": [">=20.0.0, <20.5.2"],
+        "matched_version": "20.3.1",
+        "contract_version": "1.1.0",
+        "url": "https://example.com/advisories/storage-ttl",
+    })
+}
+
+fn freshness_fields() -> Vec<(&'static str, Value)> {
+    vec![("warned_at", date_time()), ("stale_on", date_time())]
+}
+
+fn freshness_sample() -> Value {
+    json!({
+        "warned_at": "2026-10-16T00:00:00Z",
+        "stale_on": "2026-11-15T00:00:00Z",
+    })
+}
+
+fn incident_notification_fields() -> Vec<(&'static str, Value)> {
+    vec![
+        ("incident_id", uuid()),
+        ("contract_id", nullable(uuid())),
+        ("incident_type", string()),
+        (
+            "severity",
+            one_of(&["critical", "major", "minor", "informational"]),
+        ),
+        ("state", one_of(&["ongoing", "resolved"])),
+        ("description", string()),
+        ("start_time", date_time()),
+        ("end_time", nullable(date_time())),
+        ("acknowledged", json!({ "type": "boolean" })),
+        (
+            "escalation_step",
+            json!({ "type": "integer", "minimum": 0 }),
+        ),
+        ("subject", string()),
+    ]
+}
+
+fn incident_notification_sample() -> Value {
+    json!({
+        "incident_id": "00000000-0000-0000-0000-000000000002",
+        "contract_id": "00000000-0000-0000-0000-000000000000",
+        "incident_type": "exploit",
+        "severity": "major",
+        "state": "ongoing",
+        "description": "Withdrawals return more than deposited",
+        "start_time": "2026-10-01T12:00:00Z",
+        "end_time": null,
+        "acknowledged": false,
+        "escalation_step": 0,
+        "subject": "[MAJOR] exploit incident affecting contract 00000000-0000-0000-0000-000000000000 (ongoing)",
+    })
+}
+
+fn channel_verification_fields() -> Vec<(&'static str, Value)> {
+    vec![("channel_id", uuid()), ("challenge", string())]
+}
+
+fn channel_verification_sample() -> Value {
+    json!({
+        "channel_id": "00000000-0000-0000-0000-000000000004",
+        "challenge": "3f9a1c0e7b5d4e2a",
+    })
+}
+
+/// The field names `schema` describes, for checking payloads against it
+#[cfg(test)]
+pub(crate) fn schema_fields(schema: &Value) -> Vec<String> {
+    let mut fields: Vec<String> = schema["properties"]
+        .as_object()
+        .map(|properties| properties.keys().cloned().collect())
+        .unwrap_or_default();
+    fields.sort();
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(value: &Value) -> Vec<String> {
+        let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn every_version_has_one_event_per_name() {
+        for version in PAYLOAD_VERSIONS {
+            let mut names: Vec<&str> = event_types(version.version).map(|e| e.name).collect();
+            let count = names.len();
+            names.sort();
+            names.dedup();
+            assert_eq!(names.len(), count, "version {}", version.version);
+        }
+        assert!(event_type("contract.published", 2).is_some());
+        assert!(event_type("contract.published", 1).is_none());
+        assert!(event_type("contract.version", 1).is_some());
+        assert!(event_type("incident.notification", 1).is_some());
+        assert!(event_type("incident.notification", 2).is_some());
+    }
+
+    #[test]
+    fn samples_match_their_schemas() {
+        for version in PAYLOAD_VERSIONS {
+            for event in event_types(version.version) {
+                let schema = event.schema(version.version);
+                let sample = event.sample(version.version);
+                assert_eq!(keys(&sample), schema_fields(&schema), "{}", event.name);
+                assert_eq!(schema["properties"]["event"]["const"], event.name);
+                assert_eq!(sample["event_version"], version.version);
+                if event.shape == Shape::Contract {
+                    assert_eq!(
+                        keys(&sample["details"]),
+                        schema_fields(&schema["properties"]["details"]),
+                        "{}",
+                        event.name
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn payloads_are_rewritten_between_versions() {
+        let current = event_type("incident.resolved", 2).unwrap().sample(2);
+
+        let old = for_version(&current, 1);
+        assert_eq!(old["event"], "contract.incident");
+        assert_eq!(old["event_version"], 1);
+        assert_eq!(old["details"], current["details"]);
+
+        // Rows queued before version 2 are upgraded by the incident's state
+        assert_eq!(for_version(&old, 2), current);
+
+        let flat = event_type("channel.verification", 2).unwrap().sample(2);
+        assert_eq!(for_version(&flat, 1)["event"], "channel.verification");
+        assert_eq!(for_version(&flat, 1)["event_version"], 1);
+    }
+
+    #[test]
+    fn retired_versions_are_delivered_as_the_current_one() {
+        let sunset = payload_version(1).unwrap().sunset_at().unwrap();
+        let before = sunset - chrono::Duration::days(1);
+        assert_eq!(delivered_version(1, before), 1);
+        assert_eq!(delivered_version(1, sunset), CURRENT_PAYLOAD_VERSION);
+        assert_eq!(delivered_version(2, sunset), 2);
+        assert_eq!(delivered_version(99, before), CURRENT_PAYLOAD_VERSION);
+    }
+}
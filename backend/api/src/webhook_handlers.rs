@@ -0,0 +1,307 @@
+//! The webhook event catalog, see [`crate::webhook_events`].
+//!
+//!   GET  /api/webhooks/events?version=  – every event type with the JSON Schema of its payload
+//!   POST /api/webhooks/test             – a sample payload of any event and version, optionally sent to one of the caller's webhook channels
+//!
+//! Test sends go wherever the caller's channel points, so each caller gets
+//! `WEBHOOK_TEST_SENDS_PER_MINUTE` (default 5, see [`crate::config`]) of them
+//! a minute, and the channel must still resolve to a public address when
+//! sent to.
+
+use std::time::Instant;
+
+use axum::{
+    extract::{rejection::QueryRejection, Query, State},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthClaims,
+    channel_handlers::fetch_owned_channel,
+    error::{ApiError, ApiResult},
+    handlers::map_query_rejection,
+    incident_models::RenderedNotification,
+    incident_notifications::send_webhook_with_status,
+    state::AppState,
+    subscription_models::SubscriptionEvent,
+    webhook_events::{self, CURRENT_PAYLOAD_VERSION, PAYLOAD_VERSIONS},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadVersionStatus {
+    Current,
+    /// Still delivered to subscriptions pinned to it, until `sunset_at`
+    Deprecated,
+    /// Past its sunset; pinned subscriptions get the current version
+    Retired,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PayloadVersionInfo {
+    pub version: i32,
+    pub status: PayloadVersionStatus,
+    pub sunset_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct WebhookEventType {
+    /// Value of the payload's `event` field
+    pub event: String,
+    pub description: String,
+    /// What `events` of a subscription must include to receive it; absent
+    /// for events sent outside subscriptions
+    pub subscription_event: Option<SubscriptionEvent>,
+    /// JSON Schema (draft 2020-12) of the payload
+    #[schema(value_type = Object)]
+    pub schema: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct WebhookEventCatalog {
+    /// Version the events below are described in
+    pub payload_version: i32,
+    pub current_version: i32,
+    pub versions: Vec<PayloadVersionInfo>,
+    pub events: Vec<WebhookEventType>,
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookEventsQuery {
+    /// Payload version to describe; the current one by default
+    pub version: Option<i32>,
+}
+
+/// Body of `POST /api/webhooks/test`
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct WebhookTestRequest {
+    /// Event type, as named in `payload_version`
+    pub event: String,
+    /// The current version by default
+    pub payload_version: Option<i32>,
+    /// One of the caller's webhook channels to send the sample to; it
+    /// needn't be verified and the attempt doesn't count towards its health
+    pub channel_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct WebhookTestDelivery {
+    pub channel_id: Uuid,
+    pub delivered: bool,
+    /// HTTP status the webhook answered with, if it answered
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct WebhookTestResult {
+    pub event: String,
+    pub payload_version: i32,
+    /// The sample, exactly as a receiver gets it
+    #[schema(value_type = Object)]
+    pub payload: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivery: Option<WebhookTestDelivery>,
+}
+
+fn known_version(version: Option<i32>) -> ApiResult<i32> {
+    let version = version.unwrap_or(CURRENT_PAYLOAD_VERSION);
+    webhook_events::payload_version(version)
+        .map(|known| known.version)
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                "UnknownPayloadVersion",
+                format!(
+                    "No webhook payload version {}; versions are {}",
+                    version,
+                    PAYLOAD_VERSIONS
+                        .iter()
+                        .map(|known| known.version.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            )
+        })
+}
+
+fn catalog(version: i32, now: DateTime<Utc>) -> WebhookEventCatalog {
+    WebhookEventCatalog {
+        payload_version: version,
+        current_version: CURRENT_PAYLOAD_VERSION,
+        versions: PAYLOAD_VERSIONS
+            .iter()
+            .map(|known| PayloadVersionInfo {
+                version: known.version,
+                status: if known.version == CURRENT_PAYLOAD_VERSION {
+                    PayloadVersionStatus::Current
+                } else if known.is_retired(now) {
+                    PayloadVersionStatus::Retired
+                } else {
+                    PayloadVersionStatus::Deprecated
+                },
+                sunset_at: known.sunset_at(),
+            })
+            .collect(),
+        events: webhook_events::event_types(version)
+            .map(|event| WebhookEventType {
+                event: event.name.to_string(),
+                description: event.description.to_string(),
+                subscription_event: event.subscription_event,
+                schema: event.schema(version),
+            })
+            .collect(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/webhooks/events",
+    params(WebhookEventsQuery),
+    responses(
+        (status = 200, description = "Every webhook event of the version with its payload schema", body = WebhookEventCatalog),
+        (status = 400, description = "Unknown payload version")
+    ),
+    tag = "Subscriptions"
+)]
+pub async fn list_webhook_events(
+    query: Result<Query<WebhookEventsQuery>, QueryRejection>,
+) -> ApiResult<Json<WebhookEventCatalog>> {
+    let Query(query) = query.map_err(map_query_rejection)?;
+    let version = known_version(query.version)?;
+    Ok(Json(catalog(version, Utc::now())))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/test",
+    request_body = WebhookTestRequest,
+    responses(
+        (status = 200, description = "The sample payload, and how sending it went when a channel was given", body = WebhookTestResult),
+        (status = 400, description = "Unknown event or payload version, or not a webhook channel"),
+        (status = 401, description = "Missing or invalid API token"),
+        (status = 404, description = "Channel not found"),
+        (status = 429, description = "Too many test sends by the caller in the last minute")
+    ),
+    tag = "Subscriptions"
+)]
+pub async fn test_webhook(
+    State(state): State<AppState>,
+    claims: AuthClaims,
+    Json(req): Json<WebhookTestRequest>,
+) -> ApiResult<Json<WebhookTestResult>> {
+    let version = known_version(req.payload_version)?;
+    let event = webhook_events::event_type(&req.event, version).ok_or_else(|| {
+        ApiError::bad_request(
+            "UnknownWebhookEvent",
+            format!(
+                "No webhook event '{}' in payload version {}; events are {}",
+                req.event,
+                version,
+                webhook_events::event_types(version)
+                    .map(|event| event.name)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    })?;
+    let payload = event.sample(version);
+
+    let delivery = match req.channel_id {
+        Some(channel_id) => {
+            let channel = fetch_owned_channel(&state, channel_id, &claims).await?;
+            if channel.kind != "webhook" {
+                return Err(ApiError::bad_request(
+                    "NotAWebhookChannel",
+                    format!("Channel {} is an {} channel", channel_id, channel.kind),
+                ));
+            }
+            state
+                .webhook_test_sends
+                .check(&claims.sub, Instant::now())
+                .map_err(|retry_after| {
+                    ApiError::rate_limited(format!(
+                        "At most {} test webhooks a minute can be sent",
+                        state.config.webhook_test_sends_per_minute
+                    ))
+                    .with_retry_after(retry_after)
+                })?;
+            let notification = RenderedNotification {
+                subject: String::new(),
+                body: String::new(),
+                payload: payload.clone(),
+            };
+            let (status_code, result) =
+                send_webhook_with_status(&state.outbound, &channel.target, &notification).await;
+            tracing::info!(
+                %channel_id,
+                event = event.name,
+                payload_version = version,
+                ?status_code,
+                delivered = result.is_ok(),
+                "test webhook sent"
+            );
+            Some(WebhookTestDelivery {
+                channel_id,
+                delivered: result.is_ok(),
+                status_code,
+                error: result.err(),
+            })
+        }
+        None => None,
+    };
+
+    Ok(Json(WebhookTestResult {
+        event: event.name.to_string(),
+        payload_version: version,
+        payload,
+        delivery,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalog_describes_one_version() {
+        let now = webhook_events::payload_version(1)
+            .and_then(|version| version.sunset_at())
+            .unwrap()
+            - chrono::Duration::days(1);
+        let current = catalog(CURRENT_PAYLOAD_VERSION, now);
+        assert!(current
+            .events
+            .iter()
+            .any(|event| event.event == "contract.published"
+                && event.subscription_event == Some(SubscriptionEvent::Version)));
+        assert!(current
+            .events
+            .iter()
+            .all(|event| event.schema["properties"]["event_version"]["const"]
+                == CURRENT_PAYLOAD_VERSION));
+
+        let old = catalog(1, now);
+        assert!(old
+            .events
+            .iter()
+            .any(|event| event.event == "contract.version"));
+        assert_eq!(
+            old.versions
+                .iter()
+                .find(|known| known.version == 1)
+                .map(|known| known.status),
+            Some(PayloadVersionStatus::Deprecated)
+        );
+    }
+
+    #[test]
+    fn unknown_versions_are_rejected() {
+        assert_eq!(known_version(None).unwrap(), CURRENT_PAYLOAD_VERSION);
+        assert_eq!(known_version(Some(1)).unwrap(), 1);
+        assert!(known_version(Some(0)).is_err());
+    }
+}
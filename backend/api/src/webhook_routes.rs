@@ -0,0 +1,15 @@
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use crate::{state::AppState, webhook_handlers};
+
+pub fn webhook_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/api/webhooks/events",
+            get(webhook_handlers::list_webhook_events),
+        )
+        .route("/api/webhooks/test", post(webhook_handlers::test_webhook))
+}
@@ -0,0 +1,127 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// SECURITY ADVISORY API TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// To run tests:
+// 1. Start the API server: cargo run --bin api
+// 2. Run tests: cargo test --test advisory_tests -- --include-ignored
+// ═══════════════════════════════════════════════════════════════════════════
+
+mod common;
+
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::bearer_token;
+
+fn api_base_url() -> String {
+    std::env::var("TEST_API_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+/// Subject of the tokens these tests mint
+const TOKEN_SUBJECT: &str = "advisory-tests";
+
+fn advisory(affected_versions: &[&str]) -> Value {
+    json!({
+        "component": "soroban-sdk",
+        "affected_versions": affected_versions,
+        "severity": "high",
+        "title": format!("Test advisory {}", Uuid::new_v4()),
+        "url": "https://example.com/advisories/test",
+    })
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn advisories_are_admin_only_and_validate_ranges() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/advisories", base);
+
+    let res = client
+        .post(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&advisory(&[">=20.0.0, <20.5.2"]))
+        .send()
+        .await
+        .expect("failed to create advisory");
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    for ranges in [&[][..], &["20.x"][..], &[">=20.0.0,"][..]] {
+        let res = client
+            .post(&url)
+            .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+            .json(&advisory(ranges))
+            .send()
+            .await
+            .expect("failed to create advisory");
+        assert_eq!(
+            res.status(),
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "{:?}",
+            ranges
+        );
+        let body: Value = res.json().await.expect("invalid json");
+        assert_eq!(
+            body["error"]["details"]["reason"],
+            "InvalidAffectedVersions"
+        );
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn resolving_an_advisory_clears_its_notices() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    // No released SDK is this old, so nothing real is affected
+    let res = client
+        .post(format!("{}/api/advisories", base))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&advisory(&["<0.0.1"]))
+        .send()
+        .await
+        .expect("failed to create advisory");
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let created: Value = res.json().await.expect("invalid json");
+    let id = created["id"].as_str().expect("advisory missing id");
+    assert_eq!(created["resolved_at"], Value::Null);
+    assert_eq!(created["affected_contracts"], 0);
+
+    let res = client
+        .patch(format!("{}/api/advisories/{}", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "resolved": true }))
+        .send()
+        .await
+        .expect("failed to resolve advisory");
+    assert_eq!(res.status(), StatusCode::OK);
+    let resolved: Value = res.json().await.expect("invalid json");
+    assert!(resolved["resolved_at"].is_string());
+
+    let res = client
+        .get(format!("{}/api/advisories/{}/contracts", base, id))
+        .send()
+        .await
+        .expect("failed to list affected contracts");
+    assert_eq!(res.status(), StatusCode::OK);
+    let contracts: Value = res.json().await.expect("invalid json");
+    assert_eq!(contracts, json!([]));
+
+    let res = client
+        .delete(format!("{}/api/advisories/{}", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .send()
+        .await
+        .expect("failed to delete advisory");
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    let res = client
+        .get(format!("{}/api/advisories/{}", base, id))
+        .send()
+        .await
+        .expect("failed to get advisory");
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
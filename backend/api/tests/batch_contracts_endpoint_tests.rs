@@ -86,27 +86,28 @@ async fn batch_endpoint_returns_ordered_results_under_threshold() {
         threshold
     );
 
-    let results: Vec<Option<Value>> = batch_res
+    let body: Value = batch_res
         .json()
         .await
         .expect("failed to deserialize batch contracts response");
+    let results = body["contracts"]
+        .as_object()
+        .expect("batch response missing contracts map");
 
     assert_eq!(
         results.len(),
         requested_ids.len(),
-        "response must match request length"
+        "every requested id must appear in the response"
     );
 
-    for i in 0..50 {
-        let row = results[i]
-            .as_ref()
-            .expect("existing contract id should not yield null result");
+    for id in requested_ids.iter().take(50) {
+        let row = &results[id];
 
         assert_eq!(
             row.get("id").and_then(Value::as_str),
-            Some(requested_ids[i].as_str()),
-            "response order mismatch at index {}",
-            i
+            Some(id.as_str()),
+            "response mapped {} to the wrong contract",
+            id
         );
 
         assert!(
@@ -123,12 +124,35 @@ async fn batch_endpoint_returns_ordered_results_under_threshold() {
         );
     }
 
-    assert!(
-        results.last().and_then(|v| v.as_ref()).is_none(),
-        "missing contract should return null in response array"
+    let unknown = requested_ids.last().unwrap();
+    assert_eq!(results[unknown], json!({ "error": "not_found" }));
+    assert_eq!(
+        body["missing"],
+        json!([unknown]),
+        "missing contract should be listed in `missing`"
     );
 }
 
+#[tokio::test]
+#[ignore = "requires running API + database with contract data"]
+async fn batch_endpoint_keeps_legacy_array_without_envelope() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+    let unknown = "00000000-0000-0000-0000-000000000000";
+
+    let res = client
+        .post(format!("{}/api/contracts/batch?envelope=false", base))
+        .json(&[unknown])
+        .send()
+        .await
+        .expect("failed to call batch contracts endpoint");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers().contains_key("deprecation"));
+    let results: Vec<Option<Value>> = res.json().await.expect("expected a bare array");
+    assert_eq!(results, vec![None]);
+}
+
 #[tokio::test]
 #[ignore = "requires running API + database with contract data"]
 async fn batch_endpoint_rejects_more_than_100_ids() {
@@ -0,0 +1,21 @@
+//! Helpers shared by the integration tests that talk to a running API.
+
+use serde_json::json;
+
+/// Mints a bearer token for `subject`, signed with the server's JWT_SECRET.
+pub fn bearer_token(subject: &str, admin: bool) -> String {
+    let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must match the running API");
+    let now = chrono::Utc::now().timestamp();
+    let claims = json!({
+        "sub": subject,
+        "iat": now,
+        "exp": now + 600,
+        "admin": admin,
+    });
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(secret.as_bytes()),
+    )
+    .expect("failed to sign test token")
+}
@@ -1,8 +1,11 @@
+use std::collections::HashMap;
+
+use api::cors::CorsConfig;
 use axum::http::HeaderValue;
 use axum::{
     body::Body,
     http::{header, Method, Request, StatusCode},
-    routing::get,
+    routing::{get, put},
     Router,
 };
 use tower::ServiceExt;
@@ -114,3 +117,92 @@ async fn test_cors_preflight_blocked() {
         .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
         .is_none());
 }
+
+fn configured_app(vars: &[(&str, &str)]) -> Router {
+    let vars: HashMap<String, String> = vars
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    let config = CorsConfig::from_lookup(|key| vars.get(key).cloned()).expect("valid config");
+
+    Router::new()
+        .route(
+            "/api/incidents/:id",
+            put(|| async { "ok" }).delete(|| async { "ok" }),
+        )
+        .layer(config.layer())
+}
+
+fn preflight(method: &str, origin: &str) -> Request<Body> {
+    Request::builder()
+        .uri("/api/incidents/123")
+        .method(Method::OPTIONS)
+        .header(header::ORIGIN, origin)
+        .header(header::ACCESS_CONTROL_REQUEST_METHOD, method)
+        .header(
+            header::ACCESS_CONTROL_REQUEST_HEADERS,
+            "authorization,content-type",
+        )
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_configured_preflight_allows_write_methods() {
+    for method in ["PUT", "PATCH", "DELETE"] {
+        let response = configured_app(&[("CORS_MAX_AGE_SECS", "600")])
+            .oneshot(preflight(method, "http://localhost:3000"))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert_eq!(
+            headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+            "http://localhost:3000"
+        );
+        let allowed = headers[header::ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap();
+        assert!(allowed.contains(method), "{method} not in {allowed}");
+        assert_eq!(headers[header::ACCESS_CONTROL_MAX_AGE], "600");
+        assert!(headers
+            .get(header::ACCESS_CONTROL_ALLOW_CREDENTIALS)
+            .is_none());
+    }
+}
+
+#[tokio::test]
+async fn test_configured_credentials_echo_explicit_origin() {
+    let response = configured_app(&[
+        ("ALLOWED_ORIGINS", "https://app.example.com"),
+        ("CORS_ALLOW_CREDENTIALS", "true"),
+    ])
+    .oneshot(preflight("DELETE", "https://app.example.com"))
+    .await
+    .unwrap();
+
+    let headers = response.headers();
+    assert_eq!(
+        headers[header::ACCESS_CONTROL_ALLOW_ORIGIN],
+        "https://app.example.com"
+    );
+    assert_eq!(headers[header::ACCESS_CONTROL_ALLOW_CREDENTIALS], "true");
+}
+
+#[tokio::test]
+async fn test_configured_wildcard_allows_any_origin() {
+    let response = configured_app(&[("ALLOWED_ORIGINS", "*")])
+        .oneshot(preflight("PUT", "http://localhost:5173"))
+        .await
+        .unwrap();
+
+    assert_eq!(response.headers()[header::ACCESS_CONTROL_ALLOW_ORIGIN], "*");
+}
+
+#[test]
+fn test_credentials_with_wildcard_fail_startup() {
+    let vars = HashMap::from([("ALLOWED_ORIGINS", "*"), ("CORS_ALLOW_CREDENTIALS", "true")]);
+    let err = CorsConfig::from_lookup(|key| vars.get(key).map(|v| v.to_string())).unwrap_err();
+    assert!(err.to_string().contains("explicit ALLOWED_ORIGINS"));
+}
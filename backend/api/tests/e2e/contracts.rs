@@ -0,0 +1,237 @@
+use reqwest::{header, StatusCode};
+use serde_json::json;
+
+use crate::harness::{publish_request, unique_name, TestApp};
+
+/// Publish, find, read, edit and deprecate one contract. The registry has
+/// no hard delete; deprecation is how a publisher takes a contract out of
+/// service.
+#[tokio::test]
+async fn contract_lifecycle() {
+    let app = TestApp::start().await;
+    let name = unique_name("Lifecycle Vault");
+
+    let request = publish_request(&name);
+    let (status, contract) = app.post("/api/contracts", None, request.clone()).await;
+    assert!(status.is_success(), "publish failed: {status} {contract}");
+    let id = contract["id"].as_str().expect("contract id").to_string();
+    assert_eq!(contract["name"], name);
+    assert_eq!(contract["network"], "testnet");
+    assert_eq!(contract["publish_status"], "published");
+
+    // The same contract ID can't be registered twice on a network
+    let (status, _) = app.post("/api/contracts", None, request).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, page) = app
+        .get(&format!(
+            "/api/contracts?query={}",
+            name.replace(' ', "%20")
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let items = page["items"].as_array().expect("items");
+    assert_eq!(items.len(), 1, "{page}");
+    assert_eq!(items[0]["id"], id);
+
+    let res = app
+        .http
+        .get(app.url(&format!("/api/contracts/{}", id)))
+        .send()
+        .await
+        .expect("request failed");
+    assert_eq!(res.status(), StatusCode::OK);
+    let etag = res.headers()[header::ETAG].to_str().unwrap().to_string();
+    let info: serde_json::Value = res.json().await.expect("invalid json");
+    assert_eq!(info["contract_id"], contract["contract_id"]);
+    assert_eq!(info["tags"], json!(["e2e"]));
+    assert_eq!(info["freshness"]["stale"], false);
+
+    let update = json!({ "description": "Rebalanced weekly", "tags": ["e2e", "vault"] });
+    let res = app
+        .http
+        .patch(app.url(&format!("/api/contracts/{}/metadata", id)))
+        .header(header::IF_MATCH, &etag)
+        .json(&update)
+        .send()
+        .await
+        .expect("request failed");
+    assert_eq!(res.status(), StatusCode::OK);
+    let updated: serde_json::Value = res.json().await.expect("invalid json");
+    assert_eq!(updated["description"], "Rebalanced weekly");
+    assert_eq!(updated["tags"], json!(["e2e", "vault"]));
+    assert_eq!(updated["revision"], info["revision"].as_i64().unwrap() + 1);
+
+    // An edit based on the old revision is refused
+    let res = app
+        .http
+        .patch(app.url(&format!("/api/contracts/{}/metadata", id)))
+        .header(header::IF_MATCH, &etag)
+        .json(&json!({ "description": "Lost update" }))
+        .send()
+        .await
+        .expect("request failed");
+    assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+    let (status, info) = app.get(&format!("/api/contracts/{}", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(info["description"], "Rebalanced weekly");
+
+    let retirement_at = chrono::Utc::now() + chrono::Duration::days(30);
+    let (status, deprecation) = app
+        .post(
+            &format!("/api/contracts/{}/deprecate", id),
+            None,
+            json!({
+                "retirement_at": retirement_at,
+                "migration_guide_url": "https://example.com/migrate",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{deprecation}");
+    assert_eq!(deprecation["status"], "deprecated");
+
+    let (status, deprecation) = app
+        .get(&format!("/api/contracts/{}/deprecation-info", id))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(deprecation["status"], "deprecated");
+    assert_eq!(
+        deprecation["migration_guide_url"],
+        "https://example.com/migrate"
+    );
+}
+
+#[tokio::test]
+async fn batch_verify_checks_contracts_against_the_rpc() {
+    let app = TestApp::start().await;
+    let deployed = app.seed_contract(&unique_name("Deployed Pool")).await;
+    let missing = app.seed_contract(&unique_name("Missing Pool")).await;
+    app.rpc.deploy(
+        deployed["contract_id"].as_str().unwrap(),
+        deployed["wasm_hash"].as_str().unwrap(),
+    );
+
+    let (status, report) = app
+        .post(
+            "/api/contracts/batch-verify",
+            None,
+            json!({ "contracts": [
+                { "contract_id": deployed["contract_id"] },
+                { "contract_id": missing["contract_id"] },
+            ] }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{report}");
+    assert_eq!(report["total"], 2);
+
+    let results = report["results"].as_array().expect("results");
+    let result_for = |contract: &serde_json::Value| {
+        results
+            .iter()
+            .find(|r| r["contract_id"] == contract["contract_id"])
+            .cloned()
+            .expect("result for every contract")
+    };
+    let found = result_for(&deployed);
+    assert_eq!(found["on_chain"]["contract_exists_on_chain"], true);
+    assert_eq!(found["on_chain"]["wasm_hash_matches"], true);
+    assert_eq!(
+        found["on_chain"]["latest_ledger"],
+        crate::mock_rpc::LATEST_LEDGER
+    );
+    let absent = result_for(&missing);
+    assert_eq!(absent["verified"], false);
+    assert_eq!(absent["on_chain"]["contract_exists_on_chain"], false);
+    assert!(app.rpc.calls().iter().any(|m| m == "getLedgerEntries"));
+
+    // Finding the contract on chain counts toward its freshness
+    let (_, info) = app
+        .get(&format!(
+            "/api/contracts/{}",
+            deployed["id"].as_str().unwrap()
+        ))
+        .await;
+    assert!(
+        info["freshness"]["last_onchain_confirmation_at"].is_string(),
+        "{info}"
+    );
+    let (_, info) = app
+        .get(&format!(
+            "/api/contracts/{}",
+            missing["id"].as_str().unwrap()
+        ))
+        .await;
+    assert!(
+        info["freshness"]["last_onchain_confirmation_at"].is_null(),
+        "{info}"
+    );
+}
+
+#[tokio::test]
+async fn registry_changes_are_grouped_by_kind() {
+    let app = TestApp::start().await;
+    let fresh = app.seed_contract(&unique_name("Fresh Pool")).await;
+    let older = app.seed_contract(&unique_name("Older Pool")).await;
+    let older_id = older["id"].as_str().unwrap().to_string();
+    // Published before the window, edited inside it
+    sqlx::query("UPDATE contracts SET created_at = NOW() - INTERVAL '10 days' WHERE id = $1")
+        .bind(uuid::Uuid::parse_str(&older_id).unwrap())
+        .execute(&app.db)
+        .await
+        .unwrap();
+    let (status, edited) = app
+        .patch(
+            &format!("/api/contracts/{}/metadata", older_id),
+            None,
+            json!({ "description": "Rebalanced daily" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{edited}");
+
+    let since = (chrono::Utc::now() - chrono::Duration::hours(1))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let ids_in = |page: &serde_json::Value| -> Vec<String> {
+        page["items"]
+            .as_array()
+            .expect("items")
+            .iter()
+            .map(|item| item["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let (status, added) = app
+        .get(&format!(
+            "/api/contracts/changes?since={}&group=added&limit=100",
+            since
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{added}");
+    assert!(added.get("updated").is_none(), "only the requested group");
+    let added_ids = ids_in(&added["added"]);
+    assert!(added_ids.contains(&fresh["id"].as_str().unwrap().to_string()));
+    assert!(!added_ids.contains(&older_id));
+
+    let (status, updated) = app
+        .get(&format!(
+            "/api/contracts/changes?since={}&group=updated&limit=100",
+            since
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{updated}");
+    let item = updated["updated"]["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|item| item["id"] == older_id.as_str())
+        .cloned()
+        .expect("the edited contract is listed as updated");
+    assert_eq!(item["detail"], "metadata_updated");
+
+    let too_early = (chrono::Utc::now() - chrono::Duration::days(400))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let (status, refused) = app
+        .get(&format!("/api/contracts/changes?since={}", too_early))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{refused}");
+}
@@ -0,0 +1,230 @@
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::harness::{unique_name, TestApp};
+
+/// Open an incident against a contract, work it through its timeline and
+/// resolve it.
+#[tokio::test]
+async fn incident_lifecycle() {
+    let app = TestApp::start().await;
+    let contract = app.seed_contract(&unique_name("Incident Oracle")).await;
+    let contract_id = contract["id"].as_str().unwrap();
+    let token = app.token("e2e-oncall", false);
+
+    let (status, incident) = app
+        .post(
+            "/api/incidents",
+            None,
+            json!({
+                "incident_type": "outage",
+                "severity": "major",
+                "description": "Price feed stopped updating",
+                "affected_contract_ids": [contract_id],
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{incident}");
+    let id = incident["id"].as_str().expect("incident id").to_string();
+    assert!(incident["end_time"].is_null());
+
+    let (status, update) = app
+        .post(
+            &format!("/api/incidents/{}/updates", id),
+            Some(&token),
+            json!({ "message": "Stale upstream identified", "status": "identified" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{update}");
+    assert_eq!(update["author"], "e2e-oncall");
+    assert_eq!(update["post_resolution"], false);
+
+    // Severity changes land in the incident's audit log
+    let (status, escalated) = app
+        .patch(
+            &format!("/api/incidents/{}", id),
+            Some(&token),
+            json!({ "severity": "critical" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{escalated}");
+    assert_eq!(escalated["severity"], "critical");
+    let changed_by: String = sqlx::query_scalar(
+        "SELECT changed_by FROM incident_audit_log
+         WHERE incident_id = $1::uuid AND action = 'severity_changed'",
+    )
+    .bind(&id)
+    .fetch_one(&app.db)
+    .await
+    .expect("severity change audited");
+    assert_eq!(changed_by, "e2e-oncall");
+
+    let (status, resolved) = app
+        .patch(
+            &format!("/api/incidents/{}", id),
+            Some(&token),
+            json!({
+                "end_time": chrono::Utc::now(),
+                "lessons_learned": "Alert on feed staleness",
+            }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{resolved}");
+    assert!(resolved["end_time"].is_string());
+
+    let (status, update) = app
+        .post(
+            &format!("/api/incidents/{}/updates", id),
+            Some(&token),
+            json!({ "message": "Feed healthy for an hour", "status": "resolved" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(update["post_resolution"], true);
+
+    let (status, detail) = app.get(&format!("/api/incidents/{}", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(detail["lessons_learned"], "Alert on feed staleness");
+    assert_eq!(detail["latest_update"]["status"], "resolved");
+
+    let (status, timeline) = app.get(&format!("/api/incidents/{}/updates", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(timeline.as_array().map(Vec::len), Some(2));
+
+    let (status, page) = app
+        .get(&format!("/api/contracts/{}/incidents", contract_id))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(page["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .any(|i| i["id"] == id.as_str()));
+}
+
+#[tokio::test]
+async fn seeded_incidents_follow_resolution_rules() {
+    let app = TestApp::start().await;
+    let contract = app.seed_contract(&unique_name("Seeded Bridge")).await;
+    let contract_id = contract["id"].as_str().unwrap().parse().unwrap();
+    let id = app.seed_incident(Some(contract_id), 3).await;
+
+    // An incident can't end before it started
+    let (status, _) = app
+        .patch(
+            &format!("/api/incidents/{}", id),
+            None,
+            json!({ "end_time": chrono::Utc::now() - chrono::Duration::hours(4) }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Nor can recovery metrics be recorded while it is still open
+    let (status, _) = app
+        .patch(
+            &format!("/api/incidents/{}", id),
+            None,
+            json!({ "rto_achieved": "2h" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+
+    let (status, resolved) = app
+        .patch(
+            &format!("/api/incidents/{}", id),
+            None,
+            json!({ "end_time": chrono::Utc::now(), "rto_achieved": "2h" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{resolved}");
+    assert_eq!(resolved["rto_achieved"]["seconds"], 7200);
+
+    // Resolved incidents drop off the contract's open incidents
+    let (status, page) = app
+        .get(&format!(
+            "/api/contracts/{}/incidents?open_only=true",
+            contract_id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["items"], json!([]));
+}
+
+/// Backfill incident history from NDJSON: bad lines are reported by number
+/// and skipped, and a second run only reports duplicates.
+#[tokio::test]
+async fn incident_import_reports_every_line() {
+    let app = TestApp::start().await;
+    let admin = app.token("e2e-admin", true);
+    let contract = app.seed_contract(&unique_name("Imported Oracle")).await;
+    let contract_id = contract["id"].as_str().unwrap();
+    let external_id = format!("PD-{}", uuid::Uuid::new_v4().simple());
+    let started = chrono::Utc::now() - chrono::Duration::days(30);
+
+    let body = [
+        json!({
+            "external_id": external_id,
+            "incident_type": "outage",
+            "severity": "critical",
+            "description": "Feed halted during upgrade",
+            "start_time": started,
+            "end_time": started + chrono::Duration::minutes(90),
+            "rto_achieved": "1h30m",
+            "affected_contract_ids": [contract_id],
+        })
+        .to_string(),
+        String::new(),
+        json!({
+            "incident_type": "degradation",
+            "description": "Never resolved",
+            "start_time": started,
+        })
+        .to_string(),
+        "{not json".to_string(),
+    ]
+    .join("\n");
+
+    let import = |dry_run: bool| {
+        app.http
+            .post(app.url(&format!("/api/incidents/import?dry_run={}", dry_run)))
+            .bearer_auth(&admin)
+            .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+            .body(body.clone())
+            .send()
+    };
+
+    let res = import(true).await.expect("dry run");
+    assert_eq!(res.status(), StatusCode::OK);
+    let report: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(report["records"], 3);
+    assert_eq!(report["invalid"], 2);
+    assert_eq!(report["lines"][0]["status"], "valid");
+    assert_eq!(report["lines"][1]["line"], 3);
+    assert_eq!(report["lines"][2]["line"], 4);
+
+    let res = import(false).await.expect("import");
+    let report: serde_json::Value = res.json().await.unwrap();
+    assert_eq!(report["imported"], 1, "{report}");
+    let id = report["lines"][0]["incident_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let (status, incident) = app.get(&format!("/api/incidents/{}", id)).await;
+    assert_eq!(status, StatusCode::OK, "{incident}");
+    assert_eq!(incident["source"], "import");
+    assert_eq!(incident["external_id"], external_id.as_str());
+    assert_eq!(incident["rto_achieved"]["seconds"], 5400);
+    assert_eq!(incident["affected_contracts"][0]["id"], contract_id);
+
+    let report: serde_json::Value = import(false).await.unwrap().json().await.unwrap();
+    assert_eq!(report["imported"], 0);
+    assert_eq!(report["duplicates"], 1);
+
+    let (_, metrics) = app.get("/api/incidents/metrics").await;
+    assert_eq!(metrics["total_incidents"], 1);
+    let (_, metrics) = app
+        .get("/api/incidents/metrics?exclude_imported=true")
+        .await;
+    assert_eq!(metrics["total_incidents"], 0);
+}
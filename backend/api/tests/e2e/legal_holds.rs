@@ -0,0 +1,90 @@
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+use crate::harness::TestApp;
+
+/// A held incident can't be deleted until the hold is released, and both
+/// ends of the hold are audited.
+#[tokio::test]
+async fn held_incident_is_locked_until_released() {
+    let app = TestApp::start().await;
+    let admin = app.token("e2e-counsel", true);
+    let incident = app.seed_incident(None, 2).await;
+
+    let (status, _) = app
+        .post(
+            "/api/admin/holds",
+            Some(&app.token("e2e-oncall", false)),
+            json!({ "target_type": "incident", "target_id": incident, "reason": "Case 114" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, hold) = app
+        .post(
+            "/api/admin/holds",
+            Some(&admin),
+            json!({ "target_type": "incident", "target_id": incident, "reason": "Case 114" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CREATED, "{hold}");
+    let hold_id = hold["id"].as_str().unwrap().to_string();
+
+    let (status, _) = app
+        .post(
+            "/api/admin/holds",
+            Some(&admin),
+            json!({ "target_type": "incident", "target_id": incident, "reason": "Again" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, active) = app
+        .send(Method::GET, "/api/admin/holds", Some(&admin), None)
+        .await;
+    assert_eq!(status, StatusCode::OK, "{active}");
+    assert!(active["holds"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|h| h["id"] == hold_id.as_str()));
+
+    let delete_path = format!("/api/incidents/{}", incident);
+    let (status, refused) = app
+        .send(Method::DELETE, &delete_path, Some(&admin), None)
+        .await;
+    assert_eq!(status, StatusCode::LOCKED, "{refused}");
+    assert_eq!(refused["details"]["hold"]["reason"], "Case 114");
+    assert_eq!(refused["details"]["hold"]["set_by"], "e2e-counsel");
+    let deleted_at: Option<chrono::DateTime<chrono::Utc>> =
+        sqlx::query_scalar("SELECT deleted_at FROM incidents WHERE id = $1")
+            .bind(incident)
+            .fetch_one(&app.db)
+            .await
+            .unwrap();
+    assert!(deleted_at.is_none(), "the refused delete was rolled back");
+
+    let (status, released) = app
+        .post(
+            &format!("/api/admin/holds/{}/release", hold_id),
+            Some(&admin),
+            json!({ "reason": "Case closed" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{released}");
+    assert_eq!(released["released_by"], "e2e-counsel");
+
+    let actions: Vec<String> = sqlx::query_scalar(
+        "SELECT action FROM incident_audit_log WHERE incident_id = $1 ORDER BY timestamp",
+    )
+    .bind(incident)
+    .fetch_all(&app.db)
+    .await
+    .unwrap();
+    assert_eq!(actions, ["legal_hold_set", "legal_hold_released"]);
+
+    let (status, _) = app
+        .send(Method::DELETE, &delete_path, Some(&admin), None)
+        .await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+}
@@ -0,0 +1,20 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// END-TO-END TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// Each test gets its own registry: a throwaway Postgres container with the
+// embedded migrations applied, the `api` binary running against it and a
+// mock Soroban RPC answering the on-chain verification calls. Docker must
+// be available.
+//
+// To run tests:
+//   cargo test -p api --features integration
+// Set E2E_LOG=1 to see the server's logs.
+// ═══════════════════════════════════════════════════════════════════════════
+
+mod contracts;
+mod harness;
+mod incidents;
+mod legal_holds;
+mod mock_rpc;
+mod search;
@@ -0,0 +1,139 @@
+//! A Soroban RPC stand-in with canned answers for the calls on-chain
+//! verification makes.
+//!
+//! Contracts registered with [`MockRpc::deploy`] have an instance entry
+//! running the given WASM hash; every other ledger key is missing. The
+//! ledger sits at [`LATEST_LEDGER`] and no contract has recent events.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use axum::{extract::State, routing::post, Json, Router};
+use serde_json::{json, Value};
+use stellar_xdr::curr::{
+    ContractDataDurability, ContractDataEntry, ContractExecutable, ContractId, ExtensionPoint,
+    Hash, LedgerEntry, LedgerEntryData, LedgerEntryExt, LedgerKey, LedgerKeyContractData, Limits,
+    ReadXdr, ScAddress, ScContractInstance, ScVal, WriteXdr,
+};
+
+pub const LATEST_LEDGER: u32 = 1_000_000;
+
+#[derive(Default)]
+struct Ledger {
+    /// Contract address bytes to the WASM hash its instance runs
+    deployed: HashMap<[u8; 32], [u8; 32]>,
+    /// Methods called so far, oldest first
+    calls: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct MockRpc {
+    url: String,
+    ledger: Arc<Mutex<Ledger>>,
+}
+
+impl MockRpc {
+    pub async fn start() -> Self {
+        let ledger = Arc::new(Mutex::new(Ledger::default()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock rpc");
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let app = Router::new()
+            .route("/", post(handle))
+            .with_state(ledger.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        Self { url, ledger }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Puts an instance of `contract_id` running `wasm_hash` on the ledger
+    pub fn deploy(&self, contract_id: &str, wasm_hash: &str) {
+        let contract = stellar_strkey::Contract::from_string(contract_id)
+            .expect("contract ID is a strkey")
+            .0;
+        let wasm: [u8; 32] = hex::decode(wasm_hash)
+            .expect("wasm hash is hex")
+            .try_into()
+            .expect("wasm hash is 32 bytes");
+        self.ledger.lock().unwrap().deployed.insert(contract, wasm);
+    }
+
+    pub fn calls(&self) -> Vec<String> {
+        self.ledger.lock().unwrap().calls.clone()
+    }
+}
+
+async fn handle(State(ledger): State<Arc<Mutex<Ledger>>>, Json(req): Json<Value>) -> Json<Value> {
+    let method = req["method"].as_str().unwrap_or_default().to_string();
+    let mut ledger = ledger.lock().unwrap();
+    ledger.calls.push(method.clone());
+
+    let result = match method.as_str() {
+        "getHealth" => json!({ "status": "healthy", "latestLedger": LATEST_LEDGER }),
+        "getLatestLedger" => json!({ "sequence": LATEST_LEDGER, "protocolVersion": 22 }),
+        "getEvents" => json!({ "events": [], "latestLedger": LATEST_LEDGER }),
+        "getLedgerEntries" => {
+            let keys = req["params"]["keys"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default();
+            let entries: Vec<Value> = keys
+                .iter()
+                .filter_map(|key| {
+                    let key = key.as_str()?;
+                    let contract = instance_key_contract(key)?;
+                    let wasm = ledger.deployed.get(&contract)?;
+                    Some(json!({
+                        "key": key,
+                        "xdr": instance_entry(contract, *wasm),
+                        "lastModifiedLedgerSeq": LATEST_LEDGER,
+                    }))
+                })
+                .collect();
+            json!({ "entries": entries, "latestLedger": LATEST_LEDGER })
+        }
+        _ => {
+            return Json(json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": { "code": -32601, "message": format!("mock RPC does not answer {}", method) },
+            }));
+        }
+    };
+    Json(json!({ "jsonrpc": "2.0", "id": req["id"], "result": result }))
+}
+
+/// The contract whose instance `key` points at, if it is an instance key
+fn instance_key_contract(key: &str) -> Option<[u8; 32]> {
+    match LedgerKey::from_xdr_base64(key, Limits::none()).ok()? {
+        LedgerKey::ContractData(LedgerKeyContractData {
+            contract: ScAddress::Contract(ContractId(Hash(contract))),
+            key: ScVal::LedgerKeyContractInstance,
+            ..
+        }) => Some(contract),
+        _ => None,
+    }
+}
+
+fn instance_entry(contract: [u8; 32], wasm: [u8; 32]) -> String {
+    LedgerEntry {
+        last_modified_ledger_seq: LATEST_LEDGER,
+        data: LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: ScAddress::Contract(ContractId(Hash(contract))),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+            val: ScVal::ContractInstance(ScContractInstance {
+                executable: ContractExecutable::Wasm(Hash(wasm)),
+                storage: None,
+            }),
+        }),
+        ext: LedgerEntryExt::V0,
+    }
+    .to_xdr_base64(Limits::none())
+    .expect("encode ledger entry")
+}
@@ -0,0 +1,108 @@
+use reqwest::StatusCode;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::harness::{unique_name, TestApp};
+
+/// A word only this test's contracts contain
+fn unique_term() -> String {
+    format!("zeph{}", &Uuid::new_v4().simple().to_string()[..8])
+}
+
+async fn search_ids(app: &TestApp, term: &str) -> Vec<String> {
+    let (status, page) = app
+        .get(&format!(
+            "/api/contracts?query={}&sort_by=relevance&sort_order=desc",
+            term
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK, "{page}");
+    page["items"]
+        .as_array()
+        .expect("items")
+        .iter()
+        .map(|item| item["id"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn name_match_outranks_readme_only_match() {
+    let app = TestApp::start().await;
+    let term = unique_term();
+
+    let readme_only = app.seed_contract(&unique_name("Readme Pool")).await;
+    let readme_id = readme_only["id"].as_str().unwrap().to_string();
+    let (status, body) = app
+        .patch(
+            &format!("/api/contracts/{}/metadata", readme_id),
+            None,
+            json!({ "readme": format!("# Usage\n\nRoutes swaps through {term} pools.") }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    let named = app.seed_contract(&format!("Vault {}", term)).await;
+    let named_id = named["id"].as_str().unwrap().to_string();
+
+    assert_eq!(
+        search_ids(&app, &term).await,
+        [named_id.clone(), readme_id.clone()]
+    );
+
+    // Turning the README's indexing off takes it out of search straight away
+    let (status, body) = app
+        .patch(
+            &format!("/api/contracts/{}/metadata", readme_id),
+            None,
+            json!({ "index_readme": false }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{body}");
+    assert_eq!(search_ids(&app, &term).await, [named_id]);
+
+    let (_, info) = app.get(&format!("/api/contracts/{}", readme_id)).await;
+    assert_eq!(info["index_readme"], false);
+    assert!(info["readme"].as_str().unwrap().contains(&term));
+}
+
+#[tokio::test]
+async fn admins_tune_search_weights() {
+    let app = TestApp::start().await;
+    let admin = app.token("GADMIN", true);
+
+    let (status, _) = app.get("/api/admin/search/weights").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let (status, weights) = app
+        .send(
+            reqwest::Method::GET,
+            "/api/admin/search/weights",
+            Some(&admin),
+            None,
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{weights}");
+    assert_eq!(weights["name"], 1.0);
+    assert!(weights["readme"].as_f64().unwrap() < weights["description"].as_f64().unwrap());
+
+    let (status, weights) = app
+        .send(
+            reqwest::Method::PUT,
+            "/api/admin/search/weights",
+            Some(&admin),
+            Some(json!({ "readme": 0.5 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{weights}");
+    assert_eq!(weights["readme"], 0.5);
+    assert_eq!(weights["updated_by"], "GADMIN");
+
+    let (status, _) = app
+        .send(
+            reqwest::Method::PUT,
+            "/api/admin/search/weights",
+            Some(&admin),
+            Some(json!({ "name": 2.0 })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,151 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// ERROR RESPONSE SHAPE TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// Every error class must come back as `{ "error": { "code", "message",
+// "details" } }` with the matching HTTP status.
+//
+// To run tests:
+// 1. Start the API server: cargo run --bin api
+// 2. Run tests: cargo test --test error_tests -- --include-ignored
+// ═══════════════════════════════════════════════════════════════════════════
+
+mod common;
+
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::bearer_token;
+
+fn api_base_url() -> String {
+    std::env::var("TEST_API_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+/// Subject of the tokens these tests mint
+const TOKEN_SUBJECT: &str = "error-tests";
+
+/// Asserts the status and standard error envelope, returning `error`.
+async fn expect_error(res: reqwest::Response, status: StatusCode, code: &str) -> Value {
+    assert_eq!(res.status(), status);
+    let header_id = res
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_owned)
+        .expect("X-Request-ID header");
+    let body: Value = res.json().await.expect("invalid json");
+    let error = body["error"].clone();
+    assert_eq!(error["code"], code, "unexpected body: {}", body);
+    assert!(error["message"].as_str().is_some_and(|m| !m.is_empty()));
+    assert!(error["details"].is_object());
+    assert_eq!(error["request_id"], header_id.as_str());
+    error
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn missing_resource_returns_not_found() {
+    let res = reqwest::Client::new()
+        .get(format!(
+            "{}/api/incidents/{}",
+            api_base_url(),
+            Uuid::new_v4()
+        ))
+        .send()
+        .await
+        .expect("request failed");
+
+    let error = expect_error(res, StatusCode::NOT_FOUND, "NOT_FOUND").await;
+    assert_eq!(error["details"]["reason"], "IncidentNotFound");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn invalid_field_returns_validation_error() {
+    let res = reqwest::Client::new()
+        .post(format!("{}/api/contracts/batch-verify", api_base_url()))
+        .json(&json!({ "contracts": [] }))
+        .send()
+        .await
+        .expect("request failed");
+
+    let error = expect_error(res, StatusCode::BAD_REQUEST, "VALIDATION_ERROR").await;
+    assert_eq!(error["details"]["field"], "contracts");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn duplicate_resource_returns_conflict() {
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/recovery-objectives", api_base_url());
+    let create = || {
+        client
+            .post(&url)
+            .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+            .json(&json!({ "rto_target": "4h", "rpo_target": "1h" }))
+            .send()
+    };
+
+    // The global objective may already exist; either way the second create
+    // must conflict.
+    let first = create().await.expect("request failed");
+    let created: Option<Value> = if first.status() == StatusCode::CREATED {
+        Some(first.json().await.expect("invalid json"))
+    } else {
+        None
+    };
+
+    let res = create().await.expect("request failed");
+    let error = expect_error(res, StatusCode::CONFLICT, "CONFLICT").await;
+    assert_eq!(error["details"]["reason"], "RecoveryObjectiveExists");
+
+    if let Some(created) = created {
+        client
+            .delete(format!("{}/{}", url, created["id"].as_str().unwrap()))
+            .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+            .send()
+            .await
+            .expect("cleanup failed");
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn missing_token_returns_unauthorized() {
+    let res = reqwest::Client::new()
+        .get(format!("{}/api/recovery-objectives", api_base_url()))
+        .send()
+        .await
+        .expect("request failed");
+
+    expect_error(res, StatusCode::UNAUTHORIZED, "UNAUTHORIZED").await;
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn non_admin_returns_forbidden() {
+    let res = reqwest::Client::new()
+        .get(format!("{}/api/recovery-objectives", api_base_url()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .send()
+        .await
+        .expect("request failed");
+
+    expect_error(res, StatusCode::FORBIDDEN, "FORBIDDEN").await;
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn caller_request_id_is_echoed_in_errors() {
+    let request_id = format!("error-tests-{}", Uuid::new_v4());
+    let res = reqwest::Client::new()
+        .get(format!("{}/api/incidents/not-a-uuid", api_base_url()))
+        .header("x-request-id", &request_id)
+        .send()
+        .await
+        .expect("request failed");
+
+    let error = expect_error(res, StatusCode::BAD_REQUEST, "BAD_REQUEST").await;
+    assert_eq!(error["request_id"], request_id.as_str());
+}
@@ -0,0 +1,963 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// INCIDENT API TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// To run tests:
+// 1. Start the API server: cargo run --bin api
+// 2. Run tests: cargo test --test incident_tests -- --include-ignored
+// ═══════════════════════════════════════════════════════════════════════════
+
+mod common;
+
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::bearer_token;
+
+fn api_base_url() -> String {
+    std::env::var("TEST_API_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+/// Subject of the tokens these tests mint
+const TOKEN_SUBJECT: &str = "incident-tests";
+
+async fn create_test_incident(client: &reqwest::Client, base_url: &str) -> Value {
+    let res = client
+        .post(format!("{}/api/incidents", base_url))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "incident_type": "outage",
+            "description": format!("Test incident {}", Uuid::new_v4()),
+        }))
+        .send()
+        .await
+        .expect("failed to create test incident");
+
+    assert_eq!(res.status(), StatusCode::CREATED);
+    res.json().await.expect("failed to parse incident response")
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn get_incident_returns_full_record() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+
+    let res = client
+        .get(format!("{}/api/incidents/{}", base, id))
+        .send()
+        .await
+        .expect("failed to fetch incident");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let body: Value = res.json().await.expect("invalid json");
+    assert_eq!(body["id"], created["id"]);
+    assert_eq!(body["incident_type"], "outage");
+    assert_eq!(body["description"], created["description"]);
+    assert!(body["end_time"].is_null());
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn get_incident_missing_returns_404() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let res = client
+        .get(format!("{}/api/incidents/{}", base, Uuid::new_v4()))
+        .send()
+        .await
+        .expect("failed to fetch incident");
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let body: Value = res.json().await.expect("invalid json");
+    assert_eq!(body["error"]["code"], "NOT_FOUND");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn get_incident_malformed_id_returns_400() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let res = client
+        .get(format!("{}/api/incidents/not-a-uuid", base))
+        .send()
+        .await
+        .expect("failed to fetch incident");
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let body: Value = res.json().await.expect("invalid json");
+    assert_eq!(body["error"]["code"], "BAD_REQUEST");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn delete_incident_requires_admin() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+
+    let res = client
+        .delete(format!("{}/api/incidents/{}", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .send()
+        .await
+        .expect("failed to delete incident");
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn opening_incidents_requires_admin() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+    let incident = json!({
+        "incident_type": "outage",
+        "severity": "critical",
+        "description": "Unauthorized incident",
+        "notify_users": true,
+    });
+
+    let res = client
+        .post(format!("{}/api/incidents", base))
+        .json(&incident)
+        .send()
+        .await
+        .expect("failed to create incident");
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = client
+        .post(format!("{}/api/incidents", base))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&incident)
+        .send()
+        .await
+        .expect("failed to create incident");
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn updating_incidents_requires_admin() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let created = create_test_incident(&client, &base).await;
+    let url = format!(
+        "{}/api/incidents/{}",
+        base,
+        created["id"].as_str().expect("incident missing id")
+    );
+    let change = json!({ "severity": "critical", "notify_users": true });
+
+    let res = client
+        .patch(&url)
+        .json(&change)
+        .send()
+        .await
+        .expect("failed to update incident");
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let res = client
+        .patch(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&change)
+        .send()
+        .await
+        .expect("failed to update incident");
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let unchanged: Value = client
+        .get(&url)
+        .send()
+        .await
+        .expect("failed to fetch incident")
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(unchanged["severity"], created["severity"]);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn delete_incident_soft_deletes_and_hides_from_list() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+    let token = bearer_token(TOKEN_SUBJECT, true);
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+
+    let res = client
+        .delete(format!("{}/api/incidents/{}", base, id))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to delete incident");
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+
+    // Deleting twice is a 404, as is fetching the deleted row.
+    let res = client
+        .delete(format!("{}/api/incidents/{}", base, id))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to delete incident");
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let res = client
+        .get(format!("{}/api/incidents/{}", base, id))
+        .send()
+        .await
+        .expect("failed to fetch incident");
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    let listed: Value = client
+        .get(format!("{}/api/incidents?limit=100", base))
+        .send()
+        .await
+        .expect("failed to list incidents")
+        .json()
+        .await
+        .expect("invalid json");
+    let items = listed["items"]
+        .as_array()
+        .expect("items should be an array");
+    assert!(items.iter().all(|i| i["id"] != created["id"]));
+
+    let listed: Value = client
+        .get(format!(
+            "{}/api/incidents?limit=100&include_deleted=true",
+            base
+        ))
+        .bearer_auth(&token)
+        .send()
+        .await
+        .expect("failed to list incidents")
+        .json()
+        .await
+        .expect("invalid json");
+    let deleted = listed["items"]
+        .as_array()
+        .expect("items should be an array")
+        .iter()
+        .find(|i| i["id"] == created["id"])
+        .expect("deleted incident should be listed for admins");
+    assert!(deleted["deleted_at"].is_string());
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn include_deleted_is_admin_only() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let res = client
+        .get(format!("{}/api/incidents?include_deleted=true", base))
+        .send()
+        .await
+        .expect("failed to list incidents");
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn incident_updates_form_a_chronological_timeline() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+    let token = bearer_token(TOKEN_SUBJECT, false);
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+
+    for (message, status) in [
+        ("Looking into it", "investigating"),
+        ("Root cause found", "identified"),
+    ] {
+        let res = client
+            .post(format!("{}/api/incidents/{}/updates", base, id))
+            .bearer_auth(&token)
+            .json(&json!({ "message": message, "status": status }))
+            .send()
+            .await
+            .expect("failed to post update");
+        assert_eq!(res.status(), StatusCode::CREATED);
+    }
+
+    let timeline: Vec<Value> = client
+        .get(format!("{}/api/incidents/{}/updates", base, id))
+        .send()
+        .await
+        .expect("failed to list updates")
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(timeline.len(), 2);
+    assert_eq!(timeline[0]["message"], "Looking into it");
+    assert_eq!(timeline[1]["status"], "identified");
+    assert_eq!(timeline[1]["author"], "incident-tests");
+
+    let detail: Value = client
+        .get(format!("{}/api/incidents/{}", base, id))
+        .send()
+        .await
+        .expect("failed to fetch incident")
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(detail["latest_update"]["message"], "Root cause found");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn updates_after_resolution_are_flagged() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+
+    let res = client
+        .patch(format!("{}/api/incidents/{}", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "end_time": chrono::Utc::now() }))
+        .send()
+        .await
+        .expect("failed to resolve incident");
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let update: Value = client
+        .post(format!("{}/api/incidents/{}/updates", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&json!({ "message": "Postmortem scheduled" }))
+        .send()
+        .await
+        .expect("failed to post update")
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(update["post_resolution"], true);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn posting_updates_requires_authentication() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+
+    let res = client
+        .post(format!("{}/api/incidents/{}/updates", base, id))
+        .json(&json!({ "message": "anonymous" }))
+        .send()
+        .await
+        .expect("failed to post update");
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn notification_preview_is_admin_only_dry_run() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+    let url = format!("{}/api/incidents/{}/notifications/preview", base, id);
+
+    let res = client
+        .get(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .send()
+        .await
+        .expect("failed to request preview");
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let preview: Value = client
+        .get(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .send()
+        .await
+        .expect("failed to request preview")
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(preview["auto_notify"], true);
+    assert!(preview["notification"]["subject"]
+        .as_str()
+        .expect("subject should be a string")
+        .starts_with("[MAJOR] outage"));
+    assert!(preview["channels"].is_array());
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn unknown_affected_contracts_return_422() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+    let unknown = Uuid::new_v4();
+
+    let res = client
+        .post(format!("{}/api/incidents", base))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "incident_type": "outage",
+            "description": "RPC outage",
+            "affected_contract_ids": [unknown],
+        }))
+        .send()
+        .await
+        .expect("failed to create incident");
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = res.json().await.expect("invalid json");
+    assert_eq!(
+        body["details"]["unknown_contract_ids"][0],
+        unknown.to_string()
+    );
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn contract_incidents_for_missing_contract_returns_404() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let res = client
+        .get(format!(
+            "{}/api/contracts/{}/incidents",
+            base,
+            Uuid::new_v4()
+        ))
+        .send()
+        .await
+        .expect("failed to list contract incidents");
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn list_incidents_returns_pagination_envelope() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    create_test_incident(&client, &base).await;
+    create_test_incident(&client, &base).await;
+
+    let page: Value = client
+        .get(format!("{}/api/incidents?limit=1&offset=0", base))
+        .send()
+        .await
+        .expect("failed to list incidents")
+        .json()
+        .await
+        .expect("invalid json");
+
+    assert_eq!(page["limit"], 1);
+    assert_eq!(page["offset"], 0);
+    assert_eq!(page["items"].as_array().map(Vec::len), Some(1));
+    assert!(page["total"].as_i64().unwrap_or(0) >= 2);
+    assert_eq!(page["has_more"], true);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn list_incidents_envelope_false_returns_bare_array() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let res = client
+        .get(format!("{}/api/incidents?envelope=false", base))
+        .send()
+        .await
+        .expect("failed to list incidents");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers()
+            .get("deprecation")
+            .and_then(|v| v.to_str().ok()),
+        Some("true")
+    );
+    let body: Value = res.json().await.expect("invalid json");
+    assert!(body.is_array());
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn open_incident_report_is_markdown_with_banner() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+
+    let res = client
+        .get(format!(
+            "{}/api/incidents/{}/report?format=markdown",
+            base, id
+        ))
+        .send()
+        .await
+        .expect("failed to fetch report");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .starts_with("text/markdown"));
+    let body = res.text().await.expect("invalid body");
+    assert!(body.contains("INCIDENT STILL OPEN"));
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn report_rejects_unsupported_format() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+
+    let res = client
+        .get(format!("{}/api/incidents/{}/report?format=pdf", base, id))
+        .send()
+        .await
+        .expect("failed to fetch report");
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn update_missing_incident_returns_404() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let res = client
+        .patch(format!("{}/api/incidents/{}", base, Uuid::new_v4()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "lessons_learned": "n/a" }))
+        .send()
+        .await
+        .expect("failed to update incident");
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    let body: Value = res.json().await.expect("invalid json");
+    assert_eq!(body["error"]["code"], "NOT_FOUND");
+    assert_eq!(body["error"]["details"]["reason"], "IncidentNotFound");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn update_rejects_end_time_before_start_time() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+
+    let res = client
+        .patch(format!("{}/api/incidents/{}", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "end_time": "2000-01-01T00:00:00Z" }))
+        .send()
+        .await
+        .expect("failed to update incident");
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn update_rejects_recovery_metrics_on_open_incident() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+
+    let res = client
+        .patch(format!("{}/api/incidents/{}", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "rto_achieved": "1h" }))
+        .send()
+        .await
+        .expect("failed to update incident");
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn partial_update_preserves_omitted_fields() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+    let url = format!("{}/api/incidents/{}", base, id);
+
+    let resolved: Value = client
+        .patch(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "end_time": chrono::Utc::now().to_rfc3339(),
+            "rto_achieved": 1800,
+        }))
+        .send()
+        .await
+        .expect("failed to resolve incident")
+        .json()
+        .await
+        .expect("invalid json");
+    assert!(!resolved["end_time"].is_null());
+
+    let updated: Value = client
+        .patch(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "lessons_learned": "Add alerting on ledger lag" }))
+        .send()
+        .await
+        .expect("failed to update incident")
+        .json()
+        .await
+        .expect("invalid json");
+
+    assert_eq!(updated["end_time"], resolved["end_time"]);
+    assert_eq!(updated["rto_achieved"]["seconds"], 1800);
+    assert_eq!(updated["lessons_learned"], "Add alerting on ledger lag");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn severity_changes_are_recorded_in_timeline() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+    assert_eq!(incident["severity"], "major");
+
+    for severity in ["critical", "critical"] {
+        let res = client
+            .patch(format!("{}/api/incidents/{}", base, id))
+            .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+            .json(&json!({ "severity": severity }))
+            .send()
+            .await
+            .expect("failed to update incident");
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    let timeline: Vec<Value> = client
+        .get(format!("{}/api/incidents/{}/updates", base, id))
+        .send()
+        .await
+        .expect("failed to fetch timeline")
+        .json()
+        .await
+        .expect("invalid json");
+
+    // Only the first update changed the severity
+    assert_eq!(timeline.len(), 1);
+    assert_eq!(
+        timeline[0]["message"],
+        "Severity changed from major to critical."
+    );
+    assert_eq!(timeline[0]["author"], TOKEN_SUBJECT);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn status_is_public_cacheable_and_hides_internal_fields() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    create_test_incident(&client, &base).await;
+
+    let res = client
+        .get(format!("{}/api/status", base))
+        .send()
+        .await
+        .expect("failed to fetch status");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.headers()
+            .get("cache-control")
+            .and_then(|v| v.to_str().ok()),
+        Some("public, max-age=30")
+    );
+    let body: Value = res.json().await.expect("invalid json");
+    assert_ne!(body["status"], "operational");
+    let open = body["open_incidents"].as_array().expect("open incidents");
+    assert!(!open.is_empty());
+    assert!(open.iter().all(|i| i.get("lessons_learned").is_none()));
+    assert!(body["uptime"]["last_30_days"].as_f64().is_some());
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn postmortem_action_items_can_be_completed_individually() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+
+    let postmortem: Value = client
+        .put(format!("{}/api/incidents/{}/postmortem", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&json!({
+            "root_cause": "Expired TLS certificate",
+            "detection_method": "Customer report",
+            "contributing_factors": ["No expiry alerting"],
+            "action_items": [
+                { "description": "Automate renewal", "owner": "ops", "due_date": "2030-01-01" }
+            ]
+        }))
+        .send()
+        .await
+        .expect("failed to save postmortem")
+        .json()
+        .await
+        .expect("invalid json");
+    let item_id = postmortem["action_items"][0]["id"]
+        .as_str()
+        .expect("action item id");
+    assert_eq!(postmortem["action_items"][0]["done"], false);
+
+    let open: Value = client
+        .get(format!("{}/api/incidents/action-items?open=true", base))
+        .send()
+        .await
+        .expect("failed to list action items")
+        .json()
+        .await
+        .expect("invalid json");
+    assert!(open
+        .as_array()
+        .expect("array")
+        .iter()
+        .any(|i| i["id"] == item_id));
+
+    let res = client
+        .patch(format!(
+            "{}/api/incidents/{}/action-items/{}",
+            base, id, item_id
+        ))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&json!({ "done": true }))
+        .send()
+        .await
+        .expect("failed to update action item");
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let detail: Value = client
+        .get(format!("{}/api/incidents/{}", base, id))
+        .send()
+        .await
+        .expect("failed to fetch incident")
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(
+        detail["postmortem"]["root_cause"],
+        "Expired TLS certificate"
+    );
+    assert_eq!(detail["postmortem"]["action_items"][0]["done"], true);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn postmortem_requires_authentication() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+
+    let res = client
+        .put(format!("{}/api/incidents/{}/postmortem", base, id))
+        .json(&json!({ "root_cause": "unknown" }))
+        .send()
+        .await
+        .expect("failed to save postmortem");
+
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn incident_feed_is_atom_with_stable_entry_ids() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let incident = create_test_incident(&client, &base).await;
+    let id = incident["id"].as_str().expect("incident id");
+
+    let fetch = || async {
+        let res = client
+            .get(format!("{}/api/incidents/feed.atom", base))
+            .send()
+            .await
+            .expect("failed to fetch feed");
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .starts_with("application/atom+xml"));
+        res.text().await.expect("invalid body")
+    };
+
+    let first = fetch().await;
+    let second = fetch().await;
+    let entry_id = format!("incidents/{}/opened</id>", id);
+    assert!(first.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+    assert!(first.contains(&entry_id));
+    assert!(second.contains(&entry_id));
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn incident_feed_rejects_unknown_include() {
+    let base = api_base_url();
+    let res = reqwest::Client::new()
+        .get(format!("{}/api/incidents/feed.atom?include=drafts", base))
+        .send()
+        .await
+        .expect("failed to fetch feed");
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn acknowledging_is_idempotent_and_recorded_in_timeline() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+
+    let created = create_test_incident(&client, &base).await;
+    let id = created["id"].as_str().expect("incident missing id");
+    let url = format!("{}/api/incidents/{}/ack", base, id);
+    assert!(created["acknowledged_at"].is_null());
+    assert_eq!(created["escalation_step"], 0);
+
+    let res = client.post(&url).send().await.expect("failed to ack");
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    let first: Value = client
+        .post(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .send()
+        .await
+        .expect("failed to ack")
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(first["acknowledged_by"], "incident-tests");
+    assert!(first["acknowledged_at"].is_string());
+
+    let res = client
+        .post(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .send()
+        .await
+        .expect("failed to ack");
+    assert_eq!(res.status(), StatusCode::OK);
+    let second: Value = res.json().await.expect("invalid json");
+    assert_eq!(second["acknowledged_at"], first["acknowledged_at"]);
+
+    let timeline: Vec<Value> = client
+        .get(format!("{}/api/incidents/{}/updates", base, id))
+        .send()
+        .await
+        .expect("failed to list updates")
+        .json()
+        .await
+        .expect("invalid json");
+    let acks = timeline
+        .iter()
+        .filter(|update| {
+            update["message"]
+                .as_str()
+                .is_some_and(|message| message.starts_with("Acknowledged by"))
+        })
+        .count();
+    assert_eq!(acks, 1);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn acknowledging_missing_incident_returns_404() {
+    let base = api_base_url();
+    let res = reqwest::Client::new()
+        .post(format!("{}/api/incidents/{}/ack", base, Uuid::new_v4()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .send()
+        .await
+        .expect("failed to ack");
+
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn escalation_policies_are_admin_only_and_validate_channels() {
+    let base = api_base_url();
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/escalation-policies", base);
+    let policy = json!({
+        "incident_type": format!("test-{}", Uuid::new_v4().simple()),
+        "steps": [{ "delay": "5m", "channel_ids": [Uuid::new_v4()] }],
+    });
+
+    let res = client
+        .post(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&policy)
+        .send()
+        .await
+        .expect("failed to create policy");
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = client
+        .post(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&policy)
+        .send()
+        .await
+        .expect("failed to create policy");
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = res.json().await.expect("invalid json");
+    assert_eq!(
+        body["error"]["details"]["reason"],
+        "UnknownNotificationChannel"
+    );
+
+    let res = client
+        .post(&url)
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "steps": [] }))
+        .send()
+        .await
+        .expect("failed to create policy");
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,161 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// MAINTENANCE WINDOW API TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// To run tests:
+// 1. Start the API server: cargo run --bin api
+// 2. Run tests: cargo test --test maintenance_tests -- --include-ignored
+// ═══════════════════════════════════════════════════════════════════════════
+
+mod common;
+
+use chrono::{Duration, Utc};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::bearer_token;
+
+fn api_base_url() -> String {
+    std::env::var("TEST_API_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+/// Subject of the tokens these tests mint
+const TOKEN_SUBJECT: &str = "maintenance-tests";
+
+/// Unique component name so tests don't collide with each other's windows.
+fn component() -> String {
+    format!("test-{}", Uuid::new_v4())
+}
+
+async fn schedule(
+    client: &reqwest::Client,
+    component: &str,
+    start_offset_hours: i64,
+    hours: i64,
+) -> reqwest::Response {
+    let start = Utc::now() + Duration::hours(start_offset_hours);
+    client
+        .post(format!("{}/api/maintenance-windows", api_base_url()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "title": "Database upgrade",
+            "scheduled_start": start.to_rfc3339(),
+            "scheduled_end": (start + Duration::hours(hours)).to_rfc3339(),
+            "components": [component],
+        }))
+        .send()
+        .await
+        .expect("failed to schedule maintenance window")
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn scheduling_requires_admin() {
+    let res = reqwest::Client::new()
+        .post(format!("{}/api/maintenance-windows", api_base_url()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .json(&json!({
+            "title": "Upgrade",
+            "scheduled_start": Utc::now().to_rfc3339(),
+            "scheduled_end": (Utc::now() + Duration::hours(1)).to_rfc3339(),
+            "components": ["api"],
+        }))
+        .send()
+        .await
+        .expect("request failed");
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn overlapping_window_for_same_component_is_rejected() {
+    let client = reqwest::Client::new();
+    let component = component();
+
+    let first = schedule(&client, &component, 24, 2).await;
+    assert_eq!(first.status(), StatusCode::CREATED);
+    let first: Value = first.json().await.expect("invalid json");
+
+    let overlapping = schedule(&client, &component, 25, 2).await;
+    assert_eq!(overlapping.status(), StatusCode::CONFLICT);
+    let body: Value = overlapping.json().await.expect("invalid json");
+    assert_eq!(body["details"]["conflicting_window_id"], first["id"]);
+
+    let adjacent = schedule(&client, &component, 26, 1).await;
+    assert_eq!(adjacent.status(), StatusCode::CREATED);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn upcoming_windows_are_listed_and_cancelled_ones_dropped() {
+    let client = reqwest::Client::new();
+    let base = api_base_url();
+
+    let window: Value = schedule(&client, &component(), 48, 1)
+        .await
+        .json()
+        .await
+        .expect("invalid json");
+    let id = window["id"].as_str().expect("window id");
+
+    let upcoming = |client: reqwest::Client, base: String| async move {
+        let windows: Vec<Value> = client
+            .get(format!("{}/api/maintenance-windows?upcoming=true", base))
+            .send()
+            .await
+            .expect("failed to list windows")
+            .json()
+            .await
+            .expect("invalid json");
+        windows
+    };
+    assert!(upcoming(client.clone(), base.clone())
+        .await
+        .iter()
+        .any(|w| w["id"] == id));
+
+    let res = client
+        .patch(format!("{}/api/maintenance-windows/{}", base, id))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "status": "cancelled" }))
+        .send()
+        .await
+        .expect("failed to cancel window");
+    assert_eq!(res.status(), StatusCode::OK);
+
+    assert!(!upcoming(client.clone(), base.clone())
+        .await
+        .iter()
+        .any(|w| w["id"] == id));
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn incidents_during_maintenance_are_tagged() {
+    let client = reqwest::Client::new();
+    let base = api_base_url();
+
+    let window: Value = schedule(&client, &component(), -1, 2)
+        .await
+        .json()
+        .await
+        .expect("invalid json");
+
+    let incident: Value = client
+        .post(format!("{}/api/incidents", base))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "incident_type": "outage",
+            "description": "Planned failover",
+        }))
+        .send()
+        .await
+        .expect("failed to create incident")
+        .json()
+        .await
+        .expect("invalid json");
+
+    assert_eq!(incident["maintenance_window_id"], window["id"]);
+}
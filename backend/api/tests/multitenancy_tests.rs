@@ -25,6 +25,22 @@ fn test_state() -> AppState {
             .max_connections(1)
             .connect_lazy("postgres://localhost/test")
             .unwrap(),
+        config: Arc::new(
+            api::config::Config::from_lookup(|key| match key {
+                "DATABASE_URL" => Some("postgres://localhost/test".to_string()),
+                "JWT_SECRET" => Some("a".repeat(32)),
+                _ => None,
+            })
+            .unwrap(),
+        ),
+        read_db: None,
+        outbound: api::outbound_http::OutboundClient::new(Default::default()),
+        flags: Default::default(),
+        component_health: Arc::new(api::component_health::ComponentHealth::new(
+            "http://localhost:3001/api/status",
+            Default::default(),
+        )),
+        source_storage: shared::source_storage::SourceStorage::local(std::env::temp_dir()),
         started_at: Instant::now(),
         cache: Arc::new(CacheLayer::new(CacheConfig::default())),
         registry,
@@ -0,0 +1,176 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// ORGANIZATION OWNERSHIP TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// Organizations own contracts; their admins (owners) manage members and their
+// members (maintainers) publish and update the org's contracts.
+//
+// Tokens are signed locally, so JWT_SECRET must match the server's.
+//
+// To run tests:
+// 1. Start the API server: cargo run --bin api
+// 2. Run tests: JWT_SECRET=... cargo test --test organization_tests -- --include-ignored
+// ═══════════════════════════════════════════════════════════════════════════
+
+mod common;
+
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::bearer_token;
+
+fn api_base_url() -> String {
+    std::env::var("TEST_API_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+fn token_for(address: &str) -> String {
+    bearer_token(address, false)
+}
+
+fn unique(prefix: &str) -> String {
+    format!("{}-{}", prefix, &Uuid::new_v4().simple().to_string()[..12])
+}
+
+/// Creates a public organization owned by `owner` and returns its JSON
+async fn create_org(client: &reqwest::Client, owner: &str) -> Value {
+    let slug = unique("org");
+    let res = client
+        .post(format!("{}/api/organizations", api_base_url()))
+        .bearer_auth(token_for(owner))
+        .json(&json!({ "name": slug, "slug": slug, "is_private": false }))
+        .send()
+        .await
+        .expect("request failed");
+    assert_eq!(res.status(), StatusCode::CREATED);
+    res.json().await.expect("invalid json")
+}
+
+async fn add_member(client: &reqwest::Client, org: &Value, as_: &str, address: &str, role: &str) {
+    let res = client
+        .post(format!(
+            "{}/api/organizations/{}/members",
+            api_base_url(),
+            org["id"].as_str().unwrap()
+        ))
+        .bearer_auth(token_for(as_))
+        .json(&json!({ "address": address, "role": role }))
+        .send()
+        .await
+        .expect("request failed");
+    assert_eq!(res.status(), StatusCode::CREATED);
+}
+
+async fn publish(client: &reqwest::Client, org: &Value, as_: &str) -> reqwest::Response {
+    client
+        .post(format!("{}/api/contracts", api_base_url()))
+        .bearer_auth(token_for(as_))
+        .json(&json!({
+            "contract_id": format!("C{}", Uuid::new_v4().simple()).to_uppercase(),
+            "name": unique("org-contract"),
+            "network": "testnet",
+            "publisher_address": as_,
+            "organization": org["slug"],
+        }))
+        .send()
+        .await
+        .expect("request failed")
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn maintainers_publish_and_update_org_contracts() {
+    let client = reqwest::Client::new();
+    let owner = unique("owner");
+    let maintainer = unique("maintainer");
+    let outsider = unique("outsider");
+    let org = create_org(&client, &owner).await;
+    add_member(&client, &org, &owner, &maintainer, "maintainer").await;
+
+    let res = publish(&client, &org, &outsider).await;
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = publish(&client, &org, &maintainer).await;
+    assert_eq!(res.status(), StatusCode::OK);
+    let contract: Value = res.json().await.unwrap();
+    assert_eq!(contract["organization_id"], org["id"]);
+
+    let metadata_url = format!(
+        "{}/api/contracts/{}/metadata",
+        api_base_url(),
+        contract["id"].as_str().unwrap()
+    );
+    let res = client
+        .patch(&metadata_url)
+        .bearer_auth(token_for(&outsider))
+        .json(&json!({ "description": "not mine" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+    let res = client
+        .patch(&metadata_url)
+        .bearer_auth(token_for(&owner))
+        .json(&json!({ "description": "team maintained" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let page: Value = client
+        .get(format!(
+            "{}/api/organizations/{}/contracts",
+            api_base_url(),
+            org["slug"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["id"], contract["id"]);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn the_last_owner_cannot_be_removed_or_demoted() {
+    let client = reqwest::Client::new();
+    let owner = unique("owner");
+    let org = create_org(&client, &owner).await;
+    let members_url = format!(
+        "{}/api/organizations/{}/members",
+        api_base_url(),
+        org["id"].as_str().unwrap()
+    );
+
+    let res = client
+        .delete(format!("{}/{}", members_url, owner))
+        .bearer_auth(token_for(&owner))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    let res = client
+        .post(&members_url)
+        .bearer_auth(token_for(&owner))
+        .json(&json!({ "address": owner, "role": "maintainer" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::CONFLICT);
+
+    // With a second owner the first one may leave
+    let second = unique("owner");
+    add_member(&client, &org, &owner, &second, "owner").await;
+    let res = client
+        .delete(format!("{}/{}", members_url, owner))
+        .bearer_auth(token_for(&owner))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+}
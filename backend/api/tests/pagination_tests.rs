@@ -0,0 +1,69 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// PAGINATION ENVELOPE TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// Contract and incident listings share one envelope
+// (`items`, `total`, `limit`, `offset`, `has_more`) and reject the same
+// out-of-range parameters with a 400.
+//
+// To run tests:
+// 1. Start the API server: cargo run --bin api
+// 2. Run tests: cargo test --test pagination_tests -- --include-ignored
+// ═══════════════════════════════════════════════════════════════════════════
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+const LISTINGS: [&str; 2] = ["/api/contracts", "/api/incidents"];
+
+fn api_base_url() -> String {
+    std::env::var("TEST_API_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn listings_share_the_envelope() {
+    let client = reqwest::Client::new();
+
+    for path in LISTINGS {
+        let page: Value = client
+            .get(format!("{}{}?limit=5&offset=0", api_base_url(), path))
+            .send()
+            .await
+            .expect("request failed")
+            .json()
+            .await
+            .expect("invalid json");
+
+        assert!(page["items"].is_array(), "{path}: {page}");
+        assert!(page["total"].is_i64(), "{path}: {page}");
+        assert_eq!(page["limit"], 5, "{path}");
+        assert_eq!(page["offset"], 0, "{path}");
+        assert!(page["has_more"].is_boolean(), "{path}");
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn out_of_range_parameters_are_rejected_uniformly() {
+    let client = reqwest::Client::new();
+
+    for path in LISTINGS {
+        for (query, field) in [
+            ("limit=0", "limit"),
+            ("limit=101", "limit"),
+            ("offset=-1", "offset"),
+        ] {
+            let res = client
+                .get(format!("{}{}?{}", api_base_url(), path, query))
+                .send()
+                .await
+                .expect("request failed");
+
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{path}?{query}");
+            let body: Value = res.json().await.expect("invalid json");
+            assert_eq!(body["error"]["code"], "VALIDATION_ERROR", "{path}?{query}");
+            assert_eq!(body["error"]["details"]["field"], field, "{path}?{query}");
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// RECOVERY OBJECTIVE API TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// To run tests:
+// 1. Start the API server: cargo run --bin api
+// 2. Run tests: cargo test --test recovery_objective_tests -- --include-ignored
+// ═══════════════════════════════════════════════════════════════════════════
+
+mod common;
+
+use chrono::{Duration, Utc};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use common::bearer_token;
+
+fn api_base_url() -> String {
+    std::env::var("TEST_API_BASE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string())
+}
+
+/// Subject of the tokens these tests mint
+const TOKEN_SUBJECT: &str = "recovery-objective-tests";
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn managing_objectives_requires_admin() {
+    let res = reqwest::Client::new()
+        .get(format!("{}/api/recovery-objectives", api_base_url()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, false))
+        .send()
+        .await
+        .expect("request failed");
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn override_for_unknown_contract_returns_422() {
+    let res = reqwest::Client::new()
+        .post(format!("{}/api/recovery-objectives", api_base_url()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "contract_id": Uuid::new_v4(),
+            "rto_target": "2h",
+            "rpo_target": "15m",
+        }))
+        .send()
+        .await
+        .expect("request failed");
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn zero_rto_target_is_rejected() {
+    let res = reqwest::Client::new()
+        .post(format!("{}/api/recovery-objectives", api_base_url()))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({ "rto_target": 0, "rpo_target": 0 }))
+        .send()
+        .await
+        .expect("request failed");
+
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let body: Value = res.json().await.expect("invalid json");
+    assert_eq!(body["error"]["code"], "UNPROCESSABLE_ENTITY");
+    assert_eq!(body["error"]["details"]["reason"], "InvalidRecoveryDuration");
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn resolving_an_incident_records_target_and_delta() {
+    let client = reqwest::Client::new();
+    let base_url = api_base_url();
+
+    let created: Value = client
+        .post(format!("{}/api/incidents", base_url))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "incident_type": "outage",
+            "severity": "minor",
+            "description": "Recovery objective test incident",
+            "start_time": (Utc::now() - Duration::hours(2)).to_rfc3339(),
+        }))
+        .send()
+        .await
+        .expect("failed to create incident")
+        .json()
+        .await
+        .expect("invalid json");
+
+    let res = client
+        .patch(format!(
+            "{}/api/incidents/{}",
+            base_url,
+            created["id"].as_str().unwrap()
+        ))
+        .bearer_auth(bearer_token(TOKEN_SUBJECT, true))
+        .json(&json!({
+            "end_time": Utc::now().to_rfc3339(),
+            "rto_achieved": "1h",
+        }))
+        .send()
+        .await
+        .expect("failed to resolve incident");
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let resolved: Value = res.json().await.expect("invalid json");
+    let objectives = &resolved["objectives"];
+    let target = objectives["rto_target_seconds"]
+        .as_i64()
+        .expect("RTO target recorded on resolution");
+    assert_eq!(
+        objectives["rto_delta_seconds"].as_i64(),
+        Some(3600 - target)
+    );
+    assert_eq!(objectives["rto_met"].as_bool(), Some(3600 <= target));
+    assert!(objectives["rpo_target_seconds"].is_i64());
+    assert!(objectives["rpo_met"].is_null());
+
+    // Warnings only appear when an operator configured targets, but they
+    // must never mention an objective that was recorded.
+    if let Some(warnings) = resolved["warnings"].as_array() {
+        assert!(warnings
+            .iter()
+            .all(|w| !w.as_str().unwrap_or_default().contains("rto_achieved")));
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires running API + database"]
+async fn metrics_report_target_source_and_mean_delta() {
+    let metrics: Value = reqwest::Client::new()
+        .get(format!("{}/api/incidents/metrics", api_base_url()))
+        .send()
+        .await
+        .expect("request failed")
+        .json()
+        .await
+        .expect("invalid json");
+
+    for key in ["rto_compliance", "rpo_compliance"] {
+        let compliance = &metrics[key];
+        assert!(compliance["target_seconds"].is_i64());
+        assert!(compliance["target_source"].is_string());
+        assert!(compliance.get("mean_delta_seconds").is_some());
+    }
+}
@@ -4,7 +4,7 @@
 //
 // Comprehensive tests for the review system covering:
 // - Review submission and validation
-// - Review fetching with sorting
+// - Review fetching with pagination, sorting and the rating filter
 // - Helpful voting
 // - Review flagging
 // - Admin moderation
@@ -237,7 +237,7 @@ async fn test_fetch_reviews_sorting() {
     // Create a test contract
     let contract_id = create_test_contract(&client, &base).await;
 
-    // Test default sorting (most_recent)
+    // Test default sorting (newest)
     let res = client
         .get(format!("{}/api/contracts/{}/reviews", base, contract_id))
         .send()
@@ -250,25 +250,45 @@ async fn test_fetch_reviews_sorting() {
         "fetching reviews should succeed"
     );
 
-    let reviews: Vec<Value> = res.json().await.expect("failed to parse reviews");
-    assert!(reviews.is_array(), "reviews response should be an array");
+    let page: Value = res.json().await.expect("failed to parse reviews");
+    assert!(page["items"].is_array(), "reviews should be paginated");
+    assert_eq!(page["limit"], 20, "default page size");
+
+    // Test every sort order, and the older `sort_by` names
+    for sort in [
+        "sort=newest",
+        "sort=highest",
+        "sort=lowest",
+        "sort=most_helpful",
+        "sort_by=highest_rated",
+    ] {
+        let res = client
+            .get(format!(
+                "{}/api/contracts/{}/reviews?{}",
+                base, contract_id, sort
+            ))
+            .send()
+            .await
+            .expect("failed to fetch reviews");
+
+        assert_eq!(res.status(), StatusCode::OK, "{} should be accepted", sort);
+    }
 
-    // Test sorting by most_helpful
     let res = client
         .get(format!(
-            "{}/api/contracts/{}/reviews?sort_by=most_helpful",
+            "{}/api/contracts/{}/reviews?sort=best",
             base, contract_id
         ))
         .send()
         .await
         .expect("failed to fetch reviews");
 
-    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
 
-    // Test sorting by highest_rated
+    // Test pagination
     let res = client
         .get(format!(
-            "{}/api/contracts/{}/reviews?sort_by=highest_rated",
+            "{}/api/contracts/{}/reviews?limit=10&offset=0",
             base, contract_id
         ))
         .send()
@@ -276,11 +296,14 @@ async fn test_fetch_reviews_sorting() {
         .expect("failed to fetch reviews");
 
     assert_eq!(res.status(), StatusCode::OK);
+    let page: Value = res.json().await.expect("failed to parse reviews");
+    assert_eq!(page["limit"], 10);
+    assert_eq!(page["offset"], 0);
 
-    // Test sorting by lowest_rated
+    // Test the star filter
     let res = client
         .get(format!(
-            "{}/api/contracts/{}/reviews?sort_by=lowest_rated",
+            "{}/api/contracts/{}/reviews?rating=4",
             base, contract_id
         ))
         .send()
@@ -289,17 +312,20 @@ async fn test_fetch_reviews_sorting() {
 
     assert_eq!(res.status(), StatusCode::OK);
 
-    // Test pagination
     let res = client
         .get(format!(
-            "{}/api/contracts/{}/reviews?limit=10&offset=0",
+            "{}/api/contracts/{}/reviews?rating=6",
             base, contract_id
         ))
         .send()
         .await
         .expect("failed to fetch reviews");
 
-    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.status(),
+        StatusCode::BAD_REQUEST,
+        "ratings are 1 to 5 stars"
+    );
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -358,6 +384,20 @@ async fn test_rating_aggregation() {
         "average rating should be between 0 and 5"
     );
     assert!(total_reviews >= 0, "total reviews should be non-negative");
+
+    // The contract's details carry the same aggregate and its histogram
+    let res = client
+        .get(format!("{}/api/contracts/{}", base, contract_id))
+        .send()
+        .await
+        .expect("failed to fetch contract");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let contract: Value = res.json().await.expect("failed to parse contract");
+    let histogram = &contract["ratings"]["rating_distribution"];
+    for stars in ["stars_1", "stars_2", "stars_3", "stars_4", "stars_5"] {
+        assert_eq!(histogram[stars], 0, "{} should be 0 for no reviews", stars);
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -459,6 +499,22 @@ async fn test_helpful_voting() {
         StatusCode::NOT_FOUND,
         "voting on non-existent review should fail"
     );
+
+    // Toggling a helpful vote needs a token to key the vote on
+    let res = client
+        .post(format!(
+            "{}/api/contracts/{}/reviews/{}/helpful",
+            base, contract_id, fake_review_id
+        ))
+        .send()
+        .await
+        .expect("failed to vote");
+
+    assert_eq!(
+        res.status(),
+        StatusCode::UNAUTHORIZED,
+        "helpful votes require authentication"
+    );
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -556,11 +612,14 @@ async fn test_reviews_edge_cases() {
         .expect("failed to fetch reviews");
 
     assert_eq!(res.status(), StatusCode::OK);
-    let reviews: Vec<Value> = res.json().await.expect("failed to parse reviews");
-    assert!(
-        reviews.is_empty() || reviews.is_array(),
-        "should return empty array for no reviews"
+    let page: Value = res.json().await.expect("failed to parse reviews");
+    assert_eq!(
+        page["items"].as_array().map(Vec::len),
+        Some(0),
+        "should return an empty page for no reviews"
     );
+    assert_eq!(page["total"], 0);
+    assert_eq!(page["has_more"], false);
 
     // Test: Rating stats for contract with no reviews
     let res = client
@@ -0,0 +1,119 @@
+// ═══════════════════════════════════════════════════════════════════════════
+// GRACEFUL SHUTDOWN TESTS
+// ═══════════════════════════════════════════════════════════════════════════
+//
+// These serve a small router on a local port, so no database is needed.
+//
+// To run tests:
+//   cargo test --test shutdown_tests
+// ═══════════════════════════════════════════════════════════════════════════
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::shutdown::{self, Shutdown};
+use axum::{middleware, routing::get, Router};
+use reqwest::StatusCode;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+
+async fn slow() -> &'static str {
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    "done"
+}
+
+fn app(draining: Arc<AtomicBool>) -> Router {
+    Router::new()
+        .route("/slow", get(slow))
+        .route("/fast", get(|| async { "ok" }))
+        .layer(middleware::from_fn_with_state(
+            draining,
+            shutdown::track_in_flight,
+        ))
+}
+
+#[tokio::test]
+async fn in_flight_request_completes_while_new_requests_are_rejected() {
+    let draining = Arc::new(AtomicBool::new(false));
+    let shutdown = Arc::new(Shutdown::new(draining.clone(), Duration::from_secs(5)));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    let (trigger, signal) = oneshot::channel::<()>();
+    let server = {
+        let shutdown = shutdown.clone();
+        let app = app(draining.clone());
+        tokio::spawn(async move {
+            shutdown
+                .serve(listener, app, async {
+                    let _ = signal.await;
+                })
+                .await
+        })
+    };
+
+    let in_flight = tokio::spawn(reqwest::Client::new().get(format!("{base}/slow")).send());
+    // Let the slow request reach its handler before shutdown starts.
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    trigger.send(()).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    assert!(shutdown.is_draining());
+    match reqwest::Client::new()
+        .get(format!("{base}/fast"))
+        .send()
+        .await
+    {
+        // Refused outright once the listener is closed, or a 503 if the
+        // connection was accepted just before.
+        Ok(res) => assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE),
+        Err(err) => assert!(err.is_connect(), "unexpected error: {err}"),
+    }
+
+    let res = in_flight
+        .await
+        .unwrap()
+        .expect("in-flight request should complete");
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await.unwrap(), "done");
+
+    let drained = server.await.unwrap().expect("server error");
+    assert!(drained, "requests should drain within the timeout");
+}
+
+#[tokio::test]
+async fn drain_timeout_cuts_off_stuck_requests() {
+    let draining = Arc::new(AtomicBool::new(false));
+    let shutdown = Shutdown::new(draining.clone(), Duration::from_millis(200));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    let _in_flight = tokio::spawn(reqwest::Client::new().get(format!("{base}/slow")).send());
+    let signal = tokio::time::sleep(Duration::from_millis(300));
+
+    let drained = shutdown
+        .serve(listener, app(draining), signal)
+        .await
+        .expect("server error");
+    assert!(!drained);
+}
+
+#[tokio::test]
+async fn background_tasks_finish_their_current_unit_of_work() {
+    let shutdown = Shutdown::new(Arc::new(AtomicBool::new(false)), Duration::from_secs(5));
+    let finished = Arc::new(AtomicBool::new(false));
+
+    let token = shutdown.token();
+    let done = finished.clone();
+    shutdown.spawn(async move {
+        token.cancelled().await;
+        // Simulates writing out the unit of work in progress.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        done.store(true, Ordering::SeqCst);
+    });
+
+    assert!(shutdown.stop_background_tasks().await);
+    assert!(finished.load(Ordering::SeqCst));
+    assert!(shutdown.is_draining());
+}
@@ -1,9 +1,12 @@
-//! Parse Soroban contract ABI and generate OpenAPI 3.0 documentation.
+//! Parse Soroban contract ABI and generate OpenAPI 3.0 documentation and
+//! TypeScript bindings.
 
 pub mod openapi;
 pub mod parser;
 pub mod types;
+pub mod typescript;
 
 pub use openapi::{generate_openapi, to_json, to_yaml, OpenApiDoc};
 pub use parser::{parse_contract_abi, parse_json_spec, ParseError, RawContractSpec};
 pub use types::*;
+pub use typescript::{generate_typescript, BindingsSource, GeneratedFile};
@@ -87,6 +87,22 @@ impl SorobanType {
                         element_type: Box::new(Self::from_type_string(&inner)),
                     };
                 }
+                if let Some(inner) = Self::extract_generic(trimmed, "Result") {
+                    if let Some((ok, err)) = Self::split_pair(&inner) {
+                        return SorobanType::Result {
+                            ok_type: Box::new(Self::from_type_string(ok)),
+                            err_type: Box::new(Self::from_type_string(err)),
+                        };
+                    }
+                }
+                if let Some(inner) = Self::extract_generic(trimmed, "Map") {
+                    if let Some((key, value)) = Self::split_pair(&inner) {
+                        return SorobanType::Map {
+                            key_type: Box::new(Self::from_type_string(key)),
+                            value_type: Box::new(Self::from_type_string(value)),
+                        };
+                    }
+                }
                 if let Some(n) = Self::extract_bytes_n(trimmed) {
                     return SorobanType::BytesN { n };
                 }
@@ -106,6 +122,20 @@ impl SorobanType {
         }
     }
 
+    /// Splits `A, B` at the comma outside any nested `<...>`
+    fn split_pair(inner: &str) -> Option<(&str, &str)> {
+        let mut depth = 0usize;
+        for (i, c) in inner.char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => depth = depth.checked_sub(1)?,
+                ',' if depth == 0 => return Some((&inner[..i], &inner[i + 1..])),
+                _ => {}
+            }
+        }
+        None
+    }
+
     fn extract_bytes_n(type_str: &str) -> Option<u32> {
         if type_str.starts_with("BytesN<") && type_str.ends_with('>') {
             type_str[7..type_str.len() - 1].parse().ok()
@@ -0,0 +1,559 @@
+//! TypeScript bindings generation from contract ABI.
+//!
+//! Produces a module of argument interfaces, contract types and a typed
+//! client that wraps `contract.Client` from `@stellar/stellar-sdk`. Values
+//! are typed the way the SDK converts them from `ScVal`s, so the wrapper
+//! only narrows what the untyped client already returns.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+use crate::types::*;
+
+/// A file to write into the bindings directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    /// Path relative to the output directory
+    pub path: String,
+    pub contents: String,
+}
+
+/// Where the ABI came from, named in the header of every generated file
+#[derive(Debug, Clone)]
+pub struct BindingsSource {
+    /// Contract address, or the registry id when it has none
+    pub contract_id: String,
+    pub version: Option<String>,
+}
+
+/// Generate TypeScript bindings: `<contract>.ts` with the types and client,
+/// and `index.ts` re-exporting it.
+pub fn generate_typescript(abi: &ContractABI, source: &BindingsSource) -> Vec<GeneratedFile> {
+    let module = module_name(&abi.name);
+    let header = header(abi, source);
+
+    let mut index = header.clone();
+    let _ = writeln!(index, "export * from \"./{}\";", module);
+
+    vec![
+        GeneratedFile {
+            path: format!("{}.ts", module),
+            contents: header + &module_body(abi),
+        },
+        GeneratedFile {
+            path: "index.ts".to_string(),
+            contents: index,
+        },
+    ]
+}
+
+fn header(abi: &ContractABI, source: &BindingsSource) -> String {
+    let version = source
+        .version
+        .as_deref()
+        .or(abi.version.as_deref())
+        .unwrap_or("unknown");
+    format!(
+        "// TypeScript bindings for {} ({}), version {}.\n\
+         // Generated by soroban-registry generate-bindings; do not edit.\n\n",
+        comment_safe(&abi.name),
+        comment_safe(&source.contract_id),
+        comment_safe(version)
+    )
+}
+
+fn module_body(abi: &ContractABI) -> String {
+    let mut out = String::new();
+    out.push_str("import type { Buffer } from \"buffer\";\n");
+    out.push_str("import { contract } from \"@stellar/stellar-sdk\";\n");
+
+    // HashMap order is not stable; sort so regenerating gives the same file
+    let types: BTreeMap<&String, &SorobanType> = abi.types.iter().collect();
+    for (name, ty) in &types {
+        out.push('\n');
+        write_type_definition(&mut out, name, ty, abi);
+    }
+
+    if !abi.errors.is_empty() {
+        out.push_str("\n/** Contract error codes, as passed to `errorTypes` */\n");
+        out.push_str("export const Errors: Record<number, { message: string }> = {\n");
+        // Keys must be unique; codes can repeat across error enums
+        let mut seen = BTreeSet::new();
+        for error in abi.errors.iter().filter(|error| seen.insert(error.code)) {
+            let _ = writeln!(
+                out,
+                "  {}: {{ message: {} }},",
+                error.code,
+                string_literal(&error.name)
+            );
+        }
+        out.push_str("};\n");
+    }
+
+    let functions: Vec<&ContractFunction> = abi.public_functions().collect();
+    for function in &functions {
+        if function.params.is_empty() {
+            continue;
+        }
+        out.push('\n');
+        write_doc(&mut out, "", function.doc.as_deref());
+        let _ = writeln!(
+            out,
+            "export interface {} {{",
+            args_interface(&function.name)
+        );
+        for param in &function.params {
+            write_doc(&mut out, "  ", param.doc.as_deref());
+            let _ = writeln!(
+                out,
+                "  {}: {};",
+                property_name(&param.name),
+                ts_type(&param.param_type, abi)
+            );
+        }
+        out.push_str("}\n");
+    }
+
+    write_client(&mut out, abi, &functions);
+    out
+}
+
+fn write_type_definition(out: &mut String, name: &str, ty: &SorobanType, abi: &ContractABI) {
+    let ident = type_name(name);
+    match ty {
+        SorobanType::Struct { fields, .. } if is_tuple_struct(fields) => {
+            let elements: Vec<String> = fields
+                .iter()
+                .map(|field| ts_type(&field.field_type, abi))
+                .collect();
+            let _ = writeln!(
+                out,
+                "export type {} = readonly [{}];",
+                ident,
+                elements.join(", ")
+            );
+        }
+        SorobanType::Struct { fields, .. } => {
+            let _ = writeln!(out, "export interface {} {{", ident);
+            for field in fields {
+                write_doc(out, "  ", field.doc.as_deref());
+                let _ = writeln!(
+                    out,
+                    "  {}: {};",
+                    property_name(&field.name),
+                    ts_type(&field.field_type, abi)
+                );
+            }
+            out.push_str("}\n");
+        }
+        SorobanType::Enum { variants, .. } if is_integer_enum(variants) => {
+            let _ = writeln!(out, "export enum {} {{", ident);
+            for variant in variants {
+                write_doc(out, "  ", variant.doc.as_deref());
+                let _ = writeln!(
+                    out,
+                    "  {} = {},",
+                    identifier(&variant.name),
+                    variant.value.unwrap_or_default()
+                );
+            }
+            out.push_str("}\n");
+        }
+        SorobanType::Enum { variants, .. } => {
+            // Unions come back from the SDK as `{ tag, values }`
+            let _ = writeln!(out, "export type {} =", ident);
+            if variants.is_empty() {
+                out.push_str("  never;\n");
+            }
+            for (i, variant) in variants.iter().enumerate() {
+                let values = match &variant.fields {
+                    Some(fields) if !fields.is_empty() => {
+                        let elements: Vec<String> = fields
+                            .iter()
+                            .map(|field| ts_type(&field.field_type, abi))
+                            .collect();
+                        format!("readonly [{}]", elements.join(", "))
+                    }
+                    _ => "void".to_string(),
+                };
+                let end = if i + 1 == variants.len() { ";" } else { "" };
+                let _ = writeln!(
+                    out,
+                    "  | {{ tag: {}; values: {} }}{}",
+                    string_literal(&variant.name),
+                    values,
+                    end
+                );
+            }
+        }
+        other => {
+            let _ = writeln!(out, "export type {} = {};", ident, ts_type(other, abi));
+        }
+    }
+}
+
+fn write_client(out: &mut String, abi: &ContractABI, functions: &[&ContractFunction]) {
+    let class = format!("{}Client", pascal_case(&abi.name));
+    out.push('\n');
+    let _ = writeln!(
+        out,
+        "/** Typed wrapper around a `contract.Client` for {} */",
+        comment_safe(&abi.name)
+    );
+    let _ = writeln!(out, "export class {} {{", class);
+    out.push_str("  constructor(public readonly client: contract.Client) {}\n\n");
+    out.push_str("  /** Connects to a deployed instance, loading its spec from the network */\n");
+    let _ = writeln!(
+        out,
+        "  static async from(options: contract.ClientOptions): Promise<{}> {{",
+        class
+    );
+    let _ = writeln!(
+        out,
+        "    return new {}(await contract.Client.from(options));",
+        class
+    );
+    out.push_str("  }\n");
+
+    for function in functions {
+        out.push('\n');
+        write_doc(out, "  ", function.doc.as_deref());
+        let result = ts_type(&function.return_type, abi);
+        let method = method_name(&function.name);
+        let name = string_literal(&function.name);
+        if function.params.is_empty() {
+            let _ = writeln!(
+                out,
+                "  {}(options?: contract.MethodOptions): Promise<contract.AssembledTransaction<{}>> {{",
+                method, result
+            );
+            let _ = writeln!(out, "    return this.#call({}, [options]);", name);
+        } else {
+            let _ = writeln!(
+                out,
+                "  {}(args: {}, options?: contract.MethodOptions): Promise<contract.AssembledTransaction<{}>> {{",
+                method,
+                args_interface(&function.name),
+                result
+            );
+            let _ = writeln!(out, "    return this.#call({}, [args, options]);", name);
+        }
+        out.push_str("  }\n");
+    }
+
+    // The SDK client defines one method per contract function at runtime,
+    // taking only the options when the function has no arguments
+    out.push('\n');
+    out.push_str(
+        "  #call<T>(method: string, args: unknown[]): Promise<contract.AssembledTransaction<T>> {\n",
+    );
+    out.push_str("    const fn = (this.client as unknown as Record<string, unknown>)[method];\n");
+    out.push_str("    if (typeof fn !== \"function\") {\n");
+    out.push_str("      throw new TypeError(`contract client has no method ${method}`);\n");
+    out.push_str("    }\n");
+    out.push_str("    return fn.apply(this.client, args);\n");
+    out.push_str("  }\n");
+    out.push_str("}\n");
+}
+
+/// The TypeScript type the SDK produces for a value of `ty`
+fn ts_type(ty: &SorobanType, abi: &ContractABI) -> String {
+    match ty {
+        SorobanType::Bool => "boolean".to_string(),
+        SorobanType::I32 | SorobanType::U32 => "number".to_string(),
+        SorobanType::I64
+        | SorobanType::U64
+        | SorobanType::I128
+        | SorobanType::U128
+        | SorobanType::I256
+        | SorobanType::U256
+        | SorobanType::Timepoint
+        | SorobanType::Duration => "bigint".to_string(),
+        SorobanType::Symbol | SorobanType::String | SorobanType::Address => "string".to_string(),
+        SorobanType::Bytes | SorobanType::BytesN { .. } => "Buffer".to_string(),
+        SorobanType::Void => "void".to_string(),
+        SorobanType::Option { value_type } => format!("{} | undefined", ts_type(value_type, abi)),
+        SorobanType::Result { ok_type, .. } => {
+            format!("contract.Result<{}>", ts_type(ok_type, abi))
+        }
+        SorobanType::Vec { element_type } => format!("Array<{}>", ts_type(element_type, abi)),
+        SorobanType::Map {
+            key_type,
+            value_type,
+        } => format!(
+            "Map<{}, {}>",
+            ts_type(key_type, abi),
+            ts_type(value_type, abi)
+        ),
+        SorobanType::Tuple { elements } => {
+            let elements: Vec<String> = elements.iter().map(|e| ts_type(e, abi)).collect();
+            format!("readonly [{}]", elements.join(", "))
+        }
+        SorobanType::Struct { name, .. } | SorobanType::Enum { name, .. } => type_name(name),
+        SorobanType::Custom { name } => {
+            if abi.types.contains_key(name) {
+                return type_name(name);
+            }
+            let parsed = SorobanType::from_type_string(name);
+            if !matches!(parsed, SorobanType::Custom { name: ref n } if n == name) {
+                return ts_type(&parsed, abi);
+            }
+            format!("unknown /* unsupported type: {} */", comment_safe(name))
+        }
+    }
+}
+
+fn is_tuple_struct(fields: &[StructField]) -> bool {
+    !fields.is_empty()
+        && fields
+            .iter()
+            .all(|field| field.name.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn is_integer_enum(variants: &[EnumVariant]) -> bool {
+    !variants.is_empty()
+        && variants.iter().all(|variant| {
+            variant.value.is_some() && variant.fields.as_ref().is_none_or(Vec::is_empty)
+        })
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: Option<&str>) {
+    let Some(doc) = doc.map(str::trim).filter(|doc| !doc.is_empty()) else {
+        return;
+    };
+    let _ = writeln!(out, "{}/**", indent);
+    for line in doc.lines() {
+        let line = comment_safe(line.trim_end());
+        if line.is_empty() {
+            let _ = writeln!(out, "{} *", indent);
+        } else {
+            let _ = writeln!(out, "{} * {}", indent, line);
+        }
+    }
+    let _ = writeln!(out, "{} */", indent);
+}
+
+/// Keeps text from closing the comment it is placed in
+fn comment_safe(text: &str) -> String {
+    text.replace("*/", "*\\/")
+}
+
+fn string_literal(text: &str) -> String {
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+const RESERVED_WORDS: &[&str] = &[
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "null",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+];
+
+/// Globals the generated module uses, which a contract type must not shadow
+const MODULE_GLOBALS: &[&str] = &[
+    "Array",
+    "Buffer",
+    "Errors",
+    "Map",
+    "Promise",
+    "Record",
+    "TypeError",
+    "contract",
+];
+
+/// The name a contract-defined type is exported under
+fn type_name(name: &str) -> String {
+    let mut ident = identifier(name);
+    if MODULE_GLOBALS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+/// A valid TypeScript identifier for a contract-defined name
+fn identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    if RESERVED_WORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
+
+/// Property keys may be any string, so only quote the ones that need it
+fn property_name(name: &str) -> String {
+    let plain = !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        name.to_string()
+    } else {
+        string_literal(name)
+    }
+}
+
+/// Client methods keep the contract's function names; `client` is taken by
+/// the wrapped SDK client
+fn method_name(name: &str) -> String {
+    match name {
+        "client" | "constructor" => format!("{}_", name),
+        _ => property_name(name),
+    }
+}
+
+fn args_interface(function: &str) -> String {
+    format!("{}Args", pascal_case(function))
+}
+
+fn pascal_case(name: &str) -> String {
+    let mut out = String::new();
+    for word in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn module_name(name: &str) -> String {
+    let module: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    // `index` is the re-export file
+    if module.is_empty() || module == "index" {
+        "contract".to_string()
+    } else {
+        module
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_json_spec;
+
+    fn bindings(spec: &str) -> String {
+        let abi = parse_json_spec(spec, "token").unwrap();
+        let source = BindingsSource {
+            contract_id: "CABC".to_string(),
+            version: Some("1.2.0".to_string()),
+        };
+        let files = generate_typescript(&abi, &source);
+        assert_eq!(files[0].path, "token.ts");
+        assert_eq!(files[1].path, "index.ts");
+        assert!(files[1].contents.contains("export * from \"./token\";"));
+        files[0].contents.clone()
+    }
+
+    #[test]
+    fn maps_arguments_and_results() {
+        let ts = bindings(
+            r#"[{"type":"function","name":"transfer","inputs":[
+                {"name":"from","value":{"type":"address"}},
+                {"name":"amount","value":{"type":"i128"}},
+                {"name":"memo","value":{"type":"option","element":{"type":"bytes"}}}
+            ],"outputs":[{"type":"u32"}]},
+            {"type":"function","name":"name","outputs":[{"type":"string"}]}]"#,
+        );
+        assert!(ts.starts_with("// TypeScript bindings for token (CABC), version 1.2.0."));
+        assert!(ts.contains(
+            "export interface TransferArgs {\n  from: string;\n  amount: bigint;\n  memo: Buffer | undefined;\n}"
+        ));
+        assert!(ts.contains("transfer(args: TransferArgs, options?: contract.MethodOptions): Promise<contract.AssembledTransaction<number>>"));
+        assert!(ts.contains("name(options?: contract.MethodOptions): Promise<contract.AssembledTransaction<string>>"));
+        assert!(ts.contains("return this.#call(\"name\", [options]);"));
+    }
+
+    #[test]
+    fn maps_contract_types() {
+        let ts = bindings(
+            r#"[{"type":"struct","name":"Point","fields":[
+                {"name":"0","value":{"type":"i32"}},{"name":"1","value":{"type":"i32"}}]},
+            {"type":"union","name":"Asset","cases":[
+                {"name":"Native"},
+                {"name":"Token","fields":[{"name":"0","value":{"type":"address"}}]}]},
+            {"type":"error_enum","name":"Error","cases":[{"name":"Paused","value":3}]},
+            {"type":"function","name":"locate","inputs":[
+                {"name":"asset","value":{"type":"Asset"}},
+                {"name":"proof","value":{"type":"Groth16Proof"}}
+            ],"outputs":[{"type":"Point"}]}]"#,
+        );
+        assert!(ts.contains(
+            "export type Asset =\n  | { tag: \"Native\"; values: void }\n  | { tag: \"Token\"; values: readonly [string] };"
+        ));
+        assert!(ts.contains("export enum Error {\n  Paused = 3,\n}"));
+        assert!(ts.contains("  3: { message: \"Error::Paused\" },"));
+        assert!(ts.contains("export type Point = readonly [number, number];"));
+        assert!(
+            ts.contains("  asset: Asset;\n  proof: unknown /* unsupported type: Groth16Proof */;")
+        );
+        assert!(ts.contains("Promise<contract.AssembledTransaction<Point>>"));
+    }
+
+    #[test]
+    fn sanitizes_names() {
+        assert_eq!(identifier("delete"), "delete_");
+        assert_eq!(identifier("2fa"), "_2fa");
+        assert_eq!(type_name("Map"), "Map_");
+        assert_eq!(property_name("my-field"), "\"my-field\"");
+        assert_eq!(method_name("client"), "client_");
+        assert_eq!(pascal_case("hello_world"), "HelloWorld");
+        assert_eq!(module_name("Index"), "contract");
+        assert_eq!(comment_safe("a */ b"), "a *\\/ b");
+    }
+}
@@ -0,0 +1,18 @@
+// Uses the bindings the way an application would, so type errors in the
+// generated client surface here as well as in the module itself.
+import { contract } from "@stellar/stellar-sdk";
+import { Errors, Status, TokenClient, type Asset, type TransferArgs } from "./generated";
+
+export async function transfer(options: contract.ClientOptions, args: TransferArgs) {
+  const client = await TokenClient.from(options);
+  const tx = await client.transfer(args);
+  const result: contract.Result<bigint> = tx.result;
+  return result.isOk() ? result.unwrap() : Errors[1]?.message;
+}
+
+export async function describe(client: TokenClient, asset: Asset) {
+  const balance: bigint = (await client.balance({ id: "GABC", asset })).result;
+  const status: Status = (await client.status()).result;
+  const decimals: number = (await client.decimals()).result;
+  return { balance, status, decimals, raw: client.client };
+}
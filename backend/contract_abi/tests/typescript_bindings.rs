@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use contract_abi::{generate_typescript, parse_json_spec, BindingsSource, GeneratedFile};
+
+fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn token_bindings() -> Vec<GeneratedFile> {
+    let spec = fs::read_to_string(fixtures().join("token_abi.json")).unwrap();
+    let abi = parse_json_spec(&spec, "token").unwrap();
+    let source = BindingsSource {
+        contract_id: "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC".to_string(),
+        version: Some("2.0.1".to_string()),
+    };
+    generate_typescript(&abi, &source)
+}
+
+fn file<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+    &files
+        .iter()
+        .find(|file| file.path == path)
+        .unwrap_or_else(|| panic!("{path} was not generated"))
+        .contents
+}
+
+#[test]
+fn token_bindings_are_typed_from_the_spec() {
+    let files = token_bindings();
+    let module = file(&files, "token.ts");
+
+    assert!(module.starts_with(
+        "// TypeScript bindings for token (CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC), version 2.0.1."
+    ));
+    assert!(file(&files, "index.ts").ends_with("export * from \"./token\";\n"));
+
+    assert!(module.contains("  issued_at: bigint;\n  icon_hash: Buffer | undefined;\n"));
+    assert!(module.contains("export type Allowance = readonly [string, bigint];"));
+    assert!(module.contains("  /**\n   * Transfers are rejected\n   */\n  Frozen = 1,\n"));
+    assert!(module.contains("  holders: Map<string, bigint>;\n"));
+    assert!(module.contains("  2: { message: \"Error::Frozen\" },\n"));
+    assert!(module.contains("Promise<contract.AssembledTransaction<contract.Result<bigint>>>"));
+    assert!(module.contains("Promise<contract.AssembledTransaction<Array<Allowance>>>"));
+    assert!(module.contains("  proof: unknown /* unsupported type: Groth16Proof */;\n"));
+    assert!(module.contains("  initialize(args: InitializeArgs, options?: contract.MethodOptions): Promise<contract.AssembledTransaction<void>> {"));
+}
+
+#[test]
+fn bindings_are_stable_across_runs() {
+    assert_eq!(token_bindings(), token_bindings());
+}
+
+#[test]
+#[ignore = "needs the npm packages in tests/fixtures/typescript"]
+fn token_bindings_type_check() {
+    let fixture = fixtures().join("typescript");
+    let generated = fixture.join("generated");
+    let _ = fs::remove_dir_all(&generated);
+    fs::create_dir_all(&generated).unwrap();
+    for file in token_bindings() {
+        fs::write(generated.join(&file.path), &file.contents).unwrap();
+    }
+
+    let output = Command::new(fixture.join("node_modules/.bin/tsc"))
+        .args(["--noEmit", "-p"])
+        .arg(&fixture)
+        .output()
+        .expect("tsc is not installed; run `npm install` in tests/fixtures/typescript");
+    assert!(
+        output.status.success(),
+        "tsc rejected the bindings:\n{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
@@ -0,0 +1,223 @@
+//! Estimating what each exported method of a built contract costs from its
+//! WASM bytecode, without running it.
+//!
+//! Every function a method can reach through direct calls is counted once,
+//! as if each ran straight through: loops count a single iteration and
+//! indirect calls are not followed. The estimate is therefore a floor, but
+//! a stable one, so it compares well between two builds or two contracts.
+
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use wasmparser::{ExternalKind, Operator, Parser, Payload, TypeRef};
+
+use crate::error::{ProfilerError, Result};
+use crate::fees::ResourceUsage;
+use crate::profile::{CallFrame, FunctionProfile, ProfileData, ProfileMode};
+use crate::schema::PROFILE_SCHEMA_VERSION;
+
+/// Bytes in one WASM linear memory page
+const WASM_PAGE_BYTES: u64 = 65_536;
+
+/// What the budget engine charges, modelled on the Soroban host's budget
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BudgetModel {
+    /// CPU instructions per WASM instruction executed
+    pub cpu_per_instruction: u64,
+    /// CPU instructions per call into the host, i.e. an imported function
+    pub cpu_per_host_call: u64,
+    /// Memory per local variable of a function on the call path
+    pub memory_per_local: u64,
+}
+
+impl Default for BudgetModel {
+    fn default() -> Self {
+        Self {
+            cpu_per_instruction: 4,
+            cpu_per_host_call: 1_000,
+            memory_per_local: 8,
+        }
+    }
+}
+
+/// The estimated cost of one invocation of an exported method
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MethodBudget {
+    pub method: String,
+    /// Only `cpu_instructions` and `memory_bytes` are estimated
+    pub resources: ResourceUsage,
+}
+
+/// A function defined in the module, as far as the budget sees it
+#[derive(Debug, Default)]
+struct FunctionBody {
+    instructions: u64,
+    locals: u64,
+    /// Function indices of direct calls, imports included
+    calls: Vec<u32>,
+}
+
+/// Estimates every exported method of the module `wasm`, sorted by name.
+///
+/// ```
+/// use soroban_profiler::{estimate_wasm_methods, BudgetModel};
+///
+/// // (module (func (export "hello") nop nop))
+/// let wasm = [
+///     0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+///     0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type: () -> ()
+///     0x03, 0x02, 0x01, 0x00, // one function of type 0
+///     0x07, 0x09, 0x01, 0x05, b'h', b'e', b'l', b'l', b'o', 0x00, 0x00, // export
+///     0x0a, 0x06, 0x01, 0x04, 0x00, 0x01, 0x01, 0x0b, // body: nop nop end
+/// ];
+/// let methods = estimate_wasm_methods(&wasm, &BudgetModel::default())?;
+///
+/// assert_eq!(methods.len(), 1);
+/// assert_eq!(methods[0].method, "hello");
+/// assert_eq!(methods[0].resources.cpu_instructions, Some(12));
+/// # Ok::<(), soroban_profiler::ProfilerError>(())
+/// ```
+pub fn estimate_wasm_methods(wasm: &[u8], model: &BudgetModel) -> Result<Vec<MethodBudget>> {
+    let invalid = |err: wasmparser::BinaryReaderError| ProfilerError::InvalidWasm(err.to_string());
+
+    let mut imported_functions = 0u32;
+    let mut memory_pages = 0u64;
+    let mut exports = Vec::new();
+    let mut bodies = Vec::new();
+
+    for payload in Parser::new(0).parse_all(wasm) {
+        match payload.map_err(invalid)? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if let TypeRef::Func(_) = import.map_err(invalid)?.ty {
+                        imported_functions += 1;
+                    }
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    memory_pages += memory.map_err(invalid)?.initial;
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export.map_err(invalid)?;
+                    if export.kind == ExternalKind::Func {
+                        exports.push((export.name.to_string(), export.index));
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let mut function = FunctionBody::default();
+                for local in body.get_locals_reader().map_err(invalid)? {
+                    function.locals += u64::from(local.map_err(invalid)?.0);
+                }
+                let mut operators = body.get_operators_reader().map_err(invalid)?;
+                while !operators.eof() {
+                    if let Operator::Call { function_index } = operators.read().map_err(invalid)? {
+                        function.calls.push(function_index);
+                    }
+                    function.instructions += 1;
+                }
+                bodies.push(function);
+            }
+            _ => {}
+        }
+    }
+
+    let memory_bytes = memory_pages * WASM_PAGE_BYTES;
+    let mut methods: Vec<MethodBudget> = exports
+        .into_iter()
+        .filter(|(_, index)| *index >= imported_functions)
+        .map(|(method, index)| {
+            let (cpu, locals) = reachable_cost(&bodies, imported_functions, index, model);
+            MethodBudget {
+                method,
+                resources: ResourceUsage {
+                    cpu_instructions: Some(cpu),
+                    memory_bytes: Some(memory_bytes + locals * model.memory_per_local),
+                    ..ResourceUsage::default()
+                },
+            }
+        })
+        .collect();
+    methods.sort_by(|a, b| a.method.cmp(&b.method));
+    Ok(methods)
+}
+
+/// CPU instructions and locals of the function at `entry` and every defined
+/// function it reaches through direct calls, each counted once
+fn reachable_cost(
+    bodies: &[FunctionBody],
+    imported_functions: u32,
+    entry: u32,
+    model: &BudgetModel,
+) -> (u64, u64) {
+    let mut seen = HashSet::new();
+    let mut pending = vec![entry];
+    let (mut cpu, mut locals) = (0u64, 0u64);
+
+    while let Some(index) = pending.pop() {
+        let Some(defined) = index.checked_sub(imported_functions) else {
+            cpu += model.cpu_per_host_call;
+            continue;
+        };
+        if !seen.insert(index) {
+            continue;
+        }
+        let Some(body) = bodies.get(defined as usize) else {
+            continue;
+        };
+        cpu += body.instructions * model.cpu_per_instruction;
+        locals += body.locals;
+        pending.extend(&body.calls);
+    }
+    (cpu, locals)
+}
+
+impl ProfileData {
+    /// A profile of the budget estimates for `contract`, one function per
+    /// method. As in [`ProfileData::from_simulation`], CPU instructions stand
+    /// in for time at one instruction per nanosecond and the methods run one
+    /// after another; each method's full [`ResourceUsage`] is in `methods`.
+    pub fn from_budgets(contract: impl Into<String>, methods: &[MethodBudget]) -> Self {
+        let mut functions = HashMap::new();
+        let mut call_stack = Vec::new();
+        let mut elapsed = 0u64;
+        for budget in methods {
+            let instructions = budget.resources.cpu_instructions.unwrap_or(0);
+            functions.insert(
+                budget.method.clone(),
+                FunctionProfile::from_durations(
+                    budget.method.clone(),
+                    &[Duration::from_nanos(instructions)],
+                ),
+            );
+            call_stack.push(CallFrame {
+                function: budget.method.clone(),
+                start_time: elapsed,
+                end_time: elapsed + instructions,
+                children: Vec::new(),
+            });
+            elapsed += instructions;
+        }
+
+        ProfileData {
+            schema_version: PROFILE_SCHEMA_VERSION,
+            contract_path: contract.into(),
+            method: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            total_duration: Duration::from_nanos(elapsed),
+            functions,
+            call_stack,
+            overhead_per_call: Duration::ZERO,
+            overhead_percent: 0.0,
+            mode: ProfileMode::Budgeted,
+            sample_interval: Duration::ZERO,
+            resources: ResourceUsage::default(),
+            fee_estimate: None,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Export to the Trace Event format read by `chrome://tracing` and Perfetto.
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use crate::error::Result;
+use crate::export::write_file;
+use crate::profile::{CallFrame, ProfileData};
+
+/// Every call runs on one thread of one process
+const PID: u32 = 1;
+const TID: u32 = 1;
+
+/// Serializes the calls in `profile.call_stack` as Trace Event JSON, one
+/// complete (`X`) event per call, named after the contract path (process)
+/// and profiled method (thread).
+///
+/// Timestamps are microseconds since the profiler started. A call is moved
+/// forward, and if need be shortened, so that it starts after its previous
+/// sibling and ends within its caller: explicit durations passed to
+/// [`Profiler::exit_function`](crate::Profiler::exit_function) and the
+/// overhead subtracted from measured spans do not line up with the wall
+/// clock, but the timeline has to nest.
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::{export_chrome_trace, Profiler};
+///
+/// let mut profiler = Profiler::with_overhead(Duration::ZERO);
+/// profiler.enter_function("swap");
+/// profiler.enter_function("update_reserves");
+/// profiler.exit_function("update_reserves", Duration::from_micros(80));
+/// profiler.exit_function("swap", Duration::from_micros(100));
+/// let profile = profiler.finish("contracts/pool/src/lib.rs".into(), Some("swap".into()));
+///
+/// let trace: serde_json::Value = serde_json::from_str(&export_chrome_trace(&profile)?)?;
+/// let events = trace["traceEvents"].as_array().unwrap();
+/// assert_eq!(events[0]["args"]["name"], "contracts/pool/src/lib.rs");
+/// assert_eq!(events[2]["name"], "swap");
+/// assert_eq!(events[2]["dur"], 100.0);
+/// assert_eq!(events[3]["name"], "update_reserves");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn export_chrome_trace(profile: &ProfileData) -> Result<String> {
+    let mut events = vec![
+        metadata_event("process_name", &profile.contract_path),
+        metadata_event("thread_name", profile.method.as_deref().unwrap_or("main")),
+    ];
+
+    let mut cursor = 0;
+    for frame in &profile.call_stack {
+        let (start, end) = fit(frame, cursor, u64::MAX);
+        push_events(&mut events, frame, start, end);
+        cursor = end;
+    }
+
+    let trace = json!({
+        "traceEvents": events,
+        "displayTimeUnit": "ns",
+        "otherData": {
+            "schema_version": profile.schema_version,
+            "timestamp": profile.timestamp,
+        },
+    });
+    Ok(serde_json::to_string(&trace)?)
+}
+
+/// Writes [`export_chrome_trace`] to `output_path`.
+pub fn write_chrome_trace(profile: &ProfileData, output_path: &Path) -> Result<()> {
+    write_file(output_path, export_chrome_trace(profile)?)
+}
+
+fn metadata_event(name: &str, value: &str) -> Value {
+    json!({
+        "name": name,
+        "ph": "M",
+        "pid": PID,
+        "tid": TID,
+        "args": { "name": value },
+    })
+}
+
+/// Pushes the event for `frame`, placed at `start..end` in nanoseconds, then
+/// those of the calls it made.
+fn push_events(events: &mut Vec<Value>, frame: &CallFrame, start: u64, end: u64) {
+    events.push(json!({
+        "name": frame.function,
+        "cat": "function",
+        "ph": "X",
+        "ts": micros(start),
+        "dur": micros(end - start),
+        "pid": PID,
+        "tid": TID,
+    }));
+
+    let mut cursor = start;
+    for child in &frame.children {
+        let (child_start, child_end) = fit(child, cursor, end);
+        push_events(events, child, child_start, child_end);
+        cursor = child_end;
+    }
+}
+
+/// Where `frame` goes between `earliest` and `latest`: at its recorded start
+/// if that is in range, otherwise as close to it as its duration allows.
+fn fit(frame: &CallFrame, earliest: u64, latest: u64) -> (u64, u64) {
+    let duration = u64::try_from(frame.duration().as_nanos())
+        .unwrap_or(u64::MAX)
+        .min(latest - earliest);
+    let start = frame.start_time.max(earliest).min(latest - duration);
+    (start, start + duration)
+}
+
+fn micros(nanos: u64) -> f64 {
+    nanos as f64 / 1_000.0
+}
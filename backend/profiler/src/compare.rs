@@ -0,0 +1,304 @@
+//! Comparing runs and suggesting optimisations.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::profile::{duration_nanos, ProfileData, ProfileMode};
+
+/// Per-function change from `profile1` (the baseline) to `profile2`, largest
+/// absolute change first. Functions only one of the profiles has are
+/// reported as [`ChangeStatus::New`] or [`ChangeStatus::Removed`] with their
+/// absolute time.
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::{compare_profiles, ChangeStatus, Profiler};
+///
+/// let run = |functions: &[(&str, u64)]| {
+///     let mut profiler = Profiler::new();
+///     for (name, nanos) in functions {
+///         profiler.enter_function(name);
+///         profiler.exit_function(name, Duration::from_nanos(*nanos));
+///     }
+///     profiler.finish("lib.rs".into(), None)
+/// };
+///
+/// let changes = compare_profiles(
+///     &run(&[("transfer", 1_000), ("legacy_hook", 300)]),
+///     &run(&[("transfer", 1_500), ("mint", 2_000)]),
+/// );
+///
+/// assert_eq!(changes[0].function, "mint");
+/// assert_eq!(changes[0].status, ChangeStatus::New);
+/// assert_eq!(changes[0].current_time, Duration::from_nanos(2_000));
+/// assert_eq!(changes[0].time_diff_percent, None);
+///
+/// assert_eq!(changes[1].status, ChangeStatus::Slower);
+/// assert_eq!(changes[1].time_diff_percent, Some(50.0));
+///
+/// assert_eq!(changes[2].status, ChangeStatus::Removed);
+/// assert_eq!(changes[2].baseline_time, Duration::from_nanos(300));
+/// ```
+pub fn compare_profiles(profile1: &ProfileData, profile2: &ProfileData) -> Vec<ComparisonResult> {
+    let mut results = Vec::new();
+
+    let all_functions: HashSet<_> = profile1
+        .functions
+        .keys()
+        .chain(profile2.functions.keys())
+        .collect();
+
+    for func_name in all_functions {
+        let baseline = profile1.functions.get(func_name);
+        let current = profile2.functions.get(func_name);
+        let baseline_time = baseline.map(|f| f.total_time);
+        let current_time = current.map(|f| f.total_time);
+
+        let status = match (baseline_time, current_time) {
+            (Some(baseline), Some(current)) if current > baseline => ChangeStatus::Slower,
+            (Some(baseline), Some(current)) if current < baseline => ChangeStatus::Faster,
+            (Some(_), Some(_)) => ChangeStatus::Unchanged,
+            (Some(_), None) => ChangeStatus::Removed,
+            (None, Some(_)) => ChangeStatus::New,
+            (None, None) => continue,
+        };
+
+        let baseline_time = baseline_time.unwrap_or(Duration::ZERO);
+        let current_time = current_time.unwrap_or(Duration::ZERO);
+        let time_diff = current_time.as_nanos() as i64 - baseline_time.as_nanos() as i64;
+        // A function the baseline did not run has nothing to be relative to
+        let time_diff_percent = (baseline_time.as_nanos() > 0)
+            .then(|| (time_diff as f64 / baseline_time.as_nanos() as f64) * 100.0);
+
+        let baseline_p99 = baseline.and_then(|f| f.percentile(99.0));
+        let current_p99 = current.and_then(|f| f.percentile(99.0));
+        let distribution_shift = match (baseline, current, baseline_p99, current_p99) {
+            (Some(baseline), Some(current), Some(baseline_p99), Some(current_p99)) => {
+                percent_change(baseline_p99, current_p99) > DISTRIBUTION_SHIFT_PERCENT
+                    && percent_change(baseline.avg_time, current.avg_time)
+                        <= DISTRIBUTION_SHIFT_PERCENT
+            }
+            _ => false,
+        };
+
+        results.push(ComparisonResult {
+            function: func_name.clone(),
+            status,
+            time_diff_ns: time_diff,
+            time_diff_percent,
+            baseline_time,
+            current_time,
+            baseline_p99_ns: baseline_p99.map(|p99| p99.as_nanos() as u64),
+            current_p99_ns: current_p99.map(|p99| p99.as_nanos() as u64),
+            distribution_shift,
+        });
+    }
+
+    results.sort_by(|a, b| {
+        b.time_diff_ns
+            .abs()
+            .cmp(&a.time_diff_ns.abs())
+            .then_with(|| a.function.cmp(&b.function))
+    });
+    results
+}
+
+/// Change in the estimated fee per invocation from `baseline` to `current`,
+/// or `None` unless both profiles carry a
+/// [`fee_estimate`](ProfileData::fee_estimate).
+pub fn compare_fees(baseline: &ProfileData, current: &ProfileData) -> Option<FeeChange> {
+    let baseline_stroops = baseline.fee_estimate.as_ref()?.total_stroops;
+    let current_stroops = current.fee_estimate.as_ref()?.total_stroops;
+    let diff_stroops = current_stroops as i64 - baseline_stroops as i64;
+
+    Some(FeeChange {
+        baseline_stroops,
+        current_stroops,
+        diff_stroops,
+        diff_percent: (baseline_stroops > 0)
+            .then(|| diff_stroops as f64 / baseline_stroops as f64 * 100.0),
+    })
+}
+
+/// See [`compare_fees`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FeeChange {
+    pub baseline_stroops: u64,
+    pub current_stroops: u64,
+    pub diff_stroops: i64,
+    /// `None` when the baseline fee is zero
+    pub diff_percent: Option<f64>,
+}
+
+/// How much a function's p99 has to grow, in percent, while its mean stays
+/// within the same margin, to count as a [`ComparisonResult::distribution_shift`]
+pub const DISTRIBUTION_SHIFT_PERCENT: f64 = 25.0;
+
+fn percent_change(baseline: Duration, current: Duration) -> f64 {
+    let baseline = baseline.as_nanos().max(1) as f64;
+    (current.as_nanos() as f64 - baseline) / baseline * 100.0
+}
+
+/// What happened to a function between the baseline and the current profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeStatus {
+    Slower,
+    Faster,
+    Unchanged,
+    /// Only the current profile has the function
+    New,
+    /// Only the baseline has the function
+    Removed,
+}
+
+impl fmt::Display for ChangeStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Slower => "slower",
+            Self::Faster => "faster",
+            Self::Unchanged => "unchanged",
+            Self::New => "new",
+            Self::Removed => "removed",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonResult {
+    pub function: String,
+    pub status: ChangeStatus,
+    pub time_diff_ns: i64,
+    /// Change relative to the baseline; `None` when the baseline has no time
+    /// for the function, e.g. for [`ChangeStatus::New`]
+    pub time_diff_percent: Option<f64>,
+    /// Zero for [`ChangeStatus::New`]
+    #[serde(with = "duration_nanos")]
+    pub baseline_time: Duration,
+    /// Zero for [`ChangeStatus::Removed`]
+    #[serde(with = "duration_nanos")]
+    pub current_time: Duration,
+    /// p99 call duration, when the profile recorded a histogram for the
+    /// function
+    pub baseline_p99_ns: Option<u64>,
+    pub current_p99_ns: Option<u64>,
+    /// The slowest calls got slower by more than [`DISTRIBUTION_SHIFT_PERCENT`]
+    /// while the mean did not, e.g. a slow path that became more common
+    pub distribution_shift: bool,
+}
+
+/// Limits a comparison has to stay within, e.g. to fail a CI run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegressionGate {
+    /// Largest slowdown allowed for a function the baseline also ran, in
+    /// percent
+    pub max_slowdown_percent: f64,
+    /// Largest time allowed for a function the baseline did not have, since a
+    /// new hot path is a regression too
+    pub max_new_function_time: Duration,
+}
+
+impl Default for RegressionGate {
+    fn default() -> Self {
+        Self {
+            max_slowdown_percent: 10.0,
+            max_new_function_time: Duration::from_millis(1),
+        }
+    }
+}
+
+impl RegressionGate {
+    /// Changes that exceed the gate, in the order given.
+    pub fn violations<'a>(&self, changes: &'a [ComparisonResult]) -> Vec<&'a ComparisonResult> {
+        changes
+            .iter()
+            .filter(|change| match (change.status, change.time_diff_percent) {
+                (ChangeStatus::Slower, Some(percent)) => percent > self.max_slowdown_percent,
+                (ChangeStatus::Slower | ChangeStatus::New, _) => {
+                    change.current_time > self.max_new_function_time
+                }
+                _ => false,
+            })
+            .collect()
+    }
+}
+
+/// Human-readable suggestions for hot, frequently called or erratic
+/// functions.
+pub fn generate_recommendations(profile: &ProfileData) -> Vec<String> {
+    let mut recommendations = Vec::new();
+
+    let hot_functions: Vec<_> = profile
+        .functions
+        .values()
+        // Simulated and budgeted profiles have no call tree to be hot in
+        .filter(|_| !profile.mode.counts_instructions())
+        .filter(|f| f.total_time.as_nanos() as f64 > profile.total_duration.as_nanos() as f64 * 0.1)
+        .collect();
+
+    if !hot_functions.is_empty() {
+        recommendations.push(format!(
+            "Optimize hot functions: {}",
+            hot_functions
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let high_call_count: Vec<_> = profile
+        .functions
+        .values()
+        // Sampled profiles count samples, not calls
+        .filter(|f| profile.mode == ProfileMode::Instrumented && f.call_count > 1000)
+        .collect();
+
+    if !high_call_count.is_empty() {
+        recommendations.push(format!(
+            "Consider caching for frequently called functions: {}",
+            high_call_count
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    let high_variance: Vec<_> = profile
+        .functions
+        .values()
+        .filter(|f| {
+            let variance = (f.max_time.as_nanos() as f64 - f.min_time.as_nanos() as f64)
+                / f.avg_time.as_nanos().max(1) as f64;
+            variance > 2.0
+        })
+        .collect();
+
+    if !high_variance.is_empty() {
+        recommendations.push(format!(
+            "Investigate high variance in execution time: {}",
+            high_variance
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    if profile.overhead_percent > 5.0 {
+        recommendations.push(format!(
+            "Profiling overhead ({:.2}%) exceeds 5% threshold. Consider reducing instrumentation.",
+            profile.overhead_percent
+        ));
+    }
+
+    if recommendations.is_empty() {
+        recommendations.push("No optimization recommendations at this time.".to_string());
+    }
+
+    recommendations
+}
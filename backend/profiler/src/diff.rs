@@ -0,0 +1,268 @@
+//! Differential flame graphs between a baseline and a current profile.
+
+use std::path::Path;
+
+use crate::error::Result;
+use crate::export::{
+    folded_stacks, format_float, write_file, FLAME_GRAPH_WIDTH, FRAME_HEIGHT, FRAME_STEP,
+    FRAME_TOP, MIN_FRAME_WIDTH,
+};
+use crate::profile::ProfileData;
+
+/// Renders where time moved between `baseline` and `current`.
+///
+/// Both profiles are reduced to their [`folded_stacks`] and merged by call
+/// path. Each frame is as wide as its inclusive time in both runs together,
+/// so callees always fit beneath their callers. Frames that got slower are
+/// red and frames that got faster are blue, more saturated the larger the
+/// change relative to the largest one; frames only one profile has are
+/// hatched instead (`added` or `removed`).
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::{differential_flame_graph_svg, Profiler};
+///
+/// let run = |reserves_micros| {
+///     let mut profiler = Profiler::with_overhead(Duration::ZERO);
+///     profiler.enter_function("swap");
+///     profiler.enter_function("update_reserves");
+///     profiler.exit_function("update_reserves", Duration::from_micros(reserves_micros));
+///     profiler.exit_function("swap", Duration::from_micros(100));
+///     profiler.finish("lib.rs".into(), None)
+/// };
+///
+/// let svg = differential_flame_graph_svg(&run(40), &run(80));
+/// assert!(svg.contains("<g class=\"frame slower\">"));
+/// assert!(svg.contains("update_reserves: 0.040ms -&gt; 0.080ms (+100.0%)"));
+/// ```
+pub fn differential_flame_graph_svg(baseline: &ProfileData, current: &ProfileData) -> String {
+    let baseline_folded = folded_stacks(baseline);
+    let current_folded = folded_stacks(current);
+
+    let mut roots = Vec::new();
+    // Current paths first, so the layout follows the current run
+    for (path, nanos) in parse_folded(&current_folded) {
+        insert(&mut roots, &path, Side::Current, nanos);
+    }
+    for (path, nanos) in parse_folded(&baseline_folded) {
+        insert(&mut roots, &path, Side::Baseline, nanos);
+    }
+
+    let combined: u64 = roots.iter().map(DiffNode::combined).sum();
+    let max_delta = max_abs_delta(&roots).max(1);
+    let height = FRAME_TOP + (tree_depth(&roots) as f64 + 1.0) * FRAME_STEP;
+
+    let mut svg = String::with_capacity(16 * 1024);
+    svg.push_str(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"1200\" height=\"",
+    );
+    svg.push_str(&format_float(height));
+    svg.push_str("\">\n");
+    svg.push_str("<defs>\n");
+    svg.push_str("<pattern id=\"added\" patternUnits=\"userSpaceOnUse\" width=\"6\" height=\"6\" patternTransform=\"rotate(45)\"><rect width=\"6\" height=\"6\" fill=\"#ffd6d6\"/><line x1=\"0\" y1=\"0\" x2=\"0\" y2=\"6\" stroke=\"#d62728\" stroke-width=\"3\"/></pattern>\n");
+    svg.push_str("<pattern id=\"removed\" patternUnits=\"userSpaceOnUse\" width=\"6\" height=\"6\" patternTransform=\"rotate(-45)\"><rect width=\"6\" height=\"6\" fill=\"#d6e4ff\"/><line x1=\"0\" y1=\"0\" x2=\"0\" y2=\"6\" stroke=\"#1f77b4\" stroke-width=\"3\"/></pattern>\n");
+    svg.push_str("</defs>\n");
+    svg.push_str("<style>\n");
+    svg.push_str(".frame { font-family: monospace; font-size: 12px; }\n");
+    svg.push_str(".frame rect { stroke: #000; stroke-width: 1px; }\n");
+    svg.push_str(".added rect { fill: url(#added); }\n");
+    svg.push_str(".removed rect { fill: url(#removed); }\n");
+    svg.push_str("</style>\n");
+
+    let scale = FLAME_GRAPH_WIDTH / combined.max(1) as f64;
+    let mut x = 0.0;
+    for root in &roots {
+        push_frames(&mut svg, root, x, 0, scale, max_delta);
+        x += root.combined() as f64 * scale;
+    }
+
+    svg.push_str("</svg>");
+
+    svg
+}
+
+/// Writes [`differential_flame_graph_svg`] to `output_path`.
+pub fn generate_differential_flame_graph(
+    baseline: &ProfileData,
+    current: &ProfileData,
+    output_path: &Path,
+) -> Result<()> {
+    write_file(output_path, differential_flame_graph_svg(baseline, current))
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Baseline,
+    Current,
+}
+
+/// One call path with its inclusive time in each profile, in nanoseconds
+struct DiffNode<'a> {
+    name: &'a str,
+    baseline: u64,
+    current: u64,
+    children: Vec<DiffNode<'a>>,
+}
+
+impl DiffNode<'_> {
+    fn combined(&self) -> u64 {
+        self.baseline + self.current
+    }
+
+    fn delta(&self) -> i128 {
+        self.current as i128 - self.baseline as i128
+    }
+
+    fn class(&self) -> &'static str {
+        match (self.baseline, self.current) {
+            (0, _) => "added",
+            (_, 0) => "removed",
+            (baseline, current) if current > baseline => "slower",
+            (baseline, current) if current < baseline => "faster",
+            _ => "unchanged",
+        }
+    }
+}
+
+/// Splits `caller;callee count` lines into their path and count.
+fn parse_folded(folded: &str) -> Vec<(Vec<&str>, u64)> {
+    folded
+        .lines()
+        .filter_map(|line| {
+            let (path, count) = line.rsplit_once(' ')?;
+            Some((path.split(';').collect(), count.parse().ok()?))
+        })
+        .collect()
+}
+
+/// Adds `nanos` of self time at `path`, and so to the inclusive time of
+/// every frame along it.
+fn insert<'a>(nodes: &mut Vec<DiffNode<'a>>, path: &[&'a str], side: Side, nanos: u64) {
+    let Some((name, rest)) = path.split_first() else {
+        return;
+    };
+    let index = match nodes.iter().position(|node| node.name == *name) {
+        Some(index) => index,
+        None => {
+            nodes.push(DiffNode {
+                name,
+                baseline: 0,
+                current: 0,
+                children: Vec::new(),
+            });
+            nodes.len() - 1
+        }
+    };
+
+    let node = &mut nodes[index];
+    match side {
+        Side::Baseline => node.baseline += nanos,
+        Side::Current => node.current += nanos,
+    }
+    insert(&mut node.children, rest, side, nanos);
+}
+
+fn max_abs_delta(nodes: &[DiffNode<'_>]) -> u128 {
+    nodes
+        .iter()
+        .map(|node| {
+            node.delta()
+                .unsigned_abs()
+                .max(max_abs_delta(&node.children))
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+fn tree_depth(nodes: &[DiffNode<'_>]) -> usize {
+    nodes
+        .iter()
+        .map(|node| 1 + tree_depth(&node.children))
+        .max()
+        .unwrap_or(0)
+}
+
+fn push_frames(
+    svg: &mut String,
+    node: &DiffNode<'_>,
+    x: f64,
+    depth: usize,
+    scale: f64,
+    max_delta: u128,
+) {
+    let bar_width = node.combined() as f64 * scale;
+    if bar_width < MIN_FRAME_WIDTH {
+        return;
+    }
+    let y = FRAME_TOP + depth as f64 * FRAME_STEP;
+    let class = node.class();
+
+    svg.push_str("<g class=\"frame ");
+    svg.push_str(class);
+    svg.push_str("\">\n");
+    svg.push_str("<title>");
+    svg.push_str(node.name);
+    svg.push_str(": ");
+    svg.push_str(&change_summary(node));
+    svg.push_str("</title>\n");
+
+    svg.push_str("<rect x=\"");
+    svg.push_str(&format_float(x));
+    svg.push_str("\" y=\"");
+    svg.push_str(&format_float(y));
+    svg.push_str("\" width=\"");
+    svg.push_str(&format_float(bar_width));
+    svg.push_str("\" height=\"");
+    svg.push_str(&format_float(FRAME_HEIGHT));
+    svg.push('"');
+    if class != "added" && class != "removed" {
+        svg.push_str(" fill=\"");
+        svg.push_str(&delta_color(node.delta(), max_delta));
+        svg.push('"');
+    }
+    svg.push_str("/>\n");
+
+    svg.push_str("<text x=\"");
+    svg.push_str(&format_float(x + 5.0));
+    svg.push_str("\" y=\"");
+    svg.push_str(&format_float(y + 15.0));
+    svg.push_str("\">");
+    svg.push_str(node.name);
+    svg.push_str("</text>\n");
+
+    svg.push_str("</g>\n");
+
+    let mut child_x = x;
+    for child in &node.children {
+        push_frames(svg, child, child_x, depth + 1, scale, max_delta);
+        child_x += child.combined() as f64 * scale;
+    }
+}
+
+fn change_summary(node: &DiffNode<'_>) -> String {
+    let millis = |nanos: u64| format!("{:.3}ms", nanos as f64 / 1_000_000.0);
+    match node.class() {
+        "added" => format!("new, {}", millis(node.current)),
+        "removed" => format!("removed, was {}", millis(node.baseline)),
+        _ => format!(
+            "{} -&gt; {} ({:+.1}%)",
+            millis(node.baseline),
+            millis(node.current),
+            node.delta() as f64 / node.baseline as f64 * 100.0
+        ),
+    }
+}
+
+/// Red for slower, blue for faster, white for unchanged; the larger the
+/// change relative to `max_delta`, the more saturated.
+fn delta_color(delta: i128, max_delta: u128) -> String {
+    let intensity = delta.unsigned_abs() as f64 / max_delta as f64;
+    let faded = (255.0 - 200.0 * intensity.min(1.0)).round() as u8;
+    if delta > 0 {
+        format!("rgb(255,{},{})", faded, faded)
+    } else if delta < 0 {
+        format!("rgb({},{},255)", faded, faded)
+    } else {
+        "rgb(255,255,255)".to_string()
+    }
+}
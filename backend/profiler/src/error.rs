@@ -0,0 +1,45 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+pub type Result<T, E = ProfilerError> = std::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum ProfilerError {
+    #[error("failed to read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to write {}: {source}", path.display())]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("method '{0}' not found in contract")]
+    MethodNotFound(String),
+
+    /// The profile was written by a newer release than this one
+    #[error(
+        "profile uses schema version {found}, but this release only understands versions up to \
+         {supported}; upgrade to compare against it"
+    )]
+    UnsupportedSchema { found: u32, supported: u32 },
+
+    #[error("invalid profile: {0}")]
+    InvalidProfile(String),
+
+    #[error("invalid WASM module: {0}")]
+    InvalidWasm(String),
+
+    #[error("invalid fee config {}: {source}", path.display())]
+    InvalidFeeConfig {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("invalid profile JSON: {0}")]
+    Json(#[from] serde_json::Error),
+}
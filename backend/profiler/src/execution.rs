@@ -0,0 +1,226 @@
+//! Profiling a contract's functions.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::{ProfilerError, Result};
+use crate::profile::{ProfileData, Profiler};
+use crate::sampling::Sampler;
+
+/// What to profile, built with [`ProfileOptions::builder`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileOptions {
+    contract_path: PathBuf,
+    method: Option<String>,
+    iterations: u32,
+    sample_rate_hz: Option<u32>,
+}
+
+impl ProfileOptions {
+    /// Starts options for the contract source at `contract_path`, profiling
+    /// it once from its first function.
+    pub fn builder(contract_path: impl Into<PathBuf>) -> ProfileOptionsBuilder {
+        ProfileOptionsBuilder {
+            options: Self {
+                contract_path: contract_path.into(),
+                method: None,
+                iterations: 1,
+                sample_rate_hz: None,
+            },
+        }
+    }
+
+    pub fn contract_path(&self) -> &Path {
+        &self.contract_path
+    }
+
+    pub fn method(&self) -> Option<&str> {
+        self.method.as_deref()
+    }
+
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Samples per second when sampling, `None` when every call is timed
+    pub fn sample_rate_hz(&self) -> Option<u32> {
+        self.sample_rate_hz
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ProfileOptionsBuilder {
+    options: ProfileOptions,
+}
+
+impl ProfileOptionsBuilder {
+    /// Profiles from `method` instead of the first function.
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.options.method = Some(method.into());
+        self
+    }
+
+    /// Runs the contract this many times (at least once), so min, max and
+    /// average reflect run-to-run variance.
+    pub fn iterations(mut self, iterations: u32) -> Self {
+        self.options.iterations = iterations.max(1);
+        self
+    }
+
+    /// Samples the stack `rate_hz` times a second with a [`Sampler`] instead
+    /// of timing every call.
+    pub fn sampling(mut self, rate_hz: u32) -> Self {
+        self.options.sample_rate_hz = Some(rate_hz.max(1));
+        self
+    }
+
+    pub fn build(self) -> ProfileOptions {
+        self.options
+    }
+}
+
+/// Profiles the contract from the method selected by `options`, or from its
+/// first function, with the contract's other functions nested beneath it.
+///
+/// When a method is given but the contract does not define it, the returned
+/// profile has no functions.
+///
+/// ```
+/// use soroban_profiler::{profile_contract, ProfileOptions};
+///
+/// let dir = tempfile::tempdir()?;
+/// let contract = dir.path().join("lib.rs");
+/// std::fs::write(&contract, "pub fn transfer() {}\nfn balance() {}\n")?;
+///
+/// let options = ProfileOptions::builder(&contract)
+///     .method("transfer")
+///     .iterations(3)
+///     .build();
+/// let profile = profile_contract(&options)?;
+///
+/// let transfer = &profile.functions["transfer"];
+/// assert_eq!(transfer.call_count, 3);
+/// assert_eq!(transfer.children, vec!["balance"]);
+/// assert!(transfer.self_time < transfer.total_time);
+/// assert_eq!(profile.call_stack.len(), 3);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub fn profile_contract(options: &ProfileOptions) -> Result<ProfileData> {
+    let path = options.contract_path();
+    let functions = parse_contract_functions(path)?;
+    let defined = match options.method() {
+        Some(method) => functions.iter().any(|func| func == method),
+        None => !functions.is_empty(),
+    };
+
+    let contract_path = path.display().to_string();
+    let method = options.method().map(str::to_string);
+    let run = |hooks: &mut dyn ExecutionHooks| -> Result<()> {
+        if defined {
+            for _ in 0..options.iterations() {
+                simulate_execution(path, options.method(), hooks)?;
+            }
+        }
+        Ok(())
+    };
+
+    match options.sample_rate_hz() {
+        Some(rate_hz) => {
+            let mut sampler = Sampler::new(rate_hz);
+            run(&mut sampler)?;
+            Ok(sampler.finish(contract_path, method))
+        }
+        None => {
+            let mut profiler = Profiler::new();
+            run(&mut profiler)?;
+            Ok(profiler.finish(contract_path, method))
+        }
+    }
+}
+
+/// Calls [`simulate_execution`] makes around each function it runs
+pub trait ExecutionHooks {
+    fn enter(&mut self, name: &str);
+    fn exit(&mut self);
+}
+
+impl ExecutionHooks for Profiler {
+    fn enter(&mut self, name: &str) {
+        Profiler::enter(self, name);
+    }
+
+    fn exit(&mut self) {
+        Profiler::exit(self);
+    }
+}
+
+impl ExecutionHooks for Sampler {
+    fn enter(&mut self, name: &str) {
+        Sampler::enter(self, name);
+    }
+
+    fn exit(&mut self) {
+        Sampler::exit(self);
+    }
+}
+
+/// Names of the functions defined in a contract source file.
+pub fn parse_contract_functions(contract_path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(contract_path).map_err(|source| ProfilerError::Read {
+        path: contract_path.to_path_buf(),
+        source,
+    })?;
+
+    let mut functions = Vec::new();
+    let lines: Vec<&str> = content.lines().collect();
+
+    for line in lines.iter() {
+        if line.trim().starts_with("pub fn ") || line.trim().starts_with("fn ") {
+            if let Some(name_start) = line.find("fn ") {
+                let after_fn = &line[name_start + 3..];
+                if let Some(name_end) = after_fn.find('(') {
+                    let func_name = after_fn[..name_end].trim();
+                    if !func_name.is_empty() {
+                        functions.push(func_name.to_string());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(functions)
+}
+
+/// Runs `method` (or the first function) with every other function nested
+/// beneath it, reporting each call to `profiler`.
+pub fn simulate_execution(
+    contract_path: &Path,
+    method: Option<&str>,
+    profiler: &mut (impl ExecutionHooks + ?Sized),
+) -> Result<()> {
+    let functions = parse_contract_functions(contract_path)?;
+
+    let target_method =
+        method.unwrap_or_else(|| functions.first().map(|s| s.as_str()).unwrap_or("main"));
+
+    if !functions.contains(&target_method.to_string()) {
+        return Err(ProfilerError::MethodNotFound(target_method.to_string()));
+    }
+
+    profiler.enter(target_method);
+
+    for func in &functions {
+        if func == target_method {
+            continue;
+        }
+
+        profiler.enter(func);
+        std::thread::sleep(Duration::from_micros(100));
+        profiler.exit();
+    }
+
+    profiler.exit();
+
+    Ok(())
+}
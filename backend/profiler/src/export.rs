@@ -0,0 +1,370 @@
+//! Exporters for [`ProfileData`]: SVG flame graphs, folded stacks and JSON.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::error::{ProfilerError, Result};
+use crate::profile::{CallFrame, FunctionProfile, ProfileData};
+
+/// Serializes `profile` as pretty-printed JSON in the current schema, ready
+/// to be loaded again with [`crate::load_profile`].
+pub fn to_json(profile: &ProfileData) -> Result<String> {
+    Ok(serde_json::to_string_pretty(profile)?)
+}
+
+/// Writes [`to_json`] to `output_path`.
+pub fn write_json(profile: &ProfileData, output_path: &Path) -> Result<()> {
+    write_file(output_path, to_json(profile)?)
+}
+
+pub(crate) fn write_file(path: &Path, contents: String) -> Result<()> {
+    fs::write(path, contents).map_err(|source| ProfilerError::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Original formatting-heavy implementation, kept as a benchmark reference
+/// for [`generate_flame_graph`].
+#[doc(hidden)]
+pub fn generate_flame_graph_old(profile: &ProfileData, output_path: &Path) -> Result<()> {
+    let mut svg = String::from(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" version="1.1" width="1200" height="800">
+<style>
+.frame { font-family: monospace; font-size: 12px; }
+.frame rect { stroke: #000; stroke-width: 1px; }
+.hot { fill: #ff6b6b; }
+.warm { fill: #ffa500; }
+.cool { fill: #4ecdc4; }
+</style>
+"#,
+    );
+
+    let max_time = profile
+        .functions
+        .values()
+        .map(|f| f.total_time.as_nanos())
+        .max()
+        .unwrap_or(1) as f64;
+
+    let mut y = 20.0;
+    let bar_height = 20.0;
+    let width = 1200.0;
+
+    let mut sorted_functions: Vec<_> = profile.functions.values().collect();
+    sorted_functions.sort_by_key(|function| std::cmp::Reverse(function.total_time));
+
+    for func in sorted_functions.iter().take(30) {
+        let time_ratio = func.total_time.as_nanos() as f64 / max_time;
+        let bar_width = width * time_ratio.min(1.0);
+
+        let color_class = if time_ratio > 0.7 {
+            "hot"
+        } else if time_ratio > 0.3 {
+            "warm"
+        } else {
+            "cool"
+        };
+
+        svg.push_str(&format!(
+            "<g class=\"frame\">\n<rect x=\"0\" y=\"{}\" width=\"{}\" height=\"{}\" class=\"{}\"/>\n<text x=\"5\" y=\"{}\" fill=\"white\">{}</text>\n<text x=\"{}\" y=\"{}\" fill=\"white\" text-anchor=\"end\">{:.2}ms</text>\n</g>\n",
+            y,
+            bar_width,
+            bar_height,
+            color_class,
+            y + 15.0,
+            func.name,
+            bar_width - 5.0,
+            y + 15.0,
+            func.total_time.as_secs_f64() * 1000.0
+        ));
+
+        y += bar_height + 2.0;
+    }
+
+    svg.push_str("</svg>");
+
+    write_file(output_path, svg)
+}
+
+/// Writes [`flame_graph_svg`] to `output_path`.
+pub fn generate_flame_graph(profile: &ProfileData, output_path: &Path) -> Result<()> {
+    write_file(output_path, flame_graph_svg(profile))
+}
+
+/// Renders the nested calls as an icicle-style flame graph: callers on top,
+/// each frame as wide as its share of the run and coloured by that share.
+/// Identical call paths are merged.
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::{flame_graph_svg, Profiler};
+///
+/// let mut profiler = Profiler::new();
+/// profiler.enter_function("transfer");
+/// profiler.exit_function("transfer", Duration::from_millis(2));
+/// let svg = flame_graph_svg(&profiler.finish("lib.rs".into(), None));
+///
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains(">transfer</text>"));
+/// ```
+pub fn flame_graph_svg(profile: &ProfileData) -> String {
+    let roots = stack_tree(profile);
+    let run_time: Duration = roots.iter().map(|node| node.total).sum();
+    let height = FRAME_TOP + (tree_depth(&roots) as f64 + 1.0) * FRAME_STEP;
+
+    let mut svg = String::with_capacity(16 * 1024);
+
+    svg.push_str(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" version=\"1.1\" width=\"1200\" height=\"",
+    );
+    svg.push_str(&format_float(height));
+    svg.push_str("\">\n");
+    svg.push_str("<style>\n");
+    svg.push_str(".frame { font-family: monospace; font-size: 12px; }\n");
+    svg.push_str(".frame rect { stroke: #000; stroke-width: 1px; }\n");
+    svg.push_str(".hot { fill: #ff6b6b; }\n");
+    svg.push_str(".warm { fill: #ffa500; }\n");
+    svg.push_str(".cool { fill: #4ecdc4; }\n");
+    svg.push_str("</style>\n");
+
+    let scale = FLAME_GRAPH_WIDTH / run_time.as_nanos().max(1) as f64;
+    let mut x = 0.0;
+    for root in &roots {
+        push_frames(&mut svg, root, x, 0, scale, run_time);
+        x += root.total.as_nanos() as f64 * scale;
+    }
+
+    svg.push_str("</svg>");
+
+    svg
+}
+
+pub(crate) const FLAME_GRAPH_WIDTH: f64 = 1200.0;
+pub(crate) const FRAME_TOP: f64 = 20.0;
+pub(crate) const FRAME_HEIGHT: f64 = 20.0;
+pub(crate) const FRAME_STEP: f64 = FRAME_HEIGHT + 2.0;
+/// Frames narrower than this are left out along with everything they called
+pub(crate) const MIN_FRAME_WIDTH: f64 = 0.1;
+
+fn push_frames(
+    svg: &mut String,
+    node: &StackNode<'_>,
+    x: f64,
+    depth: usize,
+    scale: f64,
+    run_time: Duration,
+) {
+    let bar_width = node.total.as_nanos() as f64 * scale;
+    if bar_width < MIN_FRAME_WIDTH {
+        return;
+    }
+    let y = FRAME_TOP + depth as f64 * FRAME_STEP;
+
+    let time_ratio = node.total.as_nanos() as f64 / run_time.as_nanos().max(1) as f64;
+    let color_class = if time_ratio > 0.7 {
+        "hot"
+    } else if time_ratio > 0.3 {
+        "warm"
+    } else {
+        "cool"
+    };
+
+    svg.push_str("<g class=\"frame\">\n");
+    svg.push_str("<title>");
+    svg.push_str(node.name);
+    svg.push_str(" (");
+    svg.push_str(&format_float(node.total.as_secs_f64() * 1000.0));
+    svg.push_str("ms total, ");
+    svg.push_str(&format_float(node.self_time().as_secs_f64() * 1000.0));
+    svg.push_str("ms self)</title>\n");
+
+    svg.push_str("<rect x=\"");
+    svg.push_str(&format_float(x));
+    svg.push_str("\" y=\"");
+    svg.push_str(&format_float(y));
+    svg.push_str("\" width=\"");
+    svg.push_str(&format_float(bar_width));
+    svg.push_str("\" height=\"");
+    svg.push_str(&format_float(FRAME_HEIGHT));
+    svg.push_str("\" class=\"");
+    svg.push_str(color_class);
+    svg.push_str("\"/>\n");
+
+    svg.push_str("<text x=\"");
+    svg.push_str(&format_float(x + 5.0));
+    svg.push_str("\" y=\"");
+    svg.push_str(&format_float(y + 15.0));
+    svg.push_str("\" fill=\"white\">");
+    svg.push_str(node.name);
+    svg.push_str("</text>\n");
+
+    svg.push_str("<text x=\"");
+    svg.push_str(&format_float(x + bar_width - 5.0));
+    svg.push_str("\" y=\"");
+    svg.push_str(&format_float(y + 15.0));
+    svg.push_str("\" fill=\"white\" text-anchor=\"end\">");
+    svg.push_str(&format_float(node.total.as_secs_f64() * 1000.0));
+    svg.push_str("ms</text>\n");
+
+    svg.push_str("</g>\n");
+
+    let mut child_x = x;
+    for child in &node.children {
+        push_frames(svg, child, child_x, depth + 1, scale, run_time);
+        child_x += child.total.as_nanos() as f64 * scale;
+    }
+}
+
+/// Renders the nested calls in the folded-stack format read by `flamegraph.pl`
+/// and `inferno`: one `caller;callee count` line per call path, weighted by
+/// the path's self time in nanoseconds.
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::{folded_stacks, Profiler};
+///
+/// let mut profiler = Profiler::new();
+/// profiler.enter_function("swap");
+/// profiler.enter_function("update_reserves");
+/// profiler.exit_function("update_reserves", Duration::from_nanos(800));
+/// profiler.exit_function("swap", Duration::from_nanos(1_000));
+///
+/// let folded = folded_stacks(&profiler.finish("lib.rs".into(), None));
+/// assert_eq!(folded, "swap 200\nswap;update_reserves 800\n");
+/// ```
+pub fn folded_stacks(profile: &ProfileData) -> String {
+    let mut folded = String::new();
+    let mut path = Vec::new();
+    for root in &stack_tree(profile) {
+        push_folded(&mut folded, &mut path, root);
+    }
+    folded
+}
+
+/// Writes [`folded_stacks`] to `output_path`.
+pub fn write_folded_stacks(profile: &ProfileData, output_path: &Path) -> Result<()> {
+    write_file(output_path, folded_stacks(profile))
+}
+
+fn push_folded<'a>(folded: &mut String, path: &mut Vec<&'a str>, node: &StackNode<'a>) {
+    path.push(node.name);
+    let self_nanos = node.self_time().as_nanos();
+    if self_nanos > 0 {
+        let _ = writeln!(folded, "{} {}", path.join(";"), self_nanos);
+    }
+    for child in &node.children {
+        push_folded(folded, path, child);
+    }
+    path.pop();
+}
+
+/// Calls merged by path; the flame graph and folded stacks are drawn from it.
+struct StackNode<'a> {
+    name: &'a str,
+    total: Duration,
+    children: Vec<StackNode<'a>>,
+}
+
+impl StackNode<'_> {
+    fn self_time(&self) -> Duration {
+        let children: Duration = self.children.iter().map(|child| child.total).sum();
+        self.total.saturating_sub(children)
+    }
+}
+
+/// Merges the recorded calls by path. Profiles saved without a call stack
+/// fall back to the caller/callee links between functions.
+fn stack_tree(profile: &ProfileData) -> Vec<StackNode<'_>> {
+    if !profile.call_stack.is_empty() {
+        let frames: Vec<&CallFrame> = profile.call_stack.iter().collect();
+        return merge_frames(&frames);
+    }
+
+    let mut roots: Vec<&FunctionProfile> = profile
+        .functions
+        .values()
+        .filter(|function| function.parents.is_empty())
+        .collect();
+    roots.sort_by(|a, b| b.total_time.cmp(&a.total_time).then(a.name.cmp(&b.name)));
+
+    let mut ancestors = Vec::new();
+    roots
+        .into_iter()
+        .map(|function| linked_node(profile, function, &mut ancestors))
+        .collect()
+}
+
+fn merge_frames<'a>(frames: &[&'a CallFrame]) -> Vec<StackNode<'a>> {
+    let mut merged: Vec<(StackNode<'a>, Vec<&'a CallFrame>)> = Vec::new();
+    for frame in frames {
+        let index = match merged
+            .iter()
+            .position(|(node, _)| node.name == frame.function)
+        {
+            Some(index) => index,
+            None => {
+                let node = StackNode {
+                    name: &frame.function,
+                    total: Duration::ZERO,
+                    children: Vec::new(),
+                };
+                merged.push((node, Vec::new()));
+                merged.len() - 1
+            }
+        };
+        let (node, children) = &mut merged[index];
+        node.total += frame.duration();
+        children.extend(frame.children.iter());
+    }
+
+    merged
+        .into_iter()
+        .map(|(mut node, children)| {
+            node.children = merge_frames(&children);
+            node
+        })
+        .collect()
+}
+
+fn linked_node<'a>(
+    profile: &'a ProfileData,
+    function: &'a FunctionProfile,
+    ancestors: &mut Vec<&'a str>,
+) -> StackNode<'a> {
+    ancestors.push(&function.name);
+    let children = function
+        .children
+        .iter()
+        // Recursion would otherwise never end
+        .filter(|child| !ancestors.contains(&child.as_str()))
+        .filter_map(|child| profile.functions.get(child))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|child| linked_node(profile, child, ancestors))
+        .collect();
+    ancestors.pop();
+
+    StackNode {
+        name: &function.name,
+        total: function.total_time,
+        children,
+    }
+}
+
+fn tree_depth(nodes: &[StackNode<'_>]) -> usize {
+    nodes
+        .iter()
+        .map(|node| 1 + tree_depth(&node.children))
+        .max()
+        .unwrap_or(0)
+}
+
+pub(crate) fn format_float(v: f64) -> String {
+    let mut s = String::new();
+    s.reserve(16);
+    let _ = write!(&mut s, "{:.2}", v);
+    s
+}
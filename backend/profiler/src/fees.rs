@@ -0,0 +1,256 @@
+//! Estimating what an invocation costs on-chain from the resources it used.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ProfilerError, Result};
+
+/// Stroops in one XLM
+pub const STROOPS_PER_XLM: u64 = 10_000_000;
+
+/// Resources one invocation of the profiled method used. A field is `None`
+/// when the profiling engine cannot measure it; local engines measure none,
+/// remote simulation can fill all of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    pub cpu_instructions: Option<u64>,
+    /// Not charged for, only limited
+    pub memory_bytes: Option<u64>,
+    /// Ledger entries read, not counting those also written
+    pub read_entries: Option<u64>,
+    pub write_entries: Option<u64>,
+    pub read_bytes: Option<u64>,
+    pub write_bytes: Option<u64>,
+    pub transaction_size_bytes: Option<u64>,
+}
+
+/// The network's resource fee parameters, in stroops, plus the usage to
+/// assume for resources a profile did not measure.
+///
+/// Loaded from a JSON file with [`FeeConfig::load`]; fields left out keep
+/// their [`Default`], which follows the public network's settings at the time
+/// of writing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FeeConfig {
+    /// Per 10,000 CPU instructions
+    pub fee_per_instructions_increment: u64,
+    /// Per ledger entry read, written entries included
+    pub fee_per_read_entry: u64,
+    pub fee_per_write_entry: u64,
+    pub fee_per_read_1kb: u64,
+    pub fee_per_write_1kb: u64,
+    /// Per KiB of transaction propagated over the network
+    pub fee_per_transaction_size_1kb: u64,
+    /// Per KiB of transaction kept in history archives
+    pub fee_historical_1kb: u64,
+    /// Flat fee for getting the transaction into a ledger
+    pub inclusion_fee: u64,
+    /// Usage assumed for resources the profile has no measurement of
+    pub assumed_usage: ResourceUsage,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            fee_per_instructions_increment: 25,
+            fee_per_read_entry: 6_250,
+            fee_per_write_entry: 10_000,
+            fee_per_read_1kb: 1_786,
+            fee_per_write_1kb: 11_800,
+            fee_per_transaction_size_1kb: 1_624,
+            fee_historical_1kb: 16_235,
+            inclusion_fee: 100,
+            // A small contract call: instance and code read, one entry written
+            assumed_usage: ResourceUsage {
+                cpu_instructions: Some(2_000_000),
+                memory_bytes: None,
+                read_entries: Some(2),
+                write_entries: Some(1),
+                read_bytes: Some(8_192),
+                write_bytes: Some(256),
+                transaction_size_bytes: Some(512),
+            },
+        }
+    }
+}
+
+/// Historical fees also cover the transaction result, roughly this size
+const TRANSACTION_RESULT_SIZE_BYTES: u64 = 300;
+
+impl FeeConfig {
+    /// Reads fee parameters from a JSON file, see [`FeeConfig`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|source| ProfilerError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&content).map_err(|source| ProfilerError::InvalidFeeConfig {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Applies the fee parameters to `usage`, falling back to
+    /// [`assumed_usage`](Self::assumed_usage) (or zero) for every resource
+    /// that was not measured.
+    ///
+    /// ```
+    /// use soroban_profiler::{FeeConfig, FeeResource, ResourceUsage};
+    ///
+    /// let usage = ResourceUsage {
+    ///     cpu_instructions: Some(4_000_000),
+    ///     ..ResourceUsage::default()
+    /// };
+    /// let estimate = FeeConfig::default().estimate(&usage);
+    ///
+    /// let cpu = &estimate.components[0];
+    /// assert_eq!(cpu.resource, FeeResource::CpuInstructions);
+    /// assert!(cpu.measured);
+    /// assert_eq!(cpu.stroops, 10_000);
+    /// assert!(!estimate.fully_measured());
+    /// ```
+    pub fn estimate(&self, usage: &ResourceUsage) -> FeeEstimate {
+        let assumed = &self.assumed_usage;
+        let pick = |measured: Option<u64>, assumed: Option<u64>| match measured {
+            Some(amount) => (amount, true),
+            None => (assumed.unwrap_or(0), false),
+        };
+
+        let (instructions, instructions_measured) =
+            pick(usage.cpu_instructions, assumed.cpu_instructions);
+        let (read_entries, read_entries_measured) = pick(usage.read_entries, assumed.read_entries);
+        let (write_entries, write_entries_measured) =
+            pick(usage.write_entries, assumed.write_entries);
+        let (read_bytes, read_bytes_measured) = pick(usage.read_bytes, assumed.read_bytes);
+        let (write_bytes, write_bytes_measured) = pick(usage.write_bytes, assumed.write_bytes);
+        let (size, size_measured) =
+            pick(usage.transaction_size_bytes, assumed.transaction_size_bytes);
+
+        let components = vec![
+            FeeComponent {
+                resource: FeeResource::CpuInstructions,
+                amount: instructions,
+                measured: instructions_measured,
+                stroops: instructions
+                    .saturating_mul(self.fee_per_instructions_increment)
+                    .div_ceil(10_000),
+            },
+            FeeComponent {
+                resource: FeeResource::ReadEntries,
+                amount: read_entries,
+                measured: read_entries_measured && write_entries_measured,
+                // Every entry written is read first
+                stroops: (read_entries + write_entries).saturating_mul(self.fee_per_read_entry),
+            },
+            FeeComponent {
+                resource: FeeResource::WriteEntries,
+                amount: write_entries,
+                measured: write_entries_measured,
+                stroops: write_entries.saturating_mul(self.fee_per_write_entry),
+            },
+            FeeComponent {
+                resource: FeeResource::ReadBytes,
+                amount: read_bytes,
+                measured: read_bytes_measured,
+                stroops: read_bytes
+                    .saturating_mul(self.fee_per_read_1kb)
+                    .div_ceil(1024),
+            },
+            FeeComponent {
+                resource: FeeResource::WriteBytes,
+                amount: write_bytes,
+                measured: write_bytes_measured,
+                stroops: write_bytes
+                    .saturating_mul(self.fee_per_write_1kb)
+                    .div_ceil(1024),
+            },
+            FeeComponent {
+                resource: FeeResource::TransactionSize,
+                amount: size,
+                measured: size_measured,
+                stroops: size
+                    .saturating_mul(self.fee_per_transaction_size_1kb)
+                    .div_ceil(1024)
+                    + (size + TRANSACTION_RESULT_SIZE_BYTES)
+                        .saturating_mul(self.fee_historical_1kb)
+                        .div_ceil(1024),
+            },
+        ];
+
+        let resource_stroops: u64 = components.iter().map(|component| component.stroops).sum();
+        FeeEstimate {
+            total_stroops: resource_stroops + self.inclusion_fee,
+            inclusion_fee: self.inclusion_fee,
+            components,
+        }
+    }
+}
+
+/// Estimated fee for one invocation, see [`FeeConfig::estimate`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    /// Resource fees plus the inclusion fee
+    pub total_stroops: u64,
+    pub inclusion_fee: u64,
+    pub components: Vec<FeeComponent>,
+}
+
+impl FeeEstimate {
+    pub fn total_xlm(&self) -> f64 {
+        self.total_stroops as f64 / STROOPS_PER_XLM as f64
+    }
+
+    /// Whether every component came from a measurement rather than
+    /// [`FeeConfig::assumed_usage`]
+    pub fn fully_measured(&self) -> bool {
+        self.components.iter().all(|component| component.measured)
+    }
+
+    /// Components that fell back to [`FeeConfig::assumed_usage`]
+    pub fn assumed(&self) -> impl Iterator<Item = &FeeComponent> {
+        self.components
+            .iter()
+            .filter(|component| !component.measured)
+    }
+}
+
+/// What one resource adds to the fee
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeComponent {
+    pub resource: FeeResource,
+    /// Instructions, entries or bytes
+    pub amount: u64,
+    /// `false` when `amount` was assumed rather than measured
+    pub measured: bool,
+    pub stroops: u64,
+}
+
+/// A resource Soroban charges for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeResource {
+    CpuInstructions,
+    ReadEntries,
+    WriteEntries,
+    ReadBytes,
+    WriteBytes,
+    TransactionSize,
+}
+
+impl fmt::Display for FeeResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CpuInstructions => "CPU instructions",
+            Self::ReadEntries => "ledger entries read",
+            Self::WriteEntries => "ledger entries written",
+            Self::ReadBytes => "bytes read",
+            Self::WriteBytes => "bytes written",
+            Self::TransactionSize => "transaction size",
+        })
+    }
+}
@@ -0,0 +1,244 @@
+//! Fixed-size latency histograms.
+//!
+//! Call durations are counted in logarithmic buckets: exact below 4ns, then
+//! four buckets per power of two, so each bucket is at most a quarter of its
+//! lower bound wide. That covers every `u64` nanosecond value in
+//! [`BUCKET_COUNT`] buckets, however many calls are recorded.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Buckets per power of two
+const SUB_BUCKETS: u64 = 4;
+
+/// Number of buckets needed to cover every `u64` nanosecond value
+pub const BUCKET_COUNT: usize = 252;
+
+/// Sparkline characters, from fewest to most calls
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Call counts per logarithmic duration bucket. Only buckets that were hit
+/// are stored or serialized.
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::LatencyHistogram;
+///
+/// let mut histogram = LatencyHistogram::default();
+/// for _ in 0..90 {
+///     histogram.record(Duration::from_micros(10)); // cache hit
+/// }
+/// for _ in 0..10 {
+///     histogram.record(Duration::from_millis(2)); // storage miss
+/// }
+///
+/// assert_eq!(histogram.count(), 100);
+/// assert!(histogram.percentile(50.0).unwrap() < Duration::from_micros(12));
+/// assert!(histogram.percentile(99.0).unwrap() > Duration::from_millis(1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "Vec<HistogramBucket>", from = "Vec<HistogramBucket>")]
+pub struct LatencyHistogram {
+    counts: BTreeMap<u8, u64>,
+}
+
+/// One non-empty bucket: `count` calls took at least `lower_ns` and less than
+/// `upper_ns` nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub lower_ns: u64,
+    pub upper_ns: u64,
+    pub count: u64,
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        *self.counts.entry(bucket_index(nanos)).or_default() += 1;
+    }
+
+    /// Number of calls recorded
+    pub fn count(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Non-empty buckets, shortest first
+    pub fn buckets(&self) -> impl Iterator<Item = HistogramBucket> + '_ {
+        self.counts.iter().map(|(&index, &count)| {
+            let (lower_ns, upper_ns) = bucket_bounds(index);
+            HistogramBucket {
+                lower_ns,
+                upper_ns,
+                count,
+            }
+        })
+    }
+
+    /// Duration below which `percentile` percent of the calls fell,
+    /// interpolated linearly inside the bucket it lands in. `None` when
+    /// nothing was recorded.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let total = self.count();
+        if total == 0 {
+            return None;
+        }
+
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * total as f64).max(1.0);
+        let mut seen = 0u64;
+        for bucket in self.buckets() {
+            if (seen + bucket.count) as f64 >= rank {
+                let within = (rank - seen as f64) / bucket.count as f64;
+                let width = (bucket.upper_ns - bucket.lower_ns) as f64;
+                let nanos = bucket.lower_ns as f64 + within * width;
+                return Some(Duration::from_nanos(nanos as u64));
+            }
+            seen += bucket.count;
+        }
+        self.buckets()
+            .last()
+            .map(|bucket| Duration::from_nanos(bucket.upper_ns))
+    }
+
+    /// One character per bucket from the shortest to the longest recorded
+    /// call, taller for more calls and blank for empty buckets.
+    pub fn sparkline(&self) -> String {
+        let (Some((&first, _)), Some((&last, _))) =
+            (self.counts.first_key_value(), self.counts.last_key_value())
+        else {
+            return String::new();
+        };
+        let max = self.counts.values().copied().max().unwrap_or(1);
+
+        (first..=last)
+            .map(|index| match self.counts.get(&index) {
+                None => ' ',
+                Some(&count) => {
+                    let level = count * (SPARKS.len() as u64 - 1) / max;
+                    SPARKS[level as usize]
+                }
+            })
+            .collect()
+    }
+}
+
+impl From<LatencyHistogram> for Vec<HistogramBucket> {
+    fn from(histogram: LatencyHistogram) -> Self {
+        histogram.buckets().collect()
+    }
+}
+
+impl From<Vec<HistogramBucket>> for LatencyHistogram {
+    fn from(buckets: Vec<HistogramBucket>) -> Self {
+        let mut counts = BTreeMap::new();
+        for bucket in buckets {
+            *counts.entry(bucket_index(bucket.lower_ns)).or_default() += bucket.count;
+        }
+        Self { counts }
+    }
+}
+
+fn bucket_index(nanos: u64) -> u8 {
+    if nanos < SUB_BUCKETS {
+        return nanos as u8;
+    }
+    let exponent = 63 - nanos.leading_zeros() as u64;
+    let sub_bucket = (nanos >> (exponent - 2)) & (SUB_BUCKETS - 1);
+    (SUB_BUCKETS + (exponent - 2) * SUB_BUCKETS + sub_bucket) as u8
+}
+
+/// Lower bound (inclusive) and upper bound (exclusive) of a bucket
+fn bucket_bounds(index: u8) -> (u64, u64) {
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return (index, index + 1);
+    }
+    let exponent = (index - SUB_BUCKETS) / SUB_BUCKETS + 2;
+    let sub_bucket = (index - SUB_BUCKETS) % SUB_BUCKETS;
+    let width = 1u64 << (exponent - 2);
+    let lower = (SUB_BUCKETS + sub_bucket) * width;
+    (lower, lower.saturating_add(width))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_cover_every_value_in_order() {
+        assert_eq!(bucket_index(u64::MAX) as usize, BUCKET_COUNT - 1);
+
+        let mut previous_upper = 0;
+        for index in 0..BUCKET_COUNT as u8 {
+            let (lower, upper) = bucket_bounds(index);
+            assert_eq!(lower, previous_upper, "gap before bucket {index}");
+            assert_eq!(bucket_index(lower), index);
+            assert_eq!(bucket_index(upper - 1), index);
+            previous_upper = upper;
+        }
+    }
+
+    #[test]
+    fn buckets_are_at_most_a_quarter_wide() {
+        for index in SUB_BUCKETS as u8..BUCKET_COUNT as u8 {
+            let (lower, upper) = bucket_bounds(index);
+            assert!(
+                upper - lower <= lower / 4,
+                "bucket {index}: {lower}..{upper}"
+            );
+        }
+    }
+
+    #[test]
+    fn percentiles_separate_a_bimodal_distribution() {
+        let mut histogram = LatencyHistogram::default();
+        for _ in 0..95 {
+            histogram.record(Duration::from_nanos(1_000));
+        }
+        for _ in 0..5 {
+            histogram.record(Duration::from_nanos(100_000));
+        }
+
+        let p50 = histogram.percentile(50.0).unwrap().as_nanos();
+        let p90 = histogram.percentile(90.0).unwrap().as_nanos();
+        let p99 = histogram.percentile(99.0).unwrap().as_nanos();
+        assert!((896..1_024).contains(&p50), "p50 = {p50}");
+        assert!((896..1_024).contains(&p90), "p90 = {p90}");
+        assert!((98_304..114_688).contains(&p99), "p99 = {p99}");
+        assert_eq!(LatencyHistogram::default().percentile(50.0), None);
+    }
+
+    #[test]
+    fn serializes_only_buckets_that_were_hit() {
+        let mut histogram = LatencyHistogram::default();
+        histogram.record(Duration::from_nanos(1_000));
+        histogram.record(Duration::from_nanos(1_010));
+
+        let json = serde_json::to_value(&histogram).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{ "lower_ns": 896, "upper_ns": 1024, "count": 2 }])
+        );
+        assert_eq!(
+            serde_json::from_value::<LatencyHistogram>(json).unwrap(),
+            histogram
+        );
+    }
+
+    #[test]
+    fn sparkline_spans_the_recorded_range() {
+        let mut histogram = LatencyHistogram::default();
+        for _ in 0..8 {
+            histogram.record(Duration::from_nanos(4));
+        }
+        histogram.record(Duration::from_nanos(7));
+
+        assert_eq!(histogram.sparkline(), "█  ▁");
+        assert_eq!(LatencyHistogram::default().sparkline(), "");
+    }
+}
@@ -0,0 +1,71 @@
+//! Profile Soroban contract execution, compare runs against a saved baseline
+//! and export flame graphs and timelines.
+//!
+//! ```
+//! use soroban_profiler::{
+//!     compare_profiles, generate_recommendations, parse_profile, profile_contract, to_json,
+//!     ProfileOptions, RegressionGate,
+//! };
+//!
+//! let dir = tempfile::tempdir()?;
+//! let contract = dir.path().join("lib.rs");
+//! std::fs::write(&contract, "pub fn transfer() {}\nfn balance() {}\n")?;
+//!
+//! let options = ProfileOptions::builder(&contract).iterations(2).build();
+//! let baseline = profile_contract(&options)?;
+//!
+//! // Saved profiles stay loadable by later releases
+//! let saved = to_json(&baseline)?;
+//! let baseline = parse_profile(&saved)?.profile;
+//!
+//! let current = profile_contract(&options)?;
+//! let changes = compare_profiles(&baseline, &current);
+//! for change in &changes {
+//!     println!("{} {}", change.function, change.status);
+//! }
+//! for regression in RegressionGate::default().violations(&changes) {
+//!     eprintln!("{} regressed", regression.function);
+//! }
+//! for recommendation in generate_recommendations(&current) {
+//!     println!("{}", recommendation);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub mod budget;
+pub mod chrome_trace;
+pub mod compare;
+pub mod diff;
+pub mod error;
+pub mod execution;
+pub mod export;
+pub mod fees;
+pub mod histogram;
+pub mod profile;
+pub mod sampling;
+pub mod schema;
+
+pub use budget::{estimate_wasm_methods, BudgetModel, MethodBudget};
+pub use chrome_trace::{export_chrome_trace, write_chrome_trace};
+pub use compare::{
+    compare_fees, compare_profiles, generate_recommendations, ChangeStatus, ComparisonResult,
+    FeeChange, RegressionGate, DISTRIBUTION_SHIFT_PERCENT,
+};
+pub use diff::{differential_flame_graph_svg, generate_differential_flame_graph};
+pub use error::{ProfilerError, Result};
+pub use execution::{
+    parse_contract_functions, profile_contract, simulate_execution, ExecutionHooks, ProfileOptions,
+    ProfileOptionsBuilder,
+};
+#[doc(hidden)]
+pub use export::generate_flame_graph_old;
+pub use export::{
+    flame_graph_svg, folded_stacks, generate_flame_graph, to_json, write_folded_stacks, write_json,
+};
+pub use fees::{FeeComponent, FeeConfig, FeeEstimate, FeeResource, ResourceUsage, STROOPS_PER_XLM};
+pub use histogram::{HistogramBucket, LatencyHistogram};
+pub use profile::{
+    CallFrame, FunctionProfile, ProfileData, ProfileMode, Profiler, UNRELIABLE_OVERHEAD_FACTOR,
+};
+pub use sampling::Sampler;
+pub use schema::{load_profile, parse_profile, LoadedProfile, PROFILE_SCHEMA_VERSION};
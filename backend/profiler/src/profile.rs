@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fees::{FeeEstimate, ResourceUsage};
+use crate::histogram::LatencyHistogram;
+use crate::schema::PROFILE_SCHEMA_VERSION;
+
+/// Timing of one function across all of its calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionProfile {
+    pub name: String,
+    /// Inclusive time, including the functions it called
+    #[serde(with = "duration_nanos")]
+    pub total_time: Duration,
+    /// Time spent in the function itself, excluding the functions it called
+    #[serde(with = "duration_nanos")]
+    pub self_time: Duration,
+    /// Calls made, or in a [`ProfileMode::Sampled`] profile the number of
+    /// samples taken while the function was on the stack
+    pub call_count: u64,
+    #[serde(with = "duration_nanos")]
+    pub avg_time: Duration,
+    #[serde(with = "duration_nanos")]
+    pub min_time: Duration,
+    #[serde(with = "duration_nanos")]
+    pub max_time: Duration,
+    /// Inclusive call durations, for percentiles
+    pub histogram: LatencyHistogram,
+    /// Functions that called this one
+    pub parents: Vec<String>,
+    /// Functions called from this one
+    pub children: Vec<String>,
+    /// The average call took less than [`UNRELIABLE_OVERHEAD_FACTOR`] times
+    /// the profiler's own overhead, so its times are mostly instrumentation
+    pub unreliable: bool,
+}
+
+/// Calls shorter than this multiple of [`ProfileData::overhead_per_call`] are
+/// flagged [`FunctionProfile::unreliable`].
+pub const UNRELIABLE_OVERHEAD_FACTOR: u32 = 2;
+
+/// Empty enter/exit pairs timed by [`Profiler::calibrate`]
+const CALIBRATION_CALLS: u32 = 10_000;
+
+impl FunctionProfile {
+    /// A function that has not been called yet
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            total_time: Duration::ZERO,
+            self_time: Duration::ZERO,
+            call_count: 0,
+            avg_time: Duration::ZERO,
+            min_time: Duration::ZERO,
+            max_time: Duration::ZERO,
+            histogram: LatencyHistogram::default(),
+            parents: Vec::new(),
+            children: Vec::new(),
+            unreliable: false,
+        }
+    }
+
+    /// Summarises the individual call durations of `name`, treating every
+    /// call as a leaf so self time equals total time.
+    pub fn from_durations(name: impl Into<String>, durations: &[Duration]) -> Self {
+        let mut profile = Self::new(name);
+        for duration in durations {
+            profile.record(*duration);
+        }
+        profile.self_time = profile.total_time;
+        profile
+    }
+
+    /// Adds one call's inclusive duration to the totals and the histogram.
+    /// Self time is left to the caller.
+    pub fn record(&mut self, duration: Duration) {
+        if self.call_count == 0 {
+            self.min_time = duration;
+            self.max_time = duration;
+        } else {
+            self.min_time = self.min_time.min(duration);
+            self.max_time = self.max_time.max(duration);
+        }
+        self.call_count += 1;
+        self.total_time += duration;
+        self.avg_time =
+            Duration::from_nanos((self.total_time.as_nanos() / self.call_count as u128) as u64);
+        self.histogram.record(duration);
+    }
+
+    /// Call duration at `percentile` (0-100) from the histogram, kept within
+    /// the recorded minimum and maximum. `None` for profiles saved before
+    /// histograms were recorded.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        self.histogram
+            .percentile(percentile)
+            .map(|duration| duration.clamp(self.min_time, self.max_time))
+    }
+}
+
+pub(crate) mod duration_nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u64(duration.as_nanos() as u64)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nanos = u64::deserialize(deserializer)?;
+        Ok(Duration::from_nanos(nanos))
+    }
+}
+
+/// One profiling run. Durations are serialized as nanoseconds; see
+/// [`crate::schema`] for how saved profiles are versioned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileData {
+    pub schema_version: u32,
+    pub contract_path: String,
+    pub method: Option<String>,
+    /// RFC 3339 time the run finished
+    pub timestamp: String,
+    #[serde(with = "duration_nanos")]
+    pub total_duration: Duration,
+    pub functions: HashMap<String, FunctionProfile>,
+    /// Top-level calls, each with the calls nested beneath it
+    pub call_stack: Vec<CallFrame>,
+    /// What the instrumentation adds to each measured call, found by
+    /// [`Profiler::calibrate`] and already subtracted from the times above
+    #[serde(with = "duration_nanos")]
+    pub overhead_per_call: Duration,
+    /// Time spent in the profiler itself, as a share of `total_duration`
+    pub overhead_percent: f64,
+    /// Whether every call was timed or the stack was sampled
+    pub mode: ProfileMode,
+    /// Time between two samples; zero when instrumented
+    #[serde(with = "duration_nanos")]
+    pub sample_interval: Duration,
+    /// What one invocation of the method used, as far as the engine measured
+    pub resources: ResourceUsage,
+    /// Fee for one invocation, when it was estimated with
+    /// [`FeeConfig`](crate::FeeConfig)
+    pub fee_estimate: Option<FeeEstimate>,
+}
+
+/// How a profile was recorded
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProfileMode {
+    /// Every call was hooked and timed by a [`Profiler`]
+    #[default]
+    Instrumented,
+    /// A [`Sampler`](crate::Sampler) recorded the call stack at a fixed rate.
+    /// Times are estimates weighted by the number of samples, call counts
+    /// are sample counts and there are no per-call durations.
+    Sampled,
+    /// A deployed contract was invoked through Soroban RPC's
+    /// `simulateTransaction`. There is no timing, only the method's
+    /// [`ResourceUsage`]; see [`ProfileData::from_simulation`].
+    Simulated,
+    /// Every exported method of a built WASM module was costed from its
+    /// bytecode by the [budget engine](crate::budget). Like a simulated
+    /// profile there is no timing; see [`ProfileData::from_budgets`].
+    Budgeted,
+}
+
+impl fmt::Display for ProfileMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Instrumented => "instrumented",
+            Self::Sampled => "sampled",
+            Self::Simulated => "simulated",
+            Self::Budgeted => "budgeted",
+        })
+    }
+}
+
+impl ProfileMode {
+    /// Whether function times are CPU instructions rather than measured time
+    pub fn counts_instructions(self) -> bool {
+        matches!(self, Self::Simulated | Self::Budgeted)
+    }
+}
+
+impl ProfileData {
+    /// A profile of one simulated invocation of `method` on the deployed
+    /// contract `contract_id`.
+    ///
+    /// Simulation reports resources, not time, so the method's CPU
+    /// instructions stand in for its duration at one instruction per
+    /// nanosecond. Two simulated profiles compare, and go through a
+    /// [`RegressionGate`](crate::RegressionGate), like any others; against a
+    /// timed profile the numbers mean different things.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use soroban_profiler::{ProfileData, ProfileMode, ResourceUsage};
+    ///
+    /// let resources = ResourceUsage {
+    ///     cpu_instructions: Some(1_250_000),
+    ///     ..ResourceUsage::default()
+    /// };
+    /// let profile = ProfileData::from_simulation("CDLZ...", "transfer", resources);
+    ///
+    /// assert_eq!(profile.mode, ProfileMode::Simulated);
+    /// assert_eq!(profile.functions["transfer"].call_count, 1);
+    /// assert_eq!(profile.total_duration, Duration::from_nanos(1_250_000));
+    /// ```
+    pub fn from_simulation(
+        contract_id: impl Into<String>,
+        method: impl Into<String>,
+        resources: ResourceUsage,
+    ) -> Self {
+        let method = method.into();
+        let instructions = Duration::from_nanos(resources.cpu_instructions.unwrap_or(0));
+        let function = FunctionProfile::from_durations(method.clone(), &[instructions]);
+
+        ProfileData {
+            schema_version: PROFILE_SCHEMA_VERSION,
+            contract_path: contract_id.into(),
+            method: Some(method.clone()),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            total_duration: instructions,
+            functions: HashMap::from([(method.clone(), function)]),
+            call_stack: vec![CallFrame {
+                function: method,
+                start_time: 0,
+                end_time: instructions.as_nanos() as u64,
+                children: Vec::new(),
+            }],
+            overhead_per_call: Duration::ZERO,
+            overhead_percent: 0.0,
+            mode: ProfileMode::Simulated,
+            sample_interval: Duration::ZERO,
+            resources,
+            fee_estimate: None,
+        }
+    }
+
+    /// Functions flagged [`FunctionProfile::unreliable`]
+    pub fn unreliable_functions(&self) -> impl Iterator<Item = &FunctionProfile> {
+        self.functions
+            .values()
+            .filter(|function| function.unreliable)
+    }
+}
+
+/// One call and the calls it made, in the order they were made. Times are
+/// nanoseconds since profiling started.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallFrame {
+    pub function: String,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub children: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    /// Inclusive duration of the call
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.end_time.saturating_sub(self.start_time))
+    }
+
+    /// Duration of the call minus the calls it made
+    pub fn self_time(&self) -> Duration {
+        let children: Duration = self.children.iter().map(CallFrame::duration).sum();
+        self.duration().saturating_sub(children)
+    }
+}
+
+/// Records nested function calls as a stack of spans and turns them into
+/// [`ProfileData`].
+///
+/// Each [`enter`](Self::enter) opens a span inside the innermost open one and
+/// [`exit`](Self::exit) closes it, so a function's self time is its own
+/// duration minus that of the spans opened beneath it.
+///
+/// [`Profiler::new`] first measures what a pair of hooks costs and subtracts
+/// that from every measured span, once for the span itself and once for each
+/// call nested beneath it, never going below zero.
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::Profiler;
+///
+/// let mut profiler = Profiler::new();
+/// profiler.enter_function("swap");
+/// profiler.enter_function("update_reserves");
+/// profiler.exit_function("update_reserves", Duration::from_micros(80));
+/// profiler.exit_function("swap", Duration::from_micros(100));
+///
+/// let profile = profiler.finish("contracts/pool/src/lib.rs".into(), None);
+/// let swap = &profile.functions["swap"];
+/// assert_eq!(swap.children, vec!["update_reserves"]);
+/// assert_eq!(swap.total_time, Duration::from_micros(100));
+/// assert_eq!(swap.self_time, Duration::from_micros(20));
+/// assert_eq!(profile.functions["update_reserves"].parents, vec!["swap"]);
+/// ```
+pub struct Profiler {
+    start_time: Instant,
+    spans: Vec<OpenSpan>,
+    roots: Vec<CallFrame>,
+    function_stats: HashMap<String, FunctionProfile>,
+    overhead_per_call: Duration,
+    measured_calls: u32,
+}
+
+/// A call that has been entered but not exited yet
+struct OpenSpan {
+    name: String,
+    start: Instant,
+    children: Vec<CallFrame>,
+    /// Calls made beneath this one, at any depth
+    nested_calls: u32,
+}
+
+impl Default for Profiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profiler {
+    /// Calibrates the hooks, then starts profiling.
+    pub fn new() -> Self {
+        Self::with_overhead(Self::calibrate())
+    }
+
+    /// Starts profiling without calibrating, subtracting `overhead_per_call`
+    /// instead; zero keeps measured times as they are.
+    pub fn with_overhead(overhead_per_call: Duration) -> Self {
+        Self {
+            start_time: Instant::now(),
+            spans: Vec::new(),
+            roots: Vec::new(),
+            function_stats: HashMap::new(),
+            overhead_per_call,
+            measured_calls: 0,
+        }
+    }
+
+    /// Times empty [`enter`](Self::enter)/[`exit`](Self::exit) pairs and
+    /// returns the average cost of one pair.
+    pub fn calibrate() -> Duration {
+        let mut scratch = Self::with_overhead(Duration::ZERO);
+        let start = Instant::now();
+        for _ in 0..CALIBRATION_CALLS {
+            scratch.enter("calibration");
+            scratch.exit();
+        }
+        start.elapsed() / CALIBRATION_CALLS
+    }
+
+    /// Opens a span for `name` inside the innermost open span.
+    pub fn enter(&mut self, name: &str) {
+        if let Some(parent) = self.spans.last() {
+            let parent = parent.name.clone();
+            push_unique(&mut self.stats(&parent).children, name);
+            push_unique(&mut self.stats(name).parents, &parent);
+        }
+
+        self.spans.push(OpenSpan {
+            name: name.to_string(),
+            start: Instant::now(),
+            children: Vec::new(),
+            nested_calls: 0,
+        });
+    }
+
+    /// Closes the innermost open span and returns how long it was open, less
+    /// the profiler's overhead, or `None` if no span is open.
+    pub fn exit(&mut self) -> Option<Duration> {
+        let elapsed = self.spans.last()?.start.elapsed();
+        let span = self.spans.pop()?;
+
+        let overhead = self.overhead_per_call * (1 + span.nested_calls);
+        let duration = elapsed.saturating_sub(overhead);
+        self.measured_calls += 1;
+        self.close(span, duration);
+        Some(duration)
+    }
+
+    /// Same as [`enter`](Self::enter).
+    pub fn enter_function(&mut self, name: &str) {
+        self.enter(name);
+    }
+
+    /// Closes the innermost open span, which must be `name`, recording
+    /// `duration` instead of the measured time. For callers that time the
+    /// work themselves, so no overhead is subtracted.
+    pub fn exit_function(&mut self, name: &str, duration: Duration) {
+        match self.spans.pop() {
+            Some(span) => {
+                debug_assert_eq!(span.name, name, "spans must be exited in reverse order");
+                self.close(span, duration);
+            }
+            // Nothing was entered, so record a call without nesting
+            None => {
+                let stats = self.stats(name);
+                stats.record(duration);
+                stats.self_time += duration;
+            }
+        }
+    }
+
+    fn stats(&mut self, name: &str) -> &mut FunctionProfile {
+        self.function_stats
+            .entry(name.to_string())
+            .or_insert_with(|| FunctionProfile::new(name))
+    }
+
+    fn close(&mut self, span: OpenSpan, duration: Duration) {
+        let start_time = span
+            .start
+            .saturating_duration_since(self.start_time)
+            .as_nanos() as u64;
+        let frame = CallFrame {
+            function: span.name,
+            start_time,
+            end_time: start_time + duration.as_nanos() as u64,
+            children: span.children,
+        };
+
+        let self_time = frame.self_time();
+        let stats = self.stats(&frame.function);
+        stats.record(duration);
+        stats.self_time += self_time;
+
+        match self.spans.last_mut() {
+            Some(parent) => {
+                parent.nested_calls += 1 + span.nested_calls;
+                parent.children.push(frame);
+            }
+            None => self.roots.push(frame),
+        }
+    }
+
+    /// Closes any spans still open and summarises the run.
+    pub fn finish(mut self, contract_path: String, method: Option<String>) -> ProfileData {
+        while self.exit().is_some() {}
+
+        let total_duration = self.start_time.elapsed();
+        let overhead_total = self.overhead_per_call * self.measured_calls;
+        let overhead_percent = if total_duration.as_nanos() > 0 {
+            (overhead_total.as_nanos() as f64 / total_duration.as_nanos() as f64) * 100.0
+        } else {
+            0.0
+        };
+        let reliable_above = self.overhead_per_call * UNRELIABLE_OVERHEAD_FACTOR;
+
+        let mut functions = self.function_stats;
+        for profile in functions.values_mut() {
+            profile.unreliable = profile.avg_time < reliable_above;
+        }
+
+        ProfileData {
+            schema_version: PROFILE_SCHEMA_VERSION,
+            contract_path,
+            method,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            total_duration,
+            functions,
+            call_stack: self.roots,
+            overhead_per_call: self.overhead_per_call,
+            overhead_percent,
+            mode: ProfileMode::Instrumented,
+            sample_interval: Duration::ZERO,
+            resources: ResourceUsage::default(),
+            fee_estimate: None,
+        }
+    }
+}
+
+pub(crate) fn push_unique(names: &mut Vec<String>, name: &str) {
+    if !names.iter().any(|existing| existing == name) {
+        names.push(name.to_string());
+    }
+}
@@ -0,0 +1,285 @@
+//! Sampling the call stack at a fixed rate instead of timing every call.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::fees::ResourceUsage;
+use crate::profile::{push_unique, CallFrame, FunctionProfile, ProfileData, ProfileMode};
+use crate::schema::PROFILE_SCHEMA_VERSION;
+
+/// Records which functions are on the stack at a fixed rate and turns the
+/// samples into [`ProfileData`].
+///
+/// The code being profiled only pushes and pops names with
+/// [`enter`](Self::enter) and [`exit`](Self::exit); nothing is timed per
+/// call, so tiny hot functions are not distorted by instrumentation. A
+/// function's time is the number of samples it was on the stack (its
+/// `call_count` in the profile) times the sampling interval, and its self
+/// time the samples it was on top. Samples do not record the order calls
+/// were made in, so callers, callees and `call_stack` frames are sorted by
+/// name.
+///
+/// [`Sampler::new`] samples from a background thread. [`Sampler::manual`]
+/// only samples when [`sample`](Self::sample) is called:
+///
+/// ```
+/// use std::time::Duration;
+/// use soroban_profiler::{ProfileMode, Sampler};
+///
+/// let mut sampler = Sampler::manual(1_000);
+/// sampler.enter("swap");
+/// sampler.sample();
+/// sampler.enter("update_reserves");
+/// sampler.sample();
+/// sampler.sample();
+/// sampler.exit();
+/// sampler.exit();
+///
+/// let profile = sampler.finish("contracts/pool/src/lib.rs".into(), None);
+/// assert_eq!(profile.mode, ProfileMode::Sampled);
+/// let swap = &profile.functions["swap"];
+/// assert_eq!(swap.call_count, 3);
+/// assert_eq!(swap.total_time, Duration::from_millis(3));
+/// assert_eq!(swap.self_time, Duration::from_millis(1));
+/// ```
+pub struct Sampler {
+    start_time: Instant,
+    interval: Duration,
+    state: Arc<Mutex<SampleState>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+/// What the code being profiled and the sampling thread share
+#[derive(Default)]
+struct SampleState {
+    stack: Vec<String>,
+    /// Samples per call path, root first
+    samples: BTreeMap<Vec<String>, u64>,
+    /// Time the sampling thread spent taking samples
+    sampling_time: Duration,
+}
+
+impl SampleState {
+    fn take_sample(&mut self, weight: u64) {
+        if self.stack.is_empty() || weight == 0 {
+            return;
+        }
+        match self.samples.get_mut(&self.stack) {
+            Some(count) => *count += weight,
+            None => {
+                self.samples.insert(self.stack.clone(), weight);
+            }
+        }
+    }
+}
+
+impl Sampler {
+    /// Starts sampling `rate_hz` times a second (at least once) on a
+    /// background thread.
+    ///
+    /// When the thread wakes late, the stack it finds is counted once for
+    /// every interval that passed since the previous sample, so the weights
+    /// still add up to the elapsed time.
+    pub fn new(rate_hz: u32) -> Self {
+        let mut sampler = Self::manual(rate_hz);
+        let state = Arc::clone(&sampler.state);
+        let stop = Arc::clone(&sampler.stop);
+        let start_time = sampler.start_time;
+        let interval_nanos = sampler.interval_nanos();
+
+        sampler.thread = Some(thread::spawn(move || {
+            let mut taken = 0u64;
+            while !stop.load(Ordering::Relaxed) {
+                let next = start_time + Duration::from_nanos(interval_nanos * (taken + 1));
+                thread::sleep(next.saturating_duration_since(Instant::now()));
+
+                let woke = Instant::now();
+                let ticks =
+                    (woke.duration_since(start_time).as_nanos() / interval_nanos as u128) as u64;
+                let mut state = lock(&state);
+                state.take_sample(ticks.saturating_sub(taken));
+                state.sampling_time += woke.elapsed();
+                taken = ticks;
+            }
+        }));
+        sampler
+    }
+
+    /// Starts a sampler that only samples on [`sample`](Self::sample), each
+    /// sample standing for `1 / rate_hz` seconds.
+    pub fn manual(rate_hz: u32) -> Self {
+        Self {
+            start_time: Instant::now(),
+            interval: Duration::from_secs(1) / rate_hz.max(1),
+            state: Arc::default(),
+            stop: Arc::default(),
+            thread: None,
+        }
+    }
+
+    /// Time between two samples
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    fn interval_nanos(&self) -> u64 {
+        (self.interval.as_nanos() as u64).max(1)
+    }
+
+    /// Pushes `name` on the sampled stack.
+    pub fn enter(&mut self, name: &str) {
+        lock(&self.state).stack.push(name.to_string());
+    }
+
+    /// Pops the innermost function off the sampled stack.
+    pub fn exit(&mut self) {
+        lock(&self.state).stack.pop();
+    }
+
+    /// Records the current stack once.
+    pub fn sample(&self) {
+        lock(&self.state).take_sample(1);
+    }
+
+    /// Stops sampling and summarises the samples.
+    pub fn finish(mut self, contract_path: String, method: Option<String>) -> ProfileData {
+        self.stop_thread();
+        let total_duration = self.start_time.elapsed();
+        let state = std::mem::take(&mut *lock(&self.state));
+
+        let mut functions: HashMap<String, FunctionProfile> = HashMap::new();
+        let mut roots: Vec<SampledFrame> = Vec::new();
+        for (path, &count) in &state.samples {
+            let time = Duration::from_nanos(self.interval_nanos().saturating_mul(count));
+            let mut seen: Vec<&str> = Vec::new();
+            for (depth, name) in path.iter().enumerate() {
+                let function = functions
+                    .entry(name.clone())
+                    .or_insert_with(|| FunctionProfile::new(name.as_str()));
+                // A recursive function is on the stack once per sample
+                if !seen.contains(&name.as_str()) {
+                    seen.push(name.as_str());
+                    function.call_count += count;
+                    function.total_time += time;
+                }
+                if depth == path.len() - 1 {
+                    function.self_time += time;
+                }
+                if let Some(parent) = depth.checked_sub(1).map(|parent| &path[parent]) {
+                    push_unique(&mut functions.get_mut(name).unwrap().parents, parent);
+                    push_unique(&mut functions.get_mut(parent).unwrap().children, name);
+                }
+            }
+            insert(&mut roots, path, count);
+        }
+        for function in functions.values_mut() {
+            function.avg_time = self.interval;
+            function.min_time = self.interval;
+            function.max_time = self.interval;
+        }
+
+        let mut cursor = 0;
+        let call_stack = roots
+            .iter()
+            .map(|root| {
+                let frame = root.to_call_frame(cursor, self.interval_nanos());
+                cursor = frame.end_time;
+                frame
+            })
+            .collect();
+
+        let overhead_percent = if total_duration.as_nanos() > 0 {
+            (state.sampling_time.as_nanos() as f64 / total_duration.as_nanos() as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        ProfileData {
+            schema_version: PROFILE_SCHEMA_VERSION,
+            contract_path,
+            method,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            total_duration,
+            functions,
+            call_stack,
+            overhead_per_call: Duration::ZERO,
+            overhead_percent,
+            mode: ProfileMode::Sampled,
+            sample_interval: self.interval,
+            resources: ResourceUsage::default(),
+            fee_estimate: None,
+        }
+    }
+
+    fn stop_thread(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            // A panicking sampling thread only loses the samples it held
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for Sampler {
+    fn drop(&mut self) {
+        self.stop_thread();
+    }
+}
+
+fn lock(state: &Mutex<SampleState>) -> MutexGuard<'_, SampleState> {
+    state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Samples merged by call path
+struct SampledFrame {
+    name: String,
+    samples: u64,
+    children: Vec<SampledFrame>,
+}
+
+impl SampledFrame {
+    /// Lays the frame out from `start`, as long as its samples take, with
+    /// its children one after the other from the same start.
+    fn to_call_frame(&self, start: u64, interval_nanos: u64) -> CallFrame {
+        let mut cursor = start;
+        let children = self
+            .children
+            .iter()
+            .map(|child| {
+                let frame = child.to_call_frame(cursor, interval_nanos);
+                cursor = frame.end_time;
+                frame
+            })
+            .collect();
+
+        CallFrame {
+            function: self.name.clone(),
+            start_time: start,
+            end_time: start + interval_nanos * self.samples,
+            children,
+        }
+    }
+}
+
+fn insert(frames: &mut Vec<SampledFrame>, path: &[String], samples: u64) {
+    let Some((name, rest)) = path.split_first() else {
+        return;
+    };
+    let index = match frames.iter().position(|frame| frame.name == *name) {
+        Some(index) => index,
+        None => {
+            frames.push(SampledFrame {
+                name: name.clone(),
+                samples: 0,
+                children: Vec::new(),
+            });
+            frames.len() - 1
+        }
+    };
+    frames[index].samples += samples;
+    insert(&mut frames[index].children, rest, samples);
+}
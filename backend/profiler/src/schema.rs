@@ -0,0 +1,248 @@
+//! Versioned storage format for saved profiles.
+//!
+//! Profiles are saved as JSON and later loaded as baselines, possibly by a
+//! newer release. [`parse_profile`] reads any earlier schema and upgrades it
+//! to the current [`ProfileData`] layout, filling fields older versions did
+//! not record; only versions newer than [`PROFILE_SCHEMA_VERSION`] are
+//! refused.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{json, Map, Value};
+
+use crate::error::{ProfilerError, Result};
+use crate::profile::ProfileData;
+
+/// Schema version written to new profiles. Bump it whenever `ProfileData`
+/// changes shape and teach [`parse_profile`] to upgrade the previous version,
+/// so saved baselines keep loading.
+///
+/// History:
+/// - 1: unversioned documents. Early ones only carry `total_time` and
+///   `call_count` per function and have no `call_stack`/`overhead_percent`.
+/// - 2: adds `schema_version`.
+/// - 3: adds `self_time` and `parents` per function; `call_stack` holds the
+///   nested calls.
+/// - 4: adds the calibrated `overhead_per_call`, already subtracted from
+///   measured times, and `unreliable` per function.
+/// - 5: adds a latency `histogram` per function.
+/// - 6: adds `mode` and `sample_interval`, for profiles sampled rather than
+///   instrumented.
+/// - 7: adds the measured `resources` and an optional `fee_estimate`.
+/// - 8: adds the `simulated` mode for deployed contracts profiled through
+///   Soroban RPC. Earlier documents need no changes, but earlier releases
+///   must refuse these instead of failing on the unknown mode.
+/// - 9: adds the `budgeted` mode for profiles estimated from WASM bytecode,
+///   again needing no upgrade.
+pub const PROFILE_SCHEMA_VERSION: u32 = 9;
+
+/// Version assumed for documents without a `schema_version` field
+const UNVERSIONED_SCHEMA: u32 = 1;
+
+/// A saved profile upgraded to the current [`ProfileData`] layout
+#[derive(Debug, Clone)]
+pub struct LoadedProfile {
+    pub profile: ProfileData,
+    /// Version the document was written with, before upgrading
+    pub source_version: u32,
+}
+
+impl LoadedProfile {
+    pub fn was_upgraded(&self) -> bool {
+        self.source_version != PROFILE_SCHEMA_VERSION
+    }
+}
+
+/// Reads a saved profile from disk, see [`parse_profile`].
+pub fn load_profile(path: impl AsRef<Path>) -> Result<LoadedProfile> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).map_err(|source| ProfilerError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    parse_profile(&content)
+}
+
+/// Parses a saved profile of any supported schema version.
+///
+/// ```
+/// use soroban_profiler::{parse_profile, PROFILE_SCHEMA_VERSION};
+///
+/// // Written before profiles were versioned
+/// let loaded = parse_profile(r#"{
+///     "contract_path": "contracts/token/src/lib.rs",
+///     "total_duration": 9000000,
+///     "functions": { "transfer": { "total_time": 9000000, "call_count": 3 } }
+/// }"#)?;
+///
+/// assert_eq!(loaded.source_version, 1);
+/// assert_eq!(loaded.profile.schema_version, PROFILE_SCHEMA_VERSION);
+/// assert_eq!(loaded.profile.functions["transfer"].avg_time.as_millis(), 3);
+/// # Ok::<(), soroban_profiler::ProfilerError>(())
+/// ```
+pub fn parse_profile(content: &str) -> Result<LoadedProfile> {
+    let document: Value = serde_json::from_str(content)?;
+    let source_version = match document.get("schema_version") {
+        None => UNVERSIONED_SCHEMA,
+        Some(version) => version
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= UNVERSIONED_SCHEMA)
+            .ok_or_else(|| {
+                ProfilerError::InvalidProfile(format!("invalid schema_version {}", version))
+            })?,
+    };
+    if source_version > PROFILE_SCHEMA_VERSION {
+        return Err(ProfilerError::UnsupportedSchema {
+            found: source_version,
+            supported: PROFILE_SCHEMA_VERSION,
+        });
+    }
+
+    let profile = serde_json::from_value(upgrade(document, source_version)?)?;
+    Ok(LoadedProfile {
+        profile,
+        source_version,
+    })
+}
+
+/// Rewrites a document from `version` to [`PROFILE_SCHEMA_VERSION`], one
+/// version at a time.
+fn upgrade(mut document: Value, version: u32) -> Result<Value> {
+    let root = document
+        .as_object_mut()
+        .ok_or_else(|| ProfilerError::InvalidProfile("expected a JSON object".to_string()))?;
+
+    if version < 2 {
+        upgrade_v1(root);
+    }
+    if version < 3 {
+        upgrade_v2(root);
+    }
+    if version < 4 {
+        upgrade_v3(root);
+    }
+    if version < 5 {
+        upgrade_v4(root);
+    }
+    if version < 6 {
+        upgrade_v5(root);
+    }
+    if version < 7 {
+        upgrade_v6(root);
+    }
+
+    root.insert("schema_version".to_string(), json!(PROFILE_SCHEMA_VERSION));
+    Ok(document)
+}
+
+/// v1 → v2: fills the fields early unversioned profiles did not write.
+fn upgrade_v1(root: &mut Map<String, Value>) {
+    root.entry("method").or_insert(Value::Null);
+    root.entry("timestamp").or_insert_with(|| json!(""));
+    root.entry("call_stack").or_insert_with(|| json!([]));
+    root.entry("overhead_percent").or_insert_with(|| json!(0.0));
+
+    let Some(functions) = root.get_mut("functions").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for (name, function) in functions.iter_mut() {
+        let Some(function) = function.as_object_mut() else {
+            continue;
+        };
+        let total = function
+            .get("total_time")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let calls = function
+            .get("call_count")
+            .and_then(Value::as_u64)
+            .unwrap_or(1);
+        // Only totals were recorded, so the average stands in for min and max
+        let avg = total / calls.max(1);
+
+        function.entry("name").or_insert_with(|| json!(name));
+        function.entry("total_time").or_insert_with(|| json!(total));
+        function.entry("call_count").or_insert_with(|| json!(calls));
+        function.entry("avg_time").or_insert_with(|| json!(avg));
+        function.entry("min_time").or_insert_with(|| json!(avg));
+        function.entry("max_time").or_insert_with(|| json!(avg));
+        function.entry("children").or_insert_with(|| json!([]));
+    }
+}
+
+/// v2 → v3: only inclusive time was recorded, so self time is taken to be the
+/// total, and each function's callers are rebuilt from the others' children.
+fn upgrade_v2(root: &mut Map<String, Value>) {
+    let Some(functions) = root.get_mut("functions").and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    let mut parents: HashMap<String, Vec<String>> = HashMap::new();
+    for (name, function) in functions.iter() {
+        let children = function
+            .get("children")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str);
+        for child in children {
+            let callers = parents.entry(child.to_string()).or_default();
+            if !callers.contains(name) {
+                callers.push(name.clone());
+            }
+        }
+    }
+
+    for (name, function) in functions.iter_mut() {
+        let Some(function) = function.as_object_mut() else {
+            continue;
+        };
+        let total = function
+            .get("total_time")
+            .cloned()
+            .unwrap_or_else(|| json!(0));
+        function.entry("self_time").or_insert(total);
+        function
+            .entry("parents")
+            .or_insert_with(|| json!(parents.remove(name).unwrap_or_default()));
+    }
+}
+
+/// v3 → v4: overhead was not measured, so nothing was subtracted and no
+/// function can be told apart as unreliable.
+fn upgrade_v3(root: &mut Map<String, Value>) {
+    root.entry("overhead_per_call").or_insert_with(|| json!(0));
+
+    let Some(functions) = root.get_mut("functions").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for function in functions.values_mut().filter_map(Value::as_object_mut) {
+        function.entry("unreliable").or_insert(Value::Bool(false));
+    }
+}
+
+/// v4 → v5: individual calls were not kept, so the histogram starts empty and
+/// percentiles are unknown.
+fn upgrade_v4(root: &mut Map<String, Value>) {
+    let Some(functions) = root.get_mut("functions").and_then(Value::as_object_mut) else {
+        return;
+    };
+    for function in functions.values_mut().filter_map(Value::as_object_mut) {
+        function.entry("histogram").or_insert_with(|| json!([]));
+    }
+}
+
+/// v5 → v6: every call was still instrumented.
+fn upgrade_v5(root: &mut Map<String, Value>) {
+    root.entry("mode").or_insert_with(|| json!("instrumented"));
+    root.entry("sample_interval").or_insert_with(|| json!(0));
+}
+
+/// v6 → v7: no engine measured resources yet and no fee was estimated.
+fn upgrade_v6(root: &mut Map<String, Value>) {
+    root.entry("resources").or_insert_with(|| json!({}));
+    root.entry("fee_estimate").or_insert(Value::Null);
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use soroban_profiler::{
+    estimate_wasm_methods, parse_profile, to_json, BudgetModel, ProfileData, ProfileMode,
+    ProfilerError,
+};
+
+fn section(id: u8, content: &[u8]) -> Vec<u8> {
+    let mut section = vec![id, content.len() as u8];
+    section.extend_from_slice(content);
+    section
+}
+
+fn body(code: &[u8]) -> Vec<u8> {
+    let mut body = vec![code.len() as u8];
+    body.extend_from_slice(code);
+    body
+}
+
+/// One host import, one page of memory and three functions: `transfer`
+/// calls the host and a helper that calls back into `transfer`, `balance`
+/// does nothing
+fn token_wasm() -> Vec<u8> {
+    let mut wasm = b"\0asm\x01\0\0\0".to_vec();
+    wasm.extend(section(0x01, &[0x01, 0x60, 0x00, 0x00]));
+    wasm.extend(section(0x02, &[0x01, 0x01, b'x', 0x01, b'_', 0x00, 0x00]));
+    wasm.extend(section(0x03, &[0x03, 0x00, 0x00, 0x00]));
+    wasm.extend(section(0x05, &[0x01, 0x00, 0x01]));
+
+    let mut exports = vec![0x02, 0x08];
+    exports.extend_from_slice(b"transfer");
+    exports.extend([0x00, 0x01, 0x07]);
+    exports.extend_from_slice(b"balance");
+    exports.extend([0x00, 0x02]);
+    wasm.extend(section(0x07, &exports));
+
+    let mut code = vec![0x03];
+    // transfer: two i64 locals; call helper, call host, end
+    code.extend(body(&[0x01, 0x02, 0x7e, 0x10, 0x03, 0x10, 0x00, 0x0b]));
+    // balance: nop, end
+    code.extend(body(&[0x00, 0x01, 0x0b]));
+    // helper: one i32 local; nop, call transfer, end
+    code.extend(body(&[0x01, 0x01, 0x7f, 0x01, 0x10, 0x01, 0x0b]));
+    wasm.extend(section(0x0a, &code));
+    wasm
+}
+
+#[test]
+fn costs_every_function_a_method_reaches_once() {
+    let methods = estimate_wasm_methods(&token_wasm(), &BudgetModel::default()).unwrap();
+    let names: Vec<_> = methods
+        .iter()
+        .map(|budget| budget.method.as_str())
+        .collect();
+    assert_eq!(names, ["balance", "transfer"]);
+
+    let balance = &methods[0].resources;
+    assert_eq!(balance.cpu_instructions, Some(2 * 4));
+    assert_eq!(balance.memory_bytes, Some(65_536));
+
+    // Its own three instructions, the helper's three and one host call; the
+    // helper's call back into transfer is not counted again
+    let transfer = &methods[1].resources;
+    assert_eq!(transfer.cpu_instructions, Some(6 * 4 + 1_000));
+    assert_eq!(transfer.memory_bytes, Some(65_536 + 3 * 8));
+    assert_eq!(transfer.read_entries, None);
+}
+
+#[test]
+fn follows_the_budget_model() {
+    let model = BudgetModel {
+        cpu_per_instruction: 1,
+        cpu_per_host_call: 0,
+        memory_per_local: 0,
+    };
+    let methods = estimate_wasm_methods(&token_wasm(), &model).unwrap();
+    assert_eq!(methods[1].resources.cpu_instructions, Some(6));
+    assert_eq!(methods[1].resources.memory_bytes, Some(65_536));
+}
+
+#[test]
+fn rejects_modules_that_do_not_parse() {
+    let err = estimate_wasm_methods(b"\0asm\x01\0\0\0\x0a\x05\x01", &BudgetModel::default())
+        .expect_err("truncated module must fail");
+    assert!(matches!(err, ProfilerError::InvalidWasm(_)), "{}", err);
+}
+
+#[test]
+fn budgeted_profiles_round_trip() {
+    let methods = estimate_wasm_methods(&token_wasm(), &BudgetModel::default()).unwrap();
+    let profile = ProfileData::from_budgets("CDLZ...", &methods);
+    assert_eq!(profile.mode, ProfileMode::Budgeted);
+    assert_eq!(profile.total_duration, Duration::from_nanos(8 + 1_024));
+    assert_eq!(profile.call_stack[1].start_time, 8);
+
+    let reloaded = parse_profile(&to_json(&profile).unwrap()).unwrap().profile;
+    assert_eq!(reloaded.mode, ProfileMode::Budgeted);
+    assert_eq!(
+        reloaded.functions["transfer"].total_time,
+        Duration::from_nanos(1_024)
+    );
+}
@@ -0,0 +1,165 @@
+use std::time::Duration;
+
+use serde_json::Value;
+use soroban_profiler::{export_chrome_trace, write_chrome_trace, ProfileData, Profiler};
+
+fn us(micros: u64) -> Duration {
+    Duration::from_micros(micros)
+}
+
+/// Two swaps whose explicit durations do not line up with the wall clock:
+/// emit_event is entered right after update_reserves exits, but
+/// update_reserves claims 80µs.
+fn nested_swap() -> ProfileData {
+    let mut profiler = Profiler::with_overhead(Duration::ZERO);
+    for read_storage in [30, 40] {
+        profiler.enter_function("swap");
+        profiler.enter_function("update_reserves");
+        profiler.enter_function("read_storage");
+        profiler.exit_function("read_storage", us(read_storage));
+        profiler.exit_function("update_reserves", us(80));
+        profiler.enter_function("emit_event");
+        profiler.exit_function("emit_event", us(5));
+        profiler.exit_function("swap", us(100));
+    }
+    profiler.finish("contracts/pool/src/lib.rs".into(), Some("swap".into()))
+}
+
+fn trace_events(profile: &ProfileData) -> Vec<Value> {
+    let trace: Value = serde_json::from_str(&export_chrome_trace(profile).unwrap()).unwrap();
+    trace["traceEvents"].as_array().unwrap().clone()
+}
+
+/// Complete events as (name, start, end) in nanoseconds
+fn spans(events: &[Value]) -> Vec<(String, u64, u64)> {
+    let nanos = |micros: &Value| (micros.as_f64().unwrap() * 1_000.0).round() as u64;
+    events
+        .iter()
+        .filter(|event| event["ph"] == "X")
+        .map(|event| {
+            let ts = nanos(&event["ts"]);
+            let dur = nanos(&event["dur"]);
+            (event["name"].as_str().unwrap().to_string(), ts, ts + dur)
+        })
+        .collect()
+}
+
+/// Every event either ends before the next one starts or contains it.
+fn assert_properly_nested(spans: &[(String, u64, u64)]) {
+    let mut open: Vec<&(String, u64, u64)> = Vec::new();
+    for span in spans {
+        while open.last().is_some_and(|parent| parent.2 <= span.1) {
+            open.pop();
+        }
+        if let Some(parent) = open.last() {
+            assert!(
+                span.2 <= parent.2,
+                "{} ({}..{}) overlaps the end of {} ({}..{})",
+                span.0,
+                span.1,
+                span.2,
+                parent.0,
+                parent.1,
+                parent.2
+            );
+        }
+        open.push(span);
+    }
+}
+
+#[test]
+fn events_follow_the_trace_event_format() {
+    let events = trace_events(&nested_swap());
+
+    for event in &events {
+        let phase = event["ph"].as_str().expect("every event has a phase");
+        assert!(event["name"].is_string(), "{event}");
+        assert_eq!(event["pid"], 1, "{event}");
+        assert_eq!(event["tid"], 1, "{event}");
+        match phase {
+            "M" => assert!(event["args"]["name"].is_string(), "{event}"),
+            "X" => {
+                assert!(event["ts"].as_f64().unwrap() >= 0.0, "{event}");
+                assert!(event["dur"].as_f64().unwrap() >= 0.0, "{event}");
+            }
+            other => panic!("unexpected phase {other}"),
+        }
+    }
+
+    assert_eq!(events[0]["name"], "process_name");
+    assert_eq!(events[0]["args"]["name"], "contracts/pool/src/lib.rs");
+    assert_eq!(events[1]["name"], "thread_name");
+    assert_eq!(events[1]["args"]["name"], "swap");
+}
+
+#[test]
+fn nested_calls_produce_nested_events() {
+    let events = trace_events(&nested_swap());
+    let spans = spans(&events);
+
+    let names: Vec<_> = spans.iter().map(|span| span.0.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "swap",
+            "update_reserves",
+            "read_storage",
+            "emit_event",
+            "swap",
+            "update_reserves",
+            "read_storage",
+            "emit_event",
+        ]
+    );
+    assert_properly_nested(&spans);
+
+    // Durations are kept; calls are only moved to fit
+    let durations: Vec<_> = events
+        .iter()
+        .filter_map(|event| event["dur"].as_f64())
+        .collect();
+    assert_eq!(
+        durations,
+        vec![100.0, 80.0, 30.0, 5.0, 100.0, 80.0, 40.0, 5.0]
+    );
+    assert!(
+        spans[3].1 >= spans[1].2,
+        "emit_event starts after update_reserves"
+    );
+    assert!(
+        spans[4].1 >= spans[0].2,
+        "the second swap starts after the first"
+    );
+}
+
+#[test]
+fn measured_spans_keep_their_timestamps() {
+    let mut profiler = Profiler::with_overhead(Duration::ZERO);
+    profiler.enter("swap");
+    std::thread::sleep(Duration::from_millis(1));
+    profiler.enter("update_reserves");
+    std::thread::sleep(Duration::from_millis(1));
+    profiler.exit();
+    profiler.exit();
+    let profile = profiler.finish("lib.rs".into(), None);
+
+    let events = trace_events(&profile);
+    assert_eq!(events[1]["args"]["name"], "main");
+
+    let spans = spans(&events);
+    assert_properly_nested(&spans);
+    let frame = &profile.call_stack[0].children[0];
+    assert_eq!(spans[1].1, frame.start_time);
+    assert!(spans[1].1 >= 1_000_000, "update_reserves started after 1ms");
+}
+
+#[test]
+fn writes_the_trace_to_a_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("trace.json");
+    write_chrome_trace(&nested_swap(), &path).unwrap();
+
+    let trace: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(trace["displayTimeUnit"], "ns");
+    assert_eq!(trace["traceEvents"].as_array().unwrap().len(), 2 + 8);
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use soroban_profiler::{
+    compare_fees, compare_profiles, ChangeStatus, ComparisonResult, FeeConfig, ProfileData,
+    Profiler, RegressionGate, ResourceUsage,
+};
+
+fn run(functions: &[(&str, u64)]) -> ProfileData {
+    let mut profiler = Profiler::new();
+    for (name, micros) in functions {
+        profiler.enter_function(name);
+        profiler.exit_function(name, Duration::from_micros(*micros));
+    }
+    profiler.finish("contracts/pool/src/lib.rs".into(), None)
+}
+
+fn change<'a>(changes: &'a [ComparisonResult], function: &str) -> &'a ComparisonResult {
+    changes
+        .iter()
+        .find(|change| change.function == function)
+        .unwrap_or_else(|| panic!("no change reported for {function}"))
+}
+
+#[test]
+fn added_and_removed_functions_carry_their_absolute_times() {
+    let baseline = run(&[("swap", 100), ("legacy_fee", 40)]);
+    let current = run(&[("swap", 100), ("flash_loan", 250)]);
+
+    let changes = compare_profiles(&baseline, &current);
+    assert_eq!(changes.len(), 3);
+
+    let new = change(&changes, "flash_loan");
+    assert_eq!(new.status, ChangeStatus::New);
+    assert_eq!(new.baseline_time, Duration::ZERO);
+    assert_eq!(new.current_time, Duration::from_micros(250));
+    assert_eq!(new.time_diff_ns, 250_000);
+    assert_eq!(new.time_diff_percent, None);
+
+    let removed = change(&changes, "legacy_fee");
+    assert_eq!(removed.status, ChangeStatus::Removed);
+    assert_eq!(removed.baseline_time, Duration::from_micros(40));
+    assert_eq!(removed.current_time, Duration::ZERO);
+    assert_eq!(removed.time_diff_percent, Some(-100.0));
+
+    assert_eq!(change(&changes, "swap").status, ChangeStatus::Unchanged);
+}
+
+#[test]
+fn status_serializes_as_lowercase() {
+    let changes = compare_profiles(&run(&[]), &run(&[("flash_loan", 1)]));
+    let json = serde_json::to_value(&changes[0]).unwrap();
+    assert_eq!(json["status"], "new");
+    assert!(json["time_diff_percent"].is_null());
+}
+
+#[test]
+fn gate_flags_slowdowns_and_new_hot_paths() {
+    let baseline = run(&[("swap", 100), ("quote", 100), ("legacy_fee", 900)]);
+    let current = run(&[
+        ("swap", 125),
+        ("quote", 105),
+        ("flash_loan", 2_000),
+        ("emit_event", 10),
+    ]);
+    let changes = compare_profiles(&baseline, &current);
+
+    let gate = RegressionGate {
+        max_slowdown_percent: 10.0,
+        max_new_function_time: Duration::from_millis(1),
+    };
+    let mut failed: Vec<_> = gate
+        .violations(&changes)
+        .iter()
+        .map(|change| change.function.as_str())
+        .collect();
+    failed.sort_unstable();
+
+    // quote is within 10%, emit_event is new but cheap, legacy_fee was removed
+    assert_eq!(failed, vec!["flash_loan", "swap"]);
+}
+
+#[test]
+fn flags_a_tail_that_regressed_while_the_mean_held() {
+    // 100 calls each: the baseline has one 200µs call, the current run five,
+    // with faster common calls keeping the mean where it was
+    let calls = |fast_micros: u64, slow: usize| {
+        let mut profiler = Profiler::with_overhead(Duration::ZERO);
+        for call in 0..100 {
+            let micros = if call < slow { 200 } else { fast_micros };
+            profiler.enter_function("read_storage");
+            profiler.exit_function("read_storage", Duration::from_micros(micros));
+        }
+        profiler.finish("contracts/pool/src/lib.rs".into(), None)
+    };
+    let baseline = calls(20, 1);
+    let current = calls(12, 5);
+
+    let changes = compare_profiles(&baseline, &current);
+    let read_storage = change(&changes, "read_storage");
+    assert!(read_storage.distribution_shift, "{read_storage:?}");
+    assert!(read_storage.current_p99_ns > read_storage.baseline_p99_ns);
+
+    // The same shape on both sides is no shift
+    let steady = compare_profiles(&current, &calls(12, 5));
+    assert!(!change(&steady, "read_storage").distribution_shift);
+}
+
+#[test]
+fn baselines_without_histograms_never_shift() {
+    let mut baseline = run(&[("swap", 100)]);
+    baseline.functions.get_mut("swap").unwrap().histogram = Default::default();
+
+    let changes = compare_profiles(&baseline, &run(&[("swap", 1_000)]));
+    assert_eq!(changes[0].baseline_p99_ns, None);
+    assert!(!changes[0].distribution_shift);
+}
+
+#[test]
+fn fee_deltas_need_an_estimate_on_both_sides() {
+    let estimated = |cpu_instructions: u64| {
+        let mut profile = run(&[("swap", 100)]);
+        profile.resources = ResourceUsage {
+            cpu_instructions: Some(cpu_instructions),
+            ..ResourceUsage::default()
+        };
+        profile.fee_estimate = Some(FeeConfig::default().estimate(&profile.resources));
+        profile
+    };
+    let baseline = estimated(4_000_000);
+    let current = estimated(8_000_000);
+
+    let change = compare_fees(&baseline, &current).expect("both profiles have fees");
+    // 25 stroops per 10,000 instructions
+    assert_eq!(change.diff_stroops, 10_000);
+    assert_eq!(
+        change.current_stroops - change.baseline_stroops,
+        change.diff_stroops as u64
+    );
+    assert!(change.diff_percent.unwrap() > 0.0);
+
+    assert_eq!(compare_fees(&run(&[("swap", 100)]), &current), None);
+}
@@ -0,0 +1,105 @@
+use soroban_profiler::{FeeConfig, FeeResource, ProfilerError, ResourceUsage, STROOPS_PER_XLM};
+
+fn simulated() -> ResourceUsage {
+    ResourceUsage {
+        cpu_instructions: Some(10_000_000),
+        memory_bytes: Some(2_000_000),
+        read_entries: Some(3),
+        write_entries: Some(2),
+        read_bytes: Some(4_096),
+        write_bytes: Some(1_024),
+        transaction_size_bytes: Some(724),
+    }
+}
+
+#[test]
+fn applies_the_fee_parameters_to_measured_usage() {
+    let estimate = FeeConfig::default().estimate(&simulated());
+    assert!(estimate.fully_measured());
+
+    let stroops: Vec<_> = estimate
+        .components
+        .iter()
+        .map(|component| (component.resource, component.stroops))
+        .collect();
+    assert_eq!(
+        stroops,
+        vec![
+            (FeeResource::CpuInstructions, 25_000),
+            // Written entries are read too
+            (FeeResource::ReadEntries, 5 * 6_250),
+            (FeeResource::WriteEntries, 2 * 10_000),
+            (FeeResource::ReadBytes, 4 * 1_786),
+            (FeeResource::WriteBytes, 11_800),
+            // 724 bytes of bandwidth, plus 1KiB of history with the result
+            (FeeResource::TransactionSize, 1_149 + 16_235),
+        ]
+    );
+    let resources: u64 = stroops.iter().map(|(_, stroops)| stroops).sum();
+    assert_eq!(estimate.total_stroops, resources + 100);
+    assert_eq!(
+        estimate.total_xlm(),
+        estimate.total_stroops as f64 / STROOPS_PER_XLM as f64
+    );
+}
+
+#[test]
+fn labels_assumed_dimensions() {
+    // What simulation may leave out: the ledger footprint
+    let usage = ResourceUsage {
+        read_entries: None,
+        write_entries: None,
+        ..simulated()
+    };
+    let estimate = FeeConfig::default().estimate(&usage);
+
+    let assumed: Vec<_> = estimate
+        .assumed()
+        .map(|component| component.resource)
+        .collect();
+    assert_eq!(
+        assumed,
+        vec![FeeResource::ReadEntries, FeeResource::WriteEntries]
+    );
+    let write_entries = &estimate.components[2];
+    assert_eq!(write_entries.amount, 1, "from the default assumed usage");
+}
+
+#[test]
+fn loads_partial_fee_configs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fees.json");
+    std::fs::write(
+        &path,
+        r#"{ "fee_per_instructions_increment": 50, "assumed_usage": { "read_entries": 4 } }"#,
+    )
+    .unwrap();
+
+    let config = FeeConfig::load(&path).unwrap();
+    assert_eq!(config.fee_per_instructions_increment, 50);
+    assert_eq!(
+        config.fee_per_read_entry,
+        FeeConfig::default().fee_per_read_entry
+    );
+    // Whatever the file assumes replaces the default assumptions as a whole
+    assert_eq!(config.assumed_usage.read_entries, Some(4));
+    assert_eq!(config.assumed_usage.cpu_instructions, None);
+
+    let estimate = config.estimate(&ResourceUsage::default());
+    assert_eq!(estimate.components[0].stroops, 0);
+    assert_eq!(estimate.components[1].stroops, 4 * 6_250);
+}
+
+#[test]
+fn rejects_malformed_fee_configs() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("fees.json");
+    std::fs::write(&path, r#"{ "inclusion_fee": "cheap" }"#).unwrap();
+
+    let err = FeeConfig::load(&path).unwrap_err();
+    assert!(
+        matches!(err, ProfilerError::InvalidFeeConfig { .. }),
+        "{err}"
+    );
+    assert!(err.to_string().contains("fees.json"), "{err}");
+}
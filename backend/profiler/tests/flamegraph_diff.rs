@@ -0,0 +1,103 @@
+//! Snapshot of the differential flame graph. Run with `UPDATE_SNAPSHOTS=1`
+//! to rewrite `tests/snapshots/` after an intended change to the output.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use soroban_profiler::{differential_flame_graph_svg, ProfileData, Profiler};
+
+fn snapshot(name: &str, actual: &str) {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/snapshots");
+    path.push(name);
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, actual).expect("failed to write snapshot");
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("missing snapshot {}: {err}", path.display()));
+    assert_eq!(
+        actual,
+        expected,
+        "{} is out of date; rerun with UPDATE_SNAPSHOTS=1 if the change is intended",
+        path.display()
+    );
+}
+
+fn us(micros: u64) -> Duration {
+    Duration::from_micros(micros)
+}
+
+fn call(profiler: &mut Profiler, name: &str, micros: u64, nested: impl FnOnce(&mut Profiler)) {
+    profiler.enter_function(name);
+    nested(profiler);
+    profiler.exit_function(name, us(micros));
+}
+
+/// swap ─┬─ update_reserves ── read_storage
+///       ├─ emit_event            (baseline only)
+///       └─ check_oracle          (current only)
+fn baseline() -> ProfileData {
+    let mut profiler = Profiler::with_overhead(Duration::ZERO);
+    call(&mut profiler, "swap", 100, |p| {
+        call(p, "update_reserves", 60, |p| {
+            call(p, "read_storage", 20, |_| {})
+        });
+        call(p, "emit_event", 10, |_| {});
+    });
+    profiler.finish("contracts/pool/src/lib.rs".into(), Some("swap".into()))
+}
+
+fn current() -> ProfileData {
+    let mut profiler = Profiler::with_overhead(Duration::ZERO);
+    call(&mut profiler, "swap", 120, |p| {
+        call(p, "update_reserves", 50, |p| {
+            call(p, "read_storage", 30, |_| {})
+        });
+        call(p, "check_oracle", 40, |_| {});
+    });
+    profiler.finish("contracts/pool/src/lib.rs".into(), Some("swap".into()))
+}
+
+#[test]
+fn differential_flame_graph_matches_snapshot() {
+    snapshot(
+        "flamegraph_diff.svg",
+        &differential_flame_graph_svg(&baseline(), &current()),
+    );
+}
+
+#[test]
+fn frames_are_classified_by_how_they_changed() {
+    let svg = differential_flame_graph_svg(&baseline(), &current());
+
+    let frame = |class: &str, name: &str| {
+        svg.contains(&format!("<g class=\"frame {class}\">\n<title>{name}: "))
+    };
+    assert!(frame("slower", "swap"));
+    assert!(frame("faster", "update_reserves"));
+    assert!(frame("slower", "read_storage"));
+    assert!(frame("added", "check_oracle"));
+    assert!(frame("removed", "emit_event"));
+    assert_eq!(svg.matches("<g class=\"frame").count(), 5);
+}
+
+#[test]
+fn largest_change_is_most_saturated() {
+    let svg = differential_flame_graph_svg(&baseline(), &current());
+
+    // check_oracle's 40µs is the largest change: swap's 20µs is half as saturated,
+    // update_reserves' and read_storage's 10µs a quarter
+    assert!(svg.contains("fill=\"rgb(255,155,155)\""), "{svg}");
+    assert!(svg.contains("fill=\"rgb(255,205,205)\""), "{svg}");
+    assert!(svg.contains("fill=\"rgb(205,205,255)\""), "{svg}");
+}
+
+#[test]
+fn identical_profiles_are_uncoloured() {
+    let svg = differential_flame_graph_svg(&baseline(), &baseline());
+    assert_eq!(svg.matches("class=\"frame unchanged\"").count(), 4);
+    assert_eq!(svg.matches("fill=\"rgb(255,255,255)\"").count(), 4);
+}
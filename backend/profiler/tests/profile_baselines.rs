@@ -0,0 +1,232 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use soroban_profiler::{
+    generate_recommendations, load_profile, parse_profile, FeeResource, ProfileData, ProfileMode,
+    ProfilerError, ResourceUsage, PROFILE_SCHEMA_VERSION,
+};
+
+fn fixture(name: &str) -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.push("tests/fixtures/profiles");
+    path.push(name);
+    path
+}
+
+#[test]
+fn loads_minimal_unversioned_baseline() {
+    let loaded = load_profile(fixture("v1_minimal.json")).expect("v1 baseline should load");
+    assert_eq!(loaded.source_version, 1);
+    assert!(loaded.was_upgraded());
+
+    let profile = loaded.profile;
+    assert_eq!(profile.schema_version, PROFILE_SCHEMA_VERSION);
+    assert_eq!(profile.method, None);
+    assert!(profile.call_stack.is_empty());
+    assert_eq!(profile.overhead_percent, 0.0);
+
+    let transfer = &profile.functions["transfer"];
+    assert_eq!(transfer.name, "transfer");
+    assert_eq!(transfer.call_count, 3);
+    assert_eq!(transfer.avg_time.as_nanos(), 3_000_000);
+    assert_eq!(transfer.min_time, transfer.avg_time);
+    assert_eq!(transfer.max_time, transfer.avg_time);
+}
+
+#[test]
+fn loads_full_unversioned_baseline_without_touching_recorded_fields() {
+    let loaded = load_profile(fixture("v1_full.json")).expect("v1 baseline should load");
+    assert_eq!(loaded.source_version, 1);
+
+    let transfer = &loaded.profile.functions["transfer"];
+    assert_eq!(transfer.min_time.as_nanos(), 2_000_000);
+    assert_eq!(transfer.max_time.as_nanos(), 4_000_000);
+    assert_eq!(transfer.children, vec!["balance".to_string()]);
+    assert_eq!(loaded.profile.overhead_percent, 1.5);
+    assert_eq!(
+        loaded.profile.functions["balance"].parents,
+        vec!["transfer".to_string()]
+    );
+}
+
+#[test]
+fn v2_baseline_counts_all_time_as_self_time() {
+    let loaded = load_profile(fixture("v2.json")).expect("v2 baseline should load");
+    assert_eq!(loaded.source_version, 2);
+    assert!(loaded.was_upgraded());
+    assert_eq!(loaded.profile.method.as_deref(), Some("transfer"));
+
+    let transfer = &loaded.profile.functions["transfer"];
+    assert_eq!(transfer.self_time, transfer.total_time);
+    assert!(transfer.parents.is_empty());
+}
+
+#[test]
+fn v3_baseline_has_no_overhead_subtracted() {
+    let loaded = load_profile(fixture("v3.json")).expect("v3 baseline should load");
+    assert_eq!(loaded.source_version, 3);
+    assert!(loaded.was_upgraded());
+
+    let profile = loaded.profile;
+    assert_eq!(profile.overhead_per_call, Duration::ZERO);
+    assert_eq!(profile.functions["swap"].self_time.as_nanos(), 20_000);
+    assert_eq!(profile.unreliable_functions().count(), 0);
+    assert_eq!(
+        profile.call_stack[0].children[0].function,
+        "update_reserves"
+    );
+}
+
+#[test]
+fn v4_baseline_has_no_percentiles() {
+    let loaded = load_profile(fixture("v4.json")).expect("v4 baseline should load");
+    assert_eq!(loaded.source_version, 4);
+
+    let profile = loaded.profile;
+    assert_eq!(profile.overhead_per_call.as_nanos(), 100);
+    let unreliable: Vec<_> = profile
+        .unreliable_functions()
+        .map(|function| function.name.as_str())
+        .collect();
+    assert_eq!(unreliable, vec!["emit_event"]);
+
+    let swap = &profile.functions["swap"];
+    assert!(swap.histogram.is_empty());
+    assert_eq!(swap.percentile(99.0), None);
+}
+
+#[test]
+fn v5_baseline_was_instrumented() {
+    let loaded = load_profile(fixture("v5.json")).expect("v5 baseline should load");
+    assert_eq!(loaded.source_version, 5);
+    assert_eq!(loaded.profile.mode, ProfileMode::Instrumented);
+    assert_eq!(loaded.profile.sample_interval, Duration::ZERO);
+
+    let update_reserves = &loaded.profile.functions["update_reserves"];
+    assert_eq!(update_reserves.histogram.count(), 1);
+    // Clamped to the one recorded call rather than the bucket's bounds
+    assert_eq!(
+        update_reserves.percentile(50.0),
+        Some(Duration::from_micros(80))
+    );
+}
+
+#[test]
+fn v6_baseline_has_no_fee_estimate() {
+    let loaded = load_profile(fixture("v6.json")).expect("v6 baseline should load");
+    assert_eq!(loaded.source_version, 6);
+
+    let profile = loaded.profile;
+    assert_eq!(profile.resources, ResourceUsage::default());
+    assert_eq!(profile.fee_estimate, None);
+    assert_eq!(profile.mode, ProfileMode::Sampled);
+    assert_eq!(profile.sample_interval, Duration::from_micros(100));
+    let swap = &profile.functions["swap"];
+    assert_eq!(swap.call_count, 12);
+    assert_eq!(swap.self_time, Duration::from_micros(200));
+    assert_eq!(swap.percentile(50.0), None);
+}
+
+#[test]
+fn v7_baseline_keeps_its_fee_estimate() {
+    let loaded = load_profile(fixture("v7.json")).expect("v7 baseline should load");
+    assert_eq!(loaded.source_version, 7);
+    assert!(loaded.was_upgraded());
+
+    let profile = loaded.profile;
+    assert_eq!(profile.resources.cpu_instructions, Some(4_000_000));
+    assert_eq!(profile.resources.read_entries, None);
+    let fee = profile
+        .fee_estimate
+        .expect("v7 baseline has a fee estimate");
+    assert_eq!(fee.total_stroops, 65_706);
+    let assumed: Vec<_> = fee.assumed().map(|component| component.resource).collect();
+    assert_eq!(
+        assumed,
+        vec![FeeResource::ReadEntries, FeeResource::WriteEntries]
+    );
+}
+
+#[test]
+fn v8_baseline_needs_no_changes() {
+    let loaded = load_profile(fixture("v8.json")).expect("v8 baseline should load");
+    assert_eq!(loaded.source_version, 8);
+    assert!(loaded.was_upgraded());
+
+    let profile = loaded.profile;
+    assert_eq!(profile.mode, ProfileMode::Simulated);
+    assert_eq!(profile.resources.write_entries, Some(2));
+    assert_eq!(
+        profile.functions["transfer"].total_time,
+        Duration::from_nanos(3_250_000)
+    );
+}
+
+#[test]
+fn loads_current_baseline_as_is() {
+    let loaded = load_profile(fixture("v9.json")).expect("v9 baseline should load");
+    assert_eq!(loaded.source_version, PROFILE_SCHEMA_VERSION);
+    assert!(!loaded.was_upgraded());
+
+    let profile = loaded.profile;
+    assert_eq!(profile.mode, ProfileMode::Budgeted);
+    assert!(profile.mode.counts_instructions());
+    assert_eq!(profile.method, None);
+    assert_eq!(profile.functions.len(), 2);
+    assert_eq!(
+        profile.call_stack[1].duration(),
+        Duration::from_nanos(4_000)
+    );
+}
+
+#[test]
+fn rejects_baselines_from_a_newer_release() {
+    let err = load_profile(fixture("future_v99.json")).expect_err("future schema must fail");
+    assert!(
+        matches!(
+            err,
+            ProfilerError::UnsupportedSchema {
+                found: 99,
+                supported: PROFILE_SCHEMA_VERSION
+            }
+        ),
+        "{}",
+        err
+    );
+    assert!(err.to_string().contains("schema version 99"));
+}
+
+#[test]
+fn saved_profiles_round_trip() {
+    let loaded = load_profile(fixture("v1_minimal.json")).unwrap();
+    let saved = serde_json::to_string(&loaded.profile).unwrap();
+
+    let reloaded = parse_profile(&saved).unwrap();
+    assert_eq!(reloaded.source_version, PROFILE_SCHEMA_VERSION);
+    assert_eq!(reloaded.profile.functions.len(), 2);
+}
+
+#[test]
+fn simulated_profiles_round_trip() {
+    let resources = ResourceUsage {
+        cpu_instructions: Some(3_250_000),
+        read_entries: Some(3),
+        ..ResourceUsage::default()
+    };
+    let profile = ProfileData::from_simulation(
+        "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
+        "transfer",
+        resources.clone(),
+    );
+    let saved = serde_json::to_string(&profile).unwrap();
+
+    let reloaded = parse_profile(&saved).unwrap().profile;
+    assert_eq!(reloaded.mode, ProfileMode::Simulated);
+    assert_eq!(reloaded.resources, resources);
+    assert_eq!(reloaded.call_stack[0].duration(), reloaded.total_duration);
+    // The invoked method is the whole profile, not a hot spot in it
+    assert_eq!(
+        generate_recommendations(&reloaded),
+        ["No optimization recommendations at this time."]
+    );
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use soroban_profiler::{
+    flame_graph_svg, folded_stacks, profile_contract, ProfileData, ProfileMode, ProfileOptions,
+    Sampler,
+};
+
+fn ms(millis: u64) -> Duration {
+    Duration::from_millis(millis)
+}
+
+/// swap sampled 10 times: 6 in update_reserves (2 of those in
+/// read_storage), 1 in emit_event and 3 in swap itself.
+fn sampled_swap() -> ProfileData {
+    let mut sampler = Sampler::manual(1_000);
+    let sample = |sampler: &Sampler, times: usize| (0..times).for_each(|_| sampler.sample());
+
+    sampler.enter("swap");
+    sample(&sampler, 3);
+    sampler.enter("update_reserves");
+    sample(&sampler, 4);
+    sampler.enter("read_storage");
+    sample(&sampler, 2);
+    sampler.exit();
+    sampler.exit();
+    sampler.enter("emit_event");
+    sample(&sampler, 1);
+    sampler.exit();
+    sampler.exit();
+
+    // Nothing on the stack, so nothing to count
+    sampler.sample();
+    sampler.finish("contracts/pool/src/lib.rs".into(), Some("swap".into()))
+}
+
+#[test]
+fn times_are_weighted_by_samples() {
+    let profile = sampled_swap();
+    assert_eq!(profile.mode, ProfileMode::Sampled);
+    assert_eq!(profile.sample_interval, ms(1));
+
+    let swap = &profile.functions["swap"];
+    assert_eq!(swap.call_count, 10);
+    assert_eq!(swap.total_time, ms(10));
+    assert_eq!(swap.self_time, ms(3));
+    // Samples do not record call order, so callees come sorted by name
+    assert_eq!(swap.children, vec!["emit_event", "update_reserves"]);
+    assert!(swap.histogram.is_empty());
+
+    let update_reserves = &profile.functions["update_reserves"];
+    assert_eq!(update_reserves.call_count, 6);
+    assert_eq!(update_reserves.self_time, ms(4));
+    assert_eq!(update_reserves.parents, vec!["swap"]);
+    assert_eq!(profile.functions["read_storage"].total_time, ms(2));
+
+    let self_total: Duration = profile.functions.values().map(|f| f.self_time).sum();
+    assert_eq!(self_total, swap.total_time);
+}
+
+#[test]
+fn exporters_treat_sampled_profiles_like_instrumented_ones() {
+    let profile = sampled_swap();
+
+    assert_eq!(
+        folded_stacks(&profile),
+        "swap 3000000\n\
+         swap;emit_event 1000000\n\
+         swap;update_reserves 4000000\n\
+         swap;update_reserves;read_storage 2000000\n"
+    );
+
+    let svg = flame_graph_svg(&profile);
+    assert_eq!(svg.matches("<g class=\"frame\">").count(), 4);
+    assert!(svg.contains("<rect x=\"0.00\" y=\"20.00\" width=\"1200.00\""));
+}
+
+#[test]
+fn background_sampling_follows_the_stack() {
+    let mut sampler = Sampler::new(1_000);
+    sampler.enter("swap");
+    sampler.enter("update_reserves");
+    std::thread::sleep(ms(30));
+    sampler.exit();
+    sampler.exit();
+    let profile = sampler.finish("lib.rs".into(), None);
+
+    let update_reserves = &profile.functions["update_reserves"];
+    // Weighted by elapsed intervals, so late wake-ups do not lose time
+    assert!(update_reserves.call_count >= 20, "{update_reserves:?}");
+    assert_eq!(
+        update_reserves.total_time,
+        profile.sample_interval * update_reserves.call_count as u32
+    );
+    assert!(update_reserves.total_time <= profile.total_duration);
+    assert_eq!(
+        profile.functions["swap"].call_count,
+        update_reserves.call_count
+    );
+}
+
+#[test]
+fn profile_options_select_sampling() {
+    let dir = tempfile::tempdir().unwrap();
+    let contract = dir.path().join("lib.rs");
+    std::fs::write(&contract, "pub fn transfer() {}\nfn balance() {}\n").unwrap();
+
+    let options = ProfileOptions::builder(&contract)
+        .method("transfer")
+        .sampling(10_000)
+        .build();
+    assert_eq!(options.sample_rate_hz(), Some(10_000));
+
+    let profile = profile_contract(&options).unwrap();
+    assert_eq!(profile.mode, ProfileMode::Sampled);
+    assert_eq!(profile.sample_interval, Duration::from_micros(100));
+    assert_eq!(profile.overhead_per_call, Duration::ZERO);
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use soroban_profiler::{flame_graph_svg, folded_stacks, ProfileData, Profiler};
+
+fn us(micros: u64) -> Duration {
+    Duration::from_micros(micros)
+}
+
+/// swap (100µs)
+/// ├── update_reserves (80µs)
+/// │   └── read_storage (30µs)
+/// └── emit_event (5µs)
+///
+/// run twice, the second time with read_storage taking 40µs.
+fn nested_swap() -> ProfileData {
+    let mut profiler = Profiler::new();
+    for read_storage in [30, 40] {
+        profiler.enter_function("swap");
+        profiler.enter_function("update_reserves");
+        profiler.enter_function("read_storage");
+        profiler.exit_function("read_storage", us(read_storage));
+        profiler.exit_function("update_reserves", us(80));
+        profiler.enter_function("emit_event");
+        profiler.exit_function("emit_event", us(5));
+        profiler.exit_function("swap", us(100));
+    }
+    profiler.finish("contracts/pool/src/lib.rs".into(), Some("swap".into()))
+}
+
+#[test]
+fn self_time_excludes_nested_calls() {
+    let profile = nested_swap();
+
+    let swap = &profile.functions["swap"];
+    assert_eq!(swap.call_count, 2);
+    assert_eq!(swap.total_time, us(200));
+    assert_eq!(swap.self_time, us(2 * (100 - 80 - 5)));
+
+    let update_reserves = &profile.functions["update_reserves"];
+    assert_eq!(update_reserves.total_time, us(160));
+    assert_eq!(update_reserves.self_time, us((80 - 30) + (80 - 40)));
+
+    let read_storage = &profile.functions["read_storage"];
+    assert_eq!(read_storage.total_time, us(70));
+    assert_eq!(read_storage.self_time, read_storage.total_time);
+    assert_eq!(read_storage.min_time, us(30));
+    assert_eq!(read_storage.max_time, us(40));
+
+    let self_total: Duration = profile.functions.values().map(|f| f.self_time).sum();
+    assert_eq!(self_total, swap.total_time);
+}
+
+#[test]
+fn functions_link_to_their_callers_and_callees() {
+    let profile = nested_swap();
+
+    let swap = &profile.functions["swap"];
+    assert!(swap.parents.is_empty());
+    assert_eq!(swap.children, vec!["update_reserves", "emit_event"]);
+
+    let update_reserves = &profile.functions["update_reserves"];
+    assert_eq!(update_reserves.parents, vec!["swap"]);
+    assert_eq!(update_reserves.children, vec!["read_storage"]);
+    assert_eq!(
+        profile.functions["read_storage"].parents,
+        vec!["update_reserves"]
+    );
+}
+
+#[test]
+fn call_stack_records_each_call_nested() {
+    let profile = nested_swap();
+    assert_eq!(profile.call_stack.len(), 2);
+
+    let second = &profile.call_stack[1];
+    assert_eq!(second.function, "swap");
+    assert_eq!(second.duration(), us(100));
+    assert_eq!(second.self_time(), us(15));
+
+    let update_reserves = &second.children[0];
+    assert_eq!(update_reserves.function, "update_reserves");
+    assert_eq!(update_reserves.children[0].function, "read_storage");
+    assert_eq!(update_reserves.self_time(), us(40));
+    assert_eq!(second.children[1].function, "emit_event");
+}
+
+#[test]
+fn folded_stacks_follow_the_nesting() {
+    let folded = folded_stacks(&nested_swap());
+
+    assert_eq!(
+        folded,
+        "swap 30000\n\
+         swap;update_reserves 90000\n\
+         swap;update_reserves;read_storage 70000\n\
+         swap;emit_event 10000\n"
+    );
+}
+
+#[test]
+fn flame_graph_stacks_callees_under_their_callers() {
+    let svg = flame_graph_svg(&nested_swap());
+
+    // One row per nesting level, each frame drawn once per merged call path
+    assert_eq!(svg.matches("<g class=\"frame\">").count(), 4);
+    assert!(svg.contains("<rect x=\"0.00\" y=\"20.00\" width=\"1200.00\""));
+    assert!(svg.contains("<rect x=\"0.00\" y=\"42.00\" width=\"960.00\""));
+    assert!(svg.contains("<rect x=\"0.00\" y=\"64.00\" width=\"420.00\""));
+    assert!(svg.contains("<rect x=\"960.00\" y=\"42.00\" width=\"60.00\""));
+}
+
+#[test]
+fn measured_spans_nest_without_explicit_durations() {
+    let mut profiler = Profiler::new();
+    profiler.enter("swap");
+    profiler.enter("update_reserves");
+    std::thread::sleep(Duration::from_millis(2));
+    let inner = profiler.exit().expect("update_reserves is open");
+    let outer = profiler.exit().expect("swap is open");
+    assert!(profiler.exit().is_none());
+
+    let profile = profiler.finish("lib.rs".into(), None);
+    let swap = &profile.functions["swap"];
+    assert_eq!(swap.total_time, outer);
+    assert_eq!(swap.self_time, outer - inner);
+    assert_eq!(profile.functions["update_reserves"].self_time, inner);
+}
+
+#[test]
+fn calibrated_overhead_is_subtracted_from_measured_spans() {
+    let overhead = Duration::from_millis(1);
+    let mut profiler = Profiler::with_overhead(overhead);
+
+    let started = std::time::Instant::now();
+    profiler.enter("swap");
+    profiler.enter("update_reserves");
+    std::thread::sleep(Duration::from_millis(3));
+    let inner = profiler.exit().expect("update_reserves is open");
+    std::thread::sleep(Duration::from_millis(3));
+    let outer = profiler.exit().expect("swap is open");
+    let wall = started.elapsed();
+
+    // swap pays for its own hooks and for the nested call's
+    assert!(inner >= Duration::from_millis(2), "{inner:?}");
+    assert!(outer >= Duration::from_millis(4), "{outer:?}");
+    assert!(outer <= wall - 2 * overhead, "{outer:?} vs {wall:?}");
+
+    let profile = profiler.finish("lib.rs".into(), None);
+    assert_eq!(profile.overhead_per_call, overhead);
+    assert_eq!(profile.functions["swap"].self_time, outer - inner);
+    assert!(profile.overhead_percent > 0.0);
+}
+
+#[test]
+fn calls_within_twice_the_overhead_are_unreliable_and_floor_at_zero() {
+    let mut profiler = Profiler::with_overhead(Duration::from_secs(1));
+    profiler.enter("swap");
+    profiler.enter("emit_event");
+    assert_eq!(profiler.exit(), Some(Duration::ZERO));
+    profiler.exit();
+
+    // Explicit durations are taken as already free of overhead
+    profiler.enter_function("quote");
+    profiler.exit_function("quote", Duration::from_secs(3));
+
+    let profile = profiler.finish("lib.rs".into(), None);
+    assert_eq!(profile.functions["swap"].total_time, Duration::ZERO);
+    assert_eq!(
+        profile.functions["quote"].total_time,
+        Duration::from_secs(3)
+    );
+
+    let mut unreliable: Vec<_> = profile
+        .unreliable_functions()
+        .map(|function| function.name.as_str())
+        .collect();
+    unreliable.sort_unstable();
+    assert_eq!(unreliable, vec!["emit_event", "swap"]);
+}
+
+#[test]
+fn calibration_measures_a_positive_overhead() {
+    let overhead = Profiler::calibrate();
+    assert!(overhead > Duration::ZERO);
+    assert!(overhead < Duration::from_millis(1), "{overhead:?}");
+}
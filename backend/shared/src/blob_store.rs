@@ -0,0 +1,305 @@
+//! Object storage for uploaded artifacts (WASM modules and Rust sources).
+//!
+//! Postgres only keeps a blob's backend name, key, hash and size; the bytes
+//! live in a [`BlobStore`]. [`LocalBlobStore`] writes under a directory and
+//! [`S3BlobStore`] talks to any S3-compatible service, including GCS through
+//! its interoperability endpoint.
+
+use crate::error::RegistryError;
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio_util::io::ReaderStream;
+
+/// Contents of a blob, read in chunks
+pub type BlobStream = Pin<Box<dyn Stream<Item = Result<Bytes, io::Error>> + Send>>;
+
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    /// Name recorded in `contract_sources.storage_backend`
+    fn backend(&self) -> &'static str;
+
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), RegistryError>;
+
+    /// Copies a file on disk into the store without reading it into memory
+    async fn put_file(&self, key: &str, path: &Path) -> Result<(), RegistryError>;
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, RegistryError>;
+
+    async fn stream(&self, key: &str) -> Result<BlobStream, RegistryError>;
+
+    /// Bytes `start..=end` of the blob; `end` must be within it
+    async fn stream_range(
+        &self,
+        key: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<BlobStream, RegistryError>;
+
+    async fn delete(&self, key: &str) -> Result<(), RegistryError>;
+
+    /// A URL the blob can be fetched from directly until `expires_in` has
+    /// passed, or `None` when the store cannot hand those out.
+    async fn presigned_url(
+        &self,
+        _key: &str,
+        _expires_in: Duration,
+    ) -> Result<Option<String>, RegistryError> {
+        Ok(None)
+    }
+}
+
+/// Blobs as files under `root`
+pub struct LocalBlobStore {
+    root: PathBuf,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Keys are relative to the root. Keys recorded before that hold the
+    /// full path of the file, so those are used as they are.
+    fn path(&self, key: &str) -> Result<PathBuf, RegistryError> {
+        let path = Path::new(key);
+        if path.is_absolute() || path.starts_with(&self.root) {
+            return Ok(path.to_path_buf());
+        }
+        if path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(RegistryError::InvalidInput(format!(
+                "Invalid blob key {}",
+                key
+            )));
+        }
+        Ok(self.root.join(path))
+    }
+
+    async fn prepare(&self, key: &str) -> Result<PathBuf, RegistryError> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        Ok(path)
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    fn backend(&self) -> &'static str {
+        "local"
+    }
+
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), RegistryError> {
+        fs::write(self.prepare(key).await?, bytes).await?;
+        Ok(())
+    }
+
+    async fn put_file(&self, key: &str, path: &Path) -> Result<(), RegistryError> {
+        fs::copy(path, self.prepare(key).await?).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, RegistryError> {
+        match fs::read(self.path(key)?).await {
+            Ok(bytes) => Ok(bytes),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Err(RegistryError::NotFound(format!("blob {}", key)))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn stream(&self, key: &str) -> Result<BlobStream, RegistryError> {
+        match fs::File::open(self.path(key)?).await {
+            Ok(file) => Ok(Box::pin(ReaderStream::new(file))),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                Err(RegistryError::NotFound(format!("blob {}", key)))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn stream_range(
+        &self,
+        key: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<BlobStream, RegistryError> {
+        let mut file = match fs::File::open(self.path(key)?).await {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(RegistryError::NotFound(format!("blob {}", key)))
+            }
+            Err(err) => return Err(err.into()),
+        };
+        file.seek(io::SeekFrom::Start(start)).await?;
+        Ok(Box::pin(ReaderStream::new(file.take(end - start + 1))))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), RegistryError> {
+        match fs::remove_file(self.path(key)?).await {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Blobs as objects in an S3-compatible bucket
+pub struct S3BlobStore {
+    bucket: s3::Bucket,
+    backend: &'static str,
+}
+
+impl S3BlobStore {
+    /// `backend` is the name recorded for blobs stored here, `s3` or `gcs`
+    pub fn new(bucket: s3::Bucket, backend: &'static str) -> Self {
+        Self { bucket, backend }
+    }
+
+    fn error(&self, action: &str, err: s3::error::S3Error) -> RegistryError {
+        RegistryError::Internal(format!("{} {} failed: {}", self.backend, action, err))
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    fn backend(&self) -> &'static str {
+        self.backend
+    }
+
+    async fn put(&self, key: &str, bytes: &[u8]) -> Result<(), RegistryError> {
+        self.bucket
+            .put_object(key, bytes)
+            .await
+            .map_err(|err| self.error("put_object", err))?;
+        Ok(())
+    }
+
+    async fn put_file(&self, key: &str, path: &Path) -> Result<(), RegistryError> {
+        let mut file = fs::File::open(path).await?;
+        self.bucket
+            .put_object_stream(&mut file, key)
+            .await
+            .map_err(|err| self.error("put_object_stream", err))?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>, RegistryError> {
+        let data = self
+            .bucket
+            .get_object(key)
+            .await
+            .map_err(|err| self.error("get_object", err))?;
+        Ok(data.to_vec())
+    }
+
+    async fn stream(&self, key: &str) -> Result<BlobStream, RegistryError> {
+        let response = self
+            .bucket
+            .get_object_stream(key)
+            .await
+            .map_err(|err| self.error("get_object_stream", err))?;
+        Ok(Box::pin(response.bytes.map_err(io::Error::other)))
+    }
+
+    async fn stream_range(
+        &self,
+        key: &str,
+        start: u64,
+        end: u64,
+    ) -> Result<BlobStream, RegistryError> {
+        let data = self
+            .bucket
+            .get_object_range(key, start, Some(end))
+            .await
+            .map_err(|err| self.error("get_object_range", err))?;
+        let chunk: Result<Bytes, io::Error> = Ok(data.bytes().clone());
+        Ok(Box::pin(futures_util::stream::once(async move { chunk })))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), RegistryError> {
+        self.bucket
+            .delete_object(key)
+            .await
+            .map_err(|err| self.error("delete_object", err))?;
+        Ok(())
+    }
+
+    async fn presigned_url(
+        &self,
+        key: &str,
+        expires_in: Duration,
+    ) -> Result<Option<String>, RegistryError> {
+        let seconds = u32::try_from(expires_in.as_secs()).unwrap_or(u32::MAX);
+        let url = self
+            .bucket
+            .presign_get(key, seconds, None)
+            .await
+            .map_err(|err| self.error("presign_get", err))?;
+        Ok(Some(url))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn local_blobs_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalBlobStore::new(dir.path());
+
+        store.put("c/1.0.0/wasm/a.bin", b"\0asm").await.unwrap();
+        assert_eq!(store.get("c/1.0.0/wasm/a.bin").await.unwrap(), b"\0asm");
+        assert!(dir.path().join("c/1.0.0/wasm/a.bin").exists());
+
+        let chunks: Vec<Bytes> = store
+            .stream("c/1.0.0/wasm/a.bin")
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(chunks.concat(), b"\0asm");
+
+        let range: Vec<Bytes> = store
+            .stream_range("c/1.0.0/wasm/a.bin", 1, 2)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(range.concat(), b"as");
+
+        store.delete("c/1.0.0/wasm/a.bin").await.unwrap();
+        assert!(matches!(
+            store.get("c/1.0.0/wasm/a.bin").await,
+            Err(RegistryError::NotFound(_))
+        ));
+        store.delete("c/1.0.0/wasm/a.bin").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn local_keys_stay_under_the_root() {
+        let dir = TempDir::new().unwrap();
+        let store = LocalBlobStore::new(dir.path().join("blobs"));
+        assert!(store.put("../escaped.bin", b"x").await.is_err());
+        assert!(!dir.path().join("escaped.bin").exists());
+
+        // Keys written before they were relative to the root
+        let legacy = dir.path().join("blobs/legacy.bin");
+        store.put("legacy.bin", b"old").await.unwrap();
+        assert_eq!(store.get(legacy.to_str().unwrap()).await.unwrap(), b"old");
+    }
+}
@@ -1,8 +1,11 @@
 pub mod abi;
+pub mod blob_store;
 pub mod error;
 pub mod models;
 pub mod pagination;
+pub mod search;
 pub mod semver;
+pub mod slug;
 pub mod source_storage;
 pub mod upgrade;
 
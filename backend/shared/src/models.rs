@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+pub use crate::source_storage::SourceFormat;
+
 // ═══════════════════════════════════════════════════════════════════════════
 // EXISTING REGISTRY TYPES
 // ═══════════════════════════════════════════════════════════════════════════
@@ -56,6 +58,30 @@ pub struct Contract {
     pub organization_id: Option<Uuid>,
     /// Visibility level
     pub visibility: VisibilityType,
+    /// Owning organization's slug or the publisher's username; the first
+    /// half of the contract's `namespace/slug` name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub namespace: Option<String>,
+    /// URL-safe name, unique within `namespace` on each network
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub slug: Option<String>,
+    /// Whether the contract is listed or held by publish screening
+    #[serde(default)]
+    #[sqlx(default)]
+    pub publish_status: PublishStatus,
+    /// Counts edits made through the API; sent as the `ETag` that edits
+    /// take back in `If-Match`
+    #[serde(default)]
+    #[sqlx(default)]
+    pub revision: i64,
+    /// How long ago the contract last showed signs of life, and whether the
+    /// freshness sweep has marked it stale
+    #[serde(default)]
+    #[sqlx(flatten)]
+    #[schema(value_type = FreshnessView)]
+    pub freshness: Freshness,
 }
 
 #[derive(
@@ -69,6 +95,90 @@ pub enum VisibilityType {
     Private,
 }
 
+/// Outcome of publish screening. Only `published` contracts appear in
+/// listings and search; the others wait for (or failed) admin review.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema, PartialEq, Eq, Default,
+)]
+#[sqlx(type_name = "contract_publish_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PublishStatus {
+    #[default]
+    Published,
+    PendingReview,
+    Rejected,
+}
+
+/// The timestamps a contract's freshness is computed from. A new version,
+/// a metadata edit or a re-confirmation by its publisher clears `stale_at`;
+/// the freshness sweep sets it once all three signals have gone quiet.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow)]
+#[serde(into = "FreshnessView", from = "FreshnessView")]
+pub struct Freshness {
+    /// When the newest version was published
+    #[sqlx(default)]
+    pub last_version_at: Option<DateTime<Utc>>,
+    /// When on-chain verification last found the contract with its hash
+    #[sqlx(default)]
+    pub last_seen_on_chain_at: Option<DateTime<Utc>>,
+    /// When the publisher last published, edited or re-confirmed it
+    #[sqlx(default)]
+    pub publisher_active_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    pub stale_at: Option<DateTime<Utc>>,
+}
+
+impl Freshness {
+    pub fn is_stale(&self) -> bool {
+        self.stale_at.is_some()
+    }
+}
+
+/// `freshness` as it appears in contract responses
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct FreshnessView {
+    /// `null` when the contract has no versions
+    pub days_since_version: Option<i64>,
+    /// `null` when it was never confirmed on chain
+    pub days_since_onchain_confirmation: Option<i64>,
+    pub days_since_publisher_activity: Option<i64>,
+    /// Marked stale by the freshness sweep; demoted in search
+    pub stale: bool,
+    pub last_version_at: Option<DateTime<Utc>>,
+    pub last_onchain_confirmation_at: Option<DateTime<Utc>>,
+    pub last_publisher_activity_at: Option<DateTime<Utc>>,
+    pub stale_since: Option<DateTime<Utc>>,
+}
+
+impl From<Freshness> for FreshnessView {
+    fn from(freshness: Freshness) -> Self {
+        let now = Utc::now();
+        let days = |at: Option<DateTime<Utc>>| at.map(|at| (now - at).num_days().max(0));
+        Self {
+            days_since_version: days(freshness.last_version_at),
+            days_since_onchain_confirmation: days(freshness.last_seen_on_chain_at),
+            days_since_publisher_activity: days(freshness.publisher_active_at),
+            stale: freshness.stale_at.is_some(),
+            last_version_at: freshness.last_version_at,
+            last_onchain_confirmation_at: freshness.last_seen_on_chain_at,
+            last_publisher_activity_at: freshness.publisher_active_at,
+            stale_since: freshness.stale_at,
+        }
+    }
+}
+
+impl From<FreshnessView> for Freshness {
+    fn from(view: FreshnessView) -> Self {
+        Self {
+            last_version_at: view.last_version_at,
+            last_seen_on_chain_at: view.last_onchain_confirmation_at,
+            publisher_active_at: view.last_publisher_activity_at,
+            stale_at: view.stale_since,
+        }
+    }
+}
+
 /// Response for GET /contracts/:id with optional network-specific slice (Issue #43)
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ContractGetResponse {
@@ -80,6 +190,100 @@ pub struct ContractGetResponse {
     /// When ?network= is set, that network's config slice
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network_config: Option<NetworkConfig>,
+    /// Operational history; absent on deployments without incident tracking
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub incident_summary: Option<ContractIncidentSummary>,
+    /// Average rating and star histogram of approved reviews
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ratings: Option<ContractRatingStats>,
+    /// Unresolved advisories against the toolchain the contract was built with
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security_notices: Vec<SecurityNotice>,
+    /// Per-method costs from the official profile of the newest profiled
+    /// version; absent until a verification has profiled one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub performance: Option<ContractProfileSummary>,
+    /// The newest profile the publisher uploaded from the CLI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_profile: Option<UploadedProfileSummary>,
+    /// Markdown README set by the publisher
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    /// Whether search indexes the README; absent while the database is
+    /// impaired
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_readme: Option<bool>,
+}
+
+/// The official performance profile of a verified contract version, summed
+/// up per method. Recorded when verification succeeds and replaced when the
+/// version is verified again.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ContractProfileSummary {
+    pub version: String,
+    /// How the costs were measured: `budgeted` estimates them from the
+    /// verified WASM bytecode
+    pub engine: String,
+    pub methods: Vec<MethodCost>,
+    pub profiled_at: DateTime<Utc>,
+}
+
+/// What one invocation of an exported method costs
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MethodCost {
+    pub method: String,
+    pub cpu_instructions: u64,
+    pub memory_bytes: u64,
+}
+
+/// A profile the publisher ran locally and uploaded with
+/// `soroban-registry profile --publish-to`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct UploadedProfileSummary {
+    pub id: Uuid,
+    pub method: String,
+    /// How the profile was recorded: `instrumented`, `sampled`, `simulated`
+    /// (one invocation simulated through Soroban RPC) or `budgeted`
+    pub engine: String,
+    /// Whether the numbers come from a simulation rather than a run
+    pub simulated: bool,
+    pub cli_version: String,
+    /// The profile in one line, e.g. `transfer: 1.25 ms over 4 functions
+    /// (instrumented, CLI 0.4.2)`
+    pub summary: String,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// An unresolved advisory against the soroban-sdk or rustc version that
+/// built the newest of a contract's versions with WASM metadata
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct SecurityNotice {
+    pub advisory_id: Uuid,
+    pub title: String,
+    /// low, medium, high or critical
+    pub severity: String,
+    /// `soroban-sdk` or `rustc`
+    pub component: String,
+    /// The affected version the contract was built with
+    pub matched_version: String,
+    /// Contract version whose WASM matched
+    pub contract_version: String,
+    pub url: Option<String>,
+    /// When the contract was found to be affected
+    pub created_at: DateTime<Utc>,
+}
+
+/// Incidents that affected a contract, excluding deleted ones
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct ContractIncidentSummary {
+    pub incident_count: i64,
+    /// Start of the most recent incident
+    pub last_incident_at: Option<DateTime<Utc>>,
+    /// Severity of the most recent incident: critical, major, minor or informational
+    pub last_incident_severity: Option<String>,
+    /// Whether any incident affecting the contract is still open
+    pub has_open_incident: bool,
+    pub has_open_critical_incident: bool,
 }
 
 /// Per-network config: address, verified status, min/max version (Issue #43)
@@ -177,7 +381,8 @@ pub struct ContractVersion {
     pub created_at: DateTime<Utc>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub state_schema: Option<serde_json::Value>,
-    /// Optional Ed25519 signature over "{contract_id}:{version}:{wasm_hash}"
+    /// Optional Ed25519 signature over
+    /// "{contract_id}:{version}:{wasm_hash}:{signed_at}:{nonce}"
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub signature: Option<String>,
     /// Publisher's public key corresponding to the signature (base64-encoded ed25519 key)
@@ -186,6 +391,33 @@ pub struct ContractVersion {
     /// Signature algorithm identifier (e.g. "ed25519")
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub signature_algorithm: Option<String>,
+    /// When the publisher signed this version, by their clock
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_at: Option<DateTime<Utc>>,
+    /// Random value the publisher signed along with `signed_at`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_nonce: Option<String>,
+    /// Metadata embedded in the uploaded WASM's `contractmetav0` sections:
+    /// `sdk_version`, `rustc_version`, `authors`, `repository` and all raw
+    /// `entries`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wasm_meta: Option<serde_json::Value>,
+}
+
+/// Where an uploaded artifact of a contract version is stored; the bytes
+/// themselves live in [`crate::blob_store`]
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct ContractSource {
+    pub id: Uuid,
+    pub contract_version_id: Uuid,
+    pub source_format: SourceFormat,
+    /// `local`, `s3` or `gcs`
+    pub storage_backend: String,
+    pub storage_key: String,
+    /// Hex SHA-256 of the artifact
+    pub source_hash: String,
+    pub source_size: i64,
+    pub created_at: DateTime<Utc>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -196,7 +428,11 @@ pub struct ContractVersion {
 #[sqlx(type_name = "organization_role", rename_all = "lowercase")]
 #[serde(rename_all = "lowercase")]
 pub enum OrganizationRole {
+    /// Owns the organization: manages members and settings
+    #[serde(alias = "owner")]
     Admin,
+    /// Maintains the organization's contracts: publishes, updates, uploads
+    #[serde(alias = "maintainer")]
     Member,
     Viewer,
 }
@@ -249,6 +485,13 @@ pub struct InviteMemberRequest {
     pub role: OrganizationRole,
 }
 
+/// Adds a publisher address to an organization directly, without an invitation
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct AddMemberRequest {
+    pub address: String,
+    pub role: OrganizationRole,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct UpdateOrganizationRequest {
     pub name: Option<String>,
@@ -381,6 +624,14 @@ pub struct PublishRequest {
     /// Whether this was published via CI/CD (Issue #529)
     #[serde(default)]
     pub is_cicd: bool,
+    /// Slug or id of the organization that will own the contract; the caller
+    /// must be one of its maintainers
+    #[serde(default)]
+    pub organization: Option<String>,
+    /// URL-safe name, unique within the organization or publisher; derived
+    /// from `name` when omitted
+    #[serde(default)]
+    pub slug: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -389,6 +640,15 @@ pub struct UpdateContractMetadataRequest {
     pub description: Option<String>,
     pub category: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// New slug; the old one keeps resolving to this contract
+    #[serde(default)]
+    pub slug: Option<String>,
+    /// Markdown README; an empty string removes it
+    #[serde(default)]
+    pub readme: Option<String>,
+    /// Whether search indexes the README (on by default)
+    #[serde(default)]
+    pub index_readme: Option<bool>,
     pub user_id: Option<Uuid>,
 }
 
@@ -422,6 +682,18 @@ pub struct CreateContractVersionRequest {
     pub publisher_key: Option<String>,
     #[serde(default)]
     pub signature_algorithm: Option<String>,
+    /// Unix seconds at which the signature was made; required with a
+    /// signature, which is accepted only for a few minutes after it
+    #[serde(default)]
+    pub signed_at: Option<i64>,
+    /// Random value, 16 to 128 characters of `[A-Za-z0-9_-]`, that makes each
+    /// signature single-use; required with a signature
+    #[serde(default)]
+    pub nonce: Option<String>,
+    /// Create the version even though its interface breaks callers of the
+    /// previous one without a major version bump
+    #[serde(default)]
+    pub allow_breaking: bool,
 }
 
 // ────────────────────────────────────────────────────────────────────────────
@@ -522,6 +794,52 @@ pub struct VerifyRequest {
     pub source_code: String,
     pub build_params: serde_json::Value,
     pub compiler_version: String,
+    /// Contents of the `Cargo.lock` to build with. Pins dependency versions,
+    /// is hashed into the build metadata and is turned into an SBOM.
+    #[serde(default)]
+    pub cargo_lock: Option<String>,
+    /// Repository the source was taken from
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// Commit the source was taken from. Together with `source_url`, the
+    /// submitted `cargo_lock` must match the repository's at this commit.
+    #[serde(default)]
+    pub commit_hash: Option<String>,
+}
+
+/// How a verified contract was built, captured during verification
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct BuildMetadata {
+    /// `rustc -V` of the toolchain that built the contract; `None` when a
+    /// prebuilt WASM was submitted
+    pub rustc_version: Option<String>,
+    pub soroban_sdk_version: String,
+    pub cargo_features: Vec<String>,
+    pub build_profile: String,
+    pub target: String,
+    /// Arguments passed to `cargo`
+    pub build_flags: Vec<String>,
+    /// `RUSTFLAGS` in effect for the build
+    pub rustflags: Option<String>,
+    /// SHA-256 of the `Cargo.lock` the build used
+    pub lockfile_sha256: Option<String>,
+}
+
+/// Response of `GET /api/contracts/{id}/build-info`: the build metadata of
+/// the contract's latest successful verification
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow, utoipa::ToSchema)]
+pub struct ContractBuildInfo {
+    pub contract_id: Uuid,
+    pub verification_id: Uuid,
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub build: BuildMetadata,
+    /// Repository and commit the lockfile was checked against
+    pub source_url: Option<String>,
+    pub commit_hash: Option<String>,
+    /// Whether `GET /api/contracts/{id}/sbom` has an SBOM for this build
+    pub has_sbom: bool,
+    pub verified_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -570,7 +888,10 @@ pub enum SortOrder {
 /// Search/filter parameters for contracts
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema, utoipa::IntoParams)]
 pub struct ContractSearchParams {
+    /// Ranks results by relevance; with `matches_only` also filters them
     pub query: Option<String>,
+    /// Only contracts matching `query` by name, tag or searchable text
+    pub matches_only: Option<bool>,
     pub network: Option<Network>,
     /// Multiple networks filter (e.g. ?networks=mainnet&networks=testnet)
     pub networks: Option<Vec<Network>>,
@@ -579,6 +900,15 @@ pub struct ContractSearchParams {
     /// Multiple categories filter (e.g. ?categories=DeFi&categories=NFT)
     pub categories: Option<Vec<String>>,
     pub tags: Option<Vec<String>>,
+    /// Only contracts published by this Stellar address
+    pub publisher_address: Option<String>,
+    /// Only contracts owned by this organization (slug or UUID)
+    pub organization: Option<String>,
+    /// Only contracts with a version whose WASM embeds this `contractmetav0`
+    /// key (e.g. `rssdkver`)
+    pub meta_key: Option<String>,
+    /// With `meta_key`, only values starting with this (e.g. `20.0.0`)
+    pub meta_value: Option<String>,
     pub maturity: Option<MaturityLevel>,
     pub page: Option<i64>,
     #[serde(alias = "page_size")]
@@ -1515,6 +1845,10 @@ pub enum AuditActionType {
     PublisherChanged,
     VersionCreated,
     Rollback,
+    PublishApproved,
+    PublishRejected,
+    LegalHoldSet,
+    LegalHoldReleased,
 }
 
 impl std::fmt::Display for AuditActionType {
@@ -1526,6 +1860,10 @@ impl std::fmt::Display for AuditActionType {
             Self::PublisherChanged => "publisher_changed",
             Self::VersionCreated => "version_created",
             Self::Rollback => "rollback",
+            Self::PublishApproved => "publish_approved",
+            Self::PublishRejected => "publish_rejected",
+            Self::LegalHoldSet => "legal_hold_set",
+            Self::LegalHoldReleased => "legal_hold_released",
         };
         write!(f, "{}", s)
     }
@@ -2369,6 +2707,16 @@ pub struct ContractChangelogEntry {
     pub source_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub release_notes: Option<String>,
+    pub wasm_hash: String,
+    /// Whether the WASM hash differs from the previous version's; false for
+    /// the first version
+    pub wasm_changed: bool,
+    /// Size in bytes of the uploaded WASM, if one was uploaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wasm_size: Option<i64>,
+    /// `wasm_size` minus the previous version's, when both were uploaded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size_delta: Option<i64>,
     pub breaking: bool,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub breaking_changes: Vec<String>,
@@ -2431,18 +2779,23 @@ pub enum ReviewStatus {
     Rejected,
 }
 
-/// Sort options for fetching reviews
-#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+/// Sort options for fetching reviews. The older names (`most_recent`,
+/// `highest_rated`, `lowest_rated`) are still accepted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ReviewSortBy {
-    /// Sort by helpful votes (highest first)
-    MostHelpful,
     /// Sort by creation date (newest first)
-    MostRecent,
+    #[default]
+    #[serde(alias = "most_recent")]
+    Newest,
     /// Sort by rating (highest first)
-    HighestRated,
+    #[serde(alias = "highest_rated")]
+    Highest,
     /// Sort by rating (lowest first)
-    LowestRated,
+    #[serde(alias = "lowest_rated")]
+    Lowest,
+    /// Sort by helpful votes (most first)
+    MostHelpful,
 }
 
 /// Request to create a new contract review
@@ -2484,7 +2837,7 @@ pub struct ReviewResponse {
 }
 
 /// Aggregated rating statistics for a contract
-#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema)]
 pub struct ContractRatingStats {
     /// Average rating (0.0 if no reviews)
     #[schema(example = 4.3)]
@@ -2492,13 +2845,17 @@ pub struct ContractRatingStats {
     /// Total number of approved reviews
     #[schema(example = 42)]
     pub total_reviews: i64,
-    /// Distribution of ratings (1-5 stars)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub rating_distribution: Option<RatingDistribution>,
+    /// Number of approved reviews per star (1-5), all zero without reviews
+    #[serde(default)]
+    #[sqlx(flatten)]
+    pub rating_distribution: RatingDistribution,
 }
 
-/// Distribution of ratings by star count
-#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+/// Distribution of ratings by star count. A rating counts towards its whole
+/// stars, so 4.5 is a 4-star review.
+#[derive(
+    Debug, Clone, Default, PartialEq, Serialize, Deserialize, sqlx::FromRow, utoipa::ToSchema,
+)]
 pub struct RatingDistribution {
     /// Number of 1-star reviews
     #[schema(example = 2)]
@@ -2520,7 +2877,7 @@ pub struct RatingDistribution {
 /// Request to vote on review helpfulness
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ReviewVoteRequest {
-    /// true = helpful, false = unhelpful
+    /// true records a helpful vote, false withdraws it
     pub helpful: bool,
 }
 
@@ -2540,26 +2897,16 @@ pub struct ModerateReviewRequest {
     pub action: String,
 }
 
-/// Query parameters for GET /contracts/:id/reviews
-#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+/// Query parameters for GET /contracts/:id/reviews, next to `limit`/`offset`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct GetReviewsQuery {
-    /// Sort order: most_helpful, most_recent, highest_rated, lowest_rated
-    #[serde(default = "default_review_sort")]
-    pub sort_by: ReviewSortBy,
-    /// Maximum number of reviews to return (default: 20, max: 100)
-    #[serde(default = "default_review_limit")]
-    pub limit: i64,
-    /// Offset for pagination
-    #[serde(default)]
-    pub offset: i64,
-}
-
-fn default_review_sort() -> ReviewSortBy {
-    ReviewSortBy::MostRecent
-}
-
-fn default_review_limit() -> i64 {
-    20
+    /// Sort order: newest (default), highest, lowest or most_helpful;
+    /// `sort_by` is accepted as an alias
+    #[serde(default, alias = "sort_by")]
+    pub sort: ReviewSortBy,
+    /// Only reviews with this many whole stars (1-5)
+    pub rating: Option<i32>,
 }
 
 /// Response for review voting endpoint
@@ -2569,3 +2916,12 @@ pub struct ReviewVoteResponse {
     pub helpful_count: i32,
     pub vote_recorded: bool,
 }
+
+/// Response for POST /contracts/:id/reviews/:review_id/helpful
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ReviewHelpfulResponse {
+    pub review_id: i32,
+    pub helpful_count: i32,
+    /// Whether the caller's helpful vote is now counted
+    pub voted: bool,
+}
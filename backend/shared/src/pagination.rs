@@ -42,6 +42,54 @@ pub trait CursorProvider {
     fn get_cursor(&self) -> Cursor;
 }
 
+/// Envelope returned by every list endpoint.
+///
+/// `offset` is the position of the first item in the full result set.
+/// Cursor-paginated listings also set `next_cursor`, which is omitted on the
+/// last page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Paginated<T> {
+    pub items: Vec<T>,
+    /// Total matching items across all pages
+    pub total: i64,
+    pub limit: i64,
+    pub offset: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    pub has_more: bool,
+}
+
+impl<T> Paginated<T> {
+    pub fn new(items: Vec<T>, total: i64, limit: i64, offset: i64) -> Self {
+        let has_more = offset + (items.len() as i64) < total;
+        Self {
+            items,
+            total,
+            limit,
+            offset,
+            next_cursor: None,
+            has_more,
+        }
+    }
+
+    /// Offset to request for the next page, if there is one.
+    pub fn next_offset(&self) -> Option<i64> {
+        self.has_more.then(|| self.offset + self.items.len() as i64)
+    }
+
+    /// Converts every item, keeping the page position and cursor.
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Paginated<U> {
+        Paginated {
+            items: self.items.into_iter().map(f).collect(),
+            total: self.total,
+            limit: self.limit,
+            offset: self.offset,
+            next_cursor: self.next_cursor,
+            has_more: self.has_more,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,6 +111,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn paginated_round_trips_through_json() {
+        let page = Paginated::new(vec!["a".to_string(), "b".to_string()], 5, 2, 2);
+        let json = serde_json::to_value(&page).unwrap();
+
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "items": ["a", "b"],
+                "total": 5,
+                "limit": 2,
+                "offset": 2,
+                "has_more": true,
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<Paginated<String>>(json).unwrap(),
+            page
+        );
+    }
+
+    #[test]
+    fn paginated_keeps_cursor_when_set() {
+        let mut page = Paginated::new(vec![1, 2], 10, 2, 0);
+        page.next_cursor = Some("abc".to_string());
+
+        let json = serde_json::to_string(&page).unwrap();
+        let decoded: Paginated<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.next_cursor.as_deref(), Some("abc"));
+        assert_eq!(decoded, page);
+    }
+
+    #[test]
+    fn paginated_reports_more_results() {
+        assert!(!Paginated::new(vec![0; 0], 45, 20, 45).has_more);
+        assert!(!Paginated::new(vec![0; 5], 45, 20, 40).has_more);
+
+        let page = Paginated::new(vec![0; 20], 45, 20, 20);
+        assert!(page.has_more);
+        assert_eq!(page.next_offset(), Some(40));
+    }
+
     #[test]
     fn test_invalid_cursor() {
         assert!(Cursor::decode("notbase64").is_err());
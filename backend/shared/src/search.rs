@@ -0,0 +1,366 @@
+//! Showing where a search query matched a contract's description.
+//!
+//! Fragments are plain text: control characters (terminal escapes included)
+//! are replaced and nothing is HTML-escaped, so clients must escape them
+//! before embedding them in markup. Matched terms are character ranges into
+//! the fragment rather than inline markers.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::Contract;
+
+/// Longest fragment returned, in characters, ellipses included
+pub const MAX_FRAGMENT_CHARS: usize = 160;
+/// Characters of context kept before the first match
+const CONTEXT_CHARS: usize = 40;
+const ELLIPSIS: char = '…';
+
+/// Markers `ts_headline` is asked to put around matches. Control characters
+/// never survive [`plain_text`], so they cannot be confused with content.
+pub const HEADLINE_START: char = '\u{2}';
+pub const HEADLINE_STOP: char = '\u{3}';
+
+/// A search result with the fragments that explain why it matched.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ContractSearchHit {
+    #[serde(flatten)]
+    pub contract: Contract,
+    /// Empty, and left out, when only the name or tags matched
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub highlights: Vec<SearchHighlight>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SearchHighlight {
+    /// Contract field the fragment was taken from
+    pub field: String,
+    /// Plain text, at most [`MAX_FRAGMENT_CHARS`] characters
+    pub fragment: String,
+    /// Matched terms, in order and not overlapping
+    pub matches: Vec<HighlightRange>,
+}
+
+/// Characters `start..end` of a fragment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct HighlightRange {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Options for `ts_headline` that produce output [`parse_headline`] reads.
+pub fn headline_options() -> String {
+    format!(
+        "StartSel={}, StopSel={}, MaxWords=24, MinWords=8, MaxFragments=2, FragmentDelimiter=\" {} \"",
+        HEADLINE_START, HEADLINE_STOP, ELLIPSIS
+    )
+}
+
+/// `text` with control characters turned into spaces and runs of
+/// whitespace collapsed, safe to print to a terminal.
+pub fn plain_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        if c.is_whitespace() || c.is_control() {
+            if !out.is_empty() && !out.ends_with(' ') {
+                out.push(' ');
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out
+}
+
+/// Finds the words of `query` in `text` case-insensitively, like the
+/// `ILIKE` search does, and keeps a fragment around the first match.
+/// `None` when no word of the query occurs in `text`.
+pub fn highlight_terms(field: &str, text: &str, query: &str) -> Option<SearchHighlight> {
+    let chars: Vec<char> = plain_text(text).chars().collect();
+    let lowered: Vec<char> = chars.iter().map(|&c| fold(c)).collect();
+
+    let mut terms: Vec<Vec<char>> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| term.chars().count() >= 2)
+        .filter(|term| !matches!(term.to_lowercase().as_str(), "and" | "or" | "not"))
+        .map(|term| term.chars().map(fold).collect())
+        .collect();
+    // Longer terms first, so "tokens" wins over "token" at the same position
+    terms.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    terms.dedup();
+
+    let mut ranges = Vec::new();
+    let mut position = 0;
+    while position < lowered.len() {
+        match terms
+            .iter()
+            .find(|term| lowered[position..].starts_with(term))
+        {
+            Some(term) => {
+                ranges.push((position, position + term.len()));
+                position += term.len();
+            }
+            None => position += 1,
+        }
+    }
+
+    excerpt(field, &chars, &ranges)
+}
+
+/// Reads `ts_headline` output made with [`headline_options`]. `None` when it
+/// marked nothing, i.e. the field did not match.
+pub fn parse_headline(field: &str, marked: &str) -> Option<SearchHighlight> {
+    let mut chars = Vec::new();
+    let mut ranges = Vec::new();
+    let mut open = None;
+    for c in marked.chars() {
+        match c {
+            HEADLINE_START => open = Some(chars.len()),
+            HEADLINE_STOP => {
+                if let Some(start) = open.take() {
+                    if start < chars.len() {
+                        ranges.push((start, chars.len()));
+                    }
+                }
+            }
+            c if c.is_whitespace() || c.is_control() => {
+                if chars.last().is_some_and(|&last| last != ' ') {
+                    chars.push(' ');
+                }
+            }
+            c => chars.push(c),
+        }
+    }
+    while chars.last() == Some(&' ') {
+        chars.pop();
+    }
+    ranges.retain(|&(_, end)| end <= chars.len());
+
+    excerpt(field, &chars, &ranges)
+}
+
+/// Cuts `chars` down to [`MAX_FRAGMENT_CHARS`] around the first of
+/// `ranges`, at word boundaries where possible, and shifts the ranges to
+/// match.
+fn excerpt(field: &str, chars: &[char], ranges: &[(usize, usize)]) -> Option<SearchHighlight> {
+    let &(first_start, _) = ranges.first()?;
+
+    let (mut start, mut end) = (0, chars.len());
+    if chars.len() > MAX_FRAGMENT_CHARS {
+        // Room for an ellipsis on both sides
+        let budget = MAX_FRAGMENT_CHARS - 2;
+        start = first_start.saturating_sub(CONTEXT_CHARS);
+        if let Some(space) = chars[start..first_start].iter().position(|&c| c == ' ') {
+            start += space + 1;
+        }
+        start = start.min(chars.len().saturating_sub(budget));
+        end = (start + budget).min(chars.len());
+        if end < chars.len() {
+            if let Some(space) = chars[start..end].iter().rposition(|&c| c == ' ') {
+                if space > first_start - start {
+                    end = start + space;
+                }
+            }
+        }
+    }
+
+    let prefix = usize::from(start > 0);
+    let mut fragment = String::new();
+    if start > 0 {
+        fragment.push(ELLIPSIS);
+    }
+    fragment.extend(&chars[start..end]);
+    if end < chars.len() {
+        fragment.push(ELLIPSIS);
+    }
+
+    let matches = ranges
+        .iter()
+        .filter(|&&(range_start, _)| range_start >= start && range_start < end)
+        .map(|&(range_start, range_end)| HighlightRange {
+            start: range_start - start + prefix,
+            end: range_end.min(end) - start + prefix,
+        })
+        .collect();
+
+    Some(SearchHighlight {
+        field: field.to_string(),
+        fragment,
+        matches,
+    })
+}
+
+/// Case folding that keeps one character per character, so positions in
+/// the folded text are positions in the original
+fn fold(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+impl SearchHighlight {
+    /// The fragment split into runs, each flagged with whether it is a
+    /// match. Ranges that are out of order, overlap or fall outside the
+    /// fragment are ignored, so this is safe on highlights from elsewhere.
+    pub fn segments(&self) -> Vec<(String, bool)> {
+        let chars: Vec<char> = self.fragment.chars().collect();
+        let mut segments = Vec::new();
+        let mut position = 0;
+        for range in &self.matches {
+            if range.start < position || range.start >= range.end || range.end > chars.len() {
+                continue;
+            }
+            if range.start > position {
+                segments.push((chars[position..range.start].iter().collect(), false));
+            }
+            segments.push((chars[range.start..range.end].iter().collect(), true));
+            position = range.end;
+        }
+        if position < chars.len() {
+            segments.push((chars[position..].iter().collect(), false));
+        }
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matched(highlight: &SearchHighlight) -> Vec<String> {
+        highlight
+            .segments()
+            .into_iter()
+            .filter(|(_, is_match)| *is_match)
+            .map(|(text, _)| text)
+            .collect()
+    }
+
+    #[test]
+    fn terms_match_case_insensitively_anywhere() {
+        let highlight = highlight_terms(
+            "description",
+            "Swaps Tokens between pools; each token swap pays a fee.",
+            "token swap",
+        )
+        .unwrap();
+
+        assert_eq!(
+            highlight.fragment,
+            "Swaps Tokens between pools; each token swap pays a fee."
+        );
+        assert_eq!(matched(&highlight), ["Swap", "Token", "token", "swap"]);
+    }
+
+    #[test]
+    fn no_highlight_without_a_match_in_the_field() {
+        assert_eq!(
+            highlight_terms("description", "A lending market", "oracle"),
+            None
+        );
+        assert_eq!(highlight_terms("description", "Anything", "a"), None);
+        assert_eq!(parse_headline("description", "A lending market"), None);
+    }
+
+    #[test]
+    fn long_text_is_cut_around_the_first_match() {
+        let text = format!(
+            "{} the oracle feeds prices {}",
+            "lorem ".repeat(60),
+            "ipsum ".repeat(60)
+        );
+        let highlight = highlight_terms("description", &text, "oracle").unwrap();
+
+        assert!(highlight.fragment.chars().count() <= MAX_FRAGMENT_CHARS);
+        assert!(highlight.fragment.starts_with('…'));
+        assert!(highlight.fragment.ends_with('…'));
+        assert_eq!(matched(&highlight), ["oracle"]);
+    }
+
+    #[test]
+    fn control_characters_are_removed() {
+        let highlight = highlight_terms(
+            "description",
+            "Vault\x1b[31m with\n\n\ttimelock\u{7}",
+            "timelock",
+        )
+        .unwrap();
+
+        assert_eq!(highlight.fragment, "Vault [31m with timelock");
+        assert!(!highlight.fragment.chars().any(char::is_control));
+        assert_eq!(matched(&highlight), ["timelock"]);
+    }
+
+    #[test]
+    fn headline_markers_become_ranges() {
+        let marked = format!(
+            "an automated {s}market{e} maker for {s}stable{e} pairs",
+            s = HEADLINE_START,
+            e = HEADLINE_STOP
+        );
+        let highlight = parse_headline("description", &marked).unwrap();
+
+        assert_eq!(
+            highlight.fragment,
+            "an automated market maker for stable pairs"
+        );
+        assert_eq!(
+            highlight.matches,
+            [
+                HighlightRange { start: 13, end: 19 },
+                HighlightRange { start: 30, end: 36 }
+            ]
+        );
+    }
+
+    #[test]
+    fn segments_ignore_ranges_that_do_not_fit() {
+        let highlight = SearchHighlight {
+            field: "description".to_string(),
+            fragment: "abcdef".to_string(),
+            matches: vec![
+                HighlightRange { start: 1, end: 3 },
+                HighlightRange { start: 2, end: 4 },
+                HighlightRange { start: 4, end: 99 },
+            ],
+        };
+
+        assert_eq!(
+            highlight.segments(),
+            [
+                ("a".to_string(), false),
+                ("bc".to_string(), true),
+                ("def".to_string(), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn hits_without_highlights_serialize_without_the_field() {
+        let contract: Contract = serde_json::from_value(serde_json::json!({
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "contract_id": "CABC",
+            "wasm_hash": "00",
+            "name": "Pool",
+            "description": null,
+            "publisher_id": "550e8400-e29b-41d4-a716-446655440001",
+            "network": "testnet",
+            "is_verified": false,
+            "category": null,
+            "tags": [],
+            "created_at": "2026-01-01T00:00:00Z",
+            "updated_at": "2026-01-01T00:00:00Z",
+            "verified_at": null,
+            "last_accessed_at": null,
+            "organization_id": null,
+            "visibility": "public"
+        }))
+        .unwrap();
+        let hit = ContractSearchHit {
+            contract,
+            highlights: Vec::new(),
+        };
+
+        let json = serde_json::to_value(&hit).unwrap();
+        assert_eq!(json["name"], "Pool");
+        assert!(json.get("highlights").is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! Human-friendly contract names: `namespace/slug`, optionally pinned to a
+//! version as `namespace/slug@1.2.0`.
+//!
+//! The namespace is the owning organization's slug, or the publisher's
+//! username (their lowercased Stellar address when they have none). A slug
+//! is unique within its namespace on each network.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::{ContractVersion, Network};
+
+/// Longest slug or namespace accepted
+pub const MAX_SLUG_LEN: usize = 64;
+
+/// Turns a display name into a slug: lowercase ASCII letters and digits,
+/// with every other run of characters replaced by a single `-`. May be empty
+/// when the name has no ASCII letters or digits.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    if slug.len() > MAX_SLUG_LEN {
+        slug.truncate(MAX_SLUG_LEN);
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Checks that `slug` is URL-safe: lowercase ASCII letters, digits and
+/// single hyphens between them, at most [`MAX_SLUG_LEN`] characters.
+pub fn validate_slug(slug: &str) -> Result<(), String> {
+    if slug.is_empty() {
+        return Err("slug cannot be empty".to_string());
+    }
+    if slug.len() > MAX_SLUG_LEN {
+        return Err(format!("slug must be at most {} characters", MAX_SLUG_LEN));
+    }
+    if !slug
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return Err("slug may only contain lowercase letters, digits and hyphens".to_string());
+    }
+    if slug.starts_with('-') || slug.ends_with('-') || slug.contains("--") {
+        return Err("slug hyphens must separate letters or digits".to_string());
+    }
+    Ok(())
+}
+
+/// A contract named as `namespace/slug[@version]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractName {
+    pub namespace: String,
+    pub slug: String,
+    pub version: Option<String>,
+}
+
+impl ContractName {
+    /// Parses `namespace/slug[@version]`, case-insensitively. `None` for
+    /// anything else, contract ids and UUIDs included.
+    pub fn parse(input: &str) -> Option<Self> {
+        let (name, version) = match input.trim().split_once('@') {
+            Some((name, version)) if !version.is_empty() => (name, Some(version.to_string())),
+            Some(_) => return None,
+            None => (input.trim(), None),
+        };
+        let (namespace, slug) = name.split_once('/')?;
+        let (namespace, slug) = (namespace.to_lowercase(), slug.to_lowercase());
+        if validate_slug(&namespace).is_err() || validate_slug(&slug).is_err() {
+            return None;
+        }
+        Some(Self {
+            namespace,
+            slug,
+            version,
+        })
+    }
+
+    /// `namespace/slug`, without the version
+    pub fn qualified(&self) -> String {
+        format!("{}/{}", self.namespace, self.slug)
+    }
+}
+
+impl fmt::Display for ContractName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.namespace, self.slug)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{}", version)?;
+        }
+        Ok(())
+    }
+}
+
+/// Response of `GET /api/resolve`
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ResolvedContract {
+    /// Registry UUID of the contract
+    pub id: Uuid,
+    /// On-chain contract id
+    pub contract_id: String,
+    pub network: Network,
+    /// Current `namespace/slug`
+    pub name: String,
+    /// The name that was asked for, when it is an old name of the contract
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirected_from: Option<String>,
+    /// The requested version, or the latest one. `None` only when no
+    /// version was asked for and none has been published.
+    pub version: Option<ContractVersion>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_keeps_ascii_words() {
+        assert_eq!(slugify("Liquidity Pool"), "liquidity-pool");
+        assert_eq!(slugify("  NFT -- Marketplace v2! "), "nft-marketplace-v2");
+        assert_eq!(slugify("Crème Brûlée"), "cr-me-br-l-e");
+        assert_eq!(slugify("流動性"), "");
+        assert_eq!(slugify(&"a-".repeat(40)).len(), MAX_SLUG_LEN - 1);
+    }
+
+    #[test]
+    fn slugified_names_are_valid_slugs() {
+        for name in ["Liquidity Pool", "x", "Token (v2)", &"ab ".repeat(30)] {
+            let slug = slugify(name);
+            assert_eq!(validate_slug(&slug), Ok(()), "{:?}", slug);
+        }
+    }
+
+    #[test]
+    fn invalid_slugs_are_rejected() {
+        for slug in [
+            "",
+            "Pool",
+            "pool_v2",
+            "-pool",
+            "pool-",
+            "liquidity--pool",
+            "a/b",
+        ] {
+            assert!(validate_slug(slug).is_err(), "{:?}", slug);
+        }
+        assert!(validate_slug(&"a".repeat(MAX_SLUG_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn names_parse_with_and_without_a_version() {
+        assert_eq!(
+            ContractName::parse("Acme/Liquidity-Pool@1.2.0"),
+            Some(ContractName {
+                namespace: "acme".to_string(),
+                slug: "liquidity-pool".to_string(),
+                version: Some("1.2.0".to_string()),
+            })
+        );
+        let latest = ContractName::parse("acme/liquidity-pool").unwrap();
+        assert_eq!(latest.version, None);
+        assert_eq!(latest.to_string(), "acme/liquidity-pool");
+        assert_eq!(latest.qualified(), "acme/liquidity-pool");
+    }
+
+    #[test]
+    fn ids_are_not_names() {
+        for input in [
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
+            "550e8400-e29b-41d4-a716-446655440000",
+            "acme/liquidity-pool@",
+            "acme/",
+            "acme/pool/extra",
+        ] {
+            assert_eq!(ContractName::parse(input), None, "{}", input);
+        }
+    }
+}
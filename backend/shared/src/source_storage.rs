@@ -1,12 +1,17 @@
+use crate::blob_store::{BlobStore, BlobStream, LocalBlobStore, S3BlobStore};
 use crate::error::RegistryError;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::env;
 use std::fmt;
-use std::path::PathBuf;
-use tokio::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::Type, utoipa::ToSchema)]
+#[sqlx(type_name = "source_format_type", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum SourceFormat {
     Rust,
     Wasm,
@@ -30,6 +35,17 @@ impl fmt::Display for StorageBackend {
     }
 }
 
+/// How downloads of stored artifacts are served
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadMode {
+    /// The API reads the blob and streams it to the client
+    Stream,
+    /// The client is redirected to a short-lived presigned URL (S3/GCS only)
+    Presign,
+}
+
+const DEFAULT_PRESIGN_TTL_SECS: u64 = 300;
+
 #[derive(Debug, Clone)]
 pub struct SourceStorageConfig {
     pub backend: StorageBackend,
@@ -38,6 +54,8 @@ pub struct SourceStorageConfig {
     pub s3_region: Option<String>,
     pub s3_prefix: Option<String>,
     pub s3_endpoint: Option<String>,
+    pub download_mode: DownloadMode,
+    pub presign_ttl: Duration,
 }
 
 impl SourceStorageConfig {
@@ -67,6 +85,39 @@ impl SourceStorageConfig {
             ));
         }
 
+        let download_mode = match env::var("SOURCE_STORAGE_DOWNLOAD")
+            .unwrap_or_else(|_| "stream".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "stream" => DownloadMode::Stream,
+            "presign" => DownloadMode::Presign,
+            other => {
+                return Err(RegistryError::InvalidInput(format!(
+                    "SOURCE_STORAGE_DOWNLOAD must be 'stream' or 'presign', not '{}'",
+                    other
+                )))
+            }
+        };
+        if download_mode == DownloadMode::Presign && matches!(backend, StorageBackend::Local) {
+            return Err(RegistryError::InvalidInput(
+                "SOURCE_STORAGE_DOWNLOAD=presign needs the S3 or GCS backend".to_string(),
+            ));
+        }
+
+        let presign_ttl = match env::var("SOURCE_STORAGE_PRESIGN_TTL_SECS") {
+            Ok(value) => match value.parse::<u64>() {
+                Ok(secs) if secs > 0 => Duration::from_secs(secs),
+                _ => {
+                    return Err(RegistryError::InvalidInput(format!(
+                        "SOURCE_STORAGE_PRESIGN_TTL_SECS must be a positive number of seconds, not '{}'",
+                        value
+                    )))
+                }
+            },
+            Err(_) => Duration::from_secs(DEFAULT_PRESIGN_TTL_SECS),
+        };
+
         Ok(Self {
             backend,
             local_root,
@@ -74,115 +125,188 @@ impl SourceStorageConfig {
             s3_region,
             s3_prefix,
             s3_endpoint,
+            download_mode,
+            presign_ttl,
         })
     }
+
+    fn s3_bucket(&self) -> Result<s3::Bucket, RegistryError> {
+        let region = self.s3_region.as_deref().unwrap_or("us-east-1").to_string();
+        let bucket = self.s3_bucket.clone().ok_or_else(|| {
+            RegistryError::InvalidInput("SOURCE_STORAGE_BUCKET is required".to_string())
+        })?;
+
+        let credentials = s3::creds::Credentials::default()
+            .map_err(|e| RegistryError::Internal(format!("S3 credentials error: {}", e)))?;
+        let s3_region = if let Some(ep) = self.s3_endpoint.clone() {
+            s3::Region::Custom {
+                region: region.clone(),
+                endpoint: ep,
+            }
+        } else {
+            s3::Region::Custom {
+                region: region.clone(),
+                endpoint: format!("https://s3.{}.amazonaws.com", region),
+            }
+        };
+        let b = s3::Bucket::new(&bucket, s3_region, credentials)
+            .map_err(|e| RegistryError::Internal(format!("S3 bucket init error: {}", e)))?
+            .with_path_style();
+        Ok(*b)
+    }
+}
+
+/// A stored artifact, ready to be sent to a client
+pub enum SourceDownload {
+    /// Send the client here instead
+    Redirect(String),
+    Stream(BlobStream),
 }
 
+/// Stores and reads contract artifacts in the configured [`BlobStore`].
 #[derive(Clone)]
 pub struct SourceStorage {
     config: SourceStorageConfig,
-    s3_bucket_client: Option<s3::bucket::Bucket>,
+    store: Arc<dyn BlobStore>,
+    /// Blobs written locally before the backend was switched stay readable
+    /// until `api blobs migrate` moves them
+    local: Arc<LocalBlobStore>,
 }
 
 impl SourceStorage {
     pub async fn new() -> Result<Self, RegistryError> {
         let config = SourceStorageConfig::from_env()?;
-
-        let s3_bucket_client = if matches!(config.backend, StorageBackend::S3 | StorageBackend::Gcs)
-        {
-            let region = config
-                .s3_region
-                .as_deref()
-                .unwrap_or("us-east-1")
-                .to_string();
-            let bucket = config.s3_bucket.clone().ok_or_else(|| {
-                RegistryError::InvalidInput("SOURCE_STORAGE_BUCKET is required".to_string())
-            })?;
-
-            let credentials = s3::creds::Credentials::default()
-                .map_err(|e| RegistryError::Internal(format!("S3 credentials error: {}", e)))?;
-            let s3_region = if let Some(ep) = config.s3_endpoint.clone() {
-                s3::Region::Custom {
-                    region: region.clone(),
-                    endpoint: ep,
-                }
-            } else {
-                s3::Region::Custom {
-                    region: region.clone(),
-                    endpoint: format!("https://s3.{}.amazonaws.com", region),
-                }
-            };
-            let b = s3::Bucket::new(&bucket, s3_region, credentials)
-                .map_err(|e| RegistryError::Internal(format!("S3 bucket init error: {}", e)))?
-                .with_path_style();
-            Some(*b)
-        } else {
-            None
+        let local = Arc::new(LocalBlobStore::new(config.local_root.clone()));
+        let store: Arc<dyn BlobStore> = match config.backend {
+            StorageBackend::Local => local.clone(),
+            StorageBackend::S3 => Arc::new(S3BlobStore::new(config.s3_bucket()?, "s3")),
+            StorageBackend::Gcs => Arc::new(S3BlobStore::new(config.s3_bucket()?, "gcs")),
         };
 
         Ok(Self {
             config,
-            s3_bucket_client,
+            store,
+            local,
         })
     }
 
-    /// stores source, returns (storage_backend, storage_key)
-    pub async fn store_source(
-        &self,
-        contract_id: &str,
-        version: &str,
-        format: SourceFormat,
-        source_bytes: &[u8],
-    ) -> Result<(String, String, String), RegistryError> {
-        let source_hash = compute_sha256(source_bytes);
-        let _source_size = source_bytes.len() as i64;
+    /// Local storage under `root`, streaming downloads; for tests and tools
+    pub fn local(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let local = Arc::new(LocalBlobStore::new(root.clone()));
+        Self {
+            config: SourceStorageConfig {
+                backend: StorageBackend::Local,
+                local_root: root,
+                s3_bucket: None,
+                s3_region: None,
+                s3_prefix: None,
+                s3_endpoint: None,
+                download_mode: DownloadMode::Stream,
+                presign_ttl: Duration::from_secs(DEFAULT_PRESIGN_TTL_SECS),
+            },
+            store: local.clone(),
+            local,
+        }
+    }
+
+    /// Name of the backend new blobs are written to
+    pub fn backend(&self) -> &'static str {
+        self.store.backend()
+    }
 
+    fn store_for(&self, storage_backend: &str) -> Result<&dyn BlobStore, RegistryError> {
+        if storage_backend == self.store.backend() {
+            Ok(self.store.as_ref())
+        } else if storage_backend == "local" {
+            Ok(self.local.as_ref())
+        } else {
+            Err(RegistryError::InvalidInput(format!(
+                "Blob is stored on {} but SOURCE_STORAGE_BACKEND is {}",
+                storage_backend,
+                self.store.backend()
+            )))
+        }
+    }
+
+    fn new_key(&self, contract_id: &str, version: &str, format: &SourceFormat) -> String {
         let key = format!(
-            "{}/{}/{}/{}.{}",
+            "{}/{}/{}/{}.bin",
             contract_id,
             version,
             format,
-            Uuid::new_v4(),
-            "bin"
+            Uuid::new_v4()
         );
-
         match self.config.backend {
-            StorageBackend::Local => {
-                let path = self
-                    .config
-                    .local_root
-                    .join(contract_id)
-                    .join(version)
-                    .join(format.to_string());
-                fs::create_dir_all(&path).await?;
-                let file_path = path.join(format!("{}.bin", Uuid::new_v4()));
-                fs::write(&file_path, source_bytes).await?;
-                let key = file_path.to_string_lossy().into_owned();
-                Ok(("local".to_string(), key, source_hash))
-            }
+            StorageBackend::Local => key,
             StorageBackend::S3 | StorageBackend::Gcs => {
-                let bucket = self.s3_bucket_client.as_ref().ok_or_else(|| {
-                    RegistryError::Internal("S3 client not initialized".to_string())
-                })?;
-
                 let prefix = self
                     .config
                     .s3_prefix
-                    .clone()
-                    .unwrap_or_else(|| "contract_sources".to_string());
-                let object_key = format!("{}/{}", prefix.trim_end_matches('/'), key);
-
-                bucket
-                    .put_object(&object_key, source_bytes)
-                    .await
-                    .map_err(|e| {
-                        RegistryError::Internal(format!(
-                            "Failed to upload source artifact to S3/GCS: {}",
-                            e
-                        ))
-                    })?;
-                Ok((self.config.backend.to_string(), object_key, source_hash))
+                    .as_deref()
+                    .unwrap_or("contract_sources");
+                format!("{}/{}", prefix.trim_end_matches('/'), key)
+            }
+        }
+    }
+
+    /// stores source, returns (storage_backend, storage_key, source_hash)
+    pub async fn store_source(
+        &self,
+        contract_id: &str,
+        version: &str,
+        format: SourceFormat,
+        source_bytes: &[u8],
+    ) -> Result<(String, String, String), RegistryError> {
+        let source_hash = compute_sha256(source_bytes);
+        let key = self.new_key(contract_id, version, &format);
+        self.store.put(&key, source_bytes).await?;
+        Ok((self.backend().to_string(), key, source_hash))
+    }
+
+    /// Same as [`store_source`](Self::store_source) but copies from a file
+    /// on disk, so large artifacts are never held in memory. `source_hash` is
+    /// the hex SHA-256 the caller computed while writing the file.
+    pub async fn store_source_file(
+        &self,
+        contract_id: &str,
+        version: &str,
+        format: SourceFormat,
+        file_path: &Path,
+        source_hash: String,
+    ) -> Result<(String, String, String), RegistryError> {
+        let key = self.new_key(contract_id, version, &format);
+        self.store.put_file(&key, file_path).await?;
+        Ok((self.backend().to_string(), key, source_hash))
+    }
+
+    /// Stores a registry snapshot archive next to the artifacts, returns
+    /// (storage_backend, storage_key). It is read back with
+    /// [`retrieve_source`](Self::retrieve_source).
+    pub async fn store_snapshot(
+        &self,
+        snapshot_id: Uuid,
+        archive: &[u8],
+    ) -> Result<(String, String), RegistryError> {
+        let key = self.with_prefix(format!("registry_snapshots/{}.json.gz", snapshot_id));
+        self.store.put(&key, archive).await?;
+        Ok((self.backend().to_string(), key))
+    }
+
+    /// Writes a small marker blob and reads it back, for health checks
+    pub async fn probe(&self) -> Result<(), RegistryError> {
+        let key = self.with_prefix("health/probe".to_string());
+        self.store.put(&key, b"ok").await?;
+        self.store.get(&key).await.map(|_| ())
+    }
+
+    /// `key` under the configured S3 prefix, if any
+    fn with_prefix(&self, key: String) -> String {
+        match (&self.config.backend, self.config.s3_prefix.as_deref()) {
+            (StorageBackend::S3 | StorageBackend::Gcs, Some(prefix)) => {
+                format!("{}/{}", prefix.trim_end_matches('/'), key)
             }
+            _ => key,
         }
     }
 
@@ -191,26 +315,65 @@ impl SourceStorage {
         storage_backend: &str,
         storage_key: &str,
     ) -> Result<Vec<u8>, RegistryError> {
-        match storage_backend {
-            "local" => {
-                let bytes = fs::read(storage_key).await?;
-                Ok(bytes)
-            }
-            "s3" | "gcs" => {
-                let bucket = self.s3_bucket_client.as_ref().ok_or_else(|| {
-                    RegistryError::Internal("S3/GCS bucket not initialized".to_string())
-                })?;
-
-                let data = bucket.get_object(storage_key).await.map_err(|e| {
-                    RegistryError::Internal(format!("S3/GCS get_object failed: {}", e))
-                })?;
-                Ok(data.to_vec())
+        self.store_for(storage_backend)?.get(storage_key).await
+    }
+
+    /// A presigned URL when downloads are configured to use them, else the
+    /// blob's contents as a stream: all of it, or bytes `start..=end` for
+    /// `range`. A client redirected to the URL sends its `Range` there.
+    pub async fn download_source(
+        &self,
+        storage_backend: &str,
+        storage_key: &str,
+        range: Option<(u64, u64)>,
+    ) -> Result<SourceDownload, RegistryError> {
+        let store = self.store_for(storage_backend)?;
+        if self.config.download_mode == DownloadMode::Presign {
+            if let Some(url) = store
+                .presigned_url(storage_key, self.config.presign_ttl)
+                .await?
+            {
+                return Ok(SourceDownload::Redirect(url));
             }
-            other => Err(RegistryError::InvalidInput(format!(
-                "Unknown storage backend {}",
-                other
-            ))),
         }
+        let stream = match range {
+            Some((start, end)) => store.stream_range(storage_key, start, end).await?,
+            None => store.stream(storage_key).await?,
+        };
+        Ok(SourceDownload::Stream(stream))
+    }
+
+    /// Copies a blob from another backend into the configured one, checking
+    /// it against `source_hash` first. Returns the new key; the old blob is
+    /// left for the caller to delete once the new location is recorded.
+    pub async fn copy_to_configured(
+        &self,
+        storage_backend: &str,
+        storage_key: &str,
+        source_hash: &str,
+        contract_id: &str,
+        version: &str,
+        format: SourceFormat,
+    ) -> Result<String, RegistryError> {
+        let bytes = self.retrieve_source(storage_backend, storage_key).await?;
+        let actual = compute_sha256(&bytes);
+        if actual != source_hash {
+            return Err(RegistryError::VerificationFailed(format!(
+                "{} blob {} has hash {}, expected {}",
+                storage_backend, storage_key, actual, source_hash
+            )));
+        }
+        let key = self.new_key(contract_id, version, &format);
+        self.store.put(&key, &bytes).await?;
+        Ok(key)
+    }
+
+    pub async fn delete_source(
+        &self,
+        storage_backend: &str,
+        storage_key: &str,
+    ) -> Result<(), RegistryError> {
+        self.store_for(storage_backend)?.delete(storage_key).await
     }
 }
 
@@ -252,10 +415,57 @@ mod tests {
             .expect("store source");
 
         assert_eq!(hash, compute_sha256(src));
+        assert!(key.starts_with("test_contract/1.0.0/rust/"), "{}", key);
         let loaded = storage
             .retrieve_source("local", &key)
             .await
             .expect("read source");
         assert_eq!(loaded, src);
     }
+
+    #[tokio::test]
+    async fn probe_fails_when_the_store_is_unwritable() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SourceStorage::local(temp_dir.path());
+        storage.probe().await.expect("probe");
+
+        // A file where the root directory should be
+        let blocked = temp_dir.path().join("blocked");
+        std::fs::write(&blocked, b"").unwrap();
+        assert!(SourceStorage::local(&blocked).probe().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn copies_are_checked_against_the_recorded_hash() {
+        let temp_dir = TempDir::new().unwrap();
+        let storage = SourceStorage::local(temp_dir.path());
+        let (backend, key, hash) = storage
+            .store_source("c", "1.0.0", SourceFormat::Wasm, b"\0asm")
+            .await
+            .unwrap();
+
+        let copy = storage
+            .copy_to_configured(&backend, &key, &hash, "c", "1.0.0", SourceFormat::Wasm)
+            .await
+            .unwrap();
+        assert_ne!(copy, key);
+        assert_eq!(
+            storage.retrieve_source("local", &copy).await.unwrap(),
+            b"\0asm"
+        );
+
+        let err = storage
+            .copy_to_configured(
+                &backend,
+                &key,
+                &"0".repeat(64),
+                "c",
+                "1.0.0",
+                SourceFormat::Wasm,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RegistryError::VerificationFailed(_)));
+        assert!(storage.retrieve_source("s3", &key).await.is_err());
+    }
 }
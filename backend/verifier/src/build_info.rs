@@ -0,0 +1,385 @@
+// Build metadata and software bill of materials for verified contracts
+// The SBOM is CycloneDX JSON derived from the contract's Cargo.lock
+
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use shared::RegistryError;
+use std::{process::Stdio, time::Duration};
+use tokio::{process::Command, time::timeout};
+
+/// Largest lockfile accepted
+pub const MAX_LOCKFILE_BYTES: usize = 1024 * 1024;
+const CYCLONEDX_SPEC_VERSION: &str = "1.5";
+const RUSTC_VERSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// One `[[package]]` entry of a `Cargo.lock`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    pub checksum: Option<String>,
+    /// As written in the lockfile: `name`, `name version` or
+    /// `name version (source)`
+    pub dependencies: Vec<String>,
+}
+
+impl LockedPackage {
+    fn bom_ref(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+
+    fn is_from_registry(&self) -> bool {
+        self.source
+            .as_deref()
+            .is_some_and(|source| source.starts_with("registry+") || source.starts_with("sparse+"))
+    }
+}
+
+/// SHA-256 of a lockfile, hex encoded. Line endings are normalized first, so
+/// a Windows checkout hashes the same as the committed file.
+pub fn lockfile_hash(contents: &str) -> String {
+    let normalized = contents.replace("\r\n", "\n");
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Reads the packages of a `Cargo.lock`. Only the fields the SBOM needs are
+/// kept; unknown tables and keys are skipped.
+pub fn parse_lockfile(contents: &str) -> Result<Vec<LockedPackage>, RegistryError> {
+    if contents.len() > MAX_LOCKFILE_BYTES {
+        return Err(RegistryError::InvalidInput(format!(
+            "Cargo.lock must be at most {} bytes",
+            MAX_LOCKFILE_BYTES
+        )));
+    }
+
+    let invalid = |line: usize, reason: &str| {
+        RegistryError::InvalidInput(format!("Invalid Cargo.lock at line {}: {}", line, reason))
+    };
+
+    let mut packages = Vec::new();
+    let mut current: Option<(usize, LockedPackage)> = None;
+    let mut in_dependencies = false;
+
+    for (index, raw) in contents.lines().enumerate() {
+        let line_number = index + 1;
+        let line = raw.trim();
+
+        if in_dependencies {
+            if line == "]" {
+                in_dependencies = false;
+            } else if !line.is_empty() {
+                let dependency = unquote(line.trim_end_matches(','))
+                    .ok_or_else(|| invalid(line_number, "expected a quoted dependency"))?;
+                if let Some((_, package)) = current.as_mut() {
+                    package.dependencies.push(dependency);
+                }
+            }
+            continue;
+        }
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') {
+            if let Some((start, package)) = current.take() {
+                packages.push(complete(start, package)?);
+            }
+            if line == "[[package]]" {
+                current = Some((line_number, LockedPackage::default()));
+            }
+            continue;
+        }
+
+        // Top-level keys such as `version = 3` and other tables are not needed
+        let Some((_, package)) = current.as_mut() else {
+            continue;
+        };
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| invalid(line_number, "expected key = value"))?;
+        let value = value.trim();
+        let string = || unquote(value).ok_or_else(|| invalid(line_number, "expected a string"));
+        match key.trim() {
+            "name" => package.name = string()?,
+            "version" => package.version = string()?,
+            "source" => package.source = Some(string()?),
+            "checksum" => package.checksum = Some(string()?),
+            "dependencies" => {
+                if value == "[" {
+                    in_dependencies = true;
+                } else {
+                    let list = value
+                        .strip_prefix('[')
+                        .and_then(|rest| rest.strip_suffix(']'))
+                        .ok_or_else(|| invalid(line_number, "expected a list of dependencies"))?;
+                    for item in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                        package.dependencies.push(
+                            unquote(item).ok_or_else(|| {
+                                invalid(line_number, "expected a quoted dependency")
+                            })?,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if in_dependencies {
+        return Err(RegistryError::InvalidInput(
+            "Invalid Cargo.lock: unterminated dependency list".to_string(),
+        ));
+    }
+    if let Some((start, package)) = current {
+        packages.push(complete(start, package)?);
+    }
+    if packages.is_empty() {
+        return Err(RegistryError::InvalidInput(
+            "Cargo.lock lists no packages".to_string(),
+        ));
+    }
+    Ok(packages)
+}
+
+fn complete(start: usize, package: LockedPackage) -> Result<LockedPackage, RegistryError> {
+    if package.name.is_empty() || package.version.is_empty() {
+        return Err(RegistryError::InvalidInput(format!(
+            "Invalid Cargo.lock: package at line {} has no name or version",
+            start
+        )));
+    }
+    Ok(package)
+}
+
+fn unquote(value: &str) -> Option<String> {
+    value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .map(str::to_string)
+}
+
+/// The soroban-sdk version a lockfile resolved, if it depends on it
+pub fn locked_sdk_version(packages: &[LockedPackage]) -> Option<&str> {
+    packages
+        .iter()
+        .find(|package| package.name == "soroban-sdk")
+        .map(|package| package.version.as_str())
+}
+
+/// `rustc -V` of the toolchain verification builds run with
+pub async fn rustc_version() -> Option<String> {
+    let output = timeout(
+        RUSTC_VERSION_TIMEOUT,
+        Command::new("rustc")
+            .arg("-V")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+/// The contract an SBOM describes
+pub struct SbomSubject<'a> {
+    pub name: &'a str,
+    pub version: Option<&'a str>,
+    /// SHA-256 of the deployed WASM
+    pub wasm_hash: &'a str,
+}
+
+/// A CycloneDX JSON SBOM listing every package of the lockfile and the
+/// dependencies between them. The output only depends on its inputs, so
+/// the same build always produces the same document.
+pub fn cyclonedx_sbom(subject: &SbomSubject<'_>, packages: &[LockedPackage]) -> Value {
+    let components: Vec<Value> = packages
+        .iter()
+        .map(|package| {
+            let mut component = json!({
+                "type": "library",
+                "bom-ref": package.bom_ref(),
+                "name": package.name,
+                "version": package.version,
+            });
+            if package.is_from_registry() {
+                component["purl"] =
+                    json!(format!("pkg:cargo/{}@{}", package.name, package.version));
+            }
+            if let Some(checksum) = &package.checksum {
+                component["hashes"] = json!([{ "alg": "SHA-256", "content": checksum }]);
+            }
+            if let Some(source) = package
+                .source
+                .as_deref()
+                .filter(|_| !package.is_from_registry())
+            {
+                component["externalReferences"] = json!([{ "type": "vcs", "url": source }]);
+            }
+            component
+        })
+        .collect();
+
+    let dependencies: Vec<Value> = packages
+        .iter()
+        .map(|package| {
+            let depends_on: Vec<String> = package
+                .dependencies
+                .iter()
+                .filter_map(|dependency| resolve_dependency(packages, dependency))
+                .collect();
+            json!({ "ref": package.bom_ref(), "dependsOn": depends_on })
+        })
+        .collect();
+
+    let mut contract = json!({
+        "type": "application",
+        "bom-ref": subject.name,
+        "name": subject.name,
+        "hashes": [{ "alg": "SHA-256", "content": subject.wasm_hash }],
+    });
+    if let Some(version) = subject.version {
+        contract["version"] = json!(version);
+    }
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": CYCLONEDX_SPEC_VERSION,
+        "version": 1,
+        "metadata": {
+            "component": contract,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "soroban-registry-verifier",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }]
+            }
+        },
+        "components": components,
+        "dependencies": dependencies,
+    })
+}
+
+/// The bom-ref of the package a lockfile dependency entry points at. A bare
+/// name is only written when a single version of the package is locked.
+fn resolve_dependency(packages: &[LockedPackage], dependency: &str) -> Option<String> {
+    let mut parts = dependency.split_whitespace();
+    let name = parts.next()?;
+    match parts.next() {
+        Some(version) => Some(format!("{}@{}", name, version)),
+        None => packages
+            .iter()
+            .find(|package| package.name == name)
+            .map(LockedPackage::bom_ref),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOCKFILE: &str = r#"# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 3
+
+[[package]]
+name = "escrow"
+version = "0.1.0"
+dependencies = [
+ "soroban-sdk",
+]
+
+[[package]]
+name = "soroban-sdk"
+version = "21.7.7"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "0a1b2c"
+dependencies = [
+ "stellar-xdr 21.2.0",
+ "stellar-xdr 22.0.0 (registry+https://github.com/rust-lang/crates.io-index)",
+]
+
+[[package]]
+name = "stellar-xdr"
+version = "21.2.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "stellar-xdr"
+version = "22.0.0"
+source = "git+https://github.com/stellar/rs-stellar-xdr?rev=abc#abc"
+dependencies = ["escrow"]
+
+[metadata]
+"#;
+
+    #[test]
+    fn lockfile_packages_are_parsed() {
+        let packages = parse_lockfile(LOCKFILE).expect("lockfile should parse");
+
+        assert_eq!(packages.len(), 4);
+        assert_eq!(packages[0].name, "escrow");
+        assert_eq!(packages[0].source, None);
+        assert_eq!(packages[0].dependencies, ["soroban-sdk"]);
+        assert_eq!(packages[1].checksum.as_deref(), Some("0a1b2c"));
+        assert_eq!(packages[3].dependencies, ["escrow"]);
+        assert_eq!(locked_sdk_version(&packages), Some("21.7.7"));
+    }
+
+    #[test]
+    fn malformed_lockfiles_are_rejected() {
+        for contents in [
+            "",
+            "[[package]]\nname = \"a\"\n",
+            "[[package]]\nname = a\nversion = \"1.0.0\"\n",
+            "[[package]]\nname = \"a\"\nversion = \"1.0.0\"\ndependencies = [\n \"b\",\n",
+        ] {
+            assert!(parse_lockfile(contents).is_err(), "{:?}", contents);
+        }
+    }
+
+    #[test]
+    fn lockfile_hash_ignores_line_endings() {
+        assert_eq!(
+            lockfile_hash(LOCKFILE),
+            lockfile_hash(&LOCKFILE.replace('\n', "\r\n"))
+        );
+        assert_ne!(lockfile_hash(LOCKFILE), lockfile_hash("version = 3\n"));
+    }
+
+    #[test]
+    fn sbom_lists_packages_and_their_dependencies() {
+        let packages = parse_lockfile(LOCKFILE).unwrap();
+        let subject = SbomSubject {
+            name: "acme/escrow",
+            version: Some("1.0.0"),
+            wasm_hash: "ff",
+        };
+        let sbom = cyclonedx_sbom(&subject, &packages);
+
+        assert_eq!(sbom["bomFormat"], "CycloneDX");
+        assert_eq!(sbom["metadata"]["component"]["version"], "1.0.0");
+        let components = sbom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 4);
+        assert_eq!(components[1]["purl"], "pkg:cargo/soroban-sdk@21.7.7");
+        assert_eq!(components[1]["hashes"][0]["content"], "0a1b2c");
+        assert!(components[0].get("purl").is_none());
+        assert!(components[3].get("purl").is_none());
+        assert_eq!(components[3]["externalReferences"][0]["type"], "vcs");
+
+        let dependencies = sbom["dependencies"].as_array().unwrap();
+        assert_eq!(dependencies[0]["dependsOn"], json!(["soroban-sdk@21.7.7"]));
+        assert_eq!(
+            dependencies[1]["dependsOn"],
+            json!(["stellar-xdr@21.2.0", "stellar-xdr@22.0.0"])
+        );
+        assert_eq!(sbom, cyclonedx_sbom(&subject, &packages));
+    }
+}
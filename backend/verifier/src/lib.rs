@@ -1,16 +1,20 @@
 // Contract verification engine
 // Compiles source code and compares with on-chain bytecode
 
+pub mod build_info;
+
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use build_info::LockedPackage;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
-use shared::RegistryError;
+use shared::{BuildMetadata, RegistryError};
 use std::{fs, process::Stdio, time::Duration};
 use tempfile::TempDir;
 use tokio::{process::Command, time::timeout};
 
 const DEFAULT_SOROBAN_SDK_VERSION: &str = "21.7.7";
 const BUILD_TIMEOUT: Duration = Duration::from_secs(120);
+const BUILD_TARGET: &str = "wasm32-unknown-unknown";
 
 #[derive(Debug, Clone)]
 pub struct VerificationResult {
@@ -18,6 +22,9 @@ pub struct VerificationResult {
     pub compiled_wasm_hash: String,
     pub deployed_wasm_hash: String,
     pub message: Option<String>,
+    pub build: BuildMetadata,
+    /// The module the source built to, for analysis after a match
+    pub compiled_wasm: Vec<u8>,
 }
 
 pub async fn verify_contract(
@@ -25,6 +32,7 @@ pub async fn verify_contract(
     deployed_wasm_hash: &str,
     compiler_version: Option<&str>,
     build_params: Option<&Value>,
+    cargo_lock: Option<&str>,
 ) -> Result<VerificationResult, RegistryError> {
     if source_code.trim().is_empty() {
         return Err(RegistryError::InvalidInput(
@@ -41,8 +49,10 @@ pub async fn verify_contract(
         "Starting contract verification"
     );
 
-    let compiled_wasm = compile_contract(source_code, compiler_version, build_params).await?;
+    let compiled_wasm =
+        compile_contract(source_code, compiler_version, build_params, cargo_lock).await?;
     let compiled_hash = hash_wasm(&compiled_wasm);
+    let build = build_metadata(source_code, compiler_version, build_params, cargo_lock).await?;
 
     if compiled_hash == deployed_normalized {
         return Ok(VerificationResult {
@@ -50,6 +60,8 @@ pub async fn verify_contract(
             compiled_wasm_hash: compiled_hash,
             deployed_wasm_hash: deployed_normalized,
             message: None,
+            build,
+            compiled_wasm,
         });
     }
 
@@ -61,17 +73,80 @@ pub async fn verify_contract(
             "Bytecode mismatch: compiled hash {} does not match deployed hash {}",
             compiled_hash, deployed_normalized
         )),
+        build,
+        compiled_wasm,
     })
 }
 
+/// Describes the build `compile_contract` runs for these inputs
+pub async fn build_metadata(
+    source_code: &str,
+    compiler_version: Option<&str>,
+    build_params: Option<&Value>,
+    cargo_lock: Option<&str>,
+) -> Result<BuildMetadata, RegistryError> {
+    let packages = cargo_lock.map(build_info::parse_lockfile).transpose()?;
+    let prebuilt = is_prebuilt(source_code);
+
+    Ok(BuildMetadata {
+        rustc_version: if prebuilt {
+            None
+        } else {
+            build_info::rustc_version().await
+        },
+        soroban_sdk_version: sdk_version(compiler_version, packages.as_deref())?,
+        cargo_features: build_params.map(features).unwrap_or_default(),
+        build_profile: build_params
+            .and_then(|params| params.get("profile"))
+            .and_then(Value::as_str)
+            .unwrap_or("release")
+            .to_string(),
+        target: BUILD_TARGET.to_string(),
+        build_flags: build_args(build_params),
+        rustflags: std::env::var("RUSTFLAGS")
+            .ok()
+            .filter(|flags| !flags.is_empty()),
+        lockfile_sha256: cargo_lock.map(build_info::lockfile_hash),
+    })
+}
+
+fn is_prebuilt(source_code: &str) -> bool {
+    source_code.trim().starts_with("wasm_base64:")
+}
+
+/// The soroban-sdk version to build with. A lockfile that resolves the SDK
+/// decides it, and must agree with `compiler_version` when both are given.
+fn sdk_version(
+    compiler_version: Option<&str>,
+    packages: Option<&[LockedPackage]>,
+) -> Result<String, RegistryError> {
+    let requested = compiler_version.map(str::trim).filter(|v| !v.is_empty());
+    let locked = packages.and_then(build_info::locked_sdk_version);
+    match (requested, locked) {
+        (Some(requested), Some(locked)) if requested != locked => {
+            Err(RegistryError::InvalidInput(format!(
+                "compiler_version {} does not match soroban-sdk {} in Cargo.lock",
+                requested, locked
+            )))
+        }
+        (_, Some(locked)) => Ok(locked.to_string()),
+        (Some(requested), None) => Ok(requested.to_string()),
+        (None, None) => Ok(DEFAULT_SOROBAN_SDK_VERSION.to_string()),
+    }
+}
+
 /// Compile Rust source code to WASM.
 /// Supports two source modes:
 /// - raw Rust contract source (compiled with cargo)
 /// - `wasm_base64:<...>` for precompiled test payloads
+///
+/// A `cargo_lock` is placed next to the generated manifest, pinning the
+/// versions of the dependencies it lists.
 pub async fn compile_contract(
     source_code: &str,
     compiler_version: Option<&str>,
     build_params: Option<&Value>,
+    cargo_lock: Option<&str>,
 ) -> Result<Vec<u8>, RegistryError> {
     if let Some(encoded) = source_code.trim().strip_prefix("wasm_base64:") {
         return BASE64.decode(encoded.trim()).map_err(|e| {
@@ -79,23 +154,22 @@ pub async fn compile_contract(
         });
     }
 
+    let packages = cargo_lock.map(build_info::parse_lockfile).transpose()?;
+    let sdk_version = sdk_version(compiler_version, packages.as_deref())?;
+
     let temp_dir = TempDir::new()?;
-    bootstrap_project(temp_dir.path(), source_code, compiler_version)?;
+    bootstrap_project(temp_dir.path(), source_code, &sdk_version)?;
+    if let Some(lockfile) = cargo_lock {
+        fs::write(temp_dir.path().join("Cargo.lock"), lockfile)?;
+    }
 
     let mut command = Command::new("cargo");
     command
-        .arg("build")
-        .arg("--release")
-        .arg("--target")
-        .arg("wasm32-unknown-unknown")
+        .args(build_args(build_params))
         .current_dir(temp_dir.path())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
-    if let Some(params) = build_params {
-        apply_build_params(&mut command, params);
-    }
-
     let output = timeout(BUILD_TIMEOUT, command.output())
         .await
         .map_err(|_| RegistryError::VerificationFailed("Compilation timed out".to_string()))??;
@@ -125,14 +199,11 @@ pub async fn compile_contract(
 fn bootstrap_project(
     root: &std::path::Path,
     source_code: &str,
-    compiler_version: Option<&str>,
+    sdk_version: &str,
 ) -> Result<(), RegistryError> {
     let src_dir = root.join("src");
     fs::create_dir_all(&src_dir)?;
 
-    let sdk_version = compiler_version
-        .filter(|v| !v.trim().is_empty())
-        .unwrap_or(DEFAULT_SOROBAN_SDK_VERSION);
     let cargo_toml = format!(
         "[package]\nname = \"verify_contract\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[lib]\ncrate-type = [\"cdylib\"]\n\n[dependencies]\nsoroban-sdk = \"{}\"\n",
         sdk_version
@@ -147,20 +218,39 @@ fn bootstrap_project(
     Ok(())
 }
 
-fn apply_build_params(command: &mut Command, build_params: &Value) {
+/// Arguments `cargo` is run with
+fn build_args(build_params: Option<&Value>) -> Vec<String> {
+    let mut args: Vec<String> = ["build", "--release", "--target", BUILD_TARGET]
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let Some(build_params) = build_params else {
+        return args;
+    };
     if let Some(profile) = build_params.get("profile").and_then(Value::as_str) {
-        command.arg("--profile").arg(profile);
+        args.push("--profile".to_string());
+        args.push(profile.to_string());
     }
-    if let Some(features) = build_params.get("features").and_then(Value::as_array) {
-        let joined = features
-            .iter()
-            .filter_map(Value::as_str)
-            .collect::<Vec<_>>()
-            .join(",");
-        if !joined.is_empty() {
-            command.arg("--features").arg(joined);
-        }
+    let features = features(build_params);
+    if !features.is_empty() {
+        args.push("--features".to_string());
+        args.push(features.join(","));
     }
+    args
+}
+
+fn features(build_params: &Value) -> Vec<String> {
+    build_params
+        .get("features")
+        .and_then(Value::as_array)
+        .map(|features| {
+            features
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 pub fn hash_wasm(wasm_bytes: &[u8]) -> String {
@@ -198,7 +288,7 @@ mod tests {
         let expected_hash = hash_wasm(wasm);
         let source = format!("wasm_base64:{}", BASE64.encode(wasm));
 
-        let result = verify_contract(&source, &expected_hash, None, None)
+        let result = verify_contract(&source, &expected_hash, None, None, None)
             .await
             .expect("verification should succeed");
 
@@ -212,7 +302,7 @@ mod tests {
         let source = format!("wasm_base64:{}", BASE64.encode(b"known-bad-wasm"));
         let wrong_hash = hash_wasm(b"different-wasm");
 
-        let result = verify_contract(&source, &wrong_hash, None, None)
+        let result = verify_contract(&source, &wrong_hash, None, None, None)
             .await
             .expect("verification should complete");
 
@@ -222,4 +312,41 @@ mod tests {
             .unwrap_or_default()
             .contains("Bytecode mismatch"));
     }
+
+    #[tokio::test]
+    async fn build_metadata_records_flags_and_lockfile() {
+        let lockfile = "[[package]]\nname = \"soroban-sdk\"\nversion = \"22.0.1\"\n";
+        let params = serde_json::json!({ "features": ["testutils", "alloc"] });
+        let source = format!("wasm_base64:{}", BASE64.encode(b"wasm"));
+
+        let build = build_metadata(&source, Some("22.0.1"), Some(&params), Some(lockfile))
+            .await
+            .expect("metadata should be captured");
+
+        assert_eq!(build.rustc_version, None);
+        assert_eq!(build.soroban_sdk_version, "22.0.1");
+        assert_eq!(build.cargo_features, ["testutils", "alloc"]);
+        assert_eq!(build.build_profile, "release");
+        assert!(build
+            .build_flags
+            .ends_with(&["--features".to_string(), "testutils,alloc".to_string()]));
+        assert_eq!(
+            build.lockfile_sha256,
+            Some(build_info::lockfile_hash(lockfile))
+        );
+    }
+
+    #[tokio::test]
+    async fn lockfile_must_agree_with_compiler_version() {
+        let lockfile = "[[package]]\nname = \"soroban-sdk\"\nversion = \"22.0.1\"\n";
+        let source = format!("wasm_base64:{}", BASE64.encode(b"wasm"));
+
+        let err = build_metadata(&source, Some("21.7.7"), None, Some(lockfile))
+            .await
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("does not match soroban-sdk 22.0.1"));
+    }
 }
@@ -6,6 +6,7 @@ use tempfile::NamedTempFile;
 
 use soroban_registry_cli::profiler::{
     generate_flame_graph, generate_flame_graph_old, FunctionProfile, ProfileData,
+    PROFILE_SCHEMA_VERSION,
 };
 
 fn make_large_profile(n: usize) -> ProfileData {
@@ -18,23 +19,33 @@ fn make_large_profile(n: usize) -> ProfileData {
             FunctionProfile {
                 name,
                 total_time: dur,
+                self_time: dur,
                 call_count: (i as u64) + 1,
                 avg_time: dur,
                 min_time: dur,
                 max_time: dur,
+                histogram: Default::default(),
+                parents: vec![],
                 children: vec![],
+                unreliable: false,
             },
         );
     }
 
     ProfileData {
+        schema_version: PROFILE_SCHEMA_VERSION,
         contract_path: "test".to_string(),
         method: None,
         timestamp: chrono::Utc::now().to_rfc3339(),
         total_duration: Duration::from_secs(1),
         functions,
         call_stack: vec![],
+        overhead_per_call: Duration::ZERO,
         overhead_percent: 0.0,
+        mode: Default::default(),
+        sample_interval: Duration::ZERO,
+        resources: Default::default(),
+        fee_estimate: None,
     }
 }
 
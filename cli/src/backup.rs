@@ -34,7 +34,7 @@ struct BackupRestoration {
 }
 
 pub async fn create_backup(api_url: &str, contract_id: &str, include_state: bool) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let backup: ContractBackup = client
         .post(format!("{}/api/contracts/{}/backups", api_url, contract_id))
         .json(&CreateBackupRequest { include_state })
@@ -51,7 +51,7 @@ pub async fn create_backup(api_url: &str, contract_id: &str, include_state: bool
 }
 
 pub async fn list_backups(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let backups: Vec<ContractBackup> = client
         .get(format!("{}/api/contracts/{}/backups", api_url, contract_id))
         .send()
@@ -72,7 +72,7 @@ pub async fn list_backups(api_url: &str, contract_id: &str) -> Result<()> {
 }
 
 pub async fn restore_backup(api_url: &str, contract_id: &str, backup_date: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     println!("🔄 Restoring backup from {}...", backup_date);
 
@@ -100,7 +100,7 @@ pub async fn restore_backup(api_url: &str, contract_id: &str, backup_date: &str)
 }
 
 pub async fn verify_backup(api_url: &str, contract_id: &str, backup_date: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     client
         .post(format!(
             "{}/api/contracts/{}/backups/{}/verify",
@@ -114,7 +114,7 @@ pub async fn verify_backup(api_url: &str, contract_id: &str, backup_date: &str)
 }
 
 pub async fn backup_stats(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let stats: serde_json::Value = client
         .get(format!(
             "{}/api/contracts/{}/backups/stats",
@@ -337,7 +337,7 @@ async fn register_all(
     skipped_duplicates: usize,
     json: bool,
 ) -> Result<RegistrationSummary> {
-    let client = reqwest::Client::builder()
+    let client = crate::http::client_builder()
         .timeout(std::time::Duration::from_secs(REGISTER_TIMEOUT_SECS))
         .build()?;
 
@@ -392,7 +392,7 @@ async fn register_all(
 }
 
 async fn register_one(
-    client: &reqwest::Client,
+    client: &crate::http::Client,
     url: &str,
     entry: ResolvedEntry,
 ) -> Result<RegistrationResult> {
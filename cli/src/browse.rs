@@ -0,0 +1,265 @@
+//! `browse`: the registry's categories with how many contracts use each, and
+//! then the contracts of one category a page at a time.
+//!
+//! Without a category argument the categories are listed and one is picked
+//! by number or name at a prompt. That needs a terminal on stdin; piped,
+//! scripted and `--json` runs have to name the category. In a terminal every page ends
+//! with a prompt for the next one; otherwise a single page is printed and
+//! `--offset` moves through the rest, as with `search`.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Deserialize;
+
+use crate::commands::Network;
+
+/// As returned by `/api/categories`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Category {
+    pub name: String,
+    pub slug: String,
+    #[serde(default)]
+    pub usage_count: i64,
+}
+
+/// Order of the contracts in a category
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum BrowseSort {
+    /// Most recently published first
+    #[default]
+    Newest,
+    /// Least recently published first
+    Oldest,
+    /// Most recently updated first
+    Updated,
+    /// Most deployed first
+    Deployments,
+    /// Most downloaded first
+    Downloads,
+}
+
+impl BrowseSort {
+    /// `sort_by` and `sort_order` for `/api/contracts`
+    fn params(self) -> [(&'static str, String); 2] {
+        let (sort_by, sort_order) = match self {
+            BrowseSort::Newest => ("created_at", "desc"),
+            BrowseSort::Oldest => ("created_at", "asc"),
+            BrowseSort::Updated => ("updated_at", "desc"),
+            BrowseSort::Deployments => ("deployments", "desc"),
+            BrowseSort::Downloads => ("interactions", "desc"),
+        };
+        [
+            ("sort_by", sort_by.to_string()),
+            ("sort_order", sort_order.to_string()),
+        ]
+    }
+}
+
+pub async fn run(
+    api_url: &str,
+    network: Network,
+    category: Option<&str>,
+    sort: BrowseSort,
+    limit: usize,
+    offset: usize,
+    json: bool,
+) -> Result<()> {
+    let categories = fetch_categories(api_url).await?;
+    // JSON output is for scripts, which can't answer prompts either
+    let interactive = io::stdin().is_terminal() && !json;
+
+    let category = match category {
+        Some(wanted) => find_category(&categories, wanted)?.clone(),
+        None if !interactive => anyhow::bail!(
+            "No category given, and there is no terminal to pick one in; run `browse <category>` with one of: {}",
+            category_names(&categories)
+        ),
+        None => pick_category(&categories)?.clone(),
+    };
+
+    let client = crate::http::client();
+    let mut filters =
+        crate::commands::search_filters(network, false, &[], Some(&category.name), None, None);
+    filters.extend(sort.params());
+
+    let mut offset = offset;
+    loop {
+        let mut params = filters.clone();
+        params.push(("limit", limit.to_string()));
+        params.push(("offset", offset.to_string()));
+        let page = crate::commands::fetch_contract_page(&client, api_url, &params, None).await?;
+
+        if json {
+            return crate::commands::print_search_json(api_url, &page.items);
+        }
+
+        println!(
+            "\n{} {} ({} contract(s), sorted by {})",
+            "Category:".bold().cyan(),
+            category.name.bold(),
+            page.total,
+            sort.to_possible_value()
+                .map(|value| value.get_name().to_string())
+                .unwrap_or_default()
+        );
+        println!("{}", "=".repeat(80).cyan());
+        if page.items.is_empty() {
+            println!("{}", "No contracts in this category.".yellow());
+        } else {
+            crate::commands::print_search_table(api_url, &page.items, "")?;
+        }
+        println!("\n{}\n", crate::table_format::page_summary(&page));
+
+        match page.next_offset() {
+            Some(next) if interactive && ask_for_next_page()? => offset = next as usize,
+            _ => return Ok(()),
+        }
+    }
+}
+
+async fn fetch_categories(api_url: &str) -> Result<Vec<Category>> {
+    let response = crate::http::client()
+        .get(format!("{}/api/categories", api_url))
+        .send()
+        .await
+        .context("Failed to list categories")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Failed to list categories: {} ({})", message, status);
+    }
+    response.json().await.context("Invalid response")
+}
+
+/// The category called `wanted`, by name or slug in any case
+fn find_category<'a>(categories: &'a [Category], wanted: &str) -> Result<&'a Category> {
+    let wanted = wanted.trim();
+    categories
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(wanted) || c.slug.eq_ignore_ascii_case(wanted))
+        .with_context(|| {
+            format!(
+                "Unknown category '{}'. Valid categories: {}",
+                wanted,
+                category_names(categories)
+            )
+        })
+}
+
+fn category_names(categories: &[Category]) -> String {
+    if categories.is_empty() {
+        return "(the registry has no categories)".to_string();
+    }
+    categories
+        .iter()
+        .map(|c| c.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The category an answer at the picker names: its number in the list, or
+/// its name
+fn chosen<'a>(categories: &'a [Category], answer: &str) -> Result<&'a Category> {
+    match answer.trim().parse::<usize>() {
+        Ok(n) if (1..=categories.len()).contains(&n) => Ok(&categories[n - 1]),
+        Ok(n) => anyhow::bail!("No category number {} (pick 1-{})", n, categories.len()),
+        Err(_) => find_category(categories, answer),
+    }
+}
+
+fn pick_category(categories: &[Category]) -> Result<&Category> {
+    if categories.is_empty() {
+        anyhow::bail!("The registry has no categories to browse");
+    }
+    let name_w = categories
+        .iter()
+        .map(|c| c.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let number_w = categories.len().to_string().len();
+
+    println!("\n{}", "Categories:".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for (i, category) in categories.iter().enumerate() {
+        println!(
+            "  {:>number_w$}. {:<name_w$}  {}",
+            i + 1,
+            category.name,
+            format!("{} contract(s)", category.usage_count).bright_black(),
+        );
+    }
+
+    eprint!("\nCategory to browse (number or name): ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    chosen(categories, &answer)
+}
+
+fn ask_for_next_page() -> Result<bool> {
+    eprint!("Show the next page? [Y/n] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer)? == 0 {
+        return Ok(false);
+    }
+    Ok(matches!(
+        answer.trim().to_lowercase().as_str(),
+        "" | "y" | "yes"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn categories() -> Vec<Category> {
+        serde_json::from_value(serde_json::json!([
+            { "id": "1", "name": "DeFi", "slug": "defi", "is_default": true, "usage_count": 12 },
+            { "id": "2", "name": "Oracle", "slug": "oracle", "is_default": true, "usage_count": 3 },
+            { "id": "3", "name": "DEX Aggregator", "slug": "dex-aggregator", "is_default": false, "usage_count": 0 }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn categories_are_found_by_name_or_slug() {
+        let categories = categories();
+        assert_eq!(find_category(&categories, "oracle").unwrap().name, "Oracle");
+        assert_eq!(
+            find_category(&categories, "dex-aggregator").unwrap().name,
+            "DEX Aggregator"
+        );
+
+        let err = find_category(&categories, "lending").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown category 'lending'. Valid categories: DeFi, Oracle, DEX Aggregator"
+        );
+    }
+
+    #[test]
+    fn the_picker_takes_a_number_or_a_name() {
+        let categories = categories();
+        assert_eq!(chosen(&categories, "2\n").unwrap().name, "Oracle");
+        assert_eq!(chosen(&categories, "defi\n").unwrap().name, "DeFi");
+        assert!(chosen(&categories, "0").is_err());
+        assert!(chosen(&categories, "4").is_err());
+    }
+
+    #[test]
+    fn sorts_map_to_contract_list_parameters() {
+        assert_eq!(
+            BrowseSort::Downloads.params(),
+            [
+                ("sort_by", "interactions".to_string()),
+                ("sort_order", "desc".to_string())
+            ]
+        );
+        assert_eq!(BrowseSort::Oldest.params()[1].1, "asc");
+    }
+}
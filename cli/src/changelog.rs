@@ -0,0 +1,270 @@
+//! `changelog`: a contract's versions, newest first, with their release
+//! notes, WASM changes and breaking changes, from
+//! `GET /api/contracts/:id/changelog`.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+/// Shown in place of release notes for versions published without any
+const NO_CHANGELOG: &str = "(no changelog provided)";
+
+/// One version as returned by `GET /api/contracts/:id/changelog`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogEntry {
+    pub version: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub commit_hash: Option<String>,
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub release_notes: Option<String>,
+    pub wasm_hash: String,
+    #[serde(default)]
+    pub wasm_changed: bool,
+    #[serde(default)]
+    pub wasm_size: Option<i64>,
+    #[serde(default)]
+    pub size_delta: Option<i64>,
+    #[serde(default)]
+    pub breaking: bool,
+    #[serde(default)]
+    pub breaking_changes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChangelogResponse {
+    entries: Vec<ChangelogEntry>,
+}
+
+pub async fn show(
+    api_url: &str,
+    contract_id: &str,
+    since: Option<&str>,
+    markdown: bool,
+    json: bool,
+) -> Result<()> {
+    let url = format!("{}/api/contracts/{}/changelog", api_url, contract_id);
+    let response = crate::http::client()
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch changelog")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"]
+            .as_str()
+            .or(body["message"].as_str())
+            .unwrap_or("no details");
+        anyhow::bail!("Failed to fetch changelog: {} ({})", message, status);
+    }
+
+    let changelog: ChangelogResponse = response.json().await.context("Invalid response")?;
+    let entries = newer_than(&changelog.entries, since)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "entries": entries }))?
+        );
+    } else if markdown {
+        print!("{}", render_markdown(entries));
+    } else {
+        render_text(entries);
+    }
+    Ok(())
+}
+
+/// The entries published after `since`, which must be one of them; all of
+/// them without it. Entries are newest first.
+fn newer_than<'a>(
+    entries: &'a [ChangelogEntry],
+    since: Option<&str>,
+) -> Result<&'a [ChangelogEntry]> {
+    let Some(since) = since else {
+        return Ok(entries);
+    };
+    match entries.iter().position(|e| e.version == since) {
+        Some(index) => Ok(&entries[..index]),
+        None => anyhow::bail!(
+            "Version {} not found; the contract has {}",
+            since,
+            if entries.is_empty() {
+                "no versions".to_string()
+            } else {
+                entries
+                    .iter()
+                    .map(|e| e.version.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        ),
+    }
+}
+
+fn render_text(entries: &[ChangelogEntry]) {
+    println!("\n{}", "Changelog".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    if entries.is_empty() {
+        println!("{}", "No versions to show.".yellow());
+        return;
+    }
+
+    for entry in entries {
+        println!(
+            "\n{} {}{}",
+            entry.version.bold().green(),
+            crate::time_format::show(entry.created_at).dimmed(),
+            if entry.breaking {
+                format!(" {}", "BREAKING".red().bold())
+            } else {
+                String::new()
+            }
+        );
+        println!("{}", "-".repeat(80).cyan());
+        match notes(entry) {
+            Some(notes) => {
+                for line in notes.lines() {
+                    println!("  {}", line);
+                }
+            }
+            None => println!("  {}", NO_CHANGELOG.dimmed()),
+        }
+        for change in &entry.breaking_changes {
+            println!("  {} {}", "!".red().bold(), change);
+        }
+        println!("  {}", wasm_line(entry).bright_black());
+    }
+    println!();
+}
+
+/// A CHANGELOG.md-style document, one `## [version] - date` section per
+/// version
+fn render_markdown(entries: &[ChangelogEntry]) -> String {
+    let mut out = String::from("# Changelog\n");
+    for entry in entries {
+        out.push_str(&format!(
+            "\n## [{}] - {}\n\n",
+            entry.version,
+            entry.created_at.format("%Y-%m-%d")
+        ));
+        if !entry.breaking_changes.is_empty() {
+            out.push_str("### Breaking changes\n\n");
+            for change in &entry.breaking_changes {
+                out.push_str(&format!("- {}\n", change));
+            }
+            out.push('\n');
+        }
+        match notes(entry) {
+            Some(notes) => {
+                out.push_str(notes);
+                out.push('\n');
+            }
+            None => out.push_str(&format!("_{}_\n", NO_CHANGELOG)),
+        }
+        out.push_str(&format!("\n{}\n", wasm_line(entry)));
+    }
+    out
+}
+
+fn notes(entry: &ChangelogEntry) -> Option<&str> {
+    entry
+        .release_notes
+        .as_deref()
+        .map(str::trim)
+        .filter(|notes| !notes.is_empty())
+}
+
+/// "WASM 3f2a9c1d… (changed), 18.4 KB (+1.2 KB)"
+fn wasm_line(entry: &ChangelogEntry) -> String {
+    let hash = &entry.wasm_hash[..entry.wasm_hash.len().min(12)];
+    let mut line = format!(
+        "WASM {}{}",
+        hash,
+        if entry.wasm_changed { " (changed)" } else { "" }
+    );
+    if let Some(size) = entry.wasm_size {
+        line.push_str(&format!(", {}", format_size(size)));
+        if let Some(delta) = entry.size_delta {
+            let sign = if delta < 0 { "-" } else { "+" };
+            line.push_str(&format!(" ({}{})", sign, format_size(delta.abs())));
+        }
+    }
+    line
+}
+
+fn format_size(bytes: i64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(version: &str, notes: Option<&str>) -> ChangelogEntry {
+        ChangelogEntry {
+            version: version.to_string(),
+            created_at: "2026-10-01T09:00:00Z".parse().unwrap(),
+            commit_hash: None,
+            source_url: None,
+            release_notes: notes.map(str::to_string),
+            wasm_hash: "3f2a9c1d5e6b7a8f9e0d".to_string(),
+            wasm_changed: true,
+            wasm_size: Some(18_841),
+            size_delta: Some(-1_229),
+            breaking: false,
+            breaking_changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn since_keeps_only_newer_versions() {
+        let entries = vec![
+            entry("1.2.0", None),
+            entry("1.1.0", None),
+            entry("1.0.0", None),
+        ];
+        let newer = newer_than(&entries, Some("1.1.0")).unwrap();
+        assert_eq!(newer.len(), 1);
+        assert_eq!(newer[0].version, "1.2.0");
+        assert_eq!(newer_than(&entries, Some("1.0.0")).unwrap().len(), 2);
+        assert_eq!(newer_than(&entries, None).unwrap().len(), 3);
+
+        let err = newer_than(&entries, Some("0.9.0")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Version 0.9.0 not found; the contract has 1.2.0, 1.1.0, 1.0.0"
+        );
+    }
+
+    #[test]
+    fn markdown_marks_versions_without_notes() {
+        let mut breaking = entry("2.0.0", Some("Renamed `deposit`.\n"));
+        breaking.breaking = true;
+        breaking.breaking_changes = vec!["Function `deposit` removed".to_string()];
+        let mut first = entry("1.0.0", None);
+        first.wasm_changed = false;
+        first.size_delta = None;
+
+        assert_eq!(
+            render_markdown(&[breaking, first]),
+            "# Changelog\n\
+             \n## [2.0.0] - 2026-10-01\n\n\
+             ### Breaking changes\n\n\
+             - Function `deposit` removed\n\n\
+             Renamed `deposit`.\n\
+             \nWASM 3f2a9c1d5e6b (changed), 18.4 KB (-1.2 KB)\n\
+             \n## [1.0.0] - 2026-10-01\n\n\
+             _(no changelog provided)_\n\
+             \nWASM 3f2a9c1d5e6b, 18.4 KB\n"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! `changes`: what changed in the registry since a date, from
+//! `GET /api/contracts/changes`, one section per group with its count.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use colored::Colorize;
+use serde::Deserialize;
+
+/// One contract that changed, as returned in every group
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContractChange {
+    pub contract_id: String,
+    pub name: String,
+    pub network: String,
+    pub changed_at: DateTime<Utc>,
+    #[serde(default)]
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangePage {
+    pub items: Vec<ContractChange>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContractChanges {
+    pub since: DateTime<Utc>,
+    pub until: DateTime<Utc>,
+    #[serde(default)]
+    pub added: Option<ChangePage>,
+    #[serde(default)]
+    pub verified: Option<ChangePage>,
+    #[serde(default)]
+    pub deprecated: Option<ChangePage>,
+    #[serde(default)]
+    pub removed: Option<ChangePage>,
+    #[serde(default)]
+    pub updated: Option<ChangePage>,
+}
+
+impl ContractChanges {
+    /// The groups in the order they are shown
+    fn groups(&self) -> [(&'static str, Option<&ChangePage>); 5] {
+        [
+            ("Added", self.added.as_ref()),
+            ("Verified", self.verified.as_ref()),
+            ("Deprecated", self.deprecated.as_ref()),
+            ("Removed", self.removed.as_ref()),
+            ("Updated", self.updated.as_ref()),
+        ]
+    }
+}
+
+/// `2024-06-01` (midnight UTC) or a full RFC 3339 timestamp
+fn parse_bound(flag: &str, value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(value) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        .with_context(|| {
+            format!(
+                "--{} must be a date (2024-06-01) or an RFC 3339 timestamp, got '{}'",
+                flag, value
+            )
+        })
+}
+
+pub async fn show(
+    api_url: &str,
+    since: &str,
+    until: Option<&str>,
+    network: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let mut query = vec![
+        ("since", parse_bound("since", since)?.to_rfc3339()),
+        ("limit", limit.to_string()),
+    ];
+    if let Some(until) = until {
+        query.push(("until", parse_bound("until", until)?.to_rfc3339()));
+    }
+    if let Some(network) = network {
+        query.push(("network", network.to_string()));
+    }
+
+    let url = format!("{}/api/contracts/changes", api_url);
+    let response = crate::http::client()
+        .get(&url)
+        .query(&query)
+        .send()
+        .await
+        .context("Failed to fetch registry changes")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"]
+            .as_str()
+            .or(body["message"].as_str())
+            .unwrap_or("no details");
+        anyhow::bail!("Failed to fetch registry changes: {} ({})", message, status);
+    }
+
+    let body: serde_json::Value = response.json().await.context("Invalid response")?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+    let changes: ContractChanges = serde_json::from_value(body).context("Invalid response")?;
+    render_text(&changes);
+    Ok(())
+}
+
+fn render_text(changes: &ContractChanges) {
+    println!(
+        "\n{} {} – {}",
+        "Registry changes".bold().cyan(),
+        crate::time_format::show(changes.since),
+        crate::time_format::show(changes.until)
+    );
+    println!("{}", "=".repeat(80).cyan());
+
+    for (label, page) in changes.groups() {
+        let Some(page) = page else { continue };
+        println!("\n{} ({})", label.bold(), page.total);
+        if page.items.is_empty() {
+            println!("  {}", "none".dimmed());
+            continue;
+        }
+        for change in &page.items {
+            println!(
+                "  {:<32} {:<10} {}{}",
+                change.name.green(),
+                change.network,
+                crate::time_format::show(change.changed_at).dimmed(),
+                change
+                    .detail
+                    .as_deref()
+                    .map(|detail| format!("  {}", detail.yellow()))
+                    .unwrap_or_default()
+            );
+            println!("  {}", change.contract_id.bright_black());
+        }
+        if let Some(more) = remaining(page) {
+            println!("  {}", format!("… and {} more", more).dimmed());
+        }
+    }
+    println!();
+}
+
+/// Changes in the group that weren't on the page
+fn remaining(page: &ChangePage) -> Option<i64> {
+    let more = page.total - page.items.len() as i64;
+    (more > 0).then_some(more)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_take_dates_or_timestamps() {
+        assert_eq!(
+            parse_bound("since", "2024-06-01").unwrap(),
+            "2024-06-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        assert_eq!(
+            parse_bound("since", "2024-06-01T12:30:00+02:00").unwrap(),
+            "2024-06-01T10:30:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+        let err = parse_bound("until", "last month").unwrap_err();
+        assert!(err.to_string().contains("--until"));
+    }
+
+    #[test]
+    fn groups_keep_their_counts() {
+        let changes: ContractChanges = serde_json::from_value(serde_json::json!({
+            "since": "2024-06-01T00:00:00Z",
+            "until": "2024-07-01T00:00:00Z",
+            "added": {
+                "items": [{
+                    "id": "5b3f0c1e-8a0d-4a57-9f5e-0d8c2f6b1a11",
+                    "contract_id": "CABC",
+                    "name": "Pool",
+                    "network": "testnet",
+                    "changed_at": "2024-06-12T08:00:00Z",
+                    "detail": null
+                }],
+                "total": 3, "limit": 1, "offset": 0, "has_more": true
+            },
+            "removed": { "items": [], "total": 0, "limit": 1, "offset": 0, "has_more": false }
+        }))
+        .unwrap();
+
+        let groups = changes.groups();
+        assert_eq!(groups[0].0, "Added");
+        assert_eq!(remaining(groups[0].1.unwrap()), Some(2));
+        assert!(groups[1].1.is_none());
+        assert_eq!(remaining(groups[3].1.unwrap()), None);
+    }
+}
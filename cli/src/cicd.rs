@@ -1,5 +1,5 @@
 use crate::commands::Network;
-use anyhow::{Context, Result};
+use anyhow::Result;
 use colored::Colorize;
 use std::path::Path;
 use std::process::Command;
@@ -98,7 +98,12 @@ pub async fn run_pipeline(
         None,
         vec!["cicd".to_string(), "automated".to_string()],
         &publisher,
+        None,
+        // Every auto-registered contract has the same name
+        Some(&contract_id.to_lowercase()),
         true,
+        None,
+        json,
     )
     .await?;
 
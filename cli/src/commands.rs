@@ -4,7 +4,7 @@ use anyhow::{Context, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use serde_yaml;
+use shared::pagination::Paginated;
 use std::fmt;
 use std::fs;
 use std::str::FromStr;
@@ -20,25 +20,53 @@ pub enum Network {
 use std::path::Path;
 
 use crate::patch::{PatchManager, Severity};
-use crate::profiler;
 use crate::test_framework;
+use soroban_profiler as profiler;
+use soroban_profiler::ChangeStatus;
 
 pub fn generate_flame_graph_file(profile: &profiler::ProfileData, output_path: &str) -> Result<()> {
     profiler::generate_flame_graph(profile, Path::new(output_path))
+        .with_context(|| format!("Failed to write flame graph: {}", output_path))
 }
 
+/// Profiles a contract and prints a summary. With `sample_rate_hz` the call
+/// stack is sampled that many times a second instead of timing every call.
+/// `output` is written as
+/// `output_format`, either `json` or `chrome-trace`. With `fees` the fee per
+/// invocation is estimated, and re-estimated for the baseline so both use
+/// the same parameters. `flamegraph_diff` and `gate` apply to the comparison
+/// against the `compare` baseline; the command fails when the gate is
+/// exceeded. With `json` the summary is returned as a report instead of
+/// printed. Returns the profile, for `--publish-to`.
+#[allow(clippy::too_many_arguments)]
 pub fn profile(
     contract_path: &str,
     method: Option<&str>,
+    sample_rate_hz: Option<u32>,
     output: Option<&str>,
+    output_format: &str,
     flamegraph: Option<&str>,
+    folded: Option<&str>,
     compare: Option<&str>,
+    fees: Option<&profiler::FeeConfig>,
+    flamegraph_diff: Option<&str>,
+    gate: Option<&profiler::RegressionGate>,
     show_recommendations: bool,
-) -> Result<()> {
-    println!("\n{}", "Profiling contract execution...".bold().cyan());
-    println!("{}", "=".repeat(80).cyan());
+    json: bool,
+) -> Result<ProfileRun> {
+    if !json {
+        println!("\n{}", "Profiling contract execution...".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+    }
 
-    let profile_data = profiler::profile_contract(contract_path, method)
+    let mut options = profiler::ProfileOptions::builder(contract_path);
+    if let Some(method) = method {
+        options = options.method(method);
+    }
+    if let Some(rate_hz) = sample_rate_hz {
+        options = options.sampling(rate_hz);
+    }
+    let profile_data = profiler::profile_contract(&options.build())
         .with_context(|| format!("Failed to profile contract: {}", contract_path))?;
 
     if let Some(method_name) = method {
@@ -51,6 +79,27 @@ pub fn profile(
         }
     }
 
+    if !json {
+        print_local_summary(contract_path, &profile_data);
+    }
+
+    report_profile(
+        profile_data,
+        output,
+        output_format,
+        flamegraph,
+        folded,
+        compare,
+        fees,
+        flamegraph_diff,
+        gate,
+        show_recommendations,
+        json,
+    )
+}
+
+/// The duration, overhead and slowest functions of a local profile
+fn print_local_summary(contract_path: &str, profile_data: &profiler::ProfileData) {
     println!("{}: {}", "Contract".bold(), contract_path);
     println!(
         "{}: {:.2}ms",
@@ -62,124 +111,977 @@ pub fn profile(
         "Functions profiled".bold(),
         profile_data.functions.len()
     );
+    let sampled = profile_data.mode == profiler::ProfileMode::Sampled;
+    if sampled {
+        println!(
+            "{}: every {:?} ({:.2}% of the run spent sampling; times are estimates)",
+            "Sampling".bold(),
+            profile_data.sample_interval,
+            profile_data.overhead_percent
+        );
+    } else {
+        println!(
+            "{}: {}ns per call ({:.2}% of the run, subtracted)",
+            "Profiler overhead".bold(),
+            profile_data.overhead_per_call.as_nanos(),
+            profile_data.overhead_percent
+        );
+    }
+
+    let mut functions: Vec<_> = profile_data.functions.values().collect();
+    functions.sort_by(|a, b| b.total_time.cmp(&a.total_time).then(a.name.cmp(&b.name)));
+    if !functions.is_empty() {
+        println!(
+            "\n  {:<32} {:>12} {:>12} {:>8} {:>10} {:>10} {:>10}  {}",
+            "Function".bold(),
+            "Inclusive".bold(),
+            "Self".bold(),
+            if sampled { "Samples" } else { "Calls" }.bold(),
+            "p50".bold(),
+            "p90".bold(),
+            "p99".bold(),
+            "Distribution".bold()
+        );
+        for function in functions.iter().take(10) {
+            let name = if function.unreliable {
+                format!("{} *", function.name)
+            } else {
+                function.name.clone()
+            };
+            let percentile = |percentile: f64| {
+                function
+                    .percentile(percentile)
+                    .map(|duration| format!("{:.3}ms", duration.as_secs_f64() * 1000.0))
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            println!(
+                "  {:<32} {:>10.3}ms {:>10.3}ms {:>8} {:>10} {:>10} {:>10}  {}",
+                name,
+                function.total_time.as_secs_f64() * 1000.0,
+                function.self_time.as_secs_f64() * 1000.0,
+                function.call_count,
+                percentile(50.0),
+                percentile(90.0),
+                percentile(99.0),
+                function.histogram.sparkline().cyan()
+            );
+        }
+        if functions.len() > 10 {
+            println!("  ...and {} more", functions.len() - 10);
+        }
+    }
+
+    let unreliable = profile_data.unreliable_functions().count();
+    if unreliable > 0 {
+        println!(
+            "{}",
+            format!(
+                "Note: {} function(s) ran within {}x the profiler overhead (marked *); treat their times as approximate.",
+                unreliable,
+                profiler::UNRELIABLE_OVERHEAD_FACTOR
+            )
+            .yellow()
+        );
+    }
+}
+
+/// Profiles one invocation of `method` on the deployed contract `contract`,
+/// a `C…` address or `namespace/slug[@version]`, by simulating it through
+/// the Soroban RPC at `rpc_url`. `args` is a JSON array, or an object keyed
+/// by parameter name; with the contract's interface stored in the registry
+/// they are converted to the declared types. The profile is tagged
+/// `simulated` and carries the measured resources instead of timings; the
+/// remaining options, `json` included, work as in [`profile`].
+#[allow(clippy::too_many_arguments)]
+pub async fn profile_remote(
+    api_url: &str,
+    contract: &str,
+    network: crate::config::Network,
+    rpc_url: &str,
+    method: &str,
+    args: Option<&str>,
+    output: Option<&str>,
+    output_format: &str,
+    flamegraph: Option<&str>,
+    folded: Option<&str>,
+    compare: Option<&str>,
+    fees: Option<&profiler::FeeConfig>,
+    flamegraph_diff: Option<&str>,
+    gate: Option<&profiler::RegressionGate>,
+    show_recommendations: bool,
+    json: bool,
+) -> Result<ProfileRun> {
+    if !json {
+        println!(
+            "\n{}",
+            "Profiling deployed contract through simulation..."
+                .bold()
+                .cyan()
+        );
+        println!("{}", "=".repeat(80).cyan());
+    }
+
+    let args: serde_json::Value = match args {
+        Some(args) => serde_json::from_str(args).context("--args is not valid JSON")?,
+        None => serde_json::Value::Null,
+    };
+    let (address, abi) = remote_interface(api_url, contract, network).await?;
+    if abi.is_none() && !json {
+        println!(
+            "{}",
+            "Note: the registry has no interface for this contract; argument types are inferred from the JSON."
+                .yellow()
+        );
+    }
+    let values = crate::scval::method_args(abi.as_ref(), method, &args)?;
+    let envelope = crate::simulation::invocation(&address, method, values)?;
+
+    let simulation = match crate::simulation::simulate(rpc_url, &envelope, abi.as_ref()).await {
+        Ok(simulation) => simulation,
+        Err(err) => {
+            if let Some(failure) = err.downcast_ref::<crate::simulation::SimulationFailure>() {
+                if !json {
+                    crate::simulation::print_failure(failure);
+                }
+            }
+            return Err(err.context(format!("Simulating {} on {} failed", method, address)));
+        }
+    };
+    let profile_data =
+        profiler::ProfileData::from_simulation(&address, method, simulation.resources.clone());
+
+    if !json {
+        println!("{}: {}", "Contract".bold(), address);
+        println!("{}: {}", "Method".bold(), method);
+        println!("{}: {}", "RPC".bold(), rpc_url);
+        print_resources(&profile_data.resources);
+        if let Some(fee) = simulation.min_resource_fee {
+            println!("  {:<24} {:>12} stroops", "Resource fee", fee);
+        }
+        crate::simulation::print_notes(&simulation);
+    }
+
+    let mut run = report_profile(
+        profile_data,
+        output,
+        output_format,
+        flamegraph,
+        folded,
+        compare,
+        fees,
+        flamegraph_diff,
+        gate,
+        show_recommendations,
+        json,
+    )?;
+    if let Some(report) = &mut run.report {
+        report["simulation"] = json!({
+            "rpc_url": rpc_url,
+            "resource_fee": simulation.min_resource_fee,
+            "authorizations": simulation.auth.len(),
+            "needs_restore": simulation.needs_restore,
+        });
+    }
+    Ok(run)
+}
+
+/// The contract address behind `contract` and, when the registry has it,
+/// the interface of its published version. Addresses the registry has
+/// never seen are still profiled, just without an interface.
+async fn remote_interface(
+    api_url: &str,
+    contract: &str,
+    network: crate::config::Network,
+) -> Result<(String, Option<contract_abi::ContractABI>)> {
+    let client = crate::http::client();
+    let base_url = api_url.trim_end_matches('/');
+
+    let (address, registered, version) = match shared::slug::ContractName::parse(contract) {
+        Some(name) => {
+            let resolved = crate::resolve::resolve(api_url, &name, network).await?;
+            (
+                resolved.contract_id,
+                Some((resolved.id.to_string(), resolved.name)),
+                resolved.version.map(|version| version.version),
+            )
+        }
+        None => {
+            let mut found =
+                crate::contracts::fetch_batch(base_url, &[contract.to_string()], Some("id,name"))
+                    .await
+                    .unwrap_or_default();
+            let registered = found.remove(contract).and_then(|found| {
+                let id = found["id"].as_str()?.to_string();
+                let name = found["name"].as_str().unwrap_or(contract).to_string();
+                Some((id, name))
+            });
+            (contract.to_string(), registered, None)
+        }
+    };
+    let Some((id, name)) = registered else {
+        return Ok((address, None));
+    };
+
+    let mut request = client.get(format!("{}/api/contracts/{}/abi", base_url, id));
+    if let Some(version) = &version {
+        request = request.query(&[("version", version)]);
+    }
+    let response = request
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch the interface of {}", contract))?;
+    if !response.status().is_success() {
+        return Ok((address, None));
+    }
+    let body: serde_json::Value = response.json().await?;
+    let Some(abi_json) = body.get("abi") else {
+        return Ok((address, None));
+    };
+    let abi = contract_abi::parse_json_spec(&abi_json.to_string(), &name)
+        .map_err(|e| anyhow::anyhow!("Failed to parse the interface of {}: {}", contract, e))?;
+    Ok((address, Some(abi)))
+}
+
+/// Downloads the official profile the registry recorded when it verified
+/// `contract`, a UUID, address or `namespace/slug[@version]`, and writes it
+/// to `output` (default `<contract>-<version>.profile.json`) as a baseline
+/// for `profile --compare`. The version named in `contract` is fetched,
+/// otherwise the newest one with a profile. With `json` the registry's
+/// response is printed, with the file it was written to as `written_to`.
+pub async fn fetch_profile(
+    api_url: &str,
+    contract: &str,
+    network: crate::config::Network,
+    output: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let base_url = api_url.trim_end_matches('/');
+
+    let (id, label, version) = match shared::slug::ContractName::parse(contract) {
+        Some(name) => {
+            let resolved = crate::resolve::resolve(api_url, &name, network).await?;
+            (resolved.id.to_string(), name.slug.clone(), name.version)
+        }
+        None => (contract.to_string(), contract.to_string(), None),
+    };
+    let version = match version {
+        Some(version) => version,
+        None => {
+            let uuid = match uuid::Uuid::parse_str(&id) {
+                Ok(uuid) => uuid.to_string(),
+                Err(_) => {
+                    crate::contracts::fetch_batch(base_url, std::slice::from_ref(&id), Some("id"))
+                        .await?
+                        .remove(&id)
+                        .and_then(|found| found["id"].as_str().map(str::to_string))
+                        .with_context(|| format!("{} is not in the registry", contract))?
+                }
+            };
+            let response = client
+                .get(format!("{}/api/contracts/{}", base_url, uuid))
+                .send()
+                .await
+                .with_context(|| format!("Failed to fetch {}", contract))?;
+            if !response.status().is_success() {
+                anyhow::bail!("Failed to fetch {} ({})", contract, response.status());
+            }
+            let detail: serde_json::Value = response.json().await?;
+            detail["performance"]["version"]
+                .as_str()
+                .map(str::to_string)
+                .with_context(|| {
+                    format!(
+                        "{} has no official profile yet; one is recorded when the contract is verified",
+                        contract
+                    )
+                })?
+        }
+    };
+
+    let response = client
+        .get(format!(
+            "{}/api/contracts/{}/versions/{}/profile",
+            base_url, id, version
+        ))
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch the profile of {}", contract))?;
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!(
+            "Could not fetch the profile of {}: {} ({})",
+            contract,
+            message,
+            status
+        );
+    }
+    let profile = profiler::parse_profile(&body["profile"].to_string())
+        .context("The registry returned a profile this release cannot read")?
+        .profile;
+
+    let output = output.map(str::to_string).unwrap_or_else(|| {
+        format!(
+            "{}-{}.profile.json",
+            label.replace(['/', '@'], "-"),
+            version
+        )
+    });
+    profiler::write_json(&profile, Path::new(&output))
+        .with_context(|| format!("Failed to write profile output: {}", output))?;
+
+    if json {
+        let mut body = body;
+        body["written_to"] = json!(output);
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+
+    println!(
+        "\n{}",
+        format!("Official profile of {} {}", contract, version)
+            .bold()
+            .cyan()
+    );
+    println!(
+        "{}: {}, {}",
+        "Engine".bold(),
+        body["engine"].as_str().unwrap_or("-"),
+        body["profiled_at"].as_str().unwrap_or("-")
+    );
+    println!(
+        "\n  {:<32} {:>18} {:>14}",
+        "Method".bold(),
+        "CPU instructions".bold(),
+        "Memory (bytes)".bold()
+    );
+    for method in body["methods"].as_array().into_iter().flatten() {
+        println!(
+            "  {:<32} {:>18} {:>14}",
+            method["method"].as_str().unwrap_or("-"),
+            method["cpu_instructions"].as_u64().unwrap_or(0),
+            method["memory_bytes"].as_u64().unwrap_or(0)
+        );
+    }
+
+    println!("\n{} Profile written to {}", "✓".green(), output);
+    println!(
+        "  Compare a local run with: soroban-registry profile <contract> --compare {}",
+        output
+    );
+    Ok(())
+}
+
+/// Uploads `profile` to `contract`, a UUID, address or
+/// `namespace/slug`, as the publisher's own profile of the method. The
+/// registry keeps the newest few per method and shows the latest on the
+/// contract; simulated profiles are labeled as such there. Returns the
+/// registry's summary of the upload, printed unless `json`.
+pub async fn publish_profile(
+    api_url: &str,
+    contract: &str,
+    network: crate::config::Network,
+    profile: &profiler::ProfileData,
+    auth_token: Option<&str>,
+    json: bool,
+) -> Result<serde_json::Value> {
+    let id = crate::resolve::contract_id(api_url, contract, network).await?;
+    let mut request = crate::http::client()
+        .post(format!(
+            "{}/api/contracts/{}/profiles",
+            api_url.trim_end_matches('/'),
+            id
+        ))
+        .json(&json!({
+            "cli_version": env!("CARGO_PKG_VERSION"),
+            "profile": profile,
+        }));
+    if let Some(token) = auth_token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .context("Failed to connect to registry API")?;
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!(
+            "Could not publish the profile to {}: {} ({})",
+            contract,
+            message,
+            status
+        );
+    }
+
+    if !json {
+        println!(
+            "{} Profile published to {}: {}",
+            "✓".green(),
+            contract,
+            body["summary"].as_str().unwrap_or("-")
+        );
+        if body["simulated"].as_bool() == Some(true) {
+            println!(
+                "{}",
+                "  Shown as simulated: its numbers come from Soroban RPC, not a measured run."
+                    .yellow()
+            );
+        }
+    }
+    Ok(body)
+}
+
+/// A finished profile run: the profile with its fee estimate, for
+/// `--publish-to`, and with `--json` the report still to be printed
+#[derive(Debug)]
+pub struct ProfileRun {
+    pub profile: profiler::ProfileData,
+    pub report: Option<serde_json::Value>,
+}
+
+/// The fee estimate, outputs and baseline comparison shared by local and
+/// remote profiles. With `json` nothing is printed and the report is
+/// returned instead, except when the gate fails: then it is printed before
+/// failing.
+#[allow(clippy::too_many_arguments)]
+fn report_profile(
+    mut profile_data: profiler::ProfileData,
+    output: Option<&str>,
+    output_format: &str,
+    flamegraph: Option<&str>,
+    folded: Option<&str>,
+    compare: Option<&str>,
+    fees: Option<&profiler::FeeConfig>,
+    flamegraph_diff: Option<&str>,
+    gate: Option<&profiler::RegressionGate>,
+    show_recommendations: bool,
+    json: bool,
+) -> Result<ProfileRun> {
+    if let Some(fees) = fees {
+        profile_data.fee_estimate = Some(fees.estimate(&profile_data.resources));
+    }
+    if let Some(fee) = &profile_data.fee_estimate {
+        if !json {
+            print_fee_estimate(fee);
+        }
+    }
+
+    let mut written = serde_json::Map::new();
+    let mut wrote = |key: &str, label: &str, path: &str| {
+        if json {
+            written.insert(key.to_string(), json!(path));
+        } else {
+            println!("{} {} written to {}", "✓".green(), label, path);
+        }
+    };
 
     if let Some(output_path) = output {
-        let profile_json = serde_json::to_string_pretty(&profile_data)
-            .context("Failed to serialize profile data")?;
-        fs::write(output_path, profile_json)
-            .with_context(|| format!("Failed to write profile output: {}", output_path))?;
-        println!("{} Profile output written to {}", "✓".green(), output_path);
+        match output_format {
+            "json" => profiler::write_json(&profile_data, Path::new(output_path)),
+            "chrome-trace" => profiler::write_chrome_trace(&profile_data, Path::new(output_path)),
+            other => anyhow::bail!(
+                "Unsupported profile output format: {} (expected json or chrome-trace)",
+                other
+            ),
+        }
+        .with_context(|| format!("Failed to write profile output: {}", output_path))?;
+        wrote("output", "Profile output", output_path);
     }
 
     if let Some(flamegraph_path) = flamegraph {
         generate_flame_graph_file(&profile_data, flamegraph_path)
             .with_context(|| format!("Failed to generate flame graph at {}", flamegraph_path))?;
-        println!("{} Flame graph written to {}", "✓".green(), flamegraph_path);
+        wrote("flamegraph", "Flame graph", flamegraph_path);
     }
 
+    if let Some(folded_path) = folded {
+        profiler::write_folded_stacks(&profile_data, Path::new(folded_path))
+            .with_context(|| format!("Failed to write folded stacks: {}", folded_path))?;
+        wrote("folded", "Folded stacks", folded_path);
+    }
+
+    let mut gate_failures = 0;
+    let mut comparison = serde_json::Value::Null;
     if let Some(baseline_path) = compare {
-        let baseline = profiler::load_baseline(baseline_path)
+        let mut baseline = profiler::load_profile(baseline_path)
             .with_context(|| format!("Failed to load baseline profile from {}", baseline_path))?;
-        let comparisons = profiler::compare_profiles(&baseline, &profile_data);
-
-        println!("\n{}", "Profile comparison:".bold().yellow());
-        if comparisons.is_empty() {
-            println!("No comparable function data found.");
-        } else {
-            for change in comparisons.iter().take(10) {
-                let diff_ms = change.time_diff_ns as f64 / 1_000_000.0;
-                println!(
-                    "  {} [{}] {:+.2}% ({:+.3}ms)",
-                    change.function.bold(),
-                    change.status,
-                    change.time_diff_percent,
-                    diff_ms
-                );
+        let mut warnings = Vec::new();
+        if baseline.was_upgraded() {
+            let warning = format!(
+                "baseline uses profile schema v{} (current v{}); fields it did not record were filled with defaults",
+                baseline.source_version,
+                profiler::PROFILE_SCHEMA_VERSION
+            );
+            if !json {
+                println!("{}", format!("Warning: {}.", warning).yellow());
             }
-            if comparisons.len() > 10 {
-                println!("  ...and {} more", comparisons.len() - 10);
+            warnings.push(warning);
+        }
+        if let Some(mismatch) = mode_mismatch(baseline.profile.mode, profile_data.mode) {
+            if !json {
+                println!("{}", format!("WARNING: {}", mismatch).red().bold());
             }
+            warnings.push(mismatch);
         }
-    }
+        if let Some(fees) = fees {
+            baseline.profile.fee_estimate = Some(fees.estimate(&baseline.profile.resources));
+        }
+        let comparisons = profiler::compare_profiles(&baseline.profile, &profile_data);
 
-    if show_recommendations {
-        let recommendations = profiler::generate_recommendations(&profile_data);
-        println!("\n{}", "Recommendations:".bold().magenta());
-        for recommendation in recommendations {
-            println!("  - {}", recommendation);
+        if let Some(diff_path) = flamegraph_diff {
+            profiler::generate_differential_flame_graph(
+                &baseline.profile,
+                &profile_data,
+                Path::new(diff_path),
+            )
+            .with_context(|| format!("Failed to write differential flame graph: {}", diff_path))?;
+            wrote("flamegraph_diff", "Differential flame graph", diff_path);
         }
+
+        gate_failures = if json {
+            let violations = gate
+                .map(|gate| gate.violations(&comparisons))
+                .unwrap_or_default();
+            comparison = json!({
+                "baseline": baseline_path,
+                "warnings": warnings,
+                "comparisons": comparisons,
+                "fee_change": profiler::compare_fees(&baseline.profile, &profile_data),
+                "gate_violations": violations
+                    .iter()
+                    .map(|change| change.function.as_str())
+                    .collect::<Vec<_>>(),
+            });
+            violations.len()
+        } else {
+            print_comparison(&comparisons, &baseline.profile, &profile_data, gate)
+        };
     }
 
-    println!("\n{}", "=".repeat(80).cyan());
-    println!();
+    let recommendations =
+        show_recommendations.then(|| profiler::generate_recommendations(&profile_data));
+    let report = if json {
+        Some(json!({
+            "profile": profile_data,
+            "written": written,
+            "comparison": comparison,
+            "recommendations": recommendations,
+        }))
+    } else {
+        if let Some(recommendations) = recommendations {
+            println!("\n{}", "Recommendations:".bold().magenta());
+            for recommendation in recommendations {
+                println!("  - {}", recommendation);
+            }
+        }
+        println!("\n{}", "=".repeat(80).cyan());
+        println!();
+        None
+    };
 
-    Ok(())
+    if gate_failures > 0 {
+        if let Some(report) = &report {
+            println!("{}", serde_json::to_string_pretty(report)?);
+        }
+        anyhow::bail!("{} function(s) exceeded the regression gate", gate_failures);
+    }
+
+    Ok(ProfileRun {
+        profile: profile_data,
+        report,
+    })
 }
 
-#[allow(clippy::too_many_arguments)]
-pub async fn search(
-    api_url: &str,
-    query: &str,
-    network: Network,
-    verified_only: bool,
-    networks: Vec<String>,
-    category: Option<&str>,
-    limit: usize,
-    offset: usize,
+/// Compares two saved profiles, e.g. from earlier CI runs, and prints the
+/// same report as `profile --compare`. Profiles recorded in different modes
+/// are refused unless `force` is set. Fails like `profile --ci` when `gate`
+/// is exceeded.
+pub fn diff_profile(
+    baseline_path: &str,
+    current_path: &str,
+    gate: Option<&profiler::RegressionGate>,
+    force: bool,
     json: bool,
 ) -> Result<()> {
-    let t0 = std::time::Instant::now();
-    let client = reqwest::Client::new();
+    let load = |path: &str| {
+        profiler::load_profile(path)
+            .with_context(|| format!("Failed to load profile from {}", path))
+    };
+    let baseline = load(baseline_path)?;
+    let current = load(current_path)?;
+
+    let mut warnings = Vec::new();
+    if baseline.profile.contract_path != current.profile.contract_path {
+        warnings.push(format!(
+            "the profiles are of different contracts: {} (baseline) and {} (current)",
+            baseline.profile.contract_path, current.profile.contract_path
+        ));
+    }
+    for (name, loaded) in [("baseline", &baseline), ("current", &current)] {
+        if loaded.was_upgraded() {
+            warnings.push(format!(
+                "the {} profile uses schema v{} (current v{}); fields it did not record were filled with defaults",
+                name,
+                loaded.source_version,
+                profiler::PROFILE_SCHEMA_VERSION
+            ));
+        }
+    }
+    if let Some(mismatch) = mode_mismatch(baseline.profile.mode, current.profile.mode) {
+        if !force {
+            anyhow::bail!(
+                "Refusing to diff: {} Pass --force to compare anyway.",
+                mismatch
+            );
+        }
+        warnings.push(mismatch);
+    }
 
-    let mut params: Vec<(&str, String)> = vec![
-        ("query", query.to_string()),
-        ("limit", limit.to_string()),
-        ("offset", offset.to_string()),
-    ];
+    let comparisons = profiler::compare_profiles(&baseline.profile, &current.profile);
+
+    let gate_failures = if json {
+        let violations = gate
+            .map(|gate| gate.violations(&comparisons))
+            .unwrap_or_default();
+        let describe = |path: &str, profile: &profiler::ProfileData| {
+            json!({
+                "path": path,
+                "contract_path": profile.contract_path,
+                "method": profile.method,
+                "mode": profile.mode,
+                "timestamp": profile.timestamp,
+            })
+        };
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "baseline": describe(baseline_path, &baseline.profile),
+                "current": describe(current_path, &current.profile),
+                "warnings": warnings,
+                "comparisons": comparisons,
+                "fee_change": profiler::compare_fees(&baseline.profile, &current.profile),
+                "gate_violations": violations
+                    .iter()
+                    .map(|change| change.function.as_str())
+                    .collect::<Vec<_>>(),
+            }))?
+        );
+        violations.len()
+    } else {
+        println!("\n{}", "Profile diff".bold().cyan());
+        println!("{}", "=".repeat(80).cyan());
+        for warning in &warnings {
+            println!("{}", format!("WARNING: {}", warning).red().bold());
+        }
+        for (name, path, profile) in [
+            ("Baseline", baseline_path, &baseline.profile),
+            ("Current", current_path, &current.profile),
+        ] {
+            println!(
+                "{}: {} ({}, {} profile of {})",
+                name.bold(),
+                path,
+                profile.timestamp,
+                profile.mode,
+                profile.contract_path
+            );
+        }
+        let failures = print_comparison(&comparisons, &baseline.profile, &current.profile, gate);
+        println!("\n{}", "=".repeat(80).cyan());
+        failures
+    };
 
-    if !networks.is_empty() {
-        params.push(("networks", networks.join(",")));
+    if gate_failures > 0 {
+        anyhow::bail!("{} function(s) exceeded the regression gate", gate_failures);
+    }
+    Ok(())
+}
+
+/// Why comparing a `current` profile against a `baseline` recorded in
+/// another mode is misleading, or `None` when both were recorded the same way
+fn mode_mismatch(
+    baseline: profiler::ProfileMode,
+    current: profiler::ProfileMode,
+) -> Option<String> {
+    if baseline == current {
+        return None;
+    }
+    let reason = if baseline.counts_instructions() && current.counts_instructions() {
+        "Budgeted profiles estimate CPU instructions from the bytecode while simulated ones measure them, so expect differences from the engines alone."
+    } else if baseline.counts_instructions() || current.counts_instructions() {
+        "Simulated and budgeted profiles count CPU instructions rather than measuring wall-clock time, so the two cannot be compared meaningfully."
+    } else {
+        "Sampled times are estimates and count samples rather than calls, so differences may come from the profiling mode rather than the contract."
+    };
+    Some(format!(
+        "comparing a {} profile against a {} baseline. {}",
+        current, baseline, reason
+    ))
+}
+
+/// Prints the grouped comparison, the fee change and the functions that
+/// exceed `gate`, and returns how many do.
+fn print_comparison(
+    comparisons: &[profiler::ComparisonResult],
+    baseline: &profiler::ProfileData,
+    current: &profiler::ProfileData,
+    gate: Option<&profiler::RegressionGate>,
+) -> usize {
+    println!("\n{}", "Profile comparison:".bold().yellow());
+    if comparisons.is_empty() {
+        println!("No comparable function data found.");
     } else {
-        params.push(("network", network.to_string()));
+        let mode = current.mode;
+        print_comparison_section("Regressed", ChangeStatus::Slower, comparisons, mode);
+        print_comparison_section("Improved", ChangeStatus::Faster, comparisons, mode);
+        print_comparison_section("New", ChangeStatus::New, comparisons, mode);
+        print_comparison_section("Removed", ChangeStatus::Removed, comparisons, mode);
+        print_distribution_shifts(comparisons, mode);
+        let unchanged = comparisons
+            .iter()
+            .filter(|change| change.status == ChangeStatus::Unchanged)
+            .count();
+        if unchanged > 0 {
+            println!("  {} unchanged", unchanged);
+        }
+    }
+    if let Some(change) = profiler::compare_fees(baseline, current) {
+        let delta = format!(
+            "{:+} stroops{}",
+            change.diff_stroops,
+            change
+                .diff_percent
+                .map(|percent| format!(", {:+.2}%", percent))
+                .unwrap_or_default()
+        );
+        let delta = match change.diff_stroops {
+            diff if diff > 0 => delta.red(),
+            diff if diff < 0 => delta.green(),
+            _ => delta.normal(),
+        };
+        println!(
+            "  {}: {} -> {} stroops ({})",
+            "Estimated fee".bold(),
+            change.baseline_stroops,
+            change.current_stroops,
+            delta
+        );
     }
 
-    if verified_only {
-        params.push(("verified_only", "true".to_string()));
+    let Some(gate) = gate else {
+        return 0;
+    };
+    let violations = gate.violations(comparisons);
+    for change in &violations {
+        let reason = match change.time_diff_percent {
+            Some(percent) if change.status == ChangeStatus::Slower => {
+                format!("{:+.2}% exceeds {:.2}%", percent, gate.max_slowdown_percent)
+            }
+            _ => format!(
+                "new, {} exceeds {}",
+                format_time(change.current_time, current.mode),
+                format_time(gate.max_new_function_time, current.mode)
+            ),
+        };
+        println!("{} {} {}", "✗".red(), change.function.bold(), reason.red());
     }
+    violations.len()
+}
 
-    if let Some(cat) = category {
-        params.push(("category", cat.to_string()));
+/// Prints the comparison results with `status` under `title`, largest change
+/// first.
+fn print_comparison_section(
+    title: &str,
+    status: ChangeStatus,
+    comparisons: &[profiler::ComparisonResult],
+    mode: profiler::ProfileMode,
+) {
+    let changes: Vec<_> = comparisons
+        .iter()
+        .filter(|change| change.status == status)
+        .collect();
+    if changes.is_empty() {
+        return;
     }
 
-    let response = client
-        .get(format!("{}/api/contracts", api_url))
-        .query(&params)
-        .send()
-        .await
-        .context("Failed to search contracts")?;
+    println!("  {} ({})", title.bold(), changes.len());
+    for change in changes.iter().take(10) {
+        let baseline = format_time(change.baseline_time, mode);
+        let current = format_time(change.current_time, mode);
+        match status {
+            ChangeStatus::New => println!("    {} {}", change.function, current),
+            ChangeStatus::Removed => println!("    {} {}", change.function, baseline),
+            _ => println!(
+                "    {} {:+.2}% ({} -> {})",
+                change.function,
+                change.time_diff_percent.unwrap_or_default(),
+                baseline,
+                current
+            ),
+        }
+    }
+    if changes.len() > 10 {
+        println!("    ...and {} more", changes.len() - 10);
+    }
+}
 
-    let data: serde_json::Value = response.json().await?;
-    let items = data["items"].as_array().context("Invalid response")?;
+/// Prints the fee per invocation and what each resource adds to it, marking
+/// the amounts that were assumed rather than measured.
+fn print_fee_estimate(fee: &profiler::FeeEstimate) {
+    println!(
+        "\n{}: {} stroops ({:.7} XLM) per invocation",
+        "Estimated fee".bold(),
+        fee.total_stroops,
+        fee.total_xlm()
+    );
+    for component in &fee.components {
+        let source = if component.measured {
+            "measured".green()
+        } else {
+            "assumed".yellow()
+        };
+        println!(
+            "  {:<24} {:>12} {:>10} stroops  {}",
+            component.resource.to_string(),
+            component.amount,
+            component.stroops,
+            source
+        );
+    }
+    println!(
+        "  {:<24} {:>12} {:>10} stroops",
+        "inclusion fee", "", fee.inclusion_fee
+    );
 
-    if json {
-        let contracts: Vec<serde_json::Value> = items
-            .iter()
-            .map(|c| -> Result<_> {
-                let contract_id = crate::conversions::as_str(&c["contract_id"], "contract_id")?;
-                Ok(serde_json::json!({
-                    "id":          contract_id.clone(),
-                    "name":        crate::conversions::as_str(&c["name"], "name")?,
-                    "is_verified": crate::conversions::as_bool(&c["is_verified"], "is_verified")?,
-                    "network":     crate::conversions::as_str(&c["network"], "network")?,
-                    "category":    c["category"].as_str().unwrap_or(""),
-                    "links": { "detail": format!("{}/contracts/{}", api_url, contract_id) },
-                }))
-            })
-            .collect::<Result<_, _>>()?;
+    let assumed = fee.assumed().count();
+    if assumed > 0 {
         println!(
             "{}",
-            serde_json::to_string_pretty(&serde_json::json!({ "contracts": contracts }))?
+            format!(
+                "Note: {} of {} resources were not measured by this profiling engine; their amounts come from assumed_usage in the fee config.",
+                assumed,
+                fee.components.len()
+            )
+            .yellow()
         );
+    }
+}
+
+/// Prints the functions whose p99 regressed while their mean held.
+fn print_distribution_shifts(
+    comparisons: &[profiler::ComparisonResult],
+    mode: profiler::ProfileMode,
+) {
+    let shifts: Vec<_> = comparisons
+        .iter()
+        .filter(|change| change.distribution_shift)
+        .collect();
+    if shifts.is_empty() {
+        return;
+    }
+
+    println!("  {} ({})", "Distribution shifts".bold(), shifts.len());
+    for change in shifts {
+        let p99 = |nanos: Option<u64>| {
+            format_time(
+                std::time::Duration::from_nanos(nanos.unwrap_or_default()),
+                mode,
+            )
+        };
+        println!(
+            "    {} p99 {} -> {}",
+            change.function,
+            p99(change.baseline_p99_ns),
+            p99(change.current_p99_ns)
+        );
+    }
+}
+
+/// A profiled time in milliseconds, or for a simulated or budgeted profile
+/// the CPU instructions it stands for
+fn format_time(time: std::time::Duration, mode: profiler::ProfileMode) -> String {
+    if mode.counts_instructions() {
+        format!("{} instructions", time.as_nanos())
+    } else {
+        format!("{:.3}ms", time.as_secs_f64() * 1000.0)
+    }
+}
+
+/// What a simulated invocation used
+fn print_resources(resources: &profiler::ResourceUsage) {
+    let amount = |value: Option<u64>| {
+        value
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
+    println!("\n{}", "Resources per invocation:".bold());
+    println!(
+        "  {:<24} {:>12}",
+        "CPU instructions",
+        amount(resources.cpu_instructions)
+    );
+    println!(
+        "  {:<24} {:>12} bytes",
+        "Memory",
+        amount(resources.memory_bytes)
+    );
+    println!(
+        "  {:<24} {:>12} read, {} written",
+        "Ledger entries",
+        amount(resources.read_entries),
+        amount(resources.write_entries)
+    );
+    println!(
+        "  {:<24} {:>12} read, {} written",
+        "Ledger bytes",
+        amount(resources.read_bytes),
+        amount(resources.write_bytes)
+    );
+    println!(
+        "  {:<24} {:>12} bytes",
+        "Transaction size",
+        amount(resources.transaction_size_bytes)
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn search(
+    api_url: &str,
+    query: &str,
+    network: Network,
+    verified_only: bool,
+    networks: Vec<String>,
+    category: Option<&str>,
+    publisher: Option<&str>,
+    org: Option<&str>,
+    limit: usize,
+    offset: usize,
+    json: bool,
+) -> Result<()> {
+    let t0 = std::time::Instant::now();
+    let client = crate::http::client();
+
+    // Filters without the text query, so the scope can be counted on its own
+    let filters = search_filters(network, verified_only, &networks, category, publisher, org);
+
+    let mut params = filters.clone();
+    params.push(("query", query.to_string()));
+    // Within a scope, only the matches count, for "3 matches among 17"
+    if publisher.is_some() || org.is_some() {
+        params.push(("matches_only", "true".to_string()));
+    }
+    params.push(("limit", limit.to_string()));
+    params.push(("offset", offset.to_string()));
+
+    let page = fetch_contract_page(&client, api_url, &params, org).await?;
+    let items = &page.items;
+
+    if json {
+        print_search_json(api_url, items)?;
         return Ok(());
     }
 
@@ -193,6 +1095,12 @@ pub async fn search(
     if let Some(cat) = category {
         active_filters.push(format!("category: {}", cat));
     }
+    if let Some(address) = publisher {
+        active_filters.push(format!("publisher: {}", address));
+    }
+    if let Some(slug) = org {
+        active_filters.push(format!("organization: {}", slug));
+    }
     if verified_only {
         active_filters.push("verified only".to_string());
     }
@@ -217,10 +1125,117 @@ pub async fn search(
         if verified_only {
             println!("  • Remove --verified-only to include unverified contracts");
         }
+        if publisher.is_some() || org.is_some() {
+            println!("  • Remove --publisher/--org to search the whole registry");
+        }
         println!("  • Use 'list' command to browse all contracts\n");
         return Ok(());
     }
 
+    print_search_table(api_url, items, query)?;
+
+    let elapsed_ms = t0.elapsed().as_millis();
+    println!(
+        "\n{} {} result(s) for \"{}\"  |  {}ms\n",
+        "→".cyan(),
+        items.len(),
+        query.bold(),
+        elapsed_ms
+    );
+
+    let scope = match (publisher, org) {
+        (Some(address), _) => Some(format!(
+            "by {}",
+            crate::table_format::short_address(address)
+        )),
+        (None, Some(slug)) => Some(format!("in {}", slug)),
+        (None, None) => None,
+    };
+    if let Some(scope) = scope.filter(|_| !query.trim().is_empty()) {
+        let mut scope_params = filters;
+        scope_params.push(("limit", "1".to_string()));
+        let scope_page = fetch_contract_page(&client, api_url, &scope_params, org).await?;
+        println!(
+            "{} {}",
+            "→".cyan(),
+            crate::table_format::scope_summary(page.total, scope_page.total, &scope)
+        );
+    }
+    println!("{}\n", crate::table_format::page_summary(&page));
+
+    Ok(())
+}
+
+/// `GET /api/contracts` query parameters for the `search` filters, without
+/// the text query and paging
+pub(crate) fn search_filters(
+    network: Network,
+    verified_only: bool,
+    networks: &[String],
+    category: Option<&str>,
+    publisher: Option<&str>,
+    org: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let mut filters: Vec<(&str, String)> = Vec::new();
+
+    if !networks.is_empty() {
+        filters.push(("networks", networks.join(",")));
+    } else {
+        filters.push(("network", network.to_string()));
+    }
+
+    if verified_only {
+        filters.push(("verified_only", "true".to_string()));
+    }
+
+    if let Some(cat) = category {
+        filters.push(("category", cat.to_string()));
+    }
+
+    if let Some(address) = publisher {
+        filters.push(("publisher_address", address.to_string()));
+    }
+
+    if let Some(slug) = org {
+        filters.push(("organization", slug.to_string()));
+    }
+
+    filters
+}
+
+/// `{"contracts": [...]}` for `search --json`
+pub(crate) fn print_search_json(api_url: &str, items: &[serde_json::Value]) -> Result<()> {
+    let contracts: Vec<serde_json::Value> = items
+        .iter()
+        .map(|c| -> Result<_> {
+            let contract_id = crate::conversions::as_str(&c["contract_id"], "contract_id")?;
+            let mut contract = serde_json::json!({
+                "id":          contract_id.clone(),
+                "name":        crate::conversions::as_str(&c["name"], "name")?,
+                "is_verified": crate::conversions::as_bool(&c["is_verified"], "is_verified")?,
+                "network":     crate::conversions::as_str(&c["network"], "network")?,
+                "category":    c["category"].as_str().unwrap_or(""),
+                "links": { "detail": format!("{}/contracts/{}", api_url, contract_id) },
+            });
+            if let Some(highlights) = c.get("highlights") {
+                contract["highlights"] = highlights.clone();
+            }
+            Ok(contract)
+        })
+        .collect::<Result<_, _>>()?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&serde_json::json!({ "contracts": contracts }))?
+    );
+    Ok(())
+}
+
+/// Search results as a table, with matches of `query` highlighted
+pub(crate) fn print_search_table(
+    api_url: &str,
+    items: &[serde_json::Value],
+    query: &str,
+) -> Result<()> {
     // Compute visible column widths from raw data (before applying ANSI codes).
     let name_w = items
         .iter()
@@ -256,7 +1271,19 @@ pub async fn search(
         .min(60);
 
     let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut details: Vec<Option<String>> = Vec::new();
     for contract in items {
+        // Only present when the description matched
+        let highlights: Vec<shared::search::SearchHighlight> =
+            serde_json::from_value(contract["highlights"].clone()).unwrap_or_default();
+        details.push(highlights.first().map(|highlight| {
+            format!(
+                "{} {}",
+                "↳".bright_black(),
+                crate::table_format::render_highlight(highlight)
+            )
+        }));
+
         let name = crate::conversions::as_str(&contract["name"], "name")?;
         let contract_id = crate::conversions::as_str(&contract["contract_id"], "contract_id")?;
         let is_verified = crate::conversions::as_bool(&contract["is_verified"], "is_verified")?;
@@ -264,7 +1291,10 @@ pub async fn search(
         let cat = contract["category"].as_str().unwrap_or("").to_string();
         let link = format!("{}/contracts/{}", api_url, contract_id);
 
-        let name_cell = crate::table_format::highlight_match(&name, query);
+        let mut name_cell = crate::table_format::highlight_match(&name, query);
+        if is_stale(contract) {
+            name_cell.push_str(&format!(" {}", "[stale]".bright_black()));
+        }
         let net_cell = net.bright_blue().to_string();
         let cat_display = if cat.is_empty() {
             "—".to_string()
@@ -286,19 +1316,66 @@ pub async fn search(
     let headers = ["Name", "Network", "Category", "Verified", "Links"];
     print!(
         "{}",
-        crate::table_format::render_table(&headers, &col_widths, &rows)
+        crate::table_format::render_table_with_details(&headers, &col_widths, &rows, &details)
     );
+    Ok(())
+}
 
-    let elapsed_ms = t0.elapsed().as_millis();
-    println!(
-        "\n{} {} result(s) for \"{}\"  |  {}ms\n",
-        "→".cyan(),
-        items.len(),
-        query.bold(),
-        elapsed_ms
-    );
+/// Whether the registry marked the contract stale: no new version,
+/// on-chain confirmation or publisher activity for a long time
+fn is_stale(contract: &serde_json::Value) -> bool {
+    contract["freshness"]["stale"].as_bool() == Some(true)
+}
 
-    Ok(())
+/// `freshness` of a contract response in one line, e.g.
+/// "STALE · last version 412d ago · on chain 97d ago · publisher 380d ago"
+fn freshness_line(freshness: &serde_json::Value) -> Option<String> {
+    let ago = |field: &str| match freshness[field].as_i64() {
+        Some(days) => format!("{}d ago", days),
+        None => "never".to_string(),
+    };
+    if !freshness.is_object() {
+        return None;
+    }
+    let status = if freshness["stale"].as_bool() == Some(true) {
+        "STALE".red().bold().to_string()
+    } else {
+        "fresh".green().to_string()
+    };
+    Some(format!(
+        "{} · last version {} · on chain {} · publisher {}",
+        status,
+        ago("days_since_version"),
+        ago("days_since_onchain_confirmation"),
+        ago("days_since_publisher_activity")
+    ))
+}
+
+/// One page of `GET /api/contracts`. An unknown `org` is reported as such
+/// rather than as a generic 404.
+pub(crate) async fn fetch_contract_page(
+    client: &crate::http::Client,
+    api_url: &str,
+    params: &[(&str, String)],
+    org: Option<&str>,
+) -> Result<Paginated<serde_json::Value>> {
+    let response = client
+        .get(format!("{}/api/contracts", api_url))
+        .query(params)
+        .send()
+        .await
+        .context("Failed to search contracts")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        if body["error"]["details"]["reason"] == "OrganizationNotFound" {
+            anyhow::bail!("organization not found: {}", org.unwrap_or_default());
+        }
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Failed to search contracts: {} ({})", message, status);
+    }
+    response.json().await.context("Invalid response")
 }
 
 /// Analyze two contract versions or schema files for breaking changes.
@@ -340,7 +1417,7 @@ pub async fn upgrade_analyze(
     }
 
     // Otherwise try to fetch versions from the API (assumes endpoint exists)
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contract_versions/{}", api_url, old_id);
     let old_res = client
         .get(&url)
@@ -467,6 +1544,8 @@ fn resolve_smart_routing(current_network: Network) -> String {
     }
 }
 
+/// Registers a contract. With `json` the created contract is printed as the
+/// API returned it instead of a summary.
 #[allow(clippy::too_many_arguments)]
 pub async fn publish(
     api_url: &str,
@@ -477,9 +1556,13 @@ pub async fn publish(
     category: Option<&str>,
     tags: Vec<String>,
     publisher: &str,
+    organization: Option<&str>,
+    slug: Option<&str>,
     is_cicd: bool,
+    auth_token: Option<&str>,
+    json: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts", api_url);
 
     let mut payload = json!({
@@ -492,18 +1575,27 @@ pub async fn publish(
         "publisher_address": publisher,
     });
 
+    if let Some(organization) = organization {
+        payload["organization"] = json!(organization);
+    }
+
+    if let Some(slug) = slug {
+        payload["slug"] = json!(slug);
+    }
+
     if is_cicd {
         payload["is_cicd"] = json!(true);
     }
 
-    println!("\n{}", "Publishing contract...".bold().cyan());
+    if !json {
+        println!("\n{}", "Publishing contract...".bold().cyan());
+    }
 
-    let response = client
-        .post(&url)
-        .json(&payload)
-        .send()
-        .await
-        .context("Failed to publish contract")?;
+    let mut request = client.post(&url).json(&payload);
+    if let Some(token) = auth_token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.context("Failed to publish contract")?;
 
     if !response.status().is_success() {
         let error_text = response.text().await?;
@@ -511,6 +1603,10 @@ pub async fn publish(
     }
 
     let contract: serde_json::Value = response.json().await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&contract)?);
+        return Ok(());
+    }
 
     println!("{}", "✓ Contract published successfully!".green().bold());
     println!(
@@ -528,17 +1624,82 @@ pub async fn publish(
         "Network".bold(),
         crate::conversions::as_str(&contract["network"], "network")?.bright_blue()
     );
+    if let (Some(namespace), Some(slug)) =
+        (contract["namespace"].as_str(), contract["slug"].as_str())
+    {
+        println!("{}: {}/{}", "Registry name".bold(), namespace, slug);
+    }
     println!();
 
     Ok(())
 }
 
-pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) -> Result<()> {
-    let client = reqwest::Client::new();
+/// Hands a contract over to another publisher through
+/// `PATCH /api/contracts/{id}/publisher`
+pub async fn transfer(
+    api_url: &str,
+    contract_id: &str,
+    new_publisher: &str,
+    auth_token: Option<&str>,
+) -> Result<()> {
     let url = format!(
-        "{}/api/contracts?page_size={}&network={}",
-        api_url, limit, network
+        "{}/api/contracts/{}/publisher",
+        api_url.trim_end_matches('/'),
+        contract_id
+    );
+    let mut request = crate::http::client()
+        .patch(&url)
+        .json(&json!({ "publisher_address": new_publisher }));
+    if let Some(token) = auth_token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .context("Failed to transfer contract")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Failed to transfer: {} ({})", message, status);
+    }
+
+    let contract: serde_json::Value = response.json().await?;
+    println!("{}", "✓ Contract transferred".green().bold());
+    println!(
+        "\n{}: {}",
+        "Name".bold(),
+        crate::conversions::as_str(&contract["name"], "name")?
+    );
+    println!(
+        "{}: {}",
+        "New publisher".bold(),
+        new_publisher.bright_cyan()
     );
+    println!();
+
+    Ok(())
+}
+
+pub async fn list(
+    api_url: &str,
+    limit: usize,
+    network: Network,
+    organization: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let url = match organization {
+        Some(org) => format!(
+            "{}/api/organizations/{}/contracts?limit={}",
+            api_url, org, limit
+        ),
+        None => format!(
+            "{}/api/contracts?limit={}&network={}",
+            api_url, limit, network
+        ),
+    };
 
     let response = client
         .get(&url)
@@ -546,8 +1707,13 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) ->
         .await
         .context("Failed to list contracts")?;
 
-    let data: serde_json::Value = response.json().await?;
-    let items = data["items"].as_array().context("Invalid response")?;
+    if !response.status().is_success() {
+        let error_text = response.text().await?;
+        anyhow::bail!("Failed to list contracts: {}", error_text);
+    }
+
+    let page: Paginated<serde_json::Value> = response.json().await.context("Invalid response")?;
+    let items = &page.items;
 
     if json {
         let contracts: Vec<serde_json::Value> = items
@@ -568,7 +1734,10 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) ->
         return Ok(());
     }
 
-    println!("\n{}", "Recent Contracts:".bold().cyan());
+    match organization {
+        Some(org) => println!("\n{} {}", "Contracts owned by".bold().cyan(), org.bold()),
+        None => println!("\n{}", "Recent Contracts:".bold().cyan()),
+    }
     println!("{}", "=".repeat(80).cyan());
 
     if items.is_empty() {
@@ -581,22 +1750,38 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) ->
         let contract_id = crate::conversions::as_str(&contract["contract_id"], "contract_id")?;
         let is_verified = crate::conversions::as_bool(&contract["is_verified"], "is_verified")?;
         let network = crate::conversions::as_str(&contract["network"], "network")?;
+        let published = contract["created_at"]
+            .as_str()
+            .map(crate::time_format::show_rfc3339);
 
         println!(
-            "\n{}. {} {}",
+            "\n{}. {} {}{}",
             i + 1,
             name.bold(),
             if is_verified {
                 "✓".green()
             } else {
                 "".normal()
+            },
+            if is_stale(contract) {
+                " [stale]".bright_black()
+            } else {
+                "".normal()
             }
         );
-        println!(
-            "   {} | {}",
-            contract_id.bright_black(),
-            network.bright_blue()
-        );
+        match published {
+            Some(published) => println!(
+                "   {} | {} | {}",
+                contract_id.bright_black(),
+                network.bright_blue(),
+                published.dimmed()
+            ),
+            None => println!(
+                "   {} | {}",
+                contract_id.bright_black(),
+                network.bright_blue()
+            ),
+        }
     }
 
     println!("\n{}", "=".repeat(80).cyan());
@@ -605,6 +1790,121 @@ pub async fn list(api_url: &str, limit: usize, network: Network, json: bool) ->
     Ok(())
 }
 
+/// A contract from `GET /api/me/contracts`
+#[derive(Debug, Serialize, Deserialize)]
+struct OwnedContract {
+    contract_id: String,
+    name: String,
+    network: String,
+    owned_via: String,
+    verification_status: String,
+    on_chain_status: String,
+    deprecated_at: Option<String>,
+    retirement_at: Option<String>,
+}
+
+/// Lists the contracts the caller's token owns, directly or through an
+/// organization, including the private and unverified ones `list` hides
+pub async fn list_mine(
+    api_url: &str,
+    limit: usize,
+    network: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    if crate::http::api_token().is_none() {
+        anyhow::bail!(
+            "--mine lists the contracts of the token's publisher; set {} first (`soroban-registry doctor` checks it)",
+            crate::http::token_env()
+        );
+    }
+
+    let mut url = format!("{}/api/me/contracts?limit={}", api_url, limit);
+    if let Some(network) = network {
+        url.push_str(&format!("&network={}", network));
+    }
+    let response = crate::http::client()
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to list contracts")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Failed to list contracts: {} ({})", message, status);
+    }
+
+    let page: Paginated<OwnedContract> = response.json().await.context("Invalid response")?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({ "contracts": page.items, "total": page.total }))?
+        );
+        return Ok(());
+    }
+
+    if page.items.is_empty() {
+        println!("{}", "You don't own any contracts yet.".yellow());
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = page
+        .items
+        .iter()
+        .map(|c| {
+            vec![
+                c.name.clone(),
+                c.contract_id.clone(),
+                c.network.clone(),
+                c.verification_status.clone(),
+                c.on_chain_status.replace('_', " "),
+            ]
+        })
+        .collect();
+    let details: Vec<Option<String>> = page.items.iter().map(describe_ownership).collect();
+    print!(
+        "{}",
+        crate::table_format::render_table_with_details(
+            &["NAME", "CONTRACT", "NETWORK", "VERIFICATION", "ON-CHAIN"],
+            &[24, 56, 10, 12, 14],
+            &rows,
+            &details,
+        )
+    );
+    if page.total > page.items.len() as i64 {
+        println!(
+            "{}",
+            format!(
+                "Showing {} of {}; raise --limit to see more.",
+                page.items.len(),
+                page.total
+            )
+            .bright_black()
+        );
+    }
+    Ok(())
+}
+
+/// Detail line for contracts that are deprecated or owned through an
+/// organization
+fn describe_ownership(contract: &OwnedContract) -> Option<String> {
+    let mut notes = Vec::new();
+    if contract.owned_via == "organization" {
+        notes.push("owned by your organization".to_string());
+    }
+    if let Some(deprecated_at) = &contract.deprecated_at {
+        let retires = contract.retirement_at.as_deref().unwrap_or("unscheduled");
+        notes.push(format!(
+            "deprecated {} (retires {})",
+            &deprecated_at[..deprecated_at.len().min(10)],
+            &retires[..retires.len().min(10)]
+        ));
+    }
+    (!notes.is_empty()).then(|| notes.join("; "))
+}
+
 fn extract_migration_id(migration: &serde_json::Value) -> Result<String> {
     let Some(migration_id) = migration["id"].as_str() else {
         eprintln!(
@@ -617,7 +1917,7 @@ fn extract_migration_id(migration: &serde_json::Value) -> Result<String> {
     Ok(migration_id.to_string())
 }
 pub async fn breaking_changes(api_url: &str, old_id: &str, new_id: &str, json: bool) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!(
         "{}/api/contracts/breaking-changes?old_id={}&new_id={}",
         api_url, old_id, new_id
@@ -641,39 +1941,167 @@ pub async fn breaking_changes(api_url: &str, old_id: &str, new_id: &str, json: b
         return Ok(());
     }
 
-    let breaking = crate::conversions::as_bool(&report["breaking"], "breaking")?;
-    let breaking_count = crate::conversions::as_u64(&report["breaking_count"], "breaking_count")?;
-    let non_breaking_count =
-        crate::conversions::as_u64(&report["non_breaking_count"], "non_breaking_count")?;
-
-    let header = if breaking {
-        "Breaking changes detected".red().bold()
-    } else {
-        "No breaking changes detected".green().bold()
+    let breaking = crate::conversions::as_bool(&report["breaking"], "breaking")?;
+    let breaking_count = crate::conversions::as_u64(&report["breaking_count"], "breaking_count")?;
+    let non_breaking_count =
+        crate::conversions::as_u64(&report["non_breaking_count"], "non_breaking_count")?;
+
+    let header = if breaking {
+        "Breaking changes detected".red().bold()
+    } else {
+        "No breaking changes detected".green().bold()
+    };
+
+    println!("\n{}", header);
+    println!(
+        "{} {} | {} {}",
+        "Breaking:".bold(),
+        breaking_count,
+        "Non-breaking:".bold(),
+        non_breaking_count
+    );
+
+    if let Some(changes) = report["changes"].as_array() {
+        for change in changes {
+            let severity = crate::conversions::as_str(&change["severity"], "severity")?;
+            let message = crate::conversions::as_str(&change["message"], "message")?;
+            let label = if severity == "breaking" {
+                "BREAKING".red().bold()
+            } else {
+                "INFO".yellow().bold()
+            };
+            println!("  {} {}", label, message);
+        }
+    }
+
+    Ok(())
+}
+
+/// Compare two contracts, or two versions of one, as `<id>[@<version>]`.
+pub async fn diff(api_url: &str, a: &str, b: &str, json: bool) -> Result<()> {
+    let client = crate::http::client();
+    let response = client
+        .get(format!("{}/api/contracts/compare", api_url))
+        .query(&[("a", a), ("b", b)])
+        .send()
+        .await
+        .context("Failed to compare contracts")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Failed to compare contracts: {} ({})", message, status);
+    }
+
+    let report: serde_json::Value = response.json().await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let side = |s: &serde_json::Value| match s["version"].as_str() {
+        Some(version) => format!("{}@{}", s["name"].as_str().unwrap_or("?"), version),
+        None => s["name"].as_str().unwrap_or("?").to_string(),
     };
+    println!(
+        "\n{} {} {}",
+        side(&report["a"]).bold(),
+        "→".bright_black(),
+        side(&report["b"]).bold()
+    );
 
-    println!("\n{}", header);
+    if crate::conversions::as_bool(&report["identical"], "identical")? {
+        println!("{}", "Identical".green().bold());
+        return Ok(());
+    }
+
+    let same_wasm = crate::conversions::as_bool(&report["same_wasm"], "same_wasm")?;
     println!(
-        "{} {} | {} {}",
-        "Breaking:".bold(),
-        breaking_count,
-        "Non-breaking:".bold(),
-        non_breaking_count
+        "{} {}",
+        "WASM:".bold(),
+        if same_wasm {
+            "same".green()
+        } else {
+            "different".yellow()
+        }
     );
 
-    if let Some(changes) = report["changes"].as_array() {
+    if let Some(changes) = report["metadata"].as_array().filter(|c| !c.is_empty()) {
+        println!("\n{}", "Metadata".bold());
         for change in changes {
-            let severity = crate::conversions::as_str(&change["severity"], "severity")?;
-            let message = crate::conversions::as_str(&change["message"], "message")?;
-            let label = if severity == "breaking" {
-                "BREAKING".red().bold()
-            } else {
-                "INFO".yellow().bold()
-            };
-            println!("  {} {}", label, message);
+            let field = crate::conversions::as_str(&change["field"], "field")?;
+            println!(
+                "  {:<14} {} {} {}",
+                field,
+                change["before"].to_string().red(),
+                "→".bright_black(),
+                change["after"].to_string().green()
+            );
+        }
+    }
+
+    let tags = |key: &str| -> Vec<String> {
+        report[key]
+            .as_array()
+            .map(|t| {
+                t.iter()
+                    .filter_map(|t| t.as_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let (added, removed) = (tags("tags_added"), tags("tags_removed"));
+    if !added.is_empty() || !removed.is_empty() {
+        println!("\n{}", "Tags".bold());
+        for tag in &added {
+            println!("  {} {}", "+".green(), tag);
+        }
+        for tag in &removed {
+            println!("  {} {}", "-".red(), tag);
         }
     }
 
+    let interface = &report["interface"];
+    if interface.is_null() {
+        println!(
+            "\n{}",
+            "Interface not compared: no ABI stored for one or both sides".bright_black()
+        );
+        return Ok(());
+    }
+
+    println!("\n{}", "Interface".bold());
+    for name in interface["functions_added"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        println!("  {} {}", "+".green(), name.as_str().unwrap_or_default());
+    }
+    for name in interface["functions_removed"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        println!("  {} {}", "-".red(), name.as_str().unwrap_or_default());
+    }
+    for change in interface["signatures_changed"]
+        .as_array()
+        .into_iter()
+        .flatten()
+    {
+        let before = crate::conversions::as_str(&change["before"], "before")?;
+        let after = crate::conversions::as_str(&change["after"], "after")?;
+        println!("  {} {}", "~".yellow(), before.red());
+        println!("    {}", after.green());
+    }
+    if crate::conversions::as_bool(&interface["breaking"], "breaking")? {
+        println!("\n{}", "Breaking changes detected".red().bold());
+    }
+
     Ok(())
 }
 
@@ -715,7 +2143,7 @@ pub async fn migrate(
     }
 
     // 3. Create Migration Record (Pending)
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let create_url = format!("{}/api/migrations", api_url);
 
     let payload = json!({
@@ -836,6 +2264,172 @@ pub async fn export(_api_url: &str, id: &str, output: &str, contract_dir: &str)
     Ok(())
 }
 
+/// What `verify-source` submits as the contract's source
+pub enum VerifySource<'a> {
+    /// A `lib.rs`, or a directory containing `lib.rs` or `src/lib.rs`
+    Rust(&'a str),
+    /// A prebuilt WASM module, compared as-is
+    Wasm(&'a str),
+}
+
+/// Reads the source to verify, in the form `POST /api/contracts/verify` takes
+fn read_verify_source(source: &VerifySource) -> Result<String> {
+    match source {
+        VerifySource::Wasm(path) => {
+            use base64::Engine as _;
+            let wasm = fs::read(path).with_context(|| format!("Failed to read {}", path))?;
+            Ok(format!(
+                "wasm_base64:{}",
+                base64::engine::general_purpose::STANDARD.encode(wasm)
+            ))
+        }
+        VerifySource::Rust(path) => {
+            let path = Path::new(path);
+            let file = if path.is_dir() {
+                [path.join("lib.rs"), path.join("src").join("lib.rs")]
+                    .into_iter()
+                    .find(|candidate| candidate.is_file())
+                    .with_context(|| format!("No lib.rs or src/lib.rs in {}", path.display()))?
+            } else {
+                path.to_path_buf()
+            };
+            fs::read_to_string(&file).with_context(|| format!("Failed to read {}", file.display()))
+        }
+    }
+}
+
+/// Submits a contract's source for verification. The registry builds it in
+/// the background; unless `no_wait`, the job is followed until it finishes.
+#[allow(clippy::too_many_arguments)]
+pub async fn verify_source(
+    api_url: &str,
+    contract_id: &str,
+    source: VerifySource<'_>,
+    compiler_version: &str,
+    build_params: Option<&str>,
+    cargo_lock: Option<&str>,
+    source_url: Option<&str>,
+    commit_hash: Option<&str>,
+    no_wait: bool,
+    json: bool,
+) -> Result<()> {
+    let source_code = read_verify_source(&source)?;
+    let build_params: serde_json::Value = match build_params {
+        Some(raw) => serde_json::from_str(raw).context("--build-params must be a JSON object")?,
+        None => json!({}),
+    };
+    let cargo_lock = cargo_lock
+        .map(|path| fs::read_to_string(path).with_context(|| format!("Failed to read {}", path)))
+        .transpose()?;
+
+    let response = crate::http::client()
+        .post(format!("{}/api/contracts/verify", api_url))
+        .json(&json!({
+            "contract_id": contract_id,
+            "source_code": source_code,
+            "build_params": build_params,
+            "compiler_version": compiler_version,
+            "cargo_lock": cargo_lock,
+            "source_url": source_url,
+            "commit_hash": commit_hash,
+        }))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let job: crate::jobs::Job = crate::jobs::error_for_status(response, "submit verification")
+        .await?
+        .json()
+        .await
+        .context("Failed to parse verification job")?;
+
+    if no_wait {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&job)?);
+        } else {
+            crate::jobs::print_queued(&job, "Verification");
+        }
+        return Ok(());
+    }
+
+    let job = crate::jobs::wait(api_url, job, "Verifying", json).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&job)?);
+    }
+    crate::jobs::ensure_succeeded(&job, "Verification")?;
+    if json {
+        return Ok(());
+    }
+
+    let result = job.result.unwrap_or_default();
+    println!("{}", "✓ Contract verified!".green().bold());
+    println!("  {}: {}", "Contract".bold(), contract_id);
+    if let Some(id) = result["verification_id"].as_str() {
+        println!("  {}: {}", "Verification".bold(), id.bright_black());
+    }
+    if let Some(hash) = result["compiled_wasm_hash"].as_str() {
+        println!("  {}: {}", "WASM hash".bold(), hash.bright_black());
+    }
+    if result["sbom_available"].as_bool() == Some(true) {
+        println!("  {}: available", "SBOM".bold());
+    }
+    println!();
+    Ok(())
+}
+
+/// Exports the registry into the blob store (admin). Like `verify_source`,
+/// the export runs as a job that is followed unless `no_wait`.
+pub async fn snapshot_export(api_url: &str, no_wait: bool, json: bool) -> Result<()> {
+    let response = crate::http::client()
+        .post(format!("{}/api/admin/snapshots", api_url))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let job: crate::jobs::Job = crate::jobs::error_for_status(response, "start snapshot export")
+        .await?
+        .json()
+        .await
+        .context("Failed to parse snapshot job")?;
+
+    if no_wait {
+        if json {
+            println!("{}", serde_json::to_string_pretty(&job)?);
+        } else {
+            crate::jobs::print_queued(&job, "Snapshot export");
+        }
+        return Ok(());
+    }
+
+    let job = crate::jobs::wait(api_url, job, "Exporting registry", json).await?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&job)?);
+    }
+    crate::jobs::ensure_succeeded(&job, "Snapshot export")?;
+    if json {
+        return Ok(());
+    }
+
+    let snapshot = job.result.unwrap_or_default();
+    println!("{}", "✓ Snapshot exported!".green().bold());
+    println!(
+        "  {}: {}",
+        "Snapshot".bold(),
+        snapshot["id"].as_str().unwrap_or("-")
+    );
+    if let Some(size) = snapshot["size_bytes"].as_i64() {
+        println!("  {}: {} bytes", "Size".bold(), size);
+    }
+    if let Some(sha256) = snapshot["sha256"].as_str() {
+        println!("  {}: {}", "SHA-256".bold(), sha256.bright_black());
+    }
+    if let Some(counts) = snapshot["row_counts"].as_object() {
+        for (table, rows) in counts {
+            println!("    {:<28} {}", table, rows);
+        }
+    }
+    println!();
+    Ok(())
+}
+
 pub async fn import(
     api_url: &str,
     archive: &str,
@@ -943,7 +2537,7 @@ pub async fn trust_score(api_url: &str, contract_id: &str, network: Network) ->
     let url = format!("{}/api/contracts/{}/trust-score", api_url, contract_id);
     log::debug!("GET {}", url);
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .get(&url)
         .query(&[("network", network.to_string())])
@@ -1063,7 +2657,7 @@ pub async fn patch_apply(api_url: &str, contract_id: &str, patch_id: &str) -> Re
 }
 
 pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/dependencies", api_url, contract_id);
 
     let response = client
@@ -1090,7 +2684,28 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
         return Ok(());
     }
 
-    fn print_tree(nodes: &[serde_json::Value], prefix: &str, is_last: bool) -> Result<()> {
+    // Registry status of every dependency, in one batch lookup
+    fn collect_ids(nodes: &[serde_json::Value], ids: &mut Vec<String>) {
+        for node in nodes {
+            if let Some(id) = node["contract_id"].as_str().filter(|id| *id != "unknown") {
+                ids.push(id.to_string());
+            }
+            if let Some(children) = node["dependencies"].as_array() {
+                collect_ids(children, ids);
+            }
+        }
+    }
+    let mut ids = Vec::new();
+    collect_ids(tree, &mut ids);
+    let registry =
+        crate::contracts::fetch_batch(api_url, &ids, Some("id,network,is_verified")).await?;
+
+    fn print_tree(
+        nodes: &[serde_json::Value],
+        registry: &std::collections::HashMap<String, serde_json::Value>,
+        prefix: &str,
+        is_last: bool,
+    ) -> Result<()> {
         for (i, node) in nodes.iter().enumerate() {
             let name = node["name"].as_str().unwrap_or("Unknown");
             let constraint = node["constraint_to_parent"].as_str().unwrap_or("*");
@@ -1108,34 +2723,38 @@ pub async fn deps_list(api_url: &str, contract_id: &str) -> Result<()> {
                 "├──"
             };
 
+            let status = match registry.get(&contract_id) {
+                _ if contract_id == "unknown" => "[Unresolved]".red(),
+                None => "[Not in registry]".yellow(),
+                Some(found) if found["is_verified"].as_bool() == Some(true) => {
+                    format!("[{}, verified]", found["network"].as_str().unwrap_or("?")).green()
+                }
+                Some(found) => {
+                    format!("[{}]", found["network"].as_str().unwrap_or("?")).bright_black()
+                }
+            };
+
             println!(
                 "{}{} {} ({}) {}",
                 prefix,
                 marker.bright_black(),
                 name.bold(),
                 constraint.cyan(),
-                if contract_id == "unknown" {
-                    "[Unresolved]".red()
-                } else {
-                    "".normal()
-                }
+                status
             );
 
             if let Some(children) = node["dependencies"].as_array() {
                 if !children.is_empty() {
                     let new_prefix =
                         format!("{}{}", prefix, if is_node_last { "    " } else { "│   " });
-                    let _ = print_tree(children, &new_prefix, true);
-                    let new_prefix =
-                        format!("{}{}", prefix, if is_node_last { "    " } else { "│   " });
-                    print_tree(children, &new_prefix, true)?;
+                    print_tree(children, registry, &new_prefix, true)?;
                 }
             }
         }
         Ok(())
     }
 
-    print_tree(tree, "", false)?;
+    print_tree(tree, &registry, "", false)?;
 
     println!("\n{}", "=".repeat(80).cyan());
     println!();
@@ -1262,9 +2881,184 @@ pub async fn run_tests(
 
 #[cfg(test)]
 mod tests {
-    use super::extract_migration_id;
+    use super::{
+        build_info_lines, describe_ownership, extract_migration_id, freshness_line, is_stale,
+        profiler, rating_histogram_lines, report_profile, security_notice_line, wasm_meta_lines,
+        OwnedContract,
+    };
     use serde_json::json;
 
+    #[test]
+    fn ownership_details_note_organizations_and_deprecation() {
+        let mut contract = OwnedContract {
+            contract_id: "CABC".to_string(),
+            name: "token".to_string(),
+            network: "testnet".to_string(),
+            owned_via: "publisher".to_string(),
+            verification_status: "verified".to_string(),
+            on_chain_status: "deployed".to_string(),
+            deprecated_at: None,
+            retirement_at: None,
+        };
+        assert_eq!(describe_ownership(&contract), None);
+
+        contract.owned_via = "organization".to_string();
+        contract.deprecated_at = Some("2026-10-16T17:25:03Z".to_string());
+        contract.retirement_at = Some("2026-11-15T00:00:00Z".to_string());
+        assert_eq!(
+            describe_ownership(&contract).as_deref(),
+            Some("owned by your organization; deprecated 2026-10-16 (retires 2026-11-15)")
+        );
+    }
+
+    #[test]
+    fn freshness_shows_the_days_since_each_signal() {
+        let contract = json!({
+            "freshness": {
+                "days_since_version": 412,
+                "days_since_onchain_confirmation": null,
+                "days_since_publisher_activity": 380,
+                "stale": true
+            }
+        });
+        assert!(is_stale(&contract));
+        let line = freshness_line(&contract["freshness"]).unwrap();
+        assert!(line.contains("STALE"), "{line}");
+        assert!(line.ends_with("last version 412d ago · on chain never · publisher 380d ago"));
+
+        assert!(!is_stale(&json!({ "name": "pool" })));
+        assert_eq!(freshness_line(&json!(null)), None);
+    }
+
+    #[test]
+    fn build_info_summary_lists_the_toolchain() {
+        let lines = build_info_lines(&json!({
+            "rustc_version": "rustc 1.81.0 (eeb90cda1 2024-09-04)",
+            "soroban_sdk_version": "21.7.7",
+            "cargo_features": ["testutils"],
+            "build_profile": "release",
+            "target": "wasm32-unknown-unknown",
+            "build_flags": ["build", "--release"],
+            "rustflags": null,
+            "lockfile_sha256": "ab12",
+            "source_url": "https://github.com/acme/escrow",
+            "commit_hash": "a1b2c3d",
+            "has_sbom": true
+        }));
+
+        assert_eq!(lines[0], "rustc:       rustc 1.81.0 (eeb90cda1 2024-09-04)");
+        assert_eq!(lines[1], "soroban-sdk: 21.7.7");
+        assert_eq!(lines[3], "features:    testutils");
+        assert!(lines.contains(&"Cargo.lock:  ab12".to_string()));
+        assert!(
+            lines.contains(&"source:      https://github.com/acme/escrow @ a1b2c3d".to_string())
+        );
+        assert!(lines.iter().any(|line| line.starts_with("SBOM:")));
+        assert!(!lines.iter().any(|line| line.starts_with("RUSTFLAGS")));
+    }
+
+    #[test]
+    fn build_info_summary_of_a_prebuilt_wasm() {
+        let lines = build_info_lines(&json!({
+            "rustc_version": null,
+            "soroban_sdk_version": "21.7.7",
+            "cargo_features": [],
+            "lockfile_sha256": null,
+            "has_sbom": false
+        }));
+
+        assert_eq!(lines[0], "rustc:       unknown (prebuilt WASM)");
+        assert_eq!(lines[3], "features:    default");
+        assert!(lines.contains(&"Cargo.lock:  not submitted".to_string()));
+        assert!(!lines.iter().any(|line| line.starts_with("SBOM:")));
+    }
+
+    #[test]
+    fn wasm_metadata_comes_from_the_newest_version_that_has_it() {
+        let versions = json!([
+            { "version": "1.2.0" },
+            {
+                "version": "1.1.0",
+                "wasm_meta": {
+                    "sdk_version": "22.0.7#211569aa",
+                    "rustc_version": "1.81.0",
+                    "authors": null,
+                    "repository": "https://github.com/acme/escrow",
+                    "entries": {}
+                }
+            },
+            { "version": "1.0.0", "wasm_meta": { "sdk_version": "21.0.0" } }
+        ]);
+
+        assert_eq!(
+            wasm_meta_lines(&versions).unwrap(),
+            vec![
+                "version:     1.1.0",
+                "soroban-sdk: 22.0.7#211569aa",
+                "rustc:       1.81.0",
+                "repository:  https://github.com/acme/escrow",
+            ]
+        );
+        assert_eq!(wasm_meta_lines(&json!([{ "version": "1.0.0" }])), None);
+        assert_eq!(wasm_meta_lines(&json!([])), None);
+    }
+
+    #[test]
+    fn security_notices_name_the_advisory_and_the_affected_build() {
+        let notice = json!({
+            "advisory_id": "0d1f6a52-8d2c-4d43-9d0c-3c5f8f0b9a11",
+            "title": "Storage key collision",
+            "severity": "critical",
+            "component": "soroban-sdk",
+            "matched_version": "20.1.0#4a6a9f1f",
+            "contract_version": "1.2.0",
+            "url": "https://example.com/advisories/1",
+            "created_at": "2026-10-16T12:00:00Z"
+        });
+        assert_eq!(
+            security_notice_line(&notice).unwrap(),
+            "SECURITY ADVISORY (critical): Storage key collision; version 1.2.0 was built with soroban-sdk 20.1.0#4a6a9f1f (https://example.com/advisories/1)"
+        );
+
+        let notice = json!({
+            "title": "Miscompiled overflow checks",
+            "severity": "medium",
+            "component": "rustc",
+            "matched_version": "1.79.0",
+            "contract_version": "0.3.0",
+            "url": null
+        });
+        assert_eq!(
+            security_notice_line(&notice).unwrap(),
+            "SECURITY ADVISORY (medium): Miscompiled overflow checks; version 0.3.0 was built with rustc 1.79.0"
+        );
+        assert_eq!(security_notice_line(&json!({})), None);
+    }
+
+    #[test]
+    fn rating_histogram_scales_bars_to_the_most_common_rating() {
+        let lines = rating_histogram_lines(&json!({
+            "average_rating": 4.26,
+            "total_reviews": 31,
+            "rating_distribution": {
+                "stars_1": 1, "stars_2": 0, "stars_3": 0, "stars_4": 10, "stars_5": 20
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(lines[0], "4.3 average from 31 reviews");
+        assert_eq!(lines[1], format!("5 ★ {} 20", "█".repeat(20)));
+        assert_eq!(lines[2], format!("4 ★ {:<20} 10", "█".repeat(10)));
+        assert_eq!(lines[4], format!("2 ★ {:<20} 0", ""));
+        assert_eq!(lines[5], format!("1 ★ {:<20} 1", "█"));
+
+        assert_eq!(
+            rating_histogram_lines(&json!({"total_reviews": 0})),
+            Some(vec!["No reviews yet".to_string()])
+        );
+        assert_eq!(rating_histogram_lines(&serde_json::Value::Null), None);
+    }
+
     #[test]
     fn extract_migration_id_returns_id_for_valid_payload() {
         let payload = json!({"id": "migration-123"});
@@ -1296,6 +3090,40 @@ mod tests {
             .unwrap_or_default()
             .contains("Invalid migration response: missing id"));
     }
+
+    #[test]
+    fn json_profile_reports_are_returned_instead_of_printed() {
+        let resources = profiler::ResourceUsage {
+            cpu_instructions: Some(1_200_000),
+            ..Default::default()
+        };
+        let profile = profiler::ProfileData::from_simulation("CABC", "swap", resources);
+        let run = report_profile(
+            profile,
+            None,
+            "json",
+            None,
+            None,
+            None,
+            Some(&profiler::FeeConfig::default()),
+            None,
+            None,
+            true,
+            true,
+        )
+        .unwrap();
+
+        let report = run.report.unwrap();
+        assert!(run.profile.fee_estimate.is_some());
+        assert_eq!(
+            report["profile"]["resources"]["cpu_instructions"],
+            1_200_000
+        );
+        assert!(report["profile"]["fee_estimate"].is_object());
+        assert_eq!(report["written"], json!({}));
+        assert!(report["comparison"].is_null());
+        assert!(report["recommendations"].is_array());
+    }
 }
 pub fn incident_trigger(contract_id: &str, severity_str: &str) -> Result<()> {
     use crate::incident::{IncidentManager, IncidentSeverity};
@@ -1338,7 +3166,7 @@ pub fn incident_trigger(contract_id: &str, severity_str: &str) -> Result<()> {
 }
 
 pub async fn config_get(api_url: &str, contract_id: &str, environment: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!(
         "{}/api/contracts/{}/config?environment={}",
         api_url, contract_id, environment
@@ -1398,7 +3226,7 @@ pub async fn config_set(
     secrets_data: Option<&str>,
     created_by: &str,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/config", api_url, contract_id);
 
     let mut payload = json!({
@@ -1447,7 +3275,7 @@ pub async fn config_set(
 }
 
 pub async fn config_history(api_url: &str, contract_id: &str, environment: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!(
         "{}/api/contracts/{}/config/history?environment={}",
         api_url, contract_id, environment
@@ -1497,7 +3325,7 @@ pub async fn config_rollback(
     version: i32,
     created_by: &str,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!(
         "{}/api/contracts/{}/config/rollback?environment={}",
         api_url, contract_id, environment
@@ -1588,7 +3416,7 @@ pub async fn scan_deps(
 ) -> Result<()> {
     println!("\n{}", "Scanning Dependencies...".bold().cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/scan", api_url, contract_id);
 
     // Parse dependencies
@@ -1675,8 +3503,8 @@ pub async fn scan_deps(
 #[cfg(test)]
 mod flamegraph_and_network_tests {
     mod tests_network {
-        use super::super::{generate_flame_graph_file, profile, Network};
-        use crate::profiler;
+        use super::super::{diff_profile, generate_flame_graph_file, profile, Network};
+        use soroban_profiler as profiler;
         use std::collections::HashMap;
         use std::fs;
         use std::time::Duration;
@@ -1688,22 +3516,32 @@ mod flamegraph_and_network_tests {
                 profiler::FunctionProfile {
                     name: "main".to_string(),
                     total_time: Duration::from_millis(10),
+                    self_time: Duration::from_millis(10),
                     call_count: 1,
                     avg_time: Duration::from_millis(10),
                     min_time: Duration::from_millis(10),
                     max_time: Duration::from_millis(10),
+                    histogram: Default::default(),
+                    parents: vec![],
                     children: vec![],
+                    unreliable: false,
                 },
             );
 
             profiler::ProfileData {
+                schema_version: profiler::PROFILE_SCHEMA_VERSION,
                 contract_path: "contract.rs".to_string(),
                 method: Some("main".to_string()),
                 timestamp: "2026-01-01T00:00:00Z".to_string(),
                 total_duration: Duration::from_millis(10),
                 functions,
                 call_stack: vec![],
+                overhead_per_call: Duration::ZERO,
                 overhead_percent: 0.0,
+                mode: Default::default(),
+                sample_interval: Duration::ZERO,
+                resources: Default::default(),
+                fee_estimate: None,
             }
         }
 
@@ -1748,41 +3586,162 @@ mod flamegraph_and_network_tests {
                 .join("flamegraph-output.svg");
             let invalid_output_str = invalid_output.to_string_lossy().into_owned();
 
-            let err = generate_flame_graph_file(&profile, &invalid_output_str)
-                .expect_err("expected flame graph generation to fail for invalid path");
-            assert!(
-                err.to_string().contains("Failed to write flame graph"),
-                "unexpected error: {err}"
-            );
+            let err = generate_flame_graph_file(&profile, &invalid_output_str)
+                .expect_err("expected flame graph generation to fail for invalid path");
+            assert!(
+                err.to_string().contains("Failed to write flame graph"),
+                "unexpected error: {err}"
+            );
+        }
+
+        #[test]
+        fn profile_writes_json_and_flamegraph_outputs() {
+            let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
+            let contract_path = write_sample_contract(&temp_dir);
+            let json_output = temp_dir.path().join("profile-output.json");
+            let flame_output = temp_dir.path().join("profile-output.svg");
+            let folded_output = temp_dir.path().join("profile-output.folded");
+            let json_output_str = json_output.to_string_lossy().into_owned();
+            let flame_output_str = flame_output.to_string_lossy().into_owned();
+            let folded_output_str = folded_output.to_string_lossy().into_owned();
+
+            profile(
+                &contract_path,
+                None,
+                None,
+                Some(&json_output_str),
+                "json",
+                Some(&flame_output_str),
+                Some(&folded_output_str),
+                None,
+                None,
+                None,
+                None,
+                true,
+                false,
+            )
+            .expect("expected profiling to succeed");
+
+            assert!(
+                json_output.exists(),
+                "expected JSON profile output to exist"
+            );
+            assert!(
+                flame_output.exists(),
+                "expected flame graph output to exist"
+            );
+            let folded = fs::read_to_string(&folded_output).expect("expected folded stacks");
+            assert!(
+                folded
+                    .lines()
+                    .any(|line| line.starts_with("main;helper_one ")),
+                "expected helpers nested under main: {folded}"
+            );
+        }
+
+        #[test]
+        fn profile_writes_chrome_trace_output() {
+            let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
+            let contract_path = write_sample_contract(&temp_dir);
+            let trace_output = temp_dir.path().join("profile-trace.json");
+            let trace_output_str = trace_output.to_string_lossy().into_owned();
+
+            profile(
+                &contract_path,
+                None,
+                None,
+                Some(&trace_output_str),
+                "chrome-trace",
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                false,
+                false,
+            )
+            .expect("expected profiling to succeed");
+
+            let trace: serde_json::Value = serde_json::from_str(
+                &fs::read_to_string(&trace_output).expect("expected trace output"),
+            )
+            .expect("expected valid trace JSON");
+            let events = trace["traceEvents"]
+                .as_array()
+                .expect("expected trace events");
+            assert_eq!(events[0]["args"]["name"], contract_path.as_str());
+            assert!(events
+                .iter()
+                .any(|event| event["ph"] == "X" && event["name"] == "helper_one"));
+        }
+
+        #[test]
+        fn profile_sampling_mode_compares_against_instrumented_baseline() {
+            let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
+            let contract_path = write_sample_contract(&temp_dir);
+            let baseline_path = temp_dir.path().join("baseline.json");
+            let baseline_path_str = baseline_path.to_string_lossy().into_owned();
+            let baseline_json = serde_json::to_string_pretty(&sample_profile())
+                .expect("failed to serialize baseline");
+            fs::write(&baseline_path, baseline_json).expect("failed to write baseline file");
+            let json_output = temp_dir.path().join("sampled.json");
+            let json_output_str = json_output.to_string_lossy().into_owned();
+
+            profile(
+                &contract_path,
+                None,
+                Some(10_000),
+                Some(&json_output_str),
+                "json",
+                None,
+                None,
+                Some(&baseline_path_str),
+                None,
+                None,
+                None,
+                false,
+                false,
+            )
+            .expect("expected sampled profiling to succeed");
+
+            let sampled = profiler::load_profile(&json_output).expect("expected sampled profile");
+            assert_eq!(sampled.profile.mode, profiler::ProfileMode::Sampled);
+            assert_eq!(sampled.profile.sample_interval, Duration::from_micros(100));
         }
 
         #[test]
-        fn profile_writes_json_and_flamegraph_outputs() {
+        fn profile_includes_fee_estimate_in_output() {
             let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
             let contract_path = write_sample_contract(&temp_dir);
-            let json_output = temp_dir.path().join("profile-output.json");
-            let flame_output = temp_dir.path().join("profile-output.svg");
+            let json_output = temp_dir.path().join("profile-fees.json");
             let json_output_str = json_output.to_string_lossy().into_owned();
-            let flame_output_str = flame_output.to_string_lossy().into_owned();
 
             profile(
                 &contract_path,
                 None,
+                None,
                 Some(&json_output_str),
-                Some(&flame_output_str),
+                "json",
                 None,
-                true,
+                None,
+                None,
+                Some(&profiler::FeeConfig::default()),
+                None,
+                None,
+                false,
+                false,
             )
             .expect("expected profiling to succeed");
 
-            assert!(
-                json_output.exists(),
-                "expected JSON profile output to exist"
-            );
-            assert!(
-                flame_output.exists(),
-                "expected flame graph output to exist"
-            );
+            let saved = profiler::load_profile(&json_output).expect("expected saved profile");
+            let fee = saved
+                .profile
+                .fee_estimate
+                .expect("expected a fee estimate in the profile");
+            // The local engine measures no resources
+            assert_eq!(fee.assumed().count(), fee.components.len());
+            assert!(fee.total_stroops > fee.inclusion_fee);
         }
 
         #[test]
@@ -1796,15 +3755,69 @@ mod flamegraph_and_network_tests {
                 .expect("failed to serialize baseline");
             fs::write(&baseline_path, baseline_json).expect("failed to write baseline file");
 
+            let diff_path = temp_dir.path().join("diff.svg");
+            let diff_path_str = diff_path.to_string_lossy().into_owned();
+
             profile(
                 &contract_path,
                 None,
                 None,
                 None,
+                "json",
+                None,
+                None,
                 Some(&baseline_path_str),
+                None,
+                Some(&diff_path_str),
+                None,
+                false,
                 false,
             )
             .expect("expected profiling with baseline comparison to succeed");
+
+            // The baseline only has main, so the helpers show up as added
+            let diff = fs::read_to_string(&diff_path).expect("expected differential flame graph");
+            assert!(diff.contains("<g class=\"frame added\">\n<title>helper_one: "));
+        }
+
+        #[test]
+        fn profile_gate_fails_on_new_hot_function() {
+            let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
+            let contract_path = write_sample_contract(&temp_dir);
+            let baseline_path = temp_dir.path().join("baseline.json");
+            let baseline_path_str = baseline_path.to_string_lossy().into_owned();
+
+            // The baseline only has main, so both helpers are new
+            let baseline_json = serde_json::to_string_pretty(&sample_profile())
+                .expect("failed to serialize baseline");
+            fs::write(&baseline_path, baseline_json).expect("failed to write baseline file");
+
+            let gate = profiler::RegressionGate {
+                max_slowdown_percent: 1_000_000.0,
+                max_new_function_time: Duration::ZERO,
+            };
+            let err = profile(
+                &contract_path,
+                None,
+                None,
+                None,
+                "json",
+                None,
+                None,
+                Some(&baseline_path_str),
+                None,
+                None,
+                Some(&gate),
+                false,
+                false,
+            )
+            .expect_err("expected new functions to fail the gate");
+
+            assert!(
+                err.to_string()
+                    .contains("2 function(s) exceeded the regression gate"),
+                "unexpected error: {err}"
+            );
         }
 
         #[test]
@@ -1819,7 +3832,14 @@ mod flamegraph_and_network_tests {
                 None,
                 None,
                 None,
+                "json",
+                None,
+                None,
                 Some(&missing_baseline_str),
+                None,
+                None,
+                None,
+                false,
                 false,
             )
             .expect_err("expected missing baseline to fail");
@@ -1831,6 +3851,63 @@ mod flamegraph_and_network_tests {
             );
         }
 
+        fn write_profile(
+            temp_dir: &tempfile::TempDir,
+            name: &str,
+            profile: &profiler::ProfileData,
+        ) -> String {
+            let path = temp_dir.path().join(name);
+            fs::write(
+                &path,
+                serde_json::to_string(profile).expect("failed to serialize"),
+            )
+            .expect("failed to write profile");
+            path.to_string_lossy().into_owned()
+        }
+
+        #[test]
+        fn diff_profile_refuses_mixed_modes_unless_forced() {
+            let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
+            let baseline = write_profile(&temp_dir, "baseline.json", &sample_profile());
+            let mut sampled = sample_profile();
+            sampled.mode = profiler::ProfileMode::Sampled;
+            let current = write_profile(&temp_dir, "current.json", &sampled);
+
+            let err = diff_profile(&baseline, &current, None, false, false)
+                .expect_err("expected mixed modes to be refused");
+            assert!(
+                err.to_string().contains("Pass --force"),
+                "unexpected error: {err}"
+            );
+
+            diff_profile(&baseline, &current, None, true, true)
+                .expect("expected --force to compare anyway");
+        }
+
+        #[test]
+        fn diff_profile_gate_fails_on_regression() {
+            let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
+            let baseline = write_profile(&temp_dir, "baseline.json", &sample_profile());
+            let mut slower = sample_profile();
+            slower.contract_path = "other.rs".to_string();
+            if let Some(main) = slower.functions.get_mut("main") {
+                main.total_time = Duration::from_millis(15);
+            }
+            let current = write_profile(&temp_dir, "current.json", &slower);
+
+            diff_profile(&baseline, &current, None, false, false)
+                .expect("expected a diff without a gate to succeed");
+
+            let gate = profiler::RegressionGate::default();
+            let err = diff_profile(&baseline, &current, Some(&gate), false, true)
+                .expect_err("expected a 50% slowdown to fail the gate");
+            assert!(
+                err.to_string()
+                    .contains("1 function(s) exceeded the regression gate"),
+                "unexpected error: {err}"
+            );
+        }
+
         #[test]
         fn profile_returns_error_for_unknown_method() {
             let temp_dir = tempfile::tempdir().expect("failed to create temp directory");
@@ -1841,7 +3918,14 @@ mod flamegraph_and_network_tests {
                 Some("does_not_exist"),
                 None,
                 None,
+                "json",
+                None,
+                None,
                 None,
+                None,
+                None,
+                None,
+                false,
                 false,
             )
             .expect_err("expected unknown method to fail");
@@ -1861,7 +3945,7 @@ pub async fn validate_call(
     params: &[String],
     strict: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/validate-call", api_url, contract_id);
 
     let body = json!({
@@ -1883,7 +3967,7 @@ pub async fn validate_call(
     let data: serde_json::Value = response.json().await?;
 
     if !status.is_success() {
-        let error_msg = crate::conversions::as_str(&data["message"], "message")?;
+        let error_msg = crate::conversions::as_str(&data["error"]["message"], "message")?;
         println!("\n{} {}", "Error:".bold().red(), error_msg);
         anyhow::bail!("Validation failed: {}", error_msg);
     }
@@ -1976,56 +4060,9 @@ pub async fn validate_call(
     Ok(())
 }
 
-/// Generate type-safe bindings for a contract
-pub async fn generate_bindings(
-    api_url: &str,
-    contract_id: &str,
-    language: &str,
-    output: Option<&str>,
-) -> Result<()> {
-    let client = reqwest::Client::new();
-    let url = format!(
-        "{}/api/contracts/{}/bindings?language={}",
-        api_url, contract_id, language
-    );
-
-    log::debug!("GET {}", url);
-
-    let response = client
-        .get(&url)
-        .send()
-        .await
-        .context("Failed to generate bindings")?;
-
-    let status = response.status();
-
-    if !status.is_success() {
-        let error: serde_json::Value = response.json().await?;
-        let msg = crate::conversions::as_str(&error["message"], "message")?;
-        anyhow::bail!("Failed to generate bindings: {}", msg);
-    }
-
-    let bindings = response.text().await?;
-
-    if let Some(output_path) = output {
-        fs::write(output_path, &bindings)?;
-        println!(
-            "\n{} {} bindings written to: {}",
-            "✓".green().bold(),
-            language,
-            output_path
-        );
-    } else {
-        // Print to stdout
-        println!("{}", bindings);
-    }
-
-    Ok(())
-}
-
 /// List functions available on a contract
 pub async fn list_functions(api_url: &str, contract_id: &str) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/contracts/{}/functions", api_url, contract_id);
 
     log::debug!("GET {}", url);
@@ -2040,7 +4077,7 @@ pub async fn list_functions(api_url: &str, contract_id: &str) -> Result<()> {
     let data: serde_json::Value = response.json().await?;
 
     if !status.is_success() {
-        let msg = crate::conversions::as_str(&data["message"], "message")?;
+        let msg = crate::conversions::as_str(&data["error"]["message"], "message")?;
         anyhow::bail!("Failed to list functions: {}", msg);
     }
 
@@ -2110,6 +4147,9 @@ pub async fn list_functions(api_url: &str, contract_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Incidents listed by `info --incidents`
+const RECENT_INCIDENTS: usize = 5;
+
 /// Fetch contract info from the registry. `id` is the contract's registry identifier.
 pub async fn info(
     api_url: &str,
@@ -2117,8 +4157,10 @@ pub async fn info(
     format: &str,
     highlight_method: Option<&str>,
     network: crate::config::Network,
+    build_info: bool,
+    incidents: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let base_url = api_url.trim_end_matches('/');
 
     if format == "text" {
@@ -2226,7 +4268,7 @@ pub async fn info(
     };
 
     // Aggregate data
-    let full_info = json!({
+    let mut full_info = json!({
         "metadata": metadata["contract"],
         "current_network_config": metadata["network_config"],
         "abi": abi,
@@ -2234,8 +4276,36 @@ pub async fn info(
         "dependencies": dependencies,
         "dependents": dependents,
         "versions": versions,
+        "incident_summary": metadata["incident_summary"],
+        "security_notices": metadata["security_notices"],
+        "ratings": metadata["ratings"],
+        "latest_profile": metadata["latest_profile"],
     });
 
+    // 7. Fetch build metadata, only when asked for. Null when the contract
+    // has not been verified with it.
+    if build_info {
+        let build_url = format!("{}/api/contracts/{}/build-info", base_url, contract_uuid);
+        let build_res = client
+            .get(&build_url)
+            .send()
+            .await
+            .context("Failed to fetch build metadata")?;
+        let build = match build_res.status() {
+            status if status.is_success() => build_res.json().await?,
+            reqwest::StatusCode::NOT_FOUND => serde_json::Value::Null,
+            status => anyhow::bail!("Failed to fetch build metadata: {}", status),
+        };
+        full_info["build_info"] = build;
+    }
+
+    // 8. Fetch the latest incidents, only when asked for
+    if incidents {
+        let recent =
+            crate::incident::recent_for_contract(base_url, contract_uuid, RECENT_INCIDENTS).await?;
+        full_info["incidents"] = serde_json::to_value(recent)?;
+    }
+
     // Render output
     match format {
         "json" => {
@@ -2285,9 +4355,37 @@ fn render_info_text(
         }
     );
     println!("{} {}/100", "HEALTH:  ".bold(), health_score);
+    if let Some(freshness) = freshness_line(&metadata["freshness"]) {
+        println!("{} {}", "FRESH:   ".bold(), freshness);
+    }
+    if let Some(summary) = info["latest_profile"]["summary"].as_str() {
+        println!("{} {}", "PERF:    ".bold(), summary);
+    }
     println!("{} {}", "DESC:    ".bold(), desc);
     println!("{}", "=".repeat(80).cyan());
 
+    let incident_summary = &info["incident_summary"];
+    if incident_summary["has_open_critical_incident"].as_bool() == Some(true) {
+        println!(
+            "\n{}",
+            " ⚠ OPEN CRITICAL INCIDENT: this contract is affected by an unresolved critical incident "
+                .on_red()
+                .white()
+                .bold()
+        );
+    }
+    for notice in info["security_notices"].as_array().into_iter().flatten() {
+        let Some(line) = security_notice_line(notice) else {
+            continue;
+        };
+        let line = format!(" ⚠ {} ", line);
+        if matches!(notice["severity"].as_str(), Some("high" | "critical")) {
+            println!("\n{}", line.on_red().white().bold());
+        } else {
+            println!("\n{}", line.on_yellow().black().bold());
+        }
+    }
+
     // Explorer Links
     println!("\n{}", "BLOCK EXPLORERS:".bold().underline());
     let explorer_url = match network_str {
@@ -2330,7 +4428,10 @@ fn render_info_text(
             for d in depls {
                 let env = d["environment"].as_str().unwrap_or("unknown");
                 let status = d["status"].as_str().unwrap_or("unknown");
-                let date = d["deployed_at"].as_str().unwrap_or("");
+                let date = d["deployed_at"]
+                    .as_str()
+                    .map(crate::time_format::show_rfc3339)
+                    .unwrap_or_default();
                 println!("  • {:<10} | {:<10} | {}", env, status, date);
             }
         }
@@ -2361,10 +4462,222 @@ fn render_info_text(
         }
     }
 
+    // Star histogram of approved reviews
+    if let Some(lines) = rating_histogram_lines(&info["ratings"]) {
+        println!("\n{}", "RATINGS:".bold().underline());
+        for line in lines {
+            println!("  {}", line);
+        }
+    }
+
+    // Operational history, with the latest incidents under --incidents
+    if incident_summary.is_object() || info.get("incidents").is_some() {
+        println!("\n{}", "INCIDENTS:".bold().underline());
+        if let Some(line) = incident_summary_line(incident_summary) {
+            println!("  {}", line);
+        }
+    }
+    if let Some(incidents) = info.get("incidents") {
+        let incidents: Vec<crate::incident::IncidentRecord> =
+            serde_json::from_value(incidents.clone())?;
+        for incident in &incidents {
+            println!(
+                "  • {} | {:<13} | {:<12} | {}",
+                crate::time_format::show(incident.start_time),
+                crate::incident::colorize_severity(&incident.severity),
+                incident.incident_type,
+                match incident.resolution_time() {
+                    Some(took) => format!("resolved in {}", took),
+                    None => "OPEN".red().bold().to_string(),
+                }
+            );
+        }
+    }
+
+    // Metadata embedded in the uploaded WASM
+    if let Some(lines) = wasm_meta_lines(&info["versions"]) {
+        println!("\n{}", "WASM METADATA:".bold().underline());
+        for line in lines {
+            println!("  {}", line);
+        }
+    }
+
+    // Build metadata (--build-info)
+    if let Some(build) = info.get("build_info") {
+        println!("\n{}", "BUILD:".bold().underline());
+        if build.is_null() {
+            println!("  No build metadata recorded; the contract has not been verified with it");
+        } else {
+            for line in build_info_lines(build) {
+                println!("  {}", line);
+            }
+        }
+    }
+
     println!("\n{}", "=".repeat(80).cyan());
     Ok(())
 }
 
+/// "3 incidents, latest 2 days ago (major), currently open"; `None` when the
+/// registry doesn't track incidents
+fn incident_summary_line(summary: &serde_json::Value) -> Option<String> {
+    let count = summary["incident_count"].as_i64()?;
+    if count == 0 {
+        return Some("No incidents recorded".to_string());
+    }
+    let mut line = format!("{} incident{}", count, if count == 1 { "" } else { "s" });
+    if let Some(last) = summary["last_incident_at"].as_str() {
+        line.push_str(&format!(
+            ", latest {}",
+            crate::time_format::show_rfc3339(last)
+        ));
+        if let Some(severity) = summary["last_incident_severity"].as_str() {
+            line.push_str(&format!(" ({})", severity));
+        }
+    }
+    if summary["has_open_incident"].as_bool() == Some(true) {
+        line.push_str(", currently open");
+    }
+    Some(line)
+}
+
+/// "SECURITY ADVISORY (high): <title>; version 1.2.0 was built with
+/// soroban-sdk 20.1.0", plus the advisory link when there is one
+fn security_notice_line(notice: &serde_json::Value) -> Option<String> {
+    let title = notice["title"].as_str()?;
+    let mut line = format!(
+        "SECURITY ADVISORY ({}): {}; version {} was built with {} {}",
+        notice["severity"].as_str().unwrap_or("unknown"),
+        title,
+        notice["contract_version"].as_str().unwrap_or("?"),
+        notice["component"].as_str().unwrap_or("?"),
+        notice["matched_version"].as_str().unwrap_or("?"),
+    );
+    if let Some(url) = notice["url"].as_str() {
+        line.push_str(&format!(" ({})", url));
+    }
+    Some(line)
+}
+
+/// Widest bar of the ratings histogram, in characters
+const RATING_BAR_WIDTH: usize = 20;
+
+/// The average followed by one bar per star, 5 to 1, scaled to the most
+/// common rating; `None` when the registry didn't send ratings
+fn rating_histogram_lines(ratings: &serde_json::Value) -> Option<Vec<String>> {
+    let total = ratings["total_reviews"].as_i64()?;
+    if total == 0 {
+        return Some(vec!["No reviews yet".to_string()]);
+    }
+    let counts: Vec<i64> = (1..=5)
+        .rev()
+        .map(|stars| {
+            ratings["rating_distribution"][format!("stars_{}", stars)]
+                .as_i64()
+                .unwrap_or(0)
+        })
+        .collect();
+    let most = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    let mut lines = vec![format!(
+        "{:.1} average from {} review{}",
+        ratings["average_rating"].as_f64().unwrap_or(0.0),
+        total,
+        if total == 1 { "" } else { "s" }
+    )];
+    for (stars, count) in (1..=5).rev().zip(counts) {
+        // Any reviews at all get at least one block
+        let width = match count {
+            0 => 0,
+            _ => ((count * RATING_BAR_WIDTH as i64 + most / 2) / most).max(1) as usize,
+        };
+        lines.push(format!(
+            "{} ★ {:<bar$} {}",
+            stars,
+            "█".repeat(width),
+            count,
+            bar = RATING_BAR_WIDTH
+        ));
+    }
+    Some(lines)
+}
+
+/// The toolchain summary `info --build-info` prints
+fn build_info_lines(build: &serde_json::Value) -> Vec<String> {
+    let text = |key: &str| build[key].as_str().filter(|value| !value.is_empty());
+    let list = |key: &str| {
+        build[key]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .filter(|joined| !joined.is_empty())
+    };
+
+    let mut lines = vec![
+        format!(
+            "rustc:       {}",
+            text("rustc_version").unwrap_or("unknown (prebuilt WASM)")
+        ),
+        format!(
+            "soroban-sdk: {}",
+            text("soroban_sdk_version").unwrap_or("unknown")
+        ),
+        format!(
+            "profile:     {} ({})",
+            text("build_profile").unwrap_or("release"),
+            text("target").unwrap_or("wasm32-unknown-unknown")
+        ),
+        format!(
+            "features:    {}",
+            list("cargo_features").unwrap_or_else(|| "default".to_string())
+        ),
+    ];
+    if let Some(flags) = list("build_flags") {
+        lines.push(format!("cargo flags: {}", flags));
+    }
+    if let Some(rustflags) = text("rustflags") {
+        lines.push(format!("RUSTFLAGS:   {}", rustflags));
+    }
+    lines.push(format!(
+        "Cargo.lock:  {}",
+        text("lockfile_sha256").unwrap_or("not submitted")
+    ));
+    if let (Some(url), Some(commit)) = (text("source_url"), text("commit_hash")) {
+        lines.push(format!("source:      {} @ {}", url, commit));
+    }
+    if build["has_sbom"].as_bool() == Some(true) {
+        lines.push("SBOM:        available (GET /api/contracts/{id}/sbom)".to_string());
+    }
+    lines
+}
+
+/// SDK, toolchain, authors and repository embedded in the WASM of the newest
+/// version that has metadata; `None` when no version does
+fn wasm_meta_lines(versions: &serde_json::Value) -> Option<Vec<String>> {
+    let (version, meta) = versions.as_array()?.iter().find_map(|version| {
+        let meta = version.get("wasm_meta").filter(|meta| meta.is_object())?;
+        Some((version["version"].as_str().unwrap_or("unknown"), meta))
+    })?;
+
+    let mut lines = vec![format!("version:     {}", version)];
+    for (label, key) in [
+        ("soroban-sdk", "sdk_version"),
+        ("rustc", "rustc_version"),
+        ("authors", "authors"),
+        ("repository", "repository"),
+    ] {
+        if let Some(value) = meta[key].as_str().filter(|value| !value.is_empty()) {
+            lines.push(format!("{:<12} {}", format!("{}:", label), value));
+        }
+    }
+    Some(lines)
+}
+
 pub fn doc(contract_path: &str, output: &str) -> Result<()> {
     println!("\n{}", "Generating contract documentation...".bold().cyan());
 
@@ -2527,6 +4840,109 @@ pub fn openapi(contract_path: &str, output: &str, format: &str) -> Result<()> {
     Ok(())
 }
 
+/// Generates client bindings for a registered contract from its stored ABI.
+/// `contract` is a `namespace/slug[@version]` name, a UUID or an address.
+pub async fn generate_bindings(
+    api_url: &str,
+    contract: &str,
+    version: Option<&str>,
+    lang: &str,
+    output: &str,
+    network: crate::config::Network,
+) -> Result<()> {
+    if !matches!(lang.to_lowercase().as_str(), "typescript" | "ts") {
+        anyhow::bail!("Unsupported language '{}'. Use: typescript", lang);
+    }
+    println!("\n{}", "Generating TypeScript bindings...".bold().cyan());
+
+    let client = crate::http::client();
+    let base_url = api_url.trim_end_matches('/');
+
+    let (id, mut version) = match shared::slug::ContractName::parse(contract) {
+        Some(mut name) => {
+            if let Some(version) = version {
+                name.version = Some(version.to_string());
+            }
+            let resolved = crate::resolve::resolve(api_url, &name, network).await?;
+            (
+                resolved.id.to_string(),
+                resolved.version.map(|version| version.version),
+            )
+        }
+        None => (contract.to_string(), version.map(str::to_string)),
+    };
+
+    let metadata_res = client
+        .get(format!("{}/api/contracts/{}", base_url, id))
+        .query(&[("network", network.to_string())])
+        .send()
+        .await?;
+    if !metadata_res.status().is_success() {
+        anyhow::bail!(
+            "Failed to fetch contract {}: {}",
+            contract,
+            metadata_res.status()
+        );
+    }
+    let metadata: serde_json::Value = metadata_res.json().await?;
+    let contract_uuid = metadata["contract"]["id"]
+        .as_str()
+        .context("Metadata missing contract ID")?;
+    let contract_name = metadata["contract"]["name"].as_str().unwrap_or("contract");
+    let contract_address = metadata["contract"]["contract_id"]
+        .as_str()
+        .unwrap_or(contract_uuid);
+
+    // The ABI endpoint serves the latest version when none is given, which
+    // is the first one listed
+    if version.is_none() {
+        let versions: Vec<serde_json::Value> = client
+            .get(format!(
+                "{}/api/contracts/{}/versions",
+                base_url, contract_uuid
+            ))
+            .send()
+            .await?
+            .error_for_status()
+            .context("Failed to fetch contract versions")?
+            .json()
+            .await?;
+        version = versions
+            .first()
+            .and_then(|v| v["version"].as_str())
+            .map(str::to_string);
+    }
+
+    let mut abi_req = client.get(format!("{}/api/contracts/{}/abi", base_url, contract_uuid));
+    if let Some(version) = &version {
+        abi_req = abi_req.query(&[("version", version)]);
+    }
+    let abi_res = abi_req.send().await?;
+    if !abi_res.status().is_success() {
+        anyhow::bail!("No ABI stored for {}: {}", contract, abi_res.status());
+    }
+    let body: serde_json::Value = abi_res.json().await?;
+    let abi_json = body
+        .get("abi")
+        .context("ABI response missing 'abi'")?
+        .to_string();
+    let abi = contract_abi::parse_json_spec(&abi_json, contract_name)
+        .map_err(|e| anyhow::anyhow!("Failed to parse ABI: {}", e))?;
+
+    let source = contract_abi::BindingsSource {
+        contract_id: contract_address.to_string(),
+        version,
+    };
+    fs::create_dir_all(output).with_context(|| format!("Failed to create {}", output))?;
+    for file in contract_abi::generate_typescript(&abi, &source) {
+        let path = std::path::Path::new(output).join(&file.path);
+        fs::write(&path, &file.contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("{} Wrote {}", "✓".green(), path.display());
+    }
+    Ok(())
+}
+
 pub fn sla_record(id: &str, uptime: f64, latency: f64, error_rate: f64) -> Result<()> {
     println!("\n{}", "Recording SLA metrics...".bold().cyan());
     println!("Contract ID: {}", id);
@@ -2,12 +2,15 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fmt;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
 
+use crate::time_format::TimeFormat;
+
 const DEFAULT_API_BASE: &str = "http://localhost:3001";
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 const CONFIG_DIR_NAME: &str = ".soroban-registry";
@@ -54,6 +57,8 @@ impl FromStr for Network {
 #[derive(Debug, Clone, Deserialize, Default)]
 struct ConfigFile {
     defaults: Option<DefaultsSection>,
+    #[serde(default)]
+    profiles: BTreeMap<String, ProfileSection>,
 }
 
 #[derive(Debug, Clone, Deserialize, Default)]
@@ -61,47 +66,286 @@ struct DefaultsSection {
     network: Option<String>,
     api_base: Option<String>,
     timeout: Option<u64>,
+    proxy: Option<String>,
+    ca_cert: Option<PathBuf>,
+    /// Profile used when neither `--profile` nor `SOROBAN_REGISTRY_PROFILE`
+    /// names one; set by `config use`
+    profile: Option<String>,
+    /// How timestamps read in text output, see [`crate::time_format`]
+    time_format: Option<String>,
+    /// Directory failed requests are captured in, see [`crate::debug_dump`]
+    debug_dump: Option<PathBuf>,
+    /// Anonymous usage reporting; unset until answered, see
+    /// [`crate::telemetry`]
+    telemetry: Option<bool>,
 }
 
-#[derive(Debug, Clone)]
+/// `[profiles.<name>]`: settings for one registry instance, overriding
+/// `[defaults]`
+#[derive(Debug, Clone, Deserialize, Default)]
+struct ProfileSection {
+    api_url: Option<String>,
+    /// Environment variable holding the API token for this instance, read
+    /// instead of `SOROBAN_REGISTRY_API_TOKEN`
+    token_env: Option<String>,
+    network: Option<String>,
+    timeout: Option<u64>,
+    /// Print JSON from every command that has a `--json` flag
+    json: Option<bool>,
+    /// Force coloured output on or off
+    color: Option<bool>,
+    time_format: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct RuntimeConfig {
+    /// Name of the active profile, if any
+    pub profile: Option<String>,
     pub network: Network,
     pub api_base: String,
     pub timeout: u64,
+    /// Environment variable the API token is read from
+    pub token_env: String,
+    pub json: bool,
+    pub color: Option<bool>,
+    pub time_format: TimeFormat,
 }
 
-pub fn resolve_network(cli_network: Option<String>) -> Result<Network> {
-    let config = load_defaults_section()?;
-    match cli_network.or(config.network) {
-        Some(value) => value.parse::<Network>(),
-        None => Ok(Network::Testnet),
+impl RuntimeConfig {
+    /// Built-in settings, ignoring the config file; for `doctor`, which
+    /// reports a broken file or unknown profile instead of failing on it
+    pub fn builtin(profile: Option<String>, api_base: Option<String>) -> Self {
+        Self {
+            profile,
+            network: Network::Testnet,
+            api_base: api_base.unwrap_or_else(|| DEFAULT_API_BASE.to_string()),
+            timeout: DEFAULT_TIMEOUT_SECS,
+            token_env: crate::http::API_TOKEN_ENV.to_string(),
+            json: false,
+            color: None,
+            time_format: TimeFormat::default(),
+        }
     }
 }
 
+/// Proxy and TLS settings for the CLI's HTTP clients, see [`crate::http`]
+#[derive(Debug, Clone, Default)]
+pub struct HttpTransportConfig {
+    /// Replaces the proxy from `HTTP_PROXY`/`HTTPS_PROXY`
+    pub proxy: Option<String>,
+    /// PEM file of root certificates to trust on top of the built-in ones
+    pub ca_cert: Option<PathBuf>,
+    /// Skip TLS certificate verification; never read from the config file
+    pub insecure: bool,
+    /// Directory to write a capture of every failed request to
+    pub debug_dump: Option<PathBuf>,
+}
+
+/// Resolves the settings commands run with. Flags (and their environment
+/// variables) win over the active profile, which wins over `[defaults]`.
+/// The profile is `cli_profile` (`--profile` / `SOROBAN_REGISTRY_PROFILE`)
+/// or the one chosen with `config use`.
 pub fn resolve_runtime_config(
+    cli_profile: Option<String>,
     cli_network: Option<String>,
     cli_api_base: Option<String>,
     cli_timeout: Option<u64>,
 ) -> Result<RuntimeConfig> {
-    let config = load_defaults_section()?;
+    let config = load_config()?;
+    resolve_from(&config, cli_profile, cli_network, cli_api_base, cli_timeout)
+}
 
-    let network = match cli_network.or(config.network) {
+fn resolve_from(
+    config: &ConfigFile,
+    cli_profile: Option<String>,
+    cli_network: Option<String>,
+    cli_api_base: Option<String>,
+    cli_timeout: Option<u64>,
+) -> Result<RuntimeConfig> {
+    let defaults = config.defaults.clone().unwrap_or_default();
+    let profile_name = cli_profile.or(defaults.profile);
+    let profile = match &profile_name {
+        Some(name) => find_profile(config, name)?.clone(),
+        None => ProfileSection::default(),
+    };
+
+    let network = match cli_network.or(profile.network).or(defaults.network) {
         Some(value) => value.parse::<Network>()?,
         None => Network::Testnet,
     };
 
     let api_base = cli_api_base
-        .or(config.api_base)
+        .or(profile.api_url)
+        .or(defaults.api_base)
         .unwrap_or_else(|| DEFAULT_API_BASE.to_string());
 
     let timeout = cli_timeout
-        .or(config.timeout)
+        .or(profile.timeout)
+        .or(defaults.timeout)
         .unwrap_or(DEFAULT_TIMEOUT_SECS);
 
+    let time_format = match profile.time_format.or(defaults.time_format) {
+        Some(value) => value.parse::<TimeFormat>()?,
+        None => TimeFormat::default(),
+    };
+
     Ok(RuntimeConfig {
+        profile: profile_name,
         network,
         api_base,
         timeout,
+        token_env: profile
+            .token_env
+            .unwrap_or_else(|| crate::http::API_TOKEN_ENV.to_string()),
+        json: profile.json.unwrap_or(false),
+        color: profile.color,
+        time_format,
+    })
+}
+
+/// What `profile` resolves to under the config in `table`, without flags
+#[cfg(test)]
+pub(crate) fn resolve_table(table: &toml::Table, profile: Option<String>) -> Result<RuntimeConfig> {
+    let config: ConfigFile = toml::Value::Table(table.clone()).try_into()?;
+    resolve_from(&config, profile, None, None, None)
+}
+
+fn find_profile<'a>(config: &'a ConfigFile, name: &str) -> Result<&'a ProfileSection> {
+    config.profiles.get(name).with_context(|| {
+        if config.profiles.is_empty() {
+            format!(
+                "Profile `{}` is not defined; no profiles are configured. \
+                 Add a [profiles.{}] section to the config file",
+                name, name
+            )
+        } else {
+            format!(
+                "Profile `{}` is not defined; available profiles: {}",
+                name,
+                config
+                    .profiles
+                    .keys()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        }
+    })
+}
+
+/// `config use <name>`: makes `name` the profile used by default
+pub fn use_profile(name: &str) -> Result<()> {
+    find_profile(&load_config()?, name)?;
+    // A defined profile means the config file exists
+    let path = config_file_path().context("Could not determine home directory")?;
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file at {:?}", path))?;
+    fs::write(&path, set_default_profile(&content, name))
+        .with_context(|| format!("Failed to write config file at {:?}", path))?;
+    Ok(())
+}
+
+/// Sets `profile` under `[defaults]`, leaving the rest of the file, comments
+/// included, as it was.
+fn set_default_profile(content: &str, name: &str) -> String {
+    set_default(content, "profile", &format!("{:?}", name))
+}
+
+/// Sets `key` to the TOML literal `value` under `[defaults]`, leaving the
+/// rest of the file, comments included, as it was.
+fn set_default(content: &str, key: &str, value: &str) -> String {
+    let setting = format!("{} = {}", key, value);
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+
+    let Some(header) = lines.iter().position(|line| line.trim() == "[defaults]") else {
+        let mut updated = format!("[defaults]\n{}\n", setting);
+        if !content.is_empty() {
+            updated.push('\n');
+            updated.push_str(content);
+        }
+        return updated;
+    };
+    let end = lines[header + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |offset| header + 1 + offset);
+    let existing = lines[header + 1..end]
+        .iter()
+        .position(|line| line.split_once('=').is_some_and(|(k, _)| k.trim() == key));
+    match existing {
+        Some(offset) => lines[header + 1 + offset] = setting,
+        None => lines.insert(header + 1, setting),
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    updated
+}
+
+/// `defaults.telemetry`: `None` until the user has answered
+pub fn telemetry_setting() -> Result<Option<bool>> {
+    Ok(load_defaults_section()?.telemetry)
+}
+
+/// `config set telemetry <true|false>`: records the answer under
+/// `[defaults]`, creating the config file if there is none
+pub fn set_telemetry(enabled: bool) -> Result<()> {
+    migrate_legacy_config()?;
+    let path = config_file_path().context("Could not determine home directory")?;
+    ensure_config_file_exists(&path)?;
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file at {:?}", path))?;
+    fs::write(
+        &path,
+        set_default(&content, "telemetry", &enabled.to_string()),
+    )
+    .with_context(|| format!("Failed to write config file at {:?}", path))?;
+    Ok(())
+}
+
+/// `config profiles`: lists the configured profiles, marking the default
+pub fn list_profiles(active: Option<&str>) -> Result<()> {
+    let config = load_config()?;
+    if config.profiles.is_empty() {
+        println!("No profiles configured.");
+        if let Some(path) = config_file_path() {
+            println!("Add a [profiles.<name>] section to {}", path.display());
+        }
+        return Ok(());
+    }
+
+    let default = config.defaults.and_then(|defaults| defaults.profile);
+    let active = active.or(default.as_deref());
+    for (name, profile) in &config.profiles {
+        let marker = if Some(name.as_str()) == active {
+            "*"
+        } else {
+            " "
+        };
+        println!(
+            "{} {:<16} {:<40} network {}",
+            marker,
+            name,
+            profile.api_url.as_deref().unwrap_or("(default API)"),
+            profile.network.as_deref().unwrap_or("(default)")
+        );
+    }
+    Ok(())
+}
+
+pub fn resolve_http_transport(
+    cli_proxy: Option<String>,
+    cli_ca_cert: Option<PathBuf>,
+    insecure: bool,
+    cli_debug_dump: Option<PathBuf>,
+) -> Result<HttpTransportConfig> {
+    let config = load_defaults_section()?;
+
+    Ok(HttpTransportConfig {
+        proxy: cli_proxy.or(config.proxy),
+        ca_cert: cli_ca_cert.or(config.ca_cert),
+        insecure,
+        debug_dump: cli_debug_dump.or(config.debug_dump),
     })
 }
 
@@ -125,6 +369,33 @@ pub fn show_config() -> Result<()> {
         "defaults.timeout = {}",
         defaults.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS)
     );
+    println!(
+        "defaults.proxy = {}",
+        defaults
+            .proxy
+            .unwrap_or_else(|| "(from HTTP_PROXY/HTTPS_PROXY)".to_string())
+    );
+    println!(
+        "defaults.ca_cert = {}",
+        defaults
+            .ca_cert
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "(none)".to_string())
+    );
+    println!(
+        "defaults.debug_dump = {}",
+        defaults
+            .debug_dump
+            .map(|path| path.display().to_string())
+            .unwrap_or_else(|| "(off)".to_string())
+    );
+    println!(
+        "defaults.telemetry = {}",
+        match defaults.telemetry {
+            Some(enabled) => enabled.to_string(),
+            None => "(not answered)".to_string(),
+        }
+    );
 
     Ok(())
 }
@@ -147,19 +418,59 @@ pub fn edit_config() -> Result<()> {
     Ok(())
 }
 
+/// Checks that `table` would load as a config file: known settings with the
+/// right types, valid networks, and a default profile that is defined
+pub(crate) fn validate_table(table: &toml::Table) -> Result<()> {
+    let config: ConfigFile = toml::Value::Table(table.clone())
+        .try_into()
+        .context("Invalid config")?;
+    let defaults = config.defaults.clone().unwrap_or_default();
+    if let Some(network) = &defaults.network {
+        network.parse::<Network>().context("In [defaults]")?;
+    }
+    for (name, profile) in &config.profiles {
+        if let Some(network) = &profile.network {
+            network
+                .parse::<Network>()
+                .with_context(|| format!("In [profiles.{}]", name))?;
+        }
+    }
+    if let Some(name) = &defaults.profile {
+        find_profile(&config, name)?;
+    }
+    Ok(())
+}
+
+/// The config file as a plain TOML table, empty when there is none yet, with
+/// the path it is read from and written to
+pub(crate) fn load_table() -> Result<(PathBuf, toml::Table)> {
+    migrate_legacy_config()?;
+    let path = config_file_path().context("Could not determine home directory")?;
+    if !path.exists() {
+        return Ok((path, toml::Table::new()));
+    }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read config file at {:?}", path))?;
+    let table = toml::from_str(&content).with_context(|| "Failed to parse config file")?;
+    Ok((path, table))
+}
+
 fn load_defaults_section() -> Result<DefaultsSection> {
+    Ok(load_config()?.defaults.unwrap_or_default())
+}
+
+fn load_config() -> Result<ConfigFile> {
     migrate_legacy_config()?;
     let path = match config_file_path() {
         Some(p) => p,
-        None => return Ok(DefaultsSection::default()),
+        None => return Ok(ConfigFile::default()),
     };
 
     if !path.exists() {
-        return Ok(DefaultsSection::default());
+        return Ok(ConfigFile::default());
     }
 
-    let config = load_config_file(&path)?;
-    Ok(config.defaults.unwrap_or_default())
+    load_config_file(&path)
 }
 
 fn load_config_file(path: &Path) -> Result<ConfigFile> {
@@ -182,6 +493,18 @@ fn ensure_config_file_exists(path: &Path) -> Result<()> {
 network = "testnet"
 api_base = "http://localhost:3001"
 timeout = 30
+# proxy = "http://proxy.example.com:3128"
+# ca_cert = "/etc/ssl/certs/corporate-ca.pem"
+# profile = "prod"
+# time_format = "relative"  # relative, local, utc or iso
+# debug_dump = "/tmp/soroban-registry-captures"  # capture failed requests for bug reports
+# telemetry = false  # anonymous usage counts, see `soroban-registry telemetry show`
+
+# [profiles.prod]
+# api_url = "https://registry.example.com"
+# token_env = "PROD_REGISTRY_TOKEN"
+# network = "mainnet"
+# json = false
 "#;
     fs::write(path, default_content)
         .with_context(|| format!("Failed to write default config to {:?}", path))?;
@@ -272,6 +595,157 @@ timeout = 55
         assert_eq!(defaults.network.as_deref(), Some("mainnet"));
         assert_eq!(defaults.api_base.as_deref(), Some("http://localhost:9000"));
         assert_eq!(defaults.timeout, Some(55));
+        assert_eq!(defaults.proxy, None);
+    }
+
+    #[test]
+    fn test_load_config_file_with_transport_settings() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[defaults]
+proxy = "http://proxy.example.com:3128"
+ca_cert = "/etc/ssl/certs/corporate-ca.pem"
+debug_dump = "/tmp/captures"
+"#,
+        )
+        .unwrap();
+
+        let defaults = load_config_file(&config_path).unwrap().defaults.unwrap();
+        assert_eq!(defaults.debug_dump, Some(PathBuf::from("/tmp/captures")));
+
+        assert_eq!(
+            defaults.proxy.as_deref(),
+            Some("http://proxy.example.com:3128")
+        );
+        assert_eq!(
+            defaults.ca_cert,
+            Some(PathBuf::from("/etc/ssl/certs/corporate-ca.pem"))
+        );
+    }
+
+    fn parse(content: &str) -> ConfigFile {
+        toml::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn test_profile_overrides_defaults_and_flags_override_profile() {
+        let config = parse(
+            r#"[defaults]
+network = "testnet"
+api_base = "http://localhost:9000"
+timeout = 55
+profile = "staging"
+
+[profiles.staging]
+api_url = "https://staging.example.com"
+token_env = "STAGING_TOKEN"
+
+[profiles.prod]
+api_url = "https://registry.example.com"
+network = "mainnet"
+json = true
+color = false
+time_format = "utc"
+"#,
+        );
+
+        let runtime = resolve_from(&config, None, None, None, None).unwrap();
+        assert_eq!(runtime.profile.as_deref(), Some("staging"));
+        assert_eq!(runtime.api_base, "https://staging.example.com");
+        assert_eq!(runtime.network, Network::Testnet);
+        assert_eq!(runtime.timeout, 55);
+        assert_eq!(runtime.token_env, "STAGING_TOKEN");
+        assert!(!runtime.json);
+        assert_eq!(runtime.time_format, TimeFormat::Relative);
+
+        let runtime = resolve_from(&config, Some("prod".into()), None, None, None).unwrap();
+        assert_eq!(runtime.api_base, "https://registry.example.com");
+        assert_eq!(runtime.network, Network::Mainnet);
+        assert_eq!(runtime.token_env, crate::http::API_TOKEN_ENV);
+        assert!(runtime.json);
+        assert_eq!(runtime.color, Some(false));
+        assert_eq!(runtime.time_format, TimeFormat::Utc);
+
+        let runtime = resolve_from(
+            &config,
+            Some("prod".into()),
+            Some("futurenet".into()),
+            Some("http://localhost:3001".into()),
+            None,
+        )
+        .unwrap();
+        assert_eq!(runtime.api_base, "http://localhost:3001");
+        assert_eq!(runtime.network, Network::Futurenet);
+    }
+
+    #[test]
+    fn test_undefined_profile_lists_available_ones() {
+        let config = parse(
+            r#"[profiles.staging]
+[profiles.prod]
+"#,
+        );
+        let err = resolve_from(&config, Some("dev".into()), None, None, None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Profile `dev` is not defined; available profiles: prod, staging"
+        );
+
+        let err =
+            resolve_from(&ConfigFile::default(), Some("dev".into()), None, None, None).unwrap_err();
+        assert!(err.to_string().contains("no profiles are configured"));
+    }
+
+    #[test]
+    fn test_set_default_profile_keeps_the_rest_of_the_file() {
+        let content = r#"# registry settings
+[defaults]
+network = "testnet"
+
+[profiles.prod]
+api_url = "https://registry.example.com"
+"#;
+        let updated = set_default_profile(content, "prod");
+        assert_eq!(
+            updated,
+            r#"# registry settings
+[defaults]
+profile = "prod"
+network = "testnet"
+
+[profiles.prod]
+api_url = "https://registry.example.com"
+"#
+        );
+        assert_eq!(
+            set_default_profile(&updated, "staging"),
+            updated.replace("prod\"", "staging\"")
+        );
+
+        let updated = set_default_profile("[profiles.prod]\n", "prod");
+        assert_eq!(
+            updated,
+            "[defaults]\nprofile = \"prod\"\n\n[profiles.prod]\n"
+        );
+        assert_eq!(
+            parse(&updated).defaults.unwrap().profile.as_deref(),
+            Some("prod")
+        );
+    }
+
+    #[test]
+    fn test_set_default_adds_and_replaces_keys() {
+        let content = "[defaults]\nnetwork = \"testnet\"\n# telemetry = false\n";
+        let updated = set_default(content, "telemetry", "true");
+        assert_eq!(
+            updated,
+            "[defaults]\ntelemetry = true\nnetwork = \"testnet\"\n# telemetry = false\n"
+        );
+        let updated = set_default(&updated, "telemetry", "false");
+        assert_eq!(parse(&updated).defaults.unwrap().telemetry, Some(false));
+        assert_eq!(updated.matches("telemetry = ").count(), 2);
     }
 
     #[test]
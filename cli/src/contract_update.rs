@@ -0,0 +1,257 @@
+//! `update`: edit a contract's metadata without overwriting someone else's
+//! edit.
+//!
+//! The edit is sent with `If-Match` set to the ETag of the contract as it was
+//! read. When the registry answers 412 the contract changed in between: the
+//! command refetches it, shows each field being set as it was read, as it is
+//! now and as it would become, and asks before applying the edit on top of
+//! the current revision.
+
+use std::io::{self, BufRead, Write};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use reqwest::{header, StatusCode};
+use serde_json::{json, Map, Value};
+
+/// The fields `update` sets; `None` leaves a field as it is
+#[derive(Debug, Default, Clone)]
+pub struct MetadataChange {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub slug: Option<String>,
+}
+
+impl MetadataChange {
+    /// The fields being set, as the API names them
+    fn fields(&self) -> Vec<(&'static str, Value)> {
+        let mut fields = Vec::new();
+        if let Some(name) = &self.name {
+            fields.push(("name", json!(name)));
+        }
+        if let Some(description) = &self.description {
+            fields.push(("description", json!(description)));
+        }
+        if let Some(category) = &self.category {
+            fields.push(("category", json!(category)));
+        }
+        if let Some(tags) = &self.tags {
+            fields.push(("tags", json!(tags)));
+        }
+        if let Some(slug) = &self.slug {
+            fields.push(("slug", json!(slug)));
+        }
+        fields
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields().is_empty()
+    }
+
+    fn to_json(&self) -> Value {
+        let body: Map<String, Value> = self
+            .fields()
+            .into_iter()
+            .map(|(field, value)| (field.to_string(), value))
+            .collect();
+        Value::Object(body)
+    }
+}
+
+/// A field being set, as it was read, as it is now and as it would become
+#[derive(Debug, PartialEq)]
+struct FieldSummary {
+    field: &'static str,
+    read: Value,
+    current: Value,
+    yours: Value,
+}
+
+impl FieldSummary {
+    fn changed_since_read(&self) -> bool {
+        self.read != self.current
+    }
+}
+
+fn three_way(read: &Value, current: &Value, change: &MetadataChange) -> Vec<FieldSummary> {
+    change
+        .fields()
+        .into_iter()
+        .map(|(field, yours)| FieldSummary {
+            field,
+            read: read[field].clone(),
+            current: current[field].clone(),
+            yours,
+        })
+        .collect()
+}
+
+pub async fn update(
+    api_url: &str,
+    contract_id: &str,
+    change: &MetadataChange,
+    auth_token: Option<&str>,
+) -> Result<()> {
+    let base = api_url.trim_end_matches('/');
+    let (mut read, mut etag) = fetch(base, contract_id).await?;
+
+    loop {
+        let mut request = crate::http::client()
+            .patch(format!("{}/api/contracts/{}/metadata", base, contract_id))
+            .header(header::IF_MATCH, &etag)
+            .json(&change.to_json());
+        if let Some(token) = auth_token {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await.context("Failed to update contract")?;
+
+        match response.status() {
+            status if status.is_success() => {
+                let contract: Value = response.json().await?;
+                println!("{}", "✓ Contract updated".green().bold());
+                println!(
+                    "\n{}: {}",
+                    "Name".bold(),
+                    crate::conversions::as_str(&contract["name"], "name")?
+                );
+                println!("{}: {}", "Revision".bold(), contract["revision"]);
+                println!();
+                return Ok(());
+            }
+            StatusCode::PRECONDITION_FAILED => {
+                let (current, current_etag) = fetch(base, contract_id).await?;
+                eprintln!(
+                    "{} the contract changed since it was read (revision {} → {})",
+                    "Conflict:".yellow().bold(),
+                    read["revision"],
+                    current["revision"]
+                );
+                print_summary(&three_way(&read, &current, change));
+                if !ask_to_retry()? {
+                    bail!(
+                        "Update abandoned; the contract is still at revision {}",
+                        current["revision"]
+                    );
+                }
+                read = current;
+                etag = current_etag;
+            }
+            status => {
+                let body: Value = response.json().await.unwrap_or_default();
+                let message = body["error"]["message"].as_str().unwrap_or("no details");
+                bail!("Failed to update: {} ({})", message, status);
+            }
+        }
+    }
+}
+
+/// The contract and its ETag
+async fn fetch(base: &str, contract_id: &str) -> Result<(Value, String)> {
+    let response = crate::http::client()
+        .get(format!("{}/api/contracts/{}", base, contract_id))
+        .send()
+        .await
+        .context("Failed to fetch contract")?;
+    if !response.status().is_success() {
+        bail!("Failed to fetch contract ({})", response.status());
+    }
+    let etag = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|etag| etag.to_str().ok())
+        .map(str::to_string);
+    let contract: Value = response.json().await?;
+    // Registries from before revisions were sent as ETags
+    let etag = etag.unwrap_or_else(|| format!("\"{}\"", contract["revision"]));
+    Ok((contract, etag))
+}
+
+fn print_summary(summary: &[FieldSummary]) {
+    let show = |value: &Value| match value {
+        Value::Null => "-".to_string(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    };
+    eprintln!(
+        "  {:<12} {:<24} {:<24} {}",
+        "field".bold(),
+        "you read".bold(),
+        "now".bold(),
+        "yours".bold()
+    );
+    for field in summary {
+        let now = format!("{:<24}", show(&field.current));
+        let now = if field.changed_since_read() {
+            now.yellow().to_string()
+        } else {
+            now
+        };
+        eprintln!(
+            "  {:<12} {:<24} {} {}",
+            field.field,
+            show(&field.read),
+            now,
+            show(&field.yours)
+        );
+    }
+    if !summary.iter().any(FieldSummary::changed_since_read) {
+        eprintln!(
+            "  {}",
+            "(none of your fields changed; the edit was elsewhere)".bright_black()
+        );
+    }
+}
+
+fn ask_to_retry() -> Result<bool> {
+    eprint!("Apply your changes on top of the current revision? [y/N] ");
+    io::stderr().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_covers_only_fields_being_set() {
+        let read =
+            json!({ "name": "vault", "description": "old", "tags": ["defi"], "revision": 3 });
+        let current =
+            json!({ "name": "vault", "description": "newer", "tags": ["defi"], "revision": 4 });
+        let change = MetadataChange {
+            description: Some("mine".to_string()),
+            tags: Some(vec!["defi".to_string(), "yield".to_string()]),
+            ..Default::default()
+        };
+
+        let summary = three_way(&read, &current, &change);
+        assert_eq!(
+            summary,
+            vec![
+                FieldSummary {
+                    field: "description",
+                    read: json!("old"),
+                    current: json!("newer"),
+                    yours: json!("mine"),
+                },
+                FieldSummary {
+                    field: "tags",
+                    read: json!(["defi"]),
+                    current: json!(["defi"]),
+                    yours: json!(["defi", "yield"]),
+                },
+            ]
+        );
+        assert!(summary[0].changed_since_read());
+        assert!(!summary[1].changed_since_read());
+        assert_eq!(
+            change.to_json(),
+            json!({ "description": "mine", "tags": ["defi", "yield"] })
+        );
+        assert!(MetadataChange::default().is_empty());
+    }
+}
@@ -59,7 +59,7 @@ pub async fn run(api_url: &str, address: &str, network: &str, json: bool) -> Res
         api_url
     );
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     // ── 1. Fetch contract from registry by on-chain address ──────────────────
     let search_url = format!(
@@ -136,14 +136,14 @@ pub async fn run(api_url: &str, address: &str, network: &str, json: bool) -> Res
 // ── Helpers ───────────────────────────────────────────────────────────────────
 
 /// Pull the first contract item from either a paginated list or a direct object.
-fn extract_contract<'a>(raw: &'a Value, address: &str) -> Result<Value> {
+fn extract_contract(raw: &Value, address: &str) -> Result<Value> {
     // Paginated: { items: [...] }
     if let Some(items) = raw["items"].as_array() {
         return items
             .iter()
             .find(|c| {
-                c["contract_id"].as_str().map_or(false, |id| id == address)
-                    || c["network_configs"].as_object().map_or(false, |nc| {
+                c["contract_id"].as_str() == Some(address)
+                    || c["network_configs"].as_object().is_some_and(|nc| {
                         nc.values()
                             .any(|v| v["contract_id"].as_str() == Some(address))
                     })
@@ -195,7 +195,11 @@ fn build_result(contract: &Value, address: &str, network: &str) -> VerificationR
 }
 
 /// Try to fetch verification detail endpoint. Non-fatal — returns None on error.
-async fn fetch_detail(api_url: &str, client: &reqwest::Client, contract: &Value) -> Option<Value> {
+async fn fetch_detail(
+    api_url: &str,
+    client: &crate::http::Client,
+    contract: &Value,
+) -> Option<Value> {
     let id = contract["id"]
         .as_str()
         .or(contract["contract_id"].as_str())?;
@@ -1,7 +1,9 @@
+#![allow(dead_code)]
+
 use anyhow::{Context, Result};
 use colored::Colorize;
 use serde_json::json;
-use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ContractListItem {
@@ -69,6 +71,56 @@ impl std::str::FromStr for SortOrder {
     }
 }
 
+/// Most ids `POST /api/contracts/batch` accepts per request
+const BATCH_LIMIT: usize = 100;
+
+/// Looks up contracts by UUID or address with `POST /api/contracts/batch`,
+/// 100 ids per request. `fields` limits the returned fields
+/// (e.g. "id,name"). Ids the registry doesn't know are left out of the map.
+pub async fn fetch_batch(
+    api_url: &str,
+    ids: &[String],
+    fields: Option<&str>,
+) -> Result<HashMap<String, serde_json::Value>> {
+    let client = crate::http::client();
+    let mut unique: Vec<&String> = ids.iter().collect();
+    unique.sort();
+    unique.dedup();
+
+    let mut found = HashMap::new();
+    for chunk in unique.chunks(BATCH_LIMIT) {
+        let mut request = client.post(format!("{}/api/contracts/batch", api_url));
+        if let Some(fields) = fields {
+            request = request.query(&[("fields", fields)]);
+        }
+        let response = request
+            .json(chunk)
+            .send()
+            .await
+            .context("Failed to look up contracts")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            let message = body["error"]["message"].as_str().unwrap_or("no details");
+            anyhow::bail!("Failed to look up contracts: {} ({})", message, status);
+        }
+
+        let mut body: serde_json::Value = response.json().await.context("Invalid response")?;
+        let missing: HashSet<String> = serde_json::from_value(body["missing"].take())
+            .context("Invalid response: missing `missing`")?;
+        if let Some(contracts) = body["contracts"].as_object_mut() {
+            found.extend(
+                std::mem::take(contracts)
+                    .into_iter()
+                    .filter(|(id, _)| !missing.contains(id)),
+            );
+        }
+    }
+    Ok(found)
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn list_contracts(
     api_url: &str,
     network: Option<&str>,
@@ -104,7 +156,7 @@ pub async fn list_contracts(
 
     log::debug!("Fetching contracts from: {}", url);
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let response = client
         .get(&url)
         .send()
@@ -132,7 +184,7 @@ pub async fn list_contracts(
     // Parse contracts
     let mut contracts = contracts_array
         .iter()
-        .filter_map(|item| {
+        .map(|item| {
             let id = item
                 .get("id")
                 .or_else(|| item.get("contract_id"))
@@ -189,7 +241,7 @@ pub async fn list_contracts(
                 })
                 .unwrap_or_default();
 
-            Some(ContractListItem {
+            ContractListItem {
                 id,
                 name,
                 contract_id,
@@ -199,7 +251,7 @@ pub async fn list_contracts(
                 health_score,
                 created_at,
                 tags,
-            })
+            }
         })
         .collect::<Vec<_>>();
 
@@ -44,7 +44,7 @@ pub async fn estimate_costs(
     optimize: bool,
     forecast: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     let request = CostEstimateRequest {
         method_name: method.to_string(),
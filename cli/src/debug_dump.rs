@@ -0,0 +1,341 @@
+//! `--debug-dump <dir>`: a capture file for every failed request, to attach
+//! to bug reports.
+//!
+//! A request fails when it can't be sent or is answered with a 4xx or 5xx
+//! status. Its capture holds the method, URL, headers, bodies, status,
+//! timing and request id, with everything that could be a credential left
+//! out: header values are only kept for the headers in [`SAFE_HEADERS`],
+//! JSON fields named like secrets and anything shaped like a Stellar secret
+//! seed are replaced, and bodies are cut off after [`MAX_BODY_BYTES`].
+//! `doctor --last-error` prints the newest capture in the directory.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Headers whose values say nothing about the caller's credentials
+const SAFE_HEADERS: [&str; 16] = [
+    "accept",
+    "accept-ranges",
+    "content-length",
+    "content-range",
+    "content-type",
+    "date",
+    "etag",
+    "if-range",
+    "location",
+    "range",
+    "retry-after",
+    "server",
+    "user-agent",
+    "x-ratelimit-remaining",
+    "x-ratelimit-reset",
+    crate::http::REQUEST_ID_HEADER,
+];
+/// JSON fields whose values are replaced, matched as parts of the name
+const SECRET_FIELDS: [&str; 8] = [
+    "secret",
+    "password",
+    "passphrase",
+    "private",
+    "token",
+    "seed",
+    "mnemonic",
+    "signing_key",
+];
+/// Request and response bodies are each kept up to this size
+const MAX_BODY_BYTES: usize = 64 * 1024;
+const REDACTED: &str = "[redacted]";
+
+static DIR: OnceLock<PathBuf> = OnceLock::new();
+static LAST_CAPTURE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// One failed request, as written to the dump directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capture {
+    pub captured_at: DateTime<Utc>,
+    pub request_id: Option<String>,
+    pub method: String,
+    pub url: String,
+    pub request_headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    /// Missing when no response arrived
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub response_headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_body: Option<String>,
+    /// Why the request could not be sent or read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_ms: u128,
+}
+
+/// Writes captures to `dir` from now on. Call once, before the first client
+/// is created.
+pub fn enable(dir: PathBuf) {
+    let _ = DIR.set(dir);
+}
+
+/// Where captures go, when enabled
+pub fn dir() -> Option<&'static Path> {
+    DIR.get().map(PathBuf::as_path)
+}
+
+/// The capture written for the most recent failed request of this run
+pub fn last_capture() -> Option<PathBuf> {
+    LAST_CAPTURE
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// `headers` as strings, with values outside [`SAFE_HEADERS`] replaced
+pub fn redact_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SAFE_HEADERS.contains(&name.as_str()) {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            } else {
+                REDACTED.to_string()
+            };
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
+
+/// A body as text for a capture: secrets in JSON replaced, and cut off
+/// after [`MAX_BODY_BYTES`]
+pub fn redact_body(bytes: &[u8]) -> Option<String> {
+    if bytes.is_empty() {
+        return None;
+    }
+    let text = match serde_json::from_slice::<Value>(bytes) {
+        Ok(mut json) => {
+            redact_json(&mut json);
+            json.to_string()
+        }
+        Err(_) => String::from_utf8_lossy(bytes).into_owned(),
+    };
+    Some(truncate(text))
+}
+
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                let name = name.to_lowercase();
+                if SECRET_FIELDS.iter().any(|secret| name.contains(secret)) {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        Value::String(text) if is_secret_seed(text) => *text = REDACTED.to_string(),
+        _ => {}
+    }
+}
+
+/// A Stellar secret seed: `S` and 55 more base32 characters
+fn is_secret_seed(text: &str) -> bool {
+    text.len() == 56
+        && text.starts_with('S')
+        && text
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c))
+}
+
+fn truncate(mut text: String) -> String {
+    if text.len() <= MAX_BODY_BYTES {
+        return text;
+    }
+    let total = text.len();
+    let mut end = MAX_BODY_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text.truncate(end);
+    text.push_str(&format!("… [{} more bytes]", total - end));
+    text
+}
+
+/// Writes `capture` to the dump directory and remembers its path for the
+/// error message
+pub fn write(capture: &Capture) -> Result<PathBuf> {
+    let dir = dir().context("--debug-dump is not enabled")?;
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!(
+        "{}-{}.json",
+        capture.captured_at.format("%Y%m%dT%H%M%S%.3fZ"),
+        capture.request_id.as_deref().unwrap_or("no-request-id")
+    ));
+    fs::write(&path, serde_json::to_string_pretty(capture)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    *LAST_CAPTURE.lock().unwrap_or_else(PoisonError::into_inner) = Some(path.clone());
+    Ok(path)
+}
+
+/// The newest capture in `dir`; file names start with the capture time
+pub fn newest_in(dir: &Path) -> Result<Option<PathBuf>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .max_by(|a, b| a.file_name().cmp(&b.file_name())))
+}
+
+/// `doctor --last-error`
+pub fn print_last(json: bool) -> Result<()> {
+    let dir = dir().context(
+        "No capture directory: pass --debug-dump <dir> or set debug_dump in the config file",
+    )?;
+    let path = newest_in(dir)?
+        .with_context(|| format!("No failed requests captured in {}", dir.display()))?;
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let capture: Capture = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a request capture", path.display()))?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&capture)?);
+        return Ok(());
+    }
+    println!("{} {}", "Capture:".bold(), path.display());
+    print!("{}", describe(&capture));
+    Ok(())
+}
+
+fn describe(capture: &Capture) -> String {
+    let mut out = String::new();
+    let outcome = match (capture.status, &capture.error) {
+        (Some(status), _) => status.to_string(),
+        (None, Some(error)) => format!("no response: {}", error),
+        (None, None) => "no response".to_string(),
+    };
+    out.push_str(&format!(
+        "{} {} → {} in {}ms\n",
+        capture.method.bold(),
+        capture.url,
+        outcome.red(),
+        capture.duration_ms
+    ));
+    out.push_str(&format!(
+        "At {}, request id {}\n",
+        crate::time_format::show(capture.captured_at),
+        capture.request_id.as_deref().unwrap_or("(none)")
+    ));
+    for (title, headers, body) in [
+        ("Request", &capture.request_headers, &capture.request_body),
+        (
+            "Response",
+            &capture.response_headers,
+            &capture.response_body,
+        ),
+    ] {
+        if headers.is_empty() && body.is_none() {
+            continue;
+        }
+        out.push_str(&format!("\n{}\n", title.bold().cyan()));
+        for (name, value) in headers {
+            out.push_str(&format!("  {}: {}\n", name, value));
+        }
+        if let Some(body) = body {
+            out.push_str(&format!("\n{}\n", indent(&pretty(body))));
+        }
+    }
+    out
+}
+
+/// JSON bodies indented, anything else as it is
+fn pretty(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|json| serde_json::to_string_pretty(&json).ok())
+        .unwrap_or_else(|| body.to_string())
+}
+
+fn indent(text: &str) -> String {
+    text.lines()
+        .map(|line| format!("  {}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+
+    const SEED: &str = "SDHOAMBNLGCE2MV5ZKIVZAQD3VCLGP53P3OBSBI6UN5L5XZI5TKHFQL4";
+
+    #[test]
+    fn only_allowlisted_header_values_are_kept() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer abc.def"));
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        headers.insert("x-api-key", HeaderValue::from_static("k-123"));
+
+        let redacted = redact_headers(&headers);
+        assert_eq!(redacted["authorization"], REDACTED);
+        assert_eq!(redacted["x-api-key"], REDACTED);
+        assert_eq!(redacted["content-type"], "application/json");
+    }
+
+    #[test]
+    fn secrets_in_json_bodies_are_replaced() {
+        let body = serde_json::json!({
+            "name": "pool",
+            "signature": "c2lnbmF0dXJl",
+            "keys": [{ "secret_key": "abc" }, { "public_key": "GABC" }],
+            "note": SEED,
+            "auth": { "access_token": "xyz" }
+        });
+        let redacted = redact_body(body.to_string().as_bytes()).unwrap();
+        assert!(!redacted.contains("abc\"") && !redacted.contains("xyz"));
+        assert!(!redacted.contains(SEED));
+        assert!(redacted.contains("GABC") && redacted.contains("c2lnbmF0dXJl"));
+        assert_eq!(redact_body(b""), None);
+    }
+
+    #[test]
+    fn bodies_are_capped() {
+        let body = "é".repeat(MAX_BODY_BYTES);
+        let redacted = redact_body(body.as_bytes()).unwrap();
+        assert!(redacted.len() < MAX_BODY_BYTES + 32);
+        assert!(redacted.ends_with(&format!("… [{} more bytes]", MAX_BODY_BYTES)));
+    }
+
+    #[test]
+    fn the_newest_capture_is_found_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(newest_in(&dir.path().join("missing")).unwrap(), None);
+        for name in [
+            "20261016T090000.000Z-a.json",
+            "20261016T101500.250Z-b.json",
+            "notes.txt",
+        ] {
+            fs::write(dir.path().join(name), "{}").unwrap();
+        }
+        assert_eq!(
+            newest_in(dir.path()).unwrap(),
+            Some(dir.path().join("20261016T101500.250Z-b.json"))
+        );
+    }
+}
@@ -0,0 +1,618 @@
+//! `doctor`: checks the usual reasons the CLI cannot talk to the registry,
+//! from the config file down to the local cache directory.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::{DateTime, TimeZone, Utc};
+use colored::Colorize;
+use reqwest::header::DATE;
+use reqwest::{StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use tokio::net::{lookup_host, TcpStream};
+
+use crate::{config, http};
+
+/// DNS lookups and TCP connects give up after this long
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+/// Larger differences from the server clock are reported; token expiry and
+/// signed challenges are checked against it
+const MAX_CLOCK_SKEW_SECS: i64 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    /// A problem that does not stop commands from working
+    Warn,
+    /// A problem that does; makes `doctor` exit non-zero
+    Fail,
+    /// Not run, because an earlier check failed or it does not apply
+    Skip,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to try when the check did not pass
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn pass(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn skip(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Skip,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Runs every check against `api_url`, prints the results and exits with
+/// status 1 if any check failed.
+pub async fn run(
+    api_url: &str,
+    profile: Option<String>,
+    network: Option<String>,
+    json: bool,
+) -> Result<()> {
+    let checks = run_checks(api_url, profile, network).await;
+
+    if json {
+        let ok = !has_failures(&checks);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({ "ok": ok, "checks": checks }))
+                .context("Failed to serialize doctor results")?
+        );
+    } else {
+        print_checks(&checks);
+    }
+
+    if has_failures(&checks) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn run_checks(api_url: &str, profile: Option<String>, network: Option<String>) -> Vec<Check> {
+    let mut checks = vec![check_config(api_url, profile, network)];
+
+    let url = match Url::parse(api_url) {
+        Ok(url) if url.host_str().is_some() => url,
+        _ => {
+            checks.push(Check::fail(
+                "dns",
+                format!("`{}` is not a valid API URL", api_url),
+                "pass --api-url or set SOROBAN_REGISTRY_API_URL, e.g. https://registry.example.com",
+            ));
+            for name in ["tcp", "tls", "health", "auth", "clock"] {
+                checks.push(Check::skip(name, "no valid API URL"));
+            }
+            checks.push(check_cache_dir());
+            return checks;
+        }
+    };
+
+    let reachable = match http::active_proxy() {
+        // Only the proxy has to resolve and accept connections
+        Some(proxy) => {
+            let detail = format!("requests go through proxy {}", proxy);
+            checks.push(Check::skip("dns", detail.clone()));
+            checks.push(Check::skip("tcp", detail));
+            true
+        }
+        None => {
+            let (dns, addrs) = check_dns(&url).await;
+            checks.push(dns);
+            let tcp = match addrs {
+                Some(addrs) => check_tcp(&addrs).await,
+                None => Check::skip("tcp", "host did not resolve"),
+            };
+            let reachable = tcp.status == CheckStatus::Pass;
+            checks.push(tcp);
+            reachable
+        }
+    };
+
+    let mut server_time = None;
+    if reachable {
+        let (tls, health, date) = check_health(&url).await;
+        checks.push(tls);
+        checks.push(health);
+        server_time = date;
+    } else {
+        checks.push(Check::skip("tls", "API host is not reachable"));
+        checks.push(Check::skip("health", "API host is not reachable"));
+    }
+
+    let token = std::env::var(http::token_env()).ok();
+    checks.push(check_token(
+        token.as_deref(),
+        server_time.unwrap_or_else(Utc::now),
+    ));
+    checks.push(match server_time {
+        Some(server_time) => check_clock(server_time, Utc::now()),
+        None => Check::skip("clock", "no Date header from the API"),
+    });
+    checks.push(check_cache_dir());
+    checks
+}
+
+fn has_failures(checks: &[Check]) -> bool {
+    checks.iter().any(|check| check.status == CheckStatus::Fail)
+}
+
+fn check_config(api_url: &str, profile: Option<String>, network: Option<String>) -> Check {
+    let path = config::config_file_path();
+    let source = match &path {
+        Some(path) if path.exists() => path.display().to_string(),
+        Some(path) => format!("{} (not present, using defaults)", path.display()),
+        None => "no home directory, using defaults".to_string(),
+    };
+
+    match config::resolve_runtime_config(profile, network, None, None) {
+        Ok(runtime) => Check::pass(
+            "config",
+            format!(
+                "{}; profile {}, network {}, API {}, timeout {}s, proxy {}",
+                source,
+                runtime.profile.as_deref().unwrap_or("none"),
+                runtime.network,
+                api_url,
+                runtime.timeout,
+                http::active_proxy().unwrap_or_else(|| "none".to_string()),
+            ),
+        ),
+        Err(err) => Check::fail(
+            "config",
+            format!("{}: {:#}", source, err),
+            "fix the file or move it aside to fall back to the defaults; \
+             `soroban-registry config profiles` lists the defined profiles",
+        ),
+    }
+}
+
+async fn check_dns(url: &Url) -> (Check, Option<Vec<std::net::SocketAddr>>) {
+    let host = url.host_str().unwrap_or_default().to_string();
+    let port = url.port_or_known_default().unwrap_or(80);
+
+    let lookup = tokio::time::timeout(CONNECT_TIMEOUT, lookup_host((host.as_str(), port))).await;
+    match lookup {
+        Ok(Ok(addrs)) => {
+            let addrs: Vec<_> = addrs.collect();
+            if addrs.is_empty() {
+                return (
+                    Check::fail(
+                        "dns",
+                        format!("{} has no addresses", host),
+                        "check the API URL and your DNS settings",
+                    ),
+                    None,
+                );
+            }
+            let shown: Vec<String> = addrs.iter().map(|addr| addr.ip().to_string()).collect();
+            (
+                Check::pass("dns", format!("{} -> {}", host, shown.join(", "))),
+                Some(addrs),
+            )
+        }
+        Ok(Err(err)) => (
+            Check::fail(
+                "dns",
+                format!("could not resolve {}: {}", host, err),
+                "check the API URL (--api-url / SOROBAN_REGISTRY_API_URL) and your DNS settings",
+            ),
+            None,
+        ),
+        Err(_) => (
+            Check::fail(
+                "dns",
+                format!("resolving {} timed out", host),
+                "check your DNS settings or network connection",
+            ),
+            None,
+        ),
+    }
+}
+
+async fn check_tcp(addrs: &[std::net::SocketAddr]) -> Check {
+    match tokio::time::timeout(CONNECT_TIMEOUT, TcpStream::connect(addrs)).await {
+        Ok(Ok(stream)) => {
+            let peer = stream
+                .peer_addr()
+                .map(|addr| addr.to_string())
+                .unwrap_or_default();
+            Check::pass("tcp", format!("connected to {}", peer))
+        }
+        Ok(Err(err)) => Check::fail(
+            "tcp",
+            format!("could not connect: {}", err),
+            "check that the API is running and that no firewall blocks the port; \
+             behind a proxy, set HTTPS_PROXY or pass --proxy",
+        ),
+        Err(_) => Check::fail(
+            "tcp",
+            "connecting timed out",
+            "a firewall may be dropping the connection; behind a proxy, set HTTPS_PROXY \
+             or pass --proxy",
+        ),
+    }
+}
+
+/// Requests `/health/ready`, returning the TLS and health checks and the
+/// server's clock from the `Date` header.
+async fn check_health(url: &Url) -> (Check, Check, Option<DateTime<Utc>>) {
+    let https = url.scheme() == "https";
+    let ready_url = format!("{}/health/ready", url.as_str().trim_end_matches('/'));
+
+    let response = match http::client_builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client.get(&ready_url).send().await,
+        Err(err) => {
+            return (
+                Check::fail("tls", format!("{:#}", err), "check --ca-cert"),
+                Check::skip("health", "no HTTP client"),
+                None,
+            )
+        }
+    };
+
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => {
+            let detail = error_chain(&err);
+            let tls = if https {
+                Check::fail(
+                    "tls",
+                    detail,
+                    "if a proxy or middlebox re-signs TLS traffic, trust its root with \
+                     --ca-cert <path>",
+                )
+            } else {
+                Check::skip("tls", format!("plain HTTP; request failed: {}", detail))
+            };
+            let health = Check::fail(
+                "health",
+                format!("GET {} failed", ready_url),
+                http::connection_hint(&anyhow::Error::new(err))
+                    .unwrap_or_else(|| "check the API URL".to_string()),
+            );
+            return (tls, health, None);
+        }
+    };
+
+    let tls = if https {
+        Check::pass("tls", "handshake succeeded")
+    } else {
+        Check::skip("tls", "plain HTTP")
+    };
+
+    let server_time = response
+        .headers()
+        .get(DATE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .map(|date| date.with_timezone(&Utc));
+
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let health = match status {
+        StatusCode::OK => Check::pass(
+            "health",
+            match body["version"].as_str() {
+                Some(version) => format!("ready, API version {}", version),
+                None => "ready".to_string(),
+            },
+        ),
+        StatusCode::SERVICE_UNAVAILABLE => Check::fail(
+            "health",
+            format!(
+                "not ready: {}",
+                body["reason"].as_str().unwrap_or("no reason given")
+            ),
+            "the API is up but cannot serve requests yet; retry shortly or check the \
+             server logs",
+        ),
+        status => Check::fail(
+            "health",
+            format!("GET {} returned {}", ready_url, status),
+            "the URL may not point at the registry API; check --api-url",
+        ),
+    };
+
+    (tls, health, server_time)
+}
+
+fn error_chain(err: &(dyn std::error::Error + 'static)) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// The claims `doctor` looks at in an API token
+#[derive(Debug, Deserialize)]
+struct TokenClaims {
+    sub: Option<String>,
+    exp: Option<i64>,
+}
+
+/// Reads the claims of a JWT without checking its signature, which only
+/// the server can do.
+fn decode_token_claims(token: &str) -> Result<TokenClaims> {
+    let mut parts = token.trim().split('.');
+    let (Some(_header), Some(payload), Some(_signature), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        anyhow::bail!("not a JWT (expected three dot-separated parts)");
+    };
+    let payload = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .context("payload is not base64url")?;
+    serde_json::from_slice(&payload).context("payload is not a JSON object")
+}
+
+/// Checks that the API token is a JWT that has not expired by `now`, the
+/// server's clock when known.
+fn check_token(token: Option<&str>, now: DateTime<Utc>) -> Check {
+    let token = match token.map(str::trim) {
+        Some(token) if !token.is_empty() => token,
+        _ => {
+            return Check::skip(
+                "auth",
+                format!(
+                    "{} is not set; only commands that act as a publisher need it",
+                    http::token_env()
+                ),
+            )
+        }
+    };
+
+    let hint = format!(
+        "sign in again and set {} to the new token",
+        http::token_env()
+    );
+    let claims = match decode_token_claims(token) {
+        Ok(claims) => claims,
+        Err(err) => return Check::fail("auth", format!("token is invalid: {:#}", err), hint),
+    };
+
+    let subject = claims.sub.unwrap_or_else(|| "unknown subject".to_string());
+    match claims
+        .exp
+        .and_then(|exp| Utc.timestamp_opt(exp, 0).single())
+    {
+        Some(expires) if expires <= now => Check::fail(
+            "auth",
+            format!("token for {} expired at {}", subject, expires.to_rfc3339()),
+            hint,
+        ),
+        Some(expires) => Check::pass(
+            "auth",
+            format!("token for {}, expires at {}", subject, expires.to_rfc3339()),
+        ),
+        None => Check::pass("auth", format!("token for {}, no expiry", subject)),
+    }
+}
+
+fn check_clock(server_time: DateTime<Utc>, local_time: DateTime<Utc>) -> Check {
+    let skew = local_time.signed_duration_since(server_time).num_seconds();
+    let detail = match skew {
+        0 => "in sync with the API server".to_string(),
+        skew if skew > 0 => format!("{}s ahead of the API server", skew),
+        skew => format!("{}s behind the API server", -skew),
+    };
+    if skew.abs() > MAX_CLOCK_SKEW_SECS {
+        Check::warn(
+            "clock",
+            detail,
+            "sync the system clock (e.g. enable NTP); token expiry and signed challenges \
+             depend on it",
+        )
+    } else {
+        Check::pass("clock", detail)
+    }
+}
+
+fn check_cache_dir() -> Check {
+    match dirs::home_dir() {
+        Some(home) => check_dir_writable(&home.join(".soroban-registry")),
+        None => Check::warn(
+            "cache",
+            "no home directory",
+            "set HOME so the CLI can cache network status",
+        ),
+    }
+}
+
+fn check_dir_writable(dir: &Path) -> Check {
+    let result = fs::create_dir_all(dir).and_then(|()| tempfile::NamedTempFile::new_in(dir));
+    match result {
+        Ok(_) => Check::pass("cache", format!("{} is writable", dir.display())),
+        Err(err) => Check::warn(
+            "cache",
+            format!("{} is not writable: {}", dir.display(), err),
+            "fix the directory's permissions; commands still work but cannot cache results",
+        ),
+    }
+}
+
+fn print_checks(checks: &[Check]) {
+    println!("\n{}", "Soroban Registry Doctor".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    for check in checks {
+        let marker = match check.status {
+            CheckStatus::Pass => "✓".green().bold(),
+            CheckStatus::Warn => "!".yellow().bold(),
+            CheckStatus::Fail => "✗".red().bold(),
+            CheckStatus::Skip => "-".dimmed(),
+        };
+        let detail = match check.status {
+            CheckStatus::Pass => check.detail.normal(),
+            CheckStatus::Warn => check.detail.yellow(),
+            CheckStatus::Fail => check.detail.red(),
+            CheckStatus::Skip => check.detail.dimmed(),
+        };
+        println!(
+            "  {} {} {}",
+            marker,
+            format!("{:<7}", check.name).bold(),
+            detail
+        );
+        if let Some(hint) = &check.hint {
+            println!("            {} {}", "↳".bright_black(), hint);
+        }
+    }
+
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+    let warned = checks
+        .iter()
+        .filter(|check| check.status == CheckStatus::Warn)
+        .count();
+    println!();
+    if failed == 0 && warned == 0 {
+        println!("{}", "Everything looks good.".green().bold());
+    } else {
+        println!(
+            "{} failed, {} warning(s)",
+            failed.to_string().red().bold(),
+            warned.to_string().yellow().bold()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(payload: serde_json::Value) -> String {
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        format!(
+            "{}.{}.{}",
+            engine.encode(r#"{"alg":"HS256","typ":"JWT"}"#),
+            engine.encode(payload.to_string()),
+            engine.encode("signature")
+        )
+    }
+
+    fn at(timestamp: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(timestamp, 0).unwrap()
+    }
+
+    #[test]
+    fn token_check_reports_expiry_against_the_given_time() {
+        let valid = token(serde_json::json!({ "sub": "GABC", "exp": 2_000 }));
+
+        let check = check_token(Some(&valid), at(1_000));
+        assert_eq!(check.status, CheckStatus::Pass);
+        assert!(check.detail.contains("GABC"));
+
+        let check = check_token(Some(&valid), at(3_000));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.contains("expired"));
+        assert!(check.hint.is_some());
+    }
+
+    #[test]
+    fn token_check_skips_missing_and_rejects_malformed_tokens() {
+        assert_eq!(check_token(None, at(0)).status, CheckStatus::Skip);
+        assert_eq!(check_token(Some("  "), at(0)).status, CheckStatus::Skip);
+        assert_eq!(
+            check_token(Some("not-a-jwt"), at(0)).status,
+            CheckStatus::Fail
+        );
+        assert_eq!(
+            check_token(Some("a.!!!.c"), at(0)).status,
+            CheckStatus::Fail
+        );
+    }
+
+    #[test]
+    fn clock_skew_beyond_the_limit_is_a_warning() {
+        let server = at(1_000_000);
+
+        assert_eq!(check_clock(server, at(1_000_030)).status, CheckStatus::Pass);
+
+        let ahead = check_clock(server, at(1_000_300));
+        assert_eq!(ahead.status, CheckStatus::Warn);
+        assert!(ahead.detail.contains("300s ahead"));
+
+        let behind = check_clock(server, at(999_700));
+        assert!(behind.detail.contains("300s behind"));
+    }
+
+    #[test]
+    fn http_date_headers_parse_as_utc() {
+        let date = DateTime::parse_from_rfc2822("Fri, 16 Oct 2026 15:21:07 GMT")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(date.to_rfc3339(), "2026-10-16T15:21:07+00:00");
+    }
+
+    #[test]
+    fn cache_dir_check_creates_the_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path().join("nested").join(".soroban-registry");
+
+        assert_eq!(check_dir_writable(&cache).status, CheckStatus::Pass);
+        assert!(cache.is_dir());
+        assert_eq!(fs::read_dir(&cache).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn only_failures_are_critical() {
+        let checks = vec![
+            Check::pass("config", "ok"),
+            Check::warn("clock", "off", "sync it"),
+            Check::skip("auth", "unset"),
+        ];
+        assert!(!has_failures(&checks));
+
+        let mut checks = checks;
+        checks.push(Check::fail("health", "down", "retry"));
+        assert!(has_failures(&checks));
+    }
+}
@@ -0,0 +1,212 @@
+//! Resumable artifact downloads for `download`.
+//!
+//! The transfer is written to `<file>.part`, with the artifact's `ETag` kept
+//! in `<file>.part.etag`. When it is interrupted, the next run asks only for
+//! the missing bytes with `Range`, and `If-Range` makes the registry send the
+//! whole artifact instead if it changed in between. The registry's ETag is
+//! the artifact's SHA-256, so the finished file is checked against it before
+//! it replaces `<file>`.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use reqwest::header::{CONTENT_RANGE, ETAG, IF_RANGE, RANGE};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+
+/// A finished download
+#[derive(Debug)]
+pub struct Downloaded {
+    /// Size of the whole file
+    pub bytes: u64,
+    /// Bytes already on disk from an earlier, interrupted run
+    pub resumed_from: u64,
+}
+
+/// Downloads `url` to `path`, continuing a transfer an earlier run left
+/// behind
+pub async fn fetch(url: &str, query: &[(&str, &str)], path: &Path) -> Result<Downloaded> {
+    let part = with_suffix(path, ".part");
+    let etag_file = with_suffix(path, ".part.etag");
+
+    // Without its ETag a partial file can't be matched to an artifact
+    let mut etag = fs::read_to_string(&etag_file).ok();
+    let mut offset = match (&etag, fs::metadata(&part)) {
+        (Some(_), Ok(meta)) => meta.len(),
+        _ => 0,
+    };
+    let resumed_from = offset;
+
+    let mut response = request(url, query, offset, etag.as_deref()).await?;
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        let complete = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(unsatisfied_size)
+            == Some(offset);
+        if !complete {
+            // Longer than the artifact, so not a prefix of it
+            offset = 0;
+            response = request(url, query, 0, None).await?;
+        }
+    }
+
+    let status = response.status();
+    if status == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+        // Everything is on disk already; only the check below is left
+    } else if status == StatusCode::PARTIAL_CONTENT || status == StatusCode::OK {
+        if status == StatusCode::PARTIAL_CONTENT {
+            let start = response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(range_start);
+            if start != Some(offset) {
+                anyhow::bail!(
+                    "The registry answered with a different range than requested (wanted bytes {}-)",
+                    offset
+                );
+            }
+        } else {
+            offset = 0;
+        }
+
+        etag = response
+            .headers()
+            .get(ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        match &etag {
+            Some(etag) => fs::write(&etag_file, etag)
+                .with_context(|| format!("Failed to write {}", etag_file.display()))?,
+            None => {
+                let _ = fs::remove_file(&etag_file);
+            }
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(offset > 0)
+            .write(true)
+            .truncate(offset == 0)
+            .open(&part)
+            .with_context(|| format!("Failed to open {}", part.display()))?;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context("Download interrupted; run the command again to resume it")?
+        {
+            file.write_all(&chunk)
+                .with_context(|| format!("Failed to write {}", part.display()))?;
+        }
+    } else {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Download failed: {} ({})", message, status);
+    }
+
+    let (bytes, sha256) = hash_file(&part)?;
+    if let Some(expected) = etag.as_deref().and_then(sha256_of_etag) {
+        if !expected.eq_ignore_ascii_case(&sha256) {
+            let _ = fs::remove_file(&part);
+            let _ = fs::remove_file(&etag_file);
+            anyhow::bail!(
+                "Downloaded file does not match its hash (expected {}, got {}); \
+                 run the command again to download it from the start",
+                expected,
+                sha256
+            );
+        }
+    }
+    fs::rename(&part, path)
+        .with_context(|| format!("Failed to move {} to {}", part.display(), path.display()))?;
+    let _ = fs::remove_file(&etag_file);
+
+    Ok(Downloaded {
+        bytes,
+        resumed_from: if offset > 0 { resumed_from } else { 0 },
+    })
+}
+
+async fn request(
+    url: &str,
+    query: &[(&str, &str)],
+    offset: u64,
+    etag: Option<&str>,
+) -> Result<reqwest::Response> {
+    let mut request = crate::http::client().get(url).query(query);
+    if let (true, Some(etag)) = (offset > 0, etag) {
+        request = request
+            .header(RANGE, format!("bytes={}-", offset))
+            .header(IF_RANGE, etag);
+    }
+    request.send().await.context("Failed to start the download")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Start of `Content-Range: bytes {start}-{end}/{size}`
+fn range_start(content_range: &str) -> Option<u64> {
+    content_range
+        .strip_prefix("bytes ")?
+        .split_once('-')?
+        .0
+        .parse()
+        .ok()
+}
+
+/// Size from `Content-Range: bytes */{size}`
+fn unsatisfied_size(content_range: &str) -> Option<u64> {
+    content_range.strip_prefix("bytes */")?.parse().ok()
+}
+
+/// The hex SHA-256 in a strong ETag, as the registry sends them
+fn sha256_of_etag(etag: &str) -> Option<&str> {
+    let hash = etag.strip_prefix('"')?.strip_suffix('"')?;
+    (hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())).then_some(hash)
+}
+
+/// Size and hex SHA-256 of the file at `path`
+fn hash_file(path: &Path) -> Result<(u64, String)> {
+    let mut file =
+        File::open(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    let bytes = io::copy(&mut file, &mut hasher)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok((bytes, hex::encode(hasher.finalize())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_ranges_are_read() {
+        assert_eq!(range_start("bytes 100-999/1000"), Some(100));
+        assert_eq!(range_start("bytes */1000"), None);
+        assert_eq!(unsatisfied_size("bytes */1000"), Some(1000));
+        assert_eq!(unsatisfied_size("bytes 0-1/2"), None);
+    }
+
+    #[test]
+    fn only_strong_sha256_etags_are_checked() {
+        let hash = "a".repeat(64);
+        assert_eq!(
+            sha256_of_etag(&format!("\"{}\"", hash)),
+            Some(hash.as_str())
+        );
+        assert_eq!(sha256_of_etag(&format!("W/\"{}\"", hash)), None);
+        assert_eq!(sha256_of_etag("\"v1\""), None);
+        assert_eq!(
+            with_suffix(Path::new("out/pool-1.0.0.wasm"), ".part"),
+            PathBuf::from("out/pool-1.0.0.wasm.part")
+        );
+    }
+}
@@ -41,7 +41,7 @@ pub async fn query_events(
     println!("\n{}", "Contract Events".bold().cyan());
     println!("{}", "=".repeat(80).cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     if stats_only {
         let url = format!("{}/api/contracts/{}/events/stats", api_url, contract_id);
@@ -118,7 +118,7 @@ pub async fn run(
             println!("\n{}", "Posting results to registry...".bold().cyan());
         }
 
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
         // Just demonstrating the endpoint structure.
         let url = format!(
             "{}/api/contracts/00000000-0000-0000-0000-000000000000/formal-verification",
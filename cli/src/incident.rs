@@ -4,11 +4,15 @@ use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::str::FromStr;
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Utc};
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
+use shared::pagination::Paginated;
 use uuid::Uuid;
 
+use crate::table_format::{page_summary, render_table};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum IncidentSeverity {
@@ -149,6 +153,968 @@ impl IncidentManager {
     }
 }
 
+// ── Registry API incidents ───────────────────────────────────────────────────
+
+/// Incident record as returned by `GET /api/incidents`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentRecord {
+    pub id: Uuid,
+    pub contract_id: Option<Uuid>,
+    pub incident_type: String,
+    pub severity: String,
+    pub description: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub rto_achieved: Option<RecoveryDuration>,
+    pub rpo_achieved: Option<RecoveryDuration>,
+    pub lessons_learned: Option<String>,
+    pub notified_users: bool,
+    #[serde(default)]
+    pub objectives: ObjectiveOutcome,
+    #[serde(default)]
+    pub affected_contracts: Vec<AffectedContract>,
+}
+
+impl IncidentRecord {
+    /// Short description of what the incident affected, for table cells.
+    fn scope(&self) -> String {
+        match self.affected_contracts.as_slice() {
+            [] => self
+                .contract_id
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| "registry-wide".to_string()),
+            [only] => only.name.clone(),
+            [first, rest @ ..] => format!("{} (+{} more)", first.name, rest.len()),
+        }
+    }
+
+    /// How long the incident took to resolve, e.g. "3h 12m"; `None` while open.
+    pub fn resolution_time(&self) -> Option<String> {
+        self.end_time
+            .map(|end| format_seconds((end - self.start_time).num_seconds() as f64))
+    }
+}
+
+/// Contract affected by an incident
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedContract {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// Achieved RTO/RPO as reported by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoveryDuration {
+    pub seconds: i64,
+    pub display: String,
+}
+
+/// Recovery targets applied to an incident and whether they were met
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ObjectiveOutcome {
+    pub rto_target_seconds: Option<i64>,
+    pub rto_met: Option<bool>,
+    pub rto_delta_seconds: Option<i64>,
+    pub rpo_target_seconds: Option<i64>,
+    pub rpo_met: Option<bool>,
+    pub rpo_delta_seconds: Option<i64>,
+}
+
+/// Describes one objective as e.g. "2h (target 4h, met by 2h)".
+fn describe_objective(
+    achieved: Option<&RecoveryDuration>,
+    target_seconds: Option<i64>,
+    met: Option<bool>,
+    delta_seconds: Option<i64>,
+) -> String {
+    let target = target_seconds
+        .map(|t| format!("target {}", format_seconds(t as f64)))
+        .unwrap_or_else(|| "no target".to_string());
+    match (achieved, met, delta_seconds) {
+        (Some(achieved), Some(true), Some(delta)) => format!(
+            "{} ({}, met with {} to spare)",
+            achieved.display,
+            target,
+            format_seconds(-delta as f64)
+        ),
+        (Some(achieved), Some(false), Some(delta)) => format!(
+            "{} ({}, missed by {})",
+            achieved.display,
+            target,
+            format_seconds(delta as f64)
+        ),
+        (Some(achieved), _, _) => format!("{} ({})", achieved.display, target),
+        (None, _, _) => format!("not recorded ({})", target),
+    }
+}
+
+/// Pagination envelope returned by incident listings
+pub type IncidentPage = Paginated<IncidentRecord>;
+
+/// Filters accepted by `incident list`, mirroring the API query parameters
+#[derive(Debug, Clone, Default)]
+pub struct IncidentListFilters {
+    pub contract_id: Option<String>,
+    pub incident_type: Option<String>,
+    pub severity: Option<String>,
+    pub min_severity: Option<String>,
+    pub open_only: bool,
+    pub start_after: Option<String>,
+    pub start_before: Option<String>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl IncidentListFilters {
+    fn query_pairs(&self) -> Result<Vec<(&'static str, String)>> {
+        let mut pairs = vec![
+            ("limit", self.limit.to_string()),
+            ("offset", self.offset.to_string()),
+        ];
+        if let Some(contract_id) = &self.contract_id {
+            pairs.push(("contract_id", contract_id.clone()));
+        }
+        if let Some(incident_type) = &self.incident_type {
+            pairs.push(("incident_type", incident_type.clone()));
+        }
+        if let Some(severity) = &self.severity {
+            pairs.push(("severity", severity.to_lowercase()));
+        }
+        if let Some(min_severity) = &self.min_severity {
+            pairs.push(("min_severity", min_severity.to_lowercase()));
+        }
+        if self.open_only {
+            pairs.push(("open_only", "true".to_string()));
+        }
+        if let Some(after) = &self.start_after {
+            pairs.push(("start_after", parse_cli_timestamp(after)?.to_rfc3339()));
+        }
+        if let Some(before) = &self.start_before {
+            pairs.push(("start_before", parse_cli_timestamp(before)?.to_rfc3339()));
+        }
+        Ok(pairs)
+    }
+}
+
+/// Colors an API severity value: critical red, major yellow, minor blue, informational dimmed.
+pub fn colorize_severity(severity: &str) -> String {
+    match severity {
+        "critical" => severity.red().bold().to_string(),
+        "major" => severity.yellow().bold().to_string(),
+        "minor" => severity.blue().to_string(),
+        _ => severity.dimmed().to_string(),
+    }
+}
+
+/// Accepts either an RFC 3339 timestamp or a plain `YYYY-MM-DD` date (midnight UTC).
+fn parse_cli_timestamp(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(value) {
+        return Ok(ts.with_timezone(&Utc));
+    }
+    let date = chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .with_context(|| format!("invalid date: {} (expected YYYY-MM-DD or RFC 3339)", value))?;
+    Ok(date.and_hms_opt(0, 0, 0).unwrap().and_utc())
+}
+
+pub async fn list(api_url: &str, filters: &IncidentListFilters, json: bool) -> Result<()> {
+    let client = crate::http::client();
+    let resp = client
+        .get(format!("{}/api/incidents", api_url))
+        .query(&filters.query_pairs()?)
+        .send()
+        .await
+        .context("Failed to connect to registry API")?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        bail!("API returned {}: {}", status, text);
+    }
+
+    let page: IncidentPage = resp.json().await.context("Failed to parse API response")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&page)?);
+        return Ok(());
+    }
+
+    println!("\n{}", "Incidents".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+
+    let incidents = &page.items;
+    if incidents.is_empty() {
+        println!("{}", "No incidents found.".yellow());
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = incidents
+        .iter()
+        .map(|i| {
+            vec![
+                i.id.to_string(),
+                i.incident_type.clone(),
+                colorize_severity(&i.severity),
+                crate::time_format::show(i.start_time),
+                match i.end_time {
+                    Some(end) => crate::time_format::show(end),
+                    None => "OPEN".red().bold().to_string(),
+                },
+                i.scope(),
+            ]
+        })
+        .collect();
+
+    println!(
+        "{}",
+        render_table(
+            &["ID", "Type", "Severity", "Started", "Resolved", "Affected"],
+            &[36, 12, 13, 20, 20, 36],
+            &rows
+        )
+    );
+    println!("{}", page_summary(&page));
+
+    Ok(())
+}
+
+/// Timeline entry as returned by `GET /api/incidents/:id/updates`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IncidentUpdateRecord {
+    pub id: Uuid,
+    pub author: String,
+    pub message: String,
+    pub status: Option<String>,
+    pub post_resolution: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The latest incidents that affected a contract, open critical ones first.
+pub async fn recent_for_contract(
+    api_url: &str,
+    contract_id: &str,
+    limit: usize,
+) -> Result<Vec<IncidentRecord>> {
+    let client = crate::http::client();
+    let page: IncidentPage = get_json(
+        &client,
+        format!(
+            "{}/api/contracts/{}/incidents?limit={}",
+            api_url, contract_id, limit
+        ),
+    )
+    .await?;
+    Ok(page.items)
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &crate::http::Client,
+    url: String,
+) -> Result<T> {
+    let resp = client
+        .get(url)
+        .send()
+        .await
+        .context("Failed to connect to registry API")?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        bail!("API returned {}: {}", status, text);
+    }
+
+    resp.json().await.context("Failed to parse API response")
+}
+
+pub async fn show(api_url: &str, incident_id: &str, json: bool) -> Result<()> {
+    let client = crate::http::client();
+    let incident: IncidentRecord = get_json(
+        &client,
+        format!("{}/api/incidents/{}", api_url, incident_id),
+    )
+    .await?;
+    let mut timeline: Vec<IncidentUpdateRecord> = get_json(
+        &client,
+        format!("{}/api/incidents/{}/updates", api_url, incident_id),
+    )
+    .await?;
+    timeline.sort_by_key(|u| u.created_at);
+
+    if json {
+        let out = serde_json::json!({ "incident": incident, "updates": timeline });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    println!("\n{}", format!("Incident {}", incident.id).bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!("  {:<12} {}", "Type:".bold(), incident.incident_type);
+    println!(
+        "  {:<12} {}",
+        "Severity:".bold(),
+        colorize_severity(&incident.severity)
+    );
+    if incident.affected_contracts.is_empty() {
+        println!("  {:<12} {}", "Contracts:".bold(), incident.scope());
+    } else {
+        println!("  {}", "Contracts:".bold());
+        for contract in &incident.affected_contracts {
+            println!("    - {} ({})", contract.name, contract.id);
+        }
+    }
+    println!(
+        "  {:<12} {}",
+        "Started:".bold(),
+        crate::time_format::show(incident.start_time)
+    );
+    match incident.end_time {
+        Some(end) => println!(
+            "  {:<12} {}",
+            "Resolved:".bold(),
+            crate::time_format::show(end)
+        ),
+        None => println!("  {:<12} {}", "Resolved:".bold(), "OPEN".red().bold()),
+    }
+    let objectives = &incident.objectives;
+    for (label, achieved, target, met, delta) in [
+        (
+            "RTO:",
+            incident.rto_achieved.as_ref(),
+            objectives.rto_target_seconds,
+            objectives.rto_met,
+            objectives.rto_delta_seconds,
+        ),
+        (
+            "RPO:",
+            incident.rpo_achieved.as_ref(),
+            objectives.rpo_target_seconds,
+            objectives.rpo_met,
+            objectives.rpo_delta_seconds,
+        ),
+    ] {
+        let line = describe_objective(achieved, target, met, delta);
+        let line = match met {
+            Some(false) => line.red().to_string(),
+            _ => line,
+        };
+        println!("  {:<12} {}", label.bold(), line);
+    }
+    println!("\n  {}", incident.description);
+
+    println!("\n{}", "Timeline".bold().cyan());
+    println!("{}", "-".repeat(80).cyan());
+    if timeline.is_empty() {
+        println!("{}", "No updates posted yet.".yellow());
+        return Ok(());
+    }
+
+    for update in &timeline {
+        let status = update
+            .status
+            .as_deref()
+            .map(|s| format!(" [{}]", s.to_uppercase()))
+            .unwrap_or_default();
+        let post_resolution = if update.post_resolution {
+            " (post-resolution)".dimmed().to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "  {} {}{}{}",
+            crate::time_format::show(update.created_at).bold(),
+            update.author.green(),
+            status.yellow(),
+            post_resolution
+        );
+        println!("      {}", update.message);
+    }
+
+    Ok(())
+}
+
+/// Formats a second count as e.g. "3h 12m" for table output.
+fn format_seconds(seconds: f64) -> String {
+    let total = seconds.round() as i64;
+    let (days, hours, minutes) = (
+        total / 86_400,
+        (total % 86_400) / 3_600,
+        (total % 3_600) / 60,
+    );
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{}s", total),
+        (0, 0, m) => format!("{}m", m),
+        (0, h, m) => format!("{}h {}m", h, m),
+        (d, h, _) => format!("{}d {}h", d, h),
+    }
+}
+
+fn format_optional_seconds(seconds: Option<f64>) -> String {
+    seconds
+        .map(format_seconds)
+        .unwrap_or_else(|| "-".to_string())
+}
+
+pub async fn metrics(api_url: &str, window: &str, json: bool) -> Result<()> {
+    let client = crate::http::client();
+    let resp = client
+        .get(format!("{}/api/incidents/metrics", api_url))
+        .query(&[("window", window)])
+        .send()
+        .await
+        .context("Failed to connect to registry API")?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        bail!("API returned {}: {}", status, text);
+    }
+
+    let metrics: serde_json::Value = resp.json().await.context("Failed to parse API response")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&metrics)?);
+        return Ok(());
+    }
+
+    let int = |v: &serde_json::Value| v.as_i64().unwrap_or(0);
+    let compliance = |v: &serde_json::Value| match v["percentage"].as_f64() {
+        Some(pct) => {
+            let mean_delta = match v["mean_delta_seconds"].as_f64() {
+                Some(delta) if delta > 0.0 => {
+                    format!(", {} over on average", format_seconds(delta))
+                }
+                Some(delta) => format!(", {} under on average", format_seconds(-delta)),
+                None => String::new(),
+            };
+            format!(
+                "{:.0}% ({}/{} within target {}{})",
+                pct,
+                int(&v["met"]),
+                int(&v["measured"]),
+                format_seconds(v["target_seconds"].as_f64().unwrap_or(0.0)),
+                mean_delta
+            )
+        }
+        None => format!(
+            "no measurements (target {})",
+            format_seconds(v["target_seconds"].as_f64().unwrap_or(0.0))
+        ),
+    };
+
+    println!(
+        "\n{}",
+        format!(
+            "Incident Metrics (last {} days)",
+            int(&metrics["window_days"])
+        )
+        .bold()
+        .cyan()
+    );
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {:<16} {} ({} resolved, {} open)",
+        "Incidents:".bold(),
+        int(&metrics["total_incidents"]),
+        int(&metrics["resolved_incidents"]),
+        int(&metrics["open_incidents"])
+    );
+    println!(
+        "  {:<16} mean {}, median {}",
+        "MTTR:".bold(),
+        format_optional_seconds(metrics["mttr_mean_seconds"].as_f64()),
+        format_optional_seconds(metrics["mttr_median_seconds"].as_f64())
+    );
+    println!(
+        "  {:<16} {}",
+        "RTO met:".bold(),
+        compliance(&metrics["rto_compliance"])
+    );
+    println!(
+        "  {:<16} {}",
+        "RPO met:".bold(),
+        compliance(&metrics["rpo_compliance"])
+    );
+    if let Some(open) = metrics["longest_open"].as_object() {
+        println!(
+            "  {:<16} {} ({}, open {})",
+            "Longest open:".bold(),
+            open["id"].as_str().unwrap_or("-"),
+            colorize_severity(open["severity"].as_str().unwrap_or("")),
+            format_seconds(open["open_seconds"].as_f64().unwrap_or(0.0)).red()
+        );
+    }
+
+    let counts = |v: &serde_json::Value| -> Vec<Vec<String>> {
+        v.as_array()
+            .into_iter()
+            .flatten()
+            .map(|c| {
+                vec![
+                    c["key"].as_str().unwrap_or("-").to_string(),
+                    int(&c["count"]).to_string(),
+                ]
+            })
+            .collect()
+    };
+    println!(
+        "\n{}",
+        render_table(&["Type", "Count"], &[20, 8], &counts(&metrics["by_type"]))
+    );
+    println!(
+        "{}",
+        render_table(
+            &["Severity", "Count"],
+            &[20, 8],
+            &counts(&metrics["by_severity"])
+        )
+    );
+
+    let monthly: Vec<Vec<String>> = metrics["monthly"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|m| {
+            vec![
+                m["month"].as_str().unwrap_or("-").to_string(),
+                int(&m["total"]).to_string(),
+                int(&m["resolved"]).to_string(),
+                int(&m["open"]).to_string(),
+                format_optional_seconds(m["mttr_mean_seconds"].as_f64()),
+                format_optional_seconds(m["mttr_median_seconds"].as_f64()),
+            ]
+        })
+        .collect();
+    println!(
+        "{}",
+        render_table(
+            &[
+                "Month",
+                "Total",
+                "Resolved",
+                "Open",
+                "MTTR mean",
+                "MTTR median"
+            ],
+            &[8, 6, 9, 6, 11, 11],
+            &monthly
+        )
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct HeatmapContract {
+    name: String,
+    network: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HeatmapCell {
+    score: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct HeatmapRow {
+    contract: Option<HeatmapContract>,
+    other: bool,
+    contracts: i64,
+    incidents: i64,
+    downtime_minutes: Option<i64>,
+    cells: Vec<HeatmapCell>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IncidentHeatmap {
+    window_days: i64,
+    bucket: String,
+    weighted: bool,
+    buckets: Vec<DateTime<Utc>>,
+    rows: Vec<HeatmapRow>,
+    max_score: i64,
+    contracts_with_incidents: i64,
+}
+
+/// Shades from an empty cell to the busiest one
+const HEAT_SHADES: [&str; 5] = ["·", "░", "▒", "▓", "█"];
+
+/// Shade index of `score`: 0 when empty, otherwise 1-4 in quarters of `max`
+fn heat_level(score: i64, max: i64) -> usize {
+    if score <= 0 || max <= 0 {
+        return 0;
+    }
+    ((score * 4 + max - 1) / max).clamp(1, 4) as usize
+}
+
+/// Score range each non-empty shade stands for, skipping shades no whole
+/// score falls in
+fn heat_legend(max: i64) -> Vec<(usize, i64, i64)> {
+    (1..=4)
+        .filter_map(|level| {
+            let low = (level as i64 - 1) * max / 4 + 1;
+            let high = level as i64 * max / 4;
+            (low <= high).then_some((level, low, high))
+        })
+        .collect()
+}
+
+fn heat_cell(level: usize, width: usize) -> String {
+    let shade = HEAT_SHADES[level].repeat(width);
+    match level {
+        0 => shade.dimmed().to_string(),
+        1 => shade.yellow().to_string(),
+        2 => shade.bright_yellow().to_string(),
+        3 => shade.bright_red().to_string(),
+        _ => shade.red().bold().to_string(),
+    }
+}
+
+/// Shows incidents per affected contract and time bucket as a grid of
+/// shaded cells, busiest contracts first.
+pub async fn heatmap(
+    api_url: &str,
+    window: &str,
+    bucket: &str,
+    top: u32,
+    weighted: bool,
+    json: bool,
+) -> Result<()> {
+    let client = crate::http::client();
+    let resp = client
+        .get(format!("{}/api/incidents/heatmap", api_url))
+        .query(&[
+            ("window", window.to_string()),
+            ("bucket", bucket.to_string()),
+            ("top", top.to_string()),
+            ("weighted", weighted.to_string()),
+        ])
+        .send()
+        .await
+        .context("Failed to connect to registry API")?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        bail!("API returned {}: {}", status, text);
+    }
+
+    let body: serde_json::Value = resp.json().await.context("Failed to parse API response")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+
+    let heatmap: IncidentHeatmap =
+        serde_json::from_value(body).context("Failed to parse API response")?;
+    let unit = if heatmap.weighted {
+        "severity score"
+    } else {
+        "incidents"
+    };
+
+    println!(
+        "\n{}",
+        format!(
+            "Incident Heatmap (last {} days, per {}, {})",
+            heatmap.window_days, heatmap.bucket, unit
+        )
+        .bold()
+        .cyan()
+    );
+    println!("{}", "=".repeat(80).cyan());
+
+    if heatmap.rows.is_empty() {
+        println!("  No incidents affected any contract in this window.");
+        return Ok(());
+    }
+
+    // Two characters per cell while the grid fits a wide terminal
+    let cell_width = if heatmap.buckets.len() > 60 { 1 } else { 2 };
+    let label_width = 28;
+    for row in &heatmap.rows {
+        let label = match &row.contract {
+            Some(contract) => format!("{} ({})", contract.name, contract.network),
+            None => format!("other ({} contracts)", row.contracts),
+        };
+        let label: String = if label.chars().count() > label_width {
+            label.chars().take(label_width - 1).chain(['…']).collect()
+        } else {
+            label
+        };
+        let cells: String = row
+            .cells
+            .iter()
+            .map(|cell| heat_cell(heat_level(cell.score, heatmap.max_score), cell_width))
+            .collect();
+        let downtime = row
+            .downtime_minutes
+            .map(|minutes| format!(", {} down", format_seconds(minutes as f64 * 60.0)))
+            .unwrap_or_default();
+        let label = format!("{:<width$}", label, width = label_width);
+        let label = if row.other {
+            label.dimmed()
+        } else {
+            label.bold()
+        };
+        println!(
+            "  {} {} {} incidents{}",
+            label, cells, row.incidents, downtime
+        );
+    }
+
+    if let (Some(first), Some(last)) = (heatmap.buckets.first(), heatmap.buckets.last()) {
+        let first = first.format("%Y-%m-%d").to_string();
+        let last = last.format("%Y-%m-%d").to_string();
+        let span = heatmap.buckets.len() * cell_width;
+        println!(
+            "  {:<label$} {}{:>pad$}",
+            "",
+            first.dimmed(),
+            last.dimmed(),
+            label = label_width,
+            pad = span.saturating_sub(first.len()).max(last.len() + 1)
+        );
+    }
+
+    let legend: Vec<String> = std::iter::once(format!("{} 0", heat_cell(0, 1)))
+        .chain(
+            heat_legend(heatmap.max_score)
+                .into_iter()
+                .map(|(level, low, high)| {
+                    let range = if low == high {
+                        low.to_string()
+                    } else {
+                        format!("{}-{}", low, high)
+                    };
+                    format!("{} {}", heat_cell(level, 1), range)
+                }),
+        )
+        .collect();
+    println!("\n  {} {} ({})", "Legend:".bold(), legend.join("  "), unit);
+    println!(
+        "  {} contract(s) had incidents in this window",
+        heatmap.contracts_with_incidents
+    );
+
+    Ok(())
+}
+
+/// Colored badge for the overall registry state reported by `/api/status`
+pub fn status_badge(state: &str) -> String {
+    let label = format!(" {} ", state.replace('_', " ").to_uppercase());
+    match state {
+        "operational" => label.black().on_green().bold().to_string(),
+        "degraded" => label.black().on_yellow().bold().to_string(),
+        "major_outage" => label.white().on_red().bold().to_string(),
+        _ => label.dimmed().to_string(),
+    }
+}
+
+pub async fn status(api_url: &str, json: bool) -> Result<()> {
+    let client = crate::http::client();
+    let status: serde_json::Value = get_json(&client, format!("{}/api/status", api_url)).await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&status)?);
+        return Ok(());
+    }
+
+    println!("\n{}", "Registry Status".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {:<16} {}",
+        "State:".bold(),
+        status_badge(status["status"].as_str().unwrap_or("unknown"))
+    );
+    let pct = |v: &serde_json::Value| {
+        v.as_f64()
+            .map(|p| format!("{:.3}%", p))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    println!(
+        "  {:<16} {} (30d), {} (90d)",
+        "Uptime:".bold(),
+        pct(&status["uptime"]["last_30_days"]),
+        pct(&status["uptime"]["last_90_days"])
+    );
+
+    let incidents = status["open_incidents"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    println!("\n{}", "Open incidents".bold());
+    if incidents.is_empty() {
+        println!("  {}", "None".green());
+    }
+    for incident in &incidents {
+        println!(
+            "  {} [{}] {} — {}",
+            colorize_severity(incident["severity"].as_str().unwrap_or("")),
+            incident["status"].as_str().unwrap_or("investigating"),
+            incident["incident_type"].as_str().unwrap_or("-").bold(),
+            incident["description"].as_str().unwrap_or("")
+        );
+        println!(
+            "      since {}",
+            incident["start_time"]
+                .as_str()
+                .map(crate::time_format::show_rfc3339)
+                .unwrap_or_else(|| "-".to_string())
+                .dimmed()
+        );
+    }
+
+    let maintenance = status["maintenance"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    if !maintenance.is_empty() {
+        println!("\n{}", "Maintenance".bold());
+        for window in &maintenance {
+            let state = window["status"].as_str().unwrap_or("scheduled");
+            let state = if state == "in_progress" {
+                "in progress".yellow().bold()
+            } else {
+                state.normal()
+            };
+            println!(
+                "  {} [{}] {} → {}",
+                window["title"].as_str().unwrap_or("-").bold(),
+                state,
+                window["scheduled_start"].as_str().unwrap_or("-"),
+                window["scheduled_end"].as_str().unwrap_or("-")
+            );
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn report(api_url: &str, incident_id: &str, output: Option<&str>) -> Result<()> {
+    let client = crate::http::client();
+    let resp = client
+        .get(format!("{}/api/incidents/{}/report", api_url, incident_id))
+        .query(&[("format", "markdown")])
+        .send()
+        .await
+        .context("Failed to connect to registry API")?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        bail!("API returned {}: {}", status, text);
+    }
+
+    let markdown = resp.text().await.context("Failed to read report")?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &markdown)
+                .with_context(|| format!("Failed to write report to {}", path))?;
+            println!("{} Report written to {}", "✓".green(), path.bold());
+        }
+        None => print!("{}", markdown),
+    }
+
+    Ok(())
+}
+
+// ── Bulk import ──────────────────────────────────────────────────────────────
+
+/// Outcome of one line of an import, as returned by `POST /api/incidents/import`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportLineOutcome {
+    pub line: usize,
+    /// imported | valid (dry run) | duplicate | invalid
+    pub status: String,
+    pub external_id: Option<String>,
+    pub incident_id: Option<Uuid>,
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub records: usize,
+    pub imported: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+    pub lines: Vec<ImportLineOutcome>,
+}
+
+impl ImportReport {
+    /// e.g. "12 records: 10 imported, 1 duplicate, 1 invalid"
+    fn summary(&self) -> String {
+        let done = if self.dry_run {
+            let valid = self.records.saturating_sub(self.duplicates + self.invalid);
+            format!("{} would be imported", valid)
+        } else {
+            format!("{} imported", self.imported)
+        };
+        format!(
+            "{} records: {}, {} duplicate, {} invalid",
+            self.records, done, self.duplicates, self.invalid
+        )
+    }
+}
+
+/// Uploads an NDJSON file of resolved incidents, one JSON record per line,
+/// and prints what happened to each line. Fails if any line was invalid.
+pub async fn import(api_url: &str, file: &str, dry_run: bool, json: bool) -> Result<()> {
+    let body = std::fs::read(file).with_context(|| format!("Failed to read {}", file))?;
+
+    let client = crate::http::client();
+    let resp = client
+        .post(format!("{}/api/incidents/import", api_url))
+        .query(&[("dry_run", dry_run)])
+        .header(reqwest::header::CONTENT_TYPE, "application/x-ndjson")
+        .body(body)
+        .send()
+        .await
+        .context("Failed to connect to registry API")?;
+
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let text = resp.text().await.unwrap_or_default();
+        bail!("API returned {}: {}", status, text);
+    }
+
+    let report: ImportReport = resp.json().await.context("Failed to parse API response")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        for outcome in &report.lines {
+            let status = match outcome.status.as_str() {
+                "imported" | "valid" => outcome.status.green(),
+                "duplicate" => outcome.status.yellow(),
+                _ => outcome.status.red(),
+            };
+            let subject = outcome
+                .incident_id
+                .map(|id| id.to_string())
+                .or_else(|| outcome.external_id.clone())
+                .unwrap_or_default();
+            println!("  line {:>5}  {:<9}  {}", outcome.line, status, subject);
+            for error in &outcome.errors {
+                println!("               {}", error.dimmed());
+            }
+        }
+        let prefix = if report.dry_run { "Dry run: " } else { "" };
+        println!("\n{}{}", prefix, report.summary().bold());
+    }
+
+    if report.invalid > 0 {
+        bail!(
+            "{} line(s) of {} were invalid; fix them and run the import again",
+            report.invalid,
+            file
+        );
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,6 +1164,114 @@ mod tests {
         assert!(!mgr.is_halted("C5"));
     }
 
+    #[test]
+    fn list_filters_build_query_pairs() {
+        let filters = IncidentListFilters {
+            contract_id: Some("c0ffee".into()),
+            open_only: true,
+            start_after: Some("2026-01-01".into()),
+            min_severity: Some("Major".into()),
+            limit: 20,
+            ..Default::default()
+        };
+        let pairs = filters.query_pairs().unwrap();
+        assert!(pairs.contains(&("min_severity", "major".to_string())));
+        assert!(pairs.contains(&("contract_id", "c0ffee".to_string())));
+        assert!(pairs.contains(&("open_only", "true".to_string())));
+        assert!(pairs.contains(&("start_after", "2026-01-01T00:00:00+00:00".to_string())));
+        assert!(!pairs.iter().any(|(k, _)| *k == "start_before"));
+    }
+
+    #[test]
+    fn incident_page_summary_points_at_next_offset() {
+        let incident = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "contract_id": null,
+            "incident_type": "outage",
+            "severity": "major",
+            "description": "RPC outage",
+            "start_time": "2026-10-01T00:00:00Z",
+            "end_time": null,
+            "rto_achieved": null,
+            "rpo_achieved": null,
+            "lessons_learned": null,
+            "notified_users": false,
+        });
+        let page: IncidentPage = serde_json::from_value(serde_json::json!({
+            "items": [incident.clone(), incident],
+            "total": 57,
+            "limit": 2,
+            "offset": 20,
+            "has_more": true,
+        }))
+        .unwrap();
+        assert_eq!(
+            page_summary(&page),
+            "Showing 21-22 of 57 (next page: --offset 22)"
+        );
+    }
+
+    #[test]
+    fn objectives_describe_target_and_margin() {
+        let achieved = RecoveryDuration {
+            seconds: 7200,
+            display: "2h".to_string(),
+        };
+        assert_eq!(
+            describe_objective(Some(&achieved), Some(14_400), Some(true), Some(-7200)),
+            "2h (target 4h 0m, met with 2h 0m to spare)"
+        );
+        assert_eq!(
+            describe_objective(Some(&achieved), Some(3600), Some(false), Some(3600)),
+            "2h (target 1h 0m, missed by 1h 0m)"
+        );
+        assert_eq!(
+            describe_objective(None, Some(3600), None, None),
+            "not recorded (target 1h 0m)"
+        );
+    }
+
+    #[test]
+    fn format_seconds_picks_readable_units() {
+        assert_eq!(format_seconds(42.0), "42s");
+        assert_eq!(format_seconds(600.0), "10m");
+        assert_eq!(format_seconds(11_520.0), "3h 12m");
+        assert_eq!(format_seconds(93_600.0), "1d 2h");
+        assert_eq!(format_optional_seconds(None), "-");
+    }
+
+    #[test]
+    fn resolution_time_spans_start_to_end() {
+        let start = "2026-03-01T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let mut incident: IncidentRecord = serde_json::from_value(serde_json::json!({
+            "id": Uuid::nil(),
+            "contract_id": null,
+            "incident_type": "outage",
+            "severity": "critical",
+            "description": "RPC unavailable",
+            "start_time": start,
+            "end_time": null,
+            "rto_achieved": null,
+            "rpo_achieved": null,
+            "lessons_learned": null,
+            "notified_users": false
+        }))
+        .unwrap();
+        assert_eq!(incident.resolution_time(), None);
+
+        incident.end_time = Some(start + chrono::Duration::minutes(135));
+        assert_eq!(incident.resolution_time().as_deref(), Some("2h 15m"));
+    }
+
+    #[test]
+    fn list_filters_reject_bad_dates() {
+        let filters = IncidentListFilters {
+            start_before: Some("last tuesday".into()),
+            ..Default::default()
+        };
+        assert!(filters.query_pairs().is_err());
+    }
+
     #[test]
     fn invalid_state_transition_same_state() {
         let mut mgr = IncidentManager::default();
@@ -205,4 +1279,57 @@ mod tests {
         let err = mgr.update_state(id, IncidentState::Detected).unwrap_err();
         assert!(err.to_string().contains("already in state"));
     }
+
+    #[test]
+    fn status_badge_uses_readable_labels() {
+        assert!(status_badge("operational").contains(" OPERATIONAL "));
+        assert!(status_badge("major_outage").contains(" MAJOR OUTAGE "));
+    }
+
+    #[test]
+    fn import_report_summarizes_outcomes() {
+        let report: ImportReport = serde_json::from_value(serde_json::json!({
+            "dry_run": true,
+            "records": 4,
+            "imported": 0,
+            "duplicates": 1,
+            "invalid": 1,
+            "lines": [
+                { "line": 1, "status": "valid", "external_id": "PD-1" },
+                { "line": 2, "status": "valid" },
+                { "line": 3, "status": "duplicate", "external_id": "PD-1",
+                  "errors": ["external_id already appears on line 1"] },
+                { "line": 5, "status": "invalid", "errors": ["description must not be empty"] }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            report.summary(),
+            "4 records: 2 would be imported, 1 duplicate, 1 invalid"
+        );
+        assert_eq!(report.lines[3].errors.len(), 1);
+    }
+
+    #[test]
+    fn heat_levels_split_the_busiest_cell_in_quarters() {
+        assert_eq!(heat_level(0, 8), 0);
+        assert_eq!(heat_level(1, 8), 1);
+        assert_eq!(heat_level(2, 8), 1);
+        assert_eq!(heat_level(3, 8), 2);
+        assert_eq!(heat_level(8, 8), 4);
+        assert_eq!(heat_level(3, 0), 0);
+
+        assert_eq!(
+            heat_legend(8),
+            vec![(1, 1, 2), (2, 3, 4), (3, 5, 6), (4, 7, 8)]
+        );
+        // With a max of 2 only two shades can occur
+        assert_eq!(heat_legend(2), vec![(2, 1, 1), (4, 2, 2)]);
+        for max in 1..20 {
+            for (level, low, high) in heat_legend(max) {
+                assert_eq!(heat_level(low, max), level);
+                assert_eq!(heat_level(high, max), level);
+            }
+        }
+    }
 }
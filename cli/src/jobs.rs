@@ -0,0 +1,377 @@
+//! Background jobs: waiting on `verify-source` and `snapshot export`, and
+//! the `job` commands.
+//!
+//! The registry answers long-running requests with a job and runs them in
+//! the background. [`wait`] polls `GET /api/jobs/:id` until the job is done,
+//! showing a spinner on a terminal. A job queued with an API token is only
+//! visible with that token (or an admin's).
+
+use std::io::{IsTerminal, Write};
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use shared::pagination::Paginated;
+use uuid::Uuid;
+
+use crate::table_format::render_table_with_details;
+
+/// Time between status checks
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_TICK: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: Uuid,
+    pub job_type: String,
+    pub status: String,
+    pub attempts: i32,
+    pub max_attempts: i32,
+    pub last_error: Option<String>,
+    pub result: Option<Value>,
+    pub created_by: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl Job {
+    /// `succeeded`, `failed` and `dead` jobs won't change any more
+    pub fn is_finished(&self) -> bool {
+        matches!(self.status.as_str(), "succeeded" | "failed" | "dead")
+    }
+
+    fn status_label(&self) -> colored::ColoredString {
+        match self.status.as_str() {
+            "succeeded" => self.status.green(),
+            "failed" | "dead" => self.status.red(),
+            "running" => self.status.cyan(),
+            _ => self.status.yellow(),
+        }
+    }
+
+    /// What the spinner says while the job is not done
+    fn progress(&self) -> String {
+        match self.status.as_str() {
+            "queued" if self.attempts > 0 => format!(
+                "retrying after attempt {}/{}{}",
+                self.attempts,
+                self.max_attempts,
+                self.last_error
+                    .as_deref()
+                    .map(|err| format!(": {}", err))
+                    .unwrap_or_default()
+            ),
+            "queued" => "queued".to_string(),
+            _ => format!("running (attempt {}/{})", self.attempts, self.max_attempts),
+        }
+    }
+}
+
+pub(crate) async fn error_for_status(
+    response: reqwest::Response,
+    action: &str,
+) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body: Value = response.json().await.unwrap_or_default();
+    let message = body["error"]["message"].as_str().unwrap_or("no details");
+    bail!("Failed to {}: {} ({})", action, message, status)
+}
+
+pub async fn fetch(api_url: &str, id: &str) -> Result<Job> {
+    let response = crate::http::client()
+        .get(format!("{}/api/jobs/{}", api_url, id))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    error_for_status(response, "fetch job")
+        .await?
+        .json()
+        .await
+        .context("Failed to parse job")
+}
+
+/// Polls `job` until it is finished and returns its final state. `label`
+/// names the work on the spinner, which is only drawn when stderr is a
+/// terminal and `quiet` is off.
+pub async fn wait(api_url: &str, mut job: Job, label: &str, quiet: bool) -> Result<Job> {
+    let spinner = !quiet && std::io::stderr().is_terminal();
+    let mut frame = 0usize;
+
+    while !job.is_finished() {
+        let next_poll = tokio::time::Instant::now() + POLL_INTERVAL;
+        while tokio::time::Instant::now() < next_poll {
+            if spinner {
+                eprint!(
+                    "\r\x1b[2K{} {} {}",
+                    SPINNER_FRAMES[frame % SPINNER_FRAMES.len()]
+                        .to_string()
+                        .cyan(),
+                    label,
+                    job.progress().bright_black()
+                );
+                let _ = std::io::stderr().flush();
+                frame += 1;
+            }
+            tokio::time::sleep(SPINNER_TICK.min(next_poll - tokio::time::Instant::now())).await;
+        }
+        job = match fetch(api_url, &job.id.to_string()).await {
+            Ok(job) => job,
+            Err(err) => {
+                if spinner {
+                    eprint!("\r\x1b[2K");
+                }
+                return Err(err.context(format!(
+                    "Lost track of job {}; check it later with `soroban-registry job show {}`",
+                    job.id, job.id
+                )));
+            }
+        };
+    }
+
+    if spinner {
+        eprint!("\r\x1b[2K");
+        let _ = std::io::stderr().flush();
+    }
+    Ok(job)
+}
+
+/// Prints that a job was queued and how to follow it
+pub fn print_queued(job: &Job, what: &str) {
+    println!(
+        "{} {} queued as job {}",
+        "✓".green(),
+        what,
+        job.id.to_string().bold()
+    );
+    println!(
+        "  Follow it with: soroban-registry job show {} --wait",
+        job.id
+    );
+}
+
+/// Errors out unless the job succeeded
+pub fn ensure_succeeded(job: &Job, what: &str) -> Result<()> {
+    match job.status.as_str() {
+        "succeeded" => Ok(()),
+        "dead" => bail!(
+            "{} gave up after {} attempts: {}",
+            what,
+            job.attempts,
+            job.last_error.as_deref().unwrap_or("no details")
+        ),
+        _ => bail!(
+            "{} failed: {}",
+            what,
+            job.last_error.as_deref().unwrap_or("no details")
+        ),
+    }
+}
+
+pub async fn show(api_url: &str, id: &str, wait_for_it: bool, json: bool) -> Result<()> {
+    let mut job = fetch(api_url, id).await?;
+    if wait_for_it {
+        job = wait(api_url, job, "Waiting for job", json).await?;
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&job)?);
+        return Ok(());
+    }
+
+    println!("\n{} {}", "Job".bold().cyan(), job.id.to_string().bold());
+    println!("  {}: {}", "Type".bold(), job.job_type);
+    println!("  {}: {}", "Status".bold(), job.status_label());
+    println!(
+        "  {}: {}/{}",
+        "Attempts".bold(),
+        job.attempts,
+        job.max_attempts
+    );
+    println!(
+        "  {}: {}",
+        "Queued".bold(),
+        job.created_at.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    if let Some(finished_at) = job.finished_at {
+        println!(
+            "  {}: {}",
+            "Finished".bold(),
+            finished_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+    } else if job.status == "queued" && job.attempts > 0 {
+        println!(
+            "  {}: {}",
+            "Next attempt".bold(),
+            job.next_attempt_at.format("%Y-%m-%d %H:%M:%S UTC")
+        );
+    }
+    if let Some(err) = &job.last_error {
+        println!("  {}: {}", "Last error".bold(), err.red());
+    }
+    if let Some(result) = &job.result {
+        println!(
+            "  {}:\n{}",
+            "Result".bold(),
+            serde_json::to_string_pretty(result)?
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// `job failed`: admin listing of failed and dead jobs
+pub async fn list_failed(
+    api_url: &str,
+    status: Option<&str>,
+    job_type: Option<&str>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let mut query: Vec<(&str, String)> = vec![("limit", limit.to_string())];
+    if let Some(status) = status {
+        query.push(("status", status.to_string()));
+    }
+    if let Some(job_type) = job_type {
+        query.push(("job_type", job_type.to_string()));
+    }
+    let response = crate::http::client()
+        .get(format!("{}/api/admin/jobs", api_url))
+        .query(&query)
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let page: Paginated<Job> = error_for_status(response, "list failed jobs")
+        .await?
+        .json()
+        .await
+        .context("Failed to parse job list")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&page.items)?);
+        return Ok(());
+    }
+    if page.items.is_empty() {
+        println!("{}", "No failed jobs.".green());
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = page
+        .items
+        .iter()
+        .map(|job| {
+            vec![
+                job.id.to_string(),
+                job.job_type.clone(),
+                job.status.clone(),
+                format!("{}/{}", job.attempts, job.max_attempts),
+                job.finished_at
+                    .map(|at| at.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            ]
+        })
+        .collect();
+    let details: Vec<Option<String>> = page
+        .items
+        .iter()
+        .map(|job| job.last_error.clone())
+        .collect();
+    print!(
+        "{}",
+        render_table_with_details(
+            &["ID", "TYPE", "STATUS", "ATTEMPTS", "FINISHED"],
+            &[36, 18, 9, 8, 16],
+            &rows,
+            &details,
+        )
+    );
+    if page.total > page.items.len() as i64 {
+        println!(
+            "{}",
+            format!(
+                "Showing {} of {}; raise --limit to see more.",
+                page.items.len(),
+                page.total
+            )
+            .bright_black()
+        );
+    }
+    Ok(())
+}
+
+/// `job retry`: queue a failed or dead job again
+pub async fn retry(api_url: &str, id: &str, json: bool) -> Result<()> {
+    let response = crate::http::client()
+        .post(format!("{}/api/admin/jobs/{}/retry", api_url, id))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let job: Job = error_for_status(response, "retry job")
+        .await?
+        .json()
+        .await
+        .context("Failed to parse job")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&job)?);
+    } else {
+        println!(
+            "{} Job {} ({}) queued again",
+            "✓".green(),
+            job.id.to_string().bold(),
+            job.job_type
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(status: &str, attempts: i32, last_error: Option<&str>) -> Job {
+        Job {
+            id: Uuid::nil(),
+            job_type: "verify_contract".to_string(),
+            status: status.to_string(),
+            attempts,
+            max_attempts: 5,
+            last_error: last_error.map(str::to_string),
+            result: None,
+            created_by: None,
+            next_attempt_at: Utc::now(),
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        }
+    }
+
+    #[test]
+    fn progress_mentions_retries_and_outcome_is_checked() {
+        assert!(!job("queued", 0, None).is_finished());
+        assert_eq!(job("queued", 0, None).progress(), "queued");
+        assert_eq!(
+            job("queued", 2, Some("rpc timeout")).progress(),
+            "retrying after attempt 2/5: rpc timeout"
+        );
+        assert_eq!(job("running", 1, None).progress(), "running (attempt 1/5)");
+
+        assert!(ensure_succeeded(&job("succeeded", 1, None), "Verification").is_ok());
+        let err = ensure_succeeded(&job("failed", 1, Some("bytecode mismatch")), "Verification")
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Verification failed: bytecode mismatch");
+        let err = ensure_succeeded(&job("dead", 5, Some("rpc timeout")), "Export").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Export gave up after 5 attempts: rpc timeout"
+        );
+    }
+}
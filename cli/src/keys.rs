@@ -0,0 +1,643 @@
+//! Named ed25519 keypairs for signing, kept in `~/.soroban-registry/keys`.
+//!
+//! Each key is a JSON file holding its public `G…` address in the clear and
+//! its secret seed sealed with XChaCha20-Poly1305, under a key derived from a
+//! passphrase with Argon2id. Seeds and passphrases are only read from hidden
+//! prompts, stdin or `SOROBAN_REGISTRY_KEY_PASSPHRASE`, never from arguments,
+//! so they stay out of shell history and logs.
+
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+/// Passphrase used instead of prompting, for scripts and CI
+pub const PASSPHRASE_ENV: &str = "SOROBAN_REGISTRY_KEY_PASSPHRASE";
+const KEYS_DIR: &str = ".soroban-registry/keys";
+const MAX_NAME_LEN: usize = 64;
+const MIN_PASSPHRASE_LEN: usize = 8;
+const KDF_ALGORITHM: &str = "argon2id";
+const CIPHER: &str = "xchacha20poly1305";
+
+/// Version bytes of Stellar strkeys: `G…` account ids and `S…` seeds
+const ACCOUNT_ID_VERSION: u8 = 6 << 3;
+const SEED_VERSION: u8 = 18 << 3;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    /// Base64
+    salt: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+}
+
+/// A key file. The address is the associated data of the ciphertext, so it
+/// cannot be swapped for another without the seed failing to decrypt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredKey {
+    name: String,
+    address: String,
+    created_at: DateTime<Utc>,
+    kdf: KdfParams,
+    cipher: String,
+    /// Base64
+    nonce: String,
+    /// Base64 of the sealed 32-byte seed
+    ciphertext: String,
+}
+
+// ── Stellar strkeys ──────────────────────────────────────────────────────────
+
+/// The `G…` account id of a public key
+pub fn account_id(key: &VerifyingKey) -> String {
+    encode_strkey(ACCOUNT_ID_VERSION, key.as_bytes())
+}
+
+fn secret_seed(key: &SigningKey) -> String {
+    encode_strkey(SEED_VERSION, &key.to_bytes())
+}
+
+fn parse_secret_seed(seed: &str) -> Result<SigningKey> {
+    let bytes = decode_strkey(SEED_VERSION, seed.trim())
+        .context("Not a Stellar secret seed (expected S… with 56 characters)")?;
+    Ok(SigningKey::from_bytes(&bytes))
+}
+
+fn encode_strkey(version: u8, payload: &[u8; 32]) -> String {
+    let mut data = Vec::with_capacity(35);
+    data.push(version);
+    data.extend_from_slice(payload);
+    let checksum = crc16_xmodem(&data);
+    data.extend_from_slice(&checksum.to_le_bytes());
+    base32_encode(&data)
+}
+
+fn decode_strkey(version: u8, strkey: &str) -> Result<[u8; 32]> {
+    let data = base32_decode(strkey).context("invalid base32")?;
+    anyhow::ensure!(data.len() == 35, "wrong length");
+    anyhow::ensure!(data[0] == version, "wrong key type");
+    let checksum = u16::from_le_bytes([data[33], data[34]]);
+    anyhow::ensure!(crc16_xmodem(&data[..33]) == checksum, "checksum mismatch");
+    let mut payload = [0u8; 32];
+    payload.copy_from_slice(&data[1..33]);
+    Ok(payload)
+}
+
+fn crc16_xmodem(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &byte in data {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// RFC 4648 base32 without padding; strkeys are always a multiple of 5 bytes
+fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in data {
+        buffer = (buffer << 8) | u32::from(byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for c in text.bytes() {
+        let value = BASE32_ALPHABET.iter().position(|&a| a == c)? as u32;
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+// ── Sealing ──────────────────────────────────────────────────────────────────
+
+fn derive_key(passphrase: &str, kdf: &KdfParams) -> Result<[u8; 32]> {
+    anyhow::ensure!(
+        kdf.algorithm == KDF_ALGORITHM,
+        "Unsupported key derivation '{}'",
+        kdf.algorithm
+    );
+    let salt = BASE64.decode(&kdf.salt).context("Corrupt key file salt")?;
+    let params = Params::new(kdf.memory_kib, kdf.iterations, kdf.parallelism, Some(32))
+        .map_err(|err| anyhow::anyhow!("Invalid key derivation parameters: {}", err))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|err| anyhow::anyhow!("Key derivation failed: {}", err))?;
+    Ok(key)
+}
+
+fn seal(name: &str, signing_key: &SigningKey, passphrase: &str) -> Result<StoredKey> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let defaults = Params::default();
+    let kdf = KdfParams {
+        algorithm: KDF_ALGORITHM.to_string(),
+        salt: BASE64.encode(salt),
+        memory_kib: defaults.m_cost(),
+        iterations: defaults.t_cost(),
+        parallelism: defaults.p_cost(),
+    };
+    let address = account_id(&signing_key.verifying_key());
+
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &kdf)?.into());
+    let ciphertext = cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &signing_key.to_bytes(),
+                aad: address.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt the key"))?;
+
+    Ok(StoredKey {
+        name: name.to_string(),
+        address,
+        created_at: Utc::now(),
+        kdf,
+        cipher: CIPHER.to_string(),
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    })
+}
+
+fn open(stored: &StoredKey, passphrase: &str) -> Result<SigningKey> {
+    anyhow::ensure!(
+        stored.cipher == CIPHER,
+        "Unsupported key file cipher '{}'",
+        stored.cipher
+    );
+    let nonce = BASE64
+        .decode(&stored.nonce)
+        .context("Corrupt key file nonce")?;
+    anyhow::ensure!(nonce.len() == 24, "Corrupt key file nonce");
+    let ciphertext = BASE64
+        .decode(&stored.ciphertext)
+        .context("Corrupt key file ciphertext")?;
+
+    let cipher = XChaCha20Poly1305::new(&derive_key(passphrase, &stored.kdf)?.into());
+    let seed = cipher
+        .decrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: stored.address.as_bytes(),
+            },
+        )
+        .map_err(|_| anyhow::anyhow!("Wrong passphrase for key '{}'", stored.name))?;
+    let seed: [u8; 32] = seed
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Corrupt key file: seed is not 32 bytes"))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+// ── Key files ────────────────────────────────────────────────────────────────
+
+fn keys_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().context("Could not find the home directory")?;
+    Ok(home.join(KEYS_DIR))
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    anyhow::ensure!(
+        valid,
+        "Invalid key name '{}': use up to {} letters, digits, '-' or '_'",
+        name,
+        MAX_NAME_LEN
+    );
+    Ok(())
+}
+
+fn key_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+fn write_key(dir: &Path, stored: &StoredKey) -> Result<PathBuf> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+            .with_context(|| format!("Failed to restrict {}", dir.display()))?;
+    }
+
+    let path = key_path(dir, &stored.name);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = match options.open(&path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+            anyhow::bail!("A key named '{}' already exists", stored.name)
+        }
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to create {}", path.display()))
+        }
+    };
+    file.write_all(serde_json::to_string_pretty(stored)?.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(path)
+}
+
+fn read_key(dir: &Path, name: &str) -> Result<StoredKey> {
+    validate_name(name)?;
+    let path = key_path(dir, name);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            anyhow::bail!("No key named '{}'; see `soroban-registry keys list`", name)
+        }
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    serde_json::from_str(&contents).with_context(|| format!("Corrupt key file {}", path.display()))
+}
+
+fn read_keys(dir: &Path) -> Result<Vec<StoredKey>> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let mut keys = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            let contents = fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            match serde_json::from_str::<StoredKey>(&contents) {
+                Ok(stored) => keys.push(stored),
+                Err(_) => log::warn!("Skipping unreadable key file {}", path.display()),
+            }
+        }
+    }
+    keys.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(keys)
+}
+
+// ── Prompts ──────────────────────────────────────────────────────────────────
+
+fn passphrase_for(name: &str) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    rpassword::prompt_password(format!("Passphrase for key '{}': ", name)).with_context(|| {
+        format!(
+            "Failed to read the passphrase; set {} when there is no terminal",
+            PASSPHRASE_ENV
+        )
+    })
+}
+
+fn new_passphrase(name: &str) -> Result<String> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        check_passphrase(&passphrase)?;
+        return Ok(passphrase);
+    }
+    let read = |prompt: String| {
+        rpassword::prompt_password(prompt).with_context(|| {
+            format!(
+                "Failed to read the passphrase; set {} when there is no terminal",
+                PASSPHRASE_ENV
+            )
+        })
+    };
+    let passphrase = read(format!("New passphrase for key '{}': ", name))?;
+    check_passphrase(&passphrase)?;
+    anyhow::ensure!(
+        read("Repeat the passphrase: ".to_string())? == passphrase,
+        "Passphrases do not match"
+    );
+    Ok(passphrase)
+}
+
+fn check_passphrase(passphrase: &str) -> Result<()> {
+    anyhow::ensure!(
+        passphrase.chars().count() >= MIN_PASSPHRASE_LEN,
+        "The passphrase must be at least {} characters",
+        MIN_PASSPHRASE_LEN
+    );
+    Ok(())
+}
+
+// ── Commands ─────────────────────────────────────────────────────────────────
+
+/// `keys generate`: creates a keypair and stores it encrypted
+pub fn generate(name: &str) -> Result<()> {
+    validate_name(name)?;
+    let dir = keys_dir()?;
+    anyhow::ensure!(
+        !key_path(&dir, name).exists(),
+        "A key named '{}' already exists",
+        name
+    );
+
+    let signing_key = SigningKey::generate(&mut OsRng);
+    let passphrase = new_passphrase(name)?;
+    let stored = seal(name, &signing_key, &passphrase)?;
+    let path = write_key(&dir, &stored)?;
+
+    println!("{}", "✓ Keypair generated".green().bold());
+    print_public_details(&stored, &signing_key.verifying_key(), &path);
+    Ok(())
+}
+
+/// `keys import`: stores an existing `S…` seed, read from a hidden prompt or,
+/// with `from_stdin`, the first line of stdin
+pub fn import(name: &str, from_stdin: bool) -> Result<()> {
+    validate_name(name)?;
+    let dir = keys_dir()?;
+    anyhow::ensure!(
+        !key_path(&dir, name).exists(),
+        "A key named '{}' already exists",
+        name
+    );
+
+    let seed = if from_stdin {
+        let mut line = String::new();
+        io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .context("Failed to read the seed from stdin")?;
+        line
+    } else {
+        rpassword::prompt_password("Secret seed (S…): ")
+            .context("Failed to read the seed; pipe it in with --stdin when there is no terminal")?
+    };
+    let signing_key = parse_secret_seed(&seed)?;
+
+    let passphrase = new_passphrase(name)?;
+    let stored = seal(name, &signing_key, &passphrase)?;
+    let path = write_key(&dir, &stored)?;
+
+    println!("{}", "✓ Key imported".green().bold());
+    print_public_details(&stored, &signing_key.verifying_key(), &path);
+    Ok(())
+}
+
+fn print_public_details(stored: &StoredKey, verifying_key: &VerifyingKey, path: &Path) {
+    println!("  {}: {}", "Name".bold(), stored.name);
+    println!("  {}: {}", "Address".bold(), stored.address.bright_cyan());
+    println!(
+        "  {}: {}",
+        "Public key (base64)".bold(),
+        BASE64.encode(verifying_key.as_bytes())
+    );
+    println!("  {}: {}", "Stored in".bold(), path.display());
+    println!(
+        "\n  {} Sign with: soroban-registry publish --key {} …",
+        "→".bright_black(),
+        stored.name
+    );
+}
+
+/// `keys list`: names and public addresses; never asks for a passphrase
+pub fn list() -> Result<()> {
+    let keys = read_keys(&keys_dir()?)?;
+    if keys.is_empty() {
+        println!("No keys yet; create one with `soroban-registry keys generate`");
+        return Ok(());
+    }
+    println!(
+        "{:<24} {:<58} {}",
+        "NAME".bold(),
+        "ADDRESS".bold(),
+        "CREATED".bold()
+    );
+    for stored in keys {
+        println!(
+            "{:<24} {:<58} {}",
+            stored.name,
+            stored.address,
+            stored.created_at.format("%Y-%m-%d")
+        );
+    }
+    Ok(())
+}
+
+/// `keys export`: prints the `S…` seed after the key name is typed back, or
+/// with `yes`
+pub fn export(name: &str, yes: bool) -> Result<()> {
+    let stored = read_key(&keys_dir()?, name)?;
+    if !yes {
+        anyhow::ensure!(
+            io::stdin().is_terminal(),
+            "Refusing to export without confirmation; pass --yes when there is no terminal"
+        );
+        eprintln!(
+            "{}",
+            "The secret seed gives full control of this account. Anyone who sees it can sign as you."
+                .red()
+                .bold()
+        );
+        eprint!("Type the key name ({}) to print its seed: ", name);
+        io::stderr().flush()?;
+        let mut answer = String::new();
+        io::stdin().lock().read_line(&mut answer)?;
+        anyhow::ensure!(answer.trim() == name, "Export cancelled");
+    }
+
+    let signing_key = open(&stored, &passphrase_for(name)?)?;
+    println!("{}", secret_seed(&signing_key));
+    Ok(())
+}
+
+/// The signing key stored as `name`, after asking for its passphrase
+pub fn unlock(name: &str) -> Result<SigningKey> {
+    let stored = read_key(&keys_dir()?, name)?;
+    open(&stored, &passphrase_for(name)?)
+}
+
+/// The base64 public key stored as `name`, as `verify-contract` takes it;
+/// no passphrase needed
+pub fn public_key_base64(name: &str) -> Result<String> {
+    let stored = read_key(&keys_dir()?, name)?;
+    let bytes = decode_strkey(ACCOUNT_ID_VERSION, &stored.address)
+        .with_context(|| format!("Corrupt key file: invalid address for key '{}'", name))?;
+    VerifyingKey::from_bytes(&bytes).context("Corrupt key file: invalid public key")?;
+    Ok(BASE64.encode(bytes))
+}
+
+/// Proves ownership of `signing_key` to the registry with its challenge
+/// flow, returning a bearer token for the signed-in key
+pub async fn sign_in(api_url: &str, signing_key: &SigningKey) -> Result<String> {
+    let base = api_url.trim_end_matches('/');
+    let public_key = hex::encode(signing_key.verifying_key().as_bytes());
+    let client = crate::http::client();
+
+    let response = client
+        .get(format!("{}/api/auth/challenge", base))
+        .query(&[("address", &public_key)])
+        .send()
+        .await
+        .context("Failed to request a sign-in challenge")?;
+    let challenge: serde_json::Value = error_for_status(response, "request a challenge").await?;
+    let nonce = challenge["nonce"]
+        .as_str()
+        .context("Challenge response has no nonce")?;
+
+    let signature = signing_key.sign(nonce.as_bytes());
+    let response = client
+        .post(format!("{}/api/auth/verify", base))
+        .json(&serde_json::json!({
+            "address": public_key,
+            "public_key": public_key,
+            "signature": hex::encode(signature.to_bytes()),
+        }))
+        .send()
+        .await
+        .context("Failed to answer the sign-in challenge")?;
+    let session: serde_json::Value = error_for_status(response, "sign in").await?;
+    session["token"]
+        .as_str()
+        .map(str::to_string)
+        .context("Sign-in response has no token")
+}
+
+async fn error_for_status(response: reqwest::Response, action: &str) -> Result<serde_json::Value> {
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    if !status.is_success() {
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Failed to {}: {} ({})", action, message, status);
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
+
+    #[test]
+    fn zero_key_has_the_well_known_address() {
+        let key = VerifyingKey::from_bytes(&[0u8; 32]).unwrap();
+        assert_eq!(
+            account_id(&key),
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF"
+        );
+    }
+
+    #[test]
+    fn seeds_round_trip_and_are_checked() {
+        let seed = secret_seed(&test_key());
+        assert!(seed.starts_with('S'));
+        assert_eq!(seed.len(), 56);
+        assert_eq!(parse_secret_seed(&seed).unwrap().to_bytes(), [7u8; 32]);
+
+        let address = account_id(&test_key().verifying_key());
+        assert!(
+            parse_secret_seed(&address).is_err(),
+            "an address is not a seed"
+        );
+        let mut tampered = seed.clone().into_bytes();
+        tampered[10] = if tampered[10] == b'A' { b'B' } else { b'A' };
+        assert!(parse_secret_seed(std::str::from_utf8(&tampered).unwrap()).is_err());
+    }
+
+    #[test]
+    fn sealed_keys_open_only_with_their_passphrase() {
+        let stored = seal("ci", &test_key(), "correct horse").unwrap();
+        assert_eq!(stored.address, account_id(&test_key().verifying_key()));
+        assert!(!stored.ciphertext.contains(&BASE64.encode([7u8; 32])));
+
+        assert_eq!(
+            open(&stored, "correct horse").unwrap().to_bytes(),
+            [7u8; 32]
+        );
+        let err = open(&stored, "wrong horse").unwrap_err();
+        assert!(err.to_string().contains("Wrong passphrase"));
+
+        let mut swapped = stored.clone();
+        swapped.address = account_id(&VerifyingKey::from_bytes(&[0u8; 32]).unwrap());
+        assert!(open(&swapped, "correct horse").is_err());
+    }
+
+    #[test]
+    fn key_files_are_private_and_never_overwritten() {
+        let dir = tempfile::tempdir().unwrap();
+        let stored = seal("deploy", &test_key(), "correct horse").unwrap();
+        let path = write_key(dir.path(), &stored).unwrap();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let err = write_key(dir.path(), &stored).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+
+        let listed = read_keys(dir.path()).unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].address, stored.address);
+        assert_eq!(read_key(dir.path(), "deploy").unwrap().name, "deploy");
+        assert!(read_key(dir.path(), "missing").is_err());
+    }
+
+    #[test]
+    fn key_names_cannot_leave_the_keys_directory() {
+        for name in [
+            "",
+            "../evil",
+            "a/b",
+            "key.json",
+            &"k".repeat(MAX_NAME_LEN + 1),
+        ] {
+            assert!(validate_name(name).is_err(), "{:?}", name);
+        }
+        assert!(validate_name("ci-deploy_2").is_ok());
+    }
+}
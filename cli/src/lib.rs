@@ -1,2 +1,2 @@
-pub mod profiler;
+pub use soroban_profiler as profiler;
 pub mod table_format;
@@ -3,50 +3,80 @@
 mod backup;
 mod batch_register;
 mod batch_verify;
+mod browse;
+mod changelog;
+mod changes;
 mod cicd;
 mod commands;
 mod config;
+mod config_bundle;
+mod contract_update;
 mod contract_verify;
 mod contracts;
 mod conversions;
 mod coverage;
 mod dashboard;
+mod debug_dump;
+mod doctor;
+mod download;
 mod events;
 mod export;
 mod formal_verification;
 mod fuzz;
+mod http;
 mod import;
 mod incident;
 mod io_utils;
+mod jobs;
+mod keys;
 mod manifest;
 mod migration;
 mod multisig;
 mod network;
 mod package_signing;
 mod patch;
-mod profiler;
+mod plugins;
+mod publish_version;
 mod release_notes;
+mod resolve;
+mod saved_search;
+mod scval;
+mod simulation;
 mod sla;
+mod stats;
+mod subscriptions;
 mod table_format;
+mod tags;
+mod telemetry;
 mod test_framework;
+mod time_format;
+mod wasm_diff;
 mod webhook;
 mod wizard;
 
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
 use patch::Severity;
+use std::ffi::OsString;
 
 /// Soroban Registry CLI — discover, publish, verify, and deploy Soroban contracts
 #[derive(Debug, Parser)]
 #[command(name = "soroban-registry", version, about, long_about = None)]
 pub struct Cli {
-    /// Registry API URL
-    #[arg(
-        long,
-        env = "SOROBAN_REGISTRY_API_URL",
-        default_value = "http://localhost:3001"
-    )]
-    pub api_url: String,
+    /// Registry API URL [default: the profile's api_url, or http://localhost:3001]
+    #[arg(long, env = "SOROBAN_REGISTRY_API_URL")]
+    pub api_url: Option<String>,
+
+    /// Output of the command: `json` is the same as the command's --json
+    /// flag and fails for commands without one. Commands that write a file
+    /// take --output as its path, so give the format before them [default:
+    /// the profile's json setting, or text]
+    #[arg(long, global = true, value_name = "FORMAT", value_parser = ["text", "json"])]
+    pub output: Option<String>,
+
+    /// Config profile to use, see `config profiles`
+    #[arg(long, global = true, env = "SOROBAN_REGISTRY_PROFILE")]
+    pub profile: Option<String>,
 
     /// Stellar network to use (mainnet | testnet | futurenet)
     #[arg(long, global = true)]
@@ -56,16 +86,48 @@ pub struct Cli {
     #[arg(long, short = 'v', global = true)]
     pub verbose: bool,
 
+    /// Proxy for all HTTP requests, overriding HTTP_PROXY/HTTPS_PROXY (NO_PROXY still applies)
+    #[arg(long, global = true, value_name = "URL")]
+    pub proxy: Option<String>,
+
+    /// PEM file of extra root certificates to trust (e.g. a corporate TLS-intercepting CA)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub ca_cert: Option<std::path::PathBuf>,
+
+    /// DANGEROUS: skip TLS certificate verification for all HTTP requests
+    #[arg(long, global = true)]
+    pub insecure: bool,
+
+    /// How timestamps read in text output [default: the config's
+    /// time_format, or relative]; JSON output always uses RFC 3339 UTC
+    #[arg(long, global = true, value_enum)]
+    pub time_format: Option<time_format::TimeFormat>,
+
+    /// Write a capture of every failed request to this directory, with
+    /// credentials redacted, to attach to bug reports [default: the
+    /// config's debug_dump, or off]
+    #[arg(long, global = true, value_name = "DIR")]
+    pub debug_dump: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputMode {
+    /// Human-readable text
+    Text,
+    /// Machine-readable JSON, stable for scripts and CI
+    Json,
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Search for contracts in the registry
     Search {
         /// Search query
-        query: String,
+        #[arg(required_unless_present = "saved")]
+        query: Option<String>,
         /// Only show verified contracts
         #[arg(long)]
         verified_only: bool,
@@ -75,6 +137,23 @@ pub enum Commands {
         /// Filter by contract category (e.g. DEX, token, lending, oracle)
         #[arg(long)]
         category: Option<String>,
+        /// Only contracts published by this Stellar address
+        #[arg(long, value_name = "ADDRESS")]
+        publisher: Option<String>,
+        /// Only contracts owned by this organization
+        #[arg(long, value_name = "SLUG")]
+        org: Option<String>,
+        /// Also save the query and filters on the registry under this name;
+        /// requires SOROBAN_REGISTRY_API_TOKEN
+        #[arg(long, value_name = "NAME")]
+        save: Option<String>,
+        /// Run a saved search: one of yours by name, or any by its id
+        #[arg(
+            long,
+            value_name = "NAME",
+            conflicts_with_all = ["query", "verified_only", "network", "category", "publisher", "org", "save"]
+        )]
+        saved: Option<String>,
         /// Maximum number of results to return
         #[arg(long, default_value = "20")]
         limit: usize,
@@ -86,9 +165,29 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Browse contracts by category: pick one from the list of categories,
+    /// then page through its contracts
+    Browse {
+        /// Category name or slug; required when stdin is not a terminal
+        category: Option<String>,
+        /// Order of the contracts
+        #[arg(long, value_enum, default_value_t = browse::BrowseSort::Newest)]
+        sort: browse::BrowseSort,
+        /// Contracts per page
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Number of contracts to skip (for pagination)
+        #[arg(long, default_value = "0")]
+        offset: usize,
+        /// Output the page as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Get detailed information about a contract
     Info {
-        /// Contract registry identifier (UUID, contract address, or name)
+        /// Contract registry identifier (UUID, contract address, or
+        /// namespace/slug[@version])
         contract_id: String,
 
         /// Output format (text, json, yaml)
@@ -98,6 +197,40 @@ pub enum Commands {
         /// Highlight a specific ABI method
         #[arg(long)]
         highlight_method: Option<String>,
+
+        /// Also show the toolchain, build flags and lockfile hash the
+        /// contract was verified with
+        #[arg(long)]
+        build_info: bool,
+
+        /// Also list the latest incidents that affected the contract and
+        /// how long each took to resolve
+        #[arg(long)]
+        incidents: bool,
+
+        /// Output results as machine-readable JSON; same as --format json
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+    },
+
+    /// Download the published source of a contract version; an interrupted
+    /// download is resumed when run again
+    Download {
+        /// namespace/slug[@version] (latest version when omitted), or a
+        /// contract UUID together with --version
+        contract: String,
+
+        /// Version to download; overrides one given in the name
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Source format (rust, wasm)
+        #[arg(long, default_value = "rust")]
+        format: String,
+
+        /// File to write (default: <slug>-<version>.rs or .wasm)
+        #[arg(long, short = 'o')]
+        output: Option<String>,
     },
 
     /// Publish a new contract to the registry
@@ -127,8 +260,116 @@ pub enum Commands {
         tags: Option<String>,
 
         /// Publisher Stellar address
+        #[arg(long, required_unless_present = "key")]
+        publisher: Option<String>,
+
+        /// Publish as this stored key (see `keys`): its address becomes the
+        /// publisher and the request is signed in with it
+        #[arg(long, conflicts_with = "publisher")]
+        key: Option<String>,
+
+        /// Publish under this organization (slug) instead of the publisher;
+        /// requires SOROBAN_REGISTRY_API_TOKEN for one of its maintainers
+        #[arg(long)]
+        org: Option<String>,
+
+        /// URL-safe name for `namespace/slug` lookups (default: derived
+        /// from --name)
         #[arg(long)]
-        publisher: String,
+        slug: Option<String>,
+
+        /// Output the registered contract as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Add a version to a registered contract and upload its WASM; breaking
+    /// interface changes are listed first
+    PublishVersion {
+        /// Contract ID or namespace/slug
+        contract_id: String,
+
+        /// Semver version to publish (e.g. 1.2.0)
+        #[arg(long)]
+        version: String,
+
+        /// Compiled contract WASM
+        #[arg(long)]
+        wasm: std::path::PathBuf,
+
+        /// Contract interface as JSON
+        #[arg(long)]
+        abi: std::path::PathBuf,
+
+        /// Release notes for the version
+        #[arg(long)]
+        notes: Option<String>,
+
+        /// Where the version's source can be found
+        #[arg(long)]
+        source_url: Option<String>,
+
+        /// Commit the version was built from
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// Publish even though the interface breaks callers of the previous
+        /// version without a major version bump
+        #[arg(long)]
+        allow_breaking: bool,
+
+        /// Sign the version with this stored key (see `keys`) and sign in as
+        /// it to authorize the publish
+        #[arg(long)]
+        key: Option<String>,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Hand a contract over to another publisher
+    Transfer {
+        /// Contract ID or namespace/slug
+        contract_id: String,
+
+        /// Stellar address of the new publisher
+        #[arg(long)]
+        to: String,
+
+        /// Sign in as this stored key (see `keys`) to authorize the transfer
+        #[arg(long)]
+        key: Option<String>,
+    },
+
+    /// Edit a contract's metadata; a concurrent edit is shown before retrying
+    Update {
+        /// Contract ID or namespace/slug
+        contract_id: String,
+
+        /// New display name
+        #[arg(long)]
+        name: Option<String>,
+
+        /// New description
+        #[arg(long)]
+        description: Option<String>,
+
+        /// New category
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Comma-separated tags, replacing the current ones
+        #[arg(long, value_delimiter = ',')]
+        tags: Option<Vec<String>>,
+
+        /// New slug; the old one keeps resolving to the contract
+        #[arg(long)]
+        slug: Option<String>,
+
+        /// Sign in as this stored key (see `keys`) to authorize the edit
+        #[arg(long)]
+        key: Option<String>,
     },
 
     /// List recent contracts
@@ -136,6 +377,32 @@ pub enum Commands {
         /// Maximum number of contracts to show
         #[arg(long, default_value = "10")]
         limit: usize,
+        /// Only list contracts owned by this organization (slug)
+        #[arg(long)]
+        org: Option<String>,
+        /// List your own contracts, including private and unverified ones
+        /// (needs SOROBAN_REGISTRY_API_TOKEN)
+        #[arg(long, conflicts_with = "org")]
+        mine: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show registry totals, or with --timeseries how activity developed
+    Stats {
+        /// Chart publishes, verifications or downloads per interval as sparklines
+        #[arg(long)]
+        timeseries: bool,
+        /// Comma-separated metrics to chart: publishes, verifications, downloads
+        #[arg(long, default_value = "publishes", requires = "timeseries")]
+        metrics: String,
+        /// Bucket width: hour, day, week or month
+        #[arg(long, default_value = "day", requires = "timeseries")]
+        interval: String,
+        /// How far back to chart, e.g. 48h, 90d or 12w
+        #[arg(long, default_value = "90d", requires = "timeseries")]
+        window: String,
         /// Output results as machine-readable JSON
         #[arg(long)]
         json: bool,
@@ -165,6 +432,56 @@ pub enum Commands {
         json: bool,
     },
 
+    /// Compare metadata, tags and interface of two contracts or versions
+    Diff {
+        /// Base contract (UUID or contract address, optionally @version)
+        a: String,
+        /// Contract to compare against the base, in the same form
+        b: String,
+        /// Compare the two versions' stored WASM binaries: section sizes,
+        /// functions and data segments. Both sides must be versions of the
+        /// same contract
+        #[arg(long)]
+        code: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show how a contract evolved: every version, newest first, with its
+    /// release notes, WASM changes and breaking changes
+    Changelog {
+        /// Contract registry identifier (UUID, contract address, or
+        /// namespace/slug)
+        contract_id: String,
+        /// Only versions published after this one
+        #[arg(long, value_name = "VERSION")]
+        since: Option<String>,
+        /// Print a CHANGELOG.md-style document instead
+        #[arg(long, conflicts_with = "json")]
+        markdown: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Show what changed in the registry since a date: contracts added,
+    /// verified, deprecated, removed and updated, with counts per group
+    Changes {
+        /// Start of the window: a date (2024-06-01) or RFC 3339 timestamp
+        #[arg(long)]
+        since: String,
+        /// End of the window, in the same form (default now)
+        #[arg(long)]
+        until: Option<String>,
+        /// Contracts to show per group
+        #[arg(long, default_value = "10")]
+        limit: usize,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Contract state migration assistant
     Migrate {
         #[command(subcommand)]
@@ -208,6 +525,67 @@ pub enum Commands {
         output_dir: String,
     },
 
+    /// Submit a contract's source for verification against its on-chain bytecode
+    VerifySource {
+        /// Contract registry ID (UUID)
+        contract_id: String,
+
+        /// The contract's lib.rs, or a directory containing lib.rs or src/lib.rs
+        #[arg(long, default_value = ".", conflicts_with = "wasm")]
+        source: String,
+
+        /// Submit a prebuilt WASM module instead of source
+        #[arg(long)]
+        wasm: Option<String>,
+
+        /// Rust compiler version the contract was built with
+        #[arg(long)]
+        compiler_version: String,
+
+        /// Build parameters as a JSON object
+        #[arg(long)]
+        build_params: Option<String>,
+
+        /// Cargo.lock to build against
+        #[arg(long)]
+        cargo_lock: Option<String>,
+
+        /// Public URL of the source repository
+        #[arg(long)]
+        source_url: Option<String>,
+
+        /// Commit the source was taken from
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// Print the job ID and return instead of waiting for the result
+        #[arg(long)]
+        no_wait: bool,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Registry snapshots for disaster recovery (admin)
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotCommands,
+    },
+
+    /// Rename or remove a tag across your contracts
+    Tags {
+        #[command(subcommand)]
+        action: TagCommands,
+    },
+
+    /// Background jobs queued by verify-source and snapshot export
+    #[command(alias = "jobs")]
+    Job {
+        #[command(subcommand)]
+        action: JobCommands,
+    },
+
     /// Generate documentation from a contract WASM
     Doc {
         /// Path to contract WASM file
@@ -232,6 +610,25 @@ pub enum Commands {
         format: String,
     },
 
+    /// Generate client bindings from a registered contract's ABI
+    GenerateBindings {
+        /// Contract name (namespace/slug[@version]), UUID or address
+        contract_id: String,
+
+        /// Target language: typescript
+        #[arg(long, default_value = "typescript")]
+        lang: String,
+
+        /// Output directory
+        #[arg(long, short = 'o', default_value = "./bindings")]
+        output: String,
+
+        /// Version to generate from; overrides one given in the name. Defaults
+        /// to the latest
+        #[arg(long)]
+        version: Option<String>,
+    },
+
     /// Launch the interactive setup wizard
     Wizard {},
 
@@ -253,6 +650,7 @@ pub enum Commands {
     },
 
     /// Incident response management
+    #[command(alias = "incidents")]
     Incident {
         #[command(subcommand)]
         action: IncidentCommands,
@@ -284,28 +682,129 @@ pub enum Commands {
 
     /// Profile contract execution performance
     Profile {
-        /// Path to contract file
-        contract_path: String,
+        /// Path to contract file, or namespace/slug[@version] to profile
+        /// the published source of a registry contract
+        #[arg(
+            required_unless_present_any = ["remote", "fetch"],
+            conflicts_with_all = ["remote", "fetch"]
+        )]
+        contract_path: Option<String>,
 
         /// Method to profile
         #[arg(long)]
         method: Option<String>,
 
-        /// Output JSON file
+        /// Profile a deployed contract (C… address or namespace/slug) by simulating --method through Soroban RPC
+        #[arg(long, requires = "method")]
+        remote: Option<String>,
+
+        /// Soroban RPC endpoint for --remote [default: the public RPC of --network]
+        #[arg(long, requires = "remote")]
+        rpc_url: Option<String>,
+
+        /// Arguments for --remote as JSON: an array, or an object keyed by parameter name
+        #[arg(long, requires = "remote")]
+        args: Option<String>,
+
+        /// Download the official profile recorded when the registry verified this contract (UUID, address or namespace/slug[@version]) to --output, as a --compare baseline
+        #[arg(long, conflicts_with_all = ["remote", "compare"])]
+        fetch: Option<String>,
+
+        /// instrumented times every call; sampling records the call stack --sample-rate times a second
+        #[arg(long, default_value = "instrumented", value_parser = ["instrumented", "sampling"])]
+        mode: String,
+
+        /// Stack samples per second in sampling mode
+        #[arg(long, default_value_t = 1000)]
+        sample_rate: u32,
+
+        /// Output file for the profile
         #[arg(long)]
         output: Option<String>,
 
+        /// Format of --output: json, or chrome-trace for chrome://tracing and Perfetto
+        #[arg(long, default_value = "json", value_parser = ["json", "chrome-trace"])]
+        output_format: String,
+
         /// Generate flame graph
         #[arg(long)]
         flamegraph: Option<String>,
 
+        /// Write folded stacks for flamegraph.pl / inferno
+        #[arg(long)]
+        folded: Option<String>,
+
         /// Compare with baseline profile
         #[arg(long)]
         compare: Option<String>,
 
+        /// Estimate the fee per invocation with the built-in public network fee parameters
+        #[arg(long)]
+        estimate_fees: bool,
+
+        /// Estimate the fee per invocation with the fee parameters in this JSON file
+        #[arg(long)]
+        fee_config: Option<String>,
+
+        /// Write a differential flame graph against the baseline
+        #[arg(long, requires = "compare")]
+        flamegraph_diff: Option<String>,
+
+        /// Fail when the comparison exceeds the regression gate (CI mode)
+        #[arg(long, requires = "compare")]
+        ci: bool,
+
+        /// Slowdown in percent of an existing function that fails the gate
+        #[arg(long, default_value_t = 10.0)]
+        max_slowdown: f64,
+
+        /// Time in milliseconds a new function may take before it fails the gate; with --remote each millisecond stands for 1,000,000 CPU instructions
+        #[arg(long, default_value_t = 1.0)]
+        max_new_function_ms: f64,
+
         /// Show recommendations
         #[arg(long, default_value = "true")]
         recommendations: bool,
+
+        /// After a successful run, upload the profile to this registry contract (UUID, address or namespace/slug) you publish
+        #[arg(long, conflicts_with = "fetch")]
+        publish_to: Option<String>,
+
+        /// Sign in as this stored key (see `keys`) to authorize --publish-to
+        #[arg(long, requires = "publish_to")]
+        key: Option<String>,
+        /// Output the profile, written files, comparison and recommendations as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Compare two saved profiles, e.g. from earlier CI runs
+    DiffProfile {
+        /// Baseline profile (JSON written by `profile --output`)
+        baseline: String,
+
+        /// Profile to compare against the baseline
+        current: String,
+
+        /// Fail when the comparison exceeds the regression gate (CI mode)
+        #[arg(long)]
+        ci: bool,
+
+        /// Slowdown in percent of an existing function that fails the gate
+        #[arg(long, default_value_t = 10.0)]
+        max_slowdown: f64,
+
+        /// Time in milliseconds a new function may take before it fails the gate; for simulated profiles each millisecond stands for 1,000,000 CPU instructions
+        #[arg(long, default_value_t = 1.0)]
+        max_new_function_ms: f64,
+
+        /// Compare profiles recorded in different modes (instrumented, sampled, simulated)
+        #[arg(long)]
+        force: bool,
+
+        /// Output the comparison as machine-readable JSON
+        #[arg(long)]
+        json: bool,
     },
 
     /// Run integration tests
@@ -391,9 +890,14 @@ pub enum Commands {
         /// Path to the package file to sign
         package: String,
 
-        /// Private key (base64-encoded Ed25519)
-        #[arg(long)]
-        private_key: String,
+        /// Private key (base64-encoded Ed25519); prefer --key, which keeps
+        /// the secret out of shell history
+        #[arg(long, required_unless_present = "key")]
+        private_key: Option<String>,
+
+        /// Sign with this stored key (see `keys`)
+        #[arg(long, conflicts_with = "private_key")]
+        key: Option<String>,
 
         /// Contract ID
         #[arg(long)]
@@ -444,8 +948,12 @@ pub enum Commands {
         signature: String,
 
         /// Ed25519 public key (base64)
-        #[arg(long)]
-        public_key: String,
+        #[arg(long, required_unless_present = "key")]
+        public_key: Option<String>,
+
+        /// Check against the public key of this stored key (see `keys`)
+        #[arg(long, conflicts_with = "public_key")]
+        key: Option<String>,
     },
 
     /// Manage signing keys and signatures
@@ -482,6 +990,53 @@ pub enum Commands {
         action: WebhookCommands,
     },
 
+    /// Get notified when a contract publishes a version, is verified, is
+    /// named in an incident, is affected by a security advisory or is about
+    /// to be marked stale (requires SOROBAN_REGISTRY_API_TOKEN)
+    Subscribe {
+        /// Contract UUID, on-chain contract ID or namespace/slug name
+        contract_id: String,
+
+        /// Comma-separated events: version, verification, incident, security,
+        /// freshness (or all)
+        #[arg(long)]
+        events: String,
+
+        /// Receive each event as a JSON POST to this URL
+        #[arg(long, required_unless_present = "email", conflicts_with = "email")]
+        webhook: Option<String>,
+
+        /// Receive each event by email at this address
+        #[arg(long)]
+        email: Option<String>,
+
+        /// Output the subscription as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List your contract subscriptions and how their deliveries went
+    Subscriptions {
+        /// Only subscriptions to this contract
+        #[arg(long)]
+        contract: Option<String>,
+
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Stop notifications for a contract, or remove one subscription with --id
+    Unsubscribe {
+        /// Contract UUID, on-chain contract ID or namespace/slug name
+        #[arg(required_unless_present = "id", conflicts_with = "id")]
+        contract_id: Option<String>,
+
+        /// Subscription ID, as shown by `subscriptions`
+        #[arg(long)]
+        id: Option<String>,
+    },
+
     /// Auto-generate and manage release notes for contract versions
     ReleaseNotes {
         #[command(subcommand)]
@@ -494,6 +1049,30 @@ pub enum Commands {
         action: CicdCommands,
     },
 
+    /// Show the registry's public status: open incidents, maintenance and uptime
+    Status {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Diagnose config, connectivity, auth and clock problems
+    Doctor {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+        /// Print the newest failed request captured with --debug-dump
+        /// instead of running the checks
+        #[arg(long)]
+        last_error: bool,
+    },
+
+    /// Anonymous usage reporting (opt-in, see `config set telemetry`)
+    Telemetry {
+        #[command(subcommand)]
+        action: TelemetryCommands,
+    },
+
     /// Check the status of supported Stellar networks
     Network {
         #[command(subcommand)]
@@ -518,6 +1097,27 @@ pub enum Commands {
         #[arg(long)]
         json: bool,
     },
+
+    /// Plugins providing extra commands, see `plugins list`
+    Plugins {
+        #[command(subcommand)]
+        action: PluginsCommands,
+    },
+
+    /// Any other command runs the `soroban-registry-<command>` plugin on PATH
+    #[command(external_subcommand)]
+    External(Vec<OsString>),
+}
+
+/// Sub-commands for the `plugins` group
+#[derive(Debug, Subcommand)]
+pub enum PluginsCommands {
+    /// List the plugins on PATH with their paths and versions
+    List {
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Sub-commands for the `network` group
@@ -672,17 +1272,28 @@ pub enum ConfigSubcommands {
         #[arg(long)]
         environment: String,
     },
+    /// Set a contract's config, or with `telemetry <true|false>` a CLI setting
     Set {
-        #[arg(long)]
-        contract_id: String,
-        #[arg(long)]
-        environment: String,
-        #[arg(long)]
-        config_data: String,
+        /// CLI setting to change instead of a contract's config
+        #[arg(
+            value_parser = ["telemetry"],
+            requires = "value",
+            conflicts_with = "contract_id"
+        )]
+        key: Option<String>,
+        /// New value of the CLI setting
+        #[arg(action = clap::ArgAction::Set)]
+        value: Option<bool>,
+        #[arg(long, required_unless_present = "key")]
+        contract_id: Option<String>,
+        #[arg(long, required_unless_present = "key")]
+        environment: Option<String>,
+        #[arg(long, required_unless_present = "key")]
+        config_data: Option<String>,
         #[arg(long)]
         secrets_data: Option<String>,
-        #[arg(long)]
-        created_by: String,
+        #[arg(long, required_unless_present = "key")]
+        created_by: Option<String>,
     },
     History {
         #[arg(long)]
@@ -700,6 +1311,37 @@ pub enum ConfigSubcommands {
         #[arg(long)]
         created_by: String,
     },
+    /// Make a CLI profile the default for later commands
+    Use {
+        /// Profile name, as in `[profiles.<name>]`
+        name: String,
+    },
+    /// List the CLI profiles in the config file, marking the active one
+    Profiles,
+    /// Write the config file and its profiles as a bundle to share, without secrets
+    Export {
+        /// File to write the bundle to (default: stdout)
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Apply a bundle written by `config export` to the config file
+    Import {
+        /// Bundle to import
+        file: std::path::PathBuf,
+        /// Keep current values on conflicts, asking when run in a terminal (default)
+        #[arg(long, conflicts_with = "replace")]
+        merge: bool,
+        /// Make the config match the bundle; tokens already set are kept
+        #[arg(long)]
+        replace: bool,
+    },
+}
+
+/// Sub-commands for the `telemetry` group
+#[derive(Debug, Subcommand)]
+pub enum TelemetryCommands {
+    /// Print exactly what the next usage report would send
+    Show,
 }
 
 /// Sub-commands for the `contracts` group
@@ -844,6 +1486,187 @@ pub enum IncidentCommands {
         #[arg(long)]
         state: String,
     },
+    /// Show aggregate incident metrics (MTTR, counts, RTO/RPO compliance)
+    Metrics {
+        /// Look-back window, e.g. 30d, 90d or 12w
+        #[arg(long, default_value = "90d")]
+        window: String,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show incidents per affected contract over time as a shaded grid
+    Heatmap {
+        /// Look-back window, e.g. 90d, 180d or 26w
+        #[arg(long, default_value = "180d")]
+        window: String,
+        /// Column size: day (windows up to 90d), week or month
+        #[arg(long, default_value = "week")]
+        bucket: String,
+        /// Contracts shown on their own row; the rest are rolled into "other"
+        #[arg(long, default_value_t = 10)]
+        top: u32,
+        /// Score incidents by severity instead of counting them
+        #[arg(long)]
+        weighted: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Import resolved incidents from an NDJSON file, one record per line (admin)
+    Import {
+        /// NDJSON file of incident records
+        file: String,
+        /// Validate every line and report without importing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export a Markdown report for an incident
+    Report {
+        /// Incident UUID
+        incident_id: String,
+        /// Write the report to this file instead of stdout
+        #[arg(long, short)]
+        output: Option<String>,
+    },
+    /// Show an incident and its timeline of updates
+    Show {
+        /// Incident UUID
+        incident_id: String,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List incidents recorded in the registry
+    List {
+        /// Only incidents affecting this contract (registry UUID)
+        #[arg(long)]
+        contract_id: Option<String>,
+        /// Only incidents of this type (e.g. outage, data_loss, security)
+        #[arg(long)]
+        incident_type: Option<String>,
+        /// Only incidents with this severity (critical|major|minor|informational)
+        #[arg(long)]
+        severity: Option<String>,
+        /// Only incidents at least this severe (e.g. major includes critical)
+        #[arg(long)]
+        min_severity: Option<String>,
+        /// Only incidents that have not been resolved yet
+        #[arg(long)]
+        open_only: bool,
+        /// Only incidents that started on or after this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        start_after: Option<String>,
+        /// Only incidents that started before this date (YYYY-MM-DD or RFC 3339)
+        #[arg(long)]
+        start_before: Option<String>,
+        /// Maximum number of incidents to return
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Number of incidents to skip (for pagination)
+        #[arg(long, default_value = "0")]
+        offset: usize,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Sub-commands for the `snapshot` group
+#[derive(Debug, Subcommand)]
+pub enum SnapshotCommands {
+    /// Export the registry into the blob store
+    Export {
+        /// Print the job ID and return instead of waiting for the snapshot
+        #[arg(long)]
+        no_wait: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Sub-commands for the `job` group
+#[derive(Debug, Subcommand)]
+pub enum JobCommands {
+    /// Show a job's status and, once done, its result
+    Show {
+        /// Job UUID
+        id: String,
+        /// Wait until the job is done
+        #[arg(long)]
+        wait: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// List jobs that failed or ran out of attempts (admin)
+    Failed {
+        /// Only jobs with this status (failed|dead)
+        #[arg(long)]
+        status: Option<String>,
+        /// Only jobs of this type (verify_contract|registry_snapshot)
+        #[arg(long)]
+        job_type: Option<String>,
+        /// Maximum number of jobs to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Queue a failed or dead job again (admin)
+    Retry {
+        /// Job UUID
+        id: String,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+/// Sub-commands for the `tags` group
+#[derive(Debug, Subcommand)]
+pub enum TagCommands {
+    /// Rename a tag on every contract you own
+    Rename {
+        /// Tag to rename, matched case-insensitively
+        from: String,
+        /// New tag
+        to: String,
+        /// Apply to the contracts you own (required; there is no other scope yet)
+        #[arg(long, required = true)]
+        mine: bool,
+        /// Only contracts in this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Show the contracts that would change without changing them
+        #[arg(long)]
+        dry_run: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Remove a tag from every contract you own
+    Remove {
+        /// Tag to remove, matched case-insensitively
+        tag: String,
+        /// Apply to the contracts you own (required; there is no other scope yet)
+        #[arg(long, required = true)]
+        mine: bool,
+        /// Only contracts in this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Show the contracts that would change without changing them
+        #[arg(long)]
+        dry_run: bool,
+        /// Output results as machine-readable JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 /// Sub-commands for the `patch` group
@@ -883,15 +1706,41 @@ pub enum PatchCommands {
 pub enum DepsCommands {
     /// List dependencies for a contract
     List {
-        /// Contract ID
+        /// Contract ID or namespace/slug
         contract_id: String,
     },
 }
 
 #[derive(Debug, Subcommand)]
 pub enum KeysCommands {
-    /// Generate a new Ed25519 keypair for signing
-    Generate {},
+    /// Generate a new Ed25519 keypair, stored encrypted under a passphrase
+    Generate {
+        /// Name to store the key under
+        #[arg(long, default_value = "default")]
+        name: String,
+    },
+
+    /// List stored keys and their public addresses
+    List {},
+
+    /// Store an existing secret seed (S…), read from a hidden prompt
+    Import {
+        /// Name to store the key under
+        #[arg(long, default_value = "default")]
+        name: String,
+        /// Read the seed from the first line of stdin instead of prompting
+        #[arg(long)]
+        stdin: bool,
+    },
+
+    /// Print the secret seed (S…) of a stored key
+    Export {
+        /// Name of the key
+        name: String,
+        /// Skip typing the key name to confirm
+        #[arg(long)]
+        yes: bool,
+    },
 
     /// Revoke a signature
     Revoke {
@@ -1041,9 +1890,146 @@ pub enum MigrateCommands {
     },
 }
 
+/// The gate of `--ci`, with the time limit for new functions in milliseconds
+fn regression_gate(
+    max_slowdown: f64,
+    max_new_function_ms: f64,
+) -> soroban_profiler::RegressionGate {
+    soroban_profiler::RegressionGate {
+        max_slowdown_percent: max_slowdown,
+        max_new_function_time: std::time::Duration::from_secs_f64(
+            max_new_function_ms.max(0.0) / 1000.0,
+        ),
+    }
+}
+
 #[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+async fn main() {
+    if let Err(err) = run().await {
+        eprintln!("Error: {:?}", err);
+        if let Some(hint) = http::connection_hint(&err) {
+            eprintln!("{}", hint);
+        }
+        // Only meaningful if the command reached the API.
+        if let Some(request_id) = http::last_request_id() {
+            eprintln!("{}", http::failure_hint(&request_id));
+        }
+        if let Some(path) = debug_dump::last_capture() {
+            eprintln!("failed request captured in {}", path.display());
+        }
+        // After the hints, which would otherwise name the report's request
+        telemetry::finish(false).await;
+        std::process::exit(1);
+    }
+    telemetry::finish(true).await;
+}
+
+/// The format asked for with `--output`. clap shares the global flag's
+/// value with a command's own `--output` file, so for those commands only
+/// an `--output` given before the command is a format, and `args` and
+/// `cli` are parsed again without it.
+fn output_mode(
+    args: &mut Vec<std::ffi::OsString>,
+    matches: &ArgMatches,
+    cli: &mut Cli,
+) -> Result<Option<OutputMode>> {
+    let Some(mut value) = cli.output.clone() else {
+        return Ok(None);
+    };
+    let root = Cli::command();
+    let (mut command, mut leaf) = (&root, matches);
+    while let Some((name, sub_matches)) = leaf.subcommand() {
+        let Some(subcommand) = command.find_subcommand(name) else {
+            break;
+        };
+        command = subcommand;
+        leaf = sub_matches;
+    }
+    let own_output = !std::ptr::eq(command, &root)
+        && command.get_arguments().any(|arg| arg.get_id() == "output");
+    if own_output {
+        let name = matches.subcommand_name().unwrap_or_default();
+        let mut end = args
+            .iter()
+            .position(|arg| arg.as_os_str() == name)
+            .unwrap_or(args.len());
+        let mut format = None;
+        let mut i = 1;
+        while i < end {
+            let arg = args[i].to_string_lossy().into_owned();
+            if arg == "--output" && i + 1 < end {
+                format = Some(args[i + 1].to_string_lossy().into_owned());
+                args.drain(i..i + 2);
+                end -= 2;
+            } else if let Some(given) = arg.strip_prefix("--output=") {
+                format = Some(given.to_string());
+                args.remove(i);
+                end -= 1;
+            } else {
+                i += 1;
+            }
+        }
+        let Some(format) = format else {
+            return Ok(None);
+        };
+        *cli = Cli::try_parse_from(args.iter()).unwrap_or_else(|err| err.exit());
+        value = format;
+    }
+    <OutputMode as clap::ValueEnum>::from_str(&value, false)
+        .map(Some)
+        .map_err(anyhow::Error::msg)
+}
+
+/// Parses `args` again with `--json` added when JSON output is asked for,
+/// by `--output json` or the active profile, and the command has a `--json`
+/// flag that was not given. Only `--output json` (`required`) fails for
+/// commands without one; either fails when the command's other arguments
+/// do not go with `--json`.
+fn reparse_with_json(
+    args: &[std::ffi::OsString],
+    matches: &ArgMatches,
+    required: bool,
+) -> Result<Option<Cli>> {
+    let root = Cli::command();
+    let (mut command, mut matches) = (&root, matches);
+    let mut path = Vec::new();
+    while let Some((name, sub_matches)) = matches.subcommand() {
+        let Some(subcommand) = command.find_subcommand(name) else {
+            return Ok(None);
+        };
+        command = subcommand;
+        matches = sub_matches;
+        path.push(name);
+    }
+    let has_json_flag = command.get_arguments().any(|arg| arg.get_id() == "json");
+    if !has_json_flag {
+        if required {
+            anyhow::bail!(
+                "`{}` has no JSON output; run it without --output json",
+                path.join(" ")
+            );
+        }
+        return Ok(None);
+    }
+    if matches.get_flag("json") {
+        return Ok(None);
+    }
+
+    let args = args.iter().cloned().chain(["--json".into()]);
+    Cli::try_parse_from(args).map(Some).map_err(|err| {
+        anyhow::anyhow!(
+            "`{}` has no JSON output with these arguments ({}); run it with text output",
+            path.join(" "),
+            err.kind()
+        )
+    })
+}
+
+async fn run() -> Result<()> {
+    let mut args: Vec<std::ffi::OsString> = std::env::args_os().collect();
+    let matches = plugins::with_plugin_help(Cli::command()).get_matches_from(&args);
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let output = output_mode(&mut args, &matches, &mut cli)?;
 
     // ── Initialise logger ─────────────────────────────────────────────────────
     // --verbose / -v  →  DEBUG level (shows HTTP calls, payloads, timing)
@@ -1056,10 +2042,83 @@ async fn main() -> Result<()> {
         .init();
 
     log::debug!("Verbose mode enabled");
-    log::debug!("API URL: {}", cli.api_url);
+
+    // ── Resolve profile and settings ──────────────────────────────────────────
+    let mut runtime = match config::resolve_runtime_config(
+        cli.profile.clone(),
+        cli.network.clone(),
+        cli.api_url.clone(),
+        None,
+    ) {
+        Ok(runtime) => runtime,
+        // `doctor` reports a broken config file or unknown profile itself,
+        // and `config use` (or importing a fixed config) is how to get away
+        // from an unknown one
+        Err(_)
+            if matches!(
+                cli.command,
+                Commands::Doctor { .. }
+                    | Commands::Config {
+                        action: ConfigSubcommands::Use { .. }
+                            | ConfigSubcommands::Profiles
+                            | ConfigSubcommands::Export { .. }
+                            | ConfigSubcommands::Import { .. }
+                    }
+            ) =>
+        {
+            config::RuntimeConfig::builtin(cli.profile.clone(), cli.api_url.clone())
+        }
+        Err(err) => return Err(err),
+    };
+    // --output overrides the profile, for plugins too
+    if let Some(mode) = output {
+        runtime.json = mode == OutputMode::Json;
+    }
+    if runtime.json {
+        if let Some(json_cli) = reparse_with_json(&args, &matches, output.is_some())? {
+            cli = json_cli;
+        }
+    }
+    if let Some(color) = runtime.color {
+        colored::control::set_override(color);
+    }
+    let time_format = cli.time_format.unwrap_or(runtime.time_format);
+    time_format::use_format(time_format);
+    http::use_token_env(runtime.token_env.clone());
+    let api_url = runtime.api_base.clone();
+    log::debug!("Profile: {:?}", runtime.profile);
+    log::debug!("API URL: {}", api_url);
+
+    // ── Configure HTTP transport ──────────────────────────────────────────────
+    let transport = match config::resolve_http_transport(
+        cli.proxy.clone(),
+        cli.ca_cert.clone(),
+        cli.insecure,
+        cli.debug_dump.clone(),
+    ) {
+        Ok(transport) => transport,
+        // `doctor` reports a broken config file itself
+        Err(_) if matches!(cli.command, Commands::Doctor { .. }) => config::HttpTransportConfig {
+            proxy: cli.proxy.clone(),
+            ca_cert: cli.ca_cert.clone(),
+            insecure: cli.insecure,
+            debug_dump: cli.debug_dump.clone(),
+        },
+        Err(err) => return Err(err),
+    };
+    http::configure(&transport)?;
+    telemetry::start(&Cli::command(), &matches, &api_url);
+
+    if let Commands::Doctor { json, last_error } = cli.command {
+        log::debug!("Command: doctor | last_error={}", last_error);
+        if last_error {
+            return debug_dump::print_last(json);
+        }
+        return doctor::run(&api_url, cli.profile, cli.network, json).await;
+    }
 
     // ── Resolve network ───────────────────────────────────────────────────────
-    let cfg_network = config::resolve_network(cli.network.clone())?;
+    let cfg_network = runtime.network;
     let mut net_str = cfg_network.to_string();
     if net_str == "auto" {
         net_str = "mainnet".to_string();
@@ -1073,27 +2132,79 @@ async fn main() -> Result<()> {
             verified_only,
             network: filter_networks,
             category,
+            publisher,
+            org,
+            save,
+            saved,
+            limit,
+            offset,
+            json,
+        } => {
+            let networks_vec: Vec<String> = filter_networks
+                .map(|n| n.split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default();
+            log::debug!(
+                "Command: search | query={:?} verified_only={} networks={:?} category={:?} publisher={:?} org={:?} save={:?} saved={:?}",
+                query,
+                verified_only,
+                networks_vec,
+                category,
+                publisher,
+                org,
+                save,
+                saved
+            );
+            if let Some(saved) = saved {
+                saved_search::run(&api_url, &saved, limit, offset, json).await?;
+            } else {
+                let query = query.unwrap_or_default();
+                if let Some(name) = save {
+                    let mut params = commands::search_filters(
+                        network,
+                        verified_only,
+                        &networks_vec,
+                        category.as_deref(),
+                        publisher.as_deref(),
+                        org.as_deref(),
+                    );
+                    params.push(("query", query.clone()));
+                    saved_search::save(&api_url, &name, &params).await?;
+                }
+                commands::search(
+                    &api_url,
+                    &query,
+                    network,
+                    verified_only,
+                    networks_vec,
+                    category.as_deref(),
+                    publisher.as_deref(),
+                    org.as_deref(),
+                    limit,
+                    offset,
+                    json,
+                )
+                .await?;
+            }
+        }
+        Commands::Browse {
+            category,
+            sort,
             limit,
             offset,
             json,
         } => {
-            let networks_vec: Vec<String> = filter_networks
-                .map(|n| n.split(',').map(|s| s.trim().to_string()).collect())
-                .unwrap_or_default();
             log::debug!(
-                "Command: search | query={:?} verified_only={} networks={:?} category={:?}",
-                query,
-                verified_only,
-                networks_vec,
-                category
+                "Command: browse | category={:?} sort={:?} limit={} offset={}",
+                category,
+                sort,
+                limit,
+                offset
             );
-            commands::search(
-                &cli.api_url,
-                &query,
+            browse::run(
+                &api_url,
                 network,
-                verified_only,
-                networks_vec,
                 category.as_deref(),
+                sort,
                 limit,
                 offset,
                 json,
@@ -1104,19 +2215,49 @@ async fn main() -> Result<()> {
             contract_id,
             format,
             highlight_method,
+            build_info,
+            incidents,
+            json,
         } => {
+            let format = if json { "json".to_string() } else { format };
             log::debug!(
                 "Command: info | contract_id={} format={} highlight={:?}",
                 contract_id,
                 format,
                 highlight_method
             );
+            let contract_id = resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
             commands::info(
-                &cli.api_url,
+                &api_url,
                 &contract_id,
                 &format,
                 highlight_method.as_deref(),
                 cfg_network,
+                build_info,
+                incidents,
+            )
+            .await?;
+        }
+        Commands::Download {
+            contract,
+            version,
+            format,
+            output,
+        } => {
+            log::debug!(
+                "Command: download | contract={} version={:?} format={} output={:?}",
+                contract,
+                version,
+                format,
+                output
+            );
+            resolve::download(
+                &api_url,
+                &contract,
+                version.as_deref(),
+                &format,
+                output.as_deref().map(std::path::Path::new),
+                cfg_network,
             )
             .await?;
         }
@@ -1128,6 +2269,10 @@ async fn main() -> Result<()> {
             category,
             tags,
             publisher,
+            key,
+            org,
+            slug,
+            json,
         } => {
             let tags_vec = tags
                 .map(|t| t.split(',').map(|s| s.trim().to_string()).collect())
@@ -1138,8 +2283,16 @@ async fn main() -> Result<()> {
                 name,
                 tags_vec
             );
+            let (publisher, auth_token) = match key {
+                Some(key) => {
+                    let signing_key = keys::unlock(&key)?;
+                    let token = keys::sign_in(&api_url, &signing_key).await?;
+                    (keys::account_id(&signing_key.verifying_key()), Some(token))
+                }
+                None => (publisher.unwrap_or_default(), None),
+            };
             commands::publish(
-                &cli.api_url,
+                &api_url,
                 &contract_id,
                 &name,
                 description.as_deref(),
@@ -1147,13 +2300,138 @@ async fn main() -> Result<()> {
                 category.as_deref(),
                 tags_vec,
                 &publisher,
+                org.as_deref(),
+                slug.as_deref(),
                 false,
+                auth_token.as_deref(),
+                json,
             )
             .await?;
         }
-        Commands::List { limit, json } => {
-            log::debug!("Command: list | limit={}", limit);
-            commands::list(&cli.api_url, limit, network, json).await?;
+        Commands::Transfer {
+            contract_id,
+            to,
+            key,
+        } => {
+            log::debug!("Command: transfer | contract_id={} to={}", contract_id, to);
+            let contract_id = resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
+            let auth_token = match key {
+                Some(key) => Some(keys::sign_in(&api_url, &keys::unlock(&key)?).await?),
+                None => None,
+            };
+            commands::transfer(&api_url, &contract_id, &to, auth_token.as_deref()).await?;
+        }
+        Commands::PublishVersion {
+            contract_id,
+            version,
+            wasm,
+            abi,
+            notes,
+            source_url,
+            commit,
+            allow_breaking,
+            key,
+            json,
+        } => {
+            log::debug!(
+                "Command: publish-version | contract_id={} version={} allow_breaking={}",
+                contract_id,
+                version,
+                allow_breaking
+            );
+            let contract_id = resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
+            let signing_key = key.as_deref().map(keys::unlock).transpose()?;
+            let auth_token = match &signing_key {
+                Some(signing_key) => Some(keys::sign_in(&api_url, signing_key).await?),
+                None => None,
+            };
+            let new = publish_version::NewVersion {
+                version,
+                wasm,
+                abi,
+                release_notes: notes,
+                source_url,
+                commit_hash: commit,
+                allow_breaking,
+            };
+            publish_version::publish(
+                &api_url,
+                &contract_id,
+                &new,
+                signing_key.as_ref(),
+                auth_token.as_deref(),
+                json,
+            )
+            .await?;
+        }
+        Commands::Update {
+            contract_id,
+            name,
+            description,
+            category,
+            tags,
+            slug,
+            key,
+        } => {
+            log::debug!("Command: update | contract_id={}", contract_id);
+            let change = contract_update::MetadataChange {
+                name,
+                description,
+                category,
+                tags,
+                slug,
+            };
+            if change.is_empty() {
+                anyhow::bail!(
+                    "Nothing to update; pass --name, --description, --category, --tags or --slug"
+                );
+            }
+            let contract_id = resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
+            let auth_token = match key {
+                Some(key) => Some(keys::sign_in(&api_url, &keys::unlock(&key)?).await?),
+                None => None,
+            };
+            contract_update::update(&api_url, &contract_id, &change, auth_token.as_deref()).await?;
+        }
+        Commands::List {
+            limit,
+            org,
+            mine,
+            json,
+        } => {
+            log::debug!(
+                "Command: list | limit={} org={:?} mine={}",
+                limit,
+                org,
+                mine
+            );
+            if mine {
+                // Only narrow to one network when asked; a publisher's
+                // contracts usually span several
+                commands::list_mine(&api_url, limit, cli.network.as_deref(), json).await?;
+            } else {
+                commands::list(&api_url, limit, network, org.as_deref(), json).await?;
+            }
+        }
+        Commands::Stats {
+            timeseries,
+            metrics,
+            interval,
+            window,
+            json,
+        } => {
+            log::debug!(
+                "Command: stats | timeseries={} metrics={} interval={} window={}",
+                timeseries,
+                metrics,
+                interval,
+                window
+            );
+            if timeseries {
+                stats::timeseries(&api_url, &metrics, &interval, &window, json).await?;
+            } else {
+                stats::totals(&api_url, json).await?;
+            }
         }
         Commands::Dashboard {
             refresh_rate,
@@ -1180,11 +2458,56 @@ async fn main() -> Result<()> {
             json,
         } => {
             log::debug!("Command: breaking-changes | old={} new={}", old_id, new_id);
-            commands::breaking_changes(&cli.api_url, &old_id, &new_id, json).await?;
+            commands::breaking_changes(&api_url, &old_id, &new_id, json).await?;
+        }
+        Commands::Diff { a, b, code, json } => {
+            log::debug!("Command: diff | a={} b={} code={}", a, b, code);
+            if code {
+                wasm_diff::show(&api_url, &a, &b, cfg_network, json).await?;
+            } else {
+                commands::diff(&api_url, &a, &b, json).await?;
+            }
+        }
+        Commands::Changelog {
+            contract_id,
+            since,
+            markdown,
+            json,
+        } => {
+            log::debug!(
+                "Command: changelog | contract_id={} since={:?}",
+                contract_id,
+                since
+            );
+            let contract_id = resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
+            changelog::show(&api_url, &contract_id, since.as_deref(), markdown, json).await?;
+        }
+        Commands::Changes {
+            since,
+            until,
+            limit,
+            json,
+        } => {
+            log::debug!(
+                "Command: changes | since={} until={:?} limit={}",
+                since,
+                until,
+                limit
+            );
+            // Only narrow to one network when asked
+            changes::show(
+                &api_url,
+                &since,
+                until.as_deref(),
+                cli.network.as_deref(),
+                limit,
+                json,
+            )
+            .await?;
         }
         Commands::UpgradeAnalyze { old, new, json } => {
             log::debug!("Command: upgrade analyze | old={} new={}", old, new);
-            commands::upgrade_analyze(&cli.api_url, &old, &new, json).await?;
+            commands::upgrade_analyze(&api_url, &old, &new, json).await?;
         }
         Commands::Migrate { action } => match action {
             MigrateCommands::Preview { old_id, new_id } => {
@@ -1248,7 +2571,7 @@ async fn main() -> Result<()> {
             contract_dir,
         } => {
             log::debug!("Command: export | id={} output={}", id, output);
-            commands::export(&cli.api_url, &id, &output, &contract_dir).await?;
+            commands::export(&api_url, &id, &output, &contract_dir).await?;
         }
         Commands::Import {
             archive,
@@ -1259,8 +2582,117 @@ async fn main() -> Result<()> {
                 archive,
                 output_dir
             );
-            commands::import(&cli.api_url, &archive, network, &output_dir).await?;
+            commands::import(&api_url, &archive, network, &output_dir).await?;
+        }
+        Commands::VerifySource {
+            contract_id,
+            source,
+            wasm,
+            compiler_version,
+            build_params,
+            cargo_lock,
+            source_url,
+            commit,
+            no_wait,
+            json,
+        } => {
+            log::debug!(
+                "Command: verify-source | contract_id={} source={} wasm={:?}",
+                contract_id,
+                source,
+                wasm
+            );
+            let source = match &wasm {
+                Some(path) => commands::VerifySource::Wasm(path),
+                None => commands::VerifySource::Rust(&source),
+            };
+            commands::verify_source(
+                &api_url,
+                &contract_id,
+                source,
+                &compiler_version,
+                build_params.as_deref(),
+                cargo_lock.as_deref(),
+                source_url.as_deref(),
+                commit.as_deref(),
+                no_wait,
+                json,
+            )
+            .await?;
         }
+        Commands::Snapshot { action } => match action {
+            SnapshotCommands::Export { no_wait, json } => {
+                log::debug!("Command: snapshot export | no_wait={}", no_wait);
+                commands::snapshot_export(&api_url, no_wait, json).await?;
+            }
+        },
+        Commands::Tags { action } => match action {
+            TagCommands::Rename {
+                from,
+                to,
+                mine: _,
+                category,
+                dry_run,
+                json,
+            } => {
+                log::debug!(
+                    "Command: tags rename | from={} to={} category={:?} dry_run={}",
+                    from,
+                    to,
+                    category,
+                    dry_run
+                );
+                let operation = tags::rename_operation(&from, &to, category.as_deref());
+                tags::apply_to_mine(&api_url, operation, dry_run, json).await?;
+            }
+            TagCommands::Remove {
+                tag,
+                mine: _,
+                category,
+                dry_run,
+                json,
+            } => {
+                log::debug!(
+                    "Command: tags remove | tag={} category={:?} dry_run={}",
+                    tag,
+                    category,
+                    dry_run
+                );
+                let operation = tags::remove_operation(&tag, category.as_deref());
+                tags::apply_to_mine(&api_url, operation, dry_run, json).await?;
+            }
+        },
+        Commands::Job { action } => match action {
+            JobCommands::Show { id, wait, json } => {
+                log::debug!("Command: job show | id={} wait={}", id, wait);
+                jobs::show(&api_url, &id, wait, json).await?;
+            }
+            JobCommands::Failed {
+                status,
+                job_type,
+                limit,
+                json,
+            } => {
+                log::debug!(
+                    "Command: job failed | status={:?} job_type={:?} limit={}",
+                    status,
+                    job_type,
+                    limit
+                );
+                jobs::list_failed(
+                    &api_url,
+                    status.as_deref(),
+                    job_type.as_deref(),
+                    limit,
+                    json,
+                )
+                .await?;
+            }
+            JobCommands::Retry { id, json } => {
+                log::debug!("Command: job retry | id={}", id);
+                jobs::retry(&api_url, &id, json).await?;
+            }
+        },
         Commands::Doc {
             contract_path,
             output,
@@ -1285,9 +2717,32 @@ async fn main() -> Result<()> {
             );
             commands::openapi(&contract_path, &output, &format)?;
         }
+        Commands::GenerateBindings {
+            contract_id,
+            lang,
+            output,
+            version,
+        } => {
+            log::debug!(
+                "Command: generate-bindings | contract_id={} lang={} output={} version={:?}",
+                contract_id,
+                lang,
+                output,
+                version
+            );
+            commands::generate_bindings(
+                &api_url,
+                &contract_id,
+                version.as_deref(),
+                &lang,
+                &output,
+                cfg_network,
+            )
+            .await?;
+        }
         Commands::Wizard {} => {
             log::debug!("Command: wizard");
-            wizard::run(&cli.api_url).await?;
+            wizard::run(&api_url).await?;
         }
         Commands::History { search, limit } => {
             log::debug!("Command: history | search={:?} limit={}", search, limit);
@@ -1313,6 +2768,85 @@ async fn main() -> Result<()> {
                 );
                 commands::incident_update(&incident_id, &state)?;
             }
+            IncidentCommands::Metrics { window, json } => {
+                log::debug!("Command: incident metrics | window={}", window);
+                incident::metrics(&api_url, &window, json).await?;
+            }
+            IncidentCommands::Heatmap {
+                window,
+                bucket,
+                top,
+                weighted,
+                json,
+            } => {
+                log::debug!(
+                    "Command: incident heatmap | window={} bucket={} top={} weighted={}",
+                    window,
+                    bucket,
+                    top,
+                    weighted
+                );
+                incident::heatmap(&api_url, &window, &bucket, top, weighted, json).await?;
+            }
+            IncidentCommands::Import {
+                file,
+                dry_run,
+                json,
+            } => {
+                log::debug!(
+                    "Command: incident import | file={} dry_run={}",
+                    file,
+                    dry_run
+                );
+                incident::import(&api_url, &file, dry_run, json).await?;
+            }
+            IncidentCommands::Report {
+                incident_id,
+                output,
+            } => {
+                log::debug!(
+                    "Command: incident report | incident_id={} output={:?}",
+                    incident_id,
+                    output
+                );
+                incident::report(&api_url, &incident_id, output.as_deref()).await?;
+            }
+            IncidentCommands::Show { incident_id, json } => {
+                log::debug!("Command: incident show | incident_id={}", incident_id);
+                incident::show(&api_url, &incident_id, json).await?;
+            }
+            IncidentCommands::List {
+                contract_id,
+                incident_type,
+                severity,
+                min_severity,
+                open_only,
+                start_after,
+                start_before,
+                limit,
+                offset,
+                json,
+            } => {
+                log::debug!(
+                    "Command: incident list | contract_id={:?} type={:?} severity={:?} open_only={}",
+                    contract_id,
+                    incident_type,
+                    severity,
+                    open_only
+                );
+                let filters = incident::IncidentListFilters {
+                    contract_id,
+                    incident_type,
+                    severity,
+                    min_severity,
+                    open_only,
+                    start_after,
+                    start_before,
+                    limit,
+                    offset,
+                };
+                incident::list(&api_url, &filters, json).await?;
+            }
         },
         Commands::Patch { action } => match action {
             PatchCommands::Create {
@@ -1327,11 +2861,11 @@ async fn main() -> Result<()> {
                     version,
                     rollout
                 );
-                commands::patch_create(&cli.api_url, &version, &hash, sev, rollout).await?;
+                commands::patch_create(&api_url, &version, &hash, sev, rollout).await?;
             }
             PatchCommands::Notify { patch_id } => {
                 log::debug!("Command: patch notify | patch_id={}", patch_id);
-                commands::patch_notify(&cli.api_url, &patch_id).await?;
+                commands::patch_notify(&api_url, &patch_id).await?;
             }
             PatchCommands::Apply {
                 contract_id,
@@ -1342,11 +2876,13 @@ async fn main() -> Result<()> {
                     contract_id,
                     patch_id
                 );
-                commands::patch_apply(&cli.api_url, &contract_id, &patch_id).await?;
+                commands::patch_apply(&api_url, &contract_id, &patch_id).await?;
             }
             PatchCommands::Deps { command } => match command {
                 DepsCommands::List { contract_id } => {
-                    commands::deps_list(&cli.api_url, &contract_id).await?;
+                    let contract_id =
+                        resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
+                    commands::deps_list(&api_url, &contract_id).await?;
                 }
             },
         },
@@ -1368,7 +2904,7 @@ async fn main() -> Result<()> {
                     signer_vec
                 );
                 multisig::create_policy(
-                    &cli.api_url,
+                    &api_url,
                     &name,
                     threshold,
                     signer_vec,
@@ -1392,7 +2928,7 @@ async fn main() -> Result<()> {
                     policy_id
                 );
                 multisig::create_proposal(
-                    &cli.api_url,
+                    &api_url,
                     &contract_name,
                     &contract_id,
                     &wasm_hash,
@@ -1409,21 +2945,16 @@ async fn main() -> Result<()> {
                 signature_data,
             } => {
                 log::debug!("Command: multisig sign | proposal_id={}", proposal_id);
-                multisig::sign_proposal(
-                    &cli.api_url,
-                    &proposal_id,
-                    &signer,
-                    signature_data.as_deref(),
-                )
-                .await?;
+                multisig::sign_proposal(&api_url, &proposal_id, &signer, signature_data.as_deref())
+                    .await?;
             }
             MultisigCommands::Execute { proposal_id } => {
                 log::debug!("Command: multisig execute | proposal_id={}", proposal_id);
-                multisig::execute_proposal(&cli.api_url, &proposal_id).await?;
+                multisig::execute_proposal(&api_url, &proposal_id).await?;
             }
             MultisigCommands::Info { proposal_id } => {
                 log::debug!("Command: multisig info | proposal_id={}", proposal_id);
-                multisig::proposal_info(&cli.api_url, &proposal_id).await?;
+                multisig::proposal_info(&api_url, &proposal_id).await?;
             }
             MultisigCommands::ListProposals { status, limit } => {
                 log::debug!(
@@ -1431,7 +2962,7 @@ async fn main() -> Result<()> {
                     status,
                     limit
                 );
-                multisig::list_proposals(&cli.api_url, status.as_deref(), limit).await?;
+                multisig::list_proposals(&api_url, status.as_deref(), limit).await?;
             }
         },
         Commands::Fuzz {
@@ -1457,28 +2988,157 @@ async fn main() -> Result<()> {
         Commands::Profile {
             contract_path,
             method,
+            remote,
+            rpc_url,
+            args,
+            fetch,
+            mode,
+            sample_rate,
             output,
+            output_format,
             flamegraph,
+            folded,
             compare,
+            estimate_fees,
+            fee_config,
+            flamegraph_diff,
+            ci,
+            max_slowdown,
+            max_new_function_ms,
             recommendations,
+            publish_to,
+            key,
+            json,
         } => {
             log::debug!(
-                "Command: profile | contract_path={} method={:?} output={:?} flamegraph={:?} compare={:?} recommendations={}",
+                "Command: profile | contract_path={:?} method={:?} remote={:?} rpc_url={:?} args={:?} fetch={:?} mode={} sample_rate={} output={:?} output_format={} flamegraph={:?} folded={:?} compare={:?} estimate_fees={} fee_config={:?} flamegraph_diff={:?} ci={} recommendations={} publish_to={:?}",
                 contract_path,
                 method,
+                remote,
+                rpc_url,
+                args,
+                fetch,
+                mode,
+                sample_rate,
                 output,
+                output_format,
                 flamegraph,
+                folded,
                 compare,
-                recommendations
-            );
-            commands::profile(
-                &contract_path,
-                method.as_deref(),
-                output.as_deref(),
-                flamegraph.as_deref(),
-                compare.as_deref(),
+                estimate_fees,
+                fee_config,
+                flamegraph_diff,
+                ci,
                 recommendations,
-            )?;
+                publish_to
+            );
+            let gate = ci.then(|| regression_gate(max_slowdown, max_new_function_ms));
+            let sample_rate_hz = (mode == "sampling").then_some(sample_rate);
+            let fees = match fee_config {
+                Some(path) => Some(soroban_profiler::FeeConfig::load(path)?),
+                None => estimate_fees.then(soroban_profiler::FeeConfig::default),
+            };
+            // Sign in before profiling so a bad key fails fast
+            let auth_token = match (&publish_to, key) {
+                (Some(_), Some(key)) => Some(keys::sign_in(&api_url, &keys::unlock(&key)?).await?),
+                _ => None,
+            };
+            let run = if let Some(contract) = fetch {
+                commands::fetch_profile(&api_url, &contract, cfg_network, output.as_deref(), json)
+                    .await?;
+                None
+            } else if let Some(contract) = remote {
+                let rpc_url = match rpc_url {
+                    Some(url) => url,
+                    None => network::rpc_endpoint(&net_str)
+                        .ok_or_else(|| {
+                            anyhow::anyhow!("No public RPC for {}; pass --rpc-url", net_str)
+                        })?
+                        .to_string(),
+                };
+                Some(
+                    commands::profile_remote(
+                        &api_url,
+                        &contract,
+                        cfg_network,
+                        &rpc_url,
+                        method.as_deref().unwrap_or_default(),
+                        args.as_deref(),
+                        output.as_deref(),
+                        &output_format,
+                        flamegraph.as_deref(),
+                        folded.as_deref(),
+                        compare.as_deref(),
+                        fees.as_ref(),
+                        flamegraph_diff.as_deref(),
+                        gate.as_ref(),
+                        recommendations,
+                        json,
+                    )
+                    .await?,
+                )
+            } else {
+                let contract_path = contract_path.unwrap_or_default();
+                let fetched =
+                    resolve::source_for_profiling(&api_url, &contract_path, cfg_network).await?;
+                let contract_path = match &fetched {
+                    Some(source) => source.path.display().to_string(),
+                    None => contract_path,
+                };
+                Some(commands::profile(
+                    &contract_path,
+                    method.as_deref(),
+                    sample_rate_hz,
+                    output.as_deref(),
+                    &output_format,
+                    flamegraph.as_deref(),
+                    folded.as_deref(),
+                    compare.as_deref(),
+                    fees.as_ref(),
+                    flamegraph_diff.as_deref(),
+                    gate.as_ref(),
+                    recommendations,
+                    json,
+                )?)
+            };
+            if let Some(mut run) = run {
+                if let Some(contract) = publish_to {
+                    let published = commands::publish_profile(
+                        &api_url,
+                        &contract,
+                        cfg_network,
+                        &run.profile,
+                        auth_token.as_deref(),
+                        json,
+                    )
+                    .await?;
+                    if let Some(report) = &mut run.report {
+                        report["published"] = published;
+                    }
+                }
+                if let Some(report) = run.report {
+                    println!("{}", serde_json::to_string_pretty(&report)?);
+                }
+            }
+        }
+        Commands::DiffProfile {
+            baseline,
+            current,
+            ci,
+            max_slowdown,
+            max_new_function_ms,
+            force,
+            json,
+        } => {
+            log::debug!(
+                "Command: diff-profile | baseline={} current={} ci={} force={}",
+                baseline,
+                current,
+                ci,
+                force
+            );
+            let gate = ci.then(|| regression_gate(max_slowdown, max_new_function_ms));
+            commands::diff_profile(&baseline, &current, gate.as_ref(), force, json)?;
         }
         Commands::Test {
             test_file,
@@ -1522,22 +3182,37 @@ async fn main() -> Result<()> {
                 contract_id,
                 environment,
             } => {
-                commands::config_get(&cli.api_url, &contract_id, &environment).await?;
+                commands::config_get(&api_url, &contract_id, &environment).await?;
+            }
+            ConfigSubcommands::Set {
+                key: Some(_),
+                value,
+                ..
+            } => {
+                // clap requires a value with the key
+                let enabled = value.unwrap_or(false);
+                log::debug!("Command: config set telemetry | enabled={}", enabled);
+                config::set_telemetry(enabled)?;
+                println!("Telemetry {}", if enabled { "enabled" } else { "disabled" });
             }
             ConfigSubcommands::Set {
+                key: None,
                 contract_id,
                 environment,
                 config_data,
                 secrets_data,
                 created_by,
+                ..
             } => {
+                // clap requires these without a key
+                let required = |arg: Option<String>| arg.unwrap_or_default();
                 commands::config_set(
-                    &cli.api_url,
-                    &contract_id,
-                    &environment,
-                    &config_data,
+                    &api_url,
+                    &required(contract_id),
+                    &required(environment),
+                    &required(config_data),
                     secrets_data.as_deref(),
-                    &created_by,
+                    &required(created_by),
                 )
                 .await?;
             }
@@ -1545,7 +3220,7 @@ async fn main() -> Result<()> {
                 contract_id,
                 environment,
             } => {
-                commands::config_history(&cli.api_url, &contract_id, &environment).await?;
+                commands::config_history(&api_url, &contract_id, &environment).await?;
             }
             ConfigSubcommands::Rollback {
                 contract_id,
@@ -1554,7 +3229,7 @@ async fn main() -> Result<()> {
                 created_by,
             } => {
                 commands::config_rollback(
-                    &cli.api_url,
+                    &api_url,
                     &contract_id,
                     &environment,
                     version,
@@ -1562,6 +3237,36 @@ async fn main() -> Result<()> {
                 )
                 .await?;
             }
+            ConfigSubcommands::Use { name } => {
+                log::debug!("Command: config use | name={}", name);
+                config::use_profile(&name)?;
+                println!("Using profile {}", name);
+            }
+            ConfigSubcommands::Profiles => {
+                log::debug!("Command: config profiles");
+                config::list_profiles(runtime.profile.as_deref())?;
+            }
+            ConfigSubcommands::Export { output } => {
+                log::debug!("Command: config export | output={:?}", output);
+                config_bundle::export(output.as_deref().map(std::path::Path::new))?;
+            }
+            ConfigSubcommands::Import {
+                file,
+                merge: _,
+                replace,
+            } => {
+                log::debug!(
+                    "Command: config import | file={:?} replace={}",
+                    file,
+                    replace
+                );
+                let mode = if replace {
+                    config_bundle::ImportMode::Replace
+                } else {
+                    config_bundle::ImportMode::Merge
+                };
+                config_bundle::import(&file, mode)?;
+            }
         },
         Commands::VerifyFormal {
             contract_path,
@@ -1569,15 +3274,14 @@ async fn main() -> Result<()> {
             output,
             post,
         } => {
-            formal_verification::run(&cli.api_url, &contract_path, &properties, &output, post)
-                .await?;
+            formal_verification::run(&api_url, &contract_path, &properties, &output, post).await?;
         }
         Commands::ScanDeps {
             contract_id,
             dependencies,
             fail_on_high,
         } => {
-            commands::scan_deps(&cli.api_url, &contract_id, &dependencies, fail_on_high).await?;
+            commands::scan_deps(&api_url, &contract_id, &dependencies, fail_on_high).await?;
         }
         Commands::Coverage {
             contract_path,
@@ -1590,6 +3294,7 @@ async fn main() -> Result<()> {
         Commands::Sign {
             package,
             private_key,
+            key,
             contract_id,
             version,
             expires_at,
@@ -1600,10 +3305,14 @@ async fn main() -> Result<()> {
                 contract_id,
                 version
             );
+            let signing_key = match key {
+                Some(key) => keys::unlock(&key)?,
+                None => package_signing::decode_private_key(&private_key.unwrap_or_default())?,
+            };
             package_signing::sign_package(
-                &cli.api_url,
+                &api_url,
                 &package,
-                &private_key,
+                &signing_key,
                 &contract_id,
                 &version,
                 expires_at.as_deref(),
@@ -1622,7 +3331,7 @@ async fn main() -> Result<()> {
                 contract_id
             );
             package_signing::verify_package(
-                &cli.api_url,
+                &api_url,
                 &package,
                 &contract_id,
                 version.as_deref(),
@@ -1636,6 +3345,7 @@ async fn main() -> Result<()> {
             version,
             signature,
             public_key,
+            key,
         } => {
             log::debug!(
                 "Command: verify-contract | wasm_path={} contract_id={} version={}",
@@ -1643,6 +3353,10 @@ async fn main() -> Result<()> {
                 contract_id,
                 version
             );
+            let public_key = match key {
+                Some(key) => keys::public_key_base64(&key)?,
+                None => public_key.unwrap_or_default(),
+            };
             package_signing::verify_contract_local(
                 &wasm_path,
                 &contract_id,
@@ -1652,9 +3366,21 @@ async fn main() -> Result<()> {
             )?;
         }
         Commands::Keys { action } => match action {
-            KeysCommands::Generate {} => {
-                log::debug!("Command: keys generate");
-                package_signing::generate_keypair()?;
+            KeysCommands::Generate { name } => {
+                log::debug!("Command: keys generate | name={}", name);
+                keys::generate(&name)?;
+            }
+            KeysCommands::List {} => {
+                log::debug!("Command: keys list");
+                keys::list()?;
+            }
+            KeysCommands::Import { name, stdin } => {
+                log::debug!("Command: keys import | name={}", name);
+                keys::import(&name, stdin)?;
+            }
+            KeysCommands::Export { name, yes } => {
+                log::debug!("Command: keys export | name={}", name);
+                keys::export(&name, yes)?;
             }
             KeysCommands::Revoke {
                 signature_id,
@@ -1662,17 +3388,12 @@ async fn main() -> Result<()> {
                 reason,
             } => {
                 log::debug!("Command: keys revoke | signature_id={}", signature_id);
-                package_signing::revoke_signature(
-                    &cli.api_url,
-                    &signature_id,
-                    &revoked_by,
-                    &reason,
-                )
-                .await?;
+                package_signing::revoke_signature(&api_url, &signature_id, &revoked_by, &reason)
+                    .await?;
             }
             KeysCommands::Custody { contract_id } => {
                 log::debug!("Command: keys custody | contract_id={}", contract_id);
-                package_signing::get_chain_of_custody(&cli.api_url, &contract_id).await?;
+                package_signing::get_chain_of_custody(&api_url, &contract_id).await?;
             }
             KeysCommands::Log {
                 contract_id,
@@ -1681,7 +3402,7 @@ async fn main() -> Result<()> {
             } => {
                 log::debug!("Command: keys log");
                 package_signing::get_transparency_log(
-                    &cli.api_url,
+                    &api_url,
                     contract_id.as_deref(),
                     entry_type.as_deref(),
                     limit,
@@ -1699,7 +3420,57 @@ async fn main() -> Result<()> {
                 contracts,
                 initiated_by
             );
-            batch_verify::run_batch_verify(&cli.api_url, &contracts, &initiated_by, json).await?;
+            batch_verify::run_batch_verify(&api_url, &contracts, &initiated_by, json).await?;
+        }
+        Commands::Subscribe {
+            contract_id,
+            events,
+            webhook,
+            email,
+            json,
+        } => {
+            let events = subscriptions::parse_events(&events)?;
+            log::debug!(
+                "Command: subscribe | contract_id={} events={:?}",
+                contract_id,
+                events
+            );
+            let contract_id = resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
+            subscriptions::subscribe(
+                &api_url,
+                &contract_id,
+                &events,
+                webhook.as_deref(),
+                email.as_deref(),
+                json,
+            )
+            .await?;
+        }
+        Commands::Subscriptions { contract, json } => {
+            log::debug!("Command: subscriptions | contract={:?}", contract);
+            let contract = match contract {
+                Some(contract) => {
+                    Some(resolve::contract_id(&api_url, &contract, cfg_network).await?)
+                }
+                None => None,
+            };
+            subscriptions::list(&api_url, contract.as_deref(), json).await?;
+        }
+        Commands::Unsubscribe { contract_id, id } => {
+            log::debug!(
+                "Command: unsubscribe | contract_id={:?} id={:?}",
+                contract_id,
+                id
+            );
+            match (contract_id, id) {
+                (_, Some(id)) => subscriptions::remove(&api_url, &id).await?,
+                (Some(contract_id), None) => {
+                    let contract_id =
+                        resolve::contract_id(&api_url, &contract_id, cfg_network).await?;
+                    subscriptions::unsubscribe(&api_url, &contract_id).await?;
+                }
+                (None, None) => unreachable!("clap requires a contract or --id"),
+            }
         }
         Commands::Webhook { action } => match action {
             WebhookCommands::Create {
@@ -1714,27 +3485,27 @@ async fn main() -> Result<()> {
                     url,
                     event_list
                 );
-                webhook::create_webhook(&cli.api_url, &url, event_list, secret.as_deref()).await?;
+                webhook::create_webhook(&api_url, &url, event_list, secret.as_deref()).await?;
             }
             WebhookCommands::List {} => {
                 log::debug!("Command: webhook list");
-                webhook::list_webhooks(&cli.api_url).await?;
+                webhook::list_webhooks(&api_url).await?;
             }
             WebhookCommands::Delete { webhook_id } => {
                 log::debug!("Command: webhook delete | id={}", webhook_id);
-                webhook::delete_webhook(&cli.api_url, &webhook_id).await?;
+                webhook::delete_webhook(&api_url, &webhook_id).await?;
             }
             WebhookCommands::Test { webhook_id } => {
                 log::debug!("Command: webhook test | id={}", webhook_id);
-                webhook::test_webhook(&cli.api_url, &webhook_id).await?;
+                webhook::test_webhook(&api_url, &webhook_id).await?;
             }
             WebhookCommands::Logs { webhook_id, limit } => {
                 log::debug!("Command: webhook logs | id={} limit={}", webhook_id, limit);
-                webhook::webhook_logs(&cli.api_url, &webhook_id, limit).await?;
+                webhook::webhook_logs(&api_url, &webhook_id, limit).await?;
             }
             WebhookCommands::Retry { delivery_id } => {
                 log::debug!("Command: webhook retry | delivery_id={}", delivery_id);
-                webhook::retry_delivery(&cli.api_url, &delivery_id).await?;
+                webhook::retry_delivery(&api_url, &delivery_id).await?;
             }
             WebhookCommands::VerifySig {
                 secret,
@@ -1758,7 +3529,7 @@ async fn main() -> Result<()> {
                     network,
                     json
                 );
-                contract_verify::run(&cli.api_url, &address, &network, json).await?;
+                contract_verify::run(&api_url, &address, &network, json).await?;
             }
         },
         // ── Release Notes commands ───────────────────────────────────────────
@@ -1777,7 +3548,7 @@ async fn main() -> Result<()> {
                     version
                 );
                 release_notes::generate(
-                    &cli.api_url,
+                    &api_url,
                     &contract_id,
                     &version,
                     previous_version.as_deref(),
@@ -1797,7 +3568,7 @@ async fn main() -> Result<()> {
                     contract_id,
                     version
                 );
-                release_notes::view(&cli.api_url, &contract_id, &version, json).await?;
+                release_notes::view(&api_url, &contract_id, &version, json).await?;
             }
             ReleaseNotesCommands::Edit {
                 contract_id,
@@ -1812,7 +3583,7 @@ async fn main() -> Result<()> {
                     version
                 );
                 release_notes::edit(
-                    &cli.api_url,
+                    &api_url,
                     &contract_id,
                     &version,
                     file.as_deref(),
@@ -1832,18 +3603,12 @@ async fn main() -> Result<()> {
                     contract_id,
                     version
                 );
-                release_notes::publish(
-                    &cli.api_url,
-                    &contract_id,
-                    &version,
-                    skip_version_update,
-                    json,
-                )
-                .await?;
+                release_notes::publish(&api_url, &contract_id, &version, skip_version_update, json)
+                    .await?;
             }
             ReleaseNotesCommands::List { contract_id, json } => {
                 log::debug!("Command: release-notes list | contract_id={}", contract_id);
-                release_notes::list(&cli.api_url, &contract_id, json).await?;
+                release_notes::list(&api_url, &contract_id, json).await?;
             }
         },
 
@@ -1861,7 +3626,7 @@ async fn main() -> Result<()> {
                     network
                 );
                 cicd::run_pipeline(
-                    &cli.api_url,
+                    &api_url,
                     &contract_path,
                     &network,
                     skip_scan,
@@ -1877,6 +3642,19 @@ async fn main() -> Result<()> {
         },
 
         // ── Network commands (issue #523) ────────────────────────────────────
+        Commands::Status { json } => {
+            log::debug!("Command: status");
+            incident::status(&api_url, json).await?;
+        }
+
+        Commands::Telemetry { action } => match action {
+            TelemetryCommands::Show => {
+                log::debug!("Command: telemetry show");
+                telemetry::show()?;
+            }
+        },
+        Commands::Doctor { .. } => unreachable!("doctor runs before the network is resolved"),
+
         Commands::Network { action } => match action {
             NetworkCommands::Status { json } => {
                 log::debug!("Command: network status");
@@ -1898,7 +3676,7 @@ async fn main() -> Result<()> {
                 publisher
             );
             batch_register::run_batch_register(
-                &cli.api_url,
+                &api_url,
                 &manifest,
                 publisher.as_deref(),
                 dry_run,
@@ -1906,6 +3684,21 @@ async fn main() -> Result<()> {
             )
             .await?;
         }
+
+        Commands::Plugins { action } => match action {
+            PluginsCommands::List { json } => {
+                log::debug!("Command: plugins list");
+                plugins::list(&Cli::command(), json).await?;
+            }
+        },
+        Commands::External(args) => {
+            let env = plugins::plugin_env(&runtime, &net_str, time_format);
+            let code = plugins::run(&args, &env).await?;
+            if code != 0 {
+                telemetry::finish(false).await;
+                std::process::exit(code);
+            }
+        }
     }
 
     Ok(())
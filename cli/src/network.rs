@@ -216,8 +216,16 @@ async fn fetch_close_time(def: &NetworkDef, client: &reqwest::Client) -> Option<
     None
 }
 
+/// Public Soroban RPC endpoint of `network`
+pub fn rpc_endpoint(network: &str) -> Option<&'static str> {
+    NETWORKS
+        .iter()
+        .find(|def| def.name == network)
+        .map(|def| def.rpc_endpoint)
+}
+
 pub async fn status(json: bool) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::transport_builder().build()?;
 
     // Check all three networks concurrently
     let (mainnet, testnet, futurenet) = tokio::join!(
@@ -230,7 +238,7 @@ pub async fn status(json: bool) -> Result<()> {
         && matches!(testnet.status, NetworkStatus::Down)
         && matches!(futurenet.status, NetworkStatus::Down);
 
-    let mut networks: Vec<NetworkInfo> = if all_down {
+    let networks: Vec<NetworkInfo> = if all_down {
         // Serve from cache when offline
         if let Some(cache) = load_cache() {
             eprintln!(
@@ -3,7 +3,6 @@ use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
 use chrono::Utc;
 use colored::Colorize;
 use ed25519_dalek::{Signer, SigningKey, Verifier};
-use rand::rngs::OsRng;
 use serde_json::json;
 use sha2::{Digest, Sha256};
 use std::fs;
@@ -13,7 +12,7 @@ use std::path::Path;
 pub async fn sign_package(
     api_url: &str,
     package_path: &str,
-    private_key: &str,
+    signing_key: &SigningKey,
     contract_id: &str,
     version: &str,
     expires_at: Option<&str>,
@@ -26,7 +25,6 @@ pub async fn sign_package(
     println!("  {}: {}", "Package".bold(), package_path.bright_black());
     println!("  {}: {}", "Hash".bold(), package_hash.bright_black());
 
-    let signing_key = decode_private_key(private_key)?;
     let verifying_key = signing_key.verifying_key();
     let public_key_bytes = verifying_key.to_bytes();
     let public_key_b64 = BASE64.encode(public_key_bytes);
@@ -45,7 +43,7 @@ pub async fn sign_package(
     println!("  {}: {}", "Contract ID".bold(), contract_id.bright_black());
     println!("  {}: {}", "Version".bold(), version);
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/signatures", api_url);
 
     let expires_dt = expires_at
@@ -115,7 +113,7 @@ pub async fn verify_package(
     println!("  {}: {}", "Package".bold(), package_path.bright_black());
     println!("  {}: {}", "Hash".bold(), package_hash.bright_black());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
 
     if let Some(sig_b64) = signature_arg {
         verify_with_signature(
@@ -134,7 +132,7 @@ pub async fn verify_package(
 
 async fn verify_with_signature(
     api_url: &str,
-    client: &reqwest::Client,
+    client: &crate::http::Client,
     contract_id: &str,
     version: Option<&str>,
     package_hash: &str,
@@ -160,7 +158,9 @@ async fn verify_with_signature(
     let result: serde_json::Value = response.json().await?;
 
     if !status.is_success() {
-        let msg = result["message"].as_str().unwrap_or("Unknown error");
+        let msg = result["error"]["message"]
+            .as_str()
+            .unwrap_or("Unknown error");
         bail!("Verification failed: {}", msg);
     }
 
@@ -190,7 +190,7 @@ async fn verify_with_signature(
 
 async fn verify_from_registry(
     api_url: &str,
-    client: &reqwest::Client,
+    client: &crate::http::Client,
     contract_id: &str,
     version: Option<&str>,
     package_hash: &str,
@@ -293,7 +293,7 @@ pub async fn revoke_signature(
 ) -> Result<()> {
     println!("\n{}", "Revoking signature...".bold().cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/signatures/{}/revoke", api_url, signature_id);
 
     let payload = json!({
@@ -330,7 +330,7 @@ pub async fn get_chain_of_custody(api_url: &str, contract_id: &str) -> Result<()
     println!("\n{}", "Chain of Custody".bold().cyan());
     println!("{}", "=".repeat(70).cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let url = format!("{}/api/signatures/custody/{}", api_url, contract_id);
 
     let response = client
@@ -393,7 +393,7 @@ pub async fn get_transparency_log(
     println!("\n{}", "Transparency Log".bold().cyan());
     println!("{}", "=".repeat(70).cyan());
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let mut url = format!("{}/api/signatures/transparency?limit={}", api_url, limit);
 
     if let Some(cid) = contract_id {
@@ -460,43 +460,6 @@ pub async fn get_transparency_log(
     Ok(())
 }
 
-pub fn generate_keypair() -> Result<()> {
-    println!("\n{}", "Generating Ed25519 keypair...".bold().cyan());
-
-    let mut csprng = OsRng;
-    let signing_key: SigningKey = SigningKey::generate(&mut csprng);
-    let verifying_key = signing_key.verifying_key();
-
-    let secret_bytes = signing_key.to_bytes();
-    let public_bytes = verifying_key.to_bytes();
-
-    let secret_b64 = BASE64.encode(secret_bytes);
-    let public_b64 = BASE64.encode(public_bytes);
-    let stellar_address = derive_stellar_address(&public_bytes);
-
-    println!("{}", "\n✓ Keypair generated successfully!".green().bold());
-    println!();
-    println!("  {} (keep this secret!):", "Private Key".bold().red());
-    println!("  {}", secret_b64.bright_red());
-    println!();
-    println!("  {}:", "Public Key".bold().green());
-    println!("  {}", public_b64.bright_green());
-    println!();
-    println!("  {}:", "Stellar Address".bold().cyan());
-    println!("  {}", stellar_address.bright_cyan());
-    println!();
-    println!(
-        "  {} Use the private key to sign packages:",
-        "→".bright_black()
-    );
-    println!(
-        "  soroban-registry sign package.tar.gz --private-key {} --contract-id <ID> --version <VERSION>\n",
-        secret_b64.bright_red()
-    );
-
-    Ok(())
-}
-
 fn read_package_file(path: &str) -> Result<Vec<u8>> {
     let path = Path::new(path);
     if !path.exists() {
@@ -517,7 +480,7 @@ fn compute_hash(data: &[u8]) -> String {
     format!("{:x}", hasher.finalize())
 }
 
-fn decode_private_key(key: &str) -> Result<SigningKey> {
+pub fn decode_private_key(key: &str) -> Result<SigningKey> {
     let bytes = BASE64
         .decode(key)
         .context("Invalid private key format (expected base64)")?;
@@ -80,7 +80,7 @@ impl PatchManager {
         severity: Severity,
         rollout: u8,
     ) -> Result<SecurityPatch> {
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
         let payload = serde_json::json!({
             "target_version": version,
             "severity": severity,
@@ -105,7 +105,7 @@ impl PatchManager {
         api_url: &str,
         patch_id: &str,
     ) -> Result<(SecurityPatch, Vec<serde_json::Value>)> {
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
 
         let patch_resp = client
             .get(format!("{}/api/patches/{}", api_url, patch_id))
@@ -133,7 +133,7 @@ impl PatchManager {
     }
 
     pub async fn apply(api_url: &str, contract_id: &str, patch_id: &str) -> Result<PatchAudit> {
-        let client = reqwest::Client::new();
+        let client = crate::http::client();
 
         let patch_resp = client
             .get(format!("{}/api/patches/{}", api_url, patch_id))
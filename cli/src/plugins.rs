@@ -0,0 +1,399 @@
+//! External subcommands, git style.
+//!
+//! `soroban-registry foo <args>` with no built-in `foo` runs the first
+//! executable named `soroban-registry-foo` on `PATH` with `<args>`, and exits
+//! with its status. Built-in commands (and their aliases) always win, so a
+//! plugin can't take over `publish` or `login`; such plugins are listed as
+//! shadowed and never run.
+//!
+//! The plugin gets the settings this run resolved, so it talks to the same
+//! registry the same way:
+//!
+//! | Variable                       | Value                                          |
+//! |--------------------------------|------------------------------------------------|
+//! | `SOROBAN_REGISTRY_API_URL`     | Registry API URL                               |
+//! | `SOROBAN_REGISTRY_PROFILE`     | Active config profile (unset without one)      |
+//! | `SOROBAN_REGISTRY_NETWORK`     | `mainnet`, `testnet` or `futurenet`            |
+//! | `SOROBAN_REGISTRY_TOKEN_ENV`   | Name of the variable holding the API token     |
+//! | `SOROBAN_REGISTRY_OUTPUT`      | `json` or `text`                               |
+//! | `SOROBAN_REGISTRY_COLOR`       | `always`, `never` or `auto`                    |
+//! | `SOROBAN_REGISTRY_TIME_FORMAT` | `relative`, `local`, `utc` or `iso`            |
+//!
+//! The token itself is never passed, only where to read it from.
+//!
+//! `plugins list` runs each plugin with `--plugin-info`, which should print
+//! `{"version": "1.2.0", "description": "..."}` and exit 0; plugins that
+//! don't answer within a few seconds are listed without a version.
+
+use std::collections::BTreeSet;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use clap::Command;
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::config::RuntimeConfig;
+use crate::time_format::TimeFormat;
+
+/// Executables named `<PREFIX><name>` provide the `<name>` subcommand
+pub const PLUGIN_PREFIX: &str = "soroban-registry-";
+/// Flag a plugin answers with its [`PluginInfo`]
+pub const PLUGIN_INFO_FLAG: &str = "--plugin-info";
+const PLUGIN_INFO_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    /// Subcommand it provides
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// What a plugin reports about itself via [`PLUGIN_INFO_FLAG`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PluginInfo {
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PluginListing {
+    name: String,
+    path: PathBuf,
+    version: Option<String>,
+    description: Option<String>,
+    /// A built-in command of the same name runs instead
+    shadowed: bool,
+}
+
+/// Plugins on `PATH`, by name; where two directories have the same plugin
+/// the first one wins, as it would when run
+pub fn discover() -> Vec<Plugin> {
+    std::env::var_os("PATH")
+        .map(|path| discover_in(&path))
+        .unwrap_or_default()
+}
+
+fn discover_in(path_var: &OsStr) -> Vec<Plugin> {
+    let mut seen = BTreeSet::new();
+    let mut plugins = Vec::new();
+    for dir in std::env::split_paths(path_var) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut found: Vec<Plugin> = entries
+            .flatten()
+            .filter_map(|entry| {
+                let name = plugin_name(&entry.file_name())?;
+                let path = entry.path();
+                is_executable(&path).then_some(Plugin { name, path })
+            })
+            .collect();
+        found.sort_by(|a, b| a.name.cmp(&b.name));
+        for plugin in found {
+            if seen.insert(plugin.name.clone()) {
+                plugins.push(plugin);
+            }
+        }
+    }
+    plugins.sort_by(|a, b| a.name.cmp(&b.name));
+    plugins
+}
+
+/// `soroban-registry-foo` (or `soroban-registry-foo.exe` on Windows) → `foo`
+fn plugin_name(file_name: &OsStr) -> Option<String> {
+    let file_name = file_name.to_str()?;
+    let name = file_name.strip_prefix(PLUGIN_PREFIX)?;
+    let name = if cfg!(windows) {
+        name.strip_suffix(".exe").unwrap_or(name)
+    } else {
+        name
+    };
+    (!name.is_empty() && !name.starts_with('-')).then(|| name.to_string())
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "exe")
+}
+
+/// Names and aliases of the built-in commands, which plugins can't replace
+pub fn builtin_names(root: &Command) -> BTreeSet<String> {
+    let mut names: BTreeSet<String> = root
+        .get_subcommands()
+        .flat_map(|command| std::iter::once(command.get_name()).chain(command.get_all_aliases()))
+        .map(str::to_string)
+        .collect();
+    names.insert("help".to_string());
+    names
+}
+
+/// Adds the installed plugins to the end of `--help`
+pub fn with_plugin_help(root: Command) -> Command {
+    let builtins = builtin_names(&root);
+    let names: Vec<String> = discover()
+        .into_iter()
+        .map(|plugin| plugin.name)
+        .filter(|name| !builtins.contains(name))
+        .collect();
+    if names.is_empty() {
+        return root;
+    }
+    root.after_help(format!(
+        "Installed plugins (see `plugins list`):\n  {}",
+        names.join(", ")
+    ))
+}
+
+/// Variables describing this run, set for the plugin; `None` unsets one
+pub fn plugin_env(
+    runtime: &RuntimeConfig,
+    network: &str,
+    time_format: TimeFormat,
+) -> Vec<(&'static str, Option<String>)> {
+    let color = match runtime.color {
+        Some(true) => "always",
+        Some(false) => "never",
+        None => "auto",
+    };
+    vec![
+        ("SOROBAN_REGISTRY_API_URL", Some(runtime.api_base.clone())),
+        ("SOROBAN_REGISTRY_PROFILE", runtime.profile.clone()),
+        ("SOROBAN_REGISTRY_NETWORK", Some(network.to_string())),
+        (
+            "SOROBAN_REGISTRY_TOKEN_ENV",
+            Some(runtime.token_env.clone()),
+        ),
+        (
+            "SOROBAN_REGISTRY_OUTPUT",
+            Some(if runtime.json { "json" } else { "text" }.to_string()),
+        ),
+        ("SOROBAN_REGISTRY_COLOR", Some(color.to_string())),
+        (
+            "SOROBAN_REGISTRY_TIME_FORMAT",
+            Some(time_format.to_string()),
+        ),
+    ]
+}
+
+/// Runs the plugin for `args[0]` with the rest of `args`, returning its exit
+/// code
+pub async fn run(args: &[OsString], env: &[(&'static str, Option<String>)]) -> Result<i32> {
+    let (name, rest) = args.split_first().context("No plugin name given")?;
+    let name = name.to_string_lossy();
+    let Some(plugin) = discover().into_iter().find(|plugin| plugin.name == name) else {
+        anyhow::bail!(
+            "'{}' is not a soroban-registry command and no `{}{}` plugin is on PATH; see `soroban-registry --help`",
+            name,
+            PLUGIN_PREFIX,
+            name
+        );
+    };
+    log::debug!("Plugin: {} ({})", plugin.name, plugin.path.display());
+    spawn(&plugin, rest, env).await
+}
+
+async fn spawn(
+    plugin: &Plugin,
+    args: &[OsString],
+    env: &[(&'static str, Option<String>)],
+) -> Result<i32> {
+    let mut command = tokio::process::Command::new(&plugin.path);
+    command.args(args);
+    for (key, value) in env {
+        match value {
+            Some(value) => command.env(key, value),
+            None => command.env_remove(key),
+        };
+    }
+    let status = command
+        .status()
+        .await
+        .with_context(|| format!("Failed to run {}", plugin.path.display()))?;
+    // Killed by a signal, which has no exit code
+    Ok(status.code().unwrap_or(128))
+}
+
+/// Asks a plugin for its [`PluginInfo`]; `None` if it doesn't answer
+pub async fn plugin_info(path: &Path) -> Option<PluginInfo> {
+    let output = tokio::process::Command::new(path)
+        .arg(PLUGIN_INFO_FLAG)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(PLUGIN_INFO_TIMEOUT, output)
+        .await
+        .ok()?
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    serde_json::from_slice(&output.stdout).ok()
+}
+
+/// `plugins list`
+pub async fn list(root: &Command, json: bool) -> Result<()> {
+    let builtins = builtin_names(root);
+    let mut listings = Vec::new();
+    for plugin in discover() {
+        let info = plugin_info(&plugin.path).await.unwrap_or_default();
+        listings.push(PluginListing {
+            shadowed: builtins.contains(&plugin.name),
+            name: plugin.name,
+            path: plugin.path,
+            version: info.version,
+            description: info.description,
+        });
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&listings)?);
+        return Ok(());
+    }
+    if listings.is_empty() {
+        println!(
+            "No plugins found. Put an executable named {}<name> on PATH to add `<name>`.",
+            PLUGIN_PREFIX
+        );
+        return Ok(());
+    }
+    println!("\n{}", "Plugins".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    for listing in &listings {
+        println!(
+            "{:<24} {:<12} {}",
+            listing.name.green(),
+            listing.version.as_deref().unwrap_or("unknown"),
+            listing.description.as_deref().unwrap_or_default()
+        );
+        println!("  {}", listing.path.display().to_string().bright_black());
+        if listing.shadowed {
+            println!(
+                "  {}",
+                format!(
+                    "shadowed by the built-in `{}` command, never run",
+                    listing.name
+                )
+                .yellow()
+            );
+        }
+    }
+    println!();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, Parser};
+    use std::fs;
+
+    #[cfg(unix)]
+    fn write_plugin(dir: &Path, file_name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+        let path = dir.join(file_name);
+        fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn the_first_plugin_on_path_wins() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        let approve = write_plugin(first.path(), "soroban-registry-approve", "exit 0");
+        write_plugin(second.path(), "soroban-registry-approve", "exit 1");
+        write_plugin(second.path(), "soroban-registry-deploy-internal", "exit 0");
+        // Not executable, and not a plugin
+        fs::write(second.path().join("soroban-registry-notes"), "").unwrap();
+        write_plugin(second.path(), "stellar-approve", "exit 0");
+
+        let path = std::env::join_paths([first.path(), second.path()]).unwrap();
+        let plugins = discover_in(&path);
+        let names: Vec<&str> = plugins.iter().map(|plugin| plugin.name.as_str()).collect();
+        assert_eq!(names, ["approve", "deploy-internal"]);
+        assert_eq!(plugins[0].path, approve);
+    }
+
+    #[test]
+    fn built_in_commands_win_over_plugins() {
+        let builtins = builtin_names(&crate::Cli::command());
+        assert!(builtins.contains("search"));
+        assert!(builtins.contains("help"));
+
+        let cli = crate::Cli::try_parse_from(["soroban-registry", "search", "token"]).unwrap();
+        assert!(matches!(cli.command, crate::Commands::Search { .. }));
+
+        let cli = crate::Cli::try_parse_from(["soroban-registry", "approve", "--ticket", "OPS-1"])
+            .unwrap();
+        let crate::Commands::External(args) = cli.command else {
+            panic!("expected a plugin invocation");
+        };
+        assert_eq!(args, ["approve", "--ticket", "OPS-1"]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn plugins_describe_themselves() {
+        let dir = tempfile::tempdir().unwrap();
+        let answers = write_plugin(
+            dir.path(),
+            "soroban-registry-approve",
+            r#"echo '{"version": "1.2.0", "description": "Internal approvals"}'"#,
+        );
+        let silent = write_plugin(dir.path(), "soroban-registry-legacy", "exit 2");
+
+        let info = plugin_info(&answers).await.unwrap();
+        assert_eq!(info.version.as_deref(), Some("1.2.0"));
+        assert_eq!(info.description.as_deref(), Some("Internal approvals"));
+        assert!(plugin_info(&silent).await.is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn plugins_get_the_arguments_and_settings_of_the_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let seen = dir.path().join("seen");
+        let path = write_plugin(
+            dir.path(),
+            "soroban-registry-approve",
+            &format!(
+                r#"echo "$* $SOROBAN_REGISTRY_API_URL $SOROBAN_REGISTRY_TOKEN_ENV $SOROBAN_REGISTRY_OUTPUT ${{SOROBAN_REGISTRY_PROFILE-none}}" > {}
+exit 3"#,
+                seen.display()
+            ),
+        );
+        let mut runtime = RuntimeConfig::builtin(None, Some("https://registry.example".into()));
+        runtime.json = true;
+        let env = plugin_env(&runtime, "testnet", TimeFormat::Iso);
+        assert!(env.contains(&("SOROBAN_REGISTRY_TIME_FORMAT", Some("iso".into()))));
+
+        let plugin = Plugin {
+            name: "approve".into(),
+            path,
+        };
+        let code = spawn(&plugin, &["--ticket".into(), "OPS-1".into()], &env)
+            .await
+            .unwrap();
+        assert_eq!(code, 3);
+        assert_eq!(
+            fs::read_to_string(&seen).unwrap().trim(),
+            "--ticket OPS-1 https://registry.example SOROBAN_REGISTRY_API_TOKEN json none"
+        );
+    }
+}
@@ -0,0 +1,401 @@
+//! `publish-version`: add a version to a registered contract and upload its
+//! WASM.
+//!
+//! Before anything is created the new interface is compared with the
+//! previous version's through `POST /api/contracts/:id/versions/compat`.
+//! Breaking changes are listed, and when the major version wasn't bumped for
+//! them the command stops unless `--allow-breaking` is passed.
+//!
+//! With `--key` the version is signed with the stored key. The signature
+//! covers the current time and a random nonce, and the registry accepts it
+//! only for a few minutes and only once, so a skewed local clock shows up
+//! as a rejected publish; the offset to the registry's `Date` header is
+//! reported then.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use ed25519_dalek::{Signer, SigningKey};
+use rand::RngCore;
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+/// Shown changes per kind before the rest are summed up
+const MAX_LISTED_CHANGES: usize = 20;
+
+pub struct NewVersion {
+    pub version: String,
+    pub wasm: PathBuf,
+    pub abi: PathBuf,
+    pub release_notes: Option<String>,
+    pub source_url: Option<String>,
+    pub commit_hash: Option<String>,
+    pub allow_breaking: bool,
+}
+
+/// As returned by the compat endpoints and version creation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionCompat {
+    pub version: String,
+    pub previous_version: String,
+    /// `breaking`, `additive` or `compatible`
+    pub compatibility: String,
+    #[serde(default)]
+    pub breaking_count: usize,
+    #[serde(default)]
+    pub changes: Vec<InterfaceChange>,
+    #[serde(default)]
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterfaceChange {
+    /// `breaking` or `non_breaking`
+    pub severity: String,
+    pub category: String,
+    pub message: String,
+}
+
+impl InterfaceChange {
+    fn is_breaking(&self) -> bool {
+        self.severity == "breaking"
+    }
+}
+
+pub async fn publish(
+    api_url: &str,
+    contract_id: &str,
+    new: &NewVersion,
+    signing_key: Option<&SigningKey>,
+    auth_token: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let base = api_url.trim_end_matches('/');
+    let wasm = std::fs::read(&new.wasm)
+        .with_context(|| format!("Failed to read {}", new.wasm.display()))?;
+    if !wasm.starts_with(b"\0asm") {
+        anyhow::bail!("{} is not a WASM module", new.wasm.display());
+    }
+    let abi: Value = serde_json::from_str(
+        &std::fs::read_to_string(&new.abi)
+            .with_context(|| format!("Failed to read {}", new.abi.display()))?,
+    )
+    .with_context(|| format!("{} is not valid JSON", new.abi.display()))?;
+
+    let preview: Option<VersionCompat> = ensure_success(
+        crate::http::client()
+            .post(format!(
+                "{}/api/contracts/{}/versions/compat",
+                base, contract_id
+            ))
+            .json(&json!({ "version": new.version, "abi": abi }))
+            .send()
+            .await
+            .context("Failed to compare interfaces")?,
+        "Failed to compare interfaces",
+    )
+    .await?
+    .json()
+    .await
+    .context("Invalid response")?;
+
+    if let Some(compat) = &preview {
+        if !json {
+            print!("{}", render_summary(compat));
+        }
+        if compat.warning.is_some() && !new.allow_breaking {
+            anyhow::bail!(
+                "{} breaks callers of {} without a major version bump; publish it as {}.0.0 \
+                 or pass --allow-breaking",
+                compat.version,
+                compat.previous_version,
+                major_of(&compat.previous_version) + 1
+            );
+        }
+    }
+
+    let wasm_hash = hex::encode(Sha256::digest(&wasm));
+    let body = CreateVersion {
+        contract_id,
+        version: &new.version,
+        wasm_hash: &wasm_hash,
+        abi: &abi,
+        source_url: new.source_url.as_deref(),
+        commit_hash: new.commit_hash.as_deref(),
+        release_notes: new.release_notes.as_deref(),
+        allow_breaking: new.allow_breaking,
+        signed: signing_key.map(|key| sign(key, contract_id, &new.version, &wasm_hash)),
+    };
+    let mut request = crate::http::client()
+        .post(format!("{}/api/contracts/{}/versions", base, contract_id))
+        .json(&body);
+    if let Some(token) = auth_token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.context("Failed to create version")?;
+    if !response.status().is_success() {
+        return Err(creation_failure(response).await);
+    }
+    let created: Value = response.json().await.context("Invalid response")?;
+
+    let mut request = crate::http::client()
+        .post(format!(
+            "{}/api/contracts/{}/versions/{}/wasm",
+            base, contract_id, new.version
+        ))
+        .header(reqwest::header::CONTENT_TYPE, "application/wasm")
+        .body(wasm);
+    if let Some(token) = auth_token {
+        request = request.bearer_auth(token);
+    }
+    let uploaded: Value = ensure_success(
+        request.send().await.context("Failed to upload WASM")?,
+        "Failed to upload WASM",
+    )
+    .await?
+    .json()
+    .await
+    .context("Invalid response")?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&json!({
+                "version": created,
+                "compat": preview,
+                "wasm": uploaded,
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} {} {}",
+        "✓ Published".green().bold(),
+        contract_id,
+        new.version.bold()
+    );
+    if let Some(warning) = created["compat"]["warning"].as_str() {
+        println!("  {} {}", "warning:".yellow().bold(), warning);
+    }
+    if let Some(hash) = uploaded["source_hash"].as_str() {
+        println!("  WASM {}", &hash[..hash.len().min(12)]);
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct CreateVersion<'a> {
+    contract_id: &'a str,
+    version: &'a str,
+    wasm_hash: &'a str,
+    abi: &'a Value,
+    source_url: Option<&'a str>,
+    commit_hash: Option<&'a str>,
+    release_notes: Option<&'a str>,
+    allow_breaking: bool,
+    #[serde(flatten)]
+    signed: Option<SignedPublish>,
+}
+
+/// Signature fields of a version publish, as the registry expects them
+#[derive(Debug, Serialize)]
+struct SignedPublish {
+    signature: String,
+    publisher_key: String,
+    signature_algorithm: &'static str,
+    signed_at: i64,
+    nonce: String,
+}
+
+fn sign(
+    signing_key: &SigningKey,
+    contract_id: &str,
+    version: &str,
+    wasm_hash: &str,
+) -> SignedPublish {
+    let mut nonce = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    let nonce = hex::encode(nonce);
+    let signed_at = Utc::now().timestamp();
+    let message = format!(
+        "{}:{}:{}:{}:{}",
+        contract_id, version, wasm_hash, signed_at, nonce
+    );
+    SignedPublish {
+        signature: BASE64.encode(signing_key.sign(message.as_bytes()).to_bytes()),
+        publisher_key: BASE64.encode(signing_key.verifying_key().as_bytes()),
+        signature_algorithm: "ed25519",
+        signed_at,
+        nonce,
+    }
+}
+
+/// Error reasons for a signature the registry's clock puts out of range
+const TIMING_REJECTIONS: [&str; 2] = ["SignatureClockSkew", "SignatureExpired"];
+
+/// Why version creation failed, with the measured clock offset when the
+/// registry rejected the signature's timestamp
+async fn creation_failure(response: Response) -> anyhow::Error {
+    let status = response.status();
+    let offset = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|date| date.to_str().ok())
+        .and_then(|date| clock_offset(Utc::now(), date));
+    let body: Value = response.json().await.unwrap_or_default();
+    let message = body["error"]["message"].as_str().unwrap_or("no details");
+    let reason = body["error"]["details"]["reason"].as_str().unwrap_or("");
+    match offset {
+        Some(offset) if TIMING_REJECTIONS.contains(&reason) => anyhow::anyhow!(
+            "Failed to create version: {} ({}); your clock is {}, correct it and publish again",
+            message,
+            status,
+            describe_offset(offset)
+        ),
+        _ => anyhow::anyhow!("Failed to create version: {} ({})", message, status),
+    }
+}
+
+/// Seconds the local clock at `local` is ahead of the registry's, from the
+/// registry's HTTP `Date` header
+fn clock_offset(local: DateTime<Utc>, date_header: &str) -> Option<i64> {
+    let server = DateTime::parse_from_rfc2822(date_header).ok()?;
+    Some(local.timestamp() - server.timestamp())
+}
+
+fn describe_offset(offset: i64) -> String {
+    match offset {
+        0 => "in sync with the registry's to the second".to_string(),
+        ahead if ahead > 0 => format!("{}s ahead of the registry's", ahead),
+        behind => format!("{}s behind the registry's", -behind),
+    }
+}
+
+async fn ensure_success(response: Response, action: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body: Value = response.json().await.unwrap_or_default();
+    let message = body["error"]["message"]
+        .as_str()
+        .or(body["message"].as_str())
+        .unwrap_or("no details");
+    anyhow::bail!("{}: {} ({})", action, message, status)
+}
+
+fn major_of(version: &str) -> u64 {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+        .unwrap_or(0)
+}
+
+/// How the new interface compares with the previous version's, breaking
+/// changes first
+fn render_summary(compat: &VersionCompat) -> String {
+    let (breaking, other): (Vec<_>, Vec<_>) = compat.changes.iter().partition(|c| c.is_breaking());
+    let mut out = format!(
+        "Interface {} → {}: {}\n",
+        compat.previous_version, compat.version, compat.compatibility
+    );
+    for (changes, marker) in [(&breaking, "!"), (&other, "+")] {
+        for change in changes.iter().take(MAX_LISTED_CHANGES) {
+            out.push_str(&format!("  {} {}\n", marker, change.message));
+        }
+        if changes.len() > MAX_LISTED_CHANGES {
+            out.push_str(&format!(
+                "  … and {} more\n",
+                changes.len() - MAX_LISTED_CHANGES
+            ));
+        }
+    }
+    if let Some(warning) = &compat.warning {
+        out.push_str(&format!("  warning: {}\n", warning));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(severity: &str, message: &str) -> InterfaceChange {
+        InterfaceChange {
+            severity: severity.to_string(),
+            category: String::new(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn summary_lists_breaking_changes_first() {
+        let compat = VersionCompat {
+            version: "1.1.0".to_string(),
+            previous_version: "1.0.0".to_string(),
+            compatibility: "breaking".to_string(),
+            breaking_count: 1,
+            changes: vec![
+                change("non_breaking", "Function 'burn' was added"),
+                change("breaking", "Function 'mint' was removed"),
+            ],
+            warning: Some(
+                "1 breaking change without a major version bump from 1.0.0 \
+                 (expected 2.0.0 or later)"
+                    .to_string(),
+            ),
+        };
+        assert_eq!(
+            render_summary(&compat),
+            "Interface 1.0.0 → 1.1.0: breaking\n\
+             \x20 ! Function 'mint' was removed\n\
+             \x20 + Function 'burn' was added\n\
+             \x20 warning: 1 breaking change without a major version bump from 1.0.0 \
+             (expected 2.0.0 or later)\n"
+        );
+        assert_eq!(major_of(&compat.previous_version), 1);
+    }
+
+    #[test]
+    fn clock_offset_is_measured_against_the_date_header() {
+        let local = DateTime::parse_from_rfc3339("2026-10-16T09:02:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let offset = clock_offset(local, "Fri, 16 Oct 2026 09:00:00 GMT").unwrap();
+        assert_eq!(offset, 125);
+        assert_eq!(describe_offset(offset), "125s ahead of the registry's");
+        assert_eq!(
+            describe_offset(clock_offset(local, "Fri, 16 Oct 2026 09:03:00 GMT").unwrap()),
+            "55s behind the registry's"
+        );
+        assert_eq!(clock_offset(local, "yesterday"), None);
+    }
+
+    #[test]
+    fn signatures_cover_the_timestamp_and_nonce() {
+        use ed25519_dalek::Verifier;
+
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let first = sign(&key, "CABC", "1.1.0", "ab12");
+        let second = sign(&key, "CABC", "1.1.0", "ab12");
+        assert_ne!(first.nonce, second.nonce);
+        assert_eq!(first.nonce.len(), 32);
+
+        let message = format!("CABC:1.1.0:ab12:{}:{}", first.signed_at, first.nonce);
+        let signature =
+            ed25519_dalek::Signature::from_slice(&BASE64.decode(&first.signature).unwrap())
+                .unwrap();
+        assert!(key
+            .verifying_key()
+            .verify(message.as_bytes(), &signature)
+            .is_ok());
+    }
+}
@@ -79,7 +79,7 @@ pub async fn generate(
         "contract_address": contract_address,
     });
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .post(format!(
             "{}/api/contracts/{}/release-notes/generate",
@@ -114,7 +114,7 @@ pub async fn view(
     version: &str,
     json_output: bool,
 ) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .get(format!(
             "{}/api/contracts/{}/release-notes/{}",
@@ -164,7 +164,7 @@ pub async fn edit(
         "notes_text": text,
     });
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .put(format!(
             "{}/api/contracts/{}/release-notes/{}",
@@ -211,7 +211,7 @@ pub async fn publish(
         "update_version_record": !skip_version_update,
     });
 
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .post(format!(
             "{}/api/contracts/{}/release-notes/{}/publish",
@@ -248,7 +248,7 @@ pub async fn publish(
 
 /// List all release notes for a contract
 pub async fn list(api_url: &str, contract_id: &str, json_output: bool) -> Result<()> {
-    let client = reqwest::Client::new();
+    let client = crate::http::client();
     let resp = client
         .get(format!(
             "{}/api/contracts/{}/release-notes",
@@ -0,0 +1,319 @@
+//! Accepting `namespace/slug[@version]` wherever a contract id is expected.
+//!
+//! Names are resolved through `GET /api/resolve`. Anything that does not
+//! parse as a name is passed through unchanged, so UUIDs and contract
+//! addresses keep working.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+use shared::slug::{ContractName, ResolvedContract};
+
+use crate::config::Network;
+
+/// Looks `name` up on `network`. With [`Network::Auto`] the API picks the
+/// network, failing when the name exists on more than one.
+pub async fn resolve(
+    api_url: &str,
+    name: &ContractName,
+    network: Network,
+) -> Result<ResolvedContract> {
+    let url = format!("{}/api/resolve", api_url.trim_end_matches('/'));
+    let response = crate::http::client()
+        .get(&url)
+        .query(&resolve_query(name, network))
+        .send()
+        .await
+        .with_context(|| format!("Failed to resolve {}", name))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!("Could not resolve {}: {} ({})", name, message, status);
+    }
+    let resolved: ResolvedContract = response
+        .json()
+        .await
+        .context("Invalid response from /api/resolve")?;
+
+    // On stderr, so `--format json` output stays parseable
+    eprintln!("{}", describe(name, &resolved).dimmed());
+    Ok(resolved)
+}
+
+fn resolve_query(name: &ContractName, network: Network) -> Vec<(&'static str, String)> {
+    let mut query = vec![("name", name.to_string())];
+    if network != Network::Auto {
+        query.push(("network", network.to_string()));
+    }
+    query
+}
+
+/// One line saying what a name resolved to
+fn describe(name: &ContractName, resolved: &ResolvedContract) -> String {
+    let mut line = format!(
+        "{} → {} on {}",
+        name, resolved.contract_id, resolved.network
+    );
+    if let Some(version) = &resolved.version {
+        line.push_str(&format!(", version {}", version.version));
+    }
+    if let Some(old) = &resolved.redirected_from {
+        line.push_str(&format!(" ({} was renamed to {})", old, resolved.name));
+    }
+    line
+}
+
+/// The registry UUID for `input` when it is a contract name; `input`
+/// itself otherwise.
+pub async fn contract_id(api_url: &str, input: &str, network: Network) -> Result<String> {
+    match ContractName::parse(input) {
+        Some(name) => Ok(resolve(api_url, &name, network).await?.id.to_string()),
+        None => Ok(input.to_string()),
+    }
+}
+
+/// Source fetched from the registry into a temporary directory, removed
+/// when this is dropped
+pub struct FetchedSource {
+    _dir: tempfile::TempDir,
+    pub path: PathBuf,
+}
+
+/// Fetches the Rust source of the named version when `contract_path` is a
+/// contract name rather than a file on disk, for `profile`. `None` when
+/// there is a file at `contract_path` or it is not a name.
+pub async fn source_for_profiling(
+    api_url: &str,
+    contract_path: &str,
+    network: Network,
+) -> Result<Option<FetchedSource>> {
+    if Path::new(contract_path).exists() {
+        return Ok(None);
+    }
+    let Some(name) = ContractName::parse(contract_path) else {
+        return Ok(None);
+    };
+
+    let resolved = resolve(api_url, &name, network).await?;
+    let version = published_version(&name, &resolved)?;
+    let source = fetch_source(api_url, &resolved.id.to_string(), version, "rust").await?;
+
+    let dir = tempfile::tempdir().context("Failed to create a temporary directory")?;
+    let path = dir.path().join(format!("{}.rs", name.slug));
+    fs::write(&path, source).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(Some(FetchedSource { _dir: dir, path }))
+}
+
+fn published_version<'a>(name: &ContractName, resolved: &'a ResolvedContract) -> Result<&'a str> {
+    resolved
+        .version
+        .as_ref()
+        .map(|version| version.version.as_str())
+        .with_context(|| format!("{} has no published versions", name))
+}
+
+/// Downloads the source of one version of a contract, resuming an earlier
+/// interrupted download of it. `contract` is a name, or a UUID together with
+/// `version`.
+pub async fn download(
+    api_url: &str,
+    contract: &str,
+    version: Option<&str>,
+    format: &str,
+    output: Option<&Path>,
+    network: Network,
+) -> Result<()> {
+    let extension = match format {
+        "rust" => "rs",
+        "wasm" => "wasm",
+        other => anyhow::bail!("Unknown source format '{}', expected rust or wasm", other),
+    };
+
+    let (id, label, version) = match ContractName::parse(contract) {
+        Some(mut name) => {
+            if let Some(version) = version {
+                name.version = Some(version.to_string());
+            }
+            let resolved = resolve(api_url, &name, network).await?;
+            let version = published_version(&name, &resolved)?.to_string();
+            (resolved.id.to_string(), name.slug, version)
+        }
+        None => {
+            let version = version.with_context(|| {
+                format!(
+                    "Pass --version to download {}, or name the contract as namespace/slug",
+                    contract
+                )
+            })?;
+            (
+                contract.to_string(),
+                contract.to_string(),
+                version.to_string(),
+            )
+        }
+    };
+
+    let path = match output {
+        Some(path) => path.to_path_buf(),
+        None => PathBuf::from(default_file_name(&label, &version, extension)),
+    };
+    let url = format!(
+        "{}/api/contracts/{}/versions/{}/source/download",
+        api_url.trim_end_matches('/'),
+        id,
+        version
+    );
+    let downloaded = crate::download::fetch(&url, &[("source_format", format)], &path)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to download the {} source of {} {}",
+                format, id, version
+            )
+        })?;
+
+    print!(
+        "{} {} bytes of {} source to {}",
+        "Downloaded".green().bold(),
+        downloaded.bytes,
+        format,
+        path.display()
+    );
+    if downloaded.resumed_from > 0 {
+        print!(" (resumed at byte {})", downloaded.resumed_from);
+    }
+    println!();
+    Ok(())
+}
+
+fn default_file_name(label: &str, version: &str, extension: &str) -> String {
+    let safe = |text: &str| -> String {
+        text.chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_') {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    };
+    format!("{}-{}.{}", safe(label), safe(version), extension)
+}
+
+/// Fetches and checks a stored source through
+/// `GET /api/contracts/{id}/versions/{version}/source`
+async fn fetch_source(api_url: &str, id: &str, version: &str, format: &str) -> Result<Vec<u8>> {
+    let url = format!(
+        "{}/api/contracts/{}/versions/{}/source",
+        api_url.trim_end_matches('/'),
+        id,
+        version
+    );
+    let response = crate::http::client()
+        .get(&url)
+        .query(&[("source_format", format)])
+        .send()
+        .await
+        .context("Failed to fetch contract source")?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "No {} source for version {} of {}: {}",
+            format,
+            version,
+            id,
+            response.status()
+        );
+    }
+
+    let body: serde_json::Value = response.json().await?;
+    let encoded = body["source_base64"]
+        .as_str()
+        .context("Source response has no source_base64")?;
+    let source = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .context("Source is not valid base64")?;
+
+    if let Some(expected) = body["source_hash"].as_str() {
+        let actual = hex::encode(Sha256::digest(&source));
+        if !actual.eq_ignore_ascii_case(expected) {
+            anyhow::bail!(
+                "Downloaded source does not match its hash (expected {}, got {})",
+                expected,
+                actual
+            );
+        }
+    }
+    Ok(source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(redirected_from: Option<&str>) -> ResolvedContract {
+        serde_json::from_value(serde_json::json!({
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "contract_id": "CABC",
+            "network": "testnet",
+            "name": "acme/liquidity-pool",
+            "redirected_from": redirected_from,
+            "version": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn auto_network_is_left_to_the_api() {
+        let name = ContractName::parse("acme/pool@1.2.0").unwrap();
+        assert_eq!(
+            resolve_query(&name, Network::Testnet),
+            [
+                ("name", "acme/pool@1.2.0".to_string()),
+                ("network", "testnet".to_string())
+            ]
+        );
+        assert_eq!(
+            resolve_query(&name, Network::Auto),
+            [("name", "acme/pool@1.2.0".to_string())]
+        );
+    }
+
+    #[test]
+    fn renames_are_reported() {
+        let name = ContractName::parse("acme/old-pool").unwrap();
+        let line = describe(&name, &resolved(Some("acme/old-pool")));
+        assert!(line.contains("CABC on testnet"), "{}", line);
+        assert!(
+            line.contains("acme/old-pool was renamed to acme/liquidity-pool"),
+            "{}",
+            line
+        );
+        assert!(!describe(&name, &resolved(None)).contains("renamed"));
+    }
+
+    #[test]
+    fn a_name_without_versions_cannot_be_downloaded() {
+        let name = ContractName::parse("acme/liquidity-pool").unwrap();
+        let err = published_version(&name, &resolved(None)).unwrap_err();
+        assert!(err.to_string().contains("no published versions"));
+    }
+
+    #[test]
+    fn default_file_names_are_safe() {
+        assert_eq!(
+            default_file_name("liquidity-pool", "1.2.0", "rs"),
+            "liquidity-pool-1.2.0.rs"
+        );
+        assert_eq!(
+            default_file_name("pool", "../../etc/x", "wasm"),
+            "pool-.._.._etc_x.wasm"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! `search --save` and `search --saved`: searches stored on the registry
+//! under a name, through `/api/searches`.
+//!
+//! `--saved` looks the name up among the caller's own saved searches, and
+//! otherwise takes it as the id of a search someone shared. The search runs
+//! on the registry with the caller's access, so private contracts the caller
+//! can no longer see drop out of the results.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+use shared::pagination::Paginated;
+
+/// As returned by `/api/searches`
+#[derive(Debug, Clone, Deserialize)]
+pub struct SavedSearch {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub params: BTreeMap<String, String>,
+}
+
+async fn ensure_success(response: Response, action: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let message = body["error"]["message"].as_str().unwrap_or("no details");
+    anyhow::bail!("{}: {} ({})", action, message, status)
+}
+
+/// Saves `params` under `name`, replacing the caller's search of that name.
+/// The confirmation goes to stderr so `--json` output stays parseable.
+pub async fn save(api_url: &str, name: &str, params: &[(&str, String)]) -> Result<SavedSearch> {
+    let params: BTreeMap<&str, &str> = params
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    let response = crate::http::client()
+        .post(format!("{}/api/searches", api_url))
+        .json(&serde_json::json!({ "name": name, "params": params }))
+        .send()
+        .await
+        .context("Failed to save search")?;
+    let search: SavedSearch = ensure_success(response, "Failed to save search")
+        .await?
+        .json()
+        .await
+        .context("Invalid response")?;
+    eprintln!(
+        "{} Saved search '{}' as {}; run it with `search --saved {}`",
+        "✓".green(),
+        search.name,
+        search.id.bold(),
+        search.name
+    );
+    Ok(search)
+}
+
+/// The caller's saved search called `name_or_id`, or the search with that id
+pub async fn resolve(api_url: &str, name_or_id: &str) -> Result<SavedSearch> {
+    let client = crate::http::client();
+
+    // Without an API token there are no searches of one's own to look in
+    let response = client
+        .get(format!("{}/api/me/searches", api_url))
+        .send()
+        .await
+        .context("Failed to list saved searches")?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        let own: Vec<SavedSearch> = ensure_success(response, "Failed to list saved searches")
+            .await?
+            .json()
+            .await
+            .context("Invalid response")?;
+        if let Some(search) = own.into_iter().find(|s| s.name == name_or_id) {
+            return Ok(search);
+        }
+    }
+
+    let response = client
+        .get(format!("{}/api/searches/{}", api_url, name_or_id))
+        .send()
+        .await
+        .context("Failed to fetch saved search")?;
+    if response.status() == StatusCode::NOT_FOUND {
+        anyhow::bail!(
+            "No saved search named '{}', and no saved search has that id",
+            name_or_id
+        );
+    }
+    ensure_success(response, "Failed to fetch saved search")
+        .await?
+        .json()
+        .await
+        .context("Invalid response")
+}
+
+pub async fn run(
+    api_url: &str,
+    name_or_id: &str,
+    limit: usize,
+    offset: usize,
+    json: bool,
+) -> Result<()> {
+    let t0 = std::time::Instant::now();
+    let search = resolve(api_url, name_or_id).await?;
+    let response = crate::http::client()
+        .get(format!("{}/api/searches/{}/results", api_url, search.id))
+        .query(&[("limit", limit), ("offset", offset)])
+        .send()
+        .await
+        .context("Failed to run saved search")?;
+    let page: Paginated<serde_json::Value> = ensure_success(response, "Failed to run saved search")
+        .await?
+        .json()
+        .await
+        .context("Invalid response")?;
+
+    if json {
+        return crate::commands::print_search_json(api_url, &page.items);
+    }
+
+    println!("\n{}", "Search Results:".bold().cyan());
+    println!("{}", "=".repeat(80).cyan());
+    println!(
+        "  {} {} ({})",
+        "Saved search:".bold(),
+        search.name,
+        search.id.bright_black()
+    );
+    if !search.params.is_empty() {
+        println!(
+            "  {} {}\n",
+            "Parameters:".bold(),
+            describe(&search.params).bright_blue()
+        );
+    }
+    if page.items.is_empty() {
+        println!("{}", "No contracts match this saved search.".yellow());
+        println!("{}\n", crate::table_format::page_summary(&page));
+        return Ok(());
+    }
+
+    let query = search.params.get("query").map(String::as_str).unwrap_or("");
+    crate::commands::print_search_table(api_url, &page.items, query)?;
+    println!(
+        "\n{} {} result(s)  |  {}ms",
+        "→".cyan(),
+        page.items.len(),
+        t0.elapsed().as_millis()
+    );
+    println!("{}\n", crate::table_format::page_summary(&page));
+    Ok(())
+}
+
+/// "category: oracle | network: mainnet | query: price"
+fn describe(params: &BTreeMap<String, String>) -> String {
+    params
+        .iter()
+        .map(|(name, value)| format!("{}: {}", name, value))
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parameters_are_described_by_name() {
+        let search: SavedSearch = serde_json::from_value(serde_json::json!({
+            "id": "a1B2c3D4e5",
+            "owner": "GABC",
+            "name": "mainnet oracles",
+            "params": { "network": "mainnet", "category": "oracle", "verified_only": "true" },
+            "created_at": "2026-10-16T09:00:00Z",
+            "updated_at": "2026-10-16T09:00:00Z"
+        }))
+        .unwrap();
+        assert_eq!(
+            describe(&search.params),
+            "category: oracle | network: mainnet | verified_only: true"
+        );
+    }
+}
@@ -0,0 +1,651 @@
+//! Turning JSON arguments from the command line into Soroban `ScVal`s.
+//!
+//! With the contract's stored interface each argument is converted to the
+//! declared parameter type, so `5` becomes an `i128` where the method takes
+//! one and `"Native"` the matching union case. Without it the type is
+//! guessed from the JSON: numbers become `i128`, the usual amount type,
+//! strings that are `G…`/`C…` addresses become addresses and other strings
+//! `String`s. A one-key object naming a type, e.g. `{"u32": 5}` or
+//! `{"symbol": "transfer"}`, picks the type explicitly.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use contract_abi::{ContractABI, EnumVariant, SorobanType, StructField};
+use serde_json::Value;
+use stellar_xdr::curr::{
+    Duration as ScDuration, Int256Parts, ScAddress, ScBytes, ScMap, ScMapEntry, ScString, ScSymbol,
+    ScVal, ScVec, TimePoint, UInt256Parts,
+};
+
+/// Type names accepted as the key of a typed literal
+const TYPED_LITERALS: &[&str] = &[
+    "bool",
+    "i32",
+    "u32",
+    "i64",
+    "u64",
+    "i128",
+    "u128",
+    "i256",
+    "u256",
+    "timepoint",
+    "duration",
+    "symbol",
+    "string",
+    "bytes",
+    "address",
+];
+
+/// Converts the `--args` of a call to `method`: a JSON array in parameter
+/// order, or, with an interface, an object keyed by parameter name.
+pub fn method_args(abi: Option<&ContractABI>, method: &str, args: &Value) -> Result<Vec<ScVal>> {
+    let Some(abi) = abi else {
+        return match args {
+            Value::Null => Ok(Vec::new()),
+            Value::Array(values) => values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    infer(value).with_context(|| format!("argument {}", index + 1))
+                })
+                .collect(),
+            _ => anyhow::bail!(
+                "--args must be a JSON array when the contract's interface is unknown"
+            ),
+        };
+    };
+
+    let function = abi.find_function(method).with_context(|| {
+        let names: Vec<&str> = abi.functions.iter().map(|f| f.name.as_str()).collect();
+        format!(
+            "{} has no method '{}' (it has: {})",
+            abi.name,
+            method,
+            names.join(", ")
+        )
+    })?;
+
+    let values: Vec<&Value> = match args {
+        Value::Null => Vec::new(),
+        Value::Array(values) => values.iter().collect(),
+        Value::Object(named) => {
+            if let Some(unknown) = named
+                .keys()
+                .find(|key| !function.params.iter().any(|param| &param.name == *key))
+            {
+                anyhow::bail!("{} has no parameter '{}'", method, unknown);
+            }
+            function
+                .params
+                .iter()
+                .map(|param| named.get(&param.name).unwrap_or(&Value::Null))
+                .collect()
+        }
+        _ => anyhow::bail!("--args must be a JSON array or an object keyed by parameter name"),
+    };
+    if values.len() != function.params.len() {
+        anyhow::bail!(
+            "{} takes {} argument(s) ({}), got {}",
+            method,
+            function.params.len(),
+            function
+                .params
+                .iter()
+                .map(|param| format!("{}: {}", param.name, param.param_type.display_name()))
+                .collect::<Vec<_>>()
+                .join(", "),
+            values.len()
+        );
+    }
+
+    function
+        .params
+        .iter()
+        .zip(values)
+        .map(|(param, value)| {
+            to_scval(value, &param.param_type, abi)
+                .with_context(|| format!("argument '{}'", param.name))
+        })
+        .collect()
+}
+
+/// Converts `value` to the declared type `ty`, looking custom types up in
+/// `abi`.
+pub fn to_scval(value: &Value, ty: &SorobanType, abi: &ContractABI) -> Result<ScVal> {
+    let expected = || format!("expected {}, got {}", ty.display_name(), value);
+    Ok(match ty {
+        SorobanType::Bool => ScVal::Bool(value.as_bool().with_context(expected)?),
+        SorobanType::I32 => ScVal::I32(integer(value)?),
+        SorobanType::U32 => ScVal::U32(integer(value)?),
+        SorobanType::I64 => ScVal::I64(integer(value)?),
+        SorobanType::U64 => ScVal::U64(integer(value)?),
+        SorobanType::I128 => integer::<i128>(value)?.into(),
+        SorobanType::U128 => integer::<u128>(value)?.into(),
+        SorobanType::I256 => ScVal::I256(parse_i256(&digits(value)?)?),
+        SorobanType::U256 => ScVal::U256(parse_u256(&digits(value)?)?),
+        SorobanType::Timepoint => ScVal::Timepoint(TimePoint(integer(value)?)),
+        SorobanType::Duration => ScVal::Duration(ScDuration(integer(value)?)),
+        SorobanType::Symbol => symbol(value.as_str().with_context(expected)?)?,
+        SorobanType::String => string(value.as_str().with_context(expected)?)?,
+        SorobanType::Bytes => bytes(value.as_str().with_context(expected)?, None)?,
+        SorobanType::BytesN { n } => bytes(value.as_str().with_context(expected)?, Some(*n))?,
+        SorobanType::Address => address(value.as_str().with_context(expected)?)?,
+        SorobanType::Void => match value {
+            Value::Null => ScVal::Void,
+            _ => anyhow::bail!(expected()),
+        },
+        SorobanType::Option { value_type } => match value {
+            Value::Null => ScVal::Void,
+            value => to_scval(value, value_type, abi)?,
+        },
+        SorobanType::Vec { element_type } => vec(value
+            .as_array()
+            .with_context(expected)?
+            .iter()
+            .map(|element| to_scval(element, element_type, abi))
+            .collect::<Result<_>>()?)?,
+        SorobanType::Tuple { elements } => {
+            let values = value.as_array().with_context(expected)?;
+            if values.len() != elements.len() {
+                anyhow::bail!(expected());
+            }
+            vec(values
+                .iter()
+                .zip(elements)
+                .map(|(value, ty)| to_scval(value, ty, abi))
+                .collect::<Result<_>>()?)?
+        }
+        SorobanType::Map {
+            key_type,
+            value_type,
+        } => {
+            let pairs: Vec<(ScVal, ScVal)> = match value {
+                // Object keys are strings, so they are read as the key type
+                Value::Object(entries) => entries
+                    .iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            map_key(key, key_type, abi)?,
+                            to_scval(value, value_type, abi)?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?,
+                Value::Array(entries) => entries
+                    .iter()
+                    .map(|entry| match entry.as_array().map(Vec::as_slice) {
+                        Some([key, value]) => Ok((
+                            to_scval(key, key_type, abi)?,
+                            to_scval(value, value_type, abi)?,
+                        )),
+                        _ => anyhow::bail!("expected [key, value] pairs, got {}", entry),
+                    })
+                    .collect::<Result<_>>()?,
+                _ => anyhow::bail!(expected()),
+            };
+            map(pairs)?
+        }
+        SorobanType::Struct { name, fields } => structure(value, name, fields, abi)?,
+        SorobanType::Enum { name, variants } => enumeration(value, name, variants, abi)?,
+        SorobanType::Custom { name } => {
+            let declared = abi.types.get(name).with_context(|| {
+                format!("type {} is not declared in the contract's interface", name)
+            })?;
+            to_scval(value, declared, abi)?
+        }
+        SorobanType::Result { .. } => anyhow::bail!("{} cannot be an argument", ty.display_name()),
+    })
+}
+
+/// Guesses the type of `value`, see the module docs.
+pub fn infer(value: &Value) -> Result<ScVal> {
+    Ok(match value {
+        Value::Null => ScVal::Void,
+        Value::Bool(flag) => ScVal::Bool(*flag),
+        Value::Number(_) => integer::<i128>(value)?.into(),
+        Value::String(text) if looks_like_address(text) => address(text)?,
+        Value::String(text) => string(text)?,
+        Value::Array(values) => vec(values.iter().map(infer).collect::<Result<_>>()?)?,
+        Value::Object(entries) => match typed_literal(entries) {
+            Some((ty, value)) => to_scval(value, &ty, &ContractABI::new(String::new()))?,
+            None => map(entries
+                .iter()
+                .map(|(key, value)| Ok((symbol(key)?, infer(value)?)))
+                .collect::<Result<_>>()?)?,
+        },
+    })
+}
+
+fn typed_literal(entries: &serde_json::Map<String, Value>) -> Option<(SorobanType, &Value)> {
+    let (name, value) = entries.iter().next().filter(|_| entries.len() == 1)?;
+    TYPED_LITERALS
+        .contains(&name.as_str())
+        .then(|| (SorobanType::from_type_string(name), value))
+}
+
+fn looks_like_address(text: &str) -> bool {
+    text.len() == 56 && (text.starts_with('G') || text.starts_with('C'))
+}
+
+/// A struct is a map keyed by field name, or a vector when its fields are
+/// positional (`0`, `1`, ...)
+fn structure(
+    value: &Value,
+    name: &str,
+    fields: &[StructField],
+    abi: &ContractABI,
+) -> Result<ScVal> {
+    let positional = fields
+        .iter()
+        .enumerate()
+        .all(|(index, field)| field.name == index.to_string());
+    if positional && !fields.is_empty() {
+        let values = value
+            .as_array()
+            .filter(|values| values.len() == fields.len())
+            .with_context(|| format!("expected {} as an array of {} values", name, fields.len()))?;
+        return vec(values
+            .iter()
+            .zip(fields)
+            .map(|(value, field)| to_scval(value, &field.field_type, abi))
+            .collect::<Result<_>>()?);
+    }
+
+    let entries = value
+        .as_object()
+        .with_context(|| format!("expected {} as an object, got {}", name, value))?;
+    if let Some(unknown) = entries
+        .keys()
+        .find(|key| !fields.iter().any(|field| &field.name == *key))
+    {
+        anyhow::bail!("{} has no field '{}'", name, unknown);
+    }
+    map(fields
+        .iter()
+        .map(|field| {
+            let value = entries.get(&field.name).unwrap_or(&Value::Null);
+            let converted = to_scval(value, &field.field_type, abi)
+                .with_context(|| format!("field '{}' of {}", field.name, name))?;
+            Ok((symbol(&field.name)?, converted))
+        })
+        .collect::<Result<_>>()?)
+}
+
+/// An enum with values is a `u32`, given as the case name or its value. A
+/// union case is a vector of its name and values: `"Native"`, or
+/// `{"Token": "C…"}` / `{"Token": ["C…"]}` for a case that carries data.
+fn enumeration(
+    value: &Value,
+    name: &str,
+    variants: &[EnumVariant],
+    abi: &ContractABI,
+) -> Result<ScVal> {
+    let find = |case: &str| {
+        variants
+            .iter()
+            .find(|variant| variant.name == case)
+            .with_context(|| {
+                let names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+                format!(
+                    "{} has no case '{}' (expected one of: {})",
+                    name,
+                    case,
+                    names.join(", ")
+                )
+            })
+    };
+
+    if variants.iter().all(|variant| variant.value.is_some()) {
+        return match value {
+            Value::String(case) => Ok(ScVal::U32(find(case)?.value.unwrap_or_default())),
+            Value::Number(_) => {
+                let number: u32 = integer(value)?;
+                if !variants.iter().any(|variant| variant.value == Some(number)) {
+                    anyhow::bail!("{} has no case with value {}", name, number);
+                }
+                Ok(ScVal::U32(number))
+            }
+            _ => anyhow::bail!("expected a case of {}, got {}", name, value),
+        };
+    }
+
+    let (case, data) = match value {
+        Value::String(case) => (case.as_str(), None),
+        Value::Object(entries) if entries.len() == 1 => {
+            let (case, data) = entries.iter().next().expect("one entry");
+            (case.as_str(), Some(data))
+        }
+        _ => anyhow::bail!("expected a case of {}, got {}", name, value),
+    };
+    let variant = find(case)?;
+    let fields = variant.fields.as_deref().unwrap_or_default();
+    let data: Vec<&Value> = match data {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Array(values)) if fields.len() != 1 => values.iter().collect(),
+        Some(value) => vec![value],
+    };
+    if data.len() != fields.len() {
+        anyhow::bail!(
+            "{}::{} takes {} value(s), got {}",
+            name,
+            case,
+            fields.len(),
+            data.len()
+        );
+    }
+
+    let mut values = vec![symbol(case)?];
+    for (value, field) in data.into_iter().zip(fields) {
+        values.push(to_scval(value, &field.field_type, abi)?);
+    }
+    vec(values)
+}
+
+fn map_key(key: &str, ty: &SorobanType, abi: &ContractABI) -> Result<ScVal> {
+    let value = match ty {
+        SorobanType::Bool => Value::Bool(key.parse().context("expected a bool key")?),
+        SorobanType::I32
+        | SorobanType::U32
+        | SorobanType::I64
+        | SorobanType::U64
+        | SorobanType::Timepoint
+        | SorobanType::Duration => Value::Number(
+            serde_json::Number::from_str(key)
+                .with_context(|| format!("expected a number key, got {}", key))?,
+        ),
+        _ => Value::String(key.to_string()),
+    };
+    to_scval(&value, ty, abi)
+}
+
+/// A JSON number, or a string of digits for values beyond what JSON
+/// numbers hold exactly
+fn integer<T: FromStr>(value: &Value) -> Result<T> {
+    let text = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.trim().to_string(),
+        _ => anyhow::bail!("expected an integer, got {}", value),
+    };
+    text.parse()
+        .map_err(|_| anyhow::anyhow!("{} is not an integer in range", text))
+}
+
+fn digits(value: &Value) -> Result<String> {
+    match value {
+        Value::Number(number) => Ok(number.to_string()),
+        Value::String(text) => Ok(text.trim().to_string()),
+        _ => anyhow::bail!("expected an integer, got {}", value),
+    }
+}
+
+/// Decimal digits as four 64-bit limbs, most significant first
+fn parse_limbs(digits: &str) -> Result<[u64; 4]> {
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+        anyhow::bail!("{} is not an integer", digits);
+    }
+    let mut limbs = [0u64; 4];
+    for digit in digits.bytes() {
+        let mut carry = u128::from(digit - b'0');
+        for limb in limbs.iter_mut().rev() {
+            let product = u128::from(*limb) * 10 + carry;
+            *limb = product as u64;
+            carry = product >> 64;
+        }
+        if carry != 0 {
+            anyhow::bail!("{} does not fit in 256 bits", digits);
+        }
+    }
+    Ok(limbs)
+}
+
+fn parse_u256(digits: &str) -> Result<UInt256Parts> {
+    let [hi_hi, hi_lo, lo_hi, lo_lo] = parse_limbs(digits)?;
+    Ok(UInt256Parts {
+        hi_hi,
+        hi_lo,
+        lo_hi,
+        lo_lo,
+    })
+}
+
+fn parse_i256(digits: &str) -> Result<Int256Parts> {
+    let (negative, magnitude) = match digits.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, digits),
+    };
+    let mut limbs = parse_limbs(magnitude)?;
+    let top_bit = limbs[0] >> 63 == 1;
+    let is_min = limbs == [1 << 63, 0, 0, 0];
+    if top_bit && !(negative && is_min) {
+        anyhow::bail!("{} does not fit in i256", digits);
+    }
+    if negative {
+        // Two's complement: invert and add one
+        let mut carry = true;
+        for limb in limbs.iter_mut().rev() {
+            let (sum, overflow) = (!*limb).overflowing_add(u64::from(carry));
+            *limb = sum;
+            carry = overflow;
+        }
+    }
+    Ok(Int256Parts {
+        hi_hi: limbs[0] as i64,
+        hi_lo: limbs[1],
+        lo_hi: limbs[2],
+        lo_lo: limbs[3],
+    })
+}
+
+/// Symbols are at most 32 characters from `[a-zA-Z0-9_]`
+pub fn to_symbol(text: &str) -> Result<ScSymbol> {
+    let valid = text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    match ScSymbol::try_from(text) {
+        Ok(symbol) if valid => Ok(symbol),
+        _ => anyhow::bail!("'{}' is not a valid symbol", text),
+    }
+}
+
+fn symbol(text: &str) -> Result<ScVal> {
+    Ok(ScVal::Symbol(to_symbol(text)?))
+}
+
+fn string(text: &str) -> Result<ScVal> {
+    let string = text
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("string is too long"))?;
+    Ok(ScVal::String(ScString(string)))
+}
+
+/// Hex, with or without `0x`
+fn bytes(text: &str, length: Option<u32>) -> Result<ScVal> {
+    let decoded = hex::decode(text.trim_start_matches("0x"))
+        .with_context(|| format!("'{}' is not hex", text))?;
+    if let Some(length) = length {
+        if decoded.len() != length as usize {
+            anyhow::bail!("expected {} bytes, got {}", length, decoded.len());
+        }
+    }
+    let bytes = decoded
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("bytes are too long"))?;
+    Ok(ScVal::Bytes(ScBytes(bytes)))
+}
+
+fn address(text: &str) -> Result<ScVal> {
+    let address = ScAddress::from_str(text)
+        .map_err(|_| anyhow::anyhow!("'{}' is not a G… or C… address", text))?;
+    Ok(ScVal::Address(address))
+}
+
+fn vec(values: Vec<ScVal>) -> Result<ScVal> {
+    let values = values
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("too many values"))?;
+    Ok(ScVal::Vec(Some(ScVec(values))))
+}
+
+/// Entries sorted by key, as the host requires
+fn map(pairs: Vec<(ScVal, ScVal)>) -> Result<ScVal> {
+    let entries = pairs.into_iter().map(|(key, val)| ScMapEntry { key, val });
+    let map =
+        ScMap::sorted_from(entries).map_err(|_| anyhow::anyhow!("map keys must be distinct"))?;
+    Ok(ScVal::Map(Some(map)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const ACCOUNT: &str = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+    const CONTRACT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+
+    fn token() -> ContractABI {
+        let spec = include_str!("../../backend/contract_abi/tests/fixtures/token_abi.json");
+        contract_abi::parse_json_spec(spec, "token").unwrap()
+    }
+
+    fn sym(text: &str) -> ScVal {
+        symbol(text).unwrap()
+    }
+
+    #[test]
+    fn converts_arguments_to_the_declared_types() {
+        let abi = token();
+        let args = method_args(
+            Some(&abi),
+            "transfer",
+            &json!([
+                ACCOUNT,
+                CONTRACT,
+                "170141183460469231731687303715884105727",
+                null
+            ]),
+        )
+        .unwrap();
+
+        assert!(matches!(&args[0], ScVal::Address(ScAddress::Account(_))));
+        assert!(matches!(&args[1], ScVal::Address(ScAddress::Contract(_))));
+        assert_eq!(args[2], ScVal::from(i128::MAX));
+        assert_eq!(args[3], ScVal::Void);
+
+        // By name, in any order
+        let named = method_args(
+            Some(&abi),
+            "transfer",
+            &json!({"amount": 5, "to": CONTRACT, "from": ACCOUNT, "memo": "00ff"}),
+        )
+        .unwrap();
+        assert_eq!(named[2], ScVal::from(5i128));
+        assert_eq!(named[3], bytes("00ff", None).unwrap());
+    }
+
+    #[test]
+    fn converts_custom_types() {
+        let abi = token();
+        let metadata = json!({
+            "name": "Token",
+            "symbol": "TKN",
+            "decimals": 7,
+            "issued_at": 1700000000,
+            "icon_hash": null,
+        });
+        let args = method_args(
+            Some(&abi),
+            "initialize",
+            &json!([ACCOUNT, metadata, {ACCOUNT: "100"}]),
+        )
+        .unwrap();
+
+        let ScVal::Map(Some(fields)) = &args[1] else {
+            panic!("structs are maps: {:?}", args[1]);
+        };
+        let keys: Vec<&ScVal> = fields.iter().map(|entry| &entry.key).collect();
+        assert_eq!(
+            keys,
+            [
+                &sym("decimals"),
+                &sym("icon_hash"),
+                &sym("issued_at"),
+                &sym("name"),
+                &sym("symbol")
+            ]
+        );
+        assert_eq!(fields[0].val, ScVal::U32(7));
+        assert_eq!(fields[2].val, ScVal::Timepoint(TimePoint(1_700_000_000)));
+
+        let asset = |value| method_args(Some(&abi), "balance", &json!([ACCOUNT, value])).unwrap();
+        assert_eq!(asset(json!("Native"))[1], vec(vec![sym("Native")]).unwrap());
+        assert_eq!(
+            asset(json!({"Token": CONTRACT}))[1],
+            vec(vec![sym("Token"), address(CONTRACT).unwrap()]).unwrap()
+        );
+    }
+
+    #[test]
+    fn explains_what_does_not_fit() {
+        let abi = token();
+        let err = |method, args| {
+            format!(
+                "{:#}",
+                method_args(Some(&abi), method, &args).expect_err("should fail")
+            )
+        };
+
+        assert!(err("mint", json!([])).contains("no method 'mint'"));
+        assert!(err("transfer", json!([ACCOUNT])).contains("takes 4 argument(s)"));
+        assert!(err("transfer", json!({"value": 1})).contains("no parameter 'value'"));
+        assert!(
+            err("transfer", json!([ACCOUNT, CONTRACT, 1.5, null])).contains("argument 'amount'")
+        );
+        assert!(err("balance", json!([ACCOUNT, "Wrapped"])).contains("no case 'Wrapped'"));
+        assert!(err("allowances", json!([ACCOUNT, "00"])).contains("Groth16Proof"));
+    }
+
+    #[test]
+    fn infers_types_without_an_interface() {
+        let args = method_args(
+            None,
+            "anything",
+            &json!([7, ACCOUNT, "hello", {"u32": 3}, {"symbol": "XLM"}, [true, null]]),
+        )
+        .unwrap();
+        assert_eq!(args[0], ScVal::from(7i128));
+        assert_eq!(args[1], address(ACCOUNT).unwrap());
+        assert_eq!(args[2], string("hello").unwrap());
+        assert_eq!(args[3], ScVal::U32(3));
+        assert_eq!(args[4], sym("XLM"));
+        assert_eq!(args[5], vec(vec![ScVal::Bool(true), ScVal::Void]).unwrap());
+
+        assert!(method_args(None, "anything", &json!({"amount": 1})).is_err());
+    }
+
+    #[test]
+    fn parses_256_bit_integers() {
+        assert_eq!(
+            parse_u256("340282366920938463463374607431768211456").unwrap(),
+            UInt256Parts {
+                hi_hi: 0,
+                hi_lo: 1,
+                lo_hi: 0,
+                lo_lo: 0
+            }
+        );
+        assert_eq!(
+            parse_i256("-1").unwrap(),
+            Int256Parts {
+                hi_hi: -1,
+                hi_lo: u64::MAX,
+                lo_hi: u64::MAX,
+                lo_lo: u64::MAX
+            }
+        );
+        let min = format!(
+            "-{}",
+            "57896044618658097711785492504343953926634992332820282019728792003956564819968"
+        );
+        assert_eq!(parse_i256(&min).unwrap().hi_hi, i64::MIN);
+        assert!(parse_i256(&min[1..]).is_err());
+        assert!(parse_u256(&"9".repeat(80)).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+//! Invoking a deployed contract through Soroban RPC's `simulateTransaction`,
+//! for `profile --remote`.
+//!
+//! Simulation runs the invocation against the current ledger without
+//! submitting anything, so no account or signature is needed: the
+//! transaction comes from the all-zero account and the RPC records the
+//! authorizations a real submission would need instead of checking them.
+
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use contract_abi::ContractABI;
+use serde::Deserialize;
+use serde_json::json;
+use soroban_profiler::ResourceUsage;
+use stellar_xdr::curr::{
+    DecoratedSignature, HostFunction, InvokeContractArgs, InvokeHostFunctionOp, Limits, Memo,
+    MuxedAccount, Operation, OperationBody, Preconditions, ReadXdr, ScAddress, ScVal,
+    SequenceNumber, SignatureHint, SorobanAuthorizationEntry, SorobanTransactionData, Transaction,
+    TransactionEnvelope, TransactionExt, TransactionV1Envelope, Uint256, WriteXdr,
+};
+
+/// Simulation ignores the fee, but the envelope must carry one
+const BASE_FEE: u32 = 100;
+
+/// What a successful simulation reported
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub resources: ResourceUsage,
+    /// Resource fee in stroops, as the network would charge it
+    pub min_resource_fee: Option<u64>,
+    /// Authorizations a real submission must sign
+    pub auth: Vec<SorobanAuthorizationEntry>,
+    /// Archived entries must be restored before the invocation can succeed
+    pub needs_restore: bool,
+}
+
+/// A simulation the RPC ran but the host rejected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimulationFailure {
+    pub reason: String,
+    /// The RPC's message, including the host's event log
+    pub detail: String,
+}
+
+impl std::fmt::Display for SimulationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl std::error::Error for SimulationFailure {}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<SimulateResult>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulateResult {
+    error: Option<String>,
+    transaction_data: Option<String>,
+    min_resource_fee: Option<String>,
+    /// Dropped by newer RPC releases, the only source of memory usage
+    cost: Option<Cost>,
+    #[serde(default)]
+    results: Vec<HostFunctionResult>,
+    restore_preamble: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Cost {
+    mem_bytes: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HostFunctionResult {
+    #[serde(default)]
+    auth: Vec<String>,
+}
+
+/// A transaction invoking `method` on `contract` with `args`, from the
+/// all-zero account
+pub fn invocation(contract: &str, method: &str, args: Vec<ScVal>) -> Result<TransactionEnvelope> {
+    let contract_address = ScAddress::from_str(contract)
+        .map_err(|_| anyhow::anyhow!("'{}' is not a C… contract address", contract))?;
+    if !matches!(contract_address, ScAddress::Contract(_)) {
+        anyhow::bail!("'{}' is an account, not a contract", contract);
+    }
+    let function_name = crate::scval::to_symbol(method)
+        .with_context(|| format!("'{}' is not a valid method name", method))?;
+
+    let operation = Operation {
+        source_account: None,
+        body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+            host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                contract_address,
+                function_name,
+                args: args
+                    .try_into()
+                    .map_err(|_| anyhow::anyhow!("too many arguments"))?,
+            }),
+            auth: Default::default(),
+        }),
+    };
+    let transaction = Transaction {
+        source_account: MuxedAccount::Ed25519(Uint256([0; 32])),
+        fee: BASE_FEE,
+        seq_num: SequenceNumber(1),
+        cond: Preconditions::None,
+        memo: Memo::None,
+        operations: vec![operation].try_into().expect("one operation"),
+        ext: TransactionExt::V0,
+    };
+    Ok(TransactionEnvelope::Tx(TransactionV1Envelope {
+        tx: transaction,
+        signatures: Default::default(),
+    }))
+}
+
+/// Sends `envelope` to `simulateTransaction`. A host error comes back as a
+/// [`SimulationFailure`] naming what went wrong; `abi` gives contract
+/// errors their names.
+pub async fn simulate(
+    rpc_url: &str,
+    envelope: &TransactionEnvelope,
+    abi: Option<&ContractABI>,
+) -> Result<Simulation> {
+    let request = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "simulateTransaction",
+        "params": {
+            "transaction": envelope.to_xdr_base64(Limits::none())?,
+        },
+    });
+    let response: RpcResponse = crate::http::transport_builder()
+        .build()
+        .context("Failed to build the RPC client")?
+        .post(rpc_url)
+        .json(&request)
+        .send()
+        .await
+        .with_context(|| format!("Failed to reach Soroban RPC at {}", rpc_url))?
+        .error_for_status()
+        .with_context(|| format!("Soroban RPC at {} rejected the request", rpc_url))?
+        .json()
+        .await
+        .context("Invalid response from simulateTransaction")?;
+
+    if let Some(error) = response.error {
+        anyhow::bail!(
+            "simulateTransaction failed: {} (code {})",
+            error.message,
+            error.code
+        );
+    }
+    let result = response
+        .result
+        .context("simulateTransaction returned no result")?;
+    if let Some(error) = result.error {
+        return Err(describe_failure(&error, abi).into());
+    }
+    parse_result(result, envelope)
+}
+
+fn parse_result(result: SimulateResult, envelope: &TransactionEnvelope) -> Result<Simulation> {
+    let data = SorobanTransactionData::from_xdr_base64(
+        result
+            .transaction_data
+            .as_deref()
+            .context("simulateTransaction returned no transactionData")?,
+        Limits::none(),
+    )
+    .context("Invalid transactionData from simulateTransaction")?;
+    let auth = result
+        .results
+        .iter()
+        .flat_map(|result| &result.auth)
+        .map(|entry| SorobanAuthorizationEntry::from_xdr_base64(entry, Limits::none()))
+        .collect::<Result<Vec<_>, _>>()
+        .context("Invalid auth entry from simulateTransaction")?;
+
+    let footprint = &data.resources.footprint;
+    let number = |value: &Option<String>| value.as_deref().and_then(|v| v.parse::<u64>().ok());
+    let resources = ResourceUsage {
+        cpu_instructions: Some(u64::from(data.resources.instructions)),
+        memory_bytes: result
+            .cost
+            .as_ref()
+            .and_then(|cost| number(&cost.mem_bytes)),
+        read_entries: Some(footprint.read_only.len() as u64),
+        write_entries: Some(footprint.read_write.len() as u64),
+        read_bytes: Some(u64::from(data.resources.disk_read_bytes)),
+        write_bytes: Some(u64::from(data.resources.write_bytes)),
+        transaction_size_bytes: Some(submitted_size(envelope, &data, &auth)?),
+    };
+
+    Ok(Simulation {
+        resources,
+        min_resource_fee: number(&result.min_resource_fee),
+        auth,
+        needs_restore: result.restore_preamble.is_some(),
+    })
+}
+
+/// Size of the transaction as it would be submitted: with the simulated
+/// resources and auth entries attached and one signature
+fn submitted_size(
+    envelope: &TransactionEnvelope,
+    data: &SorobanTransactionData,
+    auth: &[SorobanAuthorizationEntry],
+) -> Result<u64> {
+    let TransactionEnvelope::Tx(envelope) = envelope else {
+        anyhow::bail!("expected a v1 transaction envelope");
+    };
+    let mut submitted = envelope.clone();
+    submitted.tx.ext = TransactionExt::V1(data.clone());
+    let mut operations = submitted.tx.operations.to_vec();
+    if let Some(OperationBody::InvokeHostFunction(op)) =
+        operations.first_mut().map(|operation| &mut operation.body)
+    {
+        op.auth = auth
+            .to_vec()
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("too many auth entries"))?;
+    }
+    submitted.tx.operations = operations.try_into().expect("the same operations");
+    submitted.signatures = vec![DecoratedSignature {
+        hint: SignatureHint([0; 4]),
+        signature: vec![0; 64].try_into().expect("64 bytes"),
+    }]
+    .try_into()
+    .expect("one signature");
+
+    let bytes = TransactionEnvelope::Tx(submitted).to_xdr(Limits::none())?;
+    Ok(bytes.len() as u64)
+}
+
+/// Names the host error in an RPC `error` message, e.g.
+/// `HostError: Error(Contract, #2)` followed by the event log.
+pub fn describe_failure(error: &str, abi: Option<&ContractABI>) -> SimulationFailure {
+    let reason = match host_error(error) {
+        Some(("Contract", code)) => {
+            let code = code.trim_start_matches('#');
+            let name = abi.and_then(|abi| {
+                abi.errors
+                    .iter()
+                    .find(|known| code.parse() == Ok(known.code))
+            });
+            match name {
+                Some(known) => format!("the contract returned error {} ({})", code, known.name),
+                None => format!("the contract returned error {}", code),
+            }
+        }
+        Some(("Auth", _)) => {
+            "authorization failed: the invocation needs a signature the simulation could not record"
+                .to_string()
+        }
+        Some(("WasmVm", _)) => {
+            "the contract trapped (a panic, an unwrap on an error or an arithmetic overflow)"
+                .to_string()
+        }
+        Some(("Budget", "ExceededLimit")) => {
+            "the invocation exceeded the network's per-transaction resource limits".to_string()
+        }
+        Some(("Storage", "MissingValue")) => {
+            "a ledger entry the invocation reads does not exist or has been archived".to_string()
+        }
+        Some(("Value" | "Object", _)) => {
+            "an argument does not have the type the contract expects".to_string()
+        }
+        Some((kind, code)) => format!("the host failed with {} error {}", kind, code),
+        None => "simulation failed".to_string(),
+    };
+    SimulationFailure {
+        reason,
+        detail: error.trim().to_string(),
+    }
+}
+
+/// The type and code of the first `Error(Type, Code)` in `message`
+fn host_error(message: &str) -> Option<(&str, &str)> {
+    let start = message.find("Error(")? + "Error(".len();
+    let end = start + message[start..].find(')')?;
+    let (kind, code) = message[start..end].split_once(',')?;
+    Some((kind.trim(), code.trim()))
+}
+
+/// Prints the authorizations a real submission must sign and whether
+/// archived entries must be restored first
+pub fn print_notes(simulation: &Simulation) {
+    if !simulation.auth.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "Note: the invocation requires {} authorization(s); simulation recorded them, a real submission must have them signed.",
+                simulation.auth.len()
+            )
+            .yellow()
+        );
+    }
+    if simulation.needs_restore {
+        println!(
+            "{}",
+            "Note: some ledger entries are archived and must be restored before this invocation can run; the resources above exclude the restore."
+                .yellow()
+        );
+    }
+}
+
+/// Prints a failed simulation: what went wrong, then the RPC's message
+pub fn print_failure(failure: &SimulationFailure) {
+    println!("{} {}", "✗ Simulation failed:".red().bold(), failure.reason);
+    for line in failure.detail.lines() {
+        println!("  {}", line.dimmed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+
+    fn token() -> ContractABI {
+        let spec = include_str!("../../backend/contract_abi/tests/fixtures/token_abi.json");
+        contract_abi::parse_json_spec(spec, "token").unwrap()
+    }
+
+    #[test]
+    fn builds_an_invocation_from_the_zero_account() {
+        let envelope = invocation(CONTRACT, "decimals", Vec::new()).unwrap();
+        let TransactionEnvelope::Tx(envelope) = &envelope else {
+            panic!("expected a v1 envelope");
+        };
+        assert_eq!(
+            envelope.tx.source_account,
+            MuxedAccount::Ed25519(Uint256([0; 32]))
+        );
+        let OperationBody::InvokeHostFunction(op) = &envelope.tx.operations[0].body else {
+            panic!("expected an invocation");
+        };
+        let HostFunction::InvokeContract(call) = &op.host_function else {
+            panic!("expected a contract call");
+        };
+        assert_eq!(call.contract_address.to_string(), CONTRACT);
+        assert_eq!(call.function_name.to_string(), "decimals");
+
+        let account = "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF";
+        assert!(invocation(account, "decimals", Vec::new()).is_err());
+        assert!(invocation(CONTRACT, "not a symbol", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn maps_simulated_resources() {
+        use stellar_xdr::curr::{
+            ContractDataDurability, LedgerFootprint, LedgerKey, LedgerKeyContractData,
+            SorobanResources, SorobanTransactionDataExt,
+        };
+
+        let entry = |key: &str| {
+            LedgerKey::ContractData(LedgerKeyContractData {
+                contract: ScAddress::from_str(CONTRACT).unwrap(),
+                key: ScVal::Symbol(crate::scval::to_symbol(key).unwrap()),
+                durability: ContractDataDurability::Persistent,
+            })
+        };
+        let data = SorobanTransactionData {
+            ext: SorobanTransactionDataExt::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: vec![entry("Admin"), entry("Metadata")].try_into().unwrap(),
+                    read_write: vec![entry("Balance")].try_into().unwrap(),
+                },
+                instructions: 3_250_000,
+                disk_read_bytes: 6_144,
+                write_bytes: 296,
+            },
+            resource_fee: 61_000,
+        };
+        let result: SimulateResult = serde_json::from_value(json!({
+            "transactionData": data.to_xdr_base64(Limits::none()).unwrap(),
+            "minResourceFee": "61000",
+            "cost": { "cpuInsns": "3250000", "memBytes": "1200000" },
+            "results": [{ "auth": [], "xdr": "AAAAAQ==" }],
+            "latestLedger": 1200,
+        }))
+        .unwrap();
+
+        let envelope = invocation(CONTRACT, "transfer", Vec::new()).unwrap();
+        let bare = envelope.to_xdr(Limits::none()).unwrap().len() as u64;
+        let simulation = parse_result(result, &envelope).unwrap();
+        let resources = &simulation.resources;
+        assert_eq!(resources.cpu_instructions, Some(3_250_000));
+        assert_eq!(resources.memory_bytes, Some(1_200_000));
+        assert_eq!(resources.read_entries, Some(2));
+        assert_eq!(resources.write_entries, Some(1));
+        assert_eq!(resources.read_bytes, Some(6_144));
+        assert_eq!(resources.write_bytes, Some(296));
+        assert!(resources.transaction_size_bytes.unwrap() > bare + 64);
+        assert_eq!(simulation.min_resource_fee, Some(61_000));
+        assert!(simulation.auth.is_empty());
+        assert!(!simulation.needs_restore);
+    }
+
+    #[test]
+    fn names_host_errors() {
+        let abi = token();
+        let contract = describe_failure(
+            "HostError: Error(Contract, #2)\n\nEvent log (newest first):\n   0: [Diagnostic Event] ...",
+            Some(&abi),
+        );
+        assert_eq!(
+            contract.reason,
+            "the contract returned error 2 (Error::Frozen)"
+        );
+        assert!(contract.detail.contains("Event log"));
+
+        let trap = describe_failure("HostError: Error(WasmVm, InvalidAction)", None);
+        assert!(trap.reason.starts_with("the contract trapped"));
+        let auth = describe_failure("HostError: Error(Auth, InvalidAction)", None);
+        assert!(auth.reason.starts_with("authorization failed"));
+        assert_eq!(
+            describe_failure("HostError: Error(Contract, #7)", Some(&abi)).reason,
+            "the contract returned error 7"
+        );
+        assert_eq!(describe_failure("boom", None).reason, "simulation failed");
+    }
+}
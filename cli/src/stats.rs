@@ -0,0 +1,146 @@
+//! `stats`: registry totals, or with `--timeseries` how publishing,
+//! verification and download activity developed over a window.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+/// Sparkline glyphs, lowest first
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Totals {
+    total_contracts: i64,
+    verified_contracts: i64,
+    total_publishers: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Point {
+    bucket: DateTime<Utc>,
+    value: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Series {
+    metric: String,
+    total: i64,
+    points: Vec<Point>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Timeseries {
+    interval: String,
+    window: String,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    series: Vec<Series>,
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(url: &str, action: &str) -> Result<T> {
+    let response = crate::http::client()
+        .get(url)
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        bail!("Failed to {}: {} ({})", action, message, status);
+    }
+    response
+        .json()
+        .await
+        .context("Failed to parse API response")
+}
+
+pub async fn totals(api_url: &str, json: bool) -> Result<()> {
+    let totals: Totals = get_json(&format!("{}/api/stats", api_url), "fetch stats").await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&totals)?);
+        return Ok(());
+    }
+
+    println!("{}: {}", "Contracts".bold(), totals.total_contracts);
+    println!("{}: {}", "Verified".bold(), totals.verified_contracts);
+    println!("{}: {}", "Publishers".bold(), totals.total_publishers);
+    Ok(())
+}
+
+pub async fn timeseries(
+    api_url: &str,
+    metrics: &str,
+    interval: &str,
+    window: &str,
+    json: bool,
+) -> Result<()> {
+    let url = format!(
+        "{}/api/analytics/timeseries?metrics={}&interval={}&window={}",
+        api_url, metrics, interval, window
+    );
+    let timeseries: Timeseries = get_json(&url, "fetch time series").await?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&timeseries)?);
+        return Ok(());
+    }
+
+    println!(
+        "{} per {}, last {} ({} to {})",
+        "Registry activity".bold(),
+        timeseries.interval,
+        timeseries.window,
+        timeseries.from.format("%Y-%m-%d"),
+        timeseries.to.format("%Y-%m-%d")
+    );
+    let width = timeseries
+        .series
+        .iter()
+        .map(|s| s.metric.len())
+        .max()
+        .unwrap_or(0);
+    for series in &timeseries.series {
+        let values: Vec<i64> = series.points.iter().map(|p| p.value).collect();
+        println!(
+            "  {:<width$}  {:>6}  {}",
+            series.metric,
+            series.total,
+            sparkline(&values).cyan(),
+            width = width
+        );
+    }
+    Ok(())
+}
+
+/// One glyph per value, scaled to the largest. Any non-zero value gets
+/// at least the second-lowest bar so it stands out from empty buckets.
+fn sparkline(values: &[i64]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| {
+            if value <= 0 || max <= 0 {
+                return BARS[0];
+            }
+            let level = (value * (BARS.len() as i64 - 1) + max - 1) / max;
+            BARS[level.clamp(1, BARS.len() as i64 - 1) as usize]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_to_the_peak() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[0, 0, 0]), "▁▁▁");
+        assert_eq!(sparkline(&[0, 1, 7, 14]), "▁▂▅█");
+        // small but non-zero values stay visible next to a large peak
+        assert_eq!(sparkline(&[1, 1000]), "▂█");
+    }
+}
@@ -0,0 +1,329 @@
+//! Per-contract event subscriptions: `subscribe`, `subscriptions` and
+//! `unsubscribe`.
+//!
+//! A subscription belongs to the identity of the API token in
+//! `SOROBAN_REGISTRY_API_TOKEN`. The registry sends each subscribed event to
+//! a webhook (as a JSON POST) or an email address, retrying failed
+//! deliveries; `subscriptions` shows how recent deliveries went.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::table_format::render_table_with_details;
+
+/// Events a subscription can ask for, as the API names them
+pub const EVENTS: [&str; 5] = [
+    "version",
+    "verification",
+    "incident",
+    "security",
+    "freshness",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: Uuid,
+    pub contract_id: Uuid,
+    pub event_types: Vec<String>,
+    pub channel: String,
+    pub target: String,
+    pub consecutive_failures: i32,
+    pub last_error: Option<String>,
+    pub last_delivered_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    /// Only filled in by `GET /api/me/subscriptions`
+    #[serde(default)]
+    pub recent_deliveries: Vec<Delivery>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Delivery {
+    pub event_type: String,
+    pub subject: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Parses `--events`, e.g. "version,incident". Event names are
+/// case-insensitive; `all` stands for every event.
+pub fn parse_events(input: &str) -> Result<Vec<String>> {
+    let mut events: Vec<String> = Vec::new();
+    for event in input.split(',').map(|e| e.trim().to_lowercase()) {
+        if event.is_empty() {
+            continue;
+        }
+        if event == "all" {
+            return Ok(EVENTS.iter().map(|e| e.to_string()).collect());
+        }
+        if !EVENTS.contains(&event.as_str()) {
+            bail!(
+                "unknown event '{}' (expected {} or all)",
+                event,
+                EVENTS.join(", ")
+            );
+        }
+        if !events.contains(&event) {
+            events.push(event);
+        }
+    }
+    if events.is_empty() {
+        bail!("--events must name at least one of {}", EVENTS.join(", "));
+    }
+    Ok(events)
+}
+
+/// Subscriptions are tied to a token, so fail early with a hint rather
+/// than with the API's 401
+fn require_token() -> Result<()> {
+    if crate::http::api_token().is_none() {
+        bail!(
+            "Subscriptions belong to an API token; set {} first (`soroban-registry doctor` checks it)",
+            crate::http::token_env()
+        );
+    }
+    Ok(())
+}
+
+async fn error_for_status(response: reqwest::Response, action: &str) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let message = body["error"]["message"].as_str().unwrap_or("no details");
+    bail!("Failed to {}: {} ({})", action, message, status)
+}
+
+pub async fn subscribe(
+    api_url: &str,
+    contract_id: &str,
+    events: &[String],
+    webhook: Option<&str>,
+    email: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    require_token()?;
+
+    let response = crate::http::client()
+        .post(format!(
+            "{}/api/contracts/{}/subscriptions",
+            api_url, contract_id
+        ))
+        .json(&serde_json::json!({
+            "events": events,
+            "webhook_url": webhook,
+            "email": email,
+        }))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let created = response.status() == reqwest::StatusCode::CREATED;
+    let subscription: Subscription = error_for_status(response, "subscribe")
+        .await?
+        .json()
+        .await
+        .context("Failed to parse API response")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&subscription)?);
+        return Ok(());
+    }
+
+    let verb = if created {
+        "Subscribed to"
+    } else {
+        "Updated subscription to"
+    };
+    println!("{} {} {}", "✓".green(), verb, contract_id.bold());
+    println!(
+        "  {}: {}",
+        "ID".bold(),
+        subscription.id.to_string().bright_black()
+    );
+    println!(
+        "  {}: {}",
+        "Events".bold(),
+        subscription.event_types.join(", ").bright_blue()
+    );
+    println!(
+        "  {}: {} {}",
+        "Delivery".bold(),
+        subscription.channel,
+        subscription.target
+    );
+    Ok(())
+}
+
+/// Lists the caller's subscriptions, all of them or those to one contract
+pub async fn list(api_url: &str, contract_id: Option<&str>, json: bool) -> Result<()> {
+    require_token()?;
+
+    let url = match contract_id {
+        Some(contract_id) => format!("{}/api/contracts/{}/subscriptions", api_url, contract_id),
+        None => format!("{}/api/me/subscriptions", api_url),
+    };
+    let response = crate::http::client()
+        .get(url)
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let subscriptions: Vec<Subscription> = error_for_status(response, "list subscriptions")
+        .await?
+        .json()
+        .await
+        .context("Failed to parse API response")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&subscriptions)?);
+        return Ok(());
+    }
+
+    if subscriptions.is_empty() {
+        println!("{}", "No subscriptions.".yellow());
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = subscriptions
+        .iter()
+        .map(|s| {
+            vec![
+                s.id.to_string(),
+                s.contract_id.to_string(),
+                s.event_types.join(","),
+                format!("{} {}", s.channel, s.target),
+                health(s),
+            ]
+        })
+        .collect();
+    let details: Vec<Option<String>> = subscriptions.iter().map(describe_deliveries).collect();
+    print!(
+        "{}",
+        render_table_with_details(
+            &["ID", "CONTRACT", "EVENTS", "DELIVERY", "STATUS"],
+            &[36, 36, 28, 40, 16],
+            &rows,
+            &details,
+        )
+    );
+    Ok(())
+}
+
+/// "ok", "3 failing" or "no deliveries yet"
+fn health(subscription: &Subscription) -> String {
+    if subscription.consecutive_failures > 0 {
+        format!("{} failing", subscription.consecutive_failures)
+            .red()
+            .to_string()
+    } else if subscription.last_delivered_at.is_some() {
+        "ok".green().to_string()
+    } else {
+        "no deliveries yet".dimmed().to_string()
+    }
+}
+
+/// The last error, or the latest delivery, shown under a subscription's row
+fn describe_deliveries(subscription: &Subscription) -> Option<String> {
+    if let Some(error) = &subscription.last_error {
+        return Some(format!("last error: {}", error).red().to_string());
+    }
+    subscription.recent_deliveries.first().map(|delivery| {
+        format!(
+            "latest: {} — {} ({}, {} attempt{})",
+            delivery.created_at.format("%Y-%m-%d %H:%M"),
+            delivery.subject,
+            delivery.status,
+            delivery.attempts,
+            if delivery.attempts == 1 { "" } else { "s" }
+        )
+        .dimmed()
+        .to_string()
+    })
+}
+
+/// Removes every subscription the caller holds on a contract
+pub async fn unsubscribe(api_url: &str, contract_id: &str) -> Result<()> {
+    require_token()?;
+
+    let response = crate::http::client()
+        .delete(format!(
+            "{}/api/contracts/{}/subscriptions",
+            api_url, contract_id
+        ))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    error_for_status(response, "unsubscribe").await?;
+
+    println!("{} Unsubscribed from {}", "✓".green(), contract_id.bold());
+    Ok(())
+}
+
+/// Removes one subscription by ID
+pub async fn remove(api_url: &str, subscription_id: &str) -> Result<()> {
+    require_token()?;
+
+    let response = crate::http::client()
+        .delete(format!(
+            "{}/api/me/subscriptions/{}",
+            api_url, subscription_id
+        ))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    error_for_status(response, "remove subscription").await?;
+
+    println!(
+        "{} Subscription {} removed",
+        "✓".green(),
+        subscription_id.bright_black()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_validated_and_deduplicated() {
+        assert_eq!(
+            parse_events("Version, incident,version").unwrap(),
+            vec!["version", "incident"]
+        );
+        assert_eq!(parse_events("all").unwrap(), EVENTS.to_vec());
+        assert!(parse_events("version,deploy").is_err());
+        assert!(parse_events(" , ").is_err());
+    }
+
+    #[test]
+    fn failing_subscriptions_show_their_last_error() {
+        let mut subscription: Subscription = serde_json::from_value(serde_json::json!({
+            "id": Uuid::nil(),
+            "subscriber": "GABC",
+            "contract_id": Uuid::nil(),
+            "event_types": ["incident"],
+            "channel": "webhook",
+            "target": "https://hooks.example.com",
+            "consecutive_failures": 2,
+            "last_error": "webhook returned 500 Internal Server Error",
+            "last_delivered_at": null,
+            "created_at": "2026-10-16T12:00:00Z",
+        }))
+        .unwrap();
+        assert!(health(&subscription).contains("2 failing"));
+        assert!(describe_deliveries(&subscription)
+            .unwrap()
+            .contains("webhook returned 500"));
+
+        subscription.consecutive_failures = 0;
+        subscription.last_error = None;
+        assert!(describe_deliveries(&subscription).is_none());
+        assert!(health(&subscription).contains("no deliveries yet"));
+    }
+}
@@ -1,4 +1,6 @@
 use colored::Colorize;
+use shared::pagination::Paginated;
+use shared::search::SearchHighlight;
 
 /// Returns the number of visible (non-ANSI-escape) characters in `s`.
 /// ANSI color escape sequences (\x1b[...m) are stripped before counting.
@@ -19,6 +21,50 @@ fn visible_len(s: &str) -> usize {
     count
 }
 
+/// One-line position summary for a page of results,
+/// e.g. "Showing 21-40 of 57 (next page: --offset 40)".
+pub fn page_summary<T>(page: &Paginated<T>) -> String {
+    if page.items.is_empty() {
+        return format!("Showing 0 of {}", page.total);
+    }
+    let first = page.offset + 1;
+    let last = page.offset + page.items.len() as i64;
+    let mut line = format!("Showing {}-{} of {}", first, last, page.total);
+    if let Some(next) = page.next_offset() {
+        line.push_str(&format!(" (next page: --offset {})", next));
+    }
+    line
+}
+
+/// Shortens a Stellar address to its first and last four characters,
+/// e.g. "GABC…WXYZ". Short strings are returned unchanged.
+pub fn short_address(address: &str) -> String {
+    let chars: Vec<char> = address.chars().collect();
+    if chars.len() <= 12 {
+        return address.to_string();
+    }
+    let head: String = chars[..4].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}…{}", head, tail)
+}
+
+/// How many results matched within a scoped search,
+/// e.g. "3 matches among 17 contracts by GABC…WXYZ".
+pub fn scope_summary(matches: i64, scope_total: i64, scope: &str) -> String {
+    format!(
+        "{} {} among {} {} {}",
+        matches,
+        if matches == 1 { "match" } else { "matches" },
+        scope_total,
+        if scope_total == 1 {
+            "contract"
+        } else {
+            "contracts"
+        },
+        scope
+    )
+}
+
 /// Highlights all case-insensitive occurrences of `query` within `text`
 /// by wrapping each match in yellow+bold ANSI codes.
 /// Non-matching portions are returned verbatim.
@@ -49,6 +95,27 @@ pub fn highlight_match(text: &str, query: &str) -> String {
     }
 }
 
+/// Renders a search highlight's fragment with the matched terms in bold.
+/// Control characters are replaced, so the fragment cannot inject terminal
+/// escapes even if the server did not strip them.
+pub fn render_highlight(highlight: &SearchHighlight) -> String {
+    highlight
+        .segments()
+        .into_iter()
+        .map(|(text, is_match)| {
+            let text: String = text
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c })
+                .collect();
+            if is_match {
+                text.bold().to_string()
+            } else {
+                text
+            }
+        })
+        .collect()
+}
+
 /// Pads `s` with trailing spaces so that its visible terminal width equals `width`.
 /// Works correctly for strings that contain ANSI escape sequences.
 pub fn pad_to(s: &str, width: usize) -> String {
@@ -62,6 +129,17 @@ pub fn pad_to(s: &str, width: usize) -> String {
 /// `col_widths` must be the *visible* column widths (not byte lengths).
 /// Cells in `rows` may contain ANSI escape sequences; alignment is handled correctly.
 pub fn render_table(headers: &[&str], col_widths: &[usize], rows: &[Vec<String>]) -> String {
+    render_table_with_details(headers, col_widths, rows, &[])
+}
+
+/// Like [`render_table`], with `details[i]`, when set, printed on its own
+/// indented line under row `i`.
+pub fn render_table_with_details(
+    headers: &[&str],
+    col_widths: &[usize],
+    rows: &[Vec<String>],
+    details: &[Option<String>],
+) -> String {
     let sep = "  ";
     let mut out = String::new();
 
@@ -77,7 +155,7 @@ pub fn render_table(headers: &[&str], col_widths: &[usize], rows: &[Vec<String>]
     out.push_str(&sep_parts.join(sep).bright_black().to_string());
     out.push('\n');
 
-    for row in rows {
+    for (i, row) in rows.iter().enumerate() {
         let row_parts: Vec<String> = row
             .iter()
             .zip(col_widths.iter())
@@ -85,6 +163,11 @@ pub fn render_table(headers: &[&str], col_widths: &[usize], rows: &[Vec<String>]
             .collect();
         out.push_str(&row_parts.join(sep));
         out.push('\n');
+        if let Some(Some(detail)) = details.get(i) {
+            out.push_str("    ");
+            out.push_str(detail);
+            out.push('\n');
+        }
     }
 
     out
@@ -123,6 +206,21 @@ mod tests {
         assert!(upper.contains("hello"));
     }
 
+    #[test]
+    fn scope_summary_names_the_scope() {
+        let address = "GABCDEFGHIJKLMNOPQRSTUVWXYZ234567ABCDEFGHIJKLMNOPQRSTUVW";
+        assert_eq!(short_address(address), "GABC…TUVW");
+        assert_eq!(short_address("GSHORT"), "GSHORT");
+        assert_eq!(
+            scope_summary(3, 17, &format!("by {}", short_address(address))),
+            "3 matches among 17 contracts by GABC…TUVW"
+        );
+        assert_eq!(
+            scope_summary(1, 1, "in acme"),
+            "1 match among 1 contract in acme"
+        );
+    }
+
     #[test]
     fn pad_to_adds_trailing_spaces() {
         assert_eq!(pad_to("hi", 5), "hi   ");
@@ -161,4 +259,47 @@ mod tests {
         assert!(out.contains("Name"));
         assert!(out.contains("Network"));
     }
+
+    #[test]
+    fn render_table_puts_details_under_their_row() {
+        let rows = vec![
+            vec!["alice".to_string()],
+            vec!["bob".to_string()],
+            vec!["carol".to_string()],
+        ];
+        let details = vec![None, Some("matched here".to_string())];
+        let out = render_table_with_details(&["Name"], &[5], &rows, &details);
+
+        let lines: Vec<&str> = out.lines().collect();
+        assert!(lines[3].contains("bob"));
+        assert_eq!(lines[4], "    matched here");
+        assert!(lines[5].contains("carol"));
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn render_highlight_keeps_text_and_strips_control_characters() {
+        let highlight = SearchHighlight {
+            field: "description".to_string(),
+            fragment: "pays a \x1b[2Jfee".to_string(),
+            matches: vec![shared::search::HighlightRange { start: 0, end: 4 }],
+        };
+
+        let out = render_highlight(&highlight);
+        assert!(out.contains("pays"));
+        assert!(out.contains(" a  [2Jfee"));
+        assert!(!out.contains("\x1b[2J"));
+    }
+
+    #[test]
+    fn page_summary_points_at_the_next_offset() {
+        let page = Paginated::new(vec!["a", "b"], 57, 2, 20);
+        assert_eq!(
+            page_summary(&page),
+            "Showing 21-22 of 57 (next page: --offset 22)"
+        );
+
+        let last = Paginated::new(vec!["a"], 21, 2, 20);
+        assert_eq!(page_summary(&last), "Showing 21-21 of 21");
+    }
 }
@@ -0,0 +1,163 @@
+//! `tags rename` and `tags remove`: one tag change across every contract the
+//! caller owns, through `POST /api/me/contracts/tags`.
+//!
+//! The registry applies the change to all of them in one transaction and
+//! audits each contract it touched. `--dry-run` asks for the same change
+//! rolled back, to see which contracts it would hit first.
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::table_format::render_table;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagChange {
+    pub contract_id: Uuid,
+    pub name: String,
+    pub old_tags: Vec<String>,
+    pub new_tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkTagResponse {
+    pub dry_run: bool,
+    pub affected: usize,
+    pub contracts: Vec<TagChange>,
+}
+
+/// `{rename: {from, to}}`, limited to `category` when given
+pub fn rename_operation(from: &str, to: &str, category: Option<&str>) -> Value {
+    with_scope(json!({ "rename": { "from": from, "to": to } }), category)
+}
+
+/// `{remove: tag}`, limited to `category` when given
+pub fn remove_operation(tag: &str, category: Option<&str>) -> Value {
+    with_scope(json!({ "remove": tag }), category)
+}
+
+fn with_scope(mut operation: Value, category: Option<&str>) -> Value {
+    if let Some(category) = category {
+        operation["where"] = json!({ "category": category });
+    }
+    operation
+}
+
+pub async fn apply_to_mine(
+    api_url: &str,
+    operation: Value,
+    dry_run: bool,
+    json: bool,
+) -> Result<()> {
+    if crate::http::api_token().is_none() {
+        bail!(
+            "--mine needs to know who you are; set {} first (`soroban-registry doctor` checks it)",
+            crate::http::token_env()
+        );
+    }
+
+    let response = crate::http::client()
+        .post(format!("{}/api/me/contracts/tags", api_url))
+        .json(&json!({ "operations": [operation], "dry_run": dry_run }))
+        .send()
+        .await
+        .context("Failed to reach registry API")?;
+    let status = response.status();
+    if !status.is_success() {
+        let body: Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        bail!("Failed to update tags: {} ({})", message, status);
+    }
+    let result: BulkTagResponse = response
+        .json()
+        .await
+        .context("Failed to parse API response")?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&result)?);
+        return Ok(());
+    }
+    print!("{}", describe(&result));
+    Ok(())
+}
+
+fn describe(result: &BulkTagResponse) -> String {
+    let mut out = String::new();
+    if result.contracts.is_empty() {
+        out.push_str(&format!(
+            "{}\n",
+            "None of your contracts carry that tag.".yellow()
+        ));
+        return out;
+    }
+
+    let rows: Vec<Vec<String>> = result
+        .contracts
+        .iter()
+        .map(|c| {
+            vec![
+                c.name.clone(),
+                c.old_tags.join(", ").bright_black().to_string(),
+                c.new_tags.join(", "),
+            ]
+        })
+        .collect();
+    out.push_str(&render_table(
+        &["CONTRACT", "BEFORE", "AFTER"],
+        &[30, 40, 40],
+        &rows,
+    ));
+
+    if result.dry_run {
+        out.push_str(&format!(
+            "\n{} contract(s) would change. Nothing was changed; run again without --dry-run to apply.\n",
+            result.affected
+        ));
+    } else {
+        out.push_str(&format!(
+            "\n{} Updated tags on {} contract(s)\n",
+            "✓".green(),
+            result.affected
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn operations_carry_the_category_scope() {
+        assert_eq!(
+            rename_operation("amm", "dex", None),
+            json!({ "rename": { "from": "amm", "to": "dex" } })
+        );
+        assert_eq!(
+            remove_operation("legacy", Some("DeFi")),
+            json!({ "remove": "legacy", "where": { "category": "DeFi" } })
+        );
+    }
+
+    #[test]
+    fn dry_runs_say_nothing_changed() {
+        let mut result = BulkTagResponse {
+            dry_run: true,
+            affected: 1,
+            contracts: vec![TagChange {
+                contract_id: Uuid::nil(),
+                name: "pool".to_string(),
+                old_tags: vec!["amm".to_string(), "swap".to_string()],
+                new_tags: vec!["dex".to_string(), "swap".to_string()],
+            }],
+        };
+        let preview = describe(&result);
+        assert!(preview.contains("amm, swap") && preview.contains("dex, swap"));
+        assert!(preview.contains("1 contract(s) would change"));
+
+        result.dry_run = false;
+        assert!(describe(&result).contains("Updated tags on 1 contract(s)"));
+    }
+}
@@ -0,0 +1,428 @@
+//! Anonymous usage reporting, off unless the user opts in.
+//!
+//! The first interactive run asks once and stores the answer as
+//! `defaults.telemetry`; `config set telemetry <true|false>` changes it and
+//! `SOROBAN_REGISTRY_TELEMETRY=off` (or `DO_NOT_TRACK=1`) turns it off
+//! whatever the config says.
+//!
+//! When on, each run records the command name (just `plugin` for plugins),
+//! the names of the flags given on the command line (never their values, and
+//! never positional arguments), how long it took and whether it succeeded. Events are kept in
+//! `~/.soroban-registry/telemetry/events.jsonl` and sent to `/api/telemetry`
+//! at most once a day, with a random install id and no token.
+//! `telemetry show` prints exactly what the next report would send.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Command};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config;
+
+/// Turns reporting off when set to `off`, `false` or `0`
+pub const TELEMETRY_ENV: &str = "SOROBAN_REGISTRY_TELEMETRY";
+const DO_NOT_TRACK_ENV: &str = "DO_NOT_TRACK";
+
+const TELEMETRY_DIR_NAME: &str = "telemetry";
+const EVENTS_FILE_NAME: &str = "events.jsonl";
+const STATE_FILE_NAME: &str = "state.json";
+/// Oldest events are dropped beyond this, e.g. while the API is unreachable
+const MAX_EVENTS: usize = 1_000;
+const REPORT_INTERVAL_HOURS: i64 = 24;
+const SEND_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The run being recorded, set by [`start`] when reporting is on
+static PENDING: Mutex<Option<Pending>> = Mutex::new(None);
+
+#[derive(Debug)]
+struct Pending {
+    api_url: String,
+    command: String,
+    flags: Vec<String>,
+    started: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Consent {
+    Enabled,
+    Disabled,
+    /// Turned off by [`TELEMETRY_ENV`] or `DO_NOT_TRACK`
+    KillSwitch,
+    Unanswered,
+}
+
+/// One recorded run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Event {
+    /// Subcommand path, e.g. `contracts list`
+    pub command: String,
+    /// Long names of the flags given, e.g. `--limit`, sorted
+    pub flags: Vec<String>,
+    pub duration_ms: u64,
+    pub success: bool,
+}
+
+/// The body of `POST /api/telemetry`
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub install_id: Uuid,
+    pub cli_version: &'static str,
+    pub events: Vec<Event>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct State {
+    install_id: Uuid,
+    last_attempt_at: Option<DateTime<Utc>>,
+}
+
+pub fn consent() -> Consent {
+    if kill_switch() {
+        return Consent::KillSwitch;
+    }
+    match config::telemetry_setting() {
+        Ok(Some(true)) => Consent::Enabled,
+        Ok(None) => Consent::Unanswered,
+        // A config file that doesn't load never counts as a yes
+        Ok(Some(false)) | Err(_) => Consent::Disabled,
+    }
+}
+
+fn kill_switch() -> bool {
+    let off = std::env::var(TELEMETRY_ENV)
+        .is_ok_and(|value| matches!(value.trim(), "off" | "false" | "0"));
+    let do_not_track = std::env::var(DO_NOT_TRACK_ENV)
+        .is_ok_and(|value| !value.trim().is_empty() && value.trim() != "0");
+    off || do_not_track
+}
+
+/// Begins recording the run `matches` parsed from `root`, asking first if
+/// the user has never answered and is at a terminal.
+pub fn start(root: &Command, matches: &ArgMatches, api_url: &str) {
+    let (command, flags) = invocation(root, matches);
+    // Those commands are how to answer, so don't ask on top of them
+    let answering = command == "telemetry show" || command == "config set";
+    if !answering && consent() == Consent::Unanswered {
+        ask();
+    }
+    if consent() != Consent::Enabled {
+        return;
+    }
+    *PENDING.lock().unwrap_or_else(PoisonError::into_inner) = Some(Pending {
+        api_url: api_url.to_string(),
+        command,
+        flags,
+        started: Instant::now(),
+    });
+}
+
+/// Records how the run [`start`] began ended, and sends the report if the
+/// last attempt was a day or more ago. Never fails the command.
+pub async fn finish(success: bool) {
+    let Some(pending) = PENDING
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()
+    else {
+        return;
+    };
+    let Some(dir) = telemetry_dir() else {
+        return;
+    };
+    let event = Event {
+        command: pending.command,
+        flags: pending.flags,
+        duration_ms: pending.started.elapsed().as_millis() as u64,
+        success,
+    };
+    if let Err(err) = append_event(&dir, event, MAX_EVENTS) {
+        log::debug!("Could not record telemetry event: {:#}", err);
+        return;
+    }
+    if let Err(err) = send_if_due(&dir, &pending.api_url).await {
+        log::debug!("Could not send telemetry: {:#}", err);
+    }
+}
+
+fn ask() {
+    if !io::stdin().is_terminal() || !io::stderr().is_terminal() {
+        return;
+    }
+    eprint!(
+        "Help improve soroban-registry by sending anonymous usage counts once a day?\n\
+         Only command and flag names, durations and success are sent, never arguments,\n\
+         values or addresses; `soroban-registry telemetry show` prints the exact report.\n\
+         Change your answer with `soroban-registry config set telemetry <true|false>`. [y/N] "
+    );
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if io::stdin().lock().read_line(&mut answer).is_err() {
+        return;
+    }
+    let enabled = matches!(answer.trim().to_lowercase().as_str(), "y" | "yes");
+    if let Err(err) = config::set_telemetry(enabled) {
+        log::warn!("Could not save telemetry answer: {:#}", err);
+    }
+}
+
+/// The subcommand path of `matches` and the long names of the flags given
+/// on its command line. Values and positional arguments are left out.
+fn invocation(root: &Command, matches: &ArgMatches) -> (String, Vec<String>) {
+    let mut path = Vec::new();
+    let mut flags = BTreeSet::new();
+    let (mut command, mut matches) = (root, matches);
+    loop {
+        for arg in command.get_arguments() {
+            let Some(long) = arg.get_long() else {
+                continue;
+            };
+            if matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine) {
+                flags.insert(format!("--{}", long));
+            }
+        }
+        let Some((name, sub_matches)) = matches.subcommand() else {
+            break;
+        };
+        let Some(subcommand) = command.find_subcommand(name) else {
+            // A plugin; its name may be an organization's own, so isn't sent
+            path.push("plugin".to_string());
+            break;
+        };
+        path.push(subcommand.get_name().to_string());
+        (command, matches) = (subcommand, sub_matches);
+    }
+    (path.join(" "), flags.into_iter().collect())
+}
+
+fn telemetry_dir() -> Option<PathBuf> {
+    config::config_file_path()?
+        .parent()
+        .map(|dir| dir.join(TELEMETRY_DIR_NAME))
+}
+
+fn append_event(dir: &Path, event: Event, max_events: usize) -> Result<()> {
+    let mut events = read_events(dir)?;
+    events.push(event);
+    let excess = events.len().saturating_sub(max_events);
+    events.drain(..excess);
+    write_events(dir, &events)
+}
+
+fn read_events(dir: &Path) -> Result<Vec<Event>> {
+    let path = dir.join(EVENTS_FILE_NAME);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).with_context(|| format!("Failed to read {:?}", path))?;
+    // A line that doesn't parse, e.g. from an interrupted write, is dropped
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn write_events(dir: &Path, events: &[Event]) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create directory {:?}", dir))?;
+    let mut content = String::new();
+    for event in events {
+        content.push_str(&serde_json::to_string(event)?);
+        content.push('\n');
+    }
+    let path = dir.join(EVENTS_FILE_NAME);
+    fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))
+}
+
+fn load_state(dir: &Path) -> Result<State> {
+    let path = dir.join(STATE_FILE_NAME);
+    let state = fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    match state {
+        Some(state) => Ok(state),
+        None => {
+            let state = State {
+                install_id: Uuid::new_v4(),
+                last_attempt_at: None,
+            };
+            save_state(dir, &state)?;
+            Ok(state)
+        }
+    }
+}
+
+fn save_state(dir: &Path, state: &State) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create directory {:?}", dir))?;
+    let path = dir.join(STATE_FILE_NAME);
+    fs::write(&path, serde_json::to_string_pretty(state)?)
+        .with_context(|| format!("Failed to write {:?}", path))
+}
+
+fn report(dir: &Path) -> Result<Report> {
+    Ok(Report {
+        install_id: load_state(dir)?.install_id,
+        cli_version: env!("CARGO_PKG_VERSION"),
+        events: read_events(dir)?,
+    })
+}
+
+fn is_due(last_attempt_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    last_attempt_at.is_none_or(|last| now - last >= chrono::Duration::hours(REPORT_INTERVAL_HOURS))
+}
+
+/// Sends the pending events when a day has passed since the last attempt,
+/// successful or not. They are only cleared once the API has accepted them.
+async fn send_if_due(dir: &Path, api_url: &str) -> Result<()> {
+    let mut state = load_state(dir)?;
+    let now = Utc::now();
+    if !is_due(state.last_attempt_at, now) {
+        return Ok(());
+    }
+    state.last_attempt_at = Some(now);
+    save_state(dir, &state)?;
+
+    let report = report(dir)?;
+    if report.events.is_empty() {
+        return Ok(());
+    }
+    // Not `http::client()`: that one sends the API token, and reports must
+    // not say who sent them
+    let client = crate::http::transport_builder()
+        .timeout(SEND_TIMEOUT)
+        .build()?;
+    let response = client
+        .post(format!("{}/api/telemetry", api_url))
+        .json(&report)
+        .send()
+        .await?;
+    if response.status().is_success() {
+        write_events(dir, &[])?;
+    } else {
+        log::debug!("Telemetry report answered {}", response.status());
+    }
+    Ok(())
+}
+
+/// `telemetry show`: the report the next send would make, as JSON on
+/// stdout, with whether and when it would be sent on stderr
+pub fn show() -> Result<()> {
+    let dir = telemetry_dir().context("Could not determine home directory")?;
+    let report = report(&dir)?;
+    let status = match consent() {
+        Consent::Enabled => "enabled",
+        Consent::Disabled => "disabled (defaults.telemetry = false)",
+        Consent::KillSwitch => "off (SOROBAN_REGISTRY_TELEMETRY or DO_NOT_TRACK is set)",
+        Consent::Unanswered => "disabled (not answered yet)",
+    };
+    eprintln!("Telemetry: {}", status);
+    if consent() == Consent::Enabled {
+        let last_attempt_at = load_state(&dir)?.last_attempt_at;
+        match last_attempt_at {
+            Some(last) if !is_due(Some(last), Utc::now()) => eprintln!(
+                "Next report after {}",
+                last + chrono::Duration::hours(REPORT_INTERVAL_HOURS)
+            ),
+            _ => eprintln!("Next report at the end of the next command"),
+        }
+    } else {
+        eprintln!("Nothing is sent; enable with `soroban-registry config set telemetry true`");
+    }
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::{CommandFactory, FromArgMatches};
+    use tempfile::tempdir;
+
+    fn invocation_of(args: &[&str]) -> (String, Vec<String>) {
+        let root = crate::Cli::command();
+        let matches = root.clone().try_get_matches_from(args).unwrap();
+        crate::Cli::from_arg_matches(&matches).unwrap();
+        invocation(&root, &matches)
+    }
+
+    #[test]
+    fn records_flag_names_without_values() {
+        let (command, flags) = invocation_of(&[
+            "soroban-registry",
+            "--api-url",
+            "https://registry.internal",
+            "search",
+            "secret-query",
+            "--verified-only",
+            "--category",
+            "secret-category",
+        ]);
+        assert_eq!(command, "search");
+        assert_eq!(flags, ["--api-url", "--category", "--verified-only"]);
+
+        let event = Event {
+            command,
+            flags,
+            duration_ms: 12,
+            success: true,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(!json.contains("secret"), "{}", json);
+        assert!(!json.contains("registry.internal"), "{}", json);
+    }
+
+    #[test]
+    fn records_the_subcommand_path() {
+        let (command, flags) = invocation_of(&["soroban-registry", "config", "profiles"]);
+        assert_eq!(command, "config profiles");
+        assert!(flags.is_empty());
+    }
+
+    #[test]
+    fn plugins_are_recorded_without_their_name() {
+        let (command, flags) =
+            invocation_of(&["soroban-registry", "acme-approve", "--ticket", "OPS-1"]);
+        assert_eq!(command, "plugin");
+        assert!(flags.is_empty());
+    }
+
+    #[test]
+    fn keeps_the_newest_events() {
+        let dir = tempdir().unwrap();
+        for duration_ms in 0..5 {
+            let event = Event {
+                command: "stats".into(),
+                flags: Vec::new(),
+                duration_ms,
+                success: true,
+            };
+            append_event(dir.path(), event, 3).unwrap();
+        }
+        let durations: Vec<_> = read_events(dir.path())
+            .unwrap()
+            .iter()
+            .map(|event| event.duration_ms)
+            .collect();
+        assert_eq!(durations, [2, 3, 4]);
+    }
+
+    #[test]
+    fn keeps_the_install_id_and_reports_daily() {
+        let dir = tempdir().unwrap();
+        let first = load_state(dir.path()).unwrap().install_id;
+        assert_eq!(load_state(dir.path()).unwrap().install_id, first);
+
+        let now = Utc::now();
+        assert!(is_due(None, now));
+        assert!(!is_due(Some(now - chrono::Duration::hours(23)), now));
+        assert!(is_due(Some(now - chrono::Duration::hours(24)), now));
+    }
+}
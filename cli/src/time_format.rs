@@ -0,0 +1,218 @@
+//! How timestamps read in text output, set with `--time-format` or
+//! `time_format` in the config file.
+//!
+//! `relative` (the default) shows "3 hours ago" for the last week and the
+//! local date and time before that; `local`, `utc` and `iso` always show an
+//! absolute time. JSON output is unaffected and keeps the RFC 3339 UTC
+//! timestamps the API sends.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use chrono::{DateTime, Duration, FixedOffset, Local, SecondsFormat, Utc};
+
+/// How far back (or ahead) `relative` shows times as relative ones
+const RELATIVE_WINDOW_DAYS: i64 = 7;
+
+static FORMAT: OnceLock<TimeFormat> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum TimeFormat {
+    /// "3 hours ago" within a week, the local date and time otherwise
+    #[default]
+    Relative,
+    /// Local date and time, e.g. 2026-10-16 14:05
+    Local,
+    /// UTC date and time, e.g. 2026-10-16 12:05 UTC
+    Utc,
+    /// RFC 3339 in UTC, e.g. 2026-10-16T12:05:00Z
+    Iso,
+}
+
+impl fmt::Display for TimeFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TimeFormat::Relative => "relative",
+            TimeFormat::Local => "local",
+            TimeFormat::Utc => "utc",
+            TimeFormat::Iso => "iso",
+        })
+    }
+}
+
+impl FromStr for TimeFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "relative" => Ok(TimeFormat::Relative),
+            "local" => Ok(TimeFormat::Local),
+            "utc" => Ok(TimeFormat::Utc),
+            "iso" => Ok(TimeFormat::Iso),
+            _ => anyhow::bail!(
+                "Invalid time format: {}. Allowed values: relative, local, utc, iso",
+                s
+            ),
+        }
+    }
+}
+
+/// Sets the format for the rest of the run; called once from `main`
+pub fn use_format(format: TimeFormat) {
+    let _ = FORMAT.set(format);
+}
+
+/// Renders timestamps in one format against one clock
+#[derive(Debug, Clone, Copy)]
+pub struct TimeDisplay {
+    format: TimeFormat,
+    now: DateTime<Utc>,
+    /// `None` for the machine's timezone
+    offset: Option<FixedOffset>,
+}
+
+impl TimeDisplay {
+    /// The configured format, in the local timezone, as of now
+    pub fn current() -> Self {
+        Self {
+            format: FORMAT.get().copied().unwrap_or_default(),
+            now: Utc::now(),
+            offset: None,
+        }
+    }
+
+    /// A fixed clock and timezone, so output doesn't depend on when or
+    /// where it is rendered
+    #[cfg(test)]
+    pub fn fixed(format: TimeFormat, now: DateTime<Utc>, offset: FixedOffset) -> Self {
+        Self {
+            format,
+            now,
+            offset: Some(offset),
+        }
+    }
+
+    pub fn show(&self, at: DateTime<Utc>) -> String {
+        match self.format {
+            TimeFormat::Relative => {
+                relative(self.now - at).unwrap_or_else(|| self.local(at).to_string())
+            }
+            TimeFormat::Local => self.local(at).to_string(),
+            TimeFormat::Utc => at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            TimeFormat::Iso => at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        }
+    }
+
+    /// Like [`TimeDisplay::show`] for an RFC 3339 string from the API,
+    /// which is returned as is when it doesn't parse
+    pub fn show_rfc3339(&self, at: &str) -> String {
+        match DateTime::parse_from_rfc3339(at) {
+            Ok(parsed) => self.show(parsed.with_timezone(&Utc)),
+            Err(_) => at.to_string(),
+        }
+    }
+
+    fn local(&self, at: DateTime<Utc>) -> impl fmt::Display {
+        let at = match self.offset {
+            Some(offset) => at.with_timezone(&offset),
+            None => at.with_timezone(&Local).fixed_offset(),
+        };
+        at.format("%Y-%m-%d %H:%M")
+    }
+}
+
+/// [`TimeDisplay::show`] with the configured format, as of now
+pub fn show(at: DateTime<Utc>) -> String {
+    TimeDisplay::current().show(at)
+}
+
+/// [`TimeDisplay::show_rfc3339`] with the configured format, as of now
+pub fn show_rfc3339(at: &str) -> String {
+    TimeDisplay::current().show_rfc3339(at)
+}
+
+/// "5 minutes ago" or "in 2 days"; `None` outside the relative window
+fn relative(elapsed: Duration) -> Option<String> {
+    if elapsed.num_days().abs() >= RELATIVE_WINDOW_DAYS {
+        return None;
+    }
+    let seconds = elapsed.num_seconds().abs();
+    let (count, unit) = match seconds {
+        0..=59 => return Some("just now".to_string()),
+        60..=3_599 => (seconds / 60, "minute"),
+        3_600..=86_399 => (seconds / 3_600, "hour"),
+        _ => (seconds / 86_400, "day"),
+    };
+    let span = format!("{} {}{}", count, unit, if count == 1 { "" } else { "s" });
+    Some(if elapsed < Duration::zero() {
+        format!("in {}", span)
+    } else {
+        format!("{} ago", span)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(rfc3339: &str) -> DateTime<Utc> {
+        rfc3339.parse().unwrap()
+    }
+
+    fn display(format: TimeFormat) -> TimeDisplay {
+        // 2026-10-16 12:00 UTC, viewed from UTC+2
+        TimeDisplay::fixed(
+            format,
+            at("2026-10-16T12:00:00Z"),
+            FixedOffset::east_opt(2 * 3600).unwrap(),
+        )
+    }
+
+    #[test]
+    fn relative_times_within_a_week() {
+        let times = display(TimeFormat::Relative);
+        assert_eq!(times.show(at("2026-10-16T11:59:30Z")), "just now");
+        assert_eq!(times.show(at("2026-10-16T11:59:00Z")), "1 minute ago");
+        assert_eq!(times.show(at("2026-10-16T09:10:00Z")), "2 hours ago");
+        assert_eq!(times.show(at("2026-10-13T12:00:00Z")), "3 days ago");
+        assert_eq!(times.show(at("2026-10-18T13:00:00Z")), "in 2 days");
+        assert_eq!(times.show(at("2026-10-09T12:00:01Z")), "6 days ago");
+        // A week or more away: the local date and time
+        assert_eq!(times.show(at("2026-10-09T12:00:00Z")), "2026-10-09 14:00");
+        assert_eq!(times.show(at("2026-12-01T23:30:00Z")), "2026-12-02 01:30");
+    }
+
+    #[test]
+    fn absolute_formats_ignore_the_clock() {
+        let started = at("2026-03-01T23:30:00Z");
+        assert_eq!(display(TimeFormat::Local).show(started), "2026-03-02 01:30");
+        assert_eq!(
+            display(TimeFormat::Utc).show(started),
+            "2026-03-01 23:30 UTC"
+        );
+        assert_eq!(
+            display(TimeFormat::Iso).show(started),
+            "2026-03-01T23:30:00Z"
+        );
+        assert_eq!(
+            display(TimeFormat::Iso).show_rfc3339("2026-03-02T01:30:00.123456+02:00"),
+            "2026-03-01T23:30:00Z"
+        );
+        assert_eq!(
+            display(TimeFormat::Iso).show_rfc3339("yesterday"),
+            "yesterday"
+        );
+    }
+
+    #[test]
+    fn formats_parse_from_config_values() {
+        assert_eq!("UTC".parse::<TimeFormat>().unwrap(), TimeFormat::Utc);
+        assert_eq!(
+            "relative".parse::<TimeFormat>().unwrap(),
+            TimeFormat::Relative
+        );
+        assert!("rfc3339".parse::<TimeFormat>().is_err());
+        assert_eq!(TimeFormat::Iso.to_string(), "iso");
+    }
+}
@@ -0,0 +1,324 @@
+//! `diff --code`: what two versions' stored WASM modules differ in, from
+//! `GET /api/contracts/:id/versions/compare`, section sizes first, then
+//! functions and data segments.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Deserialize;
+
+use crate::config::Network;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ComparedBinary {
+    pub version: String,
+    pub stored: bool,
+    #[serde(default)]
+    pub size: Option<i64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SectionDelta {
+    pub section: String,
+    pub from_bytes: Option<u64>,
+    pub to_bytes: Option<u64>,
+    pub delta: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FunctionRef {
+    pub index: u32,
+    pub name: Option<String>,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FunctionChange {
+    pub name: Option<String>,
+    pub to_index: u32,
+    pub from_size: u64,
+    pub to_size: u64,
+    pub from_hash: String,
+    pub to_hash: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FunctionDiff {
+    pub matched_by: String,
+    pub added: Vec<FunctionRef>,
+    pub removed: Vec<FunctionRef>,
+    pub changed: Vec<FunctionChange>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DataSegmentChange {
+    pub change: String,
+    pub offset: Option<i64>,
+    pub from_bytes: Option<u64>,
+    pub to_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DataSegmentDiff {
+    pub changes: Vec<DataSegmentChange>,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BinaryDiff {
+    pub identical: bool,
+    pub sections: Vec<SectionDelta>,
+    pub functions: FunctionDiff,
+    pub data_segments: DataSegmentDiff,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WasmComparison {
+    pub from: ComparedBinary,
+    pub to: ComparedBinary,
+    pub complete: bool,
+    #[serde(default)]
+    pub missing: Vec<String>,
+    #[serde(default)]
+    pub sections: Option<Vec<SectionDelta>>,
+    #[serde(default)]
+    pub diff: Option<BinaryDiff>,
+}
+
+/// Splits `contract@version`; `--code` compares versions, so both need one
+fn split_version(input: &str) -> Result<(&str, &str)> {
+    match input.rsplit_once('@') {
+        Some((contract, version)) if !contract.is_empty() && !version.is_empty() => {
+            Ok((contract, version))
+        }
+        _ => anyhow::bail!(
+            "--code compares two versions of one contract: expected <contract>@<version>, got '{}'",
+            input
+        ),
+    }
+}
+
+pub async fn show(api_url: &str, a: &str, b: &str, network: Network, json: bool) -> Result<()> {
+    let (from_contract, from) = split_version(a)?;
+    let (to_contract, to) = split_version(b)?;
+    let contract_id = crate::resolve::contract_id(api_url, from_contract, network).await?;
+    if crate::resolve::contract_id(api_url, to_contract, network).await? != contract_id {
+        anyhow::bail!(
+            "--code compares two versions of one contract, but '{}' and '{}' are different contracts",
+            from_contract,
+            to_contract
+        );
+    }
+
+    let url = format!("{}/api/contracts/{}/versions/compare", api_url, contract_id);
+    let response = crate::http::client()
+        .get(&url)
+        .query(&[("from", from), ("to", to)])
+        .send()
+        .await
+        .context("Failed to compare contract binaries")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["error"]["message"].as_str().unwrap_or("no details");
+        anyhow::bail!(
+            "Failed to compare contract binaries: {} ({})",
+            message,
+            status
+        );
+    }
+
+    let body: serde_json::Value = response.json().await.context("Invalid response")?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&body)?);
+        return Ok(());
+    }
+    let comparison: WasmComparison = serde_json::from_value(body).context("Invalid response")?;
+    render_text(&comparison);
+    Ok(())
+}
+
+fn render_text(comparison: &WasmComparison) {
+    println!(
+        "\n{} {} {}",
+        binary_label(&comparison.from).bold(),
+        "→".bright_black(),
+        binary_label(&comparison.to).bold()
+    );
+
+    if !comparison.complete {
+        println!(
+            "{} no stored WASM for {}; only section sizes are shown",
+            "Partial:".yellow().bold(),
+            comparison.missing.join(", ")
+        );
+        if let Some(sections) = &comparison.sections {
+            render_sections(sections);
+        }
+        return;
+    }
+    let Some(diff) = &comparison.diff else {
+        return;
+    };
+    if diff.identical {
+        println!("{}", "Identical".green().bold());
+        return;
+    }
+
+    render_sections(&diff.sections);
+
+    let functions = &diff.functions;
+    println!(
+        "\n{} (matched by {}, {} unchanged)",
+        "Functions".bold(),
+        functions.matched_by,
+        functions.unchanged
+    );
+    for function in &functions.added {
+        println!(
+            "  {} {:<40} {} bytes",
+            "+".green(),
+            function_label(function.name.as_deref(), function.index),
+            function.size
+        );
+    }
+    for function in &functions.removed {
+        println!(
+            "  {} {:<40} {} bytes",
+            "-".red(),
+            function_label(function.name.as_deref(), function.index),
+            function.size
+        );
+    }
+    for function in &functions.changed {
+        println!(
+            "  {} {:<40} {} → {} bytes  {}",
+            "~".yellow(),
+            function_label(function.name.as_deref(), function.to_index),
+            function.from_size,
+            function.to_size,
+            format!("{} → {}", function.from_hash, function.to_hash).bright_black()
+        );
+    }
+
+    let segments = &diff.data_segments;
+    println!(
+        "\n{} ({} unchanged)",
+        "Data segments".bold(),
+        segments.unchanged
+    );
+    for segment in &segments.changes {
+        let marker = match segment.change.as_str() {
+            "added" => "+".green(),
+            "removed" => "-".red(),
+            _ => "~".yellow(),
+        };
+        let offset = segment
+            .offset
+            .map(|offset| format!("offset {}", offset))
+            .unwrap_or_else(|| "passive".to_string());
+        println!(
+            "  {} {:<20} {} → {} bytes",
+            marker,
+            offset,
+            optional_bytes(segment.from_bytes),
+            optional_bytes(segment.to_bytes)
+        );
+    }
+}
+
+fn render_sections(sections: &[SectionDelta]) {
+    println!("\n{}", "Sections".bold());
+    for section in sections {
+        let delta = signed(section.delta);
+        println!(
+            "  {:<24} {:>10} → {:<10} {}",
+            section.section,
+            optional_bytes(section.from_bytes),
+            optional_bytes(section.to_bytes),
+            match section.delta {
+                0 => delta.bright_black(),
+                d if d > 0 => delta.red(),
+                _ => delta.green(),
+            }
+        );
+    }
+}
+
+fn binary_label(binary: &ComparedBinary) -> String {
+    match (binary.stored, binary.size) {
+        (true, Some(size)) => format!("{} ({} bytes)", binary.version, size),
+        (true, None) => binary.version.clone(),
+        (false, _) => format!("{} (not stored)", binary.version),
+    }
+}
+
+fn function_label(name: Option<&str>, index: u32) -> String {
+    match name {
+        Some(name) => format!("{} (#{})", name, index),
+        None => format!("#{}", index),
+    }
+}
+
+fn optional_bytes(bytes: Option<u64>) -> String {
+    bytes
+        .map(|b| b.to_string())
+        .unwrap_or_else(|| "–".to_string())
+}
+
+fn signed(delta: i64) -> String {
+    if delta > 0 {
+        format!("+{}", delta)
+    } else {
+        delta.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_sides_need_a_version() {
+        assert_eq!(split_version("CABC@1.0.0").unwrap(), ("CABC", "1.0.0"));
+        assert_eq!(
+            split_version("acme/escrow@1.1.0").unwrap(),
+            ("acme/escrow", "1.1.0")
+        );
+        for input in ["CABC", "CABC@", "@1.0.0"] {
+            let err = split_version(input).unwrap_err();
+            assert!(err.to_string().contains("<contract>@<version>"));
+        }
+    }
+
+    #[test]
+    fn partial_results_read_without_a_diff() {
+        let comparison: WasmComparison = serde_json::from_value(serde_json::json!({
+            "from": { "version": "1.0.0", "wasm_hash": "aa", "stored": true, "size": 812 },
+            "to": { "version": "1.1.0", "wasm_hash": "bb", "stored": false, "size": null },
+            "complete": false,
+            "missing": ["1.1.0"],
+            "sections": [
+                { "section": "code", "from_bytes": 600, "to_bytes": null, "delta": -600 }
+            ]
+        }))
+        .unwrap();
+
+        assert!(!comparison.complete);
+        assert!(comparison.diff.is_none());
+        assert_eq!(binary_label(&comparison.to), "1.1.0 (not stored)");
+        assert_eq!(binary_label(&comparison.from), "1.0.0 (812 bytes)");
+        assert_eq!(comparison.sections.unwrap()[0].delta, -600);
+    }
+
+    #[test]
+    fn labels_and_deltas() {
+        assert_eq!(function_label(Some("transfer"), 4), "transfer (#4)");
+        assert_eq!(function_label(None, 4), "#4");
+        assert_eq!(signed(12), "+12");
+        assert_eq!(signed(-3), "-3");
+        assert_eq!(signed(0), "0");
+        assert_eq!(optional_bytes(None), "–");
+    }
+}
@@ -267,8 +267,12 @@ fn print_item(v: &serde_json::Value) {
     if let Some(signer) = v.get("signer_masked").and_then(|x| x.as_str()) {
         println!("   {} {}", "Signer:".bold(), signer.bright_black());
     }
-    if let Some(ts) = v.get("ts").and_then(|x| x.as_u64()) {
-        println!("   {} {}", "Timestamp:".bold(), ts);
+    if let Some(at) = v
+        .get("ts")
+        .and_then(|x| x.as_i64())
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+    {
+        println!("   {} {}", "When:".bold(), crate::time_format::show(at));
     }
 }
 
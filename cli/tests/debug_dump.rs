@@ -0,0 +1,117 @@
+//! `--debug-dump` writes a redacted capture of a failed request, and
+//! `doctor --last-error` prints it back.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::thread;
+
+const TOKEN: &str = "very-secret-api-token";
+
+fn binary() -> PathBuf {
+    PathBuf::from(env!("CARGO_BIN_EXE_soroban-registry"))
+}
+
+fn read_request(stream: &TcpStream) {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            return;
+        }
+    }
+}
+
+/// Answers every request with a 500 and an API error body
+fn serve(listener: TcpListener) {
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { return };
+            read_request(&stream);
+            let body = r#"{"error":{"code":"InternalError","message":"database unavailable"}}"#;
+            let response = format!(
+                "HTTP/1.1 500 Internal Server Error\r\nContent-Type: application/json\r\n\
+                 Content-Length: {}\r\nSet-Cookie: session=abc\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes());
+        }
+    });
+}
+
+fn run(home: &Path, args: &[&str]) -> Output {
+    Command::new(binary())
+        .args(args)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env("SOROBAN_REGISTRY_API_TOKEN", TOKEN)
+        .env_remove("SOROBAN_REGISTRY_PROFILE")
+        .env("NO_PROXY", "127.0.0.1")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn failed_requests_are_captured_without_credentials() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let api_url = format!("http://{}", listener.local_addr().unwrap());
+    serve(listener);
+
+    let home = tempfile::tempdir().unwrap();
+    let dumps = home.path().join("captures");
+    let dumps_arg = dumps.to_str().unwrap();
+
+    let failed = run(
+        home.path(),
+        &[
+            "--api-url",
+            &api_url,
+            "browse",
+            "oracle",
+            "--debug-dump",
+            dumps_arg,
+        ],
+    );
+    assert!(!failed.status.success());
+    let stderr = String::from_utf8_lossy(&failed.stderr);
+    assert!(stderr.contains("database unavailable"), "{stderr}");
+    assert!(stderr.contains("failed request captured in"), "{stderr}");
+
+    let captures: Vec<_> = std::fs::read_dir(&dumps)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(captures.len(), 1);
+    let content = std::fs::read_to_string(&captures[0]).unwrap();
+    assert!(!content.contains(TOKEN), "{content}");
+    assert!(!content.contains("session=abc"), "{content}");
+
+    let capture: serde_json::Value = serde_json::from_str(&content).unwrap();
+    assert_eq!(capture["method"], "GET");
+    assert_eq!(capture["url"], format!("{}/api/categories", api_url));
+    assert_eq!(capture["status"], 500);
+    assert_eq!(capture["request_headers"]["authorization"], "[redacted]");
+    assert!(capture["request_id"]
+        .as_str()
+        .is_some_and(|id| !id.is_empty()));
+    assert!(capture["response_body"]
+        .as_str()
+        .unwrap()
+        .contains("database unavailable"));
+
+    let shown = run(
+        home.path(),
+        &["doctor", "--last-error", "--debug-dump", dumps_arg],
+    );
+    assert!(
+        shown.status.success(),
+        "{}",
+        String::from_utf8_lossy(&shown.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&shown.stdout);
+    assert!(stdout.contains("/api/categories"), "{stdout}");
+    assert!(stdout.contains("500"), "{stdout}");
+    assert!(stdout.contains("database unavailable"), "{stdout}");
+}
@@ -0,0 +1,155 @@
+//! `download` killed halfway through a transfer resumes it on the next run.
+//!
+//! A stand-in registry sends the first half of an artifact and then stalls;
+//! the CLI is killed while waiting for the rest. The second run must ask for
+//! the remaining bytes only and end up with the artifact's exact bytes.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+const CONTRACT: &str = "550e8400-e29b-41d4-a716-446655440000";
+const SIZE: usize = 512 * 1024;
+
+fn binary() -> PathBuf {
+    PathBuf::from(env!("CARGO_BIN_EXE_soroban-registry"))
+}
+
+fn artifact() -> Vec<u8> {
+    let mut bytes = b"\0asm\x01\0\0\0".to_vec();
+    bytes.extend((0..SIZE - 8).map(|i| (i * 31 % 251) as u8));
+    bytes
+}
+
+/// Request line and headers, lowercased names
+fn read_request(stream: &TcpStream) -> Vec<String> {
+    let mut reader = BufReader::new(stream);
+    let mut lines = Vec::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+            return lines;
+        }
+        lines.push(line.trim_end().to_lowercase());
+    }
+}
+
+fn header<'a>(request: &'a [String], name: &str) -> Option<&'a str> {
+    request
+        .iter()
+        .find_map(|line| line.strip_prefix(&format!("{}: ", name)))
+}
+
+/// Serves `body`: the first response stalls after half of it, later ones
+/// honor `Range`. Returns the `Range` header of every request.
+fn serve(listener: TcpListener, body: Vec<u8>) -> Arc<Mutex<Vec<Option<String>>>> {
+    let ranges = Arc::new(Mutex::new(Vec::new()));
+    let seen = ranges.clone();
+    let etag = format!("\"{}\"", hex::encode(Sha256::digest(&body)));
+    thread::spawn(move || {
+        for (n, stream) in listener.incoming().enumerate() {
+            let Ok(mut stream) = stream else { return };
+            let request = read_request(&stream);
+            let range = header(&request, "range").map(str::to_string);
+            seen.lock().unwrap().push(range.clone());
+            let (body, etag) = (body.clone(), etag.clone());
+            thread::spawn(move || {
+                let start = range
+                    .as_deref()
+                    .filter(|_| header(&request, "if-range") == Some(etag.as_str()))
+                    .and_then(|r| r.strip_prefix("bytes=")?.strip_suffix('-')?.parse().ok())
+                    .unwrap_or(0usize);
+                let status = if start > 0 {
+                    format!(
+                        "206 Partial Content\r\nContent-Range: bytes {}-{}/{}",
+                        start,
+                        body.len() - 1,
+                        body.len()
+                    )
+                } else {
+                    "200 OK".to_string()
+                };
+                let head = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/wasm\r\nContent-Length: {}\r\n\
+                     Accept-Ranges: bytes\r\nETag: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len() - start,
+                    etag
+                );
+                let _ = stream.write_all(head.as_bytes());
+                if n == 0 {
+                    let _ = stream.write_all(&body[..body.len() / 2]);
+                    let _ = stream.flush();
+                    thread::sleep(Duration::from_secs(60));
+                } else {
+                    let _ = stream.write_all(&body[start..]);
+                }
+            });
+        }
+    });
+    ranges
+}
+
+fn download(api_url: &str, home: &Path, output: &Path) -> Command {
+    let mut command = Command::new(binary());
+    command
+        .args(["--api-url", api_url, "download", CONTRACT])
+        .args(["--version", "1.0.0", "--format", "wasm", "-o"])
+        .arg(output)
+        .env("HOME", home)
+        .env("XDG_CONFIG_HOME", home.join(".config"))
+        .env_remove("SOROBAN_REGISTRY_API_TOKEN")
+        .env_remove("SOROBAN_REGISTRY_PROFILE")
+        .env("NO_PROXY", "127.0.0.1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    command
+}
+
+#[test]
+fn killed_download_resumes_to_the_same_bytes() {
+    let body = artifact();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let api_url = format!("http://{}", listener.local_addr().unwrap());
+    let ranges = serve(listener, body.clone());
+
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("pool-1.0.0.wasm");
+    let part = dir.path().join("pool-1.0.0.wasm.part");
+
+    let mut first = download(&api_url, dir.path(), &output).spawn().unwrap();
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while std::fs::metadata(&part).map_or(0, |m| m.len()) < (SIZE / 2) as u64 {
+        assert!(Instant::now() < deadline, "first half never arrived");
+        thread::sleep(Duration::from_millis(20));
+    }
+    first.kill().unwrap();
+    first.wait().unwrap();
+    assert!(!output.exists());
+
+    let second = download(&api_url, dir.path(), &output).output().unwrap();
+    assert!(
+        second.status.success(),
+        "{}",
+        String::from_utf8_lossy(&second.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&second.stdout);
+    assert!(stdout.contains("resumed at byte 262144"), "{stdout}");
+
+    let downloaded = std::fs::read(&output).unwrap();
+    assert_eq!(
+        hex::encode(Sha256::digest(&downloaded)),
+        hex::encode(Sha256::digest(&body))
+    );
+    assert!(!part.exists());
+    assert_eq!(
+        *ranges.lock().unwrap(),
+        vec![None, Some(format!("bytes={}-", SIZE / 2))]
+    );
+}
@@ -74,3 +74,28 @@ fn test_network_flag_global() {
     assert!(!stderr.contains("Invalid network"));
     assert!(!stderr.contains("unexpected argument"));
 }
+
+#[test]
+fn test_undefined_profile_lists_available_profiles() {
+    let home = tempfile::tempdir().unwrap();
+    let config_dir = home.path().join(".soroban-registry");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(
+        config_dir.join("config.toml"),
+        "[profiles.staging]\napi_url = \"http://localhost:3001\"\n\n[profiles.prod]\n",
+    )
+    .unwrap();
+
+    let output = Command::new(get_binary_path())
+        .env("HOME", home.path())
+        .env_remove("SOROBAN_REGISTRY_API_URL")
+        .arg("--profile")
+        .arg("dev")
+        .arg("list")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Profile `dev` is not defined; available profiles: prod, staging"));
+}
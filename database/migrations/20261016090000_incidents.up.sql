@@ -0,0 +1,18 @@
+-- Operational incidents tracked by the registry (outages, data loss, security events)
+
+CREATE TABLE IF NOT EXISTS incidents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id UUID REFERENCES contracts(id) ON DELETE SET NULL,  -- NULL means registry-wide
+    incident_type VARCHAR(50) NOT NULL,          -- 'outage', 'data_loss', 'security', 'degradation', ...
+    description TEXT NOT NULL,
+    start_time TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    end_time TIMESTAMPTZ,                        -- NULL while the incident is still open
+    rto_achieved TEXT,
+    rpo_achieved TEXT,
+    lessons_learned TEXT,
+    notified_users BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incidents_start_time ON incidents(start_time DESC);
@@ -0,0 +1,21 @@
+-- Soft-delete for incidents so postmortems and metrics stay reconstructable
+
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_incidents_not_deleted
+    ON incidents(start_time DESC)
+    WHERE deleted_at IS NULL;
+
+-- Append-only record of administrative actions on incidents
+CREATE TABLE IF NOT EXISTS incident_audit_log (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL,                  -- Intentional: no FK, log outlives the row
+    action      VARCHAR(50) NOT NULL,           -- 'deleted', ...
+    changed_by  VARCHAR(255) NOT NULL,          -- JWT subject of the actor
+    details     JSONB,
+    timestamp   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_audit_log_incident_id
+    ON incident_audit_log(incident_id, timestamp DESC);
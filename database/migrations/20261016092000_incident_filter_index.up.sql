@@ -0,0 +1,3 @@
+-- Backs the common "incidents for contract X in a time range" listing filter
+CREATE INDEX IF NOT EXISTS idx_incidents_contract_start_time
+    ON incidents(contract_id, start_time DESC);
@@ -0,0 +1,15 @@
+ALTER TABLE incidents
+    DROP CONSTRAINT IF EXISTS incidents_rto_achieved_non_negative,
+    DROP CONSTRAINT IF EXISTS incidents_rpo_achieved_non_negative,
+    ADD COLUMN IF NOT EXISTS rto_achieved TEXT,
+    ADD COLUMN IF NOT EXISTS rpo_achieved TEXT;
+
+-- Plain seconds, which the up migration reads back unchanged
+UPDATE incidents
+SET rto_achieved = rto_achieved_seconds::TEXT,
+    rpo_achieved = rpo_achieved_seconds::TEXT
+WHERE rto_achieved_seconds IS NOT NULL OR rpo_achieved_seconds IS NOT NULL;
+
+ALTER TABLE incidents
+    DROP COLUMN IF EXISTS rto_achieved_seconds,
+    DROP COLUMN IF EXISTS rpo_achieved_seconds;
@@ -0,0 +1,45 @@
+-- Store achieved RTO/RPO as integer seconds instead of free-form strings
+
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS rto_achieved_seconds BIGINT,
+    ADD COLUMN IF NOT EXISTS rpo_achieved_seconds BIGINT;
+
+-- Best-effort conversion of legacy values such as '7200', '2h', '120m' or 'PT2H'.
+-- Anything Postgres cannot read as an interval is left NULL.
+CREATE OR REPLACE FUNCTION pg_temp.parse_recovery_duration(raw TEXT)
+RETURNS BIGINT AS $$
+DECLARE
+    trimmed TEXT := btrim(raw);
+    seconds BIGINT;
+BEGIN
+    IF trimmed IS NULL OR trimmed = '' THEN
+        RETURN NULL;
+    END IF;
+
+    IF trimmed ~ '^[0-9]+$' THEN
+        seconds := trimmed::BIGINT;
+    ELSE
+        seconds := EXTRACT(EPOCH FROM trimmed::INTERVAL)::BIGINT;
+    END IF;
+
+    IF seconds < 0 THEN
+        RETURN NULL;
+    END IF;
+    RETURN seconds;
+EXCEPTION WHEN OTHERS THEN
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+UPDATE incidents
+SET rto_achieved_seconds = pg_temp.parse_recovery_duration(rto_achieved),
+    rpo_achieved_seconds = pg_temp.parse_recovery_duration(rpo_achieved)
+WHERE rto_achieved IS NOT NULL OR rpo_achieved IS NOT NULL;
+
+ALTER TABLE incidents
+    DROP COLUMN IF EXISTS rto_achieved,
+    DROP COLUMN IF EXISTS rpo_achieved;
+
+ALTER TABLE incidents
+    ADD CONSTRAINT incidents_rto_achieved_non_negative CHECK (rto_achieved_seconds >= 0),
+    ADD CONSTRAINT incidents_rpo_achieved_non_negative CHECK (rpo_achieved_seconds >= 0);
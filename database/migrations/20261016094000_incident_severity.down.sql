@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_incidents_severity;
+ALTER TABLE incidents DROP COLUMN IF EXISTS severity;
+DROP TYPE IF EXISTS incident_severity;
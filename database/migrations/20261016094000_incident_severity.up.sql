@@ -0,0 +1,13 @@
+-- Incident severity; declaration order doubles as the sort order (most severe first)
+DO $$ BEGIN
+    CREATE TYPE incident_severity AS ENUM ('critical', 'major', 'minor', 'informational');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS severity incident_severity NOT NULL DEFAULT 'major';
+
+CREATE INDEX IF NOT EXISTS idx_incidents_severity
+    ON incidents(severity, start_time DESC)
+    WHERE deleted_at IS NULL;
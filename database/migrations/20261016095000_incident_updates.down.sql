@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS incident_updates;
+DROP FUNCTION IF EXISTS reject_incident_update_modification();
+DROP TYPE IF EXISTS incident_update_status;
@@ -0,0 +1,32 @@
+-- Running narrative of an incident; rows are append-only (corrections are new updates)
+
+DO $$ BEGIN
+    CREATE TYPE incident_update_status AS ENUM ('investigating', 'identified', 'monitoring', 'resolved');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+CREATE TABLE IF NOT EXISTS incident_updates (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id     UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    author          VARCHAR(255) NOT NULL,
+    message         TEXT NOT NULL,
+    status          incident_update_status,
+    post_resolution BOOLEAN NOT NULL DEFAULT FALSE,  -- Posted after the incident's end_time was set
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_updates_incident_created
+    ON incident_updates(incident_id, created_at);
+
+CREATE OR REPLACE FUNCTION reject_incident_update_modification()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'incident updates are immutable; post a correction instead';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_incident_updates_immutable ON incident_updates;
+CREATE TRIGGER trg_incident_updates_immutable
+    BEFORE UPDATE ON incident_updates
+    FOR EACH ROW EXECUTE FUNCTION reject_incident_update_modification();
@@ -0,0 +1,30 @@
+-- Delivery of incident notifications to operator-configured channels
+
+CREATE TABLE IF NOT EXISTS notification_channels (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name        VARCHAR(255) NOT NULL UNIQUE,
+    kind        VARCHAR(20) NOT NULL CHECK (kind IN ('webhook', 'smtp')),
+    target      TEXT NOT NULL,                  -- Webhook URL or recipient email address
+    enabled     BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One row per (incident, channel); the dispatcher retries until delivered or out of attempts
+CREATE TABLE IF NOT EXISTS incident_notification_deliveries (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id     UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    channel_id      UUID NOT NULL REFERENCES notification_channels(id) ON DELETE CASCADE,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending'
+                        CHECK (status IN ('pending', 'delivered', 'failed')),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at    TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (incident_id, channel_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_notification_deliveries_due
+    ON incident_notification_deliveries(next_attempt_at)
+    WHERE status = 'pending';
+
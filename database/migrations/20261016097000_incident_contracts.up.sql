@@ -0,0 +1,18 @@
+-- Incidents can affect many contracts; incidents.contract_id is kept as the
+-- primary affected contract for older clients.
+
+CREATE TABLE IF NOT EXISTS incident_contracts (
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (incident_id, contract_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_contracts_contract_id
+    ON incident_contracts(contract_id);
+
+INSERT INTO incident_contracts (incident_id, contract_id)
+SELECT i.id, i.contract_id
+FROM incidents i
+JOIN contracts c ON c.id = i.contract_id
+ON CONFLICT DO NOTHING;
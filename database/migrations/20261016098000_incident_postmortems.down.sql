@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS incident_action_items;
+
+ALTER TABLE incidents
+    DROP COLUMN IF EXISTS root_cause,
+    DROP COLUMN IF EXISTS detection_method,
+    DROP COLUMN IF EXISTS contributing_factors;
@@ -0,0 +1,25 @@
+-- Structured postmortem fields and trackable follow-up actions for incidents
+
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS root_cause TEXT,
+    ADD COLUMN IF NOT EXISTS detection_method TEXT,
+    ADD COLUMN IF NOT EXISTS contributing_factors TEXT[] NOT NULL DEFAULT '{}';
+
+CREATE TABLE IF NOT EXISTS incident_action_items (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    description TEXT NOT NULL,
+    owner       VARCHAR(255),
+    due_date    DATE,
+    done        BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_action_items_incident
+    ON incident_action_items(incident_id, created_at);
+
+-- Outstanding follow-ups are listed across all incidents, ordered by due date
+CREATE INDEX IF NOT EXISTS idx_incident_action_items_open
+    ON incident_action_items(due_date NULLS LAST)
+    WHERE NOT done;
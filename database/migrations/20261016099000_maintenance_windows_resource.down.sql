@@ -0,0 +1,53 @@
+ALTER TABLE incidents DROP COLUMN IF EXISTS maintenance_window_id;
+
+DROP INDEX IF EXISTS idx_maintenance_windows_schedule;
+ALTER TABLE maintenance_windows DROP CONSTRAINT IF EXISTS maintenance_windows_valid_range;
+
+ALTER TABLE maintenance_windows
+    ADD COLUMN IF NOT EXISTS contract_id UUID REFERENCES contracts(id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS message TEXT,
+    ADD COLUMN IF NOT EXISTS started_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS scheduled_end_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS ended_at TIMESTAMPTZ;
+
+-- The old shape has exactly one contract and a publisher as creator per
+-- window; windows that can't be expressed that way are dropped
+DELETE FROM maintenance_windows w
+WHERE NOT EXISTS (SELECT 1 FROM maintenance_window_contracts c WHERE c.window_id = w.id)
+   OR NOT EXISTS (SELECT 1 FROM publishers p WHERE p.id::TEXT = w.created_by);
+
+UPDATE maintenance_windows w
+SET contract_id = (
+        SELECT c.contract_id FROM maintenance_window_contracts c
+        WHERE c.window_id = w.id
+        ORDER BY c.contract_id
+        LIMIT 1
+    ),
+    message = COALESCE(description, title),
+    started_at = scheduled_start,
+    scheduled_end_at = scheduled_end,
+    ended_at = CASE WHEN status IN ('completed', 'cancelled') THEN updated_at END;
+
+DROP TABLE IF EXISTS maintenance_window_contracts;
+
+ALTER TABLE maintenance_windows
+    DROP COLUMN IF EXISTS title,
+    DROP COLUMN IF EXISTS description,
+    DROP COLUMN IF EXISTS scheduled_start,
+    DROP COLUMN IF EXISTS scheduled_end,
+    DROP COLUMN IF EXISTS status,
+    DROP COLUMN IF EXISTS components,
+    DROP COLUMN IF EXISTS updated_at,
+    ALTER COLUMN created_by TYPE UUID USING created_by::UUID,
+    ALTER COLUMN contract_id SET NOT NULL,
+    ALTER COLUMN message SET NOT NULL,
+    ALTER COLUMN started_at SET NOT NULL,
+    ALTER COLUMN started_at SET DEFAULT NOW(),
+    ADD CONSTRAINT maintenance_windows_created_by_fkey
+        FOREIGN KEY (created_by) REFERENCES publishers(id);
+
+DROP TYPE IF EXISTS maintenance_window_status;
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_windows_contract_id ON maintenance_windows(contract_id);
+CREATE INDEX IF NOT EXISTS idx_maintenance_windows_ended_at ON maintenance_windows(ended_at);
+CREATE INDEX IF NOT EXISTS idx_maintenance_windows_scheduled_end_at ON maintenance_windows(scheduled_end_at);
@@ -0,0 +1,84 @@
+-- Promote maintenance windows to a first-class, registry-wide resource.
+-- The original table (017_maintenance_mode) tracked one contract per window
+-- with free-form timing; rows are carried over into the new shape.
+
+DO $$ BEGIN
+    CREATE TYPE maintenance_window_status AS ENUM ('scheduled', 'in_progress', 'completed', 'cancelled');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+ALTER TABLE maintenance_windows
+    ADD COLUMN IF NOT EXISTS title VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS description TEXT,
+    ADD COLUMN IF NOT EXISTS scheduled_start TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS scheduled_end TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS status maintenance_window_status NOT NULL DEFAULT 'scheduled',
+    ADD COLUMN IF NOT EXISTS components TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE TABLE IF NOT EXISTS maintenance_window_contracts (
+    window_id   UUID NOT NULL REFERENCES maintenance_windows(id) ON DELETE CASCADE,
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    PRIMARY KEY (window_id, contract_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_window_contracts_contract
+    ON maintenance_window_contracts(contract_id);
+
+DO $$ BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'maintenance_windows' AND column_name = 'message'
+    ) THEN
+        UPDATE maintenance_windows
+        SET title = LEFT(message, 255),
+            description = message,
+            scheduled_start = started_at,
+            scheduled_end = GREATEST(
+                COALESCE(ended_at, scheduled_end_at, started_at + INTERVAL '1 hour'),
+                started_at + INTERVAL '1 minute'
+            ),
+            status = CASE
+                WHEN ended_at IS NOT NULL THEN 'completed'::maintenance_window_status
+                WHEN started_at <= NOW() THEN 'in_progress'::maintenance_window_status
+                ELSE 'scheduled'::maintenance_window_status
+            END;
+
+        INSERT INTO maintenance_window_contracts (window_id, contract_id)
+        SELECT id, contract_id FROM maintenance_windows
+        ON CONFLICT DO NOTHING;
+    END IF;
+END $$;
+
+ALTER TABLE maintenance_windows
+    DROP CONSTRAINT IF EXISTS maintenance_windows_created_by_fkey,
+    DROP COLUMN IF EXISTS contract_id,
+    DROP COLUMN IF EXISTS message,
+    DROP COLUMN IF EXISTS started_at,
+    DROP COLUMN IF EXISTS scheduled_end_at,
+    DROP COLUMN IF EXISTS ended_at;
+
+-- Windows are now created by admins identified by their JWT subject
+ALTER TABLE maintenance_windows
+    ALTER COLUMN created_by TYPE VARCHAR(255) USING created_by::TEXT,
+    ALTER COLUMN title SET NOT NULL,
+    ALTER COLUMN scheduled_start SET NOT NULL,
+    ALTER COLUMN scheduled_end SET NOT NULL;
+
+ALTER TABLE maintenance_windows
+    ADD CONSTRAINT maintenance_windows_valid_range CHECK (scheduled_end > scheduled_start);
+
+CREATE INDEX IF NOT EXISTS idx_maintenance_windows_schedule
+    ON maintenance_windows(scheduled_start, scheduled_end)
+    WHERE status IN ('scheduled', 'in_progress');
+
+-- Incidents that start inside a maintenance window are tagged so metrics can
+-- leave planned downtime out
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS maintenance_window_id UUID
+        REFERENCES maintenance_windows(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_incidents_maintenance_window
+    ON incidents(maintenance_window_id)
+    WHERE maintenance_window_id IS NOT NULL;
@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_incidents_open_watchdog_component;
+
+ALTER TABLE incidents
+    DROP COLUMN IF EXISTS watchdog_component;
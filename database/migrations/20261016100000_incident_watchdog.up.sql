@@ -0,0 +1,9 @@
+-- Incidents opened automatically by the health watchdog record the failing
+-- component so a flapping dependency maps onto a single open incident.
+
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS watchdog_component VARCHAR(50);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_incidents_open_watchdog_component
+    ON incidents(watchdog_component)
+    WHERE watchdog_component IS NOT NULL AND end_time IS NULL AND deleted_at IS NULL;
@@ -0,0 +1,9 @@
+ALTER TABLE incidents
+    DROP COLUMN IF EXISTS rto_target_seconds,
+    DROP COLUMN IF EXISTS rto_met,
+    DROP COLUMN IF EXISTS rto_delta_seconds,
+    DROP COLUMN IF EXISTS rpo_target_seconds,
+    DROP COLUMN IF EXISTS rpo_met,
+    DROP COLUMN IF EXISTS rpo_delta_seconds;
+
+DROP TABLE IF EXISTS recovery_objectives;
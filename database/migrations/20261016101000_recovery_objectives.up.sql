@@ -0,0 +1,29 @@
+-- Recovery time/point objectives: one global row (contract_id NULL) plus
+-- optional per-contract overrides. Incidents snapshot the targets in force
+-- when they are resolved, along with whether the achieved values met them.
+
+CREATE TABLE IF NOT EXISTS recovery_objectives (
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id        UUID REFERENCES contracts(id) ON DELETE CASCADE,
+    rto_target_seconds BIGINT NOT NULL CHECK (rto_target_seconds > 0),
+    rpo_target_seconds BIGINT NOT NULL CHECK (rpo_target_seconds >= 0),
+    created_by         VARCHAR(255) NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at         TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_recovery_objectives_global
+    ON recovery_objectives ((contract_id IS NULL))
+    WHERE contract_id IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_recovery_objectives_contract
+    ON recovery_objectives(contract_id)
+    WHERE contract_id IS NOT NULL;
+
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS rto_target_seconds BIGINT,
+    ADD COLUMN IF NOT EXISTS rto_met BOOLEAN,
+    ADD COLUMN IF NOT EXISTS rto_delta_seconds BIGINT,
+    ADD COLUMN IF NOT EXISTS rpo_target_seconds BIGINT,
+    ADD COLUMN IF NOT EXISTS rpo_met BOOLEAN,
+    ADD COLUMN IF NOT EXISTS rpo_delta_seconds BIGINT;
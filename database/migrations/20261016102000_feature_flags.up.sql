@@ -0,0 +1,9 @@
+-- Runtime overrides for feature flags. A flag without a row uses its
+-- compiled-in default or FEATURE_<NAME> from the environment.
+
+CREATE TABLE IF NOT EXISTS feature_flags (
+    name       VARCHAR(100) PRIMARY KEY,
+    enabled    BOOLEAN NOT NULL,
+    updated_by VARCHAR(255),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,7 @@
+DROP TRIGGER IF EXISTS contracts_assign_slug ON contracts;
+DROP FUNCTION IF EXISTS contracts_assign_slug();
+DROP TABLE IF EXISTS contract_slug_redirects;
+ALTER TABLE contracts DROP CONSTRAINT IF EXISTS contracts_namespace_slug_network_key;
+ALTER TABLE contracts DROP COLUMN IF EXISTS slug, DROP COLUMN IF EXISTS namespace;
+DROP FUNCTION IF EXISTS contract_namespace(UUID, UUID);
+DROP FUNCTION IF EXISTS contract_slugify(TEXT);
@@ -0,0 +1,103 @@
+-- Human-friendly contract names: `namespace/slug`, unique per network.
+-- The namespace is the owning organization's slug, else the publisher's
+-- username, else their lowercased Stellar address.
+
+ALTER TABLE contracts
+    ADD COLUMN IF NOT EXISTS namespace VARCHAR(64),
+    ADD COLUMN IF NOT EXISTS slug VARCHAR(64);
+
+-- Same rules as shared::slug::slugify; NULL when nothing is left
+CREATE OR REPLACE FUNCTION contract_slugify(input TEXT) RETURNS TEXT AS $$
+    SELECT NULLIF(
+        trim(BOTH '-' FROM left(
+            trim(BOTH '-' FROM regexp_replace(lower(COALESCE(input, '')), '[^a-z0-9]+', '-', 'g')),
+            64
+        )),
+        ''
+    )
+$$ LANGUAGE SQL IMMUTABLE;
+
+CREATE OR REPLACE FUNCTION contract_namespace(p_publisher_id UUID, p_organization_id UUID)
+RETURNS TEXT AS $$
+    SELECT COALESCE(
+        (SELECT contract_slugify(slug) FROM organizations WHERE id = p_organization_id),
+        (SELECT COALESCE(contract_slugify(username), lower(stellar_address))
+           FROM publishers WHERE id = p_publisher_id)
+    )
+$$ LANGUAGE SQL STABLE;
+
+-- Existing contracts get a slug from their name; duplicates within a
+-- namespace get part of their id appended
+WITH named AS (
+    SELECT id,
+           network,
+           contract_namespace(publisher_id, organization_id) AS namespace,
+           COALESCE(contract_slugify(name), 'contract') AS base,
+           ROW_NUMBER() OVER (
+               PARTITION BY contract_namespace(publisher_id, organization_id),
+                            COALESCE(contract_slugify(name), 'contract'),
+                            network
+               ORDER BY created_at, id
+           ) AS n
+      FROM contracts
+     WHERE slug IS NULL
+)
+UPDATE contracts c
+   SET namespace = named.namespace,
+       slug = CASE
+                  WHEN named.n = 1 THEN named.base
+                  ELSE rtrim(left(named.base, 55), '-') || '-' || left(named.id::text, 8)
+              END
+  FROM named
+ WHERE c.id = named.id;
+
+ALTER TABLE contracts
+    ADD CONSTRAINT contracts_namespace_slug_network_key UNIQUE (namespace, slug, network);
+
+-- Old names of renamed contracts, so they keep resolving
+CREATE TABLE IF NOT EXISTS contract_slug_redirects (
+    namespace   VARCHAR(64) NOT NULL,
+    slug        VARCHAR(64) NOT NULL,
+    network     network_type NOT NULL,
+    contract_id UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (namespace, slug, network)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_slug_redirects_contract
+    ON contract_slug_redirects(contract_id);
+
+-- Contracts inserted without a name (by the indexer or seeder) get one,
+-- numbered when the plain slug is taken. The API always sets the slug and
+-- reports collisions instead.
+CREATE OR REPLACE FUNCTION contracts_assign_slug() RETURNS TRIGGER AS $$
+DECLARE
+    base TEXT;
+    candidate TEXT;
+    n INT := 1;
+BEGIN
+    IF NEW.namespace IS NULL THEN
+        NEW.namespace := contract_namespace(NEW.publisher_id, NEW.organization_id);
+    END IF;
+    IF NEW.slug IS NULL THEN
+        base := COALESCE(contract_slugify(NEW.name), 'contract');
+        candidate := base;
+        WHILE EXISTS (
+            SELECT 1 FROM contracts
+             WHERE namespace = NEW.namespace AND slug = candidate AND network = NEW.network
+        ) OR EXISTS (
+            SELECT 1 FROM contract_slug_redirects
+             WHERE namespace = NEW.namespace AND slug = candidate AND network = NEW.network
+        ) LOOP
+            n := n + 1;
+            candidate := rtrim(left(base, 60), '-') || '-' || n;
+        END LOOP;
+        NEW.slug := candidate;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER contracts_assign_slug
+    BEFORE INSERT ON contracts
+    FOR EACH ROW EXECUTE FUNCTION contracts_assign_slug();
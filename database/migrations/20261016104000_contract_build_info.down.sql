@@ -0,0 +1 @@
+DROP TABLE IF EXISTS contract_build_info;
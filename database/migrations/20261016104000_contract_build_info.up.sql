@@ -0,0 +1,23 @@
+-- Build metadata of successful verifications: toolchain, flags and the hash
+-- of the Cargo.lock the contract was built with, plus a CycloneDX SBOM when
+-- a lockfile was submitted.
+
+CREATE TABLE IF NOT EXISTS contract_build_info (
+    verification_id     UUID PRIMARY KEY REFERENCES verifications(id) ON DELETE CASCADE,
+    contract_id         UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    rustc_version       TEXT,
+    soroban_sdk_version VARCHAR(50) NOT NULL,
+    cargo_features      TEXT[] NOT NULL DEFAULT '{}',
+    build_profile       VARCHAR(100) NOT NULL,
+    target              VARCHAR(100) NOT NULL,
+    build_flags         TEXT[] NOT NULL DEFAULT '{}',
+    rustflags           TEXT,
+    lockfile_sha256     VARCHAR(64),
+    source_url          TEXT,
+    commit_hash         VARCHAR(64),
+    sbom                JSONB,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_build_info_contract
+    ON contract_build_info(contract_id, created_at DESC);
@@ -0,0 +1,17 @@
+-- Registry snapshots for disaster recovery drills. The archive itself lives
+-- in the blob store; this table records where, and what it holds.
+
+CREATE TABLE IF NOT EXISTS registry_snapshots (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    format_version  INTEGER NOT NULL,
+    storage_backend VARCHAR(20) NOT NULL,
+    storage_key     TEXT NOT NULL,
+    size_bytes      BIGINT NOT NULL CHECK (size_bytes >= 0),
+    sha256          VARCHAR(64) NOT NULL,
+    row_counts      JSONB NOT NULL DEFAULT '{}',
+    created_by      VARCHAR(255) NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_registry_snapshots_created_at
+    ON registry_snapshots(created_at DESC);
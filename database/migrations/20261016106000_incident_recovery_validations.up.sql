@@ -0,0 +1,18 @@
+-- Evidence that a restore performed during an incident was checked: what was
+-- restored, from which snapshot, the row-count/checksum comparisons made and
+-- whether they passed.
+
+CREATE TABLE IF NOT EXISTS incident_recovery_validations (
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_id  UUID NOT NULL REFERENCES incidents(id) ON DELETE CASCADE,
+    restored     TEXT NOT NULL,
+    snapshot_id  UUID REFERENCES registry_snapshots(id) ON DELETE SET NULL,
+    checks       JSONB NOT NULL DEFAULT '[]',
+    passed       BOOLEAN NOT NULL,
+    notes        TEXT,
+    performed_by VARCHAR(255) NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_incident_recovery_validations_incident
+    ON incident_recovery_validations(incident_id, created_at);
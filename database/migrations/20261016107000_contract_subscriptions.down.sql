@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS contract_subscription_deliveries;
+DROP TABLE IF EXISTS contract_subscriptions;
@@ -0,0 +1,45 @@
+-- Per-user subscriptions to a contract's events, delivered to a webhook or
+-- an email address chosen by the subscriber.
+
+CREATE TABLE IF NOT EXISTS contract_subscriptions (
+    id                   UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    subscriber           VARCHAR(255) NOT NULL,   -- `sub` of the subscriber's API token
+    contract_id          UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    event_types          TEXT[] NOT NULL
+                             CHECK (cardinality(event_types) > 0
+                                    AND event_types <@ ARRAY['version', 'verification', 'incident']),
+    channel              VARCHAR(20) NOT NULL CHECK (channel IN ('webhook', 'email')),
+    target               TEXT NOT NULL,           -- Webhook URL or recipient email address
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    last_error           TEXT,
+    last_delivered_at    TIMESTAMPTZ,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (subscriber, contract_id, channel, target)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_subscriptions_contract
+    ON contract_subscriptions(contract_id);
+
+-- One row per (subscription, event); `event_key` identifies the event so the
+-- same one is never queued twice for a subscription
+CREATE TABLE IF NOT EXISTS contract_subscription_deliveries (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    subscription_id UUID NOT NULL REFERENCES contract_subscriptions(id) ON DELETE CASCADE,
+    event_type      TEXT NOT NULL,
+    event_key       TEXT NOT NULL,
+    subject         TEXT NOT NULL,
+    body            TEXT NOT NULL,
+    payload         JSONB NOT NULL,
+    status          VARCHAR(20) NOT NULL DEFAULT 'pending'
+                        CHECK (status IN ('pending', 'delivered', 'failed')),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at    TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (subscription_id, event_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_subscription_deliveries_due
+    ON contract_subscription_deliveries(next_attempt_at)
+    WHERE status = 'pending';
@@ -0,0 +1,3 @@
+ALTER TABLE contract_subscriptions DROP COLUMN IF EXISTS channel_id;
+DROP TABLE IF EXISTS user_notification_channel_attempts;
+DROP TABLE IF EXISTS user_notification_channels;
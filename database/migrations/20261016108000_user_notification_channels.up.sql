@@ -0,0 +1,62 @@
+-- Notification channels registered by users themselves. A channel only
+-- receives deliveries once its owner proved control of it: a webhook echoes
+-- a challenge, an email address follows a signed confirmation link.
+
+CREATE TABLE IF NOT EXISTS user_notification_channels (
+    id                   UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner                VARCHAR(255) NOT NULL,   -- `sub` of the owner's API token
+    name                 VARCHAR(100),
+    kind                 VARCHAR(20) NOT NULL CHECK (kind IN ('webhook', 'email')),
+    target               TEXT NOT NULL,           -- Webhook URL or recipient email address
+    status               VARCHAR(20) NOT NULL DEFAULT 'pending'
+                             CHECK (status IN ('pending', 'verified', 'failing', 'disabled')),
+    -- Webhook challenge, or the nonce inside the emailed confirmation link;
+    -- replaced whenever verification is started again
+    verification_token   TEXT,
+    verified_at          TIMESTAMPTZ,
+    consecutive_failures INTEGER NOT NULL DEFAULT 0,
+    last_error           TEXT,
+    last_delivered_at    TIMESTAMPTZ,
+    disabled_at          TIMESTAMPTZ,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (owner, kind, target)
+);
+
+-- Recent verification and delivery attempts per channel, for debugging
+CREATE TABLE IF NOT EXISTS user_notification_channel_attempts (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel_id      UUID NOT NULL REFERENCES user_notification_channels(id) ON DELETE CASCADE,
+    purpose         VARCHAR(20) NOT NULL CHECK (purpose IN ('verification', 'delivery')),
+    delivery_id     UUID REFERENCES contract_subscription_deliveries(id) ON DELETE SET NULL,
+    subject         TEXT,
+    succeeded       BOOLEAN NOT NULL,
+    status_code     INTEGER,                 -- HTTP status a webhook answered with
+    error           TEXT,
+    attempted_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_notification_channel_attempts_recent
+    ON user_notification_channel_attempts(channel_id, attempted_at DESC);
+
+-- Subscriptions deliver through a channel. Targets subscribed to before
+-- channels existed keep working as verified channels.
+ALTER TABLE contract_subscriptions
+    ADD COLUMN IF NOT EXISTS channel_id UUID
+        REFERENCES user_notification_channels(id) ON DELETE RESTRICT;
+
+INSERT INTO user_notification_channels (owner, kind, target, status, verified_at)
+SELECT DISTINCT subscriber, channel, target, 'verified', NOW()
+FROM contract_subscriptions
+ON CONFLICT (owner, kind, target) DO NOTHING;
+
+UPDATE contract_subscriptions s
+SET channel_id = c.id
+FROM user_notification_channels c
+WHERE s.channel_id IS NULL
+  AND c.owner = s.subscriber AND c.kind = s.channel AND c.target = s.target;
+
+ALTER TABLE contract_subscriptions ALTER COLUMN channel_id SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_contract_subscriptions_channel
+    ON contract_subscriptions(channel_id);
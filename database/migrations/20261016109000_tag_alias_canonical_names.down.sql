@@ -0,0 +1,19 @@
+DROP FUNCTION IF EXISTS normalize_contract_tags(TEXT[]);
+DROP INDEX IF EXISTS idx_tag_aliases_canonical_name;
+
+ALTER TABLE tag_aliases
+    DROP CONSTRAINT IF EXISTS tag_aliases_not_canonical,
+    ADD COLUMN IF NOT EXISTS canonical_tag_id UUID REFERENCES tags(id) ON DELETE CASCADE;
+
+-- Aliases whose canonical tag has no row in `tags` can't be kept
+UPDATE tag_aliases a
+   SET canonical_tag_id = t.id
+  FROM tags t
+ WHERE t.name = a.canonical;
+DELETE FROM tag_aliases WHERE canonical_tag_id IS NULL;
+
+ALTER TABLE tag_aliases
+    ALTER COLUMN canonical_tag_id SET NOT NULL,
+    DROP COLUMN canonical;
+
+CREATE INDEX IF NOT EXISTS idx_tag_aliases_canonical ON tag_aliases(canonical_tag_id);
@@ -0,0 +1,34 @@
+-- Contract tags are free-form strings in contracts.tags, but tag_aliases
+-- pointed at rows of the prefixed `tags` taxonomy, which contracts never
+-- reference. Aliases now name their canonical tag directly. Aliases are
+-- stored lowercased and match tags case-insensitively; the canonical tag
+-- keeps the spelling contracts are rewritten to.
+
+ALTER TABLE tag_aliases ADD COLUMN IF NOT EXISTS canonical VARCHAR(255);
+
+UPDATE tag_aliases a
+   SET canonical = t.name
+  FROM tags t
+ WHERE t.id = a.canonical_tag_id;
+
+UPDATE tag_aliases SET alias = lower(btrim(alias));
+
+ALTER TABLE tag_aliases
+    ALTER COLUMN canonical SET NOT NULL,
+    DROP COLUMN canonical_tag_id,
+    ADD CONSTRAINT tag_aliases_not_canonical CHECK (alias <> lower(canonical));
+
+CREATE INDEX IF NOT EXISTS idx_tag_aliases_canonical_name ON tag_aliases(lower(canonical));
+
+-- Rewrites aliased tags to their canonical form, keeping the order of first
+-- appearance and dropping duplicates the rewrite creates. Used on publish,
+-- on metadata updates and to backfill contracts when an alias is added.
+CREATE OR REPLACE FUNCTION normalize_contract_tags(input TEXT[]) RETURNS TEXT[] AS $$
+    SELECT COALESCE(array_agg(tag ORDER BY first_seen), '{}')
+    FROM (
+        SELECT COALESCE(a.canonical, t.tag) AS tag, MIN(t.pos) AS first_seen
+        FROM unnest(input) WITH ORDINALITY AS t(tag, pos)
+        LEFT JOIN tag_aliases a ON a.alias = lower(btrim(t.tag))
+        GROUP BY 1
+    ) resolved
+$$ LANGUAGE sql STABLE STRICT;
@@ -0,0 +1,8 @@
+-- Values can't be removed from audit_action_type; the audit log may
+-- already reference them, so they stay.
+
+DROP INDEX IF EXISTS idx_contracts_description_trgm;
+DROP TABLE IF EXISTS contract_publish_flags;
+DROP INDEX IF EXISTS idx_contracts_pending_review;
+ALTER TABLE contracts DROP COLUMN IF EXISTS publish_status;
+DROP TYPE IF EXISTS contract_publish_status;
@@ -0,0 +1,42 @@
+-- Publishes are screened for duplicates and spam (see the API's
+-- `publish_screening` module). Suspicious ones are held as `pending_review`,
+-- out of listings and search until an admin approves or rejects them; every
+-- finding is kept in `contract_publish_flags` for the review queue.
+
+DO $$ BEGIN
+    CREATE TYPE contract_publish_status AS ENUM ('published', 'pending_review', 'rejected');
+EXCEPTION
+    WHEN duplicate_object THEN NULL;
+END $$;
+
+ALTER TABLE contracts
+    ADD COLUMN IF NOT EXISTS publish_status contract_publish_status NOT NULL DEFAULT 'published';
+
+CREATE INDEX IF NOT EXISTS idx_contracts_pending_review
+    ON contracts(created_at)
+    WHERE publish_status = 'pending_review';
+
+CREATE TABLE IF NOT EXISTS contract_publish_flags (
+    id                  UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id         UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    kind                VARCHAR(32) NOT NULL
+                            CHECK (kind IN ('duplicate_wasm', 'similar_name',
+                                            'similar_description', 'blocked_term')),
+    -- Existing contract the publish resembles; NULL for blocked terms
+    related_contract_id UUID REFERENCES contracts(id) ON DELETE SET NULL,
+    similarity          DOUBLE PRECISION,         -- Trigram similarity, 0..1
+    detail              TEXT NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_publish_flags_contract
+    ON contract_publish_flags(contract_id);
+CREATE INDEX IF NOT EXISTS idx_contract_publish_flags_related
+    ON contract_publish_flags(related_contract_id);
+
+-- Near-duplicate descriptions are looked up by trigram similarity
+CREATE INDEX IF NOT EXISTS idx_contracts_description_trgm
+    ON contracts USING gin (description gin_trgm_ops);
+
+ALTER TYPE audit_action_type ADD VALUE IF NOT EXISTS 'publish_approved';
+ALTER TYPE audit_action_type ADD VALUE IF NOT EXISTS 'publish_rejected';
@@ -0,0 +1,36 @@
+-- Background jobs for work that outlives a request: the API answers with a
+-- job id and workers run the job, retrying transient failures with backoff
+-- (see the API's `jobs` module). `failed` jobs were rejected by their handler
+-- and are not retried; `dead` jobs ran out of attempts.
+
+CREATE TABLE IF NOT EXISTS jobs (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_type        VARCHAR(64) NOT NULL,
+    payload         JSONB NOT NULL DEFAULT '{}',
+    status          VARCHAR(20) NOT NULL DEFAULT 'queued'
+                        CHECK (status IN ('queued', 'running', 'succeeded', 'failed', 'dead')),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    max_attempts    INTEGER NOT NULL CHECK (max_attempts > 0),
+    last_error      TEXT,
+    result          JSONB,
+    created_by      VARCHAR(255),            -- `sub` of the requester's API token
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- A running job whose lease has expired is picked up again; its worker died
+    locked_until    TIMESTAMPTZ,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at      TIMESTAMPTZ,
+    finished_at     TIMESTAMPTZ,
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_jobs_due
+    ON jobs(next_attempt_at)
+    WHERE status = 'queued';
+
+CREATE INDEX IF NOT EXISTS idx_jobs_leased
+    ON jobs(locked_until)
+    WHERE status = 'running';
+
+CREATE INDEX IF NOT EXISTS idx_jobs_unsuccessful
+    ON jobs(status, finished_at DESC)
+    WHERE status IN ('failed', 'dead');
@@ -0,0 +1,10 @@
+DROP INDEX IF EXISTS idx_reviews_contract_helpful;
+ALTER TABLE reviews ALTER COLUMN helpful_count DROP NOT NULL;
+
+DROP TRIGGER IF EXISTS update_reviews_updated_at ON reviews;
+CREATE TRIGGER update_reviews_updated_at
+    BEFORE UPDATE ON reviews
+    FOR EACH ROW
+    EXECUTE FUNCTION update_reviews_updated_at_column();
+
+DROP TABLE IF EXISTS review_helpful_votes;
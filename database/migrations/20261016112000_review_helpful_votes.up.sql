@@ -0,0 +1,36 @@
+-- Helpfulness votes, one per caller (the token's subject) and review.
+-- A vote is either cast or not; review_votes, which also recorded
+-- "unhelpful" votes by publisher, is no longer written.
+CREATE TABLE review_helpful_votes (
+    review_id INT NOT NULL REFERENCES reviews(id) ON DELETE CASCADE,
+    voter TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (review_id, voter)
+);
+
+INSERT INTO review_helpful_votes (review_id, voter, created_at)
+SELECT v.review_id, p.stellar_address, COALESCE(v.created_at, NOW())
+FROM review_votes v
+JOIN publishers p ON p.id = v.user_id
+WHERE v.vote
+ON CONFLICT DO NOTHING;
+
+-- A vote is not an edit of the review
+DROP TRIGGER IF EXISTS update_reviews_updated_at ON reviews;
+CREATE TRIGGER update_reviews_updated_at
+    BEFORE UPDATE OF version, rating, review_text, status ON reviews
+    FOR EACH ROW
+    EXECUTE FUNCTION update_reviews_updated_at_column();
+
+UPDATE reviews r
+SET helpful_count = (SELECT COUNT(*) FROM review_helpful_votes h WHERE h.review_id = r.id);
+
+ALTER TABLE reviews
+    ALTER COLUMN helpful_count SET DEFAULT 0,
+    ALTER COLUMN helpful_count SET NOT NULL;
+
+CREATE INDEX idx_reviews_contract_helpful
+    ON reviews(contract_id, helpful_count DESC, created_at DESC)
+    WHERE status = 'approved';
+
+COMMENT ON COLUMN reviews.helpful_count IS 'Number of helpful votes in review_helpful_votes';
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS idx_incidents_escalation_candidates;
+
+ALTER TABLE incidents
+    DROP COLUMN IF EXISTS acknowledged_at,
+    DROP COLUMN IF EXISTS acknowledged_by,
+    DROP COLUMN IF EXISTS escalation_step,
+    DROP COLUMN IF EXISTS escalation_clock_at;
+
+DROP TABLE IF EXISTS escalation_policies;
@@ -0,0 +1,39 @@
+-- Escalation of unacknowledged incidents: one global policy (incident_type
+-- NULL) plus optional per-incident-type policies. Each step is
+-- {"delay_seconds": N, "channel_ids": [...]} and fires N seconds after the
+-- previous one (the first after the incident is opened), re-notifying the
+-- listed notification_channels.
+
+CREATE TABLE IF NOT EXISTS escalation_policies (
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    incident_type VARCHAR(50),
+    -- Incidents at least this severe escalate
+    min_severity  incident_severity NOT NULL DEFAULT 'critical',
+    steps         JSONB NOT NULL CHECK (jsonb_typeof(steps) = 'array' AND jsonb_array_length(steps) > 0),
+    created_by    VARCHAR(255) NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_escalation_policies_global
+    ON escalation_policies ((incident_type IS NULL))
+    WHERE incident_type IS NULL;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_escalation_policies_incident_type
+    ON escalation_policies(incident_type)
+    WHERE incident_type IS NOT NULL;
+
+-- escalation_step counts the steps fired so far and is only advanced by a
+-- conditional update, so a restarted or concurrent scheduler never fires a
+-- step twice. escalation_clock_at is when the wait for the next step began.
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS acknowledged_at     TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS acknowledged_by     VARCHAR(255),
+    ADD COLUMN IF NOT EXISTS escalation_step     INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS escalation_clock_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+UPDATE incidents SET escalation_clock_at = created_at;
+
+CREATE INDEX IF NOT EXISTS idx_incidents_escalation_candidates
+    ON incidents(escalation_clock_at)
+    WHERE end_time IS NULL AND deleted_at IS NULL AND acknowledged_at IS NULL;
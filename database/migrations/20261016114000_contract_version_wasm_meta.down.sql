@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_contract_versions_wasm_meta_entries;
+ALTER TABLE contract_versions DROP COLUMN IF EXISTS wasm_meta;
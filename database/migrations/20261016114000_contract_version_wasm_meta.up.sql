@@ -0,0 +1,9 @@
+-- Metadata embedded in the uploaded WASM's contractmetav0 sections: SDK and
+-- rustc versions, authors, repository and every raw key/value entry.
+ALTER TABLE contract_versions ADD COLUMN wasm_meta JSONB;
+
+-- Backs ?meta_key= on the contract listing
+CREATE INDEX idx_contract_versions_wasm_meta_entries
+    ON contract_versions USING GIN ((wasm_meta -> 'entries'));
+
+COMMENT ON COLUMN contract_versions.wasm_meta IS 'contractmetav0 metadata of the uploaded WASM; NULL when none was uploaded or it embeds none';
@@ -0,0 +1,11 @@
+DELETE FROM contract_subscriptions WHERE event_types = ARRAY['security'];
+UPDATE contract_subscriptions SET event_types = array_remove(event_types, 'security');
+ALTER TABLE contract_subscriptions DROP CONSTRAINT IF EXISTS contract_subscriptions_event_types_check;
+ALTER TABLE contract_subscriptions ADD CONSTRAINT contract_subscriptions_event_types_check
+    CHECK (cardinality(event_types) > 0
+           AND event_types <@ ARRAY['version', 'verification', 'incident']);
+
+DROP TABLE IF EXISTS security_notices;
+DROP TABLE IF EXISTS advisories;
+DROP TYPE IF EXISTS advisory_severity;
+DROP TYPE IF EXISTS advisory_component;
@@ -0,0 +1,40 @@
+-- Advisories against soroban-sdk and rustc versions, and the contracts they
+-- affect. A contract is affected when the newest of its versions with WASM
+-- metadata was built with a version in one of the advisory's ranges.
+
+CREATE TYPE advisory_component AS ENUM ('soroban-sdk', 'rustc');
+CREATE TYPE advisory_severity AS ENUM ('low', 'medium', 'high', 'critical');
+
+CREATE TABLE advisories (
+    id                UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    component         advisory_component NOT NULL,
+    -- Version requirements such as '>=20.0.0, <20.5.2'; a version in any of
+    -- them is affected
+    affected_versions TEXT[] NOT NULL CHECK (cardinality(affected_versions) > 0),
+    severity          advisory_severity NOT NULL,
+    title             VARCHAR(255) NOT NULL,
+    description       TEXT NOT NULL DEFAULT '',
+    url               TEXT,
+    resolved_at       TIMESTAMPTZ,
+    created_by        VARCHAR(255) NOT NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE security_notices (
+    advisory_id         UUID NOT NULL REFERENCES advisories(id) ON DELETE CASCADE,
+    contract_id         UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    -- The version whose WASM metadata matched, and the toolchain version it names
+    contract_version_id UUID NOT NULL REFERENCES contract_versions(id) ON DELETE CASCADE,
+    matched_version     TEXT NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (advisory_id, contract_id)
+);
+
+CREATE INDEX idx_security_notices_contract ON security_notices(contract_id);
+
+-- Subscribers can ask to hear about new notices
+ALTER TABLE contract_subscriptions DROP CONSTRAINT IF EXISTS contract_subscriptions_event_types_check;
+ALTER TABLE contract_subscriptions ADD CONSTRAINT contract_subscriptions_event_types_check
+    CHECK (cardinality(event_types) > 0
+           AND event_types <@ ARRAY['version', 'verification', 'incident', 'security']);
@@ -0,0 +1,4 @@
+-- Optimistic concurrency for contract edits: every edit through the API
+-- increments the revision, and an edit sent with If-Match only applies
+-- while the contract is still at the revision the client read.
+ALTER TABLE contracts ADD COLUMN revision BIGINT NOT NULL DEFAULT 1;
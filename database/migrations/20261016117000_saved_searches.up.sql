@@ -0,0 +1,12 @@
+-- Named contract searches: the query parameters of `GET /api/contracts`,
+-- stored by a user under a short id that can be shared with teammates.
+
+CREATE TABLE IF NOT EXISTS saved_searches (
+    id         VARCHAR(16) PRIMARY KEY,          -- Short random id used in URLs
+    owner      VARCHAR(255) NOT NULL,            -- `sub` of the owner's API token
+    name       VARCHAR(100) NOT NULL,
+    params     JSONB NOT NULL DEFAULT '{}',      -- Query parameter name -> value
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (owner, name)
+);
@@ -0,0 +1,3 @@
+ALTER TABLE contract_versions
+    DROP COLUMN IF EXISTS signature_nonce,
+    DROP COLUMN IF EXISTS signed_at;
@@ -0,0 +1,5 @@
+-- When a version was signed and the nonce signed with it; the registry only
+-- accepts each signature shortly after signed_at and only once
+ALTER TABLE contract_versions
+    ADD COLUMN IF NOT EXISTS signed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS signature_nonce TEXT;
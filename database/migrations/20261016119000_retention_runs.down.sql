@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_incidents_resolved_end_time;
+DROP INDEX IF EXISTS idx_channel_attempts_attempted_at;
+DROP INDEX IF EXISTS idx_incident_audit_log_timestamp;
+DROP TABLE IF EXISTS retention_runs;
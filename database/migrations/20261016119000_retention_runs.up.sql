@@ -0,0 +1,23 @@
+-- Runs of the nightly retention purge (see backend/api/src/retention.rs).
+-- Each row is the summary audit record of one run: the policy it applied
+-- and how many rows of each table it purged, or would have in a dry run.
+
+CREATE TABLE IF NOT EXISTS retention_runs (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    run_date    DATE NOT NULL UNIQUE,            -- One run per UTC day across all instances
+    dry_run     BOOLEAN NOT NULL,
+    policy      JSONB NOT NULL,                  -- Table -> days kept, null for forever
+    counts      JSONB NOT NULL DEFAULT '{}',     -- Table -> rows deleted or anonymized
+    error       TEXT,                            -- Why the run stopped early, if it did
+    started_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
+
+-- Purge lookups by age on tables that had no index on their timestamp
+CREATE INDEX IF NOT EXISTS idx_incident_audit_log_timestamp
+    ON incident_audit_log(timestamp);
+CREATE INDEX IF NOT EXISTS idx_channel_attempts_attempted_at
+    ON user_notification_channel_attempts(attempted_at);
+CREATE INDEX IF NOT EXISTS idx_incidents_resolved_end_time
+    ON incidents(end_time)
+    WHERE end_time IS NOT NULL;
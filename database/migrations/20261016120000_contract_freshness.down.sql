@@ -0,0 +1,16 @@
+DELETE FROM contract_subscriptions WHERE event_types = ARRAY['freshness'];
+UPDATE contract_subscriptions SET event_types = array_remove(event_types, 'freshness');
+ALTER TABLE contract_subscriptions DROP CONSTRAINT IF EXISTS contract_subscriptions_event_types_check;
+ALTER TABLE contract_subscriptions ADD CONSTRAINT contract_subscriptions_event_types_check
+    CHECK (cardinality(event_types) > 0
+           AND event_types <@ ARRAY['version', 'verification', 'incident', 'security']);
+
+DROP INDEX IF EXISTS idx_contracts_stale_at;
+DROP TRIGGER IF EXISTS trigger_contract_versions_freshness ON contract_versions;
+DROP FUNCTION IF EXISTS contracts_record_version_published();
+
+ALTER TABLE contracts
+    DROP COLUMN IF EXISTS stale_at,
+    DROP COLUMN IF EXISTS stale_warned_at,
+    DROP COLUMN IF EXISTS publisher_active_at,
+    DROP COLUMN IF EXISTS last_version_at;
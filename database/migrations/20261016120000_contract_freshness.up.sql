@@ -0,0 +1,52 @@
+-- Contract freshness (see backend/api/src/contract_freshness.rs). A contract
+-- is fresh while it gets new versions, is confirmed on chain
+-- (last_seen_on_chain_at) or sees its publisher; the sweep warns
+-- subscribers and then sets stale_at once all three have gone quiet.
+
+ALTER TABLE contracts
+    ADD COLUMN IF NOT EXISTS last_version_at     TIMESTAMPTZ,  -- Newest version's publish time
+    ADD COLUMN IF NOT EXISTS publisher_active_at TIMESTAMPTZ,  -- Last version, metadata edit or re-confirmation
+    ADD COLUMN IF NOT EXISTS stale_warned_at     TIMESTAMPTZ,  -- Subscribers told it is about to go stale
+    ADD COLUMN IF NOT EXISTS stale_at            TIMESTAMPTZ;  -- Marked stale by the sweep
+
+UPDATE contracts c
+SET last_version_at = v.latest
+FROM (
+    SELECT contract_id, MAX(created_at) AS latest
+    FROM contract_versions
+    GROUP BY contract_id
+) v
+WHERE v.contract_id = c.id;
+
+UPDATE contracts
+SET publisher_active_at = GREATEST(created_at, updated_at, last_version_at)
+WHERE publisher_active_at IS NULL;
+
+ALTER TABLE contracts ALTER COLUMN publisher_active_at SET DEFAULT NOW();
+
+-- A new version is publisher activity: it clears any stale state at once
+CREATE OR REPLACE FUNCTION contracts_record_version_published()
+RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE contracts
+    SET last_version_at = NEW.created_at,
+        publisher_active_at = NOW(),
+        stale_warned_at = NULL,
+        stale_at = NULL
+    WHERE id = NEW.contract_id;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_contract_versions_freshness
+    AFTER INSERT ON contract_versions
+    FOR EACH ROW EXECUTE FUNCTION contracts_record_version_published();
+
+CREATE INDEX IF NOT EXISTS idx_contracts_stale_at
+    ON contracts(stale_at) WHERE stale_at IS NOT NULL;
+
+-- Subscribers can ask to hear before a contract is marked stale
+ALTER TABLE contract_subscriptions DROP CONSTRAINT IF EXISTS contract_subscriptions_event_types_check;
+ALTER TABLE contract_subscriptions ADD CONSTRAINT contract_subscriptions_event_types_check
+    CHECK (cardinality(event_types) > 0
+           AND event_types <@ ARRAY['version', 'verification', 'incident', 'security', 'freshness']);
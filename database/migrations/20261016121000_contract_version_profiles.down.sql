@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_contract_version_profiles_profiled_at;
+DROP TABLE IF EXISTS contract_version_profiles;
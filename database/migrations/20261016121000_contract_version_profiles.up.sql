@@ -0,0 +1,17 @@
+-- Official performance profiles of verified contract versions (see
+-- backend/api/src/contract_profile.rs). Verification profiles the WASM it
+-- built and replaces the version's row, so each version has at most one.
+
+CREATE TABLE IF NOT EXISTS contract_version_profiles (
+    contract_id     UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    version         VARCHAR(50) NOT NULL,
+    verification_id UUID NOT NULL REFERENCES verifications(id) ON DELETE CASCADE,
+    engine          VARCHAR(20) NOT NULL,     -- budgeted
+    profile         JSONB NOT NULL,           -- soroban-profiler ProfileData
+    methods         JSONB NOT NULL,           -- [{method, cpu_instructions, memory_bytes}]
+    profiled_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (contract_id, version)
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_version_profiles_profiled_at
+    ON contract_version_profiles(contract_id, profiled_at DESC);
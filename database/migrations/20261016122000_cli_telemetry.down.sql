@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS cli_telemetry_flags;
+DROP TABLE IF EXISTS cli_telemetry_commands;
+DROP TABLE IF EXISTS cli_telemetry_installs;
+DROP TABLE IF EXISTS cli_telemetry_salt;
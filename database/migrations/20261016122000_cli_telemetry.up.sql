@@ -0,0 +1,34 @@
+-- Anonymous CLI usage counts (see backend/api/src/telemetry_handlers.rs).
+-- Reports are folded into daily aggregates; the only per-client value kept
+-- is a salted hash of the CLI's random install id, to allow one report per
+-- install per day.
+
+CREATE TABLE IF NOT EXISTS cli_telemetry_salt (
+    id   BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    salt TEXT NOT NULL DEFAULT gen_random_uuid()::text
+);
+
+INSERT INTO cli_telemetry_salt DEFAULT VALUES ON CONFLICT DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS cli_telemetry_installs (
+    day          DATE NOT NULL,
+    install_hash TEXT NOT NULL,               -- sha256(salt || install id)
+    PRIMARY KEY (day, install_hash)
+);
+
+CREATE TABLE IF NOT EXISTS cli_telemetry_commands (
+    day               DATE NOT NULL,
+    command           VARCHAR(100) NOT NULL,
+    invocations       BIGINT NOT NULL DEFAULT 0,
+    failures          BIGINT NOT NULL DEFAULT 0,
+    total_duration_ms BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, command)
+);
+
+CREATE TABLE IF NOT EXISTS cli_telemetry_flags (
+    day     DATE NOT NULL,
+    command VARCHAR(100) NOT NULL,
+    flag    VARCHAR(64) NOT NULL,
+    uses    BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (day, command, flag)
+);
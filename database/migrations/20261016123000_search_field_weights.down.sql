@@ -0,0 +1,21 @@
+DROP TABLE IF EXISTS search_field_weights;
+
+DROP TRIGGER IF EXISTS trg_contracts_search_document ON contracts;
+DROP FUNCTION IF EXISTS contracts_search_document_update();
+DROP FUNCTION IF EXISTS contracts_search_document(TEXT, TEXT[], TEXT, TEXT, TEXT, BOOLEAN);
+
+DROP INDEX IF EXISTS idx_contracts_search_document;
+ALTER TABLE contracts DROP COLUMN IF EXISTS search_document;
+ALTER TABLE contracts
+  ADD COLUMN search_document tsvector
+    GENERATED ALWAYS AS (
+      setweight(to_tsvector('english', COALESCE(name, '')), 'A') ||
+      setweight(to_tsvector('english', COALESCE(category, '')), 'B') ||
+      setweight(to_tsvector('english', COALESCE(description, '')), 'C')
+    ) STORED;
+CREATE INDEX IF NOT EXISTS idx_contracts_search_document
+  ON contracts USING GIN (search_document);
+
+ALTER TABLE contracts
+    DROP COLUMN IF EXISTS index_readme,
+    DROP COLUMN IF EXISTS readme;
@@ -0,0 +1,75 @@
+-- Per-field search weighting and an opt-out for indexing READMEs (see
+-- backend/api/src/search_weights.rs).
+--
+-- search_document was generated from name, category and description. It is
+-- now kept by a trigger so it can take tags, and the README unless the
+-- publisher turned `index_readme` off, each under its own weight label:
+--   name A, tags and category B, description C, README D
+-- How much each label counts when ranking is read from search_field_weights
+-- on every query, so it can be tuned without a redeploy.
+
+ALTER TABLE contracts
+    ADD COLUMN IF NOT EXISTS readme TEXT,
+    ADD COLUMN IF NOT EXISTS index_readme BOOLEAN NOT NULL DEFAULT TRUE;
+
+DROP INDEX IF EXISTS idx_contracts_search_document;
+ALTER TABLE contracts DROP COLUMN IF EXISTS search_document;
+ALTER TABLE contracts ADD COLUMN search_document tsvector;
+
+CREATE OR REPLACE FUNCTION contracts_search_document(
+    name TEXT,
+    tags TEXT[],
+    category TEXT,
+    description TEXT,
+    readme TEXT,
+    index_readme BOOLEAN
+) RETURNS tsvector AS $$
+    SELECT setweight(to_tsvector('english', COALESCE(name, '')), 'A') ||
+           setweight(to_tsvector('english',
+               COALESCE(array_to_string(tags, ' '), '') || ' ' || COALESCE(category, '')), 'B') ||
+           setweight(to_tsvector('english', COALESCE(description, '')), 'C') ||
+           CASE WHEN index_readme
+                THEN setweight(to_tsvector('english', COALESCE(readme, '')), 'D')
+                ELSE ''::tsvector
+           END
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION contracts_search_document_update() RETURNS trigger AS $$
+BEGIN
+    NEW.search_document := contracts_search_document(
+        NEW.name, NEW.tags, NEW.category, NEW.description, NEW.readme, NEW.index_readme
+    );
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS trg_contracts_search_document ON contracts;
+CREATE TRIGGER trg_contracts_search_document
+    BEFORE INSERT OR UPDATE OF name, tags, category, description, readme, index_readme
+    ON contracts
+    FOR EACH ROW
+    EXECUTE FUNCTION contracts_search_document_update();
+
+-- Reindexing every contract is not an edit
+ALTER TABLE contracts DISABLE TRIGGER update_contracts_updated_at;
+UPDATE contracts
+   SET search_document = contracts_search_document(
+       name, tags, category, description, readme, index_readme
+   );
+ALTER TABLE contracts ENABLE TRIGGER update_contracts_updated_at;
+
+CREATE INDEX IF NOT EXISTS idx_contracts_search_document
+    ON contracts USING GIN (search_document);
+
+-- ts_rank weights, 0 to 1, of the fields' labels; a single row
+CREATE TABLE IF NOT EXISTS search_field_weights (
+    id          BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    name        REAL NOT NULL DEFAULT 1.0 CHECK (name BETWEEN 0 AND 1),
+    tags        REAL NOT NULL DEFAULT 0.4 CHECK (tags BETWEEN 0 AND 1),
+    description REAL NOT NULL DEFAULT 0.2 CHECK (description BETWEEN 0 AND 1),
+    readme      REAL NOT NULL DEFAULT 0.1 CHECK (readme BETWEEN 0 AND 1),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_by  TEXT
+);
+
+INSERT INTO search_field_weights DEFAULT VALUES ON CONFLICT DO NOTHING;
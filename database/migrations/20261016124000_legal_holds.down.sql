@@ -0,0 +1,7 @@
+-- Values can't be removed from audit_action_type; the audit log may
+-- already reference them, so they stay.
+
+ALTER TABLE incidents DROP COLUMN IF EXISTS legal_hold;
+ALTER TABLE contracts DROP COLUMN IF EXISTS legal_hold;
+DROP INDEX IF EXISTS idx_legal_holds_active_target;
+DROP TABLE IF EXISTS legal_holds;
@@ -0,0 +1,30 @@
+-- Legal holds keep a contract or incident from being deleted, unpublished
+-- or purged while a dispute or investigation is open
+
+CREATE TABLE IF NOT EXISTS legal_holds (
+    id             UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    target_type    VARCHAR(20) NOT NULL CHECK (target_type IN ('contract', 'incident')),
+    target_id      UUID NOT NULL,              -- Intentional: no FK, the hold outlives the row
+    reason         TEXT NOT NULL,
+    set_by         VARCHAR(255) NOT NULL,      -- JWT subject of the admin
+    set_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    released_by    VARCHAR(255),
+    released_at    TIMESTAMPTZ,
+    release_reason TEXT,
+    CHECK ((released_at IS NULL) = (released_by IS NULL))
+);
+
+-- At most one active hold per record
+CREATE UNIQUE INDEX IF NOT EXISTS idx_legal_holds_active_target
+    ON legal_holds(target_type, target_id)
+    WHERE released_at IS NULL;
+
+-- Mirrors whether an active hold exists, so deletes and purges can check
+-- the row itself
+ALTER TABLE contracts
+    ADD COLUMN IF NOT EXISTS legal_hold BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS legal_hold BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TYPE audit_action_type ADD VALUE IF NOT EXISTS 'legal_hold_set';
+ALTER TYPE audit_action_type ADD VALUE IF NOT EXISTS 'legal_hold_released';
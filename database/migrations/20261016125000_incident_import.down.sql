@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_incidents_external_id;
+ALTER TABLE incidents
+    DROP COLUMN IF EXISTS external_id,
+    DROP COLUMN IF EXISTS source;
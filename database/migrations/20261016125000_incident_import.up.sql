@@ -0,0 +1,10 @@
+-- Incidents backfilled from other trackers through POST /api/incidents/import
+
+ALTER TABLE incidents
+    ADD COLUMN IF NOT EXISTS source VARCHAR(20) NOT NULL DEFAULT 'api',  -- 'api' | 'import'
+    ADD COLUMN IF NOT EXISTS external_id VARCHAR(255);                   -- ID in the tracker it came from
+
+-- Re-running an import skips the incidents it already brought in
+CREATE UNIQUE INDEX IF NOT EXISTS idx_incidents_external_id
+    ON incidents(external_id)
+    WHERE external_id IS NOT NULL;
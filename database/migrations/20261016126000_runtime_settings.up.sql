@@ -0,0 +1,10 @@
+-- Runtime overrides for numeric settings such as the slow-query threshold
+-- and request time budgets. A setting without a row uses its compiled-in
+-- default or its environment variable.
+
+CREATE TABLE IF NOT EXISTS runtime_settings (
+    name       VARCHAR(100) PRIMARY KEY,
+    value      BIGINT NOT NULL CHECK (value > 0),
+    updated_by VARCHAR(255),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1 @@
+ALTER TABLE contract_subscriptions DROP COLUMN IF EXISTS payload_version;
@@ -0,0 +1,9 @@
+-- Webhook payload versions (see backend/api/src/webhook_events.rs). Existing
+-- subscriptions keep the version they were built against; new ones get the
+-- current version unless they ask for another.
+
+ALTER TABLE contract_subscriptions
+    ADD COLUMN IF NOT EXISTS payload_version INTEGER NOT NULL DEFAULT 1
+        CHECK (payload_version > 0);
+
+ALTER TABLE contract_subscriptions ALTER COLUMN payload_version SET DEFAULT 2;
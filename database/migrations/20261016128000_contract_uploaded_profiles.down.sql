@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_contract_uploaded_profiles_method;
+DROP TABLE IF EXISTS contract_uploaded_profiles;
@@ -0,0 +1,22 @@
+-- Profiles publishers upload from `soroban-registry profile --publish-to`
+-- (see backend/api/src/profile_uploads.rs). Only the newest
+-- CONTRACT_PROFILES_PER_METHOD rows of each contract and method are kept.
+
+CREATE TABLE IF NOT EXISTS contract_uploaded_profiles (
+    id                UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id       UUID NOT NULL REFERENCES contracts(id) ON DELETE CASCADE,
+    method            VARCHAR(255) NOT NULL,
+    engine            VARCHAR(20) NOT NULL,     -- instrumented | sampled | simulated | budgeted
+    cli_version       VARCHAR(32) NOT NULL,
+    uploaded_by       VARCHAR(255) NOT NULL,    -- subject of the uploader's API token
+    total_duration_ns BIGINT NOT NULL,          -- CPU instructions for simulated and budgeted
+    cpu_instructions  BIGINT,
+    memory_bytes      BIGINT,
+    function_count    INTEGER NOT NULL,
+    size_bytes        INTEGER NOT NULL,
+    profile           JSONB NOT NULL,           -- soroban-profiler ProfileData
+    uploaded_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_contract_uploaded_profiles_method
+    ON contract_uploaded_profiles(contract_id, method, uploaded_at DESC);
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_contracts_organization_published;
+DROP INDEX IF EXISTS idx_contracts_publisher_published;
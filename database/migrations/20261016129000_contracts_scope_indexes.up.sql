@@ -0,0 +1,11 @@
+-- GET /api/contracts scoped with `publisher_address` or `organization`
+-- (see list_contracts in backend/api/src/handlers.rs). Every listing also
+-- keeps only published contracts, so these cover the scope and that filter
+-- together instead of leaving the planner the bare publisher_id and
+-- organization_id indexes from 001 and 056.
+
+CREATE INDEX IF NOT EXISTS idx_contracts_publisher_published
+    ON contracts (publisher_id, publish_status, visibility);
+
+CREATE INDEX IF NOT EXISTS idx_contracts_organization_published
+    ON contracts (organization_id, publish_status, visibility);
@@ -206,6 +206,11 @@ export interface PaginatedResponse<T> {
   page: number;
   page_size: number;
   total_pages: number;
+  // Envelope fields returned by the API; page/page_size/total_pages are derived from them
+  limit?: number;
+  offset?: number;
+  has_more?: boolean;
+  next_cursor?: string;
 }
 
 export interface DependencyTreeNode {
@@ -623,6 +628,12 @@ export const api = {
     if (typeof raw.pages === 'number' && raw.total_pages === undefined) {
       normalized.total_pages = raw.pages as number;
     }
+    if (typeof raw.limit === 'number' && typeof raw.offset === 'number') {
+      const limit = Math.max(1, raw.limit);
+      normalized.page_size = limit;
+      normalized.page = Math.floor(raw.offset / limit) + 1;
+      normalized.total_pages = Math.max(1, Math.ceil(normalized.total / limit));
+    }
     return normalized;
   },
 